@@ -13,7 +13,9 @@ use leb128;
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::BinaryIndexedView,
-    file_format::{AbilitySet, CompiledModule, LocalIndex, SignatureToken, StructHandleIndex},
+    file_format::{
+        AbilitySet, CompiledModule, LocalIndex, SignatureToken, StructHandleIndex, Visibility,
+    },
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -73,6 +75,50 @@ pub fn execute<
     gas_status: &mut SuiGasStatus,
     ctx: &mut TxContext,
 ) -> Result<(), ExecutionError> {
+    execute_function(
+        vm, state_view, module_id, function, type_args, args, gas_status, ctx, false,
+    )?;
+    Ok(())
+}
+
+/// Executes `module::function<type_args>(args)` like `execute` does, to inspect its outcome: the
+/// function may be any public function rather than an entry one, and the values it returns are
+/// returned serialized with BCS, in order. The changes to `state_view` are not meant to be
+/// committed.
+#[allow(clippy::too_many_arguments)]
+pub fn dev_inspect<
+    E: Debug,
+    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+>(
+    vm: &MoveVM,
+    state_view: &mut S,
+    module_id: ModuleId,
+    function: &Identifier,
+    type_args: Vec<TypeTag>,
+    args: Vec<CallArg>,
+    gas_status: &mut SuiGasStatus,
+    ctx: &mut TxContext,
+) -> Result<Vec<Vec<u8>>, ExecutionError> {
+    execute_function(
+        vm, state_view, module_id, function, type_args, args, gas_status, ctx, true,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_function<
+    E: Debug,
+    S: ResourceResolver<Error = E> + ModuleResolver<Error = E> + Storage + ParentSync,
+>(
+    vm: &MoveVM,
+    state_view: &mut S,
+    module_id: ModuleId,
+    function: &Identifier,
+    type_args: Vec<TypeTag>,
+    args: Vec<CallArg>,
+    gas_status: &mut SuiGasStatus,
+    ctx: &mut TxContext,
+    dev_inspect: bool,
+) -> Result<Vec<Vec<u8>>, ExecutionError> {
     let objects = args
         .iter()
         .filter_map(|arg| match arg {
@@ -100,6 +146,13 @@ pub fn execute<
         .flatten()
         .collect();
     let module = vm.load_module(&module_id, state_view)?;
+    if dev_inspect && is_private_function(&module, function) {
+        return Err(ExecutionError::new_with_source(
+            ExecutionErrorKind::NonEntryFunctionInvoked,
+            "Can only inspect `public` or `entry` functions",
+        ));
+    }
+    // Genesis calls private functions, and inspection public ones, which are not entry functions
     let is_genesis = ctx.digest() == TransactionDigest::genesis();
     let TypeCheckSuccess {
         module_id,
@@ -108,7 +161,14 @@ pub fn execute<
         by_value_objects,
         mutable_ref_objects,
         has_ctx_arg,
-    } = resolve_and_type_check(&objects, &module, function, &type_args, args, is_genesis)?;
+    } = resolve_and_type_check(
+        &objects,
+        &module,
+        function,
+        &type_args,
+        args,
+        is_genesis || dev_inspect,
+    )?;
 
     if has_ctx_arg {
         args.push(ctx.to_vec());
@@ -129,8 +189,18 @@ pub fn execute<
     )
 }
 
+/// Whether `function` of `module` is neither `public` nor `entry`.
+fn is_private_function(module: &CompiledModule, function: &Identifier) -> bool {
+    module.function_defs.iter().any(|fdef| {
+        module.identifier_at(module.function_handle_at(fdef.function).name)
+            == function.as_ident_str()
+            && !fdef.is_entry
+            && fdef.visibility != Visibility::Public
+    })
+}
+
 /// This function calls into Move VM to execute a Move function
-/// call.
+/// call, returning the values it returns serialized.
 #[allow(clippy::too_many_arguments)]
 fn execute_internal<
     E: Debug,
@@ -148,7 +218,7 @@ fn execute_internal<
     mut mutable_ref_objects: BTreeMap<LocalIndex, ObjectID>,
     gas_status: &mut SuiGasStatus, // gas status for the current call operation
     ctx: &mut TxContext,
-) -> Result<(), ExecutionError> {
+) -> Result<Vec<Vec<u8>>, ExecutionError> {
     // object_owner_map maps from object ID to its exclusive object owner.
     // This map will be used for detecting circular ownership among
     // objects, which can only happen to objects exclusively owned
@@ -238,8 +308,10 @@ fn execute_internal<
         object_owner_map,
     )?;
 
-    debug_assert!(return_values.is_empty());
-    Ok(())
+    Ok(return_values
+        .into_iter()
+        .map(|(bytes, _layout)| bytes)
+        .collect())
 }
 
 pub fn publish<
@@ -37,12 +37,13 @@ pub fn get_genesis_context() -> TxContext {
 
 /// Create and return objects wrapping the genesis modules for sui
 fn create_genesis_module_objects() -> Genesis {
-    let sui_modules = sui_framework::get_sui_framework();
-    let std_modules = sui_framework::get_move_stdlib();
-    let objects = vec![
-        Object::new_package(std_modules.clone(), TransactionDigest::genesis()),
-        Object::new_package(sui_modules.clone(), TransactionDigest::genesis()),
-    ];
-    let modules = vec![std_modules, sui_modules];
+    let modules: Vec<_> = sui_framework::get_system_packages()
+        .iter()
+        .map(|package| package.modules.clone())
+        .collect();
+    let objects = modules
+        .iter()
+        .map(|modules| Object::new_package(modules.clone(), TransactionDigest::genesis()))
+        .collect();
     Genesis { objects, modules }
 }
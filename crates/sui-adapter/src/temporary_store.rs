@@ -4,7 +4,9 @@
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::{ModuleId, StructTag};
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::sync::Mutex;
 use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
 };
@@ -17,10 +19,14 @@ use sui_types::storage::{
 };
 use sui_types::{
     event::Event,
-    gas::{GasCostSummary, SuiGasStatus},
+    gas::{GasCostSummary, NonRefundableStorageRates, SuiGasStatus},
     object::Owner,
 };
 
+#[cfg(test)]
+#[path = "unit_tests/temporary_store_tests.rs"]
+mod temporary_store_tests;
+
 pub struct InnerTemporaryStore {
     pub objects: BTreeMap<ObjectID, Object>,
     pub mutable_inputs: Vec<ObjectRef>,
@@ -46,6 +52,16 @@ pub struct TemporaryStore<S> {
     deleted: BTreeMap<ObjectID, (SequenceNumber, DeleteKind)>,
     /// Ordered sequence of events emitted by execution
     events: Vec<Event>,
+    /// The loads from `store` during execution, which can't change until the transaction is
+    /// committed, so that each package and parent entry is only read once per transaction.
+    /// Loaded modules are kept across transactions by the Move VM itself.
+    runtime_loads: Mutex<RuntimeLoads>,
+}
+
+#[derive(Default)]
+struct RuntimeLoads {
+    packages: BTreeMap<ObjectID, Option<Object>>,
+    parent_entries: BTreeMap<ObjectID, Option<ObjectRef>>,
 }
 
 impl<S> TemporaryStore<S> {
@@ -62,6 +78,7 @@ impl<S> TemporaryStore<S> {
             _written: BTreeMap::new(),
             deleted: BTreeMap::new(),
             events: Vec::new(),
+            runtime_loads: Mutex::default(),
         }
     }
 
@@ -74,6 +91,10 @@ impl<S> TemporaryStore<S> {
         &self.deleted
     }
 
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
     /// Break up the structure and return its internal stores (objects, active_inputs, written, deleted)
     pub fn into_inner(self) -> (InnerTemporaryStore, Vec<Event>) {
         #[cfg(debug_assertions)]
@@ -187,6 +208,40 @@ impl<S> TemporaryStore<S> {
         Ok(())
     }
 
+    /// Charge gas for storing the events emitted and the effects, at `rates`. The size of the
+    /// effects is the size of the object changes they list, which is most of it. To be called
+    /// once the gas object is written, as it is listed too.
+    pub fn charge_gas_for_events_and_effects(
+        &self,
+        gas_status: &mut SuiGasStatus,
+        rates: &NonRefundableStorageRates,
+    ) -> Result<(), ExecutionError> {
+        let event_size: usize = self
+            .events
+            .iter()
+            .map(|event| bcs::serialized_size(event).expect("Events are serializable"))
+            .sum();
+        gas_status.charge_events(event_size, rates)?;
+
+        let written_size: usize = self
+            ._written
+            .values()
+            .map(|(object, _)| {
+                bcs::serialized_size(&(object.compute_object_reference(), object.owner))
+                    .expect("Object references are serializable")
+            })
+            .sum();
+        let deleted_size: usize = self
+            .deleted
+            .iter()
+            .map(|(id, (version, _))| {
+                bcs::serialized_size(&(id, version, ObjectDigest::OBJECT_DIGEST_DELETED))
+                    .expect("Object references are serializable")
+            })
+            .sum();
+        gas_status.charge_effects(written_size + deleted_size, rates)
+    }
+
     pub fn to_effects(
         self,
         shared_object_refs: Vec<ObjectRef>,
@@ -370,31 +425,33 @@ impl<S: BackingPackageStore> ModuleResolver for TemporaryStore<S> {
     type Error = SuiError;
     fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
         let package_id = &ObjectID::from(*module_id.address());
-        let package_obj;
-        let package = match self.read_object(package_id) {
-            Some(object) => object,
-            None => match self.store.get_package(package_id)? {
-                Some(object) => {
-                    package_obj = object;
-                    &package_obj
-                }
-                None => {
-                    return Ok(None);
-                }
-            },
+        if let Some(package) = self.read_object(package_id) {
+            return package_module(package, module_id);
+        }
+        let mut runtime_loads = self.runtime_loads.lock().unwrap();
+        let package = match runtime_loads.packages.entry(*package_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.store.get_package(package_id)?),
         };
-        match &package.data {
-            Data::Package(c) => Ok(c
-                .serialized_module_map()
-                .get(module_id.name().as_str())
-                .cloned()),
-            _ => Err(SuiError::BadObjectType {
-                error: "Expected module object".to_string(),
-            }),
+        match package {
+            Some(package) => package_module(package, module_id),
+            None => Ok(None),
         }
     }
 }
 
+fn package_module(package: &Object, module_id: &ModuleId) -> Result<Option<Vec<u8>>, SuiError> {
+    match &package.data {
+        Data::Package(c) => Ok(c
+            .serialized_module_map()
+            .get(module_id.name().as_str())
+            .cloned()),
+        _ => Err(SuiError::BadObjectType {
+            error: "Expected module object".to_string(),
+        }),
+    }
+}
+
 impl<S> ResourceResolver for TemporaryStore<S> {
     type Error = SuiError;
 
@@ -432,6 +489,12 @@ impl<S> ResourceResolver for TemporaryStore<S> {
 
 impl<S: ParentSync> ParentSync for TemporaryStore<S> {
     fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
-        self.store.get_latest_parent_entry_ref(object_id)
+        let mut runtime_loads = self.runtime_loads.lock().unwrap();
+        match runtime_loads.parent_entries.entry(object_id) {
+            Entry::Occupied(entry) => Ok(*entry.get()),
+            Entry::Vacant(entry) => {
+                Ok(*entry.insert(self.store.get_latest_parent_entry_ref(object_id)?))
+            }
+        }
     }
 }
@@ -0,0 +1,82 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::Cell;
+
+use move_core_types::identifier::Identifier;
+use sui_types::move_package::MovePackage;
+
+use super::*;
+
+/// A store counting its reads.
+#[derive(Default)]
+struct CountingStore {
+    packages: BTreeMap<ObjectID, Object>,
+    package_reads: Cell<usize>,
+    parent_entry_reads: Cell<usize>,
+}
+
+impl BackingPackageStore for CountingStore {
+    fn get_package(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.package_reads.set(self.package_reads.get() + 1);
+        Ok(self.packages.get(package_id).cloned())
+    }
+}
+
+impl ParentSync for CountingStore {
+    fn get_latest_parent_entry_ref(&self, _object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
+        self.parent_entry_reads
+            .set(self.parent_entry_reads.get() + 1);
+        Ok(None)
+    }
+}
+
+#[test]
+fn test_runtime_loads_read_once() {
+    let package_id = ObjectID::random();
+    let modules = BTreeMap::from([("a".to_string(), vec![1]), ("b".to_string(), vec![2])]);
+    let mut store = CountingStore::default();
+    store.packages.insert(
+        package_id,
+        Object {
+            data: Data::Package(MovePackage::new(package_id, &modules)),
+            owner: Owner::Immutable,
+            previous_transaction: TransactionDigest::genesis(),
+            storage_rebate: 0,
+        },
+    );
+    let temporary_store = TemporaryStore::new(
+        &store,
+        InputObjects::new(vec![]),
+        TransactionDigest::genesis(),
+    );
+    let module_id = |name: &str| ModuleId::new(package_id.into(), Identifier::new(name).unwrap());
+
+    // Each module of the package is read from a single load of it
+    assert_eq!(
+        temporary_store.get_module(&module_id("a")).unwrap(),
+        Some(vec![1])
+    );
+    assert_eq!(
+        temporary_store.get_module(&module_id("b")).unwrap(),
+        Some(vec![2])
+    );
+    assert_eq!(temporary_store.get_module(&module_id("c")).unwrap(), None);
+    assert_eq!(store.package_reads.get(), 1);
+
+    // Missing packages are only looked up once too
+    let missing = ModuleId::new(ObjectID::random().into(), Identifier::new("a").unwrap());
+    for _ in 0..2 {
+        assert_eq!(temporary_store.get_module(&missing).unwrap(), None);
+    }
+    assert_eq!(store.package_reads.get(), 2);
+
+    let child = ObjectID::random();
+    for _ in 0..2 {
+        assert_eq!(
+            temporary_store.get_latest_parent_entry_ref(child).unwrap(),
+            None
+        );
+    }
+    assert_eq!(store.parent_entry_reads.get(), 1);
+}
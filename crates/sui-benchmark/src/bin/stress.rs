@@ -7,24 +7,51 @@ use futures::future::try_join_all;
 use futures::StreamExt;
 use prometheus::Registry;
 use rand::seq::SliceRandom;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use strum_macros::EnumString;
+use sui_benchmark::drivers::baseline::BaselineRegistry;
 use sui_benchmark::drivers::bench_driver::BenchDriver;
+use sui_benchmark::drivers::distributed::{lead, LeaderLink, ShardPlan};
 use sui_benchmark::drivers::driver::Driver;
+use sui_benchmark::drivers::fault::{FaultInjector, FaultSchedule};
+use sui_benchmark::drivers::otlp::{OtlpConfig, ResourceAttribute};
+use sui_benchmark::drivers::replay::{load_trace, ReplayDriver, TraceRecorder};
 use sui_benchmark::drivers::BenchmarkCmp;
 use sui_benchmark::drivers::BenchmarkStats;
+use sui_benchmark::drivers::HistogramConfig;
 use sui_benchmark::drivers::Interval;
+use sui_benchmark::drivers::LoadProfile;
+use sui_benchmark::drivers::MaxTpsSearch;
+use sui_benchmark::drivers::ReportFormat;
+use sui_benchmark::drivers::SummaryFormat;
+use sui_benchmark::drivers::Threshold;
+use sui_benchmark::drivers::ThresholdCheck;
+use sui_benchmark::validator_proxy::BenchTarget;
+use sui_benchmark::validator_proxy::FullNodeProxy;
+use sui_benchmark::validator_proxy::LocalValidatorAggregatorProxy;
+use sui_benchmark::validator_proxy::RecordingProxy;
+use sui_benchmark::validator_proxy::ValidatorProxy;
+use sui_benchmark::workloads::gas_pool::GasPool;
+use sui_benchmark::workloads::object_store::ObjectStoreConfig;
+use sui_benchmark::workloads::object_store::ObjectStoreWorkload;
+use sui_benchmark::workloads::publish_package::PublishPackageWorkload;
 use sui_benchmark::workloads::shared_counter::SharedCounterWorkload;
-use sui_benchmark::workloads::transfer_object::TransferObjectWorkload;
+use sui_benchmark::workloads::transfer_object::{TransferObjectWorkload, TransferTopology};
 use sui_benchmark::workloads::workload::get_latest;
+use sui_benchmark::workloads::workload::seeded_rng;
 use sui_benchmark::workloads::workload::CombinationWorkload;
+use sui_benchmark::workloads::workload::KeySchemeMix;
 use sui_benchmark::workloads::workload::Payload;
+use sui_benchmark::workloads::workload::RngStream;
 use sui_benchmark::workloads::workload::Workload;
 use sui_benchmark::workloads::workload::WorkloadInfo;
+use sui_benchmark::workloads::workload::WorkloadMix;
 use sui_benchmark::workloads::workload::WorkloadType;
+use sui_benchmark::workloads::workload::MAX_GAS_FOR_TESTING;
 use sui_config::gateway::GatewayConfig;
 use sui_config::Config;
 use sui_config::PersistedConfig;
@@ -58,6 +85,9 @@ use tokio::runtime::Builder;
 use tokio::sync::Barrier;
 use tracing::error;
 
+/// How often the coins given back to the gas pool too small to lease again are merged.
+const DUST_MERGE_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Parser)]
 #[clap(name = "Stress Testing Framework")]
 struct Opts {
@@ -67,6 +97,18 @@ struct Opts {
     /// Num of accounts to use for transfer objects
     #[clap(long, default_value = "5", global = true)]
     pub num_transfer_accounts: u64,
+    /// How the transfer accounts pass objects to each other: `star`
+    /// through a hub account, `ring`, `all-to-all`, or `hotspot:k` to
+    /// send every object to one of k hot accounts
+    #[clap(long, default_value = "star", global = true)]
+    pub topology: TransferTopology,
+    /// Bytes of payload of each object created by the mint and grow
+    /// workloads
+    #[clap(long, default_value = "1024", global = true)]
+    pub object_payload_size: u64,
+    /// Objects created by each transaction of the mint and grow workloads
+    #[clap(long, default_value = "10", global = true)]
+    pub object_fan_out: u64,
     /// Num server threads
     #[clap(long, default_value = "24", global = true)]
     pub num_server_threads: u64,
@@ -90,11 +132,24 @@ struct Opts {
     pub primary_gas_id: String,
     #[clap(long, default_value = "5000", global = true)]
     pub primary_gas_objects: u64,
+    /// Number of gas coins split from the primary gas coin at startup,
+    /// which the workloads fund their accounts from concurrently. More
+    /// coins are split whenever the pool runs out, which is reported as
+    /// the pool being exhausted
+    #[clap(long, default_value = "1000", global = true)]
+    pub gas_pool_size: usize,
     /// Whether to run local or remote benchmark
     /// NOTE: For running remote benchmark we must have the following
     /// gateway_config_path, keypair_path and primary_gas_id
     #[clap(long, parse(try_from_str), default_value = "true", global = true)]
     pub local: bool,
+    /// Where a remote benchmark submits its transactions, either the
+    /// "validators" of the gateway config or the JSON-RPC endpoint of a
+    /// fullnode or gateway with "rpc:<url>". Transactions are signed by
+    /// the benchmark, and only their end-to-end latency is known when
+    /// going through an endpoint
+    #[clap(long, default_value = "validators", global = true)]
+    pub target: BenchTarget,
     /// Default workload is 100% transfer object
     #[clap(subcommand)]
     run_spec: RunSpec,
@@ -116,23 +171,166 @@ struct Opts {
     /// one (or some) is slow.
     #[clap(long, parse(try_from_str), default_value = "true", global = true)]
     pub disjoint_mode: bool,
+    /// Seed of the random choices of the workloads: generated accounts,
+    /// selected objects and the sampling of the workload mix. Runs with
+    /// the same seed issue the same sequence of transactions, each shard
+    /// of a distributed run deriving its own seed from it
+    #[clap(long, global = true)]
+    pub seed: Option<u64>,
+    /// Signature schemes of the generated sender accounts with their
+    /// relative weights, e.g. "ed25519=80,secp256k1=20". The latency is
+    /// then also reported per scheme. All accounts are ed25519 ones by default
+    #[clap(long, global = true)]
+    pub key_schemes: Option<KeySchemeMix>,
     /// Number of transactions or duration to
     /// run the benchmark for. Default set to
     /// "unbounded" i.e. benchmark runs forever
-    /// until terminated with a ctrl-c. However,
+    /// until terminated with a ctrl-c, which still
+    /// reports the stats collected so far. However,
     /// if we wanted to run the test for
     /// 60 seconds, this could be set as "60s".
     /// And if we wanted to run the test for
     /// 10,000 transactions we could set it to
-    /// "10000"
+    /// "10000". Both can be combined as
+    /// "10000|60s" to stop at whichever comes first
     #[clap(long, global = true, default_value = "unbounded")]
     pub run_duration: Interval,
+    /// Multi-stage load profile which replaces `run_duration` and the
+    /// constant target qps when set, e.g.
+    /// "ramp:0-5000tps/60s,hold:5000tps/300s,ramp:5000-0tps/60s".
+    /// Statistics are reported per stage as well as in aggregate.
+    #[clap(long, global = true)]
+    pub load_profile: Option<LoadProfile>,
+    /// Duration (e.g. "30s") or number of transactions to run the
+    /// workload for before collecting statistics, so that connection
+    /// setup and cold caches don't skew the latency tail. The warmup
+    /// is part of the run duration.
+    #[clap(long, global = true)]
+    pub warmup: Option<Interval>,
+    /// Search for the maximum sustainable throughput instead of running
+    /// at the target qps, which becomes the upper bound of the search.
+    /// Offered loads are binary searched, each held for
+    /// `--search-step-duration`, until the highest one whose p99 latency
+    /// and error rate stay within bounds is known within
+    /// `--search-precision-tps`
+    #[clap(long, global = true)]
+    pub find_max_tps: bool,
+    /// Highest p99 latency of a sustainable load when searching
+    #[clap(long, default_value = "1000", global = true)]
+    pub max_p99_latency_ms: u64,
+    /// Highest ratio of failed transactions of a sustainable load when
+    /// searching
+    #[clap(long, default_value = "0.01", global = true)]
+    pub max_error_rate: f64,
+    /// How long each offered load is held for when searching
+    #[clap(long, default_value = "30s", parse(try_from_str = parse_search_step_duration), global = true)]
+    pub search_step_duration: Duration,
+    /// The search stops once the maximum sustainable throughput is known
+    /// within this many tps
+    #[clap(long, default_value = "50", global = true)]
+    pub search_precision_tps: u64,
+    /// Report the stats of each window of this length while the
+    /// benchmark is running, e.g. "30s". Stats are computed over the
+    /// window rather than cumulatively.
+    #[clap(long, parse(try_from_str = parse_report_interval), global = true)]
+    pub report_interval: Option<Duration>,
+    /// Format of the periodic reports, "table" (stderr) or "json"
+    /// (one object per line on stdout)
+    #[clap(long, default_value = "table", global = true)]
+    pub report_format: ReportFormat,
+    /// How long the transactions in flight are waited for once the
+    /// benchmark is interrupted with SIGINT or SIGTERM, before the stats
+    /// collected so far are reported. Interrupting again exits right away
+    #[clap(long, default_value = "30s", parse(try_from_str = parse_shutdown_timeout), global = true)]
+    pub shutdown_timeout: Duration,
+    /// Format of the final report and of its comparison with
+    /// `--compare-with`, "table" (stderr) or "markdown" (GitHub-flavored,
+    /// on stdout, e.g. to post as a PR comment)
+    #[clap(long, default_value = "table", global = true)]
+    pub format: SummaryFormat,
     /// Path where benchmark stats is stored
     #[clap(long, default_value = "/tmp/bench_result", global = true)]
     pub benchmark_stats_path: String,
     /// Path where previous benchmark stats is stored to use for comparison
     #[clap(long, default_value = "", global = true)]
     pub compare_with: String,
+    /// Regression threshold on the relative difference of a metric with
+    /// `--compare-with`, e.g. `tps>=-5%` or `p99<=+10%`. Can be repeated.
+    /// The benchmark exits with an error if any threshold is violated
+    #[clap(long, global = true)]
+    pub check: Vec<Threshold>,
+    /// Directory of the registry of named baselines used by the `baseline`
+    /// and `compare` commands. Defaults to `benchmark-baselines` in the
+    /// Sui config directory
+    #[clap(long, global = true)]
+    pub baseline_dir: Option<PathBuf>,
+    /// Faults to inject between the benchmark and the validators at
+    /// scheduled points of the run, e.g.
+    /// "kill:validator-2@60s,restart@120s,delay=200ms:validator-0+validator-1@180s".
+    /// Faults are kill, pause, delay=<duration> and restart (or resume),
+    /// validators are named as in the committee, and can be left out to
+    /// hit the ones of the previous fault
+    #[clap(long, global = true)]
+    pub fault: Option<FaultSchedule>,
+    /// Directory where the latency histograms are also written in the
+    /// HdrHistogram percentile distribution (.hgrm) format, for plotting
+    #[clap(long, global = true)]
+    pub hgrm_dir: Option<PathBuf>,
+    /// Highest latency the histograms track, in milliseconds. Slower
+    /// transactions are recorded as this value
+    #[clap(long, default_value = "100000", global = true)]
+    pub histogram_max_ms: u64,
+    /// Decimal digits to which the histograms keep latencies apart, from
+    /// 0 to 5. More digits take more memory
+    #[clap(long, default_value = "2", global = true)]
+    pub histogram_significant_digits: u8,
+    /// Also follow the checkpoints of the validators, and report the time
+    /// from submitting each transaction until it appears in a checkpoint.
+    /// Only supported when the validators are the target
+    #[clap(long, global = true)]
+    pub measure_checkpoint_latency: bool,
+    /// Lead a benchmark distributed over several stress clients instead
+    /// of generating load: listen on this address for `--num-shards`
+    /// clients started with `--leader-address`, split the target qps
+    /// and workers between them, and report their aggregated stats
+    #[clap(long, global = true)]
+    pub lead_on: Option<SocketAddr>,
+    /// Number of stress clients a leader waits for
+    #[clap(long, default_value = "1", global = true)]
+    pub num_shards: usize,
+    /// Run the shard of the load assigned by the leader at this address.
+    /// Only supported for remote benchmarks
+    #[clap(long, global = true)]
+    pub leader_address: Option<SocketAddr>,
+    /// Record the transactions submitted during the run, with the time
+    /// they were submitted at, to a trace at this path. The trace can be
+    /// replayed with the `replay` command against a network in the state
+    /// the recording started from
+    #[clap(long, global = true)]
+    pub record: Option<PathBuf>,
+    /// Write one CSV row per second of the run to this path, with the
+    /// transactions submitted, succeeded and failed in that second, and
+    /// the p50 and p99 latencies of those which succeeded. Each client
+    /// of a distributed run writes its own
+    #[clap(long, global = true)]
+    pub timeseries: Option<PathBuf>,
+    /// Record the p50, p95 and p99 latencies of each window of this
+    /// length, e.g. "10s", and write them as a `quantiles` array of
+    /// `[elapsed_s, p50, p95, p99]` points with the final stats
+    #[clap(long, parse(try_from_str = parse_quantile_interval), global = true)]
+    pub quantile_interval: Option<Duration>,
+    /// Export the transaction counts and the latency histograms of the
+    /// run to this OpenTelemetry OTLP/HTTP receiver, e.g.
+    /// http://localhost:4318, as they are sampled
+    #[clap(long, global = true)]
+    pub otlp_endpoint: Option<String>,
+    /// How often the stats are exported to the OTLP receiver
+    #[clap(long, default_value = "10s", parse(try_from_str = parse_otlp_export_interval), global = true)]
+    pub otlp_export_interval: Duration,
+    /// Attribute of the exported OTLP resource as key=value, e.g. to tell
+    /// runs apart in the backend. Can be repeated
+    #[clap(long, global = true)]
+    pub otlp_attribute: Vec<ResourceAttribute>,
 }
 
 #[derive(Debug, Clone, Parser, Eq, PartialEq, EnumString)]
@@ -158,6 +356,11 @@ pub enum RunSpec {
         // transactions in the benchmark workload
         #[clap(long, default_value = "1")]
         transfer_object: u32,
+        // weighted mix of workloads to interleave, e.g.
+        // "transfer=70,shared-counter=20,publish=10".
+        // Overrides --shared-counter and --transfer-object
+        #[clap(long)]
+        workload: Option<WorkloadMix>,
         // Target qps
         #[clap(long, default_value = "1000", global = true)]
         target_qps: u64,
@@ -171,6 +374,58 @@ pub enum RunSpec {
         #[clap(long, default_value = "10", global = true)]
         stat_collection_interval: u64,
     },
+    // Submit the transactions of a trace recorded with
+    // --record at the times they were recorded at, to run
+    // the exact same load again, e.g. against another
+    // build of the validators
+    Replay {
+        // path of the trace to replay
+        #[clap(long)]
+        trace: PathBuf,
+        // factor applied to the recorded times, e.g. 0.5
+        // to replay the trace twice as fast
+        #[clap(long, default_value = "1", parse(try_from_str = parse_time_scale))]
+        time_scale: f64,
+    },
+    // Save the stats of a run at --benchmark-stats-path
+    // under a name, list or remove the saved baselines
+    Baseline {
+        #[clap(subcommand)]
+        command: BaselineCommand,
+    },
+    // Compare the stats of the last run at
+    // --benchmark-stats-path with saved baselines,
+    // checking the --check thresholds against each
+    Compare {
+        // name of a baseline to compare with, can be repeated
+        #[clap(long, required = true)]
+        against: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Subcommand, Eq, PartialEq)]
+#[clap(rename_all = "kebab-case")]
+pub enum BaselineCommand {
+    // Save the stats of the last run as a baseline
+    Save {
+        // name of the baseline, e.g. "v1.2-shared"
+        #[clap(long)]
+        name: String,
+        // what the run was, e.g. the workload and setup
+        #[clap(long)]
+        description: Option<String>,
+        // replace the baseline if it already exists
+        #[clap(long)]
+        force: bool,
+    },
+    // List the saved baselines, oldest first
+    #[default]
+    List,
+    // Remove a saved baseline
+    Remove {
+        #[clap(long)]
+        name: String,
+    },
 }
 
 pub async fn follow(authority_client: NetworkAuthorityClient, download_txes: bool) {
@@ -225,100 +480,131 @@ pub async fn follow(authority_client: NetworkAuthorityClient, download_txes: boo
     });
 }
 
-fn make_combination_workload(
-    target_qps: u64,
-    num_workers: u64,
-    in_flight_ratio: u64,
-    primary_gas_id: ObjectID,
-    primary_gas_account_owner: SuiAddress,
-    primary_gas_account_keypair: Arc<AccountKeyPair>,
-    opts: &Opts,
-) -> WorkloadInfo {
-    let mut workloads = HashMap::<WorkloadType, (u32, Box<dyn Workload<dyn Payload>>)>::new();
-    match opts.run_spec {
+fn parse_report_interval(s: &str) -> Result<Duration, String> {
+    let interval = duration_str::parse(s).map_err(|e| format!("Invalid duration '{s}': {e}"))?;
+    if interval < Duration::from_secs(1) {
+        return Err("Report interval must be at least 1s".to_string());
+    }
+    Ok(interval)
+}
+
+fn parse_quantile_interval(s: &str) -> Result<Duration, String> {
+    let interval = duration_str::parse(s).map_err(|e| format!("Invalid duration '{s}': {e}"))?;
+    if interval < Duration::from_secs(1) {
+        return Err("Quantile interval must be at least 1s".to_string());
+    }
+    Ok(interval)
+}
+
+fn parse_otlp_export_interval(s: &str) -> Result<Duration, String> {
+    let interval = duration_str::parse(s).map_err(|e| format!("Invalid duration '{s}': {e}"))?;
+    if interval < Duration::from_secs(1) {
+        return Err("OTLP export interval must be at least 1s".to_string());
+    }
+    Ok(interval)
+}
+
+fn parse_shutdown_timeout(s: &str) -> Result<Duration, String> {
+    duration_str::parse(s).map_err(|e| format!("Invalid duration '{s}': {e}"))
+}
+
+fn parse_search_step_duration(s: &str) -> Result<Duration, String> {
+    let step = duration_str::parse(s).map_err(|e| format!("Invalid duration '{s}': {e}"))?;
+    if step < Duration::from_secs(1) {
+        return Err("Search steps must last at least 1s".to_string());
+    }
+    Ok(step)
+}
+
+fn histogram_config(opts: &Opts) -> Result<HistogramConfig> {
+    HistogramConfig::new(opts.histogram_max_ms, opts.histogram_significant_digits)
+        .map_err(|e| anyhow!(e))
+}
+
+fn parse_time_scale(s: &str) -> Result<f64, String> {
+    let scale: f64 = s
+        .parse()
+        .map_err(|e| format!("Invalid time scale '{s}': {e}"))?;
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err("Time scale must be a positive number".to_string());
+    }
+    Ok(scale)
+}
+
+/// Relative weight of each workload in the run. An explicit `--workload` mix
+/// takes precedence over the per-workload weight flags.
+fn workload_weights(opts: &Opts) -> Vec<(WorkloadType, u32)> {
+    match &opts.run_spec {
         RunSpec::Bench {
             shared_counter,
             transfer_object,
+            workload,
             ..
-        } => {
-            if shared_counter > 0 {
-                let workload = SharedCounterWorkload::new_boxed(
-                    primary_gas_id,
-                    primary_gas_account_owner,
-                    primary_gas_account_keypair.clone(),
-                    None,
-                );
-                workloads
-                    .entry(WorkloadType::SharedCounter)
-                    .or_insert((shared_counter, workload));
-            }
-            if transfer_object > 0 {
-                let workload = TransferObjectWorkload::new_boxed(
-                    opts.num_transfer_accounts,
-                    primary_gas_id,
-                    primary_gas_account_owner,
-                    primary_gas_account_keypair,
-                );
-                workloads
-                    .entry(WorkloadType::TransferObject)
-                    .or_insert((transfer_object, workload));
-            }
-        }
-    }
-    let workload = CombinationWorkload::new_boxed(workloads);
-    WorkloadInfo {
-        target_qps,
-        num_workers,
-        max_in_flight_ops: in_flight_ratio * target_qps,
-        workload,
+        } => match workload {
+            Some(mix) => mix.weights.clone(),
+            None => [
+                (WorkloadType::SharedCounter, *shared_counter),
+                (WorkloadType::TransferObject, *transfer_object),
+            ]
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0)
+            .collect(),
+        },
+        RunSpec::Replay { .. } | RunSpec::Baseline { .. } | RunSpec::Compare { .. } => vec![],
     }
 }
 
-fn make_shared_counter_workload(
-    target_qps: u64,
-    num_workers: u64,
-    max_in_flight_ops: u64,
-    primary_gas_id: ObjectID,
-    owner: SuiAddress,
-    keypair: Arc<AccountKeyPair>,
-) -> Option<WorkloadInfo> {
-    if target_qps == 0 || max_in_flight_ops == 0 || num_workers == 0 {
-        None
-    } else {
-        let workload = SharedCounterWorkload::new_boxed(primary_gas_id, owner, keypair, None);
-        Some(WorkloadInfo {
-            target_qps,
-            num_workers,
-            max_in_flight_ops,
-            workload,
-        })
+fn make_workload(
+    workload_type: WorkloadType,
+    gas_pool: Arc<GasPool>,
+    seed: Option<u64>,
+    opts: &Opts,
+) -> Box<dyn Workload<dyn Payload>> {
+    let rng = seeded_rng(seed, RngStream::Workload(workload_type));
+    let key_schemes = opts.key_schemes.clone().unwrap_or_default();
+    match workload_type {
+        WorkloadType::SharedCounter => {
+            SharedCounterWorkload::new_boxed(gas_pool, None, key_schemes, rng)
+        }
+        WorkloadType::TransferObject => TransferObjectWorkload::new_boxed(
+            opts.num_transfer_accounts,
+            opts.topology,
+            gas_pool,
+            key_schemes,
+            rng,
+        ),
+        WorkloadType::PublishPackage => {
+            PublishPackageWorkload::new_boxed(gas_pool, key_schemes, rng)
+        }
+        WorkloadType::MintObjects | WorkloadType::GrowCollection => {
+            let config = ObjectStoreConfig {
+                payload_size: opts.object_payload_size,
+                fan_out: opts.object_fan_out,
+            };
+            ObjectStoreWorkload::new_boxed(workload_type, config, gas_pool, key_schemes, rng)
+        }
     }
 }
 
-fn make_transfer_object_workload(
+fn make_combination_workload(
     target_qps: u64,
     num_workers: u64,
-    max_in_flight_ops: u64,
-    num_transfer_accounts: u64,
-    primary_gas_id: &ObjectID,
-    owner: SuiAddress,
-    keypair: Arc<AccountKeyPair>,
-) -> Option<WorkloadInfo> {
-    if target_qps == 0 || max_in_flight_ops == 0 || num_workers == 0 {
-        None
-    } else {
-        let workload = TransferObjectWorkload::new_boxed(
-            num_transfer_accounts,
-            *primary_gas_id,
-            owner,
-            keypair,
-        );
-        Some(WorkloadInfo {
-            target_qps,
-            num_workers,
-            max_in_flight_ops,
-            workload,
-        })
+    in_flight_ratio: u64,
+    gas_pool: Arc<GasPool>,
+    seed: Option<u64>,
+    opts: &Opts,
+) -> WorkloadInfo {
+    let mut workloads = BTreeMap::<WorkloadType, (u32, Box<dyn Workload<dyn Payload>>)>::new();
+    for (workload_type, weight) in workload_weights(opts) {
+        let workload = make_workload(workload_type, gas_pool.clone(), seed, opts);
+        workloads.entry(workload_type).or_insert((weight, workload));
+    }
+    let workload = CombinationWorkload::new_boxed(workloads, seeded_rng(seed, RngStream::Mix));
+    WorkloadInfo {
+        target_qps,
+        num_workers,
+        max_in_flight_ops: in_flight_ratio * target_qps,
+        workload,
     }
 }
 
@@ -346,6 +632,40 @@ fn make_transfer_object_workload(
 /// --in-flight-ratio 2 \
 /// --shared-counter 50 \
 /// --transfer-object 50```
+/// To load test a fullnode or a gateway instead of the validators, e.g. a
+/// devnet-like deployment, add `--local false --target rpc:<url>` to the
+/// remote benchmark flags above, without the gateway config
+/// To interleave several workloads by weight, replace the
+/// per-workload flags with a mix, e.g.
+/// `--workload transfer=70,shared-counter=20,publish=10`
+/// To benchmark storage writes, mint objects or grow collections of them,
+/// e.g. `--object-payload-size 16384 --object-fan-out 50 bench --workload mint=1`
+/// To watch throughput while an unbounded run is going, add
+/// `--report-interval 30s` (and `--report-format json` for JSON lines)
+/// To generate more load than a single client can, start a leader with
+/// `--lead-on 0.0.0.0:7000 --num-shards 4 bench --target-qps 40000` and
+/// four remote benchmark clients with `--leader-address <leader>:7000`
+/// To measure liveness while a validator is down, add e.g.
+/// `--run-duration 180s --report-interval 10s --fault kill:validator-2@60s,restart@120s`
+/// To find the highest load sustained with a p99 under 500ms, add
+/// `--find-max-tps --max-p99-latency-ms 500 bench --target-qps 20000`
+/// To fail on regressions, e.g. in CI, add
+/// `--compare-with <previous stats> --check 'tps>=-5%' --check 'p99<=+10%'`
+/// To post the comparison as a PR comment, add `--format markdown` and
+/// capture stdout
+/// To transfer objects between accounts other than through a hub, add
+/// e.g. `--num-transfer-accounts 20 --topology hotspot:2`
+/// To issue the same transactions in two runs, e.g. to bisect a regression,
+/// add the same `--seed 42` to both
+/// To reproduce a run exactly, record it on a remote network with
+/// `--record /tmp/trace.jsonl`, restore the network to the state the run
+/// started from, and replay the trace with
+/// `replay --trace /tmp/trace.jsonl`, adding `--time-scale 0.5` to replay it
+/// twice as fast
+/// To compare a change with named baselines rather than stats files, save
+/// the stats of a run with `baseline save --name v1.2-shared`, then after
+/// running the change compare with `compare --against v1.2-shared`,
+/// repeating `--against` to compare with several baselines
 #[tokio::main]
 async fn main() -> Result<()> {
     let mut config = telemetry_subscribers::TelemetryConfig::new("stress");
@@ -354,6 +674,103 @@ async fn main() -> Result<()> {
     let _guard = config.with_env().init();
     let opts: Opts = Opts::parse();
 
+    match &opts.run_spec {
+        RunSpec::Baseline { command } => return baseline(&opts, command),
+        RunSpec::Compare { against } => return compare_with_baselines(&opts, against),
+        _ => (),
+    }
+    if !opts.check.is_empty() && opts.compare_with.is_empty() {
+        return Err(anyhow!(
+            "Regression thresholds need previous stats, use --compare-with"
+        ));
+    }
+    if opts.find_max_tps && (opts.load_profile.is_some() || opts.warmup.is_some()) {
+        return Err(anyhow!(
+            "Searching the maximum throughput can't follow a load profile or warm up"
+        ));
+    }
+    let histogram_config = histogram_config(&opts)?;
+    if let BenchTarget::Rpc(_) = opts.target {
+        if opts.local {
+            return Err(anyhow!(
+                "Benchmarks of an RPC endpoint need a remote network, use --local false"
+            ));
+        }
+        if opts.fault.is_some() {
+            return Err(anyhow!(
+                "Fault injection needs the validators as the target of the benchmark"
+            ));
+        }
+        if opts.measure_checkpoint_latency {
+            return Err(anyhow!(
+                "Measuring the checkpoint latency needs the validators as the target of the benchmark"
+            ));
+        }
+    }
+    if opts.fault.is_some() && (opts.lead_on.is_some() || opts.leader_address.is_some()) {
+        return Err(anyhow!(
+            "Fault injection is not supported in distributed benchmarks"
+        ));
+    }
+    if opts.record.is_some() && opts.local {
+        return Err(anyhow!(
+            "Recording a trace needs a remote network to replay it against, use --local false"
+        ));
+    }
+    if let RunSpec::Replay { trace, time_scale } = &opts.run_spec {
+        return replay(&opts, trace, *time_scale).await;
+    }
+    if let Some(address) = opts.lead_on {
+        if opts.load_profile.is_some() || opts.find_max_tps {
+            return Err(anyhow!(
+                "Load profiles and throughput searches are not supported in distributed benchmarks"
+            ));
+        }
+        let plan = match opts.run_spec {
+            RunSpec::Bench {
+                target_qps,
+                num_workers,
+                ..
+            } => ShardPlan {
+                target_qps,
+                num_workers,
+                run_duration: opts.run_duration,
+            },
+            _ => unreachable!("Only benchmarks get this far"),
+        };
+        let stats = lead(address, opts.num_shards, plan).await?;
+        return report(
+            &stats,
+            &opts.compare_with,
+            &opts.benchmark_stats_path,
+            &opts.check,
+            opts.hgrm_dir.as_deref(),
+            opts.format,
+        );
+    }
+    let leader = match opts.leader_address {
+        Some(_) if opts.local => {
+            return Err(anyhow!(
+                "Distributed benchmarks need a remote network, use --local false"
+            ));
+        }
+        Some(address) => {
+            let (link, shard) = LeaderLink::connect(address).await?;
+            eprintln!(
+                "Running shard {} of {} with {} qps",
+                shard.index + 1,
+                shard.num_shards,
+                shard.target_qps
+            );
+            Some((link, shard))
+        }
+        None => None,
+    };
+    let shard = leader.as_ref().map(|(_, shard)| *shard);
+    let seed = opts
+        .seed
+        .map(|seed| seed.wrapping_add(shard.map_or(0, |shard| shard.index as u64)));
+
     let barrier = Arc::new(Barrier::new(2));
     let cloned_barrier = barrier.clone();
     let (primary_gas_id, owner, keypair, gateway_config) = if opts.local {
@@ -422,7 +839,12 @@ async fn main() -> Result<()> {
                 join_all(follower_handles).await;
             });
         });
-        (primary_gas_id, owner, Arc::new(keypair), gateway_config)
+        (
+            primary_gas_id,
+            owner,
+            Arc::new(keypair),
+            Some(gateway_config),
+        )
     } else {
         eprintln!("Configuring remote benchmark..");
         std::thread::spawn(move || {
@@ -433,34 +855,18 @@ async fn main() -> Result<()> {
                     cloned_barrier.wait().await;
                 });
         });
-        let config_path = Some(&opts.gateway_config_path)
-            .filter(|s| !s.is_empty())
-            .map(PathBuf::from)
-            .ok_or_else(|| {
-                anyhow!(format!(
-                    "Failed to find gateway config at path: {}",
-                    opts.gateway_config_path
-                ))
-            })?;
-        let config: GatewayConfig = PersistedConfig::read(&config_path)?;
-        let committee = GatewayState::make_committee(&config)?;
-        let registry = prometheus::Registry::new();
-        let authority_clients = GatewayState::make_authority_clients(
-            &config,
-            NetworkAuthorityClientMetrics::new(&registry),
-        );
-        let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
-        let aggregator = AuthorityAggregator::new(
-            committee,
-            epoch_store,
-            authority_clients,
-            AuthAggMetrics::new(&registry),
-            SafeClientMetrics::new(&registry),
-        );
+        // The gateway config is only needed to reach the validators
+        let (proxy, config) = make_remote_proxy(&opts).await?;
         let offset = ObjectID::from_hex_literal(&opts.primary_gas_id)?;
         let ids = ObjectID::in_range(offset, opts.primary_gas_objects)?;
-        let primary_gas_id = ids.choose(&mut rand::thread_rng()).unwrap();
-        let primary_gas = get_latest(*primary_gas_id, &aggregator)
+        // Shards of a distributed benchmark must not share gas
+        let primary_gas_id = match shard {
+            Some(shard) => &ids[shard.index % ids.len()],
+            None => ids
+                .choose(&mut seeded_rng(seed, RngStream::PrimaryGas))
+                .unwrap(),
+        };
+        let primary_gas = get_latest(*primary_gas_id, proxy.as_ref())
             .await
             .ok_or_else(|| {
                 anyhow!(format!(
@@ -511,99 +917,214 @@ async fn main() -> Result<()> {
         .unwrap();
     let prev_benchmark_stats_path = opts.compare_with.clone();
     let curr_benchmark_stats_path = opts.benchmark_stats_path.clone();
+    let thresholds = opts.check.clone();
+    let hgrm_dir = opts.hgrm_dir.clone();
+    let format = opts.format;
+    // The connection to the leader moves over to the client runtime
+    let leader_stream = leader.map(|(link, _)| link.into_std()).transpose()?;
     let handle = std::thread::spawn(move || {
         client_runtime.block_on(async move {
-            let committee = GatewayState::make_committee(&gateway_config).unwrap();
+            let mut leader = leader_stream.map(LeaderLink::from_std).transpose()?;
             let registry: Registry = metrics::start_prometheus_server(
                 format!("{}:{}", opts.client_metric_host, opts.client_metric_port)
                     .parse()
                     .unwrap(),
             );
-            let authority_clients = GatewayState::make_authority_clients(
-                &gateway_config,
-                NetworkAuthorityClientMetrics::new(&registry),
-            );
+            let mut faults = None;
+            let proxy: Arc<dyn ValidatorProxy> = match &opts.target {
+                BenchTarget::Rpc(url) => Arc::new(FullNodeProxy::from_url(url).await?),
+                BenchTarget::Validators => {
+                    // Unwrap safe since the config is only left out for RPC targets
+                    let mut gateway_config = gateway_config.unwrap();
+                    // Faults are injected into the traffic of the clients created below
+                    if let Some(schedule) = opts.fault.clone() {
+                        let injector =
+                            FaultInjector::start(&mut gateway_config.validator_set).await?;
+                        injector.check(&schedule)?;
+                        faults = Some((schedule, Arc::new(injector)));
+                    }
+                    let committee = GatewayState::make_committee(&gateway_config).unwrap();
+                    let authority_clients = GatewayState::make_authority_clients(
+                        &gateway_config,
+                        NetworkAuthorityClientMetrics::new(&registry),
+                    );
 
-            let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
-            let aggregator = AuthorityAggregator::new(
-                committee,
-                epoch_store,
-                authority_clients,
-                AuthAggMetrics::new(&registry),
-                SafeClientMetrics::new(&registry),
-            );
+                    let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
+                    let aggregator = AuthorityAggregator::new(
+                        committee,
+                        epoch_store,
+                        authority_clients,
+                        AuthAggMetrics::new(&registry),
+                        SafeClientMetrics::new(&registry),
+                    );
+                    Arc::new(LocalValidatorAggregatorProxy::from_aggregator(aggregator))
+                }
+            };
+            let recorder = opts
+                .record
+                .as_deref()
+                .map(TraceRecorder::create)
+                .transpose()?
+                .map(Arc::new);
+            let proxy: Arc<dyn ValidatorProxy> = match &recorder {
+                Some(recorder) => Arc::new(RecordingProxy::new(proxy, recorder.clone())),
+                None => proxy,
+            };
             match opts.run_spec {
                 RunSpec::Bench {
-                    target_qps,
-                    num_workers,
+                    mut target_qps,
+                    mut num_workers,
                     in_flight_ratio,
                     stat_collection_interval,
-                    shared_counter,
-                    transfer_object,
                     ..
                 } => {
+                    if let Some(shard) = shard {
+                        target_qps = shard.target_qps;
+                        num_workers = shard.num_workers;
+                    }
+                    // Provision workers for the peak of the profile, the driver
+                    // scales the offered load down from there
+                    if let Some(load_profile) = &opts.load_profile {
+                        target_qps = load_profile.max_tps();
+                    }
+                    let primary_gas = get_latest(primary_gas_id, proxy.as_ref())
+                        .await
+                        .ok_or_else(|| {
+                            anyhow!("Failed to read primary gas object with id: {primary_gas_id}")
+                        })?;
+                    let gas_pool = Arc::new(
+                        GasPool::new(
+                            primary_gas.compute_object_reference(),
+                            owner,
+                            keypair,
+                            opts.gas_pool_size,
+                            MAX_GAS_FOR_TESTING,
+                            proxy.as_ref(),
+                        )
+                        .await?,
+                    );
+                    let dust_merger = gas_pool
+                        .clone()
+                        .spawn_dust_merger(proxy.clone(), DUST_MERGE_INTERVAL);
                     let workloads = if !opts.disjoint_mode {
                         let mut combination_workload = make_combination_workload(
                             target_qps,
                             num_workers,
                             in_flight_ratio,
-                            primary_gas_id,
-                            owner,
-                            keypair,
+                            gas_pool.clone(),
+                            seed,
                             &opts,
                         );
-                        combination_workload.workload.init(&aggregator).await;
+                        combination_workload.workload.init(proxy.as_ref()).await;
                         vec![combination_workload]
                     } else {
                         let mut workloads = vec![];
-                        let shared_counter_weight =
-                            shared_counter as f32 / (shared_counter + transfer_object) as f32;
-                        let shared_counter_qps = (shared_counter_weight * target_qps as f32) as u64;
-                        let shared_counter_num_workers =
-                            (shared_counter_weight * num_workers as f32).ceil() as u64;
-                        let shared_counter_max_ops = (shared_counter_qps * in_flight_ratio) as u64;
-                        if let Some(mut shared_counter_workload) = make_shared_counter_workload(
-                            shared_counter_qps,
-                            shared_counter_num_workers,
-                            shared_counter_max_ops,
-                            primary_gas_id,
-                            owner,
-                            keypair.clone(),
-                        ) {
-                            shared_counter_workload.workload.init(&aggregator).await;
-                            workloads.push(shared_counter_workload);
-                        }
-                        let transfer_object_weight = 1.0 - shared_counter_weight;
-                        let transfer_object_qps = target_qps - shared_counter_qps;
-                        let trasnfer_object_num_workers =
-                            (transfer_object_weight * num_workers as f32).ceil() as u64;
-                        let trasnfer_object_max_ops =
-                            (transfer_object_qps * in_flight_ratio) as u64;
-                        if let Some(mut transfer_object_workload) = make_transfer_object_workload(
-                            transfer_object_qps,
-                            trasnfer_object_num_workers,
-                            trasnfer_object_max_ops,
-                            opts.num_transfer_accounts,
-                            &primary_gas_id,
-                            owner,
-                            keypair,
-                        ) {
-                            transfer_object_workload.workload.init(&aggregator).await;
-                            workloads.push(transfer_object_workload);
+                        let weights = workload_weights(&opts);
+                        let total_weight: u32 = weights.iter().map(|(_, weight)| weight).sum();
+                        let mut remaining_qps = target_qps;
+                        for (index, (workload_type, weight)) in weights.iter().enumerate() {
+                            let share = *weight as f32 / total_weight as f32;
+                            // The last workload picks up the rounding leftovers
+                            let qps = if index == weights.len() - 1 {
+                                remaining_qps
+                            } else {
+                                std::cmp::min((share * target_qps as f32) as u64, remaining_qps)
+                            };
+                            remaining_qps -= qps;
+                            let workers = (share * num_workers as f32).ceil() as u64;
+                            let max_in_flight_ops = qps * in_flight_ratio;
+                            if qps == 0 || workers == 0 || max_in_flight_ops == 0 {
+                                continue;
+                            }
+                            let mut workload = WorkloadInfo {
+                                target_qps: qps,
+                                num_workers: workers,
+                                max_in_flight_ops,
+                                workload: make_workload(
+                                    *workload_type,
+                                    gas_pool.clone(),
+                                    seed,
+                                    &opts,
+                                ),
+                            };
+                            workload.workload.init(proxy.as_ref()).await;
+                            workloads.push(workload);
                         }
                         workloads
                     };
-                    let interval = opts.run_duration;
+                    let interval = match (&opts.load_profile, shard) {
+                        // The search ends the run once it's over
+                        _ if opts.find_max_tps => Interval::Time(Duration::MAX),
+                        (Some(load_profile), _) => Interval::Time(load_profile.total_duration()),
+                        (None, Some(shard)) => shard.run_duration,
+                        (None, None) => opts.run_duration,
+                    };
                     // We only show continuous progress in stderr
                     // if benchmark is running in unbounded mode,
                     // otherwise summarized benchmark results are
                     // published in the end
                     let show_progress = interval.is_unbounded();
-                    let driver = BenchDriver::new(stat_collection_interval);
-                    driver
-                        .run(workloads, aggregator, &registry, show_progress, interval)
-                        .await
+                    let mut driver = BenchDriver::new(stat_collection_interval)
+                        .with_shutdown_timeout(opts.shutdown_timeout)
+                        .with_histogram_config(histogram_config);
+                    if let Some(load_profile) = opts.load_profile.clone() {
+                        driver = driver.with_load_profile(load_profile);
+                    }
+                    if let Some(warmup) = opts.warmup {
+                        driver = driver.with_warmup(warmup);
+                    }
+                    if opts.measure_checkpoint_latency {
+                        driver = driver.with_checkpoint_latency();
+                    }
+                    if let Some(path) = opts.timeseries.clone() {
+                        driver = driver.with_timeseries(path);
+                    }
+                    if let Some(interval) = opts.quantile_interval {
+                        driver = driver.with_quantile_series(interval);
+                    }
+                    if let Some(endpoint) = opts.otlp_endpoint.clone() {
+                        driver = driver.with_otlp(OtlpConfig {
+                            endpoint,
+                            export_interval: opts.otlp_export_interval,
+                            resource_attributes: opts.otlp_attribute.clone(),
+                        });
+                    }
+                    if opts.find_max_tps {
+                        driver = driver.with_max_tps_search(MaxTpsSearch::new(
+                            target_qps,
+                            opts.max_p99_latency_ms,
+                            opts.max_error_rate,
+                            opts.search_step_duration,
+                            opts.search_precision_tps,
+                        ));
+                    }
+                    if let Some(report_interval) = opts.report_interval {
+                        driver = driver.with_report_interval(report_interval, opts.report_format);
+                    }
+                    if let Some((schedule, injector)) = faults {
+                        driver = driver.with_faults(schedule, injector);
+                    }
+                    if let Some(leader) = &mut leader {
+                        leader.ready_and_wait_for_start().await?;
+                    }
+                    let mut stats = driver
+                        .run(workloads, proxy, &registry, show_progress, interval)
+                        .await?;
+                    dust_merger.abort();
+                    stats.gas_pool = Some(gas_pool.stats());
+                    if let Some(recorder) = &recorder {
+                        recorder.flush()?;
+                        eprintln!(
+                            "Trace recorded to {}",
+                            opts.record.as_ref().unwrap().display()
+                        );
+                    }
+                    if let Some(leader) = &mut leader {
+                        leader.send_stats(&stats).await?;
+                    }
+                    Ok::<_, anyhow::Error>(stats)
                 }
+                _ => unreachable!("Only benchmarks get this far"),
             }
         })
     });
@@ -612,27 +1133,306 @@ async fn main() -> Result<()> {
         Err(anyhow!("Failed to join client runtime: {:?}", err))
     } else {
         let stats: BenchmarkStats = joined.unwrap().unwrap();
+        report(
+            &stats,
+            &prev_benchmark_stats_path,
+            &curr_benchmark_stats_path,
+            &thresholds,
+            hgrm_dir.as_deref(),
+            format,
+        )
+    }
+}
+
+/// The proxy to the target of a remote benchmark, and the gateway config if the validators
+/// are the target.
+async fn make_remote_proxy(
+    opts: &Opts,
+) -> Result<(Box<dyn ValidatorProxy>, Option<GatewayConfig>)> {
+    let (proxy, config): (Box<dyn ValidatorProxy>, _) = match &opts.target {
+        BenchTarget::Rpc(url) => (Box::new(FullNodeProxy::from_url(url).await?), None),
+        BenchTarget::Validators => {
+            let config_path = Some(&opts.gateway_config_path)
+                .filter(|s| !s.is_empty())
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    anyhow!(format!(
+                        "Failed to find gateway config at path: {}",
+                        opts.gateway_config_path
+                    ))
+                })?;
+            let config: GatewayConfig = PersistedConfig::read(&config_path)?;
+            let committee = GatewayState::make_committee(&config)?;
+            let registry = prometheus::Registry::new();
+            let authority_clients = GatewayState::make_authority_clients(
+                &config,
+                NetworkAuthorityClientMetrics::new(&registry),
+            );
+            let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
+            let aggregator = AuthorityAggregator::new(
+                committee,
+                epoch_store,
+                authority_clients,
+                AuthAggMetrics::new(&registry),
+                SafeClientMetrics::new(&registry),
+            );
+            (
+                Box::new(LocalValidatorAggregatorProxy::from_aggregator(aggregator)),
+                Some(config),
+            )
+        }
+    };
+    Ok((proxy, config))
+}
+
+/// Replays the trace at `trace_path` against the target, and reports its stats like a run.
+async fn replay(opts: &Opts, trace_path: &Path, time_scale: f64) -> Result<()> {
+    if opts.local {
+        return Err(anyhow!(
+            "Replaying a trace needs the network it was recorded on, use --local false"
+        ));
+    }
+    if opts.load_profile.is_some()
+        || opts.find_max_tps
+        || opts.warmup.is_some()
+        || opts.fault.is_some()
+        || opts.measure_checkpoint_latency
+        || opts.quantile_interval.is_some()
+        || opts.lead_on.is_some()
+        || opts.leader_address.is_some()
+        || opts.record.is_some()
+    {
+        return Err(anyhow!(
+            "Replaying a trace only follows its recorded load, and can't be combined with load \
+             profiles, searches, warmups, faults, checkpoint latencies, quantile series, \
+             distributed runs or another recording"
+        ));
+    }
+    let trace = load_trace(trace_path)?;
+    let (proxy, _) = make_remote_proxy(opts).await?;
+    let mut driver =
+        ReplayDriver::new(trace, time_scale).with_histogram_config(histogram_config(opts)?);
+    if let Some(path) = opts.timeseries.clone() {
+        driver = driver.with_timeseries(path);
+    }
+    let stats = driver.run(Arc::from(proxy)).await?;
+    report(
+        &stats,
+        &opts.compare_with,
+        &opts.benchmark_stats_path,
+        &opts.check,
+        opts.hgrm_dir.as_deref(),
+        opts.format,
+    )
+}
+
+/// Prints the final report in `format`, compared with the stats at `prev_benchmark_stats_path`
+/// if any, and saves the stats to `curr_benchmark_stats_path` if set, and the latency histograms
+/// to `hgrm_dir` if set. Fails if the comparison violates any of `thresholds`.
+fn report(
+    stats: &BenchmarkStats,
+    prev_benchmark_stats_path: &str,
+    curr_benchmark_stats_path: &str,
+    thresholds: &[Threshold],
+    hgrm_dir: Option<&Path>,
+    format: SummaryFormat,
+) -> Result<()> {
+    if format == SummaryFormat::Markdown {
+        println!("### Benchmark Report\n");
+        println!("{}", stats.to_markdown());
+        if let Some(max_tps) = stats.max_sustainable_tps {
+            println!("Maximum sustainable throughput: **{} tps**\n", max_tps);
+        }
+    } else {
         let table = stats.to_table();
         eprintln!("Benchmark Report:");
         eprintln!("{}", table);
-        if !prev_benchmark_stats_path.is_empty() {
-            let data = std::fs::read_to_string(&prev_benchmark_stats_path)?;
-            let prev_stats: BenchmarkStats = serde_json::from_str(&data)?;
-            let cmp = BenchmarkCmp {
-                new: &stats,
-                old: &prev_stats,
-            };
-            let cmp_table = cmp.to_table();
+        if !stats.stages.is_empty() {
+            eprintln!("Benchmark Report per Stage:");
+            eprintln!("{}", stats.to_stage_table());
+        }
+        if let Some(max_tps) = stats.max_sustainable_tps {
+            eprintln!("Maximum sustainable throughput: {} tps", max_tps);
+        }
+        if stats.workloads.len() > 1 {
+            eprintln!("Benchmark Report per Workload:");
+            eprintln!("{}", stats.to_workload_table());
+        }
+        if stats.schemes.len() > 1 {
+            eprintln!("Benchmark Report per Signature Scheme:");
+            eprintln!("{}", stats.to_scheme_table());
+        }
+    }
+    if !stats.faults.is_empty() {
+        eprintln!("Injected Faults:");
+        eprintln!("{}", stats.to_fault_table());
+    }
+    if let Some(gas_pool) = &stats.gas_pool {
+        let summary = format!(
+            "{} coins leased, pool exhausted {} times, {} dust coins merged",
+            gas_pool.leased, gas_pool.exhausted, gas_pool.dust_merged
+        );
+        if format == SummaryFormat::Markdown {
+            println!("Gas pool: {}\n", summary);
+        } else {
+            eprintln!("Gas pool: {}", summary);
+        }
+    }
+    if let Some(hgrm_dir) = hgrm_dir {
+        for path in stats.write_hgrm_files(hgrm_dir)? {
+            eprintln!("Latency histogram written to {}", path.display());
+        }
+    }
+    let mut violations: Vec<String> = vec![];
+    if !prev_benchmark_stats_path.is_empty() {
+        let data = std::fs::read_to_string(prev_benchmark_stats_path)?;
+        let prev_stats: BenchmarkStats = serde_json::from_str(&data)?;
+        violations = compare(
+            stats,
+            &prev_stats,
+            prev_benchmark_stats_path,
+            thresholds,
+            format,
+        )?;
+    }
+    if !curr_benchmark_stats_path.is_empty() {
+        let serialized = serde_json::to_string(stats)?;
+        std::fs::write(curr_benchmark_stats_path, serialized)?;
+    }
+    if !violations.is_empty() {
+        return Err(anyhow!(
+            "Regression thresholds violated: {}",
+            violations.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// Prints the comparison of `stats` with `prev_stats`, the stats of `label`, in `format`, and
+/// checks `thresholds` against it. Returns the violated thresholds.
+fn compare(
+    stats: &BenchmarkStats,
+    prev_stats: &BenchmarkStats,
+    label: &str,
+    thresholds: &[Threshold],
+    format: SummaryFormat,
+) -> Result<Vec<String>> {
+    let cmp = BenchmarkCmp {
+        new: stats,
+        old: prev_stats,
+    };
+    if format == SummaryFormat::Markdown {
+        println!("### Benchmark Comparison with `{}`\n", label);
+        println!("{}", cmp.to_markdown());
+    } else {
+        let cmp_table = cmp.to_table();
+        eprintln!("Benchmark Comparison Report[{}]:", label);
+        eprintln!("{}", cmp_table);
+        if stats.workloads.len() > 1 && !cmp.workload_cmps().is_empty() {
+            eprintln!("Benchmark Comparison Report per Workload:");
+            eprintln!("{}", cmp.to_workload_table());
+        }
+        if stats.schemes.len() > 1 && !cmp.scheme_cmps().is_empty() {
+            eprintln!("Benchmark Comparison Report per Signature Scheme:");
+            eprintln!("{}", cmp.to_scheme_table());
+        }
+    }
+    if !thresholds.is_empty() {
+        let checks = cmp.check(thresholds).map_err(|e| anyhow!(e))?;
+        if format == SummaryFormat::Markdown {
+            println!("### Benchmark Regression Check\n");
+            println!("{}", ThresholdCheck::to_markdown(&checks));
+        } else {
+            eprintln!("Benchmark Regression Check:");
+            eprintln!("{}", ThresholdCheck::to_table(&checks));
+        }
+        return Ok(checks
+            .into_iter()
+            .filter(|check| !check.passed)
+            .map(|check| {
+                format!(
+                    "{} ({:.2}%)",
+                    check.threshold,
+                    check.comparison.diff_ratio * 100f64
+                )
+            })
+            .collect());
+    }
+    Ok(vec![])
+}
+
+fn baseline_registry(opts: &Opts) -> Result<BaselineRegistry> {
+    let dir = match &opts.baseline_dir {
+        Some(dir) => dir.clone(),
+        None => sui_config::sui_config_dir()?.join("benchmark-baselines"),
+    };
+    Ok(BaselineRegistry::new(dir))
+}
+
+fn read_stats(path: &str) -> Result<BenchmarkStats> {
+    let data = std::fs::read_to_string(path).map_err(|e| {
+        anyhow!(
+            "Failed to read the stats of the last run at {path}, see --benchmark-stats-path: {e}"
+        )
+    })?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+/// Saves, lists or removes named baselines of the registry.
+fn baseline(opts: &Opts, command: &BaselineCommand) -> Result<()> {
+    let registry = baseline_registry(opts)?;
+    match command {
+        BaselineCommand::Save {
+            name,
+            description,
+            force,
+        } => {
+            let stats = read_stats(&opts.benchmark_stats_path)?;
+            let metadata = registry.save(name, &stats, description.clone(), *force)?;
             eprintln!(
-                "Benchmark Comparison Report[{}]:",
-                prev_benchmark_stats_path
+                "Saved the stats at {} as baseline '{}' ({:.2} tps, p99 {} ms)",
+                opts.benchmark_stats_path, metadata.name, metadata.tps, metadata.p99_latency_ms
             );
-            eprintln!("{}", cmp_table);
         }
-        if !curr_benchmark_stats_path.is_empty() {
-            let serialized = serde_json::to_string(&stats)?;
-            std::fs::write(curr_benchmark_stats_path, serialized)?;
+        BaselineCommand::List => {
+            let baselines = registry.list()?;
+            if baselines.is_empty() {
+                eprintln!("No baselines saved yet, see `baseline save`");
+            } else {
+                println!("{}", BaselineRegistry::to_table(&baselines));
+            }
+        }
+        BaselineCommand::Remove { name } => {
+            registry.remove(name)?;
+            eprintln!("Removed baseline '{}'", name);
         }
-        Ok(())
     }
+    Ok(())
+}
+
+/// Compares the stats of the last run with each of the baselines named `against`. Fails if any
+/// comparison violates the thresholds.
+fn compare_with_baselines(opts: &Opts, against: &[String]) -> Result<()> {
+    let registry = baseline_registry(opts)?;
+    let stats = read_stats(&opts.benchmark_stats_path)?;
+    let mut violations = vec![];
+    for name in against {
+        let (metadata, baseline_stats) = registry.load(name)?;
+        let label = match &metadata.git_commit {
+            Some(commit) => format!("{} @ {}", name, commit),
+            None => name.clone(),
+        };
+        violations.extend(
+            compare(&stats, &baseline_stats, &label, &opts.check, opts.format)?
+                .into_iter()
+                .map(|violation| format!("{}: {}", name, violation)),
+        );
+    }
+    if !violations.is_empty() {
+        return Err(anyhow!(
+            "Regression thresholds violated: {}",
+            violations.join(", ")
+        ));
+    }
+    Ok(())
 }
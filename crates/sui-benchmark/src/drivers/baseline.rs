@@ -0,0 +1,191 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A local registry of the stats of past runs saved under a name, e.g. `v1.2-shared`, so that a
+//! run can be compared with several baselines by name rather than by the paths of their stats.
+//!
+//! Each baseline is a directory of the registry named after it, holding the stats of the run and
+//! metadata about where they come from.
+
+use super::BenchmarkStats;
+use anyhow::{anyhow, Context, Result};
+use comfy_table::{ContentArrangement, Table};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STATS_FILE: &str = "stats.json";
+const METADATA_FILE: &str = "metadata.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineMetadata {
+    pub name: String,
+    /// Unix time the baseline was saved at, in seconds.
+    pub saved_at: u64,
+    /// Commit checked out where the baseline was saved, if in a git repository, suffixed with
+    /// `-dirty` if the working tree had uncommitted changes.
+    pub git_commit: Option<String>,
+    pub description: Option<String>,
+    /// Throughput and p99 latency of the run, to tell baselines apart when listing them.
+    pub tps: f64,
+    pub p99_latency_ms: u64,
+}
+
+pub struct BaselineRegistry {
+    dir: PathBuf,
+}
+
+impl BaselineRegistry {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Saves `stats` as the baseline `name`, replacing an existing one only if `overwrite` is set.
+    pub fn save(
+        &self,
+        name: &str,
+        stats: &BenchmarkStats,
+        description: Option<String>,
+        overwrite: bool,
+    ) -> Result<BaselineMetadata> {
+        let dir = self.baseline_dir(name)?;
+        if dir.exists() && !overwrite {
+            return Err(anyhow!(
+                "Baseline '{name}' already exists, use --force to replace it"
+            ));
+        }
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create baseline directory {}", dir.display()))?;
+        let metadata = BaselineMetadata {
+            name: name.to_string(),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            git_commit: git_commit(),
+            description,
+            tps: stats.tps(),
+            p99_latency_ms: stats.latency_ms.histogram.value_at_quantile(0.99),
+        };
+        write_json(&dir.join(STATS_FILE), stats)?;
+        write_json(&dir.join(METADATA_FILE), &metadata)?;
+        Ok(metadata)
+    }
+
+    pub fn load(&self, name: &str) -> Result<(BaselineMetadata, BenchmarkStats)> {
+        let dir = self.baseline_dir(name)?;
+        if !dir.exists() {
+            return Err(anyhow!(
+                "No baseline '{name}' in {}, see `baseline list`",
+                self.dir.display()
+            ));
+        }
+        Ok((
+            read_json(&dir.join(METADATA_FILE))?,
+            read_json(&dir.join(STATS_FILE))?,
+        ))
+    }
+
+    /// The metadata of the baselines of the registry, oldest first.
+    pub fn list(&self) -> Result<Vec<BaselineMetadata>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+        let entries = std::fs::read_dir(&self.dir)
+            .with_context(|| format!("Failed to read baselines in {}", self.dir.display()))?;
+        let mut baselines = vec![];
+        for entry in entries {
+            let path = entry?.path().join(METADATA_FILE);
+            if path.exists() {
+                baselines.push(read_json::<BaselineMetadata>(&path)?);
+            }
+        }
+        baselines.sort_by(|a, b| (a.saved_at, &a.name).cmp(&(b.saved_at, &b.name)));
+        Ok(baselines)
+    }
+
+    pub fn remove(&self, name: &str) -> Result<()> {
+        let dir = self.baseline_dir(name)?;
+        if !dir.exists() {
+            return Err(anyhow!("No baseline '{name}' in {}", self.dir.display()));
+        }
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to remove baseline directory {}", dir.display()))
+    }
+
+    pub fn to_table(baselines: &[BaselineMetadata]) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "name",
+                "saved at (unix)",
+                "commit",
+                "tps",
+                "p99 (ms)",
+                "description",
+            ]);
+        for baseline in baselines {
+            table.add_row(vec![
+                baseline.name.clone(),
+                baseline.saved_at.to_string(),
+                baseline.git_commit.clone().unwrap_or_default(),
+                format!("{:.2}", baseline.tps),
+                baseline.p99_latency_ms.to_string(),
+                baseline.description.clone().unwrap_or_default(),
+            ]);
+        }
+        table
+    }
+
+    /// The directory of the baseline `name`, which must be a plain file name so that it stays
+    /// within the registry.
+    fn baseline_dir(&self, name: &str) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+        if !valid {
+            return Err(anyhow!(
+                "Invalid baseline name '{name}', expected letters, digits, '.', '-' or '_'"
+            ));
+        }
+        Ok(self.dir.join(name))
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let serialized = serde_json::to_string(value)?;
+    std::fs::write(path, serialized).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&data).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// The commit checked out in the current directory, if it is in a git repository.
+fn git_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .map(|output| !output.stdout.is_empty())
+        .unwrap_or(false);
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
@@ -16,30 +16,43 @@ use prometheus::GaugeVec;
 use prometheus::HistogramVec;
 use prometheus::IntCounterVec;
 use prometheus::Registry;
-use sui_core::authority_aggregator::AuthorityAggregator;
 use tokio::sync::OnceCell;
 
 use crate::drivers::driver::Driver;
 use crate::drivers::HistogramWrapper;
+use crate::drivers::QuantilePoint;
+use crate::validator_proxy::ValidatorProxy;
+use crate::workloads::workload::KeyScheme;
 use crate::workloads::workload::Payload;
 use crate::workloads::workload::WorkloadInfo;
-use std::collections::{BTreeMap, VecDeque};
+use crate::workloads::workload::WorkloadType;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use sui_core::authority_client::NetworkAuthorityClient;
-use sui_core::quorum_driver::{QuorumDriverHandler, QuorumDriverMetrics};
+use sui_types::base_types::TransactionDigest;
 use sui_types::crypto::EmptySignInfo;
-use sui_types::messages::{
-    ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
-    TransactionEnvelope,
-};
+use sui_types::messages::TransactionEnvelope;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
 use tokio::sync::Barrier;
 use tokio::time;
 use tokio::time::Instant;
 use tracing::{debug, error};
 
+use super::fault::{FaultEvent, FaultInjector, FaultSchedule};
+use super::otlp::{OtlpConfig, OtlpExporter};
+use super::timeseries::TimeSeries;
 use super::BenchmarkStats;
+use super::HistogramConfig;
 use super::Interval;
+use super::LoadProfile;
+use super::MaxTpsSearch;
+use super::ReportFormat;
+use super::SchemeStats;
+use super::StageStats;
+use super::WorkloadStats;
 pub struct BenchMetrics {
     pub num_success: IntCounterVec,
     pub num_error: IntCounterVec,
@@ -50,6 +63,18 @@ pub struct BenchMetrics {
     pub validators_in_effects_cert: IntCounterVec,
 }
 
+// How often workers re-evaluate their offered load when following a load profile or a search
+const LOAD_PROFILE_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the transactions in flight are waited for once the run is interrupted, by default.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+// How often new checkpoints are looked for when measuring the checkpoint latency
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// Transactions not seen in a checkpoint by then are assumed to have failed
+const CHECKPOINT_LATENCY_TIMEOUT: Duration = Duration::from_secs(60);
+
 const LATENCY_SEC_BUCKETS: &[f64] = &[
     0.01, 0.05, 0.1, 0.25, 0.5, 1., 2.5, 5., 10., 20., 30., 60., 90.,
 ];
@@ -116,12 +141,102 @@ struct Stats {
     pub num_no_gas: u64,
     pub num_submitted: u64,
     pub num_in_flight: u64,
+    /// Load profile stage the sample was collected in, if any
+    pub stage: Option<usize>,
+    /// Whether the sample was collected during warmup and must not be accounted for
+    pub warmup: bool,
+    /// Whether this is the last sample of the worker for `stage`
+    pub stage_over: bool,
     pub bench_stats: BenchmarkStats,
+    /// The same window broken down by workload
+    pub workload_stats: HashMap<WorkloadType, BenchmarkStats>,
+    /// The same window broken down by signature scheme of the senders
+    pub scheme_stats: HashMap<KeyScheme, BenchmarkStats>,
+}
+
+pub(crate) fn empty_benchmark_stats(histogram_config: HistogramConfig) -> BenchmarkStats {
+    BenchmarkStats {
+        duration: Duration::ZERO,
+        num_error: 0,
+        num_success: 0,
+        latency_ms: HistogramWrapper::new(histogram_config),
+        response_time_ms: HistogramWrapper::new(histogram_config),
+        submit_to_cert_latency_ms: HistogramWrapper::new(histogram_config),
+        cert_to_effects_latency_ms: HistogramWrapper::new(histogram_config),
+        checkpoint_latency_ms: HistogramWrapper::new(histogram_config),
+        stages: vec![],
+        workloads: vec![],
+        schemes: vec![],
+        max_sustainable_tps: None,
+        faults: vec![],
+        gas_pool: None,
+        quantiles: vec![],
+    }
+}
+
+/// Hands out the per-workload or per-scheme stats of the window which just ended, leaving empty
+/// ones behind.
+fn take_keyed_stats<K: Eq + Hash>(
+    keyed_stats: &mut HashMap<K, BenchmarkStats>,
+    duration: Duration,
+) -> HashMap<K, BenchmarkStats> {
+    keyed_stats
+        .drain()
+        .map(|(key, mut stats)| {
+            stats.duration = duration;
+            (key, stats)
+        })
+        .collect()
+}
+
+/// Time a transaction spent in each phase of its execution.
+#[derive(Clone, Copy)]
+struct TransactionLatency {
+    total: Duration,
+    /// From when the transaction was due to be submitted at the target rate.
+    response: Duration,
+    /// Unknown when the target doesn't return the certificate on its own.
+    submit_to_cert: Option<Duration>,
+}
+
+/// Load offered during a [`MaxTpsSearch`], as set by the stats task for the workers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Probe {
+    /// Offer `tps` in total for probe `index`.
+    Running {
+        index: usize,
+        tps: u64,
+    },
+    /// Stop offering load and wait for the transactions of probe `index` to land.
+    Draining {
+        index: usize,
+    },
+    Done,
+}
+
+impl Probe {
+    /// Stage a worker is in and total load it offers, depending on whether it still has
+    /// transactions in flight.
+    fn stage_and_tps(&self, in_flight: bool) -> (Option<usize>, u64) {
+        match *self {
+            Probe::Running { index, tps } => (Some(index), tps),
+            // Stay in the probe until everything in flight has landed
+            Probe::Draining { index } if in_flight => (Some(index), 0),
+            Probe::Draining { .. } | Probe::Done => (None, 0),
+        }
+    }
 }
 
 type RetryType = Box<(TransactionEnvelope<EmptySignInfo>, Box<dyn Payload>)>;
 enum NextOp {
-    Response(Option<(Duration, Box<dyn Payload>)>),
+    Response(
+        Option<(
+            TransactionLatency,
+            WorkloadType,
+            KeyScheme,
+            Box<dyn Payload>,
+        )>,
+    ),
     Retry(RetryType),
 }
 
@@ -143,6 +258,200 @@ pub struct BenchWorker {
 pub struct BenchDriver {
     pub stat_collection_interval: u64,
     pub start_time: Instant,
+    pub load_profile: Option<LoadProfile>,
+    pub report_interval: Option<(Duration, ReportFormat)>,
+    pub warmup: Option<Interval>,
+    pub max_tps_search: Option<MaxTpsSearch>,
+    pub faults: Option<(FaultSchedule, Arc<FaultInjector>)>,
+    pub shutdown_timeout: Duration,
+    pub measure_checkpoint_latency: bool,
+    pub timeseries_path: Option<PathBuf>,
+    pub quantile_interval: Option<Duration>,
+    pub otlp: Option<OtlpConfig>,
+    pub histogram_config: HistogramConfig,
+}
+
+/// Resolves on the first SIGINT, or SIGTERM on unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate =
+            signal(SignalKind::terminate()).expect("Failed to install the SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Emits the stats of one reporting window.
+fn report_window(stats: &BenchmarkStats, elapsed: Duration, format: ReportFormat) {
+    match format {
+        ReportFormat::Json => match serde_json::to_string(&stats.to_window_report(elapsed)) {
+            Ok(line) => println!("{}", line),
+            Err(e) => error!("Failed to serialize window report: {}", e),
+        },
+        ReportFormat::Table if stats.num_success + stats.num_error == 0 => {
+            eprintln!(
+                "No transactions completed in the {}s before {}s",
+                stats.duration.as_secs(),
+                elapsed.as_secs()
+            );
+        }
+        ReportFormat::Table => {
+            eprintln!(
+                "Benchmark Report for the {}s before {}s:",
+                stats.duration.as_secs(),
+                elapsed.as_secs()
+            );
+            eprintln!("{}", stats.to_table());
+        }
+    }
+    if format == ReportFormat::Table && !stats.faults.is_empty() {
+        eprintln!("Faults injected in the window:");
+        eprintln!("{}", stats.to_fault_table());
+    }
+}
+
+/// The stats of the checkpoint watcher, as the extra worker `id`.
+fn checkpoint_stats(
+    id: usize,
+    histogram: &hdrhistogram::Histogram<u64>,
+    workload_stats: &mut HashMap<WorkloadType, BenchmarkStats>,
+    duration: Duration,
+    histogram_config: HistogramConfig,
+) -> Stats {
+    let mut bench_stats = empty_benchmark_stats(histogram_config);
+    bench_stats.duration = duration;
+    bench_stats.checkpoint_latency_ms = HistogramWrapper {
+        histogram: histogram.clone(),
+    };
+    Stats {
+        id,
+        num_no_gas: 0,
+        num_submitted: 0,
+        num_in_flight: 0,
+        stage: None,
+        warmup: false,
+        stage_over: false,
+        bench_stats,
+        workload_stats: take_keyed_stats(workload_stats, duration),
+        scheme_stats: HashMap::new(),
+    }
+}
+
+/// Follows the checkpoints of `proxy`, and records the time from the submission of each
+/// transaction received on `submitted` until it appears in one. The latencies are sent to the
+/// stats task every `stat_interval`, as the stats of the extra worker `id`. Once the workers
+/// are done, waits up to `drain_timeout` for the transactions which aren't checkpointed yet.
+async fn watch_checkpoints(
+    id: usize,
+    proxy: Arc<dyn ValidatorProxy>,
+    mut submitted: mpsc::UnboundedReceiver<(TransactionDigest, Instant, WorkloadType)>,
+    stats_tx: mpsc::Sender<Stats>,
+    stat_interval: Duration,
+    drain_timeout: Duration,
+    histogram_config: HistogramConfig,
+) {
+    let mut next_seq = match proxy.get_checkpoint_transactions(None).await {
+        Ok(latest) => latest.map_or(0, |(seq, _)| seq + 1),
+        Err(e) => {
+            error!("Not measuring the checkpoint latency: {}", e);
+            return;
+        }
+    };
+    let mut pending: HashMap<TransactionDigest, (Instant, WorkloadType)> = HashMap::new();
+    let mut latency_histogram = histogram_config.new_histogram();
+    let mut workload_stats: HashMap<WorkloadType, BenchmarkStats> = HashMap::new();
+    let mut poll_interval = time::interval(CHECKPOINT_POLL_INTERVAL);
+    let mut stat_interval = time::interval(stat_interval);
+    let mut stat_start_time = Instant::now();
+    // Set once the workers are done
+    let mut drain_deadline: Option<Instant> = None;
+    loop {
+        tokio::select! {
+            _ = poll_interval.tick() => {
+                // Take in everything submitted so far, so that no transaction of the
+                // checkpoints polled below is missed
+                loop {
+                    match submitted.try_recv() {
+                        Ok((digest, start, workload_type)) => {
+                            pending.entry(digest).or_insert((start, workload_type));
+                        }
+                        Err(mpsc::error::TryRecvError::Empty) => break,
+                        Err(mpsc::error::TryRecvError::Disconnected) => {
+                            drain_deadline.get_or_insert_with(|| Instant::now() + drain_timeout);
+                            break;
+                        }
+                    }
+                }
+                loop {
+                    match proxy.get_checkpoint_transactions(Some(next_seq)).await {
+                        Ok(Some((_, digests))) => {
+                            next_seq += 1;
+                            for digest in digests {
+                                if let Some((start, workload_type)) = pending.remove(&digest) {
+                                    let latency_ms = start.elapsed().as_millis().try_into().unwrap();
+                                    latency_histogram.saturating_record(latency_ms);
+                                    workload_stats
+                                        .entry(workload_type)
+                                        .or_insert_with(|| empty_benchmark_stats(histogram_config))
+                                        .checkpoint_latency_ms
+                                        .histogram
+                                        .saturating_record(latency_ms);
+                                }
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            debug!("Failed to get checkpoint {}: {}", next_seq, e);
+                            break;
+                        }
+                    }
+                }
+                // Transactions which failed are never checkpointed
+                pending.retain(|_, (start, _)| start.elapsed() < CHECKPOINT_LATENCY_TIMEOUT);
+                if let Some(deadline) = drain_deadline {
+                    if pending.is_empty() || Instant::now() >= deadline {
+                        break;
+                    }
+                }
+            }
+            _ = stat_interval.tick() => {
+                if stats_tx
+                    .try_send(checkpoint_stats(
+                        id,
+                        &latency_histogram,
+                        &mut workload_stats,
+                        stat_start_time.elapsed(),
+                        histogram_config,
+                    ))
+                    .is_err()
+                {
+                    debug!("Failed to update stat!");
+                }
+                stat_start_time = Instant::now();
+                latency_histogram.reset();
+            }
+        }
+    }
+    // send stats one last time, waiting for room so that they are not lost
+    if stats_tx
+        .send(checkpoint_stats(
+            id,
+            &latency_histogram,
+            &mut workload_stats,
+            stat_start_time.elapsed(),
+            histogram_config,
+        ))
+        .await
+        .is_err()
+    {
+        debug!("Failed to update stat!");
+    }
 }
 
 impl BenchDriver {
@@ -150,8 +459,90 @@ impl BenchDriver {
         BenchDriver {
             stat_collection_interval,
             start_time: Instant::now(),
+            load_profile: None,
+            report_interval: None,
+            warmup: None,
+            max_tps_search: None,
+            faults: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            measure_checkpoint_latency: false,
+            timeseries_path: None,
+            quantile_interval: None,
+            otlp: None,
+            histogram_config: HistogramConfig::default(),
         }
     }
+    /// Vary the offered load over time instead of running at a constant target qps.
+    /// Each worker follows the profile scaled by its share of the total target qps.
+    pub fn with_load_profile(mut self, load_profile: LoadProfile) -> BenchDriver {
+        self.load_profile = Some(load_profile);
+        self
+    }
+    /// Periodically report the stats collected since the previous report, in addition to the
+    /// cumulative stats returned at the end of the run.
+    pub fn with_report_interval(mut self, interval: Duration, format: ReportFormat) -> BenchDriver {
+        self.report_interval = Some((interval, format));
+        self
+    }
+    /// Run the workload for `warmup` (a duration, or a number of transactions across all
+    /// workers) before collecting stats, so connection setup and cold caches don't skew them.
+    /// The warmup counts towards the run duration.
+    pub fn with_warmup(mut self, warmup: Interval) -> BenchDriver {
+        self.warmup = Some(warmup);
+        self
+    }
+    /// Search for the maximum sustainable throughput instead of running at a constant target
+    /// qps, which bounds the search. The run ends with the search, and each probe is reported
+    /// as a stage.
+    pub fn with_max_tps_search(mut self, search: MaxTpsSearch) -> BenchDriver {
+        self.max_tps_search = Some(search);
+        self
+    }
+    /// Inject the faults of `schedule` through `injector` as the run goes, and record when
+    /// they were injected in the stats and the periodic reports.
+    pub fn with_faults(
+        mut self,
+        schedule: FaultSchedule,
+        injector: Arc<FaultInjector>,
+    ) -> BenchDriver {
+        self.faults = Some((schedule, injector));
+        self
+    }
+    /// Once interrupted by SIGINT or SIGTERM, the workers stop submitting and wait up to
+    /// `timeout` for the transactions in flight before the stats collected so far are returned.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> BenchDriver {
+        self.shutdown_timeout = timeout;
+        self
+    }
+    /// Also follow the checkpoints of the target, and record the time from the submission of
+    /// each transaction until it appears in one. The target must expose its checkpoints.
+    pub fn with_checkpoint_latency(mut self) -> BenchDriver {
+        self.measure_checkpoint_latency = true;
+        self
+    }
+    /// Also write the throughput and latency of each second of the run, warmup included, as
+    /// CSV rows to `path` while the run goes.
+    pub fn with_timeseries(mut self, path: PathBuf) -> BenchDriver {
+        self.timeseries_path = Some(path);
+        self
+    }
+    /// Also record the p50, p95 and p99 latencies of each `interval` of the run, warmup
+    /// excluded, and return them as the `quantiles` of the final stats.
+    pub fn with_quantile_series(mut self, interval: Duration) -> BenchDriver {
+        self.quantile_interval = Some(interval);
+        self
+    }
+    /// Also export the counts and latencies sampled during the run, warmup excluded, to an
+    /// OpenTelemetry receiver every `config.export_interval`.
+    pub fn with_otlp(mut self, config: OtlpConfig) -> BenchDriver {
+        self.otlp = Some(config);
+        self
+    }
+    /// Record the latencies in histograms bounded by `config` rather than the default ones.
+    pub fn with_histogram_config(mut self, config: HistogramConfig) -> BenchDriver {
+        self.histogram_config = config;
+        self
+    }
     pub fn update_progress(
         start_time: Instant,
         interval: Interval,
@@ -172,12 +563,18 @@ impl BenchDriver {
                     progress_bar.finish_and_clear();
                 }
             }
+            Interval::CountOrTime(count, duration) => {
+                progress_bar.inc(1);
+                if progress_bar.position() >= count || Instant::now() - start_time >= duration {
+                    progress_bar.finish_and_clear();
+                }
+            }
         }
     }
     pub async fn make_workers(
         &self,
         workload_info: &WorkloadInfo,
-        aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: &dyn ValidatorProxy,
     ) -> Vec<BenchWorker> {
         let mut num_requests = workload_info.max_in_flight_ops / workload_info.num_workers;
         let mut target_qps = workload_info.target_qps / workload_info.num_workers;
@@ -193,7 +590,7 @@ impl BenchDriver {
                     target_qps,
                     payload: workload_info
                         .workload
-                        .make_test_payloads(num_requests, aggregator)
+                        .make_test_payloads(num_requests, proxy)
                         .await,
                 });
             }
@@ -207,22 +604,23 @@ impl Driver<BenchmarkStats> for BenchDriver {
     async fn run(
         &self,
         workloads: Vec<WorkloadInfo>,
-        aggregator: AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: Arc<dyn ValidatorProxy>,
         registry: &Registry,
         show_progress: bool,
         run_duration: Interval,
     ) -> Result<BenchmarkStats, anyhow::Error> {
-        let committee = Arc::new(aggregator.committee.clone());
         let mut tasks = Vec::new();
         let (tx, mut rx) = tokio::sync::mpsc::channel(100);
         let mut bench_workers = vec![];
         for workload in workloads.iter() {
-            bench_workers.extend(self.make_workers(workload, &aggregator).await);
+            bench_workers.extend(self.make_workers(workload, proxy.as_ref()).await);
         }
         let num_workers = bench_workers.len() as u64;
         if num_workers == 0 {
             return Err(anyhow!("No workers to run benchmark!"));
         }
+        let total_target_qps: u64 = workloads.iter().map(|w| w.target_qps).sum();
+        let histogram_config = self.histogram_config;
         let stat_delay_micros = 1_000_000 * self.stat_collection_interval;
         let metrics = Arc::new(BenchMetrics::new(registry));
         let barrier = Arc::new(Barrier::new(num_workers as usize));
@@ -239,19 +637,92 @@ impl Driver<BenchmarkStats> for BenchDriver {
                 .with_style(
                     ProgressStyle::with_template("{prefix}: {wide_bar} {pos}/{len}").unwrap(),
                 ),
+            // Progress is shown against the count, the duration may end the run before
+            Interval::CountOrTime(count, _) => ProgressBar::new(count)
+                .with_prefix("Running benchmark(count|duration):")
+                .with_style(
+                    ProgressStyle::with_template("{prefix}: {wide_bar} {pos}/{len}").unwrap(),
+                ),
+        });
+        // The stats task steers the workers through the probes of a search
+        let first_probe = self
+            .max_tps_search
+            .as_ref()
+            .and_then(|search| search.next_tps())
+            .map_or(Probe::Done, |tps| Probe::Running { index: 0, tps });
+        let (probe_tx, probe_rx) = watch::channel(first_probe);
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let shutdown_timeout = self.shutdown_timeout;
+        // The workers tell the checkpoint watcher when they submit a transaction
+        let (submitted_tx, submitted_rx) = mpsc::unbounded_channel();
+        let checkpoint_task = if self.measure_checkpoint_latency {
+            Some(tokio::spawn(watch_checkpoints(
+                num_workers as usize,
+                proxy.clone(),
+                submitted_rx,
+                tx.clone(),
+                Duration::from_micros(stat_delay_micros),
+                shutdown_timeout,
+                self.histogram_config,
+            )))
+        } else {
+            drop(submitted_rx);
+            None
+        };
+        let timeseries = match &self.timeseries_path {
+            Some(path) => Some(Arc::new(TimeSeries::create(path)?)),
+            None => None,
+        };
+        let timeseries_task = timeseries.clone().map(TimeSeries::spawn_writer);
+        let otlp = match &self.otlp {
+            Some(config) => Some(Arc::new(OtlpExporter::new(
+                config.clone(),
+                self.histogram_config,
+            )?)),
+            None => None,
+        };
+        let otlp_task = otlp.clone().map(OtlpExporter::spawn_exporter);
+        let shutdown_task = tokio::spawn(async move {
+            shutdown_signal().await;
+            eprintln!(
+                "Interrupted, waiting up to {}s for the transactions in flight, interrupt again to exit now",
+                shutdown_timeout.as_secs()
+            );
+            let _ = shutdown_tx.send(true);
+            shutdown_signal().await;
+            std::process::exit(130);
         });
         for (i, worker) in bench_workers.into_iter().enumerate() {
-            let committee = committee.clone();
-            let request_delay_micros = 1_000_000 / worker.target_qps;
+            let load_profile = self.load_profile.clone();
+            // Each worker warms up on its share of a transaction count
+            let warmup = self.warmup.map(|warmup| match warmup {
+                Interval::Count(count) => Interval::Count((count + num_workers - 1) / num_workers),
+                Interval::Time(duration) => Interval::Time(duration),
+                Interval::CountOrTime(count, duration) => {
+                    Interval::CountOrTime((count + num_workers - 1) / num_workers, duration)
+                }
+            });
+            let probes = self.max_tps_search.as_ref().map(|_| probe_rx.clone());
+            let worker_share = worker.target_qps as f64 / total_target_qps as f64;
+            let worker_qps = move |tps: u64| (tps as f64 * worker_share).round() as u64;
+            let (initial_stage, mut current_qps) = match (&load_profile, &probes) {
+                (Some(profile), _) => (Some(0), worker_qps(profile.tps_at(Duration::ZERO))),
+                (None, Some(probes)) => {
+                    let (stage, tps) = probes.borrow().stage_and_tps(false);
+                    (stage, worker_qps(tps))
+                }
+                (None, None) => (None, worker.target_qps),
+            };
+            let request_delay_micros = 1_000_000 / std::cmp::max(current_qps, 1);
             let mut free_pool = worker.payload;
             let progress = progress.clone();
             let tx_cloned = tx.clone();
             let cloned_barrier = barrier.clone();
             let metrics_cloned = metrics.clone();
-            // Make a per worker quorum driver, otherwise they all share the same task.
-            let quorum_driver_handler =
-                QuorumDriverHandler::new(aggregator.clone(), QuorumDriverMetrics::new_for_tests());
-            let qd = quorum_driver_handler.clone_quorum_driver();
+            let proxy: Arc<dyn ValidatorProxy> = Arc::from(proxy.clone_new());
+            let mut shutdown = shutdown_rx.clone();
+            let submitted_tx = submitted_tx.clone();
+            let timeseries = timeseries.clone();
             let runner = tokio::spawn(async move {
                 cloned_barrier.wait().await;
                 let start_time = print_and_start_benchmark().await;
@@ -260,19 +731,65 @@ impl Driver<BenchmarkStats> for BenchDriver {
                 let mut num_no_gas = 0;
                 let mut num_in_flight: u64 = 0;
                 let mut num_submitted = 0;
-                let mut latency_histogram =
-                    hdrhistogram::Histogram::<u64>::new_with_max(100000, 2).unwrap();
+                let mut latency_histogram = histogram_config.new_histogram();
+                let mut submit_to_cert_histogram = latency_histogram.clone();
+                let mut cert_to_effects_histogram = latency_histogram.clone();
+                let mut response_time_histogram = latency_histogram.clone();
+                let mut workload_stats: HashMap<WorkloadType, BenchmarkStats> = HashMap::new();
+                let mut scheme_stats: HashMap<KeyScheme, BenchmarkStats> = HashMap::new();
                 let mut request_interval =
                     time::interval(Duration::from_micros(request_delay_micros));
                 request_interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+                // When the next transaction is due at the target rate. It only moves forward as
+                // transactions are submitted, so it falls behind while the driver is held back
+                let mut request_period = Duration::from_micros(request_delay_micros);
+                let mut next_intended_start = Instant::now();
                 let mut stat_interval = time::interval(Duration::from_micros(stat_delay_micros));
+                let mut profile_interval = time::interval(LOAD_PROFILE_UPDATE_INTERVAL);
+                let mut stage = initial_stage;
+                let mut warming_up = warmup.is_some();
+                let mut warmup_completed: u64 = 0;
                 let mut futures: FuturesUnordered<BoxFuture<NextOp>> = FuturesUnordered::new();
 
                 let mut retry_queue: VecDeque<RetryType> = VecDeque::new();
                 let mut stat_start_time: Instant = Instant::now();
+                // Set once interrupted, no more transactions are submitted
+                let mut drain_deadline: Option<Instant> = None;
                 loop {
+                    if warming_up {
+                        let warmup_over = match warmup {
+                            Some(Interval::Count(count)) => warmup_completed >= count,
+                            Some(Interval::Time(duration)) => start_time.elapsed() >= duration,
+                            Some(Interval::CountOrTime(count, duration)) => {
+                                warmup_completed >= count || start_time.elapsed() >= duration
+                            }
+                            None => true,
+                        };
+                        if warmup_over {
+                            // Start the first accounted window from scratch
+                            warming_up = false;
+                            num_success = 0;
+                            num_error = 0;
+                            num_no_gas = 0;
+                            num_submitted = 0;
+                            stat_start_time = Instant::now();
+                            latency_histogram.reset();
+                            submit_to_cert_histogram.reset();
+                            cert_to_effects_histogram.reset();
+                            response_time_histogram.reset();
+                            workload_stats.clear();
+                            scheme_stats.clear();
+                        }
+                    }
                     tokio::select! {
-                            _ = tokio::signal::ctrl_c() => {
+                            Ok(()) = shutdown.changed(), if drain_deadline.is_none() => {
+                                if futures.is_empty() {
+                                    break;
+                                }
+                                drain_deadline = Some(Instant::now() + shutdown_timeout);
+                            }
+                            _ = time::sleep_until(drain_deadline.unwrap_or_else(Instant::now)), if drain_deadline.is_some() => {
+                                // Give up on the transactions still in flight
                                 break;
                             }
                             _ = stat_interval.tick() => {
@@ -282,12 +799,21 @@ impl Driver<BenchmarkStats> for BenchDriver {
                                         num_no_gas,
                                         num_in_flight,
                                         num_submitted,
+                                        stage,
+                                        warmup: warming_up,
+                                        stage_over: false,
                                         bench_stats: BenchmarkStats {
                                             duration: stat_start_time.elapsed(),
                                             num_error,
                                             num_success,
                                             latency_ms: HistogramWrapper {histogram: latency_histogram.clone()},
+                                            response_time_ms: HistogramWrapper {histogram: response_time_histogram.clone()},
+                                            submit_to_cert_latency_ms: HistogramWrapper {histogram: submit_to_cert_histogram.clone()},
+                                            cert_to_effects_latency_ms: HistogramWrapper {histogram: cert_to_effects_histogram.clone()},
+                                            ..empty_benchmark_stats(histogram_config)
                                         },
+                                        workload_stats: take_keyed_stats(&mut workload_stats, stat_start_time.elapsed()),
+                                        scheme_stats: take_keyed_stats(&mut scheme_stats, stat_start_time.elapsed()),
                                     })
                                     .is_err()
                                 {
@@ -299,50 +825,131 @@ impl Driver<BenchmarkStats> for BenchDriver {
                                 num_submitted = 0;
                                 stat_start_time = Instant::now();
                                 latency_histogram.reset();
+                                submit_to_cert_histogram.reset();
+                                cert_to_effects_histogram.reset();
+                                response_time_histogram.reset();
                         }
-                        _ = request_interval.tick() => {
+                        _ = profile_interval.tick(), if load_profile.is_some() || probes.is_some() => {
+                            let (new_stage, tps, over) = match &load_profile {
+                                Some(profile) => {
+                                    let elapsed = start_time.elapsed();
+                                    let new_stage = profile.stage_at(elapsed).map(|(index, _)| index);
+                                    (new_stage, profile.tps_at(elapsed), new_stage.is_none())
+                                }
+                                None => {
+                                    let probe = *probes.as_ref().unwrap().borrow();
+                                    let (new_stage, tps) = probe.stage_and_tps(!futures.is_empty());
+                                    (new_stage, tps, probe == Probe::Done)
+                                }
+                            };
+                            if new_stage != stage {
+                                // Flush the current window so that every sample is
+                                // attributed to exactly one stage. A search waits for the
+                                // last sample of each worker, so it must not be dropped
+                                if tx_cloned
+                                    .send(Stats {
+                                        id: i as usize,
+                                        num_no_gas,
+                                        num_in_flight,
+                                        num_submitted,
+                                        stage,
+                                        warmup: warming_up,
+                                        stage_over: true,
+                                        bench_stats: BenchmarkStats {
+                                            duration: stat_start_time.elapsed(),
+                                            num_error,
+                                            num_success,
+                                            latency_ms: HistogramWrapper {histogram: latency_histogram.clone()},
+                                            response_time_ms: HistogramWrapper {histogram: response_time_histogram.clone()},
+                                            submit_to_cert_latency_ms: HistogramWrapper {histogram: submit_to_cert_histogram.clone()},
+                                            cert_to_effects_latency_ms: HistogramWrapper {histogram: cert_to_effects_histogram.clone()},
+                                            ..empty_benchmark_stats(histogram_config)
+                                        },
+                                        workload_stats: take_keyed_stats(&mut workload_stats, stat_start_time.elapsed()),
+                                        scheme_stats: take_keyed_stats(&mut scheme_stats, stat_start_time.elapsed()),
+                                    })
+                                    .await
+                                    .is_err()
+                                {
+                                    debug!("Failed to update stat!");
+                                }
+                                num_success = 0;
+                                num_error = 0;
+                                num_no_gas = 0;
+                                num_submitted = 0;
+                                stat_start_time = Instant::now();
+                                latency_histogram.reset();
+                                submit_to_cert_histogram.reset();
+                                cert_to_effects_histogram.reset();
+                                response_time_histogram.reset();
+                                stage = new_stage;
+                            }
+                            if over && futures.is_empty() {
+                                // The profile or the search is over and everything in flight has landed
+                                break;
+                            }
+                            let qps = worker_qps(tps);
+                            if qps != current_qps {
+                                current_qps = qps;
+                                if qps > 0 {
+                                    request_period = Duration::from_micros(1_000_000 / qps);
+                                    request_interval = time::interval(request_period);
+                                    request_interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+                                    next_intended_start = Instant::now();
+                                }
+                            }
+                        }
+                        _ = request_interval.tick(), if drain_deadline.is_none() => {
+                            if current_qps == 0 {
+                                // Idle while the load profile asks for no traffic
+                                continue
+                            }
 
                             // If a retry is available send that
                             // (sending retries here subjects them to our rate limit)
                             if let Some(b) = retry_queue.pop_front() {
                                 num_error += 1;
                                 num_submitted += 1;
+                                workload_stats.entry(b.1.get_workload_type()).or_insert_with(|| empty_benchmark_stats(histogram_config)).num_error += 1;
+                                scheme_stats.entry(b.1.get_key_scheme()).or_insert_with(|| empty_benchmark_stats(histogram_config)).num_error += 1;
                                 metrics_cloned.num_submitted.with_label_values(&[&b.1.get_workload_type().to_string()]).inc();
+                                if let Some(timeseries) = &timeseries {
+                                    timeseries.record_submitted();
+                                }
                                 let metrics_cloned = metrics_cloned.clone();
-                                let committee_cloned = committee.clone();
                                 let start = Instant::now();
-                                let res = qd
-                                    .execute_transaction(ExecuteTransactionRequest {
-                                        transaction: b.0.clone(),
-                                        request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
-                                    })
+                                let intended_start = next_intended_start;
+                                next_intended_start += request_period;
+                                let proxy_cloned = proxy.clone();
+                                let tx = b.0.clone();
+                                let res = async move { proxy_cloned.execute_transaction(tx).await }
                                     .map(move |res| {
                                         match res {
-                                            Ok(ExecuteTransactionResponse::EffectsCert(result)) => {
-                                                let (cert, effects) = *result;
-                                                let new_version = effects.effects.mutated.iter().find(|(object_ref, _)| {
+                                            Ok(executed) => {
+                                                let new_version = executed.effects.mutated.iter().find(|(object_ref, _)| {
                                                     object_ref.0 == b.1.get_object_id()
                                                 }).map(|x| x.0).unwrap();
                                                 let latency = start.elapsed();
                                                 metrics_cloned.latency_s.with_label_values(&[&b.1.get_workload_type().to_string()]).observe(latency.as_secs_f64());
                                                 metrics_cloned.num_success.with_label_values(&[&b.1.get_workload_type().to_string()]).inc();
                                                 metrics_cloned.num_in_flight.with_label_values(&[&b.1.get_workload_type().to_string()]).dec();
-                                                cert.auth_sign_info.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_tx_cert.with_label_values(&[&name.unwrap().to_string()]).inc());
-                                                effects.auth_signature.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.unwrap().to_string()]).inc());
+                                                executed.tx_cert_signers.iter().for_each(|name| metrics_cloned.validators_in_tx_cert.with_label_values(&[&name.to_string()]).inc());
+                                                executed.effects_cert_signers.iter().for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.to_string()]).inc());
                                                 NextOp::Response(Some((
-                                                    latency,
-                                                    b.1.make_new_payload(new_version, effects.effects.gas_object.0),
+                                                    TransactionLatency {
+                                                        total: latency,
+                                                        response: intended_start.elapsed(),
+                                                        submit_to_cert: executed.submit_to_cert,
+                                                    },
+                                                    b.1.get_workload_type(),
+                                                    b.1.get_key_scheme(),
+                                                    b.1.make_new_payload(new_version, executed.effects.gas_object.0),
                                                 ),
                                                 ))
                                             }
-                                            Ok(resp) => {
-                                                error!("unexpected_response: {:?}", resp);
-                                                metrics_cloned.num_error.with_label_values(&[&b.1.get_workload_type().to_string(), "unknown_error"]).inc();
-                                                NextOp::Retry(b)
-                                            }
-                                            Err(sui_err) => {
-                                                error!("{}", sui_err);
-                                                metrics_cloned.num_error.with_label_values(&[&b.1.get_workload_type().to_string(), &sui_err.to_string()]).inc();
+                                            Err(err) => {
+                                                error!("{}", err);
+                                                metrics_cloned.num_error.with_label_values(&[&b.1.get_workload_type().to_string(), &err.to_string()]).inc();
                                                 NextOp::Retry(b)
                                             }
                                         }
@@ -360,41 +967,47 @@ impl Driver<BenchmarkStats> for BenchDriver {
                                 num_submitted += 1;
                                 metrics_cloned.num_in_flight.with_label_values(&[&payload.get_workload_type().to_string()]).inc();
                                 metrics_cloned.num_submitted.with_label_values(&[&payload.get_workload_type().to_string()]).inc();
+                                if let Some(timeseries) = &timeseries {
+                                    timeseries.record_submitted();
+                                }
                                 let tx = payload.make_transaction();
                                 let start = Instant::now();
+                                let intended_start = next_intended_start;
+                                next_intended_start += request_period;
+                                if !warming_up {
+                                    // Fails if the checkpoint latency isn't measured, on purpose
+                                    let _ = submitted_tx.send((*tx.digest(), start, payload.get_workload_type()));
+                                }
                                 let metrics_cloned = metrics_cloned.clone();
-                                let committee_cloned = committee.clone();
-                                let res = qd
-                                    .execute_transaction(ExecuteTransactionRequest {
-                                        transaction: tx.clone(),
-                                    request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
-                                })
+                                let proxy_cloned = proxy.clone();
+                                let tx_cloned = tx.clone();
+                                let res = async move { proxy_cloned.execute_transaction(tx_cloned).await }
                                 .map(move |res| {
                                     match res {
-                                        Ok(ExecuteTransactionResponse::EffectsCert(result)) => {
-                                            let (cert, effects) = *result;
-                                            let new_version = effects.effects.mutated.iter().find(|(object_ref, _)| {
+                                        Ok(executed) => {
+                                            let new_version = executed.effects.mutated.iter().find(|(object_ref, _)| {
                                                 object_ref.0 == payload.get_object_id()
                                             }).map(|x| x.0).unwrap();
                                             let latency = start.elapsed();
                                             metrics_cloned.latency_s.with_label_values(&[&payload.get_workload_type().to_string()]).observe(latency.as_secs_f64());
                                             metrics_cloned.num_success.with_label_values(&[&payload.get_workload_type().to_string()]).inc();
                                             metrics_cloned.num_in_flight.with_label_values(&[&payload.get_workload_type().to_string()]).dec();
-                                            cert.auth_sign_info.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_tx_cert.with_label_values(&[&name.unwrap().to_string()]).inc());
-                                            effects.auth_signature.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.unwrap().to_string()]).inc());
+                                            executed.tx_cert_signers.iter().for_each(|name| metrics_cloned.validators_in_tx_cert.with_label_values(&[&name.to_string()]).inc());
+                                            executed.effects_cert_signers.iter().for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.to_string()]).inc());
                                             NextOp::Response(Some((
-                                                latency,
-                                                payload.make_new_payload(new_version, effects.effects.gas_object.0),
+                                                TransactionLatency {
+                                                    total: latency,
+                                                    response: intended_start.elapsed(),
+                                                    submit_to_cert: executed.submit_to_cert,
+                                                },
+                                                payload.get_workload_type(),
+                                                payload.get_key_scheme(),
+                                                payload.make_new_payload(new_version, executed.effects.gas_object.0),
                                             )))
                                         }
-                                        Ok(resp) => {
-                                            error!("unexpected_response: {:?}", resp);
-                                            metrics_cloned.num_error.with_label_values(&[&payload.get_workload_type().to_string(), "unknown_error"]).inc();
-                                            NextOp::Retry(Box::new((tx, payload)))
-                                        }
-                                        Err(sui_err) => {
-                                            error!("Retry due to error: {}", sui_err);
-                                            metrics_cloned.num_error.with_label_values(&[&payload.get_workload_type().to_string(), &sui_err.to_string()]).inc();
+                                        Err(err) => {
+                                            error!("Retry due to error: {}", err);
+                                            metrics_cloned.num_error.with_label_values(&[&payload.get_workload_type().to_string(), &err.to_string()]).inc();
                                             NextOp::Retry(Box::new((tx, payload)))
                                         }
                                     }
@@ -405,17 +1018,49 @@ impl Driver<BenchmarkStats> for BenchDriver {
                         Some(op) = futures.next() => {
                             match op {
                                 NextOp::Retry(b) => {
+                                    if let Some(timeseries) = &timeseries {
+                                        timeseries.record_failure();
+                                    }
                                     retry_queue.push_back(b);
                                     BenchDriver::update_progress(*start_time, run_duration, progress.clone());
                                     if progress.is_finished() {
                                         break;
                                     }
                                 }
-                                NextOp::Response(Some((latency, new_payload))) => {
+                                NextOp::Response(Some((latency, workload_type, key_scheme, new_payload))) => {
+                                    warmup_completed += 1;
                                     num_success += 1;
                                     num_in_flight -= 1;
                                     free_pool.push(new_payload);
-                                    latency_histogram.record(latency.as_millis().try_into().unwrap()).unwrap();
+                                    if let Some(timeseries) = &timeseries {
+                                        timeseries.record_success(latency.total);
+                                    }
+                                    let total_ms = latency.total.as_millis().try_into().unwrap();
+                                    // Latencies above the maximum of the histograms are recorded as the maximum
+                                    latency_histogram.saturating_record(total_ms);
+                                    let response_ms = latency.response.as_millis().try_into().unwrap();
+                                    response_time_histogram.saturating_record(response_ms);
+                                    // The phases are only timed when the target exposes the certificate
+                                    let phases_ms = latency.submit_to_cert.map(|submit_to_cert| {
+                                        let submit_to_cert_ms: u64 = submit_to_cert.as_millis().try_into().unwrap();
+                                        (submit_to_cert_ms, total_ms.saturating_sub(submit_to_cert_ms))
+                                    });
+                                    if let Some((submit_to_cert_ms, cert_to_effects_ms)) = phases_ms {
+                                        submit_to_cert_histogram.saturating_record(submit_to_cert_ms);
+                                        cert_to_effects_histogram.saturating_record(cert_to_effects_ms);
+                                    }
+                                    for stats in [
+                                        workload_stats.entry(workload_type).or_insert_with(|| empty_benchmark_stats(histogram_config)),
+                                        scheme_stats.entry(key_scheme).or_insert_with(|| empty_benchmark_stats(histogram_config)),
+                                    ] {
+                                        stats.num_success += 1;
+                                        stats.latency_ms.histogram.saturating_record(total_ms);
+                                        stats.response_time_ms.histogram.saturating_record(response_ms);
+                                        if let Some((submit_to_cert_ms, cert_to_effects_ms)) = phases_ms {
+                                            stats.submit_to_cert_latency_ms.histogram.saturating_record(submit_to_cert_ms);
+                                            stats.cert_to_effects_latency_ms.histogram.saturating_record(cert_to_effects_ms);
+                                        }
+                                    }
                                     BenchDriver::update_progress(*start_time, run_duration, progress.clone());
                                     if progress.is_finished() {
                                         break;
@@ -426,16 +1071,22 @@ impl Driver<BenchmarkStats> for BenchDriver {
                                     unreachable!();
                                 }
                             }
+                            if drain_deadline.is_some() && futures.is_empty() {
+                                break;
+                            }
                         }
                     }
                 }
-                // send stats one last time
+                // send stats one last time, waiting for room so that they are not lost
                 if tx_cloned
-                    .try_send(Stats {
+                    .send(Stats {
                         id: i as usize,
                         num_no_gas,
                         num_in_flight,
                         num_submitted,
+                        stage,
+                        warmup: warming_up,
+                        stage_over: false,
                         bench_stats: BenchmarkStats {
                             duration: stat_start_time.elapsed(),
                             num_error,
@@ -443,8 +1094,27 @@ impl Driver<BenchmarkStats> for BenchDriver {
                             latency_ms: HistogramWrapper {
                                 histogram: latency_histogram,
                             },
+                            response_time_ms: HistogramWrapper {
+                                histogram: response_time_histogram,
+                            },
+                            submit_to_cert_latency_ms: HistogramWrapper {
+                                histogram: submit_to_cert_histogram,
+                            },
+                            cert_to_effects_latency_ms: HistogramWrapper {
+                                histogram: cert_to_effects_histogram,
+                            },
+                            ..empty_benchmark_stats(histogram_config)
                         },
+                        workload_stats: take_keyed_stats(
+                            &mut workload_stats,
+                            stat_start_time.elapsed(),
+                        ),
+                        scheme_stats: take_keyed_stats(
+                            &mut scheme_stats,
+                            stat_start_time.elapsed(),
+                        ),
                     })
+                    .await
                     .is_err()
                 {
                     debug!("Failed to update stat!");
@@ -453,42 +1123,210 @@ impl Driver<BenchmarkStats> for BenchDriver {
             tasks.push(runner);
         }
 
+        let stage_profile = self.load_profile.clone();
+        let faults = self.faults.clone();
+        let report = self.report_interval;
+        let quantile_interval = self.quantile_interval;
+        let warmup = self.warmup;
+        let mut search = self.max_tps_search.clone();
+        let otlp_recorder = otlp.clone();
+        drop(probe_rx);
         let stat_task = tokio::spawn(async move {
-            let mut benchmark_stat = BenchmarkStats {
-                duration: Duration::ZERO,
-                num_error: 0,
-                num_success: 0,
-                latency_ms: HistogramWrapper {
-                    histogram: hdrhistogram::Histogram::<u64>::new_with_max(100000, 2).unwrap(),
-                },
-            };
+            let mut benchmark_stat = empty_benchmark_stats(histogram_config);
+            let mut stage_stats: BTreeMap<usize, BenchmarkStats> = BTreeMap::new();
+            let mut workload_stats: BTreeMap<WorkloadType, BenchmarkStats> = BTreeMap::new();
+            let mut scheme_stats: BTreeMap<KeyScheme, BenchmarkStats> = BTreeMap::new();
             let mut stat_collection: BTreeMap<usize, Stats> = BTreeMap::new();
             let mut counter = 0;
             let mut stat;
             let start = Instant::now();
-            while let Some(
-                sample_stat @ Stats {
-                    id,
-                    num_no_gas: _,
-                    num_in_flight: _,
-                    num_submitted: _,
-                    bench_stats: _,
-                },
-            ) = rx.recv().await
-            {
-                benchmark_stat.update(start.elapsed(), &sample_stat.bench_stats);
+            let mut window_stat = empty_benchmark_stats(histogram_config);
+            let mut window_start = start;
+            // Start of the accounted part of the run, once warmup is over
+            let mut measure_start: Option<Instant> = None;
+            // Only polled when reporting is enabled
+            let report_period = report
+                .map(|(period, _)| period)
+                .unwrap_or(Duration::from_secs(1));
+            let mut report_interval = time::interval_at(start + report_period, report_period);
+            // Only polled when a quantile series is recorded
+            let quantile_period = quantile_interval.unwrap_or(Duration::from_secs(1));
+            let mut quantile_tick = time::interval_at(start + quantile_period, quantile_period);
+            let mut quantile_histogram = histogram_config.new_histogram();
+            let mut quantiles: Vec<QuantilePoint> = vec![];
+            // Only polled when searching
+            let step = search
+                .as_ref()
+                .map(|search| search.step_duration)
+                .unwrap_or(Duration::from_secs(1));
+            let mut probe = first_probe;
+            let mut probe_tps: Vec<u64> = vec![];
+            if let Probe::Running { tps, .. } = probe {
+                probe_tps.push(tps);
+            }
+            let mut probe_start = start;
+            let mut workers_drained: u64 = 0;
+            let probe_end = time::sleep_until(start + step);
+            tokio::pin!(probe_end);
+            let mut pending_faults = faults
+                .as_ref()
+                .map(|(schedule, _)| schedule.faults.iter().peekable());
+            let next_fault = time::sleep_until(
+                start
+                    + pending_faults
+                        .as_mut()
+                        .and_then(|faults| faults.peek())
+                        .map_or(Duration::ZERO, |fault| fault.at),
+            );
+            tokio::pin!(next_fault);
+            loop {
+                let sample_stat = tokio::select! {
+                    _ = &mut next_fault, if pending_faults.as_mut().and_then(|faults| faults.peek()).is_some() => {
+                        let (_, injector) = faults.as_ref().unwrap();
+                        let pending = pending_faults.as_mut().unwrap();
+                        let fault = pending.next().unwrap();
+                        injector.inject(fault);
+                        eprintln!("Injected fault {}", fault);
+                        let event = FaultEvent {
+                            elapsed: start.elapsed(),
+                            fault: fault.to_string(),
+                        };
+                        window_stat.faults.push(event.clone());
+                        benchmark_stat.faults.push(event);
+                        if let Some(fault) = pending.peek() {
+                            next_fault.as_mut().reset(start + fault.at);
+                        }
+                        continue;
+                    }
+                    _ = &mut probe_end, if matches!(probe, Probe::Running { .. }) => {
+                        if let Probe::Running { index, .. } = probe {
+                            probe = Probe::Draining { index };
+                            let _ = probe_tx.send(probe);
+                        }
+                        continue;
+                    }
+                    _ = report_interval.tick(), if report.is_some() => {
+                        let (_, format) = report.unwrap();
+                        window_stat.duration = window_start.elapsed();
+                        report_window(&window_stat, start.elapsed(), format);
+                        window_stat = empty_benchmark_stats(histogram_config);
+                        window_start = Instant::now();
+                        continue;
+                    }
+                    _ = quantile_tick.tick(), if quantile_interval.is_some() => {
+                        if !quantile_histogram.is_empty() {
+                            quantiles.push(QuantilePoint::new(start.elapsed(), &quantile_histogram));
+                            quantile_histogram.reset();
+                        }
+                        continue;
+                    }
+                    sample = rx.recv() => match sample {
+                        Some(sample) => sample,
+                        None => break,
+                    },
+                };
+                if sample_stat.warmup {
+                    continue;
+                }
+                let id = sample_stat.id;
+                let stage = sample_stat.stage;
+                // The checkpoint watcher reports as an extra worker, with latencies only
+                let from_watcher = id == num_workers as usize;
+                if warmup.is_some() && !from_watcher {
+                    // Workers finish warming up at slightly different times, account from the
+                    // earliest accounted window
+                    let sample_start = Instant::now() - sample_stat.bench_stats.duration;
+                    measure_start =
+                        Some(measure_start.map_or(sample_start, |s| s.min(sample_start)));
+                }
+                let measured = measure_start.unwrap_or(start).elapsed();
+                benchmark_stat.update(measured, &sample_stat.bench_stats);
+                if let Some(otlp) = &otlp_recorder {
+                    otlp.record(&sample_stat.bench_stats);
+                }
+                window_stat.update(window_start.elapsed(), &sample_stat.bench_stats);
+                if quantile_interval.is_some() {
+                    quantile_histogram
+                        .add(&sample_stat.bench_stats.latency_ms.histogram)
+                        .unwrap();
+                }
+                for (workload_type, stats) in &sample_stat.workload_stats {
+                    workload_stats
+                        .entry(*workload_type)
+                        .or_insert_with(|| empty_benchmark_stats(histogram_config))
+                        .update(measured, stats);
+                }
+                for (key_scheme, stats) in &sample_stat.scheme_stats {
+                    scheme_stats
+                        .entry(*key_scheme)
+                        .or_insert_with(|| empty_benchmark_stats(histogram_config))
+                        .update(measured, stats);
+                }
+                if let Some(stage) = stage {
+                    let stage_duration = match &stage_profile {
+                        Some(profile) => profile.stage_elapsed(stage, start.elapsed()),
+                        None => std::cmp::min(probe_start.elapsed(), step),
+                    };
+                    stage_stats
+                        .entry(stage)
+                        .or_insert_with(|| empty_benchmark_stats(histogram_config))
+                        .update(stage_duration, &sample_stat.bench_stats);
+                }
+                if let Probe::Draining { index } = probe {
+                    if sample_stat.stage_over && stage == Some(index) {
+                        workers_drained += 1;
+                    }
+                    if workers_drained == num_workers {
+                        // Every worker is done with the probe, pick the next one from its stats
+                        workers_drained = 0;
+                        let search = search.as_mut().unwrap();
+                        let tps = probe_tps[index];
+                        let stats = stage_stats
+                            .entry(index)
+                            .or_insert_with(|| empty_benchmark_stats(histogram_config));
+                        stats.duration = std::cmp::min(probe_start.elapsed(), step);
+                        let sustained = search.record(tps, stats);
+                        eprintln!(
+                            "Probe {} at {} tps: {}, tps = {}, p99 = {}ms, num_error = {}",
+                            index + 1,
+                            tps,
+                            if sustained {
+                                "sustained"
+                            } else {
+                                "not sustained"
+                            },
+                            stats.tps().round(),
+                            stats.latency_ms.histogram.value_at_quantile(0.99),
+                            stats.num_error,
+                        );
+                        probe = match search.next_tps() {
+                            Some(tps) => {
+                                probe_tps.push(tps);
+                                probe_start = Instant::now();
+                                probe_end.as_mut().reset(probe_start + step);
+                                Probe::Running {
+                                    index: index + 1,
+                                    tps,
+                                }
+                            }
+                            None => Probe::Done,
+                        };
+                        let _ = probe_tx.send(probe);
+                    }
+                }
+                if from_watcher {
+                    continue;
+                }
                 stat_collection.insert(id, sample_stat);
                 let mut total_qps: f32 = 0.0;
                 let mut num_success: u64 = 0;
                 let mut num_error: u64 = 0;
-                let mut latency_histogram =
-                    hdrhistogram::Histogram::<u64>::new_with_max(100000, 2).unwrap();
+                let mut latency_histogram = histogram_config.new_histogram();
                 let mut num_in_flight: u64 = 0;
                 let mut num_submitted: u64 = 0;
                 let mut num_no_gas = 0;
                 for (_, v) in stat_collection.iter() {
-                    total_qps +=
-                        v.bench_stats.num_success as f32 / v.bench_stats.duration.as_secs() as f32;
+                    total_qps += v.bench_stats.tps() as f32;
                     num_success += v.bench_stats.num_success;
                     num_error += v.bench_stats.num_error;
                     num_no_gas += v.num_no_gas;
@@ -512,11 +1350,60 @@ impl Driver<BenchmarkStats> for BenchDriver {
                     }
                 }
             }
+            if let Some(profile) = &stage_profile {
+                benchmark_stat.stages = stage_stats
+                    .into_iter()
+                    .map(|(index, stats)| StageStats {
+                        stage: profile.stages[index].to_string(),
+                        stats,
+                    })
+                    .collect();
+            } else if let Some(search) = &search {
+                benchmark_stat.stages = stage_stats
+                    .into_iter()
+                    .map(|(index, stats)| StageStats {
+                        stage: format!("probe:{}tps", probe_tps[index]),
+                        stats,
+                    })
+                    .collect();
+                benchmark_stat.max_sustainable_tps = Some(search.max_sustainable_tps());
+            }
+            benchmark_stat.workloads = workload_stats
+                .into_iter()
+                .map(|(workload_type, stats)| WorkloadStats {
+                    workload: workload_type.to_string(),
+                    stats,
+                })
+                .collect();
+            benchmark_stat.schemes = scheme_stats
+                .into_iter()
+                .map(|(key_scheme, stats)| SchemeStats {
+                    scheme: key_scheme.to_string(),
+                    stats,
+                })
+                .collect();
+            if !quantile_histogram.is_empty() {
+                quantiles.push(QuantilePoint::new(start.elapsed(), &quantile_histogram));
+            }
+            benchmark_stat.quantiles = quantiles;
             benchmark_stat
         });
         drop(tx);
+        drop(submitted_tx);
+        drop(shutdown_rx);
         let _res: Vec<_> = try_join_all(tasks).await.unwrap().into_iter().collect();
+        if let Some(checkpoint_task) = checkpoint_task {
+            checkpoint_task.await.unwrap();
+        }
         let benchmark_stat = stat_task.await.unwrap();
+        shutdown_task.abort();
+        if let (Some(timeseries), Some(task)) = (timeseries, timeseries_task) {
+            task.abort();
+            timeseries.finish()?;
+        }
+        if let (Some(otlp), Some(task)) = (otlp, otlp_task) {
+            otlp.finish(task).await;
+        }
         Ok(benchmark_stat)
     }
 }
@@ -0,0 +1,182 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coordination of a benchmark spread over several stress clients, for loads a single client
+//! can't generate. A leader accepts one connection per worker, hands each worker a shard of
+//! the load, starts them all at once when they are ready, and merges the stats they send back.
+//!
+//! Messages are JSON encoded and framed by a big endian u32 length over plain TCP.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::net::SocketAddr;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::BenchmarkStats;
+use super::Interval;
+
+/// Upper bound on the size of a message, serialized stats carry a few histograms.
+const MAX_MESSAGE_SIZE: usize = 64 << 20;
+
+/// The load a leader splits across its workers.
+#[derive(Debug, Clone, Copy)]
+pub struct ShardPlan {
+    pub target_qps: u64,
+    pub num_workers: u64,
+    pub run_duration: Interval,
+}
+
+/// The part of a [`ShardPlan`] run by a single stress client.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WorkloadShard {
+    /// Index of this shard, can be used to pick resources disjoint from other shards'.
+    pub index: usize,
+    pub num_shards: usize,
+    pub target_qps: u64,
+    pub num_workers: u64,
+    pub run_duration: Interval,
+}
+
+impl ShardPlan {
+    /// Splits the plan in `num_shards` shards, the first shards take the rounding leftovers.
+    pub fn shards(&self, num_shards: usize) -> Vec<WorkloadShard> {
+        let n = num_shards as u64;
+        (0..num_shards)
+            .map(|index| {
+                let extra_qps = u64::from((index as u64) < self.target_qps % n);
+                WorkloadShard {
+                    index,
+                    num_shards,
+                    target_qps: self.target_qps / n + extra_qps,
+                    num_workers: std::cmp::max(1, (self.num_workers + n - 1) / n),
+                    run_duration: self.run_duration,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum LeaderMessage {
+    Assign(WorkloadShard),
+    Start,
+}
+
+/// Generic over the stats so that workers can send them by reference.
+#[derive(Serialize, Deserialize)]
+enum WorkerMessage<S> {
+    Ready,
+    Stats(S),
+}
+
+async fn send<T: Serialize>(stream: &mut TcpStream, message: &T) -> Result<()> {
+    let bytes = serde_json::to_vec(message)?;
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        bail!("Message of {} bytes is too large to send", bytes.len());
+    }
+    stream.write_u32(bytes.len() as u32).await?;
+    stream.write_all(&bytes).await?;
+    Ok(())
+}
+
+async fn receive<T: DeserializeOwned>(stream: &mut TcpStream) -> Result<T> {
+    let len = stream.read_u32().await? as usize;
+    if len > MAX_MESSAGE_SIZE {
+        bail!("Refusing to receive a message of {} bytes", len);
+    }
+    let mut bytes = vec![0; len];
+    stream.read_exact(&mut bytes).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Runs the leader of a distributed benchmark: waits for `num_shards` workers to connect on
+/// `address`, runs `plan` across them and returns their merged stats.
+pub async fn lead(
+    address: SocketAddr,
+    num_shards: usize,
+    plan: ShardPlan,
+) -> Result<BenchmarkStats> {
+    if num_shards == 0 {
+        bail!("A distributed benchmark needs at least one worker");
+    }
+    let listener = TcpListener::bind(address).await?;
+    eprintln!("Waiting for {num_shards} stress workers on {address}..");
+    let mut workers = Vec::with_capacity(num_shards);
+    for shard in plan.shards(num_shards) {
+        let (mut stream, peer) = listener.accept().await?;
+        eprintln!(
+            "Worker {peer} runs shard {} with {} qps",
+            shard.index, shard.target_qps
+        );
+        send(&mut stream, &LeaderMessage::Assign(shard)).await?;
+        workers.push((peer, stream));
+    }
+
+    for (peer, stream) in &mut workers {
+        match receive::<WorkerMessage<BenchmarkStats>>(stream).await {
+            Ok(WorkerMessage::Ready) => {}
+            Ok(_) => bail!("Worker {peer} sent stats before the benchmark started"),
+            Err(e) => bail!("Worker {peer} failed to get ready: {e}"),
+        }
+    }
+    eprintln!("All workers are ready, starting benchmark");
+    for (_, stream) in &mut workers {
+        send(stream, &LeaderMessage::Start).await?;
+    }
+
+    let mut merged: Option<BenchmarkStats> = None;
+    for (peer, stream) in &mut workers {
+        let stats = match receive::<WorkerMessage<BenchmarkStats>>(stream).await {
+            Ok(WorkerMessage::Stats(stats)) => stats,
+            Ok(_) => bail!("Worker {peer} sent an unexpected message"),
+            Err(e) => bail!("Worker {peer} failed to report its stats: {e}"),
+        };
+        match &mut merged {
+            Some(merged) => merged.merge(&stats),
+            None => merged = Some(stats),
+        }
+    }
+    merged.ok_or_else(|| anyhow!("No stats were collected"))
+}
+
+/// A stress client's connection to the leader of a distributed benchmark.
+pub struct LeaderLink {
+    stream: TcpStream,
+}
+
+impl LeaderLink {
+    /// Connects to the leader at `address` and waits for the shard to run.
+    pub async fn connect(address: SocketAddr) -> Result<(Self, WorkloadShard)> {
+        let mut stream = TcpStream::connect(address).await?;
+        match receive(&mut stream).await? {
+            LeaderMessage::Assign(shard) => Ok((Self { stream }, shard)),
+            LeaderMessage::Start => Err(anyhow!("Leader started before assigning a shard")),
+        }
+    }
+
+    /// Detaches the connection from the current runtime, to move it to another one.
+    pub fn into_std(self) -> Result<std::net::TcpStream> {
+        Ok(self.stream.into_std()?)
+    }
+
+    /// Attaches a connection detached with [`LeaderLink::into_std`] to the current runtime.
+    pub fn from_std(stream: std::net::TcpStream) -> Result<Self> {
+        Ok(Self {
+            stream: TcpStream::from_std(stream)?,
+        })
+    }
+
+    /// Tells the leader the workload is set up and waits for every other worker to be too.
+    pub async fn ready_and_wait_for_start(&mut self) -> Result<()> {
+        send(&mut self.stream, &WorkerMessage::<()>::Ready).await?;
+        match receive(&mut self.stream).await? {
+            LeaderMessage::Start => Ok(()),
+            LeaderMessage::Assign(_) => Err(anyhow!("Leader assigned a second shard")),
+        }
+    }
+
+    pub async fn send_stats(&mut self, stats: &BenchmarkStats) -> Result<()> {
+        send(&mut self.stream, &WorkerMessage::Stats(stats)).await
+    }
+}
@@ -4,9 +4,9 @@
 use crate::drivers::Interval;
 use async_trait::async_trait;
 use prometheus::Registry;
-use sui_core::authority_aggregator::AuthorityAggregator;
-use sui_core::authority_client::NetworkAuthorityClient;
+use std::sync::Arc;
 
+use crate::validator_proxy::ValidatorProxy;
 use crate::workloads::workload::WorkloadInfo;
 
 #[async_trait]
@@ -14,7 +14,7 @@ pub trait Driver<T> {
     async fn run(
         &self,
         workload: Vec<WorkloadInfo>,
-        aggregator: AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: Arc<dyn ValidatorProxy>,
         registry: &Registry,
         show_progress: bool,
         run_duration: Interval,
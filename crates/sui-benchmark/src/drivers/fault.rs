@@ -0,0 +1,291 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use duration_str::parse;
+use multiaddr::{Multiaddr, Protocol};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{fmt, io, str::FromStr, time::Duration};
+use sui_config::ValidatorInfo;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tracing::{debug, error};
+
+/// What happens to the traffic between the benchmark and a validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultAction {
+    /// Reset all connections and refuse new ones.
+    Kill,
+    /// Keep connections open but hold back all traffic.
+    Pause,
+    /// Hold back each chunk of traffic for the given time.
+    Delay(Duration),
+    /// Back to normal after any of the above.
+    Restart,
+}
+
+impl fmt::Display for FaultAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FaultAction::Kill => write!(f, "kill"),
+            FaultAction::Pause => write!(f, "pause"),
+            FaultAction::Delay(delay) => write!(f, "delay={:?}", delay),
+            FaultAction::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+impl FromStr for FaultAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kill" => Ok(FaultAction::Kill),
+            "pause" => Ok(FaultAction::Pause),
+            "restart" | "resume" => Ok(FaultAction::Restart),
+            _ => match s.strip_prefix("delay=") {
+                Some(delay) => parse(delay)
+                    .map(FaultAction::Delay)
+                    .map_err(|e| format!("Invalid delay in '{s}': {e}")),
+                None => Err(format!(
+                    "Unknown fault '{s}', expected kill, pause, delay=<duration>, restart or resume"
+                )),
+            },
+        }
+    }
+}
+
+/// A fault hitting some validators at some point of the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fault {
+    pub action: FaultAction,
+    /// Names of the validators, as in the committee.
+    pub validators: Vec<String>,
+    /// Time since the start of the run.
+    pub at: Duration,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}@{:?}",
+            self.action,
+            self.validators.join("+"),
+            self.at
+        )
+    }
+}
+
+/// Faults to inject during a run, e.g. `kill:validator-2@60s,restart@120s`. Each fault is
+/// `<action>:<validator>[+<validator>...]@<time>`, and the validators can be left out to hit
+/// the same ones as the previous fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaultSchedule {
+    pub faults: Vec<Fault>,
+}
+
+impl FromStr for FaultSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut faults: Vec<Fault> = vec![];
+        for fault in s.split(',').map(str::trim) {
+            let (spec, at) = fault
+                .rsplit_once('@')
+                .ok_or_else(|| format!("Missing fault time in '{fault}'"))?;
+            let at = parse(at).map_err(|e| format!("Invalid fault time in '{fault}': {e}"))?;
+            let (action, validators) = match spec.split_once(':') {
+                Some((action, validators)) => {
+                    (action, validators.split('+').map(str::to_string).collect())
+                }
+                None => match faults.last() {
+                    Some(previous) => (spec, previous.validators.clone()),
+                    None => return Err(format!("Missing validators in '{fault}'")),
+                },
+            };
+            faults.push(Fault {
+                action: action.parse()?,
+                validators,
+                at,
+            });
+        }
+        faults.sort_by_key(|fault| fault.at);
+        Ok(FaultSchedule { faults })
+    }
+}
+
+/// A fault as it was injected, to annotate the stats with.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FaultEvent {
+    /// Time since the start of the run.
+    pub elapsed: Duration,
+    pub fault: String,
+}
+
+/// Routes the traffic of the benchmark to each validator through a local proxy, which faults
+/// are injected into. Validators still talk to each other directly, so the faults are only seen
+/// by the benchmark clients.
+pub struct FaultInjector {
+    links: HashMap<String, watch::Sender<FaultAction>>,
+}
+
+impl FaultInjector {
+    /// Starts a proxy for each validator of `validator_set`, and points it at the proxies.
+    pub async fn start(validator_set: &mut [ValidatorInfo]) -> Result<Self> {
+        let mut links = HashMap::new();
+        for validator in validator_set.iter_mut() {
+            let upstream = resolve(&validator.network_address).await?;
+            let listener =
+                TcpListener::bind(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await?;
+            let proxy_address = listener.local_addr()?;
+            validator.network_address = validator
+                .network_address
+                .iter()
+                .map(|protocol| match protocol {
+                    Protocol::Ip4(_)
+                    | Protocol::Ip6(_)
+                    | Protocol::Dns(_)
+                    | Protocol::Dns4(_)
+                    | Protocol::Dns6(_) => Protocol::Ip4(Ipv4Addr::LOCALHOST),
+                    Protocol::Tcp(_) => Protocol::Tcp(proxy_address.port()),
+                    protocol => protocol,
+                })
+                .collect();
+            let (link, state) = watch::channel(FaultAction::Restart);
+            tokio::spawn(proxy(listener, upstream, state));
+            links.insert(validator.name.clone(), link);
+        }
+        Ok(FaultInjector { links })
+    }
+
+    /// Fails if the schedule hits validators which aren't in the committee.
+    pub fn check(&self, schedule: &FaultSchedule) -> Result<()> {
+        for fault in &schedule.faults {
+            for validator in &fault.validators {
+                if !self.links.contains_key(validator) {
+                    return Err(anyhow!("Unknown validator {validator} in fault {fault}"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn inject(&self, fault: &Fault) {
+        for validator in &fault.validators {
+            if let Some(link) = self.links.get(validator) {
+                let _ = link.send(fault.action);
+            }
+        }
+    }
+}
+
+async fn resolve(address: &Multiaddr) -> Result<SocketAddr> {
+    let mut host = None;
+    let mut port = None;
+    for protocol in address.iter() {
+        match protocol {
+            Protocol::Ip4(ip) => host = Some(ip.to_string()),
+            Protocol::Ip6(ip) => host = Some(ip.to_string()),
+            Protocol::Dns(name) | Protocol::Dns4(name) | Protocol::Dns6(name) => {
+                host = Some(name.to_string())
+            }
+            Protocol::Tcp(p) => port = Some(p),
+            _ => {}
+        }
+    }
+    let (host, port) = host
+        .zip(port)
+        .ok_or_else(|| anyhow!("Cannot proxy validator address {address}"))?;
+    tokio::net::lookup_host((host.as_str(), port))
+        .await?
+        .next()
+        .ok_or_else(|| anyhow!("Cannot resolve validator address {address}"))
+}
+
+async fn proxy(listener: TcpListener, upstream: SocketAddr, state: watch::Receiver<FaultAction>) {
+    loop {
+        let inbound = match listener.accept().await {
+            Ok((inbound, _)) => inbound,
+            Err(e) => {
+                error!("Fault injection proxy failed to accept: {}", e);
+                continue;
+            }
+        };
+        if *state.borrow() == FaultAction::Kill {
+            continue;
+        }
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = forward(inbound, upstream, state).await {
+                debug!("Fault injection proxy connection ended: {}", e);
+            }
+        });
+    }
+}
+
+async fn forward(
+    inbound: TcpStream,
+    upstream: SocketAddr,
+    state: watch::Receiver<FaultAction>,
+) -> io::Result<()> {
+    let outbound = TcpStream::connect(upstream).await?;
+    let (inbound_read, inbound_write) = inbound.into_split();
+    let (outbound_read, outbound_write) = outbound.into_split();
+    // Dropping both directions on error resets the connection
+    tokio::try_join!(
+        pipe(inbound_read, outbound_write, state.clone()),
+        pipe(outbound_read, inbound_write, state),
+    )?;
+    Ok(())
+}
+
+async fn pipe(
+    mut from: OwnedReadHalf,
+    mut to: OwnedWriteHalf,
+    mut state: watch::Receiver<FaultAction>,
+) -> io::Result<()> {
+    let killed = || io::Error::new(io::ErrorKind::ConnectionReset, "validator killed");
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = tokio::select! {
+            n = from.read(&mut buf) => n?,
+            _ = until_killed(&mut state) => return Err(killed()),
+        };
+        if n == 0 {
+            return to.shutdown().await;
+        }
+        loop {
+            let action = *state.borrow();
+            match action {
+                FaultAction::Restart => break,
+                FaultAction::Delay(delay) => {
+                    tokio::time::sleep(delay).await;
+                    break;
+                }
+                FaultAction::Pause => {
+                    if state.changed().await.is_err() {
+                        return Err(killed());
+                    }
+                }
+                FaultAction::Kill => return Err(killed()),
+            }
+        }
+        to.write_all(&buf[..n]).await?;
+    }
+}
+
+async fn until_killed(state: &mut watch::Receiver<FaultAction>) {
+    loop {
+        if *state.borrow() == FaultAction::Kill {
+            return;
+        }
+        if state.changed().await.is_err() {
+            // The injector is gone, no fault will come
+            futures::future::pending::<()>().await;
+        }
+    }
+}
@@ -2,10 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use duration_str::parse;
-use std::{str::FromStr, time::Duration};
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
 
+pub mod baseline;
 pub mod bench_driver;
+pub mod distributed;
 pub mod driver;
+pub mod fault;
+pub mod otlp;
+pub mod replay;
+pub mod timeseries;
+use crate::workloads::gas_pool::GasPoolStats;
 use comfy_table::{Cell, Color, ContentArrangement, Row, Table};
 use hdrhistogram::{serialization::Serializer, Histogram};
 
@@ -13,6 +25,8 @@ use hdrhistogram::{serialization::Serializer, Histogram};
 pub enum Interval {
     Count(u64),
     Time(tokio::time::Duration),
+    /// Whichever of a number of transactions or a duration is reached first.
+    CountOrTime(u64, tokio::time::Duration),
 }
 
 impl Interval {
@@ -25,6 +39,20 @@ impl FromStr for Interval {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((first, second)) = s.split_once('|') {
+            // A count and a duration, in either order, e.g. "1000000|10m"
+            return match (first.parse::<Interval>()?, second.parse::<Interval>()?) {
+                (Interval::Count(count), Interval::Time(duration))
+                | (Interval::Time(duration), Interval::Count(count))
+                    if duration != tokio::time::Duration::MAX =>
+                {
+                    Ok(Interval::CountOrTime(count, duration))
+                }
+                _ => Err(format!(
+                    "Invalid interval '{s}', expected a number of cycles and a time duration, e.g. 1000000|10m"
+                )),
+            };
+        }
         if let Ok(i) = s.parse() {
             Ok(Interval::Count(i))
         } else if let Ok(d) = parse(s) {
@@ -37,12 +65,425 @@ impl FromStr for Interval {
     }
 }
 
+/// How periodic reports are emitted while a benchmark is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A stats table on stderr.
+    Table,
+    /// One JSON object per line on stdout.
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!(
+                "Unknown report format '{s}', expected table or json"
+            )),
+        }
+    }
+}
+
+/// How the final report, and its comparison with previous stats, is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryFormat {
+    /// Tables on stderr.
+    Table,
+    /// GitHub-flavored markdown tables on stdout, e.g. to post as a PR comment.
+    Markdown,
+}
+
+impl FromStr for SummaryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(SummaryFormat::Table),
+            "markdown" => Ok(SummaryFormat::Markdown),
+            _ => Err(format!("Unknown format '{s}', expected table or markdown")),
+        }
+    }
+}
+
+/// Renders a GitHub-flavored markdown table, with the first column left-aligned and the other
+/// ones right-aligned.
+fn markdown_table(header: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+    let alignments: Vec<&str> = (0..header.len())
+        .map(|i| if i == 0 { ":---" } else { "---:" })
+        .collect();
+    let mut table = format!(
+        "| {} |\n| {} |\n",
+        header.join(" | "),
+        alignments.join(" | ")
+    );
+    for row in rows {
+        // Pipes would end the cell early
+        let row: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+        table.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+    table
+}
+
+/// A single stage of a [`LoadProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStage {
+    /// Linearly change the offered load from `from_tps` to `to_tps` over `duration`.
+    Ramp {
+        from_tps: u64,
+        to_tps: u64,
+        duration: Duration,
+    },
+    /// Keep the offered load at `tps` for `duration`.
+    Hold { tps: u64, duration: Duration },
+}
+
+impl LoadStage {
+    pub fn duration(&self) -> Duration {
+        match self {
+            LoadStage::Ramp { duration, .. } | LoadStage::Hold { duration, .. } => *duration,
+        }
+    }
+
+    pub fn max_tps(&self) -> u64 {
+        match self {
+            LoadStage::Ramp {
+                from_tps, to_tps, ..
+            } => std::cmp::max(*from_tps, *to_tps),
+            LoadStage::Hold { tps, .. } => *tps,
+        }
+    }
+
+    /// Offered load `elapsed` into this stage.
+    pub fn tps_at(&self, elapsed: Duration) -> u64 {
+        match self {
+            LoadStage::Ramp {
+                from_tps,
+                to_tps,
+                duration,
+            } => {
+                let progress = if duration.is_zero() {
+                    1.0
+                } else {
+                    (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+                };
+                let delta = *to_tps as f64 - *from_tps as f64;
+                (*from_tps as f64 + delta * progress).round() as u64
+            }
+            LoadStage::Hold { tps, .. } => *tps,
+        }
+    }
+}
+
+impl fmt::Display for LoadStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadStage::Ramp {
+                from_tps,
+                to_tps,
+                duration,
+            } => write!(f, "ramp:{}-{}tps/{:?}", from_tps, to_tps, duration),
+            LoadStage::Hold { tps, duration } => write!(f, "hold:{}tps/{:?}", tps, duration),
+        }
+    }
+}
+
+impl FromStr for LoadStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, spec) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Missing stage kind in '{s}'"))?;
+        let (rate, duration) = spec
+            .split_once('/')
+            .ok_or_else(|| format!("Missing stage duration in '{s}'"))?;
+        let duration = parse(duration).map_err(|e| format!("Invalid duration in '{s}': {e}"))?;
+        let rate = rate.strip_suffix("tps").unwrap_or(rate);
+        let parse_tps = |tps: &str| {
+            tps.parse::<u64>()
+                .map_err(|e| format!("Invalid tps in '{s}': {e}"))
+        };
+        match kind {
+            "ramp" => {
+                let (from_tps, to_tps) = rate
+                    .split_once('-')
+                    .ok_or_else(|| format!("Ramp stage needs a <from>-<to> range in '{s}'"))?;
+                Ok(LoadStage::Ramp {
+                    from_tps: parse_tps(from_tps)?,
+                    to_tps: parse_tps(to_tps)?,
+                    duration,
+                })
+            }
+            "hold" => Ok(LoadStage::Hold {
+                tps: parse_tps(rate)?,
+                duration,
+            }),
+            _ => Err(format!("Unknown stage kind '{kind}', expected ramp or hold")),
+        }
+    }
+}
+
+/// A sequence of load stages run back to back, e.g.
+/// `ramp:0-5000tps/60s,hold:5000tps/300s,ramp:5000-0tps/60s`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadProfile {
+    pub stages: Vec<LoadStage>,
+}
+
+impl LoadProfile {
+    pub fn total_duration(&self) -> Duration {
+        self.stages.iter().map(|stage| stage.duration()).sum()
+    }
+
+    pub fn max_tps(&self) -> u64 {
+        self.stages
+            .iter()
+            .map(|stage| stage.max_tps())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the index of the stage running `elapsed` into the profile, together with the
+    /// time spent in that stage so far, or None once the profile is over.
+    pub fn stage_at(&self, elapsed: Duration) -> Option<(usize, Duration)> {
+        let mut stage_start = Duration::ZERO;
+        for (index, stage) in self.stages.iter().enumerate() {
+            let stage_end = stage_start + stage.duration();
+            if elapsed < stage_end {
+                return Some((index, elapsed - stage_start));
+            }
+            stage_start = stage_end;
+        }
+        None
+    }
+
+    /// Offered load `elapsed` into the profile, zero once the profile is over.
+    pub fn tps_at(&self, elapsed: Duration) -> u64 {
+        self.stage_at(elapsed)
+            .map(|(index, in_stage)| self.stages[index].tps_at(in_stage))
+            .unwrap_or(0)
+    }
+
+    /// Time spent in stage `index` after `elapsed` into the profile.
+    pub fn stage_elapsed(&self, index: usize, elapsed: Duration) -> Duration {
+        let stage_start: Duration = self.stages[..index].iter().map(|s| s.duration()).sum();
+        let stage_end = stage_start + self.stages[index].duration();
+        std::cmp::min(elapsed, stage_end).saturating_sub(stage_start)
+    }
+}
+
+impl FromStr for LoadProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let stages = s
+            .split(',')
+            .map(|stage| stage.trim().parse())
+            .collect::<Result<Vec<LoadStage>, _>>()?;
+        if stages.is_empty() {
+            return Err("Load profile needs at least one stage".to_string());
+        }
+        Ok(LoadProfile { stages })
+    }
+}
+
+/// Binary search for the highest offered load the network sustains: the load is held at each
+/// probed rate for `step_duration`, and a rate is sustained if the p99 latency and the error
+/// rate stay within bounds and the achieved throughput keeps up with the offered load.
+#[derive(Debug, Clone)]
+pub struct MaxTpsSearch {
+    pub max_p99_latency_ms: u64,
+    pub max_error_rate: f64,
+    pub step_duration: Duration,
+    /// The search stops once the highest sustained and the lowest unsustained rates are
+    /// closer than this.
+    pub precision_tps: u64,
+    /// Highest rate sustained so far.
+    low: u64,
+    /// Lowest rate not sustained so far.
+    high: u64,
+}
+
+/// Fraction of the offered load a probe must achieve to be sustained.
+const MIN_ACHIEVED_LOAD: f64 = 0.9;
+
+impl MaxTpsSearch {
+    /// Searches rates up to `max_tps`.
+    pub fn new(
+        max_tps: u64,
+        max_p99_latency_ms: u64,
+        max_error_rate: f64,
+        step_duration: Duration,
+        precision_tps: u64,
+    ) -> Self {
+        MaxTpsSearch {
+            max_p99_latency_ms,
+            max_error_rate,
+            step_duration,
+            precision_tps: std::cmp::max(precision_tps, 1),
+            low: 0,
+            high: max_tps + 1,
+        }
+    }
+
+    /// Rate to probe next, or None once the search is over.
+    pub fn next_tps(&self) -> Option<u64> {
+        if self.high - self.low <= self.precision_tps {
+            None
+        } else {
+            Some(self.low + (self.high - self.low) / 2)
+        }
+    }
+
+    /// Narrows the search down with the stats of a probe at `tps`, returns whether the rate
+    /// was sustained.
+    pub fn record(&mut self, tps: u64, stats: &BenchmarkStats) -> bool {
+        let sustained = self.sustains(tps, stats);
+        if sustained {
+            self.low = std::cmp::max(self.low, tps);
+        } else {
+            self.high = std::cmp::min(self.high, tps);
+        }
+        sustained
+    }
+
+    /// Highest rate sustained so far, zero if none was.
+    pub fn max_sustainable_tps(&self) -> u64 {
+        self.low
+    }
+
+    fn sustains(&self, tps: u64, stats: &BenchmarkStats) -> bool {
+        if stats.num_success == 0 || stats.duration.is_zero() {
+            return false;
+        }
+        stats.latency_ms.histogram.value_at_quantile(0.99) <= self.max_p99_latency_ms
+            && stats.error_rate() <= self.max_error_rate
+            && stats.tps() >= tps as f64 * MIN_ACHIEVED_LOAD
+    }
+}
+
 // wrapper which implements serde
 #[allow(dead_code)]
 pub struct HistogramWrapper {
     histogram: Histogram<u64>,
 }
 
+impl Default for HistogramWrapper {
+    fn default() -> Self {
+        HistogramWrapper::new(HistogramConfig::default())
+    }
+}
+
+/// Bounds of the latency histograms. Latencies above `max_value_ms` are recorded as
+/// `max_value_ms`, so it must be raised for targets slower than that.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramConfig {
+    pub max_value_ms: u64,
+    /// Decimal digits to which the values are kept apart, from 0 to 5.
+    pub significant_digits: u8,
+}
+
+impl Default for HistogramConfig {
+    fn default() -> Self {
+        HistogramConfig {
+            max_value_ms: 100_000,
+            significant_digits: 2,
+        }
+    }
+}
+
+impl HistogramConfig {
+    pub fn new(max_value_ms: u64, significant_digits: u8) -> Result<Self, String> {
+        // Values are tracked from 1ms, and the histogram needs at least twice that range
+        if max_value_ms < 2 {
+            return Err(format!(
+                "Invalid histogram maximum {max_value_ms}ms, expected at least 2ms"
+            ));
+        }
+        if significant_digits > 5 {
+            return Err(format!(
+                "Invalid histogram precision of {significant_digits} significant digits, \
+                 expected at most 5"
+            ));
+        }
+        Ok(HistogramConfig {
+            max_value_ms,
+            significant_digits,
+        })
+    }
+
+    pub fn new_histogram(&self) -> Histogram<u64> {
+        Histogram::<u64>::new_with_max(self.max_value_ms, self.significant_digits).unwrap()
+    }
+}
+
+/// Adds `other` to `histogram`, which grows if `other` was recorded with a higher maximum.
+fn add_histogram(histogram: &mut Histogram<u64>, other: &Histogram<u64>) {
+    if histogram.add(other).is_err() {
+        histogram.auto(true);
+        histogram.add(other).unwrap();
+        histogram.auto(false);
+    }
+}
+
+impl HistogramWrapper {
+    pub fn new(config: HistogramConfig) -> Self {
+        HistogramWrapper {
+            histogram: config.new_histogram(),
+        }
+    }
+
+    /// Writes the histogram in the HdrHistogram percentile distribution (.hgrm) text format
+    /// understood by HdrHistogram plotting tools.
+    pub fn write_hgrm<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        let histogram = &self.histogram;
+        writeln!(
+            writer,
+            "{:>12} {:>14} {:>10} {:>14}\n",
+            "Value", "Percentile", "TotalCount", "1/(1-Percentile)"
+        )?;
+        let mut total_count = 0;
+        for v in histogram.iter_quantiles(5) {
+            total_count += v.count_since_last_iteration();
+            let quantile = v.quantile_iterated_to();
+            if quantile < 1.0 {
+                writeln!(
+                    writer,
+                    "{:12.3} {:2.12} {:10} {:14.2}",
+                    v.value_iterated_to() as f64,
+                    quantile,
+                    total_count,
+                    1.0 / (1.0 - quantile)
+                )?;
+            } else {
+                writeln!(
+                    writer,
+                    "{:12.3} {:2.12} {:10}",
+                    v.value_iterated_to() as f64,
+                    quantile,
+                    total_count
+                )?;
+            }
+        }
+        writeln!(
+            writer,
+            "#[Mean    = {:12.3}, StdDeviation   = {:12.3}]",
+            histogram.mean(),
+            histogram.stdev()
+        )?;
+        writeln!(
+            writer,
+            "#[Max     = {:12.3}, Total count    = {:12}]",
+            histogram.max() as f64,
+            histogram.len()
+        )
+    }
+}
+
 impl serde::Serialize for HistogramWrapper {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut vec = Vec::new();
@@ -70,57 +511,491 @@ pub struct BenchmarkStats {
     pub num_error: u64,
     pub num_success: u64,
     pub latency_ms: HistogramWrapper,
+    /// Time from when a transaction was due to be submitted at the target rate to its effects,
+    /// as in wrk2. `latency_ms` starts at the actual submission, so it leaves out the time
+    /// transactions are held back while the driver waits for the ones in flight, and hides the
+    /// tail latency of a target which can't keep up.
+    #[serde(default)]
+    pub response_time_ms: HistogramWrapper,
+    /// Time from submitting a transaction to getting its certificate.
+    #[serde(default)]
+    pub submit_to_cert_latency_ms: HistogramWrapper,
+    /// Time from getting a certificate to getting its effects certificate.
+    #[serde(default)]
+    pub cert_to_effects_latency_ms: HistogramWrapper,
+    /// Time from submitting a transaction to seeing it in a checkpoint, only measured when
+    /// asked for.
+    #[serde(default)]
+    pub checkpoint_latency_ms: HistogramWrapper,
+    /// Per-stage statistics when the run followed a [`LoadProfile`].
+    #[serde(default)]
+    pub stages: Vec<StageStats>,
+    /// Per-workload statistics, keyed by workload type.
+    #[serde(default)]
+    pub workloads: Vec<WorkloadStats>,
+    /// Per-scheme statistics, keyed by the signature scheme of the senders.
+    #[serde(default)]
+    pub schemes: Vec<SchemeStats>,
+    /// Result of a [`MaxTpsSearch`], whose probes are reported as stages.
+    #[serde(default)]
+    pub max_sustainable_tps: Option<u64>,
+    /// Faults injected during the run.
+    #[serde(default)]
+    pub faults: Vec<fault::FaultEvent>,
+    /// What the gas pool of the workloads did, for runs which had one.
+    #[serde(default)]
+    pub gas_pool: Option<GasPoolStats>,
+    /// Latency quantiles of each interval of the run, when a quantile series was asked for.
+    /// They are not merged with the stats of other runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quantiles: Vec<QuantilePoint>,
+}
+
+/// Latency quantiles of one interval of the run, serialized as a compact
+/// `[elapsed_s, p50, p95, p99]` array for plotting.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QuantilePoint(pub f64, pub u64, pub u64, pub u64);
+
+impl QuantilePoint {
+    /// The quantiles of `histogram`, recorded over the interval ending `elapsed` into the run.
+    pub fn new(elapsed: Duration, histogram: &Histogram<u64>) -> Self {
+        QuantilePoint(
+            elapsed.as_secs_f64(),
+            histogram.value_at_quantile(0.5),
+            histogram.value_at_quantile(0.95),
+            histogram.value_at_quantile(0.99),
+        )
+    }
+}
+
+/// Statistics of a single stage of a [`LoadProfile`].
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct StageStats {
+    pub stage: String,
+    pub stats: BenchmarkStats,
+}
+
+/// Statistics of a single workload of a mixed run.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct WorkloadStats {
+    pub workload: String,
+    pub stats: BenchmarkStats,
+}
+
+/// Statistics of the transactions sent by the accounts of a single signature scheme.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SchemeStats {
+    pub scheme: String,
+    pub stats: BenchmarkStats,
+}
+
+/// Summary of the stats collected over one reporting window.
+#[derive(serde::Serialize)]
+pub struct WindowReport {
+    /// Time since the start of the run at the end of the window.
+    pub elapsed_s: u64,
+    pub duration_s: u64,
+    pub num_success: u64,
+    pub num_error: u64,
+    pub tps: f64,
+    pub latency_ms_min: u64,
+    pub latency_ms_p50: u64,
+    pub latency_ms_p99: u64,
+    pub latency_ms_max: u64,
+    /// Faults injected during the window.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub faults: Vec<String>,
 }
 
 impl BenchmarkStats {
+    pub fn to_window_report(&self, elapsed: Duration) -> WindowReport {
+        let histogram = &self.latency_ms.histogram;
+        WindowReport {
+            elapsed_s: elapsed.as_secs(),
+            duration_s: self.duration.as_secs(),
+            num_success: self.num_success,
+            num_error: self.num_error,
+            tps: self.tps(),
+            latency_ms_min: histogram.min(),
+            latency_ms_p50: histogram.value_at_quantile(0.5),
+            latency_ms_p99: histogram.value_at_quantile(0.99),
+            latency_ms_max: histogram.max(),
+            faults: self
+                .faults
+                .iter()
+                .map(|event| event.fault.clone())
+                .collect(),
+        }
+    }
+    /// Successful transactions per second, zero over an empty duration.
+    pub fn tps(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.num_success as f64 / self.duration.as_secs_f64()
+    }
+    /// Fraction of the transactions which failed, zero if there were none.
+    pub fn error_rate(&self) -> f64 {
+        let total = self.num_success + self.num_error;
+        if total == 0 {
+            return 0.0;
+        }
+        self.num_error as f64 / total as f64
+    }
     pub fn update(&mut self, duration: Duration, sample_stat: &BenchmarkStats) {
         self.duration = duration;
         self.num_error += sample_stat.num_error;
         self.num_success += sample_stat.num_success;
-        self.latency_ms
-            .histogram
-            .add(&sample_stat.latency_ms.histogram)
-            .unwrap();
+        self.add_histograms(sample_stat);
+    }
+    fn add_histograms(&mut self, other: &BenchmarkStats) {
+        for (mine, theirs) in [
+            (&mut self.latency_ms, &other.latency_ms),
+            (&mut self.response_time_ms, &other.response_time_ms),
+            (
+                &mut self.submit_to_cert_latency_ms,
+                &other.submit_to_cert_latency_ms,
+            ),
+            (
+                &mut self.cert_to_effects_latency_ms,
+                &other.cert_to_effects_latency_ms,
+            ),
+            (
+                &mut self.checkpoint_latency_ms,
+                &other.checkpoint_latency_ms,
+            ),
+        ] {
+            add_histogram(&mut mine.histogram, &theirs.histogram);
+        }
+    }
+    /// The overall stats, followed by the latency of each phase of a transaction.
+    /// Folds in the stats of a run that went on at the same time as this one, e.g. on another
+    /// machine. Stage, workload and scheme stats are matched by name.
+    pub fn merge(&mut self, other: &BenchmarkStats) {
+        self.duration = std::cmp::max(self.duration, other.duration);
+        self.num_error += other.num_error;
+        self.num_success += other.num_success;
+        self.add_histograms(other);
+        for stage in &other.stages {
+            match self.stages.iter_mut().find(|s| s.stage == stage.stage) {
+                Some(mine) => mine.stats.merge(&stage.stats),
+                None => self.stages.push(StageStats {
+                    stage: stage.stage.clone(),
+                    stats: stage.stats.duplicate(),
+                }),
+            }
+        }
+        if let Some(theirs) = &other.gas_pool {
+            self.gas_pool
+                .get_or_insert_with(GasPoolStats::default)
+                .merge(theirs);
+        }
+        for workload in &other.workloads {
+            match self
+                .workloads
+                .iter_mut()
+                .find(|w| w.workload == workload.workload)
+            {
+                Some(mine) => mine.stats.merge(&workload.stats),
+                None => self.workloads.push(WorkloadStats {
+                    workload: workload.workload.clone(),
+                    stats: workload.stats.duplicate(),
+                }),
+            }
+        }
+        for scheme in &other.schemes {
+            match self.schemes.iter_mut().find(|s| s.scheme == scheme.scheme) {
+                Some(mine) => mine.stats.merge(&scheme.stats),
+                None => self.schemes.push(SchemeStats {
+                    scheme: scheme.scheme.clone(),
+                    stats: scheme.stats.duplicate(),
+                }),
+            }
+        }
+    }
+    fn duplicate(&self) -> BenchmarkStats {
+        let mut copy = BenchmarkStats {
+            duration: Duration::ZERO,
+            num_error: 0,
+            num_success: 0,
+            latency_ms: HistogramWrapper::default(),
+            response_time_ms: HistogramWrapper::default(),
+            submit_to_cert_latency_ms: HistogramWrapper::default(),
+            cert_to_effects_latency_ms: HistogramWrapper::default(),
+            checkpoint_latency_ms: HistogramWrapper::default(),
+            stages: vec![],
+            workloads: vec![],
+            schemes: vec![],
+            max_sustainable_tps: None,
+            faults: vec![],
+            gas_pool: None,
+            quantiles: vec![],
+        };
+        copy.merge(self);
+        copy
+    }
+    /// Writes the latency histograms to `dir` in the .hgrm format: `latency.hgrm` for all
+    /// transactions, `response_time.hgrm`, one file per transaction phase, `checkpoint.hgrm` if
+    /// the checkpoint latency was measured, and one per workload of a mixed run and per
+    /// signature scheme of a run with several. Returns the paths of the written files.
+    pub fn write_hgrm_files(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)?;
+        let mut histograms = vec![
+            ("latency".to_string(), &self.latency_ms),
+            ("response_time".to_string(), &self.response_time_ms),
+            (
+                "submit_to_cert".to_string(),
+                &self.submit_to_cert_latency_ms,
+            ),
+            (
+                "cert_to_effects".to_string(),
+                &self.cert_to_effects_latency_ms,
+            ),
+        ];
+        if !self.checkpoint_latency_ms.histogram.is_empty() {
+            histograms.push(("checkpoint".to_string(), &self.checkpoint_latency_ms));
+        }
+        if self.workloads.len() > 1 {
+            for workload in &self.workloads {
+                histograms.push((
+                    format!("latency.{}", workload.workload),
+                    &workload.stats.latency_ms,
+                ));
+            }
+        }
+        if self.schemes.len() > 1 {
+            for scheme in &self.schemes {
+                histograms.push((
+                    format!("latency.{}", scheme.scheme),
+                    &scheme.stats.latency_ms,
+                ));
+            }
+        }
+        let mut paths = Vec::with_capacity(histograms.len());
+        for (name, histogram) in histograms {
+            let path = dir.join(format!("{name}.hgrm"));
+            let mut file = io::BufWriter::new(std::fs::File::create(&path)?);
+            histogram.write_hgrm(&mut file)?;
+            io::Write::flush(&mut file)?;
+            paths.push(path);
+        }
+        Ok(paths)
     }
     pub fn to_table(&self) -> Table {
+        let mut table = Self::labelled_table("phase", std::iter::empty());
+        let mut row = Row::new();
+        row.add_cell(Cell::new("total"));
+        for cell in self.to_row().cell_iter() {
+            row.add_cell(cell.clone());
+        }
+        table.add_row(row);
+        for (phase, histogram) in [
+            // Response time, as opposed to the service time of the total
+            ("intended->effects", &self.response_time_ms.histogram),
+            ("submit->cert", &self.submit_to_cert_latency_ms.histogram),
+            ("cert->effects", &self.cert_to_effects_latency_ms.histogram),
+            ("submit->checkpoint", &self.checkpoint_latency_ms.histogram),
+        ] {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(phase));
+            // Throughput and errors are only accounted for whole transactions
+            for _ in 0..3 {
+                row.add_cell(Cell::new(""));
+            }
+            for cell in Self::latency_cells(histogram) {
+                row.add_cell(cell);
+            }
+            table.add_row(row);
+        }
+        table
+    }
+    /// One row per load profile stage, prefixed with the stage description.
+    pub fn to_stage_table(&self) -> Table {
+        Self::labelled_table(
+            "stage",
+            self.stages.iter().map(|stage| (&stage.stage, &stage.stats)),
+        )
+    }
+    /// One row per injected fault, with the time it was injected at.
+    pub fn to_fault_table(&self) -> Table {
         let mut table = Table::new();
         table
             .set_content_arrangement(ContentArrangement::Dynamic)
             .set_width(200)
-            .set_header(vec![
-                "duration(s)",
-                "tps",
-                "error%",
-                "min",
-                "p25",
-                "p50",
-                "p75",
-                "p90",
-                "p99",
-                "p99.9",
-                "max",
+            .set_header(vec!["at(s)", "fault"]);
+        for event in &self.faults {
+            table.add_row(vec![
+                Cell::new(format!("{:.1}", event.elapsed.as_secs_f64())),
+                Cell::new(&event.fault),
             ]);
-        let mut row = Row::new();
-        row.add_cell(Cell::new(self.duration.as_secs()));
-        row.add_cell(Cell::new(self.num_success / self.duration.as_secs()));
-        row.add_cell(Cell::new(
-            self.num_error / (self.num_error + self.num_success),
-        ));
-        row.add_cell(Cell::new(self.latency_ms.histogram.min()));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.25)));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.5)));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.75)));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.9)));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.99)));
-        row.add_cell(Cell::new(
-            self.latency_ms.histogram.value_at_quantile(0.999),
-        ));
-        row.add_cell(Cell::new(self.latency_ms.histogram.max()));
-        table.add_row(row);
+        }
         table
     }
+    /// One row per workload, prefixed with the workload name, followed by a row of the
+    /// stats of all the workloads together.
+    pub fn to_workload_table(&self) -> Table {
+        let all = "all".to_string();
+        Self::labelled_table(
+            "workload",
+            self.workloads
+                .iter()
+                .map(|workload| (&workload.workload, &workload.stats))
+                .chain(std::iter::once((&all, self))),
+        )
+    }
+    /// One row per signature scheme of the senders, followed by a row of the stats of all the
+    /// schemes together.
+    pub fn to_scheme_table(&self) -> Table {
+        let all = "all".to_string();
+        Self::labelled_table(
+            "scheme",
+            self.schemes
+                .iter()
+                .map(|scheme| (&scheme.scheme, &scheme.stats))
+                .chain(std::iter::once((&all, self))),
+        )
+    }
+    fn labelled_table<'a>(
+        label: &str,
+        rows: impl Iterator<Item = (&'a String, &'a BenchmarkStats)>,
+    ) -> Table {
+        let mut table = Table::new();
+        let mut header = vec![label];
+        header.extend(STATS_HEADER);
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(header);
+        for (name, stats) in rows {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name));
+            for cell in stats.to_row().cell_iter() {
+                row.add_cell(cell.clone());
+            }
+            table.add_row(row);
+        }
+        table
+    }
+    /// The stats as GitHub-flavored markdown: the table of [`BenchmarkStats::to_table`],
+    /// followed by the stage, workload and scheme tables if the run had several of them.
+    pub fn to_markdown(&self) -> String {
+        let mut header = vec!["phase"];
+        header.extend(STATS_HEADER);
+        let mut total = vec!["total".to_string()];
+        total.extend(self.row_values());
+        let mut rows = vec![total];
+        for (phase, histogram) in [
+            // Response time, as opposed to the service time of the total
+            ("intended->effects", &self.response_time_ms.histogram),
+            ("submit->cert", &self.submit_to_cert_latency_ms.histogram),
+            ("cert->effects", &self.cert_to_effects_latency_ms.histogram),
+            ("submit->checkpoint", &self.checkpoint_latency_ms.histogram),
+        ] {
+            let mut row = vec![phase.to_string()];
+            row.extend(std::iter::repeat(String::new()).take(3));
+            row.extend(Self::latency_values(histogram));
+            rows.push(row);
+        }
+        let mut markdown = markdown_table(&header, rows);
+        if !self.stages.is_empty() {
+            markdown.push_str("\n**Per stage**\n\n");
+            markdown.push_str(&Self::labelled_markdown(
+                "stage",
+                self.stages.iter().map(|stage| (&stage.stage, &stage.stats)),
+            ));
+        }
+        if self.workloads.len() > 1 {
+            let all = "all".to_string();
+            markdown.push_str("\n**Per workload**\n\n");
+            markdown.push_str(&Self::labelled_markdown(
+                "workload",
+                self.workloads
+                    .iter()
+                    .map(|workload| (&workload.workload, &workload.stats))
+                    .chain(std::iter::once((&all, self))),
+            ));
+        }
+        if self.schemes.len() > 1 {
+            let all = "all".to_string();
+            markdown.push_str("\n**Per signature scheme**\n\n");
+            markdown.push_str(&Self::labelled_markdown(
+                "scheme",
+                self.schemes
+                    .iter()
+                    .map(|scheme| (&scheme.scheme, &scheme.stats))
+                    .chain(std::iter::once((&all, self))),
+            ));
+        }
+        markdown
+    }
+    fn labelled_markdown<'a>(
+        label: &str,
+        rows: impl Iterator<Item = (&'a String, &'a BenchmarkStats)>,
+    ) -> String {
+        let mut header = vec![label];
+        header.extend(STATS_HEADER);
+        markdown_table(
+            &header,
+            rows.map(|(name, stats)| {
+                let mut row = vec![name.clone()];
+                row.extend(stats.row_values());
+                row
+            }),
+        )
+    }
+    fn to_row(&self) -> Row {
+        let mut row = Row::new();
+        for value in self.row_values() {
+            row.add_cell(Cell::new(value));
+        }
+        row
+    }
+    fn row_values(&self) -> Vec<String> {
+        let mut values = vec![
+            self.duration.as_secs().to_string(),
+            format!("{:.2}", self.tps()),
+            format!("{:.2}", self.error_rate() * 100.0),
+        ];
+        values.extend(Self::latency_values(&self.latency_ms.histogram));
+        values
+    }
+    fn latency_cells(histogram: &Histogram<u64>) -> Vec<Cell> {
+        Self::latency_values(histogram)
+            .into_iter()
+            .map(Cell::new)
+            .collect()
+    }
+    fn latency_values(histogram: &Histogram<u64>) -> Vec<String> {
+        vec![
+            histogram.min(),
+            histogram.value_at_quantile(0.25),
+            histogram.value_at_quantile(0.5),
+            histogram.value_at_quantile(0.75),
+            histogram.value_at_quantile(0.9),
+            histogram.value_at_quantile(0.99),
+            histogram.value_at_quantile(0.999),
+            histogram.max(),
+        ]
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect()
+    }
 }
 
+const STATS_HEADER: [&str; 11] = [
+    "duration(s)",
+    "tps",
+    "error%",
+    "min",
+    "p25",
+    "p50",
+    "p75",
+    "p90",
+    "p99",
+    "p99.9",
+    "max",
+];
+
 /// A comparison between an old and a new benchmark.
 /// All differences are reported in terms of measuring improvements
 /// (negative) or regressions (positive). That is, if an old benchmark
@@ -132,11 +1007,161 @@ pub struct Comparison {
     pub name: String,
     pub old_value: String,
     pub new_value: String,
-    pub diff: i64,
+    pub diff: f64,
     pub diff_ratio: f64,
     pub speedup: f64,
 }
 
+impl Comparison {
+    /// Compares a metric which went from `old` to `new`, for which higher values are better if
+    /// `higher_is_better`. A change from zero has an infinite ratio.
+    pub fn new(name: &str, old: f64, new: f64, higher_is_better: bool) -> Comparison {
+        let diff = new - old;
+        let diff_ratio = if diff == 0.0 {
+            0.0
+        } else if old == 0.0 {
+            f64::INFINITY.copysign(diff)
+        } else {
+            diff / old
+        };
+        let speedup = if higher_is_better {
+            1.0 + diff_ratio
+        } else {
+            1.0 / (1.0 + diff_ratio)
+        };
+        Comparison {
+            name: name.to_string(),
+            old_value: format!("{:.2}", old),
+            new_value: format!("{:.2}", new),
+            diff,
+            diff_ratio,
+            speedup,
+        }
+    }
+}
+
+/// Direction of a [`Threshold`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdOp {
+    AtLeast,
+    AtMost,
+}
+
+/// A bound on the relative difference of a [`Comparison`], e.g. `tps>=-5%`
+/// (throughput drops by at most 5%) or `p99<=+10%` (p99 latency grows by
+/// at most 10%). Latency metrics can be named without their `_latency` suffix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    pub metric: String,
+    pub op: ThresholdOp,
+    /// Bound on `diff_ratio`, in percent.
+    pub percent: f64,
+}
+
+impl Threshold {
+    fn applies_to(&self, name: &str) -> bool {
+        name == self.metric || name.strip_suffix("_latency") == Some(self.metric.as_str())
+    }
+
+    fn is_met_by(&self, cmp: &Comparison) -> bool {
+        let percent = cmp.diff_ratio * 100f64;
+        match self.op {
+            ThresholdOp::AtLeast => percent >= self.percent,
+            ThresholdOp::AtMost => percent <= self.percent,
+        }
+    }
+}
+
+impl fmt::Display for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let op = match self.op {
+            ThresholdOp::AtLeast => ">=",
+            ThresholdOp::AtMost => "<=",
+        };
+        write!(f, "{}{}{:+}%", self.metric, op, self.percent)
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid threshold '{s}', expected <metric>>=<percent>% or <metric><=<percent>%"
+            )
+        };
+        let (metric, op, bound) = if let Some((metric, bound)) = s.split_once(">=") {
+            (metric, ThresholdOp::AtLeast, bound)
+        } else if let Some((metric, bound)) = s.split_once("<=") {
+            (metric, ThresholdOp::AtMost, bound)
+        } else {
+            return Err(invalid());
+        };
+        let percent: f64 = bound
+            .trim()
+            .strip_suffix('%')
+            .and_then(|percent| percent.parse().ok())
+            .ok_or_else(invalid)?;
+        let metric = metric.trim();
+        if metric.is_empty() || !percent.is_finite() {
+            return Err(invalid());
+        }
+        Ok(Threshold {
+            metric: metric.to_string(),
+            op,
+            percent,
+        })
+    }
+}
+
+/// The outcome of a [`Threshold`] on the comparison of its metric.
+#[derive(Clone, Debug)]
+pub struct ThresholdCheck {
+    pub threshold: Threshold,
+    pub comparison: Comparison,
+    pub passed: bool,
+}
+
+impl ThresholdCheck {
+    /// The checks as a GitHub-flavored markdown table.
+    pub fn to_markdown(checks: &[ThresholdCheck]) -> String {
+        markdown_table(
+            &["threshold", "old", "new", "diff_ratio", "result"],
+            checks.iter().map(|check| {
+                vec![
+                    check.threshold.to_string(),
+                    check.comparison.old_value.clone(),
+                    check.comparison.new_value.clone(),
+                    format!("{:.2}%", check.comparison.diff_ratio * 100f64),
+                    if check.passed { "✅ pass" } else { "❌ FAIL" }.to_string(),
+                ]
+            }),
+        )
+    }
+    pub fn to_table(checks: &[ThresholdCheck]) -> Table {
+        let mut table = Table::new();
+        table.set_header(vec!["threshold", "old", "new", "diff_ratio", "result"]);
+        for check in checks {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(check.threshold.to_string()));
+            row.add_cell(Cell::new(&check.comparison.old_value));
+            row.add_cell(Cell::new(&check.comparison.new_value));
+            row.add_cell(Cell::new(format!(
+                "{:.2}%",
+                check.comparison.diff_ratio * 100f64
+            )));
+            if check.passed {
+                row.add_cell(Cell::new("pass").fg(Color::Green));
+            } else {
+                row.add_cell(Cell::new("FAIL").fg(Color::Red));
+            }
+            table.add_row(row);
+        }
+        table
+    }
+}
+
 pub struct BenchmarkCmp<'a> {
     pub new: &'a BenchmarkStats,
     pub old: &'a BenchmarkStats,
@@ -144,9 +1169,67 @@ pub struct BenchmarkCmp<'a> {
 
 impl BenchmarkCmp<'_> {
     pub fn to_table(&self) -> Table {
+        Self::cmp_table(self.all_cmps())
+    }
+    /// The comparisons of [`BenchmarkCmp::workload_cmps`], empty if no workload ran in both
+    /// benchmarks.
+    pub fn to_workload_table(&self) -> Table {
+        Self::cmp_table(self.workload_cmps())
+    }
+    /// The comparisons of [`BenchmarkCmp::scheme_cmps`], empty if no signature scheme was used
+    /// in both benchmarks.
+    pub fn to_scheme_table(&self) -> Table {
+        Self::cmp_table(self.scheme_cmps())
+    }
+    /// The comparisons as GitHub-flavored markdown, each marked as an improvement (🟢) or a
+    /// regression (🔴) with an arrow for the direction of the change, followed by the
+    /// comparisons of each workload and signature scheme if any.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = Self::cmp_markdown(self.all_cmps());
+        let workload_cmps = self.workload_cmps();
+        if !workload_cmps.is_empty() {
+            markdown.push_str("\n**Per workload**\n\n");
+            markdown.push_str(&Self::cmp_markdown(workload_cmps));
+        }
+        let scheme_cmps = self.scheme_cmps();
+        if !scheme_cmps.is_empty() {
+            markdown.push_str("\n**Per signature scheme**\n\n");
+            markdown.push_str(&Self::cmp_markdown(scheme_cmps));
+        }
+        markdown
+    }
+    fn cmp_markdown(cmps: Vec<Comparison>) -> String {
+        markdown_table(
+            &["", "name", "old", "new", "diff", "diff_ratio", "speedup"],
+            cmps.into_iter().map(|cmp| {
+                let marker = match cmp.diff {
+                    diff if diff == 0.0 => "⚪ =",
+                    // Neither better nor worse if the ratio is undefined, e.g. from zero
+                    _ if !cmp.speedup.is_finite() => "⚪",
+                    _ if cmp.speedup > 1.0 => "🟢",
+                    _ => "🔴",
+                };
+                let arrow = match cmp.diff {
+                    diff if diff > 0.0 => " ↑",
+                    diff if diff < 0.0 => " ↓",
+                    _ => "",
+                };
+                vec![
+                    format!("{marker}{arrow}"),
+                    cmp.name,
+                    cmp.old_value,
+                    cmp.new_value,
+                    format!("{:+.2}", cmp.diff),
+                    format!("{:+.2}%", cmp.diff_ratio * 100f64),
+                    format!("{:.2}x", cmp.speedup),
+                ]
+            }),
+        )
+    }
+    fn cmp_table(cmps: Vec<Comparison>) -> Table {
         let mut table = Table::new();
         table.set_header(vec!["name", "old", "new", "diff", "diff_ratio", "speedup"]);
-        for cmp in self.all_cmps() {
+        for cmp in cmps {
             let diff_ratio = format!("{:.2}%", cmp.diff_ratio * 100f64);
             let speedup = format!("{:.2}x", cmp.speedup);
             let diff = format!("{:.2}", cmp.diff);
@@ -167,6 +1250,36 @@ impl BenchmarkCmp<'_> {
         }
         table
     }
+    /// Evaluates `thresholds` against [`BenchmarkCmp::all_cmps`], [`BenchmarkCmp::workload_cmps`]
+    /// and [`BenchmarkCmp::scheme_cmps`]. Fails if a threshold names an unknown metric.
+    pub fn check(&self, thresholds: &[Threshold]) -> Result<Vec<ThresholdCheck>, String> {
+        let mut cmps = self.all_cmps();
+        cmps.extend(self.workload_cmps());
+        cmps.extend(self.scheme_cmps());
+        thresholds
+            .iter()
+            .map(|threshold| {
+                let comparison = cmps
+                    .iter()
+                    .find(|cmp| threshold.applies_to(&cmp.name))
+                    .ok_or_else(|| {
+                        format!(
+                            "Unknown metric '{}' in threshold, expected one of {}",
+                            threshold.metric,
+                            cmps.iter()
+                                .map(|cmp| cmp.name.as_str())
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    })?;
+                Ok(ThresholdCheck {
+                    threshold: threshold.clone(),
+                    comparison: comparison.clone(),
+                    passed: threshold.is_met_by(comparison),
+                })
+            })
+            .collect()
+    }
     pub fn all_cmps(&self) -> Vec<Comparison> {
         vec![
             self.cmp_tps(),
@@ -179,156 +1292,389 @@ impl BenchmarkCmp<'_> {
             self.cmp_p99_latency(),
             self.cmp_p999_latency(),
             self.cmp_max_latency(),
+            self.cmp_phase_latency(
+                "submit_to_cert_p50_latency",
+                |stats| &stats.submit_to_cert_latency_ms.histogram,
+                0.5,
+            ),
+            self.cmp_phase_latency(
+                "submit_to_cert_p99_latency",
+                |stats| &stats.submit_to_cert_latency_ms.histogram,
+                0.99,
+            ),
+            self.cmp_phase_latency(
+                "cert_to_effects_p50_latency",
+                |stats| &stats.cert_to_effects_latency_ms.histogram,
+                0.5,
+            ),
+            self.cmp_phase_latency(
+                "cert_to_effects_p99_latency",
+                |stats| &stats.cert_to_effects_latency_ms.histogram,
+                0.99,
+            ),
+            self.cmp_phase_latency(
+                "checkpoint_p50_latency",
+                |stats| &stats.checkpoint_latency_ms.histogram,
+                0.5,
+            ),
+            self.cmp_phase_latency(
+                "checkpoint_p99_latency",
+                |stats| &stats.checkpoint_latency_ms.histogram,
+                0.99,
+            ),
         ]
     }
-    pub fn cmp_tps(&self) -> Comparison {
-        let old_tps = self.old.num_success / self.old.duration.as_secs();
-        let new_tps = self.new.num_success / self.new.duration.as_secs();
-        let diff = new_tps as i64 - old_tps as i64;
-        let diff_ratio = diff as f64 / old_tps as f64;
-        let speedup = 1.0 + diff_ratio;
-        Comparison {
-            name: "tps".to_string(),
-            old_value: format!("{:.2}", old_tps),
-            new_value: format!("{:.2}", new_tps),
-            diff,
-            diff_ratio,
-            speedup,
+    /// The comparisons of each workload which ran in both benchmarks with itself, named
+    /// `<workload>:<metric>`, e.g. `shared_counter:p99_latency`.
+    pub fn workload_cmps(&self) -> Vec<Comparison> {
+        Self::labelled_cmps(
+            self.new
+                .workloads
+                .iter()
+                .map(|w| (&w.workload, &w.stats))
+                .collect(),
+            self.old
+                .workloads
+                .iter()
+                .map(|w| (&w.workload, &w.stats))
+                .collect(),
+        )
+    }
+    /// The comparisons of each signature scheme used in both benchmarks with itself, named
+    /// `<scheme>:<metric>`, e.g. `secp256k1:p99_latency`.
+    pub fn scheme_cmps(&self) -> Vec<Comparison> {
+        Self::labelled_cmps(
+            self.new
+                .schemes
+                .iter()
+                .map(|s| (&s.scheme, &s.stats))
+                .collect(),
+            self.old
+                .schemes
+                .iter()
+                .map(|s| (&s.scheme, &s.stats))
+                .collect(),
+        )
+    }
+    fn labelled_cmps(
+        new: Vec<(&String, &BenchmarkStats)>,
+        old: Vec<(&String, &BenchmarkStats)>,
+    ) -> Vec<Comparison> {
+        let mut cmps = vec![];
+        for (label, new) in new {
+            let old = match old.iter().find(|(old_label, _)| *old_label == label) {
+                Some((_, old)) => old,
+                None => continue,
+            };
+            let labelled_cmp = BenchmarkCmp { new, old };
+            cmps.extend(labelled_cmp.all_cmps().into_iter().map(|cmp| Comparison {
+                name: format!("{}:{}", label, cmp.name),
+                ..cmp
+            }));
         }
+        cmps
+    }
+    pub fn cmp_tps(&self) -> Comparison {
+        Comparison::new("tps", self.old.tps(), self.new.tps(), true)
     }
     pub fn cmp_error_rate(&self) -> Comparison {
-        let old_error_rate = self.old.num_error / (self.old.num_error + self.old.num_success);
-        let new_error_rate = self.new.num_error / (self.new.num_error + self.new.num_success);
-        let diff = new_error_rate as i64 - old_error_rate as i64;
-        let diff_ratio = diff as f64 / old_error_rate as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "error_rate".to_string(),
-            old_value: format!("{:.2}", old_error_rate),
-            new_value: format!("{:.2}", new_error_rate),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        // In percent, as in the stats table
+        Comparison::new(
+            "error_rate",
+            self.old.error_rate() * 100.0,
+            self.new.error_rate() * 100.0,
+            false,
+        )
     }
     pub fn cmp_min_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.min() as i64;
-        let new = self.new.latency_ms.histogram.min() as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "min_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        Comparison::new(
+            "min_latency",
+            self.old.latency_ms.histogram.min() as f64,
+            self.new.latency_ms.histogram.min() as f64,
+            false,
+        )
     }
     pub fn cmp_p25_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.25) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.25) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p25_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p25_latency", |stats| &stats.latency_ms.histogram, 0.25)
     }
     pub fn cmp_p50_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.5) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.5) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p50_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p50_latency", |stats| &stats.latency_ms.histogram, 0.5)
     }
     pub fn cmp_p75_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.75) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.75) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p75_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p75_latency", |stats| &stats.latency_ms.histogram, 0.75)
     }
     pub fn cmp_p90_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.9) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.9) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p90_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p90_latency", |stats| &stats.latency_ms.histogram, 0.9)
     }
     pub fn cmp_p99_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.99) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.99) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p99_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p99_latency", |stats| &stats.latency_ms.histogram, 0.99)
     }
     pub fn cmp_p999_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.value_at_quantile(0.999) as i64;
-        let new = self.new.latency_ms.histogram.value_at_quantile(0.999) as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "p999_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
-        }
+        self.cmp_phase_latency("p999_latency", |stats| &stats.latency_ms.histogram, 0.999)
     }
     pub fn cmp_max_latency(&self) -> Comparison {
-        let old = self.old.latency_ms.histogram.max() as i64;
-        let new = self.new.latency_ms.histogram.max() as i64;
-        let diff = new - old;
-        let diff_ratio = diff as f64 / old as f64;
-        let speedup = 1.0 / (1.0 + diff_ratio);
-        Comparison {
-            name: "max_latency".to_string(),
-            old_value: format!("{:.2}", old),
-            new_value: format!("{:.2}", new),
-            diff,
-            diff_ratio,
-            speedup,
+        Comparison::new(
+            "max_latency",
+            self.old.latency_ms.histogram.max() as f64,
+            self.new.latency_ms.histogram.max() as f64,
+            false,
+        )
+    }
+    /// Compares the latency of a single transaction phase at `quantile`.
+    pub fn cmp_phase_latency(
+        &self,
+        name: &str,
+        histogram: fn(&BenchmarkStats) -> &Histogram<u64>,
+        quantile: f64,
+    ) -> Comparison {
+        let (old, new) = (histogram(self.old), histogram(self.new));
+        // Stats saved before the phase was timed have no samples, whose quantiles read as 0 and
+        // would compare as an infinite regression
+        if old.is_empty() || new.is_empty() {
+            return Comparison::new(name, 0.0, 0.0, false);
+        }
+        Comparison::new(
+            name,
+            old.value_at_quantile(quantile) as f64,
+            new.value_at_quantile(quantile) as f64,
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(duration: Duration, num_success: u64, num_error: u64) -> BenchmarkStats {
+        BenchmarkStats {
+            duration,
+            num_success,
+            num_error,
+            ..bench_driver::empty_benchmark_stats(HistogramConfig::default())
+        }
+    }
+
+    #[test]
+    fn test_tps() {
+        assert_eq!(stats(Duration::ZERO, 10, 0).tps(), 0.0);
+        assert_eq!(stats(Duration::from_secs(10), 0, 5).tps(), 0.0);
+        assert_eq!(stats(Duration::from_secs(2), 3, 0).tps(), 1.5);
+        assert_eq!(stats(Duration::from_millis(400), 1, 0).tps(), 2.5);
+    }
+
+    #[test]
+    fn test_error_rate() {
+        assert_eq!(stats(Duration::ZERO, 0, 0).error_rate(), 0.0);
+        assert_eq!(stats(Duration::from_secs(1), 3, 1).error_rate(), 0.25);
+        assert_eq!(stats(Duration::from_secs(1), 0, 2).error_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let cmp = Comparison::new("tps", 100.0, 150.0, true);
+        assert_eq!((cmp.diff, cmp.diff_ratio, cmp.speedup), (50.0, 0.5, 1.5));
+        assert_eq!(
+            (cmp.old_value.as_str(), cmp.new_value.as_str()),
+            ("100.00", "150.00")
+        );
+
+        let cmp = Comparison::new("p99_latency", 100.0, 200.0, false);
+        assert_eq!((cmp.diff, cmp.diff_ratio, cmp.speedup), (100.0, 1.0, 0.5));
+
+        let cmp = Comparison::new("tps", 0.0, 0.0, true);
+        assert_eq!((cmp.diff, cmp.diff_ratio, cmp.speedup), (0.0, 0.0, 1.0));
+
+        let cmp = Comparison::new("tps", 0.0, 12.5, true);
+        assert_eq!(cmp.diff_ratio, f64::INFINITY);
+        let cmp = Comparison::new("num_error", 0.0, -1.0, false);
+        assert_eq!(cmp.diff_ratio, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_cmp_phase_latency() {
+        let mut old = stats(Duration::from_secs(1), 1, 0);
+        let mut new = stats(Duration::from_secs(1), 1, 0);
+        new.submit_to_cert_latency_ms.histogram.record(20).unwrap();
+        let phase: fn(&BenchmarkStats) -> &Histogram<u64> =
+            |stats| &stats.submit_to_cert_latency_ms.histogram;
+
+        // A phase missing from either side compares as unchanged
+        let cmp = BenchmarkCmp {
+            new: &new,
+            old: &old,
+        }
+        .cmp_phase_latency("submit_to_cert_p50_latency", phase, 0.5);
+        assert_eq!((cmp.diff, cmp.diff_ratio, cmp.speedup), (0.0, 0.0, 1.0));
+
+        old.submit_to_cert_latency_ms.histogram.record(10).unwrap();
+        let cmp = BenchmarkCmp {
+            new: &new,
+            old: &old,
+        }
+        .cmp_phase_latency("submit_to_cert_p50_latency", phase, 0.5);
+        assert_eq!((cmp.diff, cmp.diff_ratio, cmp.speedup), (10.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_parse_load_profile() {
+        let profile: LoadProfile = "ramp:0-5000tps/60s, hold:5000tps/5m,ramp:5000-0/60s"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            profile.stages,
+            vec![
+                LoadStage::Ramp {
+                    from_tps: 0,
+                    to_tps: 5000,
+                    duration: Duration::from_secs(60),
+                },
+                LoadStage::Hold {
+                    tps: 5000,
+                    duration: Duration::from_secs(300),
+                },
+                LoadStage::Ramp {
+                    from_tps: 5000,
+                    to_tps: 0,
+                    duration: Duration::from_secs(60),
+                },
+            ]
+        );
+        let printed: Vec<String> = profile.stages.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            printed.join(",").parse::<LoadProfile>(),
+            Ok(profile.clone())
+        );
+
+        assert_eq!(profile.total_duration(), Duration::from_secs(420));
+        assert_eq!(profile.max_tps(), 5000);
+        assert_eq!(
+            profile.stage_at(Duration::from_secs(60)),
+            Some((1, Duration::ZERO))
+        );
+        assert_eq!(profile.tps_at(Duration::from_secs(30)), 2500);
+        assert_eq!(profile.tps_at(Duration::from_secs(100)), 5000);
+        assert_eq!(profile.tps_at(Duration::from_secs(390)), 2500);
+        assert_eq!(profile.tps_at(Duration::from_secs(420)), 0);
+
+        for s in [
+            "",
+            "hold:5000tps",
+            "5000tps/60s",
+            "ramp:5000tps/60s",
+            "ramp:0-abc/60s",
+            "hold:-1tps/60s",
+            "pause:10tps/60s",
+            "hold:10tps/forever",
+            "hold:10tps/60s,",
+        ] {
+            assert!(s.parse::<LoadProfile>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_max_tps_search() {
+        let probe = |num_success: u64, num_error: u64, latency_ms: u64| {
+            let mut probe_stats = stats(Duration::from_secs(10), num_success, num_error);
+            probe_stats
+                .latency_ms
+                .histogram
+                .record_n(latency_ms, num_success)
+                .unwrap();
+            probe_stats
+        };
+        let mut search = MaxTpsSearch::new(1000, 100, 0.01, Duration::from_secs(10), 10);
+        assert_eq!(search.next_tps(), Some(500));
+        assert!(search.record(500, &probe(5000, 0, 50)));
+        assert_eq!(search.next_tps(), Some(750));
+        // The p99 latency is too high
+        assert!(!search.record(750, &probe(7500, 0, 200)));
+        assert_eq!(search.next_tps(), Some(625));
+        // Too many transactions fail
+        assert!(!search.record(625, &probe(6250, 100, 50)));
+        assert_eq!(search.next_tps(), Some(562));
+        // The throughput doesn't keep up with the offered load
+        assert!(!search.record(562, &probe(4000, 0, 50)));
+        assert_eq!(search.next_tps(), Some(531));
+        assert!(!search.record(531, &probe(0, 0, 50)));
+        assert_eq!(search.next_tps(), Some(515));
+        assert!(search.record(515, &probe(5150, 0, 50)));
+        assert_eq!(search.next_tps(), Some(523));
+        assert!(search.record(523, &probe(5230, 0, 50)));
+        assert_eq!(search.next_tps(), None);
+        assert_eq!(search.max_sustainable_tps(), 523);
+
+        let search = MaxTpsSearch::new(0, 100, 0.01, Duration::from_secs(10), 0);
+        assert_eq!(search.next_tps(), None);
+        assert_eq!(search.max_sustainable_tps(), 0);
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert!(matches!(
+            "1000".parse::<Interval>(),
+            Ok(Interval::Count(1000))
+        ));
+        assert!(matches!(
+            "10m".parse::<Interval>(),
+            Ok(Interval::Time(d)) if d == Duration::from_secs(600)
+        ));
+        assert!("unbounded".parse::<Interval>().unwrap().is_unbounded());
+        for s in ["1000000|10m", "10m|1000000"] {
+            assert!(matches!(
+                s.parse::<Interval>(),
+                Ok(Interval::CountOrTime(1000000, d)) if d == Duration::from_secs(600)
+            ));
+        }
+
+        for s in [
+            "",
+            "ten",
+            "-5",
+            "10|20",
+            "1m|2m",
+            "100|unbounded",
+            "100|",
+            "|10m",
+            "1|2|3m",
+        ] {
+            assert!(s.parse::<Interval>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_parse_threshold() {
+        let threshold: Threshold = "tps>=-5%".parse().unwrap();
+        assert_eq!(
+            threshold,
+            Threshold {
+                metric: "tps".to_string(),
+                op: ThresholdOp::AtLeast,
+                percent: -5.0,
+            }
+        );
+        assert_eq!(threshold.to_string().parse::<Threshold>(), Ok(threshold));
+        assert_eq!(
+            " p99 <= +12.5% ".parse(),
+            Ok(Threshold {
+                metric: "p99".to_string(),
+                op: ThresholdOp::AtMost,
+                percent: 12.5,
+            })
+        );
+
+        for s in [
+            "",
+            "tps",
+            "tps=5%",
+            "tps>=5",
+            ">=5%",
+            "tps>=%",
+            "tps>=abc%",
+            "tps>=inf%",
+            "tps>=NaN%",
+        ] {
+            assert!(s.parse::<Threshold>().is_err(), "{s}");
         }
     }
 }
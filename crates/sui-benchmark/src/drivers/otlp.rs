@@ -0,0 +1,308 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the stats of a run over the OpenTelemetry metrics protocol (OTLP), so that they
+//! land in the observability backend that already collects the metrics of the network rather
+//! than in files to import after the fact.
+//!
+//! The stats are posted as JSON to an OTLP/HTTP receiver, e.g. an OpenTelemetry collector, once
+//! per export interval. Each export holds what happened since the previous one, with the delta
+//! aggregation temporality: the counts of succeeded and failed transactions, and the latencies
+//! as explicit bucket histograms.
+
+use anyhow::{anyhow, Result};
+use hdrhistogram::Histogram;
+use serde_json::{json, Value};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+use tracing::error;
+
+use super::{add_histogram, BenchmarkStats, HistogramConfig};
+
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const SCOPE_NAME: &str = "sui-benchmark";
+
+/// `AGGREGATION_TEMPORALITY_DELTA` of the OTLP metrics protocol.
+const AGGREGATION_TEMPORALITY_DELTA: u32 = 1;
+
+/// Upper bounds of the buckets the latencies are exported in, in milliseconds. Latencies above
+/// the last one fall in an extra bucket.
+const LATENCY_BUCKETS_MS: [u64; 17] = [
+    1, 2, 5, 10, 20, 50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000, 20_000, 50_000, 100_000,
+    200_000,
+];
+
+/// Names and descriptions of the exported latency histograms, in the order of `latencies`.
+const LATENCY_METRICS: [(&str, &str); 5] = [
+    (
+        "sui_benchmark.latency",
+        "Time from the submission of a transaction to its certified effects",
+    ),
+    (
+        "sui_benchmark.response_time",
+        "Time from when a transaction was meant to be submitted to its certified effects",
+    ),
+    (
+        "sui_benchmark.submit_to_cert_latency",
+        "Time from the submission of a transaction to its certificate",
+    ),
+    (
+        "sui_benchmark.cert_to_effects_latency",
+        "Time from the certificate of a transaction to its certified effects",
+    ),
+    (
+        "sui_benchmark.checkpoint_latency",
+        "Time from the submission of a transaction to its inclusion in a checkpoint",
+    ),
+];
+
+fn latencies(stats: &BenchmarkStats) -> [&Histogram<u64>; 5] {
+    [
+        &stats.latency_ms.histogram,
+        &stats.response_time_ms.histogram,
+        &stats.submit_to_cert_latency_ms.histogram,
+        &stats.cert_to_effects_latency_ms.histogram,
+        &stats.checkpoint_latency_ms.histogram,
+    ]
+}
+
+/// Where and how to export the stats of a run.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Base URL of the OTLP/HTTP receiver, e.g. `http://localhost:4318`. The metrics are posted
+    /// to its `/v1/metrics` path.
+    pub endpoint: String,
+    pub export_interval: Duration,
+    /// Attributes of the exported resource besides `service.name`, e.g. to tell runs apart.
+    pub resource_attributes: Vec<ResourceAttribute>,
+}
+
+/// A `key=value` attribute of the exported resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+impl FromStr for ResourceAttribute {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok(ResourceAttribute {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+            _ => Err(format!("Invalid attribute '{s}', expected key=value")),
+        }
+    }
+}
+
+/// The stats recorded since the previous export.
+struct PendingInterval {
+    /// Unix time the interval started at, in nanoseconds.
+    start_unix_nano: u64,
+    num_success: u64,
+    num_error: u64,
+    latencies: [Histogram<u64>; 5],
+}
+
+impl PendingInterval {
+    fn new(histogram_config: HistogramConfig) -> Self {
+        Self {
+            start_unix_nano: unix_nano_now(),
+            num_success: 0,
+            num_error: 0,
+            latencies: [(); 5].map(|_| histogram_config.new_histogram()),
+        }
+    }
+}
+
+/// Accumulates the stats sampled during a run, and exports them every interval.
+pub struct OtlpExporter {
+    client: reqwest::Client,
+    url: String,
+    config: OtlpConfig,
+    histogram_config: HistogramConfig,
+    pending: Mutex<PendingInterval>,
+    stop: Notify,
+}
+
+impl OtlpExporter {
+    pub fn new(config: OtlpConfig, histogram_config: HistogramConfig) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(EXPORT_TIMEOUT).build()?;
+        Ok(Self {
+            client,
+            url: format!("{}/v1/metrics", config.endpoint.trim_end_matches('/')),
+            config,
+            histogram_config,
+            pending: Mutex::new(PendingInterval::new(histogram_config)),
+            stop: Notify::new(),
+        })
+    }
+
+    /// Folds in the stats sampled by a worker since its previous sample.
+    pub fn record(&self, stats: &BenchmarkStats) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.num_success += stats.num_success;
+        pending.num_error += stats.num_error;
+        for (mine, theirs) in pending.latencies.iter_mut().zip(latencies(stats)) {
+            add_histogram(mine, theirs);
+        }
+    }
+
+    /// Posts the stats recorded since the previous export to the receiver.
+    pub async fn export(&self) -> Result<()> {
+        let pending = std::mem::replace(
+            &mut *self.pending.lock().unwrap(),
+            PendingInterval::new(self.histogram_config),
+        );
+        let request = self.export_request(&pending, unix_nano_now());
+        let response = self.client.post(&self.url).json(&request).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "OTLP receiver at {} answered {}: {}",
+                self.url,
+                response.status(),
+                response.text().await.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Exports the stats every export interval, and once more when `finish` is called.
+    pub fn spawn_exporter(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = self.config.export_interval;
+            let mut interval = time::interval_at(Instant::now() + period, period);
+            loop {
+                let stopping = tokio::select! {
+                    _ = interval.tick() => false,
+                    _ = self.stop.notified() => true,
+                };
+                // A failed export is not retried, the run goes on without its stats
+                if let Err(e) = self.export().await {
+                    error!("Failed to export the stats over OTLP: {}", e);
+                }
+                if stopping {
+                    break;
+                }
+            }
+        })
+    }
+
+    /// Has the task of `spawn_exporter` export the stats left once the run is over, and waits
+    /// for it to end.
+    pub async fn finish(&self, task: JoinHandle<()>) {
+        self.stop.notify_one();
+        let _ = task.await;
+    }
+
+    /// An `ExportMetricsServiceRequest` in the JSON encoding of the protocol.
+    fn export_request(&self, pending: &PendingInterval, end_unix_nano: u64) -> Value {
+        let start = pending.start_unix_nano.to_string();
+        let end = end_unix_nano.to_string();
+        let counter = |name: &str, description: &str, value: u64| {
+            json!({
+                "name": name,
+                "description": description,
+                "unit": "{transaction}",
+                "sum": {
+                    "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+                    "isMonotonic": true,
+                    "dataPoints": [{
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": end,
+                        "asInt": value.to_string(),
+                    }],
+                },
+            })
+        };
+        let mut metrics = vec![
+            counter(
+                "sui_benchmark.transactions_succeeded",
+                "Transactions whose effects were certified",
+                pending.num_success,
+            ),
+            counter(
+                "sui_benchmark.transactions_failed",
+                "Transactions which failed or timed out",
+                pending.num_error,
+            ),
+        ];
+        for ((name, description), histogram) in LATENCY_METRICS.iter().zip(&pending.latencies) {
+            // Phases which aren't measured by this run are left out
+            if histogram.is_empty() {
+                continue;
+            }
+            let (bucket_counts, sum) = bucket_counts(histogram);
+            metrics.push(json!({
+                "name": name,
+                "description": description,
+                "unit": "ms",
+                "histogram": {
+                    "aggregationTemporality": AGGREGATION_TEMPORALITY_DELTA,
+                    "dataPoints": [{
+                        "startTimeUnixNano": start,
+                        "timeUnixNano": end,
+                        "count": histogram.len().to_string(),
+                        "sum": sum,
+                        "min": histogram.min() as f64,
+                        "max": histogram.max() as f64,
+                        "bucketCounts": bucket_counts
+                            .iter()
+                            .map(|count| count.to_string())
+                            .collect::<Vec<_>>(),
+                        "explicitBounds": LATENCY_BUCKETS_MS
+                            .iter()
+                            .map(|bound| *bound as f64)
+                            .collect::<Vec<_>>(),
+                    }],
+                },
+            }));
+        }
+        let attributes: Vec<Value> = std::iter::once(("service.name", SCOPE_NAME))
+            .chain(
+                self.config
+                    .resource_attributes
+                    .iter()
+                    .map(|attribute| (attribute.key.as_str(), attribute.value.as_str())),
+            )
+            .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+            .collect();
+        json!({
+            "resourceMetrics": [{
+                "resource": { "attributes": attributes },
+                "scopeMetrics": [{
+                    "scope": { "name": SCOPE_NAME },
+                    "metrics": metrics,
+                }],
+            }],
+        })
+    }
+}
+
+/// The counts of `histogram` in the buckets of `LATENCY_BUCKETS_MS`, and the sum of its values.
+fn bucket_counts(histogram: &Histogram<u64>) -> (Vec<u64>, f64) {
+    let mut counts = vec![0; LATENCY_BUCKETS_MS.len() + 1];
+    let mut sum = 0.0;
+    for value in histogram.iter_recorded() {
+        let latency = value.value_iterated_to();
+        let count = value.count_at_value();
+        counts[LATENCY_BUCKETS_MS.partition_point(|bound| *bound < latency)] += count;
+        sum += latency as f64 * count as f64;
+    }
+    (counts, sum)
+}
+
+fn unix_nano_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recording of the transactions submitted during a run, and their replay, so that a tricky
+//! performance anomaly can be reproduced, or the exact same load run against several builds.
+//!
+//! A trace holds one JSON line per submitted transaction, with the time since the start of the
+//! recording it was submitted at and its BCS bytes in base64. Transactions spend the objects
+//! created by the ones before them, so a trace only replays against a network in the state it
+//! was recorded from, e.g. started again from the same genesis.
+
+use anyhow::{anyhow, Context, Result};
+use futures::{stream::FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sui_types::messages::Transaction;
+use tokio::time::{self, Instant};
+use tracing::{debug, error};
+
+use super::timeseries::TimeSeries;
+use super::{BenchmarkStats, HistogramConfig, HistogramWrapper};
+use crate::validator_proxy::ValidatorProxy;
+
+/// How many times a replayed transaction is submitted before giving up on it. A replay faster
+/// than the recording can submit a transaction before the one it depends on is executed.
+const REPLAY_MAX_ATTEMPTS: u32 = 10;
+
+const REPLAY_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Serialize, Deserialize)]
+struct TraceLine {
+    offset_us: u64,
+    tx_bytes: String,
+}
+
+/// A transaction of a trace, and when it was submitted since the start of the recording.
+pub struct TraceEntry {
+    pub offset: Duration,
+    pub transaction: Transaction,
+}
+
+/// Appends the transactions submitted through it to a trace file.
+pub struct TraceRecorder {
+    start: Instant,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl TraceRecorder {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create trace {}", path.display()))?;
+        Ok(Self {
+            start: Instant::now(),
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub fn record(&self, transaction: &Transaction) -> Result<()> {
+        let line = serde_json::to_string(&TraceLine {
+            offset_us: self.start.elapsed().as_micros() as u64,
+            tx_bytes: base64::encode(bcs::to_bytes(transaction)?),
+        })?;
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{}", line)?;
+        Ok(())
+    }
+
+    /// Writes out the buffered part of the trace, to call once the run is over.
+    pub fn flush(&self) -> Result<()> {
+        self.writer.lock().unwrap().flush()?;
+        Ok(())
+    }
+}
+
+/// Reads the trace at `path`, in the order it was recorded.
+pub fn load_trace(path: &Path) -> Result<Vec<TraceEntry>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open trace {}", path.display()))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let line: TraceLine = serde_json::from_str(&line?)
+                .with_context(|| format!("Invalid entry on line {} of the trace", index + 1))?;
+            let bytes = base64::decode(&line.tx_bytes)
+                .with_context(|| format!("Invalid transaction on line {}", index + 1))?;
+            Ok(TraceEntry {
+                offset: Duration::from_micros(line.offset_us),
+                transaction: bcs::from_bytes(&bytes)
+                    .with_context(|| format!("Invalid transaction on line {}", index + 1))?,
+            })
+        })
+        .collect()
+}
+
+/// Submits the transactions of a trace at the times they were recorded at, scaled by
+/// `time_scale`: 2.0 replays the trace over twice the time, 0.5 twice as fast.
+pub struct ReplayDriver {
+    pub trace: Vec<TraceEntry>,
+    pub time_scale: f64,
+    pub timeseries_path: Option<PathBuf>,
+    pub histogram_config: HistogramConfig,
+}
+
+impl ReplayDriver {
+    pub fn new(trace: Vec<TraceEntry>, time_scale: f64) -> ReplayDriver {
+        ReplayDriver {
+            trace,
+            time_scale,
+            timeseries_path: None,
+            histogram_config: HistogramConfig::default(),
+        }
+    }
+
+    /// Also write the throughput and latency of each second of the replay as CSV rows to `path`.
+    pub fn with_timeseries(mut self, path: PathBuf) -> ReplayDriver {
+        self.timeseries_path = Some(path);
+        self
+    }
+
+    /// Record the latencies in histograms bounded by `config` rather than the default ones.
+    pub fn with_histogram_config(mut self, config: HistogramConfig) -> ReplayDriver {
+        self.histogram_config = config;
+        self
+    }
+
+    pub async fn run(self, proxy: Arc<dyn ValidatorProxy>) -> Result<BenchmarkStats> {
+        if self.trace.is_empty() {
+            return Err(anyhow!("The trace has no transactions to replay"));
+        }
+        let mut latency_histogram = HistogramWrapper::new(self.histogram_config);
+        let mut response_time_histogram = HistogramWrapper::new(self.histogram_config);
+        let mut submit_to_cert_histogram = HistogramWrapper::new(self.histogram_config);
+        let mut cert_to_effects_histogram = HistogramWrapper::new(self.histogram_config);
+        let mut num_success = 0;
+        let mut num_error = 0;
+        let mut futures = FuturesUnordered::new();
+        let timeseries = match &self.timeseries_path {
+            Some(path) => Some(Arc::new(TimeSeries::create(path)?)),
+            None => None,
+        };
+        let timeseries_task = timeseries.clone().map(TimeSeries::spawn_writer);
+        let mut trace = self.trace.into_iter().peekable();
+        eprintln!("Replaying {} transactions", trace.len());
+        let start = Instant::now();
+        loop {
+            let next_submission = trace
+                .peek()
+                .map(|entry| start + entry.offset.mul_f64(self.time_scale));
+            tokio::select! {
+                _ = time::sleep_until(next_submission.unwrap_or_else(Instant::now)), if next_submission.is_some() => {
+                    let transaction = trace.next().unwrap().transaction;
+                    let proxy = proxy.clone();
+                    let intended_start = next_submission.unwrap();
+                    let timeseries = timeseries.clone();
+                    futures.push(async move {
+                        let start = Instant::now();
+                        let mut attempts = 0;
+                        loop {
+                            attempts += 1;
+                            if let Some(timeseries) = &timeseries {
+                                timeseries.record_submitted();
+                            }
+                            let result = proxy.execute_transaction(transaction.clone()).await;
+                            if let Some(timeseries) = &timeseries {
+                                match &result {
+                                    Ok(_) => timeseries.record_success(start.elapsed()),
+                                    Err(_) => timeseries.record_failure(),
+                                }
+                            }
+                            match result {
+                                Ok(executed) => {
+                                    return Ok((start.elapsed(), intended_start.elapsed(), executed.submit_to_cert))
+                                }
+                                Err(err) if attempts < REPLAY_MAX_ATTEMPTS => {
+                                    debug!("Retrying {:?}: {}", transaction.digest(), err);
+                                    time::sleep(REPLAY_RETRY_DELAY).await;
+                                }
+                                Err(err) => return Err(err),
+                            }
+                        }
+                    });
+                }
+                Some(result) = futures.next() => match result {
+                    Ok((latency, response_time, submit_to_cert)) => {
+                        num_success += 1;
+                        let total_ms = latency.as_millis().try_into().unwrap();
+                        latency_histogram.histogram.saturating_record(total_ms);
+                        let response_time_ms = response_time.as_millis().try_into().unwrap();
+                        response_time_histogram.histogram.saturating_record(response_time_ms);
+                        if let Some(submit_to_cert) = submit_to_cert {
+                            let submit_to_cert_ms = submit_to_cert.as_millis().try_into().unwrap();
+                            submit_to_cert_histogram.histogram.saturating_record(submit_to_cert_ms);
+                            cert_to_effects_histogram
+                                .histogram
+                                .saturating_record(total_ms.saturating_sub(submit_to_cert_ms));
+                        }
+                    }
+                    Err(err) => {
+                        error!("Failed to replay transaction: {}", err);
+                        num_error += 1;
+                    }
+                },
+                else => break,
+            }
+        }
+        if let (Some(timeseries), Some(task)) = (timeseries, timeseries_task) {
+            task.abort();
+            timeseries.finish()?;
+        }
+        Ok(BenchmarkStats {
+            duration: start.elapsed(),
+            num_error,
+            num_success,
+            latency_ms: latency_histogram,
+            response_time_ms: response_time_histogram,
+            submit_to_cert_latency_ms: submit_to_cert_histogram,
+            cert_to_effects_latency_ms: cert_to_effects_histogram,
+            checkpoint_latency_ms: HistogramWrapper::new(self.histogram_config),
+            stages: vec![],
+            workloads: vec![],
+            schemes: vec![],
+            max_sustainable_tps: None,
+            faults: vec![],
+            gas_pool: None,
+            quantiles: vec![],
+        })
+    }
+}
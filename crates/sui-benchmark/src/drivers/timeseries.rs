@@ -0,0 +1,158 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Throughput and latency of each second of a run, appended to a CSV file as the run goes. The
+//! end-of-run histograms average out a throughput collapse or a latency spike, one row per
+//! second shows when it happened and for how long.
+
+use anyhow::{Context, Result};
+use hdrhistogram::Histogram;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+use tracing::error;
+
+const HEADER: &str = "timestamp,elapsed_s,submitted,succeeded,failed,latency_ms_p50,latency_ms_p99";
+
+/// What happened during one second. Submissions count in the second they were made, successes
+/// and failures in the second they completed.
+struct Second {
+    submitted: u64,
+    succeeded: u64,
+    failed: u64,
+    latency_ms: Histogram<u64>,
+}
+
+impl Default for Second {
+    fn default() -> Self {
+        Self {
+            submitted: 0,
+            succeeded: 0,
+            failed: 0,
+            latency_ms: Histogram::<u64>::new_with_max(100000, 2).unwrap(),
+        }
+    }
+}
+
+struct Rows {
+    writer: BufWriter<File>,
+    /// The second of the next row to write.
+    next: u64,
+}
+
+/// Collects the outcomes of the transactions of a run by the second they happened in.
+pub struct TimeSeries {
+    start: Instant,
+    /// Unix time of `start`, in seconds.
+    start_timestamp: u64,
+    seconds: Mutex<BTreeMap<u64, Second>>,
+    rows: Mutex<Rows>,
+}
+
+impl TimeSeries {
+    /// Creates the CSV file at `path` with its header, the first row being the second from now.
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create time series {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{}", HEADER)?;
+        writer.flush()?;
+        Ok(Self {
+            start: Instant::now(),
+            start_timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            seconds: Mutex::new(BTreeMap::new()),
+            rows: Mutex::new(Rows { writer, next: 0 }),
+        })
+    }
+
+    pub fn record_submitted(&self) {
+        self.with_current_second(|second| second.submitted += 1);
+    }
+
+    pub fn record_success(&self, latency: Duration) {
+        let latency_ms = latency.as_millis().try_into().unwrap_or(u64::MAX);
+        self.with_current_second(|second| {
+            second.succeeded += 1;
+            second.latency_ms.saturating_record(latency_ms);
+        });
+    }
+
+    pub fn record_failure(&self) {
+        self.with_current_second(|second| second.failed += 1);
+    }
+
+    /// Writes a row for each second over since the last call. Seconds without any transaction
+    /// get a row too, so that stalls show up as such.
+    pub fn write_elapsed(&self) -> Result<()> {
+        self.write_until(self.start.elapsed().as_secs())
+    }
+
+    /// Writes the rows left once the run is over, the last one for the second in progress.
+    pub fn finish(&self) -> Result<()> {
+        self.write_until(self.start.elapsed().as_secs() + 1)
+    }
+
+    /// Writes the rows of the seconds as they go by, until the returned task is aborted.
+    pub fn spawn_writer(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval =
+                time::interval_at(self.start + Duration::from_secs(1), Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.write_elapsed() {
+                    error!("Failed to write the time series: {}", e);
+                }
+            }
+        })
+    }
+
+    fn with_current_second(&self, f: impl FnOnce(&mut Second)) {
+        let index = self.start.elapsed().as_secs();
+        f(self.seconds.lock().unwrap().entry(index).or_default());
+    }
+
+    fn write_until(&self, end: u64) -> Result<()> {
+        let mut rows = self.rows.lock().unwrap();
+        while rows.next < end {
+            let index = rows.next;
+            let second = self
+                .seconds
+                .lock()
+                .unwrap()
+                .remove(&index)
+                .unwrap_or_default();
+            // Percentiles are left empty rather than 0 when nothing completed
+            let (p50, p99) = if second.succeeded > 0 {
+                (
+                    second.latency_ms.value_at_quantile(0.5).to_string(),
+                    second.latency_ms.value_at_quantile(0.99).to_string(),
+                )
+            } else {
+                (String::new(), String::new())
+            };
+            writeln!(
+                rows.writer,
+                "{},{},{},{},{},{},{}",
+                self.start_timestamp + index,
+                index,
+                second.submitted,
+                second.succeeded,
+                second.failed,
+                p50,
+                p99
+            )?;
+            rows.next += 1;
+        }
+        // Flushed every second, so that the file can be followed while the run goes
+        rows.writer.flush()?;
+        Ok(())
+    }
+}
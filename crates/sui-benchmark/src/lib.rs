@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod drivers;
+pub mod validator_proxy;
 pub mod workloads;
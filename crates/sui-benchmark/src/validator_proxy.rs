@@ -0,0 +1,318 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use sui_core::{
+    authority_aggregator::AuthorityAggregator,
+    authority_client::NetworkAuthorityClient,
+    quorum_driver::{QuorumDriver, QuorumDriverHandler, QuorumDriverMetrics},
+};
+use sui_json_rpc_types::{OwnedObjectRef, SuiExecuteTransactionResponse, SuiTransactionEffects};
+use sui_sdk::SuiClient;
+use sui_types::{
+    base_types::{AuthorityName, ObjectID, ObjectRef, TransactionDigest},
+    messages::{ExecuteTransactionRequestType, Transaction, TransactionEffects},
+    messages_checkpoint::{CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber},
+    object::{Object, ObjectRead, Owner},
+};
+use tokio::time::Instant;
+use tracing::error;
+
+use crate::drivers::replay::TraceRecorder;
+
+/// Where the benchmark submits its transactions, e.g. `rpc:https://fullnode.devnet.sui.io:443`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchTarget {
+    /// The validators of the gateway config, driven by an embedded quorum driver.
+    Validators,
+    /// A fullnode or gateway JSON-RPC endpoint, which executes the transactions signed by the
+    /// benchmark.
+    Rpc(String),
+}
+
+impl FromStr for BenchTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            _ if s == "validators" => Ok(BenchTarget::Validators),
+            Some(("rpc", url)) if !url.is_empty() => Ok(BenchTarget::Rpc(url.to_string())),
+            _ => Err(format!(
+                "Unknown target '{s}', expected validators or rpc:<url>"
+            )),
+        }
+    }
+}
+
+/// The effects of a transaction which the workloads need, whichever way it was executed.
+#[derive(Debug, Clone)]
+pub struct ExecutionEffects {
+    pub created: Vec<(ObjectRef, Owner)>,
+    pub mutated: Vec<(ObjectRef, Owner)>,
+    pub gas_object: (ObjectRef, Owner),
+}
+
+impl From<TransactionEffects> for ExecutionEffects {
+    fn from(effects: TransactionEffects) -> Self {
+        Self {
+            created: effects.created,
+            mutated: effects.mutated,
+            gas_object: effects.gas_object,
+        }
+    }
+}
+
+impl From<SuiTransactionEffects> for ExecutionEffects {
+    fn from(effects: SuiTransactionEffects) -> Self {
+        let to_refs = |refs: Vec<OwnedObjectRef>| -> Vec<(ObjectRef, Owner)> {
+            refs.into_iter()
+                .map(|o| (o.reference.to_object_ref(), o.owner))
+                .collect()
+        };
+        Self {
+            created: to_refs(effects.created),
+            mutated: to_refs(effects.mutated),
+            gas_object: (
+                effects.gas_object.reference.to_object_ref(),
+                effects.gas_object.owner,
+            ),
+        }
+    }
+}
+
+pub struct ExecutedTransaction {
+    pub effects: ExecutionEffects,
+    /// Time from the submission to the certificate, unknown when the target executes the
+    /// transaction in a single call.
+    pub submit_to_cert: Option<Duration>,
+    /// Validators which signed the certificate, if known.
+    pub tx_cert_signers: Vec<AuthorityName>,
+    /// Validators which signed the effects, if known.
+    pub effects_cert_signers: Vec<AuthorityName>,
+}
+
+/// What the benchmark reads objects from and submits its transactions to.
+#[async_trait]
+pub trait ValidatorProxy: Send + Sync {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object>;
+
+    /// Executes a transaction signed by the benchmark, returning once its effects are certified.
+    async fn execute_transaction(&self, tx: Transaction) -> Result<ExecutedTransaction>;
+
+    /// The sequence number and the transactions of checkpoint `seq`, or of the latest
+    /// checkpoint if `None`. `None` if there is no such checkpoint yet.
+    async fn get_checkpoint_transactions(
+        &self,
+        seq: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<(CheckpointSequenceNumber, Vec<TransactionDigest>)>>;
+
+    /// A proxy to the same target for another worker, which doesn't queue behind this one.
+    fn clone_new(&self) -> Box<dyn ValidatorProxy>;
+}
+
+/// Submits the transactions to the validators through an embedded quorum driver.
+pub struct LocalValidatorAggregatorProxy {
+    aggregator: AuthorityAggregator<NetworkAuthorityClient>,
+    _qd_handler: QuorumDriverHandler<NetworkAuthorityClient>,
+    qd: Arc<QuorumDriver<NetworkAuthorityClient>>,
+}
+
+impl LocalValidatorAggregatorProxy {
+    pub fn from_aggregator(aggregator: AuthorityAggregator<NetworkAuthorityClient>) -> Self {
+        let qd_handler =
+            QuorumDriverHandler::new(aggregator.clone(), QuorumDriverMetrics::new_for_tests());
+        let qd = qd_handler.clone_quorum_driver();
+        Self {
+            aggregator,
+            _qd_handler: qd_handler,
+            qd,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatorProxy for LocalValidatorAggregatorProxy {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
+        match self.aggregator.get_object_info_execute(object_id).await? {
+            ObjectRead::Exists(_, object, _) => Ok(object),
+            other => Err(anyhow!("Object {object_id} is not available: {other:?}")),
+        }
+    }
+
+    async fn execute_transaction(&self, tx: Transaction) -> Result<ExecutedTransaction> {
+        // Drive both phases ourselves to time them separately
+        let start = Instant::now();
+        let cert = self.qd.process_transaction(tx).await?;
+        let submit_to_cert = start.elapsed();
+        let (cert, effects) = self.qd.process_certificate(cert).await?;
+        let committee = &self.aggregator.committee;
+        Ok(ExecutedTransaction {
+            tx_cert_signers: cert
+                .auth_sign_info
+                .authorities(committee)
+                .filter_map(|name| name.ok().copied())
+                .collect(),
+            effects_cert_signers: effects
+                .auth_signature
+                .authorities(committee)
+                .filter_map(|name| name.ok().copied())
+                .collect(),
+            effects: effects.effects.into(),
+            submit_to_cert: Some(submit_to_cert),
+        })
+    }
+
+    async fn get_checkpoint_transactions(
+        &self,
+        seq: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<(CheckpointSequenceNumber, Vec<TransactionDigest>)>> {
+        // Validators store checkpoints at their own pace, the first one which has it will do
+        let mut errors = vec![];
+        for client in self.aggregator.authority_clients.values() {
+            match client
+                .handle_checkpoint(CheckpointRequest::authenticated(seq, true))
+                .await
+            {
+                Ok(CheckpointResponse::AuthenticatedCheckpoint {
+                    checkpoint: Some(checkpoint),
+                    contents: Some(contents),
+                }) => {
+                    return Ok(Some((
+                        checkpoint.summary().sequence_number,
+                        contents.iter().map(|digests| digests.transaction).collect(),
+                    )))
+                }
+                Ok(_) => continue,
+                Err(err) => errors.push(err),
+            }
+        }
+        if errors.len() == self.aggregator.authority_clients.len() {
+            return Err(anyhow!(
+                "No validator returned checkpoint {seq:?}: {errors:?}"
+            ));
+        }
+        Ok(None)
+    }
+
+    fn clone_new(&self) -> Box<dyn ValidatorProxy> {
+        // A new quorum driver, otherwise all the workers share the same task
+        Box::new(Self::from_aggregator(self.aggregator.clone()))
+    }
+}
+
+/// Records the transactions submitted through another proxy to a trace, to replay them later.
+pub struct RecordingProxy {
+    inner: Arc<dyn ValidatorProxy>,
+    recorder: Arc<TraceRecorder>,
+}
+
+impl RecordingProxy {
+    pub fn new(inner: Arc<dyn ValidatorProxy>, recorder: Arc<TraceRecorder>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+#[async_trait]
+impl ValidatorProxy for RecordingProxy {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
+        self.inner.get_object(object_id).await
+    }
+
+    async fn execute_transaction(&self, tx: Transaction) -> Result<ExecutedTransaction> {
+        // Retries are recorded too, the trace is what the target saw
+        if let Err(e) = self.recorder.record(&tx) {
+            error!("Failed to record transaction {:?}: {}", tx.digest(), e);
+        }
+        self.inner.execute_transaction(tx).await
+    }
+
+    async fn get_checkpoint_transactions(
+        &self,
+        seq: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<(CheckpointSequenceNumber, Vec<TransactionDigest>)>> {
+        self.inner.get_checkpoint_transactions(seq).await
+    }
+
+    fn clone_new(&self) -> Box<dyn ValidatorProxy> {
+        Box::new(Self {
+            inner: Arc::from(self.inner.clone_new()),
+            recorder: self.recorder.clone(),
+        })
+    }
+}
+
+/// Submits the transactions through the JSON-RPC API of a fullnode or a gateway.
+pub struct FullNodeProxy {
+    sui_client: Arc<SuiClient>,
+}
+
+impl FullNodeProxy {
+    pub async fn from_url(http_url: &str) -> Result<Self> {
+        let sui_client = SuiClient::new_rpc_client(http_url, None).await?;
+        Ok(Self {
+            sui_client: Arc::new(sui_client),
+        })
+    }
+}
+
+#[async_trait]
+impl ValidatorProxy for FullNodeProxy {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object> {
+        self.sui_client
+            .read_api()
+            .get_object(object_id)
+            .await?
+            .into_object()?
+            .try_into()
+    }
+
+    async fn execute_transaction(&self, tx: Transaction) -> Result<ExecutedTransaction> {
+        let effects = if self.sui_client.is_gateway() {
+            let response = self
+                .sui_client
+                .quorum_driver()
+                .execute_transaction(tx)
+                .await?;
+            response.effects
+        } else {
+            match self
+                .sui_client
+                .quorum_driver()
+                .execute_transaction_by_fullnode(
+                    tx,
+                    ExecuteTransactionRequestType::WaitForEffectsCert,
+                )
+                .await?
+            {
+                SuiExecuteTransactionResponse::EffectsCert { effects, .. } => effects.effects,
+                other => return Err(anyhow!("Unexpected response: {other:?}")),
+            }
+        };
+        // The certificate isn't returned on its own, only the end-to-end latency is known
+        Ok(ExecutedTransaction {
+            effects: effects.into(),
+            submit_to_cert: None,
+            tx_cert_signers: vec![],
+            effects_cert_signers: vec![],
+        })
+    }
+
+    async fn get_checkpoint_transactions(
+        &self,
+        _seq: Option<CheckpointSequenceNumber>,
+    ) -> Result<Option<(CheckpointSequenceNumber, Vec<TransactionDigest>)>> {
+        Err(anyhow!(
+            "The transactions of a checkpoint are not available through the JSON-RPC API"
+        ))
+    }
+
+    fn clone_new(&self) -> Box<dyn ValidatorProxy> {
+        // The HTTP client pools its connections, so the workers can share it
+        Box::new(Self {
+            sui_client: self.sui_client.clone(),
+        })
+    }
+}
@@ -0,0 +1,304 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Gas for the accounts of the workloads, split from the primary gas coin ahead of time. Funding
+//! each account straight from the primary coin takes one transaction on that coin per account,
+//! so setting up thousands of payloads serializes on it. The pool splits the primary coin into
+//! many coins in a few transactions instead, and hands them out to the accounts concurrently.
+
+use anyhow::{anyhow, Result};
+use move_core_types::identifier::IdentStr;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    coin::{COIN_JOIN_VEC_FUNC_NAME, COIN_MODULE_NAME, COIN_SPLIT_VEC_FUNC_NAME},
+    crypto::{AccountKeyPair, Signature},
+    gas::MAX_GAS_BUDGET,
+    gas_coin::{GasCoin, GAS},
+    messages::{CallArg, ObjectArg, Transaction},
+    object::Owner,
+    transaction_builder::{pure, TransactionBuilder},
+    SUI_FRAMEWORK_OBJECT_ID,
+};
+use test_utils::messages::{make_transfer_sui_transaction, MAX_GAS};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time;
+use tracing::error;
+
+use super::workload::{get_latest, transfer_sui_for_testing, MAX_GAS_FOR_TESTING};
+use crate::validator_proxy::ValidatorProxy;
+
+/// Coins split from the primary coin per transaction, to stay within the gas budget.
+const SPLIT_BATCH_SIZE: usize = 100;
+
+/// Coins worth less than this fraction of a fresh pool coin are merged back into the primary
+/// coin instead of being leased again.
+const DUST_FRACTION: u64 = 10;
+
+/// What the pool did over a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasPoolStats {
+    /// Coins handed out to the workloads.
+    pub leased: u64,
+    /// Leases which found the pool empty, and waited for more coins to be split from the
+    /// primary coin. A pool sized for the run is never exhausted.
+    pub exhausted: u64,
+    /// Coins given back too small to lease again, and merged into the primary coin.
+    pub dust_merged: u64,
+}
+
+impl GasPoolStats {
+    /// Folds in the stats of a pool of another stress client.
+    pub fn merge(&mut self, other: &GasPoolStats) {
+        self.leased += other.leased;
+        self.exhausted += other.exhausted;
+        self.dust_merged += other.dust_merged;
+    }
+}
+
+struct PoolCoins {
+    primary_gas: ObjectRef,
+    /// Pays for the splits and merges, since the primary coin can't pay for its own.
+    pool_gas: ObjectRef,
+    /// Coins to lease, with a lower bound of their value.
+    coins: Vec<(ObjectRef, u64)>,
+    dust: Vec<ObjectRef>,
+}
+
+/// Coins of `coin_value` owned by the primary gas account, leased to the workloads.
+pub struct GasPool {
+    owner: SuiAddress,
+    keypair: Arc<AccountKeyPair>,
+    coin_value: u64,
+    framework: ObjectRef,
+    coins: Mutex<PoolCoins>,
+    leased: AtomicU64,
+    exhausted: AtomicU64,
+    dust_merged: AtomicU64,
+}
+
+impl GasPool {
+    /// Splits `size` coins of `coin_value` from `primary_gas`, owned by `owner`.
+    pub async fn new(
+        primary_gas: ObjectRef,
+        owner: SuiAddress,
+        keypair: Arc<AccountKeyPair>,
+        size: usize,
+        coin_value: u64,
+        proxy: &dyn ValidatorProxy,
+    ) -> Result<GasPool> {
+        let framework = proxy
+            .get_object(SUI_FRAMEWORK_OBJECT_ID)
+            .await?
+            .compute_object_reference();
+        let (primary_gas, pool_gas) = transfer_sui_for_testing(
+            (primary_gas, Owner::AddressOwner(owner)),
+            &keypair,
+            MAX_GAS_FOR_TESTING,
+            owner,
+            proxy,
+        )
+        .await
+        .ok_or_else(|| anyhow!("Failed to create the gas of the gas pool"))?;
+        let pool = GasPool {
+            owner,
+            keypair,
+            coin_value,
+            framework,
+            coins: Mutex::new(PoolCoins {
+                primary_gas,
+                pool_gas,
+                coins: vec![],
+                dust: vec![],
+            }),
+            leased: AtomicU64::new(0),
+            exhausted: AtomicU64::new(0),
+            dust_merged: AtomicU64::new(0),
+        };
+        eprintln!("Splitting {size} gas coins from the primary gas coin..");
+        {
+            let mut coins = pool.coins.lock().await;
+            while coins.coins.len() < size {
+                let batch = std::cmp::min(SPLIT_BATCH_SIZE, size - coins.coins.len());
+                pool.split(&mut coins, batch, proxy).await?;
+            }
+        }
+        Ok(pool)
+    }
+
+    pub fn stats(&self) -> GasPoolStats {
+        GasPoolStats {
+            leased: self.leased.load(Ordering::Relaxed),
+            exhausted: self.exhausted.load(Ordering::Relaxed),
+            dust_merged: self.dust_merged.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Takes a coin out of the pool, owned by the primary gas account, with a lower bound of its
+    /// value. Splits more coins from the primary coin if the pool is empty.
+    pub async fn lease(&self, proxy: &dyn ValidatorProxy) -> Result<(ObjectRef, u64)> {
+        let mut coins = self.coins.lock().await;
+        if coins.coins.is_empty() {
+            self.exhausted.fetch_add(1, Ordering::Relaxed);
+            self.split(&mut coins, SPLIT_BATCH_SIZE, proxy).await?;
+        }
+        self.leased.fetch_add(1, Ordering::Relaxed);
+        Ok(coins.coins.pop().unwrap())
+    }
+
+    /// Leases a coin and transfers it to `recipient`, whole for it to pay for its transactions,
+    /// or only `amount` of it, the rest going back to the pool. Returns the reference of the
+    /// transferred coin.
+    pub async fn lease_to(
+        &self,
+        recipient: SuiAddress,
+        amount: Option<u64>,
+        proxy: &dyn ValidatorProxy,
+    ) -> Option<ObjectRef> {
+        let (coin, value) = match self.lease(proxy).await {
+            Ok(coin) => coin,
+            Err(err) => {
+                error!("Failed to lease a gas coin: {:?}", err);
+                return None;
+            }
+        };
+        // The coin pays for its own transfer, so the transfers don't contend on any coin
+        let tx = make_transfer_sui_transaction(
+            coin,
+            recipient,
+            amount,
+            self.owner,
+            self.keypair.as_ref(),
+        );
+        match proxy.execute_transaction(tx).await {
+            Ok(executed) => {
+                let effects = executed.effects;
+                match amount {
+                    None => Some(effects.gas_object.0),
+                    Some(amount) => {
+                        // At most the gas budget was charged, so this is still a lower bound
+                        let remaining = value.saturating_sub(amount).saturating_sub(MAX_GAS);
+                        self.release(effects.gas_object.0, remaining).await;
+                        effects.created.get(0).map(|(minted, _)| *minted)
+                    }
+                }
+            }
+            Err(err) => {
+                error!("Failed to transfer gas coin {}: {:?}", coin.0, err);
+                // The coin may still be usable, if the transfer never went through
+                if let Some(object) = get_latest(coin.0, proxy).await {
+                    if object.owner == Owner::AddressOwner(self.owner) {
+                        if let Ok(gas) = GasCoin::try_from(&object) {
+                            self.release(object.compute_object_reference(), gas.value())
+                                .await;
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Gives back a leased coin, still owned by the primary gas account and now worth `value`.
+    pub async fn release(&self, coin: ObjectRef, value: u64) {
+        let mut coins = self.coins.lock().await;
+        if value < self.coin_value / DUST_FRACTION {
+            coins.dust.push(coin);
+        } else {
+            coins.coins.push((coin, value));
+        }
+    }
+
+    /// Merges the coins given back too small to lease into the primary coin.
+    pub async fn merge_dust(&self, proxy: &dyn ValidatorProxy) -> Result<()> {
+        let mut coins = self.coins.lock().await;
+        if coins.dust.is_empty() {
+            return Ok(());
+        }
+        let dust: Vec<ObjectArg> = coins
+            .dust
+            .iter()
+            .map(|coin| ObjectArg::ImmOrOwnedObject(*coin))
+            .collect();
+        let num_dust = dust.len() as u64;
+        let tx = self.move_call(&coins, COIN_JOIN_VEC_FUNC_NAME, CallArg::ObjVec(dust))?;
+        let effects = proxy.execute_transaction(tx).await?.effects;
+        coins.pool_gas = effects.gas_object.0;
+        coins.primary_gas = find_mutated(&effects.mutated, coins.primary_gas)?;
+        coins.dust.clear();
+        self.dust_merged.fetch_add(num_dust, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Merges the dust every `period` until the returned task is aborted.
+    pub fn spawn_dust_merger(
+        self: Arc<Self>,
+        proxy: Arc<dyn ValidatorProxy>,
+        period: Duration,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(err) = self.merge_dust(proxy.as_ref()).await {
+                    error!("Failed to merge the dust of the gas pool: {:?}", err);
+                }
+            }
+        })
+    }
+
+    async fn split(
+        &self,
+        coins: &mut PoolCoins,
+        count: usize,
+        proxy: &dyn ValidatorProxy,
+    ) -> Result<()> {
+        let amounts = vec![self.coin_value; count];
+        let tx = self.move_call(coins, COIN_SPLIT_VEC_FUNC_NAME, pure(&amounts)?)?;
+        let effects = proxy.execute_transaction(tx).await?.effects;
+        coins.pool_gas = effects.gas_object.0;
+        coins.primary_gas = find_mutated(&effects.mutated, coins.primary_gas)?;
+        coins.coins.extend(
+            effects
+                .created
+                .iter()
+                .map(|(coin, _)| (*coin, self.coin_value)),
+        );
+        Ok(())
+    }
+
+    /// A call to `function` of the coin module on the primary coin, paid for by the pool gas.
+    fn move_call(
+        &self,
+        coins: &PoolCoins,
+        function: &IdentStr,
+        argument: CallArg,
+    ) -> Result<Transaction> {
+        let mut builder = TransactionBuilder::new(self.owner, *MAX_GAS_BUDGET);
+        builder.move_call(
+            self.framework,
+            COIN_MODULE_NAME.to_owned(),
+            function.to_owned(),
+            vec![GAS::type_tag()],
+            vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(coins.primary_gas)),
+                argument,
+            ],
+        );
+        let data = builder.build(coins.pool_gas)?;
+        let signature = Signature::new(&data, self.keypair.as_ref());
+        Ok(Transaction::new(data, signature))
+    }
+}
+
+fn find_mutated(mutated: &[(ObjectRef, Owner)], object: ObjectRef) -> Result<ObjectRef> {
+    mutated
+        .iter()
+        .find(|(reference, _)| reference.0 == object.0)
+        .map(|(reference, _)| *reference)
+        .ok_or_else(|| anyhow!("Object {} was not mutated", object.0))
+}
@@ -1,6 +1,9 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod gas_pool;
+pub mod object_store;
+pub mod publish_package;
 pub mod shared_counter;
 pub mod transfer_object;
 pub mod workload;
@@ -0,0 +1,231 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Workloads which create many objects with sizeable payloads, to benchmark storage writes and
+//! the growth of the object store rather than the transfer of a few small objects. Either each
+//! transaction mints a batch of objects owned by its sender, like an NFT drop, or it adds a batch
+//! of objects to a collection which owns them, the collection growing for the whole run.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use rand::rngs::StdRng;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::{get_key_pair_from_rng, EmptySignInfo, Signature, SuiKeyPair},
+    gas::MAX_GAS_BUDGET,
+    messages::{CallArg, ObjectArg, Transaction, TransactionEnvelope},
+    object::Owner,
+    transaction_builder::{pure, TransactionBuilder},
+};
+
+use super::gas_pool::GasPool;
+use super::shared_counter::publish_basics_package;
+use super::workload::{
+    submit_transaction, Gas, KeyScheme, KeySchemeMix, Payload, Workload, WorkloadType,
+};
+use crate::validator_proxy::ValidatorProxy;
+
+const MODULE_NAME: &str = "object_store";
+
+/// Shape of the objects created by each transaction of the object store workloads.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectStoreConfig {
+    /// Bytes of payload of each object.
+    pub payload_size: u64,
+    /// Objects created by each transaction.
+    pub fan_out: u64,
+}
+
+pub struct ObjectStoreTestPayload {
+    package_ref: ObjectRef,
+    /// The collection grown by the transactions, or `None` to mint objects owned by the sender.
+    collection: Option<ObjectRef>,
+    config: ObjectStoreConfig,
+    gas: Gas,
+    sender: SuiAddress,
+    keypair: Arc<SuiKeyPair>,
+}
+
+impl Payload for ObjectStoreTestPayload {
+    fn make_new_payload(
+        self: Box<Self>,
+        new_object: ObjectRef,
+        new_gas: ObjectRef,
+    ) -> Box<dyn Payload> {
+        Box::new(ObjectStoreTestPayload {
+            package_ref: self.package_ref,
+            collection: self.collection.map(|_| new_object),
+            config: self.config,
+            gas: (new_gas, self.gas.1),
+            sender: self.sender,
+            keypair: self.keypair.clone(),
+        })
+    }
+    fn make_transaction(&self) -> TransactionEnvelope<EmptySignInfo> {
+        let mut arguments = vec![
+            pure(&self.config.fan_out).unwrap(),
+            pure(&self.config.payload_size).unwrap(),
+        ];
+        let function = match self.collection {
+            Some(collection) => {
+                arguments.insert(0, CallArg::Object(ObjectArg::ImmOrOwnedObject(collection)));
+                "grow"
+            }
+            None => "mint",
+        };
+        make_object_store_transaction(
+            self.package_ref,
+            function,
+            arguments,
+            self.gas.0,
+            self.sender,
+            &self.keypair,
+        )
+    }
+    fn get_object_id(&self) -> ObjectID {
+        // Minting only mutates the gas object, so track that across transactions
+        match self.collection {
+            Some(collection) => collection.0,
+            None => self.gas.0 .0,
+        }
+    }
+    fn get_workload_type(&self) -> WorkloadType {
+        match self.collection {
+            Some(_) => WorkloadType::GrowCollection,
+            None => WorkloadType::MintObjects,
+        }
+    }
+    fn get_key_scheme(&self) -> KeyScheme {
+        KeyScheme::of(&self.keypair)
+    }
+}
+
+fn make_object_store_transaction(
+    package_ref: ObjectRef,
+    function: &str,
+    arguments: Vec<CallArg>,
+    gas: ObjectRef,
+    sender: SuiAddress,
+    keypair: &SuiKeyPair,
+) -> Transaction {
+    // Large payloads take far more than the usual test budget to build and store
+    let mut builder = TransactionBuilder::new(sender, *MAX_GAS_BUDGET);
+    builder.move_call(
+        package_ref,
+        MODULE_NAME.parse().unwrap(),
+        function.parse().unwrap(),
+        vec![],
+        arguments,
+    );
+    let data = builder.build(gas).unwrap();
+    let signature = Signature::new(&data, keypair);
+    Transaction::new(data, signature)
+}
+
+/// Mints objects owned by the sender of each transaction for `MintObjects`, or adds objects to
+/// a collection per payload for `GrowCollection`.
+pub struct ObjectStoreWorkload {
+    workload_type: WorkloadType,
+    config: ObjectStoreConfig,
+    pub gas_pool: Arc<GasPool>,
+    pub basics_package_ref: Option<ObjectRef>,
+    key_schemes: KeySchemeMix,
+    rng: Mutex<StdRng>,
+}
+
+impl ObjectStoreWorkload {
+    pub fn new_boxed(
+        workload_type: WorkloadType,
+        config: ObjectStoreConfig,
+        gas_pool: Arc<GasPool>,
+        key_schemes: KeySchemeMix,
+        rng: StdRng,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        assert!(matches!(
+            workload_type,
+            WorkloadType::MintObjects | WorkloadType::GrowCollection
+        ));
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(ObjectStoreWorkload {
+            workload_type,
+            config,
+            gas_pool,
+            basics_package_ref: None,
+            key_schemes,
+            rng: Mutex::new(rng),
+        }))
+    }
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for ObjectStoreWorkload {
+    async fn init(&mut self, proxy: &dyn ValidatorProxy) {
+        if self.basics_package_ref.is_some() {
+            return;
+        }
+        let (address, keypair) = get_key_pair_from_rng(self.rng.get_mut().unwrap());
+        let gas = self.gas_pool.lease_to(address, None, proxy).await.unwrap();
+        eprintln!("Publishing basics package");
+        self.basics_package_ref = Some(publish_basics_package(gas, proxy, address, &keypair).await)
+    }
+    async fn make_test_payloads(
+        &self,
+        count: u64,
+        proxy: &dyn ValidatorProxy,
+    ) -> Vec<Box<dyn Payload>> {
+        let package_ref = self.basics_package_ref.unwrap();
+        let keypairs: Vec<(SuiAddress, SuiKeyPair)> = (0..count)
+            .map(|_| {
+                self.key_schemes
+                    .generate_account(&mut *self.rng.lock().unwrap())
+            })
+            .collect();
+        let accounts: Vec<(SuiAddress, SuiKeyPair, ObjectRef)> =
+            join_all(keypairs.into_iter().map(|(address, keypair)| async move {
+                self.gas_pool
+                    .lease_to(address, None, proxy)
+                    .await
+                    .map(|gas| (address, keypair, gas))
+            }))
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        if self.workload_type == WorkloadType::GrowCollection {
+            eprintln!("Creating collections, this may take a while..");
+        }
+        join_all(
+            accounts
+                .into_iter()
+                .map(|(sender, keypair, gas)| async move {
+                    let (collection, gas) = match self.workload_type {
+                        WorkloadType::GrowCollection => {
+                            let transaction = make_object_store_transaction(
+                                package_ref,
+                                "create_collection",
+                                vec![],
+                                gas,
+                                sender,
+                                &keypair,
+                            );
+                            let effects = submit_transaction(transaction, proxy)
+                                .await
+                                .expect("Failed to create collection!");
+                            (Some(effects.created[0].0), effects.gas_object.0)
+                        }
+                        _ => (None, gas),
+                    };
+                    Box::<dyn Payload>::from(Box::new(ObjectStoreTestPayload {
+                        package_ref,
+                        collection,
+                        config: self.config,
+                        gas: (gas, Owner::AddressOwner(sender)),
+                        sender,
+                        keypair: Arc::new(keypair),
+                    }))
+                }),
+        )
+        .await
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::gas_pool::GasPool;
+use super::workload::{Gas, KeyScheme, KeySchemeMix, Payload, Workload, WorkloadType};
+use crate::validator_proxy::ValidatorProxy;
+use async_trait::async_trait;
+use futures::future::join_all;
+use rand::rngs::StdRng;
+use std::sync::{Arc, Mutex};
+use sui_test_utils::TestPackage;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::{EmptySignInfo, SuiKeyPair},
+    messages::TransactionEnvelope,
+    object::Owner,
+};
+use test_utils::messages::make_publish_transaction;
+
+pub struct PublishPackageTestPayload {
+    modules: Arc<Vec<Vec<u8>>>,
+    gas: Gas,
+    sender: SuiAddress,
+    keypair: Arc<SuiKeyPair>,
+}
+
+impl Payload for PublishPackageTestPayload {
+    fn make_new_payload(self: Box<Self>, _: ObjectRef, new_gas: ObjectRef) -> Box<dyn Payload> {
+        Box::new(PublishPackageTestPayload {
+            modules: self.modules.clone(),
+            gas: (new_gas, self.gas.1),
+            sender: self.sender,
+            keypair: self.keypair.clone(),
+        })
+    }
+    fn make_transaction(&self) -> TransactionEnvelope<EmptySignInfo> {
+        make_publish_transaction(
+            self.gas.0,
+            self.modules.to_vec(),
+            self.sender,
+            self.keypair.as_ref(),
+        )
+    }
+    fn get_object_id(&self) -> ObjectID {
+        // Publishing only mutates the gas object, so track that across transactions
+        self.gas.0 .0
+    }
+    fn get_workload_type(&self) -> WorkloadType {
+        WorkloadType::PublishPackage
+    }
+    fn get_key_scheme(&self) -> KeyScheme {
+        KeyScheme::of(&self.keypair)
+    }
+}
+
+/// Repeatedly publishes the basics example package, each payload paying from its own gas coin.
+pub struct PublishPackageWorkload {
+    pub gas_pool: Arc<GasPool>,
+    pub modules: Option<Arc<Vec<Vec<u8>>>>,
+    key_schemes: KeySchemeMix,
+    rng: Mutex<StdRng>,
+}
+
+impl PublishPackageWorkload {
+    pub fn new_boxed(
+        gas_pool: Arc<GasPool>,
+        key_schemes: KeySchemeMix,
+        rng: StdRng,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(PublishPackageWorkload {
+            gas_pool,
+            modules: None,
+            key_schemes,
+            rng: Mutex::new(rng),
+        }))
+    }
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for PublishPackageWorkload {
+    async fn init(&mut self, _proxy: &dyn ValidatorProxy) {
+        if self.modules.is_some() {
+            return;
+        }
+        // Compile once up front, every payload publishes the same bytes
+        eprintln!("Compiling basics package");
+        self.modules = Some(Arc::new(TestPackage::Basics.build()));
+    }
+    async fn make_test_payloads(
+        &self,
+        count: u64,
+        proxy: &dyn ValidatorProxy,
+    ) -> Vec<Box<dyn Payload>> {
+        let modules = self.modules.clone().unwrap();
+        let keypairs: Vec<(SuiAddress, SuiKeyPair)> = (0..count)
+            .map(|_| {
+                self.key_schemes
+                    .generate_account(&mut *self.rng.lock().unwrap())
+            })
+            .collect();
+        join_all(keypairs.into_iter().map(|(address, keypair)| {
+            let modules = modules.clone();
+            async move {
+                self.gas_pool
+                    .lease_to(address, None, proxy)
+                    .await
+                    .map(|gas| PublishPackageTestPayload {
+                        modules,
+                        gas: (gas, Owner::AddressOwner(address)),
+                        sender: address,
+                        keypair: Arc::new(keypair),
+                    })
+            }
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .map(|payload| Box::<dyn Payload>::from(Box::new(payload)))
+        .collect()
+    }
+}
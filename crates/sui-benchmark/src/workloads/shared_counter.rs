@@ -1,31 +1,31 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::workload::{submit_transaction, Gas, Payload, Workload, WorkloadType};
-use crate::workloads::workload::{get_latest, transfer_sui_for_testing, MAX_GAS_FOR_TESTING};
+use super::gas_pool::GasPool;
+use super::workload::{
+    submit_transaction, Gas, KeyScheme, KeySchemeMix, Payload, Workload, WorkloadType,
+};
+use crate::validator_proxy::ValidatorProxy;
 use async_trait::async_trait;
 use futures::future::join_all;
-use std::{path::PathBuf, sync::Arc};
-use sui_core::{
-    authority_aggregator::AuthorityAggregator, authority_client::NetworkAuthorityClient,
-};
+use rand::rngs::StdRng;
+use std::sync::{Arc, Mutex};
+use sui_test_utils::TestPackage;
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
-    crypto::{get_key_pair, AccountKeyPair, EmptySignInfo},
+    crypto::{get_key_pair_from_rng, AccountKeyPair, EmptySignInfo, SuiKeyPair},
     messages::TransactionEnvelope,
     object::Owner,
 };
+use test_utils::messages::create_publish_move_package_transaction;
 use test_utils::messages::{make_counter_create_transaction, make_counter_increment_transaction};
-use test_utils::{
-    messages::create_publish_move_package_transaction, transaction::parse_package_ref,
-};
 
 pub struct SharedCounterTestPayload {
     package_ref: ObjectRef,
     counter_id: ObjectID,
     gas: Gas,
     sender: SuiAddress,
-    keypair: Arc<AccountKeyPair>,
+    keypair: Arc<SuiKeyPair>,
 }
 
 impl Payload for SharedCounterTestPayload {
@@ -44,7 +44,7 @@ impl Payload for SharedCounterTestPayload {
             self.package_ref,
             self.counter_id,
             self.sender,
-            &self.keypair,
+            self.keypair.as_ref(),
         )
     }
     fn get_object_id(&self) -> ObjectID {
@@ -53,104 +53,86 @@ impl Payload for SharedCounterTestPayload {
     fn get_workload_type(&self) -> WorkloadType {
         WorkloadType::SharedCounter
     }
+    fn get_key_scheme(&self) -> KeyScheme {
+        KeyScheme::of(&self.keypair)
+    }
 }
 
 pub struct SharedCounterWorkload {
-    pub test_gas: ObjectID,
-    pub test_gas_owner: SuiAddress,
-    pub test_gas_keypair: Arc<AccountKeyPair>,
+    pub gas_pool: Arc<GasPool>,
     pub basics_package_ref: Option<ObjectRef>,
+    key_schemes: KeySchemeMix,
+    rng: Mutex<StdRng>,
 }
 
 impl SharedCounterWorkload {
     pub fn new_boxed(
-        gas: ObjectID,
-        owner: SuiAddress,
-        keypair: Arc<AccountKeyPair>,
+        gas_pool: Arc<GasPool>,
         basics_package_ref: Option<ObjectRef>,
+        key_schemes: KeySchemeMix,
+        rng: StdRng,
     ) -> Box<dyn Workload<dyn Payload>> {
         Box::<dyn Workload<dyn Payload>>::from(Box::new(SharedCounterWorkload {
-            test_gas: gas,
-            test_gas_owner: owner,
-            test_gas_keypair: keypair,
+            gas_pool,
             basics_package_ref,
+            key_schemes,
+            rng: Mutex::new(rng),
         }))
     }
 }
 
 pub async fn publish_basics_package(
     gas: ObjectRef,
-    aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+    proxy: &dyn ValidatorProxy,
     sender: SuiAddress,
     keypair: &AccountKeyPair,
 ) -> ObjectRef {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("../../sui_programmability/examples/basics");
-    let transaction = create_publish_move_package_transaction(gas, path, sender, keypair);
-    let effects = submit_transaction(transaction, aggregator).await.unwrap();
-    parse_package_ref(&effects).unwrap()
+    let transaction =
+        create_publish_move_package_transaction(gas, TestPackage::Basics.path(), sender, keypair);
+    let effects = submit_transaction(transaction, proxy).await.unwrap();
+    effects
+        .created
+        .iter()
+        .find(|(_, owner)| matches!(owner, Owner::Immutable))
+        .map(|(reference, _)| *reference)
+        .unwrap()
 }
 
 #[async_trait]
 impl Workload<dyn Payload> for SharedCounterWorkload {
-    async fn init(&mut self, aggregator: &AuthorityAggregator<NetworkAuthorityClient>) {
+    async fn init(&mut self, proxy: &dyn ValidatorProxy) {
         if self.basics_package_ref.is_some() {
             return;
         }
-        // publish basics package
-        let primary_gas = get_latest(self.test_gas, aggregator).await.unwrap();
-        let primary_gas_ref = primary_gas.compute_object_reference();
-        let mut publish_module_gas_ref = None;
-        let (address, keypair) = get_key_pair();
-        if let Some((_updated, minted)) = transfer_sui_for_testing(
-            (primary_gas_ref, Owner::AddressOwner(self.test_gas_owner)),
-            &self.test_gas_keypair,
-            MAX_GAS_FOR_TESTING,
-            address,
-            aggregator,
-        )
-        .await
-        {
-            publish_module_gas_ref = Some((address, keypair, minted));
-        }
+        let (address, keypair) = get_key_pair_from_rng(self.rng.get_mut().unwrap());
+        let gas = self.gas_pool.lease_to(address, None, proxy).await.unwrap();
         // Publish basics package
         eprintln!("Publishing basics package");
-        let publish_module_gas = publish_module_gas_ref.unwrap();
-        self.basics_package_ref = Some(
-            publish_basics_package(
-                publish_module_gas.2,
-                aggregator,
-                publish_module_gas.0,
-                &publish_module_gas.1,
-            )
-            .await,
-        )
+        self.basics_package_ref = Some(publish_basics_package(gas, proxy, address, &keypair).await)
     }
     async fn make_test_payloads(
         &self,
         count: u64,
-        aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: &dyn ValidatorProxy,
     ) -> Vec<Box<dyn Payload>> {
-        // Read latest test gas object
-        let primary_gas = get_latest(self.test_gas, aggregator).await.unwrap();
-        let mut primary_gas_ref = primary_gas.compute_object_reference();
         // Make as many gas objects as the number of counters
-        let mut counters_gas = vec![];
-        for _ in 0..count {
-            let (address, keypair) = get_key_pair();
-            if let Some((updated, minted)) = transfer_sui_for_testing(
-                (primary_gas_ref, Owner::AddressOwner(self.test_gas_owner)),
-                &self.test_gas_keypair,
-                MAX_GAS_FOR_TESTING,
-                address,
-                aggregator,
-            )
+        let keypairs: Vec<(SuiAddress, SuiKeyPair)> = (0..count)
+            .map(|_| {
+                self.key_schemes
+                    .generate_account(&mut *self.rng.lock().unwrap())
+            })
+            .collect();
+        let counters_gas: Vec<(SuiAddress, SuiKeyPair, ObjectRef)> =
+            join_all(keypairs.into_iter().map(|(address, keypair)| async move {
+                self.gas_pool
+                    .lease_to(address, None, proxy)
+                    .await
+                    .map(|gas| (address, keypair, gas))
+            }))
             .await
-            {
-                primary_gas_ref = updated;
-                counters_gas.push((address, keypair, minted));
-            }
-        }
+            .into_iter()
+            .flatten()
+            .collect();
         // create counters using gas objects we created above
         eprintln!("Creating shared counters, this may take a while..");
         let futures = counters_gas
@@ -162,7 +144,7 @@ impl Workload<dyn Payload> for SharedCounterWorkload {
                     sender,
                     &keypair,
                 );
-                if let Some(effects) = submit_transaction(transaction, aggregator).await {
+                if let Some(effects) = submit_transaction(transaction, proxy).await {
                     Box::new(SharedCounterTestPayload {
                         package_ref: self.basics_package_ref.unwrap(),
                         counter_id: effects.created[0].0 .0,
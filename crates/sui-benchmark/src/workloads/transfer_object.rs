@@ -1,32 +1,123 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
+use crate::validator_proxy::ValidatorProxy;
 use async_trait::async_trait;
-use rand::seq::IteratorRandom;
-use sui_core::{
-    authority_aggregator::AuthorityAggregator, authority_client::NetworkAuthorityClient,
-};
+use futures::future::join_all;
+use rand::{rngs::StdRng, seq::SliceRandom};
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
-    crypto::{get_key_pair, AccountKeyPair, EmptySignInfo},
+    crypto::{EmptySignInfo, SuiKeyPair},
     messages::TransactionEnvelope,
     object::Owner,
 };
 
 use test_utils::messages::make_transfer_object_transaction;
 
-use super::workload::{
-    get_latest, transfer_sui_for_testing, Gas, Payload, Workload, WorkloadType, MAX_GAS_FOR_TESTING,
-};
+use super::gas_pool::GasPool;
+use super::workload::{Gas, KeyScheme, KeySchemeMix, Payload, Workload, WorkloadType};
+
+/// How the accounts of the transfer workload pass objects to each other, which changes how
+/// contended their locks are and how local the objects they touch are.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TransferTopology {
+    /// Objects start at a hub account, which sends each to a random other account, which sends
+    /// it back.
+    Star,
+    /// Each account sends its objects to the next one.
+    Ring,
+    /// Each account sends its objects to a random other account.
+    AllToAll,
+    /// Objects are sent to one of the first `k` accounts, so that a few hot accounts receive
+    /// all transfers. They send them on to another hot account, or to a random account if
+    /// there is no other.
+    Hotspot(usize),
+}
+
+impl TransferTopology {
+    /// The account the `index`th object of the workload starts at.
+    fn initial_owner(&self, accounts: &[SuiAddress], index: usize) -> SuiAddress {
+        match self {
+            TransferTopology::Star => accounts[0],
+            _ => accounts[index % accounts.len()],
+        }
+    }
+
+    /// The account `from` sends the object it just received to.
+    fn recipient(&self, accounts: &[SuiAddress], from: SuiAddress, rng: &mut StdRng) -> SuiAddress {
+        let others = |candidates: &[SuiAddress]| -> Vec<SuiAddress> {
+            candidates.iter().copied().filter(|a| *a != from).collect()
+        };
+        match self {
+            TransferTopology::Star if from != accounts[0] => accounts[0],
+            TransferTopology::Star | TransferTopology::AllToAll => {
+                *others(accounts).choose(rng).unwrap()
+            }
+            TransferTopology::Ring => {
+                let position = accounts.iter().position(|a| *a == from).unwrap();
+                accounts[(position + 1) % accounts.len()]
+            }
+            TransferTopology::Hotspot(k) => {
+                let hot = others(&accounts[..(*k).min(accounts.len())]);
+                match hot.choose(rng) {
+                    Some(recipient) => *recipient,
+                    None => *others(accounts).choose(rng).unwrap(),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for TransferTopology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferTopology::Star => write!(f, "star"),
+            TransferTopology::Ring => write!(f, "ring"),
+            TransferTopology::AllToAll => write!(f, "all-to-all"),
+            TransferTopology::Hotspot(k) => write!(f, "hotspot:{k}"),
+        }
+    }
+}
+
+impl FromStr for TransferTopology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "star" => Ok(TransferTopology::Star),
+            "ring" => Ok(TransferTopology::Ring),
+            "all-to-all" | "all_to_all" => Ok(TransferTopology::AllToAll),
+            _ => match s.strip_prefix("hotspot:") {
+                Some(k) => match k.parse() {
+                    Ok(k) if k > 0 => Ok(TransferTopology::Hotspot(k)),
+                    _ => Err(format!(
+                        "Invalid number of hot accounts in '{s}', expected a positive integer"
+                    )),
+                },
+                None => Err(format!(
+                    "Unknown topology '{s}', expected one of star, ring, all-to-all, hotspot:<k>"
+                )),
+            },
+        }
+    }
+}
 
 pub struct TransferObjectTestPayload {
     transfer_object: ObjectRef,
     transfer_from: SuiAddress,
     transfer_to: SuiAddress,
     gas: Vec<Gas>,
-    keypairs: Arc<HashMap<SuiAddress, AccountKeyPair>>,
+    keypairs: Arc<BTreeMap<SuiAddress, SuiKeyPair>>,
+    accounts: Arc<Vec<SuiAddress>>,
+    topology: TransferTopology,
+    rng: Arc<Mutex<StdRng>>,
 }
 
 impl Payload for TransferObjectTestPayload {
@@ -46,17 +137,20 @@ impl Payload for TransferObjectTestPayload {
                 }
             })
             .collect();
-        let (_, recipient) = self
-            .gas
-            .iter()
-            .find(|x| x.1.get_owner_address().unwrap() != self.transfer_to)
-            .unwrap();
+        let recipient = self.topology.recipient(
+            &self.accounts,
+            self.transfer_to,
+            &mut self.rng.lock().unwrap(),
+        );
         Box::new(TransferObjectTestPayload {
             transfer_object: new_object,
             transfer_from: self.transfer_to,
-            transfer_to: recipient.get_owner_address().unwrap(),
+            transfer_to: recipient,
             gas: updated_gas,
             keypairs: self.keypairs.clone(),
+            accounts: self.accounts.clone(),
+            topology: self.topology,
+            rng: self.rng.clone(),
         })
     }
     fn make_transaction(&self) -> TransactionEnvelope<EmptySignInfo> {
@@ -79,110 +173,105 @@ impl Payload for TransferObjectTestPayload {
     fn get_workload_type(&self) -> WorkloadType {
         WorkloadType::TransferObject
     }
+    fn get_key_scheme(&self) -> KeyScheme {
+        KeyScheme::of(self.keypairs.get(&self.transfer_from).unwrap())
+    }
 }
 
 pub struct TransferObjectWorkload {
-    pub test_gas: ObjectID,
-    pub test_gas_owner: SuiAddress,
-    pub test_gas_keypair: Arc<AccountKeyPair>,
+    pub gas_pool: Arc<GasPool>,
     pub num_accounts: u64,
-    pub transfer_keypairs: Arc<HashMap<SuiAddress, AccountKeyPair>>,
+    pub transfer_keypairs: Arc<BTreeMap<SuiAddress, SuiKeyPair>>,
+    pub topology: TransferTopology,
+    /// The accounts in the order they were generated, which the topology refers to.
+    accounts: Arc<Vec<SuiAddress>>,
+    rng: Arc<Mutex<StdRng>>,
 }
 
 impl TransferObjectWorkload {
     pub fn new_boxed(
         num_accounts: u64,
-        gas: ObjectID,
-        owner: SuiAddress,
-        keypair: Arc<AccountKeyPair>,
+        topology: TransferTopology,
+        gas_pool: Arc<GasPool>,
+        key_schemes: KeySchemeMix,
+        mut rng: StdRng,
     ) -> Box<dyn Workload<dyn Payload>> {
+        assert!(
+            num_accounts >= 2,
+            "Transferring objects needs at least 2 accounts"
+        );
         // create several accounts to transfer object between
-        let keypairs: Arc<HashMap<SuiAddress, AccountKeyPair>> =
-            Arc::new((0..num_accounts).map(|_| get_key_pair()).collect());
+        let accounts: Vec<(SuiAddress, SuiKeyPair)> = (0..num_accounts)
+            .map(|_| key_schemes.generate_account(&mut rng))
+            .collect();
         Box::new(TransferObjectWorkload {
-            test_gas: gas,
-            test_gas_owner: owner,
-            test_gas_keypair: keypair,
+            gas_pool,
             num_accounts,
-            transfer_keypairs: keypairs,
+            accounts: Arc::new(accounts.iter().map(|(address, _)| *address).collect()),
+            transfer_keypairs: Arc::new(accounts.into_iter().collect()),
+            topology,
+            rng: Arc::new(Mutex::new(rng)),
         })
     }
 }
 
 #[async_trait]
 impl Workload<dyn Payload> for TransferObjectWorkload {
-    async fn init(&mut self, _aggregator: &AuthorityAggregator<NetworkAuthorityClient>) {
+    async fn init(&mut self, _proxy: &dyn ValidatorProxy) {
         return;
     }
     async fn make_test_payloads(
         &self,
         count: u64,
-        aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: &dyn ValidatorProxy,
     ) -> Vec<Box<dyn Payload>> {
-        // Read latest test gas object
-        let primary_gas = get_latest(self.test_gas, aggregator).await.unwrap();
-        let mut primary_gas_ref = primary_gas.compute_object_reference();
-        let owner = *self
-            .transfer_keypairs
-            .keys()
-            .choose(&mut rand::thread_rng())
-            .unwrap();
         // create as many gas objects as there are number of transfer objects times number of accounts
         eprintln!("Creating enough gas to transfer objects..");
-        let mut transfer_gas: Vec<Vec<Gas>> = vec![];
-        for _i in 0..count {
-            let mut account_transfer_gas = vec![];
-            for (owner, _) in self.transfer_keypairs.iter() {
-                if let Some((updated, minted)) = transfer_sui_for_testing(
-                    (primary_gas_ref, Owner::AddressOwner(self.test_gas_owner)),
-                    &self.test_gas_keypair,
-                    MAX_GAS_FOR_TESTING,
-                    *owner,
-                    aggregator,
-                )
-                .await
-                {
-                    primary_gas_ref = updated;
-                    account_transfer_gas.push((minted, Owner::AddressOwner(*owner)));
-                }
-            }
-            transfer_gas.push(account_transfer_gas);
-        }
+        let transfer_gas: Vec<Vec<Gas>> = join_all((0..count).map(|_| async move {
+            join_all(self.transfer_keypairs.keys().map(|owner| async move {
+                self.gas_pool
+                    .lease_to(*owner, None, proxy)
+                    .await
+                    .map(|gas| (gas, Owner::AddressOwner(*owner)))
+            }))
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+        }))
+        .await;
         eprintln!("Creating objects to transfer..");
         // create transfer objects with 1 SUI value each
-        let mut transfer_objects: Vec<Gas> = vec![];
-        for _i in 0..count {
-            if let Some((updated, minted)) = transfer_sui_for_testing(
-                (primary_gas_ref, Owner::AddressOwner(self.test_gas_owner)),
-                &self.test_gas_keypair,
-                1,
-                owner,
-                aggregator,
-            )
+        let transfer_objects: Vec<(SuiAddress, ObjectRef)> =
+            join_all((0..count as usize).map(|i| async move {
+                let owner = self.topology.initial_owner(&self.accounts, i);
+                self.gas_pool
+                    .lease_to(owner, Some(1), proxy)
+                    .await
+                    .map(|object| (owner, object))
+            }))
             .await
-            {
-                primary_gas_ref = updated;
-                transfer_objects.push((minted, Owner::AddressOwner(owner)));
-            }
-        }
-        let refs: Vec<(Vec<Gas>, ObjectRef)> = transfer_gas
             .into_iter()
-            .zip(transfer_objects.iter())
-            .map(|(g, t)| (g, t.0))
+            .flatten()
+            .collect();
+        let refs: Vec<(Vec<Gas>, (SuiAddress, ObjectRef))> = transfer_gas
+            .into_iter()
+            .zip(transfer_objects.into_iter())
             .collect();
         refs.iter()
-            .map(|(g, t)| {
-                let from = owner;
-                let (_, to) = *g
-                    .iter()
-                    .find(|x| x.1.get_owner_address().unwrap() != from)
-                    .unwrap();
+            .map(|(g, (from, t))| {
+                let to =
+                    self.topology
+                        .recipient(&self.accounts, *from, &mut self.rng.lock().unwrap());
                 Box::new(TransferObjectTestPayload {
                     transfer_object: *t,
-                    transfer_from: from,
-                    transfer_to: to.get_owner_address().unwrap(),
+                    transfer_from: *from,
+                    transfer_to: to,
                     gas: g.clone(),
                     keypairs: self.transfer_keypairs.clone(),
+                    accounts: self.accounts.clone(),
+                    topology: self.topology,
+                    rng: self.rng.clone(),
                 })
             })
             .map(|b| Box::<dyn Payload>::from(b))
@@ -1,32 +1,25 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::validator_proxy::{ExecutionEffects, ValidatorProxy};
 use async_trait::async_trait;
-use std::{collections::HashMap, fmt};
-use sui_core::quorum_driver::{QuorumDriverHandler, QuorumDriverMetrics};
-use sui_core::{
-    authority_aggregator::AuthorityAggregator, authority_client::NetworkAuthorityClient,
-};
+use std::{collections::BTreeMap, fmt, str::FromStr, sync::Mutex};
 use sui_types::{
     base_types::{ObjectID, ObjectRef},
     crypto::EmptySignInfo,
-    messages::{TransactionEffects, TransactionEnvelope},
-    object::{Object, ObjectRead, Owner},
+    messages::TransactionEnvelope,
+    object::{Object, Owner},
 };
 
-use futures::FutureExt;
 use sui_types::{
     base_types::SuiAddress,
-    crypto::AccountKeyPair,
-    messages::{
-        ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
-        Transaction,
-    },
+    crypto::{get_key_pair_from_rng, AccountKeyPair, SuiKeyPair},
+    messages::Transaction,
 };
 use test_utils::messages::make_transfer_sui_transaction;
 use tracing::log::error;
 
-use rand::{prelude::*, rngs::OsRng};
+use rand::{prelude::*, rngs::StdRng};
 use rand_distr::WeightedAliasIndex;
 
 // This is the maximum gas we will transfer from primary coin into any gas coin
@@ -42,7 +35,7 @@ pub async fn transfer_sui_for_testing(
     keypair: &AccountKeyPair,
     value: u64,
     address: SuiAddress,
-    client: &AuthorityAggregator<NetworkAuthorityClient>,
+    proxy: &dyn ValidatorProxy,
 ) -> Option<UpdatedAndNewlyMinted> {
     let tx = make_transfer_sui_transaction(
         gas.0,
@@ -51,19 +44,11 @@ pub async fn transfer_sui_for_testing(
         gas.1.get_owner_address().unwrap(),
         keypair,
     );
-    let quorum_driver_handler =
-        QuorumDriverHandler::new(client.clone(), QuorumDriverMetrics::new_for_tests());
-    let qd = quorum_driver_handler.clone_quorum_driver();
-    qd.execute_transaction(ExecuteTransactionRequest {
-        transaction: tx.clone(),
-        request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
-    })
-    .map(move |res| match res {
-        Ok(ExecuteTransactionResponse::EffectsCert(result)) => {
-            let (_, effects) = *result;
-            let minted = effects.effects.created.get(0).unwrap().0;
+    match proxy.execute_transaction(tx).await {
+        Ok(executed) => {
+            let effects = executed.effects;
+            let minted = effects.created.get(0).unwrap().0;
             let updated = effects
-                .effects
                 .mutated
                 .iter()
                 .find(|(k, _)| k.0 == gas.0 .0)
@@ -71,47 +56,34 @@ pub async fn transfer_sui_for_testing(
                 .0;
             Some((updated, minted))
         }
-        Ok(resp) => {
-            error!("Unexpected response while transferring sui: {:?}", resp);
-            None
-        }
         Err(err) => {
             error!("Error while transferring sui: {:?}", err);
             None
         }
-    })
-    .await
+    }
 }
 
-pub async fn get_latest(
-    object_id: ObjectID,
-    aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
-) -> Option<Object> {
+pub async fn get_latest(object_id: ObjectID, proxy: &dyn ValidatorProxy) -> Option<Object> {
     // Return the latest object version
-    match aggregator.get_object_info_execute(object_id).await.unwrap() {
-        ObjectRead::Exists(_, object, _) => Some(object),
-        _ => None,
+    match proxy.get_object(object_id).await {
+        Ok(object) => Some(object),
+        Err(err) => {
+            error!("Error while reading object {}: {:?}", object_id, err);
+            None
+        }
     }
 }
 
 pub async fn submit_transaction(
     transaction: Transaction,
-    aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
-) -> Option<TransactionEffects> {
-    let qd = QuorumDriverHandler::new(aggregator.clone(), QuorumDriverMetrics::new_for_tests());
-    if let ExecuteTransactionResponse::EffectsCert(result) = qd
-        .clone_quorum_driver()
-        .execute_transaction(ExecuteTransactionRequest {
-            transaction,
-            request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
-        })
-        .await
-        .unwrap()
-    {
-        let (_, effects) = *result;
-        Some(effects.effects)
-    } else {
-        None
+    proxy: &dyn ValidatorProxy,
+) -> Option<ExecutionEffects> {
+    match proxy.execute_transaction(transaction).await {
+        Ok(executed) => Some(executed.effects),
+        Err(err) => {
+            error!("Error while submitting transaction: {:?}", err);
+            None
+        }
     }
 }
 
@@ -124,13 +96,15 @@ pub trait Payload: Send + Sync {
     fn make_transaction(&self) -> TransactionEnvelope<EmptySignInfo>;
     fn get_object_id(&self) -> ObjectID;
     fn get_workload_type(&self) -> WorkloadType;
+    /// The signature scheme of the sender of the transactions.
+    fn get_key_scheme(&self) -> KeyScheme;
 }
 
 pub struct CombinationPayload {
     payloads: Vec<Box<dyn Payload>>,
     dist: WeightedAliasIndex<u32>,
     curr_index: usize,
-    rng: OsRng,
+    rng: StdRng,
 }
 
 impl Payload for CombinationPayload {
@@ -154,7 +128,7 @@ impl Payload for CombinationPayload {
             payloads: new_payloads,
             dist: self.dist,
             curr_index: next_index,
-            rng: self.rng,
+            rng,
         })
     }
     fn make_transaction(&self) -> TransactionEnvelope<EmptySignInfo> {
@@ -171,12 +145,18 @@ impl Payload for CombinationPayload {
             .unwrap()
             .get_workload_type()
     }
+    fn get_key_scheme(&self) -> KeyScheme {
+        self.payloads.get(self.curr_index).unwrap().get_key_scheme()
+    }
 }
 
-#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum WorkloadType {
     SharedCounter,
     TransferObject,
+    PublishPackage,
+    MintObjects,
+    GrowCollection,
 }
 
 impl fmt::Display for WorkloadType {
@@ -184,41 +164,230 @@ impl fmt::Display for WorkloadType {
         match self {
             WorkloadType::SharedCounter => write!(f, "shared_counter"),
             WorkloadType::TransferObject => write!(f, "transfer_object"),
+            WorkloadType::PublishPackage => write!(f, "publish_package"),
+            WorkloadType::MintObjects => write!(f, "mint_objects"),
+            WorkloadType::GrowCollection => write!(f, "grow_collection"),
+        }
+    }
+}
+
+impl FromStr for WorkloadType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shared-counter" | "shared_counter" => Ok(WorkloadType::SharedCounter),
+            "transfer" | "transfer-object" | "transfer_object" => Ok(WorkloadType::TransferObject),
+            "publish" | "publish-package" | "publish_package" => Ok(WorkloadType::PublishPackage),
+            "mint" | "mint-objects" | "mint_objects" => Ok(WorkloadType::MintObjects),
+            "grow" | "grow-collection" | "grow_collection" => Ok(WorkloadType::GrowCollection),
+            _ => Err(format!(
+                "Unknown workload '{s}', expected one of transfer, shared-counter, publish, mint, grow"
+            )),
         }
     }
 }
 
+/// Relative weights of the workloads to interleave in a run, e.g.
+/// `transfer=70,shared-counter=20,publish=10`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadMix {
+    pub weights: Vec<(WorkloadType, u32)>,
+}
+
+impl FromStr for WorkloadMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights: Vec<(WorkloadType, u32)> = vec![];
+        for entry in s.split(',') {
+            let (workload, weight) = entry
+                .trim()
+                .split_once('=')
+                .ok_or_else(|| format!("Expected <workload>=<weight> in '{entry}'"))?;
+            let workload: WorkloadType = workload.trim().parse()?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid weight in '{entry}': {e}"))?;
+            if weights.iter().any(|(w, _)| *w == workload) {
+                return Err(format!("Workload '{workload}' is listed more than once"));
+            }
+            if weight > 0 {
+                weights.push((workload, weight));
+            }
+        }
+        if weights.is_empty() {
+            return Err("Workload mix needs at least one workload with a non-zero weight".into());
+        }
+        Ok(WorkloadMix { weights })
+    }
+}
+
+/// Signature scheme of the accounts sending the transactions of a workload.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum KeyScheme {
+    Ed25519,
+    Secp256k1,
+}
+
+impl KeyScheme {
+    pub fn of(keypair: &SuiKeyPair) -> KeyScheme {
+        match keypair {
+            SuiKeyPair::Ed25519SuiKeyPair(_) => KeyScheme::Ed25519,
+            SuiKeyPair::Secp256k1SuiKeyPair(_) => KeyScheme::Secp256k1,
+        }
+    }
+}
+
+impl fmt::Display for KeyScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KeyScheme::Ed25519 => write!(f, "ed25519"),
+            KeyScheme::Secp256k1 => write!(f, "secp256k1"),
+        }
+    }
+}
+
+impl FromStr for KeyScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ed25519" => Ok(KeyScheme::Ed25519),
+            "secp256k1" => Ok(KeyScheme::Secp256k1),
+            "multisig" => Err("Multisig accounts are not supported by this version of Sui".into()),
+            _ => Err(format!(
+                "Unknown key scheme '{s}', expected one of ed25519, secp256k1"
+            )),
+        }
+    }
+}
+
+/// Relative weights of the signature schemes of the generated accounts, e.g.
+/// `ed25519=80,secp256k1=20`. All accounts are ed25519 ones by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySchemeMix {
+    pub weights: Vec<(KeyScheme, u32)>,
+}
+
+impl Default for KeySchemeMix {
+    fn default() -> Self {
+        KeySchemeMix {
+            weights: vec![(KeyScheme::Ed25519, 1)],
+        }
+    }
+}
+
+impl FromStr for KeySchemeMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut weights: Vec<(KeyScheme, u32)> = vec![];
+        for entry in s.split(',') {
+            let (scheme, weight) = match entry.trim().split_once('=') {
+                Some((scheme, weight)) => (scheme, weight),
+                // A single scheme needs no weight
+                None => (entry, "1"),
+            };
+            let scheme: KeyScheme = scheme.trim().parse()?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .map_err(|e| format!("Invalid weight in '{entry}': {e}"))?;
+            if weights.iter().any(|(s, _)| *s == scheme) {
+                return Err(format!("Key scheme '{scheme}' is listed more than once"));
+            }
+            if weight > 0 {
+                weights.push((scheme, weight));
+            }
+        }
+        if weights.is_empty() {
+            return Err("Key scheme mix needs at least one scheme with a non-zero weight".into());
+        }
+        Ok(KeySchemeMix { weights })
+    }
+}
+
+impl KeySchemeMix {
+    /// Generates an account of a scheme picked by weight. The scheme is only drawn from `rng`
+    /// when there are several, so a run with a single scheme generates the same accounts from
+    /// the same seed whichever it is.
+    pub fn generate_account(&self, rng: &mut StdRng) -> (SuiAddress, SuiKeyPair) {
+        let scheme = if self.weights.len() == 1 {
+            self.weights[0].0
+        } else {
+            let dist =
+                WeightedAliasIndex::new(self.weights.iter().map(|(_, w)| *w).collect()).unwrap();
+            self.weights[dist.sample(rng)].0
+        };
+        match scheme {
+            KeyScheme::Ed25519 => {
+                let (address, keypair) = get_key_pair_from_rng(rng);
+                (address, SuiKeyPair::Ed25519SuiKeyPair(keypair))
+            }
+            KeyScheme::Secp256k1 => {
+                let (address, keypair) = get_key_pair_from_rng(rng);
+                (address, SuiKeyPair::Secp256k1SuiKeyPair(keypair))
+            }
+        }
+    }
+}
+
+/// Independent streams of random choices of a run, so that e.g. adding a workload to the mix
+/// doesn't change the accounts generated by the others.
+#[derive(Copy, Clone, Debug)]
+pub enum RngStream {
+    /// Choice of the primary gas object among the available ones.
+    PrimaryGas,
+    /// Sampling of the workloads of a mix by weight.
+    Mix,
+    /// Accounts and objects of a workload.
+    Workload(WorkloadType),
+}
+
+/// RNG of a stream of random choices. Runs with the same `seed` make the same choices, and
+/// so issue the same sequence of transactions; without a seed the choices are random.
+pub fn seeded_rng(seed: Option<u64>, stream: RngStream) -> StdRng {
+    let stream = match stream {
+        RngStream::PrimaryGas => 0,
+        RngStream::Mix => 1,
+        RngStream::Workload(workload_type) => 2 + workload_type as u64,
+    };
+    match seed {
+        // seed_from_u64 scrambles its input, so nearby seeds still give unrelated streams
+        Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15)),
+        None => StdRng::from_entropy(),
+    }
+}
+
 #[async_trait]
 pub trait Workload<T: Payload + ?Sized>: Send + Sync {
-    async fn init(&mut self, aggregator: &AuthorityAggregator<NetworkAuthorityClient>);
-    async fn make_test_payloads(
-        &self,
-        count: u64,
-        client: &AuthorityAggregator<NetworkAuthorityClient>,
-    ) -> Vec<Box<T>>;
+    async fn init(&mut self, proxy: &dyn ValidatorProxy);
+    async fn make_test_payloads(&self, count: u64, proxy: &dyn ValidatorProxy) -> Vec<Box<T>>;
 }
 
 type WeightAndPayload = (u32, Box<dyn Workload<dyn Payload>>);
 pub struct CombinationWorkload {
-    workloads: HashMap<WorkloadType, WeightAndPayload>,
+    workloads: BTreeMap<WorkloadType, WeightAndPayload>,
+    rng: Mutex<StdRng>,
 }
 
 #[async_trait]
 impl Workload<dyn Payload> for CombinationWorkload {
-    async fn init(&mut self, aggregator: &AuthorityAggregator<NetworkAuthorityClient>) {
+    async fn init(&mut self, proxy: &dyn ValidatorProxy) {
         for (_, (_, workload)) in self.workloads.iter_mut() {
-            workload.init(aggregator).await;
+            workload.init(proxy).await;
         }
     }
     async fn make_test_payloads(
         &self,
         count: u64,
-        aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+        proxy: &dyn ValidatorProxy,
     ) -> Vec<Box<dyn Payload>> {
-        let mut workloads: HashMap<WorkloadType, (u32, Vec<Box<dyn Payload>>)> = HashMap::new();
+        let mut workloads: BTreeMap<WorkloadType, (u32, Vec<Box<dyn Payload>>)> = BTreeMap::new();
         for (workload_type, (weight, workload)) in self.workloads.iter() {
-            let payloads: Vec<Box<dyn Payload>> =
-                workload.make_test_payloads(count, aggregator).await;
+            let payloads: Vec<Box<dyn Payload>> = workload.make_test_payloads(count, proxy).await;
             assert_eq!(payloads.len() as u64, count);
             workloads
                 .entry(*workload_type)
@@ -232,11 +401,15 @@ impl Workload<dyn Payload> for CombinationWorkload {
                 all_payloads.push(payloads.pop().unwrap());
                 dist.push(*weight);
             }
+            let dist = WeightedAliasIndex::new(dist).unwrap();
+            let mut rng = StdRng::from_rng(&mut *self.rng.lock().unwrap()).unwrap();
+            // Pick the first payload by weight too, so short runs follow the mix
+            let curr_index = dist.sample(&mut rng);
             res.push(Box::new(CombinationPayload {
                 payloads: all_payloads,
-                dist: WeightedAliasIndex::new(dist).unwrap(),
-                curr_index: 0,
-                rng: OsRng::default(),
+                dist,
+                curr_index,
+                rng,
             }));
         }
         res.into_iter()
@@ -247,9 +420,13 @@ impl Workload<dyn Payload> for CombinationWorkload {
 
 impl CombinationWorkload {
     pub fn new_boxed(
-        workloads: HashMap<WorkloadType, WeightAndPayload>,
+        workloads: BTreeMap<WorkloadType, WeightAndPayload>,
+        rng: StdRng,
     ) -> Box<dyn Workload<dyn Payload>> {
-        Box::new(CombinationWorkload { workloads })
+        Box::new(CombinationWorkload {
+            workloads,
+            rng: Mutex::new(rng),
+        })
     }
 }
 
@@ -259,3 +436,48 @@ pub struct WorkloadInfo {
     pub max_in_flight_ops: u64,
     pub workload: Box<dyn Workload<dyn Payload>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workload_mix() {
+        let mix: WorkloadMix = "transfer=70, shared-counter=20,publish_package=10"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            mix.weights,
+            vec![
+                (WorkloadType::TransferObject, 70),
+                (WorkloadType::SharedCounter, 20),
+                (WorkloadType::PublishPackage, 10),
+            ]
+        );
+
+        // Workloads weighted 0 are left out of the mix
+        let mix: WorkloadMix = "mint=0,grow=1".parse().unwrap();
+        assert_eq!(mix.weights, vec![(WorkloadType::GrowCollection, 1)]);
+    }
+
+    #[test]
+    fn test_parse_invalid_workload_mix() {
+        for mix in [
+            // Empty mixes
+            "",
+            "transfer=0",
+            "transfer=0,publish=0",
+            // Bad weights
+            "transfer",
+            "transfer=",
+            "transfer=-1",
+            "transfer=0.5",
+            "transfer=seventy",
+            // Unknown and repeated workloads
+            "swap=10",
+            "transfer=70,transfer-object=30",
+        ] {
+            assert!(mix.parse::<WorkloadMix>().is_err(), "{mix}");
+        }
+    }
+}
@@ -296,6 +296,10 @@ pub async fn new_wallet_context_from_cluster(
         keystore,
         client_type: ClientType::RPC(rpc_url.into(), None),
         active_address: Some(address),
+        signers: Default::default(),
+        journal: None,
+        aliases: Default::default(),
+        checksummed_addresses: false,
     }
     .persisted(&wallet_config_path)
     .save()
@@ -4,11 +4,13 @@
 use crate::{
     genesis,
     genesis_config::{GenesisConfig, ValidatorGenesisInfo},
+    node::StorageBackendConfig,
     utils, ConsensusConfig, NetworkConfig, NodeConfig, ValidatorInfo, AUTHORITIES_DB_NAME,
     CONSENSUS_DB_NAME,
 };
 use rand::rngs::OsRng;
 use std::{
+    net::Ipv4Addr,
     num::NonZeroUsize,
     path::{Path, PathBuf},
     sync::Arc,
@@ -22,6 +24,19 @@ use sui_types::{
     },
 };
 
+/// First port handed out when ports are not randomized. Validator `i` gets the block of
+/// `PORTS_PER_VALIDATOR` ports starting at `DETERMINISTIC_BASE_PORT + i * PORTS_PER_VALIDATOR`.
+const DETERMINISTIC_BASE_PORT: u16 = 10_000;
+const PORTS_PER_VALIDATOR: u16 = 10;
+
+fn deterministic_port(index: usize, offset: u16) -> u16 {
+    u16::try_from(index)
+        .ok()
+        .and_then(|index| index.checked_mul(PORTS_PER_VALIDATOR))
+        .and_then(|port| port.checked_add(DETERMINISTIC_BASE_PORT + offset))
+        .expect("too many validators for deterministic ports")
+}
+
 pub enum CommitteeConfig {
     Size(NonZeroUsize),
     Validators(Vec<ValidatorGenesisInfo>),
@@ -40,6 +55,7 @@ pub struct ConfigBuilder<R = OsRng> {
     initial_accounts_config: Option<GenesisConfig>,
     with_swarm: bool,
     validator_ip_sel: ValidatorIpSelection,
+    storage_backend: StorageBackendConfig,
 }
 
 impl ConfigBuilder {
@@ -58,6 +74,7 @@ impl ConfigBuilder {
             } else {
                 ValidatorIpSelection::Localhost
             },
+            storage_backend: StorageBackendConfig::default(),
         }
     }
 }
@@ -93,6 +110,12 @@ impl<R> ConfigBuilder<R> {
         self
     }
 
+    /// Set the backend of the authority stores of the validators.
+    pub fn storage_backend(mut self, storage_backend: StorageBackendConfig) -> Self {
+        self.storage_backend = storage_backend;
+        self
+    }
+
     pub fn rng<N: ::rand::RngCore + ::rand::CryptoRng>(self, rng: N) -> ConfigBuilder<N> {
         ConfigBuilder {
             rng: Some(rng),
@@ -102,12 +125,12 @@ impl<R> ConfigBuilder<R> {
             initial_accounts_config: self.initial_accounts_config,
             with_swarm: self.with_swarm,
             validator_ip_sel: self.validator_ip_sel,
+            storage_backend: self.storage_backend,
         }
     }
 }
 
 impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
-    //TODO right now we always randomize ports, we may want to have a default port configuration
     pub fn build(mut self) -> NetworkConfig {
         let committee = self.committee.take().unwrap();
 
@@ -158,6 +181,15 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
         network_key_pair: NetworkKeyPair,
     ) -> ValidatorGenesisInfo {
         match self.validator_ip_sel {
+            ValidatorIpSelection::Localhost if !self.randomize_ports => {
+                ValidatorGenesisInfo::from_localhost_with_base_port(
+                    key_pair,
+                    worker_key_pair,
+                    account_key_pair,
+                    network_key_pair,
+                    deterministic_port(index, 0),
+                )
+            }
             ValidatorIpSelection::Localhost => ValidatorGenesisInfo::from_localhost_for_testing(
                 key_pair,
                 worker_key_pair,
@@ -171,7 +203,7 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     todo!("smarter IP formatting required");
                 }
 
-                let ip = format!("10.10.0.{}", low_octet);
+                let ip = Ipv4Addr::new(10, 10, 0, low_octet as u8).into();
 
                 ValidatorGenesisInfo::from_base_ip(
                     key_pair,
@@ -233,6 +265,12 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
 
         let genesis = {
             let mut builder = genesis::Builder::new().add_objects(objects);
+            if let Some(publisher_allowlist) = &initial_accounts_config.publisher_allowlist {
+                builder = builder.with_publisher_allowlist(publisher_allowlist.clone());
+            }
+            if let Some(flavor) = initial_accounts_config.framework_flavor {
+                builder = builder.with_framework_flavor(flavor);
+            }
 
             for (validator, proof_of_possession) in validator_set {
                 builder = builder.add_validator(validator, proof_of_possession);
@@ -243,7 +281,8 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
 
         let validator_configs = validators
             .into_iter()
-            .map(|validator| {
+            .enumerate()
+            .map(|(i, validator)| {
                 let public_key: AuthorityPublicKeyBytes = validator.key_pair.public().into();
                 let db_path = self
                     .config_directory
@@ -255,24 +294,50 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     .config_directory
                     .join(CONSENSUS_DB_NAME)
                     .join(encode_bytes_hex(&public_key));
-                let consensus_config = ConsensusConfig {
+                let mut consensus_config = ConsensusConfig {
                     consensus_address,
                     consensus_db_path,
                     delay_step: Some(15_000),
+                    relay_to_leader: None,
                     narwhal_config: Default::default(),
                 };
 
+                // Ports 0..4 of the block are taken by the validator's genesis addresses.
+                let (metrics_address, admin_interface_port, json_rpc_address) =
+                    if self.randomize_ports {
+                        (
+                            utils::available_local_socket_address(),
+                            utils::get_available_port(),
+                            utils::available_local_socket_address(),
+                        )
+                    } else {
+                        let narwhal_config = &mut consensus_config.narwhal_config;
+                        narwhal_config.consensus_api_grpc.socket_addr =
+                            utils::local_network_address(deterministic_port(i, 7));
+                        narwhal_config.prometheus_metrics.socket_addr =
+                            utils::local_network_address(deterministic_port(i, 8));
+                        (
+                            utils::local_socket_address(deterministic_port(i, 4)),
+                            deterministic_port(i, 5),
+                            utils::local_socket_address(deterministic_port(i, 6)),
+                        )
+                    };
+
                 NodeConfig {
                     protocol_key_pair: Arc::new(validator.key_pair),
                     worker_key_pair: Arc::new(validator.worker_key_pair),
                     account_key_pair: Arc::new(validator.account_key_pair),
                     network_key_pair: Arc::new(validator.network_key_pair),
+                    key_sources: Default::default(),
                     db_path,
+                    storage_backend: self.storage_backend,
                     network_address,
-                    metrics_address: utils::available_local_socket_address(),
-                    admin_interface_port: utils::get_available_port(),
-                    json_rpc_address: utils::available_local_socket_address(),
+                    metrics_address,
+                    admin_interface_port,
+                    log_filter: None,
+                    json_rpc_address,
                     websocket_address: None,
+                    transaction_submit_address: None,
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
                     enable_gossip: true,
@@ -281,6 +346,20 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> ConfigBuilder<R> {
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
                     grpc_concurrency_limit: initial_accounts_config.grpc_concurrency_limit,
+                    connection_limits: Default::default(),
+                    checkpoint_limits: Default::default(),
+                    store_cache: Default::default(),
+                    json_rpc_server: Default::default(),
+                    execution_cross_check: None,
+                    execution_engine_activations: Default::default(),
+                    checkpoint_export: None,
+                    pruning: None,
+                    state_snapshot: None,
+                    health: None,
+                    otlp: None,
+                    request_batching: None,
+                    storage: None,
+                    admission_control: None,
                 }
             })
             .collect();
@@ -11,15 +11,16 @@ use move_vm_runtime::native_functions::NativeFunctionTable;
 use narwhal_crypto::NetworkPublicKey;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
 use std::{fs, path::Path};
 use sui_adapter::adapter;
 use sui_adapter::adapter::MoveVM;
 use sui_adapter::in_memory_storage::InMemoryStorage;
 use sui_adapter::temporary_store::{InnerTemporaryStore, TemporaryStore};
-use sui_types::base_types::ObjectID;
+use sui_framework::Flavor;
 use sui_types::base_types::TransactionDigest;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::crypto::{AuthorityPublicKey, ToFromBytes};
 use sui_types::crypto::{AuthorityPublicKeyBytes, AuthoritySignature};
 use sui_types::gas::SuiGasStatus;
@@ -249,6 +250,8 @@ pub struct GenesisValidatorInfo {
 pub struct Builder {
     objects: BTreeMap<ObjectID, Object>,
     validators: BTreeMap<AuthorityPublicKeyBytes, GenesisValidatorInfo>,
+    publisher_allowlist: Option<BTreeSet<SuiAddress>>,
+    framework_flavor: Flavor,
 }
 
 impl Default for Builder {
@@ -262,6 +265,8 @@ impl Builder {
         Self {
             objects: Default::default(),
             validators: Default::default(),
+            publisher_allowlist: None,
+            framework_flavor: Flavor::Production,
         }
     }
 
@@ -292,14 +297,28 @@ impl Builder {
         self
     }
 
+    /// Restricts publishing packages to `publishers`, for private networks. The validators can
+    /// change the allowlist later on by voting for changes.
+    pub fn with_publisher_allowlist(mut self, publishers: BTreeSet<SuiAddress>) -> Self {
+        self.publisher_allowlist = Some(publishers);
+        self
+    }
+
+    /// Publishes the system packages built for `flavor`, e.g. with their test-only code for a
+    /// local network, instead of the production ones.
+    pub fn with_framework_flavor(mut self, flavor: Flavor) -> Self {
+        self.framework_flavor = flavor;
+        self
+    }
+
     pub fn build(self) -> Genesis {
         let mut genesis_ctx = sui_adapter::genesis::get_genesis_context();
 
-        // Get Move and Sui Framework
-        let modules = [
-            sui_framework::get_move_stdlib(),
-            sui_framework::get_sui_framework(),
-        ];
+        // Get the Move stdlib, the Sui framework and the other system packages
+        let modules: Vec<_> = sui_framework::get_modules(self.framework_flavor)
+            .iter()
+            .map(|package| package.modules.clone())
+            .collect();
 
         let objects = self.objects.into_iter().map(|(_, o)| o).collect::<Vec<_>>();
         let validators = self
@@ -307,7 +326,13 @@ impl Builder {
             .into_iter()
             .map(|(_, v)| v)
             .collect::<Vec<_>>();
-        let objects = create_genesis_objects(&mut genesis_ctx, &modules, &objects, &validators);
+        let objects = create_genesis_objects(
+            &mut genesis_ctx,
+            &modules,
+            &objects,
+            &validators,
+            self.publisher_allowlist.as_ref(),
+        );
 
         let genesis = Genesis {
             objects,
@@ -383,9 +408,27 @@ impl Builder {
             committee.insert(validator_info.info.protocol_key(), validator_info);
         }
 
+        // Load the publisher allowlist, if any
+        let allowlist_path = path.join(GENESIS_BUILDER_PUBLISHER_ALLOWLIST_FILE);
+        let publisher_allowlist = if allowlist_path.exists() {
+            Some(serde_yaml::from_slice(&fs::read(allowlist_path)?)?)
+        } else {
+            None
+        };
+
+        // Load the framework flavor, the production one if unset
+        let flavor_path = path.join(GENESIS_BUILDER_FRAMEWORK_FLAVOR_FILE);
+        let framework_flavor = if flavor_path.exists() {
+            serde_yaml::from_slice(&fs::read(flavor_path)?)?
+        } else {
+            Flavor::Production
+        };
+
         Ok(Self {
             objects,
             validators: committee,
+            publisher_allowlist,
+            framework_flavor,
         })
     }
 
@@ -415,6 +458,22 @@ impl Builder {
             fs::write(committee_dir.join(hex_name), validator_info_bytes)?;
         }
 
+        // Write the publisher allowlist
+        if let Some(publisher_allowlist) = &self.publisher_allowlist {
+            fs::write(
+                path.join(GENESIS_BUILDER_PUBLISHER_ALLOWLIST_FILE),
+                serde_yaml::to_vec(publisher_allowlist)?,
+            )?;
+        }
+
+        // Write the framework flavor
+        if self.framework_flavor != Flavor::Production {
+            fs::write(
+                path.join(GENESIS_BUILDER_FRAMEWORK_FLAVOR_FILE),
+                serde_yaml::to_vec(&self.framework_flavor)?,
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -424,6 +483,7 @@ fn create_genesis_objects(
     modules: &[Vec<CompiledModule>],
     input_objects: &[Object],
     validators: &[GenesisValidatorInfo],
+    publisher_allowlist: Option<&BTreeSet<SuiAddress>>,
 ) -> Vec<Object> {
     let mut store = InMemoryStorage::new(Vec::new());
 
@@ -446,7 +506,14 @@ fn create_genesis_objects(
         store.insert_object(object.to_owned());
     }
 
-    generate_genesis_system_object(&mut store, &move_vm, validators, genesis_ctx).unwrap();
+    generate_genesis_system_object(
+        &mut store,
+        &move_vm,
+        validators,
+        publisher_allowlist,
+        genesis_ctx,
+    )
+    .unwrap();
 
     store
         .into_inner()
@@ -528,6 +595,7 @@ pub fn generate_genesis_system_object(
     store: &mut InMemoryStorage,
     move_vm: &MoveVM,
     committee: &[GenesisValidatorInfo],
+    publisher_allowlist: Option<&BTreeSet<SuiAddress>>,
     genesis_ctx: &mut TxContext,
 ) -> Result<()> {
     let genesis_digest = genesis_ctx.digest();
@@ -557,6 +625,7 @@ pub fn generate_genesis_system_object(
         stakes.push(validator.stake());
         gas_prices.push(validator.gas_price());
     }
+    let publishers: Vec<SuiAddress> = publisher_allowlist.into_iter().flatten().copied().collect();
 
     adapter::execute(
         move_vm,
@@ -573,6 +642,8 @@ pub fn generate_genesis_system_object(
             CallArg::Pure(bcs::to_bytes(&network_addresses).unwrap()),
             CallArg::Pure(bcs::to_bytes(&stakes).unwrap()),
             CallArg::Pure(bcs::to_bytes(&gas_prices).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&publisher_allowlist.is_some()).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&publishers).unwrap()),
         ],
         &mut SuiGasStatus::new_unmetered(),
         genesis_ctx,
@@ -592,6 +663,8 @@ pub fn generate_genesis_system_object(
 
 const GENESIS_BUILDER_OBJECT_DIR: &str = "objects";
 const GENESIS_BUILDER_COMMITTEE_DIR: &str = "committee";
+const GENESIS_BUILDER_PUBLISHER_ALLOWLIST_FILE: &str = "publisher-allowlist";
+const GENESIS_BUILDER_FRAMEWORK_FLAVOR_FILE: &str = "framework-flavor";
 
 #[cfg(test)]
 mod test {
@@ -4,9 +4,12 @@
 use crate::{utils, DEFAULT_GAS_PRICE, DEFAULT_STAKE};
 use anyhow::Result;
 use multiaddr::Multiaddr;
+use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use sui_framework::Flavor;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::committee::StakeUnit;
 use sui_types::crypto::{
@@ -26,6 +29,13 @@ pub struct GenesisConfig {
     pub grpc_load_shed: Option<bool>,
     pub grpc_concurrency_limit: Option<usize>,
     pub accounts: Vec<AccountConfig>,
+    /// Restricts publishing packages to these addresses, for private networks. Anyone can publish
+    /// packages if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher_allowlist: Option<BTreeSet<SuiAddress>>,
+    /// Which build of the system packages to publish at genesis, the production one if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework_flavor: Option<Flavor>,
 }
 
 impl Config for GenesisConfig {}
@@ -126,20 +136,44 @@ impl ValidatorGenesisInfo {
         }
     }
 
+    /// Like `from_localhost_for_testing`, but with the four addresses on the consecutive ports
+    /// starting at `base_port` instead of on freshly allocated ones.
+    pub fn from_localhost_with_base_port(
+        key_pair: AuthorityKeyPair,
+        worker_key_pair: NetworkKeyPair,
+        account_key_pair: SuiKeyPair,
+        network_key_pair: NetworkKeyPair,
+        base_port: u16,
+    ) -> Self {
+        let make_addr = |offset: u16| utils::local_network_address(base_port + offset);
+
+        Self {
+            key_pair,
+            worker_key_pair,
+            account_key_pair,
+            network_key_pair,
+            network_address: make_addr(0),
+            stake: DEFAULT_STAKE,
+            gas_price: DEFAULT_GAS_PRICE,
+            narwhal_primary_address: make_addr(1),
+            narwhal_worker_address: make_addr(2),
+            narwhal_consensus_address: make_addr(3),
+        }
+    }
+
     pub fn from_base_ip(
         key_pair: AuthorityKeyPair,
         worker_key_pair: NetworkKeyPair,
         account_key_pair: SuiKeyPair,
         network_key_pair: NetworkKeyPair,
-        ip: String,
+        ip: IpAddr,
         // Port offset allows running many SuiNodes inside the same simulator node, which is
         // helpful for tests that don't use Swarm.
         port_offset: usize,
     ) -> Self {
         assert!(port_offset < 1000);
         let port_offset: u16 = port_offset.try_into().unwrap();
-        let make_addr =
-            |port: u16| -> Multiaddr { format!("/ip4/{}/tcp/{}/http", ip, port).parse().unwrap() };
+        let make_addr = |port: u16| -> Multiaddr { utils::ip_network_address(ip, port) };
 
         ValidatorGenesisInfo {
             key_pair,
@@ -208,6 +242,30 @@ impl GenesisConfig {
         num_authorities: usize,
         num_accounts: usize,
         num_objects_per_account: usize,
+    ) -> Self {
+        Self::custom_genesis_with_rng(
+            num_authorities,
+            num_accounts,
+            num_objects_per_account,
+            &mut OsRng,
+        )
+    }
+
+    /// Same as `for_local_testing`, with gas object ids drawn from `rng`.
+    pub fn for_local_testing_with_rng<R: ::rand::RngCore + ::rand::CryptoRng>(rng: &mut R) -> Self {
+        Self::custom_genesis_with_rng(
+            DEFAULT_NUMBER_OF_AUTHORITIES,
+            DEFAULT_NUMBER_OF_ACCOUNT,
+            DEFAULT_NUMBER_OF_OBJECT_PER_ACCOUNT,
+            rng,
+        )
+    }
+
+    pub fn custom_genesis_with_rng<R: ::rand::RngCore + ::rand::CryptoRng>(
+        num_authorities: usize,
+        num_accounts: usize,
+        num_objects_per_account: usize,
+        rng: &mut R,
     ) -> Self {
         assert!(
             num_authorities > 0,
@@ -219,7 +277,7 @@ impl GenesisConfig {
             let mut objects = Vec::new();
             for _ in 0..num_objects_per_account {
                 objects.push(ObjectConfig {
-                    object_id: ObjectID::random(),
+                    object_id: ObjectID::random_from_rng(rng),
                     gas_value: DEFAULT_GAS_AMOUNT,
                 })
             }
@@ -245,6 +303,8 @@ impl Default for GenesisConfig {
             grpc_load_shed: None,
             grpc_concurrency_limit: Some(DEFAULT_GRPC_CONCURRENCY_LIMIT),
             accounts: vec![],
+            publisher_allowlist: None,
+            framework_flavor: None,
         }
     }
 }
@@ -0,0 +1,138 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use sui_types::crypto::EncodeDecodeBase64;
+
+/// Key pairs of a node to load from somewhere else than its config file, e.g. a secrets
+/// manager. Key pairs without a source are read from the config file.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct KeySources {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_key: Option<KeySource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_key: Option<KeySource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_key: Option<KeySource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_key: Option<KeySource>,
+}
+
+impl KeySources {
+    pub fn is_empty(&self) -> bool {
+        self.protocol_key.is_none()
+            && self.worker_key.is_none()
+            && self.account_key.is_none()
+            && self.network_key.is_none()
+    }
+}
+
+/// Where to load a key pair from.
+///
+/// Consensus needs the protocol, worker and network key pairs themselves rather than a signing
+/// service, so they are loaded into the node's memory whatever their source.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeySource {
+    /// A secret of a HashiCorp Vault KV version 2 secrets engine.
+    Vault(VaultKeySource),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VaultKeySource {
+    /// Address of the Vault server, e.g. `https://vault.example.com:8200`.
+    pub address: String,
+    /// Mount path of the secrets engine.
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+    /// Path of the secret within the secrets engine.
+    pub path: String,
+    /// Field of the secret holding the base64 encoded key pair.
+    #[serde(default = "default_vault_field")]
+    pub field: String,
+    /// Environment variable holding the Vault token, which is kept out of the config file.
+    #[serde(default = "default_vault_token_env")]
+    pub token_env: String,
+    /// Vault Enterprise namespace of the secrets engine, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+fn default_vault_field() -> String {
+    "key".to_string()
+}
+
+fn default_vault_token_env() -> String {
+    "VAULT_TOKEN".to_string()
+}
+
+#[derive(Deserialize)]
+struct VaultSecretResponse {
+    data: VaultSecretData,
+}
+
+#[derive(Deserialize)]
+struct VaultSecretData {
+    data: BTreeMap<String, String>,
+}
+
+impl KeySource {
+    pub async fn load<T: EncodeDecodeBase64>(&self) -> Result<T> {
+        let encoded = match self {
+            KeySource::Vault(source) => source.read().await?,
+        };
+        T::decode_base64(encoded.trim()).map_err(|e| anyhow!("Invalid key pair in {self}: {e}"))
+    }
+}
+
+impl std::fmt::Display for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySource::Vault(source) => write!(
+                f,
+                "Vault secret {}/{}#{} at {}",
+                source.mount, source.path, source.field, source.address
+            ),
+        }
+    }
+}
+
+impl VaultKeySource {
+    async fn read(&self) -> Result<String> {
+        let token = std::env::var(&self.token_env)
+            .with_context(|| format!("Cannot read the Vault token from ${}", self.token_env))?;
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            self.address.trim_end_matches('/'),
+            self.mount.trim_matches('/'),
+            self.path.trim_start_matches('/')
+        );
+        let mut request = reqwest::Client::new()
+            .get(&url)
+            .header("X-Vault-Token", token);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let mut response: VaultSecretResponse = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Cannot read Vault secret {url}"))?
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response for Vault secret {url}"))?;
+        response
+            .data
+            .data
+            .remove(&self.field)
+            .ok_or_else(|| anyhow!("Vault secret {url} has no field {}", self.field))
+    }
+}
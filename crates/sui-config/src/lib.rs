@@ -14,6 +14,7 @@ pub mod builder;
 pub mod gateway;
 pub mod genesis;
 pub mod genesis_config;
+pub mod key_source;
 pub mod node;
 mod swarm;
 pub mod utils;
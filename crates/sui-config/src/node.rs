@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::genesis;
+use crate::key_source::KeySources;
 use crate::Config;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use multiaddr::Multiaddr;
 use narwhal_config::Parameters as ConsensusParameters;
 use serde::{Deserialize, Serialize};
@@ -13,7 +14,7 @@ use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use sui_types::base_types::SuiAddress;
-use sui_types::committee::StakeUnit;
+use sui_types::committee::{EpochId, StakeUnit};
 use sui_types::crypto::AccountKeyPair;
 use sui_types::crypto::AuthorityKeyPair;
 use sui_types::crypto::AuthorityPublicKeyBytes;
@@ -45,18 +46,35 @@ pub struct NodeConfig {
     #[serde(default = "default_worker_key_pair")]
     #[serde_as(as = "Arc<KeyPairBase64>")]
     pub network_key_pair: Arc<NetworkKeyPair>,
+    /// Key pairs to load from elsewhere, in place of the ones above.
+    #[serde(default, skip_serializing_if = "KeySources::is_empty")]
+    pub key_sources: KeySources,
     pub db_path: PathBuf,
+    /// Backend of the tables of the authority store, in `db-path` by default. The other stores
+    /// of the node stay in `db-path` whatever the backend.
+    #[serde(default, skip_serializing_if = "StorageBackendConfig::is_default")]
+    pub storage_backend: StorageBackendConfig,
+    /// The addresses below take IPv4 or IPv6 hosts, e.g. `/ip6/::/tcp/8080/http` or
+    /// `[::]:9000`. Binding to `::` also accepts IPv4 connections on dual-stack hosts.
     #[serde(default = "default_grpc_address")]
     pub network_address: Multiaddr,
     #[serde(default = "default_json_rpc_address")]
     pub json_rpc_address: SocketAddr,
     #[serde(default = "default_websocket_address")]
     pub websocket_address: Option<SocketAddr>,
+    /// Address of the HTTP endpoint submitting signed transactions, `POST /v1/transactions`, for
+    /// relays and payment processors without a JSON-RPC client. Only served by fullnodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_submit_address: Option<SocketAddr>,
 
     #[serde(default = "default_metrics_address")]
     pub metrics_address: SocketAddr,
     #[serde(default = "default_admin_interface_port")]
     pub admin_interface_port: u16,
+    /// Directives of the log filter, e.g. `info,sui_core=debug`, replacing the ones of the
+    /// `RUST_LOG` environment variable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_filter: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub consensus_config: Option<ConsensusConfig>,
@@ -79,6 +97,67 @@ pub struct NodeConfig {
     #[serde(default = "default_concurrency_limit")]
     pub grpc_concurrency_limit: Option<usize>,
 
+    #[serde(default)]
+    pub connection_limits: ConnectionLimitsConfig,
+
+    #[serde(default)]
+    pub checkpoint_limits: CheckpointLimitsConfig,
+
+    #[serde(default)]
+    pub store_cache: StoreCacheConfig,
+
+    #[serde(default)]
+    pub json_rpc_server: JsonRpcServerConfig,
+
+    /// Debug mode executing every certificate twice, slowing execution down. Not meant for
+    /// production nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_cross_check: Option<ExecutionCrossCheckConfig>,
+
+    /// Epoch from which each execution engine built into the binary is used, by protocol
+    /// version. Every validator must configure the same activations, or their effects diverge
+    /// at the first activation. The genesis version is always active from epoch 0.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub execution_engine_activations: BTreeMap<u64, EpochId>,
+
+    /// Object store the certified checkpoints are written to, with the certificates and effects
+    /// of their transactions, for pipelines to process the chain without querying a node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint_export: Option<ObjectStoreConfig>,
+
+    /// Retention of the transactions, effects and old object versions of the node, which are
+    /// kept forever when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pruning: Option<PruningConfig>,
+
+    /// Export of state snapshots, and bootstrap of a new node from the latest one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_snapshot: Option<StateSnapshotConfig>,
+
+    /// Health and readiness endpoints, e.g. for the probes of an orchestrator or the checks of a
+    /// load balancer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<HealthConfig>,
+
+    /// Export of the tracing spans of the node to an OpenTelemetry collector, over OTLP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp: Option<OtlpConfig>,
+
+    /// Coalescing of the transactions and certificates the node sends to each validator into
+    /// batches, sent in a single request each.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_batching: Option<RequestBatchingConfig>,
+
+    /// Tuning of the RocksDB database of the authority store, and export of its statistics as
+    /// metrics. RocksDB defaults are kept when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<StorageConfig>,
+
+    /// Limits on the transactions and certificates a validator handles at once, beyond which it
+    /// sheds new ones with a retryable overloaded error. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admission_control: Option<AdmissionControlConfig>,
+
     pub genesis: Genesis,
 }
 
@@ -126,6 +205,523 @@ pub fn bool_true() -> bool {
     true
 }
 
+/// Limits protecting the node from running out of file descriptors. An exhausted descriptor
+/// limit otherwise shows up as stalled consensus or storage rather than as a clear error.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConnectionLimitsConfig {
+    /// Maximum number of concurrent websocket JSON-RPC connections. Connections beyond this
+    /// cap are rejected by the server.
+    #[serde(default = "default_max_websocket_connections")]
+    pub max_websocket_connections: u64,
+    /// Fraction of the process file descriptor limit above which the node logs the
+    /// subsystems holding the most descriptors.
+    #[serde(default = "default_fd_warn_ratio")]
+    pub fd_warn_ratio: f64,
+    /// Fraction of the process file descriptor limit above which inbound validator requests
+    /// are rejected, until usage falls back below `fd_warn_ratio`.
+    #[serde(default = "default_fd_reject_ratio")]
+    pub fd_reject_ratio: f64,
+    /// How often descriptor usage is sampled, in milliseconds.
+    #[serde(default = "default_fd_check_interval_ms")]
+    pub fd_check_interval_ms: u64,
+    /// Maximum number of inbound gRPC connections open at once. Connections beyond this cap are
+    /// closed as soon as they are accepted. Defaults to half the process file descriptor limit.
+    /// Only read when the node starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_grpc_connections: Option<usize>,
+}
+
+fn default_max_websocket_connections() -> u64 {
+    100
+}
+
+fn default_fd_warn_ratio() -> f64 {
+    0.8
+}
+
+fn default_fd_reject_ratio() -> f64 {
+    0.95
+}
+
+fn default_fd_check_interval_ms() -> u64 {
+    10_000
+}
+
+impl Default for ConnectionLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_websocket_connections: default_max_websocket_connections(),
+            fd_warn_ratio: default_fd_warn_ratio(),
+            fd_reject_ratio: default_fd_reject_ratio(),
+            fd_check_interval_ms: default_fd_check_interval_ms(),
+            max_grpc_connections: None,
+        }
+    }
+}
+
+/// Bounds on the size of a checkpoint. The transactions agreed on for a checkpoint beyond them, in
+/// causal order, are left to the next checkpoints. Validators sign different checkpoints unless
+/// they all use the same limits.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CheckpointLimitsConfig {
+    /// Maximum number of transactions in a checkpoint.
+    #[serde(default = "default_max_checkpoint_transactions")]
+    pub max_transactions: u64,
+    /// Maximum total size in bytes of the effects of the transactions in a checkpoint. The
+    /// certificates aren't counted, as their signatures differ between validators.
+    #[serde(default = "default_max_checkpoint_effects_bytes")]
+    pub max_effects_bytes: u64,
+}
+
+fn default_max_checkpoint_transactions() -> u64 {
+    10_000
+}
+
+fn default_max_checkpoint_effects_bytes() -> u64 {
+    32 * 1024 * 1024
+}
+
+impl Default for CheckpointLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_transactions: default_max_checkpoint_transactions(),
+            max_effects_bytes: default_max_checkpoint_effects_bytes(),
+        }
+    }
+}
+
+/// Capacities of the cache of the recently read and written objects and transaction effects of
+/// the authority store, and of the packages executed transactions load. A capacity of zero
+/// disables the cache of objects, effects or packages.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoreCacheConfig {
+    /// Maximum number of object versions cached.
+    #[serde(default = "default_store_cache_capacity")]
+    pub object_capacity: usize,
+    /// Maximum number of transaction effects cached.
+    #[serde(default = "default_store_cache_capacity")]
+    pub effects_capacity: usize,
+    /// Maximum number of packages cached.
+    #[serde(default = "default_package_cache_capacity")]
+    pub package_capacity: usize,
+}
+
+fn default_store_cache_capacity() -> usize {
+    10_000
+}
+
+fn default_package_cache_capacity() -> usize {
+    1_000
+}
+
+impl Default for StoreCacheConfig {
+    fn default() -> Self {
+        Self {
+            object_capacity: default_store_cache_capacity(),
+            effects_capacity: default_store_cache_capacity(),
+            package_capacity: default_package_cache_capacity(),
+        }
+    }
+}
+
+/// Browser access, batches, request logging and abuse protection of the JSON-RPC servers of a
+/// fullnode.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcServerConfig {
+    /// Origins browsers may call the servers from, e.g. `https://wallet.example.com`, or `*` for
+    /// any origin. When unset, the origins of the `ACCESS_CONTROL_ALLOW_ORIGIN` environment
+    /// variable are allowed if it is set, and any origin otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors_allowed_origins: Option<Vec<String>>,
+    /// Request headers browsers may send on top of the CORS-safelisted ones, e.g.
+    /// `Authorization`. The servers only answer `POST` requests and their preflights, so the
+    /// allowed methods are not configurable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors_allowed_headers: Option<Vec<String>>,
+    /// Log every HTTP request with its methods, duration, status, origin and response size,
+    /// under the `sui_json_rpc::access_log` target.
+    #[serde(default)]
+    pub access_log: bool,
+    /// Answer batches, JSON arrays of requests sent at once, with the array of the result or
+    /// error of each request.
+    #[serde(default = "bool_true")]
+    pub batch_requests: bool,
+    /// Largest request the servers accept, in bytes, which bounds the size of the batches.
+    /// 10 MiB when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_request_body_size: Option<u32>,
+    /// Rate limit of the calls of each client IP address to the HTTP server, a batch taking one
+    /// call per request in it. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_ip: Option<RateLimitConfig>,
+    /// Rate limits of the calls to methods of the HTTP server, from all the clients together, by
+    /// method name, e.g. `sui_executeTransaction`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_rate_limits: BTreeMap<String, RateLimitConfig>,
+    /// The only methods the HTTP server answers, or all of them when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_methods: Option<Vec<String>>,
+    /// Methods the HTTP server doesn't answer, even if allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_methods: Vec<String>,
+    /// Time after which the HTTP server answers the calls of a request with an error. The calls
+    /// aren't cancelled and still run to completion. Unbounded when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_ms: Option<u64>,
+    /// Answer with addresses and object IDs in a checksummed, mixed case hex, which the clients
+    /// can check to catch typos. It is the form they are displayed in by the whole process, logs
+    /// included. Both forms are accepted in requests either way.
+    #[serde(default)]
+    pub checksummed_addresses: bool,
+}
+
+/// A token bucket: calls take a token each, and the bucket is refilled with
+/// `requests-per-second` tokens a second up to `burst` tokens.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimitConfig {
+    pub requests_per_second: u32,
+    /// The calls that can be made at once after a pause, `requests-per-second` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitConfig {
+    pub fn burst(&self) -> u32 {
+        self.burst.unwrap_or(self.requests_per_second)
+    }
+}
+
+impl Default for JsonRpcServerConfig {
+    fn default() -> Self {
+        Self {
+            cors_allowed_origins: None,
+            cors_allowed_headers: None,
+            access_log: false,
+            batch_requests: true,
+            max_request_body_size: None,
+            rate_limit_per_ip: None,
+            method_rate_limits: BTreeMap::new(),
+            allowed_methods: None,
+            denied_methods: vec![],
+            request_timeout_ms: None,
+            checksummed_addresses: false,
+        }
+    }
+}
+
+/// Executes every certificate a second time and compares the digests of the effects, to catch
+/// nondeterministic execution in release qualification before it forks a network.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionCrossCheckConfig {
+    /// Protocol version of the execution engine running the second execution, by default the
+    /// one of the current epoch. Compares a version registered ahead of its activation with
+    /// the active one.
+    #[serde(default)]
+    pub protocol_version: Option<u64>,
+    /// Halt the node on divergence, before the effects are committed, rather than only logging
+    /// it.
+    #[serde(default = "bool_true")]
+    pub halt_on_divergence: bool,
+}
+
+/// Deletes the certificates, effects and signed transactions of the certified checkpoints older
+/// than the retention windows, with the object versions those transactions replaced, so that the
+/// store of a node doesn't grow without bound. A checkpoint is pruned once it is out of every
+/// window set. Pruned transactions are no longer served, and checkpoints being exported must not
+/// be pruned before they are written.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PruningConfig {
+    /// Number of the latest checkpoints kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_checkpoints_to_keep: Option<u64>,
+    /// Number of the latest epochs, the current one included, whose checkpoints are kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_keep: Option<u64>,
+    /// How often the checkpoints out of the retention windows are pruned, in seconds.
+    #[serde(default = "default_pruning_interval_secs")]
+    pub interval_secs: u64,
+    /// Maximum number of checkpoints pruned per interval, bounding the load of pruning on the
+    /// store while it catches up with a long history.
+    #[serde(default = "default_max_pruned_checkpoints_per_interval")]
+    pub max_checkpoints_per_interval: u64,
+}
+
+fn default_pruning_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_pruned_checkpoints_per_interval() -> u64 {
+    100
+}
+
+impl PruningConfig {
+    /// Checks that some data is retained, as a window of zero checkpoints or epochs would prune
+    /// the data of the checkpoints being executed.
+    pub fn validate(&self) -> Result<()> {
+        if self.num_checkpoints_to_keep.is_none() && self.num_epochs_to_keep.is_none() {
+            bail!("Pruning needs num-checkpoints-to-keep or num-epochs-to-keep");
+        }
+        if self.num_checkpoints_to_keep == Some(0) || self.num_epochs_to_keep == Some(0) {
+            bail!("Pruning must keep at least one checkpoint and one epoch");
+        }
+        if self.interval_secs == 0 || self.max_checkpoints_per_interval == 0 {
+            bail!("Pruning needs a non-zero interval and number of checkpoints per interval");
+        }
+        Ok(())
+    }
+}
+
+/// Snapshots of the live objects as of a certified checkpoint, written to an object store by
+/// validators so that new nodes install the latest one and sync the checkpoints after it, rather
+/// than executing the whole history. A snapshot is verified with the certificate of its
+/// checkpoint and the signature of the validator which exported it, against the genesis
+/// committee, so only snapshots of the first epoch exported by validators can be installed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StateSnapshotConfig {
+    pub store: ObjectStoreConfig,
+    /// Export a snapshot every this many checkpoints, never when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_interval: Option<u64>,
+    /// Install the latest snapshot of the store when the node starts with empty stores.
+    #[serde(default)]
+    pub bootstrap: bool,
+}
+
+/// The `/health` and `/ready` endpoints of a node. A node is healthy while its store can be read,
+/// and ready while it is also synced to the checkpoints it knows of, within `max-checkpoint-lag`,
+/// and, for validators, hears from consensus.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HealthConfig {
+    pub address: SocketAddr,
+    /// Number of the checkpoints known to be certified a ready node may not have synced yet.
+    #[serde(default = "default_max_checkpoint_lag")]
+    pub max_checkpoint_lag: u64,
+    /// Time after which a validator which handled no consensus transaction is not ready, in
+    /// seconds. Consensus is silent while there is no traffic, so this is unchecked when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_consensus_silence_secs: Option<u64>,
+}
+
+fn default_max_checkpoint_lag() -> u64 {
+    10
+}
+
+/// The OTLP gRPC exporter of tracing spans, e.g. to correlate the spans of a transaction across
+/// validators by its digest.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OtlpConfig {
+    /// The gRPC endpoint of the collector, e.g. `http://localhost:4317`.
+    pub endpoint: String,
+    /// Share of the traces exported, from 0 for none to 1 for all.
+    #[serde(default = "default_sampling_ratio")]
+    pub sampling_ratio: f64,
+    /// Attributes of the resource the spans are exported for, in addition to its `service.name`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub resource_attributes: BTreeMap<String, String>,
+}
+
+fn default_sampling_ratio() -> f64 {
+    1.0
+}
+
+/// The batches of transactions and certificates sent to a validator. A batch is sent once it
+/// holds `max-batch-size` requests, or `linger-ms` after its first request, whichever comes first.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequestBatchingConfig {
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+    #[serde(default = "default_linger_ms")]
+    pub linger_ms: u64,
+}
+
+fn default_max_batch_size() -> usize {
+    100
+}
+
+fn default_linger_ms() -> u64 {
+    5
+}
+
+impl Default for RequestBatchingConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: default_max_batch_size(),
+            linger_ms: default_linger_ms(),
+        }
+    }
+}
+
+/// Admission of the transactions and certificates sent to a validator. Certificates, already
+/// signed by a quorum, are not shed on the depth of the consensus queue, so that the validator
+/// finishes the work the network committed to before taking on new transactions.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AdmissionControlConfig {
+    /// Maximum number of transactions and certificates handled at once.
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+    /// Maximum number of transactions and certificates of a single sender handled at once.
+    #[serde(default = "default_max_in_flight_per_sender")]
+    pub max_in_flight_per_sender: usize,
+    /// Number of certificates waiting to be sequenced by consensus above which new transactions
+    /// are shed.
+    #[serde(default = "default_max_consensus_queue_depth")]
+    pub max_consensus_queue_depth: usize,
+}
+
+fn default_max_in_flight() -> usize {
+    20_000
+}
+
+fn default_max_in_flight_per_sender() -> usize {
+    100
+}
+
+fn default_max_consensus_queue_depth() -> usize {
+    5_000
+}
+
+impl Default for AdmissionControlConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: default_max_in_flight(),
+            max_in_flight_per_sender: default_max_in_flight_per_sender(),
+            max_consensus_queue_depth: default_max_consensus_queue_depth(),
+        }
+    }
+}
+
+/// The backend the authority store of a node keeps its tables in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StorageBackendConfig {
+    /// A RocksDB database in the directory of the store.
+    #[default]
+    RocksDb,
+    /// Maps in memory, lost when the node stops. Meant for tests and simulations, e.g. of a
+    /// swarm, which run faster without writing to disk.
+    InMemory,
+}
+
+impl StorageBackendConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Tuning of the RocksDB database of the authority store. The options at the top level apply to
+/// every column family, and those of `column-families` override them for single column
+/// families, keyed by table name, e.g. `objects` or `effects`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageConfig {
+    #[serde(flatten)]
+    pub defaults: ColumnFamilyConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_families: BTreeMap<String, ColumnFamilyConfig>,
+    /// How often the statistics of the database, e.g. compaction bytes, write stalls and block
+    /// cache hits, are exported as metrics. RocksDB only collects them when set, at a small cost
+    /// on every read and write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_interval_secs: Option<u64>,
+}
+
+impl StorageConfig {
+    /// The options of `column_family`, the top level ones overridden by its own.
+    pub fn column_family(&self, column_family: &str) -> ColumnFamilyConfig {
+        match self.column_families.get(column_family) {
+            Some(config) => ColumnFamilyConfig {
+                block_cache_size_mb: config
+                    .block_cache_size_mb
+                    .or(self.defaults.block_cache_size_mb),
+                write_buffer_size_mb: config
+                    .write_buffer_size_mb
+                    .or(self.defaults.write_buffer_size_mb),
+                compression: config.compression.or(self.defaults.compression),
+                compaction_style: config.compaction_style.or(self.defaults.compaction_style),
+            },
+            None => self.defaults.clone(),
+        }
+    }
+}
+
+/// Options of RocksDB column families, the RocksDB defaults of the store when unset.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ColumnFamilyConfig {
+    /// Size of the LRU cache of uncompressed blocks. The column families without a size of their
+    /// own share a cache of the top level size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_cache_size_mb: Option<usize>,
+    /// Size of a memtable, written out to a file once full.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_buffer_size_mb: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<CompressionConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compaction_style: Option<CompactionStyleConfig>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompressionConfig {
+    None,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompactionStyleConfig {
+    /// Sorted runs of increasing size, the fewest reads and least space.
+    Level,
+    /// Sorted runs merged by size, the least write amplification.
+    Universal,
+    /// The oldest files dropped once the column family is full, for caches of data which expires.
+    Fifo,
+}
+
+/// Where blobs are stored, e.g. the exported checkpoints.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ObjectStoreConfig {
+    /// A directory of the local file system, e.g. a mounted volume.
+    File { path: PathBuf },
+    /// A bucket of S3, or of another store with an S3 compatible API, e.g. GCS with HMAC keys.
+    S3(S3StoreConfig),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct S3StoreConfig {
+    /// URL of the bucket, virtual-hosted or path style, e.g.
+    /// `https://my-bucket.s3.us-east-1.amazonaws.com` or `https://storage.googleapis.com/my-bucket`.
+    pub bucket_url: String,
+    /// Region the requests are signed for, `auto` for GCS.
+    pub region: String,
+    /// Path in the bucket the blobs are stored under.
+    #[serde(default)]
+    pub prefix: String,
+    /// Credentials of the requests, read from the `AWS_ACCESS_KEY_ID` and
+    /// `AWS_SECRET_ACCESS_KEY` environment variables when unset, to keep them out of the config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+}
+
 impl Config for NodeConfig {}
 
 impl NodeConfig {
@@ -164,6 +760,24 @@ impl NodeConfig {
     pub fn genesis(&self) -> Result<&genesis::Genesis> {
         self.genesis.genesis()
     }
+
+    /// Replaces the key pairs which have a source in `key_sources` with the ones loaded from it.
+    pub async fn load_key_pairs(&mut self) -> Result<()> {
+        let sources = &self.key_sources;
+        if let Some(source) = &sources.protocol_key {
+            self.protocol_key_pair = Arc::new(source.load().await.context("protocol key pair")?);
+        }
+        if let Some(source) = &sources.worker_key {
+            self.worker_key_pair = Arc::new(source.load().await.context("worker key pair")?);
+        }
+        if let Some(source) = &sources.account_key {
+            self.account_key_pair = Arc::new(source.load().await.context("account key pair")?);
+        }
+        if let Some(source) = &sources.network_key {
+            self.network_key_pair = Arc::new(source.load().await.context("network key pair")?);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -172,6 +786,10 @@ pub struct ConsensusConfig {
     pub consensus_address: Multiaddr,
     pub consensus_db_path: PathBuf,
     pub delay_step: Option<u64>,
+    /// Whether to submit certificates to the consensus worker of the validator leading the next
+    /// consensus round rather than to ours, so that they are sequenced sooner. Off if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relay_to_leader: Option<bool>,
 
     pub narwhal_config: ConsensusParameters,
 }
@@ -188,6 +806,10 @@ impl ConsensusConfig {
     pub fn narwhal_config(&self) -> &ConsensusParameters {
         &self.narwhal_config
     }
+
+    pub fn relay_to_leader(&self) -> bool {
+        self.relay_to_leader.unwrap_or(false)
+    }
 }
 
 /// Publicly known information about a validator
@@ -204,6 +826,9 @@ pub struct ValidatorInfo {
     pub stake: StakeUnit,
     pub delegation: StakeUnit,
     pub gas_price: u64,
+    /// Where clients and other validators reach the validator. With a DNS name, e.g.
+    /// `/dns/validator.example.com/tcp/8080/http`, the validator can move to other IPs without
+    /// changing it: clients resolve the name again periodically and when requests fail.
     pub network_address: Multiaddr,
     pub narwhal_primary_address: Multiaddr,
 
@@ -318,9 +943,34 @@ enum GenesisLocation {
 
 #[cfg(test)]
 mod tests {
-    use super::Genesis;
+    use super::{CompactionStyleConfig, CompressionConfig, Genesis, StorageConfig};
+    use crate::key_source::{KeySource, KeySources};
     use crate::{genesis, NodeConfig};
 
+    #[test]
+    fn storage_config_column_family_overrides() {
+        let config: StorageConfig = serde_yaml::from_str(
+            "block-cache-size-mb: 512\n\
+             compression: lz4\n\
+             metrics-interval-secs: 60\n\
+             column-families:\n  \
+               effects:\n    \
+                 compression: zstd\n    \
+                 compaction-style: universal\n",
+        )
+        .unwrap();
+        assert_eq!(config.metrics_interval_secs, Some(60));
+
+        let effects = config.column_family("effects");
+        assert_eq!(effects.block_cache_size_mb, Some(512));
+        assert_eq!(effects.compression, Some(CompressionConfig::Zstd));
+        assert_eq!(
+            effects.compaction_style,
+            Some(CompactionStyleConfig::Universal)
+        );
+        assert_eq!(config.column_family("objects"), config.defaults);
+    }
+
     #[test]
     fn serialize_genesis_config_from_file() {
         let g = Genesis::new_from_file("path/to/file");
@@ -357,6 +1007,24 @@ mod tests {
         assert_eq!(&genesis, loaded_genesis);
     }
 
+    #[test]
+    fn key_sources_config() {
+        let sources: KeySources = serde_yaml::from_str(
+            "protocol-key:\n  vault:\n    address: https://vault.example.com:8200\n    path: validators/0\n",
+        )
+        .unwrap();
+        match sources.protocol_key.unwrap() {
+            KeySource::Vault(source) => {
+                assert_eq!(source.mount, "secret");
+                assert_eq!(source.path, "validators/0");
+                assert_eq!(source.field, "key");
+                assert_eq!(source.token_env, "VAULT_TOKEN");
+            }
+        }
+        assert!(sources.network_key.is_none());
+        assert!(KeySources::default().is_empty());
+    }
+
     #[test]
     fn fullnode_template() {
         const TEMPLATE: &str = include_str!("../data/fullnode-template.yaml");
@@ -1,8 +1,10 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::genesis_config::GenesisConfig;
 use crate::{builder, genesis, utils, Config, NodeConfig, ValidatorInfo, FULL_NODE_DB_PATH};
-use rand::rngs::OsRng;
+use rand::rngs::{OsRng, StdRng};
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::num::NonZeroUsize;
@@ -59,6 +61,21 @@ impl NetworkConfig {
         Self::generate_with_rng(config_dir, quorum_size, OsRng)
     }
 
+    /// Generate a network whose keys, gas objects and ports are all derived from `seed`, so the
+    /// same arguments produce the same config on every run and platform. Intended for fixtures
+    /// and documentation; the ports are fixed, so networks generated this way can't run side by
+    /// side.
+    pub fn deterministic(config_dir: &Path, seed: u64, committee_size: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let initial_accounts_config = GenesisConfig::for_local_testing_with_rng(&mut rng);
+        builder::ConfigBuilder::new(config_dir)
+            .committee_size(NonZeroUsize::new(committee_size).unwrap())
+            .randomize_ports(false)
+            .initial_accounts_config(initial_accounts_config)
+            .rng(rng)
+            .build()
+    }
+
     pub fn generate_fullnode_config(&self) -> NodeConfig {
         self.generate_fullnode_config_with_custom_db_path(None, true)
     }
@@ -95,16 +112,20 @@ impl NetworkConfig {
             worker_key_pair,
             account_key_pair,
             network_key_pair,
+            key_sources: Default::default(),
             db_path: db_path.join(fullnode_db_dir.unwrap_or(FULL_NODE_DB_PATH)),
+            storage_backend: validator_config.storage_backend,
             network_address: utils::new_network_address(),
             metrics_address: utils::available_local_socket_address(),
             admin_interface_port: utils::get_available_port(),
+            log_filter: None,
             json_rpc_address: utils::available_local_socket_address(),
             websocket_address: if enable_websocket {
                 Some(utils::available_local_socket_address())
             } else {
                 None
             },
+            transaction_submit_address: None,
             consensus_config: None,
             enable_event_processing,
             enable_gossip: true,
@@ -113,6 +134,46 @@ impl NetworkConfig {
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
             grpc_concurrency_limit: None,
+            connection_limits: Default::default(),
+            checkpoint_limits: Default::default(),
+            store_cache: Default::default(),
+            json_rpc_server: Default::default(),
+            execution_cross_check: None,
+            execution_engine_activations: Default::default(),
+            checkpoint_export: None,
+            pruning: None,
+            state_snapshot: None,
+            health: None,
+            otlp: None,
+            request_batching: None,
+            storage: None,
+            admission_control: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::NetworkConfig;
+    use std::path::Path;
+
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    fn deterministic_network_config_is_stable() {
+        let config_dir = Path::new("/tmp/sui-deterministic");
+        let config = NetworkConfig::deterministic(config_dir, 42, 4);
+        let same_seed = NetworkConfig::deterministic(config_dir, 42, 4);
+        let other_seed = NetworkConfig::deterministic(config_dir, 43, 4);
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(s, serde_yaml::to_string(&same_seed).unwrap());
+        assert_ne!(s, serde_yaml::to_string(&other_seed).unwrap());
+
+        let ports = config
+            .validator_configs()
+            .iter()
+            .map(|config| config.json_rpc_address.port())
+            .collect::<Vec<_>>();
+        assert_eq!(ports, vec![10_006, 10_016, 10_026, 10_036]);
+    }
+}
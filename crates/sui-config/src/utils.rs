@@ -1,9 +1,12 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use multiaddr::{Multiaddr, Protocol};
+use std::net::{IpAddr, Ipv4Addr};
+
 #[cfg(not(msim))]
 mod inner {
-    use std::net::{TcpListener, TcpStream};
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
 
     /// Return an ephemeral, available port. On unix systems, the port returned will be in the
     /// TIME_WAIT state ensuring that the OS won't hand out this port for some grace period.
@@ -35,9 +38,7 @@ mod inner {
     }
 
     pub fn new_network_address() -> multiaddr::Multiaddr {
-        format!("/ip4/127.0.0.1/tcp/{}/http", get_available_port())
-            .parse()
-            .unwrap()
+        super::ip_network_address(Ipv4Addr::LOCALHOST.into(), get_available_port())
     }
 }
 
@@ -70,16 +71,97 @@ mod inner {
             })
             .unwrap_or_else(|| "127.0.0.1".parse().unwrap());
 
-        format!("/ip4/{}/tcp/{}/http", ip_addr, get_available_port())
-            .parse()
-            .unwrap()
+        super::ip_network_address(ip_addr, get_available_port())
     }
 }
 
 pub use inner::*;
 
 pub fn available_local_socket_address() -> std::net::SocketAddr {
-    format!("127.0.0.1:{}", get_available_port())
-        .parse()
-        .unwrap()
+    local_socket_address(get_available_port())
+}
+
+pub fn local_socket_address(port: u16) -> std::net::SocketAddr {
+    format!("127.0.0.1:{}", port).parse().unwrap()
+}
+
+pub fn local_network_address(port: u16) -> multiaddr::Multiaddr {
+    ip_network_address(Ipv4Addr::LOCALHOST.into(), port)
+}
+
+/// The address of an HTTP service on `port` of `ip`, as `/ip4/..` or `/ip6/..` following the
+/// family of `ip`.
+pub fn ip_network_address(ip: IpAddr, port: u16) -> Multiaddr {
+    Multiaddr::empty()
+        .with(Protocol::from(ip))
+        .with(Protocol::Tcp(port))
+        .with(Protocol::Http)
+}
+
+/// Checks that `address` is one other nodes can dial: an IPv4 or IPv6 address or a DNS name,
+/// followed by a TCP port. Wildcards like `0.0.0.0` and `::` only make sense as bind addresses.
+pub fn check_peer_address(address: &Multiaddr) -> anyhow::Result<()> {
+    let mut protocols = address.iter();
+    match protocols.next() {
+        Some(Protocol::Ip4(ip)) if !ip.is_unspecified() => {}
+        Some(Protocol::Ip6(ip)) if !ip.is_unspecified() => {}
+        Some(Protocol::Dns(_) | Protocol::Dns4(_) | Protocol::Dns6(_)) => {}
+        _ => anyhow::bail!(
+            "{address} should start with the IPv4 or IPv6 address, or the DNS name of the host"
+        ),
+    }
+    match protocols.next() {
+        Some(Protocol::Tcp(_)) => Ok(()),
+        _ => anyhow::bail!("{address} should have a TCP port after its host"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_peer_address, ip_network_address};
+    use multiaddr::Multiaddr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn ip_network_address_follows_ip_family() {
+        assert_eq!(
+            ip_network_address(Ipv4Addr::new(10, 0, 0, 1).into(), 8080).to_string(),
+            "/ip4/10.0.0.1/tcp/8080/http"
+        );
+        assert_eq!(
+            ip_network_address(Ipv6Addr::LOCALHOST.into(), 8080).to_string(),
+            "/ip6/::1/tcp/8080/http"
+        );
+    }
+
+    #[test]
+    fn check_peer_address_accepts_dialable_addresses() {
+        for address in [
+            "/ip4/10.0.0.1/tcp/8080/http",
+            "/ip6/::1/tcp/8080",
+            "/dns/validator.example.com/tcp/8080/http",
+            "/dns4/validator.example.com/tcp/8080",
+            "/dns6/validator.example.com/tcp/8080",
+        ] {
+            let address: Multiaddr = address.parse().unwrap();
+            check_peer_address(&address).unwrap();
+        }
+    }
+
+    #[test]
+    fn check_peer_address_rejects_undialable_addresses() {
+        for address in [
+            // Wildcards are bind addresses
+            "/ip4/0.0.0.0/tcp/8080/http",
+            "/ip6/::/tcp/8080",
+            // No host
+            "/tcp/8080/http",
+            // No TCP port
+            "/ip4/10.0.0.1/http",
+            "/dns/validator.example.com",
+        ] {
+            let address: Multiaddr = address.parse().unwrap();
+            assert!(check_peer_address(&address).is_err(), "{address}");
+        }
+    }
 }
@@ -0,0 +1,236 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control of the transactions and certificates sent to a validator, so that it sheds
+//! the load it has no capacity for with a retryable error, rather than queuing everything and
+//! answering every request late.
+//!
+//! A request is admitted as long as the validator handles fewer than `max-in-flight` requests,
+//! and fewer than `max-in-flight-per-sender` of its sender. New transactions are also shed while
+//! too many certificates wait to be sequenced by consensus, whereas certificates are not, so
+//! that the validator finishes the work it signed for first.
+
+use parking_lot::Mutex;
+use prometheus::{
+    register_int_counter_vec_with_registry, register_int_gauge_with_registry, IntCounterVec,
+    IntGauge, Registry,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use sui_config::node::AdmissionControlConfig;
+use sui_types::base_types::SuiAddress;
+use sui_types::error::{SuiError, SuiResult};
+
+pub struct AdmissionMetrics {
+    in_flight: IntGauge,
+    consensus_queue_depth: IntGauge,
+    rejected: IntCounterVec,
+}
+
+impl AdmissionMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            in_flight: register_int_gauge_with_registry!(
+                "admission_in_flight",
+                "Number of transactions and certificates being handled by the validator",
+                registry,
+            )
+            .unwrap(),
+            consensus_queue_depth: register_int_gauge_with_registry!(
+                "admission_consensus_queue_depth",
+                "Number of certificates waiting to be sequenced by consensus",
+                registry,
+            )
+            .unwrap(),
+            rejected: register_int_counter_vec_with_registry!(
+                "admission_rejected",
+                "Number of transactions and certificates shed by the validator, by kind and reason",
+                &["kind", "reason"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+pub struct AdmissionController {
+    /// No request is shed when unset.
+    config: Option<AdmissionControlConfig>,
+    in_flight: AtomicUsize,
+    in_flight_per_sender: Mutex<HashMap<SuiAddress, usize>>,
+    consensus_queue_depth: AtomicUsize,
+    metrics: AdmissionMetrics,
+}
+
+/// Counts a request among those in flight until dropped.
+pub struct AdmissionPermit<'a> {
+    controller: &'a AdmissionController,
+    sender: SuiAddress,
+}
+
+impl Drop for AdmissionPermit<'_> {
+    fn drop(&mut self) {
+        let controller = self.controller;
+        let in_flight = controller.in_flight.fetch_sub(1, Ordering::Relaxed) - 1;
+        controller.metrics.in_flight.set(in_flight as i64);
+
+        let mut per_sender = controller.in_flight_per_sender.lock();
+        if let Some(count) = per_sender.get_mut(&self.sender) {
+            *count -= 1;
+            if *count == 0 {
+                per_sender.remove(&self.sender);
+            }
+        }
+    }
+}
+
+/// Counts a certificate among those waiting for consensus until dropped.
+pub struct ConsensusQueueSlot<'a>(&'a AdmissionController);
+
+impl Drop for ConsensusQueueSlot<'_> {
+    fn drop(&mut self) {
+        let depth = self.0.consensus_queue_depth.fetch_sub(1, Ordering::Relaxed) - 1;
+        self.0.metrics.consensus_queue_depth.set(depth as i64);
+    }
+}
+
+impl AdmissionController {
+    pub fn new(config: Option<AdmissionControlConfig>, registry: &Registry) -> Self {
+        Self {
+            config,
+            in_flight: AtomicUsize::new(0),
+            in_flight_per_sender: Mutex::new(HashMap::new()),
+            consensus_queue_depth: AtomicUsize::new(0),
+            metrics: AdmissionMetrics::new(registry),
+        }
+    }
+
+    /// Admits a transaction of `sender` to be signed, unless the validator is overloaded.
+    pub fn admit_transaction(&self, sender: SuiAddress) -> SuiResult<AdmissionPermit<'_>> {
+        if let Some(config) = &self.config {
+            let depth = self.consensus_queue_depth.load(Ordering::Relaxed);
+            if depth >= config.max_consensus_queue_depth {
+                return Err(self.reject(
+                    "transaction",
+                    "consensus_queue_depth",
+                    format!("{depth} certificates are waiting for consensus"),
+                ));
+            }
+        }
+        self.admit("transaction", sender)
+    }
+
+    /// Admits a certificate of `sender` to be executed, unless the validator is overloaded.
+    pub fn admit_certificate(&self, sender: SuiAddress) -> SuiResult<AdmissionPermit<'_>> {
+        self.admit("certificate", sender)
+    }
+
+    /// Counts a certificate among those waiting to be sequenced by consensus, while the slot is
+    /// held.
+    pub fn enter_consensus_queue(&self) -> ConsensusQueueSlot<'_> {
+        let depth = self.consensus_queue_depth.fetch_add(1, Ordering::Relaxed) + 1;
+        self.metrics.consensus_queue_depth.set(depth as i64);
+        ConsensusQueueSlot(self)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    fn admit(&self, kind: &str, sender: SuiAddress) -> SuiResult<AdmissionPermit<'_>> {
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        let config = match &self.config {
+            Some(config) => config,
+            // Senders are not counted, as they have no quota
+            None => {
+                self.metrics.in_flight.set(in_flight as i64);
+                return Ok(AdmissionPermit {
+                    controller: self,
+                    sender,
+                });
+            }
+        };
+        if in_flight > config.max_in_flight {
+            self.in_flight.fetch_sub(1, Ordering::Relaxed);
+            return Err(self.reject(
+                kind,
+                "in_flight",
+                format!("{} requests are in flight", config.max_in_flight),
+            ));
+        }
+
+        {
+            let mut per_sender = self.in_flight_per_sender.lock();
+            let count = per_sender.entry(sender).or_insert(0);
+            if *count >= config.max_in_flight_per_sender {
+                drop(per_sender);
+                self.in_flight.fetch_sub(1, Ordering::Relaxed);
+                return Err(self.reject(
+                    kind,
+                    "sender_quota",
+                    format!(
+                        "{} requests of sender {sender} are in flight",
+                        config.max_in_flight_per_sender
+                    ),
+                ));
+            }
+            *count += 1;
+        }
+        self.metrics.in_flight.set(in_flight as i64);
+        Ok(AdmissionPermit {
+            controller: self,
+            sender,
+        })
+    }
+
+    fn reject(&self, kind: &str, reason: &str, error: String) -> SuiError {
+        self.metrics
+            .rejected
+            .with_label_values(&[kind, reason])
+            .inc();
+        SuiError::ValidatorOverloaded { error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admission_control() {
+        let config = AdmissionControlConfig {
+            max_in_flight: 3,
+            max_in_flight_per_sender: 2,
+            max_consensus_queue_depth: 1,
+        };
+        let controller = AdmissionController::new(Some(config), &Registry::new());
+        let (first, second) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+
+        let permits = [
+            controller.admit_transaction(first).unwrap(),
+            controller.admit_certificate(first).unwrap(),
+        ];
+        // The sender is over its quota, the others are not
+        let err = controller.admit_transaction(first).err().unwrap();
+        assert!(err.is_retryable());
+        let permit = controller.admit_transaction(second).unwrap();
+
+        // The validator is at capacity
+        assert!(controller.admit_certificate(second).is_err());
+        drop(permits);
+        assert_eq!(controller.in_flight(), 1);
+
+        // Only new transactions are shed while the consensus queue is full
+        let slot = controller.enter_consensus_queue();
+        assert!(controller.admit_transaction(first).is_err());
+        let _certificate = controller.admit_certificate(first).unwrap();
+        drop(slot);
+        let _transaction = controller.admit_transaction(first).unwrap();
+
+        drop(permit);
+        assert_eq!(controller.in_flight(), 2);
+    }
+}
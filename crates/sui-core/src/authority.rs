@@ -7,8 +7,13 @@ use crate::{
     authority_batch::{BroadcastReceiver, BroadcastSender},
     checkpoints::CheckpointStore,
     event_handler::EventHandler,
-    execution_engine,
+    execution_engine::{
+        builtin_engine, ExecutionEngine, ExecutionEngines, ProtocolVersion,
+        MOVE_VM_PROTOCOL_VERSION,
+    },
+    hot_objects::HotObjectTracker,
     metrics::start_timer,
+    performance_report::PerformanceReporter,
     query_helpers::QueryHelpers,
     transaction_input_checker,
     transaction_streamer::TransactionStreamer,
@@ -18,8 +23,10 @@ use async_trait::async_trait;
 use chrono::prelude::*;
 use fastcrypto::traits::KeyPair;
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
-use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use move_core_types::{
+    language_storage::{ModuleId, TypeTag},
+    resolver::ModuleResolver,
+};
 use narwhal_config::{
     Committee as ConsensusCommittee, WorkerCache as ConsensusWorkerCache,
     WorkerId as ConsensusWorkerId,
@@ -34,23 +41,27 @@ use prometheus::{
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
-use sui_adapter::adapter;
 use sui_adapter::temporary_store::InnerTemporaryStore;
 use sui_config::genesis::Genesis;
-use sui_json_rpc_types::{SuiEventEnvelope, SuiTransactionEffects};
+use sui_config::node::ExecutionCrossCheckConfig;
+use sui_json_rpc_types::{
+    DevInspectResults, SuiEventEnvelope, SuiTransactionEffects, TransactionQuery,
+};
 use sui_storage::{
     event_store::{EventStore, EventStoreType, StoredEvent},
+    indexes::IndexWrite,
     write_ahead_log::{DBTxGuard, TxGuard, WriteAheadLog},
     IndexStore,
 };
-use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
+use sui_types::crypto::{sha3_hash, AuthorityKeyPair, NetworkKeyPair};
 use sui_types::{
     base_types::*,
     batch::{TxSequenceNumber, UpdateItem},
@@ -61,7 +72,7 @@ use sui_types::{
     messages::*,
     object::{Object, ObjectFormatOptions, ObjectRead},
     storage::{BackingPackageStore, DeleteKind},
-    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
+    SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use tap::TapFallible;
 use thiserror::Error;
@@ -95,18 +106,36 @@ mod authority_store;
 use crate::epoch::epoch_store::EpochStore;
 use crate::metrics::TaskUtilizationExt;
 pub use authority_store::{
-    AuthorityStore, GatewayStore, ResolverWrapper, SuiDataStore, UpdateType,
+    AuthorityStore, GatewayStore, InternalSequenceNumber, ResolverWrapper, SuiDataStore, UpdateType,
 };
+use sui_types::coin::{Coin, CoinTransfer};
 use sui_types::committee::EpochId;
+use sui_types::event::{Event, EventEnvelope, EventID};
+use sui_types::filter::{EventFilter, Filter};
+use sui_types::finality_proof::{CheckpointInclusionProof, FinalityProof};
+use sui_types::gas::{EpochFeeSummary, SuiGasStatus, SupplySummary, MAX_GAS_BUDGET};
+use sui_types::gas_coin::GasCoin;
 use sui_types::messages_checkpoint::{
-    CheckpointRequest, CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
+    AuthenticatedCheckpoint, CheckpointData, CheckpointRequest, CheckpointRequestType,
+    CheckpointResponse, CheckpointSequenceNumber, CheckpointStateDiff, CheckpointTransaction,
 };
 use sui_types::object::{Owner, PastObjectRead};
 use sui_types::sui_system_state::SuiSystemState;
 
 pub mod authority_notifier;
 
+pub mod authority_store_cache;
+
+pub mod authority_store_pruner;
+
+pub mod authority_store_statistics;
+
+pub mod object_locks;
+
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
+/// Maximum number of transactions executed since its cursor an event subscription can resume
+/// from.
+pub const MAX_EVENT_BACKFILL_TRANSACTIONS: u64 = 1_000;
 const BROADCAST_CAPACITY: usize = 10_000;
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
@@ -130,6 +159,8 @@ pub struct AuthorityMetrics {
     signature_errors: IntCounter,
     pub shared_obj_tx: IntCounter,
     tx_already_processed: IntCounter,
+    execution_cross_checks: IntCounter,
+    execution_divergences: IntCounter,
     num_input_objs: Histogram,
     num_shared_objects: Histogram,
     batch_size: Histogram,
@@ -156,6 +187,11 @@ pub struct AuthorityMetrics {
     pub gossip_sync_count: IntCounter,
     pub gossip_task_success_count: IntCounter,
     pub gossip_task_error_count: IntCounter,
+
+    pub execution_driver_batch_size: Histogram,
+    pub execution_driver_batch_depth: Histogram,
+    pub execution_driver_parallelism: Histogram,
+    pub execution_driver_conflicts: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -216,6 +252,18 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            execution_cross_checks: register_int_counter_with_registry!(
+                "num_execution_cross_checks",
+                "Number of certificates executed a second time to check that execution is deterministic",
+                registry,
+            )
+            .unwrap(),
+            execution_divergences: register_int_counter_with_registry!(
+                "num_execution_divergences",
+                "Number of certificates whose second execution produced different effects",
+                registry,
+            )
+            .unwrap(),
             tx_already_processed: register_int_counter_with_registry!(
                 "num_tx_already_processed",
                 "Number of transaction orders already processed previously",
@@ -351,6 +399,33 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            execution_driver_batch_size: register_histogram_with_registry!(
+                "execution_driver_batch_size",
+                "Number of pending certificates scheduled together by the execution driver",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            execution_driver_batch_depth: register_histogram_with_registry!(
+                "execution_driver_batch_depth",
+                "Longest chain of conflicting certificates of a batch of the execution driver",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            execution_driver_parallelism: register_histogram_with_registry!(
+                "execution_driver_parallelism",
+                "Average number of certificates of a batch of the execution driver which can execute concurrently, its size over its depth",
+                POSITIVE_INT_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            execution_driver_conflicts: register_int_counter_with_registry!(
+                "execution_driver_conflicts",
+                "Number of pending certificates which waited for a conflicting certificate of their batch",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -377,15 +452,19 @@ pub struct AuthorityState {
     /// A global lock to halt all transaction/cert processing.
     halted: AtomicBool,
 
-    /// Move native functions that are available to invoke
-    pub(crate) _native_functions: NativeFunctionTable,
-    pub(crate) move_vm: Arc<MoveVM>,
+    /// Transaction execution, selected by the protocol version of the current epoch
+    pub(crate) execution_engines: ExecutionEngines<Arc<AuthorityStore>>,
+    /// Set when every certificate is executed a second time to check determinism.
+    execution_cross_check: once_cell::sync::OnceCell<ExecutionCrossCheckConfig>,
 
     /// The database
     pub(crate) database: Arc<AuthorityStore>, // TODO: remove pub
 
     indexes: Option<Arc<IndexStore>>,
 
+    /// The SUI of the coins created at genesis, which the system supply doesn't count.
+    genesis_coins: u64,
+
     pub module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>, // TODO: use strategies (e.g. LRU?) to constraint memory usage
 
     pub event_handler: Option<Arc<EventHandler>>,
@@ -408,8 +487,23 @@ pub struct AuthorityState {
     /// Ensures there can only be a single consensus client is updating the state.
     pub consensus_guardrail: AtomicUsize,
 
+    /// The round of the last consensus certificate whose transactions this authority handled.
+    pub last_consensus_round: Arc<AtomicU64>,
+    /// When this authority last handled a transaction sequenced by consensus, in milliseconds
+    /// since the Unix epoch, or zero if it hasn't since it started.
+    pub last_consensus_timestamp_ms: AtomicU64,
+    /// When this authority last committed the effects of a certificate, in milliseconds since the
+    /// Unix epoch, or zero if it hasn't since it started.
+    pub last_commit_timestamp_ms: AtomicU64,
+
     pub metrics: Arc<AuthorityMetrics>,
 
+    /// Contention on shared objects over the current epoch.
+    pub hot_objects: HotObjectTracker,
+
+    /// What this node did over the current and last epochs.
+    pub performance: PerformanceReporter,
+
     /// A channel to tell consensus to reconfigure.
     tx_reconfigure_consensus: Sender<ReconfigConsensusMessage>,
 }
@@ -434,10 +528,42 @@ impl AuthorityState {
         self.committee.load().epoch
     }
 
+    /// The execution engine for transactions of the current epoch.
+    pub fn execution_engine(&self) -> &Arc<dyn ExecutionEngine<Arc<AuthorityStore>>> {
+        self.execution_engines.engine(self.epoch())
+    }
+
+    /// Registers the execution engines built into this binary from the epochs of `activations`,
+    /// by protocol version, see `NodeConfig::execution_engine_activations`.
+    pub fn schedule_execution_engines(
+        &mut self,
+        activations: &BTreeMap<ProtocolVersion, EpochId>,
+    ) -> SuiResult {
+        self.execution_engines.schedule(activations)
+    }
+
     pub fn epoch_store(&self) -> &Arc<EpochStore> {
         &self.epoch_store
     }
 
+    /// Executes every certificate a second time from now on and compares the effects, see
+    /// [`ExecutionCrossCheckConfig`]. Can only be enabled once.
+    pub fn enable_execution_cross_check(&self, config: ExecutionCrossCheckConfig) -> SuiResult {
+        if let Some(version) = config.protocol_version {
+            fp_ensure!(
+                self.execution_engines.engine_for_version(version).is_some(),
+                SuiError::GenericAuthorityError {
+                    error: format!("No execution engine registered as version {version}"),
+                }
+            );
+        }
+        self.execution_cross_check
+            .set(config)
+            .map_err(|_| SuiError::GenericAuthorityError {
+                error: "Execution cross-check is already enabled".to_string(),
+            })
+    }
+
     async fn handle_transaction_impl(
         &self,
         transaction: Transaction,
@@ -476,6 +602,10 @@ impl AuthorityState {
         // existing transaction.
         self.set_transaction_lock(&owned_objects, signed_transaction)
             .await?;
+        self.performance
+            .record_transaction_signed(&self.committee.load());
+        // Signed and locked, but the client never gets the signature
+        sui_failpoints::fail_point_async!("handle-transaction-after-sign");
 
         // Return the signed Transaction or maybe a cert.
         self.make_transaction_info(&transaction_digest).await
@@ -515,7 +645,7 @@ impl AuthorityState {
 
     /// We cannot use handle_certificate in fullnode to execute a certificate because there is no
     /// consensus engine to assign locks for shared objects. Hence we need special handling here.
-    #[instrument(level = "trace", skip_all)]
+    #[instrument(level = "trace", skip_all, fields(tx_digest = ?certificate.digest()))]
     pub async fn handle_node_sync_certificate(
         &self,
         certificate: CertifiedTransaction,
@@ -544,6 +674,11 @@ impl AuthorityState {
                 &signed_effects.effects,
                 &tx_guard,
             )?;
+            self.hot_objects.record(
+                self.epoch(),
+                certificate.signed_data.data.shared_input_objects(),
+                None,
+            );
         }
 
         let resp = self
@@ -565,7 +700,7 @@ impl AuthorityState {
         Ok(())
     }
 
-    #[instrument(level = "trace", skip_all)]
+    #[instrument(level = "trace", skip_all, fields(tx_digest = ?certificate.digest()))]
     pub async fn handle_certificate(
         &self,
         certificate: CertifiedTransaction,
@@ -665,13 +800,14 @@ impl AuthorityState {
         Ok(())
     }
 
-    #[instrument(level = "trace", skip_all)]
+    #[instrument(level = "trace", skip_all, fields(tx_digest = ?certificate.digest()))]
     async fn process_certificate(
         &self,
         tx_guard: CertTxGuard<'_>,
         certificate: &CertifiedTransaction,
     ) -> SuiResult<TransactionInfoResponse> {
         let digest = *certificate.digest();
+        let start = Instant::now();
         // The cert could have been processed by a concurrent attempt of the same cert, so check if
         // the effects have already been written.
         if let Some(info) = self.check_tx_already_executed(&digest).await? {
@@ -719,6 +855,8 @@ impl AuthorityState {
 
         // commit_certificate finished, the tx is fully committed to the store.
         tx_guard.commit_tx();
+        self.performance
+            .record_certificate_executed(committee, start.elapsed());
 
         // Update metrics.
         self.metrics.total_effects.inc();
@@ -754,7 +892,7 @@ impl AuthorityState {
     /// non-transient error, e.g. the transaction input is somehow invalid, the correct
     /// locks are not held, etc. However, this is not entirely true, as a transient db read error
     /// may also cause this function to fail.
-    #[instrument(level = "trace", skip_all)]
+    #[instrument(level = "trace", skip_all, fields(tx_digest = ?transaction_digest))]
     async fn prepare_certificate(
         &self,
         certificate: &CertifiedTransaction,
@@ -788,23 +926,80 @@ impl AuthorityState {
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
         let (inner_temp_store, effects, _execution_error) =
-            execution_engine::execute_transaction_to_effects(
+            self.execution_engine().execute_transaction_to_effects(
                 shared_object_refs,
                 temporary_store,
                 certificate.signed_data.data.clone(),
                 transaction_digest,
                 transaction_dependencies,
-                &self.move_vm,
-                &self._native_functions,
                 gas_status,
                 self.epoch(),
             );
 
+        if let Some(config) = self.execution_cross_check.get() {
+            self.cross_check_execution(config, certificate, transaction_digest, &effects)
+                .await?;
+        }
+
         // TODO: Distribute gas charge and rebate, which can be retrieved from effects.
         let signed_effects = effects.to_sign_effects(self.epoch(), &self.name, &*self.secret);
         Ok((inner_temp_store, signed_effects))
     }
 
+    /// Executes `certificate` again from the same inputs and compares the effects with
+    /// `effects`. On divergence, the node halts before committing them if so configured.
+    async fn cross_check_execution(
+        &self,
+        config: &ExecutionCrossCheckConfig,
+        certificate: &CertifiedTransaction,
+        transaction_digest: TransactionDigest,
+        effects: &TransactionEffects,
+    ) -> SuiResult {
+        let engine = match config.protocol_version {
+            Some(version) => self
+                .execution_engines
+                .engine_for_version(version)
+                .expect("Checked when the cross-check was enabled"),
+            None => self.execution_engine(),
+        };
+        // Nothing was written since the first execution, the inputs are read again unchanged
+        let (gas_status, input_objects) =
+            transaction_input_checker::check_certificate_input(&self.database, certificate).await?;
+        let shared_object_refs = input_objects.filter_shared_objects();
+        let transaction_dependencies = input_objects.transaction_dependencies();
+        let temporary_store =
+            TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
+        let (_inner_temp_store, second_effects, _execution_error) = engine
+            .execute_transaction_to_effects(
+                shared_object_refs,
+                temporary_store,
+                certificate.signed_data.data.clone(),
+                transaction_digest,
+                transaction_dependencies,
+                gas_status,
+                self.epoch(),
+            );
+        self.metrics.execution_cross_checks.inc();
+        if second_effects.digest() == effects.digest() {
+            return Ok(());
+        }
+
+        self.metrics.execution_divergences.inc();
+        error!(
+            ?transaction_digest,
+            ?effects,
+            ?second_effects,
+            "Nondeterministic execution: executing the certificate again produced different effects"
+        );
+        if config.halt_on_divergence {
+            self.halt_validator();
+            return Err(SuiError::NondeterministicExecution {
+                digest: transaction_digest,
+            });
+        }
+        Ok(())
+    }
+
     pub async fn dry_run_transaction(
         &self,
         transaction: &Transaction,
@@ -819,20 +1014,61 @@ impl AuthorityState {
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
         let (_inner_temp_store, effects, _execution_error) =
-            execution_engine::execute_transaction_to_effects(
+            self.execution_engine().execute_transaction_to_effects(
                 shared_object_refs,
                 temporary_store,
                 transaction.signed_data.data.clone(),
                 transaction_digest,
                 transaction_dependencies,
-                &self.move_vm,
-                &self._native_functions,
                 gas_status,
                 self.epoch(),
             );
         SuiTransactionEffects::try_from(effects, self.module_cache.as_ref())
     }
 
+    /// Executes `move_call` from `sender` against the latest versions of its objects, without
+    /// signature nor gas coin, to read the values it returns and the events it emits. Nothing is
+    /// committed.
+    pub async fn dev_inspect_move_call(
+        &self,
+        sender: SuiAddress,
+        move_call: MoveCall,
+    ) -> Result<DevInspectResults, anyhow::Error> {
+        let input_object_kinds = SingleTransactionKind::Call(move_call.clone()).input_objects()?;
+        let objects = self.database.get_input_objects(&input_object_kinds)?;
+        let input_objects = input_object_kinds
+            .into_iter()
+            .zip(objects)
+            .map(|(kind, object)| {
+                object
+                    .map(|object| (kind, object))
+                    .ok_or_else(|| kind.object_not_found_error())
+            })
+            .collect::<SuiResult<_>>()?;
+        // Objects created by the call get IDs derived from this digest, as from a transaction's
+        let transaction_digest = TransactionDigest::new(sha3_hash(&move_call));
+        let mut temporary_store = TemporaryStore::new(
+            self.database.clone(),
+            InputObjects::new(input_objects),
+            transaction_digest,
+        );
+        // The budget only bounds the computation, which is charged to no coin
+        let gas_status = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1.into(), 1.into());
+        let result = self.execution_engine().dev_inspect_move_call(
+            &mut temporary_store,
+            move_call,
+            sender,
+            transaction_digest,
+            gas_status,
+            self.epoch(),
+        );
+        DevInspectResults::try_from(
+            result,
+            temporary_store.events().to_vec(),
+            self.module_cache.as_ref(),
+        )
+    }
+
     pub async fn check_tx_already_executed(
         &self,
         digest: &TransactionDigest,
@@ -845,37 +1081,6 @@ impl AuthorityState {
         }
     }
 
-    fn index_tx(
-        &self,
-        indexes: &IndexStore,
-        seq: TxSequenceNumber,
-        digest: &TransactionDigest,
-        cert: &CertifiedTransaction,
-        effects: &SignedTransactionEffects,
-        timestamp_ms: u64,
-    ) -> SuiResult {
-        indexes.index_tx(
-            cert.sender_address(),
-            cert.signed_data
-                .data
-                .input_objects()?
-                .iter()
-                .map(|o| o.object_id()),
-            effects
-                .effects
-                .all_mutated()
-                .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
-            cert.signed_data
-                .data
-                .move_calls()
-                .iter()
-                .map(|mc| (mc.package.0, mc.module.clone(), mc.function.clone())),
-            seq,
-            digest,
-            timestamp_ms,
-        )
-    }
-
     async fn process_one_tx(&self, seq: TxSequenceNumber, digest: &TransactionDigest) -> SuiResult {
         // Load cert and effects.
         let info = self.make_transaction_info(digest).await?;
@@ -896,9 +1101,14 @@ impl AuthorityState {
 
         // Index tx
         if let Some(indexes) = &self.indexes {
-            if let Err(e) =
-                self.index_tx(indexes.as_ref(), seq, digest, &cert, &effects, timestamp_ms)
-            {
+            if let Err(e) = index_tx(
+                indexes.as_ref(),
+                IndexWrite::All { timestamp_ms },
+                seq,
+                digest,
+                &cert,
+                &effects.effects,
+            ) {
                 warn!(?digest, "Couldn't index tx: {}", e);
             }
         }
@@ -1079,6 +1289,26 @@ impl AuthorityState {
         })
     }
 
+    /// Sends the object at the requested version as the chunks the requester doesn't hold.
+    pub async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        let (object_id, version, digest) = request.object_ref;
+        let object = self
+            .database
+            .get_object_by_key(&object_id, version)?
+            .ok_or(SuiError::ObjectNotFound { object_id })?;
+        fp_ensure!(
+            object.digest() == digest,
+            SuiError::InvalidObjectDigest {
+                object_id,
+                expected_digest: digest,
+            }
+        );
+        ObjectChunksResponse::new(&object, &request.known_chunks)
+    }
+
     /// Handles a request for a batch info. It returns a sequence of
     /// [batches, transactions, batches, transactions] as UpdateItems, and a flag
     /// that if true indicates the request goes beyond the last batch in the
@@ -1200,11 +1430,8 @@ impl AuthorityState {
         tx_reconfigure_consensus: Sender<ReconfigConsensusMessage>,
     ) -> Self {
         let (tx, _rx) = tokio::sync::broadcast::channel(BROADCAST_CAPACITY);
-        let native_functions =
-            sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
-        let move_vm = Arc::new(
-            adapter::new_move_vm(native_functions.clone())
-                .expect("We defined natives to not fail here"),
+        let execution_engines = ExecutionEngines::new(
+            builtin_engine(MOVE_VM_PROTOCOL_VERSION).expect("We defined natives to not fail here"),
         );
         // Only initialize an empty database.
         if store
@@ -1223,17 +1450,26 @@ impl AuthorityState {
             .committee()
             .clone();
 
+        let genesis_coins = genesis
+            .objects()
+            .iter()
+            .filter_map(|o| GasCoin::try_from(o).ok())
+            .map(|coin| coin.value())
+            .sum();
+
         let event_handler = event_store.map(|es| Arc::new(EventHandler::new(store.clone(), es)));
+        let performance = PerformanceReporter::new(name, secret.clone(), &committee);
 
         let mut state = AuthorityState {
             name,
             secret,
             committee: ArcSwap::from(Arc::new(committee)),
             halted: AtomicBool::new(false),
-            _native_functions: native_functions,
-            move_vm,
+            execution_engines,
+            execution_cross_check: once_cell::sync::OnceCell::new(),
             database: store.clone(),
             indexes,
+            genesis_coins,
             // `module_cache` uses a separate in-mem cache from `event_handler`
             // this is because they largely deal with different types of MoveStructs
             module_cache: Arc::new(SyncModuleCache::new(ResolverWrapper(store.clone()))),
@@ -1247,7 +1483,12 @@ impl AuthorityState {
                     .expect("Notifier cannot start."),
             ),
             consensus_guardrail: AtomicUsize::new(0),
+            last_consensus_round: Arc::new(AtomicU64::new(0)),
+            last_consensus_timestamp_ms: AtomicU64::new(0),
+            last_commit_timestamp_ms: AtomicU64::new(0),
             metrics: Arc::new(AuthorityMetrics::new(prometheus_registry)),
+            hot_objects: HotObjectTracker::new(prometheus_registry),
+            performance,
             tx_reconfigure_consensus,
         };
 
@@ -1429,6 +1670,7 @@ impl AuthorityState {
         self.epoch_store
             .epochs
             .insert(&cur_epoch, &AuthenticatedEpoch::Signed(signed_epoch))?;
+        self.performance.start_epoch(&new_committee);
         // TODO: Do we want to make it possible to subscribe to committee changes?
         self.committee.swap(Arc::new(new_committee));
         Ok(())
@@ -1441,11 +1683,13 @@ impl AuthorityState {
         )?)
     }
 
-    pub(crate) fn is_halted(&self) -> bool {
+    pub fn is_halted(&self) -> bool {
         self.halted.load(Ordering::Relaxed)
     }
 
-    pub(crate) fn halt_validator(&self) {
+    /// Rejects the transactions and certificates other than system ones, e.g. at the end of an
+    /// epoch or before shutting down.
+    pub fn halt_validator(&self) {
         self.halted.store(true, Ordering::Relaxed);
     }
 
@@ -1567,6 +1811,46 @@ impl AuthorityState {
         self.database.get_owner_objects(owner)
     }
 
+    /// Returns at most `limit` of the objects of `owner` in the order of their IDs, from the
+    /// object `cursor` on.
+    pub fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        self.database.get_owner_objects_page(owner, cursor, limit)
+    }
+
+    /// Returns the coins owned by `owner` in the order of their IDs, with the type of each coin,
+    /// e.g. `0x2::sui::SUI`, and its balance.
+    pub fn get_owned_coins(&self, owner: SuiAddress) -> SuiResult<Vec<(ObjectInfo, TypeTag, u64)>> {
+        let mut coins = Vec::new();
+        for info in self.get_owner_objects(Owner::AddressOwner(owner))? {
+            let object = match self.database.get_object(&info.object_id)? {
+                Some(object) => object,
+                None => continue,
+            };
+            let move_object = match object.data.try_as_move() {
+                Some(move_object)
+                    if move_object.type_.address == SUI_FRAMEWORK_ADDRESS
+                        && Coin::is_coin(&move_object.type_) =>
+                {
+                    move_object
+                }
+                _ => continue,
+            };
+            let coin_type = match move_object.type_.type_params.first() {
+                Some(coin_type) => coin_type.clone(),
+                None => continue,
+            };
+            let balance = Coin::from_bcs_bytes(move_object.contents())?.value();
+            coins.push((info, coin_type, balance));
+        }
+        coins.sort_by_key(|(info, _, _)| info.object_id);
+        Ok(coins)
+    }
+
     pub fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.database)
     }
@@ -1593,6 +1877,162 @@ impl AuthorityState {
         QueryHelpers::get_transaction(&self.database, &digest)
     }
 
+    /// Waits until the transaction `digest` is executed by this node, for at most `timeout`.
+    pub async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout: Duration,
+    ) -> Result<(CertifiedTransaction, TransactionEffects), anyhow::Error> {
+        // Subscribe before looking the transaction up, not to miss it if it's executed in between
+        let mut subscriber = self.subscribe_batch();
+        let wait = async {
+            loop {
+                if self.database.effects_exists(&digest)? {
+                    return self.get_transaction(digest).await;
+                }
+                loop {
+                    match subscriber.recv().await {
+                        Ok(UpdateItem::Transaction((_, ExecutionDigests { transaction, .. })))
+                            if transaction == digest =>
+                        {
+                            break
+                        }
+                        Ok(_) => {}
+                        // The transaction may be among the skipped updates
+                        Err(RecvError::Lagged(_)) => break,
+                        Err(RecvError::Closed) => {
+                            return Err(anyhow::anyhow!("The node is shutting down"))
+                        }
+                    }
+                }
+            }
+        };
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            anyhow::anyhow!("Transaction {digest:?} was not executed within {timeout:?}")
+        })?
+    }
+
+    /// Returns a proof that the transaction is final, including the certified checkpoint it is
+    /// in if this node has one.
+    pub async fn get_finality_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> Result<FinalityProof, anyhow::Error> {
+        let (certificate, effects) = self.get_transaction(digest).await?;
+        let checkpoint = match &self.checkpoints {
+            Some(checkpoints) => {
+                let checkpoints = checkpoints.lock();
+                let execution_digests = ExecutionDigests::new(digest, effects.digest());
+                match checkpoints
+                    .tables
+                    .transactions_to_checkpoint
+                    .get(&execution_digests)?
+                {
+                    Some(seq) => match (
+                        checkpoints.tables.checkpoints.get(&seq)?,
+                        checkpoints.tables.checkpoint_contents.get(&seq)?,
+                    ) {
+                        // A checkpoint only signed by this node proves nothing to others
+                        (Some(AuthenticatedCheckpoint::Certified(checkpoint)), Some(contents)) => {
+                            Some(CheckpointInclusionProof {
+                                checkpoint,
+                                contents,
+                            })
+                        }
+                        _ => None,
+                    },
+                    None => None,
+                }
+            }
+            None => None,
+        };
+        let epochs = (0..=certificate.auth_sign_info.epoch)
+            .map(|epoch| {
+                self.epoch_store
+                    .get_authenticated_epoch(&epoch)?
+                    .ok_or_else(|| anyhow::anyhow!("Epoch {} is unknown to this node", epoch))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(FinalityProof::new(certificate, effects, checkpoint, epochs))
+    }
+
+    /// Returns the net changes to the objects made by the transactions of the checkpoints
+    /// `from` to `to` included, computed from their effects.
+    pub fn get_checkpoint_state_diff(
+        &self,
+        from: CheckpointSequenceNumber,
+        to: CheckpointSequenceNumber,
+    ) -> Result<CheckpointStateDiff, anyhow::Error> {
+        if from > to {
+            return Err(anyhow::anyhow!(
+                "Checkpoint {} comes after checkpoint {}",
+                from,
+                to
+            ));
+        }
+        let checkpoints = self
+            .checkpoints
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("This node doesn't keep checkpoints"))?;
+        let mut transactions = vec![];
+        {
+            let checkpoints = checkpoints.lock();
+            for seq in from..=to {
+                let contents = checkpoints
+                    .tables
+                    .checkpoint_contents
+                    .get(&seq)?
+                    .ok_or_else(|| anyhow::anyhow!("Checkpoint {} is unknown to this node", seq))?;
+                transactions.extend(contents.iter().map(|digests| digests.transaction));
+            }
+        }
+        let effects = transactions
+            .iter()
+            .map(|digest| self.database.get_effects(digest))
+            .collect::<SuiResult<Vec<_>>>()?;
+        Ok(CheckpointStateDiff::from_effects(&effects))
+    }
+
+    /// Returns the checkpoint `seq` with the certificates and effects of its transactions, or
+    /// `None` if this node doesn't have a certificate for it yet.
+    pub fn get_checkpoint_data(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>, anyhow::Error> {
+        let checkpoints = self
+            .checkpoints
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("This node doesn't keep checkpoints"))?;
+        let (checkpoint, contents) = {
+            let checkpoints = checkpoints.lock();
+            match (
+                checkpoints.tables.checkpoints.get(&seq)?,
+                checkpoints.tables.checkpoint_contents.get(&seq)?,
+            ) {
+                (Some(AuthenticatedCheckpoint::Certified(checkpoint)), Some(contents)) => {
+                    (checkpoint, contents)
+                }
+                _ => return Ok(None),
+            }
+        };
+        let transactions = contents
+            .iter()
+            .map(|digests| {
+                let (certificate, effects) =
+                    QueryHelpers::get_transaction(&self.database, &digests.transaction)?;
+                Ok(CheckpointTransaction {
+                    certificate,
+                    effects,
+                })
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+        Ok(Some(CheckpointData {
+            checkpoint,
+            contents,
+            transactions,
+        }))
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -1607,10 +2047,12 @@ impl AuthorityState {
         package: ObjectID,
         module: Option<String>,
         function: Option<String>,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
     ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
         Ok(self
             .get_indexes()?
-            .get_transactions_by_move_function(package, module, function)?)
+            .get_transactions_by_move_function(package, module, function, cursor, limit, false)?)
     }
 
     pub async fn get_timestamp_ms(
@@ -1623,33 +2065,269 @@ impl AuthorityState {
     pub async fn get_transactions_by_input_object(
         &self,
         object: ObjectID,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
     ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
         Ok(self
             .get_indexes()?
-            .get_transactions_by_input_object(object)?)
+            .get_transactions_by_input_object(object, cursor, limit, false)?)
     }
 
     pub async fn get_transactions_by_mutated_object(
         &self,
         object: ObjectID,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
     ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
         Ok(self
             .get_indexes()?
-            .get_transactions_by_mutated_object(object)?)
+            .get_transactions_by_mutated_object(object, cursor, limit, false)?)
     }
 
     pub async fn get_transactions_from_addr(
         &self,
         address: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
     ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
-        Ok(self.get_indexes()?.get_transactions_from_addr(address)?)
+        Ok(self
+            .get_indexes()?
+            .get_transactions_from_addr(address, cursor, limit, false)?)
     }
 
     pub async fn get_transactions_to_addr(
         &self,
         address: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
+        Ok(self
+            .get_indexes()?
+            .get_transactions_to_addr(address, cursor, limit, false)?)
+    }
+
+    /// Returns up to `limit` of the transactions matching `query`, in the order the node executed
+    /// them from the one with the sequence number `cursor` (included) on, or backwards if
+    /// `descending`.
+    pub async fn get_transactions(
+        &self,
+        query: TransactionQuery,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
-        Ok(self.get_indexes()?.get_transactions_to_addr(address)?)
+        Ok(match query {
+            TransactionQuery::All => self
+                .database
+                .executed_transactions(cursor, limit, descending)?,
+            TransactionQuery::MoveFunction {
+                package,
+                module,
+                function,
+            } => self.get_indexes()?.get_transactions_by_move_function(
+                package, module, function, cursor, limit, descending,
+            )?,
+            TransactionQuery::InputObject(object) => self
+                .get_indexes()?
+                .get_transactions_by_input_object(object, cursor, limit, descending)?,
+            TransactionQuery::MutatedObject(object) => self
+                .get_indexes()?
+                .get_transactions_by_mutated_object(object, cursor, limit, descending)?,
+            TransactionQuery::FromAddress(address) => self
+                .get_indexes()?
+                .get_transactions_from_addr(address, cursor, limit, descending)?,
+            TransactionQuery::ToAddress(address) => self
+                .get_indexes()?
+                .get_transactions_to_addr(address, cursor, limit, descending)?,
+            TransactionQuery::Checkpoint { start, end } => {
+                self.get_checkpoint_transactions(start, end, cursor, limit, descending)?
+            }
+        })
+    }
+
+    fn get_checkpoint_transactions(
+        &self,
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<(TxSequenceNumber, TransactionDigest)>, anyhow::Error> {
+        let checkpoints = self
+            .checkpoints
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("This node doesn't keep checkpoints"))?;
+        let mut digests = vec![];
+        {
+            let checkpoints = checkpoints.lock();
+            for seq in start..=end {
+                let contents = checkpoints
+                    .tables
+                    .checkpoint_contents
+                    .get(&seq)?
+                    .ok_or_else(|| anyhow::anyhow!("Checkpoint {} is unknown to this node", seq))?;
+                digests.extend(contents.iter().map(|digests| digests.transaction));
+            }
+        }
+        // The transactions of the checkpoints this node hasn't executed yet are left out, as
+        // they have no place in its order
+        let mut transactions: Vec<_> = self
+            .get_indexes()?
+            .get_transaction_sequence_numbers(&digests)?
+            .into_iter()
+            .zip(digests)
+            .filter_map(|(seq, digest)| seq.map(|seq| (seq, digest)))
+            .filter(|(seq, _)| match cursor {
+                Some(cursor) if descending => *seq <= cursor,
+                Some(cursor) => *seq >= cursor,
+                None => true,
+            })
+            .collect();
+        transactions.sort_unstable();
+        if descending {
+            transactions.reverse();
+        }
+        transactions.truncate(limit);
+        Ok(transactions)
+    }
+
+    /// Returns the gas charged and the SUI minted in `epoch`, over the transactions indexed by
+    /// this node.
+    pub async fn get_epoch_fee_summary(
+        &self,
+        epoch: EpochId,
+    ) -> SuiResult<Option<EpochFeeSummary>> {
+        self.get_indexes()?.get_epoch_fee_summary(epoch)
+    }
+
+    /// Returns the SUI in existence as of the last transaction indexed by this node.
+    pub async fn get_supply(&self) -> SuiResult<SupplySummary> {
+        let system_state = self.get_sui_system_state_object().await?;
+        let (gas_burned, storage_rebates) = self
+            .get_indexes()?
+            .get_epoch_fee_summaries()?
+            .iter()
+            .fold((0, 0), |(burned, rebates), fees| {
+                (
+                    burned + fees.computation_cost + fees.storage_cost,
+                    rebates + fees.storage_rebate,
+                )
+            });
+        Ok(SupplySummary {
+            epoch: system_state.epoch,
+            genesis_coins: self.genesis_coins,
+            system_minted: system_state.treasury_cap.value,
+            gas_burned,
+            storage_rebates,
+        })
+    }
+
+    /// Returns the coins received by `address` from other addresses in the transactions sent to
+    /// it after `cursor`, scanning at most `limit` transactions, along with the sequence number
+    /// of the last transaction scanned. Coins count as received when they are created or
+    /// unwrapped for `address`, or transferred to it.
+    pub async fn get_coin_transfers_to_addr(
+        &self,
+        address: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+    ) -> Result<
+        (
+            Vec<(TxSequenceNumber, CoinTransfer)>,
+            Option<TxSequenceNumber>,
+        ),
+        anyhow::Error,
+    > {
+        let transactions = self
+            .get_indexes()?
+            .get_transactions_to_addr_after(address, cursor, limit)?;
+        let next_cursor = transactions.last().map(|(seq, _)| *seq).or(cursor);
+        let mut transfers = vec![];
+        for (seq, digest) in transactions {
+            let effects = self.database.get_effects(&digest)?;
+            if !effects.status.is_ok() {
+                continue;
+            }
+            let sender = self
+                .database
+                .get_certified_transaction(&digest)?
+                .ok_or(SuiError::TransactionNotFound { digest })?
+                .sender_address();
+            // Moving coins between its own objects doesn't change the balance of an address
+            if sender == address {
+                continue;
+            }
+            let transferred: HashSet<ObjectID> = effects
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::TransferObject {
+                        recipient: Owner::AddressOwner(recipient),
+                        object_id,
+                        ..
+                    } if *recipient == address => Some(*object_id),
+                    _ => None,
+                })
+                .collect();
+            let received = effects
+                .created
+                .iter()
+                .chain(effects.unwrapped.iter())
+                .chain(
+                    effects
+                        .mutated
+                        .iter()
+                        .filter(|(object_ref, _)| transferred.contains(&object_ref.0)),
+                )
+                .filter(|(_, owner)| *owner == Owner::AddressOwner(address));
+            for ((object_id, version, _), _) in received {
+                let object = self
+                    .database
+                    .get_object_by_key(object_id, *version)?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Object {} at version {} is no longer available",
+                            object_id,
+                            version
+                        )
+                    })?;
+                if let Some(transfer) = CoinTransfer::from_object(digest, sender, &object)? {
+                    transfers.push((seq, transfer));
+                }
+            }
+        }
+        Ok((transfers, next_cursor))
+    }
+
+    /// Returns at most `limit` versions of `object_id` after version `cursor`, or from the first
+    /// one if `None`, with the transaction which created each and its timestamp if indexed, and
+    /// the cursor to get the next versions with. Deletions and wrappings are listed as versions
+    /// with the digest `ObjectDigest::OBJECT_DIGEST_DELETED` or `OBJECT_DIGEST_WRAPPED`.
+    pub async fn get_object_version_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+    ) -> SuiResult<(
+        Vec<(ObjectRef, TransactionDigest, Option<u64>)>,
+        Option<SequenceNumber>,
+    )> {
+        let entries = self
+            .database
+            .get_parent_entries_after(object_id, cursor, limit)?;
+        let next_cursor = entries
+            .last()
+            .map(|(object_ref, _)| object_ref.1)
+            .or(cursor);
+        let mut versions = Vec::with_capacity(entries.len());
+        for (object_ref, digest) in entries {
+            let timestamp_ms = match &self.indexes {
+                Some(indexes) => indexes.get_timestamp_ms(&digest)?,
+                None => None,
+            };
+            versions.push((object_ref, digest, timestamp_ms));
+        }
+        Ok((versions, next_cursor))
     }
 
     /// Returns a full handle to the event store, including inserts... so be careful!
@@ -1659,6 +2337,66 @@ impl AuthorityState {
             .map(|handler| handler.event_store.clone())
     }
 
+    /// Converts events read from the event store, decoding the fields of Move events with the
+    /// schemas of their types.
+    fn decode_events(
+        &self,
+        stored_events: Vec<StoredEvent>,
+    ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
+        let envelopes = StoredEvent::into_event_envelopes(stored_events)?;
+        Ok(match &self.event_handler {
+            Some(handler) => envelopes
+                .into_iter()
+                .map(|mut envelope| {
+                    envelope.event = handler.event_schemas().decode(envelope.event);
+                    envelope
+                })
+                .collect(),
+            None => envelopes,
+        })
+    }
+
+    /// Returns the events matching `filter` emitted after `cursor`, in the order they were
+    /// emitted. Transactions which are not post-processed yet are left out, their events are
+    /// still to be sent to the subscribers of the event handler.
+    pub fn get_events_after(
+        &self,
+        cursor: EventID,
+        filter: &EventFilter,
+    ) -> Result<Vec<EventEnvelope>, anyhow::Error> {
+        let event_handler = self.event_handler.as_ref().ok_or(SuiError::NoEventStore)?;
+        let indexes = self
+            .indexes
+            .as_ref()
+            .ok_or_else(|| SuiError::UnsupportedFeatureError {
+                error: "Resuming from a cursor requires the transaction indexes".into(),
+            })?;
+        let end = self.get_total_transaction_number()?;
+        if end.saturating_sub(cursor.tx_seq_num) > MAX_EVENT_BACKFILL_TRANSACTIONS {
+            return Err(anyhow::anyhow!(
+                "More than {MAX_EVENT_BACKFILL_TRANSACTIONS} transactions were executed since \
+                cursor {cursor:?}, query the events emitted since then instead"
+            ));
+        }
+
+        let mut events = Vec::new();
+        for (seq, digest) in self.get_transactions_in_range(cursor.tx_seq_num.min(end), end)? {
+            // Indexed together with the emission of its events
+            let timestamp_ms = match indexes.get_timestamp_ms(&digest)? {
+                Some(timestamp_ms) => timestamp_ms,
+                None => continue,
+            };
+            let (_, effects) = QueryHelpers::get_transaction(&self.database, &digest)?;
+            events.extend(
+                event_handler
+                    .envelopes(&effects, timestamp_ms, seq)?
+                    .into_iter()
+                    .filter(|envelope| envelope.id() > cursor && filter.matches(envelope)),
+            );
+        }
+        Ok(events)
+    }
+
     /// Returns at most `limit` events emitted in the given transaction,
     /// emitted within [start_time, end_time) in order of events emitted.
     /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
@@ -1669,7 +2407,7 @@ impl AuthorityState {
     ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
         let stored_events = es.events_by_transaction(digest, limit).await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events emitted in the given module,
@@ -1686,7 +2424,7 @@ impl AuthorityState {
         let stored_events = es
             .events_by_module_id(start_time, end_time, module_id, limit)
             .await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events with the given move event struct name, e.g.
@@ -1705,7 +2443,7 @@ impl AuthorityState {
         let stored_events = es
             .events_by_move_event_struct_name(start_time, end_time, move_event_struct_name, limit)
             .await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events associated with the given sender,
@@ -1722,7 +2460,7 @@ impl AuthorityState {
         let stored_events = es
             .events_by_sender(start_time, end_time, sender, limit)
             .await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events associated with the given recipient,
@@ -1739,7 +2477,7 @@ impl AuthorityState {
         let stored_events = es
             .events_by_recipient(start_time, end_time, recipient, limit)
             .await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events associated with the given object,
@@ -1756,7 +2494,7 @@ impl AuthorityState {
         let stored_events = es
             .events_by_object(start_time, end_time, object, limit)
             .await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     /// Returns at most `limit` events emitted within [start_time, end_time),
@@ -1770,7 +2508,7 @@ impl AuthorityState {
     ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
         let stored_events = es.event_iterator(start_time, end_time, limit).await?;
-        StoredEvent::into_event_envelopes(stored_events)
+        self.decode_events(stored_events)
     }
 
     pub async fn insert_genesis_object(&self, object: Object) {
@@ -1821,7 +2559,7 @@ impl AuthorityState {
 
     /// Update state and signals that a new transactions has been processed
     /// to the batch maker service.
-    #[instrument(level = "trace", skip_all)]
+    #[instrument(level = "trace", skip_all, fields(tx_digest = ?certificate.digest()))]
     pub(crate) async fn commit_certificate(
         &self,
         inner_temporary_store: InnerTemporaryStore,
@@ -1851,6 +2589,8 @@ impl AuthorityState {
             )
             .await
             .tap_ok(|_| {
+                self.last_commit_timestamp_ms
+                    .store(Self::unixtime_now_ms(), Ordering::Relaxed);
                 debug!(?digest, ?effects_digest, ?self.name, "commit_certificate finished");
             })
 
@@ -1949,11 +2689,15 @@ impl ExecutionState for AuthorityState {
     async fn handle_consensus_transaction(
         &self,
         // TODO [2533]: use this once integrating Narwhal reconfiguration
-        _consensus_output: &narwhal_consensus::ConsensusOutput,
+        consensus_output: &narwhal_consensus::ConsensusOutput,
         consensus_index: ExecutionIndices,
         transaction: Self::Transaction,
     ) -> Result<Self::Outcome, Self::Error> {
         self.metrics.total_consensus_txns.inc();
+        self.last_consensus_round
+            .fetch_max(consensus_output.certificate.round(), Ordering::Relaxed);
+        self.last_consensus_timestamp_ms
+            .store(Self::unixtime_now_ms(), Ordering::Relaxed);
         let _timer = self
             .metrics
             .handle_consensus_duration_mcs
@@ -2061,3 +2805,48 @@ impl ExecutionStateError for NarwhalHandlerError {
         }
     }
 }
+
+/// Indexes the executed transaction `cert` with `effects` into `indexes`, as the `seq`-th
+/// transaction the node executed.
+pub fn index_tx(
+    indexes: &IndexStore,
+    write: IndexWrite,
+    seq: TxSequenceNumber,
+    digest: &TransactionDigest,
+    cert: &CertifiedTransaction,
+    effects: &TransactionEffects,
+) -> SuiResult {
+    // The rewards of an epoch are minted by the transaction which ends it
+    let minted = cert
+        .signed_data
+        .data
+        .kind
+        .single_transactions()
+        .map(|tx| match tx {
+            SingleTransactionKind::ChangeEpoch(change) => {
+                change.storage_charge + change.computation_charge
+            }
+            _ => 0,
+        })
+        .sum();
+    indexes.index_tx(
+        cert.sender_address(),
+        cert.signed_data
+            .data
+            .input_objects()?
+            .iter()
+            .map(|o| o.object_id()),
+        effects
+            .all_mutated()
+            .map(|(obj_ref, owner, _kind)| (*obj_ref, *owner)),
+        cert.signed_data
+            .data
+            .move_calls()
+            .iter()
+            .map(|mc| (mc.package.0, mc.module.clone(), mc.function.clone())),
+        seq,
+        digest,
+        write,
+        &EpochFeeSummary::for_transaction(cert.auth_sign_info.epoch, &effects.gas_used, minted),
+    )
+}
@@ -9,7 +9,6 @@ use std::{
 use sui_types::batch::TxSequenceNumber;
 
 use tokio::sync::Notify;
-use typed_store::traits::Map;
 
 use parking_lot::Mutex;
 
@@ -51,6 +50,12 @@ impl TransactionNotifier {
         self.low_watermark.load(Ordering::SeqCst)
     }
 
+    /// The sequence number of the next ticket and the number of tickets still being processed.
+    pub fn live_tickets(&self) -> (TxSequenceNumber, usize) {
+        let inner = self.inner.lock();
+        (inner.high_watermark, inner.live_tickets.len())
+    }
+
     /// Check that we have drained all tickets (i.e. low watermark reached high watermark).
     /// Return the watermark if we have drained.
     pub fn ticket_drained(&self) -> Option<u64> {
@@ -1,7 +1,10 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{authority_store_tables::AuthorityStoreTables, *};
+use super::{
+    authority_store_tables::{storage_options, AuthorityTables, TABLE_NAMES},
+    *,
+};
 use narwhal_executor::ExecutionIndices;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
@@ -11,21 +14,26 @@ use std::iter;
 use std::path::Path;
 use std::sync::atomic::AtomicU64;
 use std::{fmt::Debug, path::PathBuf};
+use sui_config::node::StorageConfig;
 use sui_storage::{
-    mutex_table::{LockGuard, MutexTable},
+    mutex_table::LockGuard,
+    table::{StorageBackend, Table, TableBatch},
     write_ahead_log::{DBWriteAheadLog, WriteAheadLog},
     LockService,
 };
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
 use sui_types::crypto::{AuthoritySignInfo, EmptySignInfo};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::{Owner, OBJECT_START_VERSION};
 use sui_types::storage::WriteKind;
 use sui_types::{base_types::SequenceNumber, storage::ParentSync};
 use tokio::sync::Notify;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tracing::{debug, error, info, trace};
-use typed_store::rocks::{DBBatch, DBMap};
-use typed_store::traits::Map;
+
+use super::authority_store_cache::StoreCache;
+use super::authority_store_pruner::PrunedData;
+use super::object_locks::{ObjectLockGuard, ObjectLockMetrics, ObjectLockTable};
 
 pub type AuthorityStore = SuiDataStore<AuthoritySignInfo>;
 pub type GatewayStore = SuiDataStore<EmptySignInfo>;
@@ -34,13 +42,13 @@ pub type InternalSequenceNumber = u64;
 
 pub struct CertLockGuard(LockGuard);
 
-const NUM_SHARDS: usize = 4096;
-const SHARD_SIZE: usize = 128;
-
 /// The key where the latest consensus index is stored in the database.
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
 const LAST_CONSENSUS_INDEX_ADDR: u64 = 0;
 
+/// The key where the first checkpoint not pruned yet is stored in the database.
+const PRUNING_WATERMARK_ADDR: u64 = 0;
+
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
 /// them, but other entities such as replicas will.
@@ -55,23 +63,56 @@ pub struct SuiDataStore<S> {
     /// The LockService this store depends on for locking functionality
     lock_service: LockService,
 
-    /// Internal vector of locks to manage concurrent writes to the database
-    mutex_table: MutexTable<ObjectDigest>,
+    /// Locks of the owned objects being written, to manage concurrent writes to the database
+    object_locks: ObjectLockTable,
 
     // The next sequence number.
     next_pending_seq: AtomicU64,
     // A notifier for new pending certificates
     pending_notifier: Arc<Notify>,
 
-    pub(crate) tables: AuthorityStoreTables<S>,
+    pub(crate) tables: AuthorityTables<S>,
+
+    /// Recently read and written objects and effects, disabled unless set with `with_cache`.
+    cache: StoreCache,
+
+    /// Options sharing the statistics RocksDB collects for the database of the tables, if it
+    /// collects them.
+    db_statistics: Option<Options>,
 }
 
 impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// Open an authority store by directory path
     pub fn open(path: &Path, db_options: Option<Options>) -> Self {
-        let tables =
-            AuthorityStoreTables::open_tables_read_write(path.to_path_buf(), db_options, None);
+        Self::open_with_backend(path, db_options, StorageBackend::RocksDB)
+    }
 
+    /// Open an authority store whose tables are in `backend`. The lock service and the
+    /// write-ahead log keep their own databases in the directory of the store.
+    pub fn open_with_backend(
+        path: &Path,
+        db_options: Option<Options>,
+        backend: StorageBackend,
+    ) -> Self {
+        let tables = AuthorityTables::open(path, db_options, None, backend);
+        Self::open_tables(path, tables)
+    }
+
+    /// Open an authority store whose tables are in `backend`, RocksDB being tuned by `config`.
+    pub fn open_with_config(
+        path: &Path,
+        config: &StorageConfig,
+        backend: StorageBackend,
+    ) -> SuiResult<Self> {
+        let (db_options, table_options) = storage_options(config)?;
+        let db_statistics = config.metrics_interval_secs.map(|_| db_options.clone());
+        let tables = AuthorityTables::open(path, Some(db_options), Some(table_options), backend);
+        let mut store = Self::open_tables(path, tables);
+        store.db_statistics = db_statistics;
+        Ok(store)
+    }
+
+    fn open_tables(path: &Path, tables: AuthorityTables<S>) -> Self {
         // For now, create one LockService for each SuiDataStore, and we use a specific
         // subdir of the data store directory
         let lockdb_path: PathBuf = path.join("lockdb");
@@ -94,13 +135,57 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Self {
             wal,
             lock_service,
-            mutex_table: MutexTable::new(NUM_SHARDS, SHARD_SIZE),
+            object_locks: ObjectLockTable::default(),
             next_pending_seq,
             pending_notifier: Arc::new(Notify::new()),
             tables,
+            cache: StoreCache::default(),
+            db_statistics: None,
         }
     }
 
+    /// Reads objects and effects through `cache`.
+    pub fn with_cache(mut self, cache: StoreCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub fn with_lock_metrics(mut self, metrics: ObjectLockMetrics) -> Self {
+        self.object_locks = self.object_locks.with_metrics(metrics);
+        self
+    }
+
+    /// The statistics RocksDB collected for the database of the store, as dumped by RocksDB, if
+    /// it collects them.
+    pub fn db_statistics(&self) -> Option<String> {
+        self.db_statistics
+            .as_ref()
+            .and_then(|options| options.get_statistics())
+    }
+
+    /// The integer `property` of each table of the store, e.g. `rocksdb.estimate-num-keys`, for
+    /// the tables RocksDB reports it for. Empty in memory.
+    pub fn db_property(&self, property: &str) -> SuiResult<Vec<(&'static str, u64)>> {
+        let db = match self.tables.rocksdb() {
+            Some(db) => db,
+            None => return Ok(vec![]),
+        };
+        let mut values = Vec::with_capacity(TABLE_NAMES.len());
+        for name in TABLE_NAMES {
+            let cf = match db.cf_handle(name) {
+                Some(cf) => cf,
+                None => continue,
+            };
+            let value = db
+                .property_int_value_cf(&cf, property)
+                .map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+            if let Some(value) = value {
+                values.push((*name, value));
+            }
+        }
+        Ok(values)
+    }
+
     pub async fn acquire_tx_guard(&self, cert: &CertifiedTransaction) -> SuiResult<CertTxGuard> {
         let digest = cert.digest();
         let guard = self.wal.begin_tx(digest, cert).await?;
@@ -133,10 +218,14 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         &self,
         transaction_digest: &TransactionDigest,
     ) -> SuiResult<TransactionEffects> {
-        self.tables
-            .effects
-            .get(transaction_digest)?
-            .map(|data| data.effects)
+        self.cache
+            .get_effects(transaction_digest, || {
+                Ok(self
+                    .tables
+                    .effects
+                    .get(transaction_digest)?
+                    .map(|data| data.effects))
+            })?
             .ok_or(SuiError::TransactionNotFound {
                 digest: *transaction_digest,
             })
@@ -169,6 +258,12 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .is_none())
     }
 
+    /// Syncs the writes to the tables of the store to disk, e.g. before shutting down. The tables
+    /// share a database, so syncing its log through one of them syncs them all.
+    pub fn flush(&self) -> SuiResult {
+        Ok(self.tables.objects.flush()?)
+    }
+
     pub fn next_sequence_number(&self) -> Result<TxSequenceNumber, SuiError> {
         Ok(self
             .tables
@@ -230,6 +325,16 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok(())
     }
 
+    pub fn lock_stripes(&self) -> usize {
+        self.object_locks.num_stripes()
+    }
+
+    /// The `limit` object locks with the most tasks waiting for them, each with an object waited
+    /// for and the number of tasks holding or waiting for the lock.
+    pub fn contended_locks(&self, limit: usize) -> Vec<(ObjectID, usize)> {
+        self.object_locks.contended_locks(limit)
+    }
+
     /// Get all stored certificate digests
     pub fn get_pending_digests(
         &self,
@@ -260,9 +365,9 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     }
 
     /// A function that acquires all locks associated with the objects (in order to avoid deadlocks).
-    async fn acquire_locks<'a, 'b>(&'a self, input_objects: &'b [ObjectRef]) -> Vec<LockGuard> {
-        self.mutex_table
-            .acquire_locks(input_objects.iter().map(|(_, _, digest)| *digest))
+    async fn acquire_locks(&self, input_objects: &[ObjectRef]) -> Vec<ObjectLockGuard<'_>> {
+        self.object_locks
+            .acquire_locks(input_objects.iter().map(|(id, _, _)| *id))
             .await
     }
 
@@ -280,51 +385,60 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .collect())
     }
 
+    /// Returns at most `limit` of the objects of `owner` in the order of their IDs, from the
+    /// object `cursor` on, or from the first one if `None`.
+    pub fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<ObjectInfo>, SuiError> {
+        debug!(?owner, ?cursor, limit, "get_owner_objects_page");
+        Ok(self
+            .tables
+            .owner_index
+            .iter()
+            .skip_to(&(owner, cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((object_owner, _), _)| (object_owner == &owner))
+            .take(limit)
+            .map(|(_, object_info)| object_info)
+            .collect())
+    }
+
     pub fn get_object_by_key(
         &self,
         object_id: &ObjectID,
         version: VersionNumber,
     ) -> Result<Option<Object>, SuiError> {
-        Ok(self.tables.objects.get(&ObjectKey(*object_id, version))?)
+        let key = ObjectKey(*object_id, version);
+        self.cache
+            .get_object(&key, || Ok(self.tables.objects.get(&key)?))
     }
 
     /// Read an object and return it, or Err(ObjectNotFound) if the object was not found.
     pub fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
-        let obj_entry = self
-            .tables
-            .objects
-            .iter()
-            .skip_prior_to(&ObjectKey::max_for_id(object_id))?
-            .next();
-
-        let obj = match obj_entry {
-            Some((ObjectKey(obj_id, _), obj)) if obj_id == *object_id => obj,
-            _ => return Ok(None),
-        };
-
-        // Note that the two reads in this function are (obviously) not atomic, and the
-        // object may be deleted after we have read it. Hence we check get_latest_parent_entry
-        // last, so that the write to self.tables.parent_sync gets the last word.
+        // The latest version of the object is the one of its latest parent_sync entry, so that
+        // the object itself is read by key, through the cache.
         //
-        // TODO: verify this race is ok.
-        //
-        // I believe it is - Even if the reads were atomic, calls to this function would still
-        // race with object deletions (the object could be deleted between when the function is
-        // called and when the first read takes place, which would be indistinguishable to the
-        // caller with the case in which the object is deleted in between the two reads).
-        let parent_entry = self.get_latest_parent_entry(*object_id)?;
-
-        match parent_entry {
-            None => {
-                error!(
-                    ?object_id,
-                    "Object is missing parent_sync entry, data store is inconsistent"
-                );
-                Ok(None)
-            }
-            Some((obj_ref, _)) if obj_ref.2.is_alive() => Ok(Some(obj)),
-            _ => Ok(None),
+        // Note that the two reads in this function are (obviously) not atomic, and the object
+        // may be deleted after we have read its entry, which is indistinguishable to the caller
+        // from the object being deleted right after the call.
+        let (version, digest) = match self.get_latest_parent_entry(*object_id)? {
+            Some(((_, version, digest), _)) => (version, digest),
+            None => return Ok(None),
+        };
+        if !digest.is_alive() {
+            return Ok(None);
+        }
+        let object = self.get_object_by_key(object_id, version)?;
+        if object.is_none() {
+            error!(
+                ?object_id,
+                ?version,
+                "Object of the latest parent_sync entry is missing, data store is inconsistent"
+            );
         }
+        Ok(object)
     }
 
     /// Get many objects
@@ -715,7 +829,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
 
     async fn sequence_tx(
         &self,
-        write_batch: DBBatch,
+        write_batch: TableBatch,
         inner_temporary_store: InnerTemporaryStore,
         transaction_digest: &TransactionDigest,
         proposed_seq: TxSequenceNumber,
@@ -770,6 +884,8 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         )?;
 
         batch.write()?;
+        self.cache
+            .insert_effects(*transaction_digest, &effects.effects);
 
         Ok(())
     }
@@ -777,7 +893,7 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     /// Helper function for updating the objects in the state
     async fn batch_update_objects(
         &self,
-        mut write_batch: DBBatch,
+        mut write_batch: TableBatch,
         inner_temporary_store: InnerTemporaryStore,
         transaction_digest: TransactionDigest,
         update_type: UpdateType,
@@ -877,9 +993,21 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
                 .map(|(_, (obj_ref, new_object, _kind))| (ObjectKey::from(obj_ref), new_object)),
         )?;
 
+        sui_failpoints::fail_point_async!(
+            "authority-store-before-commit",
+            Err(SuiError::GenericStorageError(
+                "Fail point authority-store-before-commit".to_string()
+            ))
+        );
         // Atomic write of all data other than locks
         write_batch.write()?;
         trace!("Finished writing batch");
+        self.cache.insert_objects(
+            written
+                .iter()
+                .map(|(_, (obj_ref, new_object, _kind))| (ObjectKey::from(obj_ref), new_object)),
+        );
+        sui_failpoints::fail_point_async!("authority-store-after-commit");
 
         // Need to have a critical section for now because we need to prevent execution of older
         // certs which may overwrite newer objects with older ones.  This can be removed once we have
@@ -969,8 +1097,9 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .iter()
             .chain(effects.created.iter())
             .chain(effects.unwrapped.iter())
-            .map(|((id, version, _), _)| ObjectKey(*id, *version));
-        write_batch = write_batch.delete_batch(&self.tables.objects, all_new_object_keys)?;
+            .map(|((id, version, _), _)| ObjectKey(*id, *version))
+            .collect::<Vec<_>>();
+        write_batch = write_batch.delete_batch(&self.tables.objects, all_new_object_keys.iter())?;
 
         // Reverting the change to the owner_index table is most complex.
         // For each newly created (i.e. created and unwrapped) object, the entry in owner_index
@@ -1014,6 +1143,8 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         write_batch = write_batch.insert_batch(&self.tables.owner_index, old_objects)?;
 
         write_batch.write()?;
+        self.cache.invalidate_objects(all_new_object_keys.iter());
+        self.cache.invalidate_effects(tx_digest);
         Ok(())
     }
 
@@ -1048,6 +1179,29 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         }))
     }
 
+    /// Returns at most `limit` entries of the parents_sync index for `object_id`, in order of
+    /// version, after version `cursor` or from the first version if `None`.
+    pub fn get_parent_entries_after(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+        limit: usize,
+    ) -> Result<Vec<(ObjectRef, TransactionDigest)>, SuiError> {
+        let start = match cursor {
+            Some(cursor) if cursor >= SequenceNumber::MAX => return Ok(vec![]),
+            Some(cursor) => cursor.increment(),
+            None => SequenceNumber::MIN,
+        };
+        Ok(self
+            .tables
+            .parent_sync
+            .iter()
+            .skip_to(&(object_id, start, ObjectDigest::new([0; 32])))?
+            .take_while(|((id, _, _), _)| *id == object_id)
+            .take(limit)
+            .collect())
+    }
+
     /// Remove the shared objects locks.
     pub fn remove_shared_objects_locks(
         &self,
@@ -1210,6 +1364,46 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .collect())
     }
 
+    /// Returns up to `limit` of the transactions executed by this node, from the one with the
+    /// sequence number `cursor` (included) onwards, or backwards if `descending`.
+    pub fn executed_transactions(
+        &self,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
+        if !descending {
+            return Ok(self
+                .tables
+                .executed_sequence
+                .iter()
+                .skip_to(&cursor.unwrap_or(0))?
+                .take(limit)
+                .map(|(seq, digests)| (seq, digests.transaction))
+                .collect());
+        }
+        let mut transactions = vec![];
+        let mut upper = cursor.unwrap_or(TxSequenceNumber::MAX);
+        while transactions.len() < limit {
+            let (seq, digests) = match self
+                .tables
+                .executed_sequence
+                .iter()
+                .skip_prior_to(&upper)?
+                .next()
+            {
+                Some(entry) => entry,
+                None => break,
+            };
+            transactions.push((seq, digests.transaction));
+            match seq.checked_sub(1) {
+                Some(next) => upper = next,
+                None => break,
+            }
+        }
+        Ok(transactions)
+    }
+
     /// Retrieves batches including transactions within a range.
     ///
     /// This function returns all signed batches that enclose the requested transaction
@@ -1323,6 +1517,76 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .map_err(SuiError::from)
     }
 
+    /// The first checkpoint whose transactions have not been pruned.
+    pub fn next_checkpoint_to_prune(&self) -> SuiResult<CheckpointSequenceNumber> {
+        Ok(self
+            .tables
+            .pruning_watermark
+            .get(&PRUNING_WATERMARK_ADDR)?
+            .unwrap_or_default())
+    }
+
+    /// Deletes the certificates, effects and signed transactions of `transactions`, the
+    /// transactions of `checkpoint`, with the object versions they took as inputs and replaced,
+    /// and records that the checkpoint is pruned, in a single batch. The transactions must have
+    /// been executed, see `objects`.
+    pub fn prune_checkpoint_transactions(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: &[TransactionDigest],
+    ) -> SuiResult<PrunedData> {
+        let mut pruned = PrunedData::default();
+        let mut pruned_object_keys = vec![];
+        let mut write_batch = self.tables.certificates.batch();
+        for digest in transactions {
+            let effects = self.get_effects(digest)?;
+            let old_object_keys: Vec<_> = effects
+                .mutated
+                .iter()
+                .map(|(r, _)| r)
+                .chain(effects.deleted.iter())
+                .chain(effects.wrapped.iter())
+                .filter_map(|(id, version, _)| Some(ObjectKey(*id, version.decrement().ok()?)))
+                .collect();
+            let old_objects: Vec<_> = self
+                .tables
+                .objects
+                .multi_get(&old_object_keys)?
+                .into_iter()
+                .flatten()
+                .collect();
+            let certificate = self.tables.certificates.get(digest)?;
+
+            pruned.transactions += 1;
+            pruned.objects += old_objects.len() as u64;
+            pruned.bytes += serialized_size(&effects)
+                + certificate.as_ref().map_or(0, serialized_size)
+                + old_objects.iter().map(serialized_size).sum::<u64>();
+
+            let old_refs = old_objects
+                .iter()
+                .map(|object| object.compute_object_reference());
+            write_batch = write_batch.delete_batch(&self.tables.parent_sync, old_refs)?;
+            write_batch = write_batch.delete_batch(&self.tables.objects, old_object_keys.iter())?;
+            pruned_object_keys.extend(old_object_keys);
+            write_batch =
+                write_batch.delete_batch(&self.tables.certificates, iter::once(digest))?;
+            write_batch = write_batch.delete_batch(&self.tables.effects, iter::once(digest))?;
+            write_batch =
+                write_batch.delete_batch(&self.tables.transactions, iter::once(digest))?;
+        }
+        write_batch = write_batch.insert_batch(
+            &self.tables.pruning_watermark,
+            iter::once((PRUNING_WATERMARK_ADDR, checkpoint + 1)),
+        )?;
+        write_batch.write()?;
+        self.cache.invalidate_objects(pruned_object_keys.iter());
+        for digest in transactions {
+            self.cache.invalidate_effects(digest);
+        }
+        Ok(pruned)
+    }
+
     pub fn get_transaction(
         &self,
         transaction_digest: &TransactionDigest,
@@ -1377,7 +1641,7 @@ impl SuiDataStore<AuthoritySignInfo> {
 }
 
 impl SuiDataStore<EmptySignInfo> {
-    pub fn pending_transactions(&self) -> &DBMap<TransactionDigest, Transaction> {
+    pub fn pending_transactions(&self) -> &Table<TransactionDigest, Transaction> {
         &self.tables.transactions
     }
 }
@@ -1386,16 +1650,18 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> BackingPackageStore
     for SuiDataStore<S>
 {
     fn get_package(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
-        let package = self.get_object(package_id)?;
-        if let Some(obj) = &package {
-            fp_ensure!(
-                obj.is_package(),
-                SuiError::BadObjectType {
-                    error: format!("Package expected, Move object found: {package_id}"),
-                }
-            );
-        }
-        Ok(package)
+        self.cache.get_package(package_id, || {
+            let package = self.get_object(package_id)?;
+            if let Some(obj) = &package {
+                fp_ensure!(
+                    obj.is_package(),
+                    SuiError::BadObjectType {
+                        error: format!("Package expected, Move object found: {package_id}"),
+                    }
+                );
+            }
+            Ok(package)
+        })
     }
 }
 
@@ -1468,3 +1734,8 @@ pub enum UpdateType {
     Transaction(TxSequenceNumber, TransactionEffectsDigest),
     Genesis,
 }
+
+/// The size of `value` in the store, as the bytes it is serialized to.
+fn serialized_size<T: Serialize>(value: &T) -> u64 {
+    bcs::serialized_size(value).unwrap_or_default() as u64
+}
@@ -0,0 +1,371 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A cache of the recently read and written objects and effects of an authority store, in front
+//! of its tables, so that hot objects, e.g. shared objects and gas coins, aren't read and
+//! deserialized again on every access.
+//!
+//! Objects are cached by ID and version, and effects by transaction digest, which the store never
+//! updates in place. Packages are immutable, so they are also cached by ID alone and shared by the
+//! executions of all transactions. Entries are only invalidated when the store deletes them, i.e.
+//! when reverting or pruning transactions. A value read from the store while entries are invalidated
+//! may be one of them, so it isn't cached.
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use std::hash::Hash;
+use sui_config::node::StoreCacheConfig;
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::error::SuiResult;
+use sui_types::messages::TransactionEffects;
+use sui_types::object::Object;
+
+use super::authority_store::ObjectKey;
+
+const OBJECTS: &str = "objects";
+const EFFECTS: &str = "effects";
+const PACKAGES: &str = "packages";
+
+struct StoreCacheMetrics {
+    hits: IntCounterVec,
+    misses: IntCounterVec,
+}
+
+impl StoreCacheMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            hits: register_int_counter_vec_with_registry!(
+                "store_cache_hits",
+                "Number of reads of the authority store served by its cache",
+                &["cache"],
+                registry,
+            )
+            .unwrap(),
+            misses: register_int_counter_vec_with_registry!(
+                "store_cache_misses",
+                "Number of reads of the authority store not found in its cache",
+                &["cache"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// The entries of a cache, and how many times some were invalidated.
+struct Entries<K: Hash + Eq, V> {
+    lru: LruCache<K, V>,
+    generation: u64,
+}
+
+impl<K: Hash + Eq, V> Entries<K, V> {
+    fn new(capacity: usize) -> Mutex<Self> {
+        Mutex::new(Self {
+            lru: LruCache::new(capacity),
+            generation: 0,
+        })
+    }
+}
+
+/// The cache of an authority store, disabled unless built with [`StoreCache::new`].
+#[derive(Default)]
+pub struct StoreCache {
+    objects: Option<Mutex<Entries<ObjectKey, Object>>>,
+    effects: Option<Mutex<Entries<TransactionDigest, TransactionEffects>>>,
+    packages: Option<Mutex<Entries<ObjectID, Object>>>,
+    metrics: Option<StoreCacheMetrics>,
+}
+
+impl StoreCache {
+    /// A cache of the capacities of `config`, a capacity of zero disabling the cache of objects,
+    /// effects or packages.
+    pub fn new(config: &StoreCacheConfig, registry: &Registry) -> Self {
+        Self {
+            objects: (config.object_capacity > 0).then(|| Entries::new(config.object_capacity)),
+            effects: (config.effects_capacity > 0).then(|| Entries::new(config.effects_capacity)),
+            packages: (config.package_capacity > 0).then(|| Entries::new(config.package_capacity)),
+            metrics: Some(StoreCacheMetrics::new(registry)),
+        }
+    }
+
+    /// The object of `key` from the cache, or else from `read`, caching it if found.
+    pub(crate) fn get_object(
+        &self,
+        key: &ObjectKey,
+        read: impl FnOnce() -> SuiResult<Option<Object>>,
+    ) -> SuiResult<Option<Object>> {
+        get_or_read(&self.objects, &self.metrics, OBJECTS, key, read)
+    }
+
+    pub(crate) fn get_effects(
+        &self,
+        digest: &TransactionDigest,
+        read: impl FnOnce() -> SuiResult<Option<TransactionEffects>>,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        get_or_read(&self.effects, &self.metrics, EFFECTS, digest, read)
+    }
+
+    /// The package `package_id` from the cache, or else from `read`, caching it if found.
+    pub(crate) fn get_package(
+        &self,
+        package_id: &ObjectID,
+        read: impl FnOnce() -> SuiResult<Option<Object>>,
+    ) -> SuiResult<Option<Object>> {
+        get_or_read(&self.packages, &self.metrics, PACKAGES, package_id, read)
+    }
+
+    pub(crate) fn insert_objects<'a>(
+        &self,
+        objects: impl Iterator<Item = (ObjectKey, &'a Object)>,
+    ) {
+        if let Some(cache) = &self.objects {
+            let mut cache = cache.lock();
+            for (key, object) in objects {
+                cache.lru.put(key, object.clone());
+            }
+        }
+    }
+
+    pub(crate) fn insert_effects(&self, digest: TransactionDigest, effects: &TransactionEffects) {
+        if let Some(cache) = &self.effects {
+            cache.lock().lru.put(digest, effects.clone());
+        }
+    }
+
+    /// Invalidates the objects of `keys`, and the packages of their IDs.
+    pub(crate) fn invalidate_objects<'a>(&self, keys: impl Iterator<Item = &'a ObjectKey>) {
+        let mut objects = self.objects.as_ref().map(|cache| cache.lock());
+        let mut packages = self.packages.as_ref().map(|cache| cache.lock());
+        for cache in objects.iter_mut() {
+            cache.generation += 1;
+        }
+        for cache in packages.iter_mut() {
+            cache.generation += 1;
+        }
+        for key in keys {
+            if let Some(cache) = &mut objects {
+                cache.lru.pop(key);
+            }
+            if let Some(cache) = &mut packages {
+                cache.lru.pop(&key.0);
+            }
+        }
+    }
+
+    pub(crate) fn invalidate_effects(&self, digest: &TransactionDigest) {
+        if let Some(cache) = &self.effects {
+            let mut cache = cache.lock();
+            cache.generation += 1;
+            cache.lru.pop(digest);
+        }
+    }
+}
+
+fn get_or_read<K, V>(
+    cache: &Option<Mutex<Entries<K, V>>>,
+    metrics: &Option<StoreCacheMetrics>,
+    label: &str,
+    key: &K,
+    read: impl FnOnce() -> SuiResult<Option<V>>,
+) -> SuiResult<Option<V>>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+{
+    let cache = match cache {
+        Some(cache) => cache,
+        None => return read(),
+    };
+    let generation = {
+        let mut cache = cache.lock();
+        if let Some(value) = cache.lru.get(key) {
+            if let Some(metrics) = metrics {
+                metrics.hits.with_label_values(&[label]).inc();
+            }
+            return Ok(Some(value.clone()));
+        }
+        cache.generation
+    };
+    if let Some(metrics) = metrics {
+        metrics.misses.with_label_values(&[label]).inc();
+    }
+    // The lock isn't held while reading, so that reads of other keys aren't blocked. Entries
+    // invalidated meanwhile may include the value read, which is then not cached.
+    let value = read()?;
+    if let Some(value) = &value {
+        let mut cache = cache.lock();
+        if cache.generation == generation {
+            cache.lru.put(key.clone(), value.clone());
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::Cell;
+    use sui_types::base_types::{ObjectID, SequenceNumber};
+
+    #[test]
+    fn test_store_cache() {
+        let registry = Registry::new();
+        let config = StoreCacheConfig {
+            object_capacity: 1,
+            effects_capacity: 0,
+            package_capacity: 0,
+        };
+        let cache = StoreCache::new(&config, &registry);
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        let key = ObjectKey(object.id(), object.version());
+        let reads = Cell::new(0);
+        let read = || {
+            reads.set(reads.get() + 1);
+            Ok(Some(object.clone()))
+        };
+
+        // The first read misses, the next one hits
+        assert_eq!(cache.get_object(&key, read).unwrap(), Some(object.clone()));
+        assert_eq!(cache.get_object(&key, read).unwrap(), Some(object.clone()));
+        assert_eq!(reads.get(), 1);
+
+        // Invalidated and evicted objects are read again
+        cache.invalidate_objects(std::iter::once(&key));
+        cache.get_object(&key, read).unwrap();
+        assert_eq!(reads.get(), 2);
+        let other = ObjectKey(ObjectID::random(), SequenceNumber::new());
+        cache.get_object(&other, read).unwrap();
+        cache.get_object(&key, read).unwrap();
+        assert_eq!(reads.get(), 4);
+
+        // Objects not found aren't cached
+        let missing = ObjectKey(ObjectID::random(), SequenceNumber::new());
+        assert_eq!(cache.get_object(&missing, || Ok(None)).unwrap(), None);
+        cache.insert_objects(std::iter::once((key, &object)));
+        assert_eq!(cache.get_object(&key, read).unwrap(), Some(object.clone()));
+        assert_eq!(reads.get(), 4);
+
+        let hits = cache
+            .metrics
+            .as_ref()
+            .unwrap()
+            .hits
+            .with_label_values(&[OBJECTS]);
+        assert_eq!(hits.get(), 2);
+
+        // The cache of effects is disabled
+        let digest = TransactionDigest::random();
+        let mut effects_reads = 0;
+        for _ in 0..2 {
+            cache
+                .get_effects(&digest, || {
+                    effects_reads += 1;
+                    Ok(None)
+                })
+                .unwrap();
+        }
+        assert_eq!(effects_reads, 2);
+    }
+
+    #[test]
+    fn test_package_cache() {
+        let config = StoreCacheConfig {
+            object_capacity: 0,
+            effects_capacity: 0,
+            package_capacity: 10,
+        };
+        let cache = StoreCache::new(&config, &Registry::new());
+        let package = Object::immutable_with_id_for_testing(ObjectID::random());
+        let reads = Cell::new(0);
+        let read = || {
+            reads.set(reads.get() + 1);
+            Ok(Some(package.clone()))
+        };
+
+        // Packages are read once, whichever transactions load them
+        for _ in 0..3 {
+            assert_eq!(
+                cache.get_package(&package.id(), read).unwrap(),
+                Some(package.clone())
+            );
+        }
+        assert_eq!(reads.get(), 1);
+
+        // Reverting the transaction which published a package evicts it
+        cache.invalidate_objects(std::iter::once(&ObjectKey(package.id(), package.version())));
+        cache.get_package(&package.id(), read).unwrap();
+        assert_eq!(reads.get(), 2);
+    }
+
+    #[test]
+    fn test_invalidate_during_read() {
+        let config = StoreCacheConfig {
+            object_capacity: 10,
+            effects_capacity: 0,
+            package_capacity: 0,
+        };
+        let cache = StoreCache::new(&config, &Registry::new());
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        let key = ObjectKey(object.id(), object.version());
+
+        // The object is reverted while it is being read: the value read isn't cached
+        let read = cache
+            .get_object(&key, || {
+                cache.invalidate_objects(std::iter::once(&key));
+                Ok(Some(object.clone()))
+            })
+            .unwrap();
+        assert_eq!(read, Some(object.clone()));
+        let reads = Cell::new(0);
+        assert_eq!(
+            cache
+                .get_object(&key, || {
+                    reads.set(reads.get() + 1);
+                    Ok(None)
+                })
+                .unwrap(),
+            None
+        );
+        assert_eq!(reads.get(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_invalidate_and_read() {
+        let config = StoreCacheConfig {
+            object_capacity: 10,
+            effects_capacity: 0,
+            package_capacity: 0,
+        };
+        let cache = StoreCache::new(&config, &Registry::new());
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        let key = ObjectKey(object.id(), object.version());
+        // Whether the object is in the store, which readers may see before it is invalidated
+        let stored = std::sync::atomic::AtomicBool::new(true);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..1000 {
+                        cache
+                            .get_object(&key, || {
+                                Ok(stored
+                                    .load(std::sync::atomic::Ordering::SeqCst)
+                                    .then(|| object.clone()))
+                            })
+                            .unwrap();
+                    }
+                });
+            }
+            scope.spawn(|| {
+                std::thread::yield_now();
+                stored.store(false, std::sync::atomic::Ordering::SeqCst);
+                cache.invalidate_objects(std::iter::once(&key));
+            });
+        });
+
+        // However the reads and the invalidation interleaved, the deleted object isn't cached
+        assert_eq!(cache.get_object(&key, || Ok(None)).unwrap(), None);
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prunes the transactions of the certified checkpoints out of the retention windows of a
+//! [`PruningConfig`], oldest first, so that the store of a node doesn't grow without bound.
+
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_config::node::PruningConfig;
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::{AuthenticatedCheckpoint, CheckpointSequenceNumber};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+use super::AuthorityState;
+
+/// What pruning the transactions of a checkpoint deleted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrunedData {
+    pub transactions: u64,
+    /// Object versions replaced by the transactions.
+    pub objects: u64,
+    /// Serialized size of the values deleted. The store reclaims the space on compaction.
+    pub bytes: u64,
+}
+
+pub struct PrunerMetrics {
+    pruned_checkpoints: IntCounter,
+    pruned_transactions: IntCounter,
+    pruned_objects: IntCounter,
+    pruned_bytes: IntCounter,
+    next_checkpoint_to_prune: IntGauge,
+}
+
+impl PrunerMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            pruned_checkpoints: register_int_counter_with_registry!(
+                "pruned_checkpoints",
+                "Number of checkpoints whose transactions were pruned",
+                registry,
+            )
+            .unwrap(),
+            pruned_transactions: register_int_counter_with_registry!(
+                "pruned_transactions",
+                "Number of transactions whose certificates and effects were pruned",
+                registry,
+            )
+            .unwrap(),
+            pruned_objects: register_int_counter_with_registry!(
+                "pruned_objects",
+                "Number of old object versions pruned",
+                registry,
+            )
+            .unwrap(),
+            pruned_bytes: register_int_counter_with_registry!(
+                "pruned_bytes",
+                "Serialized size of the data pruned, reclaimed by the store on compaction",
+                registry,
+            )
+            .unwrap(),
+            next_checkpoint_to_prune: register_int_gauge_with_registry!(
+                "next_checkpoint_to_prune",
+                "First checkpoint whose transactions have not been pruned",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+pub struct AuthorityStorePruner {
+    state: Arc<AuthorityState>,
+    config: PruningConfig,
+    metrics: PrunerMetrics,
+}
+
+impl AuthorityStorePruner {
+    pub fn new(state: Arc<AuthorityState>, config: PruningConfig, registry: &Registry) -> Self {
+        Self {
+            state,
+            config,
+            metrics: PrunerMetrics::new(registry),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(config = ?self.config, "Pruning the checkpoints out of the retention windows");
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            // Checkpoints failing to prune are retried at the next interval
+            match self.prune() {
+                Ok(0) => (),
+                Ok(count) => debug!("Pruned {} checkpoints", count),
+                Err(e) => error!("Failed to prune checkpoints: {:?}", e),
+            }
+        }
+    }
+
+    /// Prunes up to `max_checkpoints_per_interval` checkpoints, and returns how many were.
+    fn prune(&self) -> SuiResult<u64> {
+        let checkpoints = match &self.state.checkpoints {
+            Some(checkpoints) => checkpoints,
+            None => return Ok(0),
+        };
+        let database = &self.state.database;
+        let mut next = database.next_checkpoint_to_prune()?;
+        self.metrics.next_checkpoint_to_prune.set(next as i64);
+
+        let next_checkpoint = checkpoints.lock().next_checkpoint();
+        let end = match self.config.num_checkpoints_to_keep {
+            Some(keep) => next_checkpoint.saturating_sub(keep),
+            None => next_checkpoint,
+        };
+        let first_epoch_kept = self
+            .config
+            .num_epochs_to_keep
+            .map(|keep| (self.state.epoch() + 1).saturating_sub(keep));
+
+        let mut pruned = 0;
+        while next < end && pruned < self.config.max_checkpoints_per_interval {
+            let (checkpoint, contents) = {
+                let checkpoints = checkpoints.lock();
+                (
+                    checkpoints.tables.checkpoints.get(&next)?,
+                    checkpoints.tables.checkpoint_contents.get(&next)?,
+                )
+            };
+            let contents = match (checkpoint, contents) {
+                (Some(AuthenticatedCheckpoint::Certified(checkpoint)), Some(contents))
+                    if first_epoch_kept.map_or(true, |epoch| checkpoint.summary.epoch < epoch) =>
+                {
+                    contents
+                }
+                _ => break,
+            };
+            let transactions: Vec<_> = contents.iter().map(|digests| digests.transaction).collect();
+            let data = database.prune_checkpoint_transactions(next, &transactions)?;
+            self.record(next, data);
+            next += 1;
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
+    fn record(&self, checkpoint: CheckpointSequenceNumber, data: PrunedData) {
+        debug!(checkpoint, ?data, "Pruned checkpoint");
+        self.metrics.pruned_checkpoints.inc();
+        self.metrics.pruned_transactions.inc_by(data.transactions);
+        self.metrics.pruned_objects.inc_by(data.objects);
+        self.metrics.pruned_bytes.inc_by(data.bytes);
+        self.metrics
+            .next_checkpoint_to_prune
+            .set(checkpoint as i64 + 1);
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Periodic export of the statistics of the RocksDB database of the authority store as metrics:
+//! the size, memtables, pending compactions and block cache usage of each column family, and the
+//! compaction bytes, write stalls and block cache hits RocksDB collects when the
+//! [`StorageConfig`](sui_config::node::StorageConfig) of the node sets a metrics interval.
+
+use prometheus::{
+    register_gauge_with_registry, register_int_counter_with_registry,
+    register_int_gauge_vec_with_registry, Gauge, IntCounter, IntGaugeVec, Registry,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use super::AuthorityStore;
+
+/// The properties of each column family exported, with the name of their gauge.
+const PROPERTIES: &[(&str, &str)] = &[
+    ("rocksdb.estimate-num-keys", "rocksdb_estimated_num_keys"),
+    (
+        "rocksdb.total-sst-files-size",
+        "rocksdb_total_sst_files_size",
+    ),
+    ("rocksdb.cur-size-all-mem-tables", "rocksdb_mem_tables_size"),
+    (
+        "rocksdb.estimate-pending-compaction-bytes",
+        "rocksdb_estimated_pending_compaction_bytes",
+    ),
+    ("rocksdb.block-cache-usage", "rocksdb_block_cache_usage"),
+];
+
+const COMPACTION_READ_BYTES: &str = "rocksdb.compact.read.bytes";
+const COMPACTION_WRITE_BYTES: &str = "rocksdb.compact.write.bytes";
+const STALL_MICROS: &str = "rocksdb.stall.micros";
+const BLOCK_CACHE_HIT: &str = "rocksdb.block.cache.hit";
+const BLOCK_CACHE_MISS: &str = "rocksdb.block.cache.miss";
+
+pub struct StoreStatisticsMetrics {
+    properties: Vec<(&'static str, IntGaugeVec)>,
+    compaction_read_bytes: IntCounter,
+    compaction_write_bytes: IntCounter,
+    stall_micros: IntCounter,
+    block_cache_hits: IntCounter,
+    block_cache_misses: IntCounter,
+    block_cache_hit_rate: Gauge,
+}
+
+impl StoreStatisticsMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            properties: PROPERTIES
+                .iter()
+                .map(|(property, name)| {
+                    let help = format!(
+                        "The {property} property of the column families of the authority store"
+                    );
+                    let gauge = register_int_gauge_vec_with_registry!(
+                        *name,
+                        help,
+                        &["cf"],
+                        registry,
+                    )
+                    .unwrap();
+                    (*property, gauge)
+                })
+                .collect(),
+            compaction_read_bytes: register_int_counter_with_registry!(
+                "rocksdb_compaction_read_bytes",
+                "Bytes read by the compactions of the authority store",
+                registry,
+            )
+            .unwrap(),
+            compaction_write_bytes: register_int_counter_with_registry!(
+                "rocksdb_compaction_write_bytes",
+                "Bytes written by the compactions of the authority store",
+                registry,
+            )
+            .unwrap(),
+            stall_micros: register_int_counter_with_registry!(
+                "rocksdb_write_stall_micros",
+                "Time the writes to the authority store were stalled for compactions, in microseconds",
+                registry,
+            )
+            .unwrap(),
+            block_cache_hits: register_int_counter_with_registry!(
+                "rocksdb_block_cache_hits",
+                "Reads of the authority store served by the block cache",
+                registry,
+            )
+            .unwrap(),
+            block_cache_misses: register_int_counter_with_registry!(
+                "rocksdb_block_cache_misses",
+                "Reads of the authority store which missed the block cache",
+                registry,
+            )
+            .unwrap(),
+            block_cache_hit_rate: register_gauge_with_registry!(
+                "rocksdb_block_cache_hit_rate",
+                "Share of the block cache lookups of the last interval which hit",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+pub struct StoreStatisticsExporter {
+    store: Arc<AuthorityStore>,
+    interval: Duration,
+    metrics: StoreStatisticsMetrics,
+}
+
+impl StoreStatisticsExporter {
+    pub fn new(store: Arc<AuthorityStore>, interval: Duration, registry: &Registry) -> Self {
+        Self {
+            store,
+            interval,
+            metrics: StoreStatisticsMetrics::new(registry),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(interval = ?self.interval, "Exporting the statistics of the authority store");
+        let mut interval = tokio::time::interval(self.interval);
+        // RocksDB counts its tickers since the database was opened
+        let mut last = HashMap::new();
+        loop {
+            interval.tick().await;
+            self.export_properties();
+            if let Some(statistics) = self.store.db_statistics() {
+                let tickers = parse_tickers(&statistics);
+                self.export_tickers(&tickers, &last);
+                last = tickers;
+            }
+        }
+    }
+
+    fn export_properties(&self) {
+        for (property, gauge) in &self.metrics.properties {
+            match self.store.db_property(property) {
+                Ok(values) => {
+                    for (cf, value) in values {
+                        gauge.with_label_values(&[cf]).set(value as i64);
+                    }
+                }
+                Err(e) => error!("Failed to read the {property} property of the store: {e}"),
+            }
+        }
+    }
+
+    fn export_tickers(&self, tickers: &HashMap<String, u64>, last: &HashMap<String, u64>) {
+        let delta = |ticker: &str| {
+            let count = tickers.get(ticker).copied().unwrap_or(0);
+            count.saturating_sub(last.get(ticker).copied().unwrap_or(0))
+        };
+        let metrics = &self.metrics;
+        metrics
+            .compaction_read_bytes
+            .inc_by(delta(COMPACTION_READ_BYTES));
+        metrics
+            .compaction_write_bytes
+            .inc_by(delta(COMPACTION_WRITE_BYTES));
+        metrics.stall_micros.inc_by(delta(STALL_MICROS));
+
+        let (hits, misses) = (delta(BLOCK_CACHE_HIT), delta(BLOCK_CACHE_MISS));
+        metrics.block_cache_hits.inc_by(hits);
+        metrics.block_cache_misses.inc_by(misses);
+        if hits + misses > 0 {
+            metrics
+                .block_cache_hit_rate
+                .set(hits as f64 / (hits + misses) as f64);
+        }
+    }
+}
+
+/// The counts of the tickers of statistics dumped by RocksDB, from their lines such as
+/// `rocksdb.block.cache.hit COUNT : 42`. Histograms, on lines with percentiles, are skipped.
+fn parse_tickers(statistics: &str) -> HashMap<String, u64> {
+    statistics
+        .lines()
+        .filter_map(|line| {
+            let mut tokens = line.split_whitespace();
+            let name = tokens.next()?;
+            match (
+                tokens.next()?,
+                tokens.next()?,
+                tokens.next()?,
+                tokens.next(),
+            ) {
+                ("COUNT", ":", count, None) => Some((name.to_string(), count.parse().ok()?)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tickers() {
+        let statistics = "rocksdb.block.cache.miss COUNT : 7\n\
+            rocksdb.block.cache.hit COUNT : 42\n\
+            rocksdb.db.get.micros P50 : 1.5 P95 : 3.0 P99 : 4.0 P100 : 9.0 COUNT : 12 SUM : 30\n";
+        let tickers = parse_tickers(statistics);
+        assert_eq!(tickers.len(), 2);
+        assert_eq!(tickers[BLOCK_CACHE_HIT], 42);
+        assert_eq!(tickers[BLOCK_CACHE_MISS], 7);
+    }
+}
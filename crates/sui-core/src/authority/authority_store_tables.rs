@@ -6,11 +6,24 @@ use super::{
     *,
 };
 use narwhal_executor::ExecutionIndices;
-use rocksdb::Options;
+use rocksdb::{
+    BlockBasedOptions, Cache, DBCompactionStyle, DBCompressionType, DBWithThreadMode,
+    MultiThreaded, Options,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+use sui_config::node::{
+    ColumnFamilyConfig, CompactionStyleConfig, CompressionConfig, StorageConfig,
+};
 use sui_storage::default_db_options;
+use sui_storage::table::{InMemoryDB, StorageBackend, Table};
 use sui_types::base_types::{ExecutionDigests, SequenceNumber};
 use sui_types::batch::{SignedBatch, TxSequenceNumber};
-use typed_store::rocks::DBMap;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use typed_store::rocks::{default_rocksdb_options, DBMap, DBMapTableConfigMap};
 use typed_store::traits::TypedStoreDebug;
 
 use typed_store_derive::DBMapUtils;
@@ -99,6 +112,10 @@ pub struct AuthorityStoreTables<S> {
     /// by a single process acting as consensus (light) client. It is used to ensure the authority processes
     /// every message output by consensus (and in the right order).
     pub(crate) last_consensus_index: DBMap<u64, ExecutionIndices>,
+
+    /// A single value like `last_consensus_index`: the first checkpoint whose transactions have
+    /// not been pruned, which the pruner resumes from.
+    pub(crate) pruning_watermark: DBMap<u64, CheckpointSequenceNumber>,
 }
 
 // These functions are used to initialize the DB tables
@@ -114,3 +131,232 @@ fn certificates_table_default_config() -> Options {
 fn effects_table_default_config() -> Options {
     default_db_options(None, None).1
 }
+
+/// The column families of [`AuthorityStoreTables`].
+pub const TABLE_NAMES: &[&str] = &[
+    "objects",
+    "owner_index",
+    "transactions",
+    "certificates",
+    "pending_execution",
+    "parent_sync",
+    "effects",
+    "assigned_object_versions",
+    "next_object_versions",
+    "consensus_message_processed",
+    "executed_sequence",
+    "batches",
+    "last_consensus_index",
+    "pruning_watermark",
+];
+
+/// The options of the database of the store and of each of its tables, tuned by `config`.
+/// RocksDB collects the statistics of the database if `config` exports them.
+pub fn storage_options(config: &StorageConfig) -> SuiResult<(Options, DBMapTableConfigMap)> {
+    if let Some(name) = config
+        .column_families
+        .keys()
+        .find(|name| !TABLE_NAMES.contains(&name.as_str()))
+    {
+        return Err(SuiError::GenericStorageError(format!(
+            "The authority store has no column family {name}"
+        )));
+    }
+
+    let mut db_options = default_db_options(None, None).0;
+    if config.metrics_interval_secs.is_some() {
+        db_options.enable_statistics();
+    }
+
+    // Column families without a block cache size of their own share the top level cache
+    let shared_cache = config
+        .defaults
+        .block_cache_size_mb
+        .map(new_block_cache)
+        .transpose()?;
+    let mut table_options = BTreeMap::new();
+    for name in TABLE_NAMES {
+        let cf_config = config.column_family(name);
+        let cache = match config
+            .column_families
+            .get(*name)
+            .and_then(|config| config.block_cache_size_mb)
+        {
+            Some(size_mb) => Some(new_block_cache(size_mb)?),
+            None => shared_cache.clone(),
+        };
+        let options = tune(table_default_config(name), &cf_config, cache.as_ref());
+        table_options.insert(name.to_string(), options);
+    }
+    Ok((db_options, DBMapTableConfigMap::new(table_options)))
+}
+
+/// The options of the table `name` when the store is not tuned.
+fn table_default_config(name: &str) -> Options {
+    match name {
+        "objects" => objects_table_default_config(),
+        "transactions" => transactions_table_default_config(),
+        "certificates" => certificates_table_default_config(),
+        "effects" => effects_table_default_config(),
+        _ => default_rocksdb_options(),
+    }
+}
+
+fn new_block_cache(size_mb: usize) -> SuiResult<Cache> {
+    Cache::new_lru_cache(size_mb * 1024 * 1024)
+        .map_err(|e| SuiError::GenericStorageError(e.to_string()))
+}
+
+fn tune(mut options: Options, config: &ColumnFamilyConfig, cache: Option<&Cache>) -> Options {
+    if let Some(cache) = cache {
+        let mut block_options = BlockBasedOptions::default();
+        block_options.set_block_cache(cache);
+        options.set_block_based_table_factory(&block_options);
+    }
+    if let Some(size_mb) = config.write_buffer_size_mb {
+        options.set_write_buffer_size(size_mb * 1024 * 1024);
+    }
+    if let Some(compression) = config.compression {
+        options.set_compression_type(match compression {
+            CompressionConfig::None => DBCompressionType::None,
+            CompressionConfig::Snappy => DBCompressionType::Snappy,
+            CompressionConfig::Lz4 => DBCompressionType::Lz4,
+            CompressionConfig::Zstd => DBCompressionType::Zstd,
+        });
+    }
+    if let Some(compaction_style) = config.compaction_style {
+        options.set_compaction_style(match compaction_style {
+            CompactionStyleConfig::Level => DBCompactionStyle::Level,
+            CompactionStyleConfig::Universal => DBCompactionStyle::Universal,
+            CompactionStyleConfig::Fifo => DBCompactionStyle::Fifo,
+        });
+    }
+    options
+}
+
+/// Reads of the tables opened outside of an authority, e.g. as a read-only handle by tools.
+impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> AuthorityStoreTables<S> {
+    pub fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: VersionNumber,
+    ) -> SuiResult<Option<Object>> {
+        Ok(self.objects.get(&ObjectKey(*object_id, version))?)
+    }
+
+    /// All the versions of the object still in the table, in increasing version order.
+    pub fn get_object_versions(&self, object_id: &ObjectID) -> SuiResult<Vec<Object>> {
+        Ok(self
+            .objects
+            .iter()
+            .skip_to(&ObjectKey(*object_id, VersionNumber::MIN))?
+            .take_while(|(ObjectKey(id, _), _)| id == object_id)
+            .map(|(_, object)| object)
+            .collect())
+    }
+
+    pub fn get_certified_transaction(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult<Option<CertifiedTransaction>> {
+        Ok(self.certificates.get(transaction_digest)?)
+    }
+
+    pub fn get_effects(
+        &self,
+        transaction_digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        Ok(self
+            .effects
+            .get(transaction_digest)?
+            .map(|envelope| envelope.effects))
+    }
+}
+
+/// The tables of the authority store in the backend it runs on: the column families of
+/// [`AuthorityStoreTables`] with RocksDB, or tables of the same names in memory.
+pub struct AuthorityTables<S> {
+    pub(crate) objects: Table<ObjectKey, Object>,
+    pub(crate) owner_index: Table<(Owner, ObjectID), ObjectInfo>,
+    pub(crate) transactions: Table<TransactionDigest, TransactionEnvelope<S>>,
+    pub(crate) certificates: Table<TransactionDigest, CertifiedTransaction>,
+    pub(crate) pending_execution: Table<InternalSequenceNumber, TransactionDigest>,
+    pub(crate) parent_sync: Table<ObjectRef, TransactionDigest>,
+    pub(crate) effects: Table<TransactionDigest, TransactionEffectsEnvelope<S>>,
+    pub(crate) assigned_object_versions: Table<(TransactionDigest, ObjectID), SequenceNumber>,
+    pub(crate) next_object_versions: Table<ObjectID, SequenceNumber>,
+    pub(crate) consensus_message_processed: Table<TransactionDigest, bool>,
+    pub executed_sequence: Table<TxSequenceNumber, ExecutionDigests>,
+    pub batches: Table<TxSequenceNumber, SignedBatch>,
+    pub(crate) last_consensus_index: Table<u64, ExecutionIndices>,
+    pub(crate) pruning_watermark: Table<u64, CheckpointSequenceNumber>,
+}
+
+impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> AuthorityTables<S> {
+    /// Opens the tables of the store at `path` in `backend`, with the options of `table_options`
+    /// for the tables it has options for.
+    pub fn open(
+        path: &Path,
+        db_options: Option<Options>,
+        table_options: Option<DBMapTableConfigMap>,
+        backend: StorageBackend,
+    ) -> Self {
+        match backend {
+            StorageBackend::RocksDB => AuthorityStoreTables::open_tables_read_write(
+                path.to_path_buf(),
+                db_options,
+                table_options,
+            )
+            .into(),
+            StorageBackend::InMemory => Self::in_memory(&InMemoryDB::new()),
+        }
+    }
+
+    /// The database the tables are in, with RocksDB.
+    pub fn rocksdb(&self) -> Option<&Arc<DBWithThreadMode<MultiThreaded>>> {
+        match &self.objects {
+            Table::RocksDB(map) => Some(&map.rocksdb),
+            Table::InMemory(_) => None,
+        }
+    }
+
+    fn in_memory(db: &InMemoryDB) -> Self {
+        Self {
+            objects: Table::in_memory(db, "objects"),
+            owner_index: Table::in_memory(db, "owner_index"),
+            transactions: Table::in_memory(db, "transactions"),
+            certificates: Table::in_memory(db, "certificates"),
+            pending_execution: Table::in_memory(db, "pending_execution"),
+            parent_sync: Table::in_memory(db, "parent_sync"),
+            effects: Table::in_memory(db, "effects"),
+            assigned_object_versions: Table::in_memory(db, "assigned_object_versions"),
+            next_object_versions: Table::in_memory(db, "next_object_versions"),
+            consensus_message_processed: Table::in_memory(db, "consensus_message_processed"),
+            executed_sequence: Table::in_memory(db, "executed_sequence"),
+            batches: Table::in_memory(db, "batches"),
+            last_consensus_index: Table::in_memory(db, "last_consensus_index"),
+            pruning_watermark: Table::in_memory(db, "pruning_watermark"),
+        }
+    }
+}
+
+impl<S> From<AuthorityStoreTables<S>> for AuthorityTables<S> {
+    fn from(tables: AuthorityStoreTables<S>) -> Self {
+        Self {
+            objects: tables.objects.into(),
+            owner_index: tables.owner_index.into(),
+            transactions: tables.transactions.into(),
+            certificates: tables.certificates.into(),
+            pending_execution: tables.pending_execution.into(),
+            parent_sync: tables.parent_sync.into(),
+            effects: tables.effects.into(),
+            assigned_object_versions: tables.assigned_object_versions.into(),
+            next_object_versions: tables.next_object_versions.into(),
+            consensus_message_processed: tables.consensus_message_processed.into(),
+            executed_sequence: tables.executed_sequence.into(),
+            batches: tables.batches.into(),
+            last_consensus_index: tables.last_consensus_index.into(),
+            pruning_watermark: tables.pruning_watermark.into(),
+        }
+    }
+}
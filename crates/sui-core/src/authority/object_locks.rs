@@ -0,0 +1,240 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The locks the authority store holds on the owned input objects of a transaction while it
+//! commits the transaction, so that an older certificate doesn't overwrite the outputs of a newer
+//! one.
+//!
+//! The locks are striped: each object ID hashes to one of a fixed set of mutexes, so locking an
+//! object allocates nothing and takes no table-wide lock, and an uncontended object is locked with
+//! a couple of atomic operations. Objects hashing to the same stripe contend falsely, which enough
+//! stripes make rare.
+
+use parking_lot::Mutex;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry, Histogram, IntCounter,
+    Registry,
+};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use sui_types::base_types::ObjectID;
+use tokio::sync::MutexGuard;
+
+const NUM_STRIPES: usize = 32_768;
+
+const WAIT_SEC_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1., 5., 10.,
+];
+
+pub struct ObjectLockMetrics {
+    acquisitions: IntCounter,
+    contended_acquisitions: IntCounter,
+    wait_latency: Histogram,
+}
+
+impl ObjectLockMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            acquisitions: register_int_counter_with_registry!(
+                "object_lock_acquisitions",
+                "Number of owned object locks acquired by the authority store",
+                registry,
+            )
+            .unwrap(),
+            contended_acquisitions: register_int_counter_with_registry!(
+                "object_lock_contended_acquisitions",
+                "Number of owned object locks the authority store had to wait for",
+                registry,
+            )
+            .unwrap(),
+            wait_latency: register_histogram_with_registry!(
+                "object_lock_wait_latency",
+                "Time waited for a contended owned object lock, in seconds",
+                WAIT_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+struct Stripe {
+    mutex: tokio::sync::Mutex<()>,
+    /// Tasks holding or waiting for the stripe.
+    tasks: AtomicUsize,
+    /// The object locked by the latest task which had to wait for the stripe.
+    waited_for: Mutex<Option<ObjectID>>,
+}
+
+pub struct ObjectLockTable {
+    random_state: RandomState,
+    stripes: Vec<Stripe>,
+    metrics: Option<ObjectLockMetrics>,
+}
+
+/// Holds the lock of a stripe until dropped.
+pub struct ObjectLockGuard<'a> {
+    _guard: MutexGuard<'a, ()>,
+    _task: StripeTask<'a>,
+}
+
+/// Counts a task among those holding or waiting for a stripe, until dropped, even if the task
+/// stops waiting.
+struct StripeTask<'a>(&'a AtomicUsize);
+
+impl Drop for StripeTask<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for ObjectLockTable {
+    fn default() -> Self {
+        Self::new(NUM_STRIPES)
+    }
+}
+
+impl ObjectLockTable {
+    pub fn new(num_stripes: usize) -> Self {
+        Self {
+            random_state: RandomState::new(),
+            stripes: (0..num_stripes.max(1))
+                .map(|_| Stripe {
+                    mutex: tokio::sync::Mutex::new(()),
+                    tasks: AtomicUsize::new(0),
+                    waited_for: Mutex::new(None),
+                })
+                .collect(),
+            metrics: None,
+        }
+    }
+
+    pub fn with_metrics(mut self, metrics: ObjectLockMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn num_stripes(&self) -> usize {
+        self.stripes.len()
+    }
+
+    /// Locks all of `objects`, in the order of their stripes so that tasks locking overlapping
+    /// objects don't deadlock, and locking each stripe once even if several objects share it.
+    pub async fn acquire_locks(
+        &self,
+        objects: impl Iterator<Item = ObjectID>,
+    ) -> Vec<ObjectLockGuard<'_>> {
+        let mut stripes: Vec<_> = objects
+            .map(|object| (self.stripe_index(&object), object))
+            .collect();
+        stripes.sort_unstable_by_key(|(index, _)| *index);
+        stripes.dedup_by_key(|(index, _)| *index);
+
+        let mut guards = Vec::with_capacity(stripes.len());
+        for (index, object) in stripes {
+            guards.push(self.acquire_stripe(index, object).await);
+        }
+        guards
+    }
+
+    async fn acquire_stripe(&self, index: usize, object: ObjectID) -> ObjectLockGuard<'_> {
+        let stripe = &self.stripes[index];
+        stripe.tasks.fetch_add(1, Ordering::Relaxed);
+        let task = StripeTask(&stripe.tasks);
+        if let Some(metrics) = &self.metrics {
+            metrics.acquisitions.inc();
+        }
+
+        // Fast path, for the uncontended stripes
+        if let Ok(guard) = stripe.mutex.try_lock() {
+            return ObjectLockGuard {
+                _guard: guard,
+                _task: task,
+            };
+        }
+
+        *stripe.waited_for.lock() = Some(object);
+        let start = Instant::now();
+        let guard = stripe.mutex.lock().await;
+        if let Some(metrics) = &self.metrics {
+            metrics.contended_acquisitions.inc();
+            metrics.wait_latency.observe(start.elapsed().as_secs_f64());
+        }
+        ObjectLockGuard {
+            _guard: guard,
+            _task: task,
+        }
+    }
+
+    /// The `limit` stripes with the most tasks waiting for them, most contended first, each with an
+    /// object waited for and its number of tasks holding or waiting for the stripe.
+    pub fn contended_locks(&self, limit: usize) -> Vec<(ObjectID, usize)> {
+        let mut contended: Vec<_> = self
+            .stripes
+            .iter()
+            .filter_map(|stripe| {
+                let tasks = stripe.tasks.load(Ordering::Relaxed);
+                if tasks < 2 {
+                    return None;
+                }
+                let object = (*stripe.waited_for.lock())?;
+                Some((object, tasks))
+            })
+            .collect();
+        contended.sort_by(|(_, a), (_, b)| b.cmp(a));
+        contended.truncate(limit);
+        contended
+    }
+
+    fn stripe_index(&self, object: &ObjectID) -> usize {
+        let mut hasher = self.random_state.build_hasher();
+        object.hash(&mut hasher);
+        (hasher.finish() % self.stripes.len() as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::time::{sleep, timeout};
+
+    #[tokio::test]
+    async fn test_object_lock_table() {
+        let registry = Registry::new();
+        let table =
+            Arc::new(ObjectLockTable::new(1).with_metrics(ObjectLockMetrics::new(&registry)));
+        let (first, second) = (ObjectID::random(), ObjectID::random());
+
+        // Objects sharing a stripe are locked once, without deadlocking
+        let guards = table
+            .acquire_locks([first, second, first].into_iter())
+            .await;
+        assert_eq!(guards.len(), 1);
+        assert!(table.contended_locks(10).is_empty());
+
+        let waiter = {
+            let table = table.clone();
+            tokio::spawn(async move {
+                drop(table.acquire_locks(std::iter::once(second)).await);
+            })
+        };
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(table.contended_locks(10), vec![(second, 2)]);
+
+        drop(guards);
+        timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(table.contended_locks(10).is_empty());
+
+        let metrics = table.metrics.as_ref().unwrap();
+        assert_eq!(metrics.acquisitions.get(), 2);
+        assert_eq!(metrics.contended_acquisitions.get(), 1);
+    }
+}
@@ -321,6 +321,9 @@ where
                 return Ok(());
             }
         };
+        checkpoint_store
+            .lock()
+            .observe_network_checkpoint(checkpoint_summary.summary.sequence_number);
 
         sync_to_checkpoint(self, checkpoint_store, checkpoint_summary).await
     }
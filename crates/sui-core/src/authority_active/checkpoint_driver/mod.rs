@@ -307,6 +307,9 @@ where
             "Highest Checkpoint Certificate from the network: {}",
             checkpoint
         );
+        state_checkpoints
+            .lock()
+            .observe_network_checkpoint(checkpoint.summary.sequence_number);
         // Check if there are more historic checkpoints to catch up with
         let next_checkpoint = state_checkpoints.lock().next_checkpoint();
         // First sync until before the latest checkpoint. We will special
@@ -420,7 +423,12 @@ where
             seq,
             transactions.iter(),
             active_authority.state.database.clone(),
-        )
+        )?;
+    active_authority
+        .state
+        .performance
+        .record_checkpoint_signed(&active_authority.state.committee.load());
+    Ok(())
 }
 
 /// Obtain the highest checkpoint certificate from all validators.
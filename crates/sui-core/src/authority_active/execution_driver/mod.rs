@@ -1,22 +1,35 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashSet, sync::Arc};
-use sui_types::{base_types::TransactionDigest, error::SuiResult, messages::CertifiedTransaction};
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+};
+use sui_types::{
+    base_types::TransactionDigest,
+    error::{SuiError, SuiResult},
+    messages::CertifiedTransaction,
+};
 use tracing::{debug, info};
 
-use crate::authority::AuthorityStore;
+use crate::authority::{AuthorityMetrics, AuthorityStore};
 use crate::authority_client::AuthorityAPI;
 
-use futures::{stream, StreamExt};
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
 
 use super::ActiveAuthority;
 
-use tap::TapFallible;
+pub mod scheduler;
+
+use scheduler::{object_accesses, ExecutionSchedule};
 
 #[cfg(test)]
 pub(crate) mod tests;
 
+/// Maximum number of pending certificates sent for execution at a time.
+const MAX_CONCURRENT_EXECUTIONS: usize = 20;
+
 pub trait PendCertificateForExecution {
     fn add_pending_certificates(
         &self,
@@ -89,6 +102,24 @@ where
     }
 }
 
+fn record_schedule(metrics: &AuthorityMetrics, schedule: &ExecutionSchedule) {
+    if schedule.is_empty() {
+        return;
+    }
+    metrics
+        .execution_driver_batch_size
+        .observe(schedule.len() as f64);
+    metrics
+        .execution_driver_batch_depth
+        .observe(schedule.depth() as f64);
+    metrics
+        .execution_driver_parallelism
+        .observe(schedule.len() as f64 / schedule.depth() as f64);
+    metrics
+        .execution_driver_conflicts
+        .inc_by(schedule.conflicts() as u64);
+}
+
 /// Reads all pending transactions as a block and executes them.
 /// Returns whether all pending transactions succeeded.
 async fn execute_pending<A>(active_authority: &ActiveAuthority<A>) -> SuiResult<bool>
@@ -118,24 +149,64 @@ where
         .database
         .remove_pending_certificates(indexes_to_delete)?;
 
-    // Send them for execution
-    let sync_handle = active_authority.node_sync_handle();
-    let executed: Vec<_> = sync_handle
-        // map to extract digest
-        .handle_execution_request(pending_transactions.iter().map(|(_, digest)| *digest))
-        .await?
-        // zip results back together with seq
-        .zip(stream::iter(pending_transactions.iter()))
-        // filter out errors
-        .filter_map(|(result, (seq, digest))| async move {
-            result
-                .tap_err(|e| info!(?seq, ?digest, "certificate execution failed: {}", e))
-                .tap_ok(|_| debug!(?seq, ?digest, "certificate execution complete"))
-                .ok()
-                .map(|_| seq)
+    // Schedule them so that only conflicting certificates wait for each other
+    let database = &active_authority.state.database;
+    let accesses = pending_transactions
+        .iter()
+        .map(|(_, digest)| match database.read_certificate(digest)? {
+            Some(certificate) => object_accesses(database, &certificate),
+            // The certificate is downloaded when executed, with no known conflicts
+            None => Ok(vec![]),
         })
-        .collect()
-        .await;
+        .collect::<SuiResult<Vec<_>>>()?;
+    let mut schedule = ExecutionSchedule::new(accesses);
+    record_schedule(&active_authority.state.metrics, &schedule);
+
+    // Send them for execution, at most MAX_CONCURRENT_EXECUTIONS at a time
+    let sync_handle = active_authority.node_sync_handle();
+    let mut ready: VecDeque<usize> = schedule.ready().into();
+    let mut in_flight = FuturesUnordered::new();
+    let mut executed = Vec::new();
+    loop {
+        while in_flight.len() < MAX_CONCURRENT_EXECUTIONS {
+            let index = match ready.pop_front() {
+                Some(index) => index,
+                None => break,
+            };
+            let sync_handle = sync_handle.clone();
+            let digest = pending_transactions[index].1;
+            in_flight.push(async move {
+                let result = match sync_handle
+                    .handle_execution_request(std::iter::once(digest))
+                    .await
+                {
+                    Ok(mut results) => {
+                        results
+                            .next()
+                            .await
+                            .unwrap_or(Err(SuiError::GenericAuthorityError {
+                                error: "Execution request dropped".to_string(),
+                            }))
+                    }
+                    Err(e) => Err(e),
+                };
+                (index, result)
+            });
+        }
+        let (index, result) = match in_flight.next().await {
+            Some(completed) => completed,
+            None => break,
+        };
+        let (seq, digest) = &pending_transactions[index];
+        match result {
+            Ok(_) => {
+                debug!(?seq, ?digest, "certificate execution complete");
+                executed.push(*seq);
+            }
+            Err(e) => info!(?seq, ?digest, "certificate execution failed: {}", e),
+        }
+        ready.extend(schedule.complete(index));
+    }
 
     let pending_count = pending_transactions.len();
     let executed_count = executed.len();
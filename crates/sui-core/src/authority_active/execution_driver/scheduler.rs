@@ -0,0 +1,138 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Schedules a batch of pending certificates for concurrent execution. A certificate waits for
+//! the certificates before it in the batch which write an object it reads or writes, or read an
+//! object it writes, and runs concurrently with all others, so that certificates touching
+//! disjoint objects don't wait for each other.
+
+use std::collections::HashMap;
+use sui_types::base_types::ObjectID;
+use sui_types::error::SuiResult;
+use sui_types::messages::{CertifiedTransaction, InputObjectKind};
+
+use crate::authority::AuthorityStore;
+
+/// How a certificate accesses one of its input objects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectAccess {
+    Read(ObjectID),
+    Write(ObjectID),
+}
+
+/// The objects `certificate` reads and writes. Packages and immutable objects are read, all other
+/// inputs are written, including objects not created yet, e.g. by an earlier certificate.
+pub fn object_accesses(
+    store: &AuthorityStore,
+    certificate: &CertifiedTransaction,
+) -> SuiResult<Vec<ObjectAccess>> {
+    certificate
+        .signed_data
+        .data
+        .input_objects()?
+        .into_iter()
+        .map(|kind| match kind {
+            InputObjectKind::MovePackage(id) => Ok(ObjectAccess::Read(id)),
+            InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => match store.get_object(&id)? {
+                Some(object) if object.is_immutable() => Ok(ObjectAccess::Read(id)),
+                _ => Ok(ObjectAccess::Write(id)),
+            },
+            InputObjectKind::SharedMoveObject(id) => Ok(ObjectAccess::Write(id)),
+        })
+        .collect()
+}
+
+/// The dependencies between the certificates of a batch, identified by their index in it.
+pub struct ExecutionSchedule {
+    /// Number of the dependencies of each certificate which are not complete yet.
+    pending_dependencies: Vec<usize>,
+    dependents: Vec<Vec<usize>>,
+    /// Number of the certificates depending on another one of the batch.
+    conflicts: usize,
+    /// Length of the longest chain of dependencies, the number of rounds of execution the batch
+    /// takes with unlimited workers.
+    depth: usize,
+}
+
+impl ExecutionSchedule {
+    /// The schedule of the certificates accessing the objects `accesses`, in the order of the
+    /// batch.
+    pub fn new(accesses: impl IntoIterator<Item = Vec<ObjectAccess>>) -> Self {
+        // The last certificate writing each object, and the ones reading it since
+        let mut last_writer: HashMap<ObjectID, usize> = HashMap::new();
+        let mut readers: HashMap<ObjectID, Vec<usize>> = HashMap::new();
+        let mut dependencies: Vec<Vec<usize>> = vec![];
+        for (index, accesses) in accesses.into_iter().enumerate() {
+            let mut certificate_dependencies = vec![];
+            for access in accesses {
+                match access {
+                    ObjectAccess::Read(id) => {
+                        certificate_dependencies.extend(last_writer.get(&id));
+                        readers.entry(id).or_default().push(index);
+                    }
+                    ObjectAccess::Write(id) => {
+                        certificate_dependencies.extend(last_writer.insert(id, index));
+                        certificate_dependencies.extend(readers.remove(&id).unwrap_or_default());
+                    }
+                }
+            }
+            // A certificate accessing an object twice doesn't depend on itself
+            certificate_dependencies.retain(|dependency| *dependency != index);
+            certificate_dependencies.sort_unstable();
+            certificate_dependencies.dedup();
+            dependencies.push(certificate_dependencies);
+        }
+
+        let mut dependents = vec![vec![]; dependencies.len()];
+        let mut depths = vec![1; dependencies.len()];
+        for (index, certificate_dependencies) in dependencies.iter().enumerate() {
+            for &dependency in certificate_dependencies {
+                dependents[dependency].push(index);
+                depths[index] = depths[index].max(depths[dependency] + 1);
+            }
+        }
+        Self {
+            pending_dependencies: dependencies.iter().map(Vec::len).collect(),
+            conflicts: dependencies.iter().filter(|d| !d.is_empty()).count(),
+            depth: depths.into_iter().max().unwrap_or(0),
+            dependents,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending_dependencies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending_dependencies.is_empty()
+    }
+
+    pub fn conflicts(&self) -> usize {
+        self.conflicts
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The certificates which depend on no other one, in the order of the batch.
+    pub fn ready(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|index| self.pending_dependencies[*index] == 0)
+            .collect()
+    }
+
+    /// Records that certificate `index` completed, whether it succeeded or not, and returns the
+    /// certificates it made ready. A failed certificate doesn't hold back the ones depending on
+    /// it, whose execution fetches the missing parents.
+    pub fn complete(&mut self, index: usize) -> Vec<usize> {
+        let mut ready = vec![];
+        for &dependent in &self.dependents[index] {
+            self.pending_dependencies[dependent] -= 1;
+            if self.pending_dependencies[dependent] == 0 {
+                ready.push(dependent);
+            }
+        }
+        ready
+    }
+}
@@ -277,3 +277,39 @@ async fn test_parent_cert_exec() {
         .signed_effects
         .unwrap();
 }
+
+#[test]
+fn test_execution_schedule() {
+    use super::scheduler::{ExecutionSchedule, ObjectAccess::*};
+    use sui_types::base_types::ObjectID;
+
+    let (package, a, b, c) = (
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+    );
+    let mut schedule = ExecutionSchedule::new([
+        vec![Read(package), Write(a)],
+        vec![Read(package), Write(b)],
+        // Waits for the writers of a and b
+        vec![Write(a), Write(b)],
+        // Reads of c don't conflict
+        vec![Read(c)],
+        vec![Read(c), Read(package)],
+        // Waits for both readers of c
+        vec![Write(c)],
+        // Accessing an object twice doesn't conflict with itself
+        vec![Write(package), Read(package)],
+    ]);
+    assert_eq!(schedule.len(), 7);
+    assert_eq!(schedule.conflicts(), 3);
+    assert_eq!(schedule.depth(), 2);
+    assert_eq!(schedule.ready(), vec![0, 1, 3, 4]);
+
+    assert_eq!(schedule.complete(0), Vec::<usize>::new());
+    assert_eq!(schedule.complete(1), vec![2]);
+    assert_eq!(schedule.complete(3), Vec::<usize>::new());
+    // The write of the package waits for all its readers
+    assert_eq!(schedule.complete(4), vec![5, 6]);
+}
@@ -21,8 +21,9 @@ use sui_types::crypto::{get_key_pair, AuthorityKeyPair};
 use sui_types::error::SuiError;
 use sui_types::messages::{
     AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
-    CertifiedTransaction, EpochRequest, EpochResponse, ObjectInfoRequest, ObjectInfoResponse,
-    Transaction, TransactionInfoRequest, TransactionInfoResponse,
+    CertifiedTransaction, EpochRequest, EpochResponse, ObjectChunksRequest, ObjectChunksResponse,
+    ObjectInfoRequest, ObjectInfoResponse, Transaction, TransactionInfoRequest,
+    TransactionInfoResponse,
 };
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::object::Object;
@@ -121,6 +122,13 @@ impl AuthorityAPI for ConfigurableBatchActionClient {
         state.handle_object_info_request(request).await
     }
 
+    async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        self.state.handle_object_chunks_request(request).await
+    }
+
     /// Handle Object information requests for this account.
     async fn handle_transaction_info_request(
         &self,
@@ -29,6 +29,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::string::ToString;
 use std::sync::Arc;
 use std::time::Duration;
+use sui_config::node::RequestBatchingConfig;
 use sui_types::committee::StakeUnit;
 use tokio::sync::mpsc::Receiver;
 use tokio::time::{sleep, timeout};
@@ -40,6 +41,11 @@ use tap::TapFallible;
 const OBJECT_DOWNLOAD_CHANNEL_BOUND: usize = 1024;
 pub const DEFAULT_RETRIES: usize = 4;
 
+/// How many times a request is sent again to a validator which sheds it, see
+/// [`retry_overloaded`].
+const OVERLOADED_RETRIES: usize = 3;
+const MIN_OVERLOADED_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 #[cfg(test)]
 #[path = "unit_tests/authority_aggregator_tests.rs"]
 pub mod authority_aggregator_tests;
@@ -67,6 +73,14 @@ pub struct TimeoutConfig {
     // it is set to a value greater than serial_authority_request_timeout then it becomes
     // completely serial.
     pub serial_authority_request_interval: Duration,
+
+    // When set, waits past a quorum, such as post_quorum_timeout, only last until the validators
+    // still pending are expected to respond, i.e. adaptive_timeout_latency_multiplier times their
+    // usual latency since the requests were sent, but at least min_adaptive_timeout. Waits before
+    // a quorum keep their fixed timeout, since giving up early would fail the request.
+    pub adaptive_timeouts: bool,
+    pub adaptive_timeout_latency_multiplier: f64,
+    pub min_adaptive_timeout: Duration,
 }
 
 impl Default for TimeoutConfig {
@@ -77,6 +91,9 @@ impl Default for TimeoutConfig {
             post_quorum_timeout: Duration::from_secs(30),
             serial_authority_request_timeout: Duration::from_secs(5),
             serial_authority_request_interval: Duration::from_millis(1000),
+            adaptive_timeouts: true,
+            adaptive_timeout_latency_multiplier: 3.0,
+            min_adaptive_timeout: Duration::from_millis(200),
         }
     }
 }
@@ -89,6 +106,7 @@ pub struct AuthAggMetrics {
     pub num_good_stake: Histogram,
     pub num_bad_stake: Histogram,
     pub total_quorum_once_timeout: IntCounter,
+    pub total_adaptive_timeout: IntCounter,
 }
 
 // Override default Prom buckets for positive numbers in 0-50k range
@@ -134,6 +152,12 @@ impl AuthAggMetrics {
                 registry,
             )
             .unwrap(),
+            total_adaptive_timeout: register_int_counter_with_registry!(
+                "total_adaptive_timeout",
+                "Total number of waits past a quorum cut short by the adaptive timeout",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -143,6 +167,42 @@ impl AuthAggMetrics {
     }
 }
 
+/// Moving averages of the latencies of each validator's responses, from which the aggregator
+/// tells how long the validators it still waits for should take.
+#[derive(Default)]
+pub struct ValidatorLatencies {
+    latencies: parking_lot::Mutex<HashMap<AuthorityName, Duration>>,
+}
+
+impl ValidatorLatencies {
+    // Weight of the latest response in the average, so that it follows a validator slowing down
+    // or catching up within a few requests.
+    const LATEST_WEIGHT: f64 = 0.2;
+
+    pub fn record(&self, name: AuthorityName, latency: Duration) {
+        let mut latencies = self.latencies.lock();
+        let average = latencies.entry(name).or_insert(latency);
+        *average =
+            average.mul_f64(1.0 - Self::LATEST_WEIGHT) + latency.mul_f64(Self::LATEST_WEIGHT);
+    }
+
+    pub fn latency(&self, name: &AuthorityName) -> Option<Duration> {
+        self.latencies.lock().get(name).copied()
+    }
+
+    /// The latency of the slowest of `pending`, or None if one of them never responded, in which
+    /// case nothing tells how long it should take.
+    pub fn max_latency<'a>(
+        &self,
+        pending: impl IntoIterator<Item = &'a AuthorityName>,
+    ) -> Option<Duration> {
+        let latencies = self.latencies.lock();
+        pending.into_iter().try_fold(Duration::ZERO, |max, name| {
+            Some(max.max(*latencies.get(name)?))
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct AuthorityAggregator<A> {
     /// Our Sui committee.
@@ -154,6 +214,10 @@ pub struct AuthorityAggregator<A> {
     pub timeouts: TimeoutConfig,
     // Store here for clone during re-config
     pub safe_client_metrics: SafeClientMetrics,
+    /// Shared by the clones of the aggregator, which talk to the same committee.
+    pub latencies: Arc<ValidatorLatencies>,
+    /// How transactions and certificates are batched, if they are.
+    pub batching: Option<RequestBatchingConfig>,
 }
 
 impl<A> AuthorityAggregator<A> {
@@ -201,9 +265,26 @@ impl<A> AuthorityAggregator<A> {
             metrics,
             timeouts,
             safe_client_metrics,
+            latencies: Arc::new(ValidatorLatencies::default()),
+            batching: None,
         }
     }
 
+    /// Coalesces the transactions and certificates sent to each authority into batches, sent in a
+    /// single request each, as configured by `config`.
+    pub fn with_batching(mut self, config: RequestBatchingConfig) -> Self
+    where
+        A: AuthorityAPI + Send + Sync + 'static + Clone,
+    {
+        self.authority_clients = self
+            .authority_clients
+            .into_iter()
+            .map(|(name, client)| (name, client.with_batching(config)))
+            .collect();
+        self.batching = Some(config);
+        self
+    }
+
     pub fn clone_client(&self, name: &AuthorityName) -> SafeClient<A>
     where
         A: Clone,
@@ -229,6 +310,27 @@ pub enum ReduceOutput<S> {
     End(S),
 }
 
+/// Sends a request again, backing off, as long as the validator sheds it with a retryable error,
+/// e.g. because it is overloaded. The error is returned after `OVERLOADED_RETRIES` retries.
+pub(crate) async fn retry_overloaded<T, F, Fut>(mut request: F) -> SuiResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = SuiResult<T>>,
+{
+    let mut delay = MIN_OVERLOADED_RETRY_DELAY;
+    for _ in 0..OVERLOADED_RETRIES {
+        match request().await {
+            Err(err) if err.is_retryable() => {
+                debug!(?err, ?delay, "Validator shed the request, retrying");
+                sleep(delay).await;
+                delay *= 2;
+            }
+            res => return res,
+        }
+    }
+    request().await
+}
+
 #[async_trait]
 trait CertificateHandler {
     async fn handle(&self, certificate: CertifiedTransaction)
@@ -588,6 +690,10 @@ where
     /// This function provides a flexible way to communicate with a quorum of authorities, processing and
     /// processing their results into a safe overall result, and also safely allowing operations to continue
     /// past the quorum to ensure all authorities are up to date (up to a timeout).
+    ///
+    /// With adaptive timeouts, the timeout of ReduceOutput::ContinueWithTimeout is cut short to when the
+    /// authorities not heard from yet are expected to respond, going by their past latencies, so that slow
+    /// authorities don't hold up a result which only waits for them past a quorum.
     pub(crate) async fn quorum_map_then_reduce_with_timeout<'a, S, V, FMap, FReduce>(
         &'a self,
         // The initial state that will be used to fold in values from authorities.
@@ -640,28 +746,34 @@ where
         let authorities_shuffled = self.committee.shuffle_by_stake(authority_prefences, None);
 
         // First, execute in parallel for each authority FMap.
+        let start = tokio::time::Instant::now();
         let mut responses: futures::stream::FuturesUnordered<_> = authorities_shuffled
             .iter()
             .map(|name| {
                 let client = &self.authority_clients[name];
                 let execute = map_each_authority.clone();
                 async move {
-                    (
-                        *name,
-                        execute(*name, client)
-                            .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
-                            .await,
-                    )
+                    let result = execute(*name, client)
+                        .instrument(tracing::trace_span!("quorum_map_auth", authority =? name))
+                        .await;
+                    // Errors may come back early, e.g. when the connection is refused, or late,
+                    // e.g. on timeouts, so only responses tell the authority's latency.
+                    if result.is_ok() {
+                        self.latencies.record(*name, start.elapsed());
+                    }
+                    (*name, result)
                 }
             })
             .collect();
 
+        let mut pending: BTreeSet<AuthorityName> = authorities_shuffled.iter().copied().collect();
         let mut current_timeout = initial_timeout;
         let mut accumulated_state = initial_state;
         // Then, as results become available fold them into the state using FReduce.
         while let Ok(Some((authority_name, result))) =
             timeout(current_timeout, responses.next()).await
         {
+            pending.remove(&authority_name);
             let authority_weight = self.committee.weight(&authority_name);
             accumulated_state =
                 match reduce_result(accumulated_state, authority_name, authority_weight, result)
@@ -671,7 +783,7 @@ where
                     ReduceOutput::Continue(state) => state,
                     ReduceOutput::ContinueWithTimeout(state, duration) => {
                         // Adjust the waiting timeout.
-                        current_timeout = duration;
+                        current_timeout = self.adaptive_timeout(duration, &pending, start);
                         state
                     }
                     ReduceOutput::End(state) => {
@@ -683,6 +795,38 @@ where
         Ok(accumulated_state)
     }
 
+    /// How long to wait for `pending` authorities, at most `max_timeout`, once a quorum responded
+    /// to the requests sent at `start`.
+    fn adaptive_timeout(
+        &self,
+        max_timeout: Duration,
+        pending: &BTreeSet<AuthorityName>,
+        start: tokio::time::Instant,
+    ) -> Duration {
+        if !self.timeouts.adaptive_timeouts {
+            return max_timeout;
+        }
+        let expected = match self.latencies.max_latency(pending) {
+            Some(latency) => latency.mul_f64(self.timeouts.adaptive_timeout_latency_multiplier),
+            None => return max_timeout,
+        };
+        let timeout = expected
+            .saturating_sub(start.elapsed())
+            .max(self.timeouts.min_adaptive_timeout);
+        if timeout < max_timeout {
+            self.metrics.total_adaptive_timeout.inc();
+            debug!(
+                ?timeout,
+                ?max_timeout,
+                num_pending = pending.len(),
+                "Cutting short the wait for validators past a quorum"
+            );
+            timeout
+        } else {
+            max_timeout
+        }
+    }
+
     // Repeatedly calls the provided closure on a randomly selected validator until it succeeds.
     // Once all validators have been attempted, starts over at the beginning. Intended for cases
     // that must eventually succeed as long as the network is up (or comes back up) eventually.
@@ -1290,9 +1434,10 @@ where
             .quorum_map_then_reduce_with_timeout(
                 state,
                 |_name, client| {
-                    Box::pin(
-                        async move { client.handle_transaction(transaction_ref.clone()).await },
-                    )
+                    Box::pin(async move {
+                        retry_overloaded(|| client.handle_transaction(transaction_ref.clone()))
+                            .await
+                    })
                 },
                 |mut state, name, weight, result| {
                     Box::pin(async move {
@@ -1475,7 +1620,7 @@ where
                         // - we try to re-process the certificate and return the result.
 
                         let res =
-                            client.handle_certificate(cert_ref.clone())
+                            retry_overloaded(|| client.handle_certificate(cert_ref.clone()))
                                 .instrument(tracing::trace_span!("handle_certificate", authority =? name))
                                 .await;
 
@@ -1694,6 +1839,34 @@ where
         receiver
     }
 
+    /// Fetches the object at `object_ref` from the first authority which has it, transferring
+    /// only the chunks of the object which are not in `base`, a previous version of it held
+    /// locally. The object is checked against `object_ref` once put back together.
+    pub async fn fetch_object_delta(
+        &self,
+        object_ref: ObjectRef,
+        base: &Object,
+    ) -> SuiResult<Object> {
+        let request = ObjectChunksRequest::new(object_ref, Some(base))?;
+        self.quorum_once_with_timeout(
+            None,
+            None,
+            |_, client| {
+                let request = request.clone();
+                Box::pin(async move {
+                    client
+                        .handle_object_chunks_request(request)
+                        .await?
+                        .into_object(object_ref, Some(base))
+                })
+            },
+            self.timeouts.authority_request_timeout,
+            None,
+            "fetch_object_delta",
+        )
+        .await
+    }
+
     /// This function fetches one object at a time, and sends back the result over the channel
     /// The object ids are also returned so the caller can determine which fetches failed
     /// NOTE: This function assumes all authorities are honest
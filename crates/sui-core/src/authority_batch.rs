@@ -20,7 +20,6 @@ use tokio::time::interval;
 
 use futures::stream::{self, Stream};
 use futures::StreamExt;
-use typed_store::Map;
 
 use tokio::sync::broadcast::{error::RecvError, Receiver};
 use tracing::{debug, error};
@@ -6,24 +6,28 @@ use crate::authority::AuthorityState;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use fastcrypto::traits::ToFromBytes;
-use futures::{stream::BoxStream, TryStreamExt};
-use multiaddr::Multiaddr;
+use futures::{future::join_all, stream::BoxStream, TryStreamExt};
+use multiaddr::{Multiaddr, Protocol};
 use mysten_network::config::Config;
+use parking_lot::Mutex;
 use prometheus::{register_histogram_with_registry, Histogram};
 use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sui_config::genesis::Genesis;
 use sui_network::{api::ValidatorClient, tonic};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::{CheckpointRequest, CheckpointResponse};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::{error::SuiError, messages::*};
+use tracing::{info, warn};
 
 #[cfg(test)]
 use sui_types::{committee::Committee, crypto::AuthorityKeyPair, object::Object};
 
 use crate::epoch::reconfiguration::Reconfigurable;
-use sui_network::tonic::transport::Channel;
 
 #[async_trait]
 pub trait AuthorityAPI {
@@ -39,6 +43,33 @@ pub trait AuthorityAPI {
         certificate: CertifiedTransaction,
     ) -> Result<TransactionInfoResponse, SuiError>;
 
+    /// Initiate several transactions at once, returning the response to each in their order.
+    /// Unless overridden, the transactions are sent one by one, concurrently.
+    async fn handle_transaction_batch(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<Result<TransactionInfoResponse, SuiError>>, SuiError> {
+        Ok(join_all(
+            transactions
+                .into_iter()
+                .map(|transaction| self.handle_transaction(transaction)),
+        )
+        .await)
+    }
+
+    /// Execute several certificates at once, returning the response to each in their order.
+    async fn handle_certificate_batch(
+        &self,
+        certificates: Vec<CertifiedTransaction>,
+    ) -> Result<Vec<Result<TransactionInfoResponse, SuiError>>, SuiError> {
+        Ok(join_all(
+            certificates
+                .into_iter()
+                .map(|certificate| self.handle_certificate(certificate)),
+        )
+        .await)
+    }
+
     /// Handle Account information requests for this account.
     async fn handle_account_info_request(
         &self,
@@ -51,6 +82,12 @@ pub trait AuthorityAPI {
         request: ObjectInfoRequest,
     ) -> Result<ObjectInfoResponse, SuiError>;
 
+    /// Handle requests for the chunks of an object which the requester doesn't hold.
+    async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError>;
+
     /// Handle Object information requests for this account.
     async fn handle_transaction_info_request(
         &self,
@@ -72,12 +109,26 @@ pub trait AuthorityAPI {
 
 pub type BatchInfoResponseItemStream = BoxStream<'static, Result<BatchInfoResponseItem, SuiError>>;
 
+/// How long the addresses a validator's DNS name resolved to are trusted before resolving it
+/// again. The system resolver doesn't tell the TTL of the records, so this bounds how long a
+/// validator which moved is still dialed at its former address, on top of the resolver's cache.
+const DNS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Failed requests resolve the DNS name of the validator again at most this often.
+const DNS_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Clone)]
 pub struct NetworkAuthorityClient {
-    client: ValidatorClient<tonic::transport::Channel>,
+    channel: ClientChannel,
     metrics: Arc<NetworkAuthorityClientMetrics>,
 }
 
+#[derive(Clone)]
+enum ClientChannel {
+    Static(ValidatorClient<tonic::transport::Channel>),
+    Resolved(Arc<ResolvedChannel>),
+}
+
 impl NetworkAuthorityClient {
     pub async fn connect(
         address: &Multiaddr,
@@ -98,18 +149,182 @@ impl NetworkAuthorityClient {
         Ok(Self::new(channel, metrics))
     }
 
+    /// Connects lazily to the validator at `address` with the settings of `network_config`. If
+    /// the address is a DNS name, it is resolved again periodically and whenever requests fail,
+    /// and the validator is reconnected to if it moved.
+    pub fn connect_lazy_with_config(
+        address: &Multiaddr,
+        network_config: Arc<Config>,
+        metrics: Arc<NetworkAuthorityClientMetrics>,
+    ) -> anyhow::Result<Self> {
+        let channel = network_config
+            .connect_lazy(address)
+            .map_err(|err| anyhow!(err.to_string()))?;
+        let channel = match dns_name(address) {
+            Some(name) => ClientChannel::Resolved(Arc::new(ResolvedChannel {
+                address: address.clone(),
+                name,
+                network_config,
+                refreshing: AtomicBool::new(false),
+                state: Mutex::new(ResolvedState {
+                    addresses: vec![],
+                    resolved_at: None,
+                    client: ValidatorClient::new(channel),
+                }),
+            })),
+            None => ClientChannel::Static(ValidatorClient::new(channel)),
+        };
+        Ok(Self { channel, metrics })
+    }
+
     pub fn new(
         channel: tonic::transport::Channel,
         metrics: Arc<NetworkAuthorityClientMetrics>,
     ) -> Self {
         Self {
-            client: ValidatorClient::new(channel),
+            channel: ClientChannel::Static(ValidatorClient::new(channel)),
             metrics,
         }
     }
 
     fn client(&self) -> ValidatorClient<tonic::transport::Channel> {
-        self.client.clone()
+        match &self.channel {
+            ClientChannel::Static(client) => client.clone(),
+            ClientChannel::Resolved(channel) => channel.client(),
+        }
+    }
+
+    /// The error of a failed request, after resolving the validator's address again if the
+    /// failure may come from the validator having moved.
+    fn request_failed(&self, status: tonic::Status) -> SuiError {
+        if let ClientChannel::Resolved(channel) = &self.channel {
+            // Depending on where the connection broke, tonic reports it with any of these
+            if matches!(
+                status.code(),
+                tonic::Code::Unavailable | tonic::Code::Unknown | tonic::Code::DeadlineExceeded
+            ) {
+                channel.request_failed();
+            }
+        }
+        status.into()
+    }
+}
+
+/// A DNS name and TCP port, and whether only IPv4 or only IPv6 addresses are wanted.
+struct DnsName {
+    host: String,
+    port: u16,
+    family: Option<bool>,
+}
+
+/// The DNS name of `address`, if it has one.
+fn dns_name(address: &Multiaddr) -> Option<DnsName> {
+    let mut protocols = address.iter();
+    let (host, family) = match protocols.next()? {
+        Protocol::Dns(host) => (host.to_string(), None),
+        Protocol::Dns4(host) => (host.to_string(), Some(true)),
+        Protocol::Dns6(host) => (host.to_string(), Some(false)),
+        _ => return None,
+    };
+    match protocols.next()? {
+        Protocol::Tcp(port) => Some(DnsName { host, port, family }),
+        _ => None,
+    }
+}
+
+/// The channel to a validator whose address is a DNS name. The channel resolves the name itself
+/// whenever it connects, but keeps its connection to the former address of a validator which
+/// moved for as long as that address still accepts it. Resolving the name here tells when the
+/// validator moved, and the channel is replaced to connect to its new address.
+struct ResolvedChannel {
+    address: Multiaddr,
+    name: DnsName,
+    network_config: Arc<Config>,
+    /// Set while a resolution is in progress.
+    refreshing: AtomicBool,
+    state: Mutex<ResolvedState>,
+}
+
+struct ResolvedState {
+    /// The addresses the name resolved to last, sorted.
+    addresses: Vec<SocketAddr>,
+    resolved_at: Option<Instant>,
+    client: ValidatorClient<tonic::transport::Channel>,
+}
+
+impl ResolvedChannel {
+    fn client(self: &Arc<Self>) -> ValidatorClient<tonic::transport::Channel> {
+        let state = self.state.lock();
+        let client = state.client.clone();
+        let stale = state
+            .resolved_at
+            .map_or(true, |at| at.elapsed() >= DNS_REFRESH_INTERVAL);
+        drop(state);
+        if stale {
+            self.refresh();
+        }
+        client
+    }
+
+    fn request_failed(self: &Arc<Self>) {
+        let recent = self
+            .state
+            .lock()
+            .resolved_at
+            .map_or(false, |at| at.elapsed() < DNS_MIN_REFRESH_INTERVAL);
+        if !recent {
+            self.refresh();
+        }
+    }
+
+    /// Resolves the name in the background, unless that's already underway.
+    fn refresh(self: &Arc<Self>) {
+        if self.refreshing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let this = self.clone();
+        tokio::spawn(async move {
+            this.resolve().await;
+            this.refreshing.store(false, Ordering::Release);
+        });
+    }
+
+    async fn resolve(&self) {
+        let result = tokio::net::lookup_host((self.name.host.as_str(), self.name.port)).await;
+        let mut state = self.state.lock();
+        state.resolved_at = Some(Instant::now());
+        let mut addresses: Vec<SocketAddr> = match result {
+            Ok(addresses) => addresses
+                .filter(|address| self.name.family.map_or(true, |v4| address.is_ipv4() == v4))
+                .collect(),
+            Err(err) => {
+                warn!(address =% self.address, "Failed to resolve validator address: {}", err);
+                return;
+            }
+        };
+        addresses.sort();
+        addresses.dedup();
+        if addresses.is_empty() || addresses == state.addresses {
+            return;
+        }
+        // The first resolution only tells where the validator is
+        if !state.addresses.is_empty() {
+            info!(
+                address =% self.address,
+                previous = ?state.addresses,
+                current = ?addresses,
+                "Validator moved, reconnecting"
+            );
+            match self.network_config.connect_lazy(&self.address) {
+                Ok(channel) => state.client = ValidatorClient::new(channel),
+                Err(err) => {
+                    // Keep the former addresses, to try again on the next resolution
+                    warn!(address =% self.address, "Failed to reconnect to validator: {}", err);
+                    return;
+                }
+            }
+        }
+        state.addresses = addresses;
     }
 }
 
@@ -120,10 +335,11 @@ impl Reconfigurable for NetworkAuthorityClient {
     }
 
     fn recreate(
-        channel: tonic::transport::Channel,
+        address: &Multiaddr,
+        network_config: Arc<Config>,
         metrics: Arc<NetworkAuthorityClientMetrics>,
-    ) -> Self {
-        NetworkAuthorityClient::new(channel, metrics)
+    ) -> anyhow::Result<Self> {
+        NetworkAuthorityClient::connect_lazy_with_config(address, network_config, metrics)
     }
 }
 
@@ -143,7 +359,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .transaction(transaction)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
     }
 
     /// Execute a certificate.
@@ -160,7 +376,39 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .handle_certificate(certificate)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
+    }
+
+    async fn handle_transaction_batch(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<Result<TransactionInfoResponse, SuiError>>, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_transaction_batch_request_latency
+            .start_timer();
+
+        self.client()
+            .transaction_batch(TransactionBatchRequest { transactions })
+            .await
+            .map(|response| response.into_inner().responses)
+            .map_err(|status| self.request_failed(status))
+    }
+
+    async fn handle_certificate_batch(
+        &self,
+        certificates: Vec<CertifiedTransaction>,
+    ) -> Result<Vec<Result<TransactionInfoResponse, SuiError>>, SuiError> {
+        let _timer = self
+            .metrics
+            .handle_certificate_batch_request_latency
+            .start_timer();
+
+        self.client()
+            .handle_certificate_batch(CertificateBatchRequest { certificates })
+            .await
+            .map(|response| response.into_inner().responses)
+            .map_err(|status| self.request_failed(status))
     }
 
     async fn handle_account_info_request(
@@ -176,7 +424,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .account_info(request)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
     }
 
     async fn handle_object_info_request(
@@ -192,7 +440,18 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .object_info(request)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
+    }
+
+    async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        self.client()
+            .object_chunks(request)
+            .await
+            .map(tonic::Response::into_inner)
+            .map_err(|status| self.request_failed(status))
     }
 
     /// Handle Object information requests for this account.
@@ -209,7 +468,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .transaction_info(request)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
     }
 
     /// Handle Batch information requests for this authority.
@@ -221,7 +480,8 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .client()
             .batch_info(request)
             .await
-            .map(tonic::Response::into_inner)?
+            .map(tonic::Response::into_inner)
+            .map_err(|status| self.request_failed(status))?
             .map_err(Into::into);
 
         Ok(Box::pin(stream))
@@ -238,7 +498,7 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .checkpoint(request)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
     }
 
     async fn handle_epoch(&self, request: EpochRequest) -> Result<EpochResponse, SuiError> {
@@ -246,22 +506,23 @@ impl AuthorityAPI for NetworkAuthorityClient {
             .epoch_info(request)
             .await
             .map(tonic::Response::into_inner)
-            .map_err(Into::into)
+            .map_err(|status| self.request_failed(status))
     }
 }
 
 pub fn make_network_authority_client_sets_from_system_state(
     sui_system_state: &SuiSystemState,
-    network_config: &Config,
+    network_config: Arc<Config>,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
     for validator in &sui_system_state.validators.active_validators {
         let address = Multiaddr::try_from(validator.metadata.net_address.clone())?;
-        let channel = network_config
-            .connect_lazy(&address)
-            .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let client = NetworkAuthorityClient::connect_lazy_with_config(
+            &address,
+            network_config.clone(),
+            network_metrics.clone(),
+        )?;
         let name: &[u8] = &validator.metadata.name;
         let public_key_bytes = AuthorityPublicKeyBytes::from_bytes(name)?;
         authority_clients.insert(public_key_bytes, client);
@@ -271,15 +532,16 @@ pub fn make_network_authority_client_sets_from_system_state(
 
 pub fn make_network_authority_client_sets_from_genesis(
     genesis: &Genesis,
-    network_config: &Config,
+    network_config: Arc<Config>,
     network_metrics: Arc<NetworkAuthorityClientMetrics>,
 ) -> anyhow::Result<BTreeMap<AuthorityPublicKeyBytes, NetworkAuthorityClient>> {
     let mut authority_clients = BTreeMap::new();
     for validator in genesis.validator_set() {
-        let channel = network_config
-            .connect_lazy(validator.network_address())
-            .map_err(|err| anyhow!(err.to_string()))?;
-        let client = NetworkAuthorityClient::new(channel, network_metrics.clone());
+        let client = NetworkAuthorityClient::connect_lazy_with_config(
+            validator.network_address(),
+            network_config.clone(),
+            network_metrics.clone(),
+        )?;
         authority_clients.insert(validator.protocol_key(), client);
     }
     Ok(authority_clients)
@@ -310,7 +572,11 @@ impl Reconfigurable for LocalAuthorityClient {
         false
     }
 
-    fn recreate(_channel: Channel, _metrics: Arc<NetworkAuthorityClientMetrics>) -> Self {
+    fn recreate(
+        _address: &Multiaddr,
+        _network_config: Arc<Config>,
+        _metrics: Arc<NetworkAuthorityClientMetrics>,
+    ) -> anyhow::Result<Self> {
         unreachable!(); // this function should not get called because the above function returns false
     }
 }
@@ -362,6 +628,14 @@ impl AuthorityAPI for LocalAuthorityClient {
         state.handle_object_info_request(request).await
     }
 
+    async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        let state = self.state.clone();
+        state.handle_object_chunks_request(request).await
+    }
+
     /// Handle Object information requests for this account.
     async fn handle_transaction_info_request(
         &self,
@@ -464,6 +738,8 @@ impl LocalAuthorityClient {
 pub struct NetworkAuthorityClientMetrics {
     pub handle_transaction_request_latency: Histogram,
     pub handle_certificate_request_latency: Histogram,
+    pub handle_transaction_batch_request_latency: Histogram,
+    pub handle_certificate_batch_request_latency: Histogram,
     pub handle_account_info_request_latency: Histogram,
     pub handle_object_info_request_latency: Histogram,
     pub handle_transaction_info_request_latency: Histogram,
@@ -491,6 +767,20 @@ impl NetworkAuthorityClientMetrics {
                 registry
             )
             .unwrap(),
+            handle_transaction_batch_request_latency: register_histogram_with_registry!(
+                "handle_transaction_batch_request_latency",
+                "Latency of handle transaction batch request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
+            handle_certificate_batch_request_latency: register_histogram_with_registry!(
+                "handle_certificate_batch_request_latency",
+                "Latency of handle certificate batch request",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry
+            )
+            .unwrap(),
             handle_account_info_request_latency: register_histogram_with_registry!(
                 "handle_account_info_request_latency",
                 "Latency of handle account info request",
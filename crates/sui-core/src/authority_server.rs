@@ -3,10 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    admission_control::AdmissionController,
     authority::{AuthorityState, ReconfigConsensusMessage},
     consensus_adapter::{
         CheckpointConsensusAdapter, CheckpointSender, ConsensusAdapter, ConsensusAdapterMetrics,
-        ConsensusListener, ConsensusListenerMessage,
+        ConsensusListener, ConsensusListenerMessage, ConsensusRelay,
     },
     metrics::start_timer,
 };
@@ -14,10 +15,14 @@ use anyhow::anyhow;
 use anyhow::Result;
 use async_trait::async_trait;
 use fastcrypto::traits::KeyPair;
-use futures::{stream::BoxStream, TryStreamExt};
+use futures::{future::join_all, stream::BoxStream, TryStreamExt};
 use multiaddr::Multiaddr;
 use prometheus::{register_histogram_with_registry, Histogram, Registry};
-use std::{io, sync::Arc, time::Duration};
+use std::{
+    io,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use sui_config::NodeConfig;
 use sui_network::{
     api::{Validator, ValidatorServer},
@@ -143,6 +148,7 @@ impl AuthorityServer {
                 consensus_adapter: Arc::new(self.consensus_adapter),
                 _checkpoint_consensus_handle: None,
                 metrics: Arc::new(ValidatorServiceMetrics::new_for_tests()),
+                admission: Arc::new(AdmissionController::new(None, &Registry::new())),
             }))
             .bind(&address)
             .await
@@ -238,6 +244,7 @@ pub struct ValidatorService {
     consensus_adapter: Arc<ConsensusAdapter>,
     _checkpoint_consensus_handle: Option<JoinHandle<()>>,
     metrics: Arc<ValidatorServiceMetrics>,
+    admission: Arc<AdmissionController>,
 }
 
 impl ValidatorService {
@@ -295,13 +302,26 @@ impl ValidatorService {
 
         let delay_step = consensus_config.delay_step.unwrap_or(15_000);
         // The consensus adapter allows the authority to send user certificates through consensus.
-        let consensus_adapter = ConsensusAdapter::new(
+        let mut consensus_adapter = ConsensusAdapter::new(
             consensus_config.address().to_owned(),
             state.clone_committee(),
             tx_sui_to_consensus.clone(),
             Duration::from_millis(delay_step),
             ca_metrics.clone(),
         );
+        if consensus_config.relay_to_leader() {
+            let consensus_addresses = config.genesis()?.validator_set().iter().map(|validator| {
+                (
+                    validator.protocol_key(),
+                    validator.narwhal_consensus_address.clone(),
+                )
+            });
+            consensus_adapter = consensus_adapter.with_relay(ConsensusRelay::new(
+                state.name,
+                consensus_addresses,
+                state.last_consensus_round.clone(),
+            ));
+        }
 
         // Update the checkpoint store with a consensus client.
         let checkpoint_consensus_handle = if let Some(checkpoint_store) = state.checkpoints() {
@@ -331,6 +351,10 @@ impl ValidatorService {
             consensus_adapter: Arc::new(consensus_adapter),
             _checkpoint_consensus_handle: checkpoint_consensus_handle,
             metrics: Arc::new(ValidatorServiceMetrics::new(&prometheus_registry)),
+            admission: Arc::new(AdmissionController::new(
+                config.admission_control,
+                &prometheus_registry,
+            )),
         })
     }
 
@@ -338,6 +362,7 @@ impl ValidatorService {
         state: Arc<AuthorityState>,
         request: tonic::Request<Transaction>,
         metrics: Arc<ValidatorServiceMetrics>,
+        admission: Arc<AdmissionController>,
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
         let mut transaction = request.into_inner();
         let is_consensus_tx = transaction.contains_shared_object();
@@ -356,6 +381,12 @@ impl ValidatorService {
         //TODO This is really really bad, we should have different types for signature-verified transactions
         transaction.is_verified = true;
 
+        // Admitted once the signature is verified, so that nobody can use up the quota of
+        // another sender
+        let _permit = admission
+            .admit_transaction(transaction.sender_address())
+            .map_err(Self::overloaded)?;
+
         let tx_digest = transaction.digest();
 
         // Enable Trace Propagation across spans/processes using tx_digest
@@ -379,6 +410,7 @@ impl ValidatorService {
         consensus_adapter: Arc<ConsensusAdapter>,
         request: tonic::Request<CertifiedTransaction>,
         metrics: Arc<ValidatorServiceMetrics>,
+        admission: Arc<AdmissionController>,
     ) -> Result<tonic::Response<TransactionInfoResponse>, tonic::Status> {
         let mut certificate = request.into_inner();
         let is_consensus_tx = certificate.contains_shared_object();
@@ -398,6 +430,10 @@ impl ValidatorService {
         //TODO This is really really bad, we should have different types for signature verified transactions
         certificate.is_verified = true;
 
+        let _permit = admission
+            .admit_certificate(certificate.sender_address())
+            .map_err(Self::overloaded)?;
+
         // 2) Check idempotency
         let tx_digest = certificate.digest();
         if let Some(response) = state
@@ -417,10 +453,17 @@ impl ValidatorService {
                 .map_err(|e| tonic::Status::internal(e.to_string()))?
         {
             let _metrics_guard = start_timer(metrics.consensus_latency.clone());
+            let _slot = admission.enter_consensus_queue();
+            let sequencing_start = Instant::now();
             consensus_adapter
                 .submit(&state.name, &certificate)
                 .await
                 .map_err(|e| tonic::Status::internal(e.to_string()))?;
+            state.hot_objects.record(
+                state.epoch(),
+                certificate.signed_data.data.shared_input_objects(),
+                Some(sequencing_start.elapsed()),
+            );
         }
 
         // 4) Execute the certificate.
@@ -438,6 +481,24 @@ impl ValidatorService {
 
         Ok(tonic::Response::new(response))
     }
+
+    /// Sheds a request the validator has no capacity for with `RESOURCE_EXHAUSTED`, which clients
+    /// read back as a retryable [`SuiError::ValidatorOverloaded`].
+    fn overloaded(err: SuiError) -> tonic::Status {
+        match err {
+            SuiError::ValidatorOverloaded { error } => tonic::Status::resource_exhausted(error),
+            err => tonic::Status::internal(err.to_string()),
+        }
+    }
+
+    fn check_batch_size(len: usize) -> Result<(), tonic::Status> {
+        if len > MAX_TRANSACTION_BATCH_SIZE {
+            return Err(tonic::Status::invalid_argument(format!(
+                "Batch of {len} transactions exceeds the maximum of {MAX_TRANSACTION_BATCH_SIZE}"
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -451,9 +512,12 @@ impl Validator for ValidatorService {
         // Spawns a task which handles the transaction. The task will unconditionally continue
         // processing in the event that the client connection is dropped.
         let metrics = self.metrics.clone();
-        tokio::spawn(async move { Self::handle_transaction(state, request, metrics).await })
-            .await
-            .unwrap()
+        let admission = self.admission.clone();
+        tokio::spawn(
+            async move { Self::handle_transaction(state, request, metrics, admission).await },
+        )
+        .await
+        .unwrap()
     }
 
     async fn handle_certificate(
@@ -466,13 +530,88 @@ impl Validator for ValidatorService {
         // Spawns a task which handles the certificate. The task will unconditionally continue
         // processing in the event that the client connection is dropped.
         let metrics = self.metrics.clone();
+        let admission = self.admission.clone();
         tokio::spawn(async move {
-            Self::handle_certificate(state, consensus_adapter, request, metrics).await
+            Self::handle_certificate(state, consensus_adapter, request, metrics, admission).await
         })
         .await
         .unwrap()
     }
 
+    async fn transaction_batch(
+        &self,
+        request: tonic::Request<TransactionBatchRequest>,
+    ) -> Result<tonic::Response<TransactionBatchResponse>, tonic::Status> {
+        let transactions = request.into_inner().transactions;
+        Self::check_batch_size(transactions.len())?;
+
+        // Each transaction is handled as if it came in its own request, concurrently with the
+        // others, and fails on its own
+        let handles: Vec<_> = transactions
+            .into_iter()
+            .map(|transaction| {
+                let state = self.state.clone();
+                let metrics = self.metrics.clone();
+                let admission = self.admission.clone();
+                tokio::spawn(async move {
+                    Self::handle_transaction(
+                        state,
+                        tonic::Request::new(transaction),
+                        metrics,
+                        admission,
+                    )
+                    .await
+                    .map(tonic::Response::into_inner)
+                    .map_err(SuiError::from)
+                })
+            })
+            .collect();
+        let responses = join_all(handles)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        Ok(tonic::Response::new(TransactionBatchResponse { responses }))
+    }
+
+    async fn handle_certificate_batch(
+        &self,
+        request: tonic::Request<CertificateBatchRequest>,
+    ) -> Result<tonic::Response<TransactionBatchResponse>, tonic::Status> {
+        let certificates = request.into_inner().certificates;
+        Self::check_batch_size(certificates.len())?;
+
+        let handles: Vec<_> = certificates
+            .into_iter()
+            .map(|certificate| {
+                let state = self.state.clone();
+                let consensus_adapter = self.consensus_adapter.clone();
+                let metrics = self.metrics.clone();
+                let admission = self.admission.clone();
+                tokio::spawn(async move {
+                    Self::handle_certificate(
+                        state,
+                        consensus_adapter,
+                        tonic::Request::new(certificate),
+                        metrics,
+                        admission,
+                    )
+                    .await
+                    .map(tonic::Response::into_inner)
+                    .map_err(SuiError::from)
+                })
+            })
+            .collect();
+        let responses = join_all(handles)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        Ok(tonic::Response::new(TransactionBatchResponse { responses }))
+    }
+
     async fn account_info(
         &self,
         request: tonic::Request<AccountInfoRequest>,
@@ -503,6 +642,21 @@ impl Validator for ValidatorService {
         Ok(tonic::Response::new(response))
     }
 
+    async fn object_chunks(
+        &self,
+        request: tonic::Request<ObjectChunksRequest>,
+    ) -> Result<tonic::Response<ObjectChunksResponse>, tonic::Status> {
+        let request = request.into_inner();
+
+        let response = self
+            .state
+            .handle_object_chunks_request(request)
+            .await
+            .map_err(|e| tonic::Status::internal(e.to_string()))?;
+
+        Ok(tonic::Response::new(response))
+    }
+
     async fn transaction_info(
         &self,
         request: tonic::Request<TransactionInfoRequest>,
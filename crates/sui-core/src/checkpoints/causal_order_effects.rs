@@ -32,6 +32,15 @@ pub trait CausalOrder {
         transactions: impl Iterator<Item = &'a ExecutionDigests>,
         ckpt_store: &mut CheckpointStore,
     ) -> SuiResult<Vec<ExecutionDigests>>;
+
+    /// The size in bytes of the effects of each of `transactions`, which bounds the size of a
+    /// checkpoint. Orders without the effects at hand count no bytes.
+    fn get_effects_sizes<'a>(
+        &self,
+        transactions: impl Iterator<Item = &'a ExecutionDigests>,
+    ) -> SuiResult<Vec<usize>> {
+        Ok(transactions.map(|_| 0).collect())
+    }
 }
 
 pub trait EffectsStore {
@@ -214,6 +223,23 @@ impl CausalOrder for Arc<AuthorityStore> {
     ) -> SuiResult<Vec<ExecutionDigests>> {
         self.causal_order_from_effects(transactions, _ckpt_store)
     }
+
+    fn get_effects_sizes<'a>(
+        &self,
+        transactions: impl Iterator<Item = &'a ExecutionDigests>,
+    ) -> SuiResult<Vec<usize>> {
+        self.get_effects(transactions)?
+            .iter()
+            .map(|effects| match effects {
+                Some(effects) => bcs::serialized_size(effects).map_err(|e| {
+                    SuiError::from(format!("Cannot serialize effects: {}", e).as_str())
+                }),
+                None => Err(SuiError::from(
+                    "Cannot size checkpoint with unexecuted transactions.",
+                )),
+            })
+            .collect()
+    }
 }
 
 impl EffectsStore for BTreeMap<TransactionDigest, TransactionEffects> {
@@ -259,6 +285,8 @@ mod tests {
                 computation_cost: 0,
                 storage_cost: 0,
                 storage_rebate: 0,
+                event_storage_cost: 0,
+                effects_storage_cost: 0,
             },
             shared_objects: vec![],
             created: vec![],
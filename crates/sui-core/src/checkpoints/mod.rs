@@ -13,6 +13,7 @@ use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::{path::Path, sync::Arc};
+use sui_config::node::CheckpointLimitsConfig;
 use sui_storage::default_db_options;
 use sui_types::messages_checkpoint::{CheckpointProposal, CheckpointProposalContents};
 use sui_types::{
@@ -31,7 +32,7 @@ use tracing::{debug, error, info};
 use typed_store::traits::TypedStoreDebug;
 
 use typed_store::{
-    rocks::{DBBatch, DBMap},
+    rocks::{DBBatch, DBMap, TypedStoreError},
     Map,
 };
 use typed_store_derive::DBMapUtils;
@@ -160,6 +161,13 @@ pub struct CheckpointStore {
     // Consensus sender
     sender: Option<Box<dyn ConsensusSender>>,
 
+    /// Bounds on the size of the checkpoints this authority signs.
+    limits: CheckpointLimitsConfig,
+
+    /// The highest checkpoint certified by the network this authority learnt of since it started,
+    /// which it may not have synced yet.
+    highest_network_checkpoint: Option<CheckpointSequenceNumber>,
+
     /// DBMap tables
     pub tables: CheckpointStoreTables,
 }
@@ -251,6 +259,11 @@ impl CheckpointStore {
         Ok(())
     }
 
+    /// Set the bounds on the size of the checkpoints signed from now on
+    pub fn set_limits(&mut self, limits: CheckpointLimitsConfig) {
+        self.limits = limits;
+    }
+
     /// Open a checkpoint store to use to generate checkpoints, incl the information
     /// needed to sign new checkpoints.
     pub fn open(
@@ -265,6 +278,8 @@ impl CheckpointStore {
             secret,
             memory_locals: None,
             sender: None,
+            limits: CheckpointLimitsConfig::default(),
+            highest_network_checkpoint: None,
             tables: CheckpointStoreTables::open_tables_read_write(
                 path.to_path_buf(),
                 db_options,
@@ -356,11 +371,10 @@ impl CheckpointStore {
         let previous_digest = self.get_prev_checkpoint_digest(sequence_number)?;
 
         // Create a causal order of all transactions in the checkpoint.
-        let ordered_contents = CheckpointContents::new_with_causally_ordered_transactions(
-            effects_store
-                .get_complete_causal_order(transactions, self)?
-                .into_iter(),
-        );
+        let ordered = effects_store.get_complete_causal_order(transactions, self)?;
+        let ordered = self.limit_checkpoint_size(sequence_number, ordered, &effects_store)?;
+        let ordered_contents =
+            CheckpointContents::new_with_causally_ordered_transactions(ordered.into_iter());
 
         let summary =
             CheckpointSummary::new(epoch, sequence_number, &ordered_contents, previous_digest);
@@ -368,9 +382,46 @@ impl CheckpointStore {
         let checkpoint = AuthenticatedCheckpoint::Signed(
             SignedCheckpointSummary::new_from_summary(summary, self.name, &*self.secret),
         );
+        // Signed, but neither stored nor sent to the other validators
+        sui_failpoints::fail_point!("checkpoint-after-sign");
         self.handle_internal_set_checkpoint(&checkpoint, &ordered_contents)
     }
 
+    /// Keeps the longest prefix of the causally ordered `transactions` within the checkpoint
+    /// limits, and at least one transaction so that checkpoints make progress. The prefix is
+    /// causally complete too, and the transactions left out stay in `extra_transactions`, to be
+    /// proposed for the next checkpoints.
+    fn limit_checkpoint_size(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+        mut transactions: Vec<ExecutionDigests>,
+        effects_store: &impl CausalOrder,
+    ) -> SuiResult<Vec<ExecutionDigests>> {
+        let sizes = effects_store.get_effects_sizes(transactions.iter())?;
+        let mut len = 0;
+        let mut bytes = 0;
+        for size in sizes {
+            bytes += size as u64;
+            if len > 0
+                && (len as u64 >= self.limits.max_transactions
+                    || bytes > self.limits.max_effects_bytes)
+            {
+                break;
+            }
+            len += 1;
+        }
+        if len < transactions.len() {
+            info!(
+                cp_seq=?sequence_number,
+                included=len,
+                deferred=transactions.len() - len,
+                "Checkpoint over its size limits, deferring transactions to the next checkpoints"
+            );
+            transactions.truncate(len);
+        }
+        Ok(transactions)
+    }
+
     /// Call this function internally to update the latest checkpoint.
     /// Internally it is called with an unsigned checkpoint, and results
     /// in the checkpoint being signed, stored and the contents
@@ -758,6 +809,22 @@ impl CheckpointStore {
         Ok(())
     }
 
+    /// Stores the checkpoint of a state snapshot installed in an empty store as the latest one,
+    /// with none before it, so that checkpoints are synced from the next one.
+    pub fn install_snapshot_checkpoint(
+        &mut self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+    ) -> SuiResult {
+        let seq = *checkpoint.summary.sequence_number();
+        self.clear_proposal(seq)?;
+        self.handle_internal_set_checkpoint(
+            &AuthenticatedCheckpoint::Certified(checkpoint.clone()),
+            contents,
+        )?;
+        self.clear_proposal(seq + 1)
+    }
+
     fn clear_proposal(
         &mut self,
         new_expected_next_checkpoint: CheckpointSequenceNumber,
@@ -784,6 +851,28 @@ impl CheckpointStore {
         self.get_locals().next_transaction_sequence
     }
 
+    /// Records that the network certified checkpoint `seq`, e.g. when syncing to it.
+    pub fn observe_network_checkpoint(&mut self, seq: CheckpointSequenceNumber) {
+        self.highest_network_checkpoint = self.highest_network_checkpoint.max(Some(seq));
+    }
+
+    /// The highest checkpoint known to be certified, by the network or in this store.
+    pub fn highest_known_checkpoint(&mut self) -> Option<CheckpointSequenceNumber> {
+        let stored = self
+            .latest_stored_checkpoint()
+            .map(|checkpoint| checkpoint.summary().sequence_number);
+        self.highest_network_checkpoint.max(stored)
+    }
+
+    /// Syncs the writes to the tables of the store to disk, e.g. before shutting down.
+    pub fn flush(&self) -> SuiResult {
+        self.tables
+            .checkpoints
+            .rocksdb
+            .flush_wal(true)
+            .map_err(|e| TypedStoreError::RocksDBError(e.to_string()).into())
+    }
+
     /// Get the latest stored checkpoint if there is one
     pub fn latest_stored_checkpoint(&mut self) -> Option<AuthenticatedCheckpoint> {
         self.tables
@@ -595,6 +595,41 @@ fn update_processed_transactions_already_in_checkpoint() {
     );
 }
 
+#[test]
+fn sign_checkpoint_over_limits() {
+    let (committee, _keys, mut stores) = random_ckpoint_store_num(1);
+    let (_, mut cps) = stores.pop().unwrap();
+    cps.set_limits(CheckpointLimitsConfig {
+        max_transactions: 2,
+        ..Default::default()
+    });
+
+    let transactions: Vec<_> = (0..5).map(|_| ExecutionDigests::random()).collect();
+    let batch: Vec<_> = transactions
+        .iter()
+        .enumerate()
+        .map(|(u, c)| (u as u64, *c))
+        .collect();
+    cps.handle_internal_batch(0, &batch).unwrap();
+
+    cps.sign_new_checkpoint(
+        committee.epoch,
+        0,
+        transactions.iter(),
+        TestCausalOrderPendCertNoop,
+    )
+    .unwrap();
+
+    // Only the first transactions in causal order are checkpointed, the others wait for the next
+    // checkpoints
+    let contents = cps.tables.checkpoint_contents.get(&0).unwrap().unwrap();
+    assert_eq!(
+        contents.iter().cloned().collect::<Vec<_>>(),
+        transactions[..2].to_vec()
+    );
+    assert_eq!(cps.tables.extra_transactions.iter().count(), 3);
+}
+
 #[test]
 fn set_get_checkpoint() {
     let (committee, _keys, mut stores) = random_ckpoint_store();
@@ -0,0 +1,33 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+
+/// The coins of `coins`, given with their balances, paying `amount`: the largest ones first, so
+/// that as few coins as possible are spent. The last coin selected is the smallest one, and only
+/// part of its balance may be needed.
+pub fn select_coins<T>(
+    mut coins: Vec<(T, u64)>,
+    amount: u64,
+) -> Result<Vec<(T, u64)>, anyhow::Error> {
+    coins.sort_by(|(_, a), (_, b)| b.cmp(a));
+    let mut selected = Vec::new();
+    let mut total: u64 = 0;
+    for (coin, balance) in coins {
+        if total >= amount {
+            break;
+        }
+        total = total.saturating_add(balance);
+        selected.push((coin, balance));
+    }
+    if total < amount {
+        return Err(anyhow!(
+            "Insufficient balance: the coins hold {total} in total, {amount} are needed"
+        ));
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+#[path = "unit_tests/coin_selection_tests.rs"]
+mod coin_selection_tests;
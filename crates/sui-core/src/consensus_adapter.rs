@@ -24,6 +24,7 @@ use std::{
     collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
 };
+use sui_network::tonic::transport::Channel;
 use sui_types::messages_checkpoint::CheckpointFragment;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::{
@@ -68,6 +69,7 @@ type TxSequencedNotifierClose = oneshot::Sender<()>;
 pub struct ConsensusAdapterMetrics {
     // Certificate sequencing metrics
     pub sequencing_certificate_attempt: IntCounter,
+    pub sequencing_certificate_relayed: IntCounter,
     pub sequencing_certificate_success: IntCounter,
     pub sequencing_certificate_timeouts: IntCounter,
     pub sequencing_certificate_control_delay: IntGauge,
@@ -96,6 +98,12 @@ impl ConsensusAdapterMetrics {
                 registry,
             )
             .unwrap(),
+            sequencing_certificate_relayed: register_int_counter_with_registry!(
+                "sequencing_certificate_relayed",
+                "Counts the number of certificates submitted to the consensus worker of the next leader.",
+                registry,
+            )
+            .unwrap(),
             sequencing_certificate_success: register_int_counter_with_registry!(
                 "sequencing_certificate_success",
                 "Counts the number of successfully sequenced certificates.",
@@ -206,10 +214,64 @@ type ConsensusOutput = (
     /* transaction */ SerializedConsensusTransaction,
 );
 
+/// Submits certificates to the consensus worker of the validator leading the next consensus round
+/// rather than to the worker of this validator. The batches of the leader are committed with its
+/// certificate, so certificates are sequenced without waiting for a proposal of this validator to
+/// be committed, and clients reaching a single validator see them sequenced as fast as those
+/// reaching all of them.
+pub struct ConsensusRelay {
+    name: AuthorityName,
+    /// The client of the consensus worker of each validator, ordered by name, as consensus elects
+    /// leaders round-robin over the validators in that order.
+    clients: Vec<(AuthorityName, TransactionsClient<Channel>)>,
+    /// The round of the last consensus certificate this validator handled.
+    last_consensus_round: Arc<AtomicU64>,
+}
+
+impl ConsensusRelay {
+    /// A relay for validator `name`, `consensus_addresses` being where the consensus workers of
+    /// the validators take transactions.
+    pub fn new(
+        name: AuthorityName,
+        consensus_addresses: impl IntoIterator<Item = (AuthorityName, Multiaddr)>,
+        last_consensus_round: Arc<AtomicU64>,
+    ) -> Self {
+        let mut clients: Vec<_> = consensus_addresses
+            .into_iter()
+            .map(|(validator, address)| {
+                let client = TransactionsClient::new(
+                    mysten_network::client::connect_lazy(&address)
+                        .expect("Failed to connect to consensus"),
+                );
+                (validator, client)
+            })
+            .collect();
+        clients.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self {
+            name,
+            clients,
+            last_consensus_round,
+        }
+    }
+
+    /// The validator leading the next round electing a leader, i.e. the next even round, with the
+    /// client of its worker, unless it is this validator.
+    fn next_leader(&self) -> Option<&(AuthorityName, TransactionsClient<Channel>)> {
+        if self.clients.is_empty() {
+            return None;
+        }
+        let round = (self.last_consensus_round.load(Ordering::Relaxed) + 2) & !1;
+        let leader = &self.clients[(round % self.clients.len() as u64) as usize];
+        (leader.0 != self.name).then(|| leader)
+    }
+}
+
 /// Submit Sui certificates to the consensus.
 pub struct ConsensusAdapter {
     /// The network client connecting to the consensus node of this authority.
-    consensus_client: TransactionsClient<sui_network::tonic::transport::Channel>,
+    consensus_client: TransactionsClient<Channel>,
+    /// Relays certificates to the worker of the next leader, if set.
+    relay: Option<ConsensusRelay>,
     /// The Sui committee information.
     committee: Committee,
     /// A channel to notify the consensus listener to take action for a transactions.
@@ -242,6 +304,7 @@ impl ConsensusAdapter {
         );
         Self {
             consensus_client,
+            relay: None,
             committee,
             tx_consensus_listener,
             delay_step,
@@ -250,6 +313,12 @@ impl ConsensusAdapter {
         }
     }
 
+    /// Submits certificates through `relay` rather than to the worker of this authority only.
+    pub fn with_relay(mut self, relay: ConsensusRelay) -> Self {
+        self.relay = Some(relay);
+        self
+    }
+
     /// Check if this authority should submit the transaction to consensus.
     fn should_submit(_certificate: &CertifiedTransaction) -> bool {
         // TODO [issue #1647]: Right now every authority submits the transaction to consensus.
@@ -295,14 +364,7 @@ impl ConsensusAdapter {
         let now = Instant::now();
         let should_submit = Self::should_submit(certificate);
         if should_submit {
-            self.consensus_client
-                .clone()
-                .submit_transaction(TransactionProto { transaction: bytes })
-                .await
-                .map_err(|e| SuiError::ConsensusConnectionBroken(format!("{:?}", e)))
-                .tap_err(|r| {
-                    error!("Submit transaction failed with: {:?}", r);
-                })?;
+            self.submit_transaction(bytes).await?;
 
             // Increment the attempted certificate sequencing
             self.opt_metrics.as_ref().map(|metrics| {
@@ -368,6 +430,42 @@ impl ConsensusAdapter {
 
         result
     }
+
+    /// Submits `bytes` to the worker of the next leader when relaying, or to the worker of this
+    /// authority, also if relaying fails.
+    #[allow(clippy::option_map_unit_fn)]
+    async fn submit_transaction(&self, bytes: Bytes) -> SuiResult {
+        if let Some((leader, client)) = self.relay.as_ref().and_then(ConsensusRelay::next_leader) {
+            match client
+                .clone()
+                .submit_transaction(TransactionProto {
+                    transaction: bytes.clone(),
+                })
+                .await
+            {
+                Ok(_) => {
+                    self.opt_metrics.as_ref().map(|metrics| {
+                        metrics.sequencing_certificate_relayed.inc();
+                    });
+                    return Ok(());
+                }
+                Err(e) => debug!(
+                    ?leader,
+                    "Failed to relay certificate to the consensus leader, submitting it to our worker: {:?}",
+                    e
+                ),
+            }
+        }
+        self.consensus_client
+            .clone()
+            .submit_transaction(TransactionProto { transaction: bytes })
+            .await
+            .map_err(|e| SuiError::ConsensusConnectionBroken(format!("{:?}", e)))
+            .tap_err(|r| {
+                error!("Submit transaction failed with: {:?}", r);
+            })?;
+        Ok(())
+    }
 }
 
 /// This module interfaces the consensus with Sui. It receives certificates input to consensus and
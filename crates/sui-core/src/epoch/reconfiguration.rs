@@ -10,7 +10,6 @@ use multiaddr::Multiaddr;
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 use std::time::Duration;
-use sui_network::tonic;
 use sui_types::base_types::AuthorityName;
 use sui_types::committee::{Committee, EpochId, StakeUnit};
 use sui_types::crypto::{AuthorityPublicKeyBytes, AuthoritySignature};
@@ -28,9 +27,12 @@ pub trait Reconfigurable {
     fn needs_network_recreation() -> bool;
 
     fn recreate(
-        channel: tonic::transport::Channel,
+        address: &Multiaddr,
+        network_config: Arc<mysten_network::config::Config>,
         metrics: Arc<NetworkAuthorityClientMetrics>,
-    ) -> Self;
+    ) -> anyhow::Result<Self>
+    where
+        Self: Sized;
 }
 
 // TODO: Move these constants to a control config.
@@ -142,14 +144,17 @@ where
             self.net.load().clone_inner_clients()
         };
         // Replace the clients in the authority aggregator with new clients.
-        let new_net = Arc::new(AuthorityAggregator::new(
+        let mut new_net = AuthorityAggregator::new(
             new_committee,
             self.state.epoch_store().clone(),
             new_clients,
             self.net.load().metrics.clone(),
             self.net.load().safe_client_metrics.clone(),
-        ));
-        self.net.store(new_net);
+        );
+        if let Some(batching) = self.net.load().batching {
+            new_net = new_net.with_batching(batching);
+        }
+        self.net.store(Arc::new(new_net));
 
         // TODO: Update all committee in all components safely,
         // potentially restart narwhal committee/consensus adapter,
@@ -220,6 +225,7 @@ where
         net_config.connect_timeout = Some(Duration::from_secs(5));
         net_config.request_timeout = Some(Duration::from_secs(5));
         net_config.http2_keepalive_interval = Some(Duration::from_secs(5));
+        let net_config = Arc::new(net_config);
 
         let cur_clients = self.net.load().authority_clients.clone();
 
@@ -233,22 +239,24 @@ where
                 }
                 Ok(result) => result,
             };
-            // TODO: We only recreate network connection if this is a new validator.
-            // This is because creating a new network connection on the same address doesn't
-            // work. We may want to look into this and see why it doesn't work.
+            // TODO: We only recreate network connection if this is a new validator, or one which
+            // requested a new network address for the next epoch. This is because creating a new
+            // network connection on the same address doesn't work. We may want to look into this
+            // and see why it doesn't work.
+            let address_changed = !validator.next_epoch_net_address.vec.is_empty();
             if let Some(existing_client) = cur_clients.get(&public_key_bytes) {
-                // TODO: Since we rely purely on the public key to decide whether to recreate
-                // the network, it means that validators won't be able to modify their network
-                // information without also using a new public key.
-                new_clients.insert(public_key_bytes, existing_client.authority_client().clone());
-                debug!(
-                    "Adding unchanged client to the new network: {}",
-                    public_key_bytes
-                );
-                continue;
+                if !address_changed {
+                    new_clients
+                        .insert(public_key_bytes, existing_client.authority_client().clone());
+                    debug!(
+                        "Adding unchanged client to the new network: {}",
+                        public_key_bytes
+                    );
+                    continue;
+                }
             }
 
-            let address = match Multiaddr::try_from(validator.net_address) {
+            let address = match Multiaddr::try_from(validator.next_epoch_net_address().to_vec()) {
                 Err(err) => {
                     error!("Error parsing validator network address. Skip this validator in the committee: {:?}", err);
                     continue;
@@ -256,14 +264,17 @@ where
                 Ok(result) => result,
             };
 
-            let channel = match net_config.connect_lazy(&address) {
+            let client: A = match A::recreate(
+                &address,
+                net_config.clone(),
+                self.network_metrics.clone(),
+            ) {
                 Err(err) => {
                     error!("Error connecting to client {} with address {:?}. Skip this validator in the committee: {:?}", public_key_bytes, address, err);
                     continue;
                 }
                 Ok(result) => result,
             };
-            let client: A = A::recreate(channel, self.network_metrics.clone());
             debug!(
                 "New network client created for {} at {:?}",
                 public_key_bytes, address
@@ -27,7 +27,6 @@ use crate::{
     authority_active::ActiveAuthority,
     authority_aggregator::authority_aggregator_tests::init_local_authorities,
     checkpoints::{CheckpointLocals, CHECKPOINT_COUNT_PER_EPOCH},
-    execution_engine,
 };
 
 #[tokio::test]
@@ -144,17 +143,16 @@ async fn test_start_epoch_change() {
         ),
         tx_digest,
     );
-    let (inner_temporary_store, effects, _) = execution_engine::execute_transaction_to_effects(
-        vec![],
-        temporary_store,
-        transaction.signed_data.data.clone(),
-        tx_digest,
-        BTreeSet::new(),
-        &state.move_vm,
-        &state._native_functions,
-        SuiGasStatus::new_with_budget(1000, 1.into(), 1.into()),
-        state.epoch(),
-    );
+    let (inner_temporary_store, effects, _) =
+        state.execution_engine().execute_transaction_to_effects(
+            vec![],
+            temporary_store,
+            transaction.signed_data.data.clone(),
+            tx_digest,
+            BTreeSet::new(),
+            SuiGasStatus::new_with_budget(1000, 1.into(), 1.into()),
+            state.epoch(),
+        );
     let signed_effects = effects.to_sign_effects(0, &state.name, &*state.secret);
     assert_eq!(
         state
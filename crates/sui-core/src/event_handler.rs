@@ -17,6 +17,7 @@ use sui_types::{
 };
 
 use crate::authority::{AuthorityStore, ResolverWrapper};
+use crate::event_schema::EventSchemaRegistry;
 use crate::streamer::Streamer;
 use sui_types::filter::EventFilter;
 
@@ -30,30 +31,42 @@ pub struct EventHandler {
     module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
     event_streamer: Streamer<EventEnvelope, EventFilter>,
     pub(crate) event_store: Arc<EventStoreType>,
+    event_schemas: EventSchemaRegistry,
 }
 
 impl EventHandler {
     pub fn new(validator_store: Arc<AuthorityStore>, event_store: Arc<EventStoreType>) -> Self {
         let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
+        let module_cache = Arc::new(SyncModuleCache::new(ResolverWrapper(
+            validator_store.clone(),
+        )));
         Self {
-            module_cache: Arc::new(SyncModuleCache::new(ResolverWrapper(validator_store))),
+            event_schemas: EventSchemaRegistry::new(validator_store, module_cache.clone()),
+            module_cache,
             event_streamer: streamer,
             event_store,
         }
     }
 
+    pub fn event_schemas(&self) -> &EventSchemaRegistry {
+        &self.event_schemas
+    }
+
     pub async fn process_events(
         &self,
         effects: &TransactionEffects,
         timestamp_ms: u64,
         seq_num: u64,
     ) -> SuiResult {
-        let res: Result<Vec<_>, _> = effects
-            .events
-            .iter()
-            .map(|e| self.create_envelope(e, effects.transaction_digest, seq_num, timestamp_ms))
-            .collect();
-        let envelopes = res?;
+        let envelopes = self.envelopes(effects, timestamp_ms, seq_num)?;
+
+        for event in &effects.events {
+            if let Event::Publish { package_id, .. } = event {
+                if let Err(e) = self.event_schemas.register_package(*package_id) {
+                    error!(error =? e, ?package_id, "Failed to register event schemas");
+                }
+            }
+        }
 
         // Ingest all envelopes together at once (for efficiency) into Event Store
         self.event_store.add_events(&envelopes).await?;
@@ -73,11 +86,36 @@ impl EventHandler {
         Ok(())
     }
 
+    /// The envelopes of the events emitted by the transaction executed as `seq_num`, as
+    /// processed at `timestamp_ms`.
+    pub fn envelopes(
+        &self,
+        effects: &TransactionEffects,
+        timestamp_ms: u64,
+        seq_num: u64,
+    ) -> SuiResult<Vec<EventEnvelope>> {
+        effects
+            .events
+            .iter()
+            .enumerate()
+            .map(|(event_num, e)| {
+                self.create_envelope(
+                    e,
+                    effects.transaction_digest,
+                    seq_num,
+                    event_num as u64,
+                    timestamp_ms,
+                )
+            })
+            .collect()
+    }
+
     fn create_envelope(
         &self,
         event: &Event,
         digest: TransactionDigest,
         seq_num: u64,
+        event_num: u64,
         timestamp_ms: u64,
     ) -> Result<EventEnvelope, SuiError> {
         let json_value = match event {
@@ -102,6 +140,7 @@ impl EventHandler {
             timestamp_ms,
             Some(digest),
             seq_num,
+            event_num,
             event.clone(),
             json_value,
         ))
@@ -0,0 +1,147 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Schemas of the Move event types, derived from the struct definitions of the published
+//! packages. The event store keeps the BCS contents of Move events, and the schemas are used to
+//! decode them into named fields when they are queried.
+//!
+//! The structs of a package which can be emitted as events, i.e. which have `copy` and `drop`,
+//! are registered when the package is published. The schemas of the other event types, e.g. the
+//! instantiations of generic structs or the packages published before the node started, are
+//! derived when their events are first queried.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use move_core_types::language_storage::StructTag;
+use move_core_types::value::{MoveStruct, MoveStructLayout};
+use parking_lot::RwLock;
+use tracing::{debug, warn};
+
+use sui_json_rpc_types::{SuiEvent, SuiEventSchema, SuiMoveStruct};
+use sui_types::base_types::ObjectID;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::object::{MoveObject, ObjectFormatOptions};
+use sui_types::parse_sui_struct_tag;
+
+use crate::authority::{AuthorityStore, ResolverWrapper};
+
+struct RegisteredSchema {
+    layout: MoveStructLayout,
+    schema: SuiEventSchema,
+}
+
+pub struct EventSchemaRegistry {
+    store: Arc<AuthorityStore>,
+    module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
+    schemas: RwLock<HashMap<StructTag, Arc<RegisteredSchema>>>,
+}
+
+impl EventSchemaRegistry {
+    pub fn new(
+        store: Arc<AuthorityStore>,
+        module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
+    ) -> Self {
+        Self {
+            store,
+            module_cache,
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Registers the event types of the package `package_id`.
+    pub fn register_package(&self, package_id: ObjectID) -> SuiResult {
+        let object = self
+            .store
+            .get_object(&package_id)?
+            .ok_or(SuiError::ObjectNotFound {
+                object_id: package_id,
+            })?;
+        let package = object
+            .data
+            .try_as_package()
+            .ok_or(SuiError::BadObjectType {
+                error: format!("Object {package_id} is not a package"),
+            })?;
+        for module in package.serialized_module_map().values() {
+            let module = move_binary_format::CompiledModule::deserialize(module).map_err(|e| {
+                SuiError::ModuleDeserializationFailure {
+                    error: e.to_string(),
+                }
+            })?;
+            for def in &module.struct_defs {
+                let handle = module.struct_handle_at(def.struct_handle);
+                if !handle.type_parameters.is_empty()
+                    || !handle.abilities.has_copy()
+                    || !handle.abilities.has_drop()
+                {
+                    continue;
+                }
+                let tag = StructTag {
+                    address: *module.address(),
+                    module: module.name().to_owned(),
+                    name: module.identifier_at(handle.name).to_owned(),
+                    type_params: vec![],
+                };
+                if let Err(e) = self.derive(tag.clone()) {
+                    warn!(type_ = %tag, "Cannot derive event schema: {e}");
+                }
+            }
+        }
+        debug!(?package_id, "Registered event schemas");
+        Ok(())
+    }
+
+    /// Adds the schema of a Move event, and its fields if they are not decoded yet.
+    pub fn decode(&self, mut event: SuiEvent) -> SuiEvent {
+        if let SuiEvent::MoveEvent {
+            type_,
+            fields,
+            bcs,
+            schema,
+            ..
+        } = &mut event
+        {
+            let registered = parse_sui_struct_tag(type_)
+                .ok()
+                .and_then(|tag| self.get(&tag));
+            if let Some(registered) = registered {
+                if fields.is_none() {
+                    *fields = MoveStruct::simple_deserialize(bcs, &registered.layout)
+                        .ok()
+                        .map(|move_struct| match SuiMoveStruct::from(move_struct) {
+                            SuiMoveStruct::WithTypes { fields, .. } => {
+                                SuiMoveStruct::WithFields(fields)
+                            }
+                            fields => fields,
+                        });
+                }
+                *schema = Some(registered.schema.clone());
+            }
+        }
+        event
+    }
+
+    /// The schema of the event type `type_`, derived now if it isn't registered yet.
+    fn get(&self, type_: &StructTag) -> Option<Arc<RegisteredSchema>> {
+        if let Some(registered) = self.schemas.read().get(type_) {
+            return Some(registered.clone());
+        }
+        self.derive(type_.clone())
+            .map_err(|e| debug!(%type_, "Cannot derive event schema: {e}"))
+            .ok()
+    }
+
+    fn derive(&self, type_: StructTag) -> Result<Arc<RegisteredSchema>, anyhow::Error> {
+        let layout = MoveObject::get_layout_from_struct_tag(
+            type_.clone(),
+            ObjectFormatOptions::default(),
+            self.module_cache.as_ref(),
+        )?;
+        let schema = SuiEventSchema::try_from(&layout)?;
+        let registered = Arc::new(RegisteredSchema { layout, schema });
+        self.schemas.write().insert(type_, registered.clone());
+        Ok(registered)
+    }
+}
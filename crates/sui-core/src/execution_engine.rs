@@ -2,8 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_core_types::ident_str;
-use move_core_types::identifier::Identifier;
-use std::{collections::BTreeSet, sync::Arc};
+use move_core_types::identifier::{IdentStr, Identifier};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 use sui_adapter::temporary_store::InnerTemporaryStore;
 use sui_types::storage::{ParentSync, WriteKind};
 
@@ -13,7 +16,7 @@ use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use sui_adapter::adapter;
 use sui_types::coin::Coin;
 use sui_types::committee::EpochId;
-use sui_types::error::ExecutionError;
+use sui_types::error::{ExecutionError, SuiError, SuiResult};
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::ObjectArg;
@@ -21,19 +24,253 @@ use sui_types::object::{MoveObject, Owner, OBJECT_START_VERSION};
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest, TxContext},
     event::{Event, TransferType},
-    gas::{self, SuiGasStatus},
+    gas::{self, NonRefundableStorageRates, SuiGasStatus},
     messages::{
         CallArg, ChangeEpoch, ExecutionStatus, MoveCall, MoveModulePublish, SingleTransactionKind,
         TransactionData, TransactionEffects, TransferObject, TransferSui,
     },
     object::Object,
     storage::{BackingPackageStore, Storage},
-    sui_system_state::{ADVANCE_EPOCH_FUNCTION_NAME, SUI_SYSTEM_MODULE_NAME},
-    SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
+    sui_system_state::{
+        ADVANCE_EPOCH_FUNCTION_NAME, ADVANCE_EPOCH_SAFE_MODE_FUNCTION_NAME, SUI_SYSTEM_MODULE_NAME,
+    },
+    MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
-use tracing::{debug, instrument, trace};
+use tracing::{debug, error, instrument, trace};
+
+#[cfg(test)]
+#[path = "unit_tests/execution_engine_tests.rs"]
+mod execution_engine_tests;
+
+/// Version of the transaction execution logic. Changes to execution which can alter the
+/// effects of a transaction ship as a new version, so that all validators switch to them at
+/// the same epoch boundary.
+pub type ProtocolVersion = u64;
+
+/// Executes transactions with the Move VM and natives built into this binary.
+pub const MOVE_VM_PROTOCOL_VERSION: ProtocolVersion = 1;
+
+/// Turns a transaction and its loaded inputs into effects.
+pub trait ExecutionEngine<S>: Send + Sync {
+    /// The protocol version this engine implements.
+    fn version(&self) -> ProtocolVersion;
+
+    fn execute_transaction_to_effects(
+        &self,
+        shared_object_refs: Vec<ObjectRef>,
+        temporary_store: TemporaryStore<S>,
+        transaction_data: TransactionData,
+        transaction_digest: TransactionDigest,
+        transaction_dependencies: BTreeSet<TransactionDigest>,
+        gas_status: SuiGasStatus,
+        epoch: EpochId,
+    ) -> (
+        InnerTemporaryStore,
+        TransactionEffects,
+        Option<ExecutionError>,
+    );
+
+    /// Executes `move_call` from `sender` to inspect it, see `adapter::dev_inspect`: there is no
+    /// gas coin, and the values returned by the function are returned. Its changes are left in
+    /// `temporary_store`, and are not meant to be committed.
+    fn dev_inspect_move_call(
+        &self,
+        temporary_store: &mut TemporaryStore<S>,
+        move_call: MoveCall,
+        sender: SuiAddress,
+        transaction_digest: TransactionDigest,
+        gas_status: SuiGasStatus,
+        epoch: EpochId,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError>;
+}
+
+/// The default engine, executing Move calls on `move_vm`.
+pub struct MoveVmExecutionEngine {
+    move_vm: Arc<MoveVM>,
+    native_functions: NativeFunctionTable,
+    storage_rates: NonRefundableStorageRates,
+}
+
+impl MoveVmExecutionEngine {
+    pub fn new(native_functions: NativeFunctionTable) -> SuiResult<Self> {
+        let move_vm = Arc::new(adapter::new_move_vm(native_functions.clone())?);
+        Ok(Self {
+            move_vm,
+            native_functions,
+            storage_rates: NonRefundableStorageRates::default(),
+        })
+    }
+
+    /// Charges the storage of events and effects at `storage_rates` instead of the defaults.
+    pub fn with_storage_rates(mut self, storage_rates: NonRefundableStorageRates) -> Self {
+        self.storage_rates = storage_rates;
+        self
+    }
+}
+
+impl<S: BackingPackageStore + ParentSync> ExecutionEngine<S> for MoveVmExecutionEngine {
+    fn version(&self) -> ProtocolVersion {
+        MOVE_VM_PROTOCOL_VERSION
+    }
+
+    fn execute_transaction_to_effects(
+        &self,
+        shared_object_refs: Vec<ObjectRef>,
+        temporary_store: TemporaryStore<S>,
+        transaction_data: TransactionData,
+        transaction_digest: TransactionDigest,
+        transaction_dependencies: BTreeSet<TransactionDigest>,
+        gas_status: SuiGasStatus,
+        epoch: EpochId,
+    ) -> (
+        InnerTemporaryStore,
+        TransactionEffects,
+        Option<ExecutionError>,
+    ) {
+        execute_transaction_to_effects(
+            shared_object_refs,
+            temporary_store,
+            transaction_data,
+            transaction_digest,
+            transaction_dependencies,
+            &self.move_vm,
+            &self.native_functions,
+            &self.storage_rates,
+            gas_status,
+            epoch,
+        )
+    }
+
+    fn dev_inspect_move_call(
+        &self,
+        temporary_store: &mut TemporaryStore<S>,
+        move_call: MoveCall,
+        sender: SuiAddress,
+        transaction_digest: TransactionDigest,
+        mut gas_status: SuiGasStatus,
+        epoch: EpochId,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError> {
+        let mut tx_ctx = TxContext::new(&sender, &transaction_digest, epoch);
+        charge_gas_for_object_read(temporary_store, &mut gas_status)?;
+        let MoveCall {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        } = move_call;
+        adapter::dev_inspect(
+            &self.move_vm,
+            temporary_store,
+            ModuleId::new(package.0.into(), module),
+            &function,
+            type_arguments,
+            arguments,
+            &mut gas_status,
+            &mut tx_ctx,
+        )
+    }
+}
+
+/// Builds the engine of `version` built into this binary.
+pub fn builtin_engine<S: BackingPackageStore + ParentSync + 'static>(
+    version: ProtocolVersion,
+) -> SuiResult<Arc<dyn ExecutionEngine<S>>> {
+    match version {
+        MOVE_VM_PROTOCOL_VERSION => {
+            let native_functions =
+                sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+            Ok(Arc::new(MoveVmExecutionEngine::new(native_functions)?))
+        }
+        _ => Err(SuiError::GenericAuthorityError {
+            error: format!("No execution engine built in as version {version}"),
+        }),
+    }
+}
+
+/// The execution engines known to this binary, and the epoch at which each one becomes active.
+/// A new engine can be registered ahead of time and stays dormant until its activation epoch.
+pub struct ExecutionEngines<S> {
+    engines: BTreeMap<ProtocolVersion, Arc<dyn ExecutionEngine<S>>>,
+    /// Protocol version in effect from each epoch onwards.
+    activations: BTreeMap<EpochId, ProtocolVersion>,
+}
+
+impl<S> ExecutionEngines<S> {
+    /// Starts out with `engine`, active from genesis.
+    pub fn new(engine: Arc<dyn ExecutionEngine<S>>) -> Self {
+        let version = engine.version();
+        Self {
+            engines: BTreeMap::from([(version, engine)]),
+            activations: BTreeMap::from([(0, version)]),
+        }
+    }
+
+    /// Registers `engine`, to be used from epoch `activation_epoch` onwards.
+    pub fn register(
+        &mut self,
+        engine: Arc<dyn ExecutionEngine<S>>,
+        activation_epoch: EpochId,
+    ) -> SuiResult {
+        let version = engine.version();
+        // Versions only ever move forward, one activation per epoch.
+        let (latest_epoch, latest_version) = self
+            .activations
+            .iter()
+            .next_back()
+            .expect("A version is active from epoch 0");
+        if version <= *latest_version || activation_epoch <= *latest_epoch {
+            return Err(SuiError::GenericAuthorityError {
+                error: format!(
+                    "Execution engine version {version} at epoch {activation_epoch} must come after version {latest_version} at epoch {latest_epoch}"
+                ),
+            });
+        }
+        self.engines.insert(version, engine);
+        self.activations.insert(activation_epoch, version);
+        Ok(())
+    }
+
+    /// The protocol version in effect at `epoch`.
+    pub fn protocol_version(&self, epoch: EpochId) -> ProtocolVersion {
+        *self
+            .activations
+            .range(..=epoch)
+            .next_back()
+            .map(|(_, version)| version)
+            .expect("A version is active from epoch 0")
+    }
+
+    /// The engine executing transactions of `epoch`.
+    pub fn engine(&self, epoch: EpochId) -> &Arc<dyn ExecutionEngine<S>> {
+        &self.engines[&self.protocol_version(epoch)]
+    }
+
+    /// The engine registered as `version`, whether it is active yet or not.
+    pub fn engine_for_version(
+        &self,
+        version: ProtocolVersion,
+    ) -> Option<&Arc<dyn ExecutionEngine<S>>> {
+        self.engines.get(&version)
+    }
+}
+
+impl<S: BackingPackageStore + ParentSync + 'static> ExecutionEngines<S> {
+    /// Registers the built-in engine of each version of `activations` from its epoch onwards.
+    pub fn schedule(&mut self, activations: &BTreeMap<ProtocolVersion, EpochId>) -> SuiResult {
+        for (version, activation_epoch) in activations {
+            self.register(builtin_engine(*version)?, *activation_epoch)?;
+        }
+        Ok(())
+    }
+}
 
-#[instrument(name = "tx_execute_to_effects", level = "debug", skip_all)]
+#[instrument(
+    name = "tx_execute_to_effects",
+    level = "debug",
+    skip_all,
+    fields(tx_digest = ?transaction_digest)
+)]
 pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
     shared_object_refs: Vec<ObjectRef>,
     mut temporary_store: TemporaryStore<S>,
@@ -42,6 +279,7 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
     mut transaction_dependencies: BTreeSet<TransactionDigest>,
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
+    storage_rates: &NonRefundableStorageRates,
     gas_status: SuiGasStatus,
     epoch: EpochId,
 ) -> (
@@ -59,6 +297,7 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
         &mut tx_ctx,
         move_vm,
         native_functions,
+        storage_rates,
         gas_status,
     );
 
@@ -73,6 +312,8 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
         computation_gas_cost = gas_cost_summary.computation_cost,
         storage_gas_cost = gas_cost_summary.storage_cost,
         storage_gas_rebate = gas_cost_summary.storage_rebate,
+        event_storage_gas_cost = gas_cost_summary.event_storage_cost,
+        effects_storage_gas_cost = gas_cost_summary.effects_storage_cost,
         "Finished execution of transaction with status {:?}",
         status
     );
@@ -91,6 +332,86 @@ pub fn execute_transaction_to_effects<S: BackingPackageStore + ParentSync>(
     (inner, effects, execution_error)
 }
 
+/// Advances the system to the next epoch. If `advance_epoch` fails, e.g. on a bug of the system
+/// packages, the system enters safe mode with `advance_epoch_safe_mode` instead of halting the
+/// network: the epoch moves forward and its charges are settled once `advance_epoch` succeeds.
+fn advance_epoch<S: BackingPackageStore + ParentSync>(
+    change_epoch: ChangeEpoch,
+    temporary_store: &mut TemporaryStore<S>,
+    move_vm: &Arc<MoveVM>,
+    gas_status: &mut SuiGasStatus,
+    tx_ctx: &mut TxContext,
+) -> Result<(), ExecutionError> {
+    if let Err(err) = try_advance_epoch(&change_epoch, temporary_store, move_vm, gas_status, tx_ctx)
+    {
+        error!(
+            epoch = change_epoch.epoch,
+            "Failed to advance epoch, entering safe mode: {:?}", err
+        );
+        temporary_store.reset();
+        return call_advance_epoch(
+            ADVANCE_EPOCH_SAFE_MODE_FUNCTION_NAME,
+            &change_epoch,
+            temporary_store,
+            move_vm,
+            gas_status,
+            tx_ctx,
+        );
+    }
+    Ok(())
+}
+
+fn try_advance_epoch<S: BackingPackageStore + ParentSync>(
+    change_epoch: &ChangeEpoch,
+    temporary_store: &mut TemporaryStore<S>,
+    move_vm: &Arc<MoveVM>,
+    gas_status: &mut SuiGasStatus,
+    tx_ctx: &mut TxContext,
+) -> Result<(), ExecutionError> {
+    // Lets tests take the safe mode path
+    sui_failpoints::fail_point!(
+        "advance-epoch-failure",
+        Err(ExecutionError::new_with_source(
+            sui_types::error::ExecutionErrorKind::InvariantViolation,
+            "Fail point advance-epoch-failure triggered",
+        ))
+    );
+    call_advance_epoch(
+        ADVANCE_EPOCH_FUNCTION_NAME,
+        change_epoch,
+        temporary_store,
+        move_vm,
+        gas_status,
+        tx_ctx,
+    )
+}
+
+fn call_advance_epoch<S: BackingPackageStore + ParentSync>(
+    function: &IdentStr,
+    change_epoch: &ChangeEpoch,
+    temporary_store: &mut TemporaryStore<S>,
+    move_vm: &Arc<MoveVM>,
+    gas_status: &mut SuiGasStatus,
+    tx_ctx: &mut TxContext,
+) -> Result<(), ExecutionError> {
+    let module_id = ModuleId::new(SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_MODULE_NAME.to_owned());
+    adapter::execute(
+        move_vm,
+        temporary_store,
+        module_id,
+        &function.to_owned(),
+        vec![],
+        vec![
+            CallArg::Object(ObjectArg::SharedObject(SUI_SYSTEM_STATE_OBJECT_ID)),
+            CallArg::Pure(bcs::to_bytes(&change_epoch.epoch).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&change_epoch.storage_charge).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&change_epoch.computation_charge).unwrap()),
+        ],
+        gas_status,
+        tx_ctx,
+    )
+}
+
 fn charge_gas_for_object_read<S>(
     temporary_store: &TemporaryStore<S>,
     gas_status: &mut SuiGasStatus,
@@ -114,6 +435,7 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
     tx_ctx: &mut TxContext,
     move_vm: &Arc<MoveVM>,
     native_functions: &NativeFunctionTable,
+    storage_rates: &NonRefundableStorageRates,
     mut gas_status: SuiGasStatus,
 ) -> (GasCostSummary, Result<(), ExecutionError>) {
     // We must charge object read gas inside here during transaction execution, because if this fails
@@ -170,30 +492,13 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
                     tx_ctx,
                     &mut gas_status,
                 ),
-                SingleTransactionKind::ChangeEpoch(ChangeEpoch {
-                    epoch,
-                    storage_charge,
-                    computation_charge,
-                }) => {
-                    let module_id =
-                        ModuleId::new(SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_MODULE_NAME.to_owned());
-                    let function = ADVANCE_EPOCH_FUNCTION_NAME.to_owned();
-                    adapter::execute(
-                        move_vm,
-                        temporary_store,
-                        module_id,
-                        &function,
-                        vec![],
-                        vec![
-                            CallArg::Object(ObjectArg::SharedObject(SUI_SYSTEM_STATE_OBJECT_ID)),
-                            CallArg::Pure(bcs::to_bytes(&epoch).unwrap()),
-                            CallArg::Pure(bcs::to_bytes(&storage_charge).unwrap()),
-                            CallArg::Pure(bcs::to_bytes(&computation_charge).unwrap()),
-                        ],
-                        &mut gas_status,
-                        tx_ctx,
-                    )
-                }
+                SingleTransactionKind::ChangeEpoch(change_epoch) => advance_epoch(
+                    change_epoch,
+                    temporary_store,
+                    move_vm,
+                    &mut gas_status,
+                    tx_ctx,
+                ),
             };
             if result.is_err() {
                 break;
@@ -218,14 +523,18 @@ fn execute_transaction<S: BackingPackageStore + ParentSync>(
             .expect("We constructed the object map so it should always have the gas object id")
             .clone();
         trace!(?gas_object_id, "Obtained gas object");
-        if let Err(err) =
-            temporary_store.charge_gas_for_storage_changes(&mut gas_status, &mut gas_object)
-        {
+        let charge = temporary_store
+            .charge_gas_for_storage_changes(&mut gas_status, &mut gas_object)
+            .and_then(|()| {
+                temporary_store.charge_gas_for_events_and_effects(&mut gas_status, storage_rates)
+            });
+        if let Err(err) = charge {
             // If `result` is already `Err`, we basically have two errors at the same time.
             // Users should be generally more interested in the actual execution error, so we
             // let that shadow the out of gas error. Also in this case, we don't need to reset
             // the `temporary_store` because `charge_gas_for_storage_changes` won't mutate
-            // `temporary_store` if gas charge failed.
+            // `temporary_store` if gas charge failed, and the storage changes it wrote are paid
+            // for if only `charge_gas_for_events_and_effects` failed.
             //
             // If `result` is `Ok`, now we failed when charging gas, we have to reset
             // the `temporary_store` to eliminate all effects caused by the execution,
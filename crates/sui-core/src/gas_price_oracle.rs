@@ -0,0 +1,99 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of recently included transactions suggestions are derived from.
+const MAX_SAMPLES: usize = 1000;
+/// Inclusion latency under which the network is not considered congested.
+const TARGET_INCLUSION_LATENCY: Duration = Duration::from_secs(1);
+/// Number of queued or in-flight transactions at which the network is considered saturated.
+const SATURATED_QUEUE_DEPTH: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GasPriceSuggestion {
+    pub reference_gas_price: u64,
+    pub low: u64,
+    pub medium: u64,
+    pub high: u64,
+    /// How congested the network looks, from 0 (idle) to 1 (saturated).
+    pub congestion: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct InclusionSample {
+    gas_price: u64,
+    latency: Duration,
+}
+
+/// Suggests gas prices from the gas prices and inclusion latencies of recently included
+/// transactions, and from the depth of the local submission queue.
+#[derive(Default)]
+pub struct GasPriceOracle {
+    samples: Mutex<VecDeque<InclusionSample>>,
+}
+
+impl GasPriceOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a transaction with the given gas price that took `latency` from submission to
+    /// effects certificate.
+    pub fn record_inclusion(&self, gas_price: u64, latency: Duration) {
+        let mut samples = self.samples.lock();
+        if samples.len() == MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(InclusionSample { gas_price, latency });
+    }
+
+    /// Suggests gas prices no lower than `reference_gas_price`. `queue_depth` is the number of
+    /// transactions currently waiting to be, or being, submitted.
+    pub fn suggest(&self, reference_gas_price: u64, queue_depth: usize) -> GasPriceSuggestion {
+        let samples = self.samples.lock();
+        let mut gas_prices: Vec<_> = samples.iter().map(|s| s.gas_price).collect();
+        let mut latencies: Vec<_> = samples.iter().map(|s| s.latency).collect();
+        drop(samples);
+        gas_prices.sort_unstable();
+        latencies.sort_unstable();
+
+        let latency_pressure = percentile(&latencies, 50).map_or(0.0, |latency| {
+            latency.as_secs_f64() / TARGET_INCLUSION_LATENCY.as_secs_f64() - 1.0
+        });
+        let queue_pressure = queue_depth as f64 / SATURATED_QUEUE_DEPTH as f64;
+        let congestion = latency_pressure.max(queue_pressure).clamp(0.0, 1.0);
+
+        let at_least_reference = |p| {
+            percentile(&gas_prices, p)
+                .map_or(reference_gas_price, |price| price.max(reference_gas_price))
+        };
+        let low = at_least_reference(25);
+        let medium = at_least_reference(50);
+        // Outbid most recent transactions by up to 2x as the network fills up.
+        let high = (at_least_reference(90) as f64 * (1.0 + congestion)).ceil() as u64;
+
+        GasPriceSuggestion {
+            reference_gas_price,
+            low,
+            medium,
+            high: high.max(medium),
+            congestion,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted slice.
+fn percentile<T: Copy>(sorted: &[T], p: usize) -> Option<T> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len() + 99) / 100;
+    Some(sorted[rank.saturating_sub(1)])
+}
+
+#[cfg(test)]
+#[path = "unit_tests/gas_price_oracle_tests.rs"]
+mod gas_price_oracle_tests;
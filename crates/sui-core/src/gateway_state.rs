@@ -26,7 +26,7 @@ use tracing::{debug, error, trace, Instrument};
 use sui_adapter::adapter::resolve_and_type_check;
 use sui_config::gateway::GatewayConfig;
 use sui_config::ValidatorInfo;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
 use sui_types::object::{Data, ObjectFormatOptions, Owner};
 use sui_types::{
     base_types::*,
@@ -36,12 +36,13 @@ use sui_types::{
     fp_ensure,
     messages::*,
     object::{Object, ObjectRead},
-    SUI_FRAMEWORK_ADDRESS,
+    parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
 
 use crate::authority::ResolverWrapper;
 use crate::authority_aggregator::AuthAggMetrics;
 use crate::authority_client::{NetworkAuthorityClient, NetworkAuthorityClientMetrics};
+use crate::coin_selection::select_coins;
 use crate::safe_client::SafeClientMetrics;
 use crate::transaction_input_checker;
 use crate::{
@@ -73,6 +74,10 @@ pub type GatewayTxSeqNumber = u64;
 /// Number of times to retry failed TX
 const MAX_NUM_TX_RETRIES: usize = 5;
 
+/// Objects held locally at a previous version and at least this large are downloaded as the
+/// chunks which changed since that version, rather than whole.
+const DELTA_DOWNLOAD_MIN_OBJECT_SIZE: usize = 64 * 1024;
+
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
 #[derive(Clone)]
 pub struct GatewayMetrics {
@@ -278,12 +283,15 @@ impl GatewayState<NetworkAuthorityClient> {
         let mut network_config = mysten_network::config::Config::new();
         network_config.connect_timeout = Some(config.send_timeout);
         network_config.request_timeout = Some(config.recv_timeout);
+        let network_config = Arc::new(network_config);
         let net_metrics = Arc::new(network_metrics);
         for authority in &config.validator_set {
-            let channel = network_config
-                .connect_lazy(authority.network_address())
-                .unwrap();
-            let client = NetworkAuthorityClient::new(channel, net_metrics.clone());
+            let client = NetworkAuthorityClient::connect_lazy_with_config(
+                authority.network_address(),
+                network_config.clone(),
+                net_metrics.clone(),
+            )
+            .unwrap();
             authority_clients.insert(authority.protocol_key(), client);
         }
         authority_clients
@@ -389,6 +397,19 @@ pub trait GatewayAPI {
         gas_budget: u64,
     ) -> Result<TransactionData, anyhow::Error>;
 
+    /// Pay `amount` of the coins of `coin_type`, SUI if None, to `recipient` with coins of the
+    /// signer selected automatically, the largest ones first: the coins used up are transferred
+    /// whole, and the rest of the amount is split off the last coin selected.
+    async fn pay(
+        &self,
+        signer: SuiAddress,
+        coin_type: Option<String>,
+        amount: u64,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> Result<TransactionData, anyhow::Error>;
+
     /// Create a Batch Transaction that contains a vector of parameters needed to construct
     /// all the single transactions in it.
     /// Supported single transactions are TransferObject and MoveCall.
@@ -829,11 +850,14 @@ where
         // TODO: HashSet probably works here just fine.
         object_refs: BTreeSet<ObjectRef>,
     ) -> Result<BTreeMap<ObjectRef, Object>, SuiError> {
-        let mut receiver = self
-            .authorities
-            .fetch_objects_from_authorities(object_refs.clone());
+        let mut objects = self.download_object_deltas(&object_refs).await?;
+        let remaining: BTreeSet<_> = object_refs
+            .iter()
+            .filter(|object_ref| !objects.contains_key(object_ref))
+            .copied()
+            .collect();
+        let mut receiver = self.authorities.fetch_objects_from_authorities(remaining);
 
-        let mut objects = BTreeMap::new();
         while let Some(resp) = receiver.recv().await {
             if let Ok(o) = resp {
                 // TODO: Make fetch_objects_from_authorities also return object ref
@@ -851,6 +875,42 @@ where
         Ok(objects)
     }
 
+    /// Downloads the large objects of `object_refs` which are held locally at a previous version
+    /// as the chunks which changed since. Those which fail to download are left out, to be
+    /// downloaded whole.
+    async fn download_object_deltas(
+        &self,
+        object_refs: &BTreeSet<ObjectRef>,
+    ) -> SuiResult<BTreeMap<ObjectRef, Object>> {
+        let mut bases = vec![];
+        for object_ref in object_refs {
+            if let Some(base) = self.store.get_object(&object_ref.0)? {
+                if base.version() < object_ref.1
+                    && base.object_size_for_gas_metering() >= DELTA_DOWNLOAD_MIN_OBJECT_SIZE
+                {
+                    bases.push((*object_ref, base));
+                }
+            }
+        }
+        let results = future::join_all(bases.iter().map(|(object_ref, base)| async move {
+            (
+                *object_ref,
+                self.authorities.fetch_object_delta(*object_ref, base).await,
+            )
+        }))
+        .await;
+        let mut objects = BTreeMap::new();
+        for (object_ref, result) in results {
+            match result {
+                Ok(object) => {
+                    objects.insert(object_ref, object);
+                }
+                Err(err) => debug!(?object_ref, "Failed to download object delta: {}", err),
+            }
+        }
+        Ok(objects)
+    }
+
     async fn create_parsed_transaction_response(
         &self,
         tx_kind: TransactionKind,
@@ -1145,6 +1205,29 @@ where
         Ok(coins)
     }
 
+    async fn get_owned_coins_of_type(
+        &self,
+        address: SuiAddress,
+        coin_type: &TypeTag,
+    ) -> Result<Vec<(ObjectRef, u64)>, anyhow::Error> {
+        let coin_struct = StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: coin::COIN_MODULE_NAME.to_owned(),
+            name: coin::COIN_STRUCT_NAME.to_owned(),
+            type_params: vec![coin_type.clone()],
+        };
+        let mut coins = Vec::new();
+        for info in self.store.get_owner_objects(Owner::AddressOwner(address))? {
+            if info.type_ == coin_struct.to_string() {
+                let object = self.get_object_internal(&info.object_id).await?;
+                let coin =
+                    coin::Coin::from_bcs_bytes(object.data.try_as_move().unwrap().contents())?;
+                coins.push((info.into(), coin.value()));
+            }
+        }
+        Ok(coins)
+    }
+
     async fn create_public_transfer_object_transaction_kind(
         &self,
         params: TransferObjectParams,
@@ -1338,9 +1421,11 @@ where
             .create_parsed_transaction_response(tx_kind, certificate.clone(), effects.clone())
             .await?;
 
+        let effects = SuiTransactionEffects::try_from(effects, &self.module_cache)?;
         return Ok(SuiTransactionResponse {
             certificate: certificate.try_into()?,
-            effects: SuiTransactionEffects::try_from(effects, &self.module_cache)?,
+            memo: effects.memo(),
+            effects,
             timestamp_ms: None,
             parsed_data,
         });
@@ -1600,6 +1685,74 @@ where
         Ok(data)
     }
 
+    async fn pay(
+        &self,
+        signer: SuiAddress,
+        coin_type: Option<String>,
+        amount: u64,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
+        fp_ensure!(amount > 0, anyhow!("The amount to pay must be positive"));
+        let coin_type = match coin_type {
+            Some(coin_type) => parse_sui_type_tag(&coin_type)?,
+            None => GAS::type_tag(),
+        };
+        let mut coins = self.get_owned_coins_of_type(signer, &coin_type).await?;
+        coins.retain(|(object_ref, _)| Some(object_ref.0) != gas);
+        let mut coins = select_coins(coins, amount)?;
+
+        // The coins selected before the last one hold less than the amount in total, the rest of
+        // the amount is split off the last one unless it is its whole balance
+        let (last_coin, last_balance) = coins.pop().unwrap();
+        let rest = amount - coins.iter().map(|(_, balance)| balance).sum::<u64>();
+        let mut kinds: Vec<_> = coins
+            .iter()
+            .map(|(object_ref, _)| {
+                SingleTransactionKind::TransferObject(TransferObject {
+                    recipient,
+                    object_ref: *object_ref,
+                })
+            })
+            .collect();
+        kinds.push(if rest == last_balance {
+            SingleTransactionKind::TransferObject(TransferObject {
+                recipient,
+                object_ref: last_coin,
+            })
+        } else {
+            SingleTransactionKind::Call(MoveCall {
+                package: self.get_framework_object_ref().await?,
+                module: coin::COIN_MODULE_NAME.to_owned(),
+                function: coin::COIN_SPLIT_AND_TRANSFER_FUNC_NAME.to_owned(),
+                type_arguments: vec![coin_type],
+                arguments: vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(last_coin)),
+                    CallArg::Pure(bcs::to_bytes(&rest)?),
+                    CallArg::Pure(bcs::to_bytes(&recipient)?),
+                ],
+            })
+        });
+
+        let used_object_ids = coins
+            .iter()
+            .map(|(object_ref, _)| object_ref.0)
+            .chain([last_coin.0])
+            .collect();
+        let gas = self
+            .choose_gas_for_address(signer, gas_budget, gas, used_object_ids)
+            .await?;
+        let kind = if kinds.len() == 1 {
+            TransactionKind::Single(kinds.pop().unwrap())
+        } else {
+            TransactionKind::Batch(kinds)
+        };
+        let data = TransactionData::new(kind, signer, gas, gas_budget);
+        debug!(?data, "Created Pay transaction data");
+        Ok(data)
+    }
+
     async fn get_object(
         &self,
         object_id: ObjectID,
@@ -1666,10 +1819,12 @@ where
         digest: TransactionDigest,
     ) -> Result<SuiTransactionResponse, anyhow::Error> {
         let (cert, effect) = QueryHelpers::get_transaction(&self.store, &digest)?;
+        let effects = SuiTransactionEffects::try_from(effect, &self.module_cache)?;
 
         Ok(SuiTransactionResponse {
             certificate: cert.try_into()?,
-            effects: SuiTransactionEffects::try_from(effect, &self.module_cache)?,
+            memo: effects.memo(),
+            effects,
             timestamp_ms: None,
             parsed_data: None,
         })
@@ -2,97 +2,9 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use clap::*;
-use move_core_types::{
-    language_storage::TypeTag,
-    value::{MoveStructLayout, MoveTypeLayout},
-    vm_status::AbortLocation,
-};
 use pretty_assertions::assert_str_eq;
-use serde_reflection::{Registry, Result, Samples, Tracer, TracerConfig};
-use signature::Signer;
 use std::{fs::File, io::Write};
-use sui_types::{
-    base_types::{self, ObjectDigest, ObjectID, TransactionDigest, TransactionEffectsDigest},
-    batch::UpdateItem,
-    crypto::{
-        get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes,
-        AuthoritySignature, KeypairTraits, Signature,
-    },
-    messages::{
-        CallArg, EntryArgumentErrorKind, ExecutionFailureStatus, ExecutionStatus, ObjectArg,
-        ObjectInfoRequestKind, SingleTransactionKind, TransactionKind,
-    },
-    object::{Data, Owner},
-    storage::DeleteKind,
-};
-use typed_store::rocks::TypedStoreError;
-
-fn get_registry() -> Result<Registry> {
-    let config = TracerConfig::default()
-        .record_samples_for_structs(true)
-        .record_samples_for_newtype_structs(true);
-    let mut tracer = Tracer::new(config);
-    let mut samples = Samples::new();
-    // 1. Record samples for types with custom deserializers.
-    // We want to call
-    // tracer.trace_value(&mut samples, ...)?;
-    // with all the base types contained in messages, especially the ones with custom serializers;
-    // or involving generics (see [serde_reflection documentation](https://novifinancial.github.io/serde-reflection/serde_reflection/index.html)).
-    let (addr, kp): (_, AuthorityKeyPair) = get_key_pair();
-    let (s_addr, s_kp): (_, AccountKeyPair) = get_key_pair();
-
-    let pk: AuthorityPublicKeyBytes = kp.public().into();
-    tracer.trace_value(&mut samples, &addr)?;
-    tracer.trace_value(&mut samples, &kp)?;
-    tracer.trace_value(&mut samples, &pk)?;
-
-    tracer.trace_value(&mut samples, &s_addr)?;
-    tracer.trace_value(&mut samples, &s_kp)?;
-
-    // We have two signature types: one for Authority Signatures, which don't include the PubKey ...
-    let sig: AuthoritySignature = kp.sign(b"hello world");
-    tracer.trace_value(&mut samples, &sig)?;
-    // ... and the user signature which does
-
-    let sig: Signature = s_kp.sign(b"hello world");
-    tracer.trace_value(&mut samples, &sig)?;
-
-    // ObjectID and SuiAddress are the same length
-    let addr_bytes: [u8; ObjectID::LENGTH] = addr.as_ref().try_into().unwrap();
-    let oid = ObjectID::from(addr_bytes);
-    tracer.trace_value(&mut samples, &oid)?;
-
-    // ObjectDigest and Transaction digest use the `serde_as`speedup for ser/de => trace them
-    let od = ObjectDigest::random();
-    let td = TransactionDigest::random();
-    tracer.trace_value(&mut samples, &od)?;
-    tracer.trace_value(&mut samples, &td)?;
-
-    let teff = TransactionEffectsDigest::random();
-    tracer.trace_value(&mut samples, &teff)?;
-
-    // 2. Trace the main entry point(s) + every enum separately.
-    tracer.trace_type::<Owner>(&samples)?;
-    tracer.trace_type::<ExecutionStatus>(&samples)?;
-    tracer.trace_type::<ExecutionFailureStatus>(&samples)?;
-    tracer.trace_type::<AbortLocation>(&samples)?;
-    tracer.trace_type::<EntryArgumentErrorKind>(&samples)?;
-    tracer.trace_type::<CallArg>(&samples)?;
-    tracer.trace_type::<ObjectArg>(&samples)?;
-    tracer.trace_type::<Data>(&samples)?;
-    tracer.trace_type::<TypeTag>(&samples)?;
-    tracer.trace_type::<TypedStoreError>(&samples)?;
-    tracer.trace_type::<ObjectInfoRequestKind>(&samples)?;
-    tracer.trace_type::<SingleTransactionKind>(&samples)?;
-    tracer.trace_type::<TransactionKind>(&samples)?;
-    tracer.trace_type::<MoveStructLayout>(&samples)?;
-    tracer.trace_type::<MoveTypeLayout>(&samples)?;
-    tracer.trace_type::<base_types::SuiAddress>(&samples)?;
-    tracer.trace_type::<UpdateItem>(&samples)?;
-    tracer.trace_type::<DeleteKind>(&samples)?;
-
-    tracer.registry()
-}
+use sui_core::bcs_schema::bcs_schema;
 
 #[derive(Debug, Parser, Clone, Copy, ArgEnum)]
 enum Action {
@@ -115,7 +27,7 @@ const FILE_PATH: &str = "sui-core/tests/staged/sui.yaml";
 
 fn main() {
     let options = Options::parse();
-    let registry = get_registry().unwrap();
+    let registry = bcs_schema().unwrap();
     match options.action {
         Action::Print => {
             let content = serde_yaml::to_string(&registry).unwrap();
@@ -0,0 +1,174 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use prometheus::{register_int_gauge_vec_with_registry, IntGaugeVec, Registry};
+use std::collections::HashMap;
+use std::time::Duration;
+use sui_types::base_types::ObjectID;
+use sui_types::committee::EpochId;
+
+/// Number of distinct shared objects tracked per epoch, objects first seen beyond that are ignored.
+const MAX_TRACKED_OBJECTS: usize = 100_000;
+/// Number of hottest objects exported as metrics.
+const METRICS_HOT_OBJECTS: usize = 10;
+/// Number of recorded transactions between two refreshes of the metrics.
+const METRICS_REFRESH_PERIOD: u64 = 1_000;
+
+/// Contention on a shared object over the current epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HotObject {
+    pub object_id: ObjectID,
+    /// Number of certificates which took the object as input.
+    pub transaction_count: u64,
+    /// Average time these certificates waited for consensus to sequence them. Only known on
+    /// validators, which submit certificates to consensus themselves.
+    pub average_sequencing_wait: Option<Duration>,
+}
+
+#[derive(Default)]
+struct ObjectContention {
+    transaction_count: u64,
+    sequenced_count: u64,
+    total_sequencing_wait: Duration,
+}
+
+impl ObjectContention {
+    fn to_hot_object(&self, object_id: ObjectID) -> HotObject {
+        HotObject {
+            object_id,
+            transaction_count: self.transaction_count,
+            average_sequencing_wait: (self.sequenced_count > 0).then(|| {
+                let average = self.total_sequencing_wait.as_nanos() / self.sequenced_count as u128;
+                Duration::from_nanos(average as u64)
+            }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct EpochContention {
+    epoch: EpochId,
+    objects: HashMap<ObjectID, ObjectContention>,
+    records_since_refresh: u64,
+}
+
+impl EpochContention {
+    /// The `limit` most contended objects, by number of transactions and then sequencing wait.
+    fn hottest(&self, limit: usize) -> Vec<HotObject> {
+        let mut hot_objects: Vec<_> = self
+            .objects
+            .iter()
+            .map(|(object_id, contention)| contention.to_hot_object(*object_id))
+            .collect();
+        hot_objects.sort_by(|a, b| {
+            b.transaction_count
+                .cmp(&a.transaction_count)
+                .then(b.average_sequencing_wait.cmp(&a.average_sequencing_wait))
+        });
+        hot_objects.truncate(limit);
+        hot_objects
+    }
+}
+
+struct HotObjectMetrics {
+    transaction_count: IntGaugeVec,
+    average_sequencing_wait_ms: IntGaugeVec,
+}
+
+/// Tracks which shared objects the certificates of the current epoch contend on.
+pub struct HotObjectTracker {
+    contention: Mutex<EpochContention>,
+    metrics: HotObjectMetrics,
+}
+
+impl HotObjectTracker {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            contention: Mutex::new(EpochContention::default()),
+            metrics: HotObjectMetrics {
+                transaction_count: register_int_gauge_vec_with_registry!(
+                    "hot_object_transaction_count",
+                    "Number of certificates of the current epoch taking one of the most contended shared objects as input",
+                    &["object_id"],
+                    registry,
+                )
+                .unwrap(),
+                average_sequencing_wait_ms: register_int_gauge_vec_with_registry!(
+                    "hot_object_average_sequencing_wait_ms",
+                    "Average time certificates taking one of the most contended shared objects as input waited to be sequenced",
+                    &["object_id"],
+                    registry,
+                )
+                .unwrap(),
+            },
+        }
+    }
+
+    /// Records a certificate of `epoch` taking `shared_objects` as input, and how long it waited
+    /// to be sequenced if known.
+    pub fn record<'a>(
+        &self,
+        epoch: EpochId,
+        shared_objects: impl IntoIterator<Item = &'a ObjectID>,
+        sequencing_wait: Option<Duration>,
+    ) {
+        let mut contention = self.contention.lock();
+        if epoch < contention.epoch {
+            // Late certificate of a past epoch
+            return;
+        }
+        if epoch > contention.epoch {
+            *contention = EpochContention {
+                epoch,
+                ..Default::default()
+            };
+        }
+        for object_id in shared_objects {
+            if !contention.objects.contains_key(object_id)
+                && contention.objects.len() >= MAX_TRACKED_OBJECTS
+            {
+                continue;
+            }
+            let object = contention.objects.entry(*object_id).or_default();
+            object.transaction_count += 1;
+            if let Some(wait) = sequencing_wait {
+                object.sequenced_count += 1;
+                object.total_sequencing_wait += wait;
+            }
+        }
+        contention.records_since_refresh += 1;
+        if contention.records_since_refresh >= METRICS_REFRESH_PERIOD {
+            contention.records_since_refresh = 0;
+            self.refresh_metrics(&contention);
+        }
+    }
+
+    /// The `limit` most contended shared objects of the current epoch, hottest first.
+    pub fn hot_objects(&self, limit: usize) -> (EpochId, Vec<HotObject>) {
+        let contention = self.contention.lock();
+        (contention.epoch, contention.hottest(limit))
+    }
+
+    fn refresh_metrics(&self, contention: &EpochContention) {
+        self.metrics.transaction_count.reset();
+        self.metrics.average_sequencing_wait_ms.reset();
+        for hot_object in contention.hottest(METRICS_HOT_OBJECTS) {
+            let object_id = hot_object.object_id.to_string();
+            self.metrics
+                .transaction_count
+                .with_label_values(&[&object_id])
+                .set(hot_object.transaction_count as i64);
+            if let Some(wait) = hot_object.average_sequencing_wait {
+                self.metrics
+                    .average_sequencing_wait_ms
+                    .with_label_values(&[&object_id])
+                    .set(wait.as_millis() as i64);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/hot_objects_tests.rs"]
+mod hot_objects_tests;
@@ -1,21 +1,31 @@
 // Copyright (c) 2021, Facebook, Inc. and its affiliates
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
+pub mod admission_control;
 pub mod authority;
 pub mod authority_active;
 pub mod authority_aggregator;
 pub mod authority_batch;
 pub mod authority_client;
 pub mod authority_server;
+pub mod bcs_schema;
 pub mod checkpoints;
+pub mod coin_selection;
 pub mod consensus_adapter;
 pub mod epoch;
 pub mod event_handler;
+pub mod event_schema;
 pub mod execution_engine;
+pub mod gas_price_oracle;
 pub mod gateway_state;
+pub mod hot_objects;
 pub mod metrics;
+pub mod performance_report;
 pub mod quorum_driver;
+pub mod request_batcher;
 pub mod safe_client;
+pub mod state_dump;
+pub mod state_snapshot;
 pub mod streamer;
 pub mod transaction_input_checker;
 pub mod transaction_streamer;
@@ -0,0 +1,226 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::{Committee, EpochId};
+use sui_types::performance_report::{
+    DowntimeWindow, EpochPerformanceReport, SignedEpochPerformanceReport,
+};
+use tracing::{error, info};
+
+use crate::authority::StableSyncAuthoritySigner;
+
+/// Number of reports of past epochs kept in memory.
+const MAX_KEPT_REPORTS: usize = 16;
+
+struct EpochPerformance {
+    epoch: EpochId,
+    is_validator: bool,
+    start_timestamp_ms: u64,
+    transactions_signed: u64,
+    certificates_executed: u64,
+    total_certificate_latency: Duration,
+    checkpoints_signed: u64,
+    downtime: Vec<DowntimeWindow>,
+}
+
+impl EpochPerformance {
+    fn new(epoch: EpochId, is_validator: bool) -> Self {
+        Self {
+            epoch,
+            is_validator,
+            start_timestamp_ms: now_ms(),
+            transactions_signed: 0,
+            certificates_executed: 0,
+            total_certificate_latency: Duration::ZERO,
+            checkpoints_signed: 0,
+            downtime: vec![],
+        }
+    }
+
+    fn to_report(&self, authority: AuthorityName) -> EpochPerformanceReport {
+        EpochPerformanceReport {
+            epoch: self.epoch,
+            authority,
+            is_validator: self.is_validator,
+            start_timestamp_ms: self.start_timestamp_ms,
+            end_timestamp_ms: now_ms(),
+            transactions_signed: self.transactions_signed,
+            certificates_executed: self.certificates_executed,
+            average_certificate_latency_us: (self.certificates_executed > 0).then(|| {
+                (self.total_certificate_latency.as_micros() / self.certificates_executed as u128)
+                    as u64
+            }),
+            checkpoints_signed: self.checkpoints_signed,
+            downtime: self.downtime.clone(),
+        }
+    }
+}
+
+/// Records what the node does over each epoch, and signs a report of it when the epoch closes.
+pub struct PerformanceReporter {
+    name: AuthorityName,
+    secret: StableSyncAuthoritySigner,
+    current: Mutex<EpochPerformance>,
+    reports: Mutex<VecDeque<SignedEpochPerformanceReport>>,
+    /// Directory the signed reports are written to, if any.
+    report_dir: Mutex<Option<PathBuf>>,
+}
+
+impl PerformanceReporter {
+    pub fn new(
+        name: AuthorityName,
+        secret: StableSyncAuthoritySigner,
+        committee: &Committee,
+    ) -> Self {
+        Self {
+            name,
+            secret,
+            current: Mutex::new(EpochPerformance::new(
+                committee.epoch,
+                committee.authority_exists(&name),
+            )),
+            reports: Mutex::new(VecDeque::new()),
+            report_dir: Mutex::new(None),
+        }
+    }
+
+    /// Writes the reports of the epochs closing from now on to `dir`, as `epoch-<epoch>.json`,
+    /// and reads back the reports already there.
+    pub fn persist_to(&self, dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let mut stored = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "json")
+            {
+                continue;
+            }
+            match serde_json::from_slice::<SignedEpochPerformanceReport>(&std::fs::read(&path)?) {
+                Ok(report) => stored.push(report),
+                Err(e) => error!("Ignoring invalid performance report {:?}: {}", path, e),
+            }
+        }
+        let mut reports = self.reports.lock();
+        for report in stored {
+            if !reports
+                .iter()
+                .any(|r| r.report.epoch == report.report.epoch)
+            {
+                reports.push_back(report);
+            }
+        }
+        reports
+            .make_contiguous()
+            .sort_by_key(|report| report.report.epoch);
+        while reports.len() > MAX_KEPT_REPORTS {
+            reports.pop_front();
+        }
+        *self.report_dir.lock() = Some(dir.to_path_buf());
+        Ok(())
+    }
+
+    pub fn record_transaction_signed(&self, committee: &Committee) {
+        self.with_epoch(committee, |performance| {
+            performance.transactions_signed += 1
+        });
+    }
+
+    pub fn record_certificate_executed(&self, committee: &Committee, latency: Duration) {
+        self.with_epoch(committee, |performance| {
+            performance.certificates_executed += 1;
+            performance.total_certificate_latency += latency;
+        });
+    }
+
+    pub fn record_checkpoint_signed(&self, committee: &Committee) {
+        self.with_epoch(committee, |performance| performance.checkpoints_signed += 1);
+    }
+
+    /// Records that the node was down, e.g. before it restarted. The window is attributed to
+    /// the current epoch.
+    pub fn record_downtime(&self, window: DowntimeWindow) {
+        self.current.lock().downtime.push(window);
+    }
+
+    /// Closes the current epoch if `committee` is of a later one.
+    pub fn start_epoch(&self, committee: &Committee) {
+        self.with_epoch(committee, |_| ());
+    }
+
+    /// The report of the current epoch so far, unsigned as the epoch isn't over.
+    pub fn current_report(&self) -> EpochPerformanceReport {
+        self.current.lock().to_report(self.name)
+    }
+
+    /// The signed report of a past epoch, if still kept.
+    pub fn report(&self, epoch: EpochId) -> Option<SignedEpochPerformanceReport> {
+        self.reports
+            .lock()
+            .iter()
+            .find(|report| report.report.epoch == epoch)
+            .cloned()
+    }
+
+    /// The signed reports of the last past epochs, oldest first.
+    pub fn reports(&self) -> Vec<SignedEpochPerformanceReport> {
+        self.reports.lock().iter().cloned().collect()
+    }
+
+    fn with_epoch(&self, committee: &Committee, update: impl FnOnce(&mut EpochPerformance)) {
+        let mut current = self.current.lock();
+        if committee.epoch < current.epoch {
+            // Late activity of a past epoch
+            return;
+        }
+        if committee.epoch > current.epoch {
+            let closed = std::mem::replace(
+                &mut *current,
+                EpochPerformance::new(committee.epoch, committee.authority_exists(&self.name)),
+            );
+            self.close(closed);
+        }
+        update(&mut current);
+    }
+
+    fn close(&self, performance: EpochPerformance) {
+        let report =
+            SignedEpochPerformanceReport::new(performance.to_report(self.name), &*self.secret);
+        info!(
+            epoch = report.report.epoch,
+            "Epoch performance report: {}",
+            serde_json::to_string(&report).unwrap_or_default()
+        );
+        if let Some(dir) = &*self.report_dir.lock() {
+            let path = dir.join(format!("epoch-{}.json", report.report.epoch));
+            if let Err(e) = serde_json::to_vec_pretty(&report)
+                .map_err(std::io::Error::from)
+                .and_then(|json| std::fs::write(&path, json))
+            {
+                error!("Failed to write performance report {:?}: {}", path, e);
+            }
+        }
+        let mut reports = self.reports.lock();
+        if reports.len() >= MAX_KEPT_REPORTS {
+            reports.pop_front();
+        }
+        reports.push_back(report);
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "unit_tests/performance_report_tests.rs"]
+mod performance_report_tests;
@@ -6,19 +6,25 @@ pub use metrics::*;
 
 use arc_swap::ArcSwap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::task::JoinHandle;
 use tracing::Instrument;
-use tracing::{debug, warn};
+use tracing::{debug, instrument, warn};
 
 use crate::authority_aggregator::AuthorityAggregator;
 use crate::authority_client::AuthorityAPI;
+use crate::gas_price_oracle::{GasPriceOracle, GasPriceSuggestion};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::messages::{
     CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionRequest,
     ExecuteTransactionRequestType, ExecuteTransactionResponse, Transaction,
 };
+
+/// Capacity of the queue of tasks processed in the background.
+const TASK_QUEUE_SIZE: usize = 5000;
+
 pub enum QuorumTask<A> {
     ProcessTransaction(Transaction),
     ProcessCertificate(CertifiedTransaction),
@@ -46,6 +52,7 @@ pub struct QuorumDriver<A> {
     effects_subscribe_sender:
         tokio::sync::broadcast::Sender<(CertifiedTransaction, CertifiedTransactionEffects)>,
     metrics: QuorumDriverMetrics,
+    gas_price_oracle: GasPriceOracle,
 }
 
 impl<A> QuorumDriver<A> {
@@ -63,14 +70,30 @@ impl<A> QuorumDriver<A> {
             task_sender,
             effects_subscribe_sender,
             metrics,
+            gas_price_oracle: GasPriceOracle::new(),
         }
     }
+
+    /// Suggests gas prices from the transactions recently executed through this driver and the
+    /// number of transactions it is currently processing.
+    pub fn gas_price_suggestion(&self, reference_gas_price: u64) -> GasPriceSuggestion {
+        let in_flight = self.metrics.current_requests_in_flight.get().max(0) as usize;
+        let queued = TASK_QUEUE_SIZE.saturating_sub(self.task_sender.capacity());
+        self.gas_price_oracle
+            .suggest(reference_gas_price, in_flight + queued)
+    }
 }
 
 impl<A> QuorumDriver<A>
 where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
+    #[instrument(
+        name = "quorum_driver_execute_tx",
+        level = "debug",
+        skip_all,
+        fields(tx_digest = ?request.transaction.digest())
+    )]
     pub async fn execute_transaction(
         &self,
         request: ExecuteTransactionRequest,
@@ -154,6 +177,8 @@ where
         &self,
         transaction: Transaction,
     ) -> SuiResult<ExecuteTransactionResponse> {
+        let start = Instant::now();
+        let gas_price = transaction.signed_data.data.gas_price;
         let certificate = self
             .process_transaction(transaction)
             .instrument(tracing::debug_span!("process_tx"))
@@ -162,6 +187,8 @@ where
             .process_certificate(certificate)
             .instrument(tracing::debug_span!("process_cert"))
             .await?;
+        self.gas_price_oracle
+            .record_inclusion(gas_price, start.elapsed());
         Ok(ExecuteTransactionResponse::EffectsCert(Box::new(response)))
     }
 
@@ -201,7 +228,7 @@ where
     A: AuthorityAPI + Send + Sync + 'static + Clone,
 {
     pub fn new(validators: AuthorityAggregator<A>, metrics: QuorumDriverMetrics) -> Self {
-        let (task_tx, task_rx) = mpsc::channel::<QuorumTask<A>>(5000);
+        let (task_tx, task_rx) = mpsc::channel::<QuorumTask<A>>(TASK_QUEUE_SIZE);
         let (subscriber_tx, subscriber_rx) = tokio::sync::broadcast::channel::<_>(100);
         let quorum_driver = Arc::new(QuorumDriver::new(
             validators,
@@ -251,6 +278,8 @@ where
                 match task {
                     QuorumTask::ProcessTransaction(transaction) => {
                         let tx_digest = *transaction.digest();
+                        let start = Instant::now();
+                        let gas_price = transaction.signed_data.data.gas_price;
                         // TODO: We entered here because callers do not want to wait for a
                         // transaction to finish execution. When this failed, we do not have a
                         // way to notify the caller. In the future, we may want to maintain
@@ -259,10 +288,20 @@ where
                         match quorum_driver.process_transaction(transaction).await {
                             Ok(cert) => {
                                 debug!(?tx_digest, "Transaction processing succeeded");
-                                if let Err(err) = quorum_driver.process_certificate(cert).await {
-                                    warn!(?tx_digest, "Certificate processing failed: {:?}", err);
+                                match quorum_driver.process_certificate(cert).await {
+                                    Ok(_) => {
+                                        quorum_driver
+                                            .gas_price_oracle
+                                            .record_inclusion(gas_price, start.elapsed());
+                                        debug!(?tx_digest, "Certificate processing succeeded");
+                                    }
+                                    Err(err) => {
+                                        warn!(
+                                            ?tx_digest,
+                                            "Certificate processing failed: {:?}", err
+                                        );
+                                    }
                                 }
-                                debug!(?tx_digest, "Certificate processing succeeded");
                             }
                             Err(err) => {
                                 warn!(?tx_digest, "Transaction processing failed: {:?}", err);
@@ -0,0 +1,171 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coalesces the transactions and certificates sent to a validator into batches, each sent in a
+//! single request, to save the overhead of a request per transaction at high throughput.
+//!
+//! Each validator client has its own batcher, whose tasks stop once the client and its clones
+//! are dropped.
+
+use crate::authority_client::AuthorityAPI;
+use async_trait::async_trait;
+use std::time::Duration;
+use sui_config::node::RequestBatchingConfig;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{
+    CertifiedTransaction, Transaction, TransactionInfoResponse, MAX_TRANSACTION_BATCH_SIZE,
+};
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout_at, Instant};
+
+/// Requests queued for a batch before new ones wait for room.
+const QUEUE_SIZE: usize = 10_000;
+
+type Responder = oneshot::Sender<SuiResult<TransactionInfoResponse>>;
+
+#[derive(Clone)]
+pub struct RequestBatcher {
+    transactions: mpsc::Sender<(Transaction, Responder)>,
+    certificates: mpsc::Sender<(CertifiedTransaction, Responder)>,
+}
+
+impl RequestBatcher {
+    /// Spawns the tasks batching the requests sent to `client`.
+    pub fn spawn<A>(client: A, config: RequestBatchingConfig) -> Self
+    where
+        A: AuthorityAPI + Send + Sync + 'static + Clone,
+    {
+        let max_batch_size = config.max_batch_size.clamp(1, MAX_TRANSACTION_BATCH_SIZE);
+        let linger = Duration::from_millis(config.linger_ms);
+        let (transactions, rx_transactions) = mpsc::channel(QUEUE_SIZE);
+        let (certificates, rx_certificates) = mpsc::channel(QUEUE_SIZE);
+        tokio::spawn(run(client.clone(), rx_transactions, max_batch_size, linger));
+        tokio::spawn(run(client, rx_certificates, max_batch_size, linger));
+        Self {
+            transactions,
+            certificates,
+        }
+    }
+
+    pub async fn handle_transaction(
+        &self,
+        transaction: Transaction,
+    ) -> SuiResult<TransactionInfoResponse> {
+        submit(&self.transactions, transaction).await
+    }
+
+    pub async fn handle_certificate(
+        &self,
+        certificate: CertifiedTransaction,
+    ) -> SuiResult<TransactionInfoResponse> {
+        submit(&self.certificates, certificate).await
+    }
+}
+
+async fn submit<T>(
+    sender: &mpsc::Sender<(T, Responder)>,
+    request: T,
+) -> SuiResult<TransactionInfoResponse> {
+    let (tx_response, rx_response) = oneshot::channel();
+    sender
+        .send((request, tx_response))
+        .await
+        .map_err(|_| batcher_stopped())?;
+    rx_response.await.map_err(|_| batcher_stopped())?
+}
+
+fn batcher_stopped() -> SuiError {
+    SuiError::GenericAuthorityError {
+        error: "The request batcher stopped".to_string(),
+    }
+}
+
+/// A request which can be sent in a batch.
+#[async_trait]
+trait Batched: Sized + Send + 'static {
+    async fn send<A>(
+        client: &A,
+        batch: Vec<Self>,
+    ) -> SuiResult<Vec<SuiResult<TransactionInfoResponse>>>
+    where
+        A: AuthorityAPI + Send + Sync;
+}
+
+#[async_trait]
+impl Batched for Transaction {
+    async fn send<A>(
+        client: &A,
+        mut batch: Vec<Self>,
+    ) -> SuiResult<Vec<SuiResult<TransactionInfoResponse>>>
+    where
+        A: AuthorityAPI + Send + Sync,
+    {
+        // A lone request is sent as it is, which any validator supports
+        if batch.len() == 1 {
+            return Ok(vec![client.handle_transaction(batch.pop().unwrap()).await]);
+        }
+        client.handle_transaction_batch(batch).await
+    }
+}
+
+#[async_trait]
+impl Batched for CertifiedTransaction {
+    async fn send<A>(
+        client: &A,
+        mut batch: Vec<Self>,
+    ) -> SuiResult<Vec<SuiResult<TransactionInfoResponse>>>
+    where
+        A: AuthorityAPI + Send + Sync,
+    {
+        if batch.len() == 1 {
+            return Ok(vec![client.handle_certificate(batch.pop().unwrap()).await]);
+        }
+        client.handle_certificate_batch(batch).await
+    }
+}
+
+async fn run<A, T>(
+    client: A,
+    mut receiver: mpsc::Receiver<(T, Responder)>,
+    max_batch_size: usize,
+    linger: Duration,
+) where
+    A: AuthorityAPI + Send + Sync + 'static + Clone,
+    T: Batched,
+{
+    while let Some(first) = receiver.recv().await {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + linger;
+        while batch.len() < max_batch_size {
+            match timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => batch.push(request),
+                // The batch lingered long enough, or the batcher is stopping
+                Ok(None) | Err(_) => break,
+            }
+        }
+
+        // Batches are sent concurrently, so that a slow one doesn't hold the next ones back
+        let client = client.clone();
+        tokio::spawn(async move {
+            let (requests, responders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+            let count = requests.len();
+            let responses = match T::send(&client, requests).await {
+                Ok(responses) if responses.len() == count => responses,
+                Ok(responses) => {
+                    let err = SuiError::GenericAuthorityError {
+                        error: format!(
+                            "Validator returned {} responses to a batch of {count} requests",
+                            responses.len()
+                        ),
+                    };
+                    vec![Err(err); count]
+                }
+                Err(err) => vec![Err(err); count],
+            };
+            for (responder, response) in responders.into_iter().zip(responses) {
+                // The requester may have given up waiting
+                let _ = responder.send(response);
+            }
+        });
+    }
+}
@@ -5,6 +5,7 @@
 use crate::authority_client::{AuthorityAPI, BatchInfoResponseItemStream};
 use crate::epoch::epoch_store::EpochStore;
 use crate::histogram::{Histogram, HistogramVec};
+use crate::request_batcher::RequestBatcher;
 use futures::StreamExt;
 use prometheus::core::{GenericCounter, GenericGauge};
 use prometheus::{
@@ -12,6 +13,7 @@ use prometheus::{
     IntGaugeVec,
 };
 use std::sync::Arc;
+use sui_config::node::RequestBatchingConfig;
 use sui_types::batch::{AuthorityBatch, SignedBatch, TxSequenceNumber, UpdateItem};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_checkpoint::{
@@ -89,6 +91,8 @@ pub struct SafeClient<C> {
     authority_client: C,
     epoch_store: Arc<EpochStore>,
     address: AuthorityPublicKeyBytes,
+    /// Coalesces the transactions and certificates sent to the authority, when enabled.
+    batcher: Option<RequestBatcher>,
 
     metrics_total_requests_handle_transaction_and_effects_info_request:
         GenericCounter<prometheus::core::AtomicU64>,
@@ -174,6 +178,7 @@ impl<C> SafeClient<C> {
             authority_client,
             epoch_store,
             address,
+            batcher: None,
 
             metrics_total_requests_handle_transaction_and_effects_info_request,
             metrics_total_ok_responses_handle_transaction_and_effects_info_request,
@@ -450,6 +455,13 @@ impl<C> SafeClient<C>
 where
     C: AuthorityAPI + Send + Sync + Clone + 'static,
 {
+    /// Sends the transactions and certificates to the authority in batches, as configured by
+    /// `config`.
+    pub fn with_batching(mut self, config: RequestBatchingConfig) -> Self {
+        self.batcher = Some(RequestBatcher::spawn(self.authority_client.clone(), config));
+        self
+    }
+
     /// Initiate a new transfer to a Sui or Primary account.
     pub async fn handle_transaction(
         &self,
@@ -457,10 +469,14 @@ where
     ) -> Result<TransactionInfoResponse, SuiError> {
         let digest = *transaction.digest();
         let _timer = self.metrics_handle_transaction_latency.start_timer();
-        let transaction_info = self
-            .authority_client
-            .handle_transaction(transaction)
-            .await?;
+        let transaction_info = match &self.batcher {
+            Some(batcher) => batcher.handle_transaction(transaction).await?,
+            None => {
+                self.authority_client
+                    .handle_transaction(transaction)
+                    .await?
+            }
+        };
         if let Err(err) = self.check_transaction_response(&digest, None, &transaction_info) {
             self.report_client_error(&err);
             return Err(err);
@@ -491,10 +507,14 @@ where
     ) -> Result<TransactionInfoResponse, SuiError> {
         let digest = *certificate.digest();
         let _timer = self.metrics_handle_certificate_latency.start_timer();
-        let transaction_info = self
-            .authority_client
-            .handle_certificate(certificate)
-            .await?;
+        let transaction_info = match &self.batcher {
+            Some(batcher) => batcher.handle_certificate(certificate).await?,
+            None => {
+                self.authority_client
+                    .handle_certificate(certificate)
+                    .await?
+            }
+        };
 
         if let Err(err) = self.verify_certificate_response(&digest, &transaction_info) {
             self.report_client_error(&err);
@@ -532,6 +552,17 @@ where
         Ok(response)
     }
 
+    /// Handle requests for the chunks of an object, which are checked against the object
+    /// reference once put back together by `ObjectChunksResponse::into_object`.
+    pub async fn handle_object_chunks_request(
+        &self,
+        request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        self.authority_client
+            .handle_object_chunks_request(request)
+            .await
+    }
+
     /// Handle Transaction information requests for this account.
     pub async fn handle_transaction_info_request(
         &self,
@@ -0,0 +1,126 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A bounded snapshot of the in-memory state of an authority, to capture what a node was doing
+//! when it is reported stuck. The dump holds counts and digests only: no keys, signatures or
+//! transaction contents.
+
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_types::base_types::{ObjectID, TransactionDigest};
+use sui_types::batch::TxSequenceNumber;
+use sui_types::committee::EpochId;
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::authority::{AuthorityState, InternalSequenceNumber};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StateDump {
+    pub timestamp_ms: u64,
+    pub epoch: EpochId,
+    /// Whether the authority stopped processing transactions, e.g. to reconfigure.
+    pub halted: bool,
+    pub pending_certificates: PendingCertificates,
+    pub execution: ExecutionQueues,
+    pub checkpoints: Option<CheckpointQueues>,
+    pub locks: LockTable,
+    /// The most contended shared objects of the epoch, hottest first.
+    pub hot_objects: Vec<HotObjectEntry>,
+}
+
+/// Certificates waiting to be executed, e.g. those of checkpoints being synced.
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingCertificates {
+    pub count: usize,
+    /// The oldest of them, with their sequence number in the queue.
+    pub oldest: Vec<(InternalSequenceNumber, TransactionDigest)>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExecutionQueues {
+    /// The sequence number of the next executed transaction.
+    pub next_sequence: TxSequenceNumber,
+    /// Transactions before this sequence number are all executed and streamed out.
+    pub low_watermark: TxSequenceNumber,
+    /// Transactions being executed, between the low watermark and the next sequence number.
+    pub in_flight: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckpointQueues {
+    pub next_checkpoint: CheckpointSequenceNumber,
+    /// Executed transactions not in a checkpoint yet.
+    pub uncheckpointed_transactions: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LockTable {
+    /// Locks of the table, each shared by the objects whose ID hashes to it.
+    pub stripes: usize,
+    /// The locks with the most tasks waiting for them, each with an object waited for and the
+    /// number of tasks holding or waiting for the lock.
+    pub contended: Vec<(ObjectID, usize)>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct HotObjectEntry {
+    pub object_id: ObjectID,
+    pub transaction_count: u64,
+    pub average_sequencing_wait_ms: Option<u128>,
+}
+
+impl AuthorityState {
+    /// Dumps the state of the authority, listing at most `limit` entries of each queue or table.
+    pub fn dump_state(&self, limit: usize) -> SuiResult<StateDump> {
+        let pending = self.database.get_pending_digests()?;
+        let (next_sequence, in_flight) = self.batch_notifier.live_tickets();
+        let checkpoints = match &self.checkpoints {
+            Some(checkpoints) => {
+                let mut checkpoints = checkpoints.lock();
+                Some(CheckpointQueues {
+                    next_checkpoint: checkpoints.next_checkpoint(),
+                    uncheckpointed_transactions: checkpoints
+                        .tables
+                        .extra_transactions
+                        .iter()
+                        .count(),
+                })
+            }
+            None => None,
+        };
+        let (_, hot_objects) = self.hot_objects.hot_objects(limit);
+        Ok(StateDump {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default(),
+            epoch: self.epoch(),
+            halted: self.is_halted(),
+            pending_certificates: PendingCertificates {
+                count: pending.len(),
+                oldest: pending.into_iter().take(limit).collect(),
+            },
+            execution: ExecutionQueues {
+                next_sequence,
+                low_watermark: self.batch_notifier.low_watermark(),
+                in_flight,
+            },
+            checkpoints,
+            locks: LockTable {
+                stripes: self.database.lock_stripes(),
+                contended: self.database.contended_locks(limit),
+            },
+            hot_objects: hot_objects
+                .into_iter()
+                .map(|hot_object| HotObjectEntry {
+                    object_id: hot_object.object_id,
+                    transaction_count: hot_object.transaction_count,
+                    average_sequencing_wait_ms: hot_object
+                        .average_sequencing_wait
+                        .map(|wait| wait.as_millis()),
+                })
+                .collect(),
+        })
+    }
+}
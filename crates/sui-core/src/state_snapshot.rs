@@ -0,0 +1,126 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports the live objects of an authority as of a certified checkpoint as a [`StateSnapshot`],
+//! and installs one in the empty stores of a new node, which then syncs from the checkpoint after
+//! it rather than from genesis.
+
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, HashSet};
+use sui_types::base_types::{ObjectRef, TransactionDigest};
+use sui_types::committee::Committee;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages_checkpoint::{AuthenticatedCheckpoint, CheckpointSequenceNumber};
+use sui_types::state_snapshot::{
+    state_digest, SignedStateSnapshotSummary, StateSnapshot, StateSnapshotSummary,
+};
+use tracing::info;
+
+use crate::authority::{AuthorityState, AuthorityStore};
+use crate::checkpoints::CheckpointStore;
+
+impl AuthorityState {
+    /// Builds the snapshot of the objects as of checkpoint `seq`, or returns `None` if the
+    /// checkpoint isn't certified yet. The transactions of all the checkpoints up to `seq` must
+    /// still be in the store, i.e. not pruned.
+    pub fn get_state_snapshot(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Option<StateSnapshot>, anyhow::Error> {
+        let checkpoints = self
+            .checkpoints
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("This node doesn't keep checkpoints"))?;
+        let (checkpoint, contents, transactions) = {
+            let checkpoints = checkpoints.lock();
+            let (checkpoint, contents) = match (
+                checkpoints.tables.checkpoints.get(&seq)?,
+                checkpoints.tables.checkpoint_contents.get(&seq)?,
+            ) {
+                (Some(AuthenticatedCheckpoint::Certified(checkpoint)), Some(contents)) => {
+                    (checkpoint, contents)
+                }
+                _ => return Ok(None),
+            };
+            let mut transactions: HashSet<_> = checkpoints
+                .tables
+                .checkpoint_contents
+                .iter()
+                .take_while(|(checkpoint, _)| *checkpoint <= seq)
+                .flat_map(|(_, contents)| {
+                    contents
+                        .iter()
+                        .map(|digests| digests.transaction)
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            transactions.insert(TransactionDigest::genesis());
+            (checkpoint, contents, transactions)
+        };
+
+        // The last version of each object written by a transaction of the checkpoints, which
+        // later transactions may have replaced since
+        let mut latest: BTreeMap<_, ObjectRef> = BTreeMap::new();
+        for (object_ref, transaction) in self.database.tables.parent_sync.iter() {
+            if transactions.contains(&transaction) {
+                latest.insert(object_ref.0, object_ref);
+            }
+        }
+        let mut objects = vec![];
+        let mut object_refs = vec![];
+        for object_ref in latest
+            .into_values()
+            .filter(|(_, _, digest)| digest.is_alive())
+        {
+            let object = self
+                .database
+                .get_object_by_key(&object_ref.0, object_ref.1)?
+                .ok_or_else(|| anyhow::anyhow!("Missing object {:?}", object_ref))?;
+            objects.push(object);
+            object_refs.push(object_ref);
+        }
+
+        let summary = StateSnapshotSummary {
+            epoch: checkpoint.summary.epoch,
+            checkpoint: seq,
+            checkpoint_digest: checkpoint.summary.digest(),
+            object_count: objects.len() as u64,
+            state_digest: state_digest(&object_refs),
+        };
+        Ok(Some(StateSnapshot {
+            checkpoint,
+            contents,
+            summary: SignedStateSnapshotSummary::new(summary, self.name, &*self.secret),
+            objects,
+        }))
+    }
+}
+
+/// Verifies `snapshot` against `committee` and installs it in the stores of a new node, which
+/// must be empty. The checkpoint of the snapshot becomes the latest one of the node, so that
+/// checkpoint sync resumes after it.
+pub async fn install_state_snapshot(
+    store: &AuthorityStore,
+    checkpoints: &Mutex<CheckpointStore>,
+    snapshot: &StateSnapshot,
+    committee: &Committee,
+) -> SuiResult {
+    snapshot.verify(committee)?;
+    if !store.database_is_empty()? || checkpoints.lock().latest_stored_checkpoint().is_some() {
+        return Err(SuiError::GenericAuthorityError {
+            error: "State snapshots can only be installed in empty stores".to_string(),
+        });
+    }
+    store
+        .bulk_object_insert(&snapshot.objects.iter().collect::<Vec<_>>())
+        .await?;
+    checkpoints
+        .lock()
+        .install_snapshot_checkpoint(&snapshot.checkpoint, &snapshot.contents)?;
+    info!(
+        checkpoint = snapshot.summary.summary.checkpoint,
+        objects = snapshot.objects.len(),
+        "Installed state snapshot"
+    );
+    Ok(())
+}
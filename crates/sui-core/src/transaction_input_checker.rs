@@ -53,7 +53,8 @@ pub async fn check_transaction_input<S, T>(
 where
     S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
 {
-    transaction.signed_data.data.kind.validity_check()?;
+    transaction.signed_data.data.validity_check()?;
+    check_publisher(store, &transaction.signed_data.data)?;
     let gas_status = get_gas_status(store, transaction).await?;
     let input_objects = transaction.signed_data.data.input_objects()?;
     let objects = store.get_input_objects(&input_objects)?;
@@ -84,6 +85,29 @@ where
     Ok((gas_status, input_objects))
 }
 
+/// Check that the sender may publish packages, if the transaction publishes any. This is only
+/// done when signing transactions: the allowlist may change in between, and a certificate is
+/// only formed from the signatures of validators which all checked the sender.
+fn check_publisher<S>(store: &SuiDataStore<S>, transaction: &TransactionData) -> SuiResult
+where
+    S: Eq + Debug + Serialize + for<'de> Deserialize<'de>,
+{
+    let publishes = transaction
+        .kind
+        .single_transactions()
+        .any(|kind| matches!(kind, SingleTransactionKind::Publish(_)));
+    if publishes {
+        let sender = transaction.signer();
+        fp_ensure!(
+            store
+                .get_sui_system_state_object()?
+                .is_allowed_publisher(&sender.into()),
+            SuiError::PublisherNotAllowed { sender }
+        );
+    }
+    Ok(())
+}
+
 /// Checking gas budget by fetching the gas object only from the store,
 /// and check whether the balance and budget satisfies the miminum requirement.
 /// Returns the gas object (to be able to reuse it latter) and a gas status
@@ -179,6 +203,8 @@ async fn check_objects(
             }
         })
         .collect();
+    // The gas object is owned by the gas sponsor, if the transaction has one
+    let gas_object_id = transaction.gas_payment_object_ref().0;
 
     for (object_kind, object) in input_objects.into_iter().zip(objects) {
         // All objects must exist in the DB.
@@ -194,12 +220,12 @@ async fn check_objects(
         }
         // Check if the object contents match the type of lock we need for
         // this object.
-        match check_one_object(
-            &transaction.signer(),
-            object_kind,
-            &object,
-            &owned_object_authenticators,
-        ) {
+        let owner = if object_kind.object_id() == gas_object_id {
+            transaction.gas_owner()
+        } else {
+            transaction.signer()
+        };
+        match check_one_object(&owner, object_kind, &object, &owned_object_authenticators) {
             Ok(()) => all_objects.push((object_kind, object)),
             Err(e) => {
                 errors.push(e);
@@ -219,7 +245,7 @@ async fn check_objects(
 /// The logic to check one object against a reference, and return the object if all is well
 /// or an error if not.
 fn check_one_object(
-    sender: &SuiAddress,
+    owner: &SuiAddress,
     object_kind: InputObjectKind,
     object: &Object,
     owned_object_authenticators: &HashSet<SuiAddress>,
@@ -274,12 +300,13 @@ fn check_one_object(
                 Owner::Immutable => {
                     // Nothing else to check for Immutable.
                 }
-                Owner::AddressOwner(owner) => {
-                    // Check the owner is the transaction sender.
+                Owner::AddressOwner(actual_owner) => {
+                    // Check the owner is the transaction sender, or its gas sponsor for the gas
+                    // object.
                     fp_ensure!(
-                        sender == &owner,
+                        owner == &actual_owner,
                         SuiError::IncorrectSigner {
-                            error: format!("Object {:?} is owned by account address {:?}, but signer address is {:?}", object_id, owner, sender),
+                            error: format!("Object {:?} is owned by account address {:?}, but signer address is {:?}", object_id, actual_owner, owner),
                         }
                     );
                 }
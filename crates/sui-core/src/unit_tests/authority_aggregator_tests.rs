@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis::Genesis;
+use sui_config::node::RequestBatchingConfig;
 use sui_config::ValidatorInfo;
 use sui_types::crypto::{
     generate_proof_of_possession, get_key_pair, AccountKeyPair, AuthorityKeyPair,
@@ -138,6 +139,7 @@ pub async fn init_local_authorities_with_genesis(
         post_quorum_timeout: Duration::from_secs(5),
         serial_authority_request_timeout: Duration::from_secs(1),
         serial_authority_request_interval: Duration::from_secs(1),
+        ..Default::default()
     };
     let epoch_store = Arc::new(EpochStore::new_for_testing(&committee));
     (
@@ -607,6 +609,55 @@ async fn test_map_reducer() {
     assert!(!res.as_ref().unwrap().contains(&bad_auth));
 }
 
+#[tokio::test]
+async fn test_adaptive_timeout() {
+    let (authorities, _, _) = init_local_authorities(4, vec![]).await;
+    let slow_auth = *authorities.committee.sample();
+
+    let run = |slow_delay: Duration| {
+        authorities.quorum_map_then_reduce_with_timeout(
+            HashSet::new(),
+            move |name, _client| {
+                Box::pin(async move {
+                    let delay = if name == slow_auth {
+                        slow_delay
+                    } else {
+                        Duration::from_millis(10)
+                    };
+                    tokio::time::sleep(delay).await;
+                    Ok(())
+                })
+            },
+            |mut accumulated_state, authority_name, _authority_weight, _result| {
+                Box::pin(async move {
+                    accumulated_state.insert(authority_name);
+                    if accumulated_state.len() < 3 {
+                        Ok(ReduceOutput::Continue(accumulated_state))
+                    } else {
+                        Ok(ReduceOutput::ContinueWithTimeout(
+                            accumulated_state,
+                            Duration::from_secs(10 * 60),
+                        ))
+                    }
+                })
+            },
+            Duration::from_secs(10 * 60),
+        )
+    };
+
+    // The first requests tell how fast each authority usually is.
+    let res = run(Duration::from_millis(10)).await.unwrap();
+    assert_eq!(res.len(), 4);
+    assert!(authorities.latencies.latency(&slow_auth).is_some());
+
+    // Then an authority which is much slower than usual doesn't hold up the result past a quorum.
+    let start = Instant::now();
+    let res = run(Duration::from_secs(10 * 60)).await.unwrap();
+    assert_eq!(res.len(), 3);
+    assert!(!res.contains(&slow_auth));
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
 #[tokio::test]
 async fn test_get_all_owned_objects() {
     let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
@@ -900,6 +951,56 @@ async fn test_execute_cert_to_true_effects() {
     assert!(count >= 2);
 }
 
+#[tokio::test]
+async fn test_execute_transactions_in_batches() {
+    let senders: Vec<(SuiAddress, AccountKeyPair)> = (0..8).map(|_| get_key_pair()).collect();
+    let gas_objects: Vec<_> = senders
+        .iter()
+        .map(|(sender, _)| {
+            (
+                Object::with_owner_for_testing(*sender),
+                Object::with_owner_for_testing(*sender),
+            )
+        })
+        .collect();
+    let genesis_objects = gas_objects
+        .iter()
+        .flat_map(|(coin, gas)| [coin.clone(), gas.clone()])
+        .collect();
+    let (authorities, _, _) = init_local_authorities(4, genesis_objects).await;
+    let authorities = authorities.with_batching(RequestBatchingConfig {
+        max_batch_size: 3,
+        linger_ms: 50,
+    });
+
+    // The transfers are sent to each authority together, in batches of up to 3
+    let (recipient, _): (_, AccountKeyPair) = get_key_pair();
+    let transactions: Vec<_> = senders
+        .iter()
+        .zip(&gas_objects)
+        .map(|((sender, key), (coin, gas))| {
+            transfer_coin_transaction(
+                *sender,
+                key,
+                recipient,
+                coin.compute_object_reference(),
+                gas.compute_object_reference(),
+            )
+        })
+        .collect();
+    let results = futures::future::join_all(
+        transactions
+            .iter()
+            .map(|transaction| authorities.execute_transaction(transaction)),
+    )
+    .await;
+    for ((certificate, _), transaction) in
+        results.into_iter().map(Result::unwrap).zip(&transactions)
+    {
+        assert_eq!(certificate.digest(), transaction.digest());
+    }
+}
+
 #[tokio::test]
 async fn test_process_transaction_fault_success() {
     // This test exercises the 4 different possible fauling case when one authority is faulty.
@@ -1006,6 +1107,13 @@ async fn test_quorum_once_with_timeout() {
             unreachable!();
         }
 
+        async fn handle_object_chunks_request(
+            &self,
+            _request: ObjectChunksRequest,
+        ) -> Result<ObjectChunksResponse, SuiError> {
+            unreachable!();
+        }
+
         /// Handle Object information requests for this account.
         async fn handle_transaction_info_request(
             &self,
@@ -1125,3 +1233,44 @@ async fn test_quorum_once_with_timeout() {
             .collect::<Vec<Duration>>()
     );
 }
+
+#[tokio::test]
+async fn test_retry_overloaded() {
+    let attempts = Mutex::new(0);
+    let overloaded = || SuiError::ValidatorOverloaded {
+        error: "too many requests".to_string(),
+    };
+
+    // Answered once the validator has capacity again
+    let res = retry_overloaded(|| async {
+        let mut attempts = attempts.lock().unwrap();
+        *attempts += 1;
+        if *attempts < 3 {
+            Err(overloaded())
+        } else {
+            Ok(*attempts)
+        }
+    })
+    .await;
+    assert_eq!(res.unwrap(), 3);
+
+    // Other errors are not retried
+    *attempts.lock().unwrap() = 0;
+    let res: SuiResult<()> = retry_overloaded(|| async {
+        *attempts.lock().unwrap() += 1;
+        Err(SuiError::TimeoutError)
+    })
+    .await;
+    assert!(matches!(res, Err(SuiError::TimeoutError)));
+    assert_eq!(*attempts.lock().unwrap(), 1);
+
+    // Nor retried forever
+    *attempts.lock().unwrap() = 0;
+    let res: SuiResult<()> = retry_overloaded(|| async {
+        *attempts.lock().unwrap() += 1;
+        Err(overloaded())
+    })
+    .await;
+    assert!(res.unwrap_err().is_retryable());
+    assert_eq!(*attempts.lock().unwrap(), OVERLOADED_RETRIES + 1);
+}
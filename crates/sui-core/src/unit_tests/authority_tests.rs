@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::execution_engine::MoveVmExecutionEngine;
 use bcs;
 use move_binary_format::{
     file_format::{self, AddressIdentifierIndex, IdentifierIndex, ModuleHandle},
@@ -25,14 +26,17 @@ use std::task::{Context, Poll};
 
 use std::{convert::TryInto, env};
 use sui_adapter::genesis;
+use sui_storage::table::StorageBackend;
 use sui_types::{
     base_types::dbg_addr,
     crypto::{get_key_pair, Signature},
     crypto::{AccountKeyPair, AuthorityKeyPair, KeypairTraits},
+    gas_coin::GAS,
     messages::Transaction,
     object::{Owner, GAS_VALUE_FOR_TESTING, OBJECT_START_VERSION},
+    sui_serde::Base64,
     sui_system_state::SuiSystemState,
-    SUI_SYSTEM_STATE_OBJECT_ID,
+    MOVE_STDLIB_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 use sui_types::{crypto::AuthorityPublicKeyBytes, object::Data};
 
@@ -188,6 +192,56 @@ async fn test_dry_run_transaction() {
     assert_eq!(shared_object_version, SequenceNumber::MIN);
 }
 
+#[tokio::test]
+async fn test_dev_inspect_move_call() {
+    let sender = dbg_addr(1);
+    let coin_id = ObjectID::random();
+    let authority = init_state_with_ids(vec![(sender, coin_id)]).await;
+    let coin = authority.get_object(&coin_id).await.unwrap().unwrap();
+    let value = GasCoin::try_from(&coin).unwrap().value();
+    let coin_value = MoveCall {
+        package: authority.get_framework_object_ref().await.unwrap(),
+        module: Identifier::new("coin").unwrap(),
+        function: Identifier::new("value").unwrap(),
+        type_arguments: vec![GAS::type_tag()],
+        arguments: vec![CallArg::Object(ObjectArg::ImmOrOwnedObject(
+            coin.compute_object_reference(),
+        ))],
+    };
+
+    // A public function, which returns the balance of the coin
+    let results = authority
+        .dev_inspect_move_call(sender, coin_value.clone())
+        .await
+        .unwrap();
+    assert_eq!(results.error, None);
+    assert_eq!(
+        results.return_values,
+        vec![Base64::from_bytes(&bcs::to_bytes(&value).unwrap())]
+    );
+    assert!(results.events.is_empty());
+    // Nothing is committed
+    let coin_after = authority.get_object(&coin_id).await.unwrap().unwrap();
+    assert_eq!(coin_after.version(), coin.version());
+
+    // Neither can friend functions, e.g. the one creating the supply of SUI
+    let results = authority
+        .dev_inspect_move_call(
+            sender,
+            MoveCall {
+                module: Identifier::new("sui").unwrap(),
+                function: Identifier::new("new").unwrap(),
+                type_arguments: vec![],
+                arguments: vec![],
+                ..coin_value
+            },
+        )
+        .await
+        .unwrap();
+    assert!(results.return_values.is_empty());
+    assert!(results.error.is_some());
+}
+
 #[tokio::test]
 async fn test_handle_transfer_transaction_bad_signature() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -692,6 +746,62 @@ async fn test_publish_module_no_dependencies_ok() {
     assert!(response.certified_transaction.is_some());
 }
 
+// Test that only the allowlisted addresses can publish modules on a network with an allowlist
+#[tokio::test]
+async fn test_publish_module_publisher_allowlist() {
+    let (allowed, allowed_key): (_, AccountKeyPair) = get_key_pair();
+    let (other, other_key): (_, AccountKeyPair) = get_key_pair();
+    let allowed_gas = Object::with_id_owner_gas_for_testing(ObjectID::random(), allowed, MAX_GAS);
+    let other_gas = Object::with_id_owner_gas_for_testing(ObjectID::random(), other, MAX_GAS);
+    let allowed_gas_ref = allowed_gas.compute_object_reference();
+    let other_gas_ref = other_gas.compute_object_reference();
+
+    let genesis = sui_config::genesis::Builder::new()
+        .with_publisher_allowlist([allowed].into_iter().collect())
+        .build();
+    let (_, authority_key): (_, AuthorityKeyPair) = get_key_pair();
+    let committee = Committee::new(
+        0,
+        [(authority_key.public().into(), 1)].into_iter().collect(),
+    )
+    .unwrap();
+    let (tx_reconfigure_consensus, _rx_reconfigure_consensus) = tokio::sync::mpsc::channel(10);
+    let authority = AuthorityState::new_for_testing(
+        committee,
+        &authority_key,
+        None,
+        Some(&genesis),
+        None,
+        tx_reconfigure_consensus,
+    )
+    .await;
+    authority.insert_genesis_object(allowed_gas).await;
+    authority.insert_genesis_object(other_gas).await;
+
+    let mut module_bytes = Vec::new();
+    file_format::empty_module()
+        .serialize(&mut module_bytes)
+        .unwrap();
+
+    let data =
+        TransactionData::new_module(other, other_gas_ref, vec![module_bytes.clone()], MAX_GAS);
+    let signature = Signature::new(&data, &other_key);
+    let result = authority
+        .handle_transaction(Transaction::new(data, signature))
+        .await;
+    assert!(matches!(
+        result,
+        Err(SuiError::PublisherNotAllowed { sender }) if sender == other
+    ));
+
+    let data = TransactionData::new_module(allowed, allowed_gas_ref, vec![module_bytes], MAX_GAS);
+    let signature = Signature::new(&data, &allowed_key);
+    let response = send_and_confirm_transaction(&authority, Transaction::new(data, signature))
+        .await
+        .unwrap();
+    response.signed_effects.unwrap().effects.status.unwrap();
+}
+
 #[tokio::test]
 async fn test_publish_non_existing_dependent_module() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1087,6 +1197,260 @@ async fn test_handle_confirmation_transaction_ok() {
     );
 }
 
+#[tokio::test]
+async fn test_prune_checkpoint_transactions() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let digest = *certificate.digest();
+    authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap()
+        .signed_effects
+        .unwrap()
+        .effects
+        .status
+        .unwrap();
+
+    let database = &authority_state.database;
+    assert_eq!(database.next_checkpoint_to_prune().unwrap(), 0);
+    let pruned = database
+        .prune_checkpoint_transactions(0, &[digest])
+        .unwrap();
+    assert_eq!(pruned.transactions, 1);
+    // The versions of the object and of the gas object the transfer replaced
+    assert_eq!(pruned.objects, 2);
+    assert!(pruned.bytes > 0);
+    assert_eq!(database.next_checkpoint_to_prune().unwrap(), 1);
+
+    assert!(database.read_certificate(&digest).unwrap().is_none());
+    assert!(!database.effects_exists(&digest).unwrap());
+    assert!(database
+        .get_object_by_key(&object_id, object.version())
+        .unwrap()
+        .is_none());
+    // The latest versions are kept
+    let new_object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_object.owner, recipient);
+    assert_eq!(new_object.version(), object.version().increment());
+}
+
+/// Executes like the Move VM engine, but reports a different computation cost.
+struct DivergingEngine(MoveVmExecutionEngine);
+
+impl ExecutionEngine<Arc<AuthorityStore>> for DivergingEngine {
+    fn version(&self) -> ProtocolVersion {
+        MOVE_VM_PROTOCOL_VERSION + 1
+    }
+
+    fn execute_transaction_to_effects(
+        &self,
+        shared_object_refs: Vec<ObjectRef>,
+        temporary_store: TemporaryStore<Arc<AuthorityStore>>,
+        transaction_data: TransactionData,
+        transaction_digest: TransactionDigest,
+        transaction_dependencies: std::collections::BTreeSet<TransactionDigest>,
+        gas_status: sui_types::gas::SuiGasStatus,
+        epoch: EpochId,
+    ) -> (
+        InnerTemporaryStore,
+        TransactionEffects,
+        Option<sui_types::error::ExecutionError>,
+    ) {
+        let (inner_temp_store, mut effects, execution_error) =
+            ExecutionEngine::<Arc<AuthorityStore>>::execute_transaction_to_effects(
+                &self.0,
+                shared_object_refs,
+                temporary_store,
+                transaction_data,
+                transaction_digest,
+                transaction_dependencies,
+                gas_status,
+                epoch,
+            );
+        effects.gas_used.computation_cost += 1;
+        (inner_temp_store, effects, execution_error)
+    }
+
+    fn dev_inspect_move_call(
+        &self,
+        temporary_store: &mut TemporaryStore<Arc<AuthorityStore>>,
+        move_call: MoveCall,
+        sender: SuiAddress,
+        transaction_digest: TransactionDigest,
+        gas_status: sui_types::gas::SuiGasStatus,
+        epoch: EpochId,
+    ) -> Result<Vec<Vec<u8>>, sui_types::error::ExecutionError> {
+        ExecutionEngine::<Arc<AuthorityStore>>::dev_inspect_move_call(
+            &self.0,
+            temporary_store,
+            move_call,
+            sender,
+            transaction_digest,
+            gas_status,
+            epoch,
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_execution_cross_check_deterministic() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    authority_state
+        .enable_execution_cross_check(ExecutionCrossCheckConfig {
+            protocol_version: None,
+            halt_on_divergence: true,
+        })
+        .unwrap();
+    // Only once
+    assert!(authority_state
+        .enable_execution_cross_check(ExecutionCrossCheckConfig {
+            protocol_version: None,
+            halt_on_divergence: true,
+        })
+        .is_err());
+
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    let info = authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap();
+    info.signed_effects.unwrap().effects.status.unwrap();
+    assert!(!authority_state.is_halted());
+}
+
+#[tokio::test]
+async fn test_execution_cross_check_halts_on_divergence() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let object_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let mut authority_state =
+        init_state_with_ids(vec![(sender, object_id), (sender, gas_object_id)]).await;
+    // Compare with an engine registered ahead of its activation
+    let native_functions =
+        sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+    let diverging_engine = DivergingEngine(MoveVmExecutionEngine::new(native_functions).unwrap());
+    authority_state
+        .execution_engines
+        .register(Arc::new(diverging_engine), 100)
+        .unwrap();
+    assert!(authority_state
+        .enable_execution_cross_check(ExecutionCrossCheckConfig {
+            protocol_version: Some(MOVE_VM_PROTOCOL_VERSION + 2),
+            halt_on_divergence: true,
+        })
+        .is_err());
+    authority_state
+        .enable_execution_cross_check(ExecutionCrossCheckConfig {
+            protocol_version: Some(MOVE_VM_PROTOCOL_VERSION + 1),
+            halt_on_divergence: true,
+        })
+        .unwrap();
+
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let certificate = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object.compute_object_reference(),
+        gas_object.compute_object_reference(),
+        &authority_state,
+    );
+    assert!(matches!(
+        authority_state.handle_certificate(certificate).await,
+        Err(SuiError::NondeterministicExecution { .. })
+    ));
+    assert!(authority_state.is_halted());
+
+    // Nothing was committed
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(object.owner, Owner::AddressOwner(sender));
+}
+
+#[tokio::test]
+async fn test_schedule_execution_engines() {
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let mut authority_state = init_state_with_ids(vec![(sender, ObjectID::random())]).await;
+
+    authority_state
+        .schedule_execution_engines(&BTreeMap::new())
+        .unwrap();
+    // The genesis engine is already active
+    assert!(authority_state
+        .schedule_execution_engines(&BTreeMap::from([(MOVE_VM_PROTOCOL_VERSION, 5)]))
+        .is_err());
+    // No engine is built in as the next version yet
+    assert!(authority_state
+        .schedule_execution_engines(&BTreeMap::from([(MOVE_VM_PROTOCOL_VERSION + 1, 5)]))
+        .is_err());
+    assert_eq!(
+        authority_state.execution_engines.protocol_version(100),
+        MOVE_VM_PROTOCOL_VERSION
+    );
+}
+
 struct LimitedPoll<F: Future> {
     inner: Pin<Box<F>>,
     count: u64,
@@ -1687,6 +2051,56 @@ async fn test_authority_persist() {
     assert_eq!(obj2.owner, recipient);
 }
 
+#[tokio::test]
+async fn test_authority_with_in_memory_store() {
+    let seed = [1u8; 32];
+    let (committee, _, authority_key) =
+        crate::authority_batch::batch_tests::init_state_parameters_from_rng(
+            &mut StdRng::from_seed(seed),
+        );
+
+    // The lock service and the write-ahead log still keep their databases in the directory
+    let dir = env::temp_dir();
+    let path = dir.join(format!("DB_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+
+    let store = Arc::new(AuthorityStore::open_with_backend(
+        &path,
+        None,
+        StorageBackend::InMemory,
+    ));
+    let authority =
+        crate::authority_batch::batch_tests::init_state(committee, authority_key, store).await;
+
+    let recipient = dbg_addr(2);
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object = Object::with_owner_for_testing(sender);
+    let object_ref = object.compute_object_reference();
+    let gas_object = Object::with_owner_for_testing(sender);
+    let gas_object_ref = gas_object.compute_object_reference();
+    authority.insert_genesis_object(object).await;
+    authority.insert_genesis_object(gas_object).await;
+
+    let certified_transfer_transaction = init_certified_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        object_ref,
+        gas_object_ref,
+        &authority,
+    );
+    let response = authority
+        .handle_certificate(certified_transfer_transaction)
+        .await
+        .unwrap();
+    assert!(response.signed_effects.unwrap().effects.status.is_ok());
+
+    let object = authority.get_object(&object_ref.0).await.unwrap().unwrap();
+    assert_eq!(object.owner, recipient);
+    // No table was written to disk
+    assert!(!path.join("CURRENT").exists());
+}
+
 #[tokio::test]
 async fn test_idempotent_reversed_confirmation() {
     // In this test we exercise the case where an authority first receive the certificate,
@@ -1777,6 +2191,42 @@ async fn test_change_epoch_transaction() {
     assert_eq!(sui_system_object.epoch, 1);
 }
 
+#[tokio::test]
+async fn test_change_epoch_transaction_enters_safe_mode_on_failure() {
+    let authority_state = init_state().await;
+    let _fail_point =
+        sui_failpoints::configure_scoped("advance-epoch-failure", sui_failpoints::Action::Return);
+    let signed_tx = SignedTransaction::new_change_epoch(
+        1,
+        100,
+        100,
+        authority_state.name,
+        &*authority_state.secret,
+    );
+    let committee = authority_state.committee.load();
+    let mut builder =
+        SignatureAggregator::new_unsafe(signed_tx.clone().to_transaction(), &committee);
+    let certificate = builder
+        .append(
+            signed_tx.auth_sign_info.authority,
+            signed_tx.auth_sign_info.signature,
+        )
+        .unwrap()
+        .unwrap();
+    let result = authority_state
+        .handle_certificate(certificate)
+        .await
+        .unwrap();
+
+    // The epoch still advances, with the charges of the epoch held in the system state
+    assert!(result.signed_effects.unwrap().effects.status.is_ok());
+    let sui_system_object = authority_state.get_sui_system_state_object().await.unwrap();
+    assert_eq!(sui_system_object.epoch, 1);
+    assert!(sui_system_object.safe_mode);
+    assert_eq!(sui_system_object.safe_mode_storage_charge, 100);
+    assert_eq!(sui_system_object.safe_mode_computation_charge, 100);
+}
+
 #[tokio::test]
 async fn test_transfer_sui_no_amount() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -2461,3 +2911,29 @@ async fn test_consensus_message_processed() {
             .get_next_object_version(&shared_object_id),
     );
 }
+
+#[tokio::test]
+async fn test_dump_state() {
+    let authority = init_state().await;
+    let digests: Vec<_> = (0..3).map(|_| TransactionDigest::random()).collect();
+    authority
+        .database
+        .add_pending_certificates(digests.iter().map(|digest| (*digest, None)).collect())
+        .unwrap();
+
+    let dump = authority.dump_state(2).unwrap();
+    assert_eq!(dump.epoch, authority.epoch());
+    assert!(!dump.halted);
+    assert_eq!(dump.pending_certificates.count, 3);
+    assert_eq!(
+        dump.pending_certificates
+            .oldest
+            .iter()
+            .map(|(_, digest)| *digest)
+            .collect::<Vec<_>>(),
+        digests[..2]
+    );
+    assert_eq!(dump.execution.in_flight, 0);
+    assert!(dump.locks.contended.is_empty());
+    serde_json::to_string(&dump).unwrap();
+}
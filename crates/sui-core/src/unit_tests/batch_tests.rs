@@ -29,8 +29,9 @@ use std::fs;
 use std::sync::Arc;
 use sui_types::messages::{
     AccountInfoRequest, AccountInfoResponse, BatchInfoRequest, BatchInfoResponseItem,
-    CertifiedTransaction, EpochRequest, EpochResponse, ObjectInfoRequest, ObjectInfoResponse,
-    Transaction, TransactionInfoRequest, TransactionInfoResponse,
+    CertifiedTransaction, EpochRequest, EpochResponse, ObjectChunksRequest, ObjectChunksResponse,
+    ObjectInfoRequest, ObjectInfoResponse, Transaction, TransactionInfoRequest,
+    TransactionInfoResponse,
 };
 
 pub(crate) fn init_state_parameters_from_rng<R>(
@@ -555,6 +556,13 @@ impl AuthorityAPI for TrustworthyAuthorityClient {
         })
     }
 
+    async fn handle_object_chunks_request(
+        &self,
+        _request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        unimplemented!()
+    }
+
     /// Handle Object information requests for this account.
     async fn handle_transaction_info_request(
         &self,
@@ -674,6 +682,13 @@ impl AuthorityAPI for ByzantineAuthorityClient {
         })
     }
 
+    async fn handle_object_chunks_request(
+        &self,
+        _request: ObjectChunksRequest,
+    ) -> Result<ObjectChunksResponse, SuiError> {
+        unimplemented!()
+    }
+
     /// Handle Object information requests for this account.
     async fn handle_transaction_info_request(
         &self,
@@ -0,0 +1,20 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_select_coins() {
+    let coins = vec![("a", 10), ("b", 50), ("c", 30), ("d", 5)];
+
+    // The largest coin covers the amount on its own
+    assert_eq!(select_coins(coins.clone(), 50).unwrap(), vec![("b", 50)]);
+    // Then the next largest ones are added
+    assert_eq!(
+        select_coins(coins.clone(), 81).unwrap(),
+        vec![("b", 50), ("c", 30), ("a", 10)]
+    );
+    assert_eq!(select_coins(coins.clone(), 95).unwrap().len(), 4);
+    assert!(select_coins(coins.clone(), 96).is_err());
+    assert!(select_coins(coins, 0).unwrap().is_empty());
+}
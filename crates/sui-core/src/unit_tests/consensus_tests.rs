@@ -9,37 +9,23 @@ use narwhal_types::TransactionsServer;
 use narwhal_types::{Empty, TransactionProto};
 use sui_network::tonic;
 use sui_types::{
-    base_types::{ObjectID, TransactionDigest},
-    crypto::Signature,
-    gas_coin::GasCoin,
+    crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits, Signature},
     messages::{
         CallArg, CertifiedTransaction, ConsensusTransactionKind, ObjectArg, SignatureAggregator,
         Transaction, TransactionData,
     },
-    object::{MoveObject, Object, Owner, OBJECT_START_VERSION},
+    object::Object,
 };
+use test_utils::objects::test_gas_objects_with_owners;
 use test_utils::test_account_keys;
 use tokio::sync::mpsc::channel;
 
+pub use test_utils::objects::test_shared_object;
+
 /// Fixture: a few test gas objects.
 pub fn test_gas_objects() -> Vec<Object> {
-    (0..4)
-        .map(|i| {
-            let seed = format!("0x555555555555555{i}");
-            let gas_object_id = ObjectID::from_hex_literal(&seed).unwrap();
-            let (sender, _) = test_account_keys().pop().unwrap();
-            Object::with_id_owner_for_testing(gas_object_id, sender)
-        })
-        .collect()
-}
-
-/// Fixture: a a test shared object.
-pub fn test_shared_object() -> Object {
-    let seed = "0x6666666666666660";
-    let shared_object_id = ObjectID::from_hex_literal(seed).unwrap();
-    let content = GasCoin::new(shared_object_id, 10);
-    let obj = MoveObject::new_gas_coin(OBJECT_START_VERSION, content.to_bcs_bytes());
-    Object::new_move(obj, Owner::Shared, TransactionDigest::genesis())
+    let (sender, _) = test_account_keys().pop().unwrap();
+    test_gas_objects_with_owners(vec![sender; 4])
 }
 
 /// Fixture: a few test certificates containing a shared object.
@@ -225,6 +211,67 @@ async fn submit_transaction_to_consensus() {
     }
 }
 
+#[tokio::test]
+async fn relay_transaction_to_consensus_leader() {
+    let (tx_consensus_listener, _rx_consensus_listener) = channel(1);
+    let state = init_state_with_objects(test_gas_objects()).await;
+    let name = state.name;
+    let mut names = vec![name];
+    for _ in 0..2 {
+        let (_, key): (_, AuthorityKeyPair) = get_key_pair();
+        names.push(key.public().into());
+    }
+    names.sort();
+
+    // The first round electing a leader other than this authority
+    let round = (1..)
+        .map(|i| 2 * i)
+        .find(|round| names[*round as usize % names.len()] != name)
+        .unwrap();
+    let leader = names[round as usize % names.len()];
+    let last_consensus_round = Arc::new(AtomicU64::new(round - 2));
+
+    let address = || -> Multiaddr {
+        let port = sui_config::utils::get_available_port();
+        format!("/dns/localhost/tcp/{port}/http").parse().unwrap()
+    };
+    let leader_address = address();
+    let consensus_addresses: Vec<_> = names
+        .iter()
+        .map(|validator| {
+            if *validator == leader {
+                (*validator, leader_address.clone())
+            } else {
+                (*validator, address())
+            }
+        })
+        .collect();
+    let submitter = ConsensusAdapter::new(
+        address(),
+        state.clone_committee(),
+        tx_consensus_listener,
+        /* max_delay */ Duration::from_millis(1_000),
+        ConsensusAdapterMetrics::new_test(),
+    )
+    .with_relay(ConsensusRelay::new(
+        name,
+        consensus_addresses,
+        last_consensus_round,
+    ));
+
+    // Only the worker of the leader is up, so the transaction can only reach consensus through it
+    let mut handle = ConsensusMockServer::spawn(leader_address);
+    let bytes = Bytes::from(vec![1, 2, 3]);
+    loop {
+        match submitter.submit_transaction(bytes.clone()).await {
+            Ok(_) => break,
+            Err(SuiError::ConsensusConnectionBroken(..)) => (),
+            Err(e) => panic!("Unexpected error message: {e}"),
+        }
+    }
+    assert_eq!(handle.recv().await.unwrap().transaction, bytes);
+}
+
 pub struct ConsensusMockServer {
     sender: Sender<TransactionProto>,
 }
@@ -0,0 +1,74 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+struct UnusedEngine(ProtocolVersion);
+
+impl ExecutionEngine<()> for UnusedEngine {
+    fn version(&self) -> ProtocolVersion {
+        self.0
+    }
+
+    fn execute_transaction_to_effects(
+        &self,
+        _shared_object_refs: Vec<ObjectRef>,
+        _temporary_store: TemporaryStore<()>,
+        _transaction_data: TransactionData,
+        _transaction_digest: TransactionDigest,
+        _transaction_dependencies: BTreeSet<TransactionDigest>,
+        _gas_status: SuiGasStatus,
+        _epoch: EpochId,
+    ) -> (
+        InnerTemporaryStore,
+        TransactionEffects,
+        Option<ExecutionError>,
+    ) {
+        unreachable!("Engine selection tests never execute transactions")
+    }
+
+    fn dev_inspect_move_call(
+        &self,
+        _temporary_store: &mut TemporaryStore<()>,
+        _move_call: MoveCall,
+        _sender: SuiAddress,
+        _transaction_digest: TransactionDigest,
+        _gas_status: SuiGasStatus,
+        _epoch: EpochId,
+    ) -> Result<Vec<Vec<u8>>, ExecutionError> {
+        unreachable!("Engine selection tests never execute transactions")
+    }
+}
+
+#[test]
+fn test_engine_selection_by_epoch() {
+    let mut engines: ExecutionEngines<()> = ExecutionEngines::new(Arc::new(UnusedEngine(1)));
+    engines.register(Arc::new(UnusedEngine(2)), 5).unwrap();
+
+    assert_eq!(engines.protocol_version(0), 1);
+    assert_eq!(engines.protocol_version(4), 1);
+    assert_eq!(engines.protocol_version(5), 2);
+    assert_eq!(engines.protocol_version(100), 2);
+    assert_eq!(engines.engine(4).version(), 1);
+    assert_eq!(engines.engine(5).version(), 2);
+    assert_eq!(engines.engine_for_version(2).unwrap().version(), 2);
+    assert!(engines.engine_for_version(3).is_none());
+}
+
+#[test]
+fn test_engine_registration_moves_forward() {
+    let mut engines: ExecutionEngines<()> = ExecutionEngines::new(Arc::new(UnusedEngine(1)));
+    engines.register(Arc::new(UnusedEngine(3)), 10).unwrap();
+
+    // Same version again
+    assert!(engines.register(Arc::new(UnusedEngine(3)), 20).is_err());
+    // Older version
+    assert!(engines.register(Arc::new(UnusedEngine(2)), 20).is_err());
+    // Activation before the latest one
+    assert!(engines.register(Arc::new(UnusedEngine(4)), 10).is_err());
+    assert!(engines.register(Arc::new(UnusedEngine(4)), 5).is_err());
+
+    engines.register(Arc::new(UnusedEngine(4)), 11).unwrap();
+    assert_eq!(engines.protocol_version(10), 3);
+    assert_eq!(engines.protocol_version(11), 4);
+}
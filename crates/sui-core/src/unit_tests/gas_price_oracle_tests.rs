@@ -0,0 +1,63 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_suggestion_without_samples() {
+    let oracle = GasPriceOracle::new();
+    let suggestion = oracle.suggest(10, 0);
+    assert_eq!(
+        suggestion,
+        GasPriceSuggestion {
+            reference_gas_price: 10,
+            low: 10,
+            medium: 10,
+            high: 10,
+            congestion: 0.0,
+        }
+    );
+}
+
+#[test]
+fn test_suggestion_follows_recent_gas_prices() {
+    let oracle = GasPriceOracle::new();
+    for gas_price in 1..=100 {
+        oracle.record_inclusion(gas_price, Duration::from_millis(100));
+    }
+    let suggestion = oracle.suggest(30, 0);
+    assert_eq!(suggestion.congestion, 0.0);
+    // The 25th percentile is below the reference gas price.
+    assert_eq!(suggestion.low, 30);
+    assert_eq!(suggestion.medium, 50);
+    assert_eq!(suggestion.high, 90);
+}
+
+#[test]
+fn test_congestion_raises_high_suggestion() {
+    let oracle = GasPriceOracle::new();
+    for _ in 0..10 {
+        oracle.record_inclusion(10, Duration::from_millis(1500));
+    }
+    let suggestion = oracle.suggest(1, 0);
+    assert_eq!(suggestion.congestion, 0.5);
+    assert_eq!(suggestion.medium, 10);
+    assert_eq!(suggestion.high, 15);
+
+    // A saturated queue dominates a moderate latency.
+    let suggestion = oracle.suggest(1, SATURATED_QUEUE_DEPTH * 2);
+    assert_eq!(suggestion.congestion, 1.0);
+    assert_eq!(suggestion.high, 20);
+}
+
+#[test]
+fn test_oldest_samples_are_evicted() {
+    let oracle = GasPriceOracle::new();
+    for _ in 0..MAX_SAMPLES {
+        oracle.record_inclusion(1000, Duration::from_millis(100));
+    }
+    for _ in 0..MAX_SAMPLES {
+        oracle.record_inclusion(1, Duration::from_millis(100));
+    }
+    assert_eq!(oracle.suggest(1, 0).high, 1);
+}
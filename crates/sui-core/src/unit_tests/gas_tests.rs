@@ -15,8 +15,9 @@ use sui_types::object::GAS_VALUE_FOR_TESTING;
 use sui_types::{
     base_types::dbg_addr,
     crypto::{get_key_pair, Signature},
-    gas::{SuiGasStatus, MAX_GAS_BUDGET, MIN_GAS_BUDGET},
-    messages::Transaction,
+    error::ExecutionError,
+    gas::{NonRefundableStorageRates, SuiGasStatus, MAX_GAS_BUDGET, MIN_GAS_BUDGET},
+    messages::{Transaction, TransactionEffects},
 };
 
 #[tokio::test]
@@ -78,7 +79,7 @@ async fn test_tx_gas_balance_less_than_budget() {
 }
 
 #[tokio::test]
-async fn test_native_transfer_sufficient_gas() -> SuiResult {
+async fn test_native_transfer_sufficient_gas() -> Result<(), ExecutionError> {
     // This test does a native transfer with sufficient gas budget and balance.
     // It's expected to succeed. We check that gas was charged properly.
     let result = execute_transfer(*MAX_GAS_BUDGET, *MAX_GAS_BUDGET, true).await;
@@ -310,7 +311,10 @@ async fn test_publish_gas() -> anyhow::Result<()> {
         gas_object.object_size_for_gas_metering(),
         0.into(),
     )?;
+    charge_events_and_effects(&mut gas_status, &effects)?;
     assert_eq!(&gas_cost, &gas_status.summary(true));
+    assert!(gas_cost.event_storage_cost > 0);
+    assert!(gas_cost.effects_storage_cost > 0);
 
     // Create a transaction with budget DELTA less than the gas cost required.
     let total_gas_used = gas_cost.gas_used();
@@ -368,7 +372,7 @@ async fn test_publish_gas() -> anyhow::Result<()> {
 }
 
 #[tokio::test]
-async fn test_move_call_gas() -> SuiResult {
+async fn test_move_call_gas() -> Result<(), ExecutionError> {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
     let gas_object_id = ObjectID::random();
     let (authority_state, package_object_ref) =
@@ -434,12 +438,17 @@ async fn test_move_call_gas() -> SuiResult {
         gas_object.object_size_for_gas_metering(),
         0.into(),
     )?;
+    charge_events_and_effects(&mut gas_status, &effects)?;
 
     let new_cost = gas_status.summary(true);
     assert_eq!(gas_cost.storage_cost, new_cost.storage_cost);
-    // This is the total amount of storage cost paid. We will use this
-    // to check if we get back the same amount of rebate latter.
-    let prev_storage_cost = gas_cost.storage_cost;
+    assert_eq!(gas_cost.event_storage_cost, new_cost.event_storage_cost);
+    assert_eq!(gas_cost.effects_storage_cost, new_cost.effects_storage_cost);
+    // This is the total amount of storage cost paid for the objects, the events and effects
+    // are never rebated. We will use this to check if we get back the same amount of rebate
+    // latter.
+    let prev_storage_cost =
+        gas_cost.storage_cost - gas_cost.event_storage_cost - gas_cost.effects_storage_cost;
 
     // Execute object deletion, and make sure we have storage rebate.
     let data = TransactionData::new_move_call(
@@ -460,9 +469,11 @@ async fn test_move_call_gas() -> SuiResult {
     let effects = response.signed_effects.unwrap().effects;
     assert!(effects.status.is_ok());
     let gas_cost = effects.gas_used;
-    // storage_cost should be less than rebate because for object deletion, we only
-    // rebate without charging.
-    assert!(gas_cost.storage_cost > 0 && gas_cost.storage_cost < gas_cost.storage_rebate);
+    // storage_cost of the objects should be less than rebate because for object deletion, we
+    // only rebate without charging.
+    let object_storage_cost =
+        gas_cost.storage_cost - gas_cost.event_storage_cost - gas_cost.effects_storage_cost;
+    assert!(object_storage_cost > 0 && object_storage_cost < gas_cost.storage_rebate);
     // Check that we have storage rebate that's the same as previous cost.
     assert_eq!(gas_cost.storage_rebate, prev_storage_cost);
     let expected_gas_balance = expected_gas_balance - gas_cost.gas_used() + gas_cost.storage_rebate;
@@ -498,7 +509,7 @@ async fn test_move_call_gas() -> SuiResult {
 }
 
 #[tokio::test]
-async fn test_storage_gas_unit_price() -> SuiResult {
+async fn test_storage_gas_unit_price() -> Result<(), ExecutionError> {
     let mut gas_status1 = SuiGasStatus::new_with_budget(*MAX_GAS_BUDGET, 1.into(), 1.into());
     gas_status1.charge_storage_mutation(100, 200, 5.into())?;
     let gas_cost1 = gas_status1.summary(true);
@@ -514,6 +525,35 @@ async fn test_storage_gas_unit_price() -> SuiResult {
     Ok(())
 }
 
+/// Mimics the charge of the storage of the events and effects of a transaction.
+fn charge_events_and_effects(
+    gas_status: &mut SuiGasStatus,
+    effects: &TransactionEffects,
+) -> Result<(), ExecutionError> {
+    let rates = NonRefundableStorageRates::default();
+    let event_size = effects
+        .events
+        .iter()
+        .map(|event| bcs::serialized_size(event).unwrap())
+        .sum();
+    gas_status.charge_events(event_size, &rates)?;
+    let written_size: usize = effects
+        .created
+        .iter()
+        .chain(&effects.mutated)
+        .chain(&effects.unwrapped)
+        .map(|entry| bcs::serialized_size(entry).unwrap())
+        .sum();
+    let deleted_size: usize = effects
+        .deleted
+        .iter()
+        .chain(&effects.wrapped)
+        .map(|object_ref| bcs::serialized_size(object_ref).unwrap())
+        .sum();
+    gas_status.charge_effects(written_size + deleted_size, &rates)?;
+    Ok(())
+}
+
 struct TransferResult {
     pub authority_state: AuthorityState,
     pub object_id: ObjectID,
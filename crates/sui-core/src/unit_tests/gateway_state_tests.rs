@@ -7,7 +7,6 @@ use serde_json::json;
 use std::{collections::HashSet, path::Path};
 
 use signature::Signer;
-use typed_store::Map;
 
 use sui_framework::build_move_package_to_bytes;
 use sui_types::crypto::{AccountKeyPair, Signature};
@@ -719,3 +718,55 @@ async fn test_batch_transaction() {
     assert!(effects.created.is_empty());
     assert_eq!(effects.mutated.len(), 3);
 }
+
+#[tokio::test]
+async fn test_pay() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let (addr2, _key2): (_, AccountKeyPair) = get_key_pair();
+
+    let coin_object1 = Object::with_owner_for_testing(addr1);
+    let coin_object2 = Object::with_owner_for_testing(addr1);
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let genesis_objects = vec![
+        coin_object1.clone(),
+        coin_object2.clone(),
+        gas_object.clone(),
+    ];
+    let gateway = create_gateway_state(genesis_objects).await;
+
+    // The coins besides the gas object do not hold that much
+    assert!(gateway
+        .pay(
+            addr1,
+            None,
+            GAS_VALUE_FOR_TESTING * 2 + 1,
+            addr2,
+            Some(gas_object.id()),
+            5000
+        )
+        .await
+        .is_err());
+
+    // One coin is transferred whole and the rest of the amount split off the other one
+    let data = gateway
+        .pay(
+            addr1,
+            None,
+            GAS_VALUE_FOR_TESTING + 100,
+            addr2,
+            Some(gas_object.id()),
+            5000,
+        )
+        .await
+        .unwrap();
+    assert!(matches!(data.kind, TransactionKind::Batch(ref kinds) if kinds.len() == 2));
+    let signature = key1.sign(&data.to_bytes());
+    let effects = gateway
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+        .unwrap()
+        .effects;
+    assert_eq!(effects.created.len(), 1);
+    assert_eq!(effects.created[0].owner, Owner::AddressOwner(addr2));
+    assert_eq!(effects.mutated.len(), 3);
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_hot_objects_ranking() {
+    let tracker = HotObjectTracker::new(&Registry::new());
+    let (hot, warm, cold) = (ObjectID::random(), ObjectID::random(), ObjectID::random());
+    for _ in 0..3 {
+        tracker.record(0, [&hot], Some(Duration::from_millis(300)));
+    }
+    tracker.record(0, [&hot, &warm], Some(Duration::from_millis(100)));
+    tracker.record(0, [&warm], None);
+    tracker.record(0, [&cold], None);
+
+    let (epoch, hot_objects) = tracker.hot_objects(2);
+    assert_eq!(epoch, 0);
+    assert_eq!(
+        hot_objects,
+        vec![
+            HotObject {
+                object_id: hot,
+                transaction_count: 4,
+                average_sequencing_wait: Some(Duration::from_millis(250)),
+            },
+            HotObject {
+                object_id: warm,
+                transaction_count: 2,
+                average_sequencing_wait: Some(Duration::from_millis(100)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_hot_objects_reset_on_new_epoch() {
+    let tracker = HotObjectTracker::new(&Registry::new());
+    let (old, new) = (ObjectID::random(), ObjectID::random());
+    tracker.record(0, [&old], None);
+    tracker.record(1, [&new], None);
+    // Certificates of past epochs are ignored
+    tracker.record(0, [&old], None);
+
+    let (epoch, hot_objects) = tracker.hot_objects(10);
+    assert_eq!(epoch, 1);
+    assert_eq!(hot_objects.len(), 1);
+    assert_eq!(hot_objects[0].object_id, new);
+    assert_eq!(hot_objects[0].average_sequencing_wait, None);
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+
+fn new_reporter() -> (PerformanceReporter, Committee, Committee) {
+    let (_, key_pair): (_, AuthorityKeyPair) = get_key_pair();
+    let name: AuthorityName = key_pair.public().into();
+    let committee = |epoch| Committee::new(epoch, BTreeMap::from([(name, 1)])).unwrap();
+    let (first, second) = (committee(0), committee(1));
+    let reporter = PerformanceReporter::new(name, Arc::pin(key_pair), &first);
+    (reporter, first, second)
+}
+
+#[test]
+fn test_performance_report_signed_at_epoch_close() {
+    let (reporter, first, second) = new_reporter();
+    reporter.record_transaction_signed(&first);
+    reporter.record_certificate_executed(&first, Duration::from_millis(10));
+    reporter.record_certificate_executed(&first, Duration::from_millis(30));
+    reporter.record_checkpoint_signed(&first);
+    reporter.record_downtime(DowntimeWindow {
+        start_timestamp_ms: 1_000,
+        end_timestamp_ms: 2_000,
+    });
+    assert!(reporter.report(0).is_none());
+    assert_eq!(reporter.current_report().certificates_executed, 2);
+
+    reporter.start_epoch(&second);
+    // Activity of a closed epoch is ignored
+    reporter.record_transaction_signed(&first);
+
+    let report = reporter.report(0).unwrap();
+    report.verify(&first).unwrap();
+    assert!(report.report.is_validator);
+    assert_eq!(report.report.transactions_signed, 1);
+    assert_eq!(report.report.certificates_executed, 2);
+    assert_eq!(report.report.average_certificate_latency_us, Some(20_000));
+    assert_eq!(report.report.checkpoints_signed, 1);
+    assert_eq!(report.report.downtime.len(), 1);
+
+    let current = reporter.current_report();
+    assert_eq!(current.epoch, 1);
+    assert_eq!(current.transactions_signed, 0);
+    assert_eq!(current.average_certificate_latency_us, None);
+}
+
+#[test]
+fn test_performance_reports_persisted() {
+    let dir = tempfile::tempdir().unwrap();
+    let (reporter, first, second) = new_reporter();
+    reporter.persist_to(dir.path()).unwrap();
+    reporter.record_checkpoint_signed(&first);
+    reporter.start_epoch(&second);
+    assert!(dir.path().join("epoch-0.json").exists());
+
+    let (restarted, _, _) = new_reporter();
+    restarted.persist_to(dir.path()).unwrap();
+    let reports = restarted.reports();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].report.checkpoints_signed, 1);
+    reports[0].verify(&first).unwrap();
+}
@@ -19,7 +19,6 @@ use sui_types::{
 };
 
 use crate::safe_client::SafeClient;
-use typed_store::Map;
 
 #[tokio::test]
 async fn test_start_stop_batch_subsystem() {
@@ -43,6 +43,8 @@ pub const OBJ_DATA_COST_REFUNDABLE: u64 = 100;
 // Cost to store metadata of objects per byte.
 // This depends on the size of various fields including the effects
 pub const OBJ_METADATA_COST_NON_REFUNDABLE: u64 = 50;
+// Cost to store the events of a TX per byte. This is not refundable
+pub const EVENT_DATA_COST_NON_REFUNDABLE: u64 = 50;
 
 //
 // Consensus costs: costs for TXes that use shared object
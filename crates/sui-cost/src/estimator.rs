@@ -35,13 +35,17 @@ pub fn estimate_computational_costs_for_transaction(
     let unsupported_tx_kind = Err(anyhow!("Transaction kind not supported for estimator yet"));
     match tx_kind {
         TransactionKind::Single(s) => match s {
-            SingleTransactionKind::TransferSui(t) => Ok(if t.amount.is_none() {
-                cost_map.get(&CommonTransactionCosts::TransferWholeSuiCoin)
-            } else {
-                cost_map.get(&CommonTransactionCosts::TransferPortionSuiCoin)
+            SingleTransactionKind::TransferSui(t) => {
+                let cost = if t.amount.is_none() {
+                    CommonTransactionCosts::TransferWholeSuiCoin
+                } else {
+                    CommonTransactionCosts::TransferPortionSuiCoin
+                };
+                cost_map
+                    .get(&cost)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("No estimate for {}", cost))
             }
-            .unwrap()
-            .clone()),
 
             SingleTransactionKind::TransferObject(_) => unsupported_tx_kind,
             SingleTransactionKind::Publish(_) => unsupported_tx_kind,
@@ -54,7 +58,7 @@ pub fn estimate_computational_costs_for_transaction(
 
 pub fn read_estimate_file(
 ) -> Result<BTreeMap<CommonTransactionCosts, GasCostSummary>, anyhow::Error> {
-    let json_str = fs::read_to_string(&ESTIMATE_FILE).unwrap();
+    let json_str = fs::read_to_string(&ESTIMATE_FILE)?;
 
     // Remove the metadata: first 4 lines form snapshot tests
     let json_str = json_str
@@ -64,12 +68,12 @@ pub fn read_estimate_file(
         .collect::<Vec<String>>()
         .join("\n");
 
-    let cost_map: BTreeMap<String, GasCostSummary> = serde_json::from_str(&json_str).unwrap();
+    let cost_map: BTreeMap<String, GasCostSummary> = serde_json::from_str(&json_str)?;
 
-    let cost_map: BTreeMap<CommonTransactionCosts, GasCostSummary> = cost_map
-        .iter()
-        .map(|(k, v)| (CommonTransactionCosts::from_str(k).unwrap(), v.clone()))
-        .collect();
-
-    Ok(cost_map)
+    cost_map
+        .into_iter()
+        .map(|(k, v)| -> Result<_, anyhow::Error> {
+            Ok((CommonTransactionCosts::from_str(&k)?, v))
+        })
+        .collect()
 }
@@ -0,0 +1,218 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::{BTreeMap, BTreeSet};
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sui_types::messages_checkpoint::{CheckpointData, CheckpointSequenceNumber};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::{read_checkpoint, ObjectStore};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(100);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Processing of the checkpoints of the chain by a pipeline.
+#[async_trait]
+pub trait Worker: Send + Sync + 'static {
+    /// Processes a checkpoint, which is retried until it succeeds. A checkpoint can be
+    /// processed again after a restart, if it was not yet recorded as processed, so processing
+    /// should be idempotent.
+    async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<()>;
+}
+
+/// Where the executor records how far each worker got.
+#[async_trait]
+pub trait ProgressStore: Send + Sync {
+    /// The first checkpoint `task` hasn't processed, 0 for a new task.
+    async fn load(&self, task: &str) -> Result<CheckpointSequenceNumber>;
+
+    /// Records that `task` processed all the checkpoints before `next`.
+    async fn save(&self, task: &str, next: CheckpointSequenceNumber) -> Result<()>;
+}
+
+/// Records the progress of the tasks in a JSON file.
+pub struct FileProgressStore {
+    path: PathBuf,
+    progress: Mutex<BTreeMap<String, CheckpointSequenceNumber>>,
+}
+
+impl FileProgressStore {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        let progress = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("Invalid progress file {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            progress: Mutex::new(progress),
+        })
+    }
+}
+
+#[async_trait]
+impl ProgressStore for FileProgressStore {
+    async fn load(&self, task: &str) -> Result<CheckpointSequenceNumber> {
+        Ok(self
+            .progress
+            .lock()
+            .unwrap()
+            .get(task)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn save(&self, task: &str, next: CheckpointSequenceNumber) -> Result<()> {
+        let mut progress = self.progress.lock().unwrap();
+        progress.insert(task.to_string(), next);
+        // Written aside and renamed, so that a crash doesn't leave a truncated file
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, serde_json::to_vec(&*progress)?)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// A worker, and how many checkpoints it processes at once.
+struct WorkerPool {
+    task: String,
+    worker: Arc<dyn Worker>,
+    concurrency: usize,
+}
+
+/// Feeds the checkpoints of an object store to workers, in order, each from where it got to.
+///
+/// Each worker processes up to its concurrency of checkpoints at once, which can complete out
+/// of order. Its progress is the first checkpoint it hasn't processed, so after a restart it
+/// resumes from there, processing again the later checkpoints which had already completed.
+pub struct IngestionExecutor {
+    store: Arc<dyn ObjectStore>,
+    progress: Arc<dyn ProgressStore>,
+    poll_interval: Duration,
+    pools: Vec<WorkerPool>,
+}
+
+impl IngestionExecutor {
+    pub fn new(store: Arc<dyn ObjectStore>, progress: Arc<dyn ProgressStore>) -> Self {
+        Self {
+            store,
+            progress,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            pools: vec![],
+        }
+    }
+
+    /// How long to wait before looking again for a checkpoint which wasn't written yet.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Adds a worker, whose progress is recorded under `task`.
+    pub fn register<W: Worker>(&mut self, task: &str, worker: W, concurrency: usize) {
+        assert!(concurrency > 0, "Workers process at least one checkpoint");
+        self.pools.push(WorkerPool {
+            task: task.to_string(),
+            worker: Arc::new(worker),
+            concurrency,
+        });
+    }
+
+    /// Runs the workers until `exit` completes, or one of them fails to record its progress.
+    pub async fn run(self, exit: impl Future<Output = ()>) -> Result<()> {
+        let (stop, stopped) = watch::channel(false);
+        let pools = try_join_all(self.pools.into_iter().map(|pool| {
+            let handle = tokio::spawn(pool.run(
+                self.store.clone(),
+                self.progress.clone(),
+                self.poll_interval,
+                stopped.clone(),
+            ));
+            async move { handle.await? }
+        }));
+        tokio::pin!(pools);
+        tokio::select! {
+            result = &mut pools => return result.map(|_| ()),
+            _ = exit => (),
+        }
+        let _ = stop.send(true);
+        pools.await?;
+        Ok(())
+    }
+}
+
+impl WorkerPool {
+    async fn run(
+        self,
+        store: Arc<dyn ObjectStore>,
+        progress: Arc<dyn ProgressStore>,
+        poll_interval: Duration,
+        mut stopped: watch::Receiver<bool>,
+    ) -> Result<()> {
+        let mut watermark = progress.load(&self.task).await?;
+        info!("Task {} starting from checkpoint {}", self.task, watermark);
+        let mut next = watermark;
+        let mut processed = BTreeSet::new();
+        let mut in_flight = FuturesUnordered::new();
+        loop {
+            while in_flight.len() < self.concurrency {
+                match read_checkpoint(&*store, next).await {
+                    Ok(Some(checkpoint)) => {
+                        in_flight.push(process(self.worker.clone(), &self.task, checkpoint));
+                        next += 1;
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!("Failed to read checkpoint {}: {:?}", next, e);
+                        break;
+                    }
+                }
+            }
+            tokio::select! {
+                Some(seq) = in_flight.next() => {
+                    processed.insert(seq);
+                    let previous = watermark;
+                    while processed.remove(&watermark) {
+                        watermark += 1;
+                    }
+                    if watermark > previous {
+                        progress.save(&self.task, watermark).await?;
+                    }
+                }
+                // Waits for the next checkpoint to be written
+                _ = tokio::time::sleep(poll_interval), if in_flight.len() < self.concurrency => (),
+                _ = stopped.changed() => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Has `worker` process `checkpoint` until it succeeds, and returns its sequence number.
+async fn process(
+    worker: Arc<dyn Worker>,
+    task: &str,
+    checkpoint: CheckpointData,
+) -> CheckpointSequenceNumber {
+    let seq = checkpoint.sequence_number();
+    let mut delay = MIN_RETRY_DELAY;
+    while let Err(e) = worker.process_checkpoint(&checkpoint).await {
+        warn!(
+            "Task {} failed to process checkpoint {}, retrying in {:?}: {:?}",
+            task, seq, delay, e
+        );
+        tokio::time::sleep(delay).await;
+        delay = std::cmp::min(delay * 2, MAX_RETRY_DELAY);
+    }
+    seq
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ingestion of the chain data from an object store rather than from a fullnode.
+//!
+//! A node configured with a `checkpoint-export` store writes each certified checkpoint there with
+//! the certificates and effects of its transactions, as a blob named after its sequence number
+//! (see [`writer::CheckpointWriter`]). Pipelines then process the checkpoints in order from the
+//! store with [`executor::IngestionExecutor`], which runs their [`executor::Worker`]s
+//! concurrently and records how far each of them got.
+//!
+//! Nodes may also write snapshots of their state to a store every few checkpoints, for new nodes
+//! to start from (see [`snapshot::StateSnapshotWriter`]).
+
+use anyhow::{anyhow, Result};
+use sui_types::messages_checkpoint::{CheckpointData, CheckpointSequenceNumber};
+
+pub mod executor;
+pub mod object_store;
+pub mod snapshot;
+pub mod writer;
+
+pub use executor::{FileProgressStore, IngestionExecutor, ProgressStore, Worker};
+pub use object_store::{make_object_store, FileStore, ObjectStore, S3Store};
+pub use snapshot::{read_latest_snapshot, StateSnapshotWriter};
+pub use writer::CheckpointWriter;
+
+/// Version of the encoding of the checkpoint blobs, their first byte.
+const BLOB_VERSION: u8 = 1;
+
+/// Key of the blob of checkpoint `seq`.
+pub fn checkpoint_key(seq: CheckpointSequenceNumber) -> String {
+    format!("{}.chk", seq)
+}
+
+pub fn encode_checkpoint(checkpoint: &CheckpointData) -> Result<Vec<u8>> {
+    let mut bytes = vec![BLOB_VERSION];
+    bytes.extend(bcs::to_bytes(checkpoint)?);
+    Ok(bytes)
+}
+
+pub fn decode_checkpoint(bytes: &[u8]) -> Result<CheckpointData> {
+    match bytes.split_first() {
+        Some((&BLOB_VERSION, checkpoint)) => Ok(bcs::from_bytes(checkpoint)?),
+        Some((version, _)) => Err(anyhow!("Unknown checkpoint blob version {}", version)),
+        None => Err(anyhow!("Empty checkpoint blob")),
+    }
+}
+
+/// Reads checkpoint `seq` from `store`, or `None` if it wasn't written yet.
+pub async fn read_checkpoint(
+    store: &dyn ObjectStore,
+    seq: CheckpointSequenceNumber,
+) -> Result<Option<CheckpointData>> {
+    match store.get(&checkpoint_key(seq)).await? {
+        Some(bytes) => Ok(Some(decode_checkpoint(&bytes)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Context, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_core::authority::AuthorityState;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::state_snapshot::StateSnapshot;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::ObjectStore;
+
+/// Key of the blob holding the sequence number of the checkpoint of the last snapshot written.
+pub const LATEST_SNAPSHOT_KEY: &str = "snapshot-latest";
+
+/// Version of the encoding of the snapshot blobs, their first byte.
+const SNAPSHOT_VERSION: u8 = 1;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Key of the blob of the snapshot as of checkpoint `seq`.
+pub fn snapshot_key(seq: CheckpointSequenceNumber) -> String {
+    format!("snapshot-{}.snap", seq)
+}
+
+pub fn encode_snapshot(snapshot: &StateSnapshot) -> Result<Vec<u8>> {
+    let mut bytes = vec![SNAPSHOT_VERSION];
+    bytes.extend(bcs::to_bytes(snapshot)?);
+    Ok(bytes)
+}
+
+pub fn decode_snapshot(bytes: &[u8]) -> Result<StateSnapshot> {
+    match bytes.split_first() {
+        Some((&SNAPSHOT_VERSION, snapshot)) => Ok(bcs::from_bytes(snapshot)?),
+        Some((version, _)) => Err(anyhow!("Unknown snapshot blob version {}", version)),
+        None => Err(anyhow!("Empty snapshot blob")),
+    }
+}
+
+/// Reads the latest snapshot written to `store`, or `None` if there is none yet. The snapshot
+/// still has to be verified.
+pub async fn read_latest_snapshot(store: &dyn ObjectStore) -> Result<Option<StateSnapshot>> {
+    let seq = match latest_snapshot_checkpoint(store).await? {
+        Some(seq) => seq,
+        None => return Ok(None),
+    };
+    let bytes = store
+        .get(&snapshot_key(seq))
+        .await?
+        .ok_or_else(|| anyhow!("Missing snapshot of checkpoint {}", seq))?;
+    Ok(Some(decode_snapshot(&bytes)?))
+}
+
+async fn latest_snapshot_checkpoint(
+    store: &dyn ObjectStore,
+) -> Result<Option<CheckpointSequenceNumber>> {
+    match store.get(LATEST_SNAPSHOT_KEY).await? {
+        Some(bytes) => Ok(Some(
+            String::from_utf8(bytes)?
+                .trim()
+                .parse()
+                .context("Invalid latest snapshot")?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Writes a snapshot of the state of a node to an object store every `interval` checkpoints,
+/// once the checkpoint is certified.
+pub struct StateSnapshotWriter {
+    state: Arc<AuthorityState>,
+    store: Arc<dyn ObjectStore>,
+    interval: u64,
+    poll_interval: Duration,
+}
+
+impl StateSnapshotWriter {
+    pub fn new(state: Arc<AuthorityState>, store: Arc<dyn ObjectStore>, interval: u64) -> Self {
+        Self {
+            state,
+            store,
+            interval,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let latest = loop {
+            match latest_snapshot_checkpoint(&*self.store).await {
+                Ok(latest) => break latest,
+                Err(e) => {
+                    error!("Failed to read the last exported snapshot: {:?}", e);
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        };
+        // The first checkpoint of each interval, i.e. the genesis one first
+        let mut next = latest.map_or(0, |seq| (seq / self.interval + 1) * self.interval);
+        info!("Exporting state snapshots from checkpoint {}", next);
+        loop {
+            match self.write_snapshot(next).await {
+                Ok(true) => next += self.interval,
+                Ok(false) => tokio::time::sleep(self.poll_interval).await,
+                Err(e) => {
+                    error!(
+                        "Failed to export the snapshot of checkpoint {}: {:?}",
+                        next, e
+                    );
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        }
+    }
+
+    /// Writes the snapshot of checkpoint `seq`, or returns false if it isn't certified yet.
+    async fn write_snapshot(&self, seq: CheckpointSequenceNumber) -> Result<bool> {
+        let state = self.state.clone();
+        // Reading every object of the store takes a while
+        let snapshot =
+            match tokio::task::spawn_blocking(move || state.get_state_snapshot(seq)).await?? {
+                Some(snapshot) => snapshot,
+                None => return Ok(false),
+            };
+        self.store
+            .put(&snapshot_key(seq), encode_snapshot(&snapshot)?)
+            .await?;
+        self.store
+            .put(LATEST_SNAPSHOT_KEY, seq.to_string().into_bytes())
+            .await?;
+        info!(
+            "Exported the snapshot of checkpoint {} with {} objects",
+            seq,
+            snapshot.objects.len()
+        );
+        Ok(true)
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use std::time::Duration;
+use sui_core::authority::AuthorityState;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info};
+
+use crate::{checkpoint_key, encode_checkpoint, ObjectStore};
+
+/// Key of the blob holding the sequence number of the last checkpoint written, which the writer
+/// resumes after when restarted.
+pub const LATEST_CHECKPOINT_KEY: &str = "latest";
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Writes the certified checkpoints of a node to an object store, in order, each once it has the
+/// certificates and effects of all its transactions.
+pub struct CheckpointWriter {
+    state: Arc<AuthorityState>,
+    store: Arc<dyn ObjectStore>,
+    poll_interval: Duration,
+}
+
+impl CheckpointWriter {
+    pub fn new(state: Arc<AuthorityState>, store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            state,
+            store,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        let mut next = loop {
+            match self.next_checkpoint().await {
+                Ok(next) => break next,
+                Err(e) => {
+                    error!("Failed to read the last exported checkpoint: {:?}", e);
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        };
+        info!("Exporting checkpoints from checkpoint {}", next);
+        loop {
+            match self.write_checkpoint(next).await {
+                Ok(true) => next += 1,
+                Ok(false) => tokio::time::sleep(self.poll_interval).await,
+                // The checkpoint is written again until it succeeds, the blobs have no gaps
+                Err(e) => {
+                    error!("Failed to export checkpoint {}: {:?}", next, e);
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+            }
+        }
+    }
+
+    async fn next_checkpoint(&self) -> Result<CheckpointSequenceNumber> {
+        match self.store.get(LATEST_CHECKPOINT_KEY).await? {
+            Some(bytes) => {
+                let latest: CheckpointSequenceNumber = String::from_utf8(bytes)?
+                    .trim()
+                    .parse()
+                    .context("Invalid latest checkpoint")?;
+                Ok(latest + 1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Writes checkpoint `seq`, or returns false if the node can't build it yet.
+    async fn write_checkpoint(&self, seq: CheckpointSequenceNumber) -> Result<bool> {
+        let checkpoint = match self.state.get_checkpoint_data(seq)? {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(false),
+        };
+        self.store
+            .put(&checkpoint_key(seq), encode_checkpoint(&checkpoint)?)
+            .await?;
+        self.store
+            .put(LATEST_CHECKPOINT_KEY, seq.to_string().into_bytes())
+            .await?;
+        debug!(
+            "Exported checkpoint {} with {} transactions",
+            seq,
+            checkpoint.transactions.len()
+        );
+        Ok(true)
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fail points, named places in the code where tests inject faults, e.g. crash a validator right
+//! after it committed a transaction to its database, to check how it recovers.
+//!
+//! The `fail_point!` macro only evaluates fail points in simulator builds and with the `enabled`
+//! feature, it expands to nothing otherwise. Tests configure what a fail point does with
+//! `configure`, validators built with fail points also let operators do so from their admin
+//! server. Async code uses `fail_point_async!` instead, which sleeps without blocking the
+//! executor thread.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::warn;
+
+/// Whether `fail_point!` evaluates fail points in this build.
+pub const ENABLED: bool = cfg!(any(msim, feature = "enabled"));
+
+/// Evaluates fail point `name`, doing what it is configured to. In the second form, the
+/// enclosing function returns `$ret` if the fail point is configured to `Action::Return`.
+/// Sleeping blocks the thread, so async code uses `fail_point_async!`.
+#[cfg(any(msim, feature = "enabled"))]
+#[macro_export]
+macro_rules! fail_point {
+    ($name:expr) => {
+        $crate::eval($name);
+    };
+    ($name:expr, $ret:expr) => {
+        if $crate::eval($name) {
+            return $ret;
+        }
+    };
+}
+
+#[cfg(not(any(msim, feature = "enabled")))]
+#[macro_export]
+macro_rules! fail_point {
+    ($name:expr) => {};
+    ($name:expr, $ret:expr) => {};
+}
+
+/// Like `fail_point!`, in an async function: sleeping waits on a timer rather than blocking the
+/// thread.
+#[cfg(any(msim, feature = "enabled"))]
+#[macro_export]
+macro_rules! fail_point_async {
+    ($name:expr) => {
+        $crate::eval_async($name).await;
+    };
+    ($name:expr, $ret:expr) => {
+        if $crate::eval_async($name).await {
+            return $ret;
+        }
+    };
+}
+
+#[cfg(not(any(msim, feature = "enabled")))]
+#[macro_export]
+macro_rules! fail_point_async {
+    ($name:expr) => {};
+    ($name:expr, $ret:expr) => {};
+}
+
+/// What a fail point does when the code reaches it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Panics, e.g. to crash the node.
+    Panic,
+    /// Waits for a while, e.g. to widen a race window. `fail_point!` blocks the thread,
+    /// `fail_point_async!` only the task.
+    Sleep(Duration),
+    /// Returns early from the enclosing function, with the error the fail point provides. Fail
+    /// points which don't provide one ignore it.
+    Return,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Panic => write!(f, "panic"),
+            Action::Sleep(duration) => write!(f, "sleep({})", duration.as_millis()),
+            Action::Return => write!(f, "return"),
+        }
+    }
+}
+
+/// Parses `panic`, `return` or `sleep(<milliseconds>)`.
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "panic" => Ok(Action::Panic),
+            "return" => Ok(Action::Return),
+            s => s
+                .strip_prefix("sleep(")
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|millis| millis.trim().parse().ok())
+                .map(|millis| Action::Sleep(Duration::from_millis(millis)))
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown fail point action '{}', expected panic, return or sleep(<ms>)",
+                        s
+                    )
+                }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FailPoint {
+    action: Option<Action>,
+    /// Times the code reached the fail point, whether it was configured or not.
+    hits: u64,
+}
+
+static FAIL_POINTS: Lazy<Mutex<HashMap<String, FailPoint>>> = Lazy::new(Default::default);
+
+/// Makes fail point `name` do `action` from now on, replacing what it did before.
+pub fn configure(name: &str, action: Action) {
+    FAIL_POINTS
+        .lock()
+        .unwrap()
+        .entry(name.to_string())
+        .or_default()
+        .action = Some(action);
+}
+
+/// Makes fail point `name` do `action` until the returned guard is dropped, e.g. until the end
+/// of a test.
+#[must_use]
+pub fn configure_scoped(name: &str, action: Action) -> FailPointGuard {
+    configure(name, action);
+    FailPointGuard {
+        name: name.to_string(),
+    }
+}
+
+/// Makes fail point `name` do nothing again.
+pub fn remove(name: &str) {
+    if let Some(fail_point) = FAIL_POINTS.lock().unwrap().get_mut(name) {
+        fail_point.action = None;
+    }
+}
+
+/// Makes every fail point do nothing again.
+pub fn clear() {
+    for fail_point in FAIL_POINTS.lock().unwrap().values_mut() {
+        fail_point.action = None;
+    }
+}
+
+/// The fail points which do something, with what they do, by name.
+pub fn configured() -> Vec<(String, Action)> {
+    let mut configured: Vec<_> = FAIL_POINTS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|(name, fail_point)| Some((name.clone(), fail_point.action.clone()?)))
+        .collect();
+    configured.sort_by(|(a, _), (b, _)| a.cmp(b));
+    configured
+}
+
+/// Times the code reached fail point `name` since the process started, e.g. for a test to wait
+/// until a validator got past it.
+pub fn hits(name: &str) -> u64 {
+    FAIL_POINTS
+        .lock()
+        .unwrap()
+        .get(name)
+        .map_or(0, |fail_point| fail_point.hits)
+}
+
+/// Does what fail point `name` is configured to, returning whether the enclosing function should
+/// return early. Use `fail_point!` instead, which compiles this out of builds without fail points.
+#[doc(hidden)]
+pub fn eval(name: &str) -> bool {
+    match trigger(name) {
+        Some(Action::Sleep(duration)) => {
+            std::thread::sleep(duration);
+            false
+        }
+        action => action == Some(Action::Return),
+    }
+}
+
+/// Like `eval`, sleeping on a timer. Use `fail_point_async!` instead.
+#[doc(hidden)]
+pub async fn eval_async(name: &str) -> bool {
+    match trigger(name) {
+        Some(Action::Sleep(duration)) => {
+            tokio::time::sleep(duration).await;
+            false
+        }
+        action => action == Some(Action::Return),
+    }
+}
+
+/// Counts a hit of fail point `name` and returns what it is configured to do, panicking right
+/// away if that is to panic.
+fn trigger(name: &str) -> Option<Action> {
+    // The lock is released before acting, so that a panic doesn't poison it
+    let action = {
+        let mut fail_points = FAIL_POINTS.lock().unwrap();
+        let fail_point = fail_points.entry(name.to_string()).or_default();
+        fail_point.hits += 1;
+        fail_point.action.clone()
+    };
+    match &action {
+        None => {}
+        Some(Action::Panic) => {
+            warn!(fail_point = name, "Fail point triggered, panicking");
+            panic!("Fail point {} triggered", name);
+        }
+        Some(Action::Sleep(duration)) => {
+            warn!(
+                fail_point = name,
+                ?duration,
+                "Fail point triggered, sleeping"
+            );
+        }
+        Some(Action::Return) => {
+            warn!(fail_point = name, "Fail point triggered, returning early");
+        }
+    }
+    action
+}
+
+/// Makes a fail point configured by `configure_scoped` do nothing again once dropped.
+pub struct FailPointGuard {
+    name: String,
+}
+
+impl Drop for FailPointGuard {
+    fn drop(&mut self) {
+        remove(&self.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_from_str() {
+        for action in [
+            Action::Panic,
+            Action::Return,
+            Action::Sleep(Duration::from_millis(250)),
+        ] {
+            assert_eq!(action.to_string().parse::<Action>().unwrap(), action);
+        }
+        assert_eq!(
+            "sleep( 10 )".parse::<Action>().unwrap(),
+            Action::Sleep(Duration::from_millis(10))
+        );
+        assert!("sleep(ten)".parse::<Action>().is_err());
+        assert!("crash".parse::<Action>().is_err());
+    }
+
+    #[test]
+    fn test_eval() {
+        let name = "test-eval";
+        assert!(!eval(name));
+        {
+            let _guard = configure_scoped(name, Action::Return);
+            assert!(eval(name));
+            assert!(configured().contains(&(name.to_string(), Action::Return)));
+        }
+        assert!(!eval(name));
+        assert_eq!(hits(name), 3);
+
+        configure(name, Action::Panic);
+        assert!(std::panic::catch_unwind(|| eval(name)).is_err());
+        remove(name);
+        assert!(!eval(name));
+        assert_eq!(hits(name), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_eval_async() {
+        let name = "test-eval-async";
+        assert!(!eval_async(name).await);
+
+        let _guard = configure_scoped(name, Action::Sleep(Duration::from_secs(10)));
+        let start = tokio::time::Instant::now();
+        assert!(!eval_async(name).await);
+        assert!(start.elapsed() >= Duration::from_secs(10));
+
+        configure(name, Action::Return);
+        assert!(eval_async(name).await);
+        assert_eq!(hits(name), 3);
+    }
+}
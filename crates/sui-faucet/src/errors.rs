@@ -19,4 +19,25 @@ pub enum FaucetError {
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("Too many requests: {0}")]
+    RateLimited(String),
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum GasPoolError {
+    #[error("No gas coin of the pool covers a budget of {0}")]
+    NoCoinAvailable(u64),
+
+    #[error("Unknown or expired gas lease {0}")]
+    UnknownLease(uuid::Uuid),
+
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+
+    #[error("Wallet Error: `{0}`")]
+    Wallet(String),
+
+    #[error("Transaction execution failed: {0}")]
+    Execution(String),
 }
@@ -0,0 +1,104 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use sui_config::SUI_CLIENT_CONFIG;
+use sui_swarm::memory::Swarm;
+use sui_types::base_types::SuiAddress;
+use test_utils::network::setup_network_and_wallet;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{Faucet, FaucetError, FaucetReceipt, SimpleFaucet};
+
+/// An in-process faucet backed by a local swarm, for Rust tests which need funded accounts
+/// without running the faucet HTTP server:
+///
+/// ```ignore
+/// let faucet = LocalFaucet::start().await?;
+/// let coin = faucet.fund(address, 10_000).await?;
+/// ```
+///
+/// The swarm runs as long as the faucet is alive. Other crates get it with the `test-utils`
+/// feature.
+pub struct LocalFaucet {
+    swarm: Swarm,
+    faucet: SimpleFaucet,
+    address: SuiAddress,
+}
+
+impl LocalFaucet {
+    /// Starts a local swarm, and a faucet sending the coins of its first genesis account.
+    pub async fn start() -> Result<Self, FaucetError> {
+        let (swarm, wallet, address) = setup_network_and_wallet()
+            .await
+            .map_err(|err| FaucetError::Internal(format!("Fail to start local swarm: {err}")))?;
+        info!("LocalFaucet::start with faucet address: {address}");
+        let prometheus_registry = prometheus::Registry::new();
+        let faucet = SimpleFaucet::new(wallet, &prometheus_registry).await?;
+        Ok(Self {
+            swarm,
+            faucet,
+            address,
+        })
+    }
+
+    /// The swarm backing the faucet.
+    pub fn swarm(&self) -> &Swarm {
+        &self.swarm
+    }
+
+    /// Path of the client config of the swarm, to create wallets for the funded addresses.
+    pub fn wallet_config(&self) -> PathBuf {
+        self.swarm.dir().join(SUI_CLIENT_CONFIG)
+    }
+
+    /// The address the coins are sent from.
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+}
+
+#[async_trait]
+impl Faucet for LocalFaucet {
+    async fn send(
+        &self,
+        id: Uuid,
+        recipient: SuiAddress,
+        amounts: &[u64],
+    ) -> Result<FaucetReceipt, FaucetError> {
+        self.faucet.send(id, recipient, amounts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sui::client_commands::WalletContext;
+    use sui_types::gas_coin::GasCoin;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_faucet_fund() {
+        telemetry_subscribers::init_for_testing();
+        let faucet = LocalFaucet::start().await.unwrap();
+        let recipient = SuiAddress::random_for_testing_only();
+
+        let coin = faucet.fund(recipient, 1000).await.unwrap();
+        assert_eq!(coin.amount, 1000);
+
+        let context = WalletContext::new(&faucet.wallet_config()).await.unwrap();
+        let object = context
+            .client
+            .read_api()
+            .get_parsed_object(coin.id)
+            .await
+            .unwrap()
+            .into_object()
+            .unwrap();
+        assert_eq!(object.owner, recipient);
+        assert_eq!(GasCoin::try_from(&object).unwrap().value(), 1000);
+    }
+}
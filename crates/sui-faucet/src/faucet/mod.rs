@@ -10,8 +10,12 @@ use sui_types::{
 };
 use uuid::Uuid;
 
+#[cfg(any(test, feature = "test-utils"))]
+mod local_faucet;
 mod simple_faucet;
-pub use self::simple_faucet::SimpleFaucet;
+#[cfg(any(test, feature = "test-utils"))]
+pub use self::local_faucet::LocalFaucet;
+pub use self::simple_faucet::{CoinPoolConfig, SimpleFaucet};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FaucetReceipt {
@@ -33,6 +37,14 @@ pub trait Faucet {
         recipient: SuiAddress,
         amounts: &[u64],
     ) -> Result<FaucetReceipt, FaucetError>;
+
+    /// Send a single `Coin<SUI>` of `amount` to the recipient
+    async fn fund(&self, recipient: SuiAddress, amount: u64) -> Result<CoinInfo, FaucetError> {
+        let FaucetReceipt { sent } = self.send(Uuid::new_v4(), recipient, &[amount]).await?;
+        sent.into_iter().next().ok_or_else(|| {
+            FaucetError::Transfer(format!("No coin of {amount} sent to {recipient}"))
+        })
+    }
 }
 
 impl<'a> FromIterator<&'a SuiParsedObject> for FaucetReceipt {
@@ -7,9 +7,7 @@ use async_trait::async_trait;
 use crate::metrics::FaucetMetrics;
 use prometheus::Registry;
 
-// HashSet is in fact used but linter does not think so
-#[allow(unused_imports)]
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use sui::client_commands::{SuiClientCommands, WalletContext};
 use sui_json_rpc_types::{
@@ -21,7 +19,7 @@ use sui_types::{
     messages::{Transaction, TransactionData},
 };
 use tokio::sync::{
-    mpsc::{self, Receiver, Sender},
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
     Mutex,
 };
 use tokio::time::Duration;
@@ -30,11 +28,28 @@ use uuid::Uuid;
 
 use crate::{CoinInfo, Faucet, FaucetError, FaucetReceipt};
 
+/// How the faucet keeps enough coins to serve concurrent requests, each coin serving one
+/// transfer at a time.
+#[derive(Clone, Debug)]
+pub struct CoinPoolConfig {
+    /// Number of coins the pool is refilled to.
+    pub target_coins: usize,
+    /// Balance of the coins split to refill the pool.
+    pub coin_balance: u64,
+    /// Coins of a lower balance can no longer serve a request, and leave the pool.
+    pub min_coin_balance: u64,
+}
+
 pub struct SimpleFaucet {
     wallet: WalletContext,
     active_address: SuiAddress,
-    producer: Mutex<Sender<ObjectID>>,
-    consumer: Mutex<Receiver<ObjectID>>,
+    producer: Mutex<UnboundedSender<ObjectID>>,
+    consumer: Mutex<UnboundedReceiver<ObjectID>>,
+    coin_pool: Option<CoinPoolConfig>,
+    /// The coins queued or in use, including the retired ones.
+    pool_coins: Mutex<HashSet<ObjectID>>,
+    /// Coins which leave the pool the next time they are dequeued.
+    retired: Mutex<HashSet<ObjectID>>,
     metrics: FaucetMetrics,
 }
 
@@ -52,7 +67,7 @@ impl SimpleFaucet {
 
         // Sync to have the latest status
         SuiClientCommands::SyncClientState {
-            address: Some(active_address),
+            address: Some(active_address.into()),
         }
         .execute(&mut wallet)
         .await
@@ -67,9 +82,9 @@ impl SimpleFaucet {
             .map(|q| GasCoin::try_from(&q.1).unwrap())
             .collect::<Vec<GasCoin>>();
 
-        let (producer, consumer) = mpsc::channel(coins.len());
+        let (producer, consumer) = mpsc::unbounded_channel();
         for coin in &coins {
-            if let Err(e) = producer.send(*coin.id()).await {
+            if let Err(e) = producer.send(*coin.id()) {
                 panic!("Failed to set up gas pools: {:?}", e);
             }
         }
@@ -77,16 +92,152 @@ impl SimpleFaucet {
         debug!("Using coins: {:?}", coins);
 
         let metrics = FaucetMetrics::new(prometheus_registry);
+        metrics.pool_coins.set(coins.len() as i64);
 
         Ok(Self {
             wallet,
             active_address,
             producer: Mutex::new(producer),
             consumer: Mutex::new(consumer),
+            coin_pool: None,
+            pool_coins: Mutex::new(coins.iter().map(|coin| *coin.id()).collect()),
+            retired: Mutex::new(HashSet::new()),
             metrics,
         })
     }
 
+    /// Refills the pool of coins as configured by `coin_pool`, instead of only sending from the
+    /// coins the faucet starts with.
+    pub fn with_coin_pool(mut self, coin_pool: CoinPoolConfig) -> Self {
+        self.coin_pool = Some(coin_pool);
+        self
+    }
+
+    /// Retires the coins of the pool whose balance is too low to serve a request, and splits
+    /// coins off the largest one until the pool has its target number of coins again, in one
+    /// transaction. Returns the number of coins added.
+    pub async fn refill(&self) -> Result<usize, FaucetError> {
+        let config = match &self.coin_pool {
+            Some(config) => config,
+            None => return Ok(0),
+        };
+        let balances: HashMap<ObjectID, u64> = self
+            .wallet
+            .gas_objects(self.active_address)
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?
+            .into_iter()
+            .map(|(balance, _, info)| (info.object_id, balance))
+            .collect();
+        let active = {
+            let pool_coins = self.pool_coins.lock().await;
+            let mut retired = self.retired.lock().await;
+            for coin in pool_coins.iter() {
+                // The coins in use are not mutated yet, or already are
+                if balances
+                    .get(coin)
+                    .map_or(false, |balance| *balance < config.min_coin_balance)
+                {
+                    retired.insert(*coin);
+                }
+            }
+            pool_coins.len() - retired.len()
+        };
+        self.metrics.pool_coins.set(active as i64);
+        // One coin is split, and another one pays for the gas
+        if active >= config.target_coins || active < 2 {
+            return Ok(0);
+        }
+
+        let coins = self.select_coins(2).await;
+        let result = self
+            .split_coins(config, &coins, &balances, config.target_coins - active)
+            .await;
+        let producer = self.producer.lock().await;
+        for coin in coins.iter().chain(result.iter().flatten()) {
+            if let Err(e) = producer.send(*coin) {
+                panic!("Failed to put coin {:?} back to queue: {:?}", coin, e);
+            }
+        }
+        drop(producer);
+        let created = result?;
+        self.pool_coins.lock().await.extend(&created);
+        self.metrics.pool_coins.set((active + created.len()) as i64);
+        info!(count = created.len(), "Refilled the coin pool");
+        Ok(created.len())
+    }
+
+    /// Refills the pool every `interval`, forever. Panics if `interval` is zero.
+    pub async fn run_refill(&self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.refill().await {
+                warn!("Failed to refill the coin pool: {e}");
+            }
+        }
+    }
+
+    /// Splits up to `count` coins of the configured balance off the larger of `coins`, paying
+    /// for the gas with the other one.
+    async fn split_coins(
+        &self,
+        config: &CoinPoolConfig,
+        coins: &[ObjectID],
+        balances: &HashMap<ObjectID, u64>,
+        count: usize,
+    ) -> Result<Vec<ObjectID>, FaucetError> {
+        let balance = |coin: &ObjectID| balances.get(coin).copied().unwrap_or(0);
+        let (coin, gas) = if balance(&coins[0]) >= balance(&coins[1]) {
+            (coins[0], coins[1])
+        } else {
+            (coins[1], coins[0])
+        };
+        let count = count.min(
+            (balance(&coin).saturating_sub(config.min_coin_balance) / config.coin_balance.max(1))
+                as usize,
+        );
+        if count == 0 {
+            return Ok(vec![]);
+        }
+        let data = self
+            .wallet
+            .client
+            .transaction_builder()
+            .split_coin(
+                self.active_address,
+                coin,
+                vec![config.coin_balance; count],
+                Some(gas),
+                DEFAULT_GAS_BUDGET,
+            )
+            .await
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let signature = self
+            .wallet
+            .keystore
+            .sign(&self.active_address, &data.to_bytes())
+            .map_err(|e| FaucetError::Wallet(e.to_string()))?;
+        let response = self
+            .wallet
+            .client
+            .quorum_driver()
+            .execute_transaction(Transaction::new(data, signature))
+            .await
+            .map_err(|e| FaucetError::Transfer(e.to_string()))?;
+        if let SuiExecutionStatus::Failure { error } = response.effects.status {
+            return Err(FaucetError::Transfer(format!(
+                "Failed to split coin {coin}: {error}"
+            )));
+        }
+        Ok(response
+            .effects
+            .created
+            .iter()
+            .map(|created| created.reference.object_id)
+            .collect())
+    }
+
     async fn select_coins(&self, number_of_coins: usize) -> Vec<ObjectID> {
         assert!(number_of_coins > 0);
         // If the gas candidate queue is exhausted, the request will be
@@ -96,6 +247,11 @@ impl SimpleFaucet {
         let mut consumer = self.consumer.lock().await;
         let mut coins = Vec::with_capacity(number_of_coins);
         while let Some(coin) = consumer.recv().await {
+            if self.retired.lock().await.remove(&coin) {
+                self.pool_coins.lock().await.remove(&coin);
+                info!(?coin, "Retired coin from the pool");
+                continue;
+            }
             // TODO: for now we assume each SUI object is enough to cover the split
             // but this may not be true, if we run the faucet for really really long time or
             // due to some other unexpected issues.
@@ -139,7 +295,7 @@ impl SimpleFaucet {
         // in that the channel is initialized with big enough capacity.
         let producer = self.producer.lock().await;
         for coin in coins {
-            if let Err(e) = producer.send(coin).await {
+            if let Err(e) = producer.send(coin) {
                 panic!("Failed to put coin {:?} back to queue: {:?}", coin, e);
             }
         }
@@ -365,13 +521,13 @@ mod tests {
     async fn test_init_gas_queue() {
         let (_network, mut context, address) = setup_network_and_wallet().await.unwrap();
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(&mut context)
         .await
         .unwrap();
         let gases = match results {
-            SuiClientCommandResult::Gas(gases) => gases,
+            SuiClientCommandResult::Gas(gases, _) => gases,
             other => panic!("Expect SuiClientCommandResult::Gas, but got {:?}", other),
         };
         let gases = HashSet::from_iter(gases.into_iter().map(|gas| *gas.id()));
@@ -390,13 +546,13 @@ mod tests {
     async fn test_transfer_state() {
         let (_network, mut context, address) = setup_network_and_wallet().await.unwrap();
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(&mut context)
         .await
         .unwrap();
         let gases = match results {
-            SuiClientCommandResult::Gas(gases) => gases,
+            SuiClientCommandResult::Gas(gases, _) => gases,
             other => panic!("Expect SuiClientCommandResult::Gas, but got {:?}", other),
         };
         let gases = HashSet::from_iter(gases.into_iter().map(|gas| *gas.id()));
@@ -427,6 +583,27 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_refill_coin_pool() {
+        let (_network, context, _address) = setup_network_and_wallet().await.unwrap();
+        let prom_registry = prometheus::Registry::new();
+        let mut faucet = SimpleFaucet::new(context, &prom_registry)
+            .await
+            .unwrap()
+            .with_coin_pool(CoinPoolConfig {
+                target_coins: 7,
+                coin_balance: 10000,
+                min_coin_balance: 1000,
+            });
+        let coins = faucet.pool_coins.lock().await.len();
+
+        assert_eq!(faucet.refill().await.unwrap(), 7 - coins);
+        assert_eq!(faucet.refill().await.unwrap(), 0);
+        // The new coins serve requests too
+        let candidates = faucet.drain_gas_queue(7).await;
+        assert_eq!(candidates, *faucet.pool_coins.lock().await);
+    }
+
     async fn test_basic_interface(faucet: impl Faucet) {
         let recipient = SuiAddress::random_for_testing_only();
         let amounts = vec![1, 2, 3];
@@ -0,0 +1,521 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A gas pool for applications sending many transactions: it owns a pool of gas coins, leases
+//! them to application servers for a limited time, and signs and executes the transactions built
+//! on a leased coin.
+//!
+//! Transactions pay for gas with a coin of their sender, so the transactions run through the
+//! pool are sent by its address. They can only use shared and immutable objects besides the
+//! leased coin, e.g. to mint objects for the users of the application, and never the other coins
+//! of the pool. A lease which is not used before it expires goes back to the pool, and the pool
+//! is rebalanced in the background, splitting large coins and merging small ones so that it
+//! keeps enough coins of the target balance.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sui::client_commands::WalletContext;
+use sui_json_rpc_types::SuiTransactionResponse;
+use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
+use sui_types::messages::{InputObjectKind, SingleTransactionKind, Transaction, TransactionData};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::GasPoolError;
+
+const REBALANCE_GAS_BUDGET: u64 = 1000;
+
+#[derive(Clone, Debug)]
+pub struct GasPoolConfig {
+    /// Number of coins the pool keeps, so that as many transactions can run concurrently.
+    pub target_coins: usize,
+    /// Balance of the coins split by the pool.
+    pub target_balance: u64,
+    /// How long a lease lasts if its coin is not used.
+    pub lease_duration: Duration,
+}
+
+/// A gas coin leased to an application, to build a transaction sent by `sender` which pays gas
+/// with `gas` and a budget of at most `gas_budget`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GasLease {
+    pub lease_id: Uuid,
+    pub sender: SuiAddress,
+    pub gas: ObjectRef,
+    pub gas_budget: u64,
+    pub expires_in_ms: u64,
+}
+
+struct Lease {
+    coin: ObjectID,
+    gas_budget: u64,
+    expiry: Instant,
+}
+
+#[derive(Clone, Copy)]
+enum RebalanceStep {
+    Split {
+        coin: ObjectID,
+        count: u64,
+        gas: ObjectID,
+    },
+    Merge {
+        primary: ObjectID,
+        merged: ObjectID,
+        gas: ObjectID,
+    },
+}
+
+#[derive(Default)]
+struct PoolState {
+    /// The coins which are neither leased nor rebalanced, with their balance.
+    free: BTreeMap<ObjectID, (ObjectRef, u64)>,
+    leases: HashMap<Uuid, Lease>,
+    /// Coins used by a transaction of the pool or of a lease.
+    busy: HashSet<ObjectID>,
+}
+
+pub struct GasPool {
+    wallet: WalletContext,
+    address: SuiAddress,
+    config: GasPoolConfig,
+    state: Mutex<PoolState>,
+}
+
+impl GasPool {
+    /// A pool of the gas coins of the active address of `wallet`.
+    pub async fn new(
+        mut wallet: WalletContext,
+        config: GasPoolConfig,
+    ) -> Result<Self, GasPoolError> {
+        let address = wallet
+            .active_address()
+            .map_err(|e| GasPoolError::Wallet(e.to_string()))?;
+        info!(?address, "Starting gas pool");
+        let pool = Self {
+            wallet,
+            address,
+            config,
+            state: Mutex::new(PoolState::default()),
+        };
+        pool.refresh().await?;
+        Ok(pool)
+    }
+
+    pub fn address(&self) -> SuiAddress {
+        self.address
+    }
+
+    /// Leases the coin of the smallest balance covering `gas_budget`.
+    pub async fn reserve(&self, gas_budget: u64) -> Result<GasLease, GasPoolError> {
+        let mut state = self.state.lock().await;
+        let (coin, (gas, _)) = state
+            .free
+            .iter()
+            .filter(|(_, (_, balance))| *balance >= gas_budget)
+            .min_by_key(|(_, (_, balance))| *balance)
+            .map(|(coin, entry)| (*coin, *entry))
+            .ok_or(GasPoolError::NoCoinAvailable(gas_budget))?;
+        state.free.remove(&coin);
+        let lease_id = Uuid::new_v4();
+        state.leases.insert(
+            lease_id,
+            Lease {
+                coin,
+                gas_budget,
+                expiry: Instant::now() + self.config.lease_duration,
+            },
+        );
+        debug!(?lease_id, ?coin, gas_budget, "Leased gas coin");
+        Ok(GasLease {
+            lease_id,
+            sender: self.address,
+            gas,
+            gas_budget,
+            expires_in_ms: self.config.lease_duration.as_millis() as u64,
+        })
+    }
+
+    /// Returns the coin of a lease which won't be used.
+    pub async fn release(&self, lease_id: Uuid) -> Result<(), GasPoolError> {
+        let lease = self
+            .state
+            .lock()
+            .await
+            .leases
+            .remove(&lease_id)
+            .ok_or(GasPoolError::UnknownLease(lease_id))?;
+        debug!(?lease_id, coin = ?lease.coin, "Released gas lease");
+        self.refresh().await
+    }
+
+    /// Signs and executes `data`, which must pay gas with the coin of the lease. The lease ends,
+    /// whether the transaction succeeds or not.
+    pub async fn execute(
+        &self,
+        lease_id: Uuid,
+        data: TransactionData,
+    ) -> Result<SuiTransactionResponse, GasPoolError> {
+        let lease = {
+            let mut state = self.state.lock().await;
+            let lease = state
+                .leases
+                .remove(&lease_id)
+                .ok_or(GasPoolError::UnknownLease(lease_id))?;
+            if lease.expiry < Instant::now() {
+                return Err(GasPoolError::UnknownLease(lease_id));
+            }
+            if let Err(e) = self.check_transaction(&state, &lease, &data) {
+                state.leases.insert(lease_id, lease);
+                return Err(e);
+            }
+            state.busy.insert(lease.coin);
+            lease
+        };
+
+        let result = self.sign_and_execute(data).await;
+        self.state.lock().await.busy.remove(&lease.coin);
+        self.refresh().await?;
+        result
+    }
+
+    /// Ends the expired leases, so that their coins can be leased again. Their transactions can
+    /// no longer be executed, since only the pool signs them.
+    pub async fn reclaim_expired(&self) -> Result<usize, GasPoolError> {
+        let now = Instant::now();
+        let expired = {
+            let mut state = self.state.lock().await;
+            let before = state.leases.len();
+            state.leases.retain(|_, lease| lease.expiry >= now);
+            before - state.leases.len()
+        };
+        if expired > 0 {
+            info!(expired, "Reclaimed expired gas leases");
+            self.refresh().await?;
+        }
+        Ok(expired)
+    }
+
+    /// Runs one rebalancing transaction if needed: splits the largest free coin when the pool
+    /// has fewer coins than its target, or merges the two smallest when it has more.
+    pub async fn rebalance(&self) -> Result<(), GasPoolError> {
+        let (coins, step) = {
+            let mut state = self.state.lock().await;
+            let total = state.free.len() + state.leases.len() + state.busy.len();
+            let mut by_balance: Vec<_> = state
+                .free
+                .iter()
+                .map(|(coin, (_, balance))| (*balance, *coin))
+                .collect();
+            by_balance.sort();
+            // Another free coin pays for the rebalancing
+            let gas = match by_balance
+                .iter()
+                .find(|(balance, _)| *balance >= REBALANCE_GAS_BUDGET)
+            {
+                Some((_, gas)) => *gas,
+                None => return Ok(()),
+            };
+            let others: Vec<_> = by_balance
+                .into_iter()
+                .filter(|(_, coin)| *coin != gas)
+                .collect();
+            let step = if total < self.config.target_coins {
+                let (balance, coin) = match others.last() {
+                    Some(largest) => *largest,
+                    None => return Ok(()),
+                };
+                let count = ((self.config.target_coins - total + 1) as u64)
+                    .min(balance / self.config.target_balance.max(1));
+                if count < 2 {
+                    return Ok(());
+                }
+                RebalanceStep::Split { coin, count, gas }
+            } else if total > self.config.target_coins && others.len() >= 2 {
+                RebalanceStep::Merge {
+                    primary: others[1].1,
+                    merged: others[0].1,
+                    gas,
+                }
+            } else {
+                return Ok(());
+            };
+            let coins = match step {
+                RebalanceStep::Split { coin, gas, .. } => vec![coin, gas],
+                RebalanceStep::Merge {
+                    primary,
+                    merged,
+                    gas,
+                } => vec![primary, merged, gas],
+            };
+            for coin in &coins {
+                state.free.remove(coin);
+                state.busy.insert(*coin);
+            }
+            (coins, step)
+        };
+
+        let builder = self.wallet.client.transaction_builder();
+        let data = match step {
+            RebalanceStep::Split { coin, count, gas } => {
+                info!(?coin, count, "Splitting gas coin");
+                builder
+                    .split_coin_equal(self.address, coin, count, Some(gas), REBALANCE_GAS_BUDGET)
+                    .await
+            }
+            RebalanceStep::Merge {
+                primary,
+                merged,
+                gas,
+            } => {
+                info!(?primary, ?merged, "Merging gas coins");
+                builder
+                    .merge_coins(
+                        self.address,
+                        primary,
+                        merged,
+                        Some(gas),
+                        REBALANCE_GAS_BUDGET,
+                    )
+                    .await
+            }
+        };
+        let result = match data {
+            Ok(data) => self.sign_and_execute(data).await.map(|_| ()),
+            Err(e) => Err(GasPoolError::Wallet(e.to_string())),
+        };
+        {
+            let mut state = self.state.lock().await;
+            for coin in &coins {
+                state.busy.remove(coin);
+            }
+        }
+        self.refresh().await?;
+        result
+    }
+
+    /// Reclaims expired leases and rebalances the pool every `interval`, forever.
+    pub async fn run_maintenance(&self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reclaim_expired().await {
+                warn!("Failed to reclaim gas leases: {e}");
+            }
+            if let Err(e) = self.rebalance().await {
+                warn!("Failed to rebalance gas pool: {e}");
+            }
+        }
+    }
+
+    /// The number of free and leased coins.
+    pub async fn coin_counts(&self) -> (usize, usize) {
+        let state = self.state.lock().await;
+        (state.free.len(), state.leases.len())
+    }
+
+    fn check_transaction(
+        &self,
+        state: &PoolState,
+        lease: &Lease,
+        data: &TransactionData,
+    ) -> Result<(), GasPoolError> {
+        let invalid = |reason: &str| Err(GasPoolError::InvalidTransaction(reason.to_string()));
+        if data.signer() != self.address {
+            return invalid("the sender must be the address of the gas pool");
+        }
+        if data.gas_payment_object_ref().0 != lease.coin {
+            return invalid("the gas must be paid with the leased coin");
+        }
+        if data.gas_budget > lease.gas_budget {
+            return invalid("the gas budget exceeds the budget of the lease");
+        }
+        for single in data.kind.single_transactions() {
+            if matches!(single, SingleTransactionKind::TransferSui(_)) {
+                return invalid("SUI can't be transferred out of the leased coin");
+            }
+            let inputs = single
+                .input_objects()
+                .map_err(|e| GasPoolError::InvalidTransaction(e.to_string()))?;
+            for input in inputs {
+                if let InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) = input {
+                    if id == lease.coin
+                        || state.free.contains_key(&id)
+                        || state.busy.contains(&id)
+                        || state.leases.values().any(|lease| lease.coin == id)
+                    {
+                        return invalid("the coins of the gas pool can't be used");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn sign_and_execute(
+        &self,
+        data: TransactionData,
+    ) -> Result<SuiTransactionResponse, GasPoolError> {
+        let signature = self
+            .wallet
+            .keystore
+            .sign(&self.address, &data.to_bytes())
+            .map_err(|e| GasPoolError::Wallet(e.to_string()))?;
+        self.wallet
+            .execute_transaction(Transaction::new(data, signature))
+            .await
+            .map_err(|e| GasPoolError::Execution(e.to_string()))
+    }
+
+    /// Reloads the coins of the pool, those which are neither leased nor busy being free.
+    async fn refresh(&self) -> Result<(), GasPoolError> {
+        let coins = self
+            .wallet
+            .gas_objects(self.address)
+            .await
+            .map_err(|e| GasPoolError::Wallet(e.to_string()))?;
+        let mut state = self.state.lock().await;
+        let taken: HashSet<_> = state
+            .leases
+            .values()
+            .map(|lease| lease.coin)
+            .chain(state.busy.iter().copied())
+            .collect();
+        state.free = coins
+            .into_iter()
+            .filter(|(_, _, info)| !taken.contains(&info.object_id))
+            .map(|(balance, _, info)| (info.object_id, (info.to_object_ref(), balance)))
+            .collect();
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReserveGasRequest {
+    pub gas_budget: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExecuteTransactionRequest {
+    pub lease_id: Uuid,
+    /// Base64 encoded BCS bytes of the transaction data.
+    pub tx_bytes: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReleaseGasRequest {
+    pub lease_id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use sui_json::SuiJsonValue;
+    use sui_json_rpc_types::SuiExecutionStatus;
+    use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+    use test_utils::network::setup_network_and_wallet;
+
+    use super::*;
+
+    fn config() -> GasPoolConfig {
+        GasPoolConfig {
+            target_coins: 5,
+            target_balance: 10000,
+            lease_duration: Duration::from_secs(60),
+        }
+    }
+
+    async fn mint_nft(pool: &GasPool, lease: &GasLease) -> TransactionData {
+        pool.wallet
+            .client
+            .transaction_builder()
+            .move_call(
+                lease.sender,
+                SUI_FRAMEWORK_OBJECT_ID,
+                "devnet_nft",
+                "mint",
+                vec![],
+                vec![
+                    SuiJsonValue::from_str("\"name\"").unwrap(),
+                    SuiJsonValue::from_str("\"description\"").unwrap(),
+                    SuiJsonValue::from_str("\"ipfs://nft\"").unwrap(),
+                ],
+                Some(lease.gas.0),
+                lease.gas_budget,
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_gas_pool_lease() {
+        let (_network, context, _address) = setup_network_and_wallet().await.unwrap();
+        let pool = GasPool::new(context, config()).await.unwrap();
+        let (free, _) = pool.coin_counts().await;
+
+        let lease = pool.reserve(1000).await.unwrap();
+        assert_eq!(pool.coin_counts().await, (free - 1, 1));
+        let data = mint_nft(&pool, &lease).await;
+        let response = pool.execute(lease.lease_id, data).await.unwrap();
+        assert_eq!(response.effects.status, SuiExecutionStatus::Success);
+        assert_eq!(response.effects.created.len(), 1);
+        // The coin is back in the pool, and the lease is over
+        assert_eq!(pool.coin_counts().await, (free, 0));
+        let data = mint_nft(&pool, &lease).await;
+        assert!(pool.execute(lease.lease_id, data).await.is_err());
+
+        // The other coins of the pool can't be used
+        let lease = pool.reserve(1000).await.unwrap();
+        let other = pool.reserve(1000).await.unwrap();
+        let data = pool
+            .wallet
+            .client
+            .transaction_builder()
+            .transfer_object(
+                lease.sender,
+                other.gas.0,
+                Some(lease.gas.0),
+                1000,
+                SuiAddress::random_for_testing_only(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            pool.execute(lease.lease_id, data).await,
+            Err(GasPoolError::InvalidTransaction(_))
+        ));
+        pool.release(lease.lease_id).await.unwrap();
+        pool.release(other.lease_id).await.unwrap();
+        assert_eq!(pool.coin_counts().await, (free, 0));
+    }
+
+    #[tokio::test]
+    async fn test_gas_pool_expiry_and_rebalance() {
+        let (_network, context, _address) = setup_network_and_wallet().await.unwrap();
+        let pool = GasPool::new(
+            context,
+            GasPoolConfig {
+                target_coins: 7,
+                lease_duration: Duration::ZERO,
+                ..config()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(pool.coin_counts().await, (5, 0));
+
+        let lease = pool.reserve(1000).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(pool.reclaim_expired().await.unwrap(), 1);
+        let data = mint_nft(&pool, &lease).await;
+        assert!(pool.execute(lease.lease_id, data).await.is_err());
+
+        // Splits a coin to reach the target
+        pool.rebalance().await.unwrap();
+        assert_eq!(pool.coin_counts().await, (7, 0));
+    }
+}
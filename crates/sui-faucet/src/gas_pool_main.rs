@@ -0,0 +1,185 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{
+    error_handling::HandleErrorLayer,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    BoxError, Extension, Json, Router,
+};
+use clap::Parser;
+use std::{
+    borrow::Cow,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+use sui::client_commands::WalletContext;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_faucet::{
+    ExecuteTransactionRequest, GasPool, GasPoolConfig, GasPoolError, ReleaseGasRequest,
+    ReserveGasRequest,
+};
+use sui_types::crypto::SignableBytes;
+use sui_types::messages::TransactionData;
+use sui_types::sui_serde::{Base64, Encoding};
+use tower::ServiceBuilder;
+use tracing::{info, warn};
+
+#[derive(Parser)]
+#[clap(
+    name = "Sui Gas Pool",
+    about = "Leases gas coins to applications and executes their transactions",
+    rename_all = "kebab-case"
+)]
+struct GasPoolArgs {
+    #[clap(long, default_value_t = 5004)]
+    port: u16,
+
+    #[clap(long, default_value = "127.0.0.1")]
+    host_ip: IpAddr,
+
+    /// Number of gas coins kept in the pool.
+    #[clap(long, default_value_t = 100)]
+    target_coins: usize,
+
+    /// Balance of the coins split to refill the pool.
+    #[clap(long, default_value_t = 100000)]
+    target_balance: u64,
+
+    #[clap(long, default_value_t = 30)]
+    lease_duration_in_seconds: u64,
+
+    /// How often expired leases are reclaimed and the pool is rebalanced.
+    #[clap(long, default_value_t = 10)]
+    maintenance_interval_in_seconds: u64,
+
+    #[clap(long, default_value_t = 100)]
+    request_buffer_size: usize,
+
+    #[clap(long, default_value_t = 120)]
+    timeout_in_seconds: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    // initialize tracing
+    let _guard = telemetry_subscribers::TelemetryConfig::new(env!("CARGO_BIN_NAME"))
+        .with_env()
+        .init();
+
+    let args = GasPoolArgs::parse();
+    let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+    info!("Initialize wallet from config path: {:?}", wallet_conf);
+    let context = WalletContext::new(&wallet_conf).await?;
+
+    let pool = Arc::new(
+        GasPool::new(
+            context,
+            GasPoolConfig {
+                target_coins: args.target_coins,
+                target_balance: args.target_balance,
+                lease_duration: Duration::from_secs(args.lease_duration_in_seconds),
+            },
+        )
+        .await?,
+    );
+
+    let maintained = pool.clone();
+    let interval = Duration::from_secs(args.maintenance_interval_in_seconds);
+    tokio::spawn(async move { maintained.run_maintenance(interval).await });
+
+    let app = Router::new()
+        .route("/", get(health))
+        .route("/v1/reserve", post(reserve))
+        .route("/v1/execute", post(execute))
+        .route("/v1/release", post(release))
+        .layer(
+            ServiceBuilder::new()
+                .layer(HandleErrorLayer::new(handle_error))
+                .buffer(args.request_buffer_size)
+                .timeout(Duration::from_secs(args.timeout_in_seconds))
+                .layer(Extension(pool))
+                .into_inner(),
+        );
+
+    let addr = SocketAddr::new(args.host_ip, args.port);
+    info!("listening on {}", addr);
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await?;
+    Ok(())
+}
+
+async fn health() -> &'static str {
+    "OK"
+}
+
+async fn reserve(
+    Json(request): Json<ReserveGasRequest>,
+    Extension(pool): Extension<Arc<GasPool>>,
+) -> impl IntoResponse {
+    match pool.reserve(request.gas_budget).await {
+        Ok(lease) => (
+            StatusCode::CREATED,
+            Json(serde_json::to_value(lease).unwrap()),
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+async fn execute(
+    Json(request): Json<ExecuteTransactionRequest>,
+    Extension(pool): Extension<Arc<GasPool>>,
+) -> impl IntoResponse {
+    let data = match Base64::decode(&request.tx_bytes)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| TransactionData::from_signable_bytes(&bytes).map_err(|e| e.to_string()))
+    {
+        Ok(data) => data,
+        Err(e) => return error_response(GasPoolError::InvalidTransaction(e)),
+    };
+    match pool.execute(request.lease_id, data).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(serde_json::to_value(response).unwrap()),
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+async fn release(
+    Json(request): Json<ReleaseGasRequest>,
+    Extension(pool): Extension<Arc<GasPool>>,
+) -> impl IntoResponse {
+    match pool.release(request.lease_id).await {
+        Ok(()) => (StatusCode::OK, Json(serde_json::Value::Null)),
+        Err(e) => error_response(e),
+    }
+}
+
+fn error_response(error: GasPoolError) -> (StatusCode, Json<serde_json::Value>) {
+    warn!("Gas pool request failed: {error}");
+    let status = match error {
+        GasPoolError::NoCoinAvailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        GasPoolError::UnknownLease(_) => StatusCode::NOT_FOUND,
+        GasPoolError::InvalidTransaction(_) => StatusCode::BAD_REQUEST,
+        GasPoolError::Wallet(_) | GasPoolError::Execution(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        Json(serde_json::json!({ "error": error.to_string() })),
+    )
+}
+
+async fn handle_error(error: BoxError) -> impl IntoResponse {
+    if error.is::<tower::timeout::error::Elapsed>() {
+        return (StatusCode::REQUEST_TIMEOUT, Cow::from("request timed out"));
+    }
+
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Cow::from(format!("Unhandled internal error: {}", error)),
+    )
+}
@@ -3,11 +3,15 @@
 
 mod errors;
 mod faucet;
+mod gas_pool;
 mod metrics;
+mod rate_limiter;
 mod requests;
 mod responses;
 
-pub use errors::FaucetError;
+pub use errors::{FaucetError, GasPoolError};
 pub use faucet::*;
+pub use gas_pool::*;
+pub use rate_limiter::*;
 pub use requests::*;
 pub use responses::*;
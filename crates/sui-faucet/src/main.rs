@@ -3,6 +3,7 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
+    extract::ConnectInfo,
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
@@ -13,13 +14,17 @@ use http::Method;
 use std::env;
 use std::{
     borrow::Cow,
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
 use sui::client_commands::WalletContext;
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
-use sui_faucet::{Faucet, FaucetRequest, FaucetResponse, SimpleFaucet};
+use sui_faucet::{
+    CoinPoolConfig, Faucet, FaucetRequest, FaucetResponse, RateLimitConfig, RateLimiter,
+    SimpleFaucet,
+};
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
@@ -38,7 +43,7 @@ struct FaucetConfig {
     port: u16,
 
     #[clap(long, default_value = "127.0.0.1")]
-    host_ip: Ipv4Addr,
+    host_ip: IpAddr,
 
     #[clap(long, default_value_t = 50000)]
     amount: u64,
@@ -51,12 +56,40 @@ struct FaucetConfig {
 
     #[clap(long, default_value_t = 120)]
     timeout_in_seconds: u64,
+
+    /// The client config of the wallet sending the coins, e.g. of a local network or of a
+    /// devnet keypair. Defaults to the client config in the Sui config directory.
+    #[clap(long)]
+    wallet_config: Option<PathBuf>,
+
+    /// Requests accepted from each IP address per rate limit window.
+    #[clap(long, default_value_t = 10)]
+    max_requests_per_ip: u32,
+
+    /// Requests accepted for each recipient per rate limit window.
+    #[clap(long, default_value_t = 5)]
+    max_requests_per_address: u32,
+
+    #[clap(long, default_value_t = 3600, value_parser = clap::value_parser!(u64).range(1..))]
+    rate_limit_window_in_seconds: u64,
+
+    /// Number of coins the pool of the faucet is refilled to, by splitting its largest coin.
+    /// The pool is not refilled when 0.
+    #[clap(long, default_value_t = 0)]
+    target_coins: usize,
+
+    /// Balance of the coins split to refill the pool.
+    #[clap(long, default_value_t = 10_000_000)]
+    coin_balance: u64,
+
+    #[clap(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    refill_interval_in_seconds: u64,
 }
 
 struct AppState<F = SimpleFaucet> {
     faucet: F,
+    rate_limiter: RateLimiter,
     config: FaucetConfig,
-    // TODO: add counter
 }
 
 const PROM_PORT_ADDR: &str = "0.0.0.0:9184";
@@ -74,15 +107,19 @@ async fn main() -> Result<(), anyhow::Error> {
     };
     info!("Max concurrency: {max_concurrency}.");
 
-    let context = create_wallet_context().await?;
-
     let config: FaucetConfig = FaucetConfig::parse();
 
+    let context = create_wallet_context(config.wallet_config.clone()).await?;
+
     let FaucetConfig {
         host_ip,
         port,
         request_buffer_size,
         timeout_in_seconds,
+        target_coins,
+        coin_balance,
+        refill_interval_in_seconds,
+        rate_limit_window_in_seconds,
         ..
     } = config;
 
@@ -90,13 +127,46 @@ async fn main() -> Result<(), anyhow::Error> {
     info!("Starting Prometheus HTTP endpoint at {}", prom_binding);
     let prometheus_registry = sui_node::metrics::start_prometheus_server(prom_binding);
 
+    let mut faucet = SimpleFaucet::new(context, &prometheus_registry)
+        .await
+        .unwrap();
+    if target_coins > 0 {
+        faucet = faucet.with_coin_pool(CoinPoolConfig {
+            target_coins,
+            coin_balance,
+            // Each coin of a request is split off a coin of the pool, which pays for the gas
+            min_coin_balance: config.amount + 1000,
+        });
+    }
+    let rate_limiter = RateLimiter::new(
+        RateLimitConfig {
+            max_requests_per_ip: config.max_requests_per_ip,
+            max_requests_per_address: config.max_requests_per_address,
+            window: Duration::from_secs(rate_limit_window_in_seconds),
+        },
+        &prometheus_registry,
+    );
     let app_state = Arc::new(AppState {
-        faucet: SimpleFaucet::new(context, &prometheus_registry)
-            .await
-            .unwrap(),
+        faucet,
+        rate_limiter,
         config,
     });
 
+    if target_coins > 0 {
+        let refilled = app_state.clone();
+        let refill_interval = Duration::from_secs(refill_interval_in_seconds);
+        tokio::spawn(async move { refilled.faucet.run_refill(refill_interval).await });
+    }
+
+    let pruned = app_state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(rate_limit_window_in_seconds));
+        loop {
+            interval.tick().await;
+            pruned.rate_limiter.prune();
+        }
+    });
+
     // TODO: restrict access if needed
     let cors = CorsLayer::new()
         .allow_methods(vec![Method::GET, Method::POST])
@@ -117,10 +187,10 @@ async fn main() -> Result<(), anyhow::Error> {
                 .into_inner(),
         );
 
-    let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
+    let addr = SocketAddr::new(host_ip, port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -132,14 +202,19 @@ async fn health() -> &'static str {
 
 /// handler for all the request_gas requests
 async fn request_gas(
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
     Json(payload): Json<FaucetRequest>,
     Extension(state): Extension<Arc<AppState>>,
 ) -> impl IntoResponse {
     // ID for traceability
     let id = Uuid::new_v4();
-    info!(uuid = ?id, "Got new gas request.");
+    info!(uuid = ?id, ?client, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            if let Err(e) = state.rate_limiter.check(client.ip(), requests.recipient) {
+                warn!(uuid =?id, "Rejected gas request: {e}");
+                return (StatusCode::TOO_MANY_REQUESTS, Json(FaucetResponse::from(e)));
+            }
             state
                 .faucet
                 .send(
@@ -165,8 +240,13 @@ async fn request_gas(
     }
 }
 
-async fn create_wallet_context() -> Result<WalletContext, anyhow::Error> {
-    let wallet_conf = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+async fn create_wallet_context(
+    wallet_config: Option<PathBuf>,
+) -> Result<WalletContext, anyhow::Error> {
+    let wallet_conf = match wallet_config {
+        Some(wallet_config) => wallet_config,
+        None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+    };
     info!("Initialize wallet from config path: {:?}", wallet_conf);
     WalletContext::new(&wallet_conf).await
 }
@@ -14,6 +14,7 @@ pub struct FaucetMetrics {
     pub(crate) total_requests_succeeded: IntCounter,
     pub(crate) current_requests_in_flight: IntGauge,
     pub(crate) process_latency: Histogram,
+    pub(crate) pool_coins: IntGauge,
 }
 const LATENCY_SEC_BUCKETS: &[f64] = &[
     0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1., 2.5, 5., 10., 20., 30., 60., 90.,
@@ -47,6 +48,12 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            pool_coins: register_int_gauge_with_registry!(
+                "pool_coins",
+                "Number of coins in the pool of the Faucet which can serve requests",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -0,0 +1,194 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limits of the requests a client can make to the faucet: at most `max_requests_per_ip` from
+//! each IP address and `max_requests_per_address` for each recipient per window, so that a
+//! single client can't drain the faucet.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
+use sui_types::base_types::SuiAddress;
+
+use crate::FaucetError;
+
+#[derive(Clone, Debug)]
+pub struct RateLimitConfig {
+    pub max_requests_per_ip: u32,
+    pub max_requests_per_address: u32,
+    pub window: Duration,
+}
+
+/// The number of requests of each key in the current window, which starts on its first one.
+struct Windows<K> {
+    windows: HashMap<K, (Instant, u32)>,
+}
+
+impl<K: Eq + Hash> Windows<K> {
+    fn new() -> Self {
+        Self {
+            windows: HashMap::new(),
+        }
+    }
+
+    fn is_full(&self, key: &K, max_requests: u32, window: Duration, now: Instant) -> bool {
+        match self.windows.get(key) {
+            Some((start, count)) => now.duration_since(*start) < window && *count >= max_requests,
+            None => false,
+        }
+    }
+
+    fn count(&mut self, key: K, window: Duration, now: Instant) {
+        let entry = self.windows.entry(key).or_insert((now, 0));
+        if now.duration_since(entry.0) >= window {
+            *entry = (now, 0);
+        }
+        entry.1 += 1;
+    }
+
+    fn prune(&mut self, window: Duration, now: Instant) {
+        self.windows
+            .retain(|_, (start, _)| now.duration_since(*start) < window);
+    }
+}
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    per_ip: Mutex<Windows<IpAddr>>,
+    per_address: Mutex<Windows<SuiAddress>>,
+    total_requests_rate_limited: IntCounter,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig, registry: &Registry) -> Self {
+        Self {
+            config,
+            per_ip: Mutex::new(Windows::new()),
+            per_address: Mutex::new(Windows::new()),
+            total_requests_rate_limited: register_int_counter_with_registry!(
+                "total_requests_rate_limited",
+                "Total number of requests rejected by the rate limits of the Faucet",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Counts a request from `ip` for `recipient`, unless either is over its limit. Rejected
+    /// requests don't count.
+    pub fn check(&self, ip: IpAddr, recipient: SuiAddress) -> Result<(), FaucetError> {
+        let RateLimitConfig {
+            max_requests_per_ip,
+            max_requests_per_address,
+            window,
+        } = self.config;
+        let now = Instant::now();
+        let mut per_ip = self.per_ip.lock().unwrap();
+        let mut per_address = self.per_address.lock().unwrap();
+        let limit = if per_ip.is_full(&ip, max_requests_per_ip, window, now) {
+            Some(format!(
+                "more than {max_requests_per_ip} requests from {ip} in {window:?}"
+            ))
+        } else if per_address.is_full(&recipient, max_requests_per_address, window, now) {
+            Some(format!(
+                "more than {max_requests_per_address} requests for {recipient} in {window:?}"
+            ))
+        } else {
+            None
+        };
+        if let Some(limit) = limit {
+            self.total_requests_rate_limited.inc();
+            return Err(FaucetError::RateLimited(limit));
+        }
+        per_ip.count(ip, window, now);
+        per_address.count(recipient, window, now);
+        Ok(())
+    }
+
+    /// Forgets the windows which are over, so that the clients seen once don't accumulate.
+    pub fn prune(&self) {
+        let now = Instant::now();
+        self.per_ip.lock().unwrap().prune(self.config.window, now);
+        self.per_address
+            .lock()
+            .unwrap()
+            .prune(self.config.window, now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig {
+                max_requests_per_ip: 3,
+                max_requests_per_address: 2,
+                window: Duration::from_secs(60),
+            },
+            &Registry::new(),
+        );
+        let (ip, other_ip) = (
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)),
+        );
+        let (recipient, other_recipient) = (
+            SuiAddress::random_for_testing_only(),
+            SuiAddress::random_for_testing_only(),
+        );
+
+        limiter.check(ip, recipient).unwrap();
+        limiter.check(other_ip, recipient).unwrap();
+        // The recipient is over its limit, whichever IP asks
+        assert!(matches!(
+            limiter.check(ip, recipient),
+            Err(FaucetError::RateLimited(_))
+        ));
+        limiter.check(ip, other_recipient).unwrap();
+
+        // The IP is over its limit, whichever recipient it asks for
+        let limiter = RateLimiter::new(
+            RateLimitConfig {
+                max_requests_per_address: 10,
+                ..limiter.config.clone()
+            },
+            &Registry::new(),
+        );
+        for _ in 0..3 {
+            limiter
+                .check(ip, SuiAddress::random_for_testing_only())
+                .unwrap();
+        }
+        assert!(limiter
+            .check(ip, SuiAddress::random_for_testing_only())
+            .is_err());
+        limiter.check(other_ip, recipient).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let limiter = RateLimiter::new(
+            RateLimitConfig {
+                max_requests_per_ip: 1,
+                max_requests_per_address: 1,
+                window: Duration::ZERO,
+            },
+            &Registry::new(),
+        );
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let recipient = SuiAddress::random_for_testing_only();
+        // A new window starts with each request
+        limiter.check(ip, recipient).unwrap();
+        limiter.check(ip, recipient).unwrap();
+        limiter.prune();
+        assert!(limiter.per_ip.lock().unwrap().windows.is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A JSON description of the interface of Move packages: the public and entry functions of their
+//! modules, with their parameter and return types, and the layouts of their structs. Types are
+//! written as in Move source, with addresses in full, e.g. `&mut 0x2::coin::Coin<T0>`, type
+//! parameters being named `T0`, `T1`, ... in order.
+
+use move_binary_format::file_format::{Ability, AbilitySet, Visibility};
+use move_binary_format::normalized::{Function, Module, Struct, Type};
+use move_binary_format::CompiledModule;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageAbi {
+    /// The name the package is embedded as, e.g. `sui-framework`.
+    pub name: String,
+    pub address: String,
+    pub modules: Vec<ModuleAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleAbi {
+    pub name: String,
+    pub structs: Vec<StructAbi>,
+    /// The functions callable from other modules or from transactions.
+    pub functions: Vec<FunctionAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructAbi {
+    pub name: String,
+    pub abilities: Vec<String>,
+    pub type_parameters: Vec<StructTypeParameterAbi>,
+    pub fields: Vec<FieldAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructTypeParameterAbi {
+    pub constraints: Vec<String>,
+    pub is_phantom: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldAbi {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionAbi {
+    pub name: String,
+    /// `public` or `friend`, or `private` for entry functions only callable from transactions.
+    pub visibility: String,
+    pub is_entry: bool,
+    /// The constraints of each type parameter.
+    pub type_parameters: Vec<Vec<String>>,
+    pub parameters: Vec<String>,
+    pub returns: Vec<String>,
+}
+
+/// The ABI of package `name`, whose modules are `modules`.
+pub fn package_abi(name: &str, modules: &[CompiledModule]) -> PackageAbi {
+    let modules: Vec<Module> = modules.iter().map(Module::new).collect();
+    PackageAbi {
+        name: name.to_string(),
+        address: modules
+            .first()
+            .map(|module| module.address.to_hex_literal())
+            .unwrap_or_default(),
+        modules: modules.iter().map(module_abi).collect(),
+    }
+}
+
+fn module_abi(module: &Module) -> ModuleAbi {
+    ModuleAbi {
+        name: module.name.to_string(),
+        structs: module
+            .structs
+            .iter()
+            .map(|(name, s)| struct_abi(name.as_str(), s))
+            .collect(),
+        functions: module
+            .exposed_functions
+            .iter()
+            .map(|(name, f)| function_abi(name.as_str(), f))
+            .collect(),
+    }
+}
+
+fn struct_abi(name: &str, s: &Struct) -> StructAbi {
+    StructAbi {
+        name: name.to_string(),
+        abilities: abilities(s.abilities),
+        type_parameters: s
+            .type_parameters
+            .iter()
+            .map(|param| StructTypeParameterAbi {
+                constraints: abilities(param.constraints),
+                is_phantom: param.is_phantom,
+            })
+            .collect(),
+        fields: s
+            .fields
+            .iter()
+            .map(|field| FieldAbi {
+                name: field.name.to_string(),
+                type_: type_string(&field.type_),
+            })
+            .collect(),
+    }
+}
+
+fn function_abi(name: &str, f: &Function) -> FunctionAbi {
+    let visibility = match f.visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+        Visibility::Private => "private",
+    };
+    FunctionAbi {
+        name: name.to_string(),
+        visibility: visibility.to_string(),
+        is_entry: f.is_entry,
+        type_parameters: f.type_parameters.iter().map(|c| abilities(*c)).collect(),
+        parameters: f.parameters.iter().map(type_string).collect(),
+        returns: f.return_.iter().map(type_string).collect(),
+    }
+}
+
+fn abilities(set: AbilitySet) -> Vec<String> {
+    set.into_iter()
+        .map(|ability| {
+            match ability {
+                Ability::Copy => "copy",
+                Ability::Drop => "drop",
+                Ability::Store => "store",
+                Ability::Key => "key",
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+fn type_string(type_: &Type) -> String {
+    match type_ {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::Address => "address".to_string(),
+        Type::Signer => "signer".to_string(),
+        Type::Vector(element) => format!("vector<{}>", type_string(element)),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            let mut string = format!("{}::{}::{}", address.to_hex_literal(), module, name);
+            if !type_arguments.is_empty() {
+                let arguments: Vec<String> = type_arguments.iter().map(type_string).collect();
+                string.push_str(&format!("<{}>", arguments.join(", ")));
+            }
+            string
+        }
+        Type::TypeParameter(index) => format!("T{}", index),
+        Type::Reference(inner) => format!("&{}", type_string(inner)),
+        Type::MutableReference(inner) => format!("&mut {}", type_string(inner)),
+    }
+}
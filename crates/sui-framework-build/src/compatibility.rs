@@ -0,0 +1,248 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks that a new build of Move packages can replace the published one without breaking what
+//! depends on it: objects of its structs, which must keep their layout, and the modules and
+//! transactions calling its public and entry functions, which must keep their signature. Friend
+//! and private functions may change freely, and new modules, structs and functions may be added.
+
+use crate::abi::{FunctionAbi, ModuleAbi, PackageAbi, StructAbi};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The incompatibilities found between a published build of packages and a new one, none if the
+/// new build can replace the published one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub incompatibilities: Vec<Incompatibility>,
+}
+
+impl CompatibilityReport {
+    pub fn is_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+}
+
+impl fmt::Display for CompatibilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_compatible() {
+            return writeln!(f, "Compatible");
+        }
+        writeln!(
+            f,
+            "{} incompatibilities found:",
+            self.incompatibilities.len()
+        )?;
+        for incompatibility in &self.incompatibilities {
+            writeln!(f, "  {}", incompatibility)?;
+        }
+        Ok(())
+    }
+}
+
+/// A change of the new build which breaks something depending on the published one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Incompatibility {
+    pub package: String,
+    /// The module changed, unless the whole package is.
+    pub module: Option<String>,
+    pub kind: IncompatibilityKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum IncompatibilityKind {
+    PackageRemoved,
+    PackageAddressChanged {
+        old: String,
+        new: String,
+    },
+    ModuleRemoved,
+    StructRemoved {
+        name: String,
+    },
+    /// The abilities, type parameters or fields of the struct changed, so that objects of the
+    /// published struct can't be read as the new one.
+    StructLayoutChanged {
+        name: String,
+        old: StructAbi,
+        new: StructAbi,
+    },
+    /// A public or entry function was removed, or is neither public nor entry anymore.
+    FunctionRemoved {
+        name: String,
+    },
+    /// The visibility, type parameters, parameters or returns of a public function changed, or
+    /// the type parameters or parameters of an entry function.
+    FunctionSignatureChanged {
+        name: String,
+        old: FunctionAbi,
+        new: FunctionAbi,
+    },
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.module {
+            Some(module) => write!(f, "{}::{}: ", self.package, module)?,
+            None => write!(f, "{}: ", self.package)?,
+        }
+        match &self.kind {
+            IncompatibilityKind::PackageRemoved => write!(f, "package removed"),
+            IncompatibilityKind::PackageAddressChanged { old, new } => {
+                write!(f, "package moved from {} to {}", old, new)
+            }
+            IncompatibilityKind::ModuleRemoved => write!(f, "module removed"),
+            IncompatibilityKind::StructRemoved { name } => write!(f, "struct {} removed", name),
+            IncompatibilityKind::StructLayoutChanged { name, .. } => {
+                write!(f, "layout of struct {} changed", name)
+            }
+            IncompatibilityKind::FunctionRemoved { name } => {
+                write!(f, "function {} removed", name)
+            }
+            IncompatibilityKind::FunctionSignatureChanged { name, old, new } => write!(
+                f,
+                "signature of function {} changed from {} to {}",
+                name,
+                signature(old),
+                signature(new)
+            ),
+        }
+    }
+}
+
+/// Checks that the packages of the `new` build can replace those of the `published` one, packages
+/// being matched by name.
+pub fn check_compatibility(published: &[PackageAbi], new: &[PackageAbi]) -> CompatibilityReport {
+    let mut incompatibilities = Vec::new();
+    for old_package in published {
+        let package = old_package.name.clone();
+        let new_package = match new.iter().find(|p| p.name == old_package.name) {
+            Some(new_package) => new_package,
+            None => {
+                incompatibilities.push(Incompatibility {
+                    package,
+                    module: None,
+                    kind: IncompatibilityKind::PackageRemoved,
+                });
+                continue;
+            }
+        };
+        if old_package.address != new_package.address {
+            incompatibilities.push(Incompatibility {
+                package: package.clone(),
+                module: None,
+                kind: IncompatibilityKind::PackageAddressChanged {
+                    old: old_package.address.clone(),
+                    new: new_package.address.clone(),
+                },
+            });
+        }
+        for old_module in &old_package.modules {
+            let kinds = match new_package
+                .modules
+                .iter()
+                .find(|m| m.name == old_module.name)
+            {
+                Some(new_module) => check_module(old_module, new_module),
+                None => vec![IncompatibilityKind::ModuleRemoved],
+            };
+            incompatibilities.extend(kinds.into_iter().map(|kind| Incompatibility {
+                package: package.clone(),
+                module: Some(old_module.name.clone()),
+                kind,
+            }));
+        }
+    }
+    CompatibilityReport { incompatibilities }
+}
+
+fn check_module(old: &ModuleAbi, new: &ModuleAbi) -> Vec<IncompatibilityKind> {
+    let mut kinds = Vec::new();
+    for old_struct in &old.structs {
+        match new.structs.iter().find(|s| s.name == old_struct.name) {
+            None => kinds.push(IncompatibilityKind::StructRemoved {
+                name: old_struct.name.clone(),
+            }),
+            Some(new_struct) if new_struct != old_struct => {
+                kinds.push(IncompatibilityKind::StructLayoutChanged {
+                    name: old_struct.name.clone(),
+                    old: old_struct.clone(),
+                    new: new_struct.clone(),
+                })
+            }
+            Some(_) => (),
+        }
+    }
+    for old_function in old.functions.iter().filter(|f| is_depended_on(f)) {
+        match new
+            .functions
+            .iter()
+            .find(|f| f.name == old_function.name && is_depended_on(f))
+        {
+            None => kinds.push(IncompatibilityKind::FunctionRemoved {
+                name: old_function.name.clone(),
+            }),
+            Some(new_function) if !keeps_signature(old_function, new_function) => {
+                kinds.push(IncompatibilityKind::FunctionSignatureChanged {
+                    name: old_function.name.clone(),
+                    old: old_function.clone(),
+                    new: new_function.clone(),
+                })
+            }
+            Some(_) => (),
+        }
+    }
+    kinds
+}
+
+/// Whether other packages or transactions may call `function`.
+fn is_depended_on(function: &FunctionAbi) -> bool {
+    function.visibility == "public" || function.is_entry
+}
+
+/// Whether `new` can be called as `old` was, by other modules if `old` was public and by
+/// transactions if it was an entry function.
+fn keeps_signature(old: &FunctionAbi, new: &FunctionAbi) -> bool {
+    if old.type_parameters != new.type_parameters || old.parameters != new.parameters {
+        return false;
+    }
+    if old.visibility == "public" && (new.visibility != "public" || old.returns != new.returns) {
+        return false;
+    }
+    !old.is_entry || new.is_entry
+}
+
+fn signature(function: &FunctionAbi) -> String {
+    let mut signature = String::new();
+    if function.visibility != "private" {
+        signature.push_str(&function.visibility);
+        signature.push(' ');
+    }
+    if function.is_entry {
+        signature.push_str("entry ");
+    }
+    signature.push_str(&format!("fun {}", function.name));
+    if !function.type_parameters.is_empty() {
+        let type_parameters: Vec<String> = function
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(i, constraints)| {
+                if constraints.is_empty() {
+                    format!("T{}", i)
+                } else {
+                    format!("T{}: {}", i, constraints.join(" + "))
+                }
+            })
+            .collect();
+        signature.push_str(&format!("<{}>", type_parameters.join(", ")));
+    }
+    signature.push_str(&format!("({})", function.parameters.join(", ")));
+    match function.returns.len() {
+        0 => (),
+        1 => signature.push_str(&format!(": {}", function.returns[0])),
+        _ => signature.push_str(&format!(": ({})", function.returns.join(", "))),
+    }
+    signature
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The constants declared by Move modules, e.g. `const MAX_MEMO_LENGTH: u64 = 256;` in
+//! `sui::memo`, exported to Rust so that crates use the values of the framework rather than
+//! copies of them.
+//!
+//! Bytecode doesn't keep the names of constants, so they are read from the sources of the
+//! modules, then checked against the constant pools of the modules compiled from them. Only the
+//! constants of type `bool`, `u8`, `u64`, `u128` or `address` with a literal value are exported,
+//! and those declared `#[test_only]` are left out.
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::SignatureToken;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::value::MoveValue;
+use std::fmt::Write;
+use sui_types::error::{SuiError, SuiResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveConstant {
+    /// The name of the module declaring the constant, e.g. `memo`.
+    pub module: String,
+    pub name: String,
+    pub value: ConstantValue,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstantValue {
+    Bool(bool),
+    U8(u8),
+    U64(u64),
+    U128(u128),
+    Address(AccountAddress),
+}
+
+impl ConstantValue {
+    fn parse(type_: &str, value: &str) -> Option<Self> {
+        let value = value.replace('_', "");
+        match type_ {
+            "bool" => value.parse().ok().map(ConstantValue::Bool),
+            "u8" => parse_integer(&value, "u8").map(|v| ConstantValue::U8(v as u8)),
+            "u64" => parse_integer(&value, "u64").map(|v| ConstantValue::U64(v as u64)),
+            "u128" => parse_integer(&value, "u128").map(ConstantValue::U128),
+            "address" => AccountAddress::from_hex_literal(value.strip_prefix('@')?)
+                .ok()
+                .map(ConstantValue::Address),
+            _ => None,
+        }
+    }
+
+    fn signature_token(&self) -> SignatureToken {
+        match self {
+            ConstantValue::Bool(_) => SignatureToken::Bool,
+            ConstantValue::U8(_) => SignatureToken::U8,
+            ConstantValue::U64(_) => SignatureToken::U64,
+            ConstantValue::U128(_) => SignatureToken::U128,
+            ConstantValue::Address(_) => SignatureToken::Address,
+        }
+    }
+
+    fn move_value(&self) -> MoveValue {
+        match *self {
+            ConstantValue::Bool(v) => MoveValue::Bool(v),
+            ConstantValue::U8(v) => MoveValue::U8(v),
+            ConstantValue::U64(v) => MoveValue::U64(v),
+            ConstantValue::U128(v) => MoveValue::U128(v),
+            ConstantValue::Address(v) => MoveValue::Address(v),
+        }
+    }
+
+    /// The Rust type and expression of the value.
+    fn to_rust(self) -> (&'static str, String) {
+        match self {
+            ConstantValue::Bool(v) => ("bool", v.to_string()),
+            ConstantValue::U8(v) => ("u8", v.to_string()),
+            ConstantValue::U64(v) => ("u64", v.to_string()),
+            ConstantValue::U128(v) => ("u128", v.to_string()),
+            ConstantValue::Address(v) => (
+                "move_core_types::account_address::AccountAddress",
+                format!(
+                    "move_core_types::account_address::AccountAddress::new({:?})",
+                    v.into_bytes()
+                ),
+            ),
+        }
+    }
+}
+
+/// A decimal or hexadecimal literal of at most the range of `type_`, with an optional type
+/// suffix, e.g. `256`, `0xff` or `10u64`.
+fn parse_integer(value: &str, type_: &str) -> Option<u128> {
+    let value = value.strip_suffix(type_).unwrap_or(value);
+    let value = match value.strip_prefix("0x") {
+        Some(hex) => u128::from_str_radix(hex, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+    let max = match type_ {
+        "u8" => u8::MAX as u128,
+        "u64" => u64::MAX as u128,
+        _ => u128::MAX,
+    };
+    (value <= max).then_some(value)
+}
+
+/// The constants declared in the Move `source` of one or more modules, in order.
+pub fn parse_constants(source: &str) -> Vec<MoveConstant> {
+    let mut constants = Vec::new();
+    let mut module = None;
+    let mut test_only = false;
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if let Some(declaration) = line.strip_prefix("module ") {
+            // e.g. `module sui::memo {`
+            module = declaration
+                .split(|c: char| c == '{' || c.is_whitespace())
+                .next()
+                .and_then(|name| name.rsplit("::").next())
+                .map(str::to_string);
+        } else if let Some(declaration) = line.strip_prefix("const ") {
+            let constant = parse_declaration(declaration);
+            if let (Some(module), Some((name, value)), false) = (&module, constant, test_only) {
+                constants.push(MoveConstant {
+                    module: module.clone(),
+                    name,
+                    value,
+                });
+            }
+        }
+        if !line.is_empty() {
+            test_only = line == "#[test_only]";
+        }
+    }
+    constants
+}
+
+/// The name and value of a declaration `NAME: TYPE = VALUE;`, if it is of a supported type.
+fn parse_declaration(declaration: &str) -> Option<(String, ConstantValue)> {
+    let (name, rest) = declaration.split_once(':')?;
+    let (type_, value) = rest.split_once('=')?;
+    let value = value.trim().strip_suffix(';')?.trim();
+    let value = ConstantValue::parse(type_.trim(), value)?;
+    Some((name.trim().to_string(), value))
+}
+
+/// Checks that each of `constants` of `module` is in its constant pool, with the same type and
+/// value, so that constants parsed wrong are not exported.
+pub fn check_constants(module: &CompiledModule, constants: &[MoveConstant]) -> SuiResult {
+    let name = module.self_id().name().to_string();
+    for constant in constants.iter().filter(|c| c.module == name) {
+        let data = constant.value.move_value().simple_serialize();
+        let type_ = constant.value.signature_token();
+        if !module
+            .constant_pool()
+            .iter()
+            .any(|c| c.type_ == type_ && Some(&c.data) == data.as_ref())
+        {
+            return Err(SuiError::ModuleVerificationFailure {
+                error: format!(
+                    "Constant {}::{} = {:?} is not in the compiled module",
+                    name, constant.name, constant.value
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Rust source declaring `constants` in a module named after their Move module each, e.g.
+/// `memo::MAX_MEMO_LENGTH`.
+pub fn rust_source(constants: &[MoveConstant]) -> String {
+    let mut modules: Vec<&str> = constants.iter().map(|c| c.module.as_str()).collect();
+    modules.sort_unstable();
+    modules.dedup();
+    let mut source = String::new();
+    for module in modules {
+        writeln!(source, "pub mod {} {{", module).unwrap();
+        for constant in constants.iter().filter(|c| c.module == module) {
+            let (type_, value) = constant.value.to_rust();
+            writeln!(
+                source,
+                "    pub const {}: {} = {};",
+                constant.name, type_, value
+            )
+            .unwrap();
+        }
+        writeln!(source, "}}").unwrap();
+    }
+    source
+}
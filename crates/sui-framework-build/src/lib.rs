@@ -5,10 +5,16 @@ use move_binary_format::CompiledModule;
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use sha3::{Digest, Sha3_256};
 use std::{collections::HashSet, path::Path};
 use sui_types::error::{SuiError, SuiResult};
 use sui_verifier::verifier as sui_bytecode_verifier;
 
+pub mod abi;
+pub mod compatibility;
+pub mod constants;
+pub mod named_addresses;
+
 const SUI_PACKAGE_NAME: &str = "Sui";
 const MOVE_STDLIB_PACKAGE_NAME: &str = "MoveStdlib";
 
@@ -34,37 +40,49 @@ pub fn verify_modules(modules: &[CompiledModule]) -> SuiResult {
 }
 
 /// Given a `path` and a `build_config`, build the package in that path, including its dependencies.
-/// If we are building the Sui framework, we skip the check that the addresses should be 0
+/// If we are building the Sui framework, we skip the check that the addresses should be 0.
+/// The named address overrides of `named_addresses::NAMED_ADDRESSES_ENV` and
+/// `named_addresses::NAMED_ADDRESSES_FILE_ENV` are applied, so that packages depending on a
+/// relocated framework are built against its addresses.
 pub fn build_move_package_with_deps(
     path: &Path,
     build_config: BuildConfig,
 ) -> SuiResult<CompiledPackage> {
-    match build_config.compile_package_no_exit(path, &mut Vec::new()) {
-        Err(error) => Err(SuiError::ModuleBuildFailure {
-            error: error.to_string(),
-        }),
-        Ok(package) => {
-            let compiled_modules = package.root_modules_map();
-            let package_name = package.compiled_package_info.package_name.as_str();
-            let is_framework =
-                package_name == SUI_PACKAGE_NAME || package_name == MOVE_STDLIB_PACKAGE_NAME;
-            if !is_framework {
-                if let Some(m) = compiled_modules
-                    .iter_modules()
-                    .iter()
-                    .find(|m| m.self_id().address() != &AccountAddress::ZERO)
-                {
-                    return Err(SuiError::ModulePublishFailure {
-                        error: format!(
-                            "Modules must all have 0x0 as their addresses. Violated by module {:?}",
-                            m.self_id()
-                        ),
-                    });
-                }
-            }
-            Ok(package)
+    let named_addresses = named_addresses::named_address_overrides()?;
+    let build_config = named_addresses::with_named_addresses(build_config, &named_addresses);
+    let package = build_system_package_with_deps(path, build_config)?;
+    let compiled_modules = package.root_modules_map();
+    let package_name = package.compiled_package_info.package_name.as_str();
+    let is_framework = package_name == SUI_PACKAGE_NAME || package_name == MOVE_STDLIB_PACKAGE_NAME;
+    if !is_framework {
+        if let Some(m) = compiled_modules
+            .iter_modules()
+            .iter()
+            .find(|m| m.self_id().address() != &AccountAddress::ZERO)
+        {
+            return Err(SuiError::ModulePublishFailure {
+                error: format!(
+                    "Modules must all have 0x0 as their addresses. Violated by module {:?}",
+                    m.self_id()
+                ),
+            });
         }
     }
+    Ok(package)
+}
+
+/// Given a `path` and a `build_config`, build the system package in that path, including its
+/// dependencies. Unlike other packages, system packages are published at genesis at the non-zero
+/// addresses of their modules.
+pub fn build_system_package_with_deps(
+    path: &Path,
+    build_config: BuildConfig,
+) -> SuiResult<CompiledPackage> {
+    build_config
+        .compile_package_no_exit(path, &mut Vec::new())
+        .map_err(|error| SuiError::ModuleBuildFailure {
+            error: error.to_string(),
+        })
 }
 
 /// Given a package bundled with its dependencies, filter out modules that only belong to this
@@ -108,3 +126,22 @@ pub fn filter_package_modules(package: &CompiledPackage) -> SuiResult<Vec<Compil
         .cloned()
         .collect())
 }
+
+/// Digest of the modules of `packages`, which doesn't depend on the order the modules were built
+/// in: the modules of each package are hashed by module id, and the packages in the given order.
+pub fn framework_digest(packages: &[&[CompiledModule]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    for modules in packages {
+        let mut modules: Vec<&CompiledModule> = modules.iter().collect();
+        modules.sort_by_key(|m| m.self_id());
+        hasher.update((modules.len() as u64).to_le_bytes());
+        for module in modules {
+            let mut bytes = Vec::new();
+            module.serialize(&mut bytes).unwrap();
+            // Length prefixes keep the boundaries of modules and packages apart
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(&bytes);
+        }
+    }
+    hasher.finalize().into()
+}
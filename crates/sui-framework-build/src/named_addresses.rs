@@ -0,0 +1,123 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Overrides of the named addresses of Move packages, e.g. `sui`, so that a fork can build the
+//! system packages, and the packages depending on them, at other addresses without patching their
+//! `Move.toml`.
+
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_package::BuildConfig;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+use sui_types::error::{SuiError, SuiResult};
+
+/// Overrides as a comma separated list of assignments, e.g. `sui=0x3,std=0x4`. They take
+/// precedence over those of `NAMED_ADDRESSES_FILE_ENV`.
+pub const NAMED_ADDRESSES_ENV: &str = "SUI_FRAMEWORK_NAMED_ADDRESSES";
+/// Path to a TOML file of overrides, in an `[addresses]` table as in a `Move.toml`.
+pub const NAMED_ADDRESSES_FILE_ENV: &str = "SUI_FRAMEWORK_NAMED_ADDRESSES_FILE";
+
+/// Named addresses, by name.
+pub type NamedAddresses = BTreeMap<String, AccountAddress>;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NamedAddressesFile {
+    #[serde(default)]
+    addresses: BTreeMap<String, String>,
+}
+
+/// What a build of the system packages was built with, embedded next to their modules.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildMetadata {
+    /// The named addresses overridden in the build, empty if none were.
+    pub named_addresses: NamedAddresses,
+}
+
+/// The named address overrides of `NAMED_ADDRESSES_FILE_ENV` and `NAMED_ADDRESSES_ENV`, empty if
+/// neither is set. Each name must be an identifier, and each address a non-zero hex literal which
+/// no other name is assigned.
+pub fn named_address_overrides() -> SuiResult<NamedAddresses> {
+    let mut assignments = Vec::new();
+    if let Some(path) = env::var_os(NAMED_ADDRESSES_FILE_ENV) {
+        let path = PathBuf::from(path);
+        let contents = fs::read_to_string(&path).map_err(|e| {
+            build_failure(format!(
+                "Failed to read named addresses {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let file: NamedAddressesFile = toml::from_str(&contents).map_err(|e| {
+            build_failure(format!("Invalid named addresses {}: {}", path.display(), e))
+        })?;
+        assignments.extend(file.addresses);
+    }
+    if let Ok(list) = env::var(NAMED_ADDRESSES_ENV) {
+        for assignment in list.split(',').map(str::trim).filter(|a| !a.is_empty()) {
+            let (name, address) = assignment.split_once('=').ok_or_else(|| {
+                build_failure(format!(
+                    "Invalid named address assignment '{}' in {}, expected <name>=<address>",
+                    assignment, NAMED_ADDRESSES_ENV
+                ))
+            })?;
+            assignments.push((name.trim().to_string(), address.trim().to_string()));
+        }
+    }
+    parse_named_addresses(assignments)
+}
+
+/// Parses and validates `assignments`, later assignments of a name replacing earlier ones.
+pub fn parse_named_addresses(
+    assignments: impl IntoIterator<Item = (String, String)>,
+) -> SuiResult<NamedAddresses> {
+    let mut named_addresses = NamedAddresses::new();
+    for (name, address) in assignments {
+        if !Identifier::is_valid(&name) {
+            return Err(build_failure(format!(
+                "Invalid named address '{}', expected an identifier",
+                name
+            )));
+        }
+        let parsed = AccountAddress::from_hex_literal(&address).map_err(|_| {
+            build_failure(format!(
+                "Invalid address '{}' of named address {}",
+                address, name
+            ))
+        })?;
+        if parsed == AccountAddress::ZERO {
+            return Err(build_failure(format!(
+                "Named address {} can't be 0x0, which is left to packages published by users",
+                name
+            )));
+        }
+        named_addresses.insert(name, parsed);
+    }
+    let mut names_by_address = BTreeMap::new();
+    for (name, address) in &named_addresses {
+        if let Some(other) = names_by_address.insert(address, name) {
+            return Err(build_failure(format!(
+                "Named addresses {} and {} can't both be {}",
+                other,
+                name,
+                address.to_hex_literal()
+            )));
+        }
+    }
+    Ok(named_addresses)
+}
+
+/// `build_config` with `named_addresses` assigned on top of its own additional named addresses.
+pub fn with_named_addresses(
+    mut build_config: BuildConfig,
+    named_addresses: &NamedAddresses,
+) -> BuildConfig {
+    build_config
+        .additional_named_addresses
+        .extend(named_addresses.clone());
+    build_config
+}
+
+fn build_failure(error: String) -> SuiError {
+    SuiError::ModuleBuildFailure { error }
+}
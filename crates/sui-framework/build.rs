@@ -1,14 +1,104 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use move_binary_format::access::ModuleAccess;
 use move_binary_format::CompiledModule;
-use move_package::BuildConfig;
+use move_core_types::account_address::AccountAddress;
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use serde::Deserialize;
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, BTreeSet};
 use std::thread::Builder;
 use std::{
     env, fs,
     path::{Path, PathBuf},
 };
+use sui_framework_build::constants::{self, ConstantValue, MoveConstant};
+use sui_framework_build::named_addresses::{
+    self, BuildMetadata, NamedAddresses, NAMED_ADDRESSES_ENV, NAMED_ADDRESSES_FILE_ENV,
+};
+use sui_types::base_types::TRANSACTION_DIGEST_LENGTH;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID};
+
+/// Default bytecode size budget for a single framework module, in bytes.
+const DEFAULT_MAX_MODULE_SIZE: usize = 64 * 1024;
+/// Default budget for the number of modules a single framework module depends on.
+const DEFAULT_MAX_MODULE_DEPENDENCIES: usize = 24;
+
+const MAX_MODULE_SIZE_ENV: &str = "SUI_FRAMEWORK_MAX_MODULE_SIZE";
+const MAX_MODULE_DEPENDENCIES_ENV: &str = "SUI_FRAMEWORK_MAX_MODULE_DEPENDENCIES";
+/// Directory of modules built earlier, loaded instead of compiling the Move packages again when
+/// their sources didn't change. Builds which compile the packages save their modules there.
+const PREBUILT_DIR_ENV: &str = "SUI_FRAMEWORK_PREBUILT_DIR";
+/// Manifest of the Move packages to embed and publish at genesis on top of the stdlib and the
+/// framework, see `SystemPackageSpec`.
+const SYSTEM_PACKAGES_ENV: &str = "SUI_FRAMEWORK_SYSTEM_PACKAGES";
+
+const SUI_FRAMEWORK_NAME: &str = "sui-framework";
+const MOVE_STDLIB_NAME: &str = "move-stdlib";
+
+const SYSTEM_PACKAGES_FILE: &str = "system-packages";
+/// The system packages built in test mode, with their `#[test_only]` modules and functions.
+const TEST_SYSTEM_PACKAGES_FILE: &str = "system-packages-test";
+const FRAMEWORK_DIGEST_FILE: &str = "framework-digest";
+const TEST_FRAMEWORK_DIGEST_FILE: &str = "framework-digest-test";
+const FRAMEWORK_DOCS_FILE: &str = "framework-docs";
+/// What the system packages were built with, see `named_addresses::BuildMetadata`.
+const FRAMEWORK_METADATA_FILE: &str = "framework-metadata";
+/// JSON description of the interface of the system packages, see `sui_framework_build::abi`.
+const FRAMEWORK_ABI_FILE: &str = "framework-abi.json";
+/// Rust declarations of the constants of the framework, see `sui_framework_build::constants`.
+const FRAMEWORK_CONSTANTS_FILE: &str = "framework-constants.rs";
+const SOURCES_DIGEST_FILE: &str = "sources-digest";
+/// Directory of `OUT_DIR` caching the modules and docs of each system package, in a file named
+/// after the package and the digest of its sources, so that rebuilds only compile the packages
+/// whose sources changed, and those depending on them.
+const PACKAGE_CACHE_DIR: &str = "package-cache";
+/// Suffix of the name of the packages built in test mode in the package cache.
+const TEST_CACHE_SUFFIX: &str = "-test";
+
+/// Markdown docs of the modules of the system packages, by module id.
+type ModuleDocs = BTreeMap<String, String>;
+
+/// The modules of each system package, by embedded name, in publish order.
+type SystemPackages = Vec<(String, Vec<CompiledModule>)>;
+
+/// The manifest of `SYSTEM_PACKAGES_ENV`, listing each extra package as
+///
+/// ```toml
+/// [[package]]
+/// name = "oracles"
+/// path = "../oracles"
+/// address = "0x5"
+/// ```
+///
+/// Packages are published in the order they are listed, after the stdlib and the framework, so
+/// they may depend on those and on the packages listed before them.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SystemPackagesManifest {
+    #[serde(default)]
+    package: Vec<ManifestPackage>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestPackage {
+    name: String,
+    /// Relative to the manifest.
+    path: PathBuf,
+    address: String,
+}
+
+/// A Move package built into this crate and published at genesis.
+struct SystemPackageSpec {
+    /// The name the package is embedded and looked up by.
+    name: String,
+    path: PathBuf,
+    /// The address the package is published at, which all its modules must have.
+    address: AccountAddress,
+}
 
 /// Save revision info to environment variable
 fn main() {
@@ -17,61 +107,639 @@ fn main() {
     let sui_framework_path = Path::new(env!("CARGO_MANIFEST_DIR"));
     let move_stdlib_path = sui_framework_path.join("deps").join("move-stdlib");
 
-    let stdlib_path = move_stdlib_path.clone();
-    let (sui_framework, move_stdlib) = Builder::new()
-        .stack_size(16 * 1024 * 1024) // build_move_package require bigger stack size on windows.
-        .spawn(move || build_framework_and_stdlib(sui_framework_path, &stdlib_path))
-        .unwrap()
-        .join()
-        .unwrap();
+    let named_addresses = named_addresses::named_address_overrides()
+        .unwrap_or_else(|e| panic!("Invalid named address overrides: {}", e));
+    let specs =
+        system_package_specs(sui_framework_path, &move_stdlib_path, &named_addresses).unwrap();
+    let package_paths: Vec<PathBuf> = specs.iter().map(|spec| spec.path.clone()).collect();
+
+    let prebuilt_dir = env::var_os(PREBUILT_DIR_ENV).map(PathBuf::from);
+    let test_package_digests =
+        package_digests(sui_framework_path, &specs, &named_addresses, true).unwrap();
+    let package_digests =
+        package_digests(sui_framework_path, &specs, &named_addresses, false).unwrap();
+    // The digest of the last package covers the sources of all of them, in each mode
+    let mut sources_digest = package_digests.last().unwrap().to_vec();
+    sources_digest.extend(test_package_digests.last().unwrap());
+    let cache_dir = out_dir.join(PACKAGE_CACHE_DIR);
+    let prebuilt = match &prebuilt_dir {
+        Some(dir) => match load_prebuilt(dir, &sources_digest) {
+            Ok(modules) => Some(modules),
+            Err(e) => {
+                println!(
+                    "cargo:warning=Building the framework, the modules in {} can't be used: {}",
+                    dir.display(),
+                    e
+                );
+                None
+            }
+        },
+        None => None,
+    };
+    let loaded_prebuilt = prebuilt.is_some();
+    let metadata = BuildMetadata {
+        named_addresses: named_addresses.clone(),
+    };
+
+    let (packages, test_packages, docs) = match prebuilt {
+        Some(modules) => modules,
+        None => Builder::new()
+            .stack_size(16 * 1024 * 1024) // build_move_package require bigger stack size on windows.
+            .spawn(move || {
+                let (packages, docs) = build_system_packages(
+                    &specs,
+                    &package_digests,
+                    &named_addresses,
+                    &cache_dir,
+                    false,
+                );
+                let (test_packages, _) = build_system_packages(
+                    &specs,
+                    &test_package_digests,
+                    &named_addresses,
+                    &cache_dir,
+                    true,
+                );
+                (packages, test_packages, docs)
+            })
+            .unwrap()
+            .join()
+            .unwrap(),
+    };
 
-    serialize_modules_to_file(sui_framework, &out_dir.join("sui-framework")).unwrap();
-    serialize_modules_to_file(move_stdlib, &out_dir.join("move-stdlib")).unwrap();
+    // Test-only code never reaches a real network, so it is left out of the budget
+    let budget = ModuleBudget::from_env();
+    let mut over_budget = Vec::new();
+    for (name, modules) in &packages {
+        over_budget.extend(budget.check(name, modules).unwrap());
+    }
+    if !over_budget.is_empty() {
+        panic!(
+            "Framework modules exceed their budget ({} bytes, {} dependencies), \
+             raise {} / {} if the growth is intended:\n  {}",
+            budget.max_size,
+            budget.max_dependencies,
+            MAX_MODULE_SIZE_ENV,
+            MAX_MODULE_DEPENDENCIES_ENV,
+            over_budget.join("\n  ")
+        );
+    }
+
+    // Genesis and validators compare this digest to tell whether they run the same framework
+    fs::write(
+        out_dir.join(FRAMEWORK_DIGEST_FILE),
+        packages_digest(&packages),
+    )
+    .unwrap();
+    fs::write(
+        out_dir.join(TEST_FRAMEWORK_DIGEST_FILE),
+        packages_digest(&test_packages),
+    )
+    .unwrap();
+    fs::write(out_dir.join(SOURCES_DIGEST_FILE), sources_digest).unwrap();
+    fs::write(
+        out_dir.join(FRAMEWORK_DOCS_FILE),
+        bcs::to_bytes(&docs).unwrap(),
+    )
+    .unwrap();
+    // Derived from the environment of the build, which the sources digest covers
+    fs::write(
+        out_dir.join(FRAMEWORK_METADATA_FILE),
+        bcs::to_bytes(&metadata).unwrap(),
+    )
+    .unwrap();
+    // Derived from the modules, so it isn't saved with prebuilt modules but generated each time
+    let abi: Vec<_> = packages
+        .iter()
+        .map(|(name, modules)| sui_framework_build::abi::package_abi(name, modules))
+        .collect();
+    fs::write(
+        out_dir.join(FRAMEWORK_ABI_FILE),
+        serde_json::to_string_pretty(&abi).unwrap(),
+    )
+    .unwrap();
+
+    // Read from the sources, which are always at hand, and checked against the modules
+    let constants = framework_constants(sui_framework_path, &packages).unwrap();
+    fs::write(
+        out_dir.join(FRAMEWORK_CONSTANTS_FILE),
+        constants::rust_source(&constants),
+    )
+    .unwrap();
+
+    serialize_packages_to_file(packages, &out_dir.join(SYSTEM_PACKAGES_FILE)).unwrap();
+    serialize_packages_to_file(test_packages, &out_dir.join(TEST_SYSTEM_PACKAGES_FILE)).unwrap();
+
+    if let Some(dir) = &prebuilt_dir {
+        if !loaded_prebuilt {
+            save_prebuilt(&out_dir, dir).unwrap();
+        }
+    }
 
     println!("cargo:rerun-if-changed=build.rs");
-    println!(
-        "cargo:rerun-if-changed={}",
-        sui_framework_path.join("Move.toml").display()
-    );
-    println!(
-        "cargo:rerun-if-changed={}",
-        sui_framework_path.join("sources").display()
-    );
-    println!(
-        "cargo:rerun-if-changed={}",
-        move_stdlib_path.join("Move.toml").display()
-    );
-    println!(
-        "cargo:rerun-if-changed={}",
-        move_stdlib_path.join("sources").display()
-    );
+    println!("cargo:rerun-if-env-changed={}", MAX_MODULE_SIZE_ENV);
+    println!("cargo:rerun-if-env-changed={}", MAX_MODULE_DEPENDENCIES_ENV);
+    println!("cargo:rerun-if-env-changed={}", PREBUILT_DIR_ENV);
+    println!("cargo:rerun-if-env-changed={}", SYSTEM_PACKAGES_ENV);
+    println!("cargo:rerun-if-env-changed={}", NAMED_ADDRESSES_ENV);
+    println!("cargo:rerun-if-env-changed={}", NAMED_ADDRESSES_FILE_ENV);
+    if let Some(file) = env::var_os(NAMED_ADDRESSES_FILE_ENV) {
+        println!("cargo:rerun-if-changed={}", Path::new(&file).display());
+    }
+    if let Some(manifest) = env::var_os(SYSTEM_PACKAGES_ENV) {
+        println!("cargo:rerun-if-changed={}", Path::new(&manifest).display());
+    }
+    for path in package_paths {
+        println!(
+            "cargo:rerun-if-changed={}",
+            path.join("Move.toml").display()
+        );
+        println!("cargo:rerun-if-changed={}", path.join("sources").display());
+        println!("cargo:rerun-if-changed={}", path.join("tests").display());
+    }
 }
 
-fn build_framework_and_stdlib(
+/// The stdlib and the framework, at the addresses `std` and `sui` are overridden to in
+/// `named_addresses` if they are, then the packages listed in the manifest of
+/// `SYSTEM_PACKAGES_ENV`, if set.
+fn system_package_specs(
     sui_framework_path: &Path,
     move_stdlib_path: &Path,
-) -> (Vec<CompiledModule>, Vec<CompiledModule>) {
-    let pkg = sui_framework_build::build_move_package_with_deps(
-        sui_framework_path,
-        BuildConfig::default(),
+    named_addresses: &NamedAddresses,
+) -> Result<Vec<SystemPackageSpec>> {
+    let mut specs = vec![
+        SystemPackageSpec {
+            name: MOVE_STDLIB_NAME.to_string(),
+            path: move_stdlib_path.to_path_buf(),
+            address: *named_addresses.get("std").unwrap_or(&MOVE_STDLIB_ADDRESS),
+        },
+        SystemPackageSpec {
+            name: SUI_FRAMEWORK_NAME.to_string(),
+            path: sui_framework_path.to_path_buf(),
+            address: *named_addresses.get("sui").unwrap_or(&SUI_FRAMEWORK_ADDRESS),
+        },
+    ];
+    let manifest_path = match env::var_os(SYSTEM_PACKAGES_ENV) {
+        Some(path) => PathBuf::from(path),
+        None => return Ok(specs),
+    };
+    let manifest: SystemPackagesManifest = toml::from_str(
+        &fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?,
     )
-    .unwrap();
-    let sui_framework = sui_framework_build::filter_package_modules(&pkg).unwrap();
-    let move_stdlib = sui_framework_build::build_move_stdlib_modules(move_stdlib_path).unwrap();
-    (sui_framework, move_stdlib)
+    .with_context(|| format!("Invalid manifest {}", manifest_path.display()))?;
+    let manifest_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut names: BTreeSet<String> = specs.iter().map(|spec| spec.name.clone()).collect();
+    let mut addresses: BTreeSet<AccountAddress> = specs.iter().map(|spec| spec.address).collect();
+    for package in manifest.package {
+        let address = AccountAddress::from_hex_literal(&package.address).with_context(|| {
+            format!(
+                "Invalid address {} of system package {}",
+                package.address, package.name
+            )
+        })?;
+        if !names.insert(package.name.clone()) {
+            bail!("System package {} is listed twice", package.name);
+        }
+        if address == AccountAddress::ZERO || !addresses.insert(address) {
+            bail!(
+                "System package {} can't be published at {}, which is taken",
+                package.name,
+                package.address
+            );
+        }
+        specs.push(SystemPackageSpec {
+            name: package.name,
+            path: manifest_dir.join(package.path),
+            address,
+        });
+    }
+    Ok(specs)
 }
 
-fn serialize_modules_to_file(modules: Vec<CompiledModule>, file: &Path) -> Result<()> {
-    let mut serialized_modules = Vec::new();
+/// Builds the packages of `specs` with `named_addresses` substituted, in test mode if
+/// `test_mode`, or loads those whose sources didn't change from `cache_dir`, `digests` being the
+/// digests of their sources. Docs are only generated outside of test mode.
+fn build_system_packages(
+    specs: &[SystemPackageSpec],
+    digests: &[[u8; 32]],
+    named_addresses: &NamedAddresses,
+    cache_dir: &Path,
+    test_mode: bool,
+) -> (SystemPackages, ModuleDocs) {
+    let mut packages = Vec::new();
+    let mut docs = ModuleDocs::new();
+    for (spec, digest) in specs.iter().zip(digests) {
+        let cache_name = if test_mode {
+            format!("{}{}", spec.name, TEST_CACHE_SUFFIX)
+        } else {
+            spec.name.clone()
+        };
+        let cache_file = cache_dir.join(format!("{}-{}", cache_name, hex_digest(digest)));
+        let (modules, package_docs) = match load_cached_package(&cache_file) {
+            Ok(package) => package,
+            Err(_) => {
+                let package = build_system_package(spec, named_addresses, test_mode);
+                if let Err(e) = save_cached_package(cache_dir, &cache_name, &cache_file, &package) {
+                    println!(
+                        "cargo:warning=Failed to cache system package {}: {}",
+                        spec.name, e
+                    );
+                }
+                package
+            }
+        };
+        docs.extend(package_docs);
+        packages.push((spec.name.clone(), modules));
+    }
+    (packages, docs)
+}
+
+fn build_system_package(
+    spec: &SystemPackageSpec,
+    named_addresses: &NamedAddresses,
+    test_mode: bool,
+) -> (Vec<CompiledModule>, ModuleDocs) {
+    let build_config = named_addresses::with_named_addresses(
+        BuildConfig {
+            generate_docs: !test_mode,
+            test_mode,
+            ..Default::default()
+        },
+        named_addresses,
+    );
+    let pkg =
+        sui_framework_build::build_system_package_with_deps(&spec.path, build_config).unwrap();
+    let modules = sui_framework_build::filter_package_modules(&pkg).unwrap();
+    if let Some(module) = modules
+        .iter()
+        .find(|m| m.self_id().address() != &spec.address)
+    {
+        panic!(
+            "Module {} of system package {} isn't at the address of the package, {}",
+            module.self_id(),
+            spec.name,
+            spec.address.to_hex_literal()
+        );
+    }
+    sui_framework_build::verify_modules(&modules).unwrap();
+    let docs = package_docs(&pkg, &modules);
+    (modules, docs)
+}
+
+/// Loads the modules and docs of a package cached by `save_cached_package`.
+fn load_cached_package(cache_file: &Path) -> Result<(Vec<CompiledModule>, ModuleDocs)> {
+    let (modules, docs): (Vec<Vec<u8>>, ModuleDocs) = bcs::from_bytes(&fs::read(cache_file)?)?;
+    Ok((deserialize_modules(&modules)?, docs))
+}
+
+/// Caches the modules and docs of package `name` in `cache_file`, replacing those cached for
+/// other sources of the package.
+fn save_cached_package(
+    cache_dir: &Path,
+    name: &str,
+    cache_file: &Path,
+    (modules, docs): &(Vec<CompiledModule>, ModuleDocs),
+) -> Result<()> {
+    fs::create_dir_all(cache_dir)?;
+    let prefix = format!("{}-", name);
+    for entry in fs::read_dir(cache_dir)? {
+        let path = entry?.path();
+        // The digest is checked too, as another package may be named after this one, e.g. `a-b`
+        let is_cached_package = path
+            .file_name()
+            .and_then(|file| file.to_str())
+            .and_then(|file| file.strip_prefix(&prefix))
+            .map_or(false, |digest| {
+                digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+            });
+        if is_cached_package {
+            fs::remove_file(path)?;
+        }
+    }
+    // Written aside and renamed, so that an interrupted build never leaves a partial file behind
+    let mut tmp_file = cache_file.as_os_str().to_owned();
+    tmp_file.push(".tmp");
+    fs::write(
+        &tmp_file,
+        bcs::to_bytes(&(serialize_modules(modules)?, docs))?,
+    )?;
+    fs::rename(&tmp_file, cache_file)?;
+    Ok(())
+}
+
+fn hex_digest(digest: &[u8; 32]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The docs generated for the `modules` of `pkg`, by module id. The docs of its dependencies,
+/// generated under `dependencies/`, are left out.
+fn package_docs(pkg: &CompiledPackage, modules: &[CompiledModule]) -> ModuleDocs {
+    let module_ids: BTreeMap<String, String> = modules
+        .iter()
+        .map(|m| (m.self_id().name().to_string(), m.self_id().to_string()))
+        .collect();
+    pkg.compiled_docs
+        .iter()
+        .flatten()
+        .filter_map(|(file, markdown)| {
+            let file = Path::new(file);
+            if file.components().count() != 1 {
+                return None;
+            }
+            let name = file.file_stem()?.to_str()?;
+            Some((module_ids.get(name)?.clone(), markdown.clone()))
+        })
+        .collect()
+}
+
+/// The constants declared by the modules of the production build of the framework, checked
+/// against their constant pools and against the copies of them in `sui-types`.
+fn framework_constants(
+    sui_framework_path: &Path,
+    packages: &SystemPackages,
+) -> Result<Vec<MoveConstant>> {
+    let (_, modules) = packages
+        .iter()
+        .find(|(name, _)| name == SUI_FRAMEWORK_NAME)
+        .context("The framework isn't built")?;
+    let mut files = Vec::new();
+    collect_files(&sui_framework_path.join("sources"), &mut files)?;
+    files.sort();
+    let mut parsed = Vec::new();
+    for file in files {
+        if file
+            .extension()
+            .map_or(false, |extension| extension == "move")
+        {
+            parsed.extend(constants::parse_constants(&fs::read_to_string(&file)?));
+        }
+    }
+    // Test-only modules aren't part of the production build
+    parsed.retain(|constant| {
+        modules
+            .iter()
+            .any(|module| module.self_id().name().as_str() == constant.module)
+    });
     for module in modules {
-        let mut buf = Vec::new();
-        module.serialize(&mut buf)?;
-        serialized_modules.push(buf);
+        constants::check_constants(module, &parsed)?;
+    }
+
+    for (module, name, value) in mirrored_constants() {
+        let declared = parsed
+            .iter()
+            .find(|constant| constant.module == module && constant.name == name)
+            .map(|constant| constant.value);
+        if declared != Some(value) {
+            bail!(
+                "The copy of {}::{} in sui-types is {:?}, the framework declares {:?}",
+                module,
+                name,
+                value,
+                declared
+            );
+        }
+    }
+    Ok(parsed)
+}
+
+/// The constants `sui-types` keeps a copy of, as it can't depend on this crate: the module and
+/// name of each in the framework, and the value of the copy.
+fn mirrored_constants() -> Vec<(&'static str, &'static str, ConstantValue)> {
+    vec![
+        (
+            "memo",
+            "MAX_MEMO_LENGTH",
+            ConstantValue::U64(sui_types::memo::MAX_MEMO_LENGTH as u64),
+        ),
+        (
+            "object",
+            "SUI_SYSTEM_STATE_OBJECT_ID",
+            ConstantValue::Address(SUI_SYSTEM_STATE_OBJECT_ID.into()),
+        ),
+        (
+            "tx_context",
+            "TX_HASH_LENGTH",
+            ConstantValue::U64(TRANSACTION_DIGEST_LENGTH as u64),
+        ),
+    ]
+}
+
+/// Digest of everything each system package is compiled from: its name, address and Move sources,
+/// including its Move tests in test mode, the named addresses overridden, the manifest of this
+/// crate, which pins the revision of the Move compiler, and the digest of the package before it,
+/// as it may depend on any of the packages before it.
+fn package_digests(
+    sui_framework_path: &Path,
+    specs: &[SystemPackageSpec],
+    named_addresses: &NamedAddresses,
+    test_mode: bool,
+) -> Result<Vec<[u8; 32]>> {
+    let mut digests: Vec<[u8; 32]> = Vec::new();
+    for spec in specs {
+        let mut hasher = Sha3_256::new();
+        hash_file(
+            &mut hasher,
+            "Cargo.toml",
+            &sui_framework_path.join("Cargo.toml"),
+        )?;
+        if let Some(previous) = digests.last() {
+            hasher.update(previous);
+        }
+        hasher.update((spec.name.len() as u64).to_le_bytes());
+        hasher.update(spec.name.as_bytes());
+        hasher.update(spec.address.to_vec());
+        hasher.update((named_addresses.len() as u64).to_le_bytes());
+        for (name, address) in named_addresses {
+            hasher.update((name.len() as u64).to_le_bytes());
+            hasher.update(name.as_bytes());
+            hasher.update(address.to_vec());
+        }
+        let mut files = vec![spec.path.join("Move.toml")];
+        collect_files(&spec.path.join("sources"), &mut files)?;
+        if test_mode {
+            hasher.update(b"test");
+            let tests_dir = spec.path.join("tests");
+            if tests_dir.is_dir() {
+                collect_files(&tests_dir, &mut files)?;
+            }
+        }
+        files.sort();
+        hasher.update((files.len() as u64).to_le_bytes());
+        for file in files {
+            // Paths are relative to the package, so the digest doesn't depend on where the
+            // repository is checked out
+            let path = file
+                .strip_prefix(&spec.path)?
+                .to_string_lossy()
+                .into_owned();
+            hash_file(&mut hasher, &path, &file)?;
+        }
+        digests.push(hasher.finalize().into());
+    }
+    Ok(digests)
+}
+
+fn hash_file(hasher: &mut Sha3_256, path: &str, file: &Path) -> Result<()> {
+    let contents = fs::read(file)?;
+    // Length prefixes keep the boundaries of paths and contents apart
+    hasher.update((path.len() as u64).to_le_bytes());
+    hasher.update(path.as_bytes());
+    hasher.update((contents.len() as u64).to_le_bytes());
+    hasher.update(&contents);
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Loads the modules of both builds saved to `dir` by an earlier build, if they were built from
+/// the same sources and weren't altered since.
+fn load_prebuilt(
+    dir: &Path,
+    sources_digest: &[u8],
+) -> Result<(SystemPackages, SystemPackages, ModuleDocs)> {
+    if fs::read(dir.join(SOURCES_DIGEST_FILE))? != sources_digest {
+        bail!("they were built from other sources");
+    }
+    let packages = deserialize_packages_from_file(&dir.join(SYSTEM_PACKAGES_FILE))?;
+    let test_packages = deserialize_packages_from_file(&dir.join(TEST_SYSTEM_PACKAGES_FILE))?;
+    if fs::read(dir.join(FRAMEWORK_DIGEST_FILE))? != packages_digest(&packages)
+        || fs::read(dir.join(TEST_FRAMEWORK_DIGEST_FILE))? != packages_digest(&test_packages)
+    {
+        bail!("they don't match their digest");
+    }
+    let docs = bcs::from_bytes(&fs::read(dir.join(FRAMEWORK_DOCS_FILE))?)?;
+    Ok((packages, test_packages, docs))
+}
+
+fn packages_digest(packages: &SystemPackages) -> [u8; 32] {
+    sui_framework_build::framework_digest(
+        &packages
+            .iter()
+            .map(|(_, modules)| modules.as_slice())
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Copies the modules built to `out_dir` to `dir`, with their digests.
+fn save_prebuilt(out_dir: &Path, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+    // The sources digest is copied last, so that modules partially copied are never loaded
+    for file in [
+        SYSTEM_PACKAGES_FILE,
+        FRAMEWORK_DIGEST_FILE,
+        TEST_SYSTEM_PACKAGES_FILE,
+        TEST_FRAMEWORK_DIGEST_FILE,
+        FRAMEWORK_DOCS_FILE,
+        SOURCES_DIGEST_FILE,
+    ] {
+        fs::copy(out_dir.join(file), dir.join(file))?;
     }
+    Ok(())
+}
+
+/// Limits on the size of individual framework modules, which are loaded at genesis and by every
+/// transaction touching the framework.
+struct ModuleBudget {
+    max_size: usize,
+    max_dependencies: usize,
+}
 
-    let binary = bcs::to_bytes(&serialized_modules)?;
+impl ModuleBudget {
+    fn from_env() -> Self {
+        Self {
+            max_size: env_or(MAX_MODULE_SIZE_ENV, DEFAULT_MAX_MODULE_SIZE),
+            max_dependencies: env_or(MAX_MODULE_DEPENDENCIES_ENV, DEFAULT_MAX_MODULE_DEPENDENCIES),
+        }
+    }
+
+    /// Prints a size table for `modules` and returns a description of each module over budget.
+    fn check(&self, package: &str, modules: &[CompiledModule]) -> Result<Vec<String>> {
+        let mut over_budget = Vec::new();
+        eprintln!(
+            "{:<40} {:>10} {:>12}",
+            format!("{} module", package),
+            "bytes",
+            "dependencies"
+        );
+        for module in modules {
+            let mut buf = Vec::new();
+            module.serialize(&mut buf)?;
+            let name = module.self_id().name().to_string();
+            let dependencies = module.immediate_dependencies().len();
+            eprintln!("{:<40} {:>10} {:>12}", name, buf.len(), dependencies);
+            if buf.len() > self.max_size {
+                over_budget.push(format!("{}::{} is {} bytes", package, name, buf.len()));
+            }
+            if dependencies > self.max_dependencies {
+                over_budget.push(format!(
+                    "{}::{} has {} dependencies",
+                    package, name, dependencies
+                ));
+            }
+        }
+        Ok(over_budget)
+    }
+}
+
+fn env_or(var: &str, default: usize) -> usize {
+    match env::var(var) {
+        Ok(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} must be a number, got {:?}", var, value)),
+        Err(_) => default,
+    }
+}
+
+/// Reads the modules of each package, as written by `serialize_packages_to_file`.
+fn deserialize_packages_from_file(file: &Path) -> Result<SystemPackages> {
+    let serialized_packages: Vec<(String, Vec<Vec<u8>>)> = bcs::from_bytes(&fs::read(file)?)?;
+    serialized_packages
+        .into_iter()
+        .map(|(name, modules)| Ok((name, deserialize_modules(&modules)?)))
+        .collect()
+}
+
+fn deserialize_modules(modules: &[Vec<u8>]) -> Result<Vec<CompiledModule>> {
+    modules
+        .iter()
+        .map(|module| {
+            CompiledModule::deserialize(module)
+                .map_err(|e| anyhow::anyhow!("Invalid module: {:?}", e))
+        })
+        .collect()
+}
+
+/// Writes the serialized modules of each package, with its name.
+fn serialize_packages_to_file(packages: SystemPackages, file: &Path) -> Result<()> {
+    let mut serialized_packages = Vec::new();
+    for (name, modules) in packages {
+        serialized_packages.push((name, serialize_modules(&modules)?));
+    }
+
+    let binary = bcs::to_bytes(&serialized_packages)?;
 
     fs::write(file, &binary)?;
 
     Ok(())
 }
+
+fn serialize_modules(modules: &[CompiledModule]) -> Result<Vec<Vec<u8>>> {
+    let mut serialized_modules = Vec::new();
+    for module in modules {
+        let mut buf = Vec::new();
+        module.serialize(&mut buf)?;
+        serialized_modules.push(buf);
+    }
+    Ok(serialized_modules)
+}
@@ -134,7 +134,7 @@ module sui::coin {
     }
 
     /// Destroy a coin with value zero
-    public fun destroy_zero<T>(c: Coin<T>) {
+    public entry fun destroy_zero<T>(c: Coin<T>) {
         let Coin { id, balance } = c;
         object::delete(id);
         balance::destroy_zero(balance)
@@ -9,6 +9,7 @@ module sui::genesis {
     use sui::sui_system;
     use sui::tx_context::TxContext;
     use sui::validator;
+    use sui::vec_set;
     use std::option;
 
     /// The initial amount of SUI locked in the storage fund.
@@ -36,6 +37,8 @@ module sui::genesis {
         validator_net_addresses: vector<vector<u8>>,
         validator_stakes: vector<u64>,
         validator_gas_prices: vector<u64>,
+        restrict_publishers: bool,
+        publisher_allowlist: vector<address>,
         ctx: &mut TxContext,
     ) {
         let sui_supply = sui::new();
@@ -74,6 +77,20 @@ module sui::genesis {
             ));
             i = i + 1;
         };
+        let allowlist = if (restrict_publishers) {
+            let allowed = vec_set::empty();
+            let i = 0;
+            while (i < vector::length(&publisher_allowlist)) {
+                let publisher = *vector::borrow(&publisher_allowlist, i);
+                if (!vec_set::contains(&allowed, &publisher)) {
+                    vec_set::insert(&mut allowed, publisher);
+                };
+                i = i + 1;
+            };
+            option::some(allowed)
+        } else {
+            option::none()
+        };
         sui_system::create(
             validators,
             sui_supply,
@@ -81,6 +98,7 @@ module sui::genesis {
             INIT_MAX_VALIDATOR_COUNT,
             INIT_MIN_VALIDATOR_STAKE,
             INIT_STORAGE_GAS_PRICE,
+            allowlist,
         );
     }
 }
@@ -13,7 +13,9 @@ module sui::sui_system {
     use sui::validator::{Self, Validator};
     use sui::validator_set::{Self, ValidatorSet};
     use sui::stake::Stake;
-    use std::option;
+    use sui::vec_map::{Self, VecMap};
+    use sui::vec_set::{Self, VecSet};
+    use std::option::{Self, Option};
 
     friend sui::genesis;
 
@@ -51,6 +53,26 @@ module sui::sui_system {
         parameters: SystemParameters,
         /// The reference gas price for the current epoch.
         reference_gas_price: u64,
+        /// Validators reported by their peers for a low availability during the current epoch,
+        /// each with the set of validators which reported it. Validators never reported have no
+        /// entry. The records are tallied and cleared at the end of each epoch.
+        validator_report_records: VecMap<address, VecSet<address>>,
+        /// The addresses allowed to publish packages, or none if anyone can publish them. Only
+        /// set at genesis, for private networks.
+        publisher_allowlist: Option<VecSet<address>>,
+        /// Pending changes to `publisher_allowlist`, each publisher with the set of validators
+        /// which voted to add it to or remove it from the allowlist. A change is applied once
+        /// validators holding a quorum of the stake voted for it, and the votes of the current
+        /// epoch are cleared at its end.
+        publisher_allowlist_votes: VecMap<address, VecSet<address>>,
+        /// Whether the last epoch change failed, in which case the system advanced to the next
+        /// epoch in safe mode: validators, rewards and reports are left as they are, and the
+        /// charges of the epochs since are held until an epoch change succeeds again.
+        safe_mode: bool,
+        /// Storage charges of the epochs which ended in safe mode, not yet added to the storage fund.
+        safe_mode_storage_charge: u64,
+        /// Computation charges of the epochs which ended in safe mode, not yet distributed.
+        safe_mode_computation_charge: u64,
     }
 
     // ==== functions that can only be called by Genesis ====
@@ -64,6 +86,7 @@ module sui::sui_system {
         max_validator_candidate_count: u64,
         min_validator_stake: u64,
         storage_gas_price: u64,
+        publisher_allowlist: Option<VecSet<address>>,
     ) {
         let validators = validator_set::new(validators);
         let reference_gas_price = validator_set::derive_reference_gas_price(&validators);
@@ -80,6 +103,12 @@ module sui::sui_system {
                 storage_gas_price
             },
             reference_gas_price,
+            validator_report_records: vec_map::empty(),
+            publisher_allowlist,
+            publisher_allowlist_votes: vec_map::empty(),
+            safe_mode: false,
+            safe_mode_storage_charge: 0,
+            safe_mode_computation_charge: 0,
         };
         transfer::share_object(state);
     }
@@ -156,6 +185,56 @@ module sui::sui_system {
         )
     }
 
+    /// A validator, active or pending, can call this entry function to change its name. The
+    /// name must be ASCII, at most 128 bytes long and not used by another validator.
+    public entry fun update_validator_name(
+        self: &mut SuiSystemState,
+        name: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        validator_set::update_validator_name(&mut self.validators, name, ctx)
+    }
+
+    /// A validator can call this entry function to set its description, UTF-8 and at most 1024
+    /// bytes long.
+    public entry fun update_validator_description(
+        self: &mut SuiSystemState,
+        description: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        validator_set::update_validator_description(&mut self.validators, description, ctx)
+    }
+
+    /// A validator can call this entry function to set the URL of its image, ASCII and at most
+    /// 1024 bytes long.
+    public entry fun update_validator_image_url(
+        self: &mut SuiSystemState,
+        image_url: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        validator_set::update_validator_image_url(&mut self.validators, image_url, ctx)
+    }
+
+    /// A validator can call this entry function to move to a new network address, a binary
+    /// multiaddr starting with an IP or DNS protocol, at the end of the epoch.
+    public entry fun update_validator_next_epoch_net_address(
+        self: &mut SuiSystemState,
+        net_address: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        validator_set::update_validator_next_epoch_net_address(&mut self.validators, net_address, ctx)
+    }
+
+    /// A validator can call this entry function to switch to a new Ed25519 network public key at
+    /// the end of the epoch.
+    public entry fun update_validator_next_epoch_network_pubkey(
+        self: &mut SuiSystemState,
+        network_pubkey_bytes: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        validator_set::update_validator_next_epoch_network_pubkey(&mut self.validators, network_pubkey_bytes, ctx)
+    }
+
     /// A validator can request adding more stake. This will be processed at the end of epoch.
     public entry fun request_add_stake(
         self: &mut SuiSystemState,
@@ -257,12 +336,86 @@ module sui::sui_system {
         validator_set::request_switch_delegation(&mut self.validators, delegation, staked_sui, new_validator_address, ctx);
     }
 
+    /// A validator can call this entry function to report another validator for a low
+    /// availability during the current epoch. Validators reported by validators holding a quorum
+    /// of the stake lose their rewards for the epoch.
+    public entry fun report_validator(
+        self: &mut SuiSystemState,
+        validator_address: address,
+        ctx: &mut TxContext,
+    ) {
+        let sender = tx_context::sender(ctx);
+        // Both the reporter and the reported validator have to be active validators.
+        assert!(validator_set::is_active_validator(&self.validators, sender), 0);
+        assert!(validator_set::is_active_validator(&self.validators, validator_address), 0);
+        assert!(sender != validator_address, 0);
+        if (!vec_map::contains(&self.validator_report_records, &validator_address)) {
+            vec_map::insert(&mut self.validator_report_records, validator_address, vec_set::empty());
+        };
+        let reporters = vec_map::get_mut(&mut self.validator_report_records, &validator_address);
+        if (!vec_set::contains(reporters, &sender)) {
+            vec_set::insert(reporters, sender);
+        };
+    }
+
+    /// A validator can call this entry function to withdraw a report it made during the
+    /// current epoch. Aborts if the sender didn't report `validator_address`.
+    public entry fun undo_report_validator(
+        self: &mut SuiSystemState,
+        validator_address: address,
+        ctx: &mut TxContext,
+    ) {
+        let sender = tx_context::sender(ctx);
+        assert!(vec_map::contains(&self.validator_report_records, &validator_address), 0);
+        let reporters = vec_map::get_mut(&mut self.validator_report_records, &validator_address);
+        assert!(vec_set::contains(reporters, &sender), 0);
+        vec_set::remove(reporters, &sender);
+        if (vec_set::is_empty(reporters)) {
+            vec_map::remove(&mut self.validator_report_records, &validator_address);
+        };
+    }
+
+    /// A validator can call this entry function to vote for adding `publisher` to the publisher
+    /// allowlist, or for removing it if it is already allowed. The change is applied as soon as
+    /// validators holding a quorum of the stake voted for it. Aborts if the network has no
+    /// publisher allowlist.
+    public entry fun vote_publisher_allowlist_change(
+        self: &mut SuiSystemState,
+        publisher: address,
+        ctx: &mut TxContext,
+    ) {
+        let sender = tx_context::sender(ctx);
+        assert!(option::is_some(&self.publisher_allowlist), 0);
+        assert!(validator_set::is_active_validator(&self.validators, sender), 0);
+        if (!vec_map::contains(&self.publisher_allowlist_votes, &publisher)) {
+            vec_map::insert(&mut self.publisher_allowlist_votes, publisher, vec_set::empty());
+        };
+        let voters = vec_map::get_mut(&mut self.publisher_allowlist_votes, &publisher);
+        if (!vec_set::contains(voters, &sender)) {
+            vec_set::insert(voters, sender);
+        };
+        let voters = vec_set::into_keys(*voters);
+        if (validator_set::is_quorum(&self.validators, &voters)) {
+            vec_map::remove(&mut self.publisher_allowlist_votes, &publisher);
+            let allowlist = option::borrow_mut(&mut self.publisher_allowlist);
+            if (vec_set::contains(allowlist, &publisher)) {
+                vec_set::remove(allowlist, &publisher);
+            } else {
+                vec_set::insert(allowlist, publisher);
+            };
+        };
+    }
+
     /// This function should be called at the end of an epoch, and advances the system to the next epoch.
     /// It does the following things:
     /// 1. Add storage charge to the storage fund.
-    /// 2. Distribute computation charge to validator stake and delegation stake.
+    /// 2. Distribute computation charge to validator stake and delegation stake, except to the
+    ///    validators reported by a quorum of their peers.
     /// 3. Create reward information records for each validator in this epoch.
     /// 4. Update all validators.
+    /// 5. Clear the validator reports and the publisher allowlist votes of the epoch.
+    /// The charges held since the system entered safe mode are settled along with those of the
+    /// epoch, and the system leaves safe mode.
     public entry fun advance_epoch(
         self: &mut SuiSystemState,
         new_epoch: u64,
@@ -273,6 +426,12 @@ module sui::sui_system {
         // Validator will make a special system call with sender set as 0x0.
         assert!(tx_context::sender(ctx) == @0x0, 0);
 
+        let storage_charge = storage_charge + self.safe_mode_storage_charge;
+        let computation_charge = computation_charge + self.safe_mode_computation_charge;
+        self.safe_mode = false;
+        self.safe_mode_storage_charge = 0;
+        self.safe_mode_computation_charge = 0;
+
         let storage_reward = balance::increase_supply(&mut self.sui_supply, storage_charge);
         let computation_reward = balance::increase_supply(&mut self.sui_supply, computation_charge);
 
@@ -292,17 +451,47 @@ module sui::sui_system {
             &mut self.validators,
             &mut computation_reward,
             &mut delegator_reward,
+            &self.validator_report_records,
             ctx,
         );
+        self.validator_report_records = vec_map::empty();
+        self.publisher_allowlist_votes = vec_map::empty();
         // Derive the reference gas price for the new epoch
         self.reference_gas_price = validator_set::derive_reference_gas_price(&self.validators);
         // Because of precision issues with integer divisions, we expect that there will be some
-        // remaining balance in `delegator_reward` and `computation_reward`. All of these go to 
-        // the storage fund.
+        // remaining balance in `delegator_reward` and `computation_reward`, along with the slashed
+        // rewards of reported validators. All of these go to the storage fund.
         balance::join(&mut self.storage_fund, delegator_reward);
         balance::join(&mut self.storage_fund, computation_reward);
     }
 
+    /// Advances the system to the next epoch when `advance_epoch` failed, so that the network
+    /// keeps running instead of halting. Only the epoch number moves forward, the charges of the
+    /// epoch are held until `advance_epoch` succeeds again, e.g. once the system packages are
+    /// fixed, and the validators, their reports and the publisher allowlist votes carry over.
+    public entry fun advance_epoch_safe_mode(
+        self: &mut SuiSystemState,
+        new_epoch: u64,
+        storage_charge: u64,
+        computation_charge: u64,
+        ctx: &mut TxContext,
+    ) {
+        // Validator will make a special system call with sender set as 0x0.
+        assert!(tx_context::sender(ctx) == @0x0, 0);
+
+        self.epoch = self.epoch + 1;
+        // Sanity check to make sure we are advancing to the right epoch.
+        assert!(new_epoch == self.epoch, 0);
+        self.safe_mode = true;
+        self.safe_mode_storage_charge = self.safe_mode_storage_charge + storage_charge;
+        self.safe_mode_computation_charge = self.safe_mode_computation_charge + computation_charge;
+    }
+
+    /// Whether the last epoch change failed and the system is in safe mode.
+    public fun is_safe_mode(self: &SuiSystemState): bool {
+        self.safe_mode
+    }
+
     /// Return the current epoch number. Useful for applications that need a coarse-grained concept of time,
     /// since epochs are ever-increasing and epoch changes are intended to happen every 24 hours.
     public fun epoch(self: &SuiSystemState): u64 {
@@ -315,6 +504,32 @@ module sui::sui_system {
         validator_set::validator_delegate_amount(&self.validators, validator_addr)
     }
 
+    /// Returns the active validator of `validator_addr`, to read its metadata.
+    /// Aborts if `validator_addr` is not an active validator.
+    public fun active_validator(self: &SuiSystemState, validator_addr: address): &Validator {
+        validator_set::active_validator(&self.validators, validator_addr)
+    }
+
+    /// Returns the validators which reported `validator_addr` during the current epoch.
+    public fun validator_reporters(self: &SuiSystemState, validator_addr: address): vector<address> {
+        if (vec_map::contains(&self.validator_report_records, &validator_addr)) {
+            vec_set::into_keys(*vec_map::get(&self.validator_report_records, &validator_addr))
+        } else {
+            vector[]
+        }
+    }
+
+    /// Returns whether `publisher` is allowed to publish packages.
+    public fun is_allowed_publisher(self: &SuiSystemState, publisher: address): bool {
+        option::is_none(&self.publisher_allowlist)
+            || vec_set::contains(option::borrow(&self.publisher_allowlist), &publisher)
+    }
+
+    #[test_only]
+    public fun storage_fund_balance_for_testing(self: &SuiSystemState): u64 {
+        balance::value(&self.storage_fund)
+    }
+
     #[test_only]
     public fun set_epoch_for_testing(self: &mut SuiSystemState, epoch_num: u64) {
         self.epoch = epoch_num
@@ -5,6 +5,7 @@ module sui::validator {
     use std::ascii;
     use std::vector;
     use std::bcs;
+    use std::string;
 
     use sui::balance::{Self, Balance};
     use sui::sui::SUI;
@@ -12,7 +13,7 @@ module sui::validator {
     use sui::stake;
     use sui::stake::Stake;
     use sui::epoch_time_lock::EpochTimeLock;
-    use std::option::Option;
+    use std::option::{Self, Option};
     use sui::crypto::Self;
     use sui::staking_pool::{Self, Delegation, StakedSui, StakingPool};
 
@@ -41,6 +42,10 @@ module sui::validator {
         proof_of_possession: vector<u8>,
         /// A unique human-readable name of this validator.
         name: vector<u8>,
+        /// A UTF-8 description of the validator, empty if the validator didn't set one.
+        description: vector<u8>,
+        /// The URL of an image representing the validator, empty if the validator didn't set one.
+        image_url: vector<u8>,
         /// The network address of the validator (could also contain extra info such as port, DNS and etc.).
         net_address: vector<u8>,
         /// Total amount of validator stake that would be active in the next epoch.
@@ -49,6 +54,10 @@ module sui::validator {
         next_epoch_delegation: u64,
         /// This validator's gas price quote for the next epoch.
         next_epoch_gas_price: u64,
+        /// The network address the validator switches to at the end of the epoch, if it requested one.
+        next_epoch_net_address: Option<vector<u8>>,
+        /// The network public key the validator switches to at the end of the epoch, if it requested one.
+        next_epoch_network_pubkey_bytes: Option<vector<u8>>,
     }
 
     struct Validator has store {
@@ -69,6 +78,24 @@ module sui::validator {
 
     const PROOF_OF_POSSESSION_DOMAIN: vector<u8> = vector[107, 111, 115, 107];
 
+    /// The name or the network address of a validator is empty or longer than MAX_NAME_LENGTH
+    /// or MAX_NET_ADDRESS_LENGTH bytes, or its description or image URL is longer than
+    /// MAX_DESCRIPTION_LENGTH bytes.
+    const EMETADATA_INVALID_LENGTH: u64 = 1;
+    /// The network address doesn't start with an IP or DNS multiaddr protocol.
+    const EMETADATA_INVALID_NET_ADDRESS: u64 = 2;
+    /// The network public key isn't an Ed25519 public key.
+    const EMETADATA_INVALID_NETWORK_PUBKEY: u64 = 3;
+
+    const MAX_NAME_LENGTH: u64 = 128;
+    const MAX_NET_ADDRESS_LENGTH: u64 = 128;
+    const MAX_DESCRIPTION_LENGTH: u64 = 1024;
+    const NETWORK_PUBKEY_LENGTH: u64 = 32;
+
+    /// The multiaddr codes of the protocols a network address may start with: ip4, ip6, dns,
+    /// dns4 and dns6.
+    const NET_ADDRESS_PROTOCOLS: vector<u8> = vector[4, 41, 53, 54, 55];
+
     fun verify_proof_of_possession(
         proof_of_possession: vector<u8>,
         sui_address: address,
@@ -119,10 +146,14 @@ module sui::validator {
                 network_pubkey_bytes,
                 proof_of_possession,
                 name,
+                description: vector::empty(),
+                image_url: vector::empty(),
                 net_address,
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
+                next_epoch_net_address: option::none(),
+                next_epoch_network_pubkey_bytes: option::none(),
             },
             stake_amount,
             pending_stake: 0,
@@ -182,6 +213,61 @@ module sui::validator {
         self.pending_withdraw = 0;
         self.gas_price = self.metadata.next_epoch_gas_price;
         assert!(self.stake_amount == self.metadata.next_epoch_stake, 0);
+        apply_next_epoch_network_info(self);
+    }
+
+    /// Switch to the network address and public key requested for the next epoch, if any.
+    public(friend) fun apply_next_epoch_network_info(self: &mut Validator) {
+        if (option::is_some(&self.metadata.next_epoch_net_address)) {
+            self.metadata.net_address = option::extract(&mut self.metadata.next_epoch_net_address);
+        };
+        if (option::is_some(&self.metadata.next_epoch_network_pubkey_bytes)) {
+            self.metadata.network_pubkey_bytes = option::extract(&mut self.metadata.next_epoch_network_pubkey_bytes);
+        };
+    }
+
+    /// Set the name of the validator, which must be human-readable. The validator set checks that
+    /// it is unique.
+    public(friend) fun update_name(self: &mut Validator, name: vector<u8>) {
+        let length = vector::length(&name);
+        assert!(length > 0 && length <= MAX_NAME_LENGTH, EMETADATA_INVALID_LENGTH);
+        ascii::string(copy name);
+        self.metadata.name = name;
+    }
+
+    /// Set the description of the validator, which must be valid UTF-8.
+    public(friend) fun update_description(self: &mut Validator, description: vector<u8>) {
+        assert!(vector::length(&description) <= MAX_DESCRIPTION_LENGTH, EMETADATA_INVALID_LENGTH);
+        string::utf8(copy description);
+        self.metadata.description = description;
+    }
+
+    /// Set the URL of the image of the validator, which must be ASCII.
+    public(friend) fun update_image_url(self: &mut Validator, image_url: vector<u8>) {
+        assert!(vector::length(&image_url) <= MAX_DESCRIPTION_LENGTH, EMETADATA_INVALID_LENGTH);
+        ascii::string(copy image_url);
+        self.metadata.image_url = image_url;
+    }
+
+    /// Request a new network address, in the binary multiaddr format, for the next epoch. The
+    /// validator keeps serving at its current address until the end of the epoch.
+    public(friend) fun update_next_epoch_net_address(self: &mut Validator, net_address: vector<u8>) {
+        let length = vector::length(&net_address);
+        assert!(length > 0 && length <= MAX_NET_ADDRESS_LENGTH, EMETADATA_INVALID_LENGTH);
+        assert!(
+            vector::contains(&NET_ADDRESS_PROTOCOLS, vector::borrow(&net_address, 0)),
+            EMETADATA_INVALID_NET_ADDRESS
+        );
+        self.metadata.next_epoch_net_address = option::some(net_address);
+    }
+
+    /// Request a new network public key for the next epoch.
+    public(friend) fun update_next_epoch_network_pubkey(self: &mut Validator, network_pubkey_bytes: vector<u8>) {
+        assert!(
+            vector::length(&network_pubkey_bytes) == NETWORK_PUBKEY_LENGTH,
+            EMETADATA_INVALID_NETWORK_PUBKEY
+        );
+        self.metadata.next_epoch_network_pubkey_bytes = option::some(network_pubkey_bytes);
     }
 
     public(friend) fun request_add_delegation(
@@ -258,6 +344,30 @@ module sui::validator {
         self.gas_price
     }
 
+    public fun name(self: &Validator): &vector<u8> {
+        &self.metadata.name
+    }
+
+    public fun description(self: &Validator): &vector<u8> {
+        &self.metadata.description
+    }
+
+    public fun image_url(self: &Validator): &vector<u8> {
+        &self.metadata.image_url
+    }
+
+    public fun net_address(self: &Validator): &vector<u8> {
+        &self.metadata.net_address
+    }
+
+    public fun network_pubkey_bytes(self: &Validator): &vector<u8> {
+        &self.metadata.network_pubkey_bytes
+    }
+
+    public fun next_epoch_net_address(self: &Validator): &Option<vector<u8>> {
+        &self.metadata.next_epoch_net_address
+    }
+
     public fun is_duplicate(self: &Validator, other: &Validator): bool {
          self.metadata.sui_address == other.metadata.sui_address
             || self.metadata.name == other.metadata.name
@@ -295,10 +405,14 @@ module sui::validator {
                 network_pubkey_bytes,
                 proof_of_possession,
                 name,
+                description: vector::empty(),
+                image_url: vector::empty(),
                 net_address,
                 next_epoch_stake: stake_amount,
                 next_epoch_delegation: 0,
                 next_epoch_gas_price: gas_price,
+                next_epoch_net_address: option::none(),
+                next_epoch_network_pubkey_bytes: option::none(),
             },
             stake_amount,
             pending_stake: 0,
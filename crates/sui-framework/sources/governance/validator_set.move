@@ -13,12 +13,20 @@ module sui::validator_set {
     use sui::staking_pool::{Self, Delegation, StakedSui};
     use sui::epoch_time_lock::EpochTimeLock;
     use sui::priority_queue as pq;
+    use sui::vec_map::{Self, VecMap};
+    use sui::vec_set::{Self, VecSet};
 
     friend sui::sui_system;
 
     #[test_only]
     friend sui::validator_set_tests;
 
+    /// Share, in basis points, of its rewards for the epoch that a validator reported by a quorum of
+    /// the stake loses, along with its delegators.
+    const REWARD_SLASHING_RATE_BPS: u128 = 10000;
+
+    const BASIS_POINT_DENOMINATOR: u128 = 10000;
+
     struct ValidatorSet has store {
         /// Total amount of stake from all active validators (not including delegation),
         /// at the beginning of the epoch.
@@ -161,6 +169,75 @@ module sui::validator_set {
         validator::request_set_gas_price(validator, new_gas_price);
     }
 
+    /// Called by `SuiSystem`, to rename the validator of the sender, active or pending. The new
+    /// name must not be used by any other validator.
+    public(friend) fun update_validator_name(
+        self: &mut ValidatorSet,
+        name: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        let validator_address = tx_context::sender(ctx);
+        assert!(
+            !is_name_used(&self.active_validators, &name, validator_address)
+                && !is_name_used(&self.pending_validators, &name, validator_address),
+            0
+        );
+        validator::update_name(get_candidate_validator_mut(self, validator_address), name);
+        self.next_epoch_validators = derive_next_epoch_validators(self);
+    }
+
+    /// Called by `SuiSystem`, to set the description of the validator of the sender.
+    public(friend) fun update_validator_description(
+        self: &mut ValidatorSet,
+        description: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        let validator = get_candidate_validator_mut(self, tx_context::sender(ctx));
+        validator::update_description(validator, description);
+        self.next_epoch_validators = derive_next_epoch_validators(self);
+    }
+
+    /// Called by `SuiSystem`, to set the image URL of the validator of the sender.
+    public(friend) fun update_validator_image_url(
+        self: &mut ValidatorSet,
+        image_url: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        let validator = get_candidate_validator_mut(self, tx_context::sender(ctx));
+        validator::update_image_url(validator, image_url);
+        self.next_epoch_validators = derive_next_epoch_validators(self);
+    }
+
+    /// Called by `SuiSystem`, to change the network address of the validator of the sender at
+    /// the end of the epoch. The address must not be used by any other validator.
+    public(friend) fun update_validator_next_epoch_net_address(
+        self: &mut ValidatorSet,
+        net_address: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        let validator_address = tx_context::sender(ctx);
+        assert!(
+            !is_net_address_used(&self.active_validators, &net_address, validator_address)
+                && !is_net_address_used(&self.pending_validators, &net_address, validator_address),
+            0
+        );
+        let validator = get_candidate_validator_mut(self, validator_address);
+        validator::update_next_epoch_net_address(validator, net_address);
+        self.next_epoch_validators = derive_next_epoch_validators(self);
+    }
+
+    /// Called by `SuiSystem`, to change the network public key of the validator of the sender
+    /// at the end of the epoch.
+    public(friend) fun update_validator_next_epoch_network_pubkey(
+        self: &mut ValidatorSet,
+        network_pubkey_bytes: vector<u8>,
+        ctx: &TxContext,
+    ) {
+        let validator = get_candidate_validator_mut(self, tx_context::sender(ctx));
+        validator::update_next_epoch_network_pubkey(validator, network_pubkey_bytes);
+        self.next_epoch_validators = derive_next_epoch_validators(self);
+    }
+
     
     public(friend) fun request_withdraw_delegation(
         self: &mut ValidatorSet,
@@ -210,7 +287,8 @@ module sui::validator_set {
 
     /// Update the validator set at the end of epoch.
     /// It does the following things:
-    ///   1. Distribute stake award.
+    ///   1. Distribute stake award, slashing the validators reported by a quorum of the stake in
+    ///      `validator_report_records`.
     ///   2. Process pending stake deposits and withdraws for each validator (`adjust_stake`).
     ///   3. Process pending validator application and withdraws.
     ///   4. At the end, we calculate the total stake for the new epoch.
@@ -218,6 +296,7 @@ module sui::validator_set {
         self: &mut ValidatorSet,
         validator_reward: &mut Balance<SUI>,
         delegator_reward: &mut Balance<SUI>,
+        validator_report_records: &VecMap<address, VecSet<address>>,
         ctx: &mut TxContext,
     ) {
         // `compute_reward_distribution` must be called before `adjust_stake` to make sure we are using the current
@@ -229,6 +308,14 @@ module sui::validator_set {
             self.total_delegation_stake,
             balance::value(delegator_reward),
         );
+        // The slashed rewards are left in `validator_reward` and `delegator_reward`.
+        let slashed_validators = compute_slashed_validators(self, validator_report_records);
+        slash_rewards(
+            &self.active_validators,
+            &slashed_validators,
+            &mut validator_reward_amounts,
+            &mut delegator_reward_amounts,
+        );
 
         // `adjust_stake_and_gas_price` must be called before `distribute_reward`, because reward distribution goes to
         // each validator's pending stake, and that shouldn't be available in the next epoch.
@@ -298,11 +385,57 @@ module sui::validator_set {
         validator::stake_amount(validator)
     }
 
+    /// The active validator of `validator_address`. Aborts if there is none.
+    public fun active_validator(self: &ValidatorSet, validator_address: address): &Validator {
+        get_validator_ref(&self.active_validators, validator_address)
+    }
+
     public fun validator_delegate_amount(self: &ValidatorSet, validator_address: address): u64 {
         let validator = get_validator_ref(&self.active_validators, validator_address);
         validator::delegate_amount(validator)
     }
 
+    /// Returns the validators reported by validators holding at least a quorum of the stake, by
+    /// tallying `validator_report_records`.
+    public fun compute_slashed_validators(
+        self: &ValidatorSet,
+        validator_report_records: &VecMap<address, VecSet<address>>,
+    ): vector<address> {
+        let slashed_validators = vector::empty();
+        let length = vec_map::size(validator_report_records);
+        let i = 0;
+        while (i < length) {
+            let (validator_address, reporters) = vec_map::get_entry_by_idx(validator_report_records, i);
+            let reporters = vec_set::into_keys(*reporters);
+            if (is_quorum(self, &reporters)) {
+                vector::push_back(&mut slashed_validators, *validator_address);
+            };
+            i = i + 1;
+        };
+        slashed_validators
+    }
+
+    /// Returns whether the active validators among `addresses` hold at least a quorum of the stake.
+    public fun is_quorum(self: &ValidatorSet, addresses: &vector<address>): bool {
+        sum_stake(&self.active_validators, addresses) >= self.quorum_stake_threshold
+    }
+
+    /// Returns the total stake, including delegation, of the active validators among `addresses`.
+    public fun sum_stake(validators: &vector<Validator>, addresses: &vector<address>): u64 {
+        let sum = 0;
+        let length = vector::length(addresses);
+        let i = 0;
+        while (i < length) {
+            let validator_index_opt = find_validator(validators, *vector::borrow(addresses, i));
+            if (option::is_some(&validator_index_opt)) {
+                let validator = vector::borrow(validators, option::extract(&mut validator_index_opt));
+                sum = sum + validator::stake_amount(validator) + validator::delegate_amount(validator);
+            };
+            i = i + 1;
+        };
+        sum
+    }
+
     /// Checks whether a duplicate of `new_validator` is already in `validators`.
     /// Two validators duplicate if they share the same sui_address or same IP or same name.
     fun contains_duplicate_validator(validators: &vector<Validator>, new_validator: &Validator): bool {
@@ -344,6 +477,50 @@ module sui::validator_set {
         vector::borrow_mut(validators, validator_index)
     }
 
+    /// The active or pending validator of `validator_address`.
+    fun get_candidate_validator_mut(
+        self: &mut ValidatorSet,
+        validator_address: address,
+    ): &mut Validator {
+        if (option::is_some(&find_validator(&self.pending_validators, validator_address))) {
+            get_validator_mut(&mut self.pending_validators, validator_address)
+        } else {
+            get_validator_mut(&mut self.active_validators, validator_address)
+        }
+    }
+
+    /// Whether a validator of `validators` other than `validator_address` is named `name`.
+    fun is_name_used(validators: &vector<Validator>, name: &vector<u8>, validator_address: address): bool {
+        let length = vector::length(validators);
+        let i = 0;
+        while (i < length) {
+            let v = vector::borrow(validators, i);
+            if (validator::sui_address(v) != validator_address && validator::name(v) == name) {
+                return true
+            };
+            i = i + 1;
+        };
+        false
+    }
+
+    /// Whether a validator of `validators` other than `validator_address` uses `net_address`,
+    /// now or from the next epoch.
+    fun is_net_address_used(validators: &vector<Validator>, net_address: &vector<u8>, validator_address: address): bool {
+        let length = vector::length(validators);
+        let i = 0;
+        while (i < length) {
+            let v = vector::borrow(validators, i);
+            let next_epoch_net_address = validator::next_epoch_net_address(v);
+            if (validator::sui_address(v) != validator_address
+                && (validator::net_address(v) == net_address
+                    || option::contains(next_epoch_net_address, net_address))) {
+                return true
+            };
+            i = i + 1;
+        };
+        false
+    }
+
     fun get_validator_ref(
         validators: &vector<Validator>,
         validator_address: address,
@@ -375,6 +552,7 @@ module sui::validator_set {
     ) {
         while (!vector::is_empty(pending_validators)) {
             let v = vector::pop_back(pending_validators);
+            validator::apply_next_epoch_network_info(&mut v);
             vector::push_back(validators, v);
         }
     }
@@ -469,6 +647,33 @@ module sui::validator_set {
         (validator_reward_amounts, delegator_reward_amounts)
     }
 
+    /// Reduce the rewards of the `slashed_validators` and of their delegators by the slashing rate.
+    fun slash_rewards(
+        validators: &vector<Validator>,
+        slashed_validators: &vector<address>,
+        validator_reward_amounts: &mut vector<u64>,
+        delegator_reward_amounts: &mut vector<u64>,
+    ) {
+        let length = vector::length(validators);
+        let i = 0;
+        while (i < length) {
+            let validator = vector::borrow(validators, i);
+            if (vector::contains(slashed_validators, &validator::sui_address(validator))) {
+                let reward_amount = vector::borrow_mut(validator_reward_amounts, i);
+                *reward_amount = slash(*reward_amount);
+                let delegator_reward_amount = vector::borrow_mut(delegator_reward_amounts, i);
+                *delegator_reward_amount = slash(*delegator_reward_amount);
+            };
+            i = i + 1;
+        }
+    }
+
+    fun slash(reward_amount: u64): u64 {
+        // Use u128 to avoid multiplication overflow.
+        let slashed = (reward_amount as u128) * REWARD_SLASHING_RATE_BPS / BASIS_POINT_DENOMINATOR;
+        reward_amount - (slashed as u64)
+    }
+
     fun distribute_reward(
         validators: &mut vector<Validator>,
         validator_reward_amounts: &vector<u64>,
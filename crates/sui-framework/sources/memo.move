@@ -0,0 +1,36 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// Memos attached to transfers, e.g. the deposit memos exchanges need to attribute a deposit to
+/// an account. A memo is attached by calling `attach` in the same batch transaction as the
+/// transfer, and shows up as a `MemoEvent` in the events of the transaction.
+module sui::memo {
+    use std::string::{Self, String};
+    use sui::event;
+    use sui::tx_context::{Self, TxContext};
+
+    /// Maximum length of a memo, in bytes.
+    const MAX_MEMO_LENGTH: u64 = 256;
+
+    /// The memo is longer than `MAX_MEMO_LENGTH`.
+    const EMemoTooLong: u64 = 0;
+
+    /// The memo attached to the transfers of a transaction to `recipient`.
+    struct MemoEvent has copy, drop {
+        sender: address,
+        recipient: address,
+        memo: String,
+    }
+
+    /// Attaches `memo` to the transfers to `recipient` of the current transaction. Aborts if
+    /// `memo` isn't valid UTF-8 or is longer than `MAX_MEMO_LENGTH` bytes.
+    public entry fun attach(recipient: address, memo: vector<u8>, ctx: &mut TxContext) {
+        let memo = string::utf8(memo);
+        assert!(string::length(&memo) <= MAX_MEMO_LENGTH, EMemoTooLong);
+        event::emit(MemoEvent {
+            sender: tx_context::sender(ctx),
+            recipient,
+            memo,
+        })
+    }
+}
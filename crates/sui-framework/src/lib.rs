@@ -4,12 +4,20 @@
 use move_binary_format::CompiledModule;
 use move_bytecode_utils::Modules;
 use move_cli::base::test::UnitTestResult;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::gas_algebra::InternalGas;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
 use move_unit_test::UnitTestingConfig;
 use num_enum::TryFromPrimitive;
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
 use std::path::Path;
+use std::str::FromStr;
 use sui_types::{
     error::{SuiError, SuiResult},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS,
@@ -21,39 +29,270 @@ pub mod natives;
 pub use sui_framework_build::build_move_stdlib_modules as get_move_stdlib_modules;
 pub use sui_framework_build::verify_modules;
 use sui_framework_build::{build_move_package_with_deps, filter_package_modules};
+
+pub use sui_framework_build::abi::{FunctionAbi, ModuleAbi, PackageAbi, StructAbi};
+pub use sui_framework_build::compatibility::{
+    check_compatibility, CompatibilityReport, Incompatibility, IncompatibilityKind,
+};
+pub use sui_framework_build::named_addresses::{BuildMetadata, NamedAddresses};
 use sui_types::sui_serde::{Base64, Encoding};
 
 // Move unit tests will halt after executing this many steps. This is a protection to avoid divergence
 const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 100_000;
 
-static SUI_FRAMEWORK: Lazy<Vec<CompiledModule>> = Lazy::new(|| {
-    const SUI_FRAMEWORK_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/sui-framework"));
+pub const MOVE_STDLIB_PACKAGE_NAME: &str = "move-stdlib";
+pub const SUI_FRAMEWORK_PACKAGE_NAME: &str = "sui-framework";
+
+/// A Move package built into this binary and published at genesis.
+#[derive(Clone)]
+pub struct SystemPackage {
+    /// The name the package is embedded as, e.g. `sui-framework`, or its name in the
+    /// `SUI_FRAMEWORK_SYSTEM_PACKAGES` manifest the framework was built with.
+    pub name: String,
+    /// The modules of the package, in dependency order. They all have the address the package is
+    /// published at.
+    pub modules: Vec<CompiledModule>,
+}
+
+impl SystemPackage {
+    pub fn address(&self) -> AccountAddress {
+        *self.modules[0].self_id().address()
+    }
+}
+
+/// Which build of the system packages to use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Flavor {
+    /// Built without their `#[test_only]` modules and functions, as published on real networks.
+    Production,
+    /// Built in test mode, with their `#[test_only]` modules and functions, e.g.
+    /// `sui::test_scenario`, for local networks and tests which call test-only helpers.
+    Test,
+}
+
+impl Default for Flavor {
+    fn default() -> Self {
+        Flavor::Production
+    }
+}
+
+impl fmt::Display for Flavor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Flavor::Production => write!(f, "production"),
+            Flavor::Test => write!(f, "test"),
+        }
+    }
+}
 
-    let serialized_modules: Vec<Vec<u8>> = bcs::from_bytes(SUI_FRAMEWORK_BYTES).unwrap();
+impl FromStr for Flavor {
+    type Err = String;
 
-    serialized_modules
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "production" => Ok(Flavor::Production),
+            "test" => Ok(Flavor::Test),
+            _ => Err(format!(
+                "Unknown framework flavor '{s}', expected production or test"
+            )),
+        }
+    }
+}
+
+fn deserialize_system_packages(bytes: &[u8]) -> Vec<SystemPackage> {
+    let serialized_packages: Vec<(String, Vec<Vec<u8>>)> = bcs::from_bytes(bytes).unwrap();
+
+    serialized_packages
         .into_iter()
-        .map(|module| CompiledModule::deserialize(&module).unwrap())
+        .map(|(name, modules)| SystemPackage {
+            name,
+            modules: modules
+                .into_iter()
+                .map(|module| CompiledModule::deserialize(&module).unwrap())
+                .collect(),
+        })
         .collect()
+}
+
+/// The stdlib, the framework, then the extra system packages, in the order they are published.
+static SYSTEM_PACKAGES: Lazy<Vec<SystemPackage>> = Lazy::new(|| {
+    deserialize_system_packages(include_bytes!(concat!(env!("OUT_DIR"), "/system-packages")))
 });
 
-static MOVE_STDLIB: Lazy<Vec<CompiledModule>> = Lazy::new(|| {
-    const MOVE_STDLIB_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/move-stdlib"));
+/// The same packages as `SYSTEM_PACKAGES`, built in test mode.
+static TEST_SYSTEM_PACKAGES: Lazy<Vec<SystemPackage>> = Lazy::new(|| {
+    deserialize_system_packages(include_bytes!(concat!(
+        env!("OUT_DIR"),
+        "/system-packages-test"
+    )))
+});
 
-    let serialized_modules: Vec<Vec<u8>> = bcs::from_bytes(MOVE_STDLIB_BYTES).unwrap();
+/// Markdown docs of the modules of the system packages, generated from their sources by the Move
+/// docgen, by module id.
+static FRAMEWORK_DOCS: Lazy<BTreeMap<String, String>> = Lazy::new(|| {
+    const FRAMEWORK_DOCS_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/framework-docs"));
 
-    serialized_modules
-        .into_iter()
-        .map(|module| CompiledModule::deserialize(&module).unwrap())
-        .collect()
+    bcs::from_bytes(FRAMEWORK_DOCS_BYTES).unwrap()
 });
 
 pub fn get_sui_framework() -> Vec<CompiledModule> {
-    Lazy::force(&SUI_FRAMEWORK).to_owned()
+    get_system_package(SUI_FRAMEWORK_PACKAGE_NAME)
+        .unwrap()
+        .modules
+        .to_owned()
 }
 
 pub fn get_move_stdlib() -> Vec<CompiledModule> {
-    Lazy::force(&MOVE_STDLIB).to_owned()
+    get_system_package(MOVE_STDLIB_PACKAGE_NAME)
+        .unwrap()
+        .modules
+        .to_owned()
+}
+
+/// The packages built into this binary and published at genesis, in publish order: the stdlib,
+/// the framework, then the packages of the `SUI_FRAMEWORK_SYSTEM_PACKAGES` manifest, if the
+/// framework was built with one. These are the production builds, see `get_modules` for the
+/// builds with test-only code.
+pub fn get_system_packages() -> &'static [SystemPackage] {
+    get_modules(Flavor::Production)
+}
+
+/// The system packages as built for `flavor`, in publish order.
+pub fn get_modules(flavor: Flavor) -> &'static [SystemPackage] {
+    match flavor {
+        Flavor::Production => Lazy::force(&SYSTEM_PACKAGES),
+        Flavor::Test => Lazy::force(&TEST_SYSTEM_PACKAGES),
+    }
+}
+
+/// What the system packages embedded in this binary were built with.
+static BUILD_METADATA: Lazy<BuildMetadata> = Lazy::new(|| {
+    bcs::from_bytes(include_bytes!(concat!(
+        env!("OUT_DIR"),
+        "/framework-metadata"
+    )))
+    .unwrap()
+});
+
+/// The named addresses overridden when the system packages embedded in this binary were built,
+/// e.g. `sui` for a fork relocating the framework, empty if none were.
+pub fn named_addresses() -> &'static NamedAddresses {
+    &Lazy::force(&BUILD_METADATA).named_addresses
+}
+
+pub fn get_system_package(name: &str) -> Option<&'static SystemPackage> {
+    get_system_packages()
+        .iter()
+        .find(|package| package.name == name)
+}
+
+/// The constants declared by the modules of the framework, by module, e.g.
+/// `constants::memo::MAX_MEMO_LENGTH`, read from its sources and checked against its modules when
+/// it is built. Abort codes keep their Move names, e.g. `constants::coin::ENotEnough`.
+#[allow(non_upper_case_globals)]
+pub mod constants {
+    include!(concat!(env!("OUT_DIR"), "/framework-constants.rs"));
+}
+
+static FRAMEWORK_ABI_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/framework-abi.json"));
+
+static FRAMEWORK_ABI: Lazy<Vec<PackageAbi>> =
+    Lazy::new(|| serde_json::from_str(FRAMEWORK_ABI_JSON).unwrap());
+
+/// The public and entry functions and the struct layouts of the modules of each system package,
+/// as generated when they were built, in publish order.
+pub fn abi() -> &'static [PackageAbi] {
+    Lazy::force(&FRAMEWORK_ABI)
+}
+
+/// The JSON of `abi()`, for tools generating code from it, e.g. SDKs.
+pub fn abi_json() -> &'static str {
+    FRAMEWORK_ABI_JSON
+}
+
+/// The ABI of the published package `name`, whose serialized modules are `modules`, e.g. those of
+/// its package object on chain.
+pub fn published_package_abi<'a>(
+    name: &str,
+    modules: impl IntoIterator<Item = &'a [u8]>,
+) -> anyhow::Result<PackageAbi> {
+    let modules = modules
+        .into_iter()
+        .map(|bytes| {
+            CompiledModule::deserialize(bytes)
+                .map_err(|e| anyhow::anyhow!("Invalid module of package {}: {:?}", name, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(sui_framework_build::abi::package_abi(name, &modules))
+}
+
+/// Reads the ABI of published system packages from `path`, which holds either the JSON of their
+/// `abi()`, or their modules as serialized into a build of this crate, e.g. the `system-packages`
+/// of its `OUT_DIR`.
+pub fn load_published_abi(path: &Path) -> anyhow::Result<Vec<PackageAbi>> {
+    let bytes = std::fs::read(path)?;
+    if let Ok(abi) = serde_json::from_slice(&bytes) {
+        return Ok(abi);
+    }
+    let packages: Vec<(String, Vec<Vec<u8>>)> = bcs::from_bytes(&bytes).map_err(|_| {
+        anyhow::anyhow!(
+            "{} holds neither the ABI nor the modules of system packages",
+            path.display()
+        )
+    })?;
+    packages
+        .iter()
+        .map(|(name, modules)| published_package_abi(name, modules.iter().map(Vec::as_slice)))
+        .collect()
+}
+
+/// Checks that the system packages embedded in this binary can replace the `published` ones,
+/// i.e. that they keep the layout of their structs and the signature of their public and entry
+/// functions.
+pub fn check_upgrade_compatibility(published: &[PackageAbi]) -> CompatibilityReport {
+    check_compatibility(published, abi())
+}
+
+/// Digest of the system packages embedded in this binary, computed when they were built.
+/// Binaries embedding the same module bytes have the same digest.
+pub fn framework_digest() -> [u8; 32] {
+    framework_digest_of(Flavor::Production)
+}
+
+/// Digest of the system packages of `flavor` embedded in this binary.
+pub fn framework_digest_of(flavor: Flavor) -> [u8; 32] {
+    match flavor {
+        Flavor::Production => *include_bytes!(concat!(env!("OUT_DIR"), "/framework-digest")),
+        Flavor::Test => *include_bytes!(concat!(env!("OUT_DIR"), "/framework-digest-test")),
+    }
+}
+
+/// The markdown docs of module `module` of a system package, as built into this binary, or `None`
+/// if it isn't one of their modules.
+pub fn get_module_docs(module: &ModuleId) -> Option<&'static str> {
+    Lazy::force(&FRAMEWORK_DOCS)
+        .get(&module.to_string())
+        .map(String::as_str)
+}
+
+/// The markdown docs of the framework or stdlib module `module_name`, either qualified by its
+/// named address, as in `sui::coin` or `std::vector`, or by name alone, in which case a framework
+/// module comes before a stdlib module of the same name.
+pub fn module_docs(module_name: &str) -> Option<&'static str> {
+    let (addresses, name) = match module_name.split_once("::") {
+        Some(("sui", name)) => (&[SUI_FRAMEWORK_ADDRESS][..], name),
+        Some(("std", name)) => (&[MOVE_STDLIB_ADDRESS][..], name),
+        Some(_) => return None,
+        None => (
+            &[SUI_FRAMEWORK_ADDRESS, MOVE_STDLIB_ADDRESS][..],
+            module_name,
+        ),
+    };
+    let name = Identifier::new(name).ok()?;
+    addresses
+        .iter()
+        .find_map(|address| get_module_docs(&ModuleId::new(*address, name.clone())))
 }
 
 pub const DEFAULT_FRAMEWORK_PATH: &str = env!("CARGO_MANIFEST_DIR");
@@ -136,6 +375,24 @@ pub fn run_move_unit_tests(
     build_config: BuildConfig,
     config: Option<UnitTestingConfig>,
     compute_coverage: bool,
+) -> anyhow::Result<UnitTestResult> {
+    run_move_unit_tests_with_writer(
+        path,
+        build_config,
+        config,
+        compute_coverage,
+        &mut std::io::stdout(),
+    )
+}
+
+/// Runs the Move unit tests of the package at `path` like `run_move_unit_tests`, writing their
+/// report to `writer`.
+pub fn run_move_unit_tests_with_writer<W: Write + Send>(
+    path: &Path,
+    build_config: BuildConfig,
+    config: Option<UnitTestingConfig>,
+    compute_coverage: bool,
+    writer: &mut W,
 ) -> anyhow::Result<UnitTestResult> {
     let config = config
         .unwrap_or_else(|| UnitTestingConfig::default_with_bound(Some(MAX_UNIT_TEST_INSTRUCTIONS)));
@@ -149,7 +406,7 @@ pub fn run_move_unit_tests(
         },
         natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS),
         compute_coverage,
-        &mut std::io::stdout(),
+        writer,
     )
 }
 
@@ -225,6 +482,7 @@ fn verify_framework_version(pkg: &CompiledPackage) -> SuiResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use move_binary_format::access::ModuleAccess;
     use std::path::PathBuf;
 
     #[test]
@@ -246,6 +504,228 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn test_framework_digest_matches_embedded_modules() {
+        let packages: Vec<_> = get_system_packages()
+            .iter()
+            .map(|package| package.modules.as_slice())
+            .collect();
+        assert_eq!(
+            framework_digest(),
+            sui_framework_build::framework_digest(&packages)
+        );
+    }
+
+    #[test]
+    fn test_flavors() {
+        for flavor in [Flavor::Production, Flavor::Test] {
+            let packages: Vec<_> = get_modules(flavor)
+                .iter()
+                .map(|package| package.modules.as_slice())
+                .collect();
+            assert_eq!(
+                framework_digest_of(flavor),
+                sui_framework_build::framework_digest(&packages)
+            );
+        }
+        let has_test_scenario = |flavor| {
+            get_modules(flavor)
+                .iter()
+                .flat_map(|package| &package.modules)
+                .any(|module| module.self_id().name().as_str() == "test_scenario")
+        };
+        assert!(!has_test_scenario(Flavor::Production));
+        assert!(has_test_scenario(Flavor::Test));
+        let names = |flavor| {
+            get_modules(flavor)
+                .iter()
+                .map(|package| package.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(Flavor::Production), names(Flavor::Test));
+    }
+
+    #[test]
+    fn test_framework_constants() {
+        assert_eq!(
+            constants::memo::MAX_MEMO_LENGTH,
+            sui_types::memo::MAX_MEMO_LENGTH as u64
+        );
+        assert_eq!(
+            constants::object::SUI_SYSTEM_STATE_OBJECT_ID,
+            AccountAddress::from(sui_types::SUI_SYSTEM_STATE_OBJECT_ID)
+        );
+        assert_eq!(constants::coin::ENotEnough, 2);
+
+        use sui_framework_build::constants::{parse_constants, ConstantValue};
+        let parsed: Vec<_> = parse_constants(
+            "module sui::m {
+                const A: u64 = 1_000; // a comment
+                const B: address = @0x5;
+                #[test_only]
+                const C: u8 = 1;
+                const D: vector<u8> = b\"d\";
+                const E: u8 = 0xffu8;
+            }",
+        )
+        .into_iter()
+        .map(|constant| (constant.module, constant.name, constant.value))
+        .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("m".to_string(), "A".to_string(), ConstantValue::U64(1_000)),
+                (
+                    "m".to_string(),
+                    "B".to_string(),
+                    ConstantValue::Address(AccountAddress::from_hex_literal("0x5").unwrap())
+                ),
+                ("m".to_string(), "E".to_string(), ConstantValue::U8(0xff)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_abi_matches_embedded_modules() {
+        let abi = abi();
+        assert_eq!(abi.len(), get_system_packages().len());
+        let framework = abi
+            .iter()
+            .find(|package| package.name == SUI_FRAMEWORK_PACKAGE_NAME)
+            .unwrap();
+        assert_eq!(framework.address, SUI_FRAMEWORK_ADDRESS.to_hex_literal());
+        let coin = framework
+            .modules
+            .iter()
+            .find(|module| module.name == "coin")
+            .unwrap();
+        let split = coin.functions.iter().find(|f| f.name == "split").unwrap();
+        assert!(split.is_entry);
+        assert_eq!(split.visibility, "public");
+        assert_eq!(
+            split.parameters,
+            vec![
+                "&mut 0x2::coin::Coin<T0>",
+                "u64",
+                "&mut 0x2::tx_context::TxContext"
+            ]
+        );
+        let coin_struct = coin.structs.iter().find(|s| s.name == "Coin").unwrap();
+        assert_eq!(coin_struct.abilities, vec!["store", "key"]);
+    }
+
+    #[test]
+    fn test_upgrade_compatibility() {
+        let published: Vec<_> = get_system_packages()
+            .iter()
+            .map(|package| {
+                let modules: Vec<Vec<u8>> = package
+                    .modules
+                    .iter()
+                    .map(|module| {
+                        let mut bytes = Vec::new();
+                        module.serialize(&mut bytes).unwrap();
+                        bytes
+                    })
+                    .collect();
+                published_package_abi(&package.name, modules.iter().map(Vec::as_slice)).unwrap()
+            })
+            .collect();
+        assert_eq!(published, abi());
+        assert!(check_upgrade_compatibility(&published).is_compatible());
+
+        // A framework which no longer has coin::split, and whose coins lost their balance
+        let mut published = published;
+        let framework = published
+            .iter_mut()
+            .find(|package| package.name == SUI_FRAMEWORK_PACKAGE_NAME)
+            .unwrap();
+        let coin = framework
+            .modules
+            .iter_mut()
+            .find(|module| module.name == "coin")
+            .unwrap();
+        coin.functions.retain(|f| f.name != "split");
+        coin.structs
+            .iter_mut()
+            .find(|s| s.name == "Coin")
+            .unwrap()
+            .fields
+            .pop();
+        // Removing a function the new build has is compatible
+        let report = check_upgrade_compatibility(&published);
+        assert_eq!(report.incompatibilities.len(), 1);
+        assert!(matches!(
+            &report.incompatibilities[0],
+            Incompatibility {
+                module: Some(module),
+                kind: IncompatibilityKind::StructLayoutChanged { name, .. },
+                ..
+            } if module == "coin" && name == "Coin"
+        ));
+
+        // The other way around, the new build removes coin::split and changes the layout of Coin
+        let report = check_compatibility(abi(), &published);
+        let kinds: Vec<_> = report.incompatibilities.iter().map(|i| &i.kind).collect();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&&IncompatibilityKind::FunctionRemoved {
+            name: "split".to_string()
+        }));
+    }
+
+    #[test]
+    fn test_system_packages() {
+        let packages = get_system_packages();
+        assert_eq!(packages[0].name, MOVE_STDLIB_PACKAGE_NAME);
+        assert_eq!(packages[0].address(), MOVE_STDLIB_ADDRESS);
+        assert_eq!(packages[1].name, SUI_FRAMEWORK_PACKAGE_NAME);
+        assert_eq!(packages[1].address(), SUI_FRAMEWORK_ADDRESS);
+        for package in packages {
+            assert!(package
+                .modules
+                .iter()
+                .all(|m| m.self_id().address() == &package.address()));
+        }
+    }
+
+    #[test]
+    fn test_named_addresses_match_system_packages() {
+        let packages = get_system_packages();
+        let address_of = |name: &str, default| *named_addresses().get(name).unwrap_or(&default);
+        assert_eq!(
+            packages[0].address(),
+            address_of("std", MOVE_STDLIB_ADDRESS)
+        );
+        assert_eq!(
+            packages[1].address(),
+            address_of("sui", SUI_FRAMEWORK_ADDRESS)
+        );
+    }
+
+    #[test]
+    fn test_docs_of_embedded_modules() {
+        for module in get_move_stdlib().iter().chain(&get_sui_framework()) {
+            let docs = get_module_docs(&module.self_id()).unwrap();
+            assert!(docs.contains(module.self_id().name().as_str()));
+        }
+        let unknown = ModuleId::new(
+            SUI_FRAMEWORK_ADDRESS,
+            Identifier::new("no_such_module").unwrap(),
+        );
+        assert!(get_module_docs(&unknown).is_none());
+    }
+
+    #[test]
+    fn test_module_docs_by_name() {
+        let coin = module_docs("coin").unwrap();
+        assert_eq!(module_docs("sui::coin"), Some(coin));
+        assert!(module_docs("std::coin").is_none());
+        assert!(module_docs("vector").is_some());
+        assert!(module_docs("std::vector").is_some());
+        assert!(module_docs("other::coin").is_none());
+        assert!(module_docs("no such module").is_none());
+    }
+
     #[test]
     #[cfg_attr(msim, ignore)]
     fn run_examples_move_unit_tests() {
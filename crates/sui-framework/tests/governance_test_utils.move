@@ -9,7 +9,9 @@ module sui::governance_test_utils {
     use sui::validator::{Self, Validator};
     use sui::sui_system::{Self, SuiSystemState};
     use sui::test_scenario::{Self, Scenario};
+    use sui::vec_set;
     use std::option;
+    use std::vector;
 
     public fun create_validator_for_testing(
         addr: address, init_stake_amount: u64, ctx: &mut TxContext
@@ -38,6 +40,25 @@ module sui::governance_test_utils {
             1024, // max_validator_candidate_count
             0, // min_validator_stake
             1, //storage_gas_price
+            option::none(), // publisher_allowlist
+        )
+    }
+
+    public fun create_sui_system_state_with_publisher_allowlist_for_testing(
+        validators: vector<Validator>, publisher_allowlist: vector<address>
+    ) {
+        let allowlist = vec_set::empty();
+        while (!vector::is_empty(&publisher_allowlist)) {
+            vec_set::insert(&mut allowlist, vector::pop_back(&mut publisher_allowlist));
+        };
+        sui_system::create(
+            validators,
+            balance::create_supply_for_testing(1000), // sui_supply
+            balance::create_for_testing<SUI>(1000), // storage_fund
+            1024, // max_validator_candidate_count
+            0, // min_validator_stake
+            1, //storage_gas_price
+            option::some(allowlist), // publisher_allowlist
         )
     }
 
@@ -46,4 +67,16 @@ module sui::governance_test_utils {
         let new_epoch = tx_context::epoch(test_scenario::ctx(scenario));
         sui_system::advance_epoch(state, new_epoch, 0, 0, &mut tx_context::dummy());
     }
+
+    public fun advance_epoch_safe_mode(
+        state: &mut SuiSystemState,
+        storage_charge: u64,
+        computation_charge: u64,
+        scenario: &mut Scenario,
+    ) {
+        test_scenario::next_epoch(scenario);
+        let new_epoch = tx_context::epoch(test_scenario::ctx(scenario));
+        sui_system::advance_epoch_safe_mode(
+            state, new_epoch, storage_charge, computation_charge, &mut tx_context::dummy());
+    }
 }
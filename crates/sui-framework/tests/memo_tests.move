@@ -0,0 +1,28 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::memo_tests {
+    use std::vector;
+    use sui::memo;
+    use sui::tx_context;
+
+    #[test]
+    fun test_attach_memo() {
+        let ctx = tx_context::dummy();
+        memo::attach(@0x42, b"deposit 1234", &mut ctx);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_attach_memo_too_long() {
+        let ctx = tx_context::dummy();
+        let memo = vector::empty();
+        let i = 0;
+        while (i < 257) {
+            vector::push_back(&mut memo, 65);
+            i = i + 1;
+        };
+        memo::attach(@0x42, memo, &mut ctx);
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs the Move unit tests of the framework and the stdlib as Rust tests, one per Move test, so
+//! that `cargo test -p sui-framework` reports them one by one and the usual test filter selects
+//! them, e.g. `cargo test -p sui-framework --test move_unit_tests coin`.
+
+use libtest_mimic::{Arguments, Failed, Trial};
+use move_package::BuildConfig;
+use move_unit_test::UnitTestingConfig;
+use once_cell::sync::OnceCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+fn main() {
+    let args = Arguments::from_args();
+    let framework_path = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut trials = vec![];
+    for path in [
+        framework_path.to_path_buf(),
+        framework_path.join("deps").join("move-stdlib"),
+    ] {
+        let names = list_tests(&path).unwrap_or_else(|e| {
+            panic!("Failed to list the Move tests of {}: {}", path.display(), e)
+        });
+        let package = Arc::new(PackageTests {
+            path,
+            outcomes: OnceCell::new(),
+        });
+        for name in names {
+            let package = package.clone();
+            let test_name = name.clone();
+            trials.push(Trial::test(name, move || package.outcome(&test_name)));
+        }
+    }
+    libtest_mimic::run(&args, trials).exit();
+}
+
+/// The tests of a Move package, which are all run at once by the first of them to run, as the
+/// Move unit test runner compiles the package on each run.
+struct PackageTests {
+    path: PathBuf,
+    outcomes: OnceCell<Result<BTreeMap<String, Outcome>, String>>,
+}
+
+enum Outcome {
+    Pass,
+    /// The report of the failure, e.g. the abort code and the stack trace.
+    Fail(String),
+}
+
+impl PackageTests {
+    fn outcome(&self, name: &str) -> Result<(), Failed> {
+        let outcomes = self
+            .outcomes
+            .get_or_init(|| run_tests(&self.path))
+            .as_ref()
+            .map_err(Failed::from)?;
+        match outcomes.get(name) {
+            Some(Outcome::Pass) => Ok(()),
+            Some(Outcome::Fail(report)) => Err(report.into()),
+            None => Err(format!("The Move test runner reported no outcome for {}", name).into()),
+        }
+    }
+}
+
+/// The fully qualified names of the tests of the package at `path`, e.g. `0x2::coin::test_mint`.
+fn list_tests(path: &Path) -> anyhow::Result<Vec<String>> {
+    let mut output = Vec::new();
+    sui_framework::run_move_unit_tests_with_writer(
+        path,
+        BuildConfig::default(),
+        Some(UnitTestingConfig {
+            list: true,
+            ..UnitTestingConfig::default_with_bound(None)
+        }),
+        false,
+        &mut output,
+    )?;
+    Ok(strip_colors(&String::from_utf8_lossy(&output))
+        .lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .map(str::to_string)
+        .collect())
+}
+
+fn run_tests(path: &Path) -> Result<BTreeMap<String, Outcome>, String> {
+    let mut output = Vec::new();
+    sui_framework::run_move_unit_tests_with_writer(
+        path,
+        BuildConfig::default(),
+        None,
+        false,
+        &mut output,
+    )
+    .map_err(|e| format!("Failed to run the Move tests of {}: {}", path.display(), e))?;
+    Ok(parse_report(&strip_colors(&String::from_utf8_lossy(
+        &output,
+    ))))
+}
+
+/// Reads the outcome of each test from the report of the Move unit test runner, which has a
+/// `[ PASS    ] 0x2::module::test` line per test, then a box with the report of each failed test
+/// under `Failures in 0x2::module:`.
+fn parse_report(report: &str) -> BTreeMap<String, Outcome> {
+    let mut passed = BTreeMap::new();
+    let mut failures = BTreeMap::new();
+    let mut module = "";
+    let mut failure: Option<(String, String)> = None;
+    for line in report.lines() {
+        if let Some((name, mut message)) = failure.take() {
+            if line.starts_with('└') {
+                failures.insert(name, message);
+            } else {
+                message.push_str(line.trim_start_matches('│').trim_start());
+                message.push('\n');
+                failure = Some((name, message));
+            }
+        } else if let Some((status, name)) = line
+            .strip_prefix("[ ")
+            .and_then(|line| line.split_once(" ] "))
+        {
+            passed.insert(name.trim().to_string(), status.trim() == "PASS");
+        } else if let Some(failed_module) = line
+            .strip_prefix("Failures in ")
+            .and_then(|line| line.strip_suffix(':'))
+        {
+            module = failed_module;
+        } else if let Some(header) = line.strip_prefix("┌── ") {
+            let function = header.trim_end_matches(|c| c == '─' || c == ' ');
+            failure = Some((format!("{}::{}", module, function), String::new()));
+        }
+    }
+    passed
+        .into_iter()
+        .map(|(name, passed)| {
+            let outcome = if passed {
+                Outcome::Pass
+            } else {
+                Outcome::Fail(failures.remove(&name).unwrap_or_default())
+            };
+            (name, outcome)
+        })
+        .collect()
+}
+
+/// Removes the ANSI escape sequences coloring the report.
+fn strip_colors(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Sequences are `ESC [`, parameters, then a letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
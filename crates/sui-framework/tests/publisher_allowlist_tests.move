@@ -0,0 +1,146 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::publisher_allowlist_tests {
+    use sui::test_scenario::{Self, Scenario};
+    use sui::sui_system::{Self, SuiSystemState};
+
+    use sui::governance_test_utils::{
+        Self,
+        create_validator_for_testing,
+        create_sui_system_state_for_testing,
+        create_sui_system_state_with_publisher_allowlist_for_testing
+    };
+
+    const VALIDATOR_ADDR_1: address = @0x1;
+    const VALIDATOR_ADDR_2: address = @0x2;
+    const VALIDATOR_ADDR_3: address = @0x3;
+
+    const PUBLISHER_ADDR_1: address = @0x42;
+    const PUBLISHER_ADDR_2: address = @0x43;
+
+    #[test]
+    fun test_allowlist_change_needs_quorum() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            assert!(sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_1), 100);
+            assert!(!sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_2), 101);
+
+            // A third of the stake is not enough to change the allowlist
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            assert!(!sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_2), 102);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_2);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            assert!(sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_2), 103);
+
+            // Votes start over for the next change of the same publisher
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_1, test_scenario::ctx(scenario));
+            assert!(sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_1), 104);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_3);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_1, test_scenario::ctx(scenario));
+            assert!(!sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_1), 105);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    fun test_allowlist_votes_cleared_at_epoch_end() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            governance_test_utils::advance_epoch(system_state, scenario);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_2);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            assert!(!sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_2), 100);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_allowlist_vote_by_non_validator_fails() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &PUBLISHER_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_allowlist_vote_without_allowlist_fails() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        {
+            let ctx = test_scenario::ctx(scenario);
+            let validators = vector[
+                create_validator_for_testing(VALIDATOR_ADDR_1, 100, ctx),
+                create_validator_for_testing(VALIDATOR_ADDR_2, 100, ctx),
+                create_validator_for_testing(VALIDATOR_ADDR_3, 100, ctx)
+            ];
+            create_sui_system_state_for_testing(validators, 300, 100);
+        };
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            assert!(sui_system::is_allowed_publisher(system_state, PUBLISHER_ADDR_2), 100);
+            sui_system::vote_publisher_allowlist_change(
+                system_state, PUBLISHER_ADDR_2, test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    fun set_up_sui_system_state(scenario: &mut Scenario) {
+        let ctx = test_scenario::ctx(scenario);
+
+        let validators = vector[
+            create_validator_for_testing(VALIDATOR_ADDR_1, 100, ctx),
+            create_validator_for_testing(VALIDATOR_ADDR_2, 100, ctx),
+            create_validator_for_testing(VALIDATOR_ADDR_3, 100, ctx)
+        ];
+        create_sui_system_state_with_publisher_allowlist_for_testing(
+            validators, vector[PUBLISHER_ADDR_1]);
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::safe_mode_tests {
+    use sui::test_scenario::{Self, Scenario};
+    use sui::sui_system::{Self, SuiSystemState};
+
+    use sui::governance_test_utils::{
+        Self,
+        create_validator_for_testing,
+        create_sui_system_state_for_testing
+    };
+
+    const VALIDATOR_ADDR_1: address = @0x1;
+    const VALIDATOR_ADDR_2: address = @0x2;
+
+    #[test]
+    fun test_safe_mode_holds_charges_until_epoch_change_succeeds() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            assert!(!sui_system::is_safe_mode(system_state), 100);
+            let storage_fund = sui_system::storage_fund_balance_for_testing(system_state);
+
+            governance_test_utils::advance_epoch_safe_mode(system_state, 10, 0, scenario);
+            assert!(sui_system::is_safe_mode(system_state), 101);
+            assert!(sui_system::epoch(system_state) == 1, 102);
+            // The charges are held, not paid out
+            assert!(sui_system::storage_fund_balance_for_testing(system_state) == storage_fund, 103);
+
+            governance_test_utils::advance_epoch_safe_mode(system_state, 20, 0, scenario);
+            assert!(sui_system::epoch(system_state) == 2, 104);
+
+            governance_test_utils::advance_epoch(system_state, scenario);
+            assert!(!sui_system::is_safe_mode(system_state), 105);
+            assert!(sui_system::epoch(system_state) == 3, 106);
+            assert!(sui_system::storage_fund_balance_for_testing(system_state) == storage_fund + 30, 107);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    fun test_safe_mode_keeps_validator_reports() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::report_validator(system_state, VALIDATOR_ADDR_2, test_scenario::ctx(scenario));
+            governance_test_utils::advance_epoch_safe_mode(system_state, 0, 0, scenario);
+            assert!(sui_system::validator_reporters(system_state, VALIDATOR_ADDR_2) == vector[VALIDATOR_ADDR_1], 100);
+
+            governance_test_utils::advance_epoch(system_state, scenario);
+            assert!(sui_system::validator_reporters(system_state, VALIDATOR_ADDR_2) == vector[], 101);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    fun set_up_sui_system_state(scenario: &mut Scenario) {
+        let ctx = test_scenario::ctx(scenario);
+
+        let validators = vector[
+            create_validator_for_testing(VALIDATOR_ADDR_1, 100, ctx),
+            create_validator_for_testing(VALIDATOR_ADDR_2, 100, ctx)
+        ];
+        create_sui_system_state_for_testing(validators, 300, 100);
+    }
+}
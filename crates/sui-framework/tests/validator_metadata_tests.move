@@ -0,0 +1,138 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#[test_only]
+module sui::validator_metadata_tests {
+    use std::option;
+    use sui::test_scenario::{Self, Scenario};
+    use sui::sui_system::{Self, SuiSystemState};
+    use sui::validator;
+
+    use sui::governance_test_utils::{
+        Self,
+        create_validator_for_testing,
+        create_sui_system_state_for_testing
+    };
+
+    const VALIDATOR_ADDR_1: address = @0x1;
+    const VALIDATOR_ADDR_2: address = @0x2;
+
+    // /ip4/127.0.0.1/tcp/8080/http
+    const NET_ADDRESS: vector<u8> = x"047f000001061f90e003";
+    const NETWORK_PUBKEY: vector<u8> = x"ab0227038b69a6ab999766c597ba8c74725ad5e114a73c45cb0cb4c609d97526";
+
+    #[test]
+    fun test_update_description_and_image_url() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            let ctx = test_scenario::ctx(scenario);
+            sui_system::update_validator_name(system_state, b"Validator1", ctx);
+            sui_system::update_validator_description(system_state, b"Validateur \xc3\xa0 Paris", ctx);
+            sui_system::update_validator_image_url(system_state, b"https://sui.io/validator1.png", ctx);
+
+            let validator = sui_system::active_validator(system_state, VALIDATOR_ADDR_1);
+            assert!(validator::name(validator) == &b"Validator1", 100);
+            assert!(validator::description(validator) == &b"Validateur \xc3\xa0 Paris", 101);
+            assert!(validator::image_url(validator) == &b"https://sui.io/validator1.png", 102);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    fun test_network_info_applied_at_epoch_end() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            let ctx = test_scenario::ctx(scenario);
+            sui_system::update_validator_next_epoch_net_address(system_state, NET_ADDRESS, ctx);
+            sui_system::update_validator_next_epoch_network_pubkey(system_state, NETWORK_PUBKEY, ctx);
+
+            // The validator keeps its network info until the end of the epoch
+            let validator = sui_system::active_validator(system_state, VALIDATOR_ADDR_1);
+            assert!(validator::net_address(validator) == &x"FFFF", 100);
+            assert!(validator::network_pubkey_bytes(validator) == &x"FF", 101);
+            assert!(validator::next_epoch_net_address(validator) == &option::some(NET_ADDRESS), 102);
+
+            governance_test_utils::advance_epoch(system_state, scenario);
+            let validator = sui_system::active_validator(system_state, VALIDATOR_ADDR_1);
+            assert!(validator::net_address(validator) == &NET_ADDRESS, 103);
+            assert!(validator::network_pubkey_bytes(validator) == &NETWORK_PUBKEY, 104);
+            assert!(option::is_none(validator::next_epoch_net_address(validator)), 105);
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_update_name_used_by_another_validator_fails() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::update_validator_name(system_state, b"Validator1", test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_2);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::update_validator_name(system_state, b"Validator1", test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 2)]
+    fun test_update_invalid_net_address_fails() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::update_validator_next_epoch_net_address(
+                system_state, b"127.0.0.1:8080", test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 3)]
+    fun test_update_invalid_network_pubkey_fails() {
+        let scenario = &mut test_scenario::begin(&VALIDATOR_ADDR_1);
+        set_up_sui_system_state(scenario);
+
+        test_scenario::next_tx(scenario, &VALIDATOR_ADDR_1);
+        {
+            let system_state_wrapper = test_scenario::take_shared<SuiSystemState>(scenario);
+            let system_state = test_scenario::borrow_mut(&mut system_state_wrapper);
+            sui_system::update_validator_next_epoch_network_pubkey(
+                system_state, x"FFFF", test_scenario::ctx(scenario));
+            test_scenario::return_shared(scenario, system_state_wrapper);
+        };
+    }
+
+    fun set_up_sui_system_state(scenario: &mut Scenario) {
+        let ctx = test_scenario::ctx(scenario);
+
+        let validators = vector[
+            create_validator_for_testing(VALIDATOR_ADDR_1, 100, ctx),
+            create_validator_for_testing(VALIDATOR_ADDR_2, 100, ctx)
+        ];
+        create_sui_system_state_for_testing(validators, 300, 100);
+    }
+}
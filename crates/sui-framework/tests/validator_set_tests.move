@@ -11,6 +11,8 @@ module sui::validator_set_tests {
     use sui::validator_set;
     use sui::test_scenario;
     use sui::stake::Stake;
+    use sui::vec_map;
+    use sui::vec_set;
     use std::option;
 
     #[test]
@@ -82,7 +84,7 @@ module sui::validator_set_tests {
             let reward = balance::zero<SUI>();
             let delegation_reward = balance::zero<SUI>();
             let ctx1 = test_scenario::ctx(&mut scenario);
-            validator_set::advance_epoch(&mut validator_set, &mut reward, &mut delegation_reward, ctx1);
+            validator_set::advance_epoch(&mut validator_set, &mut reward, &mut delegation_reward, &vec_map::empty(), ctx1);
             // The total stake and quorum should reflect 4 validators.
             assert!(validator_set::next_epoch_validator_count(&validator_set) == 4, 0);
             assert!(validator_set::total_validator_stake(&validator_set) == 1000, 0);
@@ -94,7 +96,7 @@ module sui::validator_set_tests {
             // Total validator candidate count changes, but total stake remains during epoch.
             assert!(validator_set::next_epoch_validator_count(&validator_set) == 3, 0);
             assert!(validator_set::total_validator_stake(&validator_set) == 1000, 0);
-            validator_set::advance_epoch(&mut validator_set, &mut reward, &mut delegation_reward, ctx1);
+            validator_set::advance_epoch(&mut validator_set, &mut reward, &mut delegation_reward, &vec_map::empty(), ctx1);
             // Validator1 is gone.
             assert!(validator_set::total_validator_stake(&validator_set) == 900, 0);
             balance::destroy_zero(reward);
@@ -126,7 +128,7 @@ module sui::validator_set_tests {
             &mut validator_set,
             v2,
         );
-        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, ctx1);
+        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, &vec_map::empty(), ctx1);
 
         assert!(validator_set::derive_reference_gas_price(&validator_set) == 45, 1);
 
@@ -134,7 +136,7 @@ module sui::validator_set_tests {
             &mut validator_set,
             v3,
         );
-        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, ctx1);
+        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, &vec_map::empty(), ctx1);
 
         assert!(validator_set::derive_reference_gas_price(&validator_set) == 42, 2);
 
@@ -142,7 +144,7 @@ module sui::validator_set_tests {
             &mut validator_set,
             v4,
         );
-        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, ctx1);
+        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, &vec_map::empty(), ctx1);
 
         assert!(validator_set::derive_reference_gas_price(&validator_set) == 41, 3);
 
@@ -150,7 +152,7 @@ module sui::validator_set_tests {
             &mut validator_set,
             v5,
         );
-        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, ctx1);
+        validator_set::advance_epoch(&mut validator_set, &mut dummy_balance, &mut dummy_delegator_reward, &vec_map::empty(), ctx1);
 
         assert!(validator_set::derive_reference_gas_price(&validator_set) == 43, 4);
 
@@ -159,6 +161,41 @@ module sui::validator_set_tests {
         balance::destroy_zero(dummy_delegator_reward);
     }
 
+    #[test]
+    fun test_reported_validator_slashing() {
+        let scenario = test_scenario::begin(&@0x1);
+        let ctx1 = test_scenario::ctx(&mut scenario);
+        // Create 4 validators, with stake 100, 200, 300, 400. The quorum threshold is 667.
+        let v1 = create_validator(@0x1, 1, ctx1);
+        let v2 = create_validator(@0x2, 2, ctx1);
+        let v3 = create_validator(@0x3, 3, ctx1);
+        let v4 = create_validator(@0x4, 4, ctx1);
+        let validator_set = validator_set::new(vector[v1, v2, v3, v4]);
+
+        // Validator 1 is reported by validators with a total stake of 700, validator 2 by
+        // validators with a total stake of 400.
+        let records = vec_map::empty();
+        let reporters = vec_set::empty();
+        vec_set::insert(&mut reporters, @0x3);
+        vec_set::insert(&mut reporters, @0x4);
+        vec_map::insert(&mut records, @0x1, reporters);
+        let reporters = vec_set::empty();
+        vec_set::insert(&mut reporters, @0x1);
+        vec_set::insert(&mut reporters, @0x3);
+        vec_map::insert(&mut records, @0x2, reporters);
+        assert!(validator_set::compute_slashed_validators(&validator_set, &records) == vector[@0x1], 0);
+
+        // Only the reward of validator 1 is withheld.
+        let reward = balance::create_for_testing<SUI>(1000);
+        let delegation_reward = balance::zero();
+        validator_set::advance_epoch(&mut validator_set, &mut reward, &mut delegation_reward, &records, ctx1);
+        assert!(balance::value(&reward) == 100, 1);
+
+        balance::destroy_for_testing(reward);
+        balance::destroy_zero(delegation_reward);
+        validator_set::destroy_for_testing(validator_set);
+    }
+
     fun create_validator(addr: address, hint: u8, ctx: &mut TxContext): Validator {
         let stake_value = (hint as u64) * 100;
         let init_stake = coin::mint_for_testing(stake_value, ctx);
@@ -3,7 +3,7 @@
 
 use clap::Parser;
 use std::{
-    net::{IpAddr, Ipv4Addr, SocketAddr},
+    net::{IpAddr, SocketAddr},
     path::PathBuf,
 };
 use sui_config::SUI_GATEWAY_CONFIG;
@@ -16,7 +16,7 @@ use sui_json_rpc::JsonRpcServerBuilder;
 use tracing::info;
 
 const DEFAULT_RPC_SERVER_PORT: &str = "5001";
-const DEFAULT_RPC_SERVER_ADDR_IPV4: &str = "127.0.0.1";
+const DEFAULT_RPC_SERVER_ADDR: &str = "127.0.0.1";
 const PROM_PORT_ADDR: &str = "0.0.0.0:9184";
 
 #[cfg(test)]
@@ -37,8 +37,16 @@ struct RpcGatewayOpt {
     #[clap(long, default_value = DEFAULT_RPC_SERVER_PORT)]
     port: u16,
 
-    #[clap(long, default_value = DEFAULT_RPC_SERVER_ADDR_IPV4)]
-    host: Ipv4Addr,
+    #[clap(long, default_value = DEFAULT_RPC_SERVER_ADDR)]
+    host: IpAddr,
+
+    /// Reject batches of requests instead of answering them
+    #[clap(long)]
+    no_batch_requests: bool,
+
+    /// Largest request accepted, in bytes, which bounds the size of the batches
+    #[clap(long)]
+    max_request_body_size: Option<u32>,
 }
 
 #[tokio::main]
@@ -60,8 +68,12 @@ async fn main() -> anyhow::Result<()> {
     let gateway_config = PersistedConfig::read(&config_path)?;
     let client = GatewayState::create_client(&gateway_config, Some(&prometheus_registry))?;
 
-    let address = SocketAddr::new(IpAddr::V4(options.host), options.port);
-    let mut server = JsonRpcServerBuilder::new(false, &prometheus_registry)?;
+    let address = SocketAddr::new(options.host, options.port);
+    let mut server = JsonRpcServerBuilder::new(false, &prometheus_registry)?
+        .with_batch_requests(!options.no_batch_requests);
+    if let Some(max_size) = options.max_request_body_size {
+        server = server.with_max_request_body_size(max_size);
+    }
     server.register_module(RpcGatewayImpl::new(client.clone()))?;
     server.register_module(GatewayReadApiImpl::new(client.clone()))?;
     server.register_module(TransactionBuilderImpl::new(client.clone()))?;
@@ -28,18 +28,54 @@ async fn test_get_objects() -> Result<(), anyhow::Error> {
     let address = test_network.accounts.first().unwrap();
 
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
     assert_eq!(5, objects.len());
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_objects_in_pages() -> Result<(), anyhow::Error> {
+    let test_network = start_rpc_test_network(None).await?;
+    let http_client = test_network.http_client;
+    let address = test_network.accounts.first().unwrap();
+
+    http_client.sync_account_state(*address).await?;
+    let all = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?;
+    assert!(!all.has_next_page);
+
+    let mut objects = vec![];
+    let mut cursor = None;
+    loop {
+        let page = http_client
+            .get_objects_owned_by_address(*address, cursor, Some(2))
+            .await?;
+        assert!(page.data.len() <= 2);
+        assert_eq!(page.has_next_page, page.next_cursor.is_some());
+        objects.extend(page.data);
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(all.data, objects);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     let test_network = start_rpc_test_network(None).await?;
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
 
     let transaction_bytes: TransactionBytes = http_client
         .transfer_object(
@@ -60,7 +96,7 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     let (tx_bytes, sig_scheme, signature_bytes, pub_key) = tx.to_network_data_for_execution();
 
     let tx_response = http_client
-        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key)
+        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key, None)
         .await?;
 
     let effect = tx_response.effects;
@@ -75,7 +111,10 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
     let gas = objects.first().unwrap();
 
     let compiled_modules = build_move_package_to_bytes(
@@ -99,7 +138,7 @@ async fn test_publish() -> Result<(), anyhow::Error> {
     let (tx_bytes, sig_scheme, signature_bytes, pub_key) = tx.to_network_data_for_execution();
 
     let tx_response = http_client
-        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key)
+        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key, None)
         .await?;
     assert_eq!(6, tx_response.effects.created.len());
     Ok(())
@@ -111,7 +150,10 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
     let gas = objects.first().unwrap();
     let coin = &objects[1];
 
@@ -148,7 +190,7 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
     let (tx_bytes, sig_scheme, signature_bytes, pub_key) = tx.to_network_data_for_execution();
 
     let tx_response = http_client
-        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key)
+        .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key, None)
         .await?;
 
     let effect = tx_response.effects;
@@ -162,7 +204,10 @@ async fn test_get_object_info() -> Result<(), anyhow::Error> {
     let http_client = test_network.http_client;
     let address = test_network.accounts.first().unwrap();
     http_client.sync_account_state(*address).await?;
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
 
     for oref in objects {
         let result: GetObjectDataResponse = http_client.get_object(oref.object_id).await?;
@@ -181,7 +226,10 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
 
     http_client.sync_account_state(*address).await?;
 
-    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    let objects = http_client
+        .get_objects_owned_by_address(*address, None, None)
+        .await?
+        .data;
     let gas_id = objects.last().unwrap().object_id;
 
     // Make some transactions
@@ -201,7 +249,7 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
         let (tx_bytes, sig_scheme, signature_bytes, pub_key) = tx.to_network_data_for_execution();
 
         let response = http_client
-            .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key)
+            .execute_transaction(tx_bytes, sig_scheme, signature_bytes, pub_key, None)
             .await?;
 
         tx_responses.push(response);
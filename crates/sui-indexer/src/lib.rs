@@ -0,0 +1,17 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Indexing of the chain data into Postgres, for queries the nodes don't serve.
+//!
+//! The indexer follows the checkpoints a fullnode exports to an object store (see
+//! `sui_data_ingestion`), and writes each of them to relational tables with the transactions it
+//! certifies, the objects they changed and the events they emitted (see
+//! [`store::PgIndexerStore`]). How far it got is recorded in the same database, so it resumes
+//! from there after a restart, and can be rewound to backfill the tables from an earlier
+//! checkpoint.
+
+pub mod store;
+pub mod worker;
+
+pub use store::PgIndexerStore;
+pub use worker::IndexerWorker;
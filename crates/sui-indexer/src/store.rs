@@ -0,0 +1,428 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use sqlx::types::Json;
+use sqlx::{Postgres, Transaction};
+use sui_data_ingestion::ProgressStore;
+use sui_types::base_types::{encode_bytes_hex, ObjectRef, TransactionDigest};
+use sui_types::messages::{ExecutionStatus, TransactionEffects};
+use sui_types::messages_checkpoint::{CheckpointData, CheckpointSequenceNumber};
+use sui_types::object::Owner;
+
+const MAX_CONNECTIONS: u32 = 10;
+
+/// The tables of the indexer, created if they don't exist yet. Identifiers, addresses and
+/// digests are stored as `0x`-prefixed hex, the types of the events as the names `EventType`
+/// parses, and the data and effects of the transactions as JSON too, for the queries the columns
+/// don't cover.
+const SCHEMA: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS checkpoints (
+        sequence_number BIGINT PRIMARY KEY,
+        epoch BIGINT NOT NULL,
+        content_digest TEXT NOT NULL,
+        previous_digest TEXT,
+        transaction_count BIGINT NOT NULL
+    )",
+    "CREATE TABLE IF NOT EXISTS transactions (
+        digest TEXT PRIMARY KEY,
+        checkpoint BIGINT NOT NULL,
+        sender TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        gas_budget BIGINT NOT NULL,
+        gas_price BIGINT NOT NULL,
+        success BOOLEAN NOT NULL,
+        computation_cost BIGINT NOT NULL,
+        storage_cost BIGINT NOT NULL,
+        storage_rebate BIGINT NOT NULL,
+        data JSONB NOT NULL,
+        effects JSONB NOT NULL
+    )",
+    "CREATE INDEX IF NOT EXISTS transactions_checkpoint ON transactions (checkpoint)",
+    "CREATE INDEX IF NOT EXISTS transactions_sender ON transactions (sender)",
+    "CREATE TABLE IF NOT EXISTS objects (
+        object_id TEXT NOT NULL,
+        version BIGINT NOT NULL,
+        digest TEXT NOT NULL,
+        status TEXT NOT NULL,
+        owner JSONB,
+        transaction_digest TEXT NOT NULL,
+        checkpoint BIGINT NOT NULL,
+        PRIMARY KEY (object_id, version)
+    )",
+    "CREATE INDEX IF NOT EXISTS objects_transaction ON objects (transaction_digest)",
+    "CREATE TABLE IF NOT EXISTS events (
+        transaction_digest TEXT NOT NULL,
+        event_sequence BIGINT NOT NULL,
+        checkpoint BIGINT NOT NULL,
+        event_type TEXT NOT NULL,
+        sender TEXT,
+        package_id TEXT,
+        module_name TEXT,
+        object_id TEXT,
+        contents JSONB NOT NULL,
+        PRIMARY KEY (transaction_digest, event_sequence)
+    )",
+    "CREATE INDEX IF NOT EXISTS events_type ON events (event_type)",
+    "CREATE INDEX IF NOT EXISTS events_sender ON events (sender)",
+    "CREATE TABLE IF NOT EXISTS progress (
+        task TEXT PRIMARY KEY,
+        next_checkpoint BIGINT NOT NULL
+    )",
+];
+
+fn hex<B: AsRef<[u8]>>(bytes: B) -> String {
+    format!("0x{}", encode_bytes_hex(bytes))
+}
+
+/// Postgres has no unsigned integers: sequence numbers and amounts are stored as signed ones,
+/// which they are expected to fit in.
+fn int(value: u64) -> Result<i64> {
+    i64::try_from(value).with_context(|| format!("{value} doesn't fit in a BIGINT column"))
+}
+
+/// How a transaction changed an object, and the reference and owner it left it with.
+struct ObjectChange<'a> {
+    status: &'static str,
+    object_ref: &'a ObjectRef,
+    owner: Option<&'a Owner>,
+}
+
+fn object_changes(effects: &TransactionEffects) -> Vec<ObjectChange<'_>> {
+    let mut changes = vec![];
+    for (status, objects) in [
+        ("created", &effects.created),
+        ("mutated", &effects.mutated),
+        ("unwrapped", &effects.unwrapped),
+    ] {
+        changes.extend(objects.iter().map(|(object_ref, owner)| ObjectChange {
+            status,
+            object_ref,
+            owner: Some(owner),
+        }));
+    }
+    for (status, objects) in [("deleted", &effects.deleted), ("wrapped", &effects.wrapped)] {
+        changes.extend(objects.iter().map(|object_ref| ObjectChange {
+            status,
+            object_ref,
+            owner: None,
+        }));
+    }
+    changes
+}
+
+/// Stores the chain data in Postgres, along with the progress of the indexer so that it
+/// resumes where it stopped.
+#[derive(Clone)]
+pub struct PgIndexerStore {
+    pool: PgPool,
+}
+
+impl PgIndexerStore {
+    pub async fn connect(url: &str) -> Result<Self> {
+        let pool = PgPoolOptions::new()
+            .max_connections(MAX_CONNECTIONS)
+            .connect(url)
+            .await?;
+        Ok(Self { pool })
+    }
+
+    /// Creates the tables and indexes which don't exist yet.
+    pub async fn migrate(&self) -> Result<()> {
+        for statement in SCHEMA {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Writes a checkpoint with its transactions, the objects they changed and the events they
+    /// emitted, all at once. Rows already written are left as they are, so a checkpoint can be
+    /// written again.
+    pub async fn write_checkpoint(&self, checkpoint: &CheckpointData) -> Result<()> {
+        let summary = &checkpoint.checkpoint.summary;
+        let seq = int(summary.sequence_number)?;
+        let mut db_tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO checkpoints
+                (sequence_number, epoch, content_digest, previous_digest, transaction_count)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(seq)
+        .bind(int(summary.epoch)?)
+        .bind(hex(summary.content_digest))
+        .bind(summary.previous_digest.map(hex))
+        .bind(int(checkpoint.transactions.len() as u64)?)
+        .execute(&mut db_tx)
+        .await?;
+
+        for transaction in &checkpoint.transactions {
+            let digest = transaction.certificate.digest();
+            let data = &transaction.certificate.signed_data.data;
+            let effects = &transaction.effects;
+            sqlx::query(
+                "INSERT INTO transactions
+                    (digest, checkpoint, sender, kind, gas_budget, gas_price, success,
+                    computation_cost, storage_cost, storage_rebate, data, effects)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(hex(digest))
+            .bind(seq)
+            .bind(hex(data.signer()))
+            .bind(data.kind_as_str())
+            .bind(int(data.gas_budget)?)
+            .bind(int(data.gas_price)?)
+            .bind(matches!(effects.status, ExecutionStatus::Success))
+            .bind(int(effects.gas_used.computation_cost)?)
+            .bind(int(effects.gas_used.storage_cost)?)
+            .bind(int(effects.gas_used.storage_rebate)?)
+            .bind(Json(data))
+            .bind(Json(effects))
+            .execute(&mut db_tx)
+            .await?;
+            write_objects(&mut db_tx, seq, digest, effects).await?;
+            write_events(&mut db_tx, seq, digest, effects).await?;
+        }
+        db_tx.commit().await?;
+        Ok(())
+    }
+}
+
+async fn write_objects(
+    db_tx: &mut Transaction<'_, Postgres>,
+    seq: i64,
+    digest: &TransactionDigest,
+    effects: &TransactionEffects,
+) -> Result<()> {
+    for change in object_changes(effects) {
+        let (object_id, version, object_digest) = change.object_ref;
+        sqlx::query(
+            "INSERT INTO objects
+                (object_id, version, digest, status, owner, transaction_digest, checkpoint)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(hex(object_id))
+        .bind(int(version.value())?)
+        .bind(hex(object_digest))
+        .bind(change.status)
+        .bind(change.owner.map(Json))
+        .bind(hex(digest))
+        .bind(seq)
+        .execute(&mut *db_tx)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn write_events(
+    db_tx: &mut Transaction<'_, Postgres>,
+    seq: i64,
+    digest: &TransactionDigest,
+    effects: &TransactionEffects,
+) -> Result<()> {
+    for (event_sequence, event) in effects.events.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO events
+                (transaction_digest, event_sequence, checkpoint, event_type, sender, package_id,
+                module_name, object_id, contents)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(hex(digest))
+        .bind(int(event_sequence as u64)?)
+        .bind(seq)
+        .bind(event.variant_name())
+        .bind(event.sender().map(hex))
+        .bind(event.package_id().map(hex))
+        .bind(event.module_name())
+        .bind(event.object_id().map(hex))
+        .bind(Json(event))
+        .execute(&mut *db_tx)
+        .await?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl ProgressStore for PgIndexerStore {
+    async fn load(&self, task: &str) -> Result<CheckpointSequenceNumber> {
+        let next: Option<i64> =
+            sqlx::query_scalar("SELECT next_checkpoint FROM progress WHERE task = $1")
+                .bind(task)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(next.map(u64::try_from).transpose()?.unwrap_or_default())
+    }
+
+    async fn save(&self, task: &str, next: CheckpointSequenceNumber) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO progress (task, next_checkpoint) VALUES ($1, $2)
+            ON CONFLICT (task) DO UPDATE SET next_checkpoint = EXCLUDED.next_checkpoint",
+        )
+        .bind(task)
+        .bind(int(next)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sui_types::base_types::{ExecutionDigests, ObjectID, SuiAddress};
+    use sui_types::crypto::KeypairTraits;
+    use sui_types::event::Event;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointTransaction,
+        SignedCheckpointSummary,
+    };
+    use test_utils::messages::{
+        make_transfer_sui_transaction, make_tx_certs_and_signed_effects, random_object_ref,
+    };
+    use test_utils::{test_account_keys, test_committee, test_validator_keys};
+
+    /// A store of the database at `DATABASE_URL`, or `None` if unset, as these tests need a
+    /// Postgres server.
+    async fn test_store() -> Option<PgIndexerStore> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let store = PgIndexerStore::connect(&url).await.unwrap();
+        store.migrate().await.unwrap();
+        Some(store)
+    }
+
+    /// A checkpoint of a transaction which created and deleted an object, and emitted two events.
+    fn test_checkpoint(seq: CheckpointSequenceNumber) -> CheckpointData {
+        let (sender, keypair) = test_account_keys().pop().unwrap();
+        let transaction = make_transfer_sui_transaction(
+            random_object_ref(),
+            SuiAddress::random_for_testing_only(),
+            None,
+            sender,
+            &keypair,
+        );
+        let (mut certificates, mut effects) = make_tx_certs_and_signed_effects(vec![transaction]);
+        let certificate = certificates.pop().unwrap();
+        let mut effects = effects.pop().unwrap().effects;
+        effects
+            .created
+            .push((random_object_ref(), Owner::AddressOwner(sender)));
+        effects.deleted.push(random_object_ref());
+        effects.events.push(Event::EpochChange(0));
+        effects.events.push(Event::Checkpoint(seq));
+
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(std::iter::once(
+            ExecutionDigests::new(*certificate.digest(), effects.digest()),
+        ));
+        let committee = test_committee();
+        let signed = test_validator_keys()
+            .into_iter()
+            .map(|(key, _, _, _)| {
+                SignedCheckpointSummary::new(
+                    committee.epoch,
+                    seq,
+                    key.public().into(),
+                    &key,
+                    &contents,
+                    None,
+                )
+            })
+            .collect();
+        CheckpointData {
+            checkpoint: CertifiedCheckpointSummary::aggregate(signed, &committee).unwrap(),
+            contents,
+            transactions: vec![CheckpointTransaction {
+                certificate,
+                effects,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_rows() {
+        assert_eq!(int(i64::MAX as u64).unwrap(), i64::MAX);
+        assert!(int(u64::MAX).is_err());
+
+        let checkpoint = test_checkpoint(1);
+        let changes = object_changes(&checkpoint.transactions[0].effects);
+        let statuses: Vec<_> = changes.iter().map(|change| change.status).collect();
+        assert_eq!(statuses, vec!["created", "deleted"]);
+        assert!(changes[0].owner.is_some());
+        assert!(changes[1].owner.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_checkpoint() {
+        let store = match test_store().await {
+            Some(store) => store,
+            None => return,
+        };
+        let checkpoint = test_checkpoint(1);
+        let digest = hex(checkpoint.transactions[0].certificate.digest());
+
+        // Writing a checkpoint again changes nothing
+        store.write_checkpoint(&checkpoint).await.unwrap();
+        store.write_checkpoint(&checkpoint).await.unwrap();
+
+        let (sender, success): (String, bool) =
+            sqlx::query_as("SELECT sender, success FROM transactions WHERE digest = $1")
+                .bind(&digest)
+                .fetch_one(&store.pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            sender,
+            hex(checkpoint.transactions[0].certificate.sender_address())
+        );
+        assert!(success);
+
+        let objects: Vec<(String, Option<Json<Owner>>)> = sqlx::query_as(
+            "SELECT status, owner FROM objects WHERE transaction_digest = $1 ORDER BY status",
+        )
+        .bind(&digest)
+        .fetch_all(&store.pool)
+        .await
+        .unwrap();
+        let statuses: Vec<_> = objects.iter().map(|(status, _)| status.as_str()).collect();
+        assert_eq!(statuses, vec!["created", "deleted"]);
+        assert!(objects[0].1.is_some());
+
+        let event_types: Vec<String> = sqlx::query_scalar(
+            "SELECT event_type FROM events WHERE transaction_digest = $1 ORDER BY event_sequence",
+        )
+        .bind(&digest)
+        .fetch_all(&store.pool)
+        .await
+        .unwrap();
+        assert_eq!(event_types, vec!["EpochChange", "Checkpoint"]);
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT transaction_count FROM checkpoints WHERE sequence_number = 1",
+        )
+        .fetch_one(&store.pool)
+        .await
+        .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_progress() {
+        let store = match test_store().await {
+            Some(store) => store,
+            None => return,
+        };
+        let task = format!("test-{}", ObjectID::random());
+        assert_eq!(store.load(&task).await.unwrap(), 0);
+        store.save(&task, 5).await.unwrap();
+        assert_eq!(store.load(&task).await.unwrap(), 5);
+        // Rewound to backfill
+        store.save(&task, 2).await.unwrap();
+        assert_eq!(store.load(&task).await.unwrap(), 2);
+        assert!(store.save(&task, u64::MAX).await.is_err());
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use async_trait::async_trait;
+use sui_data_ingestion::Worker;
+use sui_types::messages_checkpoint::CheckpointData;
+use tracing::debug;
+
+use crate::store::PgIndexerStore;
+
+/// Writes each checkpoint to the store, which is idempotent as the executor requires.
+pub struct IndexerWorker {
+    store: PgIndexerStore,
+}
+
+impl IndexerWorker {
+    pub fn new(store: PgIndexerStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl Worker for IndexerWorker {
+    async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> Result<()> {
+        self.store.write_checkpoint(checkpoint).await?;
+        debug!(
+            checkpoint = checkpoint.sequence_number(),
+            transactions = checkpoint.transactions.len(),
+            "Indexed checkpoint"
+        );
+        Ok(())
+    }
+}
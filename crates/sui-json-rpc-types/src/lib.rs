@@ -19,7 +19,7 @@ use move_binary_format::normalized::{
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
-use move_core_types::value::{MoveStruct, MoveStructLayout, MoveValue};
+use move_core_types::value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue};
 use schemars::JsonSchema;
 use serde::ser::Error;
 use serde::Deserialize;
@@ -34,24 +34,28 @@ use sui_types::base_types::{
     ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
     TransactionEffectsDigest,
 };
+use sui_types::coin::CoinTransfer;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
-use sui_types::error::SuiError;
+use sui_types::error::{ExecutionError, SuiError};
 use sui_types::event::{Event, TransferType};
-use sui_types::event::{EventEnvelope, EventType};
+use sui_types::event::{EventEnvelope, EventID, EventType};
 use sui_types::filter::{EventFilter, TransactionFilter};
-use sui_types::gas::GasCostSummary;
+use sui_types::finality_proof::FinalityProof;
+use sui_types::gas::{EpochFeeSummary, GasCostSummary, SupplySummary};
 use sui_types::gas_coin::GasCoin;
+use sui_types::memo::MemoEvent;
 use sui_types::messages::{
     CallArg, CertifiedTransaction, CertifiedTransactionEffects, ExecuteTransactionResponse,
     ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, SingleTransactionKind,
     TransactionData, TransactionEffects, TransactionKind,
 };
-use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointStateDiff};
 use sui_types::move_package::{disassemble_modules, MovePackage};
 use sui_types::object::{
     Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
 };
+use sui_types::sui_amount::SuiAmount;
 use sui_types::sui_serde::{Base64, Encoding};
 
 #[cfg(test)]
@@ -145,6 +149,19 @@ pub struct SuiMoveNormalizedModule {
     pub exposed_functions: BTreeMap<String, SuiMoveNormalizedFunction>,
 }
 
+/// Documentation of a module of the Move framework or stdlib, as generated by the Move docgen.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(rename = "MoveModuleDocs", rename_all = "camelCase")]
+pub struct SuiMoveModuleDocs {
+    pub package: ObjectID,
+    pub module: String,
+    /// The digest of the framework and stdlib modules documented, the same for all the nodes
+    /// running them.
+    pub framework_digest: Base64,
+    /// The documentation, in markdown.
+    pub docs: String,
+}
+
 impl From<NormalizedModule> for SuiMoveNormalizedModule {
     fn from(module: NormalizedModule) -> Self {
         Self {
@@ -311,6 +328,9 @@ pub struct SuiTransactionResponse {
     pub effects: SuiTransactionEffects,
     pub timestamp_ms: Option<u64>,
     pub parsed_data: Option<SuiParsedTransactionResponse>,
+    /// The memo attached to the transaction with `0x2::memo::attach`, if any.
+    #[serde(default)]
+    pub memo: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
@@ -1374,6 +1394,9 @@ pub struct SuiTransactionData {
     pub sender: SuiAddress,
     pub gas_payment: SuiObjectRef,
     pub gas_budget: u64,
+    /// The owner of the gas payment paying for the transaction, when it is not the sender.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_sponsor: Option<SuiAddress>,
 }
 
 impl Display for SuiTransactionData {
@@ -1410,6 +1433,7 @@ impl TryFrom<TransactionData> for SuiTransactionData {
             sender: data.signer(),
             gas_payment: data.gas().into(),
             gas_budget: data.gas_budget,
+            gas_sponsor: data.gas_sponsor(),
         })
     }
 }
@@ -1556,6 +1580,9 @@ pub struct SuiCertifiedTransaction {
     pub data: SuiTransactionData,
     /// tx_signature is signed by the transaction sender, applied on `data`.
     pub tx_signature: Signature,
+    /// sponsor_signature is signed by the gas sponsor of the transaction, if any, applied on `data`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor_signature: Option<Signature>,
     /// authority signature information, if available, is signed by an authority, applied on `data`.
     pub auth_sign_info: AuthorityStrongQuorumSignInfo,
 }
@@ -1565,6 +1592,9 @@ impl Display for SuiCertifiedTransaction {
         let mut writer = String::new();
         writeln!(writer, "Transaction Hash: {:?}", self.transaction_digest)?;
         writeln!(writer, "Transaction Signature: {:?}", self.tx_signature)?;
+        if let Some(sponsor_signature) = &self.sponsor_signature {
+            writeln!(writer, "Sponsor Signature: {:?}", sponsor_signature)?;
+        }
         writeln!(
             writer,
             "Signed Authorities Bitmap: {:?}",
@@ -1583,6 +1613,7 @@ impl TryFrom<CertifiedTransaction> for SuiCertifiedTransaction {
             transaction_digest: *cert.digest(),
             data: cert.signed_data.data.try_into()?,
             tx_signature: cert.signed_data.tx_signature,
+            sponsor_signature: cert.signed_data.sponsor_signature,
             auth_sign_info: cert.auth_sign_info,
         })
     }
@@ -1675,6 +1706,19 @@ impl SuiTransactionEffects {
         self.mutated.iter().filter(|o| *o != &self.gas_object)
     }
 
+    /// The first memo attached to the transaction with `0x2::memo::attach`, if any.
+    pub fn memo(&self) -> Option<String> {
+        let memo_type = MemoEvent::type_().to_string();
+        self.events.iter().find_map(|event| match event {
+            SuiEvent::MoveEvent { type_, bcs, .. } if type_ == &memo_type => {
+                bcs::from_bytes::<MemoEvent>(bcs)
+                    .ok()
+                    .map(|event| event.memo)
+            }
+            _ => None,
+        })
+    }
+
     pub fn try_from(
         effect: TransactionEffects,
         resolver: &impl GetModule,
@@ -1802,6 +1846,10 @@ pub struct SuiGasCostSummary {
     pub computation_cost: u64,
     pub storage_cost: u64,
     pub storage_rebate: u64,
+    /// The part of the storage cost paid for the events emitted.
+    pub event_storage_cost: u64,
+    /// The part of the storage cost paid for the effects.
+    pub effects_storage_cost: u64,
 }
 
 impl From<GasCostSummary> for SuiGasCostSummary {
@@ -1810,10 +1858,273 @@ impl From<GasCostSummary> for SuiGasCostSummary {
             computation_cost: s.computation_cost,
             storage_cost: s.storage_cost,
             storage_rebate: s.storage_rebate,
+            event_storage_cost: s.event_storage_cost,
+            effects_storage_cost: s.effects_storage_cost,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "HotObject", rename_all = "camelCase")]
+pub struct SuiHotObject {
+    pub object_id: ObjectID,
+    pub transaction_count: u64,
+    // Only known to validators, which sequence certificates through consensus.
+    pub average_sequencing_wait_ms: Option<u64>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "HotObjects", rename_all = "camelCase")]
+pub struct SuiHotObjects {
+    pub epoch: EpochId,
+    pub objects: Vec<SuiHotObject>,
+}
+
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "FinalityProof", rename_all = "camelCase")]
+pub struct SuiFinalityProof {
+    pub transaction_digest: TransactionDigest,
+    pub epoch: EpochId,
+    /// The certified checkpoint the transaction is in, if the proof includes one.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    /// The BCS serialization of the proof, see `sui_types::finality_proof::FinalityProof`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub proof_bytes: Vec<u8>,
+}
+
+impl From<FinalityProof> for SuiFinalityProof {
+    fn from(proof: FinalityProof) -> Self {
+        Self {
+            transaction_digest: *proof.certificate.digest(),
+            epoch: proof.epoch(),
+            checkpoint: proof
+                .checkpoint
+                .as_ref()
+                .map(|inclusion| *inclusion.checkpoint.summary.sequence_number()),
+            proof_bytes: proof.to_bytes(),
+        }
+    }
+}
+
+impl SuiFinalityProof {
+    pub fn deserialize(&self) -> Result<FinalityProof, SuiError> {
+        FinalityProof::from_bytes(&self.proof_bytes)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CheckpointStateDiff", rename_all = "camelCase")]
+pub struct SuiCheckpointStateDiff {
+    pub from_checkpoint: CheckpointSequenceNumber,
+    /// The last checkpoint of the diff, before the one asked for if the range was capped.
+    pub to_checkpoint: CheckpointSequenceNumber,
+    pub created: Vec<SuiObjectRef>,
+    pub mutated: Vec<SuiObjectRef>,
+    pub deleted: Vec<SuiObjectRef>,
+    /// The created and mutated objects at their version in the diff, if asked for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objects: Option<Vec<GetPastObjectDataResponse>>,
+}
+
+impl SuiCheckpointStateDiff {
+    pub fn new(
+        from_checkpoint: CheckpointSequenceNumber,
+        to_checkpoint: CheckpointSequenceNumber,
+        diff: CheckpointStateDiff,
+        objects: Option<Vec<GetPastObjectDataResponse>>,
+    ) -> Self {
+        let to_refs = |refs: Vec<ObjectRef>| -> Vec<SuiObjectRef> {
+            refs.into_iter().map(Into::into).collect()
+        };
+        Self {
+            from_checkpoint,
+            to_checkpoint,
+            created: to_refs(diff.created),
+            mutated: to_refs(diff.mutated),
+            deleted: to_refs(diff.deleted),
+            objects,
+        }
+    }
+}
+
+/// A coin received by an address from another one.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CoinTransfer", rename_all = "camelCase")]
+pub struct SuiCoinTransfer {
+    /// The sequence number of the transaction on the node, to resume from.
+    pub tx_seq_number: GatewayTxSeqNumber,
+    pub transaction_digest: TransactionDigest,
+    pub sender: SuiAddress,
+    /// The coin at its version after the transaction.
+    pub coin: SuiObjectRef,
+    /// The type of the coin, e.g. `0x2::sui::SUI`.
+    pub coin_type: String,
+    pub amount: u64,
+}
+
+impl SuiCoinTransfer {
+    pub fn new(tx_seq_number: GatewayTxSeqNumber, transfer: CoinTransfer) -> Self {
+        Self {
+            tx_seq_number,
+            transaction_digest: transfer.transaction_digest,
+            sender: transfer.sender,
+            coin: transfer.coin.into(),
+            coin_type: transfer.coin_type.to_string(),
+            amount: transfer.amount,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CoinTransfersPage", rename_all = "camelCase")]
+pub struct SuiCoinTransfersPage {
+    pub data: Vec<SuiCoinTransfer>,
+    /// The cursor to get the next transfers with, which is the given one if there are no new
+    /// transactions yet.
+    pub next_cursor: Option<GatewayTxSeqNumber>,
+}
+
+/// A version of an object, with the transaction which created it.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectVersion", rename_all = "camelCase")]
+pub struct SuiObjectVersion {
+    pub version: SequenceNumber,
+    /// The digest of the object at this version, or the deleted or wrapped digest if the
+    /// transaction deleted or wrapped the object.
+    pub digest: ObjectDigest,
+    pub transaction_digest: TransactionDigest,
+    /// When the node executed the transaction, in milliseconds since the Unix epoch, if it
+    /// indexed it.
+    pub timestamp_ms: Option<u64>,
+}
+
+impl SuiObjectVersion {
+    pub fn new(
+        (_, version, digest): ObjectRef,
+        transaction_digest: TransactionDigest,
+        timestamp_ms: Option<u64>,
+    ) -> Self {
+        Self {
+            version,
+            digest,
+            transaction_digest,
+            timestamp_ms,
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectVersionHistoryPage", rename_all = "camelCase")]
+pub struct SuiObjectVersionHistoryPage {
+    pub data: Vec<SuiObjectVersion>,
+    /// The cursor to get the next versions with, which is the given one if there are no new
+    /// versions yet.
+    pub next_cursor: Option<SequenceNumber>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "EpochFeeSummary", rename_all = "camelCase")]
+pub struct SuiEpochFeeSummary {
+    pub epoch: EpochId,
+    pub transaction_count: u64,
+    pub computation_cost: SuiAmount,
+    pub storage_cost: SuiAmount,
+    pub storage_rebate: SuiAmount,
+    /// The SUI minted at the end of the epoch, to reward the validators and the storage fund.
+    pub minted: SuiAmount,
+}
+
+impl From<EpochFeeSummary> for SuiEpochFeeSummary {
+    fn from(summary: EpochFeeSummary) -> Self {
+        Self {
+            epoch: summary.epoch,
+            transaction_count: summary.transaction_count,
+            computation_cost: summary.computation_cost.into(),
+            storage_cost: summary.storage_cost.into(),
+            storage_rebate: summary.storage_rebate.into(),
+            minted: summary.minted.into(),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "Supply", rename_all = "camelCase")]
+pub struct SuiSupply {
+    pub epoch: EpochId,
+    pub total_supply: SuiAmount,
+    /// The SUI of the coins created at genesis.
+    pub genesis_coins: SuiAmount,
+    /// The SUI minted by the system, for the stakes and the storage fund of genesis and the
+    /// rewards of every epoch since.
+    pub system_minted: SuiAmount,
+    pub gas_burned: SuiAmount,
+    pub storage_rebates: SuiAmount,
+}
+
+impl From<SupplySummary> for SuiSupply {
+    fn from(supply: SupplySummary) -> Self {
+        Self {
+            epoch: supply.epoch,
+            total_supply: supply.total().into(),
+            genesis_coins: supply.genesis_coins.into(),
+            system_minted: supply.system_minted.into(),
+            gas_burned: supply.gas_burned.into(),
+            storage_rebates: supply.storage_rebates.into(),
         }
     }
 }
 
+/// The outcome of a Move call executed to inspect it, whose changes are not committed.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "DevInspectResults", rename_all = "camelCase")]
+pub struct DevInspectResults {
+    /// The values returned by the function, serialized with BCS, in order.
+    pub return_values: Vec<Base64>,
+    pub events: Vec<SuiEvent>,
+    /// The error the call failed with, in which case there are no return values nor events.
+    pub error: Option<String>,
+}
+
+impl DevInspectResults {
+    pub fn try_from(
+        result: Result<Vec<Vec<u8>>, ExecutionError>,
+        events: Vec<Event>,
+        resolver: &impl GetModule,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match result {
+            Ok(return_values) => Self {
+                return_values: return_values
+                    .iter()
+                    .map(|bytes| Base64::from_bytes(bytes))
+                    .collect(),
+                events: events
+                    .into_iter()
+                    .map(|event| SuiEvent::try_from(event, resolver))
+                    .collect::<Result<_, _>>()?,
+                error: None,
+            },
+            Err(error) => Self {
+                return_values: vec![],
+                events: vec![],
+                error: Some(error.to_string()),
+            },
+        })
+    }
+}
+
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "GasPriceSuggestion", rename_all = "camelCase")]
+pub struct SuiGasPriceSuggestion {
+    pub reference_gas_price: u64,
+    pub low: u64,
+    pub medium: u64,
+    pub high: u64,
+    // How congested the network looks, from 0 (idle) to 1 (saturated).
+    pub congestion: f64,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "ObjectRef")]
 pub struct OwnedObjectRef {
@@ -1829,6 +2140,8 @@ pub struct SuiEventEnvelope {
     pub timestamp: u64,
     /// Transaction digest of associated transaction, if any
     pub tx_digest: Option<TransactionDigest>,
+    /// Position of the event in the event stream of the node, to resume a subscription from
+    pub id: EventID,
     /// Specific event type
     pub event: SuiEvent,
 }
@@ -1849,6 +2162,9 @@ pub enum SuiEvent {
         #[serde_as(as = "Base64")]
         #[schemars(with = "Base64")]
         bcs: Vec<u8>,
+        /// Name and type of the fields of `type_`, if the node knows its layout
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        schema: Option<SuiEventSchema>,
     },
     /// Module published
     #[serde(rename_all = "camelCase")]
@@ -1891,6 +2207,60 @@ pub enum SuiEvent {
     Checkpoint(CheckpointSequenceNumber),
 }
 
+/// The layout of a Move event type, as derived from its struct definition.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "EventSchema")]
+pub struct SuiEventSchema {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub fields: Vec<SuiEventFieldSchema>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "EventFieldSchema")]
+pub struct SuiEventFieldSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+impl TryFrom<&MoveStructLayout> for SuiEventSchema {
+    type Error = anyhow::Error;
+
+    /// The schema of a struct layout built with its types.
+    fn try_from(layout: &MoveStructLayout) -> Result<Self, Self::Error> {
+        match layout {
+            MoveStructLayout::WithTypes { type_, fields } => Ok(Self {
+                type_: type_.to_string(),
+                fields: fields
+                    .iter()
+                    .map(|field| SuiEventFieldSchema {
+                        name: field.name.to_string(),
+                        type_: type_layout_name(&field.layout),
+                    })
+                    .collect(),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "Event schemas are derived from struct layouts with types"
+            )),
+        }
+    }
+}
+
+fn type_layout_name(layout: &MoveTypeLayout) -> String {
+    match layout {
+        MoveTypeLayout::Bool => "bool".to_string(),
+        MoveTypeLayout::U8 => "u8".to_string(),
+        MoveTypeLayout::U64 => "u64".to_string(),
+        MoveTypeLayout::U128 => "u128".to_string(),
+        MoveTypeLayout::Address => "address".to_string(),
+        MoveTypeLayout::Signer => "signer".to_string(),
+        MoveTypeLayout::Vector(inner) => format!("vector<{}>", type_layout_name(inner)),
+        MoveTypeLayout::Struct(MoveStructLayout::WithTypes { type_, .. }) => type_.to_string(),
+        MoveTypeLayout::Struct(_) => "struct".to_string(),
+    }
+}
+
 impl SuiEvent {
     pub fn try_from(event: Event, resolver: &impl GetModule) -> Result<Self, anyhow::Error> {
         Ok(match event {
@@ -1923,6 +2293,7 @@ impl SuiEvent {
                     type_,
                     fields,
                     bcs,
+                    schema: None,
                 }
             }
             Event::Publish { sender, package_id } => SuiEvent::Publish { sender, package_id },
@@ -1979,6 +2350,7 @@ impl PartialEq<SuiEventEnvelope> for EventEnvelope {
     fn eq(&self, other: &SuiEventEnvelope) -> bool {
         self.timestamp == other.timestamp
             && self.tx_digest == other.tx_digest
+            && self.id() == other.id
             && self.event == other.event
     }
 }
@@ -2000,6 +2372,7 @@ impl PartialEq<SuiEvent> for Event {
                     type_,
                     fields: _fields,
                     bcs,
+                    schema: _schema,
                 } = other
                 {
                     package_id == self_package_id
@@ -2170,6 +2543,130 @@ impl SuiObjectInfo {
     }
 }
 
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectsPage", rename_all = "camelCase")]
+pub struct SuiObjectsPage {
+    pub data: Vec<SuiObjectInfo>,
+    /// The cursor to get the next objects with, None if there are no more objects.
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
+
+impl SuiObjectsPage {
+    /// The first `limit` objects of `objects` from `cursor` on, in the order of their IDs. If
+    /// `object_type` is a struct type without type arguments, e.g. `0x2::coin::Coin`, the objects
+    /// of all its instantiations are kept, otherwise only the objects of exactly this type.
+    pub fn new(
+        mut objects: Vec<SuiObjectInfo>,
+        object_type: Option<&str>,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Self, anyhow::Error> {
+        let filter = object_type.map(parse_sui_struct_tag).transpose()?;
+        objects.retain(|object| {
+            cursor.map_or(true, |cursor| object.object_id >= cursor)
+                && filter
+                    .as_ref()
+                    .map_or(true, |filter| is_of_type(&object.type_, filter))
+        });
+        objects.sort_by_key(|object| object.object_id);
+        let next_cursor = objects.get(limit).map(|object| object.object_id);
+        objects.truncate(limit);
+        Ok(Self {
+            data: objects,
+            next_cursor,
+            has_next_page: next_cursor.is_some(),
+        })
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "TransactionsPage", rename_all = "camelCase")]
+pub struct SuiTransactionsPage {
+    pub data: Vec<(GatewayTxSeqNumber, TransactionDigest)>,
+    /// The cursor to get the next transactions with, None if there are no more transactions.
+    pub next_cursor: Option<GatewayTxSeqNumber>,
+    pub has_next_page: bool,
+}
+
+impl SuiTransactionsPage {
+    /// The first `limit` of `transactions`, which are in order from the cursor on and hold one
+    /// more transaction if there is a next page, starting at it.
+    pub fn new(
+        mut transactions: Vec<(GatewayTxSeqNumber, TransactionDigest)>,
+        limit: usize,
+    ) -> Self {
+        let next_cursor = transactions.get(limit).map(|(seq, _)| *seq);
+        transactions.truncate(limit);
+        Self {
+            data: transactions,
+            next_cursor,
+            has_next_page: next_cursor.is_some(),
+        }
+    }
+}
+
+/// The balance of an address in a type of coins.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "Balance", rename_all = "camelCase")]
+pub struct SuiBalance {
+    /// The type of the coins, e.g. `0x2::sui::SUI`.
+    pub coin_type: String,
+    pub coin_object_count: usize,
+    pub total_balance: u64,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "Coin", rename_all = "camelCase")]
+pub struct SuiCoin {
+    /// The type of the coin, e.g. `0x2::sui::SUI`.
+    pub coin_type: String,
+    pub coin_object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub balance: u64,
+    pub previous_transaction: TransactionDigest,
+}
+
+impl SuiCoin {
+    pub fn object_ref(&self) -> ObjectRef {
+        (self.coin_object_id, self.version, self.digest)
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CoinPage", rename_all = "camelCase")]
+pub struct SuiCoinsPage {
+    pub data: Vec<SuiCoin>,
+    /// The cursor to get the next coins with, None if there are no more coins.
+    pub next_cursor: Option<ObjectID>,
+    pub has_next_page: bool,
+}
+
+impl SuiCoinsPage {
+    /// The first `limit` of `coins`, which are in the order of their IDs from the cursor on and
+    /// hold one more coin if there is a next page, starting at it.
+    pub fn new(mut coins: Vec<SuiCoin>, limit: usize) -> Self {
+        let next_cursor = coins.get(limit).map(|coin| coin.coin_object_id);
+        coins.truncate(limit);
+        Self {
+            data: coins,
+            next_cursor,
+            has_next_page: next_cursor.is_some(),
+        }
+    }
+}
+
+fn is_of_type(type_: &str, filter: &StructTag) -> bool {
+    match parse_sui_struct_tag(type_) {
+        Ok(tag) if filter.type_params.is_empty() => {
+            tag.address == filter.address && tag.module == filter.module && tag.name == filter.name
+        }
+        Ok(tag) => &tag == filter,
+        Err(_) => false,
+    }
+}
+
 impl From<ObjectInfo> for SuiObjectInfo {
     fn from(info: ObjectInfo) -> Self {
         Self {
@@ -2255,7 +2752,34 @@ impl From<SuiTransactionFilter> for TransactionFilter {
     }
 }
 
-#[derive(Serialize, Deserialize, JsonSchema, Debug)]
+/// The transactions to query with `sui_getTransactions`.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub enum TransactionQuery {
+    /// All the transactions executed by the node.
+    All,
+    /// The transactions calling a Move function, of any module of the package or of any function
+    /// of the module if they are left out.
+    MoveFunction {
+        package: ObjectID,
+        module: Option<String>,
+        function: Option<String>,
+    },
+    /// The transactions taking the object as input.
+    InputObject(ObjectID),
+    /// The transactions mutating the object.
+    MutatedObject(ObjectID),
+    /// The transactions sent by the address.
+    FromAddress(SuiAddress),
+    /// The transactions sending objects to the address.
+    ToAddress(SuiAddress),
+    /// The transactions of the checkpoints `start` to `end` included.
+    Checkpoint {
+        start: CheckpointSequenceNumber,
+        end: CheckpointSequenceNumber,
+    },
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 #[serde(rename = "EventFilter")]
 pub enum SuiEventFilter {
     Package(ObjectID),
@@ -0,0 +1,85 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use jsonrpsee_core::middleware::Headers;
+use tracing::info;
+
+use crate::rpc_policy::FORWARDED_FOR_HEADER;
+
+/// Logs one line per HTTP request, once its response is sent. The server reports a request in
+/// several calls to the middleware, which are tied together by the id handed out when it starts.
+#[derive(Clone, Default)]
+pub struct AccessLog {
+    next_id: Arc<AtomicU64>,
+    pending: Arc<Mutex<HashMap<u64, PendingRequest>>>,
+}
+
+struct PendingRequest {
+    remote_addr: String,
+    origin: Option<String>,
+    /// Methods called by the request, several for a batch.
+    methods: Vec<String>,
+    success: bool,
+}
+
+impl AccessLog {
+    /// Starts tracking a request, returning its id.
+    pub fn start(&self, remote_addr: SocketAddr, headers: &Headers) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        // Behind the front server enforcing the policy, requests come from a local address and
+        // the front server passes the address of the client on
+        let forwarded_for = headers
+            .get(FORWARDED_FOR_HEADER)
+            .and_then(|client| client.to_str().ok())
+            .filter(|_| remote_addr.ip().is_loopback());
+        let remote_addr = match forwarded_for {
+            Some(client) => client.to_string(),
+            None => remote_addr.to_string(),
+        };
+        let origin = headers
+            .get("origin")
+            .and_then(|origin| origin.to_str().ok())
+            .map(|origin| origin.to_string());
+        self.pending.lock().unwrap().insert(
+            id,
+            PendingRequest {
+                remote_addr,
+                origin,
+                methods: vec![],
+                success: true,
+            },
+        );
+        id
+    }
+
+    pub fn record_call(&self, id: u64, method_name: &str, success: bool) {
+        if let Some(request) = self.pending.lock().unwrap().get_mut(&id) {
+            request.methods.push(method_name.to_string());
+            request.success &= success;
+        }
+    }
+
+    /// Logs the request once its response of `response_size` bytes is sent.
+    pub fn finish(&self, id: u64, started_at: Instant, response_size: usize) {
+        let request = match self.pending.lock().unwrap().remove(&id) {
+            Some(request) => request,
+            None => return,
+        };
+        info!(
+            target: "sui_json_rpc::access_log",
+            remote_addr = %request.remote_addr,
+            origin = request.origin.as_deref().unwrap_or("-"),
+            methods = %request.methods.join(","),
+            status = if request.success { "ok" } else { "error" },
+            duration_ms = started_at.elapsed().as_millis() as u64,
+            response_size,
+            "JSON-RPC request"
+        );
+    }
+}
@@ -7,16 +7,23 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GatewayTxSeqNumber, GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
-    MoveFunctionArgType, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
-    SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
-    SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, TransactionBytes,
+    DevInspectResults, GatewayTxSeqNumber, GetObjectDataResponse, GetPastObjectDataResponse,
+    GetRawObjectDataResponse, MoveFunctionArgType, RPCTransactionRequestParams, SuiBalance,
+    SuiCheckpointStateDiff, SuiCoinTransfersPage, SuiCoinsPage, SuiEpochFeeSummary,
+    SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse, SuiFinalityProof,
+    SuiGasCostSummary, SuiGasPriceSuggestion, SuiHotObjects, SuiMoveModuleDocs,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+    SuiObjectVersionHistoryPage, SuiObjectsPage, SuiSupply, SuiTransactionEffects,
+    SuiTransactionFilter, SuiTransactionResponse, SuiTransactionsPage, SuiTypeTag,
+    TransactionBytes, TransactionQuery,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::crypto::SignatureScheme;
+use sui_types::event::EventID;
 use sui_types::messages::ExecuteTransactionRequestType;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::sui_serde::Base64;
 
@@ -26,6 +33,32 @@ use sui_types::sui_serde::Base64;
 /// for document purposes.
 pub const EVENT_QUERY_MAX_LIMIT: usize = 100;
 
+/// Maximum number of objects returned by `sui_getHotObjects`.
+pub const HOT_OBJECTS_QUERY_MAX_LIMIT: usize = 100;
+
+/// Maximum number of checkpoints covered by `sui_getCheckpointStateDiff`.
+pub const CHECKPOINT_STATE_DIFF_MAX_RANGE: u64 = 100;
+
+/// Maximum number of transactions scanned by `sui_getTransfersToAddress` for one page.
+pub const TRANSFERS_QUERY_MAX_TRANSACTIONS: usize = 100;
+
+/// Maximum number of versions returned by `sui_getObjectVersionHistory` for one page.
+pub const OBJECT_VERSION_HISTORY_QUERY_MAX_VERSIONS: usize = 100;
+
+/// Maximum number of objects returned by `sui_getOwnedObjects`, `sui_getObjectsOwnedByAddress`
+/// and `sui_getObjectsOwnedByObject` for one page.
+pub const OWNED_OBJECTS_QUERY_MAX_LIMIT: usize = 100;
+
+/// Maximum number of transactions returned by the `sui_getTransactionsBy*`,
+/// `sui_getTransactionsFromAddress` and `sui_getTransactionsToAddress` queries for one page.
+pub const TRANSACTIONS_QUERY_MAX_LIMIT: usize = 100;
+
+/// Maximum number of coins returned by `sui_getCoins` for one page.
+pub const COINS_QUERY_MAX_LIMIT: usize = 100;
+
+/// Maximum time `sui_waitForTransaction` waits for a transaction, in milliseconds.
+pub const WAIT_FOR_TRANSACTION_MAX_TIMEOUT_MS: u64 = 60_000;
+
 #[open_rpc(namespace = "sui", tag = "Gateway Transaction Execution API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait RpcGatewayApi {
@@ -41,6 +74,9 @@ pub trait RpcGatewayApi {
         signature: Base64,
         /// signer's public key, as base-64 encoded string
         pub_key: Base64,
+        /// signature of the gas sponsor of a sponsored transaction, as base-64 encoded flag,
+        /// signature and public key bytes
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiTransactionResponse>;
 }
 
@@ -59,21 +95,50 @@ pub trait WalletSyncApi {
 #[open_rpc(namespace = "sui", tag = "Read API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait RpcReadApi {
-    /// Return the list of objects owned by an address.
+    /// Return the objects owned by an address in the order of their IDs. Up to
+    /// OWNED_OBJECTS_QUERY_MAX_LIMIT objects are returned per call, resume from the returned
+    /// cursor to get the next objects
     #[method(name = "getObjectsOwnedByAddress")]
     async fn get_objects_owned_by_address(
         &self,
         /// the owner's Sui address
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>>;
-
-    /// Return the list of objects owned by an object.
+        /// the cursor returned by the previous call, start from the first object if None
+        cursor: Option<ObjectID>,
+        /// maximum size of the result, capped to OWNED_OBJECTS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage>;
+
+    /// Return the objects owned by an object in the order of their IDs. Up to
+    /// OWNED_OBJECTS_QUERY_MAX_LIMIT objects are returned per call, resume from the returned
+    /// cursor to get the next objects
     #[method(name = "getObjectsOwnedByObject")]
     async fn get_objects_owned_by_object(
         &self,
         /// the ID of the owner object
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>>;
+        /// the cursor returned by the previous call, start from the first object if None
+        cursor: Option<ObjectID>,
+        /// maximum size of the result, capped to OWNED_OBJECTS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage>;
+
+    /// Return the objects owned by an address in the order of their IDs, only those of a Move
+    /// type if given: `0x2::coin::Coin` selects all the coins, and `0x2::coin::Coin<0x2::sui::SUI>`
+    /// the SUI coins. Up to OWNED_OBJECTS_QUERY_MAX_LIMIT objects are returned per call, resume
+    /// from the returned cursor to get the next objects
+    #[method(name = "getOwnedObjects")]
+    async fn get_owned_objects(
+        &self,
+        /// the owner's Sui address
+        address: SuiAddress,
+        /// the Move type of the objects, objects of all types if None
+        object_type: Option<String>,
+        /// the cursor returned by the previous call, start from the first object if None
+        cursor: Option<ObjectID>,
+        /// maximum size of the result, capped to OWNED_OBJECTS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage>;
 
     /// Return the total number of transactions known to the server.
     #[method(name = "getTotalTransactionNumber")]
@@ -124,8 +189,29 @@ pub trait RpcFullNodeReadApi {
         sig_scheme: SignatureScheme,
         signature: Base64,
         pub_key: Base64,
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiTransactionEffects>;
 
+    /// Execute a call to a `public` or `entry` Move function against the latest objects, without
+    /// signature nor gas coin, and return the values it returns and the events it emits. Nothing is
+    /// committed, so that state computed on chain can be read, e.g. the price of a pool
+    #[method(name = "devInspectMoveCall")]
+    async fn dev_inspect_move_call(
+        &self,
+        /// the Sui address calling the function
+        sender_address: SuiAddress,
+        /// the Move package ID, e.g. `0x2`
+        package_object_id: ObjectID,
+        /// the Move module name, e.g. `coin`
+        module: String,
+        /// the Move function name, e.g. `value`
+        function: String,
+        /// the type arguments of the Move function
+        type_arguments: Vec<SuiTypeTag>,
+        /// the arguments to be passed into the Move function, in [SuiJson](https://docs.sui.io/build/sui-json) format
+        arguments: Vec<SuiJsonValue>,
+    ) -> RpcResult<DevInspectResults>;
+
     /// Return the argument types of a Move function,
     /// based on normalized Type.
     #[method(name = "getMoveFunctionArgTypes")]
@@ -169,23 +255,65 @@ pub trait RpcFullNodeReadApi {
         function_name: String,
     ) -> RpcResult<SuiMoveNormalizedFunction>;
 
-    /// Return list of transactions for a specified input object.
+    /// Return the documentation of a module of the Move framework or stdlib run by this node,
+    /// generated from their sources, with the digest of the framework it documents
+    #[method(name = "getFrameworkModuleDocs")]
+    async fn get_framework_module_docs(
+        &self,
+        /// the package of the module, `0x1` for the stdlib or `0x2` for the framework
+        package: ObjectID,
+        /// the name of the module, e.g. `coin`
+        module_name: String,
+    ) -> RpcResult<SuiMoveModuleDocs>;
+
+    /// Return the transactions matching a query, in the order the node executed them or in
+    /// reverse. Up to TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from
+    /// the returned cursor to get the next transactions
+    #[method(name = "getTransactions")]
+    async fn get_transactions(
+        &self,
+        /// the transactions to query, by sender, recipient, object, Move function or checkpoints
+        query: TransactionQuery,
+        /// the cursor returned by the previous call, start from the first transaction (or the last
+        /// one in descending order) if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+        /// return the transactions from the latest to the oldest, false by default
+        descending_order: Option<bool>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the transactions for a specified input object, in order. Up to
+    /// TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from the returned
+    /// cursor to get the next transactions
     #[method(name = "getTransactionsByInputObject")]
     async fn get_transactions_by_input_object(
         &self,
         /// the ID of the input object
         object: ObjectID,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
-
-    /// Return list of transactions for a specified mutated object.
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the transactions for a specified mutated object, in order. Up to
+    /// TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from the returned
+    /// cursor to get the next transactions
     #[method(name = "getTransactionsByMutatedObject")]
     async fn get_transactions_by_mutated_object(
         &self,
         /// the ID of the mutated object
         object: ObjectID,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
-
-    /// Return list of transactions for a specified move function.
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the transactions for a specified move function, in order. Up to
+    /// TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from the returned
+    /// cursor to get the next transactions
     #[method(name = "getTransactionsByMoveFunction")]
     async fn get_transactions_by_move_function(
         &self,
@@ -195,23 +323,115 @@ pub trait RpcFullNodeReadApi {
         module: Option<String>,
         /// the move function name, e.g. `mint`
         function: Option<String>,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
-
-    /// Return list of transactions for a specified sender's Sui address.
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the transactions for a specified sender's Sui address, in order. Up to
+    /// TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from the returned
+    /// cursor to get the next transactions
     #[method(name = "getTransactionsFromAddress")]
     async fn get_transactions_from_addr(
         &self,
         /// the sender's Sui address
         addr: SuiAddress,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
-
-    /// Return list of transactions for a specified recipient's Sui address.
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the transactions for a specified recipient's Sui address, in order. Up to
+    /// TRANSACTIONS_QUERY_MAX_LIMIT transactions are returned per call, resume from the returned
+    /// cursor to get the next transactions
     #[method(name = "getTransactionsToAddress")]
     async fn get_transactions_to_addr(
         &self,
         /// the recipient's Sui address
         addr: SuiAddress,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>>;
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+        /// maximum size of the result, capped to TRANSACTIONS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage>;
+
+    /// Return the coins received by an address from other addresses, with their amounts and
+    /// coin types, for deposit scanners to follow without decoding the effects of every
+    /// transaction. Up to TRANSFERS_QUERY_MAX_TRANSACTIONS transactions are scanned per call,
+    /// resume from the returned cursor to get the next transfers
+    #[method(name = "getTransfersToAddress")]
+    async fn get_transfers_to_addr(
+        &self,
+        /// the recipient's Sui address
+        addr: SuiAddress,
+        /// the cursor returned by the previous call, start from the first transaction if None
+        cursor: Option<GatewayTxSeqNumber>,
+    ) -> RpcResult<SuiCoinTransfersPage>;
+
+    /// Return the versions of an object, with their digests and the transactions which created
+    /// them, for provenance tooling to walk the lineage of the object without fetching the
+    /// effects of every transaction. Up to OBJECT_VERSION_HISTORY_QUERY_MAX_VERSIONS versions are
+    /// returned per call, resume from the returned cursor to get the next versions
+    #[method(name = "getObjectVersionHistory")]
+    async fn get_object_version_history(
+        &self,
+        /// the ID of the object
+        object_id: ObjectID,
+        /// the cursor returned by the previous call, start from the first version if None
+        cursor: Option<SequenceNumber>,
+    ) -> RpcResult<SuiObjectVersionHistoryPage>;
+
+    /// Return the total balance of an address in a type of coins, and the number of coin objects
+    /// holding it
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the type of the coins, e.g. `0x2::sui::SUI`, SUI if None
+        coin_type: Option<String>,
+    ) -> RpcResult<SuiBalance>;
+
+    /// Return the total balance of an address in every type of coins it owns, sorted by coin type
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<SuiBalance>>;
+
+    /// Return the coins of a type owned by an address in the order of their IDs, with their
+    /// balances. Up to COINS_QUERY_MAX_LIMIT coins are returned per call, resume from the returned
+    /// cursor to get the next coins
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the type of the coins, e.g. `0x2::sui::SUI`, SUI if None
+        coin_type: Option<String>,
+        /// the cursor returned by the previous call, start from the first coin if None
+        cursor: Option<ObjectID>,
+        /// maximum size of the result, capped to COINS_QUERY_MAX_LIMIT
+        limit: Option<usize>,
+    ) -> RpcResult<SuiCoinsPage>;
+
+    /// Return the total supply of SUI, accounted from genesis over the transactions executed by
+    /// this node: the coins created at genesis and the SUI minted by the system, less the gas
+    /// burned net of storage rebates
+    #[method(name = "getSupply")]
+    async fn get_supply(&self) -> RpcResult<SuiSupply>;
+
+    /// Return the gas charged by the transactions of an epoch and the SUI minted at its end,
+    /// over the transactions executed by this node
+    #[method(name = "getEpochFeeSummary")]
+    async fn get_epoch_fee_summary(
+        &self,
+        /// the epoch to summarize
+        epoch: EpochId,
+    ) -> RpcResult<SuiEpochFeeSummary>;
 
     /// Note there is no software-level guarantee/SLA that objects with past versions
     /// can be retrieved by this API, even if the object and version exists/existed.
@@ -225,6 +445,43 @@ pub trait RpcFullNodeReadApi {
         /// the version of the queried object. If None, default to the latest known version
         version: SequenceNumber,
     ) -> RpcResult<GetPastObjectDataResponse>;
+
+    /// Return a self-contained proof that a transaction is final, for verifiers trusting only
+    /// the genesis committee: the certificate, the effects, the certified checkpoint including
+    /// the transaction if there is one yet, and the chain of epochs from genesis
+    #[method(name = "getFinalityProof")]
+    async fn get_finality_proof(
+        &self,
+        /// the digest of the transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<SuiFinalityProof>;
+
+    /// Return the net changes to the objects made by the transactions of a range of checkpoints,
+    /// computed from their effects, for derived databases to sync without replaying every
+    /// transaction. Ranges longer than CHECKPOINT_STATE_DIFF_MAX_RANGE are cut short, the
+    /// result says up to which checkpoint it goes
+    #[method(name = "getCheckpointStateDiff")]
+    async fn get_checkpoint_state_diff(
+        &self,
+        /// the first checkpoint of the range
+        from: CheckpointSequenceNumber,
+        /// the last checkpoint of the range, included
+        to: CheckpointSequenceNumber,
+        /// whether to also return the created and mutated objects, default to false
+        with_contents: Option<bool>,
+    ) -> RpcResult<SuiCheckpointStateDiff>;
+
+    /// Wait until this node has executed a transaction, and return it like sui_getTransaction,
+    /// instead of polling for it. Fails if it isn't executed within the timeout
+    #[method(name = "waitForTransaction")]
+    async fn wait_for_transaction(
+        &self,
+        /// the digest of the transaction
+        digest: TransactionDigest,
+        /// how long to wait in milliseconds, default to and capped at
+        /// WAIT_FOR_TRANSACTION_MAX_TIMEOUT_MS
+        timeout_ms: Option<u64>,
+    ) -> RpcResult<SuiTransactionResponse>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
@@ -347,6 +604,26 @@ pub trait RpcTransactionBuilder {
         gas_budget: u64,
     ) -> RpcResult<TransactionBytes>;
 
+    /// Create an unsigned transaction to pay an amount of coins of a type to a Sui address, with
+    /// coins of the signer selected automatically, the largest ones first: the coins used up are
+    /// transferred whole, and the rest of the amount is split off the last coin selected.
+    #[method(name = "pay")]
+    async fn pay(
+        &self,
+        /// the transaction signer's Sui address
+        signer: SuiAddress,
+        /// the type of the coins to pay with, e.g. `0x2::sui::SUI`, SUI if None
+        coin_type: Option<String>,
+        /// the amount to pay
+        amount: u64,
+        /// the recipient's Sui address
+        recipient: SuiAddress,
+        /// gas object to be used in this transaction, the gateway will pick one from the signer's possession if not provided
+        gas: Option<ObjectID>,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes>;
+
     /// Create an unsigned batched transaction.
     #[method(name = "batchTransaction")]
     async fn batch_transaction(
@@ -395,6 +672,8 @@ pub trait EventStreamingApi {
         &self,
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: SuiEventFilter,
+        /// the id of the last event received, the events emitted since then are sent before the new ones
+        cursor: Option<EventID>,
     );
 }
 
@@ -513,6 +792,9 @@ pub trait QuorumDriverApi {
         pub_key: Base64,
         /// The request type
         request_type: ExecuteTransactionRequestType,
+        /// signature of the gas sponsor of a sponsored transaction, as base-64 encoded flag,
+        /// signature and public key bytes
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiExecuteTransactionResponse>;
 }
 
@@ -530,3 +812,26 @@ pub trait EstimatorApi {
         tx_bytes: Base64,
     ) -> RpcResult<SuiGasCostSummary>;
 }
+
+#[open_rpc(namespace = "sui", tag = "Gas Price API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait GasPriceApi {
+    /// Return low, medium and high gas price suggestions derived from the inclusion latency of
+    /// recent transactions and the depth of the submission queue, and a congestion score
+    /// between 0 (idle) and 1 (saturated)
+    #[method(name = "getGasPriceSuggestion")]
+    async fn get_gas_price_suggestion(&self) -> RpcResult<SuiGasPriceSuggestion>;
+}
+
+#[open_rpc(namespace = "sui", tag = "Hot Objects API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait HotObjectsApi {
+    /// Return the shared objects most contended on in the current epoch, by number of
+    /// transactions, with the average time these transactions waited to be sequenced
+    #[method(name = "getHotObjects")]
+    async fn get_hot_objects(
+        &self,
+        /// maximum size of the result, capped to HOT_OBJECTS_QUERY_MAX_LIMIT
+        count: usize,
+    ) -> RpcResult<SuiHotObjects>;
+}
@@ -5,7 +5,7 @@ use crate::api::EventStreamingApiServer;
 use crate::streaming_api::spawn_subscription;
 use crate::SuiRpcModule;
 use async_trait::async_trait;
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
@@ -20,6 +20,8 @@ use sui_core::event_handler::EventHandler;
 use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope, SuiEventFilter};
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::event::{EventEnvelope, EventID};
+use sui_types::filter::EventFilter;
 use sui_types::object::Owner;
 use tracing::warn;
 
@@ -43,8 +45,9 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
         &self,
         mut sink: SubscriptionSink,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SubscriptionResult {
-        let filter = match filter.try_into() {
+        let filter: EventFilter = match filter.try_into() {
             Ok(filter) => filter,
             Err(e) => {
                 let e = jsonrpsee_core::Error::from(e);
@@ -53,14 +56,35 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
             }
         };
 
+        // Subscribed before reading the missed events, so no event is emitted in between
+        let stream = self.event_handler.subscribe(filter.clone());
+        let missed = match cursor {
+            Some(cursor) => match self.state.get_events_after(cursor, &filter) {
+                Ok(missed) => missed,
+                Err(e) => {
+                    let e = jsonrpsee_core::Error::from(e);
+                    warn!(error = ?e, "Rejecting subscription request.");
+                    return Ok(sink.reject(e)?);
+                }
+            },
+            None => vec![],
+        };
+        // The events of the transactions post-processed while reading the missed ones are sent
+        // by both
+        let last_sent = missed.last().map(EventEnvelope::id).or(cursor);
+        let stream =
+            stream.filter(move |e| future::ready(last_sent.map_or(true, |id| e.id() > id)));
+
         let state = self.state.clone();
-        let stream = self.event_handler.subscribe(filter);
-        let stream = stream.map(move |e| {
+        let event_handler = self.event_handler.clone();
+        let stream = futures::stream::iter(missed).chain(stream).map(move |e| {
+            let id = e.id();
             let event = SuiEvent::try_from(e.event, state.module_cache.as_ref());
             event.map(|event| SuiEventEnvelope {
                 timestamp: e.timestamp,
                 tx_digest: e.tx_digest,
-                event,
+                id,
+                event: event_handler.event_schemas().decode(event),
             })
         });
         spawn_subscription(sink, stream);
@@ -0,0 +1,58 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::api::GasPriceApiServer;
+use crate::SuiRpcModule;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::quorum_driver::QuorumDriver;
+use sui_json_rpc_types::SuiGasPriceSuggestion;
+use sui_open_rpc::Module;
+
+pub struct GasPriceApiImpl {
+    state: Arc<AuthorityState>,
+    quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+}
+
+impl GasPriceApiImpl {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+    ) -> Self {
+        Self {
+            state,
+            quorum_driver,
+        }
+    }
+}
+
+#[async_trait]
+impl GasPriceApiServer for GasPriceApiImpl {
+    async fn get_gas_price_suggestion(&self) -> RpcResult<SuiGasPriceSuggestion> {
+        let system_state = self.state.get_sui_system_state_object().await?;
+        let suggestion = self
+            .quorum_driver
+            .gas_price_suggestion(system_state.reference_gas_price);
+        Ok(SuiGasPriceSuggestion {
+            reference_gas_price: suggestion.reference_gas_price,
+            low: suggestion.low,
+            medium: suggestion.medium,
+            high: suggestion.high,
+            congestion: suggestion.congestion,
+        })
+    }
+}
+
+impl SuiRpcModule for GasPriceApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::GasPriceApiOpenRpc::module_doc()
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::api::{
     RpcGatewayApiServer, RpcReadApiServer, RpcTransactionBuilderServer, WalletSyncApiServer,
+    OWNED_OBJECTS_QUERY_MAX_LIMIT,
 };
 use crate::SuiRpcModule;
 use anyhow::anyhow;
@@ -13,7 +14,7 @@ use signature::Signature;
 use sui_core::gateway_state::{GatewayClient, GatewayTxSeqNumber};
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, RPCTransactionRequestParams, SuiObjectInfo, SuiTransactionResponse,
+    GetObjectDataResponse, RPCTransactionRequestParams, SuiObjectsPage, SuiTransactionResponse,
     SuiTypeTag, TransactionBytes,
 };
 use sui_open_rpc::Module;
@@ -74,6 +75,7 @@ impl RpcGatewayApiServer for RpcGatewayImpl {
         sig_scheme: SignatureScheme,
         signature: Base64,
         pub_key: Base64,
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiTransactionResponse> {
         let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
         let flag = vec![sig_scheme.flag()];
@@ -81,10 +83,13 @@ impl RpcGatewayApiServer for RpcGatewayImpl {
             &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
         )
         .map_err(|e| anyhow!(e))?;
-        let result = self
-            .client
-            .execute_transaction(Transaction::new(data, signature))
-            .await;
+        let mut txn = Transaction::new(data, signature);
+        if let Some(sponsor_signature) = sponsor_signature {
+            let sponsor_signature = crypto::Signature::from_bytes(&sponsor_signature.to_vec()?)
+                .map_err(|e| anyhow!(e))?;
+            txn = txn.with_sponsor_signature(sponsor_signature);
+        }
+        let result = self.client.execute_transaction(txn).await;
         Ok(result?)
     }
 }
@@ -123,17 +128,49 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
         debug!("get_objects_own_by_address : {}", address);
-        Ok(self.client.get_objects_owned_by_address(address).await?)
+        let objects = self.client.get_objects_owned_by_address(address).await?;
+        let limit = limit
+            .unwrap_or(OWNED_OBJECTS_QUERY_MAX_LIMIT)
+            .min(OWNED_OBJECTS_QUERY_MAX_LIMIT);
+        Ok(SuiObjectsPage::new(objects, None, cursor, limit)?)
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
         debug!("get_objects_own_by_object : {}", object_id);
-        Ok(self.client.get_objects_owned_by_object(object_id).await?)
+        let objects = self.client.get_objects_owned_by_object(object_id).await?;
+        let limit = limit
+            .unwrap_or(OWNED_OBJECTS_QUERY_MAX_LIMIT)
+            .min(OWNED_OBJECTS_QUERY_MAX_LIMIT);
+        Ok(SuiObjectsPage::new(objects, None, cursor, limit)?)
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
+        debug!("get_owned_objects : {}", address);
+        let objects = self.client.get_objects_owned_by_address(address).await?;
+        let limit = limit
+            .unwrap_or(OWNED_OBJECTS_QUERY_MAX_LIMIT)
+            .min(OWNED_OBJECTS_QUERY_MAX_LIMIT);
+        Ok(SuiObjectsPage::new(
+            objects,
+            object_type.as_deref(),
+            cursor,
+            limit,
+        )?)
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
@@ -302,6 +339,22 @@ impl RpcTransactionBuilderServer for TransactionBuilderImpl {
         Ok(TransactionBytes::from_data(data)?)
     }
 
+    async fn pay(
+        &self,
+        signer: SuiAddress,
+        coin_type: Option<String>,
+        amount: u64,
+        recipient: SuiAddress,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> RpcResult<TransactionBytes> {
+        let data = self
+            .client
+            .pay(signer, coin_type, amount, recipient, gas, gas_budget)
+            .await?;
+        Ok(TransactionBytes::from_data(data)?)
+    }
+
     async fn batch_transaction(
         &self,
         signer: SuiAddress,
@@ -0,0 +1,55 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::api::{HotObjectsApiServer, HOT_OBJECTS_QUERY_MAX_LIMIT};
+use crate::SuiRpcModule;
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee_core::server::rpc_module::RpcModule;
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
+use sui_json_rpc_types::{SuiHotObject, SuiHotObjects};
+use sui_open_rpc::Module;
+
+pub struct HotObjectsApiImpl {
+    state: Arc<AuthorityState>,
+}
+
+impl HotObjectsApiImpl {
+    pub fn new(state: Arc<AuthorityState>) -> Self {
+        Self { state }
+    }
+}
+
+#[async_trait]
+impl HotObjectsApiServer for HotObjectsApiImpl {
+    async fn get_hot_objects(&self, count: usize) -> RpcResult<SuiHotObjects> {
+        let (epoch, hot_objects) = self
+            .state
+            .hot_objects
+            .hot_objects(std::cmp::min(count, HOT_OBJECTS_QUERY_MAX_LIMIT));
+        Ok(SuiHotObjects {
+            epoch,
+            objects: hot_objects
+                .into_iter()
+                .map(|hot_object| SuiHotObject {
+                    object_id: hot_object.object_id,
+                    transaction_count: hot_object.transaction_count,
+                    average_sequencing_wait_ms: hot_object
+                        .average_sequencing_wait
+                        .map(|wait| wait.as_millis() as u64),
+                })
+                .collect(),
+        })
+    }
+}
+
+impl SuiRpcModule for HotObjectsApiImpl {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        crate::api::HotObjectsApiOpenRpc::module_doc()
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::env;
-use std::net::SocketAddr;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::time::Instant;
 
 pub use jsonrpsee::http_server;
@@ -19,16 +19,22 @@ use tracing::info;
 
 use sui_open_rpc::{Module, Project};
 
+use crate::access_log::AccessLog;
 use crate::http_server::{HttpServerBuilder, HttpServerHandle};
+use crate::rpc_policy::{RpcPolicy, RpcPolicyHandle};
 use crate::ws_server::{WsServerBuilder, WsServerHandle};
 
+pub mod access_log;
 pub mod api;
 pub mod bcs_api;
 pub mod estimator_api;
 pub mod event_api;
+pub mod gas_price_api;
 pub mod gateway_api;
+pub mod hot_objects_api;
 pub mod quorum_driver_api;
 pub mod read_api;
+pub mod rpc_policy;
 pub mod streaming_api;
 
 pub enum ServerBuilder<M = ()> {
@@ -37,7 +43,8 @@ pub enum ServerBuilder<M = ()> {
 }
 
 pub enum ServerHandle {
-    HttpHandler(HttpServerHandle, SocketAddr),
+    /// An HTTP server, with the handle of its policy if it started with one.
+    HttpHandler(HttpServerHandle, SocketAddr, Option<RpcPolicyHandle>),
     WsHandle(WsServerHandle, SocketAddr),
 }
 
@@ -50,7 +57,7 @@ pub enum ApiMetrics {
 impl ServerHandle {
     pub fn into_http_server_handle(self) -> Option<HttpServerHandle> {
         match self {
-            ServerHandle::HttpHandler(handle, _) => Some(handle),
+            ServerHandle::HttpHandler(handle, _, _) => Some(handle),
             _ => None,
         }
     }
@@ -64,17 +71,34 @@ impl ServerHandle {
 
     pub fn local_addr(&self) -> &SocketAddr {
         match self {
-            ServerHandle::HttpHandler(_, addr) | ServerHandle::WsHandle(_, addr) => addr,
+            ServerHandle::HttpHandler(_, addr, _) | ServerHandle::WsHandle(_, addr) => addr,
+        }
+    }
+
+    pub fn policy_handle(&self) -> Option<RpcPolicyHandle> {
+        match self {
+            ServerHandle::HttpHandler(_, _, policy_handle) => policy_handle.clone(),
+            _ => None,
         }
     }
 }
 
 pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
-    server_builder: ServerBuilder<ApiMetrics>,
+    server_builder: ServerBuilder,
+    middleware: RpcMiddleware,
     rpc_doc: Project,
+    policy: RpcPolicy,
+    max_request_body_size: u32,
 }
 
+/// The largest request jsonrpsee accepts by default, 10 MiB.
+const DEFAULT_MAX_REQUEST_BODY_SIZE: u32 = 10 * 1024 * 1024;
+
+/// Path of the HTTP server answering GET requests with the OpenRPC document of its methods, the
+/// result of `rpc.discover`.
+pub const SPEC_PATH: &str = "/spec";
+
 pub fn sui_rpc_doc() -> Project {
     Project::new(
         "Sui JSON-RPC",
@@ -106,19 +130,15 @@ impl JsonRpcServerBuilder {
         .build();
         info!(?acl);
 
-        let server_builder = if use_websocket {
-            ServerBuilder::WsBuilder(
-                WsServerBuilder::default()
-                    .set_access_control(acl)
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
+        let (server_builder, metrics) = if use_websocket {
+            (
+                ServerBuilder::WsBuilder(WsServerBuilder::default().set_access_control(acl)),
+                ApiMetrics::WebsocketMetrics(WebsocketMetrics {}),
             )
         } else {
-            ServerBuilder::HttpBuilder(
-                HttpServerBuilder::default()
-                    .set_access_control(acl)
-                    .set_middleware(ApiMetrics::JsonRpcMetrics(JsonRpcMetrics::new(
-                        prometheus_registry,
-                    ))),
+            (
+                ServerBuilder::HttpBuilder(HttpServerBuilder::default().set_access_control(acl)),
+                ApiMetrics::JsonRpcMetrics(JsonRpcMetrics::new(prometheus_registry)),
             )
         };
 
@@ -127,21 +147,18 @@ impl JsonRpcServerBuilder {
         Ok(Self {
             module,
             server_builder,
+            middleware: RpcMiddleware::new(metrics),
             rpc_doc: sui_rpc_doc(),
+            policy: RpcPolicy::default(),
+            max_request_body_size: DEFAULT_MAX_REQUEST_BODY_SIZE,
         })
     }
 
     pub fn new_without_metrics_for_testing(use_websocket: bool) -> anyhow::Result<Self> {
         let server_builder = if use_websocket {
-            ServerBuilder::WsBuilder(
-                WsServerBuilder::default()
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
-            )
+            ServerBuilder::WsBuilder(WsServerBuilder::default())
         } else {
-            ServerBuilder::HttpBuilder(
-                HttpServerBuilder::default()
-                    .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
-            )
+            ServerBuilder::HttpBuilder(HttpServerBuilder::default())
         };
 
         let module = RpcModule::new(());
@@ -149,10 +166,105 @@ impl JsonRpcServerBuilder {
         Ok(Self {
             module,
             server_builder,
+            middleware: RpcMiddleware::new(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
             rpc_doc: sui_rpc_doc(),
+            policy: RpcPolicy::default(),
+            max_request_body_size: DEFAULT_MAX_REQUEST_BODY_SIZE,
         })
     }
 
+    /// Caps the number of concurrent websocket connections, rejecting connections beyond it.
+    /// HTTP servers do not hold connections between requests, so this is a no-op for them.
+    pub fn with_max_connections(mut self, max_connections: u64) -> Self {
+        self.server_builder = match self.server_builder {
+            ServerBuilder::WsBuilder(ws_builder) => {
+                ServerBuilder::WsBuilder(ws_builder.max_connections(max_connections))
+            }
+            http_builder => http_builder,
+        };
+        self
+    }
+
+    /// Whether batches of requests are answered, rather than rejected as invalid requests.
+    pub fn with_batch_requests(mut self, supported: bool) -> Self {
+        self.server_builder = match self.server_builder {
+            ServerBuilder::HttpBuilder(http_builder) => {
+                ServerBuilder::HttpBuilder(http_builder.batch_requests_supported(supported))
+            }
+            ServerBuilder::WsBuilder(ws_builder) => {
+                ServerBuilder::WsBuilder(ws_builder.batch_requests_supported(supported))
+            }
+        };
+        self
+    }
+
+    /// Rejects requests larger than `max_size` bytes, a batch counting as one request.
+    pub fn with_max_request_body_size(mut self, max_size: u32) -> Self {
+        self.max_request_body_size = max_size;
+        self.server_builder = match self.server_builder {
+            ServerBuilder::HttpBuilder(http_builder) => {
+                ServerBuilder::HttpBuilder(http_builder.max_request_body_size(max_size))
+            }
+            ServerBuilder::WsBuilder(ws_builder) => {
+                ServerBuilder::WsBuilder(ws_builder.max_request_body_size(max_size))
+            }
+        };
+        self
+    }
+
+    /// Replaces the CORS policy of the `ACCESS_CONTROL_ALLOW_ORIGIN` environment variable with
+    /// the given origins if any, and allows browsers to send the given headers on top of the
+    /// CORS-safelisted ones.
+    pub fn with_cors(
+        mut self,
+        allowed_origins: Option<&[String]>,
+        allowed_headers: Option<&[String]>,
+    ) -> anyhow::Result<Self> {
+        if allowed_origins.is_none() && allowed_headers.is_none() {
+            return Ok(self);
+        }
+        let mut acl = AccessControlBuilder::default();
+        if let Some(origins) = allowed_origins {
+            // jsonrpsee allows any origin unless given a list, where "*" is not special
+            if !origins.iter().any(|origin| origin == "*") {
+                acl = acl.set_allowed_origins(origins)?;
+            }
+        }
+        if let Some(headers) = allowed_headers {
+            acl = acl.set_allowed_headers(headers)?;
+        }
+        let acl = acl.build();
+        info!(?acl);
+        self.server_builder = match self.server_builder {
+            ServerBuilder::HttpBuilder(http_builder) => {
+                ServerBuilder::HttpBuilder(http_builder.set_access_control(acl))
+            }
+            ServerBuilder::WsBuilder(ws_builder) => {
+                ServerBuilder::WsBuilder(ws_builder.set_access_control(acl))
+            }
+        };
+        Ok(self)
+    }
+
+    /// Logs every HTTP request once answered, see [`AccessLog`]. Websocket connections carry
+    /// many requests over their lifetime, so this is a no-op for them.
+    pub fn with_access_log(mut self) -> Self {
+        if let ServerBuilder::HttpBuilder(_) = self.server_builder {
+            self.middleware.access_log = Some(AccessLog::default());
+        }
+        self
+    }
+
+    /// Rate limits the calls, restricts the methods answered and bounds the time to answer, see
+    /// [`RpcPolicy`]. The calls of websocket connections go straight to the server, so this is a
+    /// no-op for them.
+    pub fn with_policy(mut self, policy: RpcPolicy) -> Self {
+        if let ServerBuilder::HttpBuilder(_) = self.server_builder {
+            self.policy = policy;
+        }
+        self
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), anyhow::Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -167,13 +279,41 @@ impl JsonRpcServerBuilder {
         let methods_names = self.module.method_names().collect::<Vec<_>>();
         let (handle, server_name) = match self.server_builder {
             ServerBuilder::HttpBuilder(http_builder) => {
-                let server = http_builder.build(listen_address).await?;
-                let addr = server.local_addr()?;
+                // The server listens locally behind the front server enforcing the policy, if any
+                let server_address = if self.policy.is_empty() {
+                    listen_address
+                } else {
+                    SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)
+                };
+                // The OpenRPC document of the server is also served to plain GET requests
+                let server = http_builder
+                    .health_api(SPEC_PATH, "rpc.discover")?
+                    .set_middleware(self.middleware)
+                    .build(server_address)
+                    .await?;
+                let mut addr = server.local_addr()?;
                 let handle = server.start(self.module)?;
-                (ServerHandle::HttpHandler(handle, addr), "JSON-RPC")
+                let mut policy_handle = None;
+                if !self.policy.is_empty() {
+                    let (front_addr, front_policy_handle) = rpc_policy::serve(
+                        listen_address,
+                        addr,
+                        self.policy,
+                        self.max_request_body_size,
+                    )?;
+                    addr = front_addr;
+                    policy_handle = Some(front_policy_handle);
+                }
+                (
+                    ServerHandle::HttpHandler(handle, addr, policy_handle),
+                    "JSON-RPC",
+                )
             }
             ServerBuilder::WsBuilder(ws_builder) => {
-                let server = ws_builder.build(listen_address).await?;
+                let server = ws_builder
+                    .set_middleware(self.middleware)
+                    .build(listen_address)
+                    .await?;
                 let addr = server.local_addr()?;
                 let handle = server.start(self.module)?;
                 (ServerHandle::WsHandle(handle, addr), "Websocket")
@@ -234,6 +374,89 @@ impl JsonRpcMetrics {
 #[derive(Clone)]
 pub struct WebsocketMetrics {}
 
+/// Middleware of the servers: their metrics, and the access log if enabled.
+#[derive(Clone)]
+pub struct RpcMiddleware {
+    metrics: ApiMetrics,
+    access_log: Option<AccessLog>,
+}
+
+impl RpcMiddleware {
+    fn new(metrics: ApiMetrics) -> Self {
+        Self {
+            metrics,
+            access_log: None,
+        }
+    }
+}
+
+/// When a request started, and its id in the access log if enabled.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestStart {
+    started_at: Instant,
+    access_log_id: Option<u64>,
+}
+
+impl HttpMiddleware for RpcMiddleware {
+    type Instant = RequestStart;
+
+    fn on_request(&self, remote_addr: SocketAddr, headers: &Headers) -> RequestStart {
+        RequestStart {
+            started_at: HttpMiddleware::on_request(&self.metrics, remote_addr, headers),
+            access_log_id: self
+                .access_log
+                .as_ref()
+                .map(|access_log| access_log.start(remote_addr, headers)),
+        }
+    }
+
+    fn on_call(&self, method_name: &str, params: Params, kind: MethodKind) {
+        HttpMiddleware::on_call(&self.metrics, method_name, params, kind)
+    }
+
+    fn on_result(&self, name: &str, success: bool, started_at: RequestStart) {
+        HttpMiddleware::on_result(&self.metrics, name, success, started_at.started_at);
+        if let (Some(access_log), Some(id)) = (&self.access_log, started_at.access_log_id) {
+            access_log.record_call(id, name, success);
+        }
+    }
+
+    fn on_response(&self, result: &str, started_at: RequestStart) {
+        HttpMiddleware::on_response(&self.metrics, result, started_at.started_at);
+        if let (Some(access_log), Some(id)) = (&self.access_log, started_at.access_log_id) {
+            access_log.finish(id, started_at.started_at, result.len());
+        }
+    }
+}
+
+impl WsMiddleware for RpcMiddleware {
+    type Instant = Instant;
+
+    fn on_connect(&self, remote_addr: SocketAddr, headers: &Headers) {
+        self.metrics.on_connect(remote_addr, headers)
+    }
+
+    fn on_request(&self) -> Self::Instant {
+        WsMiddleware::on_request(&self.metrics)
+    }
+
+    fn on_call(&self, method_name: &str, params: Params, kind: MethodKind) {
+        WsMiddleware::on_call(&self.metrics, method_name, params, kind)
+    }
+
+    fn on_result(&self, method_name: &str, success: bool, started_at: Self::Instant) {
+        WsMiddleware::on_result(&self.metrics, method_name, success, started_at)
+    }
+
+    fn on_response(&self, result: &str, started_at: Self::Instant) {
+        WsMiddleware::on_response(&self.metrics, result, started_at)
+    }
+
+    fn on_disconnect(&self, remote_addr: SocketAddr) {
+        self.metrics.on_disconnect(remote_addr)
+    }
+}
+
 impl HttpMiddleware for ApiMetrics {
     type Instant = Instant;
 
@@ -50,6 +50,7 @@ impl QuorumDriverApiServer for FullNodeQuorumDriverApi {
         signature: Base64,
         pub_key: Base64,
         request_type: ExecuteTransactionRequestType,
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiExecuteTransactionResponse> {
         let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
         let flag = vec![sig_scheme.flag()];
@@ -57,7 +58,12 @@ impl QuorumDriverApiServer for FullNodeQuorumDriverApi {
             &[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat(),
         )
         .map_err(|e| anyhow!(e))?;
-        let txn = Transaction::new(data, signature);
+        let mut txn = Transaction::new(data, signature);
+        if let Some(sponsor_signature) = sponsor_signature {
+            let sponsor_signature = crypto::Signature::from_bytes(&sponsor_signature.to_vec()?)
+                .map_err(|e| anyhow!(e))?;
+            txn = txn.with_sponsor_signature(sponsor_signature);
+        }
         let txn_digest = *txn.digest();
         let response = self
             .quorum_driver
@@ -3,30 +3,52 @@
 
 use crate::api::RpcFullNodeReadApiServer;
 use crate::api::RpcReadApiServer;
+use crate::api::CHECKPOINT_STATE_DIFF_MAX_RANGE;
+use crate::api::COINS_QUERY_MAX_LIMIT;
+use crate::api::OBJECT_VERSION_HISTORY_QUERY_MAX_VERSIONS;
+use crate::api::OWNED_OBJECTS_QUERY_MAX_LIMIT;
+use crate::api::TRANSACTIONS_QUERY_MAX_LIMIT;
+use crate::api::TRANSFERS_QUERY_MAX_TRANSACTIONS;
+use crate::api::WAIT_FOR_TRANSACTION_MAX_TIMEOUT_MS;
 use crate::SuiRpcModule;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use jsonrpsee::types::error::CallError;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use signature::Signature;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_core::authority::AuthorityState;
 use sui_core::gateway_state::GatewayTxSeqNumber;
+use sui_json::{resolve_public_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind,
+    DevInspectResults, GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType,
+    ObjectValueKind, SuiBalance, SuiCheckpointStateDiff, SuiCoin, SuiCoinTransfer,
+    SuiCoinTransfersPage, SuiCoinsPage, SuiEpochFeeSummary, SuiFinalityProof, SuiMoveModuleDocs,
     SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse,
+    SuiObjectVersion, SuiObjectVersionHistoryPage, SuiObjectsPage, SuiSupply,
+    SuiTransactionEffects, SuiTransactionResponse, SuiTransactionsPage, SuiTypeTag,
+    TransactionQuery,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
 use sui_types::crypto::{SignableBytes, SignatureScheme};
-use sui_types::messages::{Transaction, TransactionData};
+use sui_types::gas_coin::GAS;
+use sui_types::messages::{
+    CallArg, CertifiedTransaction, MoveCall, ObjectArg, Transaction, TransactionData,
+    TransactionEffects,
+};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::normalize_modules;
-use sui_types::object::{Data, ObjectRead, Owner};
+use sui_types::object::{Data, Object, ObjectRead, Owner};
+use sui_types::parse_sui_type_tag;
 use sui_types::sui_serde::Base64;
 
 // An implementation of the read portion of the Gateway JSON-RPC interface intended for use in
@@ -43,12 +65,96 @@ impl FullNodeApi {
     pub fn new(state: Arc<AuthorityState>) -> Self {
         Self { state }
     }
+
+    /// The coins of `owner` in the order of their IDs, only those of `coin_type` if given.
+    fn get_owned_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&TypeTag>,
+    ) -> RpcResult<Vec<SuiCoin>> {
+        Ok(self
+            .state
+            .get_owned_coins(owner)?
+            .into_iter()
+            .filter(|(_, type_, _)| coin_type.map_or(true, |coin_type| coin_type == type_))
+            .map(|(info, type_, balance)| SuiCoin {
+                coin_type: type_.to_string(),
+                coin_object_id: info.object_id,
+                version: info.version,
+                digest: info.digest,
+                balance,
+                previous_transaction: info.previous_transaction,
+            })
+            .collect())
+    }
+
+    /// The latest version of the object `object_id`.
+    async fn get_latest_object(&self, object_id: ObjectID) -> RpcResult<Object> {
+        Ok(self
+            .state
+            .get_object_read(&object_id)
+            .await?
+            .into_object()?)
+    }
+
+    /// The argument passing the latest version of the object `object_id` to a Move call.
+    async fn get_object_arg(&self, object_id: ObjectID) -> RpcResult<ObjectArg> {
+        let object = self.get_latest_object(object_id).await?;
+        Ok(if object.is_shared() {
+            ObjectArg::SharedObject(object_id)
+        } else {
+            ObjectArg::ImmOrOwnedObject(object.compute_object_reference())
+        })
+    }
+}
+
+/// The type of coins named by `coin_type`, SUI if None.
+fn coin_type_tag(coin_type: Option<String>) -> Result<TypeTag, anyhow::Error> {
+    match coin_type {
+        Some(coin_type) => parse_sui_type_tag(&coin_type),
+        None => Ok(GAS::type_tag()),
+    }
+}
+
+/// The balances of `coins` per coin type, sorted by coin type.
+fn balances(coins: Vec<SuiCoin>) -> Vec<SuiBalance> {
+    let mut balances: BTreeMap<String, SuiBalance> = BTreeMap::new();
+    for coin in coins {
+        let balance = balances
+            .entry(coin.coin_type.clone())
+            .or_insert_with(|| SuiBalance {
+                coin_type: coin.coin_type,
+                coin_object_count: 0,
+                total_balance: 0,
+            });
+        balance.coin_object_count += 1;
+        balance.total_balance += coin.balance;
+    }
+    balances.into_values().collect()
 }
 
 impl ReadApi {
     pub fn new(state: Arc<AuthorityState>) -> Self {
         Self { state }
     }
+
+    fn get_owner_objects_page(
+        &self,
+        owner: Owner,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
+        let limit = page_limit(limit, OWNED_OBJECTS_QUERY_MAX_LIMIT)?;
+        // One more object tells whether there is a next page
+        let objects = self
+            .state
+            .get_owner_objects_page(owner, cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(SuiObjectInfo::from)
+            .collect();
+        Ok(SuiObjectsPage::new(objects, None, cursor, limit)?)
+    }
 }
 
 #[async_trait]
@@ -56,27 +162,42 @@ impl RpcReadApiServer for ReadApi {
     async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
-            .state
-            .get_owner_objects(Owner::AddressOwner(address))
-            .map_err(|e| anyhow!("{e}"))?
-            .into_iter()
-            .map(SuiObjectInfo::from)
-            .collect())
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
+        self.get_owner_objects_page(Owner::AddressOwner(address), cursor, limit)
     }
 
     async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
-    ) -> RpcResult<Vec<SuiObjectInfo>> {
-        Ok(self
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
+        self.get_owner_objects_page(Owner::ObjectOwner(object_id.into()), cursor, limit)
+    }
+
+    async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiObjectsPage> {
+        let objects = self
             .state
-            .get_owner_objects(Owner::ObjectOwner(object_id.into()))
+            .get_owner_objects(Owner::AddressOwner(address))
             .map_err(|e| anyhow!("{e}"))?
             .into_iter()
             .map(SuiObjectInfo::from)
-            .collect())
+            .collect();
+        let limit = page_limit(limit, OWNED_OBJECTS_QUERY_MAX_LIMIT)?;
+        Ok(SuiObjectsPage::new(
+            objects,
+            object_type.as_deref(),
+            cursor,
+            limit,
+        )?)
     }
 
     async fn get_object(&self, object_id: ObjectID) -> RpcResult<GetObjectDataResponse> {
@@ -112,12 +233,7 @@ impl RpcReadApiServer for ReadApi {
         digest: TransactionDigest,
     ) -> RpcResult<SuiTransactionResponse> {
         let (cert, effects) = self.state.get_transaction(digest).await?;
-        Ok(SuiTransactionResponse {
-            certificate: cert.try_into()?,
-            effects: SuiTransactionEffects::try_from(effects, self.state.module_cache.as_ref())?,
-            timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
-            parsed_data: None,
-        })
+        transaction_response(&self.state, cert, effects).await
     }
 }
 
@@ -139,18 +255,76 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         sig_scheme: SignatureScheme,
         signature: Base64,
         pub_key: Base64,
+        sponsor_signature: Option<Base64>,
     ) -> RpcResult<SuiTransactionEffects> {
         let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
         let flag = vec![sig_scheme.flag()];
         let signature =
             Signature::from_bytes(&[&*flag, &*signature.to_vec()?, &pub_key.to_vec()?].concat())
                 .map_err(|e| anyhow!(e))?;
-        let txn = Transaction::new(data, signature);
+        let mut txn = Transaction::new(data, signature);
+        if let Some(sponsor_signature) = sponsor_signature {
+            let sponsor_signature =
+                Signature::from_bytes(&sponsor_signature.to_vec()?).map_err(|e| anyhow!(e))?;
+            txn = txn.with_sponsor_signature(sponsor_signature);
+        }
         let txn_digest = *txn.digest();
 
         Ok(self.state.dry_run_transaction(&txn, txn_digest).await?)
     }
 
+    async fn dev_inspect_move_call(
+        &self,
+        sender_address: SuiAddress,
+        package_object_id: ObjectID,
+        module: String,
+        function: String,
+        type_arguments: Vec<SuiTypeTag>,
+        arguments: Vec<SuiJsonValue>,
+    ) -> RpcResult<DevInspectResults> {
+        let module = Identifier::new(module)?;
+        let function = Identifier::new(function)?;
+        let package = self.get_latest_object(package_object_id).await?;
+        let json_args = resolve_public_function_args(
+            package
+                .data
+                .try_as_package()
+                .ok_or_else(|| anyhow!("Object {package_object_id} is not a package"))?,
+            module.clone(),
+            function.clone(),
+            arguments,
+        )?;
+        let mut args = Vec::with_capacity(json_args.len());
+        for json_arg in json_args {
+            args.push(match json_arg {
+                SuiJsonCallArg::Object(id) => CallArg::Object(self.get_object_arg(id).await?),
+                SuiJsonCallArg::Pure(bytes) => CallArg::Pure(bytes),
+                SuiJsonCallArg::ObjVec(ids) => {
+                    let mut object_args = Vec::with_capacity(ids.len());
+                    for id in ids {
+                        object_args.push(self.get_object_arg(id).await?);
+                    }
+                    CallArg::ObjVec(object_args)
+                }
+            })
+        }
+        let type_arguments = type_arguments
+            .into_iter()
+            .map(|arg| arg.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+        let move_call = MoveCall {
+            package: package.compute_object_reference(),
+            module,
+            function,
+            type_arguments,
+            arguments: args,
+        };
+        Ok(self
+            .state
+            .dev_inspect_move_call(sender_address, move_call)
+            .await?)
+    }
+
     async fn get_normalized_move_modules_by_package(
         &self,
         package: ObjectID,
@@ -207,6 +381,28 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         }?)
     }
 
+    async fn get_framework_module_docs(
+        &self,
+        package: ObjectID,
+        module_name: String,
+    ) -> RpcResult<SuiMoveModuleDocs> {
+        let identifier = Identifier::new(module_name.as_str()).map_err(|e| anyhow!("{e}"))?;
+        let module = ModuleId::new(package.into(), identifier);
+        Ok(match sui_framework::get_module_docs(&module) {
+            Some(docs) => Ok(SuiMoveModuleDocs {
+                package,
+                module: module_name,
+                framework_digest: Base64::from_bytes(&sui_framework::framework_digest()),
+                docs: docs.to_string(),
+            }),
+            None => Err(anyhow!(
+                "No framework module was found with name {}::{}",
+                package,
+                module_name
+            )),
+        }?)
+    }
+
     async fn get_move_function_arg_types(
         &self,
         package: ObjectID,
@@ -258,21 +454,52 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         }?)
     }
 
+    async fn get_transactions(
+        &self,
+        query: TransactionQuery,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
+            .state
+            .get_transactions(
+                query,
+                cursor,
+                limit + 1,
+                descending_order.unwrap_or_default(),
+            )
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
+    }
+
     async fn get_transactions_by_input_object(
         &self,
         object: ObjectID,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        Ok(self.state.get_transactions_by_input_object(object).await?)
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
+            .state
+            .get_transactions_by_input_object(object, cursor, limit + 1)
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
     }
 
     async fn get_transactions_by_mutated_object(
         &self,
         object: ObjectID,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        Ok(self
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
             .state
-            .get_transactions_by_mutated_object(object)
-            .await?)
+            .get_transactions_by_mutated_object(object, cursor, limit + 1)
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
     }
 
     async fn get_transactions_by_move_function(
@@ -280,25 +507,133 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         package: ObjectID,
         module: Option<String>,
         function: Option<String>,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        Ok(self
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
             .state
-            .get_transactions_by_move_function(package, module, function)
-            .await?)
+            .get_transactions_by_move_function(package, module, function, cursor, limit + 1)
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
     }
 
     async fn get_transactions_from_addr(
         &self,
         addr: SuiAddress,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        Ok(self.state.get_transactions_from_addr(addr).await?)
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
+            .state
+            .get_transactions_from_addr(addr, cursor, limit + 1)
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
     }
 
     async fn get_transactions_to_addr(
         &self,
         addr: SuiAddress,
-    ) -> RpcResult<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
-        Ok(self.state.get_transactions_to_addr(addr).await?)
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiTransactionsPage> {
+        let limit = page_limit(limit, TRANSACTIONS_QUERY_MAX_LIMIT)?;
+        let transactions = self
+            .state
+            .get_transactions_to_addr(addr, cursor, limit + 1)
+            .await?;
+        Ok(SuiTransactionsPage::new(transactions, limit))
+    }
+
+    async fn get_transfers_to_addr(
+        &self,
+        addr: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+    ) -> RpcResult<SuiCoinTransfersPage> {
+        let (transfers, next_cursor) = self
+            .state
+            .get_coin_transfers_to_addr(addr, cursor, TRANSFERS_QUERY_MAX_TRANSACTIONS)
+            .await?;
+        Ok(SuiCoinTransfersPage {
+            data: transfers
+                .into_iter()
+                .map(|(seq, transfer)| SuiCoinTransfer::new(seq, transfer))
+                .collect(),
+            next_cursor,
+        })
+    }
+
+    async fn get_object_version_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+    ) -> RpcResult<SuiObjectVersionHistoryPage> {
+        let (versions, next_cursor) = self
+            .state
+            .get_object_version_history(
+                object_id,
+                cursor,
+                OBJECT_VERSION_HISTORY_QUERY_MAX_VERSIONS,
+            )
+            .await?;
+        Ok(SuiObjectVersionHistoryPage {
+            data: versions
+                .into_iter()
+                .map(|(object_ref, transaction_digest, timestamp_ms)| {
+                    SuiObjectVersion::new(object_ref, transaction_digest, timestamp_ms)
+                })
+                .collect(),
+            next_cursor,
+        })
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<SuiBalance> {
+        let coin_type = coin_type_tag(coin_type)?;
+        let coins = self.get_owned_coins(owner, Some(&coin_type))?;
+        Ok(balances(coins).pop().unwrap_or_else(|| SuiBalance {
+            coin_type: coin_type.to_string(),
+            coin_object_count: 0,
+            total_balance: 0,
+        }))
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<SuiBalance>> {
+        Ok(balances(self.get_owned_coins(owner, None)?))
+    }
+
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<SuiCoinsPage> {
+        let coin_type = coin_type_tag(coin_type)?;
+        let limit = page_limit(limit, COINS_QUERY_MAX_LIMIT)?;
+        // One more coin tells whether there is a next page
+        let coins = self
+            .get_owned_coins(owner, Some(&coin_type))?
+            .into_iter()
+            .filter(|coin| cursor.map_or(true, |cursor| coin.coin_object_id >= cursor))
+            .take(limit + 1)
+            .collect();
+        Ok(SuiCoinsPage::new(coins, limit))
+    }
+
+    async fn get_supply(&self) -> RpcResult<SuiSupply> {
+        Ok(self.state.get_supply().await?.into())
+    }
+
+    async fn get_epoch_fee_summary(&self, epoch: EpochId) -> RpcResult<SuiEpochFeeSummary> {
+        Ok(match self.state.get_epoch_fee_summary(epoch).await? {
+            Some(summary) => Ok(summary.into()),
+            None => Err(anyhow!("No transaction of epoch {epoch} has been executed")),
+        }?)
     }
 
     async fn try_get_past_object(
@@ -313,6 +648,77 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
             .map_err(|e| anyhow!("{e}"))?
             .try_into()?)
     }
+
+    async fn get_finality_proof(&self, digest: TransactionDigest) -> RpcResult<SuiFinalityProof> {
+        Ok(self.state.get_finality_proof(digest).await?.into())
+    }
+
+    async fn get_checkpoint_state_diff(
+        &self,
+        from: CheckpointSequenceNumber,
+        to: CheckpointSequenceNumber,
+        with_contents: Option<bool>,
+    ) -> RpcResult<SuiCheckpointStateDiff> {
+        let to = std::cmp::min(to, from.saturating_add(CHECKPOINT_STATE_DIFF_MAX_RANGE - 1));
+        let diff = self.state.get_checkpoint_state_diff(from, to)?;
+        let objects = if with_contents.unwrap_or(false) {
+            let mut objects: Vec<GetPastObjectDataResponse> = vec![];
+            for (object_id, version, _) in diff.created.iter().chain(diff.mutated.iter()) {
+                objects.push(
+                    self.state
+                        .get_past_object_read(object_id, *version)
+                        .await
+                        .map_err(|e| anyhow!("{e}"))?
+                        .try_into()?,
+                );
+            }
+            Some(objects)
+        } else {
+            None
+        };
+        Ok(SuiCheckpointStateDiff::new(from, to, diff, objects))
+    }
+
+    async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout_ms: Option<u64>,
+    ) -> RpcResult<SuiTransactionResponse> {
+        let timeout_ms = timeout_ms
+            .unwrap_or(WAIT_FOR_TRANSACTION_MAX_TIMEOUT_MS)
+            .min(WAIT_FOR_TRANSACTION_MAX_TIMEOUT_MS);
+        let (cert, effects) = self
+            .state
+            .wait_for_transaction(digest, Duration::from_millis(timeout_ms))
+            .await?;
+        transaction_response(&self.state, cert, effects).await
+    }
+}
+
+/// The size of a page of transactions, at most TRANSACTIONS_QUERY_MAX_LIMIT.
+/// The size of a page of at most `max` items, `max` if not given. Empty pages are rejected, as
+/// their cursor would never advance.
+fn page_limit(limit: Option<usize>, max: usize) -> RpcResult<usize> {
+    match limit {
+        Some(0) => Err(CallError::InvalidParams(anyhow!("limit must be greater than 0")).into()),
+        limit => Ok(limit.unwrap_or(max).min(max)),
+    }
+}
+
+async fn transaction_response(
+    state: &AuthorityState,
+    cert: CertifiedTransaction,
+    effects: TransactionEffects,
+) -> RpcResult<SuiTransactionResponse> {
+    let digest = *cert.digest();
+    let effects = SuiTransactionEffects::try_from(effects, state.module_cache.as_ref())?;
+    Ok(SuiTransactionResponse {
+        certificate: cert.try_into()?,
+        memo: effects.memo(),
+        effects,
+        timestamp_ms: state.get_timestamp_ms(&digest).await?,
+        parsed_data: None,
+    })
 }
 
 impl SuiRpcModule for FullNodeApi {
@@ -0,0 +1,413 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rate limits, method allow and deny lists and request timeouts of the HTTP JSON-RPC server.
+//!
+//! The middleware of jsonrpsee only observes the requests, so the policy is enforced by a front
+//! server listening in place of the JSON-RPC server: it answers the calls the policy refuses
+//! itself, and forwards the others to the JSON-RPC server, which then listens on a local port.
+//! The policy of a running front server is replaced through its [`RpcPolicyHandle`].
+
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderValue, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::http::request::Parts;
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Method, Request, Response, Server, StatusCode, Uri};
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+/// The header the front server passes the IP address of the client in, replacing the one sent by
+/// the client if any.
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Error code of the calls refused by a rate limit.
+pub const RATE_LIMITED_CODE: i64 = -32005;
+/// Error code of the calls to a method the policy doesn't allow.
+pub const METHOD_NOT_ALLOWED_CODE: i64 = -32004;
+/// Error code of the calls not answered within the request timeout.
+pub const TIMED_OUT_CODE: i64 = -32003;
+
+/// Once this many clients are tracked, the buckets of the clients that filled up again are dropped
+/// when a new client calls, so that the memory of the limits stays bounded.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// A token bucket refilled with `requests_per_second` tokens a second, up to `burst` tokens. Each
+/// call takes a token, so a batch takes one per request in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests_per_second: u32,
+    pub burst: u32,
+}
+
+/// What the HTTP JSON-RPC server answers, and how fast.
+#[derive(Clone, Debug, Default)]
+pub struct RpcPolicy {
+    /// Limit of the calls of each client IP address.
+    pub per_ip: Option<RateLimit>,
+    /// Limits of the calls to methods, from all the clients together.
+    pub per_method: HashMap<String, RateLimit>,
+    /// The only methods answered, or all of them if unset.
+    pub allowed_methods: Option<HashSet<String>>,
+    pub denied_methods: HashSet<String>,
+    /// Time after which the calls of a request are answered with an error. The server doesn't
+    /// cancel them, so they still run to completion.
+    pub request_timeout: Option<Duration>,
+}
+
+impl RpcPolicy {
+    /// Whether the policy lets every call through, in which case no front server is needed.
+    pub fn is_empty(&self) -> bool {
+        self.per_ip.is_none()
+            && self.per_method.is_empty()
+            && self.allowed_methods.is_none()
+            && self.denied_methods.is_empty()
+            && self.request_timeout.is_none()
+    }
+}
+
+/// Why a call is answered with an error by the front server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolicyError {
+    MethodNotAllowed,
+    RateLimited,
+    TimedOut,
+}
+
+impl PolicyError {
+    fn to_response(self, id: &Value, method: &str) -> Value {
+        let (code, message) = match self {
+            PolicyError::MethodNotAllowed => (
+                METHOD_NOT_ALLOWED_CODE,
+                format!("Method {method} is not allowed on this server"),
+            ),
+            PolicyError::RateLimited => (
+                RATE_LIMITED_CODE,
+                format!("Too many calls to {method}, retry later"),
+            ),
+            PolicyError::TimedOut => (TIMED_OUT_CODE, format!("Call to {method} timed out")),
+        };
+        json!({
+            "jsonrpc": "2.0",
+            "error": { "code": code, "message": message },
+            "id": id,
+        })
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * limit.requests_per_second as f64)
+            .min(limit.burst as f64);
+        self.refilled_at = now;
+    }
+
+    fn try_take(&mut self, limit: &RateLimit, now: Instant) -> bool {
+        self.refill(limit, now);
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    fn is_full(&self, limit: &RateLimit) -> bool {
+        self.tokens >= limit.burst as f64
+    }
+}
+
+/// Decides which calls the server answers, tracking the tokens left to the clients and methods.
+pub struct PolicyEnforcer {
+    policy: RwLock<RpcPolicy>,
+    ip_buckets: Mutex<HashMap<IpAddr, TokenBucket>>,
+    method_buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl PolicyEnforcer {
+    pub fn new(policy: RpcPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            ip_buckets: Mutex::new(HashMap::new()),
+            method_buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the policy, refilling the buckets of all the clients and methods.
+    pub fn set_policy(&self, policy: RpcPolicy) {
+        let mut current = self.policy.write().unwrap();
+        self.ip_buckets.lock().unwrap().clear();
+        self.method_buckets.lock().unwrap().clear();
+        *current = policy;
+    }
+
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.policy.read().unwrap().request_timeout
+    }
+
+    /// Checks a call to `method` from the client at `ip`, taking a token from the buckets the
+    /// call is limited by.
+    pub fn check(&self, ip: IpAddr, method: &str, now: Instant) -> Result<(), PolicyError> {
+        let policy = self.policy.read().unwrap();
+        let allowed = match &policy.allowed_methods {
+            Some(allowed) => allowed.contains(method),
+            None => true,
+        };
+        if !allowed || policy.denied_methods.contains(method) {
+            return Err(PolicyError::MethodNotAllowed);
+        }
+
+        if let Some(limit) = &policy.per_ip {
+            let mut buckets = self.ip_buckets.lock().unwrap();
+            if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(&ip) {
+                buckets.retain(|_, bucket| {
+                    bucket.refill(limit, now);
+                    !bucket.is_full(limit)
+                });
+            }
+            let bucket = buckets
+                .entry(ip)
+                .or_insert_with(|| TokenBucket::full(limit, now));
+            if !bucket.try_take(limit, now) {
+                return Err(PolicyError::RateLimited);
+            }
+        }
+
+        if let Some(limit) = policy.per_method.get(method) {
+            let mut buckets = self.method_buckets.lock().unwrap();
+            let bucket = buckets
+                .entry(method.to_string())
+                .or_insert_with(|| TokenBucket::full(limit, now));
+            if !bucket.try_take(limit, now) {
+                return Err(PolicyError::RateLimited);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the policy of a running front server, e.g. when the config of the node is reloaded.
+/// The front server only runs if the server started with a policy, see [`RpcPolicy::is_empty`].
+#[derive(Clone)]
+pub struct RpcPolicyHandle(Arc<PolicyEnforcer>);
+
+impl RpcPolicyHandle {
+    pub fn update(&self, policy: RpcPolicy) {
+        self.0.set_policy(policy)
+    }
+}
+
+/// Starts the front server enforcing `policy` on `listen_address`, in front of the JSON-RPC server
+/// at `upstream`, and returns the address it listens on with the handle of the policy.
+pub(crate) fn serve(
+    listen_address: SocketAddr,
+    upstream: SocketAddr,
+    policy: RpcPolicy,
+    max_request_body_size: u32,
+) -> anyhow::Result<(SocketAddr, RpcPolicyHandle)> {
+    let enforcer = Arc::new(PolicyEnforcer::new(policy));
+    let front = Arc::new(FrontServer {
+        enforcer: enforcer.clone(),
+        client: Client::new(),
+        upstream,
+        max_request_body_size: max_request_body_size as usize,
+    });
+    let make_service = make_service_fn(move |conn: &AddrStream| {
+        let front = front.clone();
+        let client_ip = conn.remote_addr().ip();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                front.clone().handle(request, client_ip)
+            }))
+        }
+    });
+    let server = Server::try_bind(&listen_address)?.serve(make_service);
+    let local_addr = server.local_addr();
+    tokio::spawn(async move {
+        if let Err(error) = server.await {
+            warn!("JSON-RPC policy server stopped: {error}");
+        }
+    });
+    Ok((local_addr, RpcPolicyHandle(enforcer)))
+}
+
+struct FrontServer {
+    enforcer: Arc<PolicyEnforcer>,
+    client: Client<HttpConnector>,
+    upstream: SocketAddr,
+    max_request_body_size: usize,
+}
+
+impl FrontServer {
+    async fn handle(
+        self: Arc<Self>,
+        request: Request<Body>,
+        client_ip: IpAddr,
+    ) -> Result<Response<Body>, hyper::Error> {
+        let (parts, body) = request.into_parts();
+        if parts.method != Method::POST {
+            // CORS preflights, left to the JSON-RPC server
+            return Ok(self
+                .forward(parts, body, client_ip)
+                .await?
+                .unwrap_or_else(|| status_response(StatusCode::GATEWAY_TIMEOUT)));
+        }
+
+        let body = match read_body(body, self.max_request_body_size).await? {
+            Some(body) => body,
+            None => return Ok(status_response(StatusCode::PAYLOAD_TOO_LARGE)),
+        };
+        let (calls, batch) = match serde_json::from_slice(&body) {
+            Ok(Value::Array(calls)) => (calls, true),
+            Ok(call @ Value::Object(_)) => (vec![call], false),
+            // The JSON-RPC server answers invalid requests with the appropriate error
+            _ => {
+                return Ok(self
+                    .forward(parts, Body::from(body), client_ip)
+                    .await?
+                    .unwrap_or_else(|| status_response(StatusCode::GATEWAY_TIMEOUT)))
+            }
+        };
+
+        let now = Instant::now();
+        let call_count = calls.len();
+        let mut accepted = vec![];
+        let mut refused = vec![];
+        for call in calls {
+            let method = call
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            match self.enforcer.check(client_ip, method, now) {
+                Ok(()) => accepted.push(call),
+                Err(error) => {
+                    debug!(%client_ip, method, ?error, "Refused JSON-RPC call");
+                    // Notifications, without an id, are not answered
+                    if let Some(id) = call.get("id") {
+                        refused.push(error.to_response(id, method));
+                    }
+                }
+            }
+        }
+
+        if accepted.is_empty() {
+            return Ok(calls_response(refused, batch));
+        }
+        let body = if accepted.len() == call_count {
+            Body::from(body)
+        } else {
+            Body::from(Value::Array(accepted.clone()).to_string())
+        };
+        let response = match self.forward(parts, body, client_ip).await? {
+            Some(response) => response,
+            None => {
+                let timed_out = accepted.iter().filter_map(|call| {
+                    let method = call.get("method").and_then(Value::as_str)?;
+                    let id = call.get("id")?;
+                    Some(PolicyError::TimedOut.to_response(id, method))
+                });
+                refused.extend(timed_out);
+                return Ok(calls_response(refused, batch));
+            }
+        };
+        if refused.is_empty() {
+            return Ok(response);
+        }
+
+        // Some calls of the batch were refused, their errors are added to the responses of the
+        // others
+        let (mut parts, body) = response.into_parts();
+        let mut responses = match serde_json::from_slice(&hyper::body::to_bytes(body).await?) {
+            Ok(Value::Array(responses)) => responses,
+            _ => vec![],
+        };
+        responses.extend(refused);
+        parts.headers.remove(CONTENT_LENGTH);
+        Ok(Response::from_parts(
+            parts,
+            Body::from(Value::Array(responses).to_string()),
+        ))
+    }
+
+    /// Forwards a request to the JSON-RPC server, returning `None` if it doesn't answer within the
+    /// request timeout.
+    async fn forward(
+        &self,
+        mut parts: Parts,
+        body: Body,
+        client_ip: IpAddr,
+    ) -> Result<Option<Response<Body>>, hyper::Error> {
+        let path = parts.uri.path_and_query().map_or("/", |path| path.as_str());
+        parts.uri = format!("http://{}{}", self.upstream, path)
+            .parse::<Uri>()
+            .expect("The path of a valid URI stays valid");
+        parts.headers.insert(
+            FORWARDED_FOR_HEADER,
+            HeaderValue::from_str(&client_ip.to_string())
+                .expect("IP addresses are valid header values"),
+        );
+        let response = self.client.request(Request::from_parts(parts, body));
+        match self.enforcer.request_timeout() {
+            Some(timeout) => match tokio::time::timeout(timeout, response).await {
+                Ok(response) => response.map(Some),
+                Err(_) => Ok(None),
+            },
+            None => response.await.map(Some),
+        }
+    }
+}
+
+/// Reads a request body, or returns `None` if it is larger than `max_size` bytes.
+async fn read_body(mut body: Body, max_size: usize) -> Result<Option<Vec<u8>>, hyper::Error> {
+    let mut bytes = vec![];
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > max_size {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Some(bytes))
+}
+
+fn calls_response(mut responses: Vec<Value>, batch: bool) -> Response<Body> {
+    let body = if batch {
+        Value::Array(responses).to_string()
+    } else {
+        // A single call, or nothing if it was a notification
+        responses
+            .pop()
+            .map(|response| response.to_string())
+            .unwrap_or_default()
+    };
+    Response::builder()
+        .header(CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(Body::from(body))
+        .expect("The response is valid")
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("The response is valid")
+}
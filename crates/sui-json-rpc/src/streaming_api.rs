@@ -58,6 +58,7 @@ impl TransactionStreamingApiServer for TransactionStreamingApiImpl {
                 let ts = state_clone.get_timestamp_ms(&digest).await.unwrap_or(None);
                 Ok::<SuiTransactionResponse, anyhow::Error>(SuiTransactionResponse {
                     certificate: sui_tx_cert,
+                    memo: sui_tx_effects.memo(),
                     effects: sui_tx_effects,
                     timestamp_ms: ts,
                     parsed_data: None,
@@ -3,9 +3,12 @@
 
 use anyhow::{anyhow, bail};
 use move_binary_format::{
-    access::ModuleAccess, binary_views::BinaryIndexedView, file_format::SignatureToken,
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{SignatureToken, Visibility},
 };
 use move_core_types::account_address::AccountAddress;
+use move_core_types::value::{MoveStruct, MoveStructLayout};
 use move_core_types::{
     identifier::Identifier,
     value::{MoveTypeLayout, MoveValue},
@@ -17,7 +20,8 @@ use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use sui_types::base_types::{decode_bytes_hex, ObjectID, SuiAddress};
 use sui_types::move_package::MovePackage;
-use sui_verifier::entry_points_verifier::is_tx_context;
+use sui_verifier::entry_points_verifier::{is_tx_context, RESOLVED_STD_OPTION};
+use sui_verifier::{format_signature_token, resolve_struct};
 
 const HEX_PREFIX: &str = "0x";
 
@@ -39,8 +43,8 @@ pub struct SuiJsonValue(JsonValue);
 impl SuiJsonValue {
     pub fn new(json_value: JsonValue) -> Result<SuiJsonValue, anyhow::Error> {
         match json_value.clone() {
-            // No checks needed for Bool and String
-            JsonValue::Bool(_) | JsonValue::String(_) => (),
+            // No checks needed for Bool, String and Null, which stands for an empty option
+            JsonValue::Bool(_) | JsonValue::String(_) | JsonValue::Null => (),
             JsonValue::Number(n) => {
                 // Must be castable to u64
                 if !n.is_u64() {
@@ -84,23 +88,29 @@ impl SuiJsonValue {
 
             // In constructor, we have already checked that the JSON number is unsigned int of at most U64
             // Hence it is okay to unwrap() numbers
-            (JsonValue::Number(n), MoveTypeLayout::U8) => {
-                MoveValue::U8(u8::try_from(n.as_u64().unwrap())?)
-            }
+            (JsonValue::Number(n), MoveTypeLayout::U8) => MoveValue::U8(
+                u8::try_from(n.as_u64().unwrap())
+                    .map_err(|_| anyhow!("{n} is out of range for u8"))?,
+            ),
             (JsonValue::Number(n), MoveTypeLayout::U64) => MoveValue::U64(n.as_u64().unwrap()),
+            (JsonValue::Number(n), MoveTypeLayout::U128) => {
+                MoveValue::U128(n.as_u64().unwrap() as u128)
+            }
 
             // u8, u64, u128 can be encoded as String
-            (JsonValue::String(s), MoveTypeLayout::U8) => {
-                MoveValue::U8(u8::try_from(convert_string_to_u128(s.as_str())?)?)
-            }
-            (JsonValue::String(s), MoveTypeLayout::U64) => {
-                MoveValue::U64(u64::try_from(convert_string_to_u128(s.as_str())?)?)
-            }
+            (JsonValue::String(s), MoveTypeLayout::U8) => MoveValue::U8(
+                u8::try_from(convert_string_to_u128(s.as_str())?)
+                    .map_err(|_| anyhow!("{s} is out of range for u8"))?,
+            ),
+            (JsonValue::String(s), MoveTypeLayout::U64) => MoveValue::U64(
+                u64::try_from(convert_string_to_u128(s.as_str())?)
+                    .map_err(|_| anyhow!("{s} is out of range for u64"))?,
+            ),
             (JsonValue::String(s), MoveTypeLayout::U128) => {
                 MoveValue::U128(convert_string_to_u128(s.as_str())?)
             }
 
-            // U256 Not allowed for now
+            // U256 Not allowed for now, the Move VM doesn't support it yet
 
             // We can encode U8 Vector as string in 2 ways
             // 1. If it starts with 0x, we treat it as hex strings, where each pair is a byte
@@ -126,11 +136,35 @@ impl SuiJsonValue {
                 // Recursively build an IntermediateValue array
                 MoveValue::Vector(
                     a.iter()
-                        .map(|i| Self::to_move_value(i, inner))
+                        .enumerate()
+                        .map(|(i, v)| {
+                            Self::to_move_value(v, inner)
+                                .map_err(|e| anyhow!("Invalid element {i} of {val}: {e}"))
+                        })
                         .collect::<Result<Vec<_>, _>>()?,
                 )
             }
 
+            // The only structs passed by value are options, laid out as a vector of at most one
+            // element, which is how they are written too, besides null for an empty option
+            (JsonValue::Null, MoveTypeLayout::Struct(MoveStructLayout::Runtime(fields)))
+                if is_option_layout(fields) =>
+            {
+                MoveValue::Struct(MoveStruct::Runtime(vec![MoveValue::Vector(vec![])]))
+            }
+            (JsonValue::Array(a), MoveTypeLayout::Struct(MoveStructLayout::Runtime(fields)))
+                if is_option_layout(fields) =>
+            {
+                if a.len() > 1 {
+                    return Err(anyhow!(
+                        "Expected an option, written as null, [] or [value], got {val}"
+                    ));
+                }
+                MoveValue::Struct(MoveStruct::Runtime(vec![Self::to_move_value(
+                    val, &fields[0],
+                )?]))
+            }
+
             (JsonValue::String(s), MoveTypeLayout::Address) => {
                 let s = s.trim().to_lowercase();
                 if !s.starts_with(HEX_PREFIX) {
@@ -223,6 +257,8 @@ fn is_homogeneous_rec(curr_q: &mut VecDeque<&JsonValue>) -> bool {
     while !curr_q.is_empty() {
         // Okay to unwrap since we know values exist
         let curr = match curr_q.pop_front().unwrap() {
+            // An empty option can stand for a value of any type
+            JsonValue::Null => continue,
             JsonValue::Bool(_) => ValidJsonType::Bool,
             JsonValue::Number(_) => ValidJsonType::Number,
             JsonValue::String(_) => ValidJsonType::String,
@@ -247,23 +283,57 @@ fn is_homogeneous_rec(curr_q: &mut VecDeque<&JsonValue>) -> bool {
     is_homogeneous_rec(&mut next_q)
 }
 
+fn is_option_layout(fields: &[MoveTypeLayout]) -> bool {
+    matches!(fields, [MoveTypeLayout::Vector(_)])
+}
+
 fn resolve_primtive_arg(
+    view: &BinaryIndexedView,
+    idx: usize,
     arg: &SuiJsonValue,
     param: &SignatureToken,
 ) -> Result<Vec<u8>, anyhow::Error> {
-    let move_type_layout = make_prim_move_type_layout(param)?;
+    let move_type_layout = make_prim_move_type_layout(view, param)?;
     // Check that the args are what we expect or can be converted
     // Then return the serialized bcs value
     arg.to_bcs_bytes(&move_type_layout).map_err(|e| {
         anyhow!(
-            "Unable to parse arg at type {}. Got error: {:?}",
-            move_type_layout,
+            "Unable to parse arg {:?} at pos {} as {}. Got error: {}",
+            arg,
+            idx,
+            format_signature_token(view, param),
             e
         )
     })
 }
 
-fn make_prim_move_type_layout(param: &SignatureToken) -> Result<MoveTypeLayout, anyhow::Error> {
+/// Whether values of type `param` are passed as pure bytes rather than objects: primitives, and
+/// vectors and options of them.
+fn is_primitive(view: &BinaryIndexedView, param: &SignatureToken) -> bool {
+    match param {
+        SignatureToken::Bool
+        | SignatureToken::U8
+        | SignatureToken::U64
+        | SignatureToken::U128
+        | SignatureToken::Address => true,
+        SignatureToken::Vector(inner) => is_primitive(view, inner),
+        SignatureToken::StructInstantiation(idx, targs) => {
+            resolve_struct(view, *idx) == RESOLVED_STD_OPTION
+                && targs.len() == 1
+                && is_primitive(view, &targs[0])
+        }
+        SignatureToken::Signer
+        | SignatureToken::Struct(_)
+        | SignatureToken::Reference(_)
+        | SignatureToken::MutableReference(_)
+        | SignatureToken::TypeParameter(_) => false,
+    }
+}
+
+fn make_prim_move_type_layout(
+    view: &BinaryIndexedView,
+    param: &SignatureToken,
+) -> Result<MoveTypeLayout, anyhow::Error> {
     Ok(match param {
         SignatureToken::Bool => MoveTypeLayout::Bool,
         SignatureToken::U8 => MoveTypeLayout::U8,
@@ -272,18 +342,26 @@ fn make_prim_move_type_layout(param: &SignatureToken) -> Result<MoveTypeLayout,
         SignatureToken::Address => MoveTypeLayout::Address,
         SignatureToken::Signer => MoveTypeLayout::Signer,
         SignatureToken::Vector(inner) => {
-            MoveTypeLayout::Vector(Box::new(make_prim_move_type_layout(inner)?))
+            MoveTypeLayout::Vector(Box::new(make_prim_move_type_layout(view, inner)?))
+        }
+        // An option is a struct with a vector of at most one element
+        SignatureToken::StructInstantiation(idx, targs)
+            if resolve_struct(view, *idx) == RESOLVED_STD_OPTION && targs.len() == 1 =>
+        {
+            MoveTypeLayout::Struct(MoveStructLayout::new(vec![MoveTypeLayout::Vector(
+                Box::new(make_prim_move_type_layout(view, &targs[0])?),
+            )]))
         }
         SignatureToken::Struct(_)
         | SignatureToken::StructInstantiation(_, _)
         | SignatureToken::Reference(_)
         | SignatureToken::MutableReference(_)
         | SignatureToken::TypeParameter(_) => {
-            debug_assert!(
-                false,
-                "Should be unreachable. Args should be primitive types only"
-            );
-            bail!("Could not serialize argument of type {:?}", param)
+            bail!(
+                "Could not serialize argument of type {}, only primitives, and vectors and \
+                 options of them, are passed by value",
+                format_signature_token(view, param)
+            )
         }
     })
 }
@@ -329,17 +407,17 @@ fn resolve_object_vec_arg(idx: usize, arg: &SuiJsonValue) -> Result<Vec<ObjectID
 }
 
 fn resolve_call_arg(
+    view: &BinaryIndexedView,
     idx: usize,
     arg: &SuiJsonValue,
     param: &SignatureToken,
 ) -> Result<SuiJsonCallArg, anyhow::Error> {
+    if is_primitive(view, param) {
+        return Ok(SuiJsonCallArg::Pure(resolve_primtive_arg(
+            view, idx, arg, param,
+        )?));
+    }
     Ok(match param {
-        SignatureToken::Bool
-        | SignatureToken::U8
-        | SignatureToken::U64
-        | SignatureToken::U128
-        | SignatureToken::Address => SuiJsonCallArg::Pure(resolve_primtive_arg(arg, param)?),
-
         SignatureToken::Struct(_)
         | SignatureToken::StructInstantiation(_, _)
         | SignatureToken::TypeParameter(_)
@@ -348,23 +426,32 @@ fn resolve_call_arg(
             SuiJsonCallArg::Object(resolve_object_arg(idx, &arg.to_json_value())?)
         }
 
-        SignatureToken::Vector(inner) => {
-            match **inner {
-                // in terms of non-primitive vectors we only currently support vectors of objects
-                // (but not, for example, vectors of references), so vector content are either
-                // objects of we assume that they are primitive (and throw an error if not)
-                SignatureToken::Struct(_) => {
-                    SuiJsonCallArg::ObjVec(resolve_object_vec_arg(idx, arg)?)
-                }
-                _ => SuiJsonCallArg::Pure(resolve_primtive_arg(arg, param)?),
+        SignatureToken::Vector(inner) => match **inner {
+            // in terms of non-primitive vectors we only currently support vectors of objects
+            // (but not, for example, vectors of references or of vectors of objects)
+            SignatureToken::Struct(_)
+            | SignatureToken::StructInstantiation(_, _)
+            | SignatureToken::TypeParameter(_) => {
+                SuiJsonCallArg::ObjVec(resolve_object_vec_arg(idx, arg)?)
             }
-        }
+            _ => bail!(
+                "Unable to parse arg at pos {}, arguments of type {} are not supported",
+                idx,
+                format_signature_token(view, param)
+            ),
+        },
 
-        SignatureToken::Signer => unreachable!(),
+        SignatureToken::Bool
+        | SignatureToken::U8
+        | SignatureToken::U64
+        | SignatureToken::U128
+        | SignatureToken::Address
+        | SignatureToken::Signer => unreachable!(),
     })
 }
 
 fn resolve_call_args(
+    view: &BinaryIndexedView,
     json_args: &[SuiJsonValue],
     parameter_types: &[SignatureToken],
 ) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
@@ -372,7 +459,7 @@ fn resolve_call_args(
         .iter()
         .zip(parameter_types)
         .enumerate()
-        .map(|(idx, (arg, param))| resolve_call_arg(idx, arg, param))
+        .map(|(idx, (arg, param))| resolve_call_arg(view, idx, arg, param))
         .collect()
 }
 
@@ -383,6 +470,27 @@ pub fn resolve_move_function_args(
     module_ident: Identifier,
     function: Identifier,
     combined_args_json: Vec<SuiJsonValue>,
+) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
+    resolve_function_args(package, module_ident, function, combined_args_json, false)
+}
+
+/// Like `resolve_move_function_args`, for a `public` function as well as an `entry` one, whose
+/// call is only inspected rather than executed in a transaction.
+pub fn resolve_public_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    combined_args_json: Vec<SuiJsonValue>,
+) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
+    resolve_function_args(package, module_ident, function, combined_args_json, true)
+}
+
+fn resolve_function_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    combined_args_json: Vec<SuiJsonValue>,
+    allow_public: bool,
 ) -> Result<Vec<SuiJsonCallArg>, anyhow::Error> {
     // Extract the expected function signature
     let module = package.deserialize_module(&module_ident)?;
@@ -403,7 +511,7 @@ pub fn resolve_move_function_args(
     let function_signature = module.function_handle_at(fdef.function);
     let parameters = &module.signature_at(function_signature.parameters).0;
 
-    if !fdef.is_entry {
+    if !fdef.is_entry && !(allow_public && fdef.visibility == Visibility::Public) {
         bail!(
             "{}::{} does not have public(script) visibility",
             module.self_id(),
@@ -427,7 +535,11 @@ pub fn resolve_move_function_args(
     }
 
     // Check that the args are valid and convert to the correct format
-    resolve_call_args(&combined_args_json, parameters)
+    resolve_call_args(
+        &BinaryIndexedView::Module(&module),
+        &combined_args_json,
+        parameters,
+    )
 }
 
 fn convert_string_to_u128(s: &str) -> Result<u128, anyhow::Error> {
@@ -5,7 +5,9 @@ use std::path::Path;
 use std::str::FromStr;
 
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, value::MoveTypeLayout,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    value::{MoveStructLayout, MoveTypeLayout},
 };
 use serde_json::{json, Value};
 use test_fuzz::runtime::num_traits::ToPrimitive;
@@ -14,7 +16,9 @@ use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::object::Object;
 
 use super::{is_homogeneous, HEX_PREFIX};
-use super::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
+use super::{
+    resolve_move_function_args, resolve_public_function_args, SuiJsonCallArg, SuiJsonValue,
+};
 
 #[test]
 fn test_json_is_homogeneous() {
@@ -264,6 +268,57 @@ fn test_basic_args_linter_pure_args() {
                 .unwrap(),
             ),
         ),
+        // U128 value encoded as number
+        (
+            Value::from(9u64),
+            MoveTypeLayout::U128,
+            Some(bcs::to_bytes(&9u128).unwrap()),
+        ),
+        // Empty option as null
+        (
+            Value::Null,
+            option_layout(MoveTypeLayout::U64),
+            Some(bcs::to_bytes(&None::<u64>).unwrap()),
+        ),
+        // Options as vectors of at most one element
+        (
+            json!([]),
+            option_layout(MoveTypeLayout::U64),
+            Some(bcs::to_bytes(&None::<u64>).unwrap()),
+        ),
+        (
+            json!(["890"]),
+            option_layout(MoveTypeLayout::U64),
+            Some(bcs::to_bytes(&Some(890u64)).unwrap()),
+        ),
+        (
+            json!([1, 2]),
+            option_layout(MoveTypeLayout::U64),
+            None,
+        ),
+        // Nested options
+        (
+            json!([[]]),
+            option_layout(option_layout(MoveTypeLayout::U64)),
+            Some(bcs::to_bytes(&Some(None::<u64>)).unwrap()),
+        ),
+        (
+            json!([[7]]),
+            option_layout(option_layout(MoveTypeLayout::U64)),
+            Some(bcs::to_bytes(&Some(Some(7u64))).unwrap()),
+        ),
+        // Vector of options
+        (
+            json!([null, [4], []]),
+            MoveTypeLayout::Vector(Box::new(option_layout(MoveTypeLayout::U8))),
+            Some(bcs::to_bytes(&vec![None, Some(4u8), None]).unwrap()),
+        ),
+        // Option of a vector of bytes encoded as str
+        (
+            json!(["0xabcd"]),
+            option_layout(MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8))),
+            Some(bcs::to_bytes(&Some(vec![0xabu8, 0xcd])).unwrap()),
+        ),
     ];
 
     // Driver
@@ -286,6 +341,30 @@ fn test_basic_args_linter_pure_args() {
     }
 }
 
+fn option_layout(inner: MoveTypeLayout) -> MoveTypeLayout {
+    MoveTypeLayout::Struct(MoveStructLayout::new(vec![MoveTypeLayout::Vector(
+        Box::new(inner),
+    )]))
+}
+
+#[test]
+fn test_nested_arg_error_names_element() {
+    let layout = MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Vector(Box::new(
+        MoveTypeLayout::U8,
+    ))));
+    let err = SuiJsonValue::new(json!([[1, 2], [3, 300]]))
+        .unwrap()
+        .to_bcs_bytes(&layout)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Invalid element 1 of [[1,2],[3,300]]"),
+        "{}",
+        err
+    );
+    assert!(err.contains("300 is out of range for u8"), "{}", err);
+}
+
 #[test]
 fn test_basic_args_linter_top_level() {
     let path =
@@ -516,6 +595,35 @@ fn test_basic_args_linter_top_level() {
     }
 }
 
+#[test]
+fn test_basic_args_linter_public_function() {
+    let framework = Object::new_package(
+        sui_framework::get_sui_framework(),
+        TransactionDigest::genesis(),
+    );
+    let framework_pkg = framework.data.try_as_package().unwrap();
+    let module = Identifier::new("coin").unwrap();
+    let function = Identifier::new("value").unwrap();
+
+    /*
+    Function signature:
+            public fun value<T>(self: &Coin<T>): u64
+    */
+    let coin_id = ObjectID::random();
+    let args: Vec<_> = vec![SuiJsonValue::new(json!(format!("0x{:02x}", coin_id))).unwrap()];
+
+    // Only the calls of public functions which are inspected take their arguments
+    assert!(resolve_move_function_args(
+        framework_pkg,
+        module.clone(),
+        function.clone(),
+        args.clone()
+    )
+    .is_err());
+    let args = resolve_public_function_args(framework_pkg, module, function, args).unwrap();
+    assert_eq!(args, vec![SuiJsonCallArg::Object(coin_id)]);
+}
+
 #[test]
 fn test_convert_address_from_bcs() {
     let bcs_bytes = [
@@ -28,8 +28,7 @@ pub fn init_static_initializers(_args: TokenStream, item: TokenStream) -> TokenS
             // iteration of a multi-iteration test run.
             std::thread::spawn(|| {
                 ::telemetry_subscribers::init_for_testing();
-                ::sui_framework::get_move_stdlib();
-                ::sui_framework::get_sui_framework();
+                ::sui_framework::get_system_packages();
             }).join().unwrap();
 
             #body
@@ -1,11 +1,93 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, path::PathBuf};
+use std::{env, fs, path::PathBuf};
 use tonic_build::manual::{Builder, Method, Service};
 
 type Result<T> = ::std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
 
+/// The methods of the Validator interface: their name, route name, input and output types, and
+/// whether the server streams its responses.
+const VALIDATOR_METHODS: &[(&str, &str, &str, &str, bool)] = &[
+    (
+        "transaction",
+        "Transaction",
+        "sui_types::messages::Transaction",
+        "sui_types::messages::TransactionInfoResponse",
+        false,
+    ),
+    (
+        "handle_certificate",
+        "CertifiedTransaction",
+        "sui_types::messages::CertifiedTransaction",
+        "sui_types::messages::TransactionInfoResponse",
+        false,
+    ),
+    (
+        "transaction_batch",
+        "TransactionBatch",
+        "sui_types::messages::TransactionBatchRequest",
+        "sui_types::messages::TransactionBatchResponse",
+        false,
+    ),
+    (
+        "handle_certificate_batch",
+        "CertifiedTransactionBatch",
+        "sui_types::messages::CertificateBatchRequest",
+        "sui_types::messages::TransactionBatchResponse",
+        false,
+    ),
+    (
+        "account_info",
+        "AccountInfo",
+        "sui_types::messages::AccountInfoRequest",
+        "sui_types::messages::AccountInfoResponse",
+        false,
+    ),
+    (
+        "object_info",
+        "ObjectInfo",
+        "sui_types::messages::ObjectInfoRequest",
+        "sui_types::messages::ObjectInfoResponse",
+        false,
+    ),
+    (
+        "object_chunks",
+        "ObjectChunks",
+        "sui_types::messages::ObjectChunksRequest",
+        "sui_types::messages::ObjectChunksResponse",
+        false,
+    ),
+    (
+        "transaction_info",
+        "TransactionInfo",
+        "sui_types::messages::TransactionInfoRequest",
+        "sui_types::messages::TransactionInfoResponse",
+        false,
+    ),
+    (
+        "checkpoint",
+        "Checkpoint",
+        "sui_types::messages_checkpoint::CheckpointRequest",
+        "sui_types::messages_checkpoint::CheckpointResponse",
+        false,
+    ),
+    (
+        "batch_info",
+        "FollowTxStream",
+        "sui_types::messages::BatchInfoRequest",
+        "sui_types::messages::BatchInfoResponseItem",
+        true,
+    ),
+    (
+        "epoch_info",
+        "Epoch",
+        "sui_types::messages::EpochRequest",
+        "sui_types::messages::EpochResponse",
+        false,
+    ),
+];
+
 fn main() -> Result<()> {
     let out_dir = if env::var("DUMP_GENERATED_GRPC").is_ok() {
         PathBuf::from("")
@@ -15,91 +97,55 @@ fn main() -> Result<()> {
 
     let codec_path = "mysten_network::codec::BincodeCodec";
 
-    let validator_service = Service::builder()
+    let mut validator_service = Service::builder()
         .name("Validator")
         .package("sui.validator")
-        .comment("The Validator interface")
-        .method(
-            Method::builder()
-                .name("transaction")
-                .route_name("Transaction")
-                .input_type("sui_types::messages::Transaction")
-                .output_type("sui_types::messages::TransactionInfoResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("handle_certificate")
-                .route_name("CertifiedTransaction")
-                .input_type("sui_types::messages::CertifiedTransaction")
-                .output_type("sui_types::messages::TransactionInfoResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("account_info")
-                .route_name("AccountInfo")
-                .input_type("sui_types::messages::AccountInfoRequest")
-                .output_type("sui_types::messages::AccountInfoResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("object_info")
-                .route_name("ObjectInfo")
-                .input_type("sui_types::messages::ObjectInfoRequest")
-                .output_type("sui_types::messages::ObjectInfoResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("transaction_info")
-                .route_name("TransactionInfo")
-                .input_type("sui_types::messages::TransactionInfoRequest")
-                .output_type("sui_types::messages::TransactionInfoResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("checkpoint")
-                .route_name("Checkpoint")
-                .input_type("sui_types::messages_checkpoint::CheckpointRequest")
-                .output_type("sui_types::messages_checkpoint::CheckpointResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("batch_info")
-                .route_name("FollowTxStream")
-                .input_type("sui_types::messages::BatchInfoRequest")
-                .output_type("sui_types::messages::BatchInfoResponseItem")
-                .server_streaming()
-                .codec_path(codec_path)
-                .build(),
-        )
-        .method(
-            Method::builder()
-                .name("epoch_info")
-                .route_name("Epoch")
-                .input_type("sui_types::messages::EpochRequest")
-                .output_type("sui_types::messages::EpochResponse")
-                .codec_path(codec_path)
-                .build(),
-        )
-        .build();
+        .comment("The Validator interface");
+    for &(name, route_name, input_type, output_type, server_streaming) in VALIDATOR_METHODS {
+        let mut method = Method::builder()
+            .name(name)
+            .route_name(route_name)
+            .input_type(input_type)
+            .output_type(output_type)
+            .codec_path(codec_path);
+        if server_streaming {
+            method = method.server_streaming();
+        }
+        validator_service = validator_service.method(method.build());
+    }
 
     Builder::new()
         .out_dir(&out_dir)
-        .compile(&[validator_service]);
+        .compile(&[validator_service.build()]);
+
+    // The routes of the methods and the names of their messages, described by the reflection
+    // service
+    let methods: String = VALIDATOR_METHODS
+        .iter()
+        .map(
+            |(_, route_name, input_type, output_type, server_streaming)| {
+                format!(
+                    "    ({:?}, {:?}, {:?}, {}),\n",
+                    route_name,
+                    type_name(input_type),
+                    type_name(output_type),
+                    server_streaming
+                )
+            },
+        )
+        .collect();
+    fs::write(
+        out_dir.join("sui.validator.Validator.methods.rs"),
+        format!("&[\n{methods}]\n"),
+    )?;
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-env-changed=DUMP_GENERATED_GRPC");
 
     Ok(())
 }
+
+/// The name of a type without its path.
+fn type_name(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod api;
+pub mod reflection;
 
 pub use tonic;
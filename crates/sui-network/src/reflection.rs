@@ -0,0 +1,167 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The gRPC reflection service of the validator interface, so that tools like grpcurl list and
+//! describe its services without the definitions at hand.
+//!
+//! The Validator service exchanges bincode messages rather than protobuf ones, so its messages are
+//! described by their names only: reflection lists its methods, and the health service can be
+//! called as usual, e.g. `grpcurl -plaintext <address> grpc.health.v1.Health/Check`.
+
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, FileDescriptorSet, MethodDescriptorProto, ServiceDescriptorProto,
+};
+use std::collections::BTreeSet;
+use tonic_reflection::server::{ServerReflection, ServerReflectionServer};
+
+/// The route name, input and output message names of the methods of the Validator service, and
+/// whether the server streams its responses.
+const VALIDATOR_METHODS: &[(&str, &str, &str, bool)] = include!(concat!(
+    env!("OUT_DIR"),
+    "/sui.validator.Validator.methods.rs"
+));
+
+/// The reflection service, describing the Validator and health services.
+pub fn reflection_service(
+) -> Result<ServerReflectionServer<impl ServerReflection>, tonic_reflection::server::Error> {
+    let descriptors = FileDescriptorSet {
+        file: vec![validator_descriptor(), health_descriptor()],
+    }
+    .encode_to_vec();
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(&descriptors)
+        .build()
+}
+
+fn validator_descriptor() -> FileDescriptorProto {
+    let messages: BTreeSet<_> = VALIDATOR_METHODS
+        .iter()
+        .flat_map(|(_, input, output, _)| [*input, *output])
+        .collect();
+    let methods = VALIDATOR_METHODS
+        .iter()
+        .map(
+            |(route_name, input, output, server_streaming)| MethodDescriptorProto {
+                name: Some(route_name.to_string()),
+                input_type: Some(format!(".sui.validator.{input}")),
+                output_type: Some(format!(".sui.validator.{output}")),
+                server_streaming: Some(*server_streaming),
+                ..Default::default()
+            },
+        )
+        .collect();
+    FileDescriptorProto {
+        name: Some("sui/validator.proto".to_string()),
+        package: Some("sui.validator".to_string()),
+        message_type: messages
+            .into_iter()
+            .map(|name| DescriptorProto {
+                name: Some(name.to_string()),
+                ..Default::default()
+            })
+            .collect(),
+        service: vec![ServiceDescriptorProto {
+            name: Some("Validator".to_string()),
+            method: methods,
+            ..Default::default()
+        }],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+}
+
+/// The definitions of the standard health service, served by every gRPC server of the node.
+fn health_descriptor() -> FileDescriptorProto {
+    let field = |name: &str, type_: Type, type_name: Option<&str>| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(1),
+        label: Some(Label::Optional as i32),
+        r#type: Some(type_ as i32),
+        type_name: type_name.map(|type_name| type_name.to_string()),
+        json_name: Some(name.to_string()),
+        ..Default::default()
+    };
+    let status_values = ["UNKNOWN", "SERVING", "NOT_SERVING", "SERVICE_UNKNOWN"]
+        .iter()
+        .enumerate()
+        .map(|(number, name)| EnumValueDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number as i32),
+            ..Default::default()
+        })
+        .collect();
+    let method = |name: &str, server_streaming: bool| MethodDescriptorProto {
+        name: Some(name.to_string()),
+        input_type: Some(".grpc.health.v1.HealthCheckRequest".to_string()),
+        output_type: Some(".grpc.health.v1.HealthCheckResponse".to_string()),
+        server_streaming: Some(server_streaming),
+        ..Default::default()
+    };
+    FileDescriptorProto {
+        name: Some("grpc/health/v1/health.proto".to_string()),
+        package: Some("grpc.health.v1".to_string()),
+        message_type: vec![
+            DescriptorProto {
+                name: Some("HealthCheckRequest".to_string()),
+                field: vec![field("service", Type::String, None)],
+                ..Default::default()
+            },
+            DescriptorProto {
+                name: Some("HealthCheckResponse".to_string()),
+                field: vec![field(
+                    "status",
+                    Type::Enum,
+                    Some(".grpc.health.v1.HealthCheckResponse.ServingStatus"),
+                )],
+                enum_type: vec![EnumDescriptorProto {
+                    name: Some("ServingStatus".to_string()),
+                    value: status_values,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("Health".to_string()),
+            method: vec![method("Check", false), method("Watch", true)],
+            ..Default::default()
+        }],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_descriptor_describes_every_method() {
+        let descriptor = validator_descriptor();
+        let service = &descriptor.service[0];
+        assert_eq!(service.method.len(), VALIDATOR_METHODS.len());
+        let follow = service
+            .method
+            .iter()
+            .find(|method| method.name() == "FollowTxStream")
+            .unwrap();
+        assert!(follow.server_streaming());
+        assert_eq!(follow.input_type(), ".sui.validator.BatchInfoRequest");
+
+        // Every message referred to by a method is defined
+        let messages: BTreeSet<_> = descriptor
+            .message_type
+            .iter()
+            .map(|message| format!(".sui.validator.{}", message.name()))
+            .collect();
+        for method in &service.method {
+            assert!(messages.contains(method.input_type()));
+            assert!(messages.contains(method.output_type()));
+        }
+
+        assert!(reflection_service().is_ok());
+    }
+}
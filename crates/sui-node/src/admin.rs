@@ -2,24 +2,56 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::{
-    extract::Extension,
+    extract::{Extension, Path, Query},
     http::StatusCode,
-    routing::{get, post},
-    Router,
+    routing::{delete, get, post},
+    Json, Router,
 };
+use serde::Deserialize;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use telemetry_subscribers::FilterHandle;
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
+use sui_core::state_dump::StateDump;
+use sui_types::committee::EpochId;
+use sui_types::performance_report::{EpochPerformanceReport, SignedEpochPerformanceReport};
 use tracing::info;
 
+use crate::otlp::LogFilterHandle;
+
 const LOGGING_ROUTE: &str = "/logging";
+const PERFORMANCE_REPORT_ROUTE: &str = "/performance-report";
+const PERFORMANCE_REPORT_EPOCH_ROUTE: &str = "/performance-report/:epoch";
+const PERFORMANCE_REPORTS_ROUTE: &str = "/performance-reports";
+const STATE_DUMP_ROUTE: &str = "/state-dump";
+const FAIL_POINTS_ROUTE: &str = "/fail-points";
+const FAIL_POINT_ROUTE: &str = "/fail-points/:name";
+
+/// Entries of each queue or table listed in a state dump by default, and at most.
+const DEFAULT_STATE_DUMP_LIMIT: usize = 20;
+const MAX_STATE_DUMP_LIMIT: usize = 1_000;
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+pub fn start_admin_server(port: u16, filter_handle: LogFilterHandle, state: Arc<AuthorityState>) {
     let filter = filter_handle.get().unwrap();
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(
+            PERFORMANCE_REPORT_ROUTE,
+            get(get_current_performance_report),
+        )
+        .route(PERFORMANCE_REPORT_EPOCH_ROUTE, get(get_performance_report))
+        .route(PERFORMANCE_REPORTS_ROUTE, get(get_performance_reports))
+        .route(STATE_DUMP_ROUTE, get(get_state_dump))
+        .layer(Extension(filter_handle))
+        .layer(Extension(state));
+    // Only builds with fail points compiled in can be told to fail
+    if sui_failpoints::ENABLED {
+        app = app
+            .route(FAIL_POINTS_ROUTE, get(get_fail_points))
+            .route(FAIL_POINT_ROUTE, post(set_fail_point))
+            .route(FAIL_POINT_ROUTE, delete(remove_fail_point));
+    }
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -36,7 +68,7 @@ pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
     });
 }
 
-async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (StatusCode, String) {
+async fn get_filter(Extension(filter_handle): Extension<LogFilterHandle>) -> (StatusCode, String) {
     match filter_handle.get() {
         Ok(filter) => (StatusCode::OK, filter),
         Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
@@ -44,7 +76,7 @@ async fn get_filter(Extension(filter_handle): Extension<FilterHandle>) -> (Statu
 }
 
 async fn set_filter(
-    Extension(filter_handle): Extension<FilterHandle>,
+    Extension(filter_handle): Extension<LogFilterHandle>,
     new_filter: String,
 ) -> (StatusCode, String) {
     match filter_handle.update(&new_filter) {
@@ -55,3 +87,76 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+/// The report of the current epoch so far, which is signed once the epoch is over.
+async fn get_current_performance_report(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> Json<EpochPerformanceReport> {
+    Json(state.performance.current_report())
+}
+
+async fn get_performance_report(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Path(epoch): Path<EpochId>,
+) -> Result<Json<SignedEpochPerformanceReport>, (StatusCode, String)> {
+    state.performance.report(epoch).map(Json).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("No performance report for epoch {epoch}"),
+        )
+    })
+}
+
+async fn get_performance_reports(
+    Extension(state): Extension<Arc<AuthorityState>>,
+) -> Json<Vec<SignedEpochPerformanceReport>> {
+    Json(state.performance.reports())
+}
+
+#[derive(Deserialize)]
+struct StateDumpParams {
+    limit: Option<usize>,
+}
+
+/// A summary of the queues and tables of the node, for reports of stuck nodes, e.g.
+/// `curl localhost:1337/state-dump?limit=100 > dump.json`.
+async fn get_state_dump(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Query(params): Query<StateDumpParams>,
+) -> Result<Json<StateDump>, (StatusCode, String)> {
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_STATE_DUMP_LIMIT)
+        .min(MAX_STATE_DUMP_LIMIT);
+    state
+        .dump_state(limit)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// The fail points configured, one `<name>=<action>` per line.
+async fn get_fail_points() -> String {
+    sui_failpoints::configured()
+        .into_iter()
+        .map(|(name, action)| format!("{name}={action}\n"))
+        .collect()
+}
+
+/// Configures a fail point, e.g.
+/// `curl -X POST localhost:1337/fail-points/checkpoint-after-sign -d panic`.
+async fn set_fail_point(Path(name): Path<String>, action: String) -> (StatusCode, String) {
+    match action.parse::<sui_failpoints::Action>() {
+        Ok(action) => {
+            info!(fail_point = %name, %action, "Fail point configured");
+            sui_failpoints::configure(&name, action);
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err),
+    }
+}
+
+async fn remove_fail_point(Path(name): Path<String>) -> StatusCode {
+    info!(fail_point = %name, "Fail point removed");
+    sui_failpoints::remove(&name);
+    StatusCode::OK
+}
@@ -0,0 +1,209 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Caps the connections the gRPC server of the node keeps open. Each connection holds a file
+//! descriptor for as long as it is open, however many requests it multiplexes, so connections
+//! beyond the cap are closed as soon as they are accepted and their clients retry elsewhere.
+
+use futures::Stream;
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_with_registry, IntCounter, IntGauge,
+    Registry,
+};
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use sui_network::tonic::transport::server::{Connected, TcpConnectInfo};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::warn;
+
+/// How long accepting pauses after failing, e.g. because the process is out of descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+#[derive(Clone)]
+struct ConnectionLimitMetrics {
+    open_connections: IntGauge,
+    rejected_connections: IntCounter,
+}
+
+impl ConnectionLimitMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            open_connections: register_int_gauge_with_registry!(
+                "grpc_open_connections",
+                "Inbound gRPC connections open",
+                registry,
+            )
+            .unwrap(),
+            rejected_connections: register_int_counter_with_registry!(
+                "grpc_rejected_connections",
+                "Inbound gRPC connections closed on accept because too many were open",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// An accepted connection, counted against the cap until it is dropped.
+pub struct LimitedConnection {
+    stream: TcpStream,
+    _permit: Option<OwnedSemaphorePermit>,
+    open_connections: IntGauge,
+}
+
+impl Drop for LimitedConnection {
+    fn drop(&mut self) {
+        self.open_connections.dec();
+    }
+}
+
+/// Accepts the connections of `listener`, closing those beyond `max_connections` open at once.
+/// Accept errors are logged rather than returned, as they would stop the server.
+pub fn limit_connections(
+    listener: TcpListener,
+    max_connections: Option<usize>,
+    registry: &Registry,
+) -> impl Stream<Item = io::Result<LimitedConnection>> + Send {
+    let permits = max_connections.map(|max| Arc::new(Semaphore::new(max)));
+    let metrics = ConnectionLimitMetrics::new(registry);
+    futures::stream::unfold(
+        (listener, permits, metrics),
+        |(listener, permits, metrics)| async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        warn!("Failed to accept a gRPC connection: {err}");
+                        tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                        continue;
+                    }
+                };
+                let permit = match &permits {
+                    Some(permits) => match permits.clone().try_acquire_owned() {
+                        Ok(permit) => Some(permit),
+                        Err(_) => {
+                            metrics.rejected_connections.inc();
+                            continue;
+                        }
+                    },
+                    None => None,
+                };
+                if let Err(err) = stream.set_nodelay(true) {
+                    warn!("Failed to set TCP_NODELAY on a gRPC connection: {err}");
+                }
+                metrics.open_connections.inc();
+                let connection = LimitedConnection {
+                    stream,
+                    _permit: permit,
+                    open_connections: metrics.open_connections.clone(),
+                };
+                return Some((Ok::<_, io::Error>(connection), (listener, permits, metrics)));
+            }
+        },
+    )
+}
+
+impl Connected for LimitedConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.stream.connect_info()
+    }
+}
+
+impl AsyncRead for LimitedConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for LimitedConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::StreamExt;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn test_limit_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let registry = Registry::new();
+        let mut incoming = Box::pin(limit_connections(listener, Some(1), &registry));
+        let metric = |name: &str| {
+            registry
+                .gather()
+                .into_iter()
+                .find(|family| family.get_name() == name)
+                .unwrap()
+                .get_metric()[0]
+                .clone()
+        };
+        let open = || metric("grpc_open_connections").get_gauge().get_value();
+        let rejected = || {
+            metric("grpc_rejected_connections")
+                .get_counter()
+                .get_value()
+        };
+
+        let _first = TcpStream::connect(address).await.unwrap();
+        let accepted = incoming.next().await.unwrap().unwrap();
+        assert_eq!(open(), 1.0);
+
+        // The second connection is closed as soon as it is accepted
+        let mut second = TcpStream::connect(address).await.unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), incoming.next())
+                .await
+                .is_err()
+        );
+        assert_eq!(second.read(&mut [0; 1]).await.unwrap(), 0);
+        assert_eq!(rejected(), 1.0);
+
+        // Closing the first connection makes room for another
+        drop(accepted);
+        assert_eq!(open(), 0.0);
+        let _third = TcpStream::connect(address).await.unwrap();
+        incoming.next().await.unwrap().unwrap();
+        assert_eq!(open(), 1.0);
+    }
+}
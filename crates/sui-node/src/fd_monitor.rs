@@ -0,0 +1,448 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the file descriptors held by the node, grouped by the subsystem owning them, and
+//! sheds inbound validator requests when the process gets close to its descriptor limit.
+//! The gRPC server also caps its open connections, to [`grpc_connection_limit`] by default.
+
+use multiaddr::{Multiaddr, Protocol};
+use prometheus::{
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntGauge, IntGaugeVec, Registry,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use sui_config::node::ConnectionLimitsConfig;
+use sui_config::NodeConfig;
+use sui_network::tonic;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Number of subsystems logged when descriptor usage crosses the warning threshold.
+const TOP_OFFENDERS: usize = 5;
+
+#[derive(Clone)]
+pub struct FdMetrics {
+    open_fds: IntGaugeVec,
+    fd_limit: IntGauge,
+    rejected_requests: IntCounter,
+}
+
+impl FdMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            open_fds: register_int_gauge_vec_with_registry!(
+                "open_fds",
+                "Open file descriptors per subsystem",
+                &["subsystem"],
+                registry,
+            )
+            .unwrap(),
+            fd_limit: register_int_gauge_with_registry!(
+                "fd_limit",
+                "Soft limit on open file descriptors for this process",
+                registry,
+            )
+            .unwrap(),
+            rejected_requests: register_int_counter_with_registry!(
+                "fd_rejected_requests",
+                "Inbound requests rejected because the process is close to its file descriptor limit",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Cheap handle checked on every inbound validator request.
+#[derive(Clone)]
+pub struct FdGuard {
+    exhausted: Arc<AtomicBool>,
+    rejected_requests: IntCounter,
+}
+
+impl FdGuard {
+    /// gRPC interceptor rejecting requests with `RESOURCE_EXHAUSTED` while descriptors run low,
+    /// so clients retry elsewhere instead of the node failing on half-opened storage or sockets.
+    pub fn check(&self, request: tonic::Request<()>) -> Result<tonic::Request<()>, tonic::Status> {
+        if self.exhausted.load(Ordering::Relaxed) {
+            self.rejected_requests.inc();
+            Err(tonic::Status::resource_exhausted(
+                "node is close to its file descriptor limit, retry later",
+            ))
+        } else {
+            Ok(request)
+        }
+    }
+}
+
+/// Maps open descriptors to the subsystem owning them.
+struct Subsystems {
+    /// Storage directories, keyed by path prefix.
+    db_paths: Vec<(PathBuf, String)>,
+    /// Ports this node listens on; sockets bound to them are inbound connections.
+    listen_ports: HashMap<u16, String>,
+}
+
+impl Subsystems {
+    fn new(config: &NodeConfig) -> Self {
+        let mut db_paths = vec![(config.db_path().to_path_buf(), "db".to_string())];
+        let mut listen_ports = HashMap::new();
+        let mut add_port = |address: &Multiaddr, name: &str| {
+            if let Some(port) = tcp_port(address) {
+                listen_ports.insert(port, name.to_string());
+            }
+        };
+        add_port(config.network_address(), "grpc");
+        if let Some(consensus_config) = config.consensus_config() {
+            db_paths.push((
+                consensus_config.db_path().to_path_buf(),
+                "db-consensus".to_string(),
+            ));
+            add_port(consensus_config.address(), "consensus");
+            let validator = config.genesis().ok().and_then(|genesis| {
+                genesis
+                    .validator_set()
+                    .iter()
+                    .find(|v| v.protocol_key() == config.protocol_public_key())
+                    .cloned()
+            });
+            if let Some(validator) = validator {
+                add_port(&validator.narwhal_primary_address, "narwhal-primary");
+                add_port(&validator.narwhal_worker_address, "narwhal-worker");
+            }
+        }
+        listen_ports.insert(config.json_rpc_address.port(), "json-rpc".to_string());
+        if let Some(websocket_address) = config.websocket_address {
+            listen_ports.insert(websocket_address.port(), "websocket".to_string());
+        }
+        if let Some(submit_address) = config.transaction_submit_address {
+            listen_ports.insert(submit_address.port(), "transaction-submit".to_string());
+        }
+        listen_ports.insert(config.metrics_address.port(), "metrics".to_string());
+        listen_ports.insert(config.admin_interface_port, "admin".to_string());
+        // Check the most specific (longest) storage paths first.
+        db_paths.sort_by_key(|(path, _)| std::cmp::Reverse(path.as_os_str().len()));
+        Self {
+            db_paths,
+            listen_ports,
+        }
+    }
+
+    fn classify(&self, target: &Path, socket_ports: &HashMap<u64, u16>) -> String {
+        let target_str = target.to_string_lossy();
+        if let Some(inode) = target_str
+            .strip_prefix("socket:[")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            return match inode
+                .parse::<u64>()
+                .ok()
+                .and_then(|inode| socket_ports.get(&inode))
+            {
+                Some(port) => match self.listen_ports.get(port) {
+                    Some(name) => format!("{name}-inbound"),
+                    None => "outbound".to_string(),
+                },
+                None => "socket-other".to_string(),
+            };
+        }
+        if target_str.starts_with("pipe:") {
+            return "pipe".to_string();
+        }
+        if target_str.starts_with("anon_inode:") {
+            return "anon-inode".to_string();
+        }
+        for (prefix, name) in &self.db_paths {
+            if let Ok(rest) = target.strip_prefix(prefix) {
+                // Break the node's storage down by store (e.g. `db-store`, `db-checkpoints`).
+                return match (name.as_str(), rest.components().next()) {
+                    ("db", Some(component)) => {
+                        format!("db-{}", component.as_os_str().to_string_lossy())
+                    }
+                    _ => name.clone(),
+                };
+            }
+        }
+        "file".to_string()
+    }
+}
+
+/// Replaces the thresholds and check interval of a running monitor, e.g. when the config of the
+/// node is reloaded.
+#[derive(Clone)]
+pub struct FdMonitorHandle(Arc<RwLock<ConnectionLimitsConfig>>);
+
+impl FdMonitorHandle {
+    pub fn update(&self, config: &ConnectionLimitsConfig) {
+        *self.0.write().unwrap() = config.clone();
+    }
+}
+
+pub struct FdMonitor {
+    config: Arc<RwLock<ConnectionLimitsConfig>>,
+    subsystems: Subsystems,
+    metrics: FdMetrics,
+    exhausted: Arc<AtomicBool>,
+}
+
+impl FdMonitor {
+    pub fn new(config: &NodeConfig, registry: &Registry) -> Self {
+        Self {
+            config: Arc::new(RwLock::new(config.connection_limits.clone())),
+            subsystems: Subsystems::new(config),
+            metrics: FdMetrics::new(registry),
+            exhausted: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn guard(&self) -> FdGuard {
+        FdGuard {
+            exhausted: self.exhausted.clone(),
+            rejected_requests: self.metrics.rejected_requests.clone(),
+        }
+    }
+
+    pub fn handle(&self) -> FdMonitorHandle {
+        FdMonitorHandle(self.config.clone())
+    }
+
+    /// Starts periodic sampling. Descriptor introspection relies on procfs, so this is a
+    /// no-op outside of Linux and in the simulator.
+    pub fn spawn(self) -> Option<JoinHandle<()>> {
+        if !cfg!(target_os = "linux") || cfg!(msim) {
+            return None;
+        }
+        let limit = match fd_soft_limit() {
+            Some(limit) => limit,
+            None => {
+                warn!("Unable to read the file descriptor limit, descriptor monitoring disabled");
+                return None;
+            }
+        };
+        info!(limit, "Monitoring open file descriptors");
+        self.metrics.fd_limit.set(limit as i64);
+        Some(tokio::spawn(async move {
+            loop {
+                self.sample(limit);
+                // Read on every check, so that a reloaded interval applies from the next one
+                let interval = self.config.read().unwrap().fd_check_interval_ms;
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+            }
+        }))
+    }
+
+    fn sample(&self, limit: u64) {
+        let usage = match self.open_fds_by_subsystem() {
+            Ok(usage) => usage,
+            Err(err) => {
+                warn!("Failed to list open file descriptors: {err}");
+                return;
+            }
+        };
+        self.metrics.open_fds.reset();
+        for (subsystem, count) in &usage {
+            self.metrics
+                .open_fds
+                .with_label_values(&[subsystem])
+                .set(*count as i64);
+        }
+
+        let config = self.config.read().unwrap().clone();
+        let total: u64 = usage.values().sum();
+        let ratio = total as f64 / limit as f64;
+        if ratio >= config.fd_warn_ratio {
+            let mut offenders: Vec<_> = usage.iter().collect();
+            offenders.sort_by(|a, b| b.1.cmp(a.1));
+            offenders.truncate(TOP_OFFENDERS);
+            warn!(
+                total,
+                limit,
+                ?offenders,
+                "Open file descriptors are approaching the process limit"
+            );
+        }
+
+        if ratio >= config.fd_reject_ratio {
+            if !self.exhausted.swap(true, Ordering::Relaxed) {
+                warn!(
+                    total,
+                    limit, "Rejecting inbound requests until file descriptors are released"
+                );
+            }
+        } else if ratio < config.fd_warn_ratio && self.exhausted.swap(false, Ordering::Relaxed) {
+            info!(total, limit, "Accepting inbound requests again");
+        }
+    }
+
+    fn open_fds_by_subsystem(&self) -> std::io::Result<BTreeMap<String, u64>> {
+        let socket_ports = socket_local_ports();
+        let mut usage = BTreeMap::new();
+        for entry in std::fs::read_dir("/proc/self/fd")? {
+            // Descriptors can be closed between listing and reading the link.
+            if let Ok(target) = std::fs::read_link(entry?.path()) {
+                *usage
+                    .entry(self.subsystems.classify(&target, &socket_ports))
+                    .or_insert(0) += 1;
+            }
+        }
+        Ok(usage)
+    }
+}
+
+fn tcp_port(address: &Multiaddr) -> Option<u16> {
+    address.iter().find_map(|protocol| match protocol {
+        Protocol::Tcp(port) => Some(port),
+        _ => None,
+    })
+}
+
+/// Cap on the inbound gRPC connections open at once. Unless configured, half the descriptor limit
+/// is left to connections and the other half to storage, consensus and the other servers.
+pub fn grpc_connection_limit(config: &ConnectionLimitsConfig) -> Option<usize> {
+    config
+        .max_grpc_connections
+        .or_else(|| fd_soft_limit().map(|limit| limit as usize / 2))
+}
+
+/// Reads the soft `RLIMIT_NOFILE` of this process from procfs.
+fn fd_soft_limit() -> Option<u64> {
+    parse_fd_soft_limit(&std::fs::read_to_string("/proc/self/limits").ok()?)
+}
+
+fn parse_fd_soft_limit(limits: &str) -> Option<u64> {
+    limits
+        .lines()
+        .find(|line| line.starts_with("Max open files"))?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()
+}
+
+/// Maps socket inodes to their local TCP port.
+fn socket_local_ports() -> HashMap<u64, u16> {
+    let mut ports = HashMap::new();
+    for table in ["/proc/self/net/tcp", "/proc/self/net/tcp6"] {
+        if let Ok(contents) = std::fs::read_to_string(table) {
+            parse_socket_table(&contents, &mut ports);
+        }
+    }
+    ports
+}
+
+/// Adds the sockets of a `/proc/net/tcp` table to `ports`.
+fn parse_socket_table(contents: &str, ports: &mut HashMap<u64, u16>) {
+    for line in contents.lines().skip(1) {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        if let (Some(local_address), Some(inode)) = (fields.get(1), fields.get(9)) {
+            let port = local_address
+                .rsplit(':')
+                .next()
+                .and_then(|port| u16::from_str_radix(port, 16).ok());
+            if let (Some(port), Ok(inode)) = (port, inode.parse()) {
+                ports.insert(inode, port);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let subsystems = Subsystems {
+            db_paths: vec![
+                (
+                    PathBuf::from("/data/db/consensus"),
+                    "db-consensus".to_string(),
+                ),
+                (PathBuf::from("/data/db"), "db".to_string()),
+            ],
+            listen_ports: HashMap::from([(8080, "grpc".to_string())]),
+        };
+        let socket_ports = HashMap::from([(100, 8080), (101, 41000)]);
+        let classify = |target: &str| subsystems.classify(Path::new(target), &socket_ports);
+
+        assert_eq!(classify("socket:[100]"), "grpc-inbound");
+        assert_eq!(classify("socket:[101]"), "outbound");
+        assert_eq!(classify("socket:[102]"), "socket-other");
+        assert_eq!(classify("socket:[abc]"), "socket-other");
+        assert_eq!(classify("pipe:[7]"), "pipe");
+        assert_eq!(classify("anon_inode:[eventpoll]"), "anon-inode");
+        assert_eq!(classify("/data/db/store/000012.sst"), "db-store");
+        assert_eq!(classify("/data/db"), "db");
+        assert_eq!(classify("/data/db/consensus/LOG"), "db-consensus");
+        assert_eq!(classify("/data/dbx/LOG"), "file");
+        assert_eq!(classify("/etc/hosts"), "file");
+    }
+
+    #[test]
+    fn test_parse_fd_soft_limit() {
+        let limits = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            65536                1048576              files
+";
+        assert_eq!(parse_fd_soft_limit(limits), Some(65536));
+        let unlimited = "Max open files            unlimited            unlimited            files";
+        assert_eq!(parse_fd_soft_limit(unlimited), None);
+        assert_eq!(
+            parse_fd_soft_limit("Max cpu time  unlimited  unlimited  seconds"),
+            None
+        );
+        assert_eq!(parse_fd_soft_limit(""), None);
+    }
+
+    #[test]
+    fn test_parse_socket_table() {
+        let table = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4242 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000001000000:A028 00000000000000000000000000000000:0000 01 00000000:00000000 00:00000000 00000000  1000        0 4243 1 0000000000000000 20 4 30 10 -1
+   2: 0100007F:ZZZZ 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 4244 1
+   3: truncated
+";
+        let mut ports = HashMap::new();
+        parse_socket_table(table, &mut ports);
+        assert_eq!(ports, HashMap::from([(4242, 8080), (4243, 41000)]));
+    }
+
+    #[cfg(all(target_os = "linux", not(msim)))]
+    #[test]
+    fn test_socket_local_ports() {
+        use std::os::unix::io::AsRawFd;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let target = std::fs::read_link(format!("/proc/self/fd/{}", listener.as_raw_fd())).unwrap();
+        let subsystems = Subsystems {
+            db_paths: vec![],
+            listen_ports: HashMap::from([(port, "grpc".to_string())]),
+        };
+        assert_eq!(
+            subsystems.classify(&target, &socket_local_ports()),
+            "grpc-inbound"
+        );
+    }
+
+    #[test]
+    fn test_grpc_connection_limit() {
+        let mut config = ConnectionLimitsConfig {
+            max_grpc_connections: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(grpc_connection_limit(&config), Some(10));
+
+        config.max_grpc_connections = None;
+        assert_eq!(
+            grpc_connection_limit(&config),
+            fd_soft_limit().map(|limit| limit as usize / 2)
+        );
+    }
+}
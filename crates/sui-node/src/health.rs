@@ -0,0 +1,187 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `/health` and `/ready` endpoints of a node, for the liveness and readiness probes of an
+//! orchestrator and the health checks of a load balancer. Both answer with a [`HealthReport`],
+//! with status 200 when the node is healthy, respectively ready, and 503 otherwise.
+
+use axum::{extract::Extension, http::StatusCode, routing::get, Json, Router};
+use serde::Serialize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use sui_config::node::HealthConfig;
+use sui_core::authority::AuthorityState;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+const HEALTH_ROUTE: &str = "/health";
+const READY_ROUTE: &str = "/ready";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    /// Whether the store of the node can be read.
+    pub healthy: bool,
+    /// Whether the node is healthy, synced and, for validators, hears from consensus.
+    pub ready: bool,
+    /// Whether the node rejects transactions, at the end of an epoch or when shutting down.
+    pub halted: bool,
+    pub highest_synced_checkpoint: Option<CheckpointSequenceNumber>,
+    pub highest_known_checkpoint: Option<CheckpointSequenceNumber>,
+    /// When the node last committed the effects of a certificate, in milliseconds since the Unix
+    /// epoch.
+    pub last_commit_timestamp_ms: Option<u64>,
+    /// Only reported by validators.
+    pub consensus: Option<ConsensusHealth>,
+    /// Why the node is not healthy or ready.
+    pub problems: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsensusHealth {
+    pub last_round: u64,
+    /// When the validator last handled a transaction sequenced by consensus, in milliseconds since
+    /// the Unix epoch.
+    pub last_timestamp_ms: Option<u64>,
+}
+
+#[derive(Clone)]
+pub struct HealthCheck {
+    state: Arc<AuthorityState>,
+    config: HealthConfig,
+    is_validator: bool,
+}
+
+impl HealthCheck {
+    pub fn new(state: Arc<AuthorityState>, config: HealthConfig, is_validator: bool) -> Self {
+        Self {
+            state,
+            config,
+            is_validator,
+        }
+    }
+
+    pub fn report(&self) -> HealthReport {
+        let mut problems = vec![];
+        let healthy = match self.state.db().database_is_empty() {
+            Ok(_) => true,
+            Err(err) => {
+                problems.push(format!("The store can't be read: {err}"));
+                false
+            }
+        };
+        let mut ready = healthy;
+
+        let halted = self.state.is_halted();
+        if halted {
+            problems.push("The node is halted".to_string());
+            ready = false;
+        }
+
+        let (highest_synced_checkpoint, highest_known_checkpoint) = match &self.state.checkpoints {
+            Some(checkpoints) => {
+                let mut checkpoints = checkpoints.lock();
+                (
+                    checkpoints
+                        .latest_stored_checkpoint()
+                        .map(|checkpoint| checkpoint.summary().sequence_number),
+                    checkpoints.highest_known_checkpoint(),
+                )
+            }
+            None => (None, None),
+        };
+        if let Some(known) = highest_known_checkpoint {
+            let lag = match highest_synced_checkpoint {
+                Some(synced) => known.saturating_sub(synced),
+                None => known + 1,
+            };
+            if lag > self.config.max_checkpoint_lag {
+                problems.push(format!("The node is {lag} checkpoints behind"));
+                ready = false;
+            }
+        }
+
+        let now = AuthorityState::unixtime_now_ms();
+        let consensus = if self.is_validator {
+            let last_timestamp_ms = timestamp(
+                &self
+                    .state
+                    .last_consensus_timestamp_ms
+                    .load(Ordering::Relaxed),
+            );
+            if let Some(max_silence_secs) = self.config.max_consensus_silence_secs {
+                // A validator which just started hasn't heard from consensus yet either
+                let silence_ms = now.saturating_sub(last_timestamp_ms.unwrap_or(0));
+                if silence_ms > max_silence_secs * 1000 {
+                    problems.push("No transaction sequenced by consensus lately".to_string());
+                    ready = false;
+                }
+            }
+            Some(ConsensusHealth {
+                last_round: self.state.last_consensus_round.load(Ordering::Relaxed),
+                last_timestamp_ms,
+            })
+        } else {
+            None
+        };
+
+        HealthReport {
+            healthy,
+            ready,
+            halted,
+            highest_synced_checkpoint,
+            highest_known_checkpoint,
+            last_commit_timestamp_ms: timestamp(
+                &self.state.last_commit_timestamp_ms.load(Ordering::Relaxed),
+            ),
+            consensus,
+            problems,
+        }
+    }
+
+    pub fn spawn(self) -> Option<JoinHandle<()>> {
+        if cfg!(msim) {
+            // axum uses difficult-to-support features such as TcpSocket::from_raw_fd(), so we
+            // can't yet run it in the simulator.
+            warn!("not starting health server in simulator");
+            return None;
+        }
+
+        let address = self.config.address;
+        let app = Router::new()
+            .route(HEALTH_ROUTE, get(health))
+            .route(READY_ROUTE, get(ready))
+            .layer(Extension(self));
+        info!(%address, "Starting health server");
+        Some(tokio::spawn(async move {
+            if let Err(err) = axum::Server::bind(&address)
+                .serve(app.into_make_service())
+                .await
+            {
+                error!("Health server stopped: {err}");
+            }
+        }))
+    }
+}
+
+fn timestamp(timestamp_ms: &u64) -> Option<u64> {
+    (*timestamp_ms > 0).then(|| *timestamp_ms)
+}
+
+async fn health(Extension(check): Extension<HealthCheck>) -> (StatusCode, Json<HealthReport>) {
+    let report = check.report();
+    (status(report.healthy), Json(report))
+}
+
+async fn ready(Extension(check): Extension<HealthCheck>) -> (StatusCode, Json<HealthReport>) {
+    let report = check.report();
+    (status(report.ready), Json(report))
+}
+
+fn status(ok: bool) -> StatusCode {
+    if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Writes the time to a file in the node's database directory at a regular interval, so that
+//! the time the node was down before a restart can be added to its performance report.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sui_core::authority::AuthorityState;
+use sui_types::performance_report::DowntimeWindow;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+/// Number of missed heartbeats after which the node is considered to have been down, rather
+/// than just slow to restart.
+const MISSED_HEARTBEATS_FOR_DOWNTIME: u64 = 2;
+
+pub struct Heartbeat {
+    path: PathBuf,
+    state: Arc<AuthorityState>,
+}
+
+impl Heartbeat {
+    pub fn new(path: PathBuf, state: Arc<AuthorityState>) -> Self {
+        Self { path, state }
+    }
+
+    /// Records the downtime since the last heartbeat of a previous run, if any, and starts
+    /// beating.
+    pub fn spawn(self) -> JoinHandle<()> {
+        let now = now_ms();
+        if let Some(last) = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|last| last.trim().parse::<u64>().ok())
+        {
+            let threshold = HEARTBEAT_INTERVAL.as_millis() as u64 * MISSED_HEARTBEATS_FOR_DOWNTIME;
+            if now.saturating_sub(last) > threshold {
+                info!(
+                    downtime_ms = now - last,
+                    "Node was down since its last heartbeat"
+                );
+                self.state.performance.record_downtime(DowntimeWindow {
+                    start_timestamp_ms: last,
+                    end_timestamp_ms: now,
+                });
+            }
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = std::fs::write(&self.path, now_ms().to_string()) {
+                    warn!("Failed to write heartbeat to {:?}: {}", self.path, e);
+                }
+            }
+        })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -5,18 +5,23 @@ use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::Result;
 use futures::TryFutureExt;
-use mysten_network::server::ServerBuilder;
 use parking_lot::Mutex;
 use prometheus::Registry;
 use std::option::Option::None;
 use std::time::Instant;
 use std::{sync::Arc, time::Duration};
+use sui_config::node::{JsonRpcServerConfig, RateLimitConfig, StorageBackendConfig};
 use sui_config::NodeConfig;
+use sui_core::authority::authority_store_cache::StoreCache;
+use sui_core::authority::authority_store_pruner::AuthorityStorePruner;
+use sui_core::authority::authority_store_statistics::StoreStatisticsExporter;
+use sui_core::authority::object_locks::ObjectLockMetrics;
 use sui_core::authority_active::checkpoint_driver::CheckpointMetrics;
 use sui_core::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use sui_core::authority_server::ValidatorService;
 use sui_core::quorum_driver::{QuorumDriver, QuorumDriverHandler, QuorumDriverMetrics};
 use sui_core::safe_client::SafeClientMetrics;
+use sui_core::state_snapshot::install_state_snapshot;
 use sui_core::transaction_streamer::TransactionStreamer;
 use sui_core::{
     authority::{AuthorityState, AuthorityStore},
@@ -27,43 +32,78 @@ use sui_core::{
     },
     checkpoints::CheckpointStore,
 };
+use sui_data_ingestion::{
+    make_object_store, read_latest_snapshot, CheckpointWriter, ObjectStore, StateSnapshotWriter,
+};
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::streaming_api::TransactionStreamingApiImpl;
 use sui_network::api::ValidatorServer;
+use sui_network::reflection::reflection_service;
+use sui_network::tonic;
 use sui_storage::{
     event_store::{EventStoreType, SqlEventStore},
     node_sync_store::NodeSyncStore,
+    table::StorageBackend,
     IndexStore,
 };
+use sui_types::base_types::set_checksummed_addresses;
+use sui_types::committee::Committee;
 use sui_types::messages::{CertifiedTransaction, CertifiedTransactionEffects};
 use tokio::sync::mpsc::channel;
 use tracing::{error, info, warn};
 
-use crate::metrics::GrpcMetrics;
+use crate::fd_monitor::{FdMonitor, FdMonitorHandle};
+use crate::health::HealthCheck;
+use crate::heartbeat::Heartbeat;
+use crate::metrics::{GrpcMetrics, GrpcMetricsLayer};
+use crate::startup::StartupTimer;
 use sui_core::authority_client::NetworkAuthorityClientMetrics;
 use sui_core::epoch::epoch_store::EpochStore;
 use sui_json_rpc::event_api::EventReadApiImpl;
 use sui_json_rpc::event_api::EventStreamingApiImpl;
+use sui_json_rpc::gas_price_api::GasPriceApiImpl;
+use sui_json_rpc::hot_objects_api::HotObjectsApiImpl;
 use sui_json_rpc::http_server::HttpServerHandle;
 use sui_json_rpc::quorum_driver_api::FullNodeQuorumDriverApi;
 use sui_json_rpc::read_api::FullNodeApi;
 use sui_json_rpc::read_api::ReadApi;
+use sui_json_rpc::rpc_policy::{RateLimit, RpcPolicy, RpcPolicyHandle};
 use sui_json_rpc::ws_server::WsServerHandle;
 use sui_json_rpc::JsonRpcServerBuilder;
 use sui_types::crypto::KeypairTraits;
 
 pub mod admin;
+pub mod connection_limit;
+pub mod fd_monitor;
+pub mod health;
+pub mod heartbeat;
 pub mod metrics;
+pub mod otlp;
+pub mod startup;
+pub mod transaction_submit;
+
+/// How often a shutting down node checks whether the certificates being executed are done.
+const SHUTDOWN_DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 pub struct SuiNode {
     grpc_server: tokio::task::JoinHandle<Result<()>>,
-    _json_rpc_service: Option<HttpServerHandle>,
-    _ws_subscription_service: Option<WsServerHandle>,
-    _batch_subsystem_handle: tokio::task::JoinHandle<Result<()>>,
-    _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
-    _gossip_handle: Option<tokio::task::JoinHandle<()>>,
-    _execute_driver_handle: tokio::task::JoinHandle<()>,
-    _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    json_rpc_service: Option<HttpServerHandle>,
+    ws_subscription_service: Option<WsServerHandle>,
+    batch_subsystem_handle: tokio::task::JoinHandle<Result<()>>,
+    post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
+    gossip_handle: Option<tokio::task::JoinHandle<()>>,
+    execute_driver_handle: tokio::task::JoinHandle<()>,
+    checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    fd_monitor_handle: Option<tokio::task::JoinHandle<()>>,
+    heartbeat_handle: tokio::task::JoinHandle<()>,
+    checkpoint_export_handle: Option<tokio::task::JoinHandle<()>>,
+    snapshot_export_handle: Option<tokio::task::JoinHandle<()>>,
+    pruner_handle: Option<tokio::task::JoinHandle<()>>,
+    health_handle: Option<tokio::task::JoinHandle<()>>,
+    store_statistics_handle: Option<tokio::task::JoinHandle<()>>,
+    transaction_submit_handle: Option<tokio::task::JoinHandle<()>>,
+    rpc_policy: Option<RpcPolicyHandle>,
+    fd_monitor: FdMonitorHandle,
     state: Arc<AuthorityState>,
     active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
     quorum_driver_handler: Option<QuorumDriverHandler<NetworkAuthorityClient>>,
@@ -75,6 +115,7 @@ impl SuiNode {
         // TODO: maybe have a config enum that takes care of this for us.
         let is_validator = config.consensus_config().is_some();
         let is_full_node = !is_validator;
+        let mut timer = StartupTimer::new(&prometheus_registry);
 
         info!(node =? config.protocol_public_key(),
             "Initializing sui-node listening on {}", config.network_address
@@ -84,20 +125,47 @@ impl SuiNode {
 
         let secret = Arc::pin(config.protocol_key_pair().copy());
         let committee = genesis.committee()?;
-        let store = Arc::new(AuthorityStore::open(&config.db_path().join("store"), None));
+        let storage_backend = match config.storage_backend {
+            StorageBackendConfig::RocksDb => StorageBackend::RocksDB,
+            StorageBackendConfig::InMemory => StorageBackend::InMemory,
+        };
+        let store_path = config.db_path().join("store");
+        let store = match &config.storage {
+            Some(storage_config) => {
+                AuthorityStore::open_with_config(&store_path, storage_config, storage_backend)?
+            }
+            None => AuthorityStore::open_with_backend(&store_path, None, storage_backend),
+        };
+        let store = Arc::new(
+            store
+                .with_cache(StoreCache::new(&config.store_cache, &prometheus_registry))
+                .with_lock_metrics(ObjectLockMetrics::new(&prometheus_registry)),
+        );
         let epoch_store = Arc::new(EpochStore::new(
             config.db_path().join("epochs"),
             &committee,
             None,
         ));
 
-        let checkpoint_store = Arc::new(Mutex::new(CheckpointStore::open(
+        let mut checkpoint_store = CheckpointStore::open(
             &config.db_path().join("checkpoints"),
             None,
             committee.epoch,
             config.protocol_public_key(),
             secret.clone(),
-        )?));
+        )?;
+        checkpoint_store.set_limits(config.checkpoint_limits.clone());
+        let checkpoint_store = Arc::new(Mutex::new(checkpoint_store));
+        timer.step("open_stores");
+
+        if let Some(snapshot_config) = &config.state_snapshot {
+            if snapshot_config.bootstrap && store.database_is_empty()? {
+                let snapshot_store = make_object_store(&snapshot_config.store)?;
+                install_latest_snapshot(&*snapshot_store, &store, &checkpoint_store, &committee)
+                    .await?;
+                timer.step("install_state_snapshot");
+            }
+        }
 
         let index_store = if is_validator {
             None
@@ -108,6 +176,7 @@ impl SuiNode {
                 None,
             )))
         };
+        timer.step("open_indexes");
 
         let event_store = if config.enable_event_processing {
             let path = config.db_path().join("events.db");
@@ -117,6 +186,7 @@ impl SuiNode {
         } else {
             None
         };
+        timer.step("open_event_store");
 
         let (tx_reconfigure_consensus, rx_reconfigure_consensus) = channel(100);
 
@@ -124,27 +194,89 @@ impl SuiNode {
             .websocket_address
             .map(|_| Arc::new(TransactionStreamer::new()));
 
-        let state = Arc::new(
-            AuthorityState::new(
-                config.protocol_public_key(),
-                secret,
-                store,
-                epoch_store.clone(),
-                index_store.clone(),
-                event_store,
-                transaction_streamer,
-                Some(checkpoint_store),
-                genesis,
-                &prometheus_registry,
-                tx_reconfigure_consensus,
-            )
-            .await,
-        );
+        let mut state = AuthorityState::new(
+            config.protocol_public_key(),
+            secret,
+            store,
+            epoch_store.clone(),
+            index_store.clone(),
+            event_store,
+            transaction_streamer,
+            Some(checkpoint_store),
+            genesis,
+            &prometheus_registry,
+            tx_reconfigure_consensus,
+        )
+        .await;
+        state.schedule_execution_engines(&config.execution_engine_activations)?;
+        let state = Arc::new(state);
+        // Loading the framework natives, replaying the recovery log and catching the
+        // checkpoints up with the executed batches
+        timer.step("load_authority_state");
+
+        state
+            .performance
+            .persist_to(&config.db_path().join("performance_reports"))?;
+        if let Some(cross_check) = &config.execution_cross_check {
+            warn!("Executing every certificate twice to check that execution is deterministic");
+            state.enable_execution_cross_check(cross_check.clone())?;
+        }
+        let heartbeat_handle =
+            Heartbeat::new(config.db_path().join("heartbeat"), state.clone()).spawn();
+        let checkpoint_export_handle = match &config.checkpoint_export {
+            Some(store_config) => {
+                let store = make_object_store(store_config)?;
+                Some(CheckpointWriter::new(state.clone(), store).spawn())
+            }
+            None => None,
+        };
+        let snapshot_export_handle = match &config.state_snapshot {
+            Some(snapshot_config) => match snapshot_config.export_interval {
+                Some(0) => bail!("The state snapshot export interval must be non-zero"),
+                Some(interval) => {
+                    let store = make_object_store(&snapshot_config.store)?;
+                    Some(StateSnapshotWriter::new(state.clone(), store, interval).spawn())
+                }
+                None => None,
+            },
+            None => None,
+        };
+        let health_handle = config.health.as_ref().and_then(|health_config| {
+            HealthCheck::new(state.clone(), health_config.clone(), is_validator).spawn()
+        });
+        let pruner_handle = match &config.pruning {
+            Some(pruning_config) => {
+                pruning_config.validate()?;
+                Some(
+                    AuthorityStorePruner::new(
+                        state.clone(),
+                        pruning_config.clone(),
+                        &prometheus_registry,
+                    )
+                    .spawn(),
+                )
+            }
+            None => None,
+        };
+
+        let store_statistics_handle = config
+            .storage
+            .as_ref()
+            .and_then(|storage_config| storage_config.metrics_interval_secs)
+            .map(|interval_secs| {
+                StoreStatisticsExporter::new(
+                    state.db(),
+                    Duration::from_secs(interval_secs),
+                    &prometheus_registry,
+                )
+                .spawn()
+            });
 
         let mut net_config = mysten_network::config::Config::new();
         net_config.connect_timeout = Some(Duration::from_secs(5));
         net_config.request_timeout = Some(Duration::from_secs(5));
         net_config.http2_keepalive_interval = Some(Duration::from_secs(5));
+        let net_config = Arc::new(net_config);
 
         let sui_system_state = state.get_sui_system_state_object().await?;
 
@@ -153,23 +285,27 @@ impl SuiNode {
         let authority_clients = if config.enable_reconfig && sui_system_state.epoch > 0 {
             make_network_authority_client_sets_from_system_state(
                 &sui_system_state,
-                &net_config,
+                net_config.clone(),
                 network_metrics.clone(),
             )
         } else {
             make_network_authority_client_sets_from_genesis(
                 genesis,
-                &net_config,
+                net_config.clone(),
                 network_metrics.clone(),
             )
         }?;
-        let net = AuthorityAggregator::new(
+        let mut net = AuthorityAggregator::new(
             state.clone_committee(),
             epoch_store,
             authority_clients,
             AuthAggMetrics::new(&prometheus_registry),
             SafeClientMetrics::new(&prometheus_registry),
         );
+        if let Some(batching_config) = config.request_batching {
+            net = net.with_batching(batching_config);
+        }
+        timer.step("connect_committee");
 
         let quorum_driver_handler = if is_full_node {
             Some(QuorumDriverHandler::new(
@@ -194,21 +330,25 @@ impl SuiNode {
         )?);
 
         let gossip_handle = if is_full_node {
-            info!("Starting full node sync to latest checkpoint (this may take a while)");
-            let now = Instant::now();
-            if let Err(err) = active_authority.sync_to_latest_checkpoint().await {
-                error!(
-                    "Full node failed to catch up to latest checkpoint: {:?}",
-                    err
-                );
-            } else {
-                info!(
-                    "Full node caught up to latest checkpoint in {:?}",
-                    now.elapsed()
-                );
-            }
-            active_authority.clone().spawn_node_sync_process().await;
-            None
+            // Catching up can take a while, so it runs in the background rather than delaying
+            // the servers, which serve the state synced so far in the meantime
+            let active_authority = active_authority.clone();
+            Some(tokio::spawn(async move {
+                info!("Starting full node sync to latest checkpoint (this may take a while)");
+                let now = Instant::now();
+                if let Err(err) = active_authority.sync_to_latest_checkpoint().await {
+                    error!(
+                        "Full node failed to catch up to latest checkpoint: {:?}",
+                        err
+                    );
+                } else {
+                    info!(
+                        "Full node caught up to latest checkpoint in {:?}",
+                        now.elapsed()
+                    );
+                }
+                active_authority.spawn_node_sync_process().await;
+            }))
         } else if config.enable_gossip {
             // TODO: get degree from config file.
             let degree = 4;
@@ -231,6 +371,8 @@ impl SuiNode {
             None
         };
 
+        timer.step("start_sync");
+
         let batch_subsystem_handle = {
             // Start batch system so that this node can be followed
             let batch_state = state.clone();
@@ -265,44 +407,111 @@ impl SuiNode {
             None
         };
 
-        let grpc_server = {
-            let mut server_conf = mysten_network::config::Config::new();
-            server_conf.global_concurrency_limit = config.grpc_concurrency_limit;
-            server_conf.load_shed = config.grpc_load_shed;
-            let mut server_builder =
-                ServerBuilder::from_config(&server_conf, GrpcMetrics::new(&prometheus_registry));
-
-            if let Some(validator_service) = validator_service {
-                server_builder =
-                    server_builder.add_service(ValidatorServer::new(validator_service));
-            }
+        let fd_monitor = FdMonitor::new(config, &prometheus_registry);
+        let fd_guard = fd_monitor.guard();
+        let fd_monitor_config = fd_monitor.handle();
+        let fd_monitor_handle = fd_monitor.spawn();
 
-            let server = server_builder
-                .bind(config.network_address())
-                .await
+        let grpc_server = {
+            // The server accepts its connections itself, so that those beyond the cap are closed
+            // before they take a descriptor for the lifetime of a connection.
+            let address = mysten_network::multiaddr::to_socket_addr(config.network_address())
                 .map_err(|err| anyhow!(err.to_string()))?;
-            let local_addr = server.local_addr();
+            let listener = tokio::net::TcpListener::bind(address).await?;
+            let local_addr = listener.local_addr()?;
+            let incoming = connection_limit::limit_connections(
+                listener,
+                fd_monitor::grpc_connection_limit(&config.connection_limits),
+                &prometheus_registry,
+            );
+
+            let layers = tower::ServiceBuilder::new()
+                .option_layer(
+                    config
+                        .grpc_load_shed
+                        .unwrap_or_default()
+                        .then(tower::load_shed::LoadShedLayer::new),
+                )
+                .option_layer(
+                    config
+                        .grpc_concurrency_limit
+                        .map(tower::limit::ConcurrencyLimitLayer::new),
+                )
+                .layer(GrpcMetricsLayer(GrpcMetrics::new(&prometheus_registry)))
+                .into_inner();
+
+            // The health service checks the whole server by default. The Validator service is
+            // reported on by name too, and the reflection service describes both to tools like
+            // grpcurl.
+            let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+            let validator_service = match validator_service {
+                Some(validator_service) => {
+                    health_reporter
+                        .set_serving::<ValidatorServer<ValidatorService>>()
+                        .await;
+                    Some(ValidatorServer::with_interceptor(
+                        validator_service,
+                        move |request| fd_guard.check(request),
+                    ))
+                }
+                None => None,
+            };
+            let router = tonic::transport::Server::builder()
+                .layer(layers)
+                .add_service(health_service)
+                .add_optional_service(validator_service)
+                .add_service(reflection_service().map_err(|err| anyhow!(err.to_string()))?);
+
             info!("Listening to traffic on {local_addr}");
-            tokio::spawn(server.serve().map_err(Into::into))
+            tokio::spawn(
+                router
+                    .serve_with_incoming(incoming)
+                    .map_err(anyhow::Error::from),
+            )
         };
+        timer.step("bind_grpc_server");
 
-        let (json_rpc_service, ws_subscription_service) = build_http_servers(
+        let (json_rpc_service, ws_subscription_service, rpc_policy) = build_http_servers(
             state.clone(),
             &quorum_driver_handler,
             config,
             &prometheus_registry,
         )
         .await?;
+        timer.step("start_json_rpc_servers");
+
+        // Like the JSON-RPC servers, only served by fullnodes
+        let transaction_submit_handle =
+            match (config.transaction_submit_address, &quorum_driver_handler) {
+                (Some(address), Some(quorum_driver_handler)) => {
+                    Some(transaction_submit::start_transaction_submit_server(
+                        address,
+                        state.clone(),
+                        quorum_driver_handler.clone_quorum_driver(),
+                    )?)
+                }
+                _ => None,
+            };
 
         let node = Self {
             grpc_server,
-            _json_rpc_service: json_rpc_service,
-            _ws_subscription_service: ws_subscription_service,
-            _gossip_handle: gossip_handle,
-            _execute_driver_handle: execute_driver_handle,
-            _checkpoint_process_handle: checkpoint_process_handle,
-            _batch_subsystem_handle: batch_subsystem_handle,
-            _post_processing_subsystem_handle: post_processing_subsystem_handle,
+            json_rpc_service,
+            ws_subscription_service,
+            gossip_handle,
+            execute_driver_handle,
+            checkpoint_process_handle,
+            fd_monitor_handle,
+            heartbeat_handle,
+            checkpoint_export_handle,
+            snapshot_export_handle,
+            pruner_handle,
+            health_handle,
+            store_statistics_handle,
+            transaction_submit_handle,
+            rpc_policy,
+            fd_monitor: fd_monitor_config,
+            batch_subsystem_handle,
+            post_processing_subsystem_handle,
             state,
             active: active_authority,
             quorum_driver_handler,
@@ -310,6 +519,7 @@ impl SuiNode {
         };
 
         info!("SuiNode started!");
+        timer.finish();
 
         Ok(node)
     }
@@ -339,11 +549,117 @@ impl SuiNode {
     }
 
     //TODO watch/wait on all the components
-    pub async fn wait(self) -> Result<()> {
-        self.grpc_server.await??;
+    pub async fn wait(&mut self) -> Result<()> {
+        (&mut self.grpc_server).await??;
 
         Ok(())
     }
+
+    /// Applies the settings of `config` which can change while the node runs: the policy of the
+    /// JSON-RPC server, i.e. its rate limits, methods and request timeout, and the thresholds of
+    /// the file descriptor monitor. The log filter is reloaded by its owner, see `main`. The other
+    /// settings take a restart.
+    pub fn reload_config(&self, config: &NodeConfig) {
+        let policy = rpc_policy(&config.json_rpc_server);
+        match &self.rpc_policy {
+            Some(rpc_policy) => rpc_policy.update(policy),
+            None if !policy.is_empty() => {
+                warn!("The JSON-RPC server started without limits, restart the node to apply them")
+            }
+            None => {}
+        }
+        self.fd_monitor.update(&config.connection_limits);
+        info!("Reloaded the node config");
+    }
+
+    /// Shuts the node down without leaving a certificate half executed: stops accepting
+    /// transactions and requests, waits up to `drain_timeout` for the certificates being executed,
+    /// stops the background tasks and syncs the stores to disk. The certificates still executing
+    /// after the timeout are recovered from the write-ahead log on restart.
+    pub async fn shutdown(self, drain_timeout: Duration) -> Result<()> {
+        info!("Shutting down sui-node");
+        self.state.halt_validator();
+        self.grpc_server.abort();
+        if let Some(json_rpc_service) = self.json_rpc_service {
+            if let Err(err) = json_rpc_service.stop() {
+                warn!("Failed to stop the JSON-RPC server: {err}");
+            }
+        }
+        if let Some(ws_subscription_service) = self.ws_subscription_service {
+            if let Err(err) = ws_subscription_service.stop() {
+                warn!("Failed to stop the websocket server: {err}");
+            }
+        }
+        if let Some(handle) = self.transaction_submit_handle {
+            handle.abort();
+        }
+
+        let store = self.state.db();
+        let deadline = Instant::now() + drain_timeout;
+        loop {
+            let in_flight = store.wal.in_flight_txes();
+            if in_flight == 0 {
+                break;
+            }
+            if Instant::now() >= deadline {
+                warn!(in_flight, "Shutting down with certificates still executing");
+                break;
+            }
+            tokio::time::sleep(SHUTDOWN_DRAIN_POLL_INTERVAL).await;
+        }
+
+        self.execute_driver_handle.abort();
+        self.batch_subsystem_handle.abort();
+        self.heartbeat_handle.abort();
+        for handle in [
+            self.gossip_handle,
+            self.checkpoint_process_handle,
+            self.fd_monitor_handle,
+            self.checkpoint_export_handle,
+            self.snapshot_export_handle,
+            self.pruner_handle,
+            self.health_handle,
+            self.store_statistics_handle,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            handle.abort();
+        }
+        if let Some(handle) = self.post_processing_subsystem_handle {
+            handle.abort();
+        }
+
+        store.flush()?;
+        if let Some(checkpoints) = &self.state.checkpoints {
+            checkpoints.lock().flush()?;
+        }
+        info!("sui-node shut down");
+        Ok(())
+    }
+}
+
+/// Installs the latest state snapshot of `snapshot_store` in the empty stores of a new node, which
+/// then syncs the checkpoints after the one of the snapshot.
+async fn install_latest_snapshot(
+    snapshot_store: &dyn ObjectStore,
+    store: &AuthorityStore,
+    checkpoint_store: &Mutex<CheckpointStore>,
+    committee: &Committee,
+) -> Result<()> {
+    let snapshot = match read_latest_snapshot(snapshot_store).await? {
+        Some(snapshot) => snapshot,
+        None => {
+            warn!("No state snapshot to bootstrap from, syncing from genesis");
+            return Ok(());
+        }
+    };
+    info!(
+        checkpoint = snapshot.summary.summary.checkpoint,
+        "Bootstrapping from state snapshot"
+    );
+    install_state_snapshot(store, checkpoint_store, &snapshot, committee).await?;
+    Ok(())
 }
 
 pub async fn build_http_servers(
@@ -351,45 +667,79 @@ pub async fn build_http_servers(
     quorum_driver_handler: &Option<QuorumDriverHandler<NetworkAuthorityClient>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
-) -> Result<(Option<HttpServerHandle>, Option<WsServerHandle>)> {
+) -> Result<(
+    Option<HttpServerHandle>,
+    Option<WsServerHandle>,
+    Option<RpcPolicyHandle>,
+)> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
-        return Ok((None, None));
+        return Ok((None, None, None));
     }
 
     if cfg!(msim) {
         // jsonrpsee uses difficult-to-support features such as TcpSocket::from_raw_fd(), so we
         // can't yet run it in the simulator.
         warn!("disabling http servers in simulator");
-        return Ok((None, None));
+        return Ok((None, None, None));
     }
 
-    let mut server = JsonRpcServerBuilder::new(false, prometheus_registry)?;
+    let server_config = &config.json_rpc_server;
+    let mut server = JsonRpcServerBuilder::new(false, prometheus_registry)?
+        .with_cors(
+            server_config.cors_allowed_origins.as_deref(),
+            server_config.cors_allowed_headers.as_deref(),
+        )?
+        .with_batch_requests(server_config.batch_requests);
+    if let Some(max_size) = server_config.max_request_body_size {
+        server = server.with_max_request_body_size(max_size);
+    }
+    if server_config.access_log {
+        server = server.with_access_log();
+    }
+    if server_config.checksummed_addresses {
+        set_checksummed_addresses(true);
+    }
+    server = server.with_policy(rpc_policy(server_config));
 
     server.register_module(ReadApi::new(state.clone()))?;
     server.register_module(FullNodeApi::new(state.clone()))?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
+    server.register_module(HotObjectsApiImpl::new(state.clone()))?;
 
     if let Some(quorum_driver_handler_) = quorum_driver_handler {
         server.register_module(FullNodeQuorumDriverApi::new(
             quorum_driver_handler_.clone_quorum_driver(),
             state.module_cache.clone(),
         ))?;
+        server.register_module(GasPriceApiImpl::new(
+            state.clone(),
+            quorum_driver_handler_.clone_quorum_driver(),
+        ))?;
     }
 
     if let Some(event_handler) = state.event_handler.clone() {
         server.register_module(EventReadApiImpl::new(state.clone(), event_handler))?;
     }
 
-    let rpc_server_handle = server
-        .start(config.json_rpc_address)
-        .await?
+    let rpc_server = server.start(config.json_rpc_address).await?;
+    let rpc_policy = rpc_server.policy_handle();
+    let rpc_server_handle = rpc_server
         .into_http_server_handle()
         .expect("Expect a http server handle");
 
     let ws_server_handle = match config.websocket_address {
         Some(ws_addr) => {
-            let mut server = JsonRpcServerBuilder::new(true, prometheus_registry)?;
+            let mut server = JsonRpcServerBuilder::new(true, prometheus_registry)?
+                .with_cors(
+                    server_config.cors_allowed_origins.as_deref(),
+                    server_config.cors_allowed_headers.as_deref(),
+                )?
+                .with_max_connections(config.connection_limits.max_websocket_connections)
+                .with_batch_requests(server_config.batch_requests);
+            if let Some(max_size) = server_config.max_request_body_size {
+                server = server.with_max_request_body_size(max_size);
+            }
             if let Some(tx_streamer) = state.transaction_streamer.clone() {
                 server.register_module(TransactionStreamingApiImpl::new(
                     state.clone(),
@@ -411,5 +761,26 @@ pub async fn build_http_servers(
         }
         None => None,
     };
-    Ok((Some(rpc_server_handle), ws_server_handle))
+    Ok((Some(rpc_server_handle), ws_server_handle, rpc_policy))
+}
+
+fn rpc_policy(config: &JsonRpcServerConfig) -> RpcPolicy {
+    let rate_limit = |limit: &RateLimitConfig| RateLimit {
+        requests_per_second: limit.requests_per_second,
+        burst: limit.burst(),
+    };
+    RpcPolicy {
+        per_ip: config.rate_limit_per_ip.as_ref().map(rate_limit),
+        per_method: config
+            .method_rate_limits
+            .iter()
+            .map(|(method, limit)| (method.clone(), rate_limit(limit)))
+            .collect(),
+        allowed_methods: config
+            .allowed_methods
+            .as_ref()
+            .map(|methods| methods.iter().cloned().collect()),
+        denied_methods: config.denied_methods.iter().cloned().collect(),
+        request_timeout: config.request_timeout_ms.map(Duration::from_millis),
+    }
 }
@@ -8,10 +8,12 @@ use std::path::PathBuf;
 use std::time::Duration;
 use sui_config::{Config, NodeConfig};
 use sui_node::metrics;
+use sui_node::otlp::{self, LogFilterHandle};
 use sui_telemetry::send_telemetry_event;
+use tokio::signal::unix::{signal, SignalKind};
 use tokio::task;
 use tokio::time::sleep;
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case", version)]
@@ -42,10 +44,14 @@ const MEMORY_INCREASE_PROFILING_RATIO: f64 = 1.2;
 const MEMORY_PROFILING_INTERVAL_SECS: u64 = 300;
 const PROF_DUMP: &[u8] = b"prof.dump\0";
 
+// Time given to the certificates being executed to complete when shutting down
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
     let mut config = NodeConfig::load(&args.config_path)?;
+    config.load_key_pairs().await?;
 
     let prometheus_registry = metrics::start_prometheus_server(config.metrics_address);
     info!(
@@ -54,15 +60,29 @@ async fn main() -> Result<()> {
     );
 
     // Initialize logging
-    let (_guard, filter_handle) =
-        telemetry_subscribers::TelemetryConfig::new(env!("CARGO_BIN_NAME"))
-            .with_env()
-            .with_prom_registry(&prometheus_registry)
-            .init();
+    let mut _telemetry_guard = None;
+    let mut _otlp_guard = None;
+    let filter_handle = match &config.otlp {
+        Some(otlp_config) => {
+            let (guard, filter_handle) = otlp::init(otlp_config, env!("CARGO_BIN_NAME"))?;
+            _otlp_guard = Some(guard);
+            filter_handle
+        }
+        None => {
+            let (guard, filter_handle) =
+                telemetry_subscribers::TelemetryConfig::new(env!("CARGO_BIN_NAME"))
+                    .with_env()
+                    .with_prom_registry(&prometheus_registry)
+                    .init();
+            _telemetry_guard = Some(guard);
+            LogFilterHandle::Telemetry(filter_handle)
+        }
+    };
 
     if let Some(listen_address) = args.listen_address {
         config.network_address = listen_address;
     }
+    reload_log_filter(&filter_handle, &config);
 
     #[cfg(not(target_env = "msvc"))]
     {
@@ -130,10 +150,39 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
+    let mut node = sui_node::SuiNode::start(&config, prometheus_registry).await?;
 
-    let node = sui_node::SuiNode::start(&config, prometheus_registry).await?;
-    node.wait().await?;
+    sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        filter_handle.clone(),
+        node.state(),
+    );
 
-    Ok(())
+    // SIGTERM and Ctrl-C shut the node down, SIGHUP reloads its config
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    loop {
+        tokio::select! {
+            result = node.wait() => return result,
+            _ = terminate.recv() => break,
+            _ = tokio::signal::ctrl_c() => break,
+            _ = hangup.recv() => match NodeConfig::load(&args.config_path) {
+                Ok(config) => {
+                    reload_log_filter(&filter_handle, &config);
+                    node.reload_config(&config);
+                }
+                Err(err) => error!("Failed to reload the node config: {err}"),
+            },
+        }
+    }
+    node.shutdown(SHUTDOWN_DRAIN_TIMEOUT).await
+}
+
+fn reload_log_filter(filter_handle: &LogFilterHandle, config: &NodeConfig) {
+    if let Some(filter) = &config.log_filter {
+        match filter_handle.update(filter) {
+            Ok(()) => info!(filter =% filter, "Log filter reloaded"),
+            Err(err) => error!("Invalid log filter {filter}: {err}"),
+        }
+    }
 }
@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use axum::{extract::Extension, http::StatusCode, routing::get, Router};
-use mysten_network::metrics::MetricsCallbackProvider;
+use futures::future::BoxFuture;
 use prometheus::{
     register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
-    IntGaugeVec, Registry, TextEncoder,
+    IntGauge, IntGaugeVec, Registry, TextEncoder,
 };
 use std::net::SocketAddr;
-use std::time::Duration;
-use sui_network::tonic::Code;
+use std::task::{Context, Poll};
+use sui_network::tonic::codegen::http;
+use tower::{Layer, Service};
 
 use tracing::warn;
 
@@ -77,23 +78,64 @@ impl GrpcMetrics {
     }
 }
 
-impl MetricsCallbackProvider for GrpcMetrics {
-    fn on_request(&self, _path: String) {}
-    fn on_response(
-        &self,
-        _path: String,
-        _latency: Duration,
-        _status: u16,
-        _grpc_status_code: Code,
-    ) {
+/// Counts the requests a gRPC server serves, and those in flight, per route.
+#[derive(Clone)]
+pub struct GrpcMetricsLayer(pub GrpcMetrics);
+
+impl<S> Layer<S> for GrpcMetricsLayer {
+    type Service = GrpcMetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMetricsService {
+            inner,
+            metrics: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcMetricsService<S> {
+    inner: S,
+    metrics: GrpcMetrics,
+}
+
+impl<S, B> Service<http::Request<B>> for GrpcMetricsService<S>
+where
+    S: Service<http::Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let in_flight = InFlight::new(&self.metrics, request.uri().path());
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let _in_flight = in_flight;
+            response.await
+        })
     }
+}
 
-    fn on_start(&self, path: &str) {
-        self.inflight_grpc.with_label_values(&[path]).inc();
-        self.grpc_requests.with_label_values(&[path]).inc();
+/// A request counted in flight on its route until dropped.
+struct InFlight(IntGauge);
+
+impl InFlight {
+    fn new(metrics: &GrpcMetrics, path: &str) -> Self {
+        metrics.grpc_requests.with_label_values(&[path]).inc();
+        let in_flight = metrics.inflight_grpc.with_label_values(&[path]);
+        in_flight.inc();
+        Self(in_flight)
     }
+}
 
-    fn on_drop(&self, path: &str) {
-        self.inflight_grpc.with_label_values(&[path]).dec();
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
     }
 }
@@ -0,0 +1,95 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the tracing spans of a node to an OpenTelemetry collector over OTLP gRPC.
+//!
+//! `telemetry_subscribers` only exports spans to a local Jaeger agent and can't be given another
+//! layer, so a node which exports over OTLP sets up its own subscriber: the same reloadable log
+//! filter and log output, plus the OTLP layer.
+
+use anyhow::{anyhow, bail, Result};
+use opentelemetry::sdk::trace::{self, Sampler};
+use opentelemetry::sdk::Resource;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use sui_config::node::OtlpConfig;
+use telemetry_subscribers::FilterHandle;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// The log filter of a node, whichever subscriber it was set up with.
+#[derive(Clone)]
+pub enum LogFilterHandle {
+    Telemetry(FilterHandle),
+    Otlp(reload::Handle<EnvFilter, Registry>),
+}
+
+impl LogFilterHandle {
+    pub fn get(&self) -> Result<String> {
+        match self {
+            Self::Telemetry(handle) => handle.get().map_err(|err| anyhow!("{err}")),
+            Self::Otlp(handle) => Ok(handle.with_current(|filter| filter.to_string())?),
+        }
+    }
+
+    pub fn update(&self, directives: &str) -> Result<()> {
+        match self {
+            Self::Telemetry(handle) => handle.update(directives).map_err(|err| anyhow!("{err}")),
+            Self::Otlp(handle) => Ok(handle.reload(EnvFilter::try_new(directives)?)?),
+        }
+    }
+}
+
+/// Flushes the spans not exported yet when dropped.
+pub struct OtlpGuard;
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// Sets up the global subscriber of the node, exporting its spans as configured by `config`.
+/// Must be called from a Tokio runtime, which the spans are exported in batches from.
+pub fn init(config: &OtlpConfig, service_name: &str) -> Result<(OtlpGuard, LogFilterHandle)> {
+    if !(0.0..=1.0).contains(&config.sampling_ratio) {
+        bail!(
+            "The OTLP sampling ratio must be between 0 and 1, not {}",
+            config.sampling_ratio
+        );
+    }
+
+    let mut attributes = vec![KeyValue::new("service.name", service_name.to_string())];
+    attributes.extend(
+        config
+            .resource_attributes
+            .iter()
+            .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+    );
+    // Spans whose parent was sampled are always sampled, so that traces are exported whole
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sampling_ratio)));
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.endpoint),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_sampler(sampler)
+                .with_resource(Resource::new(attributes)),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)?;
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, filter_handle) = reload::Layer::new(filter);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()?;
+
+    Ok((OtlpGuard, LogFilterHandle::Otlp(filter_handle)))
+}
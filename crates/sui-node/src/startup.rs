@@ -0,0 +1,107 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Times the steps of the startup of a node, e.g. opening the stores or binding the servers, so
+//! that slow restarts can be traced to the step they spend their time in. Each step is logged as
+//! it completes, and exported as a metric.
+
+use prometheus::{
+    register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntGauge, IntGaugeVec,
+    Registry,
+};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+pub struct StartupTimer {
+    start: Instant,
+    last: Instant,
+    steps: Vec<(&'static str, Duration)>,
+    step_duration_ms: IntGaugeVec,
+    startup_duration_ms: IntGauge,
+}
+
+impl StartupTimer {
+    pub fn new(registry: &Registry) -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last: now,
+            steps: vec![],
+            step_duration_ms: register_int_gauge_vec_with_registry!(
+                "node_startup_step_duration_ms",
+                "Time the last startup of the node spent in each step, in milliseconds",
+                &["step"],
+                registry,
+            )
+            .unwrap(),
+            startup_duration_ms: register_int_gauge_with_registry!(
+                "node_startup_duration_ms",
+                "Time the last startup of the node took, in milliseconds",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Records that `step` completed, having taken the time since the previous step.
+    pub fn step(&mut self, step: &'static str) {
+        let now = Instant::now();
+        let elapsed = now - self.last;
+        self.last = now;
+        info!(
+            step,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Startup step completed"
+        );
+        self.step_duration_ms
+            .with_label_values(&[step])
+            .set(elapsed.as_millis() as i64);
+        self.steps.push((step, elapsed));
+    }
+
+    /// Logs the total time of the startup, with the slowest steps first.
+    pub fn finish(mut self) {
+        let total = self.start.elapsed();
+        self.startup_duration_ms.set(total.as_millis() as i64);
+        self.steps.sort_by(|(_, a), (_, b)| b.cmp(a));
+        let breakdown: Vec<_> = self
+            .steps
+            .iter()
+            .map(|(step, elapsed)| format!("{step}: {elapsed:?}"))
+            .collect();
+        info!(
+            total_ms = total.as_millis() as u64,
+            "Node started in {:?} ({})",
+            total,
+            breakdown.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_timer() {
+        let registry = Registry::new();
+        let mut timer = StartupTimer::new(&registry);
+        std::thread::sleep(Duration::from_millis(50));
+        timer.step("slow");
+        timer.step("fast");
+
+        // Each step is timed from the end of the previous one
+        let steps: Vec<_> = timer.steps.iter().map(|(step, _)| *step).collect();
+        assert_eq!(steps, ["slow", "fast"]);
+        let (slow, fast) = (timer.steps[0].1, timer.steps[1].1);
+        assert!(slow >= Duration::from_millis(50));
+        assert!(fast < slow);
+        let step_ms = |step| timer.step_duration_ms.with_label_values(&[step]).get();
+        assert_eq!(step_ms("slow"), slow.as_millis() as i64);
+        assert_eq!(step_ms("fast"), fast.as_millis() as i64);
+
+        let startup_duration_ms = timer.startup_duration_ms.clone();
+        timer.finish();
+        assert!(startup_duration_ms.get() >= (slow + fast).as_millis() as i64);
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal HTTP endpoint submitting signed transactions, for relays and payment processors
+//! integrating without a JSON-RPC client. Unlike the JSON-RPC API, its requests and responses are
+//! versioned in the route and stay stable across releases.
+//!
+//! Submitting is idempotent: a transaction resubmitted after a timeout or by several relays gets
+//! the outcome of its single execution.
+
+use anyhow::Result;
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use sui_core::authority::AuthorityState;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::quorum_driver::QuorumDriver;
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages::{
+    ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
+    ExecutionStatus, Transaction,
+};
+use sui_types::sui_serde::{Base64, Encoding};
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+pub const SUBMIT_TRANSACTION_ROUTE: &str = "/v1/transactions";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubmitTransactionRequest {
+    /// Base64 encoded BCS bytes of the signed transaction, as printed by `sui client tx sign`.
+    pub transaction: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmitTransactionResponse {
+    pub digest: TransactionDigest,
+    pub status: SubmitTransactionStatus,
+    /// Why the transaction failed or was rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmitTransactionStatus {
+    /// Executed, its effects are final.
+    Success,
+    /// Executed but aborted, so that only its gas is charged. Final as well.
+    Failure,
+    /// Not certified by the validators, e.g. because one of its objects is locked by another
+    /// transaction. Nothing was executed, resubmitting it may succeed.
+    Rejected,
+}
+
+/// The body of the responses to requests without a valid signed transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SubmitTransactionError {
+    pub error: String,
+}
+
+/// Serves `POST /v1/transactions` on `address`, executing the transactions with `quorum_driver`.
+pub fn start_transaction_submit_server(
+    address: SocketAddr,
+    state: Arc<AuthorityState>,
+    quorum_driver: Arc<QuorumDriver<NetworkAuthorityClient>>,
+) -> Result<JoinHandle<()>> {
+    let app = Router::new()
+        .route(SUBMIT_TRANSACTION_ROUTE, post(submit_transaction))
+        .layer(Extension(state))
+        .layer(Extension(quorum_driver));
+
+    let server = axum::Server::try_bind(&address)?;
+    info!(address =% address, "starting transaction submit server");
+
+    Ok(tokio::spawn(async move {
+        server.serve(app.into_make_service()).await.unwrap();
+    }))
+}
+
+async fn submit_transaction(
+    Extension(state): Extension<Arc<AuthorityState>>,
+    Extension(quorum_driver): Extension<Arc<QuorumDriver<NetworkAuthorityClient>>>,
+    Json(request): Json<SubmitTransactionRequest>,
+) -> Response {
+    let transaction = match decode_transaction(&request.transaction) {
+        Ok(transaction) => transaction,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(SubmitTransactionError {
+                    error: err.to_string(),
+                }),
+            )
+                .into_response()
+        }
+    };
+    let digest = *transaction.digest();
+
+    // A transaction this node already executed is not sent to the validators again
+    if let Ok((_, effects)) = state.get_transaction(digest).await {
+        debug!(?digest, "transaction resubmitted after its execution");
+        return Json(executed(digest, &effects.status)).into_response();
+    }
+
+    // The validators answer a transaction they already certified or executed with its
+    // certificate and effects, so that concurrent submissions get the same outcome
+    let response = quorum_driver
+        .execute_transaction(ExecuteTransactionRequest {
+            transaction,
+            request_type: ExecuteTransactionRequestType::WaitForEffectsCert,
+        })
+        .await;
+    match response {
+        Ok(ExecuteTransactionResponse::EffectsCert(response)) => {
+            let (_, effects) = *response;
+            Json(executed(digest, &effects.effects.status)).into_response()
+        }
+        Ok(other) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(SubmitTransactionError {
+                error: format!("Expected the effects of the transaction, got {:?}", other),
+            }),
+        )
+            .into_response(),
+        Err(err) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(SubmitTransactionResponse {
+                digest,
+                status: SubmitTransactionStatus::Rejected,
+                error: Some(err.to_string()),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+/// Decodes a signed transaction and checks its signature, so that requests which can never
+/// succeed are answered without reaching the validators.
+fn decode_transaction(transaction: &str) -> Result<Transaction> {
+    let transaction: Transaction = bcs::from_bytes(&Base64::decode(transaction)?)?;
+    transaction.verify()?;
+    Ok(transaction)
+}
+
+fn executed(digest: TransactionDigest, status: &ExecutionStatus) -> SubmitTransactionResponse {
+    match status {
+        ExecutionStatus::Success => SubmitTransactionResponse {
+            digest,
+            status: SubmitTransactionStatus::Success,
+            error: None,
+        },
+        ExecutionStatus::Failure { error } => SubmitTransactionResponse {
+            digest,
+            status: SubmitTransactionStatus::Failure,
+            error: Some(error.to_string()),
+        },
+    }
+}
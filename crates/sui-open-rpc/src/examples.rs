@@ -17,9 +17,10 @@ use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
     GatewayTxSeqNumber, MoveCallParams, OwnedObjectRef, RPCTransactionRequestParams,
     SuiCertifiedTransaction, SuiData, SuiEvent, SuiEventEnvelope, SuiExecutionStatus,
-    SuiGasCostSummary, SuiObject, SuiObjectRead, SuiObjectRef, SuiParsedData, SuiPastObjectRead,
-    SuiRawData, SuiRawMoveObject, SuiTransactionData, SuiTransactionEffects,
-    SuiTransactionResponse, TransactionBytes, TransferObjectParams,
+    SuiGasCostSummary, SuiObject, SuiObjectInfo, SuiObjectRead, SuiObjectRef, SuiObjectsPage,
+    SuiParsedData, SuiPastObjectRead, SuiRawData, SuiRawMoveObject, SuiTransactionData,
+    SuiTransactionEffects, SuiTransactionResponse, SuiTransactionsPage, TransactionBytes,
+    TransferObjectParams,
 };
 use sui_open_rpc::ExamplePairing;
 use sui_types::base_types::{
@@ -27,7 +28,7 @@ use sui_types::base_types::{
 };
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, Signature};
 use sui_types::crypto::{AuthorityQuorumSignInfo, SuiSignature};
-use sui_types::event::TransferType;
+use sui_types::event::{EventID, TransferType};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
     CallArg, MoveCall, SingleTransactionKind, Transaction, TransactionData, TransactionKind,
@@ -267,7 +268,13 @@ impl RpcExampleProvider {
                 owner: Owner::AddressOwner(owner),
                 previous_transaction: TransactionDigest::new(self.rng.gen()),
             })
+            .map(SuiObjectInfo::from)
             .collect::<Vec<_>>();
+        let result = SuiObjectsPage {
+            data: result,
+            next_cursor: None,
+            has_next_page: false,
+        };
 
         Examples::new(
             "sui_getObjectsOwnedByAddress",
@@ -289,7 +296,13 @@ impl RpcExampleProvider {
                 owner: Owner::ObjectOwner(SuiAddress::from(owner)),
                 previous_transaction: TransactionDigest::new(self.rng.gen()),
             })
+            .map(SuiObjectInfo::from)
             .collect::<Vec<_>>();
+        let result = SuiObjectsPage {
+            data: result,
+            next_cursor: None,
+            has_next_page: false,
+        };
 
         Examples::new(
             "sui_getObjectsOwnedByObject",
@@ -373,7 +386,7 @@ impl RpcExampleProvider {
     }
 
     fn get_transactions_by_input_object(&mut self) -> Examples {
-        let result = self.get_transaction_digests(5..8);
+        let result = self.transactions_page(5..8);
         Examples::new(
             "sui_getTransactionsByInputObject",
             vec![ExamplePairing::new(
@@ -385,7 +398,7 @@ impl RpcExampleProvider {
     }
 
     fn get_transactions_by_move_function(&mut self) -> Examples {
-        let result = self.get_transaction_digests(6..10);
+        let result = self.transactions_page(6..10);
         Examples::new(
             "sui_getTransactionsByMoveFunction",
             vec![ExamplePairing::new(
@@ -401,7 +414,7 @@ impl RpcExampleProvider {
     }
 
     fn get_transactions_by_mutated_object(&mut self) -> Examples {
-        let result = self.get_transaction_digests(5..8);
+        let result = self.transactions_page(5..8);
         Examples::new(
             "sui_getTransactionsByMutatedObject",
             vec![ExamplePairing::new(
@@ -413,7 +426,7 @@ impl RpcExampleProvider {
     }
 
     fn get_transactions_from_address(&mut self) -> Examples {
-        let result = self.get_transaction_digests(5..8);
+        let result = self.transactions_page(5..8);
         Examples::new(
             "sui_getTransactionsFromAddress",
             vec![ExamplePairing::new(
@@ -440,7 +453,7 @@ impl RpcExampleProvider {
     }
 
     fn get_transactions_to_address(&mut self) -> Examples {
-        let result = self.get_transaction_digests(5..8);
+        let result = self.transactions_page(5..8);
         Examples::new(
             "sui_getTransactionsToAddress",
             vec![ExamplePairing::new(
@@ -464,6 +477,14 @@ impl RpcExampleProvider {
             .collect()
     }
 
+    fn transactions_page(&mut self, range: Range<u64>) -> SuiTransactionsPage {
+        SuiTransactionsPage {
+            data: self.get_transaction_digests(range),
+            next_cursor: None,
+            has_next_page: false,
+        }
+    }
+
     fn get_transfer_data_response(
         &mut self,
     ) -> (
@@ -505,6 +526,10 @@ impl RpcExampleProvider {
         let events = vec![SuiEventEnvelope {
             timestamp: std::time::Instant::now().elapsed().as_secs(),
             tx_digest: Some(*tx_digest),
+            id: EventID {
+                tx_seq_num: 10,
+                event_num: 0,
+            },
             event: sui_event.clone(),
         }];
         let result = SuiTransactionResponse {
@@ -512,6 +537,7 @@ impl RpcExampleProvider {
                 transaction_digest: *tx_digest,
                 data: SuiTransactionData::try_from(data.clone()).unwrap(),
                 tx_signature: signature.clone(),
+                sponsor_signature: None,
                 auth_sign_info: AuthorityQuorumSignInfo {
                     epoch: 0,
                     signature: Default::default(),
@@ -524,6 +550,8 @@ impl RpcExampleProvider {
                     computation_cost: 100,
                     storage_cost: 100,
                     storage_rebate: 10,
+                    event_storage_cost: 10,
+                    effects_storage_cost: 20,
                 },
                 shared_objects: vec![],
                 transaction_digest: TransactionDigest::new(self.rng.gen()),
@@ -550,6 +578,7 @@ impl RpcExampleProvider {
             },
             timestamp_ms: None,
             parsed_data: None,
+            memo: None,
         };
 
         (data, signature, recipient, obj_id, result, events)
@@ -652,6 +681,10 @@ impl RpcExampleProvider {
         let event = SuiEventEnvelope {
             timestamp: ts,
             tx_digest: Some(*tx.digest()),
+            id: EventID {
+                tx_seq_num: 10,
+                event_num: 0,
+            },
             event: SuiEvent::MoveEvent {
                 package_id: ObjectID::from_hex_literal("0x2").unwrap(),
                 transaction_module: String::from("devnet_nft"),
@@ -659,6 +692,7 @@ impl RpcExampleProvider {
                 type_: String::from("0x2::devnet_nft::MintNFTEvent"),
                 fields: None,
                 bcs: vec![],
+                schema: None,
             },
         };
         Examples::new(
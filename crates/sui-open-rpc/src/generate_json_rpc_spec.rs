@@ -27,7 +27,9 @@ use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::RpcTransactionBuilderClient;
 use sui_json_rpc::api::WalletSyncApiClient;
 use sui_json_rpc::bcs_api::BcsApiImpl;
+use sui_json_rpc::gas_price_api::GasPriceApiImpl;
 use sui_json_rpc::gateway_api::{GatewayWalletSyncApiImpl, RpcGatewayImpl, TransactionBuilderImpl};
+use sui_json_rpc::hot_objects_api::HotObjectsApiImpl;
 use sui_json_rpc::read_api::{FullNodeApi, ReadApi};
 use sui_json_rpc::sui_rpc_doc;
 use sui_json_rpc::SuiRpcModule;
@@ -83,6 +85,8 @@ async fn main() {
     open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(EventReadApiOpenRpc::module_doc());
     open_rpc.add_module(GatewayWalletSyncApiImpl::rpc_doc_module());
+    open_rpc.add_module(GasPriceApiImpl::rpc_doc_module());
+    open_rpc.add_module(HotObjectsApiImpl::rpc_doc_module());
 
     open_rpc.add_examples(RpcExampleProvider::new().examples());
 
@@ -172,8 +176,9 @@ async fn create_response_sample() -> Result<
         network.http_client.sync_account_state(account).await?;
         let objects: Vec<SuiObjectInfo> = network
             .http_client
-            .get_objects_owned_by_address(account)
-            .await?;
+            .get_objects_owned_by_address(account, None, None)
+            .await?
+            .data;
         owned_objects.insert(account, objects);
     }
 
@@ -250,10 +255,11 @@ async fn create_transfer_response(
     coins: &[SuiObjectInfo],
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
     let response = SuiClientCommands::Transfer {
-        to: address,
+        to: address.into(),
         object_id: coins.first().unwrap().object_id,
         gas: None,
         gas_budget: 1000,
+        memo: None,
     }
     .execute(context)
     .await?;
@@ -263,6 +269,7 @@ async fn create_transfer_response(
             effects,
             timestamp_ms: None,
             parsed_data: None,
+            memo: None,
         })
     } else {
         panic!()
@@ -275,10 +282,11 @@ async fn create_transfer_sui_response(
     coins: &[SuiObjectInfo],
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
     let response = SuiClientCommands::TransferSui {
-        to: address,
+        to: address.into(),
         sui_coin_object_id: coins.first().unwrap().object_id,
         gas_budget: 1000,
         amount: Some(10),
+        memo: None,
     }
     .execute(context)
     .await?;
@@ -288,6 +296,7 @@ async fn create_transfer_sui_response(
             effects,
             timestamp_ms: None,
             parsed_data: None,
+            memo: None,
         })
     } else {
         panic!()
@@ -457,6 +466,7 @@ async fn get_nft_response(
             effects,
             timestamp_ms: None,
             parsed_data: None,
+            memo: None,
         };
         Ok((tx, object))
     } else {
@@ -14,25 +14,130 @@ use std::fs;
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{
-    derive_key_pair_from_path, get_key_pair_from_rng, EncodeDecodeBase64, PublicKey, Signature,
-    SignatureScheme, SuiKeyPair,
+    derive_key_pair_from_path, get_key_pair_from_rng, validate_path, EncodeDecodeBase64, PublicKey,
+    Signature, SignatureScheme, SuiKeyPair, SuiSignature,
 };
 
+pub use keychain::KeychainKeystore;
+pub use ledger::{LedgerSigner, LedgerTransport};
+pub use remote_signer::{RemoteSignerConfig, RemoteSignerKeystore, SigningIntent};
+
+mod keychain;
+mod ledger;
+mod remote_signer;
+
 #[derive(Serialize, Deserialize)]
 #[non_exhaustive]
 // This will work on user signatures, but not suitable for authority signatures.
 pub enum KeystoreType {
     File(PathBuf),
     InMem(usize),
+    /// Keys stored in the OS keychain under this service name.
+    Keychain(String),
+    /// Keys held by an external signer.
+    RemoteSigner(RemoteSignerConfig),
 }
 
+/// Backend holding the keys of a [`SuiKeystore`].
 pub trait AccountKeystore: Send + Sync {
     fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error>;
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
     fn keys(&self) -> Vec<PublicKey>;
+
+    /// Adds a key derived from `mnemonic`, keeping the mnemonic for export if the backend can.
+    fn add_key_with_mnemonic(
+        &mut self,
+        keypair: SuiKeyPair,
+        _mnemonic: KeyMnemonic,
+    ) -> Result<(), anyhow::Error> {
+        self.add_key(keypair)
+    }
+
+    /// The mnemonic the key of `address` was derived from, if the backend kept it.
+    fn mnemonic(&self, _address: &SuiAddress) -> Option<KeyMnemonic> {
+        None
+    }
+}
+
+/// Signs for a single address with a key held outside of the keystore, e.g. on a hardware wallet.
+pub trait AccountSigner: Send + Sync {
+    fn public_key(&self) -> Result<PublicKey, anyhow::Error>;
+    fn sign(&self, msg: &[u8]) -> Result<Signature, signature::Error>;
+}
+
+/// Where the key of an address of the wallet is held instead of the keystore, written
+/// `ledger:<derivation-path>` in the wallet config for the key at that path of a Ledger device.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum SignerConfig {
+    Ledger(DerivationPath),
+}
+
+impl SignerConfig {
+    pub fn init(&self) -> Box<dyn AccountSigner> {
+        match self {
+            SignerConfig::Ledger(derivation_path) => {
+                Box::new(LedgerSigner::new(derivation_path.clone()))
+            }
+        }
+    }
+}
+
+impl FromStr for SignerConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("ledger", path)) => {
+                let path = path
+                    .parse()
+                    .map_err(|e| anyhow!("Invalid derivation path {path}: {e}"))?;
+                // The Sui app of Ledger devices holds ed25519 keys
+                let path = validate_path(&SignatureScheme::ED25519, Some(path))?;
+                Ok(SignerConfig::Ledger(path))
+            }
+            _ => Err(anyhow!(
+                "Invalid signer {s}, expected ledger:<derivation-path>"
+            )),
+        }
+    }
+}
+
+impl Display for SignerConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignerConfig::Ledger(derivation_path) => write!(f, "ledger:{}", derivation_path),
+        }
+    }
+}
+
+impl TryFrom<String> for SignerConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<SignerConfig> for String {
+    fn from(config: SignerConfig) -> Self {
+        config.to_string()
+    }
+}
+
+/// The BIP39 mnemonic a key was derived from, and how, which recovers the key in this or another
+/// wallet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyMnemonic {
+    pub phrase: String,
+    pub key_scheme: String,
+    /// None for the default path of the key scheme, m/44'/784'/0'/0'/0' for ed25519 or
+    /// m/54'/784'/0'/0/0 for secp256k1.
+    pub derivation_path: Option<String>,
 }
 
 impl KeystoreType {
@@ -42,6 +147,12 @@ impl KeystoreType {
             KeystoreType::InMem(initial_key_number) => {
                 SuiKeystore::from(InMemKeystore::new(*initial_key_number))
             }
+            KeystoreType::Keychain(service) => {
+                SuiKeystore::from(KeychainKeystore::load_or_create(service)?)
+            }
+            KeystoreType::RemoteSigner(config) => {
+                SuiKeystore::from(RemoteSignerKeystore::connect(config)?)
+            }
         })
     }
 }
@@ -59,6 +170,16 @@ impl Display for KeystoreType {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            KeystoreType::Keychain(service) => {
+                writeln!(writer, "Keystore Type : Keychain")?;
+                write!(writer, "Keychain Service : {}", service)?;
+                write!(f, "{}", writer)
+            }
+            KeystoreType::RemoteSigner(config) => {
+                writeln!(writer, "Keystore Type : Remote Signer")?;
+                write!(writer, "Signer URL : {}", config.url)?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -66,6 +187,7 @@ impl Display for KeystoreType {
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    mnemonics: BTreeMap<SuiAddress, KeyMnemonic>,
     path: Option<PathBuf>,
 }
 
@@ -89,6 +211,22 @@ impl AccountKeystore for FileBasedKeystore {
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
+
+    fn add_key_with_mnemonic(
+        &mut self,
+        keypair: SuiKeyPair,
+        mnemonic: KeyMnemonic,
+    ) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        self.keys.insert(address, keypair);
+        self.mnemonics.insert(address, mnemonic);
+        self.save()?;
+        Ok(())
+    }
+
+    fn mnemonic(&self, address: &SuiAddress) -> Option<KeyMnemonic> {
+        self.mnemonics.get(address).cloned()
+    }
 }
 
 impl FileBasedKeystore {
@@ -96,20 +234,24 @@ impl FileBasedKeystore {
         let keys = if path.exists() {
             let reader = BufReader::new(File::open(path)?);
             let kp_strings: Vec<String> = serde_json::from_reader(reader)?;
-            kp_strings
-                .iter()
-                .map(|kpstr| {
-                    let key = SuiKeyPair::decode_base64(kpstr);
-                    key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
-                })
-                .collect::<Result<BTreeMap<_, _>, _>>()
+            decode_key_pairs(&kp_strings)
                 .map_err(|e| anyhow::anyhow!("Invalid Keypair file {:#?} {:?}", e, path))?
         } else {
             BTreeMap::new()
         };
+        let mnemonics_path = mnemonics_path(path);
+        let mnemonics = if mnemonics_path.exists() {
+            let reader = BufReader::new(File::open(&mnemonics_path)?);
+            serde_json::from_reader(reader).map_err(|e| {
+                anyhow::anyhow!("Invalid mnemonics file {:?}: {}", mnemonics_path, e)
+            })?
+        } else {
+            BTreeMap::new()
+        };
 
         Ok(Self {
             keys,
+            mnemonics,
             path: Some(path.to_path_buf()),
         })
     }
@@ -120,15 +262,14 @@ impl FileBasedKeystore {
 
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
-            let store = serde_json::to_string_pretty(
-                &self
-                    .keys
-                    .values()
-                    .map(EncodeDecodeBase64::encode_base64)
-                    .collect::<Vec<_>>(),
-            )
-            .unwrap();
-            fs::write(path, store)?
+            fs::write(path, encode_key_pairs(&self.keys))?;
+            // Keystores without mnemonics keep their single file
+            if !self.mnemonics.is_empty() {
+                fs::write(
+                    mnemonics_path(path),
+                    serde_json::to_string_pretty(&self.mnemonics)?,
+                )?;
+            }
         }
         Ok(())
     }
@@ -138,15 +279,60 @@ impl FileBasedKeystore {
     }
 }
 
-pub struct SuiKeystore(Box<dyn AccountKeystore>);
+/// The file next to the keystore at `path` holding the mnemonics of its keys, e.g.
+/// `sui.keystore.mnemonics`, so that the keystore file keeps its format.
+fn mnemonics_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".mnemonics");
+    path.with_file_name(file_name)
+}
+
+/// Decodes key pairs stored as base64 strings.
+fn decode_key_pairs(
+    kp_strings: &[String],
+) -> Result<BTreeMap<SuiAddress, SuiKeyPair>, anyhow::Error> {
+    kp_strings
+        .iter()
+        .map(|kpstr| {
+            let key = SuiKeyPair::decode_base64(kpstr).map_err(|e| anyhow!("{e}"));
+            key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+        })
+        .collect()
+}
+
+/// Encodes key pairs as a JSON list of base64 strings, the format of keystore files.
+fn encode_key_pairs(keys: &BTreeMap<SuiAddress, SuiKeyPair>) -> String {
+    serde_json::to_string_pretty(
+        &keys
+            .values()
+            .map(EncodeDecodeBase64::encode_base64)
+            .collect::<Vec<_>>(),
+    )
+    .unwrap()
+}
+
+pub struct SuiKeystore {
+    keystore: Box<dyn AccountKeystore>,
+    /// Addresses whose key is held by a signer instead of the keystore.
+    signers: BTreeMap<SuiAddress, Box<dyn AccountSigner>>,
+}
 
 impl SuiKeystore {
     fn from<S: AccountKeystore + 'static>(keystore: S) -> Self {
-        Self(Box::new(keystore))
+        Self {
+            keystore: Box::new(keystore),
+            signers: BTreeMap::new(),
+        }
     }
 
     pub fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
-        self.0.add_key(keypair)
+        self.keystore.add_key(keypair)
+    }
+
+    /// Signs for `address` with `signer`, which is only checked to hold the key of the address
+    /// when signing, so that e.g. a hardware wallet doesn't need to be plugged in until then.
+    pub fn add_signer(&mut self, address: SuiAddress, signer: Box<dyn AccountSigner>) {
+        self.signers.insert(address, signer);
     }
 
     pub fn generate_new_key(
@@ -154,30 +340,51 @@ impl SuiKeystore {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
     ) -> Result<(SuiAddress, String, SignatureScheme), anyhow::Error> {
-        let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
+        self.generate_new_key_with_words(key_scheme, derivation_path, 12)
+    }
+
+    /// Generates a key from a new mnemonic of `word_count` words, 12, 15, 18, 21 or 24.
+    pub fn generate_new_key_with_words(
+        &mut self,
+        key_scheme: SignatureScheme,
+        derivation_path: Option<DerivationPath>,
+        word_count: usize,
+    ) -> Result<(SuiAddress, String, SignatureScheme), anyhow::Error> {
+        let words = MnemonicType::for_word_count(word_count)
+            .map_err(|e| anyhow!("Invalid mnemonic word count: {e}"))?;
+        let mnemonic = Mnemonic::new(words, Language::English);
+        let key_mnemonic = key_mnemonic(&mnemonic, key_scheme, &derivation_path);
         match derive_key_pair_from_path(
             Seed::new(&mnemonic, "").as_bytes(),
             derivation_path,
             &key_scheme,
         ) {
             Ok((address, keypair)) => {
-                self.add_key(keypair)?;
+                self.keystore.add_key_with_mnemonic(keypair, key_mnemonic)?;
                 Ok((address, mnemonic.phrase().to_string(), key_scheme))
             }
             Err(e) => Err(anyhow!("error generating key {:?}", e)),
         }
     }
 
+    /// The keys of the keystore, without those of the signers.
     pub fn keys(&self) -> Vec<PublicKey> {
-        self.0.keys()
+        self.keystore.keys()
     }
 
+    /// The addresses of the keys of the keystore, then those of the signers.
     pub fn addresses(&self) -> Vec<SuiAddress> {
-        self.keys().iter().map(|k| k.into()).collect()
+        let mut addresses: Vec<SuiAddress> = self.keys().iter().map(|k| k.into()).collect();
+        for address in self.signers.keys() {
+            if !addresses.contains(address) {
+                addresses.push(*address);
+            }
+        }
+        addresses
     }
 
     pub fn signer(&self, signer: SuiAddress) -> impl Signer<Signature> + '_ {
-        KeystoreSigner::new(&*self.0, signer)
+        KeystoreSigner::new(self, signer)
     }
 
     pub fn import_from_mnemonic(
@@ -189,27 +396,63 @@ impl SuiKeystore {
         let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
             .map_err(|e| anyhow::anyhow!("Invalid mnemonic phrase: {:?}", e))?;
         let seed = Seed::new(&mnemonic, "");
+        let key_mnemonic = key_mnemonic(&mnemonic, key_scheme, &derivation_path);
         match derive_key_pair_from_path(seed.as_bytes(), derivation_path, &key_scheme) {
             Ok((address, kp)) => {
-                self.0.add_key(kp)?;
+                self.keystore.add_key_with_mnemonic(kp, key_mnemonic)?;
                 Ok(address)
             }
             Err(e) => Err(anyhow!("error getting keypair {:?}", e)),
         }
     }
 
+    /// The mnemonic the key of `address` was generated or imported from, to back it up. Keys
+    /// added otherwise, or before mnemonics were kept, have none.
+    pub fn export_mnemonic(&self, address: &SuiAddress) -> Result<KeyMnemonic, anyhow::Error> {
+        if !self.addresses().contains(address) {
+            return Err(anyhow!("Cannot find key for address: [{address}]"));
+        }
+        self.keystore
+            .mnemonic(address)
+            .ok_or_else(|| anyhow!("No mnemonic is known for the key of address [{address}]"))
+    }
+
     pub fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
-        self.0.sign(address, msg)
+        let signer = match self.signers.get(address) {
+            Some(signer) => signer,
+            None => return self.keystore.sign(address, msg),
+        };
+        let signature = signer.sign(msg)?;
+        // A signer holding another key, e.g. a Ledger device with another seed, would only be
+        // caught by the validators
+        signature.verify_bytes(msg, *address).map_err(|e| {
+            signature::Error::from_source(format!(
+                "Signer of address [{address}] does not hold its key: {e}"
+            ))
+        })?;
+        Ok(signature)
+    }
+}
+
+fn key_mnemonic(
+    mnemonic: &Mnemonic,
+    key_scheme: SignatureScheme,
+    derivation_path: &Option<DerivationPath>,
+) -> KeyMnemonic {
+    KeyMnemonic {
+        phrase: mnemonic.phrase().to_string(),
+        key_scheme: key_scheme.to_string(),
+        derivation_path: derivation_path.as_ref().map(ToString::to_string),
     }
 }
 
 struct KeystoreSigner<'a> {
-    keystore: &'a dyn AccountKeystore,
+    keystore: &'a SuiKeystore,
     address: SuiAddress,
 }
 
 impl<'a> KeystoreSigner<'a> {
-    pub fn new(keystore: &'a dyn AccountKeystore, account: SuiAddress) -> Self {
+    pub fn new(keystore: &'a SuiKeystore, account: SuiAddress) -> Self {
         Self {
             keystore,
             address: account,
@@ -226,6 +469,7 @@ impl Signer<Signature> for KeystoreSigner<'_> {
 #[derive(Default)]
 struct InMemKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    mnemonics: BTreeMap<SuiAddress, KeyMnemonic>,
 }
 
 impl AccountKeystore for InMemKeystore {
@@ -247,6 +491,21 @@ impl AccountKeystore for InMemKeystore {
     fn keys(&self) -> Vec<PublicKey> {
         self.keys.values().map(|key| key.public()).collect()
     }
+
+    fn add_key_with_mnemonic(
+        &mut self,
+        keypair: SuiKeyPair,
+        mnemonic: KeyMnemonic,
+    ) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        self.keys.insert(address, keypair);
+        self.mnemonics.insert(address, mnemonic);
+        Ok(())
+    }
+
+    fn mnemonic(&self, address: &SuiAddress) -> Option<KeyMnemonic> {
+        self.mnemonics.get(address).cloned()
+    }
 }
 
 impl InMemKeystore {
@@ -257,7 +516,10 @@ impl InMemKeystore {
             .map(|(ad, k)| (ad, SuiKeyPair::Ed25519SuiKeyPair(k)))
             .collect::<BTreeMap<SuiAddress, SuiKeyPair>>();
 
-        Self { keys }
+        Self {
+            keys,
+            mnemonics: BTreeMap::new(),
+        }
     }
 }
 
@@ -273,4 +535,16 @@ impl AccountKeystore for Box<dyn AccountKeystore> {
     fn keys(&self) -> Vec<PublicKey> {
         (**self).keys()
     }
+
+    fn add_key_with_mnemonic(
+        &mut self,
+        keypair: SuiKeyPair,
+        mnemonic: KeyMnemonic,
+    ) -> Result<(), anyhow::Error> {
+        (**self).add_key_with_mnemonic(keypair, mnemonic)
+    }
+
+    fn mnemonic(&self, address: &SuiAddress) -> Option<KeyMnemonic> {
+        (**self).mnemonic(address)
+    }
 }
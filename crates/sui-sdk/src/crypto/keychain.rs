@@ -0,0 +1,166 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use signature::Signer;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{PublicKey, Signature, SuiKeyPair};
+
+use super::{decode_key_pairs, encode_key_pairs, AccountKeystore};
+
+/// Account of the keychain item holding the keys of a service.
+const KEYCHAIN_ACCOUNT: &str = "sui.keystore";
+
+/// Keystore kept in the OS keychain: the macOS Keychain, or the Secret Service (e.g. GNOME
+/// Keyring) on Linux. All keys of a service are stored in a single item, in the same format as
+/// a keystore file.
+pub struct KeychainKeystore {
+    service: String,
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+}
+
+impl AccountKeystore for KeychainKeystore {
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        self.keys
+            .get(address)
+            .ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?
+            .try_sign(msg)
+    }
+
+    fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        let address: SuiAddress = (&keypair.public()).into();
+        self.keys.insert(address, keypair);
+        self.save()
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().map(|key| key.public()).collect()
+    }
+}
+
+impl KeychainKeystore {
+    pub fn load_or_create(service: &str) -> Result<Self, anyhow::Error> {
+        let keys = match os::read(service, KEYCHAIN_ACCOUNT)? {
+            Some(stored) => {
+                let kp_strings: Vec<String> = serde_json::from_str(&stored)?;
+                decode_key_pairs(&kp_strings).map_err(|e| {
+                    anyhow::anyhow!("Invalid keys in keychain service {service}: {:#?}", e)
+                })?
+            }
+            None => BTreeMap::new(),
+        };
+        Ok(Self {
+            service: service.to_string(),
+            keys,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        os::write(
+            &self.service,
+            KEYCHAIN_ACCOUNT,
+            &encode_key_pairs(&self.keys),
+        )
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod os {
+    use anyhow::anyhow;
+    use security_framework::passwords::{get_generic_password, set_generic_password};
+
+    const ERR_SEC_ITEM_NOT_FOUND: i32 = -25300;
+
+    pub fn read(service: &str, account: &str) -> Result<Option<String>, anyhow::Error> {
+        match get_generic_password(service, account) {
+            Ok(secret) => Ok(Some(String::from_utf8(secret)?)),
+            Err(e) if e.code() == ERR_SEC_ITEM_NOT_FOUND => Ok(None),
+            Err(e) => Err(anyhow!(
+                "Cannot read keychain item {service}/{account}: {e}"
+            )),
+        }
+    }
+
+    pub fn write(service: &str, account: &str, secret: &str) -> Result<(), anyhow::Error> {
+        set_generic_password(service, account, secret.as_bytes())
+            .map_err(|e| anyhow!("Cannot write keychain item {service}/{account}: {e}"))
+    }
+}
+
+/// The Secret Service is reached through `secret-tool` from libsecret, which passes secrets
+/// over stdin and stdout rather than on the command line.
+#[cfg(target_os = "linux")]
+mod os {
+    use anyhow::{anyhow, bail};
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    fn secret_tool() -> Command {
+        Command::new("secret-tool")
+    }
+
+    fn run_error(e: std::io::Error) -> anyhow::Error {
+        anyhow!("Cannot run secret-tool, is libsecret installed? {e}")
+    }
+
+    pub fn read(service: &str, account: &str) -> Result<Option<String>, anyhow::Error> {
+        let output = secret_tool()
+            .args(["lookup", "service", service, "account", account])
+            .output()
+            .map_err(run_error)?;
+        if !output.status.success() {
+            // A lookup without matching item fails silently.
+            if output.stderr.is_empty() {
+                return Ok(None);
+            }
+            bail!(
+                "Cannot read secret {service}/{account}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(Some(
+            String::from_utf8(output.stdout)?.trim_end().to_string(),
+        ))
+    }
+
+    pub fn write(service: &str, account: &str, secret: &str) -> Result<(), anyhow::Error> {
+        let mut child = secret_tool()
+            .args(["store", "--label", &format!("Sui keystore ({service})")])
+            .args(["service", service, "account", account])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(run_error)?;
+        // Closing stdin ends the secret.
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(secret.as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "Cannot write secret {service}/{account}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+mod os {
+    use anyhow::bail;
+
+    pub fn read(_service: &str, _account: &str) -> Result<Option<String>, anyhow::Error> {
+        bail!("OS keychain keystores are only supported on macOS and Linux")
+    }
+
+    pub fn write(_service: &str, _account: &str, _secret: &str) -> Result<(), anyhow::Error> {
+        bail!("OS keychain keystores are only supported on macOS and Linux")
+    }
+}
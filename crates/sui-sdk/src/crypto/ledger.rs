@@ -0,0 +1,290 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use bip32::DerivationPath;
+use signature::Signature as _;
+use sui_types::crypto::{PublicKey, Signature, SignatureScheme};
+
+use super::AccountSigner;
+
+const CLA: u8 = 0x00;
+const INS_GET_PUBLIC_KEY: u8 = 0x02;
+const INS_SIGN_TRANSACTION: u8 = 0x03;
+/// P1 of the first chunk of a message, and of the following ones.
+const P1_FIRST: u8 = 0x00;
+const P1_NEXT: u8 = 0x01;
+/// P2 of a chunk followed by others, and of the last one.
+const P2_MORE: u8 = 0x80;
+const P2_LAST: u8 = 0x00;
+/// An APDU carries at most 255 bytes of data, longer messages are sent in chunks.
+const MAX_APDU_DATA: usize = 255;
+
+const SW_OK: u16 = 0x9000;
+const SW_DENIED: u16 = 0x6985;
+const SW_CLA_NOT_SUPPORTED: u16 = 0x6e00;
+const SW_APP_NOT_OPEN: u16 = 0x6511;
+
+const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+/// Exchanges APDUs with a Ledger device: sends a command, and returns the response data
+/// followed by the two bytes of its status word.
+pub trait LedgerTransport: Send + Sync {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error>;
+}
+
+/// Signs with the ed25519 key at a derivation path of the Sui app of a Ledger device, so that
+/// the key never leaves the device and every transaction is approved on it.
+///
+/// The device is opened for each request, so that it can be unplugged between transactions.
+pub struct LedgerSigner {
+    derivation_path: DerivationPath,
+    transport: Option<Box<dyn LedgerTransport>>,
+}
+
+impl LedgerSigner {
+    /// Signs with the first Ledger device plugged in over USB.
+    pub fn new(derivation_path: DerivationPath) -> Self {
+        Self {
+            derivation_path,
+            transport: None,
+        }
+    }
+
+    /// Signs with the device behind `transport`, e.g. a Speculos emulator.
+    pub fn with_transport(
+        derivation_path: DerivationPath,
+        transport: Box<dyn LedgerTransport>,
+    ) -> Self {
+        Self {
+            derivation_path,
+            transport: Some(transport),
+        }
+    }
+
+    fn with_device<T>(
+        &self,
+        f: impl FnOnce(&dyn LedgerTransport) -> Result<T, anyhow::Error>,
+    ) -> Result<T, anyhow::Error> {
+        match &self.transport {
+            Some(transport) => f(&**transport),
+            None => f(&hid::HidTransport::open()?),
+        }
+    }
+
+    /// The derivation path as sent to the device: the number of its levels, then each level as
+    /// a big-endian u32 with the hardened bit set.
+    fn encoded_path(&self) -> Vec<u8> {
+        let levels = self.derivation_path.as_ref();
+        let mut bytes = vec![levels.len() as u8];
+        for level in levels {
+            bytes.extend(u32::from(*level).to_be_bytes());
+        }
+        bytes
+    }
+
+    fn public_key_bytes(&self, transport: &dyn LedgerTransport) -> Result<Vec<u8>, anyhow::Error> {
+        let key = send(
+            transport,
+            INS_GET_PUBLIC_KEY,
+            P1_FIRST,
+            P2_LAST,
+            &self.encoded_path(),
+        )?;
+        if key.len() != ED25519_PUBLIC_KEY_LENGTH {
+            bail!("Ledger device returned a public key of {} bytes", key.len());
+        }
+        Ok(key)
+    }
+}
+
+impl AccountSigner for LedgerSigner {
+    fn public_key(&self) -> Result<PublicKey, anyhow::Error> {
+        let key = self.with_device(|transport| self.public_key_bytes(transport))?;
+        PublicKey::try_from_bytes(SignatureScheme::ED25519, &key).map_err(|e| anyhow!("{e}"))
+    }
+
+    fn sign(&self, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let (signature, key) = self
+            .with_device(|transport| {
+                let key = self.public_key_bytes(transport)?;
+                let mut data = self.encoded_path();
+                data.extend_from_slice(msg);
+                let chunks: Vec<_> = data.chunks(MAX_APDU_DATA).collect();
+                let mut signature = Vec::new();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let p1 = if i == 0 { P1_FIRST } else { P1_NEXT };
+                    let p2 = if i + 1 < chunks.len() {
+                        P2_MORE
+                    } else {
+                        P2_LAST
+                    };
+                    signature = send(transport, INS_SIGN_TRANSACTION, p1, p2, chunk)?;
+                }
+                if signature.len() != ED25519_SIGNATURE_LENGTH {
+                    bail!(
+                        "Ledger device returned a signature of {} bytes",
+                        signature.len()
+                    );
+                }
+                Ok((signature, key))
+            })
+            .map_err(|e| signature::Error::from_source(format!("Ledger signing failed: {e}")))?;
+        Signature::from_bytes(&[&[SignatureScheme::ED25519.flag()], &*signature, &*key].concat())
+    }
+}
+
+/// Sends a command to the device, and returns its response data unless it failed.
+fn send(
+    transport: &dyn LedgerTransport,
+    ins: u8,
+    p1: u8,
+    p2: u8,
+    data: &[u8],
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut apdu = vec![CLA, ins, p1, p2, data.len() as u8];
+    apdu.extend_from_slice(data);
+    let mut response = transport.exchange(&apdu)?;
+    if response.len() < 2 {
+        bail!("Truncated response from the Ledger device");
+    }
+    let status = response.split_off(response.len() - 2);
+    match u16::from_be_bytes([status[0], status[1]]) {
+        SW_OK => Ok(response),
+        SW_DENIED => Err(anyhow!("Rejected on the Ledger device")),
+        SW_CLA_NOT_SUPPORTED | SW_APP_NOT_OPEN => {
+            Err(anyhow!("Open the Sui app on the Ledger device"))
+        }
+        status => Err(anyhow!("Ledger device failed with status {status:#06x}")),
+    }
+}
+
+/// The USB HID transport of Ledger devices: APDUs are prefixed with their length and split into
+/// 64 byte packets, each with a header of the channel, the APDU tag and the packet's sequence
+/// number.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+mod hid {
+    use anyhow::bail;
+
+    use super::LedgerTransport;
+
+    const PACKET_SIZE: usize = 64;
+    const CHANNEL: [u8; 2] = [0x01, 0x01];
+    const TAG_APDU: u8 = 0x05;
+    const HEADER_SIZE: usize = 5;
+
+    /// Splits `apdu` into the packets sent to the device.
+    pub(super) fn packets(apdu: &[u8]) -> Vec<[u8; PACKET_SIZE]> {
+        let mut data = (apdu.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(apdu);
+        data.chunks(PACKET_SIZE - HEADER_SIZE)
+            .enumerate()
+            .map(|(seq, chunk)| {
+                let mut packet = [0; PACKET_SIZE];
+                packet[..2].copy_from_slice(&CHANNEL);
+                packet[2] = TAG_APDU;
+                packet[3..HEADER_SIZE].copy_from_slice(&(seq as u16).to_be_bytes());
+                packet[HEADER_SIZE..HEADER_SIZE + chunk.len()].copy_from_slice(chunk);
+                packet
+            })
+            .collect()
+    }
+
+    /// Reassembles a response from the packets returned by `read_packet`.
+    pub(super) fn response(
+        mut read_packet: impl FnMut() -> Result<[u8; PACKET_SIZE], anyhow::Error>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let mut data = Vec::new();
+        let mut length = None;
+        let mut seq: u16 = 0;
+        while length.map_or(true, |length| data.len() < length) {
+            let packet = read_packet()?;
+            if packet[..2] != CHANNEL
+                || packet[2] != TAG_APDU
+                || packet[3..HEADER_SIZE] != seq.to_be_bytes()
+            {
+                bail!("Unexpected packet from the Ledger device");
+            }
+            let mut payload = &packet[HEADER_SIZE..];
+            if length.is_none() {
+                length = Some(u16::from_be_bytes([payload[0], payload[1]]) as usize);
+                payload = &payload[2..];
+            }
+            data.extend_from_slice(payload);
+            seq = seq.wrapping_add(1);
+        }
+        data.truncate(length.unwrap_or_default());
+        Ok(data)
+    }
+
+    /// A Ledger device opened through Linux's hidraw interface.
+    pub(super) struct HidTransport {
+        #[cfg(target_os = "linux")]
+        device: std::fs::File,
+    }
+
+    #[cfg(target_os = "linux")]
+    impl HidTransport {
+        const LEDGER_VENDOR_ID: &'static str = ":00002C97:";
+
+        /// Opens the first Ledger device, through the interface of its apps.
+        pub(super) fn open() -> Result<Self, anyhow::Error> {
+            for entry in std::fs::read_dir("/sys/class/hidraw")? {
+                let entry = entry?;
+                let uevent = match std::fs::read_to_string(entry.path().join("device/uevent")) {
+                    Ok(uevent) => uevent,
+                    Err(_) => continue,
+                };
+                let is_ledger = uevent.lines().any(|line| {
+                    line.starts_with("HID_ID=") && line.contains(Self::LEDGER_VENDOR_ID)
+                });
+                let is_app_interface = uevent
+                    .lines()
+                    .any(|line| line.starts_with("HID_PHYS=") && line.ends_with("/input0"));
+                if is_ledger && is_app_interface {
+                    let path = std::path::Path::new("/dev").join(entry.file_name());
+                    let device = std::fs::OpenOptions::new()
+                        .read(true)
+                        .write(true)
+                        .open(&path)
+                        .map_err(|e| anyhow::anyhow!("Cannot open Ledger device {path:?}: {e}"))?;
+                    return Ok(Self { device });
+                }
+            }
+            bail!("No Ledger device found, is it plugged in and unlocked?")
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    impl HidTransport {
+        pub(super) fn open() -> Result<Self, anyhow::Error> {
+            bail!("Ledger devices are only supported on Linux")
+        }
+    }
+
+    impl LedgerTransport for HidTransport {
+        #[cfg(target_os = "linux")]
+        fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+            use std::io::{Read, Write};
+
+            let mut device = &self.device;
+            for packet in packets(apdu) {
+                // Prefixed by the ID of the report, none for Ledger devices
+                let mut report = vec![0];
+                report.extend_from_slice(&packet);
+                device.write_all(&report)?;
+            }
+            response(|| {
+                let mut packet = [0; PACKET_SIZE];
+                device.read_exact(&mut packet)?;
+                Ok(packet)
+            })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        fn exchange(&self, _apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+            bail!("Ledger devices are only supported on Linux")
+        }
+    }
+}
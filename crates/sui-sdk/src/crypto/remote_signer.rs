@@ -0,0 +1,163 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{PublicKey, SignableBytes, Signature, SuiKeyPair, SuiSignature};
+use sui_types::messages::{SingleTransactionKind, TransactionData, TransactionKind};
+use sui_types::sui_serde::{Base64, Encoding};
+
+use super::AccountKeystore;
+
+/// Kinds of transaction a remote signer can be asked to sign.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningIntent {
+    TransferObject,
+    TransferSui,
+    Call,
+    Publish,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteSignerConfig {
+    /// Base URL of the signer, which serves `GET <url>/keys` and `POST <url>/sign`.
+    pub url: String,
+    /// Transactions the signer may be asked to sign, others are refused before reaching it.
+    pub allowed_intents: Vec<SigningIntent>,
+}
+
+#[derive(Deserialize)]
+struct KeysResponse {
+    keys: Vec<PublicKey>,
+}
+
+#[derive(Serialize)]
+struct SignRequest<'a> {
+    address: SuiAddress,
+    /// Kind of each transaction of the message, for the signer to check against its own policy.
+    intents: &'a [SigningIntent],
+    /// Base64 encoded signable bytes of the transaction data.
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: Signature,
+}
+
+/// Keystore whose keys are held by an external signer, so that they don't have to be stored
+/// on the host running the client. The signer is only asked to sign transactions of
+/// allowlisted kinds.
+pub struct RemoteSignerKeystore {
+    config: RemoteSignerConfig,
+    keys: Vec<PublicKey>,
+}
+
+impl AccountKeystore for RemoteSignerKeystore {
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let key = self
+            .keys
+            .iter()
+            .find(|key| SuiAddress::from(*key) == *address)
+            .ok_or_else(|| {
+                signature::Error::from_source(format!("Cannot find key for address: [{address}]"))
+            })?;
+        let intents = signing_intents(msg)?;
+        if let Some(intent) = intents
+            .iter()
+            .find(|intent| !self.config.allowed_intents.contains(intent))
+        {
+            return Err(signature::Error::from_source(format!(
+                "Remote signer is not allowed to sign {intent:?} transactions"
+            )));
+        }
+
+        let request = SignRequest {
+            address: *address,
+            intents: &intents,
+            message: Base64::encode(msg),
+        };
+        let url = self.endpoint("sign");
+        let response: SignResponse = off_runtime(|| {
+            reqwest::blocking::Client::new()
+                .post(&url)
+                .json(&request)
+                .send()?
+                .error_for_status()?
+                .json()
+        })
+        .map_err(|e| signature::Error::from_source(format!("Remote signer failed to sign: {e}")))?;
+        if response.signature.public_key_bytes() != key.as_ref() {
+            return Err(signature::Error::from_source(format!(
+                "Remote signer did not sign with the key of address [{address}]"
+            )));
+        }
+        Ok(response.signature)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        Err(anyhow!(
+            "Keys can't be added to a remote signer from the client"
+        ))
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.clone()
+    }
+}
+
+impl RemoteSignerKeystore {
+    /// Fetches the public keys held by the signer.
+    pub fn connect(config: &RemoteSignerConfig) -> Result<Self, anyhow::Error> {
+        let mut keystore = Self {
+            config: config.clone(),
+            keys: vec![],
+        };
+        let url = keystore.endpoint("keys");
+        let response: KeysResponse =
+            off_runtime(|| reqwest::blocking::get(&url)?.error_for_status()?.json())
+                .map_err(|e| anyhow!("Cannot fetch keys from remote signer {}: {e}", config.url))?;
+        keystore.keys = response.keys;
+        Ok(keystore)
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{path}", self.config.url.trim_end_matches('/'))
+    }
+}
+
+/// Kinds of the transactions of a message to sign, which must be transaction data.
+fn signing_intents(msg: &[u8]) -> Result<Vec<SigningIntent>, signature::Error> {
+    let data = TransactionData::from_signable_bytes(msg).map_err(|e| {
+        signature::Error::from_source(format!("Remote signers only sign transactions: {e}"))
+    })?;
+    let kinds = match &data.kind {
+        TransactionKind::Single(kind) => vec![kind],
+        TransactionKind::Batch(kinds) => kinds.iter().collect(),
+    };
+    kinds
+        .into_iter()
+        .map(|kind| match kind {
+            SingleTransactionKind::TransferObject(_) => Ok(SigningIntent::TransferObject),
+            SingleTransactionKind::TransferSui(_) => Ok(SigningIntent::TransferSui),
+            SingleTransactionKind::Call(_) => Ok(SigningIntent::Call),
+            SingleTransactionKind::Publish(_) => Ok(SigningIntent::Publish),
+            SingleTransactionKind::ChangeEpoch(_) => Err(signature::Error::from_source(
+                "Remote signers don't sign system transactions",
+            )),
+        })
+        .collect()
+}
+
+/// Runs a request of reqwest's blocking client on its own thread, as it can't run within the
+/// async runtime clients usually sign from.
+fn off_runtime<T: Send>(request: impl FnOnce() -> reqwest::Result<T> + Send) -> reqwest::Result<T> {
+    std::thread::scope(|scope| {
+        scope
+            .spawn(request)
+            .join()
+            .expect("Remote signer request panicked")
+    })
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A journal of the signed transactions submitted by a client, recorded before they are sent and
+//! removed once they are final. A client which crashes mid-submission leaves the objects of its
+//! transaction locked until the transaction is executed, and the journal keeps the transaction
+//! around to submit it again.
+//!
+//! ```ignore
+//! let journal = TransactionJournal::open(dir)?;
+//! for transaction in journal.pending()? {
+//!     client.quorum_driver().execute_transaction(transaction.clone()).await?;
+//!     journal.complete(transaction.digest())?;
+//! }
+//! ```
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages::Transaction;
+use sui_types::sui_serde::{Encoding, Hex};
+
+const EXTENSION: &str = "tx";
+
+/// A directory with one file of BCS bytes per pending transaction, named by its digest.
+pub struct TransactionJournal {
+    dir: PathBuf,
+}
+
+impl TransactionJournal {
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .map_err(|e| anyhow!("Cannot create transaction journal {:?}: {e}", dir))?;
+        Ok(Self { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Records `transaction` before it is submitted. The transaction is on disk when this
+    /// returns, and a crash while writing it leaves no partial entry.
+    pub fn record(&self, transaction: &Transaction) -> Result<(), anyhow::Error> {
+        let path = self.path(transaction.digest());
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&bcs::to_bytes(transaction)?)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// Removes the transaction of `digest` once it is final, whether it succeeded or failed.
+    pub fn complete(&self, digest: &TransactionDigest) -> Result<(), anyhow::Error> {
+        match fs::remove_file(self.path(digest)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The recorded transactions which are not known to be final.
+    pub fn pending(&self) -> Result<Vec<Transaction>, anyhow::Error> {
+        let mut transactions = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            let transaction = bcs::from_bytes(&fs::read(&path)?)
+                .map_err(|e| anyhow!("Invalid transaction in journal file {:?}: {e}", path))?;
+            transactions.push(transaction);
+        }
+        Ok(transactions)
+    }
+
+    fn path(&self, digest: &TransactionDigest) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}", Hex::encode(digest), EXTENSION))
+    }
+}
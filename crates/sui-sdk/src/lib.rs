@@ -4,12 +4,14 @@
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use futures::StreamExt;
 use futures_core::Stream;
 use jsonrpsee::core::client::{ClientT, Subscription};
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use serde::Deserialize;
 use serde::Serialize;
@@ -20,6 +22,7 @@ pub use sui_config::gateway;
 use sui_config::gateway::GatewayConfig;
 use sui_core::gateway_state::{GatewayClient, GatewayState};
 pub use sui_json as json;
+use sui_json::SuiJsonValue;
 use sui_json_rpc::api::EventStreamingApiClient;
 use sui_json_rpc::api::QuorumDriverApiClient;
 use sui_json_rpc::api::RpcBcsApiClient;
@@ -27,14 +30,22 @@ use sui_json_rpc::api::RpcFullNodeReadApiClient;
 use sui_json_rpc::api::RpcGatewayApiClient;
 use sui_json_rpc::api::RpcReadApiClient;
 use sui_json_rpc::api::WalletSyncApiClient;
+use sui_json_rpc::api::OWNED_OBJECTS_QUERY_MAX_LIMIT;
 pub use sui_json_rpc_types as rpc_types;
 use sui_json_rpc_types::{
-    GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse, SuiEventEnvelope,
-    SuiEventFilter, SuiObjectInfo, SuiTransactionResponse,
+    DevInspectResults, GatewayTxSeqNumber, GetObjectDataResponse, GetRawObjectDataResponse,
+    SuiBalance, SuiCheckpointStateDiff, SuiCoinTransfersPage, SuiCoinsPage, SuiEpochFeeSummary,
+    SuiEventEnvelope, SuiEventFilter, SuiObjectInfo, SuiObjectVersionHistoryPage, SuiObjectsPage,
+    SuiSupply, SuiTransactionEffects, SuiTransactionResponse, SuiTransactionsPage, SuiTypeTag,
+    TransactionQuery,
 };
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::event::EventID;
+use sui_types::finality_proof::FinalityProof;
 use sui_types::messages::Transaction;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use types::base_types::SequenceNumber;
 use types::messages::ExecuteTransactionRequestType;
 
@@ -42,8 +53,12 @@ use crate::transaction_builder::TransactionBuilder;
 
 // re-export essential sui crates
 pub mod crypto;
+pub mod journal;
 mod transaction_builder;
 
+/// Objects fetched by one batch of requests of [`ReadApi::get_parsed_objects`].
+const OBJECTS_BATCH_SIZE: usize = 100;
+
 pub struct SuiClient {
     api: Arc<SuiClientApi>,
     transaction_builder: TransactionBuilder,
@@ -205,26 +220,84 @@ pub struct ReadApi {
 }
 
 impl ReadApi {
+    /// All the objects owned by `address`, fetched a page at a time.
     pub async fn get_objects_owned_by_address(
         &self,
         address: SuiAddress,
     ) -> anyhow::Result<Vec<SuiObjectInfo>> {
         Ok(match &*self.api {
-            SuiClientApi::Rpc(c) => c.http.get_objects_owned_by_address(address).await?,
+            SuiClientApi::Rpc(c) => {
+                let mut objects = vec![];
+                let mut cursor = None;
+                loop {
+                    let page = c
+                        .http
+                        .get_objects_owned_by_address(address, cursor, None)
+                        .await?;
+                    objects.extend(page.data);
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => break objects,
+                    }
+                }
+            }
             SuiClientApi::Embedded(c) => c.get_objects_owned_by_address(address).await?,
         })
     }
 
+    /// All the objects owned by the object `object_id`, fetched a page at a time.
     pub async fn get_objects_owned_by_object(
         &self,
         object_id: ObjectID,
     ) -> anyhow::Result<Vec<SuiObjectInfo>> {
         Ok(match &*self.api {
-            SuiClientApi::Rpc(c) => c.http.get_objects_owned_by_object(object_id).await?,
+            SuiClientApi::Rpc(c) => {
+                let mut objects = vec![];
+                let mut cursor = None;
+                loop {
+                    let page = c
+                        .http
+                        .get_objects_owned_by_object(object_id, cursor, None)
+                        .await?;
+                    objects.extend(page.data);
+                    match page.next_cursor {
+                        Some(next) => cursor = Some(next),
+                        None => break objects,
+                    }
+                }
+            }
             SuiClientApi::Embedded(c) => c.get_objects_owned_by_object(object_id).await?,
         })
     }
 
+    /// A page of the objects owned by `address`, only those of `object_type` if given.
+    pub async fn get_owned_objects(
+        &self,
+        address: SuiAddress,
+        object_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiObjectsPage> {
+        Ok(match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_owned_objects(address, object_type, cursor, limit)
+                    .await?
+            }
+            SuiClientApi::Embedded(c) => {
+                let limit = limit
+                    .unwrap_or(OWNED_OBJECTS_QUERY_MAX_LIMIT)
+                    .min(OWNED_OBJECTS_QUERY_MAX_LIMIT);
+                SuiObjectsPage::new(
+                    c.get_objects_owned_by_address(address).await?,
+                    object_type.as_deref(),
+                    cursor,
+                    limit,
+                )?
+            }
+        })
+    }
+
     pub async fn get_parsed_object(
         &self,
         object_id: ObjectID,
@@ -235,6 +308,35 @@ impl ReadApi {
         })
     }
 
+    /// Fetches the objects in the order of `object_ids`, with one batch of requests for each
+    /// hundred objects rather than one request per object.
+    pub async fn get_parsed_objects(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> anyhow::Result<Vec<GetObjectDataResponse>> {
+        let mut objects = Vec::with_capacity(object_ids.len());
+        match &*self.api {
+            SuiClientApi::Rpc(c) => {
+                for chunk in object_ids.chunks(OBJECTS_BATCH_SIZE) {
+                    let batch = chunk
+                        .iter()
+                        .map(|object_id| ("sui_getObject", rpc_params![object_id]))
+                        .collect();
+                    let responses: Vec<Value> = c.http.batch_request(batch).await?;
+                    for response in responses {
+                        objects.push(serde_json::from_value(response)?);
+                    }
+                }
+            }
+            SuiClientApi::Embedded(c) => {
+                for object_id in object_ids {
+                    objects.push(c.get_object(*object_id).await?);
+                }
+            }
+        }
+        Ok(objects)
+    }
+
     pub async fn try_get_parsed_past_object(
         &self,
         object_id: ObjectID,
@@ -301,12 +403,74 @@ impl ReadApi {
 pub struct FullNodeApi(Arc<SuiClientApi>);
 
 impl FullNodeApi {
+    pub async fn get_transactions(
+        &self,
+        query: TransactionQuery,
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_transactions(query, cursor, limit, descending_order)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> anyhow::Result<SuiBalance> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_balance(owner, coin_type).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_all_balances(&self, owner: SuiAddress) -> anyhow::Result<Vec<SuiBalance>> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_all_balances(owner).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    pub async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiCoinsPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_coins(owner, coin_type, cursor, limit).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
     pub async fn get_transactions_by_input_object(
         &self,
         object: ObjectID,
-    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
         Ok(match &*self.0 {
-            SuiClientApi::Rpc(c) => c.http.get_transactions_by_input_object(object).await?,
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_transactions_by_input_object(object, cursor, limit)
+                    .await?
+            }
             SuiClientApi::Embedded(_) => {
                 return Err(anyhow!("Method not supported by embedded gateway client."))
             }
@@ -316,9 +480,13 @@ impl FullNodeApi {
     pub async fn get_transactions_by_mutated_object(
         &self,
         object: ObjectID,
-    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
         Ok(match &*self.0 {
-            SuiClientApi::Rpc(c) => c.http.get_transactions_by_mutated_object(object),
+            SuiClientApi::Rpc(c) => c
+                .http
+                .get_transactions_by_mutated_object(object, cursor, limit),
             SuiClientApi::Embedded(_) => {
                 return Err(anyhow!("Method not supported by embedded gateway client."))
             }
@@ -331,11 +499,13 @@ impl FullNodeApi {
         package: ObjectID,
         module: Option<String>,
         function: Option<String>,
-    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
         Ok(match &*self.0 {
             SuiClientApi::Rpc(c) => c
                 .http
-                .get_transactions_by_move_function(package, module, function),
+                .get_transactions_by_move_function(package, module, function, cursor, limit),
             SuiClientApi::Embedded(_) => {
                 return Err(anyhow!("Method not supported by embedded gateway client."))
             }
@@ -346,9 +516,11 @@ impl FullNodeApi {
     pub async fn get_transactions_from_addr(
         &self,
         addr: SuiAddress,
-    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
         Ok(match &*self.0 {
-            SuiClientApi::Rpc(c) => c.http.get_transactions_from_addr(addr),
+            SuiClientApi::Rpc(c) => c.http.get_transactions_from_addr(addr, cursor, limit),
             SuiClientApi::Embedded(_) => {
                 return Err(anyhow!("Method not supported by embedded gateway client."))
             }
@@ -359,27 +531,192 @@ impl FullNodeApi {
     pub async fn get_transactions_to_addr(
         &self,
         addr: SuiAddress,
-    ) -> anyhow::Result<Vec<(GatewayTxSeqNumber, TransactionDigest)>> {
+        cursor: Option<GatewayTxSeqNumber>,
+        limit: Option<usize>,
+    ) -> anyhow::Result<SuiTransactionsPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_transactions_to_addr(addr, cursor, limit),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    /// Fetch the coins received by `addr` from other addresses after `cursor`, which is the
+    /// `next_cursor` of the previous page, or `None` to start from the first transaction.
+    pub async fn get_transfers_to_addr(
+        &self,
+        addr: SuiAddress,
+        cursor: Option<GatewayTxSeqNumber>,
+    ) -> anyhow::Result<SuiCoinTransfersPage> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_transfers_to_addr(addr, cursor),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    /// Fetch the versions of `object_id` after version `cursor`, which is the `next_cursor` of
+    /// the previous page, or `None` to start from the first version.
+    pub async fn get_object_version_history(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<SequenceNumber>,
+    ) -> anyhow::Result<SuiObjectVersionHistoryPage> {
         Ok(match &*self.0 {
-            SuiClientApi::Rpc(c) => c.http.get_transactions_to_addr(addr),
+            SuiClientApi::Rpc(c) => c.http.get_object_version_history(object_id, cursor),
             SuiClientApi::Embedded(_) => {
                 return Err(anyhow!("Method not supported by embedded gateway client."))
             }
         }
         .await?)
     }
+
+    /// Fetch the total supply of SUI, as accounted by the fullnode from genesis.
+    pub async fn get_supply(&self) -> anyhow::Result<SuiSupply> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_supply(),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    /// Fetch the gas charged and the SUI minted in `epoch`.
+    pub async fn get_epoch_fee_summary(
+        &self,
+        epoch: EpochId,
+    ) -> anyhow::Result<SuiEpochFeeSummary> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_epoch_fee_summary(epoch),
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        }
+        .await?)
+    }
+
+    /// Fetch a proof that the transaction is final, which can be checked offline with
+    /// `FinalityProof::verify` against the genesis committee.
+    pub async fn get_finality_proof(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<FinalityProof> {
+        let proof = match &*self.0 {
+            SuiClientApi::Rpc(c) => c.http.get_finality_proof(digest).await?,
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        };
+        Ok(proof.deserialize()?)
+    }
+
+    /// Fetch the net changes to the objects made by the checkpoints `from` to `to`, which may
+    /// stop short of `to`, as told by `to_checkpoint` in the result.
+    pub async fn get_checkpoint_state_diff(
+        &self,
+        from: CheckpointSequenceNumber,
+        to: CheckpointSequenceNumber,
+        with_contents: bool,
+    ) -> anyhow::Result<SuiCheckpointStateDiff> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .get_checkpoint_state_diff(from, to, Some(with_contents))
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Wait until the full node has executed the transaction `digest`, for at most `timeout`
+    /// (capped by the node).
+    pub async fn wait_for_transaction(
+        &self,
+        digest: TransactionDigest,
+        timeout: Option<Duration>,
+    ) -> anyhow::Result<SuiTransactionResponse> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .wait_for_transaction(digest, timeout.map(|t| t.as_millis() as u64))
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Execute the transaction against the full node's current state without committing it
+    pub async fn dry_run_transaction(
+        &self,
+        tx: Transaction,
+    ) -> anyhow::Result<SuiTransactionEffects> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                let sponsor_signature = tx.sponsor_signature_for_execution();
+                c.http
+                    .dry_run_transaction(tx_bytes, flag, signature, pub_key, sponsor_signature)
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
+
+    /// Execute a call to a public or entry Move function against the full node's current state
+    /// without committing it, to read the values it returns and the events it emits
+    pub async fn dev_inspect_move_call(
+        &self,
+        sender: SuiAddress,
+        package_object_id: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<SuiJsonValue>,
+    ) -> anyhow::Result<DevInspectResults> {
+        Ok(match &*self.0 {
+            SuiClientApi::Rpc(c) => {
+                c.http
+                    .dev_inspect_move_call(
+                        sender,
+                        package_object_id,
+                        module.to_string(),
+                        function.to_string(),
+                        type_args,
+                        call_args,
+                    )
+                    .await?
+            }
+            SuiClientApi::Embedded(_) => {
+                return Err(anyhow!("Method not supported by embedded gateway client."))
+            }
+        })
+    }
 }
 pub struct EventApi(Arc<SuiClientApi>);
 
 impl EventApi {
+    /// Subscribes to the events matching `filter`. When `cursor` is the id of the last event
+    /// received from a previous subscription, the events emitted since then are sent first.
     pub async fn subscribe_event(
         &self,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> anyhow::Result<impl Stream<Item = Result<SuiEventEnvelope, anyhow::Error>>> {
         match &*self.0 {
             SuiClientApi::Rpc(RpcClient { ws: Some(c), .. }) => {
                 let subscription: Subscription<SuiEventEnvelope> =
-                    c.subscribe_event(filter).await?;
+                    c.subscribe_event(filter, cursor).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
@@ -398,8 +735,14 @@ impl QuorumDriver {
         Ok(match &*self.api {
             SuiClientApi::Rpc(c) => {
                 let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                let sponsor_signature = tx.sponsor_signature_for_execution();
                 RpcGatewayApiClient::execute_transaction(
-                    &c.http, tx_bytes, flag, signature, pub_key,
+                    &c.http,
+                    tx_bytes,
+                    flag,
+                    signature,
+                    pub_key,
+                    sponsor_signature,
                 )
                 .await?
             }
@@ -415,6 +758,7 @@ impl QuorumDriver {
         Ok(match &*self.api {
             SuiClientApi::Rpc(c) => {
                 let (tx_bytes, flag, signature, pub_key) = tx.to_network_data_for_execution();
+                let sponsor_signature = tx.sponsor_signature_for_execution();
                 QuorumDriverApiClient::execute_transaction(
                     &c.http,
                     tx_bytes,
@@ -422,6 +766,7 @@ impl QuorumDriver {
                     signature,
                     pub_key,
                     request_type,
+                    sponsor_signature,
                 )
                 .await?
             }
@@ -14,13 +14,15 @@ use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
 use sui_json_rpc_types::{RPCTransactionRequestParams, SuiData, SuiTypeTag};
 use sui_types::base_types::{ObjectID, ObjectRef, SuiAddress};
 use sui_types::error::SuiError;
-use sui_types::gas_coin::GasCoin;
+use sui_types::gas_coin::{GasCoin, GAS};
+use sui_types::memo::{MAX_MEMO_LENGTH, MEMO_ATTACH_FUNC_NAME, MEMO_MODULE_NAME};
 use sui_types::messages::{
     CallArg, InputObjectKind, MoveCall, ObjectArg, SingleTransactionKind, TransactionData,
     TransactionKind, TransferObject,
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::Object;
+use sui_types::transaction_builder::TransactionBuilder as TransactionDataBuilder;
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_OBJECT_ID};
 
 use crate::ReadApi;
@@ -28,7 +30,9 @@ use crate::ReadApi;
 pub struct TransactionBuilder(pub(crate) Arc<ReadApi>);
 
 impl TransactionBuilder {
-    async fn select_gas(
+    /// The reference of the gas object `input_gas` if given, else of a gas coin of `signer`
+    /// covering `budget` which isn't among `input_objects`.
+    pub async fn select_gas(
         &self,
         signer: SuiAddress,
         input_gas: Option<ObjectID>,
@@ -75,6 +79,51 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Same as `transfer_object`, with `memo` attached to the transfer as a `0x2::memo::MemoEvent`
+    /// by a batch transaction.
+    pub async fn transfer_object_with_memo(
+        &self,
+        signer: SuiAddress,
+        object_id: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+        recipient: SuiAddress,
+        memo: String,
+    ) -> anyhow::Result<TransactionData> {
+        let single_transfer = self.single_transfer_object(object_id, recipient).await?;
+        let attach_memo = self.single_attach_memo(recipient, memo).await?;
+        let gas = self
+            .select_gas(signer, gas, gas_budget, vec![object_id])
+            .await?;
+        Ok(TransactionData::new(
+            TransactionKind::Batch(vec![single_transfer, attach_memo]),
+            signer,
+            gas,
+            gas_budget,
+        ))
+    }
+
+    async fn single_attach_memo(
+        &self,
+        recipient: SuiAddress,
+        memo: String,
+    ) -> Result<SingleTransactionKind, anyhow::Error> {
+        fp_ensure!(
+            memo.len() <= MAX_MEMO_LENGTH,
+            anyhow!("Memo is longer than {MAX_MEMO_LENGTH} bytes")
+        );
+        Ok(SingleTransactionKind::Call(MoveCall {
+            package: self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+            module: MEMO_MODULE_NAME.to_owned(),
+            function: MEMO_ATTACH_FUNC_NAME.to_owned(),
+            type_arguments: vec![],
+            arguments: vec![
+                CallArg::Pure(bcs::to_bytes(&recipient)?),
+                CallArg::Pure(bcs::to_bytes(memo.as_bytes())?),
+            ],
+        }))
+    }
+
     async fn single_transfer_object(
         &self,
         object_id: ObjectID,
@@ -100,6 +149,50 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Same as `transfer_sui`, with `memo` attached to the transfer as a `0x2::memo::MemoEvent`.
+    /// Since transferring SUI can't be batched, the coin is transferred with a Move call, and
+    /// the gas is paid with another coin.
+    pub async fn transfer_sui_with_memo(
+        &self,
+        signer: SuiAddress,
+        sui_object_id: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+        recipient: SuiAddress,
+        amount: Option<u64>,
+        memo: String,
+    ) -> anyhow::Result<TransactionData> {
+        let transfer = match amount {
+            Some(amount) => SingleTransactionKind::Call(MoveCall {
+                package: self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+                module: coin::COIN_MODULE_NAME.to_owned(),
+                function: coin::COIN_SPLIT_AND_TRANSFER_FUNC_NAME.to_owned(),
+                type_arguments: vec![GAS::type_tag()],
+                arguments: vec![
+                    CallArg::Object(ObjectArg::ImmOrOwnedObject(
+                        self.get_object_ref(sui_object_id).await?,
+                    )),
+                    CallArg::Pure(bcs::to_bytes(&amount)?),
+                    CallArg::Pure(bcs::to_bytes(&recipient)?),
+                ],
+            }),
+            None => {
+                self.single_transfer_object(sui_object_id, recipient)
+                    .await?
+            }
+        };
+        let attach_memo = self.single_attach_memo(recipient, memo).await?;
+        let gas = self
+            .select_gas(signer, gas, gas_budget, vec![sui_object_id])
+            .await?;
+        Ok(TransactionData::new(
+            TransactionKind::Batch(vec![transfer, attach_memo]),
+            signer,
+            gas,
+            gas_budget,
+        ))
+    }
+
     pub async fn move_call(
         &self,
         signer: SuiAddress,
@@ -405,7 +498,98 @@ impl TransactionBuilder {
         ))
     }
 
-    async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
+    /// Appends a transfer of the object `object_id` to `recipient` to the steps of `builder`.
+    pub async fn add_transfer_object(
+        &self,
+        builder: &mut TransactionDataBuilder,
+        object_id: ObjectID,
+        recipient: SuiAddress,
+    ) -> anyhow::Result<()> {
+        builder.transaction(self.single_transfer_object(object_id, recipient).await?);
+        Ok(())
+    }
+
+    /// Appends a Move call to the steps of `builder`, with its JSON arguments resolved and type
+    /// checked against the function as in `move_call`.
+    pub async fn add_move_call(
+        &self,
+        builder: &mut TransactionDataBuilder,
+        package_object_id: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<SuiJsonValue>,
+    ) -> anyhow::Result<()> {
+        builder.transaction(
+            self.single_move_call(package_object_id, module, function, type_args, call_args)
+                .await?,
+        );
+        Ok(())
+    }
+
+    /// Appends a split of coins of `split_amounts` off the coin `coin_object_id` to the steps of
+    /// `builder`.
+    pub async fn add_split_coin(
+        &self,
+        builder: &mut TransactionDataBuilder,
+        coin_object_id: ObjectID,
+        split_amounts: Vec<u64>,
+    ) -> anyhow::Result<()> {
+        let coin = self.0.get_object(coin_object_id).await?.into_object()?;
+        let coin_object_ref = coin.reference.to_object_ref();
+        let coin: Object = coin.try_into()?;
+        builder.split_coin(
+            self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+            coin_object_ref,
+            coin.get_move_template_type()?,
+            split_amounts,
+        )?;
+        Ok(())
+    }
+
+    /// Appends a merge of the coin `coin_to_merge` into `primary_coin` to the steps of `builder`.
+    pub async fn add_merge_coins(
+        &self,
+        builder: &mut TransactionDataBuilder,
+        primary_coin: ObjectID,
+        coin_to_merge: ObjectID,
+    ) -> anyhow::Result<()> {
+        let coin = self.0.get_object(primary_coin).await?.into_object()?;
+        let primary_coin_ref = coin.reference.to_object_ref();
+        let coin: Object = coin.try_into()?;
+        builder.merge_coins(
+            self.get_object_ref(SUI_FRAMEWORK_OBJECT_ID).await?,
+            primary_coin_ref,
+            self.get_object_ref(coin_to_merge).await?,
+            coin.get_move_template_type()?,
+        );
+        Ok(())
+    }
+
+    /// The transaction of the steps of `builder`, paying for the gas with `gas` if given, else
+    /// with a gas coin of the gas owner which isn't an input of the steps.
+    pub async fn build(
+        &self,
+        builder: TransactionDataBuilder,
+        gas: Option<ObjectID>,
+    ) -> anyhow::Result<TransactionData> {
+        let gas = self
+            .select_gas(
+                builder.gas_owner(),
+                gas,
+                builder.gas_budget(),
+                builder.input_objects()?,
+            )
+            .await?;
+        Ok(builder.build(gas)?)
+    }
+
+    /// The argument of a Move call passing the object `object_id`, shared or not.
+    pub async fn object_arg(&self, object_id: ObjectID) -> anyhow::Result<ObjectArg> {
+        self.get_object_arg(object_id, &mut BTreeMap::new()).await
+    }
+
+    pub async fn get_object_ref(&self, object_id: ObjectID) -> anyhow::Result<ObjectRef> {
         Ok(self
             .0
             .get_object(object_id)
@@ -1,12 +1,19 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use std::str::FromStr;
+use std::sync::Mutex;
 
 use sha3::{Digest, Sha3_256};
+use signature::Signer;
 use tempfile::TempDir;
 
-use sui_sdk::crypto::KeystoreType;
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_sdk::crypto::{AccountSigner, KeystoreType, LedgerSigner, LedgerTransport, SignerConfig};
+use sui_sdk::journal::TransactionJournal;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+use sui_types::crypto::{
+    get_key_pair, Signature, SignatureScheme, SuiKeyPair, SuiSignature, SuiSignatureInner,
+};
+use sui_types::messages::{Transaction, TransactionData};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -29,6 +36,43 @@ fn mnemonic_test() {
     assert_eq!(address, imported_address);
 }
 
+#[test]
+fn mnemonic_export_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = KeystoreType::File(keystore_path.clone()).init().unwrap();
+    let (address, phrase, _) = keystore
+        .generate_new_key_with_words(SignatureScheme::Secp256k1, None, 24)
+        .unwrap();
+    assert_eq!(phrase.split_whitespace().count(), 24);
+    assert!(keystore
+        .generate_new_key_with_words(SignatureScheme::ED25519, None, 13)
+        .is_err());
+
+    // The mnemonic outlives the keystore instance
+    let keystore = KeystoreType::File(keystore_path).init().unwrap();
+    let mnemonic = keystore.export_mnemonic(&address).unwrap();
+    assert_eq!(mnemonic.phrase, phrase);
+    assert_eq!(mnemonic.key_scheme, SignatureScheme::Secp256k1.to_string());
+    assert_eq!(mnemonic.derivation_path, None);
+
+    // Importing the exported mnemonic recovers the key
+    let mut keystore2 = KeystoreType::InMem(1).init().unwrap();
+    let imported_address = keystore2
+        .import_from_mnemonic(&mnemonic.phrase, SignatureScheme::Secp256k1, None)
+        .unwrap();
+    assert_eq!(address, imported_address);
+    assert_eq!(keystore2.export_mnemonic(&address).unwrap(), mnemonic);
+
+    // Keys which weren't derived from a mnemonic have none to export
+    let other = keystore2
+        .addresses()
+        .into_iter()
+        .find(|a| *a != address)
+        .unwrap();
+    assert!(keystore2.export_mnemonic(&other).is_err());
+}
+
 /// This test confirms rust's implementation of mnemonic is the same with the Sui Wallet
 #[test]
 fn sui_wallet_address_mnemonic_test() -> Result<(), anyhow::Error> {
@@ -58,3 +102,124 @@ fn sui_wallet_address_mnemonic_test() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+/// A Ledger device holding a single key, which approves every transaction.
+struct MockLedger {
+    key: SuiKeyPair,
+    message: Mutex<Vec<u8>>,
+}
+
+impl LedgerTransport for MockLedger {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let (ins, p1, p2, data) = (apdu[1], apdu[2], apdu[3], &apdu[5..]);
+        assert_eq!(apdu[4] as usize, data.len());
+        let response = match ins {
+            // Get public key
+            0x02 => self.key.public().as_ref().to_vec(),
+            // Sign transaction
+            0x03 => {
+                let mut message = self.message.lock().unwrap();
+                if p1 == 0x00 {
+                    message.clear();
+                }
+                message.extend_from_slice(data);
+                if p2 == 0x80 {
+                    vec![]
+                } else {
+                    let path_length = 1 + 4 * message[0] as usize;
+                    let signature: Signature = self.key.try_sign(&message[path_length..]).unwrap();
+                    signature.signature_bytes().to_vec()
+                }
+            }
+            _ => return Ok(vec![0x6d, 0x00]),
+        };
+        Ok([response, vec![0x90, 0x00]].concat())
+    }
+}
+
+fn mock_ledger() -> LedgerSigner {
+    let transport = MockLedger {
+        key: SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1),
+        message: Mutex::new(vec![]),
+    };
+    LedgerSigner::with_transport("m/44'/784'/0'/0'/0'".parse().unwrap(), Box::new(transport))
+}
+
+#[test]
+fn signer_config_test() {
+    let config: SignerConfig = "ledger:m/44'/784'/0'/0'/1'".parse().unwrap();
+    assert_eq!(config.to_string(), "ledger:m/44'/784'/0'/0'/1'");
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, "\"ledger:m/44'/784'/0'/0'/1'\"");
+    assert_eq!(serde_json::from_str::<SignerConfig>(&json).unwrap(), config);
+
+    // Ledger devices hold ed25519 keys
+    assert!("ledger:m/54'/784'/0'/0/0".parse::<SignerConfig>().is_err());
+    assert!("trezor:m/44'/784'/0'/0'/0'"
+        .parse::<SignerConfig>()
+        .is_err());
+}
+
+#[test]
+fn ledger_signer_test() {
+    let mut keystore = KeystoreType::InMem(1).init().unwrap();
+    let ledger = mock_ledger();
+    let address = SuiAddress::from(&ledger.public_key().unwrap());
+    keystore.add_signer(address, Box::new(ledger));
+    assert_eq!(keystore.addresses().len(), 2);
+    assert!(keystore.addresses().contains(&address));
+
+    // Longer than a single APDU
+    let msg = vec![7; 1000];
+    let signature = keystore.sign(&address, &msg).unwrap();
+    signature.verify_bytes(&msg, address).unwrap();
+
+    // A device holding another key can't sign for the address
+    let other = keystore.addresses()[0];
+    keystore.add_signer(other, Box::new(mock_ledger()));
+    assert!(keystore.sign(&other, &msg).is_err());
+}
+
+fn signed_transfer(key: &SuiKeyPair) -> Transaction {
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::from(&key.public()),
+        None,
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        ),
+        1000,
+    );
+    Transaction::new(data.clone(), Signature::new(&data, key))
+}
+
+#[test]
+fn transaction_journal_test() {
+    let temp_dir = TempDir::new().unwrap();
+    let journal = TransactionJournal::open(temp_dir.path().join("journal")).unwrap();
+    assert!(journal.pending().unwrap().is_empty());
+
+    let key = SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1);
+    let first = signed_transfer(&key);
+    let second = signed_transfer(&key);
+    journal.record(&first).unwrap();
+    journal.record(&second).unwrap();
+    // Recording a transaction again, e.g. when resuming it, keeps a single entry
+    journal.record(&first).unwrap();
+    assert_eq!(journal.pending().unwrap().len(), 2);
+
+    journal.complete(first.digest()).unwrap();
+    // Reopened after a crash
+    let journal = TransactionJournal::open(journal.dir()).unwrap();
+    let pending = journal.pending().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].digest(), second.digest());
+    pending[0].verify().unwrap();
+
+    // Completing twice is harmless
+    journal.complete(second.digest()).unwrap();
+    journal.complete(second.digest()).unwrap();
+    assert!(journal.pending().unwrap().is_empty());
+}
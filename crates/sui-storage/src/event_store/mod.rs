@@ -26,7 +26,7 @@ use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDig
 use sui_types::error::SuiError;
 use sui_types::error::SuiError::{StorageCorruptedFieldError, StorageMissingFieldError};
 use sui_types::event::{Event, TransferType};
-use sui_types::event::{EventEnvelope, EventType};
+use sui_types::event::{EventEnvelope, EventID, EventType};
 use sui_types::object::Owner;
 use tokio_stream::StreamExt;
 
@@ -51,6 +51,10 @@ pub struct StoredEvent {
     timestamp: u64,
     /// Not present for non-transaction System events (eg EpochChange)
     tx_digest: Option<TransactionDigest>,
+    /// Sequence number of the transaction which emitted the event
+    seq_num: u64,
+    /// Index of the event in the events emitted by the transaction
+    event_num: u64,
     /// The variant name from SuiEvent, eg MoveEvent, Publish, etc.
     event_type: SharedStr,
     /// Package ID if available
@@ -108,6 +112,7 @@ impl StoredEvent {
             type_: type_.clone(),
             fields: None,
             bcs,
+            schema: None,
         })
     }
 
@@ -276,6 +281,10 @@ impl TryInto<SuiEventEnvelope> for StoredEvent {
     fn try_into(self) -> Result<SuiEventEnvelope, Self::Error> {
         let timestamp = self.timestamp;
         let tx_digest = self.tx_digest;
+        let id = EventID {
+            tx_seq_num: self.seq_num,
+            event_num: self.event_num,
+        };
         let event_type_str = self.event_type.as_str();
         let event = match EventType::from_str(event_type_str) {
             Ok(type_) => {
@@ -295,6 +304,7 @@ impl TryInto<SuiEventEnvelope> for StoredEvent {
         Ok(SuiEventEnvelope {
             timestamp,
             tx_digest,
+            id,
             event,
         })
     }
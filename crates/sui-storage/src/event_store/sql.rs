@@ -72,11 +72,13 @@ enum EventsTableColumns {
     Sender,
     /// recipient TEXT
     Recipient,
+    /// event_num INTEGER
+    EventNum,
 }
 
 const SQL_INSERT_TX: &str = "INSERT INTO events (timestamp, seq_num, tx_digest, event_type, \
     package_id, module_name, object_id, fields, move_event_name, contents, sender,  \
-    recipient) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+    recipient, event_num) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
 
 const INDEXED_COLUMNS: &[&str] = &[
     "timestamp",
@@ -269,6 +271,9 @@ impl From<SqliteRow> for StoredEvent {
     // TODO: gracefully handle data corruption/incompatibility without panicking
     fn from(row: SqliteRow) -> Self {
         let timestamp: i64 = row.get(EventsTableColumns::Timestamp as usize);
+        let seq_num: i64 = row.get(EventsTableColumns::SeqNum as usize);
+        // Events stored before the column was added are read as the first of their transaction
+        let event_num: Option<i64> = row.get(EventsTableColumns::EventNum as usize);
         let digest_raw: Option<Vec<u8>> = row.get(EventsTableColumns::TxDigest as usize);
         let tx_digest = digest_raw.map(|bytes| {
             TransactionDigest::new(
@@ -314,6 +319,8 @@ impl From<SqliteRow> for StoredEvent {
         StoredEvent {
             timestamp: timestamp as u64,
             tx_digest,
+            seq_num: seq_num as u64,
+            event_num: event_num.unwrap_or_default() as u64,
             event_type: SharedStr::from(Event::name_from_ordinal(event_type as usize)),
             package_id,
             module_name: module_name.map(|s| s.into()),
@@ -393,6 +400,7 @@ impl EventStore for SqlEventStore {
                 .bind(event.event.move_event_contents())
                 .bind(sender)
                 .bind(event.event.recipient_serialized()?)
+                .bind(event.event_num as i64)
                 .execute(&self.pool)
                 .await
                 .map_err(convert_sqlx_err)?;
@@ -540,10 +548,7 @@ impl EventStore for SqlEventStore {
         limit: usize,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let recipient_str =
-            serde_json::to_string(recipient).map_err(|e| SuiError::OwnerFailedToSerialize {
-                error: (e.to_string()),
-            })?;
+        let recipient_str = recipient.to_canonical_json();
         let rows = sqlx::query(QUERY_BY_RECIPIENT)
             .persistent(true)
             .bind(start_time as i64)
@@ -597,6 +602,8 @@ mod tests {
     fn test_queried_event_vs_test_envelope(queried: &StoredEvent, orig: &EventEnvelope) {
         assert_eq!(queried.timestamp, orig.timestamp);
         assert_eq!(queried.tx_digest, orig.tx_digest);
+        assert_eq!(queried.seq_num, orig.seq_num);
+        assert_eq!(queried.event_num, orig.event_num);
         assert_eq!(queried.event_type, shared_str!(orig.event_type()));
         assert_eq!(queried.package_id, orig.event.package_id());
         assert_eq!(
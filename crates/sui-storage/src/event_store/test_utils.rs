@@ -54,6 +54,7 @@ pub fn new_test_publish_event(
         timestamp,
         None,
         seq_num,
+        0,
         Event::Publish {
             sender: sender.unwrap_or_else(SuiAddress::random_for_testing_only),
             package_id: ObjectID::random(),
@@ -73,6 +74,7 @@ pub fn new_test_newobj_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        0,
         Event::NewObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -95,6 +97,7 @@ pub fn new_test_deleteobj_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        0,
         Event::DeleteObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -118,6 +121,7 @@ pub fn new_test_transfer_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        0,
         Event::TransferObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -161,6 +165,7 @@ pub fn new_test_move_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        0,
         move_event,
         Some(json),
     )
@@ -6,11 +6,14 @@
 
 use rocksdb::Options;
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
 use typed_store_derive::DBMapUtils;
 
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
+use sui_types::committee::EpochId;
 use sui_types::error::SuiResult;
+use sui_types::gas::EpochFeeSummary;
 
 use sui_types::base_types::ObjectRef;
 use sui_types::object::Owner;
@@ -22,6 +25,48 @@ use typed_store::traits::TypedStoreDebug;
 
 use crate::default_db_options;
 
+/// The indexes of an `IndexStore` which are derived from the certificates and effects of the
+/// transactions alone, and so can be built from the transactions a node already executed when they
+/// are added. The timestamps are the local time transactions were indexed at, so they can't.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    strum_macros::EnumString,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum TxIndex {
+    TransactionsFromAddr,
+    TransactionsToAddr,
+    TransactionsByInputObject,
+    TransactionsByMutatedObject,
+    TransactionsByMoveFunction,
+    TransactionOrder,
+    EpochFeeSummaries,
+}
+
+/// How `IndexStore::index_tx` writes a transaction.
+#[derive(Clone, Copy, Debug)]
+pub enum IndexWrite {
+    /// Into all the indexes, as indexed at `timestamp_ms`.
+    All { timestamp_ms: u64 },
+    /// Into one index only, to build it from transactions indexed before it was added.
+    Backfill(TxIndex),
+}
+
+impl IndexWrite {
+    fn includes(&self, index: TxIndex) -> bool {
+        match self {
+            IndexWrite::All { .. } => true,
+            IndexWrite::Backfill(backfilled) => *backfilled == index,
+        }
+    }
+}
+
 #[derive(DBMapUtils)]
 pub struct IndexStore {
     /// Index from sui address to transactions initiated by that address.
@@ -45,12 +90,20 @@ pub struct IndexStore {
     transactions_by_move_function:
         DBMap<(ObjectID, String, String, TxSequenceNumber), TransactionDigest>,
 
+    /// Map from transaction digest to its sequence number, to order the transactions found
+    /// otherwise, e.g. in checkpoints, as the other indexes are.
+    #[default_options_override_fn = "transaction_order_table_default_config"]
+    transaction_order: DBMap<TransactionDigest, TxSequenceNumber>,
+
     /// This is a map between the transaction digest and its timestamp (UTC timestamp in
     /// **milliseconds** since epoch 1/1/1970). A transaction digest is subjectively time stamped
     /// on a node according to the local machine time, so it varies across nodes.
     /// The timestamping happens when the node sees a txn certificate for the first time.
     #[default_options_override_fn = "timestamps_table_default_config"]
     timestamps: DBMap<TransactionDigest, u64>,
+
+    /// Map from epoch to the gas charged by its transactions and the SUI minted at its end.
+    epoch_fee_summaries: DBMap<EpochId, EpochFeeSummary>,
 }
 
 // These functions are used to initialize the DB tables
@@ -69,6 +122,9 @@ fn transactions_by_mutated_object_id_table_default_config() -> Options {
 fn transactions_by_move_function_table_default_config() -> Options {
     default_db_options(None, Some(1_000_000)).0
 }
+fn transaction_order_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).1
+}
 fn timestamps_table_default_config() -> Options {
     default_db_options(None, Some(1_000_000)).1
 }
@@ -82,55 +138,109 @@ impl IndexStore {
         move_functions: impl Iterator<Item = (ObjectID, Identifier, Identifier)> + Clone,
         sequence: TxSequenceNumber,
         digest: &TransactionDigest,
-        timestamp_ms: u64,
+        write: IndexWrite,
+        fees: &EpochFeeSummary,
     ) -> SuiResult {
-        let batch = self.transactions_from_addr.batch();
+        let mut batch = self.transactions_from_addr.batch();
 
-        let batch = batch.insert_batch(
-            &self.transactions_from_addr,
-            std::iter::once(((sender, sequence), *digest)),
-        )?;
+        if write.includes(TxIndex::TransactionsFromAddr) {
+            batch = batch.insert_batch(
+                &self.transactions_from_addr,
+                std::iter::once(((sender, sequence), *digest)),
+            )?;
+        }
 
-        let batch = batch.insert_batch(
-            &self.transactions_by_input_object_id,
-            active_inputs.map(|id| ((id, sequence), *digest)),
-        )?;
+        if write.includes(TxIndex::TransactionsByInputObject) {
+            batch = batch.insert_batch(
+                &self.transactions_by_input_object_id,
+                active_inputs.map(|id| ((id, sequence), *digest)),
+            )?;
+        }
 
-        let batch = batch.insert_batch(
-            &self.transactions_by_mutated_object_id,
-            mutated_objects
-                .clone()
-                .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
-        )?;
-
-        let batch = batch.insert_batch(
-            &self.transactions_by_move_function,
-            move_functions.map(|(obj_id, module, function)| {
-                (
-                    (obj_id, module.to_string(), function.to_string(), sequence),
-                    *digest,
-                )
-            }),
-        )?;
-
-        let batch = batch.insert_batch(
-            &self.transactions_to_addr,
-            mutated_objects.filter_map(|(_, owner)| {
-                owner
-                    .get_owner_address()
-                    .ok()
-                    .map(|addr| ((addr, sequence), digest))
-            }),
-        )?;
+        if write.includes(TxIndex::TransactionsByMutatedObject) {
+            batch = batch.insert_batch(
+                &self.transactions_by_mutated_object_id,
+                mutated_objects
+                    .clone()
+                    .map(|(obj_ref, _)| ((obj_ref.0, sequence), *digest)),
+            )?;
+        }
+
+        if write.includes(TxIndex::TransactionsByMoveFunction) {
+            batch = batch.insert_batch(
+                &self.transactions_by_move_function,
+                move_functions.map(|(obj_id, module, function)| {
+                    (
+                        (obj_id, module.to_string(), function.to_string(), sequence),
+                        *digest,
+                    )
+                }),
+            )?;
+        }
+
+        if write.includes(TxIndex::TransactionsToAddr) {
+            batch = batch.insert_batch(
+                &self.transactions_to_addr,
+                mutated_objects.filter_map(|(_, owner)| {
+                    owner
+                        .get_owner_address()
+                        .ok()
+                        .map(|addr| ((addr, sequence), digest))
+                }),
+            )?;
+        }
+
+        if write.includes(TxIndex::TransactionOrder) {
+            batch = batch.insert_batch(
+                &self.transaction_order,
+                std::iter::once((*digest, sequence)),
+            )?;
+        }
 
-        let batch =
-            batch.insert_batch(&self.timestamps, std::iter::once((*digest, timestamp_ms)))?;
+        if let IndexWrite::All { timestamp_ms } = write {
+            batch =
+                batch.insert_batch(&self.timestamps, std::iter::once((*digest, timestamp_ms)))?;
+        }
+
+        if write.includes(TxIndex::EpochFeeSummaries) {
+            // Transactions are indexed one at a time, so the summary isn't updated concurrently
+            let mut summary = self
+                .epoch_fee_summaries
+                .get(&fees.epoch)?
+                .unwrap_or_else(|| EpochFeeSummary {
+                    epoch: fees.epoch,
+                    ..Default::default()
+                });
+            summary.add(fees);
+            batch = batch.insert_batch(
+                &self.epoch_fee_summaries,
+                std::iter::once((fees.epoch, summary)),
+            )?;
+        }
 
         batch.write()?;
 
         Ok(())
     }
 
+    /// Removes all the entries of `index`, before building it again from the first transaction.
+    /// Entries of the other indexes are keyed by transaction, so writing them again is harmless,
+    /// but the fee summaries of the transactions would be added twice.
+    pub fn clear_index(&self, index: TxIndex) -> SuiResult {
+        match index {
+            TxIndex::TransactionsFromAddr => self.transactions_from_addr.clear()?,
+            TxIndex::TransactionsToAddr => self.transactions_to_addr.clear()?,
+            TxIndex::TransactionsByInputObject => self.transactions_by_input_object_id.clear()?,
+            TxIndex::TransactionsByMutatedObject => {
+                self.transactions_by_mutated_object_id.clear()?
+            }
+            TxIndex::TransactionsByMoveFunction => self.transactions_by_move_function.clear()?,
+            TxIndex::TransactionOrder => self.transaction_order.clear()?,
+            TxIndex::EpochFeeSummaries => self.epoch_fee_summaries.clear()?,
+        }
+        Ok(())
+    }
+
     /// Returns unix timestamp for a transaction if it exists
     pub fn get_timestamp_ms(
         &self,
@@ -140,16 +250,40 @@ impl IndexStore {
         Ok(ts)
     }
 
+    /// Returns the sequence numbers of the transactions `digests`, `None` for those not indexed.
+    pub fn get_transaction_sequence_numbers(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<TxSequenceNumber>>> {
+        Ok(self.transaction_order.multi_get(digests)?)
+    }
+
+    /// Returns at most `limit` of the transactions indexed under `key`, in order from the
+    /// transaction `cursor` on, or from the first one if `None`. If `descending`, in reverse
+    /// order from the transaction `cursor` back, or from the last one if `None`.
     fn get_transactions_by_object<
         KeyT: Clone + Serialize + DeserializeOwned + std::cmp::PartialEq,
     >(
         index: &DBMap<(KeyT, TxSequenceNumber), TransactionDigest>,
-        object_id: KeyT,
+        key: KeyT,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
+        if descending {
+            return get_entries_descending(
+                index,
+                cursor,
+                limit,
+                |seq| (key.clone(), seq),
+                |k| (k.0 == key).then_some(k.1),
+            );
+        }
         Ok(index
             .iter()
-            .skip_to(&(object_id.clone(), TxSequenceNumber::MIN))?
-            .take_while(|((id, _), _)| *id == object_id)
+            .skip_to(&(key.clone(), cursor.unwrap_or(TxSequenceNumber::MIN)))?
+            .take_while(|((id, _), _)| *id == key)
+            .take(limit)
             .map(|((_, seq), digest)| (seq, digest))
             .collect())
     }
@@ -157,52 +291,204 @@ impl IndexStore {
     pub fn get_transactions_by_input_object(
         &self,
         input_object: ObjectID,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
-        Self::get_transactions_by_object(&self.transactions_by_input_object_id, input_object)
+        Self::get_transactions_by_object(
+            &self.transactions_by_input_object_id,
+            input_object,
+            cursor,
+            limit,
+            descending,
+        )
     }
 
     pub fn get_transactions_by_mutated_object(
         &self,
         mutated_object: ObjectID,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
-        Self::get_transactions_by_object(&self.transactions_by_mutated_object_id, mutated_object)
+        Self::get_transactions_by_object(
+            &self.transactions_by_mutated_object_id,
+            mutated_object,
+            cursor,
+            limit,
+            descending,
+        )
     }
 
     pub fn get_transactions_from_addr(
         &self,
         addr: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
-        Self::get_transactions_by_object(&self.transactions_from_addr, addr)
+        Self::get_transactions_by_object(
+            &self.transactions_from_addr,
+            addr,
+            cursor,
+            limit,
+            descending,
+        )
     }
 
+    /// Returns at most `limit` of the transactions which called a function of `package`, only
+    /// of `module` and `function` if given, in order from the transaction `cursor` on, or in
+    /// reverse order from `cursor` back if `descending`. The index is ordered by function first,
+    /// so unless both are given the matching entries are scanned, keeping the first `limit`
+    /// transactions only.
     pub fn get_transactions_by_move_function(
         &self,
         package: ObjectID,
         module: Option<String>,
         function: Option<String>,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
-        Ok(self
-            .transactions_by_move_function
-            .iter()
-            .skip_to(&(
-                package,
-                module.clone().unwrap_or_else(|| "".to_string()),
-                function.clone().unwrap_or_else(|| "".to_string()),
-                TxSequenceNumber::MIN,
-            ))?
+        if let (Some(module), Some(function)) = (&module, &function) {
+            if descending {
+                return get_entries_descending(
+                    &self.transactions_by_move_function,
+                    cursor,
+                    limit,
+                    |seq| (package, module.clone(), function.clone(), seq),
+                    |(id, m, f, seq)| {
+                        (*id == package && m == module && f == function).then_some(*seq)
+                    },
+                );
+            }
+        }
+        let exact = module.is_some() && function.is_some();
+        let iter = self.transactions_by_move_function.iter().skip_to(&(
+            package,
+            module.clone().unwrap_or_default(),
+            function.clone().unwrap_or_default(),
+            match cursor {
+                Some(cursor) if exact => cursor,
+                _ => TxSequenceNumber::MIN,
+            },
+        ))?;
+        let matching = iter
             .take_while(|((id, m, f, _), _)| {
                 *id == package
                     && module.as_ref().map(|x| x == m).unwrap_or(true)
                     && function.as_ref().map(|x| x == f).unwrap_or(true)
             })
-            .map(|((_, _, _, seq), digest)| (seq, digest))
-            .collect())
+            .map(|((_, _, _, seq), digest)| (seq, digest));
+        if exact {
+            return Ok(matching.take(limit).collect());
+        }
+        // A transaction may call several functions of the package, keep it once
+        let mut transactions = BTreeMap::new();
+        let in_page = |seq: &TxSequenceNumber| match (cursor, descending) {
+            (None, _) => true,
+            (Some(cursor), false) => *seq >= cursor,
+            (Some(cursor), true) => *seq <= cursor,
+        };
+        for (seq, digest) in matching.filter(|(seq, _)| in_page(seq)) {
+            transactions.insert(seq, digest);
+            if transactions.len() > limit {
+                let dropped = if descending {
+                    *transactions.keys().next().unwrap()
+                } else {
+                    *transactions.keys().next_back().unwrap()
+                };
+                transactions.remove(&dropped);
+            }
+        }
+        Ok(if descending {
+            transactions.into_iter().rev().collect()
+        } else {
+            transactions.into_iter().collect()
+        })
     }
 
     pub fn get_transactions_to_addr(
         &self,
         addr: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
+        descending: bool,
+    ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
+        Self::get_transactions_by_object(
+            &self.transactions_to_addr,
+            addr,
+            cursor,
+            limit,
+            descending,
+        )
+    }
+
+    /// Returns the fee summary of `epoch`, if any of its transactions was indexed.
+    pub fn get_epoch_fee_summary(&self, epoch: EpochId) -> SuiResult<Option<EpochFeeSummary>> {
+        Ok(self.epoch_fee_summaries.get(&epoch)?)
+    }
+
+    /// Returns the fee summaries of all the epochs, in order.
+    pub fn get_epoch_fee_summaries(&self) -> SuiResult<Vec<EpochFeeSummary>> {
+        Ok(self
+            .epoch_fee_summaries
+            .iter()
+            .map(|(_, summary)| summary)
+            .collect())
+    }
+
+    /// Returns at most `limit` transactions sent to `addr` after the transaction `cursor`, or
+    /// from the first one if `None`.
+    pub fn get_transactions_to_addr_after(
+        &self,
+        addr: SuiAddress,
+        cursor: Option<TxSequenceNumber>,
+        limit: usize,
     ) -> SuiResult<Vec<(TxSequenceNumber, TransactionDigest)>> {
-        Self::get_transactions_by_object(&self.transactions_to_addr, addr)
+        let start = match cursor {
+            Some(cursor) => match cursor.checked_add(1) {
+                Some(start) => start,
+                None => return Ok(vec![]),
+            },
+            None => TxSequenceNumber::MIN,
+        };
+        self.get_transactions_to_addr(addr, Some(start), limit, false)
+    }
+}
+
+/// Returns at most `limit` entries of `index` in reverse order, from the key `to_key(cursor)`
+/// back, or from `to_key(TxSequenceNumber::MAX)` if `None`, as long as `sequence` finds the
+/// sequence number of the transaction in their key. Iterators only go forward, so each entry is
+/// found by seeking to the last key before the previous one.
+fn get_entries_descending<K, V>(
+    index: &DBMap<K, V>,
+    cursor: Option<TxSequenceNumber>,
+    limit: usize,
+    to_key: impl Fn(TxSequenceNumber) -> K,
+    sequence: impl Fn(&K) -> Option<TxSequenceNumber>,
+) -> SuiResult<Vec<(TxSequenceNumber, V)>>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    let mut entries = vec![];
+    let mut upper = cursor.unwrap_or(TxSequenceNumber::MAX);
+    while entries.len() < limit {
+        let entry = index
+            .iter()
+            .skip_prior_to(&to_key(upper))?
+            .next()
+            .and_then(|(key, value)| sequence(&key).map(|seq| (seq, value)));
+        let (seq, value) = match entry {
+            Some(entry) => entry,
+            None => break,
+        };
+        entries.push((seq, value));
+        match seq.checked_sub(1) {
+            Some(next) => upper = next,
+            None => break,
+        }
     }
+    Ok(entries)
 }
@@ -10,6 +10,7 @@ pub use indexes::IndexStore;
 pub mod event_store;
 pub mod mutex_table;
 pub mod node_sync_store;
+pub mod table;
 pub mod write_ahead_log;
 
 use rocksdb::Options;
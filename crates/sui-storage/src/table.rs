@@ -0,0 +1,514 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables of a store, independent of the backend they are persisted in.
+//!
+//! A [`Table`] has the interface of a RocksDB [`DBMap`]: typed reads and writes, iterators which
+//! seek to keys, and batches which write to several tables of the same backend atomically. A
+//! store written against it, e.g. the authority store, runs on any backend:
+//!
+//! - [`StorageBackend::RocksDB`], the column families of a RocksDB database, the default.
+//! - [`StorageBackend::InMemory`], maps in memory, for tests and simulations which don't need
+//!   their state to outlive the process.
+//!
+//! Nodes pick the backend of their authority store with the `storage-backend` field of their
+//! config, and swarms with `SwarmBuilder::with_storage_backend`.
+//!
+//! Keys are ordered by their serialization in both backends, so iterations visit them in the same
+//! order. A new backend, e.g. a network-attached or tiered store, is a new variant of [`Table`],
+//! [`TableIter`] and [`TableBatch`].
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::sync::{Arc, RwLock};
+use typed_store::rocks::{DBBatch, DBMap, TypedStoreError};
+use typed_store::traits::Map;
+
+/// The backend the tables of a store are persisted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageBackend {
+    /// A RocksDB database in the directory of the store.
+    #[default]
+    RocksDB,
+    /// Maps in memory, lost when the store is dropped.
+    InMemory,
+}
+
+/// A table of a store, mapping keys of type `K` to values of type `V`.
+pub enum Table<K, V> {
+    RocksDB(DBMap<K, V>),
+    InMemory(InMemoryTable<K, V>),
+}
+
+impl<K, V> From<DBMap<K, V>> for Table<K, V> {
+    fn from(map: DBMap<K, V>) -> Self {
+        Self::RocksDB(map)
+    }
+}
+
+impl<K, V> Table<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// The table `name` of the in-memory database `db`.
+    pub fn in_memory(db: &InMemoryDB, name: &str) -> Self {
+        Self::InMemory(InMemoryTable {
+            db: db.clone(),
+            name: name.to_string(),
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn get(&self, key: &K) -> Result<Option<V>, TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.get(key),
+            Self::InMemory(table) => Ok(table.get(key)),
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> Result<bool, TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.contains_key(key),
+            Self::InMemory(table) => Ok(table.get_raw(&encode_key(key)).is_some()),
+        }
+    }
+
+    pub fn multi_get<J: Borrow<K>>(
+        &self,
+        keys: impl IntoIterator<Item = J>,
+    ) -> Result<Vec<Option<V>>, TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.multi_get(keys),
+            Self::InMemory(table) => Ok(keys.into_iter().map(|k| table.get(k.borrow())).collect()),
+        }
+    }
+
+    pub fn insert(&self, key: &K, value: &V) -> Result<(), TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.insert(key, value),
+            Self::InMemory(table) => {
+                table.db.write(vec![(
+                    table.name.clone(),
+                    encode_key(key),
+                    Some(encode_value(value)),
+                )]);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn remove(&self, key: &K) -> Result<(), TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.remove(key),
+            Self::InMemory(table) => {
+                table
+                    .db
+                    .write(vec![(table.name.clone(), encode_key(key), None)]);
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes all the entries of the table.
+    pub fn clear(&self) -> Result<(), TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map.clear(),
+            Self::InMemory(table) => {
+                table.db.tables.write().unwrap().remove(&table.name);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    /// Syncs the write-ahead log of the database the table is in to disk, so that the writes to
+    /// all its tables outlive a crash of the machine, and not only of the process. A no-op in
+    /// memory.
+    pub fn flush(&self) -> Result<(), TypedStoreError> {
+        match self {
+            Self::RocksDB(map) => map
+                .rocksdb
+                .flush_wal(true)
+                .map_err(|e| TypedStoreError::RocksDBError(e.to_string())),
+            Self::InMemory(_) => Ok(()),
+        }
+    }
+
+    /// Iterates over the entries of the table in the order of their keys.
+    pub fn iter(&self) -> TableIter<'_, K, V> {
+        match self {
+            Self::RocksDB(map) => TableIter::RocksDB(map.iter()),
+            Self::InMemory(table) => TableIter::InMemory(InMemoryIter {
+                db: table.db.clone(),
+                name: table.name.clone(),
+                from: Some(Bound::Unbounded),
+                _phantom: PhantomData,
+            }),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// A batch of writes to tables of the backend of this table, applied atomically by
+    /// [`TableBatch::write`].
+    pub fn batch(&self) -> TableBatch {
+        match self {
+            Self::RocksDB(map) => TableBatch::RocksDB(map.batch()),
+            Self::InMemory(table) => TableBatch::InMemory(InMemoryBatch {
+                db: table.db.clone(),
+                writes: vec![],
+            }),
+        }
+    }
+}
+
+/// An iterator over the entries of a [`Table`], which seeks to keys like a RocksDB iterator.
+pub enum TableIter<'a, K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    RocksDB(<DBMap<K, V> as Map<'a, K, V>>::Iterator),
+    InMemory(InMemoryIter<K, V>),
+}
+
+impl<'a, K, V> TableIter<'a, K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Continues the iteration from the first key greater than or equal to `key`.
+    pub fn skip_to(self, key: &K) -> Result<Self, TypedStoreError> {
+        match self {
+            Self::RocksDB(iter) => iter.skip_to(key).map(Self::RocksDB),
+            Self::InMemory(mut iter) => {
+                iter.from = Some(Bound::Included(encode_key(key)));
+                Ok(Self::InMemory(iter))
+            }
+        }
+    }
+
+    /// Continues the iteration from the last key less than or equal to `key`.
+    pub fn skip_prior_to(self, key: &K) -> Result<Self, TypedStoreError> {
+        match self {
+            Self::RocksDB(iter) => iter.skip_prior_to(key).map(Self::RocksDB),
+            Self::InMemory(mut iter) => {
+                let key = encode_key(key);
+                iter.from = iter.seek(|rows| {
+                    rows.range::<Vec<u8>, _>((Bound::Unbounded, Bound::Included(&key)))
+                        .next_back()
+                        .map(|(key, _)| key.clone())
+                });
+                Ok(Self::InMemory(iter))
+            }
+        }
+    }
+
+    /// Continues the iteration from the last key of the table.
+    pub fn skip_to_last(self) -> Self {
+        match self {
+            Self::RocksDB(iter) => Self::RocksDB(iter.skip_to_last()),
+            Self::InMemory(mut iter) => {
+                iter.from = iter.seek(|rows| rows.keys().next_back().cloned());
+                Self::InMemory(iter)
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for TableIter<'a, K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::RocksDB(iter) => iter.next(),
+            Self::InMemory(iter) => iter.next(),
+        }
+    }
+}
+
+/// Writes to several tables of a backend, applied atomically.
+pub enum TableBatch {
+    RocksDB(DBBatch),
+    InMemory(InMemoryBatch),
+}
+
+impl TableBatch {
+    /// Adds the insertion of `new_vals` into `table` to the batch.
+    ///
+    /// Panics if `table` is not in the backend of the batch.
+    pub fn insert_batch<J, K, U, V>(
+        self,
+        table: &Table<K, V>,
+        new_vals: impl IntoIterator<Item = (J, U)>,
+    ) -> Result<Self, TypedStoreError>
+    where
+        J: Borrow<K>,
+        K: Serialize + DeserializeOwned,
+        U: Borrow<V>,
+        V: Serialize + DeserializeOwned,
+    {
+        match (self, table) {
+            (Self::RocksDB(batch), Table::RocksDB(map)) => {
+                batch.insert_batch(map, new_vals).map(Self::RocksDB)
+            }
+            (Self::InMemory(mut batch), Table::InMemory(table)) => {
+                batch
+                    .writes
+                    .extend(new_vals.into_iter().map(|(key, value)| {
+                        (
+                            table.name.clone(),
+                            encode_key(key.borrow()),
+                            Some(encode_value(value.borrow())),
+                        )
+                    }));
+                Ok(Self::InMemory(batch))
+            }
+            _ => panic!("A batch can only write to tables of its backend"),
+        }
+    }
+
+    /// Adds the removal of the keys `purged_vals` from `table` to the batch.
+    ///
+    /// Panics if `table` is not in the backend of the batch.
+    pub fn delete_batch<J, K, V>(
+        self,
+        table: &Table<K, V>,
+        purged_vals: impl IntoIterator<Item = J>,
+    ) -> Result<Self, TypedStoreError>
+    where
+        J: Borrow<K>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+    {
+        match (self, table) {
+            (Self::RocksDB(batch), Table::RocksDB(map)) => {
+                batch.delete_batch(map, purged_vals).map(Self::RocksDB)
+            }
+            (Self::InMemory(mut batch), Table::InMemory(table)) => {
+                batch.writes.extend(
+                    purged_vals
+                        .into_iter()
+                        .map(|key| (table.name.clone(), encode_key(key.borrow()), None)),
+                );
+                Ok(Self::InMemory(batch))
+            }
+            _ => panic!("A batch can only write to tables of its backend"),
+        }
+    }
+
+    /// Applies the writes of the batch, all at once.
+    pub fn write(self) -> Result<(), TypedStoreError> {
+        match self {
+            Self::RocksDB(batch) => batch.write(),
+            Self::InMemory(batch) => {
+                batch.db.write(batch.writes);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The serialized entries of a table, in the order of their keys.
+type Rows = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// A write to an in-memory table: the table, the key and the value, or `None` for a removal.
+type RowWrite = (String, Vec<u8>, Option<Vec<u8>>);
+
+/// A database in memory, the in-memory counterpart of a RocksDB database with its column
+/// families. Clones share the same tables.
+#[derive(Clone, Default)]
+pub struct InMemoryDB {
+    tables: Arc<RwLock<HashMap<String, Rows>>>,
+}
+
+impl InMemoryDB {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn write(&self, writes: Vec<RowWrite>) {
+        let mut tables = self.tables.write().unwrap();
+        for (name, key, value) in writes {
+            let rows = tables.entry(name).or_default();
+            match value {
+                Some(value) => rows.insert(key, value),
+                None => rows.remove(&key),
+            };
+        }
+    }
+}
+
+/// The writes of a [`TableBatch`] to tables of an [`InMemoryDB`].
+pub struct InMemoryBatch {
+    db: InMemoryDB,
+    writes: Vec<RowWrite>,
+}
+
+/// A table of an [`InMemoryDB`].
+pub struct InMemoryTable<K, V> {
+    db: InMemoryDB,
+    name: String,
+    _phantom: PhantomData<fn(K, V) -> (K, V)>,
+}
+
+impl<K, V> InMemoryTable<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    fn get(&self, key: &K) -> Option<V> {
+        self.get_raw(&encode_key(key))
+            .map(|value| decode_value(&value))
+    }
+
+    fn get_raw(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let tables = self.db.tables.read().unwrap();
+        tables.get(&self.name)?.get(key).cloned()
+    }
+}
+
+/// An iterator over the entries of an [`InMemoryTable`]. Each step reads the entry following the
+/// last one returned, so the iteration sees the writes made while it runs.
+pub struct InMemoryIter<K, V> {
+    db: InMemoryDB,
+    name: String,
+    /// The bound the next entry is after, or `None` once the iteration is over.
+    from: Option<Bound<Vec<u8>>>,
+    _phantom: PhantomData<fn(K, V) -> (K, V)>,
+}
+
+impl<K, V> InMemoryIter<K, V> {
+    /// The bound including the key `find` picks in the rows of the table, or `None` if it picks
+    /// none.
+    fn seek(&self, find: impl FnOnce(&Rows) -> Option<Vec<u8>>) -> Option<Bound<Vec<u8>>> {
+        let tables = self.db.tables.read().unwrap();
+        tables.get(&self.name).and_then(find).map(Bound::Included)
+    }
+}
+
+impl<K, V> Iterator for InMemoryIter<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let from = self.from.take()?;
+        let tables = self.db.tables.read().unwrap();
+        let (key, value) = tables
+            .get(&self.name)?
+            .range::<Vec<u8>, _>((from, Bound::Unbounded))
+            .next()?;
+        self.from = Some(Bound::Excluded(key.clone()));
+        Some((decode_key(key), decode_value(value)))
+    }
+}
+
+// Keys are serialized as by `DBMap`, big endian with fixed-size integers, so that their order is
+// the order of their serializations.
+fn key_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_big_endian()
+        .with_fixint_encoding()
+}
+
+fn encode_key<K: Serialize>(key: &K) -> Vec<u8> {
+    key_options()
+        .serialize(key)
+        .expect("Keys of in-memory tables must serialize")
+}
+
+fn decode_key<K: DeserializeOwned>(key: &[u8]) -> K {
+    key_options()
+        .deserialize(key)
+        .expect("Keys of in-memory tables are written by the table")
+}
+
+fn encode_value<V: Serialize>(value: &V) -> Vec<u8> {
+    bincode::serialize(value).expect("Values of in-memory tables must serialize")
+}
+
+fn decode_value<V: DeserializeOwned>(value: &[u8]) -> V {
+    bincode::deserialize(value).expect("Values of in-memory tables are written by the table")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(db: &InMemoryDB, name: &str) -> Table<u64, String> {
+        Table::in_memory(db, name)
+    }
+
+    #[test]
+    fn test_in_memory_iteration_seeks_like_rocksdb() {
+        let db = InMemoryDB::new();
+        let table = table(&db, "numbers");
+        for key in [300u64, 2, 1 << 40, 17] {
+            table.insert(&key, &key.to_string()).unwrap();
+        }
+
+        // Keys are in numeric order, not in the order of their little endian bytes
+        let keys: Vec<_> = table.keys().collect();
+        assert_eq!(keys, vec![2, 17, 300, 1 << 40]);
+
+        let from: Vec<_> = table.iter().skip_to(&17).unwrap().map(|(k, _)| k).collect();
+        assert_eq!(from, vec![17, 300, 1 << 40]);
+        let prior = table.iter().skip_prior_to(&299).unwrap().next();
+        assert_eq!(prior, Some((17, "17".to_string())));
+        assert_eq!(table.iter().skip_prior_to(&1).unwrap().next(), None);
+        assert_eq!(
+            table.iter().skip_to_last().next().map(|(k, _)| k),
+            Some(1 << 40)
+        );
+    }
+
+    #[test]
+    fn test_in_memory_batch_writes_tables_together() {
+        let db = InMemoryDB::new();
+        let (numbers, names) = (table(&db, "numbers"), table(&db, "names"));
+        numbers.insert(&1, &"one".to_string()).unwrap();
+
+        let batch = numbers
+            .batch()
+            .insert_batch(&names, [(1u64, "uno".to_string())])
+            .unwrap()
+            .delete_batch(&numbers, [1u64])
+            .unwrap();
+        // Nothing is written until the batch is
+        assert!(names.is_empty());
+        assert!(numbers.contains_key(&1).unwrap());
+
+        batch.write().unwrap();
+        assert!(numbers.is_empty());
+        assert_eq!(names.get(&1).unwrap(), Some("uno".to_string()));
+        assert_eq!(
+            names.multi_get([1u64, 2]).unwrap(),
+            vec![Some("uno".to_string()), None]
+        );
+
+        names.clear().unwrap();
+        assert!(names.is_empty());
+    }
+}
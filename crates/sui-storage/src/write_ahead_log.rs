@@ -11,6 +11,7 @@ use crate::mutex_table::{LockGuard, MutexTable};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use sui_types::base_types::TransactionDigest;
 use typed_store::traits::TypedStoreDebug;
@@ -106,6 +107,7 @@ where
         _mutex_guard: LockGuard,
         wal: &'a DBWriteAheadLog<C>,
     ) -> Self {
+        wal.in_flight_txes.fetch_add(1, Ordering::Relaxed);
         Self {
             tx: *tx,
             retry_num,
@@ -158,6 +160,7 @@ where
             error!(digest = ?tx, "DBTxGuard dropped without explicit commit");
             self.wal.implicit_drop_tx(&tx);
         }
+        self.wal.in_flight_txes.fetch_sub(1, Ordering::Relaxed);
     }
 }
 
@@ -179,6 +182,9 @@ pub struct DBWriteAheadLog<C> {
 
     // Guards the get/set in begin_tx
     mutex_table: MutexTable<TransactionDigest>,
+
+    // Number of the guards of txes not committed, released or dropped yet.
+    in_flight_txes: AtomicUsize,
 }
 
 const MUTEX_TABLE_SIZE: usize = 1024;
@@ -204,9 +210,15 @@ where
             tables,
             recoverable_txes: Mutex::new(recoverable_txes),
             mutex_table: MutexTable::new(MUTEX_TABLE_SIZE, MUTEX_TABLE_SHARD_SIZE),
+            in_flight_txes: AtomicUsize::new(0),
         }
     }
 
+    /// Number of the txes begun and not committed, released or dropped yet, i.e. being processed.
+    pub fn in_flight_txes(&self) -> usize {
+        self.in_flight_txes.load(Ordering::Relaxed)
+    }
+
     fn commit_tx(&self, tx: &TransactionDigest, is_commit: bool) -> SuiResult {
         if is_commit {
             debug!(digest = ?tx, "committing tx");
@@ -328,7 +340,9 @@ mod tests {
             assert!(recover_queue_empty(&log).await);
 
             let tx1 = log.begin_tx(&tx1_id, &1).await.unwrap();
+            assert_eq!(log.in_flight_txes(), 1);
             tx1.commit_tx();
+            assert_eq!(log.in_flight_txes(), 0);
 
             let tx2 = log.begin_tx(&tx2_id, &2).await.unwrap();
             tx2.commit_tx();
@@ -337,6 +351,7 @@ mod tests {
                 let _tx3 = log.begin_tx(&tx3_id, &3).await.unwrap();
                 // implicit drop
             }
+            assert_eq!(log.in_flight_txes(), 0);
 
             let (_, r) = log.read_one_recoverable_tx().await.unwrap().unwrap();
             // tx3 in recoverable txes because we dropped the guard.
@@ -3,7 +3,6 @@
 
 use futures::FutureExt;
 use prometheus::Registry;
-use std::net::{IpAddr, SocketAddr};
 use sui_config::NodeConfig;
 use sui_node::SuiNode;
 use tracing::{error, trace};
@@ -51,13 +50,8 @@ impl Container {
 
         let socket_addr =
             mysten_network::multiaddr::to_socket_addr(&config.network_address).unwrap();
-        let ip = match socket_addr {
-            SocketAddr::V4(v4) => IpAddr::V4(*v4.ip()),
-            _ => panic!("unsupported protocol"),
-        };
-
         let node = builder
-            .ip(ip)
+            .ip(socket_addr.ip())
             .name(format!("{}", config.protocol_public_key()))
             .init(|| async {
                 tracing::info!("node restarted");
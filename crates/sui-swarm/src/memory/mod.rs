@@ -7,6 +7,10 @@
 //! down a single node and ensure that all of its running tasks are also shut down, something that
 //! is extremely difficult or down right impossible to do if all the nodes are running on the same
 //! runtime.
+//!
+//! In simulator builds (`cargo simtest`, see `scripts/simtest/README.md`) each node instead runs
+//! as a simulated node of the deterministic executor, with virtual time and seeded randomness.
+//! There is no flag to switch a swarm between the two: the build decides.
 
 mod node;
 pub use node::{Node, RuntimeType};
@@ -14,6 +14,7 @@ use std::{
 };
 use sui_config::builder::{CommitteeConfig, ConfigBuilder};
 use sui_config::genesis_config::{GenesisConfig, ValidatorGenesisInfo};
+use sui_config::node::StorageBackendConfig;
 use sui_config::NetworkConfig;
 use sui_types::base_types::SuiAddress;
 use tempfile::TempDir;
@@ -27,6 +28,7 @@ pub struct SwarmBuilder<R = OsRng> {
     fullnode_count: usize,
     fullnode_rpc_addr: Option<SocketAddr>,
     websocket_rpc_addr: Option<SocketAddr>,
+    storage_backend: StorageBackendConfig,
 }
 
 impl SwarmBuilder {
@@ -40,6 +42,7 @@ impl SwarmBuilder {
             fullnode_count: 0,
             fullnode_rpc_addr: None,
             websocket_rpc_addr: None,
+            storage_backend: StorageBackendConfig::default(),
         }
     }
 }
@@ -54,6 +57,7 @@ impl<R> SwarmBuilder<R> {
             fullnode_count: self.fullnode_count,
             fullnode_rpc_addr: self.fullnode_rpc_addr,
             websocket_rpc_addr: self.websocket_rpc_addr,
+            storage_backend: self.storage_backend,
         }
     }
 
@@ -99,6 +103,15 @@ impl<R> SwarmBuilder<R> {
         self.websocket_rpc_addr = Some(websocket_rpc_addr);
         self
     }
+
+    /// Set the backend of the authority stores of the validators and fullnodes, e.g. in memory
+    /// for tests which don't restart nodes.
+    ///
+    /// Defaults to RocksDB.
+    pub fn with_storage_backend(mut self, storage_backend: StorageBackendConfig) -> Self {
+        self.storage_backend = storage_backend;
+        self
+    }
 }
 
 impl<R: ::rand::RngCore + ::rand::CryptoRng> SwarmBuilder<R> {
@@ -118,6 +131,7 @@ impl<R: ::rand::RngCore + ::rand::CryptoRng> SwarmBuilder<R> {
 
         let network_config = config_builder
             .committee(self.committee)
+            .storage_backend(self.storage_backend)
             .with_swarm()
             .rng(self.rng)
             .build();
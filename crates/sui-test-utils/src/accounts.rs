@@ -0,0 +1,43 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::objects::generate_gas_objects_with_owner;
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::{AccountKeyPair, KeypairTraits},
+    object::Object,
+    signature_seed::SignatureSeed,
+};
+
+/// The number of test accounts.
+pub const TEST_ACCOUNTS_COUNT: usize = 4;
+
+/// Generate `TEST_ACCOUNTS_COUNT` test account key pairs, the same on every call.
+pub fn test_account_keys() -> Vec<(SuiAddress, AccountKeyPair)> {
+    let mut vec = Vec::new();
+    let ss = SignatureSeed::from_bytes(&[0; 32]).unwrap();
+    for i in 0..TEST_ACCOUNTS_COUNT {
+        let kp: AccountKeyPair = ss.new_deterministic_keypair(&[i as u8], Some(&[])).unwrap();
+        vec.push((kp.public().into(), kp));
+    }
+    vec
+}
+
+/// A test account with the gas objects it owns.
+pub struct FundedAccount {
+    pub address: SuiAddress,
+    pub keypair: AccountKeyPair,
+    pub gas_objects: Vec<Object>,
+}
+
+/// The test accounts, each owning `gas_objects_per_account` gas objects of `u64::MAX` balance.
+/// The gas objects still have to be added to the genesis or the store of the authorities.
+pub fn funded_accounts(gas_objects_per_account: usize) -> Vec<FundedAccount> {
+    test_account_keys()
+        .into_iter()
+        .map(|(address, keypair)| FundedAccount {
+            address,
+            keypair,
+            gas_objects: generate_gas_objects_with_owner(gas_objects_per_account, address),
+        })
+        .collect()
+}
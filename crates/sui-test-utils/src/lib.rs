@@ -0,0 +1,15 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fixtures shared by the tests of the crates of the workspace: deterministic funded accounts,
+//! test objects, the Move packages tests publish and a builder of transactions with test
+//! defaults. Helpers spawning a cluster need the node and stay in the `test-utils` crate.
+
+pub mod accounts;
+pub mod objects;
+pub mod packages;
+pub mod transaction;
+
+pub use accounts::{funded_accounts, test_account_keys, FundedAccount};
+pub use packages::TestPackage;
+pub use transaction::{random_object_ref, TestTransactionBuilder, MAX_GAS};
@@ -0,0 +1,49 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// The example Move packages of the repository which tests publish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TestPackage {
+    /// `sui_programmability/examples/basics`, with the `counter` and `object_basics` modules.
+    Basics,
+    /// `sui_programmability/examples/fungible_tokens`.
+    FungibleTokens,
+    /// `sui_programmability/examples/nfts`.
+    Nfts,
+}
+
+impl TestPackage {
+    /// The path of the package, from anywhere in the workspace.
+    pub fn path(&self) -> PathBuf {
+        let name = match self {
+            TestPackage::Basics => "basics",
+            TestPackage::FungibleTokens => "fungible_tokens",
+            TestPackage::Nfts => "nfts",
+        };
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("../../sui_programmability/examples");
+        path.push(name);
+        path
+    }
+
+    /// The serialized modules of the package, ready to be published.
+    pub fn build(&self) -> Vec<Vec<u8>> {
+        build_move_package_bytes(self.path())
+    }
+}
+
+/// Compile the move package at `path` into serialized modules, ready to be published.
+pub fn build_move_package_bytes(path: PathBuf) -> Vec<Vec<u8>> {
+    let build_config = BuildConfig::default();
+    let modules = sui_framework::build_move_package(&path, build_config).unwrap();
+    modules
+        .iter()
+        .map(|m| {
+            let mut module_bytes = Vec::new();
+            m.serialize(&mut module_bytes).unwrap();
+            module_bytes
+        })
+        .collect()
+}
@@ -0,0 +1,139 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use crate::packages::TestPackage;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber, SuiAddress};
+use sui_types::crypto::{AccountKeyPair, Signature};
+use sui_types::messages::{
+    CallArg, MoveCall, MoveModulePublish, SingleTransactionKind, Transaction, TransactionData,
+    TransactionKind, TransferObject, TransferSui,
+};
+
+/// The maximum gas per transaction.
+pub const MAX_GAS: u64 = 10_000;
+
+/// The reference to an object which doesn't exist.
+pub fn random_object_ref() -> ObjectRef {
+    (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::new([0; 32]),
+    )
+}
+
+/// Builds the transactions of tests, with a gas budget of `MAX_GAS` and a gas price of 1
+/// unless set otherwise.
+///
+/// ```ignore
+/// let data = TestTransactionBuilder::new(sender, gas_object_ref)
+///     .move_call(package_ref, "counter", "create", vec![])
+///     .build();
+/// ```
+pub struct TestTransactionBuilder {
+    sender: SuiAddress,
+    gas_object: ObjectRef,
+    gas_budget: u64,
+    gas_price: u64,
+    kind: Option<SingleTransactionKind>,
+}
+
+impl TestTransactionBuilder {
+    pub fn new(sender: SuiAddress, gas_object: ObjectRef) -> Self {
+        Self {
+            sender,
+            gas_object,
+            gas_budget: MAX_GAS,
+            gas_price: 1,
+            kind: None,
+        }
+    }
+
+    pub fn gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = gas_budget;
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    pub fn transfer_object(mut self, object_ref: ObjectRef, recipient: SuiAddress) -> Self {
+        self.kind = Some(SingleTransactionKind::TransferObject(TransferObject {
+            recipient,
+            object_ref,
+        }));
+        self
+    }
+
+    /// Transfers `amount` of the gas object, or the whole gas object if `None`.
+    pub fn transfer_sui(mut self, recipient: SuiAddress, amount: Option<u64>) -> Self {
+        self.kind = Some(SingleTransactionKind::TransferSui(TransferSui {
+            recipient,
+            amount,
+        }));
+        self
+    }
+
+    pub fn move_call(
+        self,
+        package: ObjectRef,
+        module: &str,
+        function: &str,
+        arguments: Vec<CallArg>,
+    ) -> Self {
+        self.move_call_with_type_args(package, module, function, vec![], arguments)
+    }
+
+    pub fn move_call_with_type_args(
+        mut self,
+        package: ObjectRef,
+        module: &str,
+        function: &str,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<CallArg>,
+    ) -> Self {
+        self.kind = Some(SingleTransactionKind::Call(MoveCall {
+            package,
+            module: Identifier::new(module).unwrap(),
+            function: Identifier::new(function).unwrap(),
+            type_arguments,
+            arguments,
+        }));
+        self
+    }
+
+    pub fn publish(mut self, modules: Vec<Vec<u8>>) -> Self {
+        self.kind = Some(SingleTransactionKind::Publish(MoveModulePublish {
+            modules,
+        }));
+        self
+    }
+
+    /// Publishes one of the example packages, compiling it first.
+    pub fn publish_package(self, package: TestPackage) -> Self {
+        self.publish(package.build())
+    }
+
+    /// # Panics
+    /// If no kind of transaction was set.
+    pub fn build(self) -> TransactionData {
+        let kind = self
+            .kind
+            .expect("The kind of the transaction must be set before building it");
+        TransactionData::new_with_gas_price(
+            TransactionKind::Single(kind),
+            self.sender,
+            self.gas_object,
+            self.gas_budget,
+            self.gas_price,
+        )
+    }
+
+    pub fn build_and_sign(self, keypair: &AccountKeyPair) -> Transaction {
+        let data = self.build();
+        let signature = Signature::new(&data, keypair);
+        Transaction::new(data, signature)
+    }
+}
@@ -9,12 +9,17 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_config::genesis::Genesis;
+use sui_framework::PackageAbi;
+use sui_tool::committee_history::{export_committee_history, CommitteeHistoryFormat};
 use sui_tool::db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand};
+use sui_tool::export::{export_snapshot, ExportFormat};
+use sui_tool::index_tool::{execute_index_command, IndexCommand};
+use sui_tool::pretty_print::{called_packages, pretty_print, TransactionInput};
 
 use sui_core::authority_client::{
     AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics,
 };
-use sui_types::{base_types::*, batch::*, messages::*, object::Owner};
+use sui_types::{base_types::*, batch::*, messages::*, move_package::MovePackage, object::Owner};
 
 use anyhow::anyhow;
 use futures::stream::StreamExt;
@@ -82,6 +87,23 @@ pub enum ToolCommand {
         #[clap(long, help = "The object ID to fetch")]
         digest: TransactionDigest,
     },
+
+    /// Decode a transaction into an annotated, human readable form, including its signature
+    /// and effects. The input is base64 and may be a transaction digest, a signed transaction,
+    /// or transaction data as submitted to the JSON-RPC API (`tx_bytes`):
+    ///
+    ///     $ sui-tool pp-tx --genesis $HOME/.sui/sui_config/genesis.blob <BASE64>
+    ///
+    /// With --genesis, digests are fetched from the validators, effects are looked up, and
+    /// Move call arguments are decoded against the signature of the called function.
+    #[clap(name = "pp-tx")]
+    PrettyPrintTransaction {
+        #[clap(long = "genesis")]
+        genesis: Option<PathBuf>,
+
+        #[clap(help = "Base64 transaction digest, signed transaction or transaction data")]
+        input: TransactionInput,
+    },
     /// Tool to read validator & gateway db.
     #[clap(name = "db-tool")]
     DbTool {
@@ -92,6 +114,80 @@ pub enum ToolCommand {
         cmd: Option<DbToolCommand>,
     },
 
+    /// Export the state of the chain at a checkpoint from the database of a node, as
+    /// object, transaction and event tables for data warehouses. The schemas of the tables are
+    /// documented in `sui_tool::export`:
+    ///
+    ///     $ sui-tool export --db-path $HOME/.sui/db --genesis genesis.blob \
+    ///         --at-checkpoint 1000 --output-dir export
+    #[clap(name = "export")]
+    Export {
+        /// Path of the DB of the node
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(long, arg_enum, default_value = "parquet")]
+        format: ExportFormat,
+        /// The last checkpoint whose transactions are exported, and after which the objects are
+        /// read
+        #[clap(long)]
+        at_checkpoint: CheckpointSequenceNumber,
+        /// Directory the tables are written to
+        #[clap(long, default_value = "export")]
+        output_dir: PathBuf,
+    },
+
+    /// Export the committee of every epoch from the database of a node, with the voting weight,
+    /// stake, delegation and gas price quote of each member. The columns are documented in
+    /// `sui_tool::committee_history`:
+    ///
+    ///     $ sui-tool committee-history --db-path $HOME/.sui/db --format csv \
+    ///         --output committees.csv
+    #[clap(name = "committee-history")]
+    CommitteeHistory {
+        /// Path of the DB of the node
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long, arg_enum, default_value = "csv")]
+        format: CommitteeHistoryFormat,
+        /// File the history is written to
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Maintain the indexes of a fullnode, e.g. build an index added in a new release from the
+    /// transactions the node executed before:
+    ///
+    ///     $ sui-tool index backfill transactions-by-move-function --db-path $HOME/.sui/db
+    #[clap(name = "index")]
+    Index {
+        #[clap(subcommand)]
+        cmd: IndexCommand,
+    },
+
+    /// Check that the system packages built into this binary can replace published ones, i.e.
+    /// that they keep the layout of their structs and the signature of their public and entry
+    /// functions, e.g. to gate framework changes on the ABI of the last release:
+    ///
+    ///     $ sui-tool check-framework-compatibility --published framework-abi.json
+    ///
+    /// Fails if they can't.
+    #[clap(name = "check-framework-compatibility")]
+    CheckFrameworkCompatibility {
+        /// File of the published packages, either the JSON of their ABI, as returned by
+        /// `sui_framework::abi_json`, or their serialized modules, as in the `system-packages`
+        /// file of a build of sui-framework
+        #[clap(long, required_unless_present = "genesis", conflicts_with = "genesis")]
+        published: Option<PathBuf>,
+        /// Fetch the published packages from the validators of this genesis instead
+        #[clap(long = "genesis")]
+        genesis: Option<PathBuf>,
+        /// Print the report as JSON
+        #[clap(long)]
+        json: bool,
+    },
+
     /// Pull down the batch stream for a validator(s).
     /// Note that this command currently operates sequentially, so it will block on the first
     /// validator indefinitely. Therefore you should generally use this with a --validator=
@@ -384,6 +480,105 @@ async fn get_object(
     ret
 }
 
+/// The ABI of the system packages built into this binary, as published on the chain of `clients`,
+/// from the first validator which returns each of them.
+async fn fetch_published_framework(
+    clients: &BTreeMap<AuthorityName, NetworkAuthorityClient>,
+) -> Result<Vec<PackageAbi>> {
+    let mut published = Vec::new();
+    for package in sui_framework::get_system_packages() {
+        let id = ObjectID::from(package.address());
+        let mut object = None;
+        for client in clients.values() {
+            let (_, resp) = match get_object(client, id, None, false).await.pop() {
+                Some(resp) => resp,
+                None => continue,
+            };
+            if let Ok(ObjectInfoResponse {
+                object_and_lock: Some(ObjectResponse { object: o, .. }),
+                ..
+            }) = resp
+            {
+                object = Some(o);
+                break;
+            }
+        }
+        let object = object.ok_or_else(|| {
+            anyhow!(
+                "No validator returned system package {} at {}",
+                package.name,
+                id
+            )
+        })?;
+        let move_package = object
+            .data
+            .try_as_package()
+            .ok_or_else(|| anyhow!("Object {} isn't a package", id))?;
+        published.push(sui_framework::published_package_abi(
+            &package.name,
+            move_package
+                .serialized_module_map()
+                .values()
+                .map(Vec::as_slice),
+        )?);
+    }
+    Ok(published)
+}
+
+/// Returns the first signed transaction and effects any validator knows about for `digest`.
+async fn fetch_transaction_info(
+    clients: &BTreeMap<AuthorityName, NetworkAuthorityClient>,
+    digest: TransactionDigest,
+) -> (Option<SenderSignedData>, Option<TransactionEffects>) {
+    let mut signed_data = None;
+    let mut effects = None;
+    for client in clients.values() {
+        let resp = match client
+            .handle_transaction_info_request(TransactionInfoRequest {
+                transaction_digest: digest,
+            })
+            .await
+        {
+            Ok(resp) => resp,
+            Err(_) => continue,
+        };
+        if signed_data.is_none() {
+            signed_data = resp
+                .certified_transaction
+                .map(|cert| cert.signed_data)
+                .or_else(|| resp.signed_transaction.map(|tx| tx.signed_data));
+        }
+        if effects.is_none() {
+            effects = resp.signed_effects.map(|e| e.effects);
+        }
+        if signed_data.is_some() && effects.is_some() {
+            break;
+        }
+    }
+    (signed_data, effects)
+}
+
+async fn fetch_packages(
+    clients: &BTreeMap<AuthorityName, NetworkAuthorityClient>,
+    ids: Vec<ObjectID>,
+) -> BTreeMap<ObjectID, MovePackage> {
+    let mut packages = BTreeMap::new();
+    for id in ids {
+        for client in clients.values() {
+            let package = get_object(client, id, None, false)
+                .await
+                .into_iter()
+                .find_map(|(_, resp)| resp.ok()?.object_and_lock)
+                .and_then(|o| o.object.data.try_as_package().cloned());
+            if let Some(package) = package {
+                packages.insert(id, package);
+                break;
+            }
+        }
+    }
+    packages
+}
+
 async fn handle_batch(client: &dyn AuthorityAPI, req: &BatchInfoRequest) {
     let mut streamx = Box::pin(client.handle_batch_stream(req.clone()).await.unwrap());
 
@@ -508,6 +703,52 @@ impl ToolCommand {
                 .await;
                 println!("{:#?}", responses);
             }
+            ToolCommand::PrettyPrintTransaction { genesis, input } => {
+                let clients = match genesis {
+                    Some(genesis) => Some(make_clients(&Genesis::load(genesis)?)?),
+                    None => None,
+                };
+                let (data, signature, effects) = match (input, &clients) {
+                    (TransactionInput::Digest(digest), Some(clients)) => {
+                        match fetch_transaction_info(clients, digest).await {
+                            (Some(signed_data), effects) => {
+                                (signed_data.data, Some(signed_data.tx_signature), effects)
+                            }
+                            (None, _) => {
+                                return Err(anyhow!(
+                                    "Transaction {:?} not found on any validator",
+                                    digest
+                                ))
+                            }
+                        }
+                    }
+                    (TransactionInput::Digest(_), None) => {
+                        return Err(anyhow!("--genesis is required to look up a digest"))
+                    }
+                    (TransactionInput::Signed(signed_data), clients) => {
+                        let effects = match clients {
+                            Some(clients) => {
+                                let tx = Transaction::new(
+                                    signed_data.data.clone(),
+                                    signed_data.tx_signature.clone(),
+                                );
+                                fetch_transaction_info(clients, *tx.digest()).await.1
+                            }
+                            None => None,
+                        };
+                        (signed_data.data, Some(signed_data.tx_signature), effects)
+                    }
+                    (TransactionInput::Unsigned(data), _) => (data, None, None),
+                };
+                let packages = match &clients {
+                    Some(clients) => fetch_packages(clients, called_packages(&data)).await,
+                    None => BTreeMap::new(),
+                };
+                print!(
+                    "{}",
+                    pretty_print(&data, signature.as_ref(), effects.as_ref(), &packages)?
+                );
+            }
             ToolCommand::DbTool { db_path, cmd } => {
                 let path = PathBuf::from(db_path);
                 match cmd {
@@ -515,6 +756,49 @@ impl ToolCommand {
                     None => print_db_all_tables(path)?,
                 }
             }
+            ToolCommand::Export {
+                db_path,
+                genesis,
+                format,
+                at_checkpoint,
+                output_dir,
+            } => {
+                let genesis = Genesis::load(genesis)?;
+                export_snapshot(db_path, &genesis, at_checkpoint, format, &output_dir)?;
+            }
+            ToolCommand::CommitteeHistory {
+                db_path,
+                format,
+                output,
+            } => export_committee_history(db_path, format, &output)?,
+            ToolCommand::Index { cmd } => execute_index_command(cmd)?,
+            ToolCommand::CheckFrameworkCompatibility {
+                published,
+                genesis,
+                json,
+            } => {
+                let published = match (published, genesis) {
+                    (Some(path), _) => sui_framework::load_published_abi(&path)?,
+                    (None, Some(genesis)) => {
+                        let genesis = Genesis::load(genesis)?;
+                        fetch_published_framework(&make_clients(&genesis)?).await?
+                    }
+                    (None, None) => {
+                        return Err(anyhow!("Either --published or --genesis is required"))
+                    }
+                };
+                let report = sui_framework::check_upgrade_compatibility(&published);
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                } else {
+                    print!("{}", report);
+                }
+                if !report.is_compatible() {
+                    return Err(anyhow!(
+                        "The system packages can't replace the published ones"
+                    ));
+                }
+            }
             ToolCommand::DumpValidators { genesis } => {
                 let genesis = Genesis::load(genesis)?;
                 println!("{:#?}", genesis.validator_set());
@@ -0,0 +1,209 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the history of the committee from the database of a node, for research and for
+//! analyzing reconfigurations after the fact.
+//!
+//! The export walks the epochs of the epoch store and writes a row per member of the committee of
+//! each epoch, with its voting weight and, read from the system state object at the start of the
+//! epoch, its stake, delegation and gas price quote:
+//!
+//! - `epoch`, `first_checkpoint`: the epoch and its first checkpoint.
+//! - `authority`: the public key of the member, as nodes log it, i.e. `k#` and its hex.
+//! - `voting_weight`, `total_votes`: the votes of the member and of the whole committee.
+//! - `sui_address`, `name`: the account and name the validator registered with.
+//! - `stake`, `delegation`: the stake of the validator and the SUI delegated to it.
+//! - `gas_price`: the gas price the validator quoted for the epoch.
+//! - `next_epoch_gas_price`: its quote for the next epoch, as of the start of the epoch.
+//! - `reference_gas_price`: the reference gas price of the epoch.
+//!
+//! The columns read from the system state are empty when the versions of the system state object
+//! of the epoch were pruned from the database.
+
+use anyhow::{anyhow, Result};
+use clap::ArgEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use sui_core::authority::authority_store_tables::AuthorityStoreTables;
+use sui_core::epoch::epoch_store::EpochStore;
+use sui_types::base_types::SuiAddress;
+use sui_types::committee::EpochId;
+use sui_types::crypto::AuthoritySignInfo;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::sui_system_state::{SuiSystemState, Validator};
+use sui_types::SUI_SYSTEM_STATE_OBJECT_ID;
+use tracing::info;
+
+#[derive(Debug, Clone, Copy, ArgEnum)]
+pub enum CommitteeHistoryFormat {
+    /// A row per member of the committee of each epoch.
+    Csv,
+    /// An array of the epochs, each with the members of its committee.
+    Json,
+}
+
+/// The committee of an epoch.
+#[derive(Debug, Serialize)]
+pub struct EpochCommittee {
+    pub epoch: EpochId,
+    pub first_checkpoint: CheckpointSequenceNumber,
+    pub total_votes: u64,
+    pub reference_gas_price: Option<u64>,
+    pub members: Vec<CommitteeMember>,
+}
+
+/// A member of the committee of an epoch.
+#[derive(Debug, Serialize)]
+pub struct CommitteeMember {
+    pub authority: String,
+    pub voting_weight: u64,
+    pub sui_address: Option<String>,
+    pub name: Option<String>,
+    pub stake: Option<u64>,
+    pub delegation: Option<u64>,
+    pub gas_price: Option<u64>,
+    pub next_epoch_gas_price: Option<u64>,
+}
+
+const CSV_HEADER: &str = "epoch,first_checkpoint,authority,voting_weight,total_votes,sui_address,\
+                          name,stake,delegation,gas_price,next_epoch_gas_price,reference_gas_price";
+
+/// Writes the committee of every epoch known to the node with the database at `db_path` to
+/// `output`.
+pub fn export_committee_history(
+    db_path: PathBuf,
+    format: CommitteeHistoryFormat,
+    output: &Path,
+) -> Result<()> {
+    let history = read_committee_history(&db_path)?;
+    let mut writer = BufWriter::new(File::create(output)?);
+    match format {
+        CommitteeHistoryFormat::Csv => write_csv(&mut writer, &history)?,
+        CommitteeHistoryFormat::Json => serde_json::to_writer_pretty(&mut writer, &history)?,
+    }
+    writer.flush()?;
+
+    info!(
+        "Exported the committees of {} epochs to {}",
+        history.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Reads the committee of every epoch from the epoch store of the node, and the stakes and gas
+/// prices of its members from the versions of the system state object.
+pub fn read_committee_history(db_path: &Path) -> Result<Vec<EpochCommittee>> {
+    let epochs = EpochStore::get_read_only_handle(db_path.join("epochs"), None, None);
+    let store = AuthorityStoreTables::<AuthoritySignInfo>::get_read_only_handle(
+        db_path.join("store"),
+        None,
+        None,
+    );
+
+    // The first version of the system state object in each epoch, i.e. its state once the
+    // committee of the epoch took over
+    let mut system_states = BTreeMap::new();
+    for object in store.get_object_versions(&SUI_SYSTEM_STATE_OBJECT_ID)? {
+        let move_object = object
+            .data
+            .try_as_move()
+            .ok_or_else(|| anyhow!("The system state object is not a Move object"))?;
+        let state: SuiSystemState = bcs::from_bytes(move_object.contents())?;
+        system_states.entry(state.epoch).or_insert(state);
+    }
+
+    let last_epoch = epochs.get_latest_authenticated_epoch().epoch();
+    let mut history = Vec::new();
+    for epoch in 0..=last_epoch {
+        let info = epochs
+            .get_authenticated_epoch(&epoch)?
+            .ok_or_else(|| anyhow!("Epoch {} is not in the database", epoch))?
+            .into_epoch_info();
+        let system_state = system_states.get(&epoch);
+        let first_checkpoint = *info.first_checkpoint();
+        let committee = info.into_committee();
+
+        let members = committee
+            .voting_rights
+            .iter()
+            .map(|(name, votes)| {
+                let validator = system_state.and_then(|state| {
+                    state
+                        .validators
+                        .active_validators
+                        .iter()
+                        .find(|v| v.metadata.pubkey_bytes == name.as_ref())
+                });
+                committee_member(name.to_string(), *votes, validator)
+            })
+            .collect();
+        history.push(EpochCommittee {
+            epoch,
+            first_checkpoint,
+            total_votes: committee.total_votes,
+            reference_gas_price: system_state.map(|state| state.reference_gas_price),
+            members,
+        });
+    }
+    Ok(history)
+}
+
+fn committee_member(
+    authority: String,
+    voting_weight: u64,
+    validator: Option<&Validator>,
+) -> CommitteeMember {
+    CommitteeMember {
+        authority,
+        voting_weight,
+        sui_address: validator.map(|v| SuiAddress::from(v.metadata.sui_address).to_string()),
+        name: validator.map(|v| String::from_utf8_lossy(&v.metadata.name).into_owned()),
+        stake: validator.map(|v| v.stake_amount),
+        delegation: validator.map(|v| v.delegation_staking_pool.epoch_starting_sui_balance),
+        gas_price: validator.map(|v| v.gas_price),
+        next_epoch_gas_price: validator.map(|v| v.metadata.next_epoch_gas_price),
+    }
+}
+
+fn write_csv(writer: &mut impl Write, history: &[EpochCommittee]) -> Result<()> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+    for epoch in history {
+        for member in &epoch.members {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                epoch.epoch,
+                epoch.first_checkpoint,
+                member.authority,
+                member.voting_weight,
+                epoch.total_votes,
+                member.sui_address.as_deref().unwrap_or_default(),
+                csv_field(member.name.as_deref().unwrap_or_default()),
+                optional(member.stake),
+                optional(member.delegation),
+                optional(member.gas_price),
+                optional(member.next_epoch_gas_price),
+                optional(epoch.reference_gas_price),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn optional(value: Option<u64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Quotes `value` if it contains a separator, a quote or a line break, as validator names are
+/// free text.
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,489 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the state of the chain at a checkpoint from the database of a node, as files which
+//! data warehouses load directly.
+//!
+//! The export reads the transactions of checkpoints `0..=N` and writes three tables:
+//!
+//! - `objects`: the objects alive after checkpoint `N`, at their version then. Objects wrapped
+//!   in other objects are not alive, they are part of the contents of their wrapper.
+//! - `transactions`: a row per transaction of checkpoints `0..=N`, in checkpoint order.
+//! - `events`: a row per event emitted by these transactions.
+//!
+//! Their schemas are [`OBJECTS_SCHEMA`], [`TRANSACTIONS_SCHEMA`] and [`EVENTS_SCHEMA`]. IDs and
+//! addresses are hex with a `0x` prefix, digests are base64, as in the JSON-RPC API. Unsigned
+//! values are written as `INT64`, which all of them fit in.
+
+use anyhow::{anyhow, ensure, Result};
+use clap::ArgEnum;
+use parquet::basic::Compression;
+use parquet::column::writer::{ColumnWriter, ColumnWriterImpl};
+use parquet::data_type::{ByteArray, DataType};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_config::genesis::Genesis;
+use sui_core::authority::authority_store_tables::AuthorityStoreTables;
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_types::base_types::{ObjectID, ObjectRef, TransactionDigest};
+use sui_types::crypto::AuthoritySignInfo;
+use sui_types::event::Event;
+use sui_types::messages::{
+    CertifiedTransaction, ExecutionStatus, SingleTransactionKind, TransactionEffects,
+    TransactionKind,
+};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::{Data, Object, Owner};
+use sui_types::sui_serde::{Base64, Encoding};
+use tracing::info;
+
+/// Rows buffered before they are written out as a row group.
+const ROW_GROUP_SIZE: usize = 100_000;
+
+/// The objects alive after the checkpoint.
+///
+/// - `owner_type` is `address`, `object`, `shared` or `immutable`, and `owner` the owning address
+///   or object for the first two.
+/// - `object_type` is the Move type of the object, null for packages.
+/// - `contents` is the BCS of the fields of the Move object, or of the map from module name to
+///   bytecode of the package.
+pub const OBJECTS_SCHEMA: &str = "
+    message objects {
+        REQUIRED BYTE_ARRAY object_id (UTF8);
+        REQUIRED INT64 version;
+        REQUIRED BYTE_ARRAY digest (UTF8);
+        REQUIRED BYTE_ARRAY owner_type (UTF8);
+        OPTIONAL BYTE_ARRAY owner (UTF8);
+        OPTIONAL BYTE_ARRAY object_type (UTF8);
+        REQUIRED BOOLEAN is_package;
+        REQUIRED BYTE_ARRAY previous_transaction (UTF8);
+        REQUIRED INT64 storage_rebate;
+        REQUIRED BYTE_ARRAY contents;
+    }
+";
+
+/// The transactions of the checkpoints.
+///
+/// - `kind` is the kind of the transaction, e.g. `Call` or `TransferSui`, or `Batch`.
+/// - `error` is the execution error of failed transactions.
+/// - `created`, `mutated`, `deleted`, `wrapped` and `unwrapped` count the objects the
+///   transaction affected, the gas object being mutated.
+pub const TRANSACTIONS_SCHEMA: &str = "
+    message transactions {
+        REQUIRED BYTE_ARRAY transaction_digest (UTF8);
+        REQUIRED INT64 checkpoint;
+        REQUIRED INT64 epoch;
+        REQUIRED BYTE_ARRAY sender (UTF8);
+        REQUIRED BYTE_ARRAY kind (UTF8);
+        REQUIRED BOOLEAN success;
+        OPTIONAL BYTE_ARRAY error (UTF8);
+        REQUIRED INT64 gas_budget;
+        REQUIRED INT64 gas_price;
+        REQUIRED INT64 computation_cost;
+        REQUIRED INT64 storage_cost;
+        REQUIRED INT64 storage_rebate;
+        REQUIRED INT64 created;
+        REQUIRED INT64 mutated;
+        REQUIRED INT64 deleted;
+        REQUIRED INT64 wrapped;
+        REQUIRED INT64 unwrapped;
+        REQUIRED INT64 events;
+    }
+";
+
+/// The events emitted by the transactions, in emission order within each transaction.
+///
+/// - `event_type` is the kind of the event, e.g. `MoveEvent` or `TransferObject`, and the other
+///   columns are null for the kinds of events they don't apply to.
+/// - `move_event_type` and `contents` are the Move type and the BCS of the fields of Move events.
+pub const EVENTS_SCHEMA: &str = "
+    message events {
+        REQUIRED BYTE_ARRAY transaction_digest (UTF8);
+        REQUIRED INT64 checkpoint;
+        REQUIRED INT64 event_index;
+        REQUIRED BYTE_ARRAY event_type (UTF8);
+        OPTIONAL BYTE_ARRAY sender (UTF8);
+        OPTIONAL BYTE_ARRAY package_id (UTF8);
+        OPTIONAL BYTE_ARRAY module (UTF8);
+        OPTIONAL BYTE_ARRAY object_id (UTF8);
+        OPTIONAL INT64 object_version;
+        OPTIONAL BYTE_ARRAY recipient (UTF8);
+        OPTIONAL INT64 amount;
+        OPTIONAL BYTE_ARRAY move_event_type (UTF8);
+        OPTIONAL BYTE_ARRAY contents;
+    }
+";
+
+#[derive(Debug, Clone, Copy, ArgEnum)]
+pub enum ExportFormat {
+    /// A Parquet file per table, compressed with Snappy.
+    Parquet,
+}
+
+/// Exports the state of the chain after checkpoint `checkpoint` from the database of the node at
+/// `db_path` to `output_dir`, as `objects`, `transactions` and `events` tables.
+pub fn export_snapshot(
+    db_path: PathBuf,
+    genesis: &Genesis,
+    checkpoint: CheckpointSequenceNumber,
+    format: ExportFormat,
+    output_dir: &Path,
+) -> Result<()> {
+    let ExportFormat::Parquet = format;
+    std::fs::create_dir_all(output_dir)?;
+    let checkpoints =
+        CheckpointStoreTables::get_read_only_handle(db_path.join("checkpoints"), None, None);
+    let store = AuthorityStoreTables::<AuthoritySignInfo>::get_read_only_handle(
+        db_path.join("store"),
+        None,
+        None,
+    );
+
+    let mut transactions = TableWriter::create(
+        &output_dir.join("transactions.parquet"),
+        TRANSACTIONS_SCHEMA,
+    )?;
+    let mut events = TableWriter::create(&output_dir.join("events.parquet"), EVENTS_SCHEMA)?;
+    // The latest reference of each object, or `None` once deleted or wrapped
+    let mut live_objects: BTreeMap<ObjectID, Option<ObjectRef>> = genesis
+        .objects()
+        .iter()
+        .map(|object| (object.id(), Some(object.compute_object_reference())))
+        .collect();
+
+    for seq in 0..=checkpoint {
+        let epoch = match checkpoints.checkpoints.get(&seq)? {
+            Some(authenticated) => authenticated.summary().epoch,
+            None => return Err(anyhow!("Checkpoint {} is not in the database", seq)),
+        };
+        let contents = checkpoints
+            .checkpoint_contents
+            .get(&seq)?
+            .ok_or_else(|| anyhow!("The contents of checkpoint {} are not in the database", seq))?;
+        // Contents are causally ordered, so the effects apply in order
+        for digests in contents.iter() {
+            let digest = &digests.transaction;
+            let certificate = store
+                .get_certified_transaction(digest)?
+                .ok_or_else(|| anyhow!("Certificate of transaction {:?} not found", digest))?;
+            let effects = store
+                .get_effects(digest)?
+                .ok_or_else(|| anyhow!("Effects of transaction {:?} not found", digest))?;
+
+            transactions.push(transaction_row(&certificate, &effects, seq, epoch))?;
+            for (index, event) in effects.events.iter().enumerate() {
+                events.push(event_row(digest, seq, index, event))?;
+            }
+            for (object_ref, _, _) in effects.all_mutated() {
+                live_objects.insert(object_ref.0, Some(*object_ref));
+            }
+            for object_ref in effects.deleted.iter().chain(&effects.wrapped) {
+                live_objects.insert(object_ref.0, None);
+            }
+        }
+    }
+    let transaction_count = transactions.close()?;
+    let event_count = events.close()?;
+
+    let mut objects = TableWriter::create(&output_dir.join("objects.parquet"), OBJECTS_SCHEMA)?;
+    for (id, version, _) in live_objects.into_values().flatten() {
+        let object = store
+            .get_object_by_key(&id, version)?
+            .ok_or_else(|| anyhow!("Object {} at version {} not found", id, version))?;
+        objects.push(object_row(&object)?)?;
+    }
+    let object_count = objects.close()?;
+
+    info!(
+        "Exported {} objects, {} transactions and {} events at checkpoint {} to {}",
+        object_count,
+        transaction_count,
+        event_count,
+        checkpoint,
+        output_dir.display()
+    );
+    Ok(())
+}
+
+fn object_row(object: &Object) -> Result<Vec<Field>> {
+    let (owner_type, owner) = match object.owner {
+        Owner::AddressOwner(address) => ("address", Some(address.to_string())),
+        Owner::ObjectOwner(address) => ("object", Some(address.to_string())),
+        Owner::Shared => ("shared", None),
+        Owner::Immutable => ("immutable", None),
+    };
+    let contents = match &object.data {
+        Data::Move(move_object) => move_object.contents().to_vec(),
+        Data::Package(package) => bcs::to_bytes(package.serialized_module_map())?,
+    };
+    Ok(vec![
+        Field::String(object.id().to_string()),
+        Field::Int64(object.version().value() as i64),
+        Field::String(Base64::encode(object.digest())),
+        Field::String(owner_type.to_string()),
+        Field::optional_string(owner),
+        Field::optional_string(object.type_().map(|type_| type_.to_string())),
+        Field::Boolean(object.is_package()),
+        Field::String(Base64::encode(object.previous_transaction)),
+        Field::Int64(object.storage_rebate as i64),
+        Field::Bytes(contents),
+    ])
+}
+
+fn transaction_row(
+    certificate: &CertifiedTransaction,
+    effects: &TransactionEffects,
+    checkpoint: CheckpointSequenceNumber,
+    epoch: u64,
+) -> Vec<Field> {
+    let data = &certificate.signed_data.data;
+    let error = match &effects.status {
+        ExecutionStatus::Success => None,
+        ExecutionStatus::Failure { error } => Some(error.to_string()),
+    };
+    let kind = match &data.kind {
+        TransactionKind::Single(kind) => single_transaction_kind_name(kind),
+        TransactionKind::Batch(_) => "Batch",
+    };
+    vec![
+        Field::String(Base64::encode(effects.transaction_digest)),
+        Field::Int64(checkpoint as i64),
+        Field::Int64(epoch as i64),
+        Field::String(data.signer().to_string()),
+        Field::String(kind.to_string()),
+        Field::Boolean(effects.status.is_ok()),
+        Field::optional_string(error),
+        Field::Int64(data.gas_budget as i64),
+        Field::Int64(data.gas_price as i64),
+        Field::Int64(effects.gas_used.computation_cost as i64),
+        Field::Int64(effects.gas_used.storage_cost as i64),
+        Field::Int64(effects.gas_used.storage_rebate as i64),
+        Field::Int64(effects.created.len() as i64),
+        Field::Int64(effects.mutated.len() as i64),
+        Field::Int64(effects.deleted.len() as i64),
+        Field::Int64(effects.wrapped.len() as i64),
+        Field::Int64(effects.unwrapped.len() as i64),
+        Field::Int64(effects.events.len() as i64),
+    ]
+}
+
+fn single_transaction_kind_name(kind: &SingleTransactionKind) -> &'static str {
+    match kind {
+        SingleTransactionKind::TransferObject(_) => "TransferObject",
+        SingleTransactionKind::Publish(_) => "Publish",
+        SingleTransactionKind::Call(_) => "Call",
+        SingleTransactionKind::TransferSui(_) => "TransferSui",
+        SingleTransactionKind::ChangeEpoch(_) => "ChangeEpoch",
+    }
+}
+
+fn event_row(
+    digest: &TransactionDigest,
+    checkpoint: CheckpointSequenceNumber,
+    index: usize,
+    event: &Event,
+) -> Vec<Field> {
+    let recipient = event.recipient().map(|recipient| match recipient {
+        Owner::AddressOwner(address) => address.to_string(),
+        Owner::ObjectOwner(address) => address.to_string(),
+        Owner::Shared => "shared".to_string(),
+        Owner::Immutable => "immutable".to_string(),
+    });
+    vec![
+        Field::String(Base64::encode(digest)),
+        Field::Int64(checkpoint as i64),
+        Field::Int64(index as i64),
+        Field::String(event.variant_name().to_string()),
+        Field::optional_string(event.sender().map(|sender| sender.to_string())),
+        Field::optional_string(event.package_id().map(|id| id.to_string())),
+        Field::optional_string(event.module_name().map(str::to_string)),
+        Field::optional_string(event.object_id().map(|id| id.to_string())),
+        event
+            .object_version()
+            .map_or(Field::Null, |version| Field::Int64(version.value() as i64)),
+        Field::optional_string(recipient),
+        event
+            .amount()
+            .map_or(Field::Null, |amount| Field::Int64(amount as i64)),
+        Field::optional_string(event.move_event_name()),
+        event
+            .move_event_contents()
+            .map_or(Field::Null, |contents| Field::Bytes(contents.to_vec())),
+    ]
+}
+
+/// A value of a row, of the physical type of its column.
+enum Field {
+    Int64(i64),
+    Boolean(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    Null,
+}
+
+impl Field {
+    fn optional_string(value: Option<String>) -> Self {
+        value.map_or(Field::Null, Field::String)
+    }
+}
+
+/// Writes rows to a Parquet file, buffering them by column and writing them out as row groups.
+struct TableWriter {
+    writer: SerializedFileWriter<File>,
+    columns: Vec<Vec<Field>>,
+    rows: u64,
+}
+
+impl TableWriter {
+    fn create(path: &Path, schema: &str) -> Result<Self> {
+        let schema = Arc::new(parse_message_type(schema)?);
+        let properties = Arc::new(
+            WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build(),
+        );
+        let columns = (0..schema.get_fields().len()).map(|_| vec![]).collect();
+        Ok(Self {
+            writer: SerializedFileWriter::new(File::create(path)?, schema, properties)?,
+            columns,
+            rows: 0,
+        })
+    }
+
+    fn push(&mut self, row: Vec<Field>) -> Result<()> {
+        ensure!(
+            row.len() == self.columns.len(),
+            "Row of {} fields for {} columns",
+            row.len(),
+            self.columns.len()
+        );
+        for (column, field) in self.columns.iter_mut().zip(row) {
+            column.push(field);
+        }
+        if self.columns[0].len() >= ROW_GROUP_SIZE {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Writes out the buffered rows, and returns the number of rows of the file.
+    fn close(mut self) -> Result<u64> {
+        self.write_row_group()?;
+        self.writer.close()?;
+        Ok(self.rows)
+    }
+
+    fn write_row_group(&mut self) -> Result<()> {
+        let rows = self.columns[0].len();
+        if rows == 0 {
+            return Ok(());
+        }
+        let mut row_group = self.writer.next_row_group()?;
+        for column in &mut self.columns {
+            let fields = std::mem::take(column);
+            let mut column_writer = row_group
+                .next_column()?
+                .ok_or_else(|| anyhow!("More columns than in the schema"))?;
+            match column_writer.untyped() {
+                ColumnWriter::Int64ColumnWriter(writer) => {
+                    write_column(writer, &fields, |field| match field {
+                        Field::Int64(value) => Some(*value),
+                        _ => None,
+                    })?
+                }
+                ColumnWriter::BoolColumnWriter(writer) => {
+                    write_column(writer, &fields, |field| match field {
+                        Field::Boolean(value) => Some(*value),
+                        _ => None,
+                    })?
+                }
+                ColumnWriter::ByteArrayColumnWriter(writer) => {
+                    write_column(writer, &fields, |field| match field {
+                        Field::String(value) => Some(ByteArray::from(value.as_str())),
+                        Field::Bytes(value) => Some(ByteArray::from(value.clone())),
+                        _ => None,
+                    })?
+                }
+                _ => return Err(anyhow!("Unsupported column type")),
+            }
+            column_writer.close()?;
+        }
+        row_group.close()?;
+        self.rows += rows as u64;
+        Ok(())
+    }
+}
+
+/// Writes `fields` to a column, with the definition levels which mark nulls if it is optional.
+fn write_column<T: DataType>(
+    writer: &mut ColumnWriterImpl<'_, T>,
+    fields: &[Field],
+    value: impl Fn(&Field) -> Option<T::T>,
+) -> Result<()> {
+    let values: Vec<T::T> = fields.iter().filter_map(value).collect();
+    let descriptor = writer.get_descriptor().clone();
+    if descriptor.max_def_level() > 0 {
+        let def_levels: Vec<i16> = fields
+            .iter()
+            .map(|field| i16::from(!matches!(field, Field::Null)))
+            .collect();
+        ensure!(
+            values.len() == def_levels.iter().filter(|level| **level > 0).count(),
+            "Value of the wrong type in column {}",
+            descriptor.name()
+        );
+        writer.write_batch(&values, Some(&def_levels), None)?;
+    } else {
+        ensure!(
+            values.len() == fields.len(),
+            "Null or value of the wrong type in required column {}",
+            descriptor.name()
+        );
+        writer.write_batch(&values, None, None)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+
+    #[test]
+    fn test_table_writer_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.parquet");
+        let mut writer = TableWriter::create(&path, EVENTS_SCHEMA).unwrap();
+        let digest = TransactionDigest::random();
+        let event = Event::EpochChange(3);
+        writer.push(event_row(&digest, 7, 0, &event)).unwrap();
+        writer.push(event_row(&digest, 7, 1, &event)).unwrap();
+        assert_eq!(writer.close().unwrap(), 2);
+
+        let reader = SerializedFileReader::new(File::open(&path).unwrap()).unwrap();
+        let rows: Vec<_> = reader.get_row_iter(None).unwrap().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].get_string(0).unwrap(), &Base64::encode(digest));
+        assert_eq!(rows[1].get_long(1).unwrap(), 7);
+        assert_eq!(rows[1].get_long(2).unwrap(), 1);
+        assert_eq!(rows[1].get_string(3).unwrap(), "EpochChange");
+        assert!(rows[1].get_string(4).is_err());
+    }
+
+    #[test]
+    fn test_table_writer_rejects_null_in_required_column() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer =
+            TableWriter::create(&dir.path().join("events.parquet"), EVENTS_SCHEMA).unwrap();
+        let mut row = event_row(&TransactionDigest::random(), 0, 0, &Event::Checkpoint(0));
+        row[1] = Field::Null;
+        writer.push(row).unwrap();
+        assert!(writer.close().is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Maintenance of the indexes of a fullnode, which the node builds as it executes transactions.
+//!
+//! An index added in a new release is empty for the transactions executed before the upgrade.
+//! `sui-tool index backfill <index>` builds it from the certificates and effects the node already
+//! stores, without resyncing. The node must be stopped while the tool runs.
+
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use strum::IntoEnumIterator;
+use sui_core::authority::authority_store_tables::AuthorityStoreTables;
+use sui_core::authority::index_tx;
+use sui_storage::indexes::{IndexWrite, TxIndex};
+use sui_storage::IndexStore;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::crypto::AuthoritySignInfo;
+use tracing::info;
+
+/// How often the progress of a backfill is logged.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum IndexCommand {
+    /// Lists the indexes which can be backfilled.
+    List,
+    /// Builds an index from the transactions the node executed before the index was added.
+    Backfill(Backfill),
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct Backfill {
+    /// The index to build, as listed by `sui-tool index list`
+    index: TxIndex,
+    /// Path of the DB of the node
+    #[clap(long = "db-path")]
+    db_path: PathBuf,
+    /// The sequence number of the first transaction to index, to resume an interrupted backfill
+    /// from the transaction after the last one it logged. Indexes from the first transaction, after
+    /// clearing the index, by default.
+    #[clap(long)]
+    from: Option<TxSequenceNumber>,
+    /// Transactions indexed per second at most, to limit the load on a disk shared with other
+    /// services. Unlimited by default.
+    #[clap(long)]
+    max_tps: Option<u32>,
+}
+
+pub fn execute_index_command(cmd: IndexCommand) -> Result<()> {
+    match cmd {
+        IndexCommand::List => {
+            for index in TxIndex::iter() {
+                println!("{}", index);
+            }
+            Ok(())
+        }
+        IndexCommand::Backfill(backfill) => backfill_index(
+            backfill.db_path,
+            backfill.index,
+            backfill.from,
+            backfill.max_tps,
+        ),
+    }
+}
+
+/// Writes the transactions executed by the node at `db_path` into `index`, in execution order,
+/// from the transaction `from` on.
+pub fn backfill_index(
+    db_path: PathBuf,
+    index: TxIndex,
+    from: Option<TxSequenceNumber>,
+    max_tps: Option<u32>,
+) -> Result<()> {
+    let store = AuthorityStoreTables::<AuthoritySignInfo>::get_read_only_handle(
+        db_path.join("store"),
+        None,
+        None,
+    );
+    let indexes = IndexStore::open_tables_read_write(db_path.join("indexes"), None, None);
+
+    let start = from.unwrap_or_default();
+    if start == 0 {
+        indexes.clear_index(index)?;
+    }
+    let end = match store.executed_sequence.iter().skip_to_last().next() {
+        Some((last, _)) => last + 1,
+        None => 0,
+    };
+    let total = end.saturating_sub(start);
+    info!(
+        "Backfilling index {} with transactions {} to {}",
+        index, start, end
+    );
+
+    let min_interval = max_tps.map(|tps| Duration::from_secs(1) / tps.max(1));
+    let started = Instant::now();
+    let mut last_progress = started;
+    let mut indexed = 0u64;
+    for (seq, digests) in store.executed_sequence.iter().skip_to(&start)? {
+        let tx_started = Instant::now();
+        let digest = &digests.transaction;
+        let cert = store
+            .get_certified_transaction(digest)?
+            .ok_or_else(|| anyhow!("Certificate of transaction {:?} not found", digest))?;
+        let effects = store
+            .get_effects(digest)?
+            .ok_or_else(|| anyhow!("Effects of transaction {:?} not found", digest))?;
+        index_tx(
+            &indexes,
+            IndexWrite::Backfill(index),
+            seq,
+            digest,
+            &cert,
+            &effects,
+        )?;
+        indexed += 1;
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            info!(
+                "Indexed {} of {} transactions ({:.1}%), up to transaction {}, at {:.0} tx/s",
+                indexed,
+                total,
+                100.0 * indexed as f64 / total.max(1) as f64,
+                seq,
+                indexed as f64 / started.elapsed().as_secs_f64()
+            );
+        }
+        if let Some(min_interval) = min_interval {
+            if let Some(remaining) = min_interval.checked_sub(tx_started.elapsed()) {
+                std::thread::sleep(remaining);
+            }
+        }
+    }
+
+    info!(
+        "Backfilled index {} with {} transactions in {:.1}s",
+        index,
+        indexed,
+        started.elapsed().as_secs_f64()
+    );
+    Ok(())
+}
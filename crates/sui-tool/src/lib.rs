@@ -2,4 +2,8 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod committee_history;
 pub mod db_tool;
+pub mod export;
+pub mod index_tool;
+pub mod pretty_print;
@@ -0,0 +1,543 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Annotated, human readable rendering of transactions and their effects, used by
+//! `sui-tool pp-tx` to triage payloads.
+
+use anyhow::{anyhow, Result};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::normalized::Type;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+use sui_types::base_types::{encode_bytes_hex, ObjectID, ObjectRef, TransactionDigest};
+use sui_types::crypto::{SignableBytes, Signature, SuiSignature};
+use sui_types::messages::{
+    CallArg, ExecutionStatus, MoveCall, ObjectArg, SenderSignedData, SingleTransactionKind,
+    Transaction, TransactionData, TransactionEffects,
+};
+use sui_types::move_package::MovePackage;
+use sui_types::sui_serde::{Base64, Encoding};
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+const SIGNABLE_PREFIX: &[u8] = b"TransactionData::";
+
+/// What `pp-tx` was asked to print.
+pub enum TransactionInput {
+    /// A transaction to fetch from the validators.
+    Digest(TransactionDigest),
+    /// A transaction together with the sender signature.
+    Signed(SenderSignedData),
+    /// Bare transaction data, e.g. the `tx_bytes` submitted to the JSON-RPC API.
+    Unsigned(TransactionData),
+}
+
+impl FromStr for TransactionInput {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let bytes = Base64::decode(s.trim())?;
+        if let Ok(digest) = TransactionDigest::try_from(bytes.as_slice()) {
+            return Ok(TransactionInput::Digest(digest));
+        }
+        if let Ok(signed_data) = bcs::from_bytes::<SenderSignedData>(&bytes) {
+            return Ok(TransactionInput::Signed(signed_data));
+        }
+        // `tx_bytes` carry the signable name prefix, plain BCS is accepted as well.
+        let data = if bytes.starts_with(SIGNABLE_PREFIX) {
+            TransactionData::from_signable_bytes(&bytes)
+        } else {
+            bcs::from_bytes(&bytes).map_err(anyhow::Error::from)
+        };
+        data.map(TransactionInput::Unsigned).map_err(|_| {
+            anyhow!(
+                "Input is neither a transaction digest, a signed transaction nor transaction data"
+            )
+        })
+    }
+}
+
+/// Packages called into by `data`, whose function signatures are used to decode call arguments.
+pub fn called_packages(data: &TransactionData) -> Vec<ObjectID> {
+    let mut packages: Vec<_> = data
+        .kind
+        .single_transactions()
+        .filter_map(|kind| match kind {
+            SingleTransactionKind::Call(call) => Some(call.package.0),
+            _ => None,
+        })
+        .collect();
+    packages.sort();
+    packages.dedup();
+    packages
+}
+
+/// Renders `data` with its signature and effects, when available. Arguments of calls into
+/// `packages` are decoded against the called function's signature, other calls print raw bytes.
+pub fn pretty_print(
+    data: &TransactionData,
+    signature: Option<&Signature>,
+    effects: Option<&TransactionEffects>,
+    packages: &BTreeMap<ObjectID, MovePackage>,
+) -> Result<String> {
+    let mut out = String::new();
+    if let Some(signature) = signature {
+        let digest = *Transaction::new(data.clone(), signature.clone()).digest();
+        writeln!(out, "Transaction {:?}", digest)?;
+    } else {
+        writeln!(out, "Transaction <unsigned>")?;
+    }
+    writeln!(out, "  sender     : {}", data.signer())?;
+    writeln!(out, "  gas payment: {}", format_object_ref(&data.gas()))?;
+    writeln!(out, "  gas price  : {}", data.gas_price)?;
+    writeln!(out, "  gas budget : {}", data.gas_budget)?;
+
+    let batch_size = data.kind.batch_size();
+    if batch_size > 1 {
+        writeln!(out, "Batch of {} transactions", batch_size)?;
+    }
+    for (index, kind) in data.kind.single_transactions().enumerate() {
+        if batch_size > 1 {
+            writeln!(out, "[{}]", index)?;
+        }
+        write_single_transaction(&mut out, kind, packages)?;
+    }
+
+    match signature {
+        Some(signature) => {
+            writeln!(out, "Signature")?;
+            writeln!(out, "  scheme     : {:?}", signature.scheme())?;
+            writeln!(
+                out,
+                "  public key : {}",
+                Base64::encode(signature.public_key_bytes())
+            )?;
+            writeln!(
+                out,
+                "  signature  : {}",
+                Base64::encode(signature.signature_bytes())
+            )?;
+            match signature.verify(data, data.signer()) {
+                Ok(()) => writeln!(out, "  verifies   : yes")?,
+                Err(e) => writeln!(out, "  verifies   : NO ({})", e)?,
+            }
+        }
+        None => writeln!(out, "Signature <none>")?,
+    }
+
+    if let Some(effects) = effects {
+        write_effects(&mut out, effects)?;
+    }
+    Ok(out)
+}
+
+fn write_single_transaction(
+    out: &mut String,
+    kind: &SingleTransactionKind,
+    packages: &BTreeMap<ObjectID, MovePackage>,
+) -> Result<()> {
+    match kind {
+        SingleTransactionKind::TransferObject(t) => {
+            writeln!(out, "Transfer object")?;
+            writeln!(out, "  object     : {}", format_object_ref(&t.object_ref))?;
+            writeln!(out, "  recipient  : {}", t.recipient)?;
+        }
+        SingleTransactionKind::TransferSui(t) => {
+            writeln!(out, "Transfer SUI")?;
+            match t.amount {
+                Some(amount) => writeln!(out, "  amount     : {}", amount)?,
+                None => writeln!(out, "  amount     : full balance of the gas coin")?,
+            }
+            writeln!(out, "  recipient  : {}", t.recipient)?;
+        }
+        SingleTransactionKind::Publish(p) => {
+            writeln!(out, "Publish {} module(s)", p.modules.len())?;
+            for bytes in &p.modules {
+                match CompiledModule::deserialize(bytes) {
+                    Ok(module) => {
+                        writeln!(out, "  {} ({} bytes)", module.self_id().name(), bytes.len())?
+                    }
+                    Err(e) => writeln!(out, "  <invalid module: {}> ({} bytes)", e, bytes.len())?,
+                }
+            }
+        }
+        SingleTransactionKind::Call(call) => write_move_call(out, call, packages)?,
+        SingleTransactionKind::ChangeEpoch(e) => {
+            writeln!(out, "Change epoch")?;
+            writeln!(out, "  next epoch : {}", e.epoch)?;
+            writeln!(out, "  storage    : {}", e.storage_charge)?;
+            writeln!(out, "  computation: {}", e.computation_charge)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_move_call(
+    out: &mut String,
+    call: &MoveCall,
+    packages: &BTreeMap<ObjectID, MovePackage>,
+) -> Result<()> {
+    writeln!(
+        out,
+        "Call {}::{}::{}",
+        call.package.0.to_hex_literal(),
+        call.module,
+        call.function
+    )?;
+    writeln!(out, "  package    : {}", format_object_ref(&call.package))?;
+    for (index, type_argument) in call.type_arguments.iter().enumerate() {
+        writeln!(out, "  T{}         = {}", index, type_argument)?;
+    }
+
+    let parameters = match resolve_parameters(call, packages) {
+        Ok(parameters) => Some(parameters),
+        Err(e) => {
+            writeln!(out, "  <function signature unavailable: {}>", e)?;
+            None
+        }
+    };
+    for (index, argument) in call.arguments.iter().enumerate() {
+        let type_ = parameters.as_ref().and_then(|p| p.get(index));
+        let type_str = type_.map(format_type).unwrap_or_else(|| "?".to_string());
+        let value = match argument {
+            CallArg::Pure(bytes) => match type_ {
+                Some(type_) => decode_pure(bytes, type_),
+                None => format!("0x{}", encode_bytes_hex(bytes).to_lowercase()),
+            },
+            CallArg::Object(arg) => format_object_arg(arg),
+            CallArg::ObjVec(args) => format!(
+                "[{}]",
+                args.iter()
+                    .map(format_object_arg)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        writeln!(out, "  arg {:<7}: {} = {}", index, type_str, value)?;
+    }
+    Ok(())
+}
+
+fn resolve_parameters(
+    call: &MoveCall,
+    packages: &BTreeMap<ObjectID, MovePackage>,
+) -> Result<Vec<Type>> {
+    let package = packages
+        .get(&call.package.0)
+        .ok_or_else(|| anyhow!("package not found"))?;
+    let mut modules = package.normalize()?;
+    let module = modules
+        .remove(call.module.as_str())
+        .ok_or_else(|| anyhow!("module not found in package"))?;
+    let function = module
+        .exposed_functions
+        .get(&call.function)
+        .ok_or_else(|| anyhow!("function not found in module"))?;
+    Ok(function.parameters.clone())
+}
+
+fn write_effects(out: &mut String, effects: &TransactionEffects) -> Result<()> {
+    writeln!(out, "Effects")?;
+    match &effects.status {
+        ExecutionStatus::Success => writeln!(out, "  status     : success")?,
+        ExecutionStatus::Failure { error } => writeln!(out, "  status     : failure ({})", error)?,
+    }
+    let gas = &effects.gas_used;
+    writeln!(
+        out,
+        "  gas used   : computation {}, storage {}, rebate {}",
+        gas.computation_cost, gas.storage_cost, gas.storage_rebate
+    )?;
+    writeln!(
+        out,
+        "  gas object : {}",
+        format_object_ref(&effects.gas_object.0)
+    )?;
+    for object_ref in &effects.shared_objects {
+        writeln!(out, "  shared     : {}", format_object_ref(object_ref))?;
+    }
+    for (label, objects) in [
+        ("created", &effects.created),
+        ("mutated", &effects.mutated),
+        ("unwrapped", &effects.unwrapped),
+    ] {
+        for (object_ref, owner) in objects {
+            writeln!(
+                out,
+                "  {:<11}: {} owned by {}",
+                label,
+                format_object_ref(object_ref),
+                owner
+            )?;
+        }
+    }
+    for (label, objects) in [("deleted", &effects.deleted), ("wrapped", &effects.wrapped)] {
+        for object_ref in objects {
+            writeln!(out, "  {:<11}: {}", label, format_object_ref(object_ref))?;
+        }
+    }
+    for event in &effects.events {
+        writeln!(out, "  event      : {:?}", event)?;
+    }
+    for dependency in &effects.dependencies {
+        writeln!(out, "  depends on : {:?}", dependency)?;
+    }
+    Ok(())
+}
+
+fn format_object_ref((id, version, digest): &ObjectRef) -> String {
+    format!("{} (version {}, digest {:?})", id, version.value(), digest)
+}
+
+fn format_object_arg(arg: &ObjectArg) -> String {
+    match arg {
+        ObjectArg::ImmOrOwnedObject(object_ref) => format_object_ref(object_ref),
+        ObjectArg::SharedObject(id) => format!("{} (shared)", id),
+    }
+}
+
+fn format_type(type_: &Type) -> String {
+    match type_ {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::U128 => "u128".to_string(),
+        Type::Address => "address".to_string(),
+        Type::Signer => "signer".to_string(),
+        Type::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            let mut s = format!("{}::{}::{}", address.to_hex_literal(), module, name);
+            if !type_arguments.is_empty() {
+                let args: Vec<_> = type_arguments.iter().map(format_type).collect();
+                write!(s, "<{}>", args.join(", ")).unwrap();
+            }
+            s
+        }
+        Type::Vector(inner) => format!("vector<{}>", format_type(inner)),
+        Type::TypeParameter(index) => format!("T{}", index),
+        Type::Reference(inner) => format!("&{}", format_type(inner)),
+        Type::MutableReference(inner) => format!("&mut {}", format_type(inner)),
+    }
+}
+
+/// Decodes the BCS bytes of a pure argument as `type_`, falling back to hex when the type is
+/// not a primitive or the bytes do not match it.
+fn decode_pure(bytes: &[u8], type_: &Type) -> String {
+    let decoded = match type_ {
+        Type::Bool => decode_display::<bool>(bytes),
+        Type::U8 => decode_display::<u8>(bytes),
+        Type::U64 => decode_display::<u64>(bytes),
+        Type::U128 => decode_display::<u128>(bytes),
+        Type::Address => bcs::from_bytes::<AccountAddress>(bytes).map(|a| a.to_hex_literal()),
+        Type::Vector(inner) => match inner.as_ref() {
+            Type::U8 => bcs::from_bytes::<Vec<u8>>(bytes).map(|v| match std::str::from_utf8(&v) {
+                Ok(s) => format!("{:?} (utf8)", s),
+                Err(_) => format!("0x{}", encode_bytes_hex(&v).to_lowercase()),
+            }),
+            Type::Bool => decode_vector::<bool>(bytes),
+            Type::U64 => decode_vector::<u64>(bytes),
+            Type::U128 => decode_vector::<u128>(bytes),
+            Type::Address => bcs::from_bytes::<Vec<AccountAddress>>(bytes).map(|v| {
+                let addresses: Vec<_> = v.iter().map(|a| a.to_hex_literal()).collect();
+                format!("[{}]", addresses.join(", "))
+            }),
+            _ => return undecoded(bytes),
+        },
+        Type::Struct {
+            address,
+            module,
+            name,
+            ..
+        } => match (*address, module.as_str(), name.as_str()) {
+            (MOVE_STDLIB_ADDRESS, "string" | "ascii", "String") => {
+                bcs::from_bytes::<String>(bytes).map(|s| format!("{:?}", s))
+            }
+            (SUI_FRAMEWORK_ADDRESS, "object", "ID") => {
+                bcs::from_bytes::<ObjectID>(bytes).map(|id| id.to_string())
+            }
+            _ => return undecoded(bytes),
+        },
+        _ => return undecoded(bytes),
+    };
+    decoded.unwrap_or_else(|e| {
+        format!(
+            "0x{} (does not decode as {}: {})",
+            encode_bytes_hex(bytes).to_lowercase(),
+            format_type(type_),
+            e
+        )
+    })
+}
+
+fn decode_display<T: DeserializeOwned + Display>(bytes: &[u8]) -> Result<String, bcs::Error> {
+    bcs::from_bytes::<T>(bytes).map(|v| v.to_string())
+}
+
+fn decode_vector<T: DeserializeOwned + Display>(bytes: &[u8]) -> Result<String, bcs::Error> {
+    let values: Vec<T> = bcs::from_bytes(bytes)?;
+    let values: Vec<_> = values.iter().map(|v| v.to_string()).collect();
+    Ok(format!("[{}]", values.join(", ")))
+}
+
+fn undecoded(bytes: &[u8]) -> String {
+    format!("0x{} (undecoded)", encode_bytes_hex(bytes).to_lowercase())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use sui_types::base_types::{ObjectDigest, SequenceNumber, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair, Signable};
+
+    fn transfer_data() -> (TransactionData, AccountKeyPair) {
+        let (sender, key): (_, AccountKeyPair) = get_key_pair();
+        let gas = (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        );
+        let data = TransactionData::new_transfer_sui(
+            SuiAddress::random_for_testing_only(),
+            sender,
+            Some(10),
+            gas,
+            1000,
+        );
+        (data, key)
+    }
+
+    fn std_struct(module: &str, name: &str) -> Type {
+        Type::Struct {
+            address: MOVE_STDLIB_ADDRESS,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_arguments: vec![],
+        }
+    }
+
+    #[test]
+    fn test_parse_digest() {
+        let digest = TransactionDigest::random();
+        let input = Base64::encode(digest);
+        match input.parse().unwrap() {
+            TransactionInput::Digest(parsed) => assert_eq!(parsed, digest),
+            _ => panic!("expected a digest"),
+        }
+    }
+
+    #[test]
+    fn test_parse_signed_transaction() {
+        let (data, key) = transfer_data();
+        let signature = Signature::new(&data, &key);
+        let transaction = Transaction::new(data.clone(), signature);
+        let input = Base64::encode(bcs::to_bytes(&transaction.signed_data).unwrap());
+        match input.parse().unwrap() {
+            TransactionInput::Signed(signed_data) => assert_eq!(signed_data.data, data),
+            _ => panic!("expected a signed transaction"),
+        }
+    }
+
+    #[test]
+    fn test_parse_transaction_data() {
+        let (data, _) = transfer_data();
+        // The `tx_bytes` of the JSON-RPC API, with the signable name prefix
+        let mut tx_bytes = Vec::new();
+        data.write(&mut tx_bytes);
+        // Plain BCS, surrounded by whitespace as when pasted from a terminal
+        let plain = format!(" {}\n", Base64::encode(bcs::to_bytes(&data).unwrap()));
+        for input in [Base64::encode(tx_bytes), plain] {
+            match input.parse().unwrap() {
+                TransactionInput::Unsigned(parsed) => assert_eq!(parsed, data),
+                _ => panic!("expected transaction data"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_input() {
+        assert!("not base64!".parse::<TransactionInput>().is_err());
+        // Valid base64, but too long for a digest and not a transaction
+        let garbage = Base64::encode([7u8; 40]);
+        assert!(garbage.parse::<TransactionInput>().is_err());
+        // Transaction data cut short
+        let (data, _) = transfer_data();
+        let bytes = bcs::to_bytes(&data).unwrap();
+        let truncated = Base64::encode(&bytes[..bytes.len() - 1]);
+        assert!(truncated.parse::<TransactionInput>().is_err());
+    }
+
+    #[test]
+    fn test_decode_pure_primitives() {
+        let address = AccountAddress::random();
+        let cases = [
+            (
+                bcs::to_bytes(&true).unwrap(),
+                Type::Bool,
+                "true".to_string(),
+            ),
+            (bcs::to_bytes(&7u8).unwrap(), Type::U8, "7".to_string()),
+            (bcs::to_bytes(&42u64).unwrap(), Type::U64, "42".to_string()),
+            (
+                bcs::to_bytes(&42u128).unwrap(),
+                Type::U128,
+                "42".to_string(),
+            ),
+            (
+                bcs::to_bytes(&address).unwrap(),
+                Type::Address,
+                address.to_hex_literal(),
+            ),
+            (
+                bcs::to_bytes(&vec![1u64, 2]).unwrap(),
+                Type::Vector(Box::new(Type::U64)),
+                "[1, 2]".to_string(),
+            ),
+        ];
+        for (bytes, type_, expected) in cases {
+            assert_eq!(decode_pure(&bytes, &type_), expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_pure_strings() {
+        let bytes = bcs::to_bytes("sui").unwrap();
+        assert_eq!(
+            decode_pure(&bytes, &std_struct("string", "String")),
+            "\"sui\""
+        );
+        assert_eq!(
+            decode_pure(&bytes, &std_struct("ascii", "String")),
+            "\"sui\""
+        );
+        assert_eq!(
+            decode_pure(&bytes, &Type::Vector(Box::new(Type::U8))),
+            "\"sui\" (utf8)"
+        );
+        let bytes = bcs::to_bytes(&vec![0xffu8, 0xfe]).unwrap();
+        assert_eq!(
+            decode_pure(&bytes, &Type::Vector(Box::new(Type::U8))),
+            "0xfffe"
+        );
+    }
+
+    #[test]
+    fn test_decode_pure_fallbacks() {
+        // Bytes that do not match the parameter type are printed as hex with the reason
+        let decoded = decode_pure(&[1, 2], &Type::U64);
+        assert!(decoded.starts_with("0x0102 (does not decode as u64:"));
+        // Types without a decoder are printed as hex
+        assert_eq!(
+            decode_pure(&[1, 2], &std_struct("option", "Option")),
+            "0x0102 (undecoded)"
+        );
+        assert_eq!(decode_pure(&[1], &Type::Signer), "0x01 (undecoded)");
+    }
+}
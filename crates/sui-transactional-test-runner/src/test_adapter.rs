@@ -507,6 +507,7 @@ impl<'a> SuiTestAdapter<'a> {
             transaction_dependencies,
             &self.vm,
             &self.native_functions,
+            &gas::NonRefundableStorageRates::default(),
             gas_status,
             // TODO: Support different epochs in transactional tests.
             0,
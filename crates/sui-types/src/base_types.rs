@@ -7,6 +7,7 @@ use std::collections::{BTreeSet, HashMap};
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::anyhow;
 use base64ct::Encoding;
@@ -27,14 +28,16 @@ use sha3::Sha3_256;
 
 use crate::committee::EpochId;
 use crate::crypto::{
-    AuthorityPublicKey, AuthorityPublicKeyBytes, KeypairTraits, PublicKey, SuiPublicKey,
+    AuthorityPublicKey, AuthorityPublicKeyBytes, KeypairTraits, PublicKey, SignatureScheme,
+    SuiPublicKey,
 };
 use crate::error::ExecutionError;
 use crate::error::ExecutionErrorKind;
 use crate::error::SuiError;
+use crate::multisig::MultiSigPublicKey;
 use crate::object::{Object, Owner};
+use crate::sui_serde::AddressHex;
 use crate::sui_serde::Base64;
-use crate::sui_serde::Hex;
 use crate::sui_serde::Readable;
 use crate::waypoint::IntoPoint;
 
@@ -74,8 +77,8 @@ pub type AuthorityName = AuthorityPublicKeyBytes;
 #[serde_as]
 #[derive(Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
 pub struct ObjectID(
-    #[schemars(with = "Hex")]
-    #[serde_as(as = "Readable<Hex, _>")]
+    #[schemars(with = "AddressHex")]
+    #[serde_as(as = "Readable<AddressHex, _>")]
     AccountAddress,
 );
 
@@ -123,8 +126,8 @@ pub const SUI_ADDRESS_LENGTH: usize = ObjectID::LENGTH;
     Eq, Default, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Serialize, Deserialize, JsonSchema,
 )]
 pub struct SuiAddress(
-    #[schemars(with = "Hex")]
-    #[serde_as(as = "Readable<Hex, _>")]
+    #[schemars(with = "AddressHex")]
+    #[serde_as(as = "Readable<AddressHex, _>")]
     [u8; SUI_ADDRESS_LENGTH],
 );
 
@@ -229,6 +232,25 @@ impl From<&PublicKey> for SuiAddress {
     }
 }
 
+/// The address of a multisig is the hash of its committee, with the flag of the multisig scheme
+/// so that it can't be the address of a single key.
+impl From<&MultiSigPublicKey> for SuiAddress {
+    fn from(multisig_pk: &MultiSigPublicKey) -> Self {
+        let mut hasher = Sha3_256::default();
+        hasher.update(&[SignatureScheme::MultiSig.flag()]);
+        hasher.update(&multisig_pk.threshold().to_le_bytes());
+        for pk in multisig_pk.public_keys() {
+            hasher.update(&[pk.flag()]);
+            hasher.update(pk);
+        }
+        let g_arr = hasher.finalize();
+
+        let mut res = [0u8; SUI_ADDRESS_LENGTH];
+        res.copy_from_slice(&AsRef::<[u8]>::as_ref(&g_arr)[..SUI_ADDRESS_LENGTH]);
+        SuiAddress(res)
+    }
+}
+
 impl TryFrom<&[u8]> for SuiAddress {
     type Error = SuiError;
 
@@ -544,9 +566,67 @@ pub fn decode_bytes_hex<T: for<'a> TryFrom<&'a [u8]>>(s: &str) -> Result<T, anyh
     T::try_from(&value[..]).map_err(|_| anyhow::anyhow!("byte deserialization failed"))
 }
 
+static CHECKSUMMED_ADDRESSES: AtomicBool = AtomicBool::new(false);
+
+/// Makes the addresses, object IDs and transaction digests of the process display in their
+/// checksummed form, and the addresses and object IDs serialize to it in human-readable formats.
+/// They are parsed from both forms either way.
+pub fn set_checksummed_addresses(enabled: bool) {
+    CHECKSUMMED_ADDRESSES.store(enabled, Ordering::Relaxed);
+}
+
+pub fn checksummed_addresses() -> bool {
+    CHECKSUMMED_ADDRESSES.load(Ordering::Relaxed)
+}
+
+/// Encodes `bytes` as `0x` prefixed hex whose letters carry a checksum, as in EIP-55: a letter
+/// is uppercase when the matching nibble of the SHA3-256 hash of the lowercase hex is 8 or more.
+/// Mistyping any character changes the expected case of about half of the letters.
+pub fn encode_checksummed_hex<B: AsRef<[u8]>>(bytes: B) -> String {
+    let hex = encode_bytes_hex(bytes);
+    let hash = Sha3_256::digest(hex.as_bytes());
+    let checksummed: String = hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let byte = hash[(i / 2) % hash.len()];
+            let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0xf };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{checksummed}")
+}
+
+/// Whether the hex `s` encoding `bytes`, with or without `0x` prefix and leading zeros, carries a
+/// valid checksum. Hex in a single case, such as the legacy lowercase form, carries none and is
+/// valid.
+pub fn is_valid_checksum(s: &str, bytes: &[u8]) -> bool {
+    let hex = s.strip_prefix("0x").unwrap_or(s);
+    let mixed_case =
+        hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    !mixed_case || encode_checksummed_hex(bytes).ends_with(hex)
+}
+
+/// Decodes hex `s` as [`decode_bytes_hex`] does, checking its checksum if it has one.
+pub fn decode_checksummed_hex<T: for<'a> TryFrom<&'a [u8]>>(s: &str) -> Result<T, anyhow::Error> {
+    let bytes: Vec<u8> = decode_bytes_hex(s)?;
+    if !is_valid_checksum(s, &bytes) {
+        return Err(anyhow!("Invalid checksum of {s}, it may contain a typo"));
+    }
+    T::try_from(&bytes[..]).map_err(|_| anyhow::anyhow!("byte deserialization failed"))
+}
+
 impl fmt::Display for SuiAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#x}", self)
+        if checksummed_addresses() {
+            write!(f, "{}", encode_checksummed_hex(self))
+        } else {
+            write!(f, "{:#x}", self)
+        }
     }
 }
 
@@ -616,6 +696,17 @@ impl std::fmt::Debug for TransactionDigest {
     }
 }
 
+/// Base64, as its debug form, or checksummed hex when the process displays checksummed addresses.
+impl fmt::Display for TransactionDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if checksummed_addresses() {
+            write!(f, "{}", encode_checksummed_hex(self.0))
+        } else {
+            write!(f, "{:?}", self)
+        }
+    }
+}
+
 impl std::fmt::Debug for TransactionEffectsDigest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
         let s = base64ct::Base64::encode_string(&self.0);
@@ -830,6 +921,9 @@ pub enum ObjectIDParseError {
 
     #[error("Could not convert from bytes slice")]
     TryFromSliceError,
+
+    #[error("Invalid checksum of the ObjectID, it may contain a typo")]
+    InvalidChecksum,
     // #[error("Internal hex parser error: {err}")]
     // HexParserError { err: hex::FromHexError },
 }
@@ -894,7 +988,11 @@ impl From<SuiAddress> for AccountAddress {
 
 impl fmt::Display for ObjectID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:#x}", self)
+        if checksummed_addresses() {
+            write!(f, "{}", encode_checksummed_hex(self))
+        } else {
+            write!(f, "{:#x}", self)
+        }
     }
 }
 
@@ -957,7 +1055,7 @@ impl TryFrom<String> for ObjectID {
 impl FromStr for SuiAddress {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> Result<Self, anyhow::Error> {
-        decode_bytes_hex(s)
+        decode_checksummed_hex(s)
     }
 }
 
@@ -966,16 +1064,25 @@ impl FromStr for ObjectID {
 
     fn from_str(s: &str) -> Result<Self, ObjectIDParseError> {
         // Try to match both the literal (0xABC..) and the normal (ABC)
-        Self::from_hex(s).or_else(|_| Self::from_hex_literal(s))
+        let id = Self::from_hex(s).or_else(|_| Self::from_hex_literal(s))?;
+        if !is_valid_checksum(s, id.as_ref()) {
+            return Err(ObjectIDParseError::InvalidChecksum);
+        }
+        Ok(id)
     }
 }
 
+/// Parses both the base64 and the `0x` prefixed hex forms of a digest.
 impl FromStr for TransactionDigest {
-    type Err = base64ct::Error;
+    type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            let bytes: [u8; TRANSACTION_DIGEST_LENGTH] = decode_checksummed_hex(s)?;
+            return Ok(TransactionDigest(bytes));
+        }
         let mut result = [0u8; TRANSACTION_DIGEST_LENGTH];
-        base64ct::Base64::decode(s, &mut result)?;
+        base64ct::Base64::decode(s, &mut result).map_err(|e| anyhow!(e))?;
         Ok(TransactionDigest(result))
     }
 }
@@ -14,14 +14,21 @@ use crate::{
     error::{ExecutionError, ExecutionErrorKind},
     object::{Data, Object},
 };
-use crate::{base_types::ObjectID, id::UID, SUI_FRAMEWORK_ADDRESS};
+use crate::{
+    base_types::{ObjectID, ObjectRef, SuiAddress, TransactionDigest},
+    id::UID,
+    SUI_FRAMEWORK_ADDRESS,
+};
 use schemars::JsonSchema;
 
 pub const COIN_MODULE_NAME: &IdentStr = ident_str!("coin");
 pub const COIN_STRUCT_NAME: &IdentStr = ident_str!("Coin");
 pub const COIN_JOIN_FUNC_NAME: &IdentStr = ident_str!("join");
+pub const COIN_JOIN_VEC_FUNC_NAME: &IdentStr = ident_str!("join_vec");
+pub const COIN_DESTROY_ZERO_FUNC_NAME: &IdentStr = ident_str!("destroy_zero");
 pub const COIN_SPLIT_N_FUNC_NAME: &IdentStr = ident_str!("split_n");
 pub const COIN_SPLIT_VEC_FUNC_NAME: &IdentStr = ident_str!("split_vec");
+pub const COIN_SPLIT_AND_TRANSFER_FUNC_NAME: &IdentStr = ident_str!("split_and_transfer");
 
 // Rust version of the Move sui::coin::Coin type
 #[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, Eq, PartialEq)]
@@ -109,6 +116,43 @@ impl Coin {
     }
 }
 
+/// A coin received by an address from another one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CoinTransfer {
+    pub transaction_digest: TransactionDigest,
+    pub sender: SuiAddress,
+    /// The coin at its version after the transaction.
+    pub coin: ObjectRef,
+    /// The `T` of the `Coin<T>`.
+    pub coin_type: TypeTag,
+    pub amount: u64,
+}
+
+impl CoinTransfer {
+    /// The transfer of `object` by `sender` in the transaction `transaction_digest`, if
+    /// `object` is a coin.
+    pub fn from_object(
+        transaction_digest: TransactionDigest,
+        sender: SuiAddress,
+        object: &Object,
+    ) -> Result<Option<Self>, ExecutionError> {
+        let coin_type = match object.data.type_() {
+            Some(type_) if Coin::is_coin(type_) => match type_.type_params.first() {
+                Some(coin_type) => coin_type.clone(),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        Ok(Coin::extract_balance_if_coin(object)?.map(|amount| Self {
+            transaction_digest,
+            sender,
+            coin: object.compute_object_reference(),
+            coin_type,
+            amount,
+        }))
+    }
+}
+
 // Rust version of the Move sui::coin::TreasuryCap type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
 pub struct TreasuryCap {
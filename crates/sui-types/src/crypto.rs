@@ -35,6 +35,7 @@ use slip10_ed25519::derive_ed25519_private_key;
 use crate::base_types::{AuthorityName, SuiAddress};
 use crate::committee::{Committee, EpochId};
 use crate::error::{SuiError, SuiResult};
+use crate::multisig::MultiSigSuiSignature;
 use crate::sui_serde::{AggrAuthSignature, Base64, Readable, SuiBitmap};
 pub use enum_dispatch::enum_dispatch;
 
@@ -297,6 +298,22 @@ impl PublicKey {
             PublicKey::Secp256k1KeyPair(_) => Secp256k1SuiSignature::SCHEME,
         }
     }
+
+    /// The public key of `scheme` encoded as `key_bytes`, without the flag of the scheme.
+    pub fn try_from_bytes(
+        scheme: SignatureScheme,
+        key_bytes: &[u8],
+    ) -> Result<PublicKey, eyre::Report> {
+        match scheme {
+            SignatureScheme::ED25519 => Ok(PublicKey::Ed25519KeyPair(
+                Ed25519PublicKey::from_bytes(key_bytes)?,
+            )),
+            SignatureScheme::Secp256k1 => Ok(PublicKey::Secp256k1KeyPair(
+                Secp256k1PublicKey::from_bytes(key_bytes)?,
+            )),
+            _ => Err(eyre::eyre!("Unsupported key scheme {}", scheme.to_string())),
+        }
+    }
 }
 
 /// Defines the compressed version of the public key that we pass around
@@ -524,6 +541,9 @@ pub fn derive_key_pair_from_path(
         SignatureScheme::BLS12381 => Err(SuiError::UnsupportedFeatureError {
             error: "BLS is not supported for user key derivation".to_string(),
         }),
+        SignatureScheme::MultiSig => Err(SuiError::UnsupportedFeatureError {
+            error: "A multisig address has no key to derive".to_string(),
+        }),
     }
 }
 
@@ -591,6 +611,9 @@ pub fn validate_path(
         SignatureScheme::BLS12381 => Err(SuiError::UnsupportedFeatureError {
             error: "BLS is not supported for user key derivation".to_string(),
         }),
+        SignatureScheme::MultiSig => Err(SuiError::UnsupportedFeatureError {
+            error: "A multisig address has no key to derive".to_string(),
+        }),
     }
 }
 
@@ -640,6 +663,7 @@ where
 pub enum Signature {
     Ed25519SuiSignature,
     Secp256k1SuiSignature,
+    MultiSigSuiSignature,
 }
 
 impl Serialize for Signature {
@@ -694,6 +718,7 @@ impl AsRef<[u8]> for Signature {
         match self {
             Signature::Ed25519SuiSignature(sig) => sig.as_ref(),
             Signature::Secp256k1SuiSignature(sig) => sig.as_ref(),
+            Signature::MultiSigSuiSignature(sig) => sig.as_ref(),
         }
     }
 }
@@ -710,6 +735,8 @@ impl signature::Signature for Signature {
                     Ok(<Secp256k1SuiSignature as ToFromBytes>::from_bytes(bytes)
                         .map_err(|_| signature::Error::new())?
                         .into())
+                } else if x == &SignatureScheme::MultiSig.flag() {
+                    Ok(<MultiSigSuiSignature as signature::Signature>::from_bytes(bytes)?.into())
                 } else {
                     Err(signature::Error::new())
                 }
@@ -901,6 +928,10 @@ pub trait SuiSignature: Sized + signature::Signature {
     where
         T: Signable<Vec<u8>>;
 
+    /// Verifies the signature of `message`, the bytes `verify` checks the signature of a value
+    /// against.
+    fn verify_bytes(&self, message: &[u8], author: SuiAddress) -> SuiResult<()>;
+
     fn add_to_verification_obligation_or_verify(
         &self,
         author: SuiAddress,
@@ -914,11 +945,15 @@ impl<S: SuiSignatureInner + Sized> SuiSignature for S {
     where
         T: Signable<Vec<u8>>,
     {
-        // Currently done twice - can we improve on this?;
-        let (sig, pk) = &self.get_verification_inputs(author)?;
         let mut message = Vec::new();
         value.write(&mut message);
-        pk.verify(&message[..], sig)
+        self.verify_bytes(&message, author)
+    }
+
+    fn verify_bytes(&self, message: &[u8], author: SuiAddress) -> SuiResult<()> {
+        // Currently done twice - can we improve on this?;
+        let (sig, pk) = &self.get_verification_inputs(author)?;
+        pk.verify(message, sig)
             .map_err(|_| SuiError::InvalidSignature {
                 error: "hello".to_string(),
             })
@@ -1276,9 +1311,12 @@ mod bcs_signable {
     impl BcsSignable for crate::messages_checkpoint::CheckpointProposalSummary {}
     impl BcsSignable for crate::messages::TransactionEffects {}
     impl BcsSignable for crate::messages::TransactionData {}
+    impl BcsSignable for crate::messages::MoveCall {}
     impl BcsSignable for crate::messages::SenderSignedData {}
     impl BcsSignable for crate::messages::EpochInfo {}
     impl BcsSignable for crate::object::Object {}
+    impl BcsSignable for crate::performance_report::EpochPerformanceReport {}
+    impl BcsSignable for crate::state_snapshot::StateSnapshotSummary {}
 
     impl BcsSignable for super::bcs_signable_test::Foo {}
     #[cfg(test)]
@@ -1453,6 +1491,8 @@ pub enum SignatureScheme {
     ED25519,
     Secp256k1,
     BLS12381,
+    // The signature of a multisig address by a threshold of its keys, see `multisig`.
+    MultiSig,
 }
 
 impl SignatureScheme {
@@ -1461,6 +1501,7 @@ impl SignatureScheme {
             SignatureScheme::ED25519 => 0x00,
             SignatureScheme::Secp256k1 => 0x01,
             SignatureScheme::BLS12381 => 0xff,
+            SignatureScheme::MultiSig => 0x03,
         }
     }
 
@@ -1485,6 +1526,7 @@ impl FromStr for SignatureScheme {
             "ed25519" => Ok(SignatureScheme::ED25519),
             "secp256k1" => Ok(SignatureScheme::Secp256k1),
             "bls12381" => Ok(SignatureScheme::BLS12381),
+            "multisig" => Ok(SignatureScheme::MultiSig),
             _ => Err(SuiError::KeyConversionError(
                 "Invalid key scheme".to_string(),
             )),
@@ -1498,6 +1540,7 @@ impl ToString for SignatureScheme {
             SignatureScheme::ED25519 => "ed25519".to_string(),
             SignatureScheme::Secp256k1 => "secp256k1".to_string(),
             SignatureScheme::BLS12381 => "bls12381".to_string(),
+            SignatureScheme::MultiSig => "multisig".to_string(),
         }
     }
 }
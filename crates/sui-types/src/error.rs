@@ -130,6 +130,8 @@ pub enum SuiError {
     },
     #[error("System Transaction not accepted")]
     InvalidSystemTransaction,
+    #[error("Address {sender:?} is not allowed to publish packages on this network")]
+    PublisherNotAllowed { sender: SuiAddress },
     // Synchronization validation
     #[error("Transaction index must increase by one")]
     UnexpectedTransactionIndex,
@@ -431,10 +433,36 @@ pub enum SuiError {
 
     #[error("Invalid epoch request response: {0}")]
     InvalidEpochResponse(String),
+
+    #[error("Invalid finality proof: {0}")]
+    InvalidFinalityProof(String),
+
+    #[error("Executing transaction {digest:?} again produced different effects")]
+    NondeterministicExecution { digest: TransactionDigest },
+
+    #[error("Validator is overloaded, retry later: {error}")]
+    ValidatorOverloaded { error: String },
+
+    #[error("Invalid sponsored transaction: {error}")]
+    InvalidSponsoredTransaction { error: String },
+
+    #[error("Invalid transaction builder: {error}")]
+    InvalidTransactionBuilder { error: String },
 }
 
 pub type SuiResult<T = ()> = Result<T, SuiError>;
 
+impl SuiError {
+    /// Whether the request failed because of the transient state of the validator, and may
+    /// succeed if sent again later, unchanged.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            SuiError::ValidatorOverloaded { .. } | SuiError::ValidatorHaltedAtEpochEnd
+        )
+    }
+}
+
 // TODO these are both horribly wrong, categorization needs to be considered
 impl std::convert::From<PartialVMError> for SuiError {
     fn from(error: PartialVMError) -> Self {
@@ -466,6 +494,12 @@ impl std::convert::From<SubscriberError> for SuiError {
 
 impl From<tonic::Status> for SuiError {
     fn from(status: tonic::Status) -> Self {
+        // Validators shed the requests they have no capacity for with this code
+        if status.code() == tonic::Code::ResourceExhausted {
+            return Self::ValidatorOverloaded {
+                error: status.message().to_owned(),
+            };
+        }
         Self::RpcError(status.message().to_owned(), status.code().description())
     }
 }
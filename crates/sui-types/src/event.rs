@@ -9,6 +9,7 @@ use crate::object::ObjectFormatOptions;
 use crate::object::Owner;
 use crate::{
     base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest},
+    batch::TxSequenceNumber,
     committee::EpochId,
     messages_checkpoint::CheckpointSequenceNumber,
 };
@@ -26,7 +27,6 @@ use serde_with::serde_as;
 use serde_with::Bytes;
 use strum::VariantNames;
 use strum_macros::{EnumDiscriminants, EnumVariantNames};
-use tracing::error;
 
 /// A universal Sui event type encapsulating different types of events
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,6 +37,8 @@ pub struct EventEnvelope {
     pub tx_digest: Option<TransactionDigest>,
     /// Sequence number, must be nondecreasing for event ingestion idempotency
     pub seq_num: u64,
+    /// Index of the event in the events emitted by the transaction
+    pub event_num: u64,
     /// Specific event type
     pub event: Event,
     /// json value for MoveStruct (for MoveEvent only)
@@ -48,6 +50,7 @@ impl EventEnvelope {
         timestamp: u64,
         tx_digest: Option<TransactionDigest>,
         seq_num: u64,
+        event_num: u64,
         event: Event,
         move_struct_json_value: Option<Value>,
     ) -> Self {
@@ -55,6 +58,7 @@ impl EventEnvelope {
             timestamp,
             tx_digest,
             seq_num,
+            event_num,
             event,
             move_struct_json_value,
         }
@@ -63,6 +67,26 @@ impl EventEnvelope {
     pub fn event_type(&self) -> &'static str {
         self.event.variant_name()
     }
+
+    pub fn id(&self) -> EventID {
+        EventID {
+            tx_seq_num: self.seq_num,
+            event_num: self.event_num,
+        }
+    }
+}
+
+/// Position of an event in the event stream of a node, used as a cursor to resume a
+/// subscription from.
+#[derive(
+    Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EventID {
+    /// Sequence number of the transaction which emitted the event, in the node executing it
+    pub tx_seq_num: TxSequenceNumber,
+    /// Index of the event in the events emitted by the transaction
+    pub event_num: u64,
 }
 
 #[derive(
@@ -277,15 +301,7 @@ impl Event {
     pub fn recipient_serialized(&self) -> Result<Option<String>, SuiError> {
         match self {
             Event::TransferObject { recipient, .. } | Event::NewObject { recipient, .. } => {
-                let res = serde_json::to_string(recipient);
-                if let Err(e) = res {
-                    error!("Failed to serialize recipient field of event: {:?}", self);
-                    Err(SuiError::OwnerFailedToSerialize {
-                        error: (e.to_string()),
-                    })
-                } else {
-                    Ok(res.ok())
-                }
+                Ok(Some(recipient.to_canonical_json()))
             }
             _ => Ok(None),
         }
@@ -0,0 +1,167 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::base_types::ExecutionDigests;
+use crate::committee::{Committee, EpochId};
+use crate::error::{SuiError, SuiResult};
+use crate::messages::{AuthenticatedEpoch, CertifiedTransaction, TransactionEffects};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+#[path = "unit_tests/finality_proof_tests.rs"]
+mod finality_proof_tests;
+
+/// Version of the serialization of `FinalityProof`, bumped on any change to it.
+pub const FINALITY_PROOF_VERSION: u8 = 1;
+
+/// The checkpoint a transaction was included in, with the contents of the checkpoint.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointInclusionProof {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+}
+
+/// A self-contained proof that a transaction is final, which can be checked offline against
+/// the genesis committee alone.
+///
+/// The certificate shows that a quorum of the committee of its epoch signed the transaction.
+/// If the transaction was included in a certified checkpoint, the inclusion proof also binds
+/// the effects to it. The epochs link the genesis committee to the committee of the epoch of
+/// the transaction, each epoch being signed by the committee of the previous one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FinalityProof {
+    pub version: u8,
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+    pub checkpoint: Option<CheckpointInclusionProof>,
+    /// The authenticated epochs from genesis to the epoch of the certificate, in order.
+    pub epochs: Vec<AuthenticatedEpoch>,
+}
+
+impl FinalityProof {
+    pub fn new(
+        certificate: CertifiedTransaction,
+        effects: TransactionEffects,
+        checkpoint: Option<CheckpointInclusionProof>,
+        epochs: Vec<AuthenticatedEpoch>,
+    ) -> Self {
+        Self {
+            version: FINALITY_PROOF_VERSION,
+            certificate,
+            effects,
+            checkpoint,
+            epochs,
+        }
+    }
+
+    pub fn epoch(&self) -> EpochId {
+        self.certificate.auth_sign_info.epoch
+    }
+
+    /// The BCS serialization of the proof, which is stable across releases for a given version.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("BCS serialization of a finality proof should not fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        fp_ensure!(
+            bytes.first() == Some(&FINALITY_PROOF_VERSION),
+            SuiError::InvalidFinalityProof(format!(
+                "Unsupported version {:?}, expected {}",
+                bytes.first(),
+                FINALITY_PROOF_VERSION
+            ))
+        );
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidFinalityProof(e.to_string()))
+    }
+
+    /// Checks the proof, trusting nothing but `genesis_committee`. Epochs signed by a single
+    /// validator rather than certified by a quorum are accepted; verifiers needing more can
+    /// check that all of `epochs` are `AuthenticatedEpoch::Certified`.
+    pub fn verify(&self, genesis_committee: &Committee) -> SuiResult {
+        let committee = self.verify_epochs(genesis_committee)?;
+        self.certificate.verify(committee)?;
+
+        let transaction_digest = *self.certificate.digest();
+        fp_ensure!(
+            self.effects.transaction_digest == transaction_digest,
+            SuiError::InvalidFinalityProof(
+                "Effects are not those of the certified transaction".to_string()
+            )
+        );
+
+        if let Some(inclusion) = &self.checkpoint {
+            inclusion
+                .checkpoint
+                .verify(committee, Some(&inclusion.contents))?;
+            let execution_digests =
+                ExecutionDigests::new(transaction_digest, self.effects.digest());
+            fp_ensure!(
+                inclusion
+                    .contents
+                    .iter()
+                    .any(|digests| digests == &execution_digests),
+                SuiError::InvalidFinalityProof(
+                    "Transaction is not included in the checkpoint".to_string()
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks the chain of epochs from genesis, and returns the committee of the epoch of the
+    /// certificate.
+    fn verify_epochs(&self, genesis_committee: &Committee) -> SuiResult<&Committee> {
+        fp_ensure!(
+            self.epochs.len() as u64 == self.epoch().saturating_add(1),
+            SuiError::InvalidFinalityProof(format!(
+                "Expected the epochs up to epoch {}, got {}",
+                self.epoch(),
+                self.epochs.len()
+            ))
+        );
+        let mut previous: Option<&AuthenticatedEpoch> = None;
+        for (epoch, authenticated) in self.epochs.iter().enumerate() {
+            fp_ensure!(
+                authenticated.epoch() == epoch as EpochId,
+                SuiError::InvalidFinalityProof(format!(
+                    "Expected epoch {}, got epoch {}",
+                    epoch,
+                    authenticated.epoch()
+                ))
+            );
+            match (authenticated, previous) {
+                (AuthenticatedEpoch::Genesis(genesis), None) => {
+                    genesis.verify(genesis_committee)?
+                }
+                (AuthenticatedEpoch::Signed(signed), Some(previous)) => {
+                    signed.verify(previous.epoch_info().committee())?
+                }
+                (AuthenticatedEpoch::Certified(certified), Some(previous)) => {
+                    certified.verify(previous.epoch_info().committee())?
+                }
+                _ => {
+                    return Err(SuiError::InvalidFinalityProof(format!(
+                        "Epoch {} must be the genesis epoch if and only if it is epoch 0",
+                        epoch
+                    )))
+                }
+            }
+            if let Some(previous) = previous {
+                fp_ensure!(
+                    authenticated.epoch_info().prev_epoch_info_digest()
+                        == &previous.epoch_info().digest(),
+                    SuiError::InvalidFinalityProof(format!(
+                        "Epoch {} does not follow epoch {}",
+                        epoch,
+                        epoch - 1
+                    ))
+                );
+            }
+            previous = Some(authenticated);
+        }
+        // Unwrap safe since there is at least one epoch, checked above
+        Ok(previous.unwrap().epoch_info().committee())
+    }
+}
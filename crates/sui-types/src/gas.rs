@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    committee::EpochId,
     error::{ExecutionError, ExecutionErrorKind},
     error::{SuiError, SuiResult},
     gas_coin::GasCoin,
@@ -22,8 +23,9 @@ use std::{
 use sui_cost_tables::{
     bytecode_tables::{GasStatus, INITIAL_COST_SCHEDULE},
     non_execution_tables::{
-        BASE_TX_COST_FIXED, CONSENSUS_COST, MAXIMUM_TX_GAS, OBJ_ACCESS_COST_MUTATE_PER_BYTE,
-        OBJ_ACCESS_COST_READ_PER_BYTE, OBJ_DATA_COST_REFUNDABLE, PACKAGE_PUBLISH_COST_PER_BYTE,
+        BASE_TX_COST_FIXED, CONSENSUS_COST, EVENT_DATA_COST_NON_REFUNDABLE, MAXIMUM_TX_GAS,
+        OBJ_ACCESS_COST_MUTATE_PER_BYTE, OBJ_ACCESS_COST_READ_PER_BYTE, OBJ_DATA_COST_REFUNDABLE,
+        OBJ_METADATA_COST_NON_REFUNDABLE, PACKAGE_PUBLISH_COST_PER_BYTE,
     },
     units_types::GasUnit,
 };
@@ -52,6 +54,13 @@ pub struct GasCostSummary {
     pub computation_cost: u64,
     pub storage_cost: u64,
     pub storage_rebate: u64,
+    /// The part of `storage_cost` paid for the events the transaction emitted. Missing from
+    /// summaries recorded before events were charged for.
+    #[serde(default)]
+    pub event_storage_cost: u64,
+    /// The part of `storage_cost` paid for the effects of the transaction.
+    #[serde(default)]
+    pub effects_storage_cost: u64,
 }
 
 impl GasCostSummary {
@@ -65,6 +74,64 @@ impl GasCostSummary {
     }
 }
 
+/// The gas charged by the transactions of an epoch, and the SUI minted by the system at its
+/// end to reward the validators and the storage fund.
+#[derive(Eq, PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EpochFeeSummary {
+    pub epoch: EpochId,
+    pub transaction_count: u64,
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub minted: u64,
+}
+
+impl EpochFeeSummary {
+    /// The share of a single transaction of epoch `epoch`.
+    pub fn for_transaction(epoch: EpochId, gas_used: &GasCostSummary, minted: u64) -> Self {
+        Self {
+            epoch,
+            transaction_count: 1,
+            computation_cost: gas_used.computation_cost,
+            storage_cost: gas_used.storage_cost,
+            storage_rebate: gas_used.storage_rebate,
+            minted,
+        }
+    }
+
+    /// Folds in `other`, of the same epoch.
+    pub fn add(&mut self, other: &EpochFeeSummary) {
+        debug_assert_eq!(self.epoch, other.epoch);
+        self.transaction_count += other.transaction_count;
+        self.computation_cost += other.computation_cost;
+        self.storage_cost += other.storage_cost;
+        self.storage_rebate += other.storage_rebate;
+        self.minted += other.minted;
+    }
+}
+
+/// The SUI in existence, accounted from genesis.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct SupplySummary {
+    pub epoch: EpochId,
+    /// The SUI of the coins created at genesis, outside of the supply of the system.
+    pub genesis_coins: u64,
+    /// The SUI minted by the system: the stakes and storage fund of genesis, and the rewards
+    /// of every epoch since.
+    pub system_minted: u64,
+    /// The SUI burned by the gas charged since genesis.
+    pub gas_burned: u64,
+    /// The SUI given back by storage rebates since genesis.
+    pub storage_rebates: u64,
+}
+
+impl SupplySummary {
+    pub fn total(&self) -> u64 {
+        (self.genesis_coins + self.system_minted + self.storage_rebates)
+            .saturating_sub(self.gas_burned)
+    }
+}
+
 // Fixed cost type
 pub struct FixedCost(InternalGas);
 impl FixedCost {
@@ -158,6 +225,25 @@ static INIT_SUI_COST_TABLE: Lazy<SuiCostTable> = Lazy::new(|| SuiCostTable {
     storage_per_byte_cost: StorageCostPerByte::new(OBJ_DATA_COST_REFUNDABLE),
 });
 
+/// Per byte rates of the storage charged for the events a transaction emits and for its
+/// effects. Unlike the storage of objects, it is never rebated, since events and effects are
+/// kept for good. The rates are part of the execution protocol, each execution engine charges
+/// the ones of its protocol version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NonRefundableStorageRates {
+    pub event_per_byte: u64,
+    pub effects_per_byte: u64,
+}
+
+impl Default for NonRefundableStorageRates {
+    fn default() -> Self {
+        Self {
+            event_per_byte: EVENT_DATA_COST_NON_REFUNDABLE,
+            effects_per_byte: OBJ_METADATA_COST_NON_REFUNDABLE,
+        }
+    }
+}
+
 pub static MAX_GAS_BUDGET: Lazy<u64> = Lazy::new(|| u64::from(to_external(MAXIMUM_TX_GAS)));
 
 pub static MIN_GAS_BUDGET: Lazy<u64> =
@@ -185,6 +271,10 @@ pub struct SuiGasStatus<'a> {
     /// was the storage cost paid when the object was last mutated. It is not affected
     /// by the current storage gas unit price.
     storage_rebate: SuiGas,
+    /// The storage gas units charged for events, on top of `storage_gas_units`.
+    event_gas_units: GasUnits,
+    /// The storage gas units charged for effects, on top of `storage_gas_units`.
+    effects_gas_units: GasUnits,
 }
 
 impl<'a> SuiGasStatus<'a> {
@@ -259,6 +349,36 @@ impl<'a> SuiGasStatus<'a> {
         self.deduct_storage_cost(&storage_cost).map(|q| q.into())
     }
 
+    /// Charges the non-refundable storage of `size` bytes of events.
+    pub fn charge_events(
+        &mut self,
+        size: usize,
+        rates: &NonRefundableStorageRates,
+    ) -> Result<(), ExecutionError> {
+        if self.is_unmetered() {
+            return Ok(());
+        }
+        let cost = NumBytes::new(size as u64).mul(InternalGasPerByte::new(rates.event_per_byte));
+        let (units, result) = self.deduct_storage_units(&cost);
+        self.event_gas_units = self.event_gas_units.add(units);
+        result
+    }
+
+    /// Charges the non-refundable storage of `size` bytes of effects.
+    pub fn charge_effects(
+        &mut self,
+        size: usize,
+        rates: &NonRefundableStorageRates,
+    ) -> Result<(), ExecutionError> {
+        if self.is_unmetered() {
+            return Ok(());
+        }
+        let cost = NumBytes::new(size as u64).mul(InternalGasPerByte::new(rates.effects_per_byte));
+        let (units, result) = self.deduct_storage_units(&cost);
+        self.effects_gas_units = self.effects_gas_units.add(units);
+        result
+    }
+
     /// This function is only called during testing, where we need to mock
     /// Move VM charging gas.
     pub fn charge_vm_exec_test_only(&mut self, cost: u64) -> Result<(), ExecutionError> {
@@ -275,7 +395,10 @@ impl<'a> SuiGasStatus<'a> {
     /// computation cost.
     pub fn summary(&self, succeeded: bool) -> GasCostSummary {
         let remaining_gas = self.gas_status.remaining_gas();
-        let storage_cost = self.storage_gas_units;
+        let storage_cost = self
+            .storage_gas_units
+            .add(self.event_gas_units)
+            .add(self.effects_gas_units);
         // TODO: handle underflow how?
         let computation_cost = self
             .init_budget
@@ -289,6 +412,11 @@ impl<'a> SuiGasStatus<'a> {
                 computation_cost: computation_cost_in_sui,
                 storage_cost: storage_cost.mul(self.storage_gas_unit_price).into(),
                 storage_rebate: self.storage_rebate.into(),
+                event_storage_cost: self.event_gas_units.mul(self.storage_gas_unit_price).into(),
+                effects_storage_cost: self
+                    .effects_gas_units
+                    .mul(self.storage_gas_unit_price)
+                    .into(),
             }
         } else {
             // If execution failed, no storage creation/deletion will materialize in the store.
@@ -297,6 +425,8 @@ impl<'a> SuiGasStatus<'a> {
                 computation_cost: computation_cost_in_sui,
                 storage_cost: 0,
                 storage_rebate: 0,
+                event_storage_cost: 0,
+                effects_storage_cost: 0,
             }
         }
     }
@@ -318,6 +448,8 @@ impl<'a> SuiGasStatus<'a> {
             storage_gas_unit_price: ComputeGasPricePerUnit::new(storage_gas_unit_price),
             storage_gas_units: GasUnits::new(0),
             storage_rebate: 0.into(),
+            event_gas_units: GasUnits::new(0),
+            effects_gas_units: GasUnits::new(0),
         }
     }
 
@@ -332,6 +464,17 @@ impl<'a> SuiGasStatus<'a> {
         if self.is_unmetered() {
             return Ok(0.into());
         }
+        let (units, result) = self.deduct_storage_units(cost);
+        self.storage_gas_units = self.storage_gas_units.add(units);
+        result.map(|()| units.mul(self.storage_gas_unit_price))
+    }
+
+    /// Deducts a storage cost, returning the gas units charged for it, which are all the
+    /// remaining gas if it isn't enough.
+    fn deduct_storage_units(
+        &mut self,
+        cost: &InternalGas,
+    ) -> (GasUnits, Result<(), ExecutionError>) {
         let ext_cost = to_external(NumBytes::new(1).mul(InternalGasPerByte::new(u64::from(*cost))));
         let charge_amount = to_internal(ext_cost);
         let remaining_gas = self.gas_status.remaining_gas();
@@ -340,11 +483,12 @@ impl<'a> SuiGasStatus<'a> {
             // Even when we run out of gas, we still keep track of the storage_cost change,
             // so that at the end, we could still use it to accurately derive the
             // computation cost.
-            self.storage_gas_units = self.storage_gas_units.add(remaining_gas);
-            Err(ExecutionErrorKind::InsufficientGas.into())
+            (
+                remaining_gas,
+                Err(ExecutionErrorKind::InsufficientGas.into()),
+            )
         } else {
-            self.storage_gas_units = self.storage_gas_units.add(ext_cost);
-            Ok(ext_cost.mul(self.storage_gas_unit_price))
+            (ext_cost, Ok(()))
         }
     }
 }
@@ -23,18 +23,26 @@ pub mod coin;
 pub mod committee;
 pub mod crypto;
 pub mod event;
+pub mod finality_proof;
 pub mod gas;
 pub mod gas_coin;
 pub mod id;
+pub mod memo;
 pub mod message_envelope;
 pub mod messages;
 pub mod messages_checkpoint;
 pub mod move_package;
+pub mod multisig;
 pub mod object;
+pub mod object_chunks;
+pub mod performance_report;
 pub mod signature_seed;
+pub mod state_snapshot;
 pub mod storage;
+pub mod sui_amount;
 pub mod sui_serde;
 pub mod sui_system_state;
+pub mod transaction_builder;
 pub mod waypoint;
 
 pub mod filter;
@@ -50,7 +58,8 @@ pub const MOVE_STDLIB_ADDRESS: AccountAddress = AccountAddress::ONE;
 pub const SUI_FRAMEWORK_ADDRESS: AccountAddress = get_hex_address_two();
 pub const SUI_FRAMEWORK_OBJECT_ID: ObjectID = ObjectID::from_single_byte(2);
 
-/// 0x5: hardcoded object ID for the singleton sui system state object.
+/// 0x5: hardcoded object ID for the singleton sui system state object. The framework checks this
+/// copy of `sui::object::SUI_SYSTEM_STATE_OBJECT_ID` when it is built.
 pub const SUI_SYSTEM_STATE_OBJECT_ID: ObjectID = ObjectID::from_single_byte(5);
 
 const fn get_hex_address_two() -> AccountAddress {
@@ -0,0 +1,34 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::StructTag};
+use serde::{Deserialize, Serialize};
+
+use crate::{base_types::SuiAddress, SUI_FRAMEWORK_ADDRESS};
+
+pub const MEMO_MODULE_NAME: &IdentStr = ident_str!("memo");
+pub const MEMO_EVENT_STRUCT_NAME: &IdentStr = ident_str!("MemoEvent");
+pub const MEMO_ATTACH_FUNC_NAME: &IdentStr = ident_str!("attach");
+
+/// Maximum length of a memo in bytes, as enforced by sui::memo::attach. The framework checks this
+/// copy of its `MAX_MEMO_LENGTH` when it is built.
+pub const MAX_MEMO_LENGTH: usize = 256;
+
+/// Rust version of the Move sui::memo::MemoEvent type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct MemoEvent {
+    pub sender: SuiAddress,
+    pub recipient: SuiAddress,
+    pub memo: String,
+}
+
+impl MemoEvent {
+    pub fn type_() -> StructTag {
+        StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: MEMO_MODULE_NAME.to_owned(),
+            name: MEMO_EVENT_STRUCT_NAME.to_owned(),
+            type_params: vec![],
+        }
+    }
+}
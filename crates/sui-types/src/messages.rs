@@ -12,6 +12,7 @@ use crate::crypto::{
 use crate::gas::GasCostSummary;
 use crate::messages_checkpoint::{CheckpointFragment, CheckpointSequenceNumber};
 use crate::object::{Object, ObjectFormatOptions, Owner, OBJECT_START_VERSION};
+use crate::object_chunks::{self, ChunkDigest, ChunkInfo};
 use crate::storage::{DeleteKind, WriteKind};
 use crate::sui_serde::Base64;
 use crate::SUI_SYSTEM_STATE_OBJECT_ID;
@@ -36,7 +37,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::{
-    collections::{BTreeMap, BTreeSet, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     hash::{Hash, Hasher},
 };
 use tracing::debug;
@@ -391,6 +392,9 @@ pub struct TransactionData {
     gas_payment: ObjectRef,
     pub gas_price: u64,
     pub gas_budget: u64,
+    /// The owner of the gas object, when it pays for the gas of the transaction on behalf of the
+    /// sender. The transaction must then be signed by the sponsor as well as by the sender.
+    gas_sponsor: Option<SuiAddress>,
 }
 
 impl TransactionData {
@@ -407,6 +411,7 @@ impl TransactionData {
             gas_price: 1,
             gas_payment,
             gas_budget,
+            gas_sponsor: None,
         }
     }
 
@@ -423,6 +428,7 @@ impl TransactionData {
             gas_price,
             gas_payment,
             gas_budget,
+            gas_sponsor: None,
         }
     }
 
@@ -499,6 +505,55 @@ impl TransactionData {
         self.sender
     }
 
+    /// Has the gas of the transaction paid by `sponsor`, who must own the gas object.
+    pub fn with_gas_sponsor(mut self, sponsor: SuiAddress) -> Self {
+        self.gas_sponsor = Some(sponsor);
+        self
+    }
+
+    pub fn gas_sponsor(&self) -> Option<SuiAddress> {
+        self.gas_sponsor
+    }
+
+    /// The address the gas object must be owned by: the sponsor if any, the sender otherwise.
+    pub fn gas_owner(&self) -> SuiAddress {
+        self.gas_sponsor.unwrap_or(self.sender)
+    }
+
+    /// Checks the kind of the transaction, and that a sponsored transaction can't spend the gas
+    /// object of its sponsor other than on gas.
+    pub fn validity_check(&self) -> SuiResult {
+        self.kind.validity_check()?;
+        if self.gas_sponsor.is_none() {
+            return Ok(());
+        }
+        fp_ensure!(
+            !self
+                .kind
+                .single_transactions()
+                .any(|s| matches!(s, SingleTransactionKind::TransferSui(_))),
+            SuiError::InvalidSponsoredTransaction {
+                error: "TransferSui transfers the gas object, which the sender doesn't own"
+                    .to_string(),
+            }
+        );
+        for single in self.kind.single_transactions() {
+            fp_ensure!(
+                single
+                    .input_objects()?
+                    .iter()
+                    .all(|object| object.object_id() != self.gas_payment.0),
+                SuiError::InvalidSponsoredTransaction {
+                    error: format!(
+                        "Gas object {} of the sponsor can't be used by the transaction",
+                        self.gas_payment.0
+                    ),
+                }
+            );
+        }
+        Ok(())
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut writer = Vec::new();
         self.write(&mut writer);
@@ -572,6 +627,8 @@ pub struct SenderSignedData {
     pub data: TransactionData,
     /// tx_signature is signed by the transaction sender, applied on `data`.
     pub tx_signature: Signature,
+    /// Signed by the gas sponsor of `data` if it has one, applied on `data`.
+    pub sponsor_signature: Option<Signature>,
 }
 
 impl<S> TransactionEnvelope<S> {
@@ -591,16 +648,49 @@ impl<S> TransactionEnvelope<S> {
 
         self.signed_data
             .tx_signature
-            .add_to_verification_obligation_or_verify(self.signed_data.data.sender, obligation, idx)
+            .add_to_verification_obligation_or_verify(
+                self.signed_data.data.sender,
+                obligation,
+                idx,
+            )?;
+        match (
+            self.signed_data.data.gas_sponsor,
+            &self.signed_data.sponsor_signature,
+        ) {
+            (Some(sponsor), Some(signature)) => {
+                signature.add_to_verification_obligation_or_verify(sponsor, obligation, idx)
+            }
+            (None, None) => Ok(()),
+            (sponsor, _) => Self::missing_sponsor_signature(sponsor),
+        }
     }
 
+    /// Verifies the signature of the sender, and the one of the gas sponsor if the transaction
+    /// has one.
     pub fn verify_sender_signature(&self) -> SuiResult<()> {
         if self.is_verified || self.signed_data.data.kind.is_system_tx() {
             return Ok(());
         }
         self.signed_data
             .tx_signature
-            .verify(&self.signed_data.data, self.signed_data.data.sender)
+            .verify(&self.signed_data.data, self.signed_data.data.sender)?;
+        match (
+            self.signed_data.data.gas_sponsor,
+            &self.signed_data.sponsor_signature,
+        ) {
+            (Some(sponsor), Some(signature)) => signature.verify(&self.signed_data.data, sponsor),
+            (None, None) => Ok(()),
+            (sponsor, _) => Self::missing_sponsor_signature(sponsor),
+        }
+    }
+
+    fn missing_sponsor_signature(sponsor: Option<SuiAddress>) -> SuiResult<()> {
+        Err(SuiError::InvalidSignature {
+            error: match sponsor {
+                Some(sponsor) => format!("Missing the signature of the gas sponsor {sponsor}"),
+                None => "Sponsor signature on a transaction without gas sponsor".to_string(),
+            },
+        })
     }
 
     pub fn sender_address(&self) -> SuiAddress {
@@ -707,11 +797,42 @@ impl Transaction {
             signed_data: SenderSignedData {
                 data,
                 tx_signature: signature,
+                sponsor_signature: None,
             },
             auth_sign_info: EmptySignInfo {},
         }
     }
 
+    /// A transaction whose gas is paid by the gas sponsor of `data`, signed by both its sender
+    /// and its sponsor.
+    pub fn new_sponsored(
+        data: TransactionData,
+        signature: Signature,
+        sponsor_signature: Signature,
+    ) -> Self {
+        let mut transaction = Self::new(data, signature);
+        transaction.signed_data.sponsor_signature = Some(sponsor_signature);
+        transaction
+    }
+
+    /// Adds the signature of the gas sponsor to a transaction signed by its sender.
+    pub fn with_sponsor_signature(self, sponsor_signature: Signature) -> Self {
+        Self::new_sponsored(
+            self.signed_data.data,
+            self.signed_data.tx_signature,
+            sponsor_signature,
+        )
+    }
+
+    fn from_signed_data(signed_data: SenderSignedData) -> Self {
+        Self {
+            transaction_digest: OnceCell::new(),
+            is_verified: false,
+            signed_data,
+            auth_sign_info: EmptySignInfo {},
+        }
+    }
+
     pub fn verify(&self) -> Result<(), SuiError> {
         self.verify_sender_signature()
     }
@@ -724,6 +845,15 @@ impl Transaction {
             Base64::from_bytes(self.signed_data.tx_signature.public_key_bytes()),
         )
     }
+
+    /// The signature of the gas sponsor, if any, sent along with the network data of the
+    /// transaction as its flag, signature and public key bytes.
+    pub fn sponsor_signature_for_execution(&self) -> Option<Base64> {
+        self.signed_data
+            .sponsor_signature
+            .as_ref()
+            .map(|signature| Base64::from_bytes(signature.as_ref()))
+    }
 }
 
 impl Hash for Transaction {
@@ -788,6 +918,7 @@ impl SignedTransaction {
             tx_signature: Ed25519SuiSignature::from_bytes(&[0; Ed25519SuiSignature::LENGTH])
                 .unwrap()
                 .into(),
+            sponsor_signature: None,
         };
         let signature = AuthoritySignature::new(&signed_data, secret);
         Self {
@@ -819,7 +950,7 @@ impl SignedTransaction {
     // forming a CertifiedTransaction, where each transaction's authority signature
     // is taking out to form an aggregated signature.
     pub fn to_transaction(self) -> Transaction {
-        Transaction::new(self.signed_data.data, self.signed_data.tx_signature)
+        Transaction::from_signed_data(self.signed_data)
     }
 }
 
@@ -912,6 +1043,93 @@ impl ObjectInfoRequest {
     }
 }
 
+/// A request for an object at a given version as the chunks of its BCS bytes, leaving out the
+/// chunks the requester already holds, e.g. from a previous version of the object. Much less
+/// than the whole object is sent back when only parts of a large object changed.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ObjectChunksRequest {
+    pub object_ref: ObjectRef,
+    /// Digests of the chunks the requester holds, which are not sent back.
+    pub known_chunks: Vec<ChunkDigest>,
+}
+
+impl ObjectChunksRequest {
+    /// A request for the object at `object_ref`, reusing the chunks of `base`.
+    pub fn new(object_ref: ObjectRef, base: Option<&Object>) -> SuiResult<Self> {
+        let known_chunks = match base {
+            Some(base) => object_chunks::split(&object_bytes(base)?)
+                .into_iter()
+                .map(|(info, _)| info.digest)
+                .collect(),
+            None => vec![],
+        };
+        Ok(ObjectChunksRequest {
+            object_ref,
+            known_chunks,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectChunksResponse {
+    /// The chunks of the BCS bytes of the object, in order.
+    pub manifest: Vec<ChunkInfo>,
+    /// The contents of the chunks of the manifest which the requester doesn't hold, in the order
+    /// of the manifest, each chunk once.
+    pub chunks: Vec<Vec<u8>>,
+}
+
+impl ObjectChunksResponse {
+    /// Splits `object` into chunks, to send the ones which are not in `known_chunks`.
+    pub fn new(object: &Object, known_chunks: &[ChunkDigest]) -> SuiResult<Self> {
+        let bytes = object_bytes(object)?;
+        let mut skipped: HashSet<ChunkDigest> = known_chunks.iter().copied().collect();
+        let mut manifest = vec![];
+        let mut chunks = vec![];
+        for (info, chunk) in object_chunks::split(&bytes) {
+            // Chunks sent once are known from then on
+            if skipped.insert(info.digest) {
+                chunks.push(chunk.to_vec());
+            }
+            manifest.push(info);
+        }
+        Ok(ObjectChunksResponse { manifest, chunks })
+    }
+
+    /// Puts the object back together from the chunks sent and the ones of `base`, the object
+    /// the request was made with, and checks that it's the one of `object_ref`.
+    pub fn into_object(self, object_ref: ObjectRef, base: Option<&Object>) -> SuiResult<Object> {
+        let object_id = object_ref.0;
+        let fetch_failed = |err: String| SuiError::ObjectFetchFailed { object_id, err };
+        let base_bytes = match base {
+            Some(base) => object_bytes(base)?,
+            None => vec![],
+        };
+        let known: HashMap<_, _> = object_chunks::split(&base_bytes)
+            .into_iter()
+            .map(|(info, chunk)| (info.digest, chunk))
+            .collect();
+        let bytes = object_chunks::reassemble(&self.manifest, self.chunks, &known)
+            .map_err(|err| fetch_failed(err.to_string()))?;
+        let object: Object =
+            bcs::from_bytes(&bytes).map_err(|err| fetch_failed(err.to_string()))?;
+        fp_ensure!(
+            object.compute_object_reference() == object_ref,
+            SuiError::InvalidObjectDigest {
+                object_id,
+                expected_digest: object_ref.2,
+            }
+        );
+        Ok(object)
+    }
+}
+
+fn object_bytes(object: &Object) -> SuiResult<Vec<u8>> {
+    bcs::to_bytes(object).map_err(|err| SuiError::ObjectSerializationError {
+        error: err.to_string(),
+    })
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct AccountInfoResponse {
     pub object_ids: Vec<ObjectRef>,
@@ -979,6 +1197,28 @@ pub struct TransactionInfoResponse {
     pub signed_effects: Option<SignedTransactionEffects>,
 }
 
+/// Most transactions, or certificates, a validator handles in a single request.
+pub const MAX_TRANSACTION_BATCH_SIZE: usize = 1_000;
+
+/// Transactions handled by a validator in a single request, to save the overhead of a request
+/// per transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionBatchRequest {
+    pub transactions: Vec<Transaction>,
+}
+
+/// Certificates executed by a validator in a single request.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CertificateBatchRequest {
+    pub certificates: Vec<CertifiedTransaction>,
+}
+
+/// The response to each transaction, or certificate, of a batch, in their order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionBatchResponse {
+    pub responses: Vec<Result<TransactionInfoResponse, SuiError>>,
+}
+
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum CallResult {
     Bool(bool),
@@ -1807,7 +2047,7 @@ impl CertifiedTransaction {
     }
 
     pub fn to_transaction(self) -> Transaction {
-        Transaction::new(self.signed_data.data, self.signed_data.tx_signature)
+        Transaction::from_signed_data(self.signed_data)
     }
 
     /// Verify the certificate.
@@ -5,11 +5,11 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::slice::Iter;
 
-use crate::base_types::ExecutionDigests;
+use crate::base_types::{ExecutionDigests, ObjectID, ObjectRef};
 use crate::committee::EpochId;
 use crate::crypto::{AuthoritySignInfo, AuthoritySignInfoTrait, AuthorityWeakQuorumSignInfo};
 use crate::error::SuiResult;
-use crate::messages::CertifiedTransaction;
+use crate::messages::{CertifiedTransaction, TransactionEffects};
 use crate::waypoint::{Waypoint, WaypointDiff};
 use crate::{
     base_types::AuthorityName,
@@ -427,6 +427,102 @@ impl CheckpointContents {
     }
 }
 
+/// Net changes made to the objects by the transactions of a range of checkpoints. Objects
+/// which were both created and deleted in the range are left out, wrapped objects count as
+/// deleted and unwrapped ones as created.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CheckpointStateDiff {
+    /// Objects which didn't exist before the range, at their last version.
+    pub created: Vec<ObjectRef>,
+    /// Objects which existed before the range, at their last version.
+    pub mutated: Vec<ObjectRef>,
+    /// Objects which existed before the range and don't anymore.
+    pub deleted: Vec<ObjectRef>,
+}
+
+impl CheckpointStateDiff {
+    /// Computes the diff from the effects of the transactions of the range, in execution order.
+    pub fn from_effects<'a>(effects: impl IntoIterator<Item = &'a TransactionEffects>) -> Self {
+        // Whether each object existed before the range, its last reference and whether it
+        // exists after it
+        let mut objects: BTreeMap<ObjectID, (bool, ObjectRef, bool)> = BTreeMap::new();
+        for effects in effects {
+            let appeared = effects
+                .created
+                .iter()
+                .chain(effects.unwrapped.iter())
+                .map(|(object_ref, _)| (object_ref, false, true));
+            let mutated = effects
+                .mutated
+                .iter()
+                .map(|(object_ref, _)| (object_ref, true, true));
+            let disappeared = effects
+                .deleted
+                .iter()
+                .chain(effects.wrapped.iter())
+                .map(|object_ref| (object_ref, true, false));
+            for (object_ref, existed, exists) in appeared.chain(mutated).chain(disappeared) {
+                objects
+                    .entry(object_ref.0)
+                    .and_modify(|(_, last_ref, alive)| {
+                        *last_ref = *object_ref;
+                        *alive = exists;
+                    })
+                    .or_insert((existed, *object_ref, exists));
+            }
+        }
+        let mut diff = Self::default();
+        for (existed, object_ref, exists) in objects.into_values() {
+            match (existed, exists) {
+                (false, true) => diff.created.push(object_ref),
+                (true, true) => diff.mutated.push(object_ref),
+                (true, false) => diff.deleted.push(object_ref),
+                (false, false) => (),
+            }
+        }
+        diff
+    }
+}
+
+/// A certified checkpoint with the certificates and effects of its transactions, in the order
+/// of its contents: everything needed to process the checkpoint without asking a node for more.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointData {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    pub transactions: Vec<CheckpointTransaction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointTransaction {
+    pub certificate: CertifiedTransaction,
+    pub effects: TransactionEffects,
+}
+
+impl CheckpointData {
+    pub fn sequence_number(&self) -> CheckpointSequenceNumber {
+        self.checkpoint.summary.sequence_number
+    }
+
+    /// Checks that the checkpoint is certified by `committee`, and that the transactions are the
+    /// ones of its contents.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.checkpoint.verify(committee, Some(&self.contents))?;
+        fp_ensure!(
+            self.transactions.len() == self.contents.size(),
+            SuiError::from("Checkpoint data doesn't have a transaction per digest of its contents")
+        );
+        for (digests, transaction) in self.contents.iter().zip(&self.transactions) {
+            fp_ensure!(
+                *transaction.certificate.digest() == digests.transaction
+                    && transaction.effects.digest() == digests.effects,
+                SuiError::from("Checkpoint data transactions don't match its contents")
+            );
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CheckpointProposalSummary {
     pub sequence_number: CheckpointSequenceNumber,
@@ -646,6 +742,10 @@ mod tests {
     use rand::SeedableRng;
 
     use super::*;
+    use crate::base_types::{ObjectDigest, SequenceNumber, TransactionDigest};
+    use crate::gas::GasCostSummary;
+    use crate::messages::ExecutionStatus;
+    use crate::object::Owner;
     use crate::utils::make_committee_key;
 
     // TODO use the file name as a seed
@@ -772,4 +872,87 @@ mod tests {
         let fragment2 = proposal1.fragment_with(&proposal3);
         assert!(fragment2.verify(&committee).is_err());
     }
+
+    fn object_ref(id: ObjectID, version: u64) -> ObjectRef {
+        (
+            id,
+            SequenceNumber::from_u64(version),
+            ObjectDigest::new([0; 32]),
+        )
+    }
+
+    fn effects(
+        created: &[ObjectRef],
+        mutated: &[ObjectRef],
+        deleted: &[ObjectRef],
+        wrapped: &[ObjectRef],
+        unwrapped: &[ObjectRef],
+    ) -> TransactionEffects {
+        let owned = |refs: &[ObjectRef]| {
+            refs.iter()
+                .map(|object_ref| (*object_ref, Owner::Immutable))
+                .collect()
+        };
+        TransactionEffects {
+            status: ExecutionStatus::Success,
+            gas_used: GasCostSummary {
+                computation_cost: 0,
+                storage_cost: 0,
+                storage_rebate: 0,
+                event_storage_cost: 0,
+                effects_storage_cost: 0,
+            },
+            shared_objects: Vec::new(),
+            transaction_digest: TransactionDigest::random(),
+            created: owned(created),
+            mutated: owned(mutated),
+            unwrapped: owned(unwrapped),
+            deleted: deleted.to_vec(),
+            wrapped: wrapped.to_vec(),
+            gas_object: (mutated[0], Owner::Immutable),
+            events: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_state_diff() {
+        let mut ids: Vec<ObjectID> = (0..7).map(|_| ObjectID::random()).collect();
+        ids.sort();
+        let [gas, created, mutated, transient, deleted, rewrapped, unwrapped]: [ObjectID; 7] =
+            ids.try_into().unwrap();
+
+        let first = effects(
+            &[object_ref(created, 1), object_ref(transient, 1)],
+            &[
+                object_ref(gas, 1),
+                object_ref(mutated, 1),
+                object_ref(deleted, 1),
+            ],
+            &[],
+            &[object_ref(rewrapped, 1)],
+            &[object_ref(unwrapped, 1)],
+        );
+        let second = effects(
+            &[],
+            &[object_ref(gas, 2), object_ref(created, 2)],
+            &[object_ref(transient, 2), object_ref(deleted, 2)],
+            &[],
+            &[object_ref(rewrapped, 2)],
+        );
+        let diff = CheckpointStateDiff::from_effects([&first, &second]);
+
+        assert_eq!(
+            diff,
+            CheckpointStateDiff {
+                created: vec![object_ref(created, 2), object_ref(unwrapped, 1)],
+                mutated: vec![
+                    object_ref(gas, 2),
+                    object_ref(mutated, 1),
+                    object_ref(rewrapped, 2)
+                ],
+                deleted: vec![object_ref(deleted, 2)],
+            }
+        );
+    }
 }
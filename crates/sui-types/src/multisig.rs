@@ -0,0 +1,279 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Multisig addresses, owned by a committee of keys of which any `threshold` must sign the
+//! transactions of the address, e.g. so that no single key can move the funds of a treasury.
+//!
+//! The members are keys of the existing schemes, and sign transactions as they would for their
+//! own address. Their signatures are then combined into a `MultiSigSuiSignature`, a `Signature`
+//! which carries the committee, so that validators check it without any state.
+
+use fastcrypto::ed25519::Ed25519PublicKey;
+use fastcrypto::secp256k1::Secp256k1PublicKey;
+use fastcrypto::traits::ToFromBytes;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Bytes};
+
+use crate::base_types::SuiAddress;
+use crate::crypto::{
+    PublicKey, Signable, Signature, SignatureScheme, SuiSignature, VerificationObligation,
+};
+use crate::error::{SuiError, SuiResult};
+use crate::sui_serde::{Base64, Readable};
+
+#[cfg(test)]
+#[path = "unit_tests/multisig_tests.rs"]
+mod multisig_tests;
+
+/// Maximum number of keys in the committee of a multisig address.
+pub const MAX_SIGNERS: usize = 10;
+
+/// The committee of a multisig address: its member keys, and how many of them must sign.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSigPublicKey {
+    public_keys: Vec<PublicKey>,
+    threshold: u16,
+}
+
+impl MultiSigPublicKey {
+    /// A committee of `public_keys` of which any `threshold` must sign. The order of the keys
+    /// is part of the address.
+    pub fn new(public_keys: Vec<PublicKey>, threshold: u16) -> SuiResult<Self> {
+        let multisig_pk = Self {
+            public_keys,
+            threshold,
+        };
+        multisig_pk.validate()?;
+        Ok(multisig_pk)
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    fn validate(&self) -> SuiResult {
+        if self.public_keys.is_empty() || self.public_keys.len() > MAX_SIGNERS {
+            return Err(invalid_multisig(format!(
+                "A multisig needs between 1 and {} keys, got {}",
+                MAX_SIGNERS,
+                self.public_keys.len()
+            )));
+        }
+        if self.threshold == 0 || self.threshold as usize > self.public_keys.len() {
+            return Err(invalid_multisig(format!(
+                "The threshold must be between 1 and the number of keys {}, got {}",
+                self.public_keys.len(),
+                self.threshold
+            )));
+        }
+        for (i, public_key) in self.public_keys.iter().enumerate() {
+            if self.public_keys[..i].contains(public_key) {
+                return Err(invalid_multisig(format!(
+                    "Key {} is in the multisig twice",
+                    SuiAddress::from(public_key)
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The index in the committee of the key which made `signature`.
+    fn member_index(&self, signature: &Signature) -> SuiResult<usize> {
+        let public_key = signer_public_key(signature)?;
+        self.public_keys
+            .iter()
+            .position(|member| *member == public_key)
+            .ok_or_else(|| {
+                invalid_multisig(format!(
+                    "{} is not a key of the multisig",
+                    SuiAddress::from(&public_key)
+                ))
+            })
+    }
+}
+
+/// The signatures of members of a multisig committee, which must reach its threshold.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSig {
+    /// At most one signature per member, in the order of the committee.
+    signatures: Vec<Signature>,
+    multisig_pk: MultiSigPublicKey,
+}
+
+impl MultiSig {
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+
+    pub fn multisig_pk(&self) -> &MultiSigPublicKey {
+        &self.multisig_pk
+    }
+}
+
+/// A `MultiSig` as a transaction signature: the flag of the multisig scheme, followed by the BCS
+/// bytes of the `MultiSig`.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+pub struct MultiSigSuiSignature(
+    #[schemars(with = "Base64")]
+    #[serde_as(as = "Readable<Base64, Bytes>")]
+    Vec<u8>,
+);
+
+impl MultiSigSuiSignature {
+    /// Combines the partial signatures of members of `multisig_pk`, each made as for the address
+    /// of the member, into the signature of the multisig address. Fails unless there are at least
+    /// threshold signatures of distinct members, the signatures themselves are only checked when
+    /// verifying the result.
+    pub fn combine(multisig_pk: MultiSigPublicKey, signatures: Vec<Signature>) -> SuiResult<Self> {
+        multisig_pk.validate()?;
+        let mut indexed = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            let index = multisig_pk.member_index(&signature)?;
+            if indexed.iter().any(|(i, _)| *i == index) {
+                return Err(invalid_multisig(format!(
+                    "Key {} signed twice",
+                    SuiAddress::from(&multisig_pk.public_keys[index])
+                )));
+            }
+            indexed.push((index, signature));
+        }
+        if indexed.len() < multisig_pk.threshold as usize {
+            return Err(invalid_multisig(format!(
+                "{} signatures don't reach the threshold of {}",
+                indexed.len(),
+                multisig_pk.threshold
+            )));
+        }
+        indexed.sort_by_key(|(index, _)| *index);
+        let multisig = MultiSig {
+            signatures: indexed
+                .into_iter()
+                .map(|(_, signature)| signature)
+                .collect(),
+            multisig_pk,
+        };
+        let mut bytes = vec![SignatureScheme::MultiSig.flag()];
+        bytes.extend(bcs::to_bytes(&multisig).map_err(|e| invalid_multisig(e.to_string()))?);
+        Ok(Self(bytes))
+    }
+
+    pub fn multisig(&self) -> SuiResult<MultiSig> {
+        bcs::from_bytes(&self.0[1..]).map_err(|e| invalid_multisig(e.to_string()))
+    }
+}
+
+impl AsRef<[u8]> for MultiSigSuiSignature {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl signature::Signature for MultiSigSuiSignature {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, signature::Error> {
+        if bytes.first() != Some(&SignatureScheme::MultiSig.flag()) {
+            return Err(signature::Error::new());
+        }
+        let signature = Self(bytes.to_vec());
+        signature.multisig().map_err(|_| signature::Error::new())?;
+        Ok(signature)
+    }
+}
+
+impl SuiSignature for MultiSigSuiSignature {
+    /// The BCS bytes of the `MultiSig`.
+    fn signature_bytes(&self) -> &[u8] {
+        &self.0[1..]
+    }
+
+    /// Nothing, the committee is part of the signature.
+    fn public_key_bytes(&self) -> &[u8] {
+        &[]
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::MultiSig
+    }
+
+    fn verify<T>(&self, value: &T, author: SuiAddress) -> SuiResult<()>
+    where
+        T: Signable<Vec<u8>>,
+    {
+        let mut message = Vec::new();
+        value.write(&mut message);
+        self.verify_bytes(&message, author)
+    }
+
+    fn verify_bytes(&self, message: &[u8], author: SuiAddress) -> SuiResult<()> {
+        let multisig = self.multisig()?;
+        let multisig_pk = &multisig.multisig_pk;
+        multisig_pk.validate()?;
+        let received_addr = SuiAddress::from(multisig_pk);
+        if received_addr != author {
+            return Err(SuiError::IncorrectSigner {
+                error: format!("Multisig of {received_addr} can't sign for {author}"),
+            });
+        }
+        let mut signed = vec![false; multisig_pk.public_keys.len()];
+        for signature in &multisig.signatures {
+            let index = multisig_pk.member_index(signature)?;
+            if signed[index] {
+                return Err(invalid_multisig(format!(
+                    "Key {} signed twice",
+                    SuiAddress::from(&multisig_pk.public_keys[index])
+                )));
+            }
+            signed[index] = true;
+            signature.verify_bytes(message, SuiAddress::from(&multisig_pk.public_keys[index]))?;
+        }
+        let signers = signed.iter().filter(|signed| **signed).count();
+        if signers < multisig_pk.threshold as usize {
+            return Err(invalid_multisig(format!(
+                "{} signatures don't reach the threshold of {}",
+                signers, multisig_pk.threshold
+            )));
+        }
+        Ok(())
+    }
+
+    fn add_to_verification_obligation_or_verify(
+        &self,
+        author: SuiAddress,
+        obligation: &mut VerificationObligation,
+        idx: usize,
+    ) -> SuiResult<()> {
+        // The signatures of the members can't be batched with those of authorities
+        let message = obligation
+            .messages
+            .get(idx)
+            .ok_or(SuiError::InvalidAuthenticator)?;
+        self.verify_bytes(message, author)
+    }
+}
+
+/// The key which made `signature`, which must be of a single key scheme.
+fn signer_public_key(signature: &Signature) -> SuiResult<PublicKey> {
+    let public_key = match signature {
+        Signature::Ed25519SuiSignature(signature) => {
+            Ed25519PublicKey::from_bytes(signature.public_key_bytes()).map(PublicKey::from)
+        }
+        Signature::Secp256k1SuiSignature(signature) => {
+            Secp256k1PublicKey::from_bytes(signature.public_key_bytes()).map(PublicKey::from)
+        }
+        Signature::MultiSigSuiSignature(_) => {
+            return Err(invalid_multisig(
+                "A multisig can't be a member of another".to_string(),
+            ))
+        }
+    };
+    public_key.map_err(|_| SuiError::KeyConversionError("Invalid public key".to_string()))
+}
+
+fn invalid_multisig(error: String) -> SuiError {
+    SuiError::InvalidSignature { error }
+}
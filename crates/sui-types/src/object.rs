@@ -343,6 +343,22 @@ impl Owner {
     pub fn is_shared(&self) -> bool {
         matches!(self, Owner::Shared)
     }
+
+    /// The JSON of the owner, with its address in lowercase hex whether the process displays
+    /// checksummed addresses or not, so that it can be stored and looked up as a key.
+    pub fn to_canonical_json(&self) -> String {
+        match self {
+            Self::AddressOwner(address) => {
+                serde_json::json!({ "AddressOwner": format!("{:#x}", address) })
+            }
+            Self::ObjectOwner(address) => {
+                serde_json::json!({ "ObjectOwner": format!("{:#x}", address) })
+            }
+            Self::Shared => serde_json::json!("Shared"),
+            Self::Immutable => serde_json::json!("Immutable"),
+        }
+        .to_string()
+    }
 }
 
 impl PartialEq<SuiAddress> for Owner {
@@ -0,0 +1,136 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Content-defined chunking of the BCS bytes of objects, so that a new version of a large object
+//! can be transferred as the chunks which changed since a version the receiver already holds.
+//!
+//! Chunk boundaries are cut where a rolling gear hash of the last bytes matches a pattern, rather
+//! than at fixed offsets, so that bytes inserted or removed in an object only change the chunks
+//! around them instead of shifting every chunk after them. The boundaries only depend on the
+//! bytes, so the sender and the receiver of an object agree on them without talking it over:
+//! changing any of the constants below changes the chunks of every object.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail};
+use digest::Digest;
+use serde::{Deserialize, Serialize};
+use sha3::Sha3_256;
+
+#[cfg(test)]
+#[path = "unit_tests/object_chunks_tests.rs"]
+mod object_chunks_tests;
+
+/// No boundary is cut before this many bytes into a chunk.
+pub const MIN_CHUNK_SIZE: usize = 2 * 1024;
+
+/// A boundary is cut after this many bytes into a chunk if none matched before.
+pub const MAX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A boundary is cut where these bits of the hash are all zero, one byte in 8KiB past the
+/// minimum size on average. The high bits of a gear hash depend on the last 64 bytes, the low
+/// ones on the last few only.
+const BOUNDARY_MASK: u64 = 0x1fff << 51;
+
+/// Random values of each byte for the gear hash, from a fixed seed.
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    // splitmix64
+    let mut table = [0; 256];
+    let mut state: u64 = 0x5375_6920_4344_4321;
+    let mut i = 0;
+    while i < table.len() {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ChunkDigest(pub [u8; 32]);
+
+impl ChunkDigest {
+    pub fn new(chunk: &[u8]) -> Self {
+        ChunkDigest(Sha3_256::digest(chunk).into())
+    }
+}
+
+/// One chunk of the bytes of an object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkInfo {
+    pub digest: ChunkDigest,
+    pub length: u32,
+}
+
+/// The length of the first chunk of `data`.
+fn next_boundary(data: &[u8]) -> usize {
+    if data.len() <= MIN_CHUNK_SIZE {
+        return data.len();
+    }
+    let end = std::cmp::min(data.len(), MAX_CHUNK_SIZE);
+    let mut hash: u64 = 0;
+    for (i, byte) in data.iter().enumerate().take(end).skip(MIN_CHUNK_SIZE) {
+        hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+        if hash & BOUNDARY_MASK == 0 {
+            return i + 1;
+        }
+    }
+    end
+}
+
+/// Splits `data` into chunks, with their description.
+pub fn split(data: &[u8]) -> Vec<(ChunkInfo, &[u8])> {
+    let mut chunks = vec![];
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (chunk, next) = rest.split_at(next_boundary(rest));
+        chunks.push((
+            ChunkInfo {
+                digest: ChunkDigest::new(chunk),
+                length: chunk.len() as u32,
+            },
+            chunk,
+        ));
+        rest = next;
+    }
+    chunks
+}
+
+/// Puts back the bytes described by `manifest`. Each chunk is taken from `known` if it's there,
+/// or else from `sent`, which holds the other chunks in the order of the manifest, each once.
+pub fn reassemble(
+    manifest: &[ChunkInfo],
+    sent: Vec<Vec<u8>>,
+    known: &HashMap<ChunkDigest, &[u8]>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(manifest.iter().map(|chunk| chunk.length as usize).sum());
+    let mut sent = sent.into_iter();
+    let mut received: HashMap<ChunkDigest, Vec<u8>> = HashMap::new();
+    for info in manifest {
+        if let Some(chunk) = known.get(&info.digest) {
+            data.extend_from_slice(chunk);
+            continue;
+        }
+        if let Some(chunk) = received.get(&info.digest) {
+            data.extend_from_slice(chunk);
+            continue;
+        }
+        let chunk = sent
+            .next()
+            .ok_or_else(|| anyhow!("Chunk {:?} is neither known nor sent", info.digest))?;
+        if chunk.len() != info.length as usize || ChunkDigest::new(&chunk) != info.digest {
+            bail!("Chunk sent for {:?} doesn't match its digest", info.digest);
+        }
+        data.extend_from_slice(&chunk);
+        received.insert(info.digest, chunk);
+    }
+    if sent.next().is_some() {
+        bail!("More chunks were sent than the manifest has");
+    }
+    Ok(data)
+}
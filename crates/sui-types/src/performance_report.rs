@@ -0,0 +1,70 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::base_types::AuthorityName;
+use crate::committee::{Committee, EpochId};
+use crate::crypto::{
+    AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature, SuiAuthoritySignature,
+};
+use crate::error::SuiResult;
+use serde::{Deserialize, Serialize};
+
+/// A period during which a node was not running, in milliseconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DowntimeWindow {
+    pub start_timestamp_ms: u64,
+    pub end_timestamp_ms: u64,
+}
+
+/// What a node did over an epoch, as seen by the node itself.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochPerformanceReport {
+    pub epoch: EpochId,
+    pub authority: AuthorityName,
+    /// Whether the node was in the committee of the epoch.
+    pub is_validator: bool,
+    /// When the node started and stopped recording the epoch, in milliseconds since the Unix
+    /// epoch. The start is the first activity of the epoch seen after a restart.
+    pub start_timestamp_ms: u64,
+    pub end_timestamp_ms: u64,
+    /// Transactions the node signed, always zero on fullnodes.
+    pub transactions_signed: u64,
+    /// Certificates the node executed, whether submitted to it or synced from others.
+    pub certificates_executed: u64,
+    /// Average time the node took to execute a certificate.
+    pub average_certificate_latency_us: Option<u64>,
+    /// Checkpoints the node signed, always zero on fullnodes.
+    pub checkpoints_signed: u64,
+    pub downtime: Vec<DowntimeWindow>,
+}
+
+/// A performance report signed by the node which made it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedEpochPerformanceReport {
+    pub report: EpochPerformanceReport,
+    pub auth_signature: AuthoritySignInfo,
+}
+
+impl SignedEpochPerformanceReport {
+    pub fn new(
+        report: EpochPerformanceReport,
+        signer: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let signature = AuthoritySignature::new(&report, signer);
+        let auth_signature = AuthoritySignInfo {
+            epoch: report.epoch,
+            authority: report.authority,
+            signature,
+        };
+        Self {
+            report,
+            auth_signature,
+        }
+    }
+
+    /// Checks that the report was signed by a member of `committee`, which must be the
+    /// committee of the epoch of the report. Reports of fullnodes can't be checked this way.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.auth_signature.verify(&self.report, committee)
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Snapshots of the live objects of a node as of a certified checkpoint, which a new node
+//! installs instead of executing every transaction up to the checkpoint, then syncs from there.
+//!
+//! Checkpoints don't commit to the state, so the set of objects is committed to by a digest in a
+//! summary signed by the node exporting the snapshot. A snapshot verifies if its checkpoint is
+//! certified by the committee, and the summary is signed by a validator of the committee and
+//! matches the checkpoint and the objects.
+
+use crate::base_types::{AuthorityName, ObjectRef};
+use crate::committee::{Committee, EpochId};
+use crate::crypto::{
+    AuthoritySignInfo, AuthoritySignInfoTrait, AuthoritySignature, SuiAuthoritySignature,
+};
+use crate::error::{SuiError, SuiResult};
+use crate::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointDigest, CheckpointSequenceNumber,
+};
+use crate::object::Object;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+pub type StateDigest = [u8; 32];
+
+/// The digest of the set of objects with the references `object_refs`, in increasing order of
+/// object ID.
+pub fn state_digest<'a>(object_refs: impl IntoIterator<Item = &'a ObjectRef>) -> StateDigest {
+    let mut hasher = Sha3_256::default();
+    for object_ref in object_refs {
+        hasher.update(bcs::to_bytes(object_ref).expect("Object references serialize"));
+    }
+    hasher.finalize().into()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshotSummary {
+    pub epoch: EpochId,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub checkpoint_digest: CheckpointDigest,
+    pub object_count: u64,
+    pub state_digest: StateDigest,
+}
+
+/// A snapshot summary signed by the node which exported the snapshot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedStateSnapshotSummary {
+    pub summary: StateSnapshotSummary,
+    pub auth_signature: AuthoritySignInfo,
+}
+
+impl SignedStateSnapshotSummary {
+    pub fn new(
+        summary: StateSnapshotSummary,
+        authority: AuthorityName,
+        signer: &dyn signature::Signer<AuthoritySignature>,
+    ) -> Self {
+        let signature = AuthoritySignature::new(&summary, signer);
+        let auth_signature = AuthoritySignInfo {
+            epoch: summary.epoch,
+            authority,
+            signature,
+        };
+        Self {
+            summary,
+            auth_signature,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    pub summary: SignedStateSnapshotSummary,
+    /// The live objects as of the checkpoint, in increasing order of object ID.
+    pub objects: Vec<Object>,
+}
+
+impl StateSnapshot {
+    /// Checks that the checkpoint of the snapshot is certified by `committee`, and that the
+    /// objects are the ones of the summary, signed by a validator of `committee`.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.checkpoint.verify(committee, Some(&self.contents))?;
+        let summary = &self.summary.summary;
+        self.summary.auth_signature.verify(summary, committee)?;
+        if summary.epoch != self.checkpoint.summary.epoch
+            || summary.checkpoint != self.checkpoint.summary.sequence_number
+            || summary.checkpoint_digest != self.checkpoint.summary.digest()
+        {
+            return Err(SuiError::GenericAuthorityError {
+                error: "State snapshot summary doesn't match its checkpoint".to_string(),
+            });
+        }
+        let object_refs: Vec<_> = self
+            .objects
+            .iter()
+            .map(|object| object.compute_object_reference())
+            .collect();
+        if !object_refs.windows(2).all(|pair| pair[0].0 < pair[1].0)
+            || object_refs.len() as u64 != summary.object_count
+            || state_digest(&object_refs) != summary.state_digest
+        {
+            return Err(SuiError::GenericAuthorityError {
+                error: "State snapshot objects don't match its summary".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/state_snapshot_tests.rs"]
+mod state_snapshot_tests;
@@ -0,0 +1,144 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Amounts of SUI, counted in MIST, the smallest unit of SUI: 1 SUI is 10^9 MIST.
+//!
+//! Amounts are converted and formatted with integer arithmetic only, so that a decimal amount of
+//! SUI is always the exact amount of MIST it stands for, e.g. "0.000000001" SUI is 1 MIST.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The number of decimals of an amount of SUI in MIST.
+pub const SUI_DECIMALS: u32 = 9;
+
+/// The amount of MIST in 1 SUI.
+pub const MIST_PER_SUI: u64 = 10u64.pow(SUI_DECIMALS);
+
+/// An amount of SUI, in MIST.
+///
+/// It is serialized as its number of MIST, and displayed as a decimal number of SUI, without
+/// trailing zeros, e.g. `1.5` for 1_500_000_000 MIST. The alternate format `{:#}` shows all of the
+/// decimals instead, e.g. `1.500000000`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(transparent)]
+pub struct SuiAmount(u64);
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SuiAmountError {
+    #[error("Invalid amount of SUI: {0:?}")]
+    Invalid(String),
+    #[error("Amount of SUI {0:?} has more than 9 decimals")]
+    TooManyDecimals(String),
+    #[error("Amount of SUI {0:?} is more than the maximum amount of MIST")]
+    Overflow(String),
+}
+
+impl SuiAmount {
+    pub const ZERO: SuiAmount = SuiAmount(0);
+
+    pub const fn from_mist(mist: u64) -> Self {
+        Self(mist)
+    }
+
+    /// The amount of `sui` whole SUI, if it is at most `u64::MAX` MIST.
+    pub fn from_sui(sui: u64) -> Option<Self> {
+        sui.checked_mul(MIST_PER_SUI).map(Self)
+    }
+
+    pub const fn mist(&self) -> u64 {
+        self.0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+}
+
+impl From<u64> for SuiAmount {
+    fn from(mist: u64) -> Self {
+        Self(mist)
+    }
+}
+
+impl From<SuiAmount> for u64 {
+    fn from(amount: SuiAmount) -> Self {
+        amount.0
+    }
+}
+
+impl Display for SuiAmount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let sui = self.0 / MIST_PER_SUI;
+        let fraction = format!(
+            "{:0width$}",
+            self.0 % MIST_PER_SUI,
+            width = SUI_DECIMALS as usize
+        );
+        let fraction = if f.alternate() {
+            fraction.as_str()
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        if fraction.is_empty() {
+            f.pad(&sui.to_string())
+        } else {
+            f.pad(&format!("{sui}.{fraction}"))
+        }
+    }
+}
+
+/// Parses a decimal amount of SUI, e.g. `1.5` or `0.000000001 SUI`, into MIST exactly.
+impl FromStr for SuiAmount {
+    type Err = SuiAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let amount = s.trim();
+        let amount = amount.strip_suffix("SUI").unwrap_or(amount).trim_end();
+        let (sui, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if (sui.is_empty() && fraction.is_empty()) || !is_digits(sui) || !is_digits(fraction) {
+            return Err(SuiAmountError::Invalid(s.to_string()));
+        }
+        if fraction.len() > SUI_DECIMALS as usize {
+            return Err(SuiAmountError::TooManyDecimals(s.to_string()));
+        }
+        let overflow = || SuiAmountError::Overflow(s.to_string());
+        let sui = if sui.is_empty() {
+            0
+        } else {
+            sui.parse::<u64>().map_err(|_| overflow())?
+        };
+        let fraction = format!("{:0<width$}", fraction, width = SUI_DECIMALS as usize)
+            .parse::<u64>()
+            .map_err(|_| SuiAmountError::Invalid(s.to_string()))?;
+        sui.checked_mul(MIST_PER_SUI)
+            .and_then(|mist| mist.checked_add(fraction))
+            .map(Self)
+            .ok_or_else(overflow)
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/sui_amount_tests.rs"]
+mod sui_amount_tests;
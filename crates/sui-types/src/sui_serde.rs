@@ -16,7 +16,10 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_with::{Bytes, DeserializeAs, SerializeAs};
 
-use crate::base_types::{decode_bytes_hex, encode_bytes_hex};
+use crate::base_types::{
+    checksummed_addresses, decode_bytes_hex, decode_checksummed_hex, encode_bytes_hex,
+    encode_checksummed_hex, is_valid_checksum,
+};
 use crate::crypto::{AggregateAuthoritySignature, AuthoritySignature, KeypairTraits};
 
 #[inline]
@@ -129,12 +132,18 @@ where
     {
         if deserializer.is_human_readable() {
             let s = String::deserialize(deserializer)?;
-            if s.starts_with("0x") {
+            let address = if s.starts_with("0x") {
                 AccountAddress::from_hex_literal(&s)
             } else {
                 AccountAddress::from_hex(&s)
             }
-            .map_err(to_custom_error::<'de, D, _>)
+            .map_err(to_custom_error::<'de, D, _>)?;
+            if !is_valid_checksum(&s, address.as_ref()) {
+                return Err(D::Error::custom(format!(
+                    "Invalid checksum of {s}, it may contain a typo"
+                )));
+            }
+            Ok(address)
         } else {
             R::deserialize_as(deserializer)
         }
@@ -148,6 +157,11 @@ pub trait Encoding {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct Hex(String);
+
+/// The hex of addresses and object IDs, checksummed when the process displays checksummed
+/// addresses, see [`set_checksummed_addresses`](crate::base_types::set_checksummed_addresses).
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct AddressHex(String);
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, JsonSchema)]
 #[serde(try_from = "String")]
 pub struct Base64(String);
@@ -212,6 +226,20 @@ impl Encoding for Hex {
         format!("0x{}", encode_bytes_hex(&data).to_lowercase())
     }
 }
+impl Encoding for AddressHex {
+    fn decode(s: &str) -> Result<Vec<u8>, anyhow::Error> {
+        decode_checksummed_hex(s)
+    }
+
+    fn encode<T: AsRef<[u8]>>(data: T) -> String {
+        if checksummed_addresses() {
+            encode_checksummed_hex(data)
+        } else {
+            Hex::encode(data)
+        }
+    }
+}
+
 impl Encoding for Base64 {
     fn decode(s: &str) -> Result<Vec<u8>, anyhow::Error> {
         base64ct::Base64::decode_vec(s).map_err(|e| anyhow!(e))
@@ -266,6 +294,28 @@ where
     }
 }
 
+impl<'de> DeserializeAs<'de, Vec<u8>> for AddressHex {
+    fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::decode(&s).map_err(to_custom_error::<'de, D, _>)
+    }
+}
+
+impl<T> SerializeAs<T> for AddressHex
+where
+    T: AsRef<[u8]>,
+{
+    fn serialize_as<S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Self::encode(value).serialize(serializer)
+    }
+}
+
 pub struct KeyPairBase64 {}
 
 impl<T> SerializeAs<T> for KeyPairBase64
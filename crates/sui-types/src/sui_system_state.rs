@@ -15,6 +15,7 @@ use crate::{
 const SUI_SYSTEM_STATE_STRUCT_NAME: &IdentStr = ident_str!("SuiSystemState");
 pub const SUI_SYSTEM_MODULE_NAME: &IdentStr = ident_str!("sui_system");
 pub const ADVANCE_EPOCH_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch");
+pub const ADVANCE_EPOCH_SAFE_MODE_FUNCTION_NAME: &IdentStr = ident_str!("advance_epoch_safe_mode");
 
 /// Rust version of the Move sui::sui_system::SystemParameters type
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
@@ -38,10 +39,44 @@ pub struct ValidatorMetadata {
     pub network_pubkey_bytes: Vec<u8>,
     pub proof_of_possession_bytes: Vec<u8>,
     pub name: Vec<u8>,
+    pub description: Vec<u8>,
+    pub image_url: Vec<u8>,
     pub net_address: Vec<u8>,
     pub next_epoch_stake: u64,
     pub next_epoch_delegation: u64,
     pub next_epoch_gas_price: u64,
+    pub next_epoch_net_address: MoveOption<Vec<u8>>,
+    pub next_epoch_network_pubkey_bytes: MoveOption<Vec<u8>>,
+}
+
+impl ValidatorMetadata {
+    /// The network address the validator serves at in the next epoch: the one it requested
+    /// during the current epoch if any, its current address otherwise.
+    pub fn next_epoch_net_address(&self) -> &[u8] {
+        self.next_epoch_net_address
+            .vec
+            .first()
+            .unwrap_or(&self.net_address)
+    }
+}
+
+/// Rust version of the Move sui::vec_map::VecMap type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct VecMap<K, V> {
+    pub contents: Vec<Entry<K, V>>,
+}
+
+/// Rust version of the Move sui::vec_map::Entry type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct Entry<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+/// Rust version of the Move sui::vec_set::VecSet type
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq)]
+pub struct VecSet<T> {
+    pub contents: Vec<T>,
 }
 
 /// Rust version of the Move sui::validator::Validator type
@@ -96,6 +131,12 @@ pub struct SuiSystemState {
     pub storage_fund: Balance,
     pub parameters: SystemParameters,
     pub reference_gas_price: u64,
+    pub validator_report_records: VecMap<AccountAddress, VecSet<AccountAddress>>,
+    pub publisher_allowlist: MoveOption<VecSet<AccountAddress>>,
+    pub publisher_allowlist_votes: VecMap<AccountAddress, VecSet<AccountAddress>>,
+    pub safe_mode: bool,
+    pub safe_mode_storage_charge: u64,
+    pub safe_mode_computation_charge: u64,
     // TODO: Use getters instead of all pub.
 }
 
@@ -108,4 +149,35 @@ impl SuiSystemState {
             type_params: vec![],
         }
     }
+
+    /// The validators which reported `validator` during the current epoch.
+    pub fn validator_reporters(&self, validator: &AccountAddress) -> &[AccountAddress] {
+        self.validator_report_records
+            .contents
+            .iter()
+            .find(|entry| &entry.key == validator)
+            .map_or(&[], |entry| &entry.value.contents)
+    }
+
+    /// The total stake, including delegation, of the active validators which reported
+    /// `validator` during the current epoch. The validator loses its rewards for the epoch if
+    /// this reaches `quorum_stake_threshold`.
+    pub fn validator_reported_stake(&self, validator: &AccountAddress) -> u64 {
+        let reporters = self.validator_reporters(validator);
+        self.validators
+            .active_validators
+            .iter()
+            .filter(|v| reporters.contains(&v.metadata.sui_address))
+            .map(|v| v.stake_amount + v.delegation_staking_pool.epoch_starting_sui_balance)
+            .sum()
+    }
+
+    /// Whether `publisher` may publish packages, always true unless the network was created
+    /// with a publisher allowlist.
+    pub fn is_allowed_publisher(&self, publisher: &AccountAddress) -> bool {
+        self.publisher_allowlist
+            .vec
+            .first()
+            .map_or(true, |allowlist| allowlist.contents.contains(publisher))
+    }
 }
@@ -0,0 +1,216 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A builder composing transfers, Move calls, publishing and coin splits and merges into a
+//! single [`TransactionData`], instead of assembling its kinds by hand. A single step is built
+//! as a single transaction, and several as a batch, which can only hold transfers of objects and
+//! Move calls.
+
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+
+use crate::base_types::{ObjectID, ObjectRef, SuiAddress};
+use crate::coin;
+use crate::error::{SuiError, SuiResult};
+use crate::messages::{
+    CallArg, InputObjectKind, MoveCall, MoveModulePublish, ObjectArg, SingleTransactionKind,
+    TransactionData, TransactionKind, TransferObject, TransferSui,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_builder_tests.rs"]
+mod transaction_builder_tests;
+
+/// The BCS serialization of `value` as a pure argument of a Move call.
+pub fn pure<T: Serialize>(value: &T) -> SuiResult<CallArg> {
+    bcs::to_bytes(value)
+        .map(CallArg::Pure)
+        .map_err(|e| SuiError::InvalidTransactionBuilder {
+            error: format!("Failed to serialize a pure argument: {e}"),
+        })
+}
+
+pub struct TransactionBuilder {
+    sender: SuiAddress,
+    gas_budget: u64,
+    gas_price: Option<u64>,
+    gas_sponsor: Option<SuiAddress>,
+    transactions: Vec<SingleTransactionKind>,
+}
+
+impl TransactionBuilder {
+    pub fn new(sender: SuiAddress, gas_budget: u64) -> Self {
+        Self {
+            sender,
+            gas_budget,
+            gas_price: None,
+            gas_sponsor: None,
+            transactions: vec![],
+        }
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    /// Has the gas paid with an object of `sponsor`, who signs the transaction too.
+    pub fn with_gas_sponsor(mut self, sponsor: SuiAddress) -> Self {
+        self.gas_sponsor = Some(sponsor);
+        self
+    }
+
+    pub fn sender(&self) -> SuiAddress {
+        self.sender
+    }
+
+    /// The address the gas object must be owned by: the sponsor if any, the sender otherwise.
+    pub fn gas_owner(&self) -> SuiAddress {
+        self.gas_sponsor.unwrap_or(self.sender)
+    }
+
+    pub fn gas_budget(&self) -> u64 {
+        self.gas_budget
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    /// Appends a step built by other means.
+    pub fn transaction(&mut self, transaction: SingleTransactionKind) -> &mut Self {
+        self.transactions.push(transaction);
+        self
+    }
+
+    pub fn transfer_object(&mut self, recipient: SuiAddress, object_ref: ObjectRef) -> &mut Self {
+        self.transaction(SingleTransactionKind::TransferObject(TransferObject {
+            recipient,
+            object_ref,
+        }))
+    }
+
+    /// Transfers `amount` of the gas coin to `recipient`, or all of it when `None`. It can't be
+    /// batched.
+    pub fn transfer_sui(&mut self, recipient: SuiAddress, amount: Option<u64>) -> &mut Self {
+        self.transaction(SingleTransactionKind::TransferSui(TransferSui {
+            recipient,
+            amount,
+        }))
+    }
+
+    pub fn move_call(
+        &mut self,
+        package: ObjectRef,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<CallArg>,
+    ) -> &mut Self {
+        self.transaction(SingleTransactionKind::Call(MoveCall {
+            package,
+            module,
+            function,
+            type_arguments,
+            arguments,
+        }))
+    }
+
+    /// Publishes the compiled `modules` of a package. It can't be batched.
+    pub fn publish(&mut self, modules: Vec<Vec<u8>>) -> &mut Self {
+        self.transaction(SingleTransactionKind::Publish(MoveModulePublish {
+            modules,
+        }))
+    }
+
+    /// Splits coins of `amounts` off `coin`, of type `0x2::coin::Coin<coin_type>`, with the
+    /// framework package of reference `framework`.
+    pub fn split_coin(
+        &mut self,
+        framework: ObjectRef,
+        coin: ObjectRef,
+        coin_type: TypeTag,
+        amounts: Vec<u64>,
+    ) -> SuiResult<&mut Self> {
+        let amounts = pure(&amounts)?;
+        Ok(self.move_call(
+            framework,
+            coin::COIN_MODULE_NAME.to_owned(),
+            coin::COIN_SPLIT_VEC_FUNC_NAME.to_owned(),
+            vec![coin_type],
+            vec![CallArg::Object(ObjectArg::ImmOrOwnedObject(coin)), amounts],
+        ))
+    }
+
+    /// Merges `coin_to_merge` into `primary_coin`, both of type `0x2::coin::Coin<coin_type>`, with
+    /// the framework package of reference `framework`.
+    pub fn merge_coins(
+        &mut self,
+        framework: ObjectRef,
+        primary_coin: ObjectRef,
+        coin_to_merge: ObjectRef,
+        coin_type: TypeTag,
+    ) -> &mut Self {
+        self.move_call(
+            framework,
+            coin::COIN_MODULE_NAME.to_owned(),
+            coin::COIN_JOIN_FUNC_NAME.to_owned(),
+            vec![coin_type],
+            vec![
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(primary_coin)),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(coin_to_merge)),
+            ],
+        )
+    }
+
+    /// The IDs of the owned objects the steps take as input, which can't pay for the gas.
+    pub fn input_objects(&self) -> SuiResult<Vec<ObjectID>> {
+        let mut ids = vec![];
+        for transaction in &self.transactions {
+            for input in transaction.input_objects()? {
+                if let InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) = input {
+                    ids.push(id);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// The transaction of the steps so far, paying for the gas with `gas_payment`.
+    pub fn build(self, gas_payment: ObjectRef) -> SuiResult<TransactionData> {
+        if self.input_objects()?.contains(&gas_payment.0) {
+            return Err(SuiError::InvalidTransactionBuilder {
+                error: format!(
+                    "Gas object {} is an input of the transaction",
+                    gas_payment.0
+                ),
+            });
+        }
+        let mut transactions = self.transactions;
+        let kind = match transactions.len() {
+            0 => {
+                return Err(SuiError::InvalidTransactionBuilder {
+                    error: "Transaction has no step".to_string(),
+                })
+            }
+            1 => TransactionKind::Single(transactions.remove(0)),
+            _ => TransactionKind::Batch(transactions),
+        };
+        let mut data = match self.gas_price {
+            Some(gas_price) => TransactionData::new_with_gas_price(
+                kind,
+                self.sender,
+                gas_payment,
+                self.gas_budget,
+                gas_price,
+            ),
+            None => TransactionData::new(kind, self.sender, gas_payment, self.gas_budget),
+        };
+        if let Some(sponsor) = self.gas_sponsor {
+            data = data.with_gas_sponsor(sponsor);
+        }
+        data.validity_check()?;
+        Ok(data)
+    }
+}
@@ -377,3 +377,29 @@ fn test_address_backwards_compatibility() {
                also require updates if they use fixed values generated by the old algorithm."
     );
 }
+
+#[test]
+fn test_checksummed_address() {
+    let address = SuiAddress::from_str("0xa9f4c1e2b3d5f60718293a4b5c6d7e8f90a1b2c3").unwrap();
+    let checksummed = encode_checksummed_hex(address);
+    assert_eq!(checksummed.to_lowercase(), format!("{:#x}", address));
+    assert_eq!(SuiAddress::from_str(&checksummed).unwrap(), address);
+    assert_eq!(ObjectID::from_str(&checksummed).unwrap(), address.into());
+
+    // Changing the case of a single letter is detected as a typo
+    let position = checksummed[2..]
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap()
+        + 2;
+    let mut mistyped = checksummed.clone().into_bytes();
+    mistyped[position] ^= 0x20;
+    let mistyped = String::from_utf8(mistyped).unwrap();
+    assert!(SuiAddress::from_str(&mistyped).is_err());
+    assert!(matches!(
+        ObjectID::from_str(&mistyped),
+        Err(ObjectIDParseError::InvalidChecksum)
+    ));
+
+    // Hex in a single case has no checksum to check
+    assert!(SuiAddress::from_str(&checksummed.to_uppercase()[2..]).is_ok());
+}
@@ -32,6 +32,7 @@ fn test_move_event_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 0,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: Some(json!(BTreeMap::from([("balance", 10000)]))),
     };
@@ -88,6 +89,7 @@ fn test_transfer_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 1,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
@@ -122,6 +124,7 @@ fn test_publish_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 0,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
@@ -159,6 +162,7 @@ fn test_delete_object_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 0,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
@@ -200,6 +204,7 @@ fn test_new_object_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 0,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
@@ -232,6 +237,7 @@ fn test_epoch_change_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 1,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
@@ -247,6 +253,7 @@ fn test_checkpoint_filter() {
         timestamp: 0,
         tx_digest: Some(TransactionDigest::random()),
         seq_num: 1,
+        event_num: 0,
         event: move_event,
         move_struct_json_value: None,
     };
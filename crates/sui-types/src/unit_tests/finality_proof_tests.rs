@@ -0,0 +1,149 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use fastcrypto::traits::KeyPair;
+
+use super::*;
+use crate::base_types::{dbg_addr, ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
+use crate::crypto::{get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes};
+use crate::gas::GasCostSummary;
+use crate::messages::{
+    ExecutionStatus, GenesisEpoch, SignedTransaction, Transaction, TransactionData,
+};
+use crate::messages_checkpoint::SignedCheckpointSummary;
+use crate::object::Owner;
+
+fn random_object_ref() -> ObjectRef {
+    (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::new([0; 32]),
+    )
+}
+
+fn new_committee() -> (Committee, Vec<AuthorityKeyPair>) {
+    let key_pairs: Vec<AuthorityKeyPair> = (0..2).map(|_| get_key_pair().1).collect();
+    let authorities: BTreeMap<AuthorityPublicKeyBytes, u64> = key_pairs
+        .iter()
+        .map(|key_pair| (key_pair.public().into(), 1))
+        .collect();
+    (Committee::new(0, authorities).unwrap(), key_pairs)
+}
+
+/// A proof of a transaction certified by `key_pairs` in the genesis epoch, and included in the
+/// first checkpoint.
+fn new_proof(committee: &Committee, key_pairs: &[AuthorityKeyPair]) -> FinalityProof {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let transaction = Transaction::from_data(
+        TransactionData::new_transfer(
+            dbg_addr(1),
+            random_object_ref(),
+            sender,
+            random_object_ref(),
+            10000,
+        ),
+        &sender_key,
+    );
+    let signatures = key_pairs
+        .iter()
+        .map(|key_pair| {
+            let signed =
+                SignedTransaction::new(0, transaction.clone(), key_pair.public().into(), key_pair);
+            (
+                signed.auth_sign_info.authority,
+                signed.auth_sign_info.signature,
+            )
+        })
+        .collect();
+    let certificate =
+        CertifiedTransaction::new_with_signatures(transaction, signatures, committee).unwrap();
+
+    let effects = TransactionEffects {
+        status: ExecutionStatus::Success,
+        gas_used: GasCostSummary {
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            event_storage_cost: 0,
+            effects_storage_cost: 0,
+        },
+        shared_objects: Vec::new(),
+        transaction_digest: *certificate.digest(),
+        created: Vec::new(),
+        mutated: Vec::new(),
+        unwrapped: Vec::new(),
+        deleted: Vec::new(),
+        wrapped: Vec::new(),
+        gas_object: (random_object_ref(), Owner::AddressOwner(sender)),
+        events: Vec::new(),
+        dependencies: Vec::new(),
+    };
+
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::new(
+            *certificate.digest(),
+            effects.digest(),
+        )]
+        .into_iter(),
+    );
+    let signed_checkpoints = key_pairs
+        .iter()
+        .map(|key_pair| {
+            SignedCheckpointSummary::new(0, 0, key_pair.public().into(), key_pair, &contents, None)
+        })
+        .collect();
+    let checkpoint = CertifiedCheckpointSummary::aggregate(signed_checkpoints, committee).unwrap();
+
+    FinalityProof::new(
+        certificate,
+        effects,
+        Some(CheckpointInclusionProof {
+            checkpoint,
+            contents,
+        }),
+        vec![AuthenticatedEpoch::Genesis(GenesisEpoch::new(
+            committee.clone(),
+        ))],
+    )
+}
+
+#[test]
+fn test_finality_proof_verifies() {
+    let (committee, key_pairs) = new_committee();
+    let proof = new_proof(&committee, &key_pairs);
+    proof.verify(&committee).unwrap();
+
+    // The proof survives a round trip through its serialization
+    let proof = FinalityProof::from_bytes(&proof.to_bytes()).unwrap();
+    proof.verify(&committee).unwrap();
+}
+
+#[test]
+fn test_finality_proof_rejects_other_genesis() {
+    let (committee, key_pairs) = new_committee();
+    let proof = new_proof(&committee, &key_pairs);
+    let (other_committee, _) = new_committee();
+    assert!(proof.verify(&other_committee).is_err());
+}
+
+#[test]
+fn test_finality_proof_rejects_tampered_effects() {
+    let (committee, key_pairs) = new_committee();
+    let mut proof = new_proof(&committee, &key_pairs);
+    proof.effects.gas_used.computation_cost += 1;
+    assert!(proof.verify(&committee).is_err());
+
+    // Without the checkpoint, nothing binds the effects to the certificate
+    proof.checkpoint = None;
+    proof.verify(&committee).unwrap();
+}
+
+#[test]
+fn test_finality_proof_rejects_unknown_version() {
+    let (committee, key_pairs) = new_committee();
+    let mut bytes = new_proof(&committee, &key_pairs).to_bytes();
+    bytes[0] = FINALITY_PROOF_VERSION + 1;
+    assert!(FinalityProof::from_bytes(&bytes).is_err());
+}
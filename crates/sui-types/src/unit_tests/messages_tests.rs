@@ -414,6 +414,8 @@ fn test_digest_caching() {
             computation_cost: 0,
             storage_cost: 0,
             storage_rebate: 0,
+            event_storage_cost: 0,
+            effects_storage_cost: 0,
         },
         shared_objects: Vec::new(),
         transaction_digest: initial_digest,
@@ -565,6 +567,8 @@ fn test_user_signature_committed_in_checkpoints() {
             computation_cost: 0,
             storage_cost: 0,
             storage_rebate: 0,
+            event_storage_cost: 0,
+            effects_storage_cost: 0,
         },
         shared_objects: Vec::new(),
         transaction_digest: *tx_digest_a,
@@ -696,3 +700,42 @@ fn verify_sender_signature_correctly_with_flag() {
         .verify(&transaction.signed_data, &committee)
         .is_err());
 }
+
+#[test]
+fn test_sponsored_transaction_signatures() {
+    let (sender, sender_kp): (_, AccountKeyPair) = get_key_pair();
+    let (sponsor, sponsor_kp): (_, AccountKeyPair) = get_key_pair();
+    let gas = random_object_ref();
+    let data = TransactionData::new_transfer(sponsor, random_object_ref(), sender, gas, 10000)
+        .with_gas_sponsor(sponsor);
+    assert_eq!(data.gas_owner(), sponsor);
+    assert!(data.validity_check().is_ok());
+
+    // Both the sender and the sponsor sign
+    let transaction = Transaction::from_data(data.clone(), &sender_kp);
+    assert!(transaction.verify().is_err());
+    let sponsored = transaction.with_sponsor_signature(Signature::new(&data, &sponsor_kp));
+    assert!(sponsored.verify().is_ok());
+
+    // The sponsor can't sign in place of the sender
+    let transaction = Transaction::new_sponsored(
+        data.clone(),
+        Signature::new(&data, &sponsor_kp),
+        Signature::new(&data, &sponsor_kp),
+    );
+    assert!(transaction.verify().is_err());
+
+    // Nor sign a transaction it doesn't sponsor
+    let data = TransactionData::new_transfer(sponsor, random_object_ref(), sender, gas, 10000);
+    let transaction = Transaction::from_data(data.clone(), &sender_kp)
+        .with_sponsor_signature(Signature::new(&data, &sponsor_kp));
+    assert!(transaction.verify().is_err());
+
+    // The gas object of the sponsor can only pay for gas
+    let data =
+        TransactionData::new_transfer(sponsor, gas, sender, gas, 10000).with_gas_sponsor(sponsor);
+    assert!(matches!(
+        data.validity_check(),
+        Err(SuiError::InvalidSponsoredTransaction { .. })
+    ));
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{dbg_addr, ObjectDigest, ObjectID, SequenceNumber};
+use crate::crypto::{get_key_pair, SuiKeyPair};
+use crate::messages::{Transaction, TransactionData};
+use signature::Signature as _;
+
+fn keys() -> Vec<SuiKeyPair> {
+    vec![
+        SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1),
+        SuiKeyPair::Secp256k1SuiKeyPair(get_key_pair().1),
+        SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1),
+    ]
+}
+
+fn transfer_data(sender: SuiAddress) -> TransactionData {
+    TransactionData::new_transfer_sui(
+        dbg_addr(2),
+        sender,
+        None,
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        ),
+        10000,
+    )
+}
+
+fn sign(data: &TransactionData, keys: &[&SuiKeyPair]) -> Vec<Signature> {
+    keys.iter().map(|key| Signature::new(data, *key)).collect()
+}
+
+#[test]
+fn test_multisig_public_key() {
+    let keys = keys();
+    let public_keys: Vec<_> = keys.iter().map(|key| key.public()).collect();
+
+    let multisig_pk = MultiSigPublicKey::new(public_keys.clone(), 2).unwrap();
+    let address = SuiAddress::from(&multisig_pk);
+    // Not the address of a single key
+    assert!(public_keys
+        .iter()
+        .all(|public_key| SuiAddress::from(public_key) != address));
+    // The threshold is part of the address
+    let other_pk = MultiSigPublicKey::new(public_keys.clone(), 3).unwrap();
+    assert_ne!(SuiAddress::from(&other_pk), address);
+
+    assert!(MultiSigPublicKey::new(public_keys.clone(), 0).is_err());
+    assert!(MultiSigPublicKey::new(public_keys.clone(), 4).is_err());
+    assert!(MultiSigPublicKey::new(vec![], 1).is_err());
+    let mut duplicated = public_keys;
+    duplicated.push(duplicated[0].clone());
+    assert!(MultiSigPublicKey::new(duplicated, 2).is_err());
+}
+
+#[test]
+fn test_multisig_transaction() {
+    let keys = keys();
+    let multisig_pk =
+        MultiSigPublicKey::new(keys.iter().map(|key| key.public()).collect(), 2).unwrap();
+    let sender = SuiAddress::from(&multisig_pk);
+    let data = transfer_data(sender);
+
+    // Any two of the keys, in any order
+    let signature: Signature =
+        MultiSigSuiSignature::combine(multisig_pk.clone(), sign(&data, &[&keys[2], &keys[1]]))
+            .unwrap()
+            .into();
+    let transaction = Transaction::new(data.clone(), signature.clone());
+    transaction.verify().unwrap();
+
+    // The signature goes through the network as bytes
+    let decoded = Signature::from_bytes(signature.as_ref()).unwrap();
+    assert_eq!(decoded, signature);
+    let decoded: Transaction = bcs::from_bytes(&bcs::to_bytes(&transaction).unwrap()).unwrap();
+    decoded.verify().unwrap();
+
+    // Only for the multisig address
+    let other_sender = transfer_data(SuiAddress::from(&keys[0].public()));
+    assert!(Transaction::new(other_sender, signature).verify().is_err());
+}
+
+#[test]
+fn test_multisig_below_threshold() {
+    let keys = keys();
+    let multisig_pk =
+        MultiSigPublicKey::new(keys.iter().map(|key| key.public()).collect(), 2).unwrap();
+    let data = transfer_data(SuiAddress::from(&multisig_pk));
+
+    // Too few signatures
+    assert!(MultiSigSuiSignature::combine(multisig_pk.clone(), sign(&data, &[&keys[0]])).is_err());
+    // Twice the same key
+    assert!(
+        MultiSigSuiSignature::combine(multisig_pk.clone(), sign(&data, &[&keys[0], &keys[0]]))
+            .is_err()
+    );
+    // A key of another committee
+    let outsider = SuiKeyPair::Ed25519SuiKeyPair(get_key_pair().1);
+    assert!(MultiSigSuiSignature::combine(
+        multisig_pk.clone(),
+        sign(&data, &[&keys[0], &outsider])
+    )
+    .is_err());
+
+    // A signature of other data doesn't count
+    let other_data = transfer_data(SuiAddress::from(&multisig_pk));
+    let signatures = vec![
+        Signature::new(&data, &keys[0]),
+        Signature::new(&other_data, &keys[1]),
+    ];
+    let signature = MultiSigSuiSignature::combine(multisig_pk, signatures).unwrap();
+    assert!(Transaction::new(data, signature.into()).verify().is_err());
+}
+
+#[test]
+fn test_multisig_duplicate_signer_rejected() {
+    let keys = keys();
+    let multisig_pk =
+        MultiSigPublicKey::new(keys.iter().map(|key| key.public()).collect(), 2).unwrap();
+    let data = transfer_data(SuiAddress::from(&multisig_pk));
+
+    // A multisig crafted with the same signer twice, which `combine` refuses to make
+    let multisig = MultiSig {
+        signatures: sign(&data, &[&keys[0], &keys[0]]),
+        multisig_pk,
+    };
+    let mut bytes = vec![SignatureScheme::MultiSig.flag()];
+    bytes.extend(bcs::to_bytes(&multisig).unwrap());
+    let signature = Signature::from_bytes(&bytes).unwrap();
+    assert!(Transaction::new(data, signature).verify().is_err());
+}
+
+#[test]
+fn test_multisig_rejects_nested_multisig() {
+    let keys = keys();
+    let inner_pk = MultiSigPublicKey::new(vec![keys[0].public(), keys[1].public()], 1).unwrap();
+    let data = transfer_data(SuiAddress::from(&inner_pk));
+    let inner: Signature = MultiSigSuiSignature::combine(inner_pk, sign(&data, &[&keys[0]]))
+        .unwrap()
+        .into();
+
+    let outer_pk = MultiSigPublicKey::new(vec![keys[2].public()], 1).unwrap();
+    assert!(MultiSigSuiSignature::combine(outer_pk, vec![inner]).is_err());
+}
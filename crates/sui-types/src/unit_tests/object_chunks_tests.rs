@@ -0,0 +1,107 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashSet;
+
+fn random_bytes(len: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+fn digests(data: &[u8]) -> Vec<ChunkDigest> {
+    split(data)
+        .into_iter()
+        .map(|(info, _)| info.digest)
+        .collect()
+}
+
+#[test]
+fn test_split_bounds() {
+    let data = random_bytes(4 * 1024 * 1024, 1);
+    let chunks = split(&data);
+    assert!(chunks.len() > 1);
+    let (last, rest) = chunks.split_last().unwrap();
+    assert!(last.1.len() <= MAX_CHUNK_SIZE);
+    for (info, chunk) in rest {
+        assert!(chunk.len() > MIN_CHUNK_SIZE && chunk.len() <= MAX_CHUNK_SIZE);
+        assert_eq!(info.length as usize, chunk.len());
+        assert_eq!(info.digest, ChunkDigest::new(chunk));
+    }
+    let joined: Vec<u8> = chunks
+        .iter()
+        .flat_map(|(_, chunk)| chunk.iter().copied())
+        .collect();
+    assert_eq!(joined, data);
+
+    assert!(split(&[]).is_empty());
+    assert_eq!(split(&data[..100]).len(), 1);
+}
+
+#[test]
+fn test_edit_keeps_other_chunks() {
+    let old = random_bytes(2 * 1024 * 1024, 2);
+    let mut new = old.clone();
+    // Insert bytes in the middle, which shifts everything after them
+    let middle = new.len() / 2;
+    new.splice(middle..middle, random_bytes(100, 3));
+
+    let old_digests: HashSet<_> = digests(&old).into_iter().collect();
+    let new_digests = digests(&new);
+    let changed = new_digests
+        .iter()
+        .filter(|digest| !old_digests.contains(digest))
+        .count();
+    assert!(changed > 0 && changed <= 3, "{changed} chunks changed");
+}
+
+#[test]
+fn test_reassemble_from_known_chunks() {
+    let old = random_bytes(1024 * 1024, 4);
+    let mut new = old.clone();
+    new[300_000..300_010].copy_from_slice(&[0; 10]);
+    new.extend(random_bytes(50_000, 5));
+
+    let known: HashMap<_, _> = split(&old)
+        .into_iter()
+        .map(|(info, chunk)| (info.digest, chunk))
+        .collect();
+    let chunks = split(&new);
+    let manifest: Vec<_> = chunks.iter().map(|(info, _)| *info).collect();
+    let sent: Vec<_> = chunks
+        .iter()
+        .filter(|(info, _)| !known.contains_key(&info.digest))
+        .map(|(_, chunk)| chunk.to_vec())
+        .collect();
+    let sent_bytes: usize = sent.iter().map(Vec::len).sum();
+    assert!(sent_bytes < new.len() / 4);
+
+    assert_eq!(reassemble(&manifest, sent.clone(), &known).unwrap(), new);
+
+    // Chunks which don't match the manifest are rejected
+    let mut tampered = sent.clone();
+    tampered[0][0] ^= 1;
+    assert!(reassemble(&manifest, tampered, &known).is_err());
+    // So are missing or extra chunks
+    assert!(reassemble(&manifest, sent[1..].to_vec(), &known).is_err());
+    let mut extra = sent;
+    extra.push(vec![1, 2, 3]);
+    assert!(reassemble(&manifest, extra, &known).is_err());
+}
+
+#[test]
+fn test_repeated_chunks_sent_once() {
+    let block = random_bytes(256 * 1024, 6);
+    let data = [block.clone(), block].concat();
+    let chunks = split(&data);
+    let manifest: Vec<_> = chunks.iter().map(|(info, _)| *info).collect();
+    let mut seen = HashSet::new();
+    let sent: Vec<_> = chunks
+        .iter()
+        .filter(|(info, _)| seen.insert(info.digest))
+        .map(|(_, chunk)| chunk.to_vec())
+        .collect();
+    assert!(sent.len() < manifest.len());
+    assert_eq!(reassemble(&manifest, sent, &HashMap::new()).unwrap(), data);
+}
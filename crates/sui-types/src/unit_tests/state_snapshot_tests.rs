@@ -0,0 +1,81 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{ExecutionDigests, ObjectID};
+use crate::messages_checkpoint::SignedCheckpointSummary;
+use crate::utils::make_committee_key;
+use fastcrypto::traits::KeyPair;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+
+fn make_snapshot(rng: &mut StdRng) -> (StateSnapshot, Committee) {
+    let (keys, committee) = make_committee_key(rng);
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::random()].into_iter(),
+    );
+    let signed_checkpoints: Vec<_> = keys
+        .iter()
+        .map(|k| {
+            SignedCheckpointSummary::new(committee.epoch, 3, k.public().into(), k, &contents, None)
+        })
+        .collect();
+    let checkpoint = CertifiedCheckpointSummary::aggregate(signed_checkpoints, &committee).unwrap();
+
+    let mut objects: Vec<_> = (0..4)
+        .map(|_| Object::immutable_with_id_for_testing(ObjectID::random()))
+        .collect();
+    objects.sort_by_key(|object| object.id());
+    let object_refs: Vec<_> = objects
+        .iter()
+        .map(|object| object.compute_object_reference())
+        .collect();
+    let summary = StateSnapshotSummary {
+        epoch: committee.epoch,
+        checkpoint: 3,
+        checkpoint_digest: checkpoint.summary.digest(),
+        object_count: objects.len() as u64,
+        state_digest: state_digest(&object_refs),
+    };
+    let summary = SignedStateSnapshotSummary::new(summary, keys[0].public().into(), &keys[0]);
+    let snapshot = StateSnapshot {
+        checkpoint,
+        contents,
+        summary,
+        objects,
+    };
+    (snapshot, committee)
+}
+
+#[test]
+fn test_verify_state_snapshot() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (snapshot, committee) = make_snapshot(&mut rng);
+    assert!(snapshot.verify(&committee).is_ok());
+
+    // Fails against another committee
+    let (_, other_committee) = make_committee_key(&mut rng);
+    assert!(snapshot.verify(&other_committee).is_err());
+
+    // Fails when an object is missing, added or out of order
+    let mut missing = snapshot.clone();
+    missing.objects.pop();
+    assert!(missing.verify(&committee).is_err());
+
+    let mut added = snapshot.clone();
+    added
+        .objects
+        .push(Object::immutable_with_id_for_testing(ObjectID::new(
+            [u8::MAX; ObjectID::LENGTH],
+        )));
+    assert!(added.verify(&committee).is_err());
+
+    let mut reordered = snapshot.clone();
+    reordered.objects.swap(0, 1);
+    assert!(reordered.verify(&committee).is_err());
+
+    // Fails when the summary is of another checkpoint, even if re-signed
+    let mut other_checkpoint = snapshot;
+    other_checkpoint.summary.summary.checkpoint = 4;
+    assert!(other_checkpoint.verify(&committee).is_err());
+}
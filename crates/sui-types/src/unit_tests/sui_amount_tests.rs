@@ -0,0 +1,68 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_display_sui_amount() {
+    assert_eq!(SuiAmount::ZERO.to_string(), "0");
+    assert_eq!(SuiAmount::from_mist(1).to_string(), "0.000000001");
+    assert_eq!(SuiAmount::from_mist(1_500_000_000).to_string(), "1.5");
+    assert_eq!(SuiAmount::from_sui(42).unwrap().to_string(), "42");
+    assert_eq!(
+        SuiAmount::from_mist(u64::MAX).to_string(),
+        "18446744073709.551615"
+    );
+    assert_eq!(
+        format!("{:#}", SuiAmount::from_mist(1_500_000_000)),
+        "1.500000000"
+    );
+    assert_eq!(
+        format!("{:>6}", SuiAmount::from_mist(1_500_000_000)),
+        "   1.5"
+    );
+}
+
+#[test]
+fn test_parse_sui_amount() {
+    let parse = |s: &str| s.parse::<SuiAmount>().map(|amount| amount.mist());
+    assert_eq!(parse("1.5"), Ok(1_500_000_000));
+    assert_eq!(parse("0.000000001 SUI"), Ok(1));
+    assert_eq!(parse(".25"), Ok(250_000_000));
+    assert_eq!(parse("3."), Ok(3_000_000_000));
+    assert_eq!(parse("18446744073709.551615"), Ok(u64::MAX));
+
+    // Every amount of MIST is displayed as an amount of SUI parsed back exactly
+    for mist in [0, 1, 10, 999_999_999, 1_000_000_001, u64::MAX] {
+        let amount = SuiAmount::from_mist(mist);
+        assert_eq!(amount.to_string().parse(), Ok(amount));
+        assert_eq!(format!("{amount:#}").parse(), Ok(amount));
+    }
+
+    assert!(matches!(
+        parse("0.0000000001"),
+        Err(SuiAmountError::TooManyDecimals(_))
+    ));
+    assert!(matches!(
+        parse("18446744073709.551616"),
+        Err(SuiAmountError::Overflow(_))
+    ));
+    for invalid in ["", ".", "-1", "1.2.3", "1e9", "1,5", "SUI"] {
+        assert!(matches!(parse(invalid), Err(SuiAmountError::Invalid(_))));
+    }
+}
+
+#[test]
+fn test_sui_amount_serde() {
+    let amount = SuiAmount::from_mist(1_500_000_000);
+    assert_eq!(serde_json::to_string(&amount).unwrap(), "1500000000");
+    assert_eq!(
+        serde_json::from_str::<SuiAmount>("1500000000").unwrap(),
+        amount
+    );
+    assert_eq!(SuiAmount::from_sui(u64::MAX), None);
+    assert_eq!(
+        SuiAmount::from_mist(1).checked_sub(SuiAmount::from_mist(2)),
+        None
+    );
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{ObjectDigest, SequenceNumber};
+use crate::gas_coin::GAS;
+
+fn random_object_ref() -> ObjectRef {
+    (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::new([0; 32]),
+    )
+}
+
+#[test]
+fn test_build_batch() {
+    let (sender, recipient) = (
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    );
+    let (framework, coin, other_coin, object, gas) = (
+        random_object_ref(),
+        random_object_ref(),
+        random_object_ref(),
+        random_object_ref(),
+        random_object_ref(),
+    );
+    let mut builder = TransactionBuilder::new(sender, 10000);
+    builder
+        .split_coin(framework, coin, GAS::type_tag(), vec![10, 20])
+        .unwrap()
+        .merge_coins(framework, coin, other_coin, GAS::type_tag())
+        .transfer_object(recipient, object);
+    assert_eq!(
+        builder.input_objects().unwrap(),
+        vec![coin.0, coin.0, other_coin.0, object.0]
+    );
+
+    let data = builder.build(gas).unwrap();
+    assert_eq!(data.signer(), sender);
+    assert_eq!(data.gas(), gas);
+    match data.kind {
+        TransactionKind::Batch(transactions) => {
+            assert_eq!(transactions.len(), 3);
+            match &transactions[0] {
+                SingleTransactionKind::Call(call) => {
+                    assert_eq!(call.function.as_ident_str(), coin::COIN_SPLIT_VEC_FUNC_NAME);
+                    assert_eq!(call.arguments[1], pure(&vec![10u64, 20]).unwrap());
+                }
+                other => panic!("Unexpected transaction {other:?}"),
+            }
+        }
+        TransactionKind::Single(_) => panic!("Expected a batch"),
+    }
+}
+
+#[test]
+fn test_build_single() {
+    let (sender, sponsor) = (
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    );
+    let mut builder = TransactionBuilder::new(sender, 10000)
+        .with_gas_price(5)
+        .with_gas_sponsor(sponsor);
+    assert_eq!(builder.gas_owner(), sponsor);
+    builder.transfer_object(sponsor, random_object_ref());
+    let data = builder.build(random_object_ref()).unwrap();
+    assert!(matches!(
+        data.kind,
+        TransactionKind::Single(SingleTransactionKind::TransferObject(_))
+    ));
+    assert_eq!(data.gas_price, 5);
+    assert_eq!(data.gas_sponsor(), Some(sponsor));
+}
+
+#[test]
+fn test_build_invalid() {
+    let sender = SuiAddress::random_for_testing_only();
+    let gas = random_object_ref();
+
+    // Nothing to build
+    assert!(TransactionBuilder::new(sender, 10000).build(gas).is_err());
+
+    // The gas object is an input
+    let mut builder = TransactionBuilder::new(sender, 10000);
+    builder.transfer_object(sender, gas);
+    assert!(builder.build(gas).is_err());
+
+    // Publishing can't be batched
+    let mut builder = TransactionBuilder::new(sender, 10000);
+    builder
+        .publish(vec![vec![]])
+        .transfer_object(sender, random_object_ref());
+    assert!(matches!(
+        builder.build(gas),
+        Err(SuiError::InvalidBatchTransaction { .. })
+    ));
+}
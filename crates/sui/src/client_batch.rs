@@ -0,0 +1,205 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batch transactions of transfers, Move calls, coin splits and merges, run with
+//! `sui client batch <batch.yaml>`. All the steps are executed atomically in a single
+//! transaction, so that either all of them or none take effect:
+//!
+//! ```yaml
+//! steps:
+//!   - split:
+//!       coin: "0x3d9ec6a7fd6ec2ed54e8efd1ee1f2e0b8ff4f5f0"
+//!       amounts: [1000, 2000]
+//!   - merge:
+//!       primary_coin: "0x7c9ec6a7fd6ec2ed54e8efd1ee1f2e0b8ff4f5f1"
+//!       coin_to_merge: "0x8b9ec6a7fd6ec2ed54e8efd1ee1f2e0b8ff4f5f2"
+//!   - transfer:
+//!       object_id: "0x9a9ec6a7fd6ec2ed54e8efd1ee1f2e0b8ff4f5f3"
+//!       to: "0x57e1cd7f1d9ee4a6bd5f4e4b6ac1c7d5b0c2e3f4"
+//!   - call:
+//!       package: "0x2"
+//!       module: devnet_nft
+//!       function: mint
+//!       args: ["name", "description", "ipfs://..."]
+//! ```
+//!
+//! The objects of a batch are all read when it is built, and each object can only be used by one
+//! of its steps: a step can't use the objects created or mutated by an earlier one. Scripts run
+//! with `sui client run` can, at the cost of executing each step as a separate transaction.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{anyhow, bail};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{
+    MoveCallParams, RPCTransactionRequestParams, SuiTransactionResponse, TransferObjectParams,
+};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::coin::{COIN_JOIN_FUNC_NAME, COIN_MODULE_NAME, COIN_SPLIT_VEC_FUNC_NAME};
+use sui_types::messages::{InputObjectKind, Transaction};
+use sui_types::object::Object;
+use sui_types::parse_sui_type_tag;
+use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+
+use crate::client_commands::WalletContext;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Batch {
+    steps: Vec<BatchStep>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum BatchStep {
+    Transfer {
+        to: SuiAddress,
+        object_id: ObjectID,
+    },
+    Call {
+        package: ObjectID,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<Value>,
+    },
+    Split {
+        coin: ObjectID,
+        amounts: Vec<u64>,
+    },
+    Merge {
+        primary_coin: ObjectID,
+        coin_to_merge: ObjectID,
+    },
+}
+
+/// Builds the transaction of the batch file at `path`, sent by the owner of `gas` or else the
+/// active address, and executes it.
+pub async fn run_batch(
+    path: &Path,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    context: &mut WalletContext,
+) -> Result<SuiTransactionResponse, anyhow::Error> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read batch file {:?}: {e}", path))?;
+    let batch: Batch = serde_yaml::from_str(&contents)
+        .map_err(|e| anyhow!("Cannot parse batch file {:?}: {e}", path))?;
+    if batch.steps.is_empty() {
+        bail!("Batch file {:?} has no steps", path);
+    }
+
+    let mut params = Vec::with_capacity(batch.steps.len());
+    for (index, step) in batch.steps.into_iter().enumerate() {
+        let param = step_params(step, context)
+            .await
+            .map_err(|e| anyhow!("Invalid step {index} of the batch: {e}"))?;
+        params.push(param);
+    }
+
+    let gas_owner = context.try_get_object_owner(&gas).await?;
+    let sender = gas_owner.unwrap_or(context.active_address()?);
+    let data = context
+        .client
+        .transaction_builder()
+        .batch_transaction(sender, params, gas, gas_budget)
+        .await?;
+
+    // The validators would reject the transaction without telling which object is reused
+    let mut used = BTreeSet::new();
+    for input in data.input_objects()? {
+        if let InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) = input {
+            if !used.insert(id) {
+                bail!(
+                    "Object {id} is used by more than one step of the batch, run the steps \
+                     separately with `sui client run` to use the results of earlier ones"
+                );
+            }
+        }
+    }
+
+    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
+    context
+        .execute_transaction(Transaction::new(data, signature))
+        .await
+}
+
+async fn step_params(
+    step: BatchStep,
+    context: &WalletContext,
+) -> Result<RPCTransactionRequestParams, anyhow::Error> {
+    Ok(match step {
+        BatchStep::Transfer { to, object_id } => {
+            RPCTransactionRequestParams::TransferObjectRequestParams(TransferObjectParams {
+                recipient: to,
+                object_id,
+            })
+        }
+        BatchStep::Call {
+            package,
+            module,
+            function,
+            type_args,
+            args,
+        } => RPCTransactionRequestParams::MoveCallRequestParams(MoveCallParams {
+            package_object_id: package,
+            module,
+            function,
+            type_arguments: type_args
+                .iter()
+                .map(|arg| Ok(parse_sui_type_tag(arg)?.into()))
+                .collect::<Result<_, anyhow::Error>>()?,
+            arguments: args
+                .into_iter()
+                .map(SuiJsonValue::new)
+                .collect::<Result<_, _>>()?,
+        }),
+        BatchStep::Split { coin, amounts } => {
+            let amounts = SuiJsonValue::new(json!(amounts))?;
+            coin_call(coin, COIN_SPLIT_VEC_FUNC_NAME.as_str(), amounts, context).await?
+        }
+        BatchStep::Merge {
+            primary_coin,
+            coin_to_merge,
+        } => {
+            coin_call(
+                primary_coin,
+                COIN_JOIN_FUNC_NAME.as_str(),
+                SuiJsonValue::from_object_id(coin_to_merge),
+                context,
+            )
+            .await?
+        }
+    })
+}
+
+/// A call to `function` of the coin module of the framework, with `coin` and `arg` as arguments.
+async fn coin_call(
+    coin: ObjectID,
+    function: &str,
+    arg: SuiJsonValue,
+    context: &WalletContext,
+) -> Result<RPCTransactionRequestParams, anyhow::Error> {
+    let object: Object = context
+        .client
+        .read_api()
+        .get_object(coin)
+        .await?
+        .into_object()?
+        .try_into()?;
+    Ok(RPCTransactionRequestParams::MoveCallRequestParams(
+        MoveCallParams {
+            package_object_id: SUI_FRAMEWORK_OBJECT_ID,
+            module: COIN_MODULE_NAME.to_string(),
+            function: function.to_string(),
+            type_arguments: vec![object.get_move_template_type()?.into()],
+            arguments: vec![SuiJsonValue::from_object_id(coin), arg],
+        },
+    ))
+}
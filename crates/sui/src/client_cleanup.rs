@@ -0,0 +1,180 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cleanup of the objects an address received from another one, e.g. airdropped spam, run with
+//! `sui client cleanup --received-from <address>`. Objects can only be deleted by the functions
+//! of their module, so the cleanup is limited to coins, in a single batch transaction:
+//!
+//! - coins with a zero value are deleted with `0x2::coin::destroy_zero`,
+//! - the other coins of each type are merged into one with `0x2::coin::join_vec`.
+//!
+//! The other objects received from the address are left as they are, and reported as skipped.
+//! An object counts as received from an address if that address sent the last transaction which
+//! changed it.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::anyhow;
+use serde::Serialize;
+use serde_json::json;
+use tracing::debug;
+
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{MoveCallParams, RPCTransactionRequestParams, SuiExecutionStatus};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::coin::{
+    Coin, COIN_DESTROY_ZERO_FUNC_NAME, COIN_JOIN_VEC_FUNC_NAME, COIN_MODULE_NAME,
+};
+use sui_types::messages::Transaction;
+use sui_types::object::Object;
+use sui_types::SUI_FRAMEWORK_OBJECT_ID;
+
+use crate::client_commands::WalletContext;
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    /// The zero-value coins deleted.
+    pub deleted: Vec<ObjectID>,
+    /// The coins merged, by the coin they were merged into.
+    pub merged: BTreeMap<ObjectID, Vec<ObjectID>>,
+    /// The objects received from the address which can't be deleted.
+    pub skipped: Vec<ObjectID>,
+    /// The cleanup transaction, none if there was nothing to clean up or on a dry run.
+    pub transaction: Option<TransactionDigest>,
+}
+
+/// Deletes or merges the coins `owner` received from `received_from`, leaving `gas` alone. The
+/// transaction is only sent if `dry_run` is false.
+pub async fn cleanup(
+    owner: SuiAddress,
+    received_from: SuiAddress,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    dry_run: bool,
+    context: &mut WalletContext,
+) -> Result<CleanupReport, anyhow::Error> {
+    let mut report = CleanupReport::default();
+    let mut senders = HashMap::new();
+    // The coins to merge of each type, in the order they are listed
+    let mut coins: BTreeMap<String, Vec<ObjectID>> = BTreeMap::new();
+    let mut params = Vec::new();
+
+    let objects = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(owner)
+        .await?;
+    for info in objects {
+        if Some(info.object_id) == gas {
+            continue;
+        }
+        let sender = match senders.get(&info.previous_transaction) {
+            Some(sender) => *sender,
+            None => {
+                let sender = match context
+                    .client
+                    .read_api()
+                    .get_transaction(info.previous_transaction)
+                    .await
+                {
+                    Ok(response) => Some(response.certificate.data.sender),
+                    // e.g. the objects created at genesis
+                    Err(e) => {
+                        debug!(object_id = ?info.object_id, "Unknown sender: {e}");
+                        None
+                    }
+                };
+                senders.insert(info.previous_transaction, sender);
+                sender
+            }
+        };
+        if sender != Some(received_from) {
+            continue;
+        }
+
+        let object: Object = context
+            .client
+            .read_api()
+            .get_object(info.object_id)
+            .await?
+            .into_object()?
+            .try_into()?;
+        match Coin::extract_balance_if_coin(&object)? {
+            Some(0) => {
+                params.push(coin_call(
+                    &object,
+                    COIN_DESTROY_ZERO_FUNC_NAME.as_str(),
+                    vec![SuiJsonValue::from_object_id(info.object_id)],
+                )?);
+                report.deleted.push(info.object_id);
+            }
+            Some(_) => coins.entry(info.type_).or_default().push(info.object_id),
+            None => report.skipped.push(info.object_id),
+        }
+    }
+
+    for coins in coins.into_values() {
+        // A single coin is already as small as it gets
+        if coins.len() < 2 {
+            report.skipped.extend(coins);
+            continue;
+        }
+        let primary = coins[0];
+        let others = coins[1..].to_vec();
+        let object: Object = context
+            .client
+            .read_api()
+            .get_object(primary)
+            .await?
+            .into_object()?
+            .try_into()?;
+        params.push(coin_call(
+            &object,
+            COIN_JOIN_VEC_FUNC_NAME.as_str(),
+            vec![
+                SuiJsonValue::from_object_id(primary),
+                SuiJsonValue::new(json!(others
+                    .iter()
+                    .map(ObjectID::to_hex_literal)
+                    .collect::<Vec<_>>()))?,
+            ],
+        )?);
+        report.merged.insert(primary, others);
+    }
+
+    if params.is_empty() || dry_run {
+        return Ok(report);
+    }
+    let data = context
+        .client
+        .transaction_builder()
+        .batch_transaction(owner, params, gas, gas_budget)
+        .await?;
+    let signature = context.keystore.sign(&owner, &data.to_bytes())?;
+    let response = context
+        .execute_transaction(Transaction::new(data, signature))
+        .await?;
+    if let SuiExecutionStatus::Failure { error } = response.effects.status {
+        return Err(anyhow!("Cleanup transaction failed: {error}"));
+    }
+    report.transaction = Some(response.certificate.transaction_digest);
+    Ok(report)
+}
+
+/// A call to `function` of the coin module of the framework, for the type of the coin `object`.
+fn coin_call(
+    object: &Object,
+    function: &str,
+    arguments: Vec<SuiJsonValue>,
+) -> Result<RPCTransactionRequestParams, anyhow::Error> {
+    Ok(RPCTransactionRequestParams::MoveCallRequestParams(
+        MoveCallParams {
+            package_object_id: SUI_FRAMEWORK_OBJECT_ID,
+            module: COIN_MODULE_NAME.to_string(),
+            function: function.to_string(),
+            type_arguments: vec![object.get_move_template_type()?.into()],
+            arguments,
+        },
+    ))
+}
@@ -3,44 +3,57 @@
 
 use core::fmt;
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Debug, Display, Formatter, Write},
     path::{Path, PathBuf},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use clap::*;
 use colored::Colorize;
+use futures::StreamExt;
 use move_core_types::language_storage::TypeTag;
 use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::json;
-use tracing::info;
+use tracing::{info, warn};
 
 use sui_framework::build_move_package_to_bytes;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::SuiData;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, SuiExecuteTransactionResponse, SuiObjectInfo, SuiParsedObject,
+    GetObjectDataResponse, SuiEvent, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiObjectInfo, SuiObjectsPage, SuiParsedObject,
     SuiTransactionResponse,
 };
 use sui_json_rpc_types::{SuiCertifiedTransaction, SuiExecutionStatus, SuiTransactionEffects};
-use sui_sdk::crypto::SuiKeystore;
+use sui_sdk::crypto::{SignerConfig, SuiKeystore};
+use sui_sdk::journal::TransactionJournal;
 use sui_sdk::{ClientType, SuiClient};
-use sui_types::crypto::SignatureScheme;
+use sui_types::crypto::{validate_path, SignableBytes, SignatureScheme};
+use sui_types::sui_amount::SuiAmount;
 use sui_types::sui_serde::{Base64, Encoding};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{set_checksummed_addresses, ObjectID, SuiAddress, TransactionDigest},
     gas_coin::GasCoin,
     messages::ExecuteTransactionRequestType,
-    messages::Transaction,
+    messages::{Transaction, TransactionData},
     object::Owner,
-    parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
+    parse_sui_type_tag,
+    sui_system_state::SuiSystemState,
+    SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig};
+use crate::client_batch::run_batch;
+use crate::client_cleanup::{cleanup, CleanupReport};
+use crate::client_script::{run_script, ScriptReport};
+use crate::config::{AddressOrAlias, Config, PersistedConfig, SuiClientConfig};
+
+/// Delays before renewing the event subscription of `sui client watch-events`.
+const WATCH_EVENTS_MIN_BACKOFF: Duration = Duration::from_secs(1);
+const WATCH_EVENTS_MAX_BACKOFF: Duration = Duration::from_secs(30);
 
 pub const EXAMPLE_NFT_NAME: &str = "Example NFT";
 pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Line Tool";
@@ -53,10 +66,10 @@ pub enum SuiClientCommands {
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
-        /// An Sui address to be used as the active address for subsequent
+        /// An Sui address or alias to be used as the active address for subsequent
         /// commands.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long, value_hint = ValueHint::Url)]
@@ -142,9 +155,9 @@ pub enum SuiClientCommands {
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -158,15 +171,21 @@ pub enum SuiClientCommands {
         /// Gas budget for this transfer
         #[clap(long)]
         gas_budget: u64,
+
+        /// Memo attached to the transfer, e.g. a deposit memo. It shows up in the events of the
+        /// transaction.
+        #[clap(long)]
+        memo: Option<String>,
     },
     /// Transfer SUI, and pay gas with the same SUI coin object.
+    /// If a memo is attached, gas is paid with another coin instead.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: AddressOrAlias,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
@@ -179,12 +198,17 @@ pub enum SuiClientCommands {
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
         amount: Option<u64>,
+
+        /// Memo attached to the transfer, e.g. a deposit memo. It shows up in the events of the
+        /// transaction.
+        #[clap(long)]
+        memo: Option<String>,
     },
     /// Synchronize client state with authorities.
     #[clap(name = "sync")]
     SyncClientState {
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<AddressOrAlias>,
     },
 
     /// Obtain the Addresses managed by the client.
@@ -197,22 +221,94 @@ pub enum SuiClientCommands {
     NewAddress {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
+        /// Number of words of the recovery phrase, 12 by default or up to 24
+        #[clap(long)]
+        word_count: Option<usize>,
+    },
+
+    /// Add the address of the ed25519 key of a Ledger device at the derivation path, default to
+    /// m/44'/784'/0'/0'/0'. The key stays on the device, which signs the transactions of the
+    /// address once they are approved on it.
+    #[clap(name = "ledger-address")]
+    LedgerAddress {
+        derivation_path: Option<DerivationPath>,
     },
 
-    /// Obtain all objects owned by the address.
+    /// Manage the aliases of addresses, which client commands accept in place of the addresses.
+    #[clap(name = "alias")]
+    Alias {
+        #[clap(subcommand)]
+        cmd: AliasCommand,
+    },
+
+    /// Obtain the objects owned by the address, a page at a time.
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address or alias owning the objects
+        #[clap(long)]
+        address: Option<AddressOrAlias>,
+        /// Only list the objects of this Move type, e.g. `0x2::coin::Coin` for all the coins or
+        /// `0x2::coin::Coin<0x2::sui::SUI>` for the SUI coins
+        #[clap(long = "type")]
+        object_type: Option<String>,
+        /// List the objects from this object ID on, as printed with the previous page
         #[clap(long)]
-        address: Option<SuiAddress>,
+        cursor: Option<ObjectID>,
+        /// Maximum number of objects listed, at most 100
+        #[clap(long, default_value = "50")]
+        limit: usize,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address or alias owning the objects
+        #[clap(long)]
+        address: Option<AddressOrAlias>,
+    },
+
+    /// Show the peer reports against each active validator during the current epoch. Validators
+    /// reported by a quorum of the stake lose their rewards at the end of the epoch.
+    #[clap(name = "validator-scores")]
+    ValidatorScores,
+
+    /// Delete the zero-value coins received from an address, e.g. airdropped spam, and merge the
+    /// other coins of each type received from it into one, in a single transaction. The other
+    /// objects received from the address are listed as skipped.
+    #[clap(name = "cleanup")]
+    Cleanup {
+        /// Address or alias which sent the objects to clean up
+        #[clap(long)]
+        received_from: AddressOrAlias,
+        /// Address or alias owning the objects, the active address if not given
+        #[clap(long)]
+        address: Option<AddressOrAlias>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for the cleanup transaction
+        #[clap(long)]
+        gas_budget: u64,
+        /// Only list the objects which would be deleted, merged or skipped
         #[clap(long)]
-        address: Option<SuiAddress>,
+        dry_run: bool,
+    },
+
+    /// Print the events of a package as they are emitted, until interrupted. Requires a client
+    /// connected to a fullnode websocket, see `sui client switch --ws`.
+    #[clap(name = "watch-events")]
+    WatchEvents {
+        /// Object ID of the package emitting the events
+        #[clap(long)]
+        package: ObjectID,
+        /// Only print the events of the transactions calling this module of the package
+        #[clap(long)]
+        module: Option<String>,
+        /// Only print the Move events of this type, either a full struct tag or the name of a
+        /// struct of the package, e.g. `devnet_nft::MintNFTEvent`
+        #[clap(long)]
+        event_type: Option<String>,
     },
 
     /// Split a coin object into multiple coins.
@@ -277,6 +373,221 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas_budget: Option<u64>,
     },
+
+    /// Run a YAML script of publish, call and transfer operations, where later operations can
+    /// use the results of earlier ones and check their effects.
+    #[clap(name = "run")]
+    Run {
+        /// Path to the script
+        #[clap(parse(from_os_str))]
+        script: PathBuf,
+
+        /// Simulate the operations against the current state of the network instead of
+        /// executing them. Operations using the results of earlier ones are skipped.
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Execute a YAML file of transfers, Move calls, coin splits and merges atomically, as a
+    /// single batch transaction. Each object can only be used by one of the steps.
+    #[clap(name = "batch")]
+    Batch {
+        /// Path to the batch file
+        #[clap(parse(from_os_str))]
+        steps: PathBuf,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for the whole batch
+        #[clap(long)]
+        gas_budget: u64,
+    },
+
+    /// Build, sign and execute a transaction in separate steps, so that it can be signed on a
+    /// machine which holds the key but isn't connected to the network.
+    #[clap(name = "tx")]
+    Tx {
+        #[clap(subcommand)]
+        cmd: TxCommand,
+    },
+
+    /// Submit again the transactions of the journal which were not final when the client
+    /// stopped, e.g. after a crash, until they are executed
+    #[clap(name = "resume")]
+    Resume {
+        /// Remove the transactions which can't be executed from the journal, e.g. because their
+        /// objects were used by other transactions since
+        #[clap(long)]
+        discard_failed: bool,
+    },
+
+    /// Execute a transaction against the current state of the full node without committing it,
+    /// printing its predicted effects, events and gas cost
+    #[clap(name = "dry-run")]
+    DryRun {
+        #[clap(subcommand)]
+        kind: TxBuildCommand,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum TxCommand {
+    /// Build an unsigned transaction, printed as Base64 encoded transaction data
+    #[clap(name = "build")]
+    Build {
+        #[clap(subcommand)]
+        kind: TxBuildCommand,
+    },
+
+    /// Sign transaction data built by `tx build`, printing the signed transaction as a Base64
+    /// encoded blob. Only needs the keystore, not the network.
+    #[clap(name = "sign")]
+    Sign {
+        /// Address of the key to sign with, which must be the sender of the transaction
+        #[clap(long)]
+        key: AddressOrAlias,
+
+        /// Base64 encoded transaction data, as printed by `tx build`
+        #[clap(long)]
+        data: String,
+    },
+
+    /// Add the signature of the gas sponsor to a transaction signed by `tx sign`, printing the
+    /// sponsored transaction as a Base64 encoded blob. Only needs the keystore, not the network.
+    #[clap(name = "sponsor")]
+    Sponsor {
+        /// Address of the key to sign with, which must be the gas sponsor of the transaction
+        #[clap(long)]
+        key: AddressOrAlias,
+
+        /// Base64 encoded signed transaction, as printed by `tx sign`
+        #[clap(long)]
+        signed: String,
+    },
+
+    /// Execute a transaction signed by `tx sign`, and by `tx sponsor` if it has a gas sponsor
+    #[clap(name = "execute")]
+    Execute {
+        /// Base64 encoded signed transaction, as printed by `tx sign`
+        #[clap(long)]
+        signed: String,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum AliasCommand {
+    /// Name an address, e.g. `sui client alias add alice 0x...`
+    #[clap(name = "add")]
+    Add { alias: String, address: SuiAddress },
+
+    /// List the aliases
+    #[clap(name = "list")]
+    List,
+
+    /// Remove an alias
+    #[clap(name = "remove")]
+    Remove { alias: String },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum TxBuildCommand {
+    /// Transfer object
+    #[clap(name = "transfer")]
+    Transfer {
+        /// Recipient address or alias
+        #[clap(long)]
+        to: AddressOrAlias,
+
+        /// Object to transfer, in 20 bytes Hex string
+        #[clap(long)]
+        object_id: ObjectID,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transfer
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Memo attached to the transfer
+        #[clap(long)]
+        memo: Option<String>,
+
+        /// Address or alias of the gas sponsor, owning the gas object and paying for the gas on
+        /// behalf of the sender. The transaction must then also be signed with `tx sponsor`.
+        /// If no gas object is provided, one of the sponsor is selected.
+        #[clap(long)]
+        gas_sponsor: Option<AddressOrAlias>,
+    },
+
+    /// Transfer SUI, and pay gas with the same SUI coin object
+    #[clap(name = "transfer-sui")]
+    TransferSui {
+        /// Recipient address or alias
+        #[clap(long)]
+        to: AddressOrAlias,
+
+        /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
+        #[clap(long)]
+        sui_coin_object_id: ObjectID,
+
+        /// Gas budget for this transfer
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// The amount to transfer, if not specified, the entire coin object will be transferred.
+        #[clap(long)]
+        amount: Option<u64>,
+
+        /// Memo attached to the transfer
+        #[clap(long)]
+        memo: Option<String>,
+    },
+
+    /// Call Move function
+    #[clap(name = "call")]
+    Call {
+        /// Object ID of the package, which contains the module
+        #[clap(long)]
+        package: ObjectID,
+        /// The name of the module in the package
+        #[clap(long)]
+        module: String,
+        /// Function name in module
+        #[clap(long)]
+        function: String,
+        /// Type arguments of the function
+        #[clap(
+        long,
+        parse(try_from_str = parse_sui_type_tag),
+        multiple_occurrences = false,
+        multiple_values = true
+        )]
+        type_args: Vec<TypeTag>,
+        /// Simplified ordered args like in the function syntax
+        /// ObjectIDs, Addresses must be hex strings
+        #[clap(long, multiple_occurrences = false, multiple_values = true)]
+        args: Vec<SuiJsonValue>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget for this call
+        #[clap(long)]
+        gas_budget: u64,
+
+        /// Address or alias of the gas sponsor, owning the gas object and paying for the gas on
+        /// behalf of the sender. The transaction must then also be signed with `tx sponsor`.
+        /// If no gas object is provided, one of the sponsor is selected.
+        #[clap(long)]
+        gas_sponsor: Option<AddressOrAlias>,
+    },
 }
 
 impl SuiClientCommands {
@@ -334,15 +645,25 @@ impl SuiClientCommands {
                 object_id,
                 gas,
                 gas_budget,
+                memo,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
                 let time_start = Instant::now();
 
-                let data = context
-                    .client
-                    .transaction_builder()
-                    .transfer_object(from, object_id, gas, gas_budget, to)
-                    .await?;
+                let builder = context.client.transaction_builder();
+                let data = match memo {
+                    Some(memo) => {
+                        builder
+                            .transfer_object_with_memo(from, object_id, gas, gas_budget, to, memo)
+                            .await?
+                    }
+                    None => {
+                        builder
+                            .transfer_object(from, object_id, gas, gas_budget, to)
+                            .await?
+                    }
+                };
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
@@ -362,14 +683,26 @@ impl SuiClientCommands {
                 sui_coin_object_id: object_id,
                 gas_budget,
                 amount,
+                memo,
             } => {
+                let to = context.config.resolve_address(&to)?;
                 let from = context.get_object_owner(&object_id).await?;
 
-                let data = context
-                    .client
-                    .transaction_builder()
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
-                    .await?;
+                let builder = context.client.transaction_builder();
+                let data = match memo {
+                    Some(memo) => {
+                        builder
+                            .transfer_sui_with_memo(
+                                from, object_id, None, gas_budget, to, amount, memo,
+                            )
+                            .await?
+                    }
+                    None => {
+                        builder
+                            .transfer_sui(from, object_id, gas_budget, to, amount)
+                            .await?
+                    }
+                };
                 let signature = context.keystore.sign(&from, &data.to_bytes())?;
                 let response = context
                     .execute_transaction(Transaction::new(data, signature))
@@ -387,25 +720,48 @@ impl SuiClientCommands {
                 SuiClientCommandResult::Addresses(context.keystore.addresses())
             }
 
-            SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
-                let mut address_object = context
-                    .client
-                    .read_api()
-                    .get_objects_owned_by_address(address)
-                    .await?;
-                let object_objects = context
+            SuiClientCommands::Alias { cmd } => {
+                match cmd {
+                    AliasCommand::Add { alias, address } => {
+                        AddressOrAlias::validate_alias(&alias)?;
+                        if let Some(existing) = context.config.aliases.get(&alias) {
+                            return Err(anyhow!(
+                                "Alias {alias} is already the alias of {existing}, remove it first"
+                            ));
+                        }
+                        context.config.aliases.insert(alias, address);
+                        context.config.save()?;
+                    }
+                    AliasCommand::List => {}
+                    AliasCommand::Remove { alias } => {
+                        if context.config.aliases.remove(&alias).is_none() {
+                            return Err(anyhow!("Unknown address alias {alias}"));
+                        }
+                        context.config.save()?;
+                    }
+                }
+                SuiClientCommandResult::Aliases(context.config.aliases.clone())
+            }
+
+            SuiClientCommands::Objects {
+                address,
+                object_type,
+                cursor,
+                limit,
+            } => {
+                let address = context.address_or_active(address)?;
+                let page = context
                     .client
                     .read_api()
-                    .get_objects_owned_by_object(address.into())
+                    .get_owned_objects(address, object_type, cursor, Some(limit))
                     .await?;
-                address_object.extend(object_objects);
 
-                SuiClientCommandResult::Objects(address_object)
+                let alias = context.config.alias_of(address).map(str::to_string);
+                SuiClientCommandResult::Objects(page, alias)
             }
 
             SuiClientCommands::SyncClientState { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 context
                     .client
                     .wallet_sync_api()
@@ -417,14 +773,27 @@ impl SuiClientCommands {
             SuiClientCommands::NewAddress {
                 key_scheme,
                 derivation_path,
+                word_count,
             } => {
-                let (address, phrase, scheme) = context
-                    .keystore
-                    .generate_new_key(key_scheme, derivation_path)?;
+                let (address, phrase, scheme) = context.keystore.generate_new_key_with_words(
+                    key_scheme,
+                    derivation_path,
+                    word_count.unwrap_or(12),
+                )?;
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
+            SuiClientCommands::LedgerAddress { derivation_path } => {
+                let derivation_path = validate_path(&SignatureScheme::ED25519, derivation_path)?;
+                let signer_config = SignerConfig::Ledger(derivation_path);
+                let signer = signer_config.init();
+                let address = SuiAddress::from(&signer.public_key()?);
+                context.keystore.add_signer(address, signer);
+                context.config.signers.insert(address, signer_config);
+                context.config.save()?;
+                SuiClientCommandResult::LedgerAddress(address)
+            }
             SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.address_or_active(address)?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -432,7 +801,66 @@ impl SuiClientCommands {
                     // Ok to unwrap() since `get_gas_objects` guarantees gas
                     .map(|(_val, object, _object_ref)| GasCoin::try_from(object).unwrap())
                     .collect();
-                SuiClientCommandResult::Gas(coins)
+                let alias = context.config.alias_of(address).map(str::to_string);
+                SuiClientCommandResult::Gas(coins, alias)
+            }
+            SuiClientCommands::ValidatorScores => {
+                let object = context
+                    .client
+                    .read_api()
+                    .get_object(SUI_SYSTEM_STATE_OBJECT_ID)
+                    .await?
+                    .into_object()?;
+                let system_state: SuiSystemState = object
+                    .data
+                    .try_as_move()
+                    .ok_or_else(|| anyhow!("Sui system state is not a Move object"))?
+                    .deserialize()?;
+                let quorum_stake_threshold = system_state.validators.quorum_stake_threshold;
+                let scores = system_state
+                    .validators
+                    .active_validators
+                    .iter()
+                    .map(|validator| {
+                        let address = validator.metadata.sui_address;
+                        let reported_stake = system_state.validator_reported_stake(&address);
+                        ValidatorScore {
+                            address: SuiAddress::from(address),
+                            stake: validator.stake_amount
+                                + validator.delegation_staking_pool.epoch_starting_sui_balance,
+                            reporters: system_state
+                                .validator_reporters(&address)
+                                .iter()
+                                .map(|reporter| SuiAddress::from(*reporter))
+                                .collect(),
+                            reported_stake,
+                            slashed: reported_stake >= quorum_stake_threshold,
+                        }
+                    })
+                    .collect();
+                SuiClientCommandResult::ValidatorScores(scores)
+            }
+            SuiClientCommands::Cleanup {
+                received_from,
+                address,
+                gas,
+                gas_budget,
+                dry_run,
+            } => {
+                let owner = context.address_or_active(address)?;
+                let received_from = context.config.resolve_address(&received_from)?;
+                let report =
+                    cleanup(owner, received_from, gas, gas_budget, dry_run, context).await?;
+                SuiClientCommandResult::Cleanup(report)
+            }
+            SuiClientCommands::WatchEvents {
+                package,
+                module,
+                event_type,
+            } => {
+                let filter = watch_events_filter(package, module, event_type);
+                watch_events(context, filter, false).await?;
+                SuiClientCommandResult::WatchEvents
             }
             SuiClientCommands::SplitCoin {
                 coin_id,
@@ -485,6 +913,7 @@ impl SuiClientCommands {
             }
             SuiClientCommands::Switch { address, rpc, ws } => {
                 if let Some(addr) = address {
+                    let addr = context.config.resolve_address(&addr)?;
                     if !context.keystore.addresses().contains(&addr) {
                         return Err(anyhow!("Address {} not managed by wallet", addr));
                     }
@@ -540,6 +969,97 @@ impl SuiClientCommands {
                 let object_read = context.client.read_api().get_parsed_object(nft_id).await?;
                 SuiClientCommandResult::CreateExampleNFT(object_read)
             }
+            SuiClientCommands::Run { script, dry_run } => {
+                SuiClientCommandResult::Run(run_script(&script, dry_run, context).await?)
+            }
+            SuiClientCommands::Batch {
+                steps,
+                gas,
+                gas_budget,
+            } => {
+                let response = run_batch(&steps, gas, gas_budget, context).await?;
+                SuiClientCommandResult::Batch(response)
+            }
+            SuiClientCommands::Tx { cmd } => match cmd {
+                TxCommand::Build { kind } => {
+                    let data = build_transaction(kind, context).await?;
+                    SuiClientCommandResult::TxBuild(data.to_base64())
+                }
+                TxCommand::Sign { key, data } => {
+                    let key = context.config.resolve_address(&key)?;
+                    let transaction = sign_transaction(&context.keystore, key, &data)?;
+                    SuiClientCommandResult::TxSign(Base64::encode(bcs::to_bytes(&transaction)?))
+                }
+                TxCommand::Sponsor { key, signed } => {
+                    let key = context.config.resolve_address(&key)?;
+                    let transaction = sponsor_transaction(&context.keystore, key, &signed)?;
+                    SuiClientCommandResult::TxSign(Base64::encode(bcs::to_bytes(&transaction)?))
+                }
+                TxCommand::Execute { signed } => {
+                    let transaction: Transaction = bcs::from_bytes(&Base64::decode(&signed)?)
+                        .map_err(|e| {
+                            anyhow!(
+                                "Invalid signed transaction, expected the output of `tx sign`: {e}"
+                            )
+                        })?;
+                    // Catch a transaction signed for another sender before sending it out
+                    transaction.verify()?;
+                    let response = context.execute_transaction(transaction).await?;
+                    if matches!(response.effects.status, SuiExecutionStatus::Failure { .. }) {
+                        return Err(anyhow!(
+                            "Error executing transaction: {:#?}",
+                            response.effects.status
+                        ));
+                    }
+                    SuiClientCommandResult::TxExecute(response)
+                }
+            },
+            SuiClientCommands::Resume { discard_failed } => {
+                let journal = context.journal.as_ref().ok_or_else(|| {
+                    anyhow!("No transaction journal, set `journal` in the client config")
+                })?;
+                let mut resumed = Vec::new();
+                for transaction in journal.pending()? {
+                    let digest = *transaction.digest();
+                    match context.execute_transaction(transaction).await {
+                        Ok(response) => resumed.push(ResumedTransaction {
+                            digest,
+                            status: Some(response.effects.status),
+                            error: None,
+                        }),
+                        Err(err) => {
+                            if discard_failed {
+                                journal.complete(&digest)?;
+                            }
+                            resumed.push(ResumedTransaction {
+                                digest,
+                                status: None,
+                                error: Some(err.to_string()),
+                            });
+                        }
+                    }
+                }
+                SuiClientCommandResult::Resume(resumed)
+            }
+            SuiClientCommands::DryRun { kind } => {
+                let data = build_transaction(kind, context).await?;
+                // The full node checks the signature, as for an actual execution
+                let signature = context.keystore.sign(&data.signer(), &data.to_bytes())?;
+                let transaction = match data.gas_sponsor() {
+                    Some(sponsor) => {
+                        let sponsor_signature =
+                            context.keystore.sign(&sponsor, &data.to_bytes())?;
+                        Transaction::new_sponsored(data, signature, sponsor_signature)
+                    }
+                    None => Transaction::new(data, signature),
+                };
+                let effects = context
+                    .client
+                    .full_node_api()
+                    .dry_run_transaction(transaction)
+                    .await?;
+                SuiClientCommandResult::DryRun(effects)
+            }
         });
         ret
     }
@@ -572,6 +1092,8 @@ pub struct WalletContext {
     pub config: PersistedConfig<SuiClientConfig>,
     pub keystore: SuiKeystore,
     pub client: SuiClient,
+    /// Records transactions until they are final, if configured.
+    pub journal: Option<TransactionJournal>,
 }
 
 impl WalletContext {
@@ -582,14 +1104,19 @@ impl WalletContext {
                 config_path
             ))
         })?;
-        let keystore = config.keystore.init()?;
+        if config.checksummed_addresses {
+            set_checksummed_addresses(true);
+        }
+        let keystore = config.init_keystore()?;
         let client = config.client_type.init().await?;
+        let journal = config.init_journal()?;
 
         let config = config.persisted(config_path);
         let context = Self {
             config,
             keystore,
             client,
+            journal,
         };
         Ok(context)
     }
@@ -612,6 +1139,17 @@ impl WalletContext {
         Ok(self.config.active_address.unwrap())
     }
 
+    /// The address of `address` if any, resolving aliases, or else the active address.
+    pub fn address_or_active(
+        &mut self,
+        address: Option<AddressOrAlias>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            Some(address) => self.config.resolve_address(&address),
+            None => self.active_address(),
+        }
+    }
+
     /// Get all the gas objects (and conveniently, gas amounts) for the address
     pub async fn gas_objects(
         &self,
@@ -684,11 +1222,25 @@ impl WalletContext {
         ))
     }
 
-    /// A backward-compatible migration of transaction execution from gateway to fullnode
+    /// Executes `tx`, recording it in the journal until it is final. A transaction which fails
+    /// to execute stays in the journal, to be resumed later.
     pub async fn execute_transaction(
         &self,
         tx: Transaction,
     ) -> anyhow::Result<SuiTransactionResponse> {
+        let tx_digest = *tx.digest();
+        if let Some(journal) = &self.journal {
+            journal.record(&tx)?;
+        }
+        let response = self.submit_transaction(tx).await?;
+        if let Some(journal) = &self.journal {
+            journal.complete(&tx_digest)?;
+        }
+        Ok(response)
+    }
+
+    /// A backward-compatible migration of transaction execution from gateway to fullnode
+    async fn submit_transaction(&self, tx: Transaction) -> anyhow::Result<SuiTransactionResponse> {
         let tx_digest = *tx.digest();
         if self.client.is_gateway() {
             self.client.quorum_driver().execute_transaction(tx).await
@@ -707,6 +1259,7 @@ impl WalletContext {
                     effects,
                 }) => Ok(SuiTransactionResponse {
                     certificate,
+                    memo: effects.effects.memo(),
                     effects: effects.effects,
                     timestamp_ms: None,
                     parsed_data: None,
@@ -756,14 +1309,24 @@ impl Display for SuiClientCommandResult {
                     writeln!(writer, "{}", address)?;
                 }
             }
-            SuiClientCommandResult::Objects(object_refs) => {
+            SuiClientCommandResult::Aliases(aliases) => {
+                writeln!(writer, " {0: ^20} | {1: ^42}", "Alias", "Address")?;
+                writeln!(writer, "{}", ["-"; 65].join(""))?;
+                for (alias, address) in aliases {
+                    writeln!(writer, " {0: ^20} | {1: ^42}", alias, address)?;
+                }
+            }
+            SuiClientCommandResult::Objects(page, alias) => {
+                if let Some(alias) = alias {
+                    writeln!(writer, "Objects of {}", alias)?;
+                }
                 writeln!(
                     writer,
                     " {0: ^42} | {1: ^10} | {2: ^44} | {3: ^15} | {4: ^40}",
                     "Object ID", "Version", "Digest", "Owner Type", "Object Type"
                 )?;
                 writeln!(writer, "{}", ["-"; 165].join(""))?;
-                for oref in object_refs {
+                for oref in &page.data {
                     let owner_type = match oref.owner {
                         Owner::AddressOwner(_) => "AddressOwner",
                         Owner::ObjectOwner(_) => "object_owner",
@@ -780,11 +1343,35 @@ impl Display for SuiClientCommandResult {
                         oref.type_
                     )?
                 }
-                writeln!(writer, "Showing {} results.", object_refs.len())?;
+                writeln!(writer, "Showing {} results.", page.data.len())?;
+                if let Some(cursor) = page.next_cursor {
+                    writeln!(writer, "More objects are listed with --cursor {}", cursor)?;
+                }
             }
             SuiClientCommandResult::SyncClientState => {
                 writeln!(writer, "Client state sync complete.")?;
             }
+            SuiClientCommandResult::Cleanup(report) => {
+                for id in &report.deleted {
+                    writeln!(writer, "Deleted {id}")?;
+                }
+                for (primary, merged) in &report.merged {
+                    writeln!(writer, "Merged {} coins into {primary}", merged.len())?;
+                }
+                for id in &report.skipped {
+                    writeln!(writer, "Skipped {id}")?;
+                }
+                match &report.transaction {
+                    Some(digest) => writeln!(writer, "Cleanup transaction: {digest:?}")?,
+                    None if report.deleted.is_empty() && report.merged.is_empty() => {
+                        writeln!(writer, "Nothing to clean up.")?
+                    }
+                    None => writeln!(writer, "Dry run, no transaction was sent.")?,
+                }
+            }
+            SuiClientCommandResult::WatchEvents => {
+                writeln!(writer, "Event stream closed.")?;
+            }
             SuiClientCommandResult::NewAddress((address, recovery_phrase, scheme)) => {
                 writeln!(
                     writer,
@@ -793,15 +1380,50 @@ impl Display for SuiClientCommandResult {
                 )?;
                 writeln!(writer, "Secret Recovery Phrase : [{recovery_phrase}]")?;
             }
-            SuiClientCommandResult::Gas(gases) => {
+            SuiClientCommandResult::LedgerAddress(address) => {
+                writeln!(
+                    writer,
+                    "Added address [{address}] of the Ledger device, its transactions are signed on the device"
+                )?;
+            }
+            SuiClientCommandResult::Gas(gases, alias) => {
+                if let Some(alias) = alias {
+                    writeln!(writer, "Gas coins of {}", alias)?;
+                }
                 // TODO: generalize formatting of CLI
-                writeln!(writer, " {0: ^42} | {1: ^11}", "Object ID", "Gas Value")?;
                 writeln!(
                     writer,
-                    "----------------------------------------------------------------------"
+                    " {0: ^42} | {1: ^20} | {2: ^22}",
+                    "Object ID", "Gas Value (MIST)", "Gas Value (SUI)"
                 )?;
+                writeln!(writer, "{}", ["-"; 92].join(""))?;
                 for gas in gases {
-                    writeln!(writer, " {0: ^42} | {1: ^11}", gas.id(), gas.value())?;
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: >20} | {2: >#22}",
+                        gas.id(),
+                        gas.value(),
+                        SuiAmount::from_mist(gas.value())
+                    )?;
+                }
+            }
+            SuiClientCommandResult::ValidatorScores(scores) => {
+                writeln!(
+                    writer,
+                    " {0: ^42} | {1: ^20} | {2: ^9} | {3: ^20} | {4: ^7}",
+                    "Validator", "Stake", "Reporters", "Reported Stake", "Slashed"
+                )?;
+                writeln!(writer, "{}", ["-"; 112].join(""))?;
+                for score in scores {
+                    writeln!(
+                        writer,
+                        " {0: ^42} | {1: ^20} | {2: ^9} | {3: ^20} | {4: ^7}",
+                        score.address,
+                        score.stake,
+                        score.reporters.len(),
+                        score.reported_stake,
+                        score.slashed
+                    )?;
                 }
             }
             SuiClientCommandResult::SplitCoin(response) => {
@@ -839,6 +1461,82 @@ impl Display for SuiClientCommandResult {
                 writeln!(writer, "{}\n", "Successfully created an ExampleNFT:".bold())?;
                 writeln!(writer, "{}", object)?;
             }
+            SuiClientCommandResult::Run(report) => {
+                write!(writer, "{}", report)?;
+            }
+            SuiClientCommandResult::Batch(response) => {
+                write!(
+                    writer,
+                    "{}",
+                    write_cert_and_effects(&response.certificate, &response.effects)?
+                )?;
+            }
+            SuiClientCommandResult::TxBuild(data) => {
+                writeln!(writer, "{}", "----- Unsigned Transaction Data ----".bold())?;
+                writeln!(writer, "{}", data)?;
+            }
+            SuiClientCommandResult::TxSign(signed) => {
+                writeln!(writer, "{}", "----- Signed Transaction ----".bold())?;
+                writeln!(writer, "{}", signed)?;
+            }
+            SuiClientCommandResult::TxExecute(response) => {
+                write!(
+                    writer,
+                    "{}",
+                    write_cert_and_effects(&response.certificate, &response.effects)?
+                )?;
+            }
+            SuiClientCommandResult::Resume(resumed) => {
+                writeln!(writer, "Resumed {} transactions.", resumed.len())?;
+                for transaction in resumed {
+                    match &transaction.status {
+                        Some(status) => {
+                            writeln!(writer, "{:?}: executed, {:?}", transaction.digest, status)?
+                        }
+                        None => writeln!(
+                            writer,
+                            "{:?}: not executed, {}",
+                            transaction.digest,
+                            transaction.error.as_deref().unwrap_or_default()
+                        )?,
+                    }
+                }
+            }
+            SuiClientCommandResult::DryRun(effects) => {
+                writeln!(
+                    writer,
+                    "{}",
+                    "----- Predicted Transaction Effects ----".bold()
+                )?;
+                write!(writer, "{}", effects)?;
+                if !effects.events.is_empty() {
+                    writeln!(writer, "Events:")?;
+                    for event in &effects.events {
+                        writeln!(
+                            writer,
+                            "  - {}",
+                            serde_json::to_string(event).map_err(|_| fmt::Error)?
+                        )?;
+                    }
+                }
+                let gas = &effects.gas_used;
+                writeln!(writer, "{}", "----- Gas Cost ----".bold())?;
+                writeln!(writer, "Computation cost: {}", gas.computation_cost)?;
+                writeln!(writer, "Storage cost: {}", gas.storage_cost)?;
+                writeln!(writer, "Storage rebate: {}", gas.storage_rebate)?;
+                // The rebate is only paid back after execution, the budget must cover the rest
+                writeln!(
+                    writer,
+                    "Minimum gas budget: {}",
+                    gas.computation_cost + gas.storage_cost
+                )?;
+                if matches!(effects.status, SuiExecutionStatus::Failure { .. }) {
+                    writeln!(
+                        writer,
+                        "The transaction would fail, executing it would only charge its gas"
+                    )?;
+                }
+            }
         }
         write!(f, "{}", writer.trim_end_matches('\n'))
     }
@@ -887,6 +1585,305 @@ pub async fn call_move(
     Ok((cert, effects))
 }
 
+/// Builds the unsigned transaction data of `kind`, with the sender the signer would have in the
+/// equivalent `sui client` command.
+async fn build_transaction(
+    kind: TxBuildCommand,
+    context: &mut WalletContext,
+) -> Result<TransactionData, anyhow::Error> {
+    Ok(match kind {
+        TxBuildCommand::Transfer {
+            to,
+            object_id,
+            gas,
+            gas_budget,
+            memo,
+            gas_sponsor,
+        } => {
+            let to = context.config.resolve_address(&to)?;
+            let from = context.get_object_owner(&object_id).await?;
+            let gas_sponsor = resolve_gas_sponsor(context, gas_sponsor.as_ref())?;
+            let gas =
+                select_sponsor_gas(context, gas_sponsor, gas, gas_budget, vec![object_id]).await?;
+            let builder = context.client.transaction_builder();
+            let data = match memo {
+                Some(memo) => {
+                    builder
+                        .transfer_object_with_memo(from, object_id, gas, gas_budget, to, memo)
+                        .await?
+                }
+                None => {
+                    builder
+                        .transfer_object(from, object_id, gas, gas_budget, to)
+                        .await?
+                }
+            };
+            with_gas_sponsor(data, gas_sponsor)
+        }
+        TxBuildCommand::TransferSui {
+            to,
+            sui_coin_object_id: object_id,
+            gas_budget,
+            amount,
+            memo,
+        } => {
+            let to = context.config.resolve_address(&to)?;
+            let from = context.get_object_owner(&object_id).await?;
+            let builder = context.client.transaction_builder();
+            match memo {
+                Some(memo) => {
+                    builder
+                        .transfer_sui_with_memo(from, object_id, None, gas_budget, to, amount, memo)
+                        .await?
+                }
+                None => {
+                    builder
+                        .transfer_sui(from, object_id, gas_budget, to, amount)
+                        .await?
+                }
+            }
+        }
+        TxBuildCommand::Call {
+            package,
+            module,
+            function,
+            type_args,
+            args,
+            gas,
+            gas_budget,
+            gas_sponsor,
+        } => {
+            let gas_sponsor = resolve_gas_sponsor(context, gas_sponsor.as_ref())?;
+            // The gas object of a sponsored call belongs to the sponsor, not to the sender
+            let sender = match gas_sponsor {
+                Some(_) => context.active_address()?,
+                None => {
+                    let gas_owner = context.try_get_object_owner(&gas).await?;
+                    gas_owner.unwrap_or(context.active_address()?)
+                }
+            };
+            let gas = select_sponsor_gas(context, gas_sponsor, gas, gas_budget, vec![]).await?;
+            let data = context
+                .client
+                .transaction_builder()
+                .move_call(
+                    sender,
+                    package,
+                    &module,
+                    &function,
+                    type_args
+                        .into_iter()
+                        .map(|arg| arg.try_into())
+                        .collect::<Result<Vec<_>, _>>()?,
+                    args,
+                    gas,
+                    gas_budget,
+                )
+                .await?;
+            with_gas_sponsor(data, gas_sponsor)
+        }
+    })
+}
+
+fn resolve_gas_sponsor(
+    context: &WalletContext,
+    gas_sponsor: Option<&AddressOrAlias>,
+) -> Result<Option<SuiAddress>, anyhow::Error> {
+    gas_sponsor
+        .map(|sponsor| context.config.resolve_address(sponsor))
+        .transpose()
+}
+
+/// The gas object of a transaction paid for by `gas_sponsor`: `gas` if given, else a gas coin of
+/// the sponsor which isn't among `input_objects`.
+async fn select_sponsor_gas(
+    context: &WalletContext,
+    gas_sponsor: Option<SuiAddress>,
+    gas: Option<ObjectID>,
+    gas_budget: u64,
+    input_objects: Vec<ObjectID>,
+) -> Result<Option<ObjectID>, anyhow::Error> {
+    Ok(match (gas_sponsor, gas) {
+        (Some(sponsor), None) => {
+            let (gas, _, _) = context
+                .client
+                .transaction_builder()
+                .select_gas(sponsor, None, gas_budget, input_objects)
+                .await?;
+            Some(gas)
+        }
+        _ => gas,
+    })
+}
+
+fn with_gas_sponsor(data: TransactionData, gas_sponsor: Option<SuiAddress>) -> TransactionData {
+    match gas_sponsor {
+        Some(sponsor) => data.with_gas_sponsor(sponsor),
+        None => data,
+    }
+}
+
+/// The filter of `sui client watch-events`: the events of `package`, of the transactions calling
+/// `module` and of the Move type `event_type` if given.
+pub fn watch_events_filter(
+    package: ObjectID,
+    module: Option<String>,
+    event_type: Option<String>,
+) -> SuiEventFilter {
+    let mut filters = vec![SuiEventFilter::Package(package)];
+    if let Some(module) = module {
+        filters.push(SuiEventFilter::Module(module));
+    }
+    if let Some(event_type) = event_type {
+        // A struct of the package, unless the address is given
+        let event_type = if event_type.matches("::").count() < 2 {
+            format!("{}::{event_type}", package.to_hex_literal())
+        } else {
+            event_type
+        };
+        filters.push(SuiEventFilter::MoveEventType(event_type));
+    }
+    SuiEventFilter::All(filters)
+}
+
+/// Prints the events matching `filter` as they arrive, one table row or JSON line per event.
+/// The subscription is renewed when the connection to the fullnode drops, waiting longer after
+/// each failed attempt, and resumes after the last event printed so none is missed. This only
+/// returns if the first subscription fails.
+pub async fn watch_events(
+    context: &WalletContext,
+    filter: SuiEventFilter,
+    json: bool,
+) -> Result<(), anyhow::Error> {
+    let mut reconnected: Option<SuiClient> = None;
+    let mut cursor = None;
+    let mut subscribed = false;
+    let mut backoff = WATCH_EVENTS_MIN_BACKOFF;
+    if !json {
+        println!(
+            " {0: ^13} | {1: ^44} | {2: ^40} | {3}",
+            "Timestamp", "Transaction", "Event", "Details"
+        );
+        println!("{}", ["-"; 120].join(""));
+    }
+    loop {
+        let client = reconnected.as_ref().unwrap_or(&context.client);
+        match client
+            .event_api()
+            .subscribe_event(filter.clone(), cursor)
+            .await
+        {
+            Ok(mut stream) => {
+                subscribed = true;
+                backoff = WATCH_EVENTS_MIN_BACKOFF;
+                while let Some(item) = stream.next().await {
+                    match item {
+                        Ok(envelope) => {
+                            println!("{}", format_event(&envelope, json)?);
+                            cursor = Some(envelope.id);
+                        }
+                        Err(e) => {
+                            warn!("Event stream failed: {e}");
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) if !subscribed => return Err(e),
+            Err(e) => warn!("Cannot subscribe to events: {e}"),
+        }
+        eprintln!(
+            "{}",
+            format!("Event stream disconnected, reconnecting in {backoff:?}").yellow()
+        );
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(WATCH_EVENTS_MAX_BACKOFF);
+        match context.config.client_type.init().await {
+            Ok(client) => reconnected = Some(client),
+            Err(e) => warn!("Cannot reconnect to the fullnode: {e}"),
+        }
+    }
+}
+
+fn format_event(envelope: &SuiEventEnvelope, json: bool) -> Result<String, anyhow::Error> {
+    if json {
+        return Ok(serde_json::to_string(envelope)?);
+    }
+    let (event, details) = match &envelope.event {
+        SuiEvent::MoveEvent { type_, fields, .. } => (
+            type_.clone(),
+            fields
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?
+                .unwrap_or_default(),
+        ),
+        event => {
+            let value = serde_json::to_value(event)?;
+            match value {
+                // Externally tagged: the variant name and its content
+                serde_json::Value::Object(map) if map.len() == 1 => {
+                    let (name, content) = map.into_iter().next().unwrap();
+                    (name, content.to_string())
+                }
+                value => (String::new(), value.to_string()),
+            }
+        }
+    };
+    Ok(format!(
+        " {0: ^13} | {1: ^44} | {2: ^40} | {3}",
+        envelope.timestamp,
+        envelope
+            .tx_digest
+            .map_or_else(|| "-".to_string(), |digest| format!("{digest:?}")),
+        event,
+        details
+    ))
+}
+
+/// Signs Base64 encoded transaction `data` with the key of `key`, which must be its sender.
+pub fn sign_transaction(
+    keystore: &SuiKeystore,
+    key: SuiAddress,
+    data: &str,
+) -> Result<Transaction, anyhow::Error> {
+    let data = TransactionData::from_signable_bytes(&Base64::decode(data)?)
+        .map_err(|e| anyhow!("Invalid transaction data, expected the output of `tx build`: {e}"))?;
+    if data.signer() != key {
+        return Err(anyhow!(
+            "Transaction is sent by {}, it can't be signed with the key of {}",
+            data.signer(),
+            key
+        ));
+    }
+    let signature = keystore.sign(&key, &data.to_bytes())?;
+    Ok(Transaction::new(data, signature))
+}
+
+/// Adds the signature of `key`, which must be its gas sponsor, to the Base64 encoded `signed`
+/// transaction.
+pub fn sponsor_transaction(
+    keystore: &SuiKeystore,
+    key: SuiAddress,
+    signed: &str,
+) -> Result<Transaction, anyhow::Error> {
+    let transaction: Transaction = bcs::from_bytes(&Base64::decode(signed)?).map_err(|e| {
+        anyhow!("Invalid signed transaction, expected the output of `tx sign`: {e}")
+    })?;
+    let data = &transaction.signed_data.data;
+    match data.gas_sponsor() {
+        Some(sponsor) if sponsor == key => {}
+        Some(sponsor) => {
+            return Err(anyhow!(
+                "Transaction is sponsored by {sponsor}, it can't be signed with the key of {key}"
+            ))
+        }
+        None => return Err(anyhow!("Transaction has no gas sponsor")),
+    }
+    let signature = keystore.sign(&key, &data.to_bytes())?;
+    Ok(transaction.with_sponsor_signature(signature))
+}
+
 fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     match val {
         Some(v) => v,
@@ -894,7 +1891,7 @@ fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     }
 }
 
-fn write_cert_and_effects(
+pub(crate) fn write_cert_and_effects(
     cert: &SuiCertifiedTransaction,
     effects: &SuiTransactionEffects,
 ) -> Result<String, fmt::Error> {
@@ -913,6 +1910,14 @@ impl Debug for SuiClientCommandResult {
                 let object = object_read.object()?;
                 Ok(serde_json::to_string_pretty(&object)?)
             }
+            // Without the alias, which is only displayed
+            SuiClientCommandResult::Objects(page, _) => Ok(serde_json::to_string_pretty(&json!({
+                "Objects": page.data,
+                "nextCursor": page.next_cursor,
+            }))?),
+            SuiClientCommandResult::Gas(coins, _) => {
+                Ok(serde_json::to_string_pretty(&json!({ "Gas": coins }))?)
+            }
             _ => Ok(serde_json::to_string_pretty(self)?),
         });
         write!(f, "{}", s)
@@ -956,15 +1961,51 @@ pub enum SuiClientCommandResult {
     ),
     TransferSui(SuiCertifiedTransaction, SuiTransactionEffects),
     Addresses(Vec<SuiAddress>),
-    Objects(Vec<SuiObjectInfo>),
+    Aliases(BTreeMap<String, SuiAddress>),
+    /// A page of objects, and the alias of their owner if it has one.
+    Objects(SuiObjectsPage, #[serde(skip)] Option<String>),
     SyncClientState,
     NewAddress((SuiAddress, String, SignatureScheme)),
-    Gas(Vec<GasCoin>),
+    LedgerAddress(SuiAddress),
+    Gas(Vec<GasCoin>, #[serde(skip)] Option<String>),
+    ValidatorScores(Vec<ValidatorScore>),
+    Cleanup(CleanupReport),
+    WatchEvents,
     SplitCoin(SuiTransactionResponse),
     MergeCoin(SuiTransactionResponse),
     Switch(SwitchResponse),
     ActiveAddress(Option<SuiAddress>),
     CreateExampleNFT(GetObjectDataResponse),
+    Run(ScriptReport),
+    Batch(SuiTransactionResponse),
+    TxBuild(String),
+    TxSign(String),
+    TxExecute(SuiTransactionResponse),
+    Resume(Vec<ResumedTransaction>),
+    DryRun(SuiTransactionEffects),
+}
+
+/// The peer reports against an active validator during the current epoch.
+#[derive(Serialize, Clone, Debug)]
+pub struct ValidatorScore {
+    pub address: SuiAddress,
+    /// Stake of the validator, including delegation.
+    pub stake: u64,
+    pub reporters: Vec<SuiAddress>,
+    /// Total stake of the reporters, including delegation.
+    pub reported_stake: u64,
+    /// Whether the validator would lose its rewards if the epoch ended now.
+    pub slashed: bool,
+}
+
+/// A transaction of the journal submitted again by `sui client resume`.
+#[derive(Serialize, Clone, Debug)]
+pub struct ResumedTransaction {
+    pub digest: TransactionDigest,
+    /// The status of the executed transaction, now removed from the journal.
+    pub status: Option<SuiExecutionStatus>,
+    /// Why the transaction could not be executed.
+    pub error: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
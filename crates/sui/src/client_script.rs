@@ -0,0 +1,668 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scripts of client operations, run with `sui client run <script.yaml>`.
+//!
+//! A script is a list of steps, each publishing a package, calling a Move function or
+//! transferring an object. Steps can be named, and later steps can use the results of named
+//! steps anywhere a string is expected with `${name.output}` or `${name.output[index]}`:
+//!
+//! ```yaml
+//! steps:
+//!   - name: publish
+//!     publish:
+//!       path: ../move/my_package
+//!       gas_budget: 10000
+//!   - name: mint
+//!     call:
+//!       package: ${publish.package}
+//!       module: my_coin
+//!       function: mint
+//!       args: ["${publish.created[0]}", 100]
+//!       gas_budget: 1000
+//!     expect:
+//!       created: 1
+//! ```
+//!
+//! The outputs of a step are `digest`, `package`, `created`, `mutated`, `deleted` and
+//! `gas_used`. Each step is expected to succeed unless its `expect` section says otherwise, and
+//! the script stops at the first step which does not meet its expectations.
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail};
+use move_package::BuildConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use sui_framework::build_move_package_to_bytes;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionEffects};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::object::Owner;
+use sui_types::parse_sui_type_tag;
+
+use crate::client_commands::WalletContext;
+
+/// Outputs of a step that later steps can refer to.
+const STEP_OUTPUTS: [&str; 6] = [
+    "digest", "package", "created", "mutated", "deleted", "gas_used",
+];
+
+#[derive(Deserialize)]
+struct Script {
+    /// Steps are kept as plain values until the references they contain are resolved.
+    steps: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct ScriptStep {
+    // The name of the step, if any, is read by `validate`.
+    #[serde(flatten)]
+    operation: ScriptOperation,
+    #[serde(default)]
+    expect: Expectations,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ScriptOperation {
+    Publish {
+        /// Path to the Move package, relative to the script
+        path: PathBuf,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    },
+    Call {
+        package: ObjectID,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        args: Vec<Value>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    },
+    Transfer {
+        to: SuiAddress,
+        object_id: ObjectID,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    },
+    TransferSui {
+        to: SuiAddress,
+        sui_coin_object_id: ObjectID,
+        gas_budget: u64,
+        amount: Option<u64>,
+    },
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ExpectedStatus {
+    #[default]
+    Success,
+    Failure,
+}
+
+/// Assertions on the effects of a step.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Expectations {
+    status: ExpectedStatus,
+    /// Number of created objects, not counting a published package
+    created: Option<usize>,
+    /// Number of mutated objects, not counting the gas object
+    mutated: Option<usize>,
+    deleted: Option<usize>,
+    max_gas_used: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct StepOutputs {
+    digest: TransactionDigest,
+    package: Option<ObjectID>,
+    created: Vec<ObjectID>,
+    mutated: Vec<ObjectID>,
+    deleted: Vec<ObjectID>,
+    gas_used: u64,
+}
+
+/// A `${step.output}` or `${step.output[index]}` reference to an output of an earlier step.
+#[derive(Debug, PartialEq, Eq)]
+struct Reference {
+    step: String,
+    output: String,
+    index: Option<usize>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum StepOutcome {
+    Executed,
+    Simulated,
+    /// Skipped by a dry run because the step uses the results of earlier steps.
+    Skipped,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct StepReport {
+    pub name: String,
+    pub operation: String,
+    pub outcome: StepOutcome,
+    pub digest: Option<TransactionDigest>,
+    pub gas_used: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ScriptReport {
+    pub dry_run: bool,
+    pub steps: Vec<StepReport>,
+}
+
+/// Runs the script at `path`. With `dry_run`, every step is built and executed against the
+/// current state of a full node without being committed, so steps using the results of
+/// earlier steps are skipped.
+pub async fn run_script(
+    path: &Path,
+    dry_run: bool,
+    context: &mut WalletContext,
+) -> Result<ScriptReport, anyhow::Error> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read script file {:?}: {e}", path))?;
+    let script: Script = serde_yaml::from_str(&contents)
+        .map_err(|e| anyhow!("Cannot parse script file {:?}: {e}", path))?;
+    let names = validate(&script.steps)?;
+    let script_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut outputs: Vec<(String, Value)> = Vec::new();
+    let mut steps = Vec::new();
+    for (index, (step, name)) in script.steps.iter().zip(names).enumerate() {
+        let references = collect_references(step)?;
+        if dry_run && !references.is_empty() {
+            steps.push(StepReport {
+                name,
+                operation: operation_kind(step),
+                outcome: StepOutcome::Skipped,
+                digest: None,
+                gas_used: None,
+            });
+            continue;
+        }
+        let step: ScriptStep = serde_json::from_value(substitute(step, &outputs)?)
+            .map_err(|e| anyhow!("Invalid step {name}: {e}"))?;
+        let operation = step.operation.kind().to_string();
+
+        let step_outputs = run_step(step, script_dir, dry_run, context)
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "Step {name} ({}/{}) failed after {} executed step(s): {e}",
+                    index + 1,
+                    script.steps.len(),
+                    if dry_run { 0 } else { index },
+                )
+            })?;
+        steps.push(StepReport {
+            name: name.clone(),
+            operation,
+            outcome: if dry_run {
+                StepOutcome::Simulated
+            } else {
+                StepOutcome::Executed
+            },
+            digest: Some(step_outputs.digest),
+            gas_used: Some(step_outputs.gas_used),
+        });
+        outputs.push((name, serde_json::to_value(step_outputs)?));
+    }
+    Ok(ScriptReport { dry_run, steps })
+}
+
+/// Checks step names and references before anything is executed, and returns the name of
+/// each step. Unnamed steps are named after their position but can't be referred to.
+fn validate(steps: &[Value]) -> Result<Vec<String>, anyhow::Error> {
+    let mut named = BTreeSet::new();
+    let mut names = Vec::with_capacity(steps.len());
+    for (index, step) in steps.iter().enumerate() {
+        let name = match step.get("name") {
+            None => format!("#{}", index + 1),
+            Some(Value::String(name)) => {
+                if name.is_empty()
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                {
+                    bail!("Invalid step name {name:?}, use letters, digits, '_' and '-'");
+                }
+                name.clone()
+            }
+            Some(other) => bail!("Step #{} has a non string name {other}", index + 1),
+        };
+        let references = collect_references(step)?;
+        for reference in &references {
+            if !named.contains(&reference.step) {
+                bail!(
+                    "Step {name} refers to {}, which is not an earlier named step",
+                    reference.step
+                );
+            }
+            if !STEP_OUTPUTS.contains(&reference.output.as_str()) {
+                bail!(
+                    "Step {name} refers to unknown output {} of {}, expected one of {}",
+                    reference.output,
+                    reference.step,
+                    STEP_OUTPUTS.join(", ")
+                );
+            }
+        }
+        // Steps which don't depend on earlier ones can be checked in full upfront.
+        if references.is_empty() {
+            serde_json::from_value::<ScriptStep>(step.clone())
+                .map_err(|e| anyhow!("Invalid step {name}: {e}"))?;
+        }
+        if step.get("name").is_some() && !named.insert(name.clone()) {
+            bail!("Duplicate step name {name}");
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+async fn run_step(
+    step: ScriptStep,
+    script_dir: &Path,
+    dry_run: bool,
+    context: &mut WalletContext,
+) -> Result<StepOutputs, anyhow::Error> {
+    let is_publish = matches!(step.operation, ScriptOperation::Publish { .. });
+    let (sender, data) = step.operation.build(script_dir, context).await?;
+    let signature = context.keystore.sign(&sender, &data.to_bytes())?;
+    let transaction = Transaction::new(data, signature);
+    let effects = if dry_run {
+        context
+            .client
+            .full_node_api()
+            .dry_run_transaction(transaction)
+            .await?
+    } else {
+        context.execute_transaction(transaction).await?.effects
+    };
+    let outputs = StepOutputs::new(&effects, is_publish);
+    step.expect.check(&effects, &outputs)?;
+    Ok(outputs)
+}
+
+impl ScriptOperation {
+    fn kind(&self) -> &'static str {
+        match self {
+            ScriptOperation::Publish { .. } => "publish",
+            ScriptOperation::Call { .. } => "call",
+            ScriptOperation::Transfer { .. } => "transfer",
+            ScriptOperation::TransferSui { .. } => "transfer_sui",
+        }
+    }
+
+    /// Builds the transaction data of the operation, and returns it with its sender.
+    async fn build(
+        self,
+        script_dir: &Path,
+        context: &mut WalletContext,
+    ) -> Result<(SuiAddress, TransactionData), anyhow::Error> {
+        Ok(match self {
+            ScriptOperation::Publish {
+                path,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let compiled_modules =
+                    build_move_package_to_bytes(&script_dir.join(path), BuildConfig::default())?;
+                let data = context
+                    .client
+                    .transaction_builder()
+                    .publish(sender, compiled_modules, gas, gas_budget)
+                    .await?;
+                (sender, data)
+            }
+            ScriptOperation::Call {
+                package,
+                module,
+                function,
+                type_args,
+                args,
+                gas,
+                gas_budget,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let type_args = type_args
+                    .iter()
+                    .map(|arg| parse_sui_type_tag(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let args = args
+                    .into_iter()
+                    .map(SuiJsonValue::new)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let data = context
+                    .client
+                    .transaction_builder()
+                    .move_call(
+                        sender,
+                        package,
+                        &module,
+                        &function,
+                        type_args
+                            .into_iter()
+                            .map(|arg| arg.try_into())
+                            .collect::<Result<Vec<_>, _>>()?,
+                        args,
+                        gas,
+                        gas_budget,
+                    )
+                    .await?;
+                (sender, data)
+            }
+            ScriptOperation::Transfer {
+                to,
+                object_id,
+                gas,
+                gas_budget,
+            } => {
+                let from = context.get_object_owner(&object_id).await?;
+                let data = context
+                    .client
+                    .transaction_builder()
+                    .transfer_object(from, object_id, gas, gas_budget, to)
+                    .await?;
+                (from, data)
+            }
+            ScriptOperation::TransferSui {
+                to,
+                sui_coin_object_id,
+                gas_budget,
+                amount,
+            } => {
+                let from = context.get_object_owner(&sui_coin_object_id).await?;
+                let data = context
+                    .client
+                    .transaction_builder()
+                    .transfer_sui(from, sui_coin_object_id, gas_budget, to, amount)
+                    .await?;
+                (from, data)
+            }
+        })
+    }
+}
+
+impl StepOutputs {
+    fn new(effects: &SuiTransactionEffects, is_publish: bool) -> Self {
+        // Packages are the only immutable objects a publish creates.
+        let package = if is_publish {
+            effects
+                .created
+                .iter()
+                .find(|o| o.owner == Owner::Immutable)
+                .map(|o| o.reference.object_id)
+        } else {
+            None
+        };
+        let gas = &effects.gas_used;
+        Self {
+            digest: effects.transaction_digest,
+            package,
+            created: effects
+                .created
+                .iter()
+                .map(|o| o.reference.object_id)
+                .filter(|id| Some(*id) != package)
+                .collect(),
+            mutated: effects
+                .mutated_excluding_gas()
+                .map(|o| o.reference.object_id)
+                .collect(),
+            deleted: effects.deleted.iter().map(|o| o.object_id).collect(),
+            gas_used: (gas.computation_cost + gas.storage_cost).saturating_sub(gas.storage_rebate),
+        }
+    }
+}
+
+impl Expectations {
+    fn check(
+        &self,
+        effects: &SuiTransactionEffects,
+        outputs: &StepOutputs,
+    ) -> Result<(), anyhow::Error> {
+        match (&self.status, &effects.status) {
+            (ExpectedStatus::Success, SuiExecutionStatus::Failure { error }) => {
+                bail!("Transaction {:?} failed: {error}", outputs.digest)
+            }
+            (ExpectedStatus::Failure, SuiExecutionStatus::Success) => {
+                bail!(
+                    "Transaction {:?} succeeded but was expected to fail",
+                    outputs.digest
+                )
+            }
+            _ => {}
+        }
+        let counts = [
+            ("created", self.created, outputs.created.len()),
+            ("mutated", self.mutated, outputs.mutated.len()),
+            ("deleted", self.deleted, outputs.deleted.len()),
+        ];
+        for (what, expected, actual) in counts {
+            if let Some(expected) = expected {
+                if expected != actual {
+                    bail!("Expected {expected} {what} object(s), got {actual}");
+                }
+            }
+        }
+        if let Some(max_gas_used) = self.max_gas_used {
+            if outputs.gas_used > max_gas_used {
+                bail!(
+                    "Used {} gas, more than the expected maximum of {max_gas_used}",
+                    outputs.gas_used
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Name of the operation of a step which may not be fully resolved yet.
+fn operation_kind(step: &Value) -> String {
+    step.as_object()
+        .and_then(|fields| {
+            fields
+                .keys()
+                .find(|key| !matches!(key.as_str(), "name" | "expect"))
+        })
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Positions and contents of the references in `s`.
+fn parse_references(s: &str) -> Result<Vec<(Range<usize>, Reference)>, anyhow::Error> {
+    let mut references = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = s[offset..].find("${").map(|i| offset + i) {
+        let end = s[start..]
+            .find('}')
+            .map(|i| start + i)
+            .ok_or_else(|| anyhow!("Unterminated reference in {s:?}"))?;
+        references.push((start..end + 1, s[start + 2..end].parse()?));
+        offset = end + 1;
+    }
+    Ok(references)
+}
+
+fn collect_references(value: &Value) -> Result<Vec<Reference>, anyhow::Error> {
+    Ok(match value {
+        Value::String(s) => parse_references(s)?
+            .into_iter()
+            .map(|(_, reference)| reference)
+            .collect(),
+        Value::Array(values) => values
+            .iter()
+            .map(collect_references)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect(),
+        Value::Object(fields) => fields
+            .values()
+            .map(collect_references)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect(),
+        _ => vec![],
+    })
+}
+
+/// Replaces the references in `value` with the outputs of earlier steps. A string made of a
+/// single reference is replaced by the output itself, so that it can be a number or a list.
+fn substitute(value: &Value, outputs: &[(String, Value)]) -> Result<Value, anyhow::Error> {
+    Ok(match value {
+        Value::String(s) => {
+            let references = parse_references(s)?;
+            match references.as_slice() {
+                [] => value.clone(),
+                [(range, reference)] if range.len() == s.len() => {
+                    reference.resolve(outputs)?.clone()
+                }
+                _ => {
+                    let mut resolved = String::new();
+                    let mut offset = 0;
+                    for (range, reference) in &references {
+                        resolved.push_str(&s[offset..range.start]);
+                        match reference.resolve(outputs)? {
+                            Value::String(output) => resolved.push_str(output),
+                            Value::Number(output) => write!(resolved, "{output}")?,
+                            _ => bail!("{reference} can't be used within a string"),
+                        }
+                        offset = range.end;
+                    }
+                    resolved.push_str(&s[offset..]);
+                    Value::String(resolved)
+                }
+            }
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| substitute(value, outputs))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), substitute(value, outputs)?)))
+                .collect::<Result<Map<_, _>, anyhow::Error>>()?,
+        ),
+        _ => value.clone(),
+    })
+}
+
+impl Reference {
+    fn resolve<'a>(&self, outputs: &'a [(String, Value)]) -> Result<&'a Value, anyhow::Error> {
+        let (_, step_outputs) = outputs
+            .iter()
+            .find(|(name, _)| *name == self.step)
+            .ok_or_else(|| anyhow!("{self} refers to a step which has not run"))?;
+        let output = match step_outputs.get(&self.output) {
+            Some(Value::Null) | None => bail!("Step {} has no output {}", self.step, self.output),
+            Some(output) => output,
+        };
+        match self.index {
+            None => Ok(output),
+            Some(index) => output.get(index).ok_or_else(|| {
+                anyhow!(
+                    "{self} is out of bounds, step {} has {} {} object(s)",
+                    self.step,
+                    output.as_array().map_or(0, |values| values.len()),
+                    self.output
+                )
+            }),
+        }
+    }
+}
+
+impl std::str::FromStr for Reference {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || anyhow!("Invalid reference ${{{s}}}, expected ${{step.output[index]}}");
+        let (step, output) = s.trim().split_once('.').ok_or_else(invalid)?;
+        let (output, index) = match output.split_once('[') {
+            None => (output, None),
+            Some((output, index)) => {
+                let index = index.strip_suffix(']').ok_or_else(invalid)?;
+                (output, Some(index.parse().map_err(|_| invalid())?))
+            }
+        };
+        if step.is_empty() || output.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            step: step.to_string(),
+            output: output.to_string(),
+            index,
+        })
+    }
+}
+
+impl Display for Reference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${{{}.{}", self.step, self.output)?;
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for ScriptReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        writeln!(
+            writer,
+            " {0: ^20} | {1: ^12} | {2: ^10} | {3: ^46} | {4: ^10}",
+            "Step", "Operation", "Outcome", "Digest", "Gas Used"
+        )?;
+        writeln!(writer, "{}", ["-"; 110].join(""))?;
+        for step in &self.steps {
+            let outcome = match step.outcome {
+                StepOutcome::Executed => "executed",
+                StepOutcome::Simulated => "simulated",
+                StepOutcome::Skipped => "skipped",
+            };
+            writeln!(
+                writer,
+                " {0: ^20} | {1: ^12} | {2: ^10} | {3: ^46} | {4: ^10}",
+                step.name,
+                step.operation,
+                outcome,
+                step.digest.map(|d| format!("{d:?}")).unwrap_or_default(),
+                step.gas_used.map(|g| g.to_string()).unwrap_or_default(),
+            )?;
+        }
+        if self.dry_run {
+            writeln!(
+                writer,
+                "Dry run, nothing was executed. Skipped steps use the results of earlier steps."
+            )?;
+        }
+        write!(f, "{}", writer)
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/client_script_tests.rs"]
+mod client_script_tests;
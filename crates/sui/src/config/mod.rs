@@ -4,8 +4,12 @@
 
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Write};
-use sui_sdk::crypto::KeystoreType;
+use std::path::PathBuf;
+use std::str::FromStr;
+use sui_sdk::crypto::{KeystoreType, SignerConfig, SuiKeystore};
+use sui_sdk::journal::TransactionJournal;
 use sui_types::base_types::*;
 
 pub use sui_config::Config;
@@ -20,10 +24,114 @@ pub struct SuiClientConfig {
     pub keystore: KeystoreType,
     pub client_type: ClientType,
     pub active_address: Option<SuiAddress>,
+    /// Addresses whose transactions are signed outside of the keystore, e.g.
+    /// `ledger:m/44'/784'/0'/0'/0'` for the key at that path of a Ledger device.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub signers: BTreeMap<SuiAddress, SignerConfig>,
+    /// Directory where transactions are recorded before they are submitted, so that those
+    /// interrupted by a crash can be finished with `sui client resume`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub journal: Option<PathBuf>,
+    /// Names of addresses, accepted by the client commands in place of the addresses.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, SuiAddress>,
+    /// Print addresses, object IDs and transaction digests in a checksummed form, whose mixed
+    /// case catches typos when they are pasted back into commands.
+    #[serde(default)]
+    pub checksummed_addresses: bool,
 }
 
 impl Config for SuiClientConfig {}
 
+impl SuiClientConfig {
+    /// The keystore, signing for the addresses of the signers as well.
+    pub fn init_keystore(&self) -> Result<SuiKeystore, anyhow::Error> {
+        let mut keystore = self.keystore.init()?;
+        for (address, signer) in &self.signers {
+            keystore.add_signer(*address, signer.init());
+        }
+        Ok(keystore)
+    }
+
+    pub fn init_journal(&self) -> Result<Option<TransactionJournal>, anyhow::Error> {
+        self.journal
+            .as_ref()
+            .map(TransactionJournal::open)
+            .transpose()
+    }
+
+    pub fn resolve_address(&self, address: &AddressOrAlias) -> Result<SuiAddress, anyhow::Error> {
+        match address {
+            AddressOrAlias::Address(address) => Ok(*address),
+            AddressOrAlias::Alias(alias) => self
+                .aliases
+                .get(alias)
+                .copied()
+                .ok_or_else(|| anyhow::anyhow!("Unknown address alias {alias}")),
+        }
+    }
+
+    /// The first alias of `address`, if any.
+    pub fn alias_of(&self, address: SuiAddress) -> Option<&str> {
+        self.aliases
+            .iter()
+            .find(|(_, aliased)| **aliased == address)
+            .map(|(alias, _)| alias.as_str())
+    }
+}
+
+/// An address, or its alias in the client config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AddressOrAlias {
+    Address(SuiAddress),
+    Alias(String),
+}
+
+impl AddressOrAlias {
+    /// Aliases start with a letter, followed by letters, digits, `-` or `_`, so that they can't
+    /// be mistaken for addresses.
+    pub fn validate_alias(alias: &str) -> Result<(), anyhow::Error> {
+        let mut chars = alias.chars();
+        let valid = chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && SuiAddress::from_str(alias).is_err();
+        if !valid {
+            anyhow::bail!(
+                "Invalid address or alias {alias:?}, aliases start with a letter followed by \
+                 letters, digits, '-' or '_'"
+            );
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for AddressOrAlias {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = SuiAddress::from_str(s) {
+            return Ok(Self::Address(address));
+        }
+        Self::validate_alias(s)?;
+        Ok(Self::Alias(s.to_string()))
+    }
+}
+
+impl From<SuiAddress> for AddressOrAlias {
+    fn from(address: SuiAddress) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl Display for AddressOrAlias {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{}", address),
+            Self::Alias(alias) => write!(f, "{}", alias),
+        }
+    }
+}
+
 impl Display for SuiClientConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
@@ -31,7 +139,7 @@ impl Display for SuiClientConfig {
         writeln!(
             writer,
             "Managed addresses : {}",
-            self.keystore.init().unwrap().addresses().len()
+            self.init_keystore().unwrap().addresses().len()
         )?;
         write!(writer, "Active address: ")?;
         match self.active_address {
@@ -146,8 +146,9 @@ async fn handle_command(
                 cache.insert(CacheKey::flag("--address"), addresses.clone());
                 cache.insert(CacheKey::flag("--to"), addresses);
             }
-            SuiClientCommandResult::Objects(ref objects) => {
-                let objects = objects
+            SuiClientCommandResult::Objects(ref page, _) => {
+                let objects = page
+                    .data
                     .iter()
                     .map(|oref| format!("{}", oref.object_id))
                     .collect::<Vec<_>>();
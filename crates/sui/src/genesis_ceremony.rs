@@ -9,6 +9,7 @@ use signature::{Signer, Verifier};
 use std::{fs, path::PathBuf};
 use sui_config::{
     genesis::{Builder, Genesis},
+    utils::check_peer_address,
     SUI_GENESIS_FILENAME,
 };
 use sui_types::{
@@ -111,6 +112,14 @@ pub fn run(cmd: Ceremony) -> Result<()> {
             narwhal_worker_address,
             narwhal_consensus_address,
         } => {
+            for address in [
+                &network_address,
+                &narwhal_primary_address,
+                &narwhal_worker_address,
+                &narwhal_consensus_address,
+            ] {
+                check_peer_address(address)?;
+            }
             let mut builder = Builder::load(&dir)?;
             let keypair: AuthorityKeyPair = read_authority_keypair_from_file(validator_key_file)?;
             let account_keypair: SuiKeyPair = read_keypair_from_file(account_key_file)?;
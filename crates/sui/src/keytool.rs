@@ -18,8 +18,11 @@ use sui_types::base_types::SuiAddress;
 use sui_types::base_types::{decode_bytes_hex, encode_bytes_hex};
 use sui_types::crypto::{
     derive_key_pair_from_path, get_key_pair, AuthorityKeyPair, Ed25519SuiSignature,
-    EncodeDecodeBase64, NetworkKeyPair, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    EncodeDecodeBase64, NetworkKeyPair, PublicKey, SignableBytes, Signature, SignatureScheme,
+    SuiKeyPair, SuiSignatureInner,
 };
+use sui_types::messages::{Transaction, TransactionData};
+use sui_types::multisig::{MultiSigPublicKey, MultiSigSuiSignature};
 use sui_types::sui_serde::{Base64, Encoding};
 
 #[cfg(test)]
@@ -55,11 +58,59 @@ pub enum KeyToolCommand {
     },
     /// Import mnemonic phrase and generate keypair based on key scheme flag {ed25519 | secp256k1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or m/54'/784'/0'/0/0 for secp256k1.
+    /// The phrase is kept next to the keystore so that it can be exported again.
+    #[clap(name = "import-mnemonic", alias = "import")]
     Import {
         mnemonic_phrase: String,
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
     },
+    /// Print the mnemonic phrase, key scheme and derivation path which recover the key of an
+    /// address, for keys generated by `sui client new-address` or imported from a mnemonic.
+    ExportMnemonic {
+        #[clap(long, parse(try_from_str = decode_bytes_hex))]
+        address: SuiAddress,
+    },
+    /// Print the address of a multisig, owned by the keys of the given Base64 public keys, as
+    /// listed by `list`, of which any `threshold` must sign its transactions. The order of the
+    /// keys is part of the address.
+    MultiSigAddress {
+        #[clap(long)]
+        threshold: u16,
+        #[clap(
+            long,
+            multiple_occurrences = false,
+            multiple_values = true,
+            parse(try_from_str = parse_public_key)
+        )]
+        pks: Vec<PublicKey>,
+    },
+    /// Combine the partial signatures of keys of a multisig, as printed by `sign` for the
+    /// transaction data of `sui client tx build`, into the signature of the multisig address.
+    /// Given the transaction data, also check the signature and print the signed transaction
+    /// for `sui client tx execute`.
+    MultiSigCombinePartialSig {
+        #[clap(long)]
+        threshold: u16,
+        #[clap(
+            long,
+            multiple_occurrences = false,
+            multiple_values = true,
+            parse(try_from_str = parse_public_key)
+        )]
+        pks: Vec<PublicKey>,
+        /// Base64 encoded serialized signatures, flag || signature || public key
+        #[clap(
+            long,
+            multiple_occurrences = false,
+            multiple_values = true,
+            parse(try_from_str = parse_signature)
+        )]
+        sigs: Vec<Signature>,
+        /// Base64 encoded transaction data, as printed by `sui client tx build`
+        #[clap(long)]
+        tx_data: Option<String>,
+    },
     /// This is a temporary helper function to ensure that testnet genesis does not break while
     /// we transition towards BLS signatures.
     LoadKeypair {
@@ -128,6 +179,7 @@ impl KeyToolCommand {
                 info!("Address : {}", address);
                 let message = Base64::decode(&data).map_err(|e| anyhow!(e))?;
                 let signature = keystore.sign(&address, &message)?;
+                let serialized = signature.as_ref().to_vec();
                 // Separate pub key and signature string, signature and pub key are concatenated with an '@' symbol.
                 let signature_string = format!("{:?}", signature);
                 let sig_split = signature_string.split('@').collect::<Vec<_>>();
@@ -143,6 +195,10 @@ impl KeyToolCommand {
                 info!("Flag Base64: {}", flag);
                 info!("Public Key Base64: {}", pub_key);
                 info!("Signature : {}", signature);
+                info!(
+                    "Serialized signature (flag || signature || public key) Base64: {}",
+                    Base64::encode(serialized)
+                );
             }
             KeyToolCommand::Import {
                 mnemonic_phrase,
@@ -153,6 +209,54 @@ impl KeyToolCommand {
                     keystore.import_from_mnemonic(&mnemonic_phrase, key_scheme, derivation_path)?;
                 info!("Key imported for address [{address}]");
             }
+            KeyToolCommand::ExportMnemonic { address } => {
+                let mnemonic = keystore.export_mnemonic(&address)?;
+                println!("Mnemonic phrase : {}", mnemonic.phrase);
+                println!("Key scheme : {}", mnemonic.key_scheme);
+                println!(
+                    "Derivation path : {}",
+                    mnemonic
+                        .derivation_path
+                        .as_deref()
+                        .unwrap_or("default of the key scheme")
+                );
+            }
+            KeyToolCommand::MultiSigAddress { threshold, pks } => {
+                let multisig_pk = MultiSigPublicKey::new(pks, threshold)?;
+                println!("MultiSig address: {}", SuiAddress::from(&multisig_pk));
+                for pk in multisig_pk.public_keys() {
+                    println!(
+                        "Participant: {} (Public Key Base64: {})",
+                        SuiAddress::from(pk),
+                        pk.encode_base64()
+                    );
+                }
+            }
+            KeyToolCommand::MultiSigCombinePartialSig {
+                threshold,
+                pks,
+                sigs,
+                tx_data,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(pks, threshold)?;
+                let address = SuiAddress::from(&multisig_pk);
+                let signature: Signature = MultiSigSuiSignature::combine(multisig_pk, sigs)?.into();
+                println!("MultiSig address: {}", address);
+                println!(
+                    "MultiSig serialized signature Base64: {}",
+                    Base64::encode(signature.as_ref())
+                );
+                if let Some(tx_data) = tx_data {
+                    let data = TransactionData::from_signable_bytes(&Base64::decode(&tx_data)?)
+                        .map_err(|e| anyhow!("Invalid transaction data: {e}"))?;
+                    let transaction = Transaction::new(data, signature);
+                    transaction.verify()?;
+                    println!(
+                        "Signed transaction Base64: {}",
+                        Base64::encode(bcs::to_bytes(&transaction)?)
+                    );
+                }
+            }
 
             KeyToolCommand::LoadKeypair { file } => {
                 let res: Result<SuiKeyPair, anyhow::Error> = read_keypair_from_file(&file);
@@ -176,6 +280,16 @@ impl KeyToolCommand {
     }
 }
 
+fn parse_public_key(s: &str) -> Result<PublicKey, anyhow::Error> {
+    PublicKey::decode_base64(s).map_err(|e| anyhow!("Invalid Base64 public key {s}: {e}"))
+}
+
+fn parse_signature(s: &str) -> Result<Signature, anyhow::Error> {
+    let bytes = Base64::decode(s).map_err(|e| anyhow!("Invalid Base64 signature {s}: {e}"))?;
+    <Signature as signature::Signature>::from_bytes(&bytes)
+        .map_err(|e| anyhow!("Invalid signature {s}: {e}"))
+}
+
 fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
     let path_str = format!("{}.key", address).to_lowercase();
     let path = Path::new(&path_str);
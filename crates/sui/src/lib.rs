@@ -2,12 +2,16 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod client_batch;
+pub mod client_cleanup;
 pub mod client_commands;
+pub mod client_script;
 pub mod config;
 pub mod console;
 pub mod keytool;
 pub mod shell;
 pub mod sui_commands;
 pub mod sui_move;
+pub mod validator_commands;
 
 pub mod genesis_ceremony;
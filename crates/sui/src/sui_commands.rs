@@ -1,12 +1,16 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::client_commands::{SuiClientCommands, WalletContext};
+use crate::client_commands::{
+    sign_transaction, sponsor_transaction, watch_events, watch_events_filter,
+    SuiClientCommandResult, SuiClientCommands, TxCommand, WalletContext,
+};
 use crate::config::SuiClientConfig;
 use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
 use crate::keytool::KeyToolCommand;
 use crate::sui_move::{self, execute_move_command};
+use crate::validator_commands::SuiValidatorCommand;
 use move_package::BuildConfig;
 use std::io::{stderr, stdout, Write};
 use std::num::NonZeroUsize;
@@ -15,6 +19,7 @@ use std::{fs, io};
 
 use anyhow::{anyhow, bail};
 use clap::*;
+use clap_complete::{generate, Shell};
 use tracing::info;
 
 use sui_config::gateway::GatewayConfig;
@@ -24,10 +29,12 @@ use sui_config::{
     sui_config_dir, Config, PersistedConfig, SUI_CLIENT_CONFIG, SUI_FULLNODE_CONFIG,
     SUI_GATEWAY_CONFIG, SUI_NETWORK_CONFIG,
 };
+use sui_core::bcs_schema::bcs_schema;
 use sui_sdk::crypto::KeystoreType;
 use sui_sdk::ClientType;
 use sui_swarm::memory::Swarm;
 use sui_types::crypto::{KeypairTraits, SignatureScheme, SuiKeyPair};
+use sui_types::sui_serde::{Base64, Encoding};
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Parser)]
@@ -97,6 +104,18 @@ pub enum SuiCommand {
         json: bool,
     },
 
+    /// Commands run by validators to manage their validator.
+    #[clap(name = "validator")]
+    Validator {
+        /// Sets the file storing the state of our user accounts (an empty one will be created if missing)
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+        #[clap(subcommand)]
+        cmd: SuiValidatorCommand,
+        /// Return command outputs in json format.
+        #[clap(long, global = true)]
+        json: bool,
+    },
     /// Tool to build and test Move applications.
     #[clap(name = "move")]
     Move {
@@ -110,6 +129,29 @@ pub enum SuiCommand {
         #[clap(subcommand)]
         cmd: sui_move::Command,
     },
+
+    /// Print a completion script for a shell: bash, zsh, fish, powershell or elvish.
+    #[clap(name = "completions")]
+    Completions {
+        shell: Shell,
+        /// Client config whose aliases and addresses are completed as address arguments
+        #[clap(long = "client.config")]
+        config: Option<PathBuf>,
+    },
+
+    /// Print the BCS schema of the transactions, objects, effects and events, to generate or
+    /// check the BCS layouts of the client SDKs.
+    #[clap(name = "generate-schema")]
+    GenerateSchema {
+        #[clap(long, arg_enum, default_value = "yaml")]
+        format: SchemaFormat,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ArgEnum)]
+pub enum SchemaFormat {
+    Yaml,
+    Json,
 }
 
 impl SuiCommand {
@@ -287,6 +329,10 @@ impl SuiCommand {
                     keystore: KeystoreType::File(keystore_path),
                     client_type: ClientType::Embedded(wallet_gateway_config),
                     active_address,
+                    signers: Default::default(),
+                    journal: None,
+                    aliases: Default::default(),
+                    checksummed_addresses: false,
                 };
 
                 wallet_config.save(&client_path)?;
@@ -336,8 +382,41 @@ impl SuiCommand {
                     config.save()?;
                 }
 
+                // Signing happens on machines which may not reach the network, so it only loads the
+                // keystore.
+                if let Some(SuiClientCommands::Tx {
+                    cmd: cmd @ (TxCommand::Sign { .. } | TxCommand::Sponsor { .. }),
+                }) = &cmd
+                {
+                    let config: SuiClientConfig = PersistedConfig::read(&config_path)?;
+                    let keystore = config.init_keystore()?;
+                    let transaction = match cmd {
+                        TxCommand::Sign { key, data } => {
+                            sign_transaction(&keystore, config.resolve_address(key)?, data)?
+                        }
+                        TxCommand::Sponsor { key, signed } => {
+                            sponsor_transaction(&keystore, config.resolve_address(key)?, signed)?
+                        }
+                        _ => unreachable!(),
+                    };
+                    SuiClientCommandResult::TxSign(Base64::encode(bcs::to_bytes(&transaction)?))
+                        .print(!json);
+                    return Ok(());
+                }
+
                 let mut context = WalletContext::new(&config_path).await?;
 
+                // Events are printed as they arrive, as JSON lines with --json.
+                if let Some(SuiClientCommands::WatchEvents {
+                    package,
+                    module,
+                    event_type,
+                }) = cmd
+                {
+                    let filter = watch_events_filter(package, module, event_type);
+                    return watch_events(&context, filter, json).await;
+                }
+
                 if let Some(cmd) = cmd {
                     // Do not sync if command is a gateway switch, as the current gateway might be unreachable and causes sync to panic.
                     if !matches!(cmd, SuiClientCommands::Switch { rpc: Some(_), .. }) {
@@ -352,12 +431,84 @@ impl SuiCommand {
                 }
                 Ok(())
             }
+            SuiCommand::Validator { config, cmd, json } => {
+                let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                let mut context = WalletContext::new(&config_path).await?;
+                sync_accounts(&mut context).await?;
+                cmd.execute(&mut context).await?.print(!json);
+                Ok(())
+            }
             SuiCommand::Move {
                 package_path,
                 build_config,
                 cmd,
-            } => execute_move_command(package_path, build_config, cmd),
+            } => execute_move_command(package_path, build_config, cmd).await,
+            SuiCommand::Completions { shell, config } => {
+                let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
+                write_completions(shell, &config, &mut stdout());
+                Ok(())
+            }
+            SuiCommand::GenerateSchema { format } => {
+                let registry =
+                    bcs_schema().map_err(|e| anyhow!("Failed to trace the BCS schema: {e}"))?;
+                let schema = match format {
+                    SchemaFormat::Yaml => serde_yaml::to_string(&registry)?,
+                    SchemaFormat::Json => serde_json::to_string_pretty(&registry)?,
+                };
+                println!("{schema}");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Writes the completion script of `shell` to `out`, completing the aliases and addresses of the
+/// client config at `config` as address arguments.
+pub fn write_completions(shell: Shell, config: &Path, out: &mut dyn Write) {
+    let addresses = known_addresses(config);
+    let mut app: Command = SuiCommand::command();
+    if let Some(client) = app.find_subcommand_mut("client") {
+        add_address_hints(client, &addresses);
+    }
+    generate(shell, &mut app, "sui", out);
+}
+
+/// The client arguments taking an address or an alias.
+const ADDRESS_ARGS: [&str; 3] = ["address", "to", "key"];
+
+/// The aliases and addresses of the client config at `path`, suggested by the completion
+/// scripts. They are read when the script is generated, so the script has to be generated again
+/// to complete the aliases and addresses added later.
+fn known_addresses(path: &Path) -> Vec<String> {
+    let config: SuiClientConfig = match PersistedConfig::read(path) {
+        Ok(config) => config,
+        Err(_) => return vec![],
+    };
+    let mut addresses: Vec<_> = config.aliases.keys().cloned().collect();
+    if let Ok(keystore) = config.init_keystore() {
+        addresses.extend(
+            keystore
+                .addresses()
+                .iter()
+                .map(|address| address.to_string()),
+        );
+    }
+    addresses
+}
+
+fn add_address_hints<'a>(cmd: &mut Command<'a>, addresses: &'a [String]) {
+    if addresses.is_empty() {
+        return;
+    }
+    for sub in cmd.get_subcommands_mut() {
+        for name in ADDRESS_ARGS {
+            if sub.get_arguments().any(|arg| arg.get_id() == name) {
+                *sub = std::mem::take(sub).mut_arg(name, |arg| {
+                    arg.possible_values(addresses.iter().map(String::as_str))
+                });
+            }
         }
+        add_address_hints(sub, addresses);
     }
 }
 
@@ -365,7 +516,7 @@ impl SuiCommand {
 async fn sync_accounts(context: &mut WalletContext) -> Result<(), anyhow::Error> {
     for address in context.keystore.addresses().clone() {
         SuiClientCommands::SyncClientState {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await?;
@@ -423,6 +574,10 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
                 keystore,
                 client_type: client,
                 active_address: Some(new_address),
+                signers: Default::default(),
+                journal: None,
+                aliases: Default::default(),
+                checksummed_addresses: false,
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -1,10 +1,25 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use clap::Parser;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_binary_format::CompiledModule;
 use move_cli::base::coverage;
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_coverage::coverage_map::{CoverageMap, ExecCoverageMap};
+use move_coverage::source_coverage::SourceCoverageBuilder;
+use move_coverage::summary::summarize_inst_cov;
+use move_package::compilation::compiled_package::CompiledPackage;
 use move_package::BuildConfig;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The file `sui move test --coverage` writes the coverage map of the package to.
+const COVERAGE_MAP_FILE: &str = ".coverage_map.mvcov";
 
 #[derive(Parser)]
 pub struct Coverage {
@@ -18,3 +33,143 @@ impl Coverage {
         Ok(())
     }
 }
+
+/// The coverage collected by the last `sui move test --coverage` run of the package at `path`,
+/// with the package compiled for it.
+pub struct CoverageReport {
+    package: CompiledPackage,
+    coverage_map: CoverageMap,
+}
+
+impl CoverageReport {
+    pub fn load(path: &Path, build_config: BuildConfig) -> anyhow::Result<Self> {
+        let coverage_map = CoverageMap::from_binary_file(path.join(COVERAGE_MAP_FILE))
+            .map_err(|e| anyhow!("Cannot read the coverage map of {}: {e}", path.display()))?;
+        let package = build_config.compile_package(path, &mut Vec::new())?;
+        Ok(Self {
+            package,
+            coverage_map,
+        })
+    }
+
+    /// Writes the instruction coverage of each module and function, and the total.
+    pub fn write_summary<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        let exec_map = self.coverage_map.to_unified_exec_map();
+        let (mut total, mut covered) = (0, 0);
+        for (unit, _) in self.root_modules() {
+            let (module_total, module_covered) =
+                summarize_inst_cov(&unit.module, &exec_map).summarize_human(writer, true)?;
+            total += module_total;
+            covered += module_covered;
+        }
+        let percentage = if total == 0 {
+            100.0
+        } else {
+            covered as f64 / total as f64 * 100.0
+        };
+        writeln!(writer, "+-------------------------+")?;
+        writeln!(writer, "| % Move Coverage: {percentage:.2}  |")?;
+        writeln!(writer, "+-------------------------+")?;
+        Ok(())
+    }
+
+    /// Writes the sources of the modules, with the code which never ran highlighted.
+    pub fn write_sources<W: Write>(&self, writer: &mut W) -> anyhow::Result<()> {
+        for (unit, source_path) in self.root_modules() {
+            writeln!(writer, "// {}", source_path.display())?;
+            SourceCoverageBuilder::new(&unit.module, &self.coverage_map, &unit.source_map)
+                .compute_source_coverage(source_path)
+                .output_source_coverage(writer)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// The function and line coverage of the sources in the lcov tracefile format, where a line
+    /// counts as run as many times as its most run instruction.
+    pub fn lcov(&self) -> anyhow::Result<String> {
+        let exec_map = self.coverage_map.to_unified_exec_map();
+        let mut lcov = String::new();
+        for (unit, source_path) in self.root_modules() {
+            let module = &unit.module;
+            let source = fs::read_to_string(source_path)?;
+            let line_of = |offset: u32| source[..offset as usize].matches('\n').count() + 1;
+
+            let mut functions = Vec::new();
+            let mut lines: BTreeMap<usize, u64> = BTreeMap::new();
+            for (index, def) in module.function_defs.iter().enumerate() {
+                let code = match &def.code {
+                    Some(code) => code,
+                    None => continue,
+                };
+                let name = module
+                    .identifier_at(module.function_handle_at(def.function).name)
+                    .to_string();
+                let source_map = unit
+                    .source_map
+                    .get_function_source_map(FunctionDefinitionIndex(index as u16))?;
+                let counts = function_coverage(&exec_map, module, &name);
+                for offset in 0..code.code.len() as u16 {
+                    if let Some(loc) = source_map.get_code_location(offset) {
+                        let count = counts
+                            .and_then(|counts| counts.get(&(offset as u64)))
+                            .copied()
+                            .unwrap_or(0);
+                        let line = lines.entry(line_of(loc.start())).or_default();
+                        *line = (*line).max(count);
+                    }
+                }
+                let calls = counts
+                    .and_then(|counts| counts.get(&0))
+                    .copied()
+                    .unwrap_or(0);
+                functions.push((line_of(source_map.definition_location.start()), name, calls));
+            }
+
+            writeln!(lcov, "TN:")?;
+            writeln!(lcov, "SF:{}", source_path.display())?;
+            for (line, name, _) in &functions {
+                writeln!(lcov, "FN:{line},{name}")?;
+            }
+            for (_, name, calls) in &functions {
+                writeln!(lcov, "FNDA:{calls},{name}")?;
+            }
+            writeln!(lcov, "FNF:{}", functions.len())?;
+            let hit = functions.iter().filter(|(_, _, calls)| *calls > 0).count();
+            writeln!(lcov, "FNH:{hit}")?;
+            for (line, count) in &lines {
+                writeln!(lcov, "DA:{line},{count}")?;
+            }
+            writeln!(lcov, "LF:{}", lines.len())?;
+            let hit = lines.values().filter(|count| **count > 0).count();
+            writeln!(lcov, "LH:{hit}")?;
+            writeln!(lcov, "end_of_record")?;
+        }
+        Ok(lcov)
+    }
+
+    fn root_modules(&self) -> impl Iterator<Item = (&NamedCompiledModule, &Path)> {
+        self.package
+            .root_modules()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(module) => Some((module, unit.source_path.as_path())),
+                CompiledUnit::Script(_) => None,
+            })
+    }
+}
+
+/// The number of times each instruction of the function `name` of `module` ran.
+fn function_coverage<'a>(
+    exec_map: &'a ExecCoverageMap,
+    module: &CompiledModule,
+    name: &str,
+) -> Option<&'a BTreeMap<u64, u64>> {
+    let id = module.self_id();
+    exec_map
+        .module_maps
+        .get(&(*id.address(), id.name().to_owned()))?
+        .function_maps
+        .iter()
+        .find(|(function, _)| function.as_str() == name)
+        .map(|(_, counts)| counts)
+}
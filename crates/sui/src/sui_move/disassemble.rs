@@ -1,20 +1,101 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{anyhow, bail};
 use clap::Parser;
 use move_cli::base::disassemble;
 use move_package::BuildConfig;
+use std::fs;
 use std::path::PathBuf;
+use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
+use sui_json_rpc_types::SuiRawData;
+use sui_types::base_types::ObjectID;
+use sui_types::move_package::disassemble_modules;
+
+use crate::client_commands::WalletContext;
 
 #[derive(Parser)]
 pub struct Disassemble {
-    #[clap(flatten)]
-    pub disassemble: disassemble::Disassemble,
+    /// ID of a published package to disassemble, fetched from the network of the client config.
+    /// The bytecode of the package at --path is disassembled if not provided
+    #[clap(long = "package", conflicts_with_all = &["name", "package_name", "interactive"])]
+    package_id: Option<ObjectID>,
+    /// The module of the published package to disassemble, all of them if not provided
+    #[clap(long = "module", requires = "package_id")]
+    module: Option<String>,
+    /// Also write the raw bytecode of the modules of the published package to <module>.mv files
+    /// in this directory
+    #[clap(long = "dump-bytes", requires = "package_id", parse(from_os_str))]
+    dump_bytes: Option<PathBuf>,
+    #[clap(long = "client.config", requires = "package_id")]
+    config: Option<PathBuf>,
+    /// Start a disassembled bytecode-to-source explorer
+    #[clap(long = "interactive")]
+    interactive: bool,
+    /// The name of the package at --path. If not provided defaults to current package modules only
+    #[clap(long = "package-name")]
+    package_name: Option<String>,
+    /// The name of the module or script in the package at --path to disassemble
+    #[clap(long = "name", required_unless_present = "package_id")]
+    name: Option<String>,
 }
 
 impl Disassemble {
-    pub fn execute(self, path: Option<PathBuf>, build_config: BuildConfig) -> anyhow::Result<()> {
-        self.disassemble.execute(path, build_config)?;
+    pub async fn execute(
+        self,
+        path: Option<PathBuf>,
+        build_config: BuildConfig,
+    ) -> anyhow::Result<()> {
+        let package_id = match self.package_id {
+            Some(package_id) => package_id,
+            None => {
+                let disassemble = disassemble::Disassemble {
+                    interactive: self.interactive,
+                    package_name: self.package_name,
+                    // Ok to unwrap, clap requires the name without a package ID
+                    module_or_script_name: self.name.unwrap(),
+                };
+                return disassemble.execute(path, build_config);
+            }
+        };
+
+        let config_path = match self.config {
+            Some(config_path) => config_path,
+            None => sui_config_dir()?.join(SUI_CLIENT_CONFIG),
+        };
+        let context = WalletContext::new(&config_path).await?;
+        let object = context
+            .client
+            .read_api()
+            .get_object(package_id)
+            .await?
+            .into_object()?;
+        let mut modules = match object.data {
+            SuiRawData::Package(package) => package.module_map,
+            SuiRawData::MoveObject(_) => bail!("Object {package_id} is not a package"),
+        };
+        if let Some(module) = &self.module {
+            modules.retain(|name, _| name == module);
+            if modules.is_empty() {
+                bail!("Package {package_id} has no module {module}");
+            }
+        }
+
+        if let Some(dir) = &self.dump_bytes {
+            fs::create_dir_all(dir)?;
+            for (name, bytes) in &modules {
+                let file = dir.join(format!("{name}.mv"));
+                fs::write(&file, bytes)
+                    .map_err(|e| anyhow!("Cannot write {}: {e}", file.display()))?;
+            }
+        }
+
+        // Disassembled here rather than by the full node, so the output only depends on the
+        // bytecode
+        for (name, disassembled) in disassemble_modules(modules.values())? {
+            println!("// Module {package_id}::{name}");
+            println!("{}", disassembled.as_str().unwrap_or_default());
+        }
         Ok(())
     }
 }
@@ -0,0 +1,23 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use clap::Parser;
+
+/// Print the documentation of a module of the Sui framework or the Move stdlib, as built into
+/// this binary
+#[derive(Parser)]
+pub struct FrameworkDocs {
+    /// The module, by name like `coin`, or qualified by its address like `sui::coin` or
+    /// `std::vector`
+    pub module: String,
+}
+
+impl FrameworkDocs {
+    pub fn execute(&self) -> anyhow::Result<()> {
+        let docs = sui_framework::module_docs(&self.module)
+            .ok_or_else(|| anyhow!("No framework or stdlib module named {}", self.module))?;
+        print!("{}", docs);
+        Ok(())
+    }
+}
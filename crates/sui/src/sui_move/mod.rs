@@ -10,6 +10,7 @@ use std::path::PathBuf;
 pub mod build;
 pub mod coverage;
 pub mod disassemble;
+pub mod framework_docs;
 pub mod new;
 pub mod prove;
 pub mod unit_test;
@@ -19,6 +20,7 @@ pub enum Command {
     Build(build::Build),
     Coverage(coverage::Coverage),
     Disassemble(disassemble::Disassemble),
+    FrameworkDocs(framework_docs::FrameworkDocs),
     New(new::New),
     Prove(prove::Prove),
     Test(unit_test::Test),
@@ -32,7 +34,7 @@ pub struct Calib {
     summarize: bool,
 }
 
-pub fn execute_move_command(
+pub async fn execute_move_command(
     package_path: Option<PathBuf>,
     build_config: BuildConfig,
     command: Command,
@@ -40,7 +42,8 @@ pub fn execute_move_command(
     match command {
         Command::Build(c) => c.execute(package_path, build_config),
         Command::Coverage(c) => c.execute(package_path, build_config),
-        Command::Disassemble(c) => c.execute(package_path, build_config),
+        Command::Disassemble(c) => c.execute(package_path, build_config).await,
+        Command::FrameworkDocs(c) => c.execute(),
         Command::New(c) => c.execute(package_path),
         Command::Prove(c) => c.execute(package_path, build_config),
         Command::Test(c) => {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::build;
+use super::coverage::CoverageReport;
 use clap::Parser;
 use move_cli::base::{
     self,
@@ -9,12 +10,17 @@ use move_cli::base::{
 };
 use move_package::BuildConfig;
 use move_unit_test::UnitTestingConfig;
+use std::fs;
 use std::path::PathBuf;
 
 #[derive(Parser)]
 pub struct Test {
     #[clap(flatten)]
     pub test: test::Test,
+    /// With --coverage, also write the coverage of the package sources to this file in the lcov
+    /// format
+    #[clap(long = "lcov", requires = "compute_coverage", parse(from_os_str))]
+    pub lcov: Option<PathBuf>,
 }
 impl Test {
     pub fn execute(
@@ -32,11 +38,23 @@ impl Test {
             build_config.clone(),
             dump_bytecode_as_base64,
         )?;
-        sui_framework::run_move_unit_tests(
+        let result = sui_framework::run_move_unit_tests(
             &rerooted_path,
-            build_config,
+            build_config.clone(),
             Some(unit_test_config),
             self.test.compute_coverage,
-        )
+        )?;
+
+        if self.test.compute_coverage {
+            let report = CoverageReport::load(&rerooted_path, build_config)?;
+            let mut stdout = std::io::stdout();
+            report.write_summary(&mut stdout)?;
+            report.write_sources(&mut stdout)?;
+            if let Some(lcov) = &self.lcov {
+                fs::write(lcov, report.lcov()?)?;
+                println!("Wrote the lcov coverage report to {}", lcov.display());
+            }
+        }
+        Ok(result)
     }
 }
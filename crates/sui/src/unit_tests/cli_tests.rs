@@ -1,17 +1,30 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, time::Duration};
+use std::{
+    fmt::Write,
+    fs::read_dir,
+    path::{Path, PathBuf},
+    str,
+    str::FromStr,
+    time::Duration,
+};
 
 use anyhow::anyhow;
+use clap::Parser;
 use move_package::BuildConfig;
 use serde_json::json;
 
 use sui::client_commands::SwitchResponse;
+use sui::client_script::StepOutcome;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
-    config::SuiClientConfig,
-    sui_commands::SuiCommand,
+    client_commands::{
+        watch_events, watch_events_filter, AliasCommand, SuiClientCommandResult, SuiClientCommands,
+        TxBuildCommand, TxCommand, WalletContext,
+    },
+    config::{AddressOrAlias, SuiClientConfig},
+    sui_commands::{write_completions, SuiCommand},
+    sui_move::disassemble::Disassemble,
 };
 use sui_config::gateway::GatewayConfig;
 use sui_config::genesis_config::{AccountConfig, GenesisConfig, ObjectConfig};
@@ -20,14 +33,20 @@ use sui_config::{
     SUI_GATEWAY_CONFIG, SUI_GENESIS_FILENAME, SUI_KEYSTORE_FILENAME, SUI_NETWORK_CONFIG,
 };
 use sui_json::SuiJsonValue;
-use sui_json_rpc_types::{GetObjectDataResponse, SuiData, SuiParsedObject, SuiTransactionEffects};
+use sui_json_rpc_types::{
+    GetObjectDataResponse, SuiData, SuiEventFilter, SuiExecutionStatus, SuiParsedObject,
+    SuiRawData, SuiTransactionEffects,
+};
 use sui_sdk::crypto::KeystoreType;
 use sui_sdk::ClientType;
 use sui_types::crypto::{
     AccountKeyPair, AuthorityKeyPair, Ed25519SuiSignature, KeypairTraits, NetworkKeyPair,
     Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
 };
-use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
+use sui_types::{
+    base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin, memo::MAX_MEMO_LENGTH,
+    object::Owner,
+};
 use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
 use test_utils::network::{setup_network_and_wallet, start_test_network};
 
@@ -133,6 +152,10 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
             ..Default::default()
         }),
         active_address: None,
+        signers: Default::default(),
+        journal: None,
+        aliases: Default::default(),
+        checksummed_addresses: false,
     };
     let wallet_conf_path = working_dir.join(SUI_CLIENT_CONFIG);
     let wallet_config = wallet_config.persisted(&wallet_conf_path);
@@ -162,17 +185,66 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
+        object_type: None,
+        cursor: None,
+        limit: 50,
     }
     .execute(&mut context)
     .await?
     .print(true);
 
-    let _object_refs = context
+    let mut object_refs = context
         .client
         .read_api()
         .get_objects_owned_by_address(address)
         .await?;
+    object_refs.sort_by_key(|object| object.object_id);
+
+    // Walk the objects a page at a time
+    let mut listed = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = match (SuiClientCommands::Objects {
+            address: Some(address.into()),
+            object_type: None,
+            cursor,
+            limit: 2,
+        })
+        .execute(&mut context)
+        .await?
+        {
+            SuiClientCommandResult::Objects(page, _) => page,
+            other => panic!("Unexpected result: {other:?}"),
+        };
+        assert!(page.data.len() <= 2);
+        listed.extend(page.data);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(listed, object_refs);
+
+    // Filter by type, with or without type arguments
+    for (object_type, count) in [
+        ("0x2::coin::Coin", object_refs.len()),
+        ("0x2::coin::Coin<0x2::sui::SUI>", object_refs.len()),
+        ("0x2::devnet_nft::DevNetNFT", 0),
+    ] {
+        match (SuiClientCommands::Objects {
+            address: Some(address.into()),
+            object_type: Some(object_type.to_string()),
+            cursor: None,
+            limit: 50,
+        })
+        .execute(&mut context)
+        .await?
+        {
+            SuiClientCommandResult::Objects(page, _) => assert_eq!(page.data.len(), count),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
 
     Ok(())
 }
@@ -234,7 +306,7 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -242,7 +314,10 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
+        object_type: None,
+        cursor: None,
+        limit: 50,
     }
     .execute(&mut context)
     .await?
@@ -287,7 +362,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -297,17 +372,18 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: recipient,
+        to: recipient.into(),
         object_id: object_to_send,
         gas: Some(object_id),
         gas_budget: 50000,
+        memo: None,
     }
     .execute(&mut context)
     .await?;
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -349,7 +425,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address2),
+        address: Some(address2.into()),
     }
     .execute(&mut context)
     .await?
@@ -357,7 +433,10 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address1`
     SuiClientCommands::Objects {
-        address: Some(address1),
+        address: Some(address1.into()),
+        object_type: None,
+        cursor: None,
+        limit: 50,
     }
     .execute(&mut context)
     .await?
@@ -552,6 +631,563 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_disassemble_published_package() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    let config_path = network.dir().join(SUI_CLIENT_CONFIG);
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas_obj_id = object_refs.first().unwrap().object_id;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    let resp = SuiClientCommands::Publish {
+        package_path,
+        build_config: BuildConfig::default(),
+        gas: Some(gas_obj_id),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let (package, created_obj) = if let SuiClientCommandResult::Publish(response) = resp {
+        let publish_resp = response.parsed_data.unwrap().to_publish_response().unwrap();
+        (
+            publish_resp.package.object_id,
+            publish_resp.created_objects[0].reference.object_id,
+        )
+    } else {
+        unreachable!("Invalid response");
+    };
+    let module_map = match context
+        .client
+        .read_api()
+        .get_object(package)
+        .await?
+        .into_object()?
+        .data
+    {
+        SuiRawData::Package(package) => package.module_map,
+        SuiRawData::MoveObject(_) => unreachable!("Invalid object"),
+    };
+
+    let disassemble = |args: &[&str]| {
+        let mut argv = vec![
+            "disassemble".to_string(),
+            "--client.config".to_string(),
+            config_path.to_str().unwrap().to_string(),
+        ];
+        argv.extend(args.iter().map(|arg| arg.to_string()));
+        Disassemble::try_parse_from(argv)
+            .unwrap()
+            .execute(None, BuildConfig::default())
+    };
+    let package_arg = package.to_string();
+    let dump_dir = tempfile::tempdir()?;
+    let dump_arg = dump_dir.path().to_str().unwrap().to_string();
+
+    // The bytes of the selected module are dumped as published
+    disassemble(&[
+        "--package",
+        &package_arg,
+        "--module",
+        "trusted_coin",
+        "--dump-bytes",
+        &dump_arg,
+    ])
+    .await?;
+    let dumped: Vec<_> = read_dir(dump_dir.path())?
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(dumped, vec!["trusted_coin.mv"]);
+    assert_eq!(
+        std::fs::read(dump_dir.path().join("trusted_coin.mv"))?,
+        module_map["trusted_coin"]
+    );
+
+    // Without --module, the whole package is disassembled
+    disassemble(&["--package", &package_arg]).await?;
+
+    // Unknown modules and objects that are not packages are rejected
+    assert!(
+        disassemble(&["--package", &package_arg, "--module", "no_such_module"])
+            .await
+            .is_err()
+    );
+    assert!(disassemble(&["--package", &created_obj.to_string()])
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_script_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, _) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let package_path =
+        std::fs::canonicalize(PathBuf::from(TEST_DATA_DIR))?.join("dummy_modules_publish");
+    let temp_dir = tempfile::tempdir()?;
+    let script_path = temp_dir.path().join("script.yaml");
+    std::fs::write(
+        &script_path,
+        format!(
+            r#"
+steps:
+  - name: publish
+    publish:
+      path: {package_path:?}
+      gas_budget: 1000
+  - name: mint
+    call:
+      package: ${{publish.package}}
+      module: trusted_coin
+      function: mint
+      args: ["${{publish.created[0]}}", 100]
+      gas_budget: 1000
+    expect:
+      created: 1
+      mutated: 1
+  - transfer:
+      to: "{recipient}"
+      object_id: ${{mint.created[0]}}
+      gas_budget: 1000
+"#
+        ),
+    )?;
+
+    let resp = SuiClientCommands::Run {
+        script: script_path,
+        dry_run: false,
+    }
+    .execute(&mut context)
+    .await?;
+    resp.print(true);
+
+    let report = if let SuiClientCommandResult::Run(report) = resp {
+        report
+    } else {
+        unreachable!("Invalid response");
+    };
+    assert_eq!(report.steps.len(), 3);
+    assert!(report
+        .steps
+        .iter()
+        .all(|step| matches!(step.outcome, StepOutcome::Executed)));
+
+    // The minted coin was piped into the transfer.
+    SuiClientCommands::SyncClientState {
+        address: Some(recipient.into()),
+    }
+    .execute(&mut context)
+    .await?;
+    let recipient_objects = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert!(recipient_objects
+        .iter()
+        .any(|o| o.type_.contains("trusted_coin::TRUSTED_COIN")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_command() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs[0].object_id;
+    let primary_coin = object_refs[1].object_id;
+    let coin_to_merge = object_refs[2].object_id;
+    let transferred = object_refs[3].object_id;
+    let total_value = get_gas_value(&get_object(primary_coin, &mut context).await.unwrap())
+        + get_gas_value(&get_object(coin_to_merge, &mut context).await.unwrap());
+
+    let temp_dir = tempfile::tempdir()?;
+    let batch_path = temp_dir.path().join("batch.yaml");
+    std::fs::write(
+        &batch_path,
+        format!(
+            r#"
+steps:
+  - merge:
+      primary_coin: "{primary_coin}"
+      coin_to_merge: "{coin_to_merge}"
+  - transfer:
+      to: "{recipient}"
+      object_id: "{transferred}"
+"#
+        ),
+    )?;
+
+    let resp = SuiClientCommands::Batch {
+        steps: batch_path.clone(),
+        gas: Some(gas),
+        gas_budget: 2000,
+    }
+    .execute(&mut context)
+    .await?;
+    resp.print(true);
+    let effects = if let SuiClientCommandResult::Batch(response) = resp {
+        response.effects
+    } else {
+        unreachable!("Invalid response");
+    };
+    assert!(matches!(effects.status, SuiExecutionStatus::Success));
+
+    // Both steps took effect in the same transaction
+    assert_eq!(
+        get_gas_value(&get_object(primary_coin, &mut context).await.unwrap()),
+        total_value
+    );
+    assert_eq!(get_object(coin_to_merge, &mut context).await, None);
+    assert_eq!(
+        get_object(transferred, &mut context).await.unwrap().owner,
+        Owner::AddressOwner(recipient)
+    );
+
+    // An object can't be used by two steps
+    std::fs::write(
+        &batch_path,
+        format!(
+            r#"
+steps:
+  - split:
+      coin: "{primary_coin}"
+      amounts: [100]
+  - transfer:
+      to: "{recipient}"
+      object_id: "{primary_coin}"
+"#
+        ),
+    )?;
+    let resp = SuiClientCommands::Batch {
+        steps: batch_path,
+        gas: Some(gas),
+        gas_budget: 2000,
+    }
+    .execute(&mut context)
+    .await;
+    assert!(resp.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_address_aliases() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    // Aliases can't be mistaken for addresses
+    assert_eq!(
+        AddressOrAlias::from_str(&recipient.to_string())?,
+        AddressOrAlias::Address(recipient)
+    );
+    assert!(AddressOrAlias::from_str("0x1234").is_err());
+    assert!(AddressOrAlias::from_str("al ice").is_err());
+
+    SuiClientCommands::Alias {
+        cmd: AliasCommand::Add {
+            alias: "alice".to_string(),
+            address: recipient,
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    // An alias names a single address
+    assert!(SuiClientCommands::Alias {
+        cmd: AliasCommand::Add {
+            alias: "alice".to_string(),
+            address,
+        },
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let obj_id = object_refs.first().unwrap().object_id;
+    SuiClientCommands::Transfer {
+        to: "alice".parse()?,
+        object_id: obj_id,
+        gas: None,
+        gas_budget: 50000,
+        memo: None,
+    }
+    .execute(&mut context)
+    .await?;
+
+    let resp = SuiClientCommands::Objects {
+        address: Some("alice".parse()?),
+        object_type: None,
+        cursor: None,
+        limit: 50,
+    }
+    .execute(&mut context)
+    .await?;
+    let (objects, alias) = match resp {
+        SuiClientCommandResult::Objects(objects, alias) => (objects, alias),
+        other => panic!("Unexpected result: {other:?}"),
+    };
+    assert_eq!(alias.as_deref(), Some("alice"));
+    assert!(objects.data.iter().any(|object| object.object_id == obj_id));
+
+    let resp = SuiClientCommands::Alias {
+        cmd: AliasCommand::Remove {
+            alias: "alice".to_string(),
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    assert!(matches!(resp, SuiClientCommandResult::Aliases(aliases) if aliases.is_empty()));
+    assert!(SuiClientCommands::Objects {
+        address: Some("alice".parse()?),
+        object_type: None,
+        cursor: None,
+        limit: 50,
+    }
+    .execute(&mut context)
+    .await
+    .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transfer_with_memo() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let obj_id = object_refs.first().unwrap().object_id;
+
+    let resp = SuiClientCommands::Transfer {
+        gas: None,
+        to: recipient.into(),
+        object_id: obj_id,
+        gas_budget: 50000,
+        memo: Some("deposit 1234".to_string()),
+    }
+    .execute(&mut context)
+    .await?;
+    let effects = match resp {
+        SuiClientCommandResult::Transfer(_, _, effects) => effects,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+    assert_eq!(effects.memo().as_deref(), Some("deposit 1234"));
+
+    // The memo is returned when querying the transaction
+    let response = context
+        .client
+        .read_api()
+        .get_transaction(effects.transaction_digest)
+        .await?;
+    assert_eq!(response.memo.as_deref(), Some("deposit 1234"));
+
+    // Memos longer than the limit are rejected before submitting the transaction
+    let obj_id = object_refs.get(1).unwrap().object_id;
+    let result = SuiClientCommands::TransferSui {
+        to: recipient.into(),
+        sui_coin_object_id: obj_id,
+        gas_budget: 50000,
+        amount: Some(10),
+        memo: Some("x".repeat(MAX_MEMO_LENGTH + 1)),
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_offline_signing() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let obj_id = object_refs.first().unwrap().object_id;
+
+    let data = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Build {
+            kind: TxBuildCommand::Transfer {
+                to: recipient.into(),
+                object_id: obj_id,
+                gas: None,
+                gas_budget: 50000,
+                memo: None,
+                gas_sponsor: None,
+            },
+        },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxBuild(data) => data,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+
+    // Only the sender can sign
+    let result = SuiClientCommands::Tx {
+        cmd: TxCommand::Sign {
+            key: recipient.into(),
+            data: data.clone(),
+        },
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    let signed = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Sign {
+            key: address.into(),
+            data,
+        },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxSign(signed) => signed,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+
+    let effects = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Execute { signed },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxExecute(response) => response.effects,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+    assert_eq!(effects.mutated_excluding_gas().count(), 1);
+    let object = get_object(obj_id, &mut context).await.unwrap();
+    assert_eq!(object.owner.get_owner_address().unwrap(), recipient);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sponsored_transfer() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let sponsor = context.keystore.addresses().get(1).cloned().unwrap();
+
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let obj_id = object_refs.first().unwrap().object_id;
+
+    let data = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Build {
+            kind: TxBuildCommand::Transfer {
+                to: sponsor.into(),
+                object_id: obj_id,
+                gas: None,
+                gas_budget: 50000,
+                memo: None,
+                gas_sponsor: Some(sponsor.into()),
+            },
+        },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxBuild(data) => data,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+
+    let signed = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Sign {
+            key: address.into(),
+            data,
+        },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxSign(signed) => signed,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+
+    // The signature of the sponsor is required
+    let result = SuiClientCommands::Tx {
+        cmd: TxCommand::Execute {
+            signed: signed.clone(),
+        },
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    // Only the sponsor can sponsor
+    let result = SuiClientCommands::Tx {
+        cmd: TxCommand::Sponsor {
+            key: address.into(),
+            signed: signed.clone(),
+        },
+    }
+    .execute(&mut context)
+    .await;
+    assert!(result.is_err());
+
+    let sponsored = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Sponsor {
+            key: sponsor.into(),
+            signed,
+        },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxSign(signed) => signed,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+
+    let effects = match (SuiClientCommands::Tx {
+        cmd: TxCommand::Execute { signed: sponsored },
+    })
+    .execute(&mut context)
+    .await?
+    {
+        SuiClientCommandResult::TxExecute(response) => response.effects,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+    // The sponsor paid for the gas
+    assert_eq!(
+        effects.gas_object.owner.get_owner_address().unwrap(),
+        sponsor
+    );
+    let object = get_object(obj_id, &mut context).await.unwrap();
+    assert_eq!(object.owner.get_owner_address().unwrap(), sponsor);
+
+    Ok(())
+}
+
 #[allow(clippy::assertions_on_constants)]
 #[tokio::test]
 async fn test_native_transfer() -> Result<(), anyhow::Error> {
@@ -570,9 +1206,10 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: Some(gas_obj_id),
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
+        memo: None,
     }
     .execute(&mut context)
     .await?;
@@ -595,13 +1232,13 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     // Sync both to fetch objects
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
     .print(true);
     SuiClientCommands::SyncClientState {
-        address: Some(recipient),
+        address: Some(recipient.into()),
     }
     .execute(&mut context)
     .await?
@@ -643,7 +1280,7 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -660,9 +1297,10 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: None,
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
         gas_budget: 50000,
+        memo: None,
     }
     .execute(&mut context)
     .await?;
@@ -711,18 +1349,23 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(addr1),
+        address: Some(addr1.into()),
     }
     .execute(&mut context)
     .await?;
 
     // Run a command with address omitted
-    let os = SuiClientCommands::Objects { address: None }
-        .execute(&mut context)
-        .await?;
+    let os = SuiClientCommands::Objects {
+        address: None,
+        object_type: None,
+        cursor: None,
+        limit: 50,
+    }
+    .execute(&mut context)
+    .await?;
 
-    let mut cmd_objs = if let SuiClientCommandResult::Objects(v) = os {
-        v
+    let mut cmd_objs = if let SuiClientCommandResult::Objects(page, _) = os {
+        page.data
     } else {
         panic!("Command failed")
     };
@@ -741,7 +1384,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Switch the address
     let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         rpc: None,
         ws: None,
     }
@@ -768,6 +1411,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     let os = SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: None,
+        word_count: None,
     }
     .execute(&mut context)
     .await?;
@@ -780,7 +1424,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Check that we can switch to this address
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
+        address: Some(new_addr.into()),
         rpc: None,
         ws: None,
     }
@@ -822,6 +1466,7 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
     SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: None,
+        word_count: None,
     }
     .execute(&mut context)
     .await?;
@@ -855,7 +1500,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(addr1),
+        address: Some(addr1.into()),
     }
     .execute(&mut context)
     .await?;
@@ -874,7 +1519,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     let addr2 = context.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         rpc: None,
         ws: None,
     }
@@ -955,7 +1600,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?;
@@ -1035,7 +1680,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     assert!((get_gas_value(&g.new_coins[1]) == 1000) || (get_gas_value(&g.new_coins[1]) == 10));
 
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -1081,7 +1726,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     assert_eq!(get_gas_value(&g.new_coins[1]), orig_value / 3);
 
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?
@@ -1142,3 +1787,200 @@ async fn test_signature_flag() -> Result<(), anyhow::Error> {
     assert!(res.is_err());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_completions() -> Result<(), anyhow::Error> {
+    let (network, mut context, address) = setup_network_and_wallet().await?;
+    SuiClientCommands::Alias {
+        cmd: AliasCommand::Add {
+            alias: "alice".to_string(),
+            address,
+        },
+    }
+    .execute(&mut context)
+    .await?;
+
+    let completions = |shell, config: &Path| {
+        let mut script = Vec::new();
+        write_completions(shell, config, &mut script);
+        String::from_utf8(script).unwrap()
+    };
+    let config = network.dir().join(SUI_CLIENT_CONFIG);
+    for shell in [
+        clap_complete::Shell::Bash,
+        clap_complete::Shell::Zsh,
+        clap_complete::Shell::Fish,
+        clap_complete::Shell::PowerShell,
+    ] {
+        let script = completions(shell, &config);
+        for subcommand in ["client", "keytool", "move", "transfer-sui", "completions"] {
+            assert!(script.contains(subcommand), "{shell}: {subcommand}");
+        }
+        // Aliases and addresses of the client config complete address arguments, PowerShell
+        // scripts only complete subcommands and flags
+        if shell != clap_complete::Shell::PowerShell {
+            assert!(script.contains("alice"), "{shell}");
+            assert!(script.contains(&address.to_string()), "{shell}");
+        }
+    }
+
+    // Without a client config, addresses are not completed
+    let script = completions(
+        clap_complete::Shell::Bash,
+        Path::new("/nonexistent/client.yaml"),
+    );
+    assert!(script.contains("transfer-sui"));
+    assert!(!script.contains("alice"));
+    assert!(!script.contains(&address.to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_watch_events() -> Result<(), anyhow::Error> {
+    let package = ObjectID::from_hex_literal("0x2")?;
+    let filter = watch_events_filter(
+        package,
+        Some("devnet_nft".to_string()),
+        Some("devnet_nft::MintNFTEvent".to_string()),
+    );
+    assert_eq!(
+        filter,
+        SuiEventFilter::All(vec![
+            SuiEventFilter::Package(package),
+            SuiEventFilter::Module("devnet_nft".to_string()),
+            SuiEventFilter::MoveEventType(format!(
+                "{}::devnet_nft::MintNFTEvent",
+                package.to_hex_literal()
+            )),
+        ])
+    );
+
+    // Full struct tags are kept as they are
+    let filter = watch_events_filter(package, None, Some("0x3::m::E".to_string()));
+    assert_eq!(
+        filter,
+        SuiEventFilter::All(vec![
+            SuiEventFilter::Package(package),
+            SuiEventFilter::MoveEventType("0x3::m::E".to_string()),
+        ])
+    );
+
+    // Subscriptions need a websocket connection to a fullnode, the first failure is returned
+    let (_network, context, _) = setup_network_and_wallet().await?;
+    assert!(
+        watch_events(&context, SuiEventFilter::Package(package), false)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cleanup() -> Result<(), anyhow::Error> {
+    let (_network, mut context, address) = setup_network_and_wallet().await?;
+    let recipient = context.keystore.addresses().get(1).cloned().unwrap();
+    let object_refs = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let gas = object_refs[0].object_id;
+
+    // Send a zero-value coin and two other coins to the recipient
+    SuiClientCommands::SplitCoin {
+        coin_id: object_refs[1].object_id,
+        amounts: Some(vec![0]),
+        count: 0,
+        gas: Some(gas),
+        gas_budget: 1000,
+    }
+    .execute(&mut context)
+    .await?;
+    let zero_coin = context
+        .gas_objects(address)
+        .await?
+        .into_iter()
+        .find(|(value, _, _)| *value == 0)
+        .map(|(_, _, info)| info.object_id)
+        .unwrap();
+    for object_id in [
+        zero_coin,
+        object_refs[2].object_id,
+        object_refs[3].object_id,
+    ] {
+        SuiClientCommands::Transfer {
+            to: recipient.into(),
+            object_id,
+            gas: Some(gas),
+            gas_budget: 50000,
+            memo: None,
+        }
+        .execute(&mut context)
+        .await?;
+    }
+    let recipient_objects = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?
+        .len();
+
+    let resp = SuiClientCommands::Cleanup {
+        received_from: address.into(),
+        address: Some(recipient.into()),
+        gas: None,
+        gas_budget: 10000,
+        dry_run: true,
+    }
+    .execute(&mut context)
+    .await?;
+    let report = match resp {
+        SuiClientCommandResult::Cleanup(report) => report,
+        _ => panic!("Command failed"),
+    };
+    assert_eq!(report.deleted, vec![zero_coin]);
+    assert_eq!(report.merged.len(), 1);
+    assert_eq!(report.merged.values().next().unwrap().len(), 1);
+    assert!(report.skipped.is_empty());
+    assert!(report.transaction.is_none());
+
+    let resp = SuiClientCommands::Cleanup {
+        received_from: address.into(),
+        address: Some(recipient.into()),
+        gas: None,
+        gas_budget: 10000,
+        dry_run: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let report = match resp {
+        SuiClientCommandResult::Cleanup(report) => report,
+        _ => panic!("Command failed"),
+    };
+    assert!(report.transaction.is_some());
+    let objects = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(recipient)
+        .await?;
+    assert_eq!(objects.len(), recipient_objects - 2);
+    assert!(objects.iter().all(|info| info.object_id != zero_coin));
+
+    // Nothing is left to clean up
+    let resp = SuiClientCommands::Cleanup {
+        received_from: address.into(),
+        address: Some(recipient.into()),
+        gas: None,
+        gas_budget: 10000,
+        dry_run: false,
+    }
+    .execute(&mut context)
+    .await?;
+    let report = match resp {
+        SuiClientCommandResult::Cleanup(report) => report,
+        _ => panic!("Command failed"),
+    };
+    assert!(report.deleted.is_empty() && report.merged.is_empty());
+    assert!(report.transaction.is_none());
+    Ok(())
+}
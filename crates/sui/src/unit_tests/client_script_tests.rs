@@ -0,0 +1,124 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::json;
+
+use super::*;
+
+fn steps(yaml: &str) -> Vec<Value> {
+    serde_yaml::from_str::<Script>(yaml).unwrap().steps
+}
+
+#[test]
+fn test_parse_references() {
+    assert_eq!(
+        "publish.created[2]".parse::<Reference>().unwrap(),
+        Reference {
+            step: "publish".to_string(),
+            output: "created".to_string(),
+            index: Some(2),
+        }
+    );
+    let references = parse_references("${a.digest} and ${b.gas_used}").unwrap();
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[0].0, 0..11);
+    assert_eq!(references[1].1.to_string(), "${b.gas_used}");
+
+    assert!(parse_references("${a.digest").is_err());
+    assert!("publish".parse::<Reference>().is_err());
+    assert!("publish.created[x]".parse::<Reference>().is_err());
+}
+
+#[test]
+fn test_substitute_references() {
+    let outputs = vec![(
+        "publish".to_string(),
+        json!({ "package": "0x2", "created": ["0x3", "0x4"], "gas_used": 10 }),
+    )];
+    let step = json!({
+        "call": {
+            "package": "${publish.package}",
+            "args": ["${publish.created[1]}", "${publish.gas_used}", "gas: ${publish.gas_used}"],
+        }
+    });
+    assert_eq!(
+        substitute(&step, &outputs).unwrap(),
+        json!({
+            "call": {
+                "package": "0x2",
+                "args": ["0x4", 10, "gas: 10"],
+            }
+        })
+    );
+
+    assert!(substitute(&json!("${publish.created[2]}"), &outputs).is_err());
+    assert!(substitute(&json!("${publish.digest}"), &outputs).is_err());
+    assert!(substitute(&json!("ids: ${publish.created}"), &outputs).is_err());
+}
+
+#[test]
+fn test_validate_script() {
+    let names = validate(&steps(
+        r#"
+steps:
+  - name: publish
+    publish:
+      path: package
+      gas_budget: 1000
+  - call:
+      package: ${publish.package}
+      module: m
+      function: f
+      gas_budget: 1000
+"#,
+    ))
+    .unwrap();
+    assert_eq!(names, vec!["publish".to_string(), "#2".to_string()]);
+
+    // References must be to outputs of earlier named steps.
+    let forward = r#"
+steps:
+  - transfer:
+      to: ${later.package}
+      object_id: ${later.created[0]}
+      gas_budget: 1000
+  - name: later
+    publish:
+      path: package
+      gas_budget: 1000
+"#;
+    assert!(validate(&steps(forward)).is_err());
+    let unknown_output = r#"
+steps:
+  - name: publish
+    publish:
+      path: package
+      gas_budget: 1000
+  - transfer_sui:
+      to: ${publish.owner}
+      sui_coin_object_id: ${publish.created[0]}
+      gas_budget: 1000
+"#;
+    assert!(validate(&steps(unknown_output)).is_err());
+
+    let duplicate = r#"
+steps:
+  - name: publish
+    publish:
+      path: package
+      gas_budget: 1000
+  - name: publish
+    publish:
+      path: package
+      gas_budget: 1000
+"#;
+    assert!(validate(&steps(duplicate)).is_err());
+
+    // Steps without references are fully checked upfront.
+    let missing_budget = r#"
+steps:
+  - publish:
+      path: package
+"#;
+    assert!(validate(&steps(missing_budget)).is_err());
+}
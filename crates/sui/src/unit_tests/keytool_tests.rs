@@ -9,7 +9,7 @@ use super::KeyToolCommand;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sui_sdk::crypto::KeystoreType;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::crypto::get_key_pair;
 use sui_types::crypto::get_key_pair_from_rng;
 use sui_types::crypto::AuthorityKeyPair;
@@ -20,6 +20,8 @@ use sui_types::crypto::Signature;
 use sui_types::crypto::SignatureScheme;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::SuiSignatureInner;
+use sui_types::messages::TransactionData;
+use sui_types::multisig::MultiSigPublicKey;
 use tempfile::TempDir;
 
 #[test]
@@ -64,6 +66,7 @@ fn test_flag_in_signature_and_keypair() -> Result<(), anyhow::Error> {
                 );
                 assert!(pk1.flag() == Secp256k1SuiSignature::SCHEME.flag())
             }
+            Signature::MultiSigSuiSignature(_) => panic!("A key can't make a multisig signature"),
         }
     }
     Ok(())
@@ -284,3 +287,52 @@ fn test_valid_derivation_path() -> Result<(), anyhow::Error> {
     .is_ok());
     Ok(())
 }
+
+#[test]
+fn test_multisig_commands() -> Result<(), anyhow::Error> {
+    let mut keystore = KeystoreType::InMem(3).init().unwrap();
+    let pks = keystore.keys();
+    let multisig_pk = MultiSigPublicKey::new(pks.clone(), 2)?;
+
+    KeyToolCommand::MultiSigAddress {
+        threshold: 2,
+        pks: pks.clone(),
+    }
+    .execute(&mut keystore)?;
+
+    let data = TransactionData::new_transfer_sui(
+        SuiAddress::default(),
+        SuiAddress::from(&multisig_pk),
+        None,
+        (
+            ObjectID::random(),
+            SequenceNumber::new(),
+            ObjectDigest::new([0; 32]),
+        ),
+        10000,
+    );
+    let sigs = pks
+        .iter()
+        .take(2)
+        .map(|pk| keystore.sign(&pk.into(), &data.to_bytes()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    KeyToolCommand::MultiSigCombinePartialSig {
+        threshold: 2,
+        pks: pks.clone(),
+        sigs: sigs.clone(),
+        tx_data: Some(data.to_base64()),
+    }
+    .execute(&mut keystore)?;
+
+    // Below the threshold
+    assert!(KeyToolCommand::MultiSigCombinePartialSig {
+        threshold: 2,
+        pks,
+        sigs: sigs[..1].to_vec(),
+        tx_data: None,
+    }
+    .execute(&mut keystore)
+    .is_err());
+    Ok(())
+}
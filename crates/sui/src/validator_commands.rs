@@ -0,0 +1,178 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Debug, Display, Formatter, Write};
+
+use anyhow::anyhow;
+use clap::*;
+use multiaddr::Multiaddr;
+use serde::Serialize;
+use serde_json::json;
+use tracing::info;
+
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{SuiCertifiedTransaction, SuiTransactionEffects};
+use sui_types::base_types::ObjectID;
+use sui_types::sui_serde::{Base64, Encoding};
+use sui_types::{SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID};
+
+use crate::client_commands::{call_move, write_cert_and_effects, WalletContext};
+
+const SUI_SYSTEM_MODULE: &str = "sui_system";
+
+/// Length of the Ed25519 network public keys of the validators.
+const NETWORK_PUBKEY_LENGTH: usize = 32;
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SuiValidatorCommand {
+    /// Update the metadata of the validator of the active address. The name, description and
+    /// image URL change right away, the network address and key at the end of the epoch. Each
+    /// field is updated with its own transaction.
+    #[clap(name = "update-metadata")]
+    UpdateMetadata {
+        /// A unique name, ASCII and at most 128 bytes long
+        #[clap(long)]
+        name: Option<String>,
+        /// A description, at most 1024 bytes long
+        #[clap(long)]
+        description: Option<String>,
+        /// The URL of an image of the validator, ASCII and at most 1024 bytes long
+        #[clap(long)]
+        image_url: Option<String>,
+        /// The network address to serve at from the next epoch, e.g.
+        /// /dns/validator.example.com/tcp/8080/http
+        #[clap(long)]
+        next_epoch_net_address: Option<Multiaddr>,
+        /// The Base64 encoded Ed25519 network public key to use from the next epoch
+        #[clap(long)]
+        next_epoch_network_pubkey: Option<String>,
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+        /// Gas budget of each transaction
+        #[clap(long, default_value = "1000")]
+        gas_budget: u64,
+    },
+}
+
+impl SuiValidatorCommand {
+    pub async fn execute(
+        self,
+        context: &mut WalletContext,
+    ) -> Result<SuiValidatorCommandResponse, anyhow::Error> {
+        let ret = match self {
+            SuiValidatorCommand::UpdateMetadata {
+                name,
+                description,
+                image_url,
+                next_epoch_net_address,
+                next_epoch_network_pubkey,
+                gas,
+                gas_budget,
+            } => {
+                let network_pubkey = next_epoch_network_pubkey
+                    .map(|key| {
+                        let bytes = Base64::decode(&key)
+                            .map_err(|e| anyhow!("Invalid network public key {key}: {e}"))?;
+                        if bytes.len() != NETWORK_PUBKEY_LENGTH {
+                            return Err(anyhow!(
+                                "Invalid network public key {key}: expected {NETWORK_PUBKEY_LENGTH} bytes, got {}",
+                                bytes.len()
+                            ));
+                        }
+                        Ok(bytes)
+                    })
+                    .transpose()?;
+                let updates = [
+                    ("update_validator_name", name.map(String::into_bytes)),
+                    (
+                        "update_validator_description",
+                        description.map(String::into_bytes),
+                    ),
+                    (
+                        "update_validator_image_url",
+                        image_url.map(String::into_bytes),
+                    ),
+                    (
+                        "update_validator_next_epoch_net_address",
+                        next_epoch_net_address.map(|address| address.to_vec()),
+                    ),
+                    ("update_validator_next_epoch_network_pubkey", network_pubkey),
+                ];
+                if updates.iter().all(|(_, value)| value.is_none()) {
+                    return Err(anyhow!("No metadata to update"));
+                }
+
+                let mut transactions = Vec::new();
+                for (function, value) in updates {
+                    let value = match value {
+                        Some(value) => value,
+                        None => continue,
+                    };
+                    let args = vec![
+                        SuiJsonValue::from_object_id(SUI_SYSTEM_STATE_OBJECT_ID),
+                        SuiJsonValue::new(json!(value))?,
+                    ];
+                    info!("Calling {SUI_SYSTEM_MODULE}::{function}");
+                    let (cert, effects) = call_move(
+                        SUI_FRAMEWORK_OBJECT_ID,
+                        SUI_SYSTEM_MODULE,
+                        function,
+                        vec![],
+                        gas,
+                        gas_budget,
+                        args,
+                        context,
+                    )
+                    .await?;
+                    transactions.push((cert, effects));
+                }
+                SuiValidatorCommandResponse::UpdateMetadata(transactions)
+            }
+        };
+        Ok(ret)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SuiValidatorCommandResponse {
+    UpdateMetadata(Vec<(SuiCertifiedTransaction, SuiTransactionEffects)>),
+}
+
+impl Display for SuiValidatorCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        match self {
+            SuiValidatorCommandResponse::UpdateMetadata(transactions) => {
+                for (cert, effects) in transactions {
+                    write!(writer, "{}", write_cert_and_effects(cert, effects)?)?;
+                }
+            }
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
+
+impl Debug for SuiValidatorCommandResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = serde_json::to_string_pretty(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{s}")
+    }
+}
+
+impl SuiValidatorCommandResponse {
+    pub fn print(&self, pretty: bool) {
+        let line = if pretty {
+            format!("{self}")
+        } else {
+            format!("{:?}", self)
+        };
+        for line in line.lines() {
+            println!("{line}");
+            info!("{line}")
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_core::authority_client::AuthorityAPI;
+use sui_failpoints::Action;
+use sui_macros::*;
+use sui_types::base_types::SuiAddress;
+use test_utils::authority::{get_client, spawn_test_authorities, test_authority_configs};
+use test_utils::messages::{make_transfer_sui_transaction, make_tx_certs_and_signed_effects};
+use test_utils::objects::test_gas_objects;
+use test_utils::test_account_keys;
+
+const BEFORE_COMMIT: &str = "authority-store-before-commit";
+
+#[sui_test]
+async fn test_certificate_executes_again_after_failing_before_commit() {
+    let mut gas_objects = test_gas_objects();
+    let configs = test_authority_configs();
+    let _handles = spawn_test_authorities(gas_objects.clone(), &configs).await;
+
+    let (sender, keypair) = test_account_keys().pop().unwrap();
+    let transaction = make_transfer_sui_transaction(
+        gas_objects.pop().unwrap().compute_object_reference(),
+        SuiAddress::default(),
+        None,
+        sender,
+        &keypair,
+    );
+    let certificate = make_tx_certs_and_signed_effects(vec![transaction])
+        .0
+        .pop()
+        .unwrap();
+    let client = get_client(&configs.validator_set()[0]);
+
+    {
+        let _fail_point = sui_failpoints::configure_scoped(BEFORE_COMMIT, Action::Return);
+        let hits = sui_failpoints::hits(BEFORE_COMMIT);
+        assert!(client
+            .handle_certificate(certificate.clone())
+            .await
+            .is_err());
+        assert!(sui_failpoints::hits(BEFORE_COMMIT) > hits);
+    }
+
+    // Nothing of the first execution was committed, so the validator executes it from scratch
+    let response = client.handle_certificate(certificate).await.unwrap();
+    assert!(response.signed_effects.is_some());
+}
@@ -17,8 +17,10 @@ use std::{collections::BTreeMap, sync::Arc};
 use sui_sdk::{ClientType, SuiClient};
 use sui_types::base_types::{ObjectRef, SequenceNumber};
 use sui_types::event::TransferType;
+use sui_types::gas_coin::GAS;
 use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
 use sui_types::sui_framework_address_concat_string;
+use sui_types::sui_serde::{Base64, Encoding};
 use test_utils::authority::test_and_configure_authority_configs;
 use test_utils::messages::{
     get_gas_object_with_wallet_context, make_transfer_object_transaction_with_wallet_context,
@@ -31,11 +33,18 @@ use tokio::sync::Mutex;
 use tokio::time::timeout;
 use tokio::time::{sleep, Duration};
 
-use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
-use sui_config::utils::get_available_port;
+use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, TxBuildCommand};
+use sui_config::node::{HealthConfig, RateLimitConfig};
+use sui_config::utils::{available_local_socket_address, get_available_port};
 use sui_json_rpc_types::{
-    SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse, SuiExecutionStatus,
-    SuiMoveStruct, SuiMoveValue, SuiTransactionFilter, SuiTransactionResponse,
+    SuiBalance, SuiCoinsPage, SuiEvent, SuiEventEnvelope, SuiEventFieldSchema, SuiEventFilter,
+    SuiEventSchema, SuiExecuteTransactionResponse, SuiExecutionStatus, SuiMoveStruct, SuiMoveValue,
+    SuiTransactionFilter, SuiTransactionResponse, TransactionQuery,
+};
+use sui_node::health::HealthCheck;
+use sui_node::transaction_submit::{
+    SubmitTransactionRequest, SubmitTransactionResponse, SubmitTransactionStatus,
+    SUBMIT_TRANSACTION_ROUTE,
 };
 use sui_node::SuiNode;
 use sui_swarm::memory::Swarm;
@@ -99,6 +108,56 @@ async fn test_full_node_shared_objects() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sui_test]
+async fn test_full_node_shutdown() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+    let state = node.state();
+
+    let (_, _, _, digest) = transfer_coin(&mut context).await?;
+    wait_for_tx(digest, state.clone()).await;
+
+    // The config reloads in place
+    node.reload_config(&config);
+
+    node.shutdown(Duration::from_secs(10)).await?;
+    assert_eq!(state.db().wal.in_flight_txes(), 0);
+    assert!(state.get_transaction(digest).await.is_ok());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_health() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+    let health_config = HealthConfig {
+        address: available_local_socket_address(),
+        max_checkpoint_lag: 10,
+        max_consensus_silence_secs: Some(1),
+    };
+    let check = HealthCheck::new(node.state(), health_config, false);
+
+    let (_, _, _, digest) = transfer_coin(&mut context).await?;
+    wait_for_tx(digest, node.state()).await;
+
+    let report = check.report();
+    assert!(report.healthy && report.ready, "{:?}", report.problems);
+    assert!(report.last_commit_timestamp_ms.is_some());
+    // Full nodes don't hear from consensus
+    assert!(report.consensus.is_none());
+
+    node.state().halt_validator();
+    let report = check.report();
+    assert!(report.healthy && !report.ready);
+
+    Ok(())
+}
+
 const HOUR_MS: u64 = 3_600_000;
 
 #[tokio::test]
@@ -120,6 +179,8 @@ async fn test_full_node_move_function_index() -> Result<(), anyhow::Error> {
             package_ref.0,
             Some("counter".to_string()),
             Some("increment".to_string()),
+            None,
+            100,
         )
         .await?;
 
@@ -128,17 +189,35 @@ async fn test_full_node_move_function_index() -> Result<(), anyhow::Error> {
 
     let txes = node
         .state()
-        .get_transactions_by_move_function(package_ref.0, None, None)
+        .get_transactions_by_move_function(package_ref.0, None, None, None, 100)
         .await?;
 
     // 2 transactions in the package i.e create and increment counter
     assert_eq!(txes.len(), 2);
     assert_eq!(txes[1].1, digest);
 
+    // One at a time, resuming from the second transaction
+    let first = node
+        .state()
+        .get_transactions_by_move_function(package_ref.0, None, None, None, 1)
+        .await?;
+    assert_eq!(first, txes[..1]);
+    let second = node
+        .state()
+        .get_transactions_by_move_function(package_ref.0, None, None, Some(txes[1].0), 1)
+        .await?;
+    assert_eq!(second, txes[1..]);
+
     eprint!("start...");
     let txes = node
         .state()
-        .get_transactions_by_move_function(package_ref.0, Some("counter".to_string()), None)
+        .get_transactions_by_move_function(
+            package_ref.0,
+            Some("counter".to_string()),
+            None,
+            None,
+            100,
+        )
         .await?;
 
     // 2 transactions in the package i.e publish and increment
@@ -162,7 +241,7 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
 
     let txes = node
         .state()
-        .get_transactions_by_input_object(transferred_object)
+        .get_transactions_by_input_object(transferred_object, None, 100)
         .await?;
 
     assert_eq!(txes.len(), 1);
@@ -170,27 +249,39 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
 
     let txes = node
         .state()
-        .get_transactions_by_mutated_object(transferred_object)
+        .get_transactions_by_mutated_object(transferred_object, None, 100)
         .await?;
     assert_eq!(txes.len(), 1);
     assert_eq!(txes[0].1, digest);
 
-    let txes = node.state().get_transactions_from_addr(sender).await?;
+    let txes = node
+        .state()
+        .get_transactions_from_addr(sender, None, 100)
+        .await?;
     assert_eq!(txes.len(), 1);
     assert_eq!(txes[0].1, digest);
 
-    let txes = node.state().get_transactions_to_addr(receiver).await?;
+    let txes = node
+        .state()
+        .get_transactions_to_addr(receiver, None, 100)
+        .await?;
     assert_eq!(txes.len(), 1);
     assert_eq!(txes[0].1, digest);
 
     // Note that this is also considered a tx to the sender, because it mutated
     // one or more of the sender's objects.
-    let txes = node.state().get_transactions_to_addr(sender).await?;
+    let txes = node
+        .state()
+        .get_transactions_to_addr(sender, None, 100)
+        .await?;
     assert_eq!(txes.len(), 1);
     assert_eq!(txes[0].1, digest);
 
     // No transactions have originated from the receiver
-    let txes = node.state().get_transactions_from_addr(receiver).await?;
+    let txes = node
+        .state()
+        .get_transactions_from_addr(receiver, None, 100)
+        .await?;
     assert_eq!(txes.len(), 0);
 
     // timestamp is recorded
@@ -283,6 +374,299 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_full_node_transaction_query() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+
+    let (first_object, sender, receiver, first) = transfer_coin(&mut context).await?;
+    let (_, _, _, second) = transfer_coin(&mut context).await?;
+    wait_for_all_txes(vec![first, second], node.state().clone()).await;
+
+    let txes = node
+        .state()
+        .get_transactions(TransactionQuery::FromAddress(sender), None, 100, false)
+        .await?;
+    let digests: Vec<_> = txes.iter().map(|(_, digest)| *digest).collect();
+    assert_eq!(digests, vec![first, second]);
+
+    // The latest transactions come first in descending order, and the cursor is included
+    let txes = node
+        .state()
+        .get_transactions(TransactionQuery::FromAddress(sender), None, 1, true)
+        .await?;
+    assert_eq!(txes.len(), 1);
+    assert_eq!(txes[0].1, second);
+    let txes = node
+        .state()
+        .get_transactions(
+            TransactionQuery::ToAddress(receiver),
+            Some(txes[0].0),
+            100,
+            true,
+        )
+        .await?;
+    let digests: Vec<_> = txes.iter().map(|(_, digest)| *digest).collect();
+    assert_eq!(digests, vec![second, first]);
+
+    let txes = node
+        .state()
+        .get_transactions(TransactionQuery::InputObject(first_object), None, 100, true)
+        .await?;
+    assert_eq!(txes.len(), 1);
+    assert_eq!(txes[0].1, first);
+
+    // All the transactions executed by the node, the last of which is the second transfer
+    let txes = node
+        .state()
+        .get_transactions(TransactionQuery::All, None, 1, true)
+        .await?;
+    assert_eq!(txes[0].1, second);
+    let all = node
+        .state()
+        .get_transactions(TransactionQuery::All, None, 100, false)
+        .await?;
+    assert!(all.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(all.last(), txes.first());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_coin_read_api() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+    let (node, jsonrpc_client, _) = set_up_jsonrpc(&swarm, None).await?;
+
+    let (transferred_object, _, receiver, digest) = transfer_coin(&mut context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let coins: SuiCoinsPage = jsonrpc_client
+        .request("sui_getCoins", rpc_params![receiver])
+        .await?;
+    assert!(!coins.has_next_page);
+    assert!(coins
+        .data
+        .iter()
+        .any(|coin| coin.coin_object_id == transferred_object));
+    assert!(coins
+        .data
+        .windows(2)
+        .all(|w| w[0].coin_object_id < w[1].coin_object_id));
+
+    // The balance sums up the coins, which are all SUI
+    let balance: SuiBalance = jsonrpc_client
+        .request("sui_getBalance", rpc_params![receiver])
+        .await?;
+    assert_eq!(balance.coin_type, GAS::type_tag().to_string());
+    assert_eq!(balance.coin_object_count, coins.data.len());
+    assert_eq!(
+        balance.total_balance,
+        coins.data.iter().map(|coin| coin.balance).sum::<u64>()
+    );
+    let balances: Vec<SuiBalance> = jsonrpc_client
+        .request("sui_getAllBalances", rpc_params![receiver])
+        .await?;
+    assert_eq!(balances, vec![balance]);
+
+    // Page through the coins one at a time
+    let first_page: SuiCoinsPage = jsonrpc_client
+        .request(
+            "sui_getCoins",
+            rpc_params![receiver, "0x2::sui::SUI", None::<ObjectID>, 1],
+        )
+        .await?;
+    assert_eq!(first_page.data, coins.data[..1]);
+    assert!(first_page.has_next_page);
+    let second_page: SuiCoinsPage = jsonrpc_client
+        .request(
+            "sui_getCoins",
+            rpc_params![receiver, "0x2::sui::SUI", first_page.next_cursor, 1],
+        )
+        .await?;
+    assert_eq!(second_page.data, coins.data[1..2]);
+
+    // Empty pages are invalid, their cursor would never advance
+    for (method, params) in [
+        (
+            "sui_getCoins",
+            rpc_params![receiver, "0x2::sui::SUI", None::<ObjectID>, 0],
+        ),
+        (
+            "sui_getObjectsOwnedByAddress",
+            rpc_params![receiver, None::<ObjectID>, 0],
+        ),
+        (
+            "sui_getTransactionsToAddress",
+            rpc_params![receiver, None::<u64>, 0],
+        ),
+    ] {
+        let error = jsonrpc_client
+            .request::<serde_json::Value>(method, params)
+            .await
+            .unwrap_err();
+        assert!(
+            error.to_string().contains("limit must be greater than 0"),
+            "{error}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_coin_transfers_to_addr() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+
+    let (transferred_object, sender, receiver, digest) = transfer_coin(&mut context).await?;
+
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let (transfers, cursor) = node
+        .state()
+        .get_coin_transfers_to_addr(receiver, None, 10)
+        .await?;
+    assert_eq!(transfers.len(), 1);
+    let (seq, transfer) = &transfers[0];
+    assert_eq!(cursor, Some(*seq));
+    assert_eq!(transfer.transaction_digest, digest);
+    assert_eq!(transfer.sender, sender);
+    assert_eq!(transfer.coin.0, transferred_object);
+    assert_eq!(transfer.coin_type, GAS::type_tag());
+    assert_eq!(transfer.amount, 100000000);
+
+    // Nothing new after the cursor, which stays put
+    let (transfers, next_cursor) = node
+        .state()
+        .get_coin_transfers_to_addr(receiver, cursor, 10)
+        .await?;
+    assert!(transfers.is_empty());
+    assert_eq!(next_cursor, cursor);
+
+    // The sender only paid gas, which isn't a transfer to it
+    let (transfers, _) = node
+        .state()
+        .get_coin_transfers_to_addr(sender, None, 10)
+        .await?;
+    assert!(transfers.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_object_version_history() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+
+    let (transferred_object, _, _, digest) = transfer_coin(&mut context).await?;
+
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let (versions, cursor) = node
+        .state()
+        .get_object_version_history(transferred_object, None, 10)
+        .await?;
+    let (object_ref, transaction_digest, timestamp_ms) = versions.last().unwrap();
+    assert_eq!(cursor, Some(object_ref.1));
+    assert_eq!(*transaction_digest, digest);
+    assert!(timestamp_ms.is_some());
+    let latest = node
+        .state()
+        .get_object_read(&transferred_object)
+        .await?
+        .into_object()?;
+    assert_eq!(*object_ref, latest.compute_object_reference());
+
+    // Paging through the versions one by one walks the same lineage
+    let mut paged = vec![];
+    let mut page_cursor = None;
+    loop {
+        let (page, next_cursor) = node
+            .state()
+            .get_object_version_history(transferred_object, page_cursor, 1)
+            .await?;
+        if page.is_empty() {
+            assert_eq!(next_cursor, page_cursor);
+            break;
+        }
+        paged.extend(page);
+        page_cursor = next_cursor;
+    }
+    assert_eq!(paged, versions);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_wait_for_transaction() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+
+    let (_, _, _, digest) = transfer_coin(&mut context).await?;
+
+    // Returns once the full node has caught up with the transaction
+    let (certificate, effects) = node
+        .state()
+        .wait_for_transaction(digest, Duration::from_secs(10))
+        .await?;
+    assert_eq!(*certificate.digest(), digest);
+    assert_eq!(effects.transaction_digest, digest);
+
+    // Returns right away for the transactions already executed
+    node.state()
+        .wait_for_transaction(digest, Duration::from_millis(1))
+        .await?;
+
+    let unknown = TransactionDigest::random();
+    let result = node
+        .state()
+        .wait_for_transaction(unknown, Duration::from_millis(100))
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_fee_and_supply_accounting() -> Result<(), anyhow::Error> {
+    telemetry_subscribers::init_for_testing();
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+
+    let config = swarm.config().generate_fullnode_config();
+    let node = SuiNode::start(&config, Registry::new()).await?;
+
+    let (_, _, _, digest) = transfer_coin(&mut context).await?;
+
+    wait_for_tx(digest, node.state().clone()).await;
+
+    let fees = node.state().get_epoch_fee_summary(0).await?.unwrap();
+    assert!(fees.transaction_count >= 1);
+    assert!(fees.computation_cost > 0);
+    assert_eq!(fees.minted, 0);
+    assert!(node.state().get_epoch_fee_summary(1).await?.is_none());
+
+    // The gas charged so far is out of the supply
+    let supply = node.state().get_supply().await?;
+    assert_eq!(supply.epoch, 0);
+    assert_eq!(supply.gas_burned, fees.computation_cost + fees.storage_cost);
+    assert_eq!(supply.storage_rebates, fees.storage_rebate);
+    assert!(supply.total() < supply.genesis_coins + supply.system_minted);
+
+    Ok(())
+}
+
 // Test for syncing a node to an authority that already has many txes.
 #[sui_test]
 async fn test_full_node_cold_sync() -> Result<(), anyhow::Error> {
@@ -334,7 +718,7 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                 let context = &mut context.lock().await;
                 let address = context.keystore.addresses()[i];
                 SuiClientCommands::SyncClientState {
-                    address: Some(address),
+                    address: Some(address.into()),
                 }
                 .execute(context)
                 .await
@@ -514,16 +898,39 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     wait_for_tx(digest, node.state().clone()).await;
 
     let struct_tag_str = sui_framework_address_concat_string("::devnet_nft::MintNFTEvent");
+    let expected_schema = SuiEventSchema {
+        type_: struct_tag_str.clone(),
+        fields: vec![
+            SuiEventFieldSchema {
+                name: "object_id".into(),
+                type_: sui_framework_address_concat_string("::object::ID"),
+            },
+            SuiEventFieldSchema {
+                name: "creator".into(),
+                type_: "address".into(),
+            },
+            SuiEventFieldSchema {
+                name: "name".into(),
+                type_: "0x1::string::String".into(),
+            },
+        ],
+    };
 
     // Wait for streaming
-    let bcs = match timeout(Duration::from_secs(5), sub.next()).await {
+    let (fields, bcs) = match timeout(Duration::from_secs(5), sub.next()).await {
         Ok(Some(Ok(SuiEventEnvelope {
-            event: SuiEvent::MoveEvent {
-                type_, fields, bcs, ..
-            },
+            event:
+                SuiEvent::MoveEvent {
+                    type_,
+                    fields,
+                    bcs,
+                    schema,
+                    ..
+                },
             ..
         }))) => {
             assert_eq!(type_, struct_tag_str,);
+            assert_eq!(schema, Some(expected_schema.clone()));
             assert_eq!(
                 fields,
                 Some(SuiMoveStruct::WithFields(BTreeMap::from([
@@ -538,7 +945,7 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
                     ),
                 ])))
             );
-            bcs
+            (fields, bcs)
         }
         other => panic!("Failed to get SuiEvent, but {:?}", other),
     };
@@ -550,8 +957,9 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
         transaction_module: "devnet_nft".into(),
         sender,
         type_: sui_framework_address_concat_string("::devnet_nft::MintNFTEvent"),
-        fields: None,
+        fields,
         bcs,
+        schema: Some(expected_schema),
     };
 
     // Query by move event struct name
@@ -580,6 +988,63 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[tokio::test]
+async fn test_full_node_sub_event_resume_from_cursor() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _) = setup_network_and_wallet().await?;
+    let (node, ws_client) = set_up_subscription(&swarm).await?;
+    let filter = SuiEventFilter::MoveEventType(sui_framework_address_concat_string(
+        "::devnet_nft::MintNFTEvent",
+    ));
+
+    let mut sub: Subscription<SuiEventEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeEvent",
+            rpc_params![filter.clone()],
+            "sui_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+    let (_, _, first_digest) = create_devnet_nft(&mut context).await?;
+    wait_for_tx(first_digest, node.state().clone()).await;
+    let first = match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => envelope,
+        other => panic!("Failed to get SuiEvent, but {:?}", other),
+    };
+    assert_eq!(first.tx_digest, Some(first_digest));
+
+    // The second NFT is minted while disconnected
+    drop(sub);
+    let (_, _, second_digest) = create_devnet_nft(&mut context).await?;
+    wait_for_tx(second_digest, node.state().clone()).await;
+
+    let mut sub: Subscription<SuiEventEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeEvent",
+            rpc_params![filter, first.id],
+            "sui_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => {
+            assert_eq!(envelope.tx_digest, Some(second_digest));
+            assert!(envelope.id > first.id);
+        }
+        other => panic!("Failed to get SuiEvent, but {:?}", other),
+    };
+
+    // No more, the first NFT is not sent again
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new events are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
 // Test fullnode has event read jsonrpc endpoints working
 #[tokio::test]
 async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
@@ -593,7 +1058,7 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
 
     let txes = node
         .state()
-        .get_transactions_by_input_object(transferred_object)
+        .get_transactions_by_input_object(transferred_object, None, 100)
         .await?;
 
     assert_eq!(txes.len(), 1);
@@ -923,6 +1388,53 @@ async fn test_full_node_quorum_driver_rpc_ok() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_full_node_transaction_submit_endpoint() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, _address) = setup_network_and_wallet().await?;
+    let mut config = swarm.config().generate_fullnode_config();
+    let submit_address = available_local_socket_address();
+    config.transaction_submit_address = Some(submit_address);
+    let node = SuiNode::start(&config, Registry::new()).await?;
+    let url = format!("http://{}{}", submit_address, SUBMIT_TRANSACTION_ROUTE);
+    let client = reqwest::Client::new();
+
+    let txn = make_transactions_with_wallet_context(&mut context, 1)
+        .await
+        .swap_remove(0);
+    let digest = *txn.digest();
+    let request = SubmitTransactionRequest {
+        transaction: Base64::encode(bcs::to_bytes(&txn)?),
+    };
+    let response: SubmitTransactionResponse = client
+        .post(&url)
+        .json(&request)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(response.digest, digest);
+    assert_eq!(response.status, SubmitTransactionStatus::Success);
+
+    // Resubmitting gets the outcome of the first execution
+    wait_for_tx(digest, node.state().clone()).await;
+    let resubmitted: SubmitTransactionResponse = client
+        .post(&url)
+        .json(&request)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!(resubmitted, response);
+
+    let invalid = SubmitTransactionRequest {
+        transaction: Base64::encode(b"not a transaction"),
+    };
+    let response = client.post(&url).json(&invalid).send().await?;
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
 async fn get_obj_read_from_node(
     node: &SuiNode,
     object_id: ObjectID,
@@ -1045,3 +1557,151 @@ async fn test_get_objects_read() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_full_node_batch_get_objects() -> Result<(), anyhow::Error> {
+    let (swarm, context, address) = setup_network_and_wallet().await?;
+    let (_node, _, sui_client) = set_up_jsonrpc(&swarm, None).await?;
+
+    let mut object_ids: Vec<_> = context
+        .client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .into_iter()
+        .map(|info| info.object_id)
+        .collect();
+    object_ids.push(ObjectID::random());
+
+    let objects = sui_client
+        .read_api()
+        .get_parsed_objects(&object_ids)
+        .await?;
+    assert_eq!(objects.len(), object_ids.len());
+    for (object_id, object) in object_ids.iter().zip(objects) {
+        let expected = sui_client.read_api().get_parsed_object(*object_id).await?;
+        assert_eq!(
+            serde_json::to_value(object)?,
+            serde_json::to_value(expected)?
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_rpc_policy() -> Result<(), anyhow::Error> {
+    let (swarm, _, _) = setup_network_and_wallet().await?;
+
+    let jsonrpc_addr: SocketAddr = format!("127.0.0.1:{}", get_available_port()).parse()?;
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_addr;
+    config.json_rpc_server.denied_methods = vec!["sui_getRecentTransactions".to_string()];
+    config.json_rpc_server.method_rate_limits.insert(
+        "sui_getTotalTransactionNumber".to_string(),
+        RateLimitConfig {
+            requests_per_second: 1,
+            burst: Some(2),
+        },
+    );
+    let _node = SuiNode::start(&config, Registry::new()).await?;
+    let client = HttpClientBuilder::default().build(format!("http://{}", jsonrpc_addr))?;
+
+    // Denied methods are refused, the others are forwarded to the server
+    let error = client
+        .request::<serde_json::Value>("sui_getRecentTransactions", rpc_params![10])
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("not allowed"), "{error}");
+    client
+        .request::<serde_json::Value>("sui_getTransactionsInRange", rpc_params![0, 10])
+        .await?;
+
+    // The burst of the method is used up by the first two calls
+    for _ in 0..2 {
+        client
+            .request::<u64>("sui_getTotalTransactionNumber", rpc_params![])
+            .await?;
+    }
+    let error = client
+        .request::<u64>("sui_getTotalTransactionNumber", rpc_params![])
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("Too many calls"), "{error}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_serves_spec() -> Result<(), anyhow::Error> {
+    let (swarm, _, _) = setup_network_and_wallet().await?;
+
+    let jsonrpc_addr: SocketAddr = format!("127.0.0.1:{}", get_available_port()).parse()?;
+    let mut config = swarm.config().generate_fullnode_config();
+    config.json_rpc_address = jsonrpc_addr;
+    let _node = SuiNode::start(&config, Registry::new()).await?;
+
+    // A plain GET of /spec answers the OpenRPC document of `rpc.discover`
+    let response = reqwest::get(format!("http://{}/spec", jsonrpc_addr)).await?;
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let spec: serde_json::Value = response.json().await?;
+    assert!(spec["openrpc"].is_string(), "{spec}");
+    let methods: Vec<_> = spec["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|method| method["name"].as_str().unwrap())
+        .collect();
+    assert!(
+        methods.contains(&"sui_getTotalTransactionNumber"),
+        "{methods:?}"
+    );
+
+    let client = HttpClientBuilder::default().build(format!("http://{}", jsonrpc_addr))?;
+    let discovered: serde_json::Value = client.request("rpc.discover", rpc_params![]).await?;
+    assert_eq!(spec, discovered);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_dry_run_command() -> Result<(), anyhow::Error> {
+    let (swarm, mut context, sender) = setup_network_and_wallet().await?;
+    let (_node, _, sui_client) = set_up_jsonrpc(&swarm, None).await?;
+    // Dry runs are answered by the full node
+    context.client = sui_client;
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let read_api = context.client.read_api();
+    let sender_objects = read_api.get_objects_owned_by_address(sender).await?;
+    let coin = sender_objects.first().unwrap().object_id;
+
+    let result = SuiClientCommands::DryRun {
+        kind: TxBuildCommand::TransferSui {
+            to: recipient.into(),
+            sui_coin_object_id: coin,
+            gas_budget: 50000,
+            amount: Some(1000),
+            memo: None,
+        },
+    }
+    .execute(&mut context)
+    .await?;
+    let effects = match result {
+        SuiClientCommandResult::DryRun(effects) => effects,
+        other => panic!("Unexpected result: {other:?}"),
+    };
+    assert_eq!(effects.status, SuiExecutionStatus::Success);
+    assert!(effects.gas_used.computation_cost > 0);
+    assert_eq!(effects.created.len(), 1);
+    assert_eq!(effects.created[0].owner, Owner::AddressOwner(recipient));
+
+    // Nothing was committed, the coin is untouched and the recipient owns nothing
+    let read_api = context.client.read_api();
+    assert_eq!(
+        read_api.get_objects_owned_by_address(sender).await?,
+        sender_objects
+    );
+    assert!(read_api
+        .get_objects_owned_by_address(recipient)
+        .await?
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use sui_config::node::StorageBackendConfig;
+use sui_core::epoch::epoch_store::EpochStore;
+use sui_swarm::memory::Swarm;
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::KeypairTraits;
+use sui_types::object::Owner;
+use test_utils::authority::test_authority_aggregator;
+use test_utils::messages::make_transfer_sui_transaction;
+
+#[tokio::test]
+async fn test_in_memory_swarm_executes_transactions() -> Result<(), anyhow::Error> {
+    let mut swarm = Swarm::builder()
+        .committee_size(NonZeroUsize::new(4).unwrap())
+        .with_storage_backend(StorageBackendConfig::InMemory)
+        .build();
+    swarm.launch().await?;
+
+    let config = swarm.config();
+    let sender_key = &config.account_keys[0];
+    let sender = SuiAddress::from(&sender_key.public());
+    let recipient = SuiAddress::from(&config.account_keys[1].public());
+    let mut gas = config
+        .genesis
+        .objects()
+        .iter()
+        .find(|object| object.owner == Owner::AddressOwner(sender))
+        .unwrap()
+        .compute_object_reference();
+
+    let epoch_store = Arc::new(EpochStore::new_for_testing(&config.committee()));
+    let aggregator = test_authority_aggregator(config, epoch_store);
+
+    // The second transfer spends the gas object at the version the first one wrote, which the
+    // validators only know from their in-memory stores
+    for _ in 0..2 {
+        let transaction =
+            make_transfer_sui_transaction(gas, recipient, Some(1_000), sender, sender_key);
+        let (_, effects) = aggregator.execute_transaction(&transaction).await?;
+        assert!(effects.effects.status.is_ok());
+        gas = effects.effects.gas_object.0;
+    }
+    Ok(())
+}
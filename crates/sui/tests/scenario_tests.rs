@@ -0,0 +1,54 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::KeypairTraits;
+use sui_types::object::Owner;
+use test_utils::messages::make_transfer_sui_transaction;
+use test_utils::scenario::{Scenario, ScenarioRecorder};
+
+const SEED: u64 = 42;
+
+#[tokio::test]
+async fn test_scenario_record_and_replay() -> Result<(), anyhow::Error> {
+    let (swarm, mut recorder) = ScenarioRecorder::start(SEED).await?;
+    let config = swarm.config();
+    let sender_key = &config.account_keys[0];
+    let sender = SuiAddress::from(&sender_key.public());
+    let recipient = SuiAddress::from(&config.account_keys[1].public());
+    let mut gas = config
+        .genesis
+        .objects()
+        .iter()
+        .find(|object| object.owner == Owner::AddressOwner(sender))
+        .unwrap()
+        .compute_object_reference();
+
+    for amount in [1_000, 2_000] {
+        let transaction =
+            make_transfer_sui_transaction(gas, recipient, Some(amount), sender, sender_key);
+        let effects = recorder.execute_transaction(transaction).await?;
+        gas = effects.gas_object.0;
+    }
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("scenarios").join("transfer.json");
+    recorder.save(&path)?;
+    drop(swarm);
+
+    // The file holds the seed and every step with its outcome
+    let scenario = Scenario::load(&path)?;
+    let recorded = recorder.scenario();
+    assert_eq!(scenario.seed, SEED);
+    assert_eq!(scenario.steps.len(), 2);
+    for (loaded, recorded) in scenario.steps.iter().zip(&recorded.steps) {
+        assert_eq!(loaded.transaction.digest(), recorded.transaction.digest());
+        assert_eq!(loaded.outcome, recorded.outcome);
+        assert!(loaded.outcome.is_ok());
+    }
+
+    // Replaying on a swarm from the same seed reproduces the recorded effects
+    let drift = scenario.replay().await?;
+    assert!(drift.is_empty(), "{:#?}", drift);
+    Ok(())
+}
@@ -11,10 +11,14 @@ use rand::{
     Rng,
 };
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use sui_types::base_types::TransactionDigest;
 use tokio::time::{sleep, Duration, Instant};
 use tracing::{debug, trace};
 
 use sui_macros::*;
+use test_utils::network::setup_network_and_wallet;
+use test_utils::transaction::transfer_coin;
 
 async fn make_fut(i: usize) -> usize {
     let count_dist = Uniform::from(1..5);
@@ -118,3 +122,57 @@ async fn test_hash_collections() {
 
     debug!("final rng state: {}", OsRng.gen::<u32>());
 }
+
+#[sim_test(check_determinism)]
+async fn test_cluster_determinism() {
+    // The digests of the previous run, as the simulator runs the test twice in a row with the
+    // same seed to check its determinism
+    static PREVIOUS_DIGESTS: Mutex<Option<Vec<TransactionDigest>>> = Mutex::new(None);
+
+    let (_swarm, mut context, _) = setup_network_and_wallet().await.unwrap();
+
+    let mut digests = vec![];
+    for _ in 0..3 {
+        let (_, _, _, digest) = transfer_coin(&mut context).await.unwrap();
+        debug!("executed transaction {:?}", digest);
+        digests.push(digest);
+    }
+    assert_eq!(digests.iter().collect::<HashSet<_>>().len(), digests.len());
+    debug!("final rng state: {}", OsRng.gen::<u32>());
+
+    // The same seed gives the same keys, objects and transactions, and so the same digests
+    let mut previous = PREVIOUS_DIGESTS.lock().unwrap();
+    match previous.take() {
+        Some(previous_digests) => assert_eq!(previous_digests, digests),
+        None => *previous = Some(digests),
+    }
+}
+
+/// The time since the host booted, which the simulator doesn't virtualize, unlike the clocks of
+/// the process.
+fn host_uptime() -> Duration {
+    let uptime = std::fs::read_to_string("/proc/uptime").unwrap();
+    let seconds: f64 = uptime.split_whitespace().next().unwrap().parse().unwrap();
+    Duration::from_secs_f64(seconds)
+}
+
+#[sim_test]
+async fn test_cluster_virtual_time() {
+    let (mut swarm, mut context, _) = setup_network_and_wallet().await.unwrap();
+
+    // Three validators out of four are a quorum, so transactions go through while the requests
+    // to the stopped one time out
+    let name = swarm.validators().next().unwrap().name();
+    swarm.validator_mut(name).unwrap().stop();
+    transfer_coin(&mut context).await.unwrap();
+
+    // An hour passes without the test waiting for it
+    let start = Instant::now();
+    let host_start = host_uptime();
+    sleep(Duration::from_secs(3600)).await;
+    assert!(start.elapsed() >= Duration::from_secs(3600));
+    assert!(host_uptime() - host_start < Duration::from_secs(60));
+
+    swarm.validator_mut(name).unwrap().start().await.unwrap();
+    transfer_coin(&mut context).await.unwrap();
+}
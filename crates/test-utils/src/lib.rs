@@ -4,16 +4,16 @@ pub mod authority;
 pub mod messages;
 pub mod network;
 pub mod objects;
+pub mod scenario;
 pub mod transaction;
 use rand::{rngs::StdRng, SeedableRng};
+pub use sui_test_utils::{funded_accounts, test_account_keys, FundedAccount};
 use sui_types::{
-    base_types::SuiAddress,
     committee::Committee,
     crypto::{
         get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes,
         KeypairTraits, SuiKeyPair,
     },
-    signature_seed::SignatureSeed,
 };
 
 /// The size of the committee used for tests.
@@ -38,17 +38,6 @@ pub fn test_validator_keys() -> Vec<(AuthorityKeyPair, AuthorityKeyPair, SuiKeyP
         .collect()
 }
 
-/// Generate `COMMITTEE_SIZE` test cryptographic key pairs.
-pub fn test_account_keys() -> Vec<(SuiAddress, AccountKeyPair)> {
-    let mut vec = Vec::new();
-    let ss = SignatureSeed::from_bytes(&[0; 32]).unwrap();
-    for i in 0..TEST_COMMITTEE_SIZE {
-        let kp: AccountKeyPair = ss.new_deterministic_keypair(&[i as u8], Some(&[])).unwrap();
-        vec.push((kp.public().into(), kp));
-    }
-    vec
-}
-
 /// Generate a test Sui committee with `TEST_COMMITTEE_SIZE` members.
 pub fn test_committee() -> Committee {
     Committee::new(
@@ -6,18 +6,18 @@ use crate::{test_account_keys, test_committee, test_validator_keys};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
 use move_core_types::language_storage::TypeTag;
-use move_package::BuildConfig;
 use std::path::PathBuf;
 use sui::client_commands::WalletContext;
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
 use sui_adapter::genesis;
 use sui_json_rpc_types::SuiObjectInfo;
 use sui_sdk::crypto::SuiKeystore;
+pub use sui_test_utils::packages::build_move_package_bytes;
+pub use sui_test_utils::{random_object_ref, MAX_GAS};
+use sui_test_utils::{TestPackage, TestTransactionBuilder};
+use sui_types::base_types::ObjectID;
 use sui_types::base_types::ObjectRef;
-use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
-use sui_types::crypto::{
-    get_key_pair, AccountKeyPair, AuthorityKeyPair, AuthorityPublicKeyBytes, KeypairTraits,
-};
+use sui_types::crypto::{get_key_pair, AuthorityKeyPair, AuthorityPublicKeyBytes, KeypairTraits};
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::SignedTransactionEffects;
@@ -29,16 +29,6 @@ use sui_types::messages::{
 use sui_types::object::Object;
 use sui_types::object::Owner;
 use sui_types::{base_types::SuiAddress, crypto::Signature};
-/// The maximum gas per transaction.
-pub const MAX_GAS: u64 = 10_000;
-
-pub fn random_object_ref() -> ObjectRef {
-    (
-        ObjectID::random(),
-        SequenceNumber::new(),
-        ObjectDigest::new([0; 32]),
-    )
-}
 
 /// A helper function to get all accounts and their owned GasCoin
 /// with a WalletContext
@@ -49,11 +39,11 @@ pub async fn get_account_and_gas_coins(
     let accounts = context.keystore.addresses();
     for address in accounts {
         let result = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await?;
-        if let SuiClientCommandResult::Gas(coins) = result {
+        if let SuiClientCommandResult::Gas(coins, _) = result {
             res.push((address, coins))
         } else {
             panic!(
@@ -228,20 +218,20 @@ pub fn create_publish_move_package_transaction(
     gas_object_ref: ObjectRef,
     path: PathBuf,
     sender: SuiAddress,
-    keypair: &AccountKeyPair,
+    keypair: &dyn signature::Signer<Signature>,
 ) -> Transaction {
-    let build_config = BuildConfig::default();
-    let modules = sui_framework::build_move_package(&path, build_config).unwrap();
+    let all_module_bytes = build_move_package_bytes(path);
+    make_publish_transaction(gas_object_ref, all_module_bytes, sender, keypair)
+}
 
-    let all_module_bytes = modules
-        .iter()
-        .map(|m| {
-            let mut module_bytes = Vec::new();
-            m.serialize(&mut module_bytes).unwrap();
-            module_bytes
-        })
-        .collect();
-    let data = TransactionData::new_module(sender, gas_object_ref, all_module_bytes, MAX_GAS);
+/// Make a transaction to publish already compiled modules.
+pub fn make_publish_transaction(
+    gas_object_ref: ObjectRef,
+    module_bytes: Vec<Vec<u8>>,
+    sender: SuiAddress,
+    keypair: &dyn signature::Signer<Signature>,
+) -> Transaction {
+    let data = TransactionData::new_module(sender, gas_object_ref, module_bytes, MAX_GAS);
     let signature = Signature::new(&data, keypair);
     Transaction::new(data, signature)
 }
@@ -251,7 +241,7 @@ pub fn make_transfer_sui_transaction(
     recipient: SuiAddress,
     amount: Option<u64>,
     sender: SuiAddress,
-    keypair: &AccountKeyPair,
+    keypair: &dyn signature::Signer<Signature>,
 ) -> Transaction {
     let data = TransactionData::new_transfer_sui(recipient, sender, amount, gas_object, MAX_GAS);
     let signature = Signature::new(&data, keypair);
@@ -262,7 +252,7 @@ pub fn make_transfer_object_transaction(
     object_ref: ObjectRef,
     gas_object: ObjectRef,
     sender: SuiAddress,
-    keypair: &AccountKeyPair,
+    keypair: &dyn signature::Signer<Signature>,
     recipient: SuiAddress,
 ) -> Transaction {
     let data = TransactionData::new_transfer(recipient, object_ref, sender, gas_object, MAX_GAS);
@@ -284,28 +274,16 @@ pub fn make_transfer_object_transaction_with_wallet_context(
 
 pub fn make_publish_basics_transaction(gas_object: ObjectRef) -> Transaction {
     let (sender, keypair) = test_account_keys().pop().unwrap();
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    path.push("../../sui_programmability/examples/basics");
-    let build_config = BuildConfig::default();
-    let modules = sui_framework::build_move_package(&path, build_config).unwrap();
-    let all_module_bytes = modules
-        .iter()
-        .map(|m| {
-            let mut module_bytes = Vec::new();
-            m.serialize(&mut module_bytes).unwrap();
-            module_bytes
-        })
-        .collect();
-    let data = TransactionData::new_module(sender, gas_object, all_module_bytes, MAX_GAS);
-    let signature = Signature::new(&data, &keypair);
-    Transaction::new(data, signature)
+    TestTransactionBuilder::new(sender, gas_object)
+        .publish_package(TestPackage::Basics)
+        .build_and_sign(&keypair)
 }
 
 pub fn make_counter_create_transaction(
     gas_object: ObjectRef,
     package_ref: ObjectRef,
     sender: SuiAddress,
-    keypair: &AccountKeyPair,
+    keypair: &dyn signature::Signer<Signature>,
 ) -> Transaction {
     let data = TransactionData::new_move_call(
         sender,
@@ -326,7 +304,7 @@ pub fn make_counter_increment_transaction(
     package_ref: ObjectRef,
     counter_id: ObjectID,
     sender: SuiAddress,
-    keypair: &AccountKeyPair,
+    keypair: &dyn signature::Signer<Signature>,
 ) -> Transaction {
     let data = TransactionData::new_move_call(
         sender,
@@ -421,6 +399,8 @@ fn dummy_transaction_effects(tx: &Transaction) -> TransactionEffects {
             computation_cost: 0,
             storage_cost: 0,
             storage_rebate: 0,
+            event_storage_cost: 0,
+            effects_storage_cost: 0,
         },
         shared_objects: Vec::new(),
         transaction_digest: *tx.digest(),
@@ -92,6 +92,10 @@ pub async fn start_test_network_with_fullnodes(
             ..Default::default()
         }),
         active_address,
+        signers: Default::default(),
+        journal: None,
+        aliases: Default::default(),
+        checksummed_addresses: false,
     }
     .save(&wallet_path)?;
 
@@ -111,7 +115,7 @@ pub async fn setup_network_and_wallet() -> Result<(Swarm, WalletContext, SuiAddr
 
     // Sync client to retrieve objects from the network.
     SuiClientCommands::SyncClientState {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(&mut context)
     .await?;
@@ -0,0 +1,160 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records the transactions executed by an integration test into a scenario file, which can be
+//! replayed later against a newer binary to catch changes in execution behavior (golden replay).
+//!
+//! Scenarios run on a swarm whose genesis is derived from a seed, so that every recorded
+//! transaction touches the same objects on replay:
+//!
+//! ```ignore
+//! let (swarm, mut recorder) = ScenarioRecorder::start(SEED).await?;
+//! let effects = recorder.execute_transaction(transaction).await?;
+//! recorder.save("tests/scenarios/transfer.json")?;
+//!
+//! let drift = Scenario::load("tests/scenarios/transfer.json")?.replay().await?;
+//! assert!(drift.is_empty(), "{:#?}", drift);
+//! ```
+
+use crate::authority::test_authority_aggregator;
+use anyhow::Result;
+use rand::{rngs::StdRng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+use sui_config::genesis_config::GenesisConfig;
+use sui_core::authority_aggregator::AuthorityAggregator;
+use sui_core::authority_client::NetworkAuthorityClient;
+use sui_core::epoch::epoch_store::EpochStore;
+use sui_swarm::memory::Swarm;
+use sui_types::base_types::TransactionDigest;
+use sui_types::messages::{Transaction, TransactionEffects};
+
+/// Number of validators of the swarm scenarios run on.
+const SCENARIO_COMMITTEE_SIZE: usize = 4;
+
+/// A transaction executed while recording, together with its outcome.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScenarioStep {
+    pub transaction: Transaction,
+    pub outcome: Result<TransactionEffects, String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Seed the genesis of the swarm is derived from.
+    pub seed: u64,
+    pub steps: Vec<ScenarioStep>,
+}
+
+/// A step whose outcome on replay differs from the recorded one.
+#[derive(Debug)]
+pub struct ScenarioDrift {
+    pub step: usize,
+    pub transaction: TransactionDigest,
+    pub expected: Result<TransactionEffects, String>,
+    pub actual: Result<TransactionEffects, String>,
+}
+
+impl Scenario {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Re-executes every step on a fresh swarm, in order, and returns the steps whose
+    /// outcome changed.
+    pub async fn replay(&self) -> Result<Vec<ScenarioDrift>> {
+        let (_swarm, aggregator) = start_swarm(self.seed).await?;
+        let mut drift = Vec::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            let actual = execute(&aggregator, &step.transaction).await;
+            if actual != step.outcome {
+                drift.push(ScenarioDrift {
+                    step: index,
+                    transaction: *step.transaction.digest(),
+                    expected: step.outcome.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(drift)
+    }
+}
+
+/// Executes transactions on behalf of a test while recording them into a [`Scenario`].
+pub struct ScenarioRecorder {
+    scenario: Scenario,
+    aggregator: AuthorityAggregator<NetworkAuthorityClient>,
+}
+
+impl ScenarioRecorder {
+    /// Starts a swarm with a genesis derived from `seed`, and a recorder executing transactions
+    /// on it. Gas objects are owned by the accounts in `swarm.config().account_keys`.
+    pub async fn start(seed: u64) -> Result<(Swarm, Self)> {
+        let (swarm, aggregator) = start_swarm(seed).await?;
+        let recorder = Self {
+            scenario: Scenario {
+                seed,
+                steps: vec![],
+            },
+            aggregator,
+        };
+        Ok((swarm, recorder))
+    }
+
+    /// Executes `transaction` on the swarm and records its outcome, failures included.
+    pub async fn execute_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<TransactionEffects> {
+        let outcome = execute(&self.aggregator, &transaction).await;
+        self.scenario.steps.push(ScenarioStep {
+            transaction,
+            outcome: outcome.clone(),
+        });
+        outcome.map_err(anyhow::Error::msg)
+    }
+
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.scenario.save(path)
+    }
+}
+
+async fn start_swarm(seed: u64) -> Result<(Swarm, AuthorityAggregator<NetworkAuthorityClient>)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let genesis_config = GenesisConfig::for_local_testing_with_rng(&mut rng);
+    let mut swarm = Swarm::builder()
+        .rng(rng)
+        .committee_size(NonZeroUsize::new(SCENARIO_COMMITTEE_SIZE).unwrap())
+        .initial_accounts_config(genesis_config)
+        .build();
+    swarm.launch().await?;
+    let epoch_store = Arc::new(EpochStore::new_for_testing(&swarm.config().committee()));
+    let aggregator = test_authority_aggregator(swarm.config(), epoch_store);
+    Ok((swarm, aggregator))
+}
+
+async fn execute(
+    aggregator: &AuthorityAggregator<NetworkAuthorityClient>,
+    transaction: &Transaction,
+) -> Result<TransactionEffects, String> {
+    aggregator
+        .execute_transaction(transaction)
+        .await
+        .map(|(_, effects)| effects.effects)
+        .map_err(|e| e.to_string())
+}
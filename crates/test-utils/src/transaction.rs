@@ -241,10 +241,11 @@ pub async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
         gas_budget: 50000,
+        memo: None,
     }
     .execute(context)
     .await?;
@@ -5,8 +5,9 @@ import { Provider } from './provider';
 import { JsonRpcClient } from '../rpc/client';
 import {
   isGetObjectDataResponse,
-  isGetOwnedObjectsResponse,
   isGetTxnDigestsResponse,
+  isPaginatedObjectsResponse,
+  isPaginatedTransactionDigests,
   isSuiTransactionResponse,
   isSuiMoveFunctionArgTypes,
   isSuiMoveNormalizedModules,
@@ -174,11 +175,9 @@ export class JsonRpcProvider extends Provider {
   // Objects
   async getObjectsOwnedByAddress(address: string): Promise<SuiObjectInfo[]> {
     try {
-      return await this.client.requestWithType(
+      return await this.getAllOwnedObjects(
         'sui_getObjectsOwnedByAddress',
-        [address],
-        isGetOwnedObjectsResponse,
-        this.skipDataValidation
+        address
       );
     } catch (err) {
       throw new Error(
@@ -194,11 +193,9 @@ export class JsonRpcProvider extends Provider {
 
   async getObjectsOwnedByObject(objectId: string): Promise<SuiObjectInfo[]> {
     try {
-      return await this.client.requestWithType(
+      return await this.getAllOwnedObjects(
         'sui_getObjectsOwnedByObject',
-        [objectId],
-        isGetOwnedObjectsResponse,
-        this.skipDataValidation
+        objectId
       );
     } catch (err) {
       throw new Error(
@@ -246,23 +243,17 @@ export class JsonRpcProvider extends Provider {
   async getTransactionsForObject(
     objectID: string
   ): Promise<GetTxnDigestsResponse> {
-    const requests = [
-      {
-        method: 'sui_getTransactionsByInputObject',
-        args: [objectID],
-      },
-      {
-        method: 'sui_getTransactionsByMutatedObject',
-        args: [objectID],
-      },
-    ];
-
     try {
-      const results = await this.client.batchRequestWithType(
-        requests,
-        isGetTxnDigestsResponse,
-        this.skipDataValidation
-      );
+      const results = await Promise.all([
+        this.getAllTransactionDigests(
+          'sui_getTransactionsByInputObject',
+          objectID
+        ),
+        this.getAllTransactionDigests(
+          'sui_getTransactionsByMutatedObject',
+          objectID
+        ),
+      ]);
       return [...results[0], ...results[1]];
     } catch (err) {
       throw new Error(
@@ -274,23 +265,14 @@ export class JsonRpcProvider extends Provider {
   async getTransactionsForAddress(
     addressID: string
   ): Promise<GetTxnDigestsResponse> {
-    const requests = [
-      {
-        method: 'sui_getTransactionsToAddress',
-        args: [addressID],
-      },
-      {
-        method: 'sui_getTransactionsFromAddress',
-        args: [addressID],
-      },
-    ];
-
     try {
-      const results = await this.client.batchRequestWithType(
-        requests,
-        isGetTxnDigestsResponse,
-        this.skipDataValidation
-      );
+      const results = await Promise.all([
+        this.getAllTransactionDigests('sui_getTransactionsToAddress', addressID),
+        this.getAllTransactionDigests(
+          'sui_getTransactionsFromAddress',
+          addressID
+        ),
+      ]);
       return [...results[0], ...results[1]];
     } catch (err) {
       throw new Error(
@@ -589,4 +571,44 @@ export class JsonRpcProvider extends Provider {
   async unsubscribeEvent(id: SubscriptionId): Promise<boolean> {
     return this.wsClient.unsubscribeEvent(id);
   }
+
+  // Fetches all the pages of a paginated owned objects query
+  private async getAllOwnedObjects(
+    method: string,
+    owner: string
+  ): Promise<SuiObjectInfo[]> {
+    const objects: SuiObjectInfo[] = [];
+    let cursor: string | null = null;
+    do {
+      const page = await this.client.requestWithType(
+        method,
+        [owner, cursor, null],
+        isPaginatedObjectsResponse,
+        this.skipDataValidation
+      );
+      objects.push(...page.data);
+      cursor = page.nextCursor;
+    } while (cursor !== null);
+    return objects;
+  }
+
+  // Fetches all the pages of a paginated transactions query
+  private async getAllTransactionDigests(
+    method: string,
+    key: string
+  ): Promise<GetTxnDigestsResponse> {
+    const digests: GetTxnDigestsResponse = [];
+    let cursor: GatewayTxSeqNumber | null = null;
+    do {
+      const page = await this.client.requestWithType(
+        method,
+        [key, cursor, null],
+        isPaginatedTransactionDigests,
+        this.skipDataValidation
+      );
+      digests.push(...page.data);
+      cursor = page.nextCursor;
+    } while (cursor !== null);
+    return digests;
+  }
 }
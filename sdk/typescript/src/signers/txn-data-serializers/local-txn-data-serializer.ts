@@ -221,6 +221,7 @@ export class LocalTxnDataSerializer implements TxnDataSerializer {
       gasPrice: 1,
       gasBudget: gasBudget,
       sender: signerAddress,
+      gasSponsor: { None: null },
     };
 
     console.log('transactiondata', txData);
@@ -7,7 +7,7 @@
  * Generated type guards for "index.ts".
  * WARNING: Do not manually change this file.
  */
-import { TransactionDigest, SuiAddress, ObjectOwner, SuiObjectRef, SuiObjectInfo, ObjectContentFields, MovePackageContent, SuiData, SuiMoveObject, SuiMovePackage, SuiMoveFunctionArgTypesResponse, SuiMoveFunctionArgType, SuiMoveFunctionArgTypes, SuiMoveNormalizedModules, SuiMoveNormalizedModule, SuiMoveModuleId, SuiMoveNormalizedStruct, SuiMoveStructTypeParameter, SuiMoveNormalizedField, SuiMoveNormalizedFunction, SuiMoveVisibility, SuiMoveTypeParameterIndex, SuiMoveAbilitySet, SuiMoveNormalizedType, SuiMoveNormalizedTypeParameterType, SuiMoveNormalizedStructType, SuiObject, ObjectStatus, ObjectType, GetOwnedObjectsResponse, GetObjectDataResponse, ObjectDigest, ObjectId, SequenceNumber, MoveEvent, PublishEvent, TransferObjectEvent, DeleteObjectEvent, NewObjectEvent, SuiEvent, MoveEventField, EventType, SuiEventFilter, SuiEventEnvelope, SuiEvents, SubscriptionId, SubscriptionEvent, TransferObject, SuiTransferSui, SuiChangeEpoch, ExecuteTransactionRequestType, TransactionKindName, SuiTransactionKind, SuiTransactionData, EpochId, AuthorityQuorumSignInfo, CertifiedTransaction, GasCostSummary, ExecutionStatusType, ExecutionStatus, OwnedObjectRef, TransactionEffects, SuiTransactionResponse, SuiCertifiedTransactionEffects, SuiExecuteTransactionResponse, GatewayTxSeqNumber, GetTxnDigestsResponse, MoveCall, SuiJsonValue, EmptySignInfo, AuthorityName, AuthoritySignature, TransactionBytes, SuiParsedMergeCoinResponse, SuiParsedSplitCoinResponse, SuiParsedPublishResponse, SuiPackage, SuiParsedTransactionResponse, DelegationData, DelegationSuiObject, TransferObjectTx, TransferSuiTx, PublishTx, ObjectArg, CallArg, StructTag, TypeTag, MoveCallTx, Transaction, TransactionKind, TransactionData } from "./index";
+import { TransactionDigest, SuiAddress, ObjectOwner, SuiObjectRef, SuiObjectInfo, ObjectContentFields, MovePackageContent, SuiData, SuiMoveObject, SuiMovePackage, SuiMoveFunctionArgTypesResponse, SuiMoveFunctionArgType, SuiMoveFunctionArgTypes, SuiMoveNormalizedModules, SuiMoveNormalizedModule, SuiMoveModuleId, SuiMoveNormalizedStruct, SuiMoveStructTypeParameter, SuiMoveNormalizedField, SuiMoveNormalizedFunction, SuiMoveVisibility, SuiMoveTypeParameterIndex, SuiMoveAbilitySet, SuiMoveNormalizedType, SuiMoveNormalizedTypeParameterType, SuiMoveNormalizedStructType, SuiObject, ObjectStatus, ObjectType, GetOwnedObjectsResponse, PaginatedObjectsResponse, GetObjectDataResponse, ObjectDigest, ObjectId, SequenceNumber, MoveEvent, PublishEvent, TransferObjectEvent, DeleteObjectEvent, NewObjectEvent, SuiEvent, MoveEventField, EventType, SuiEventFilter, SuiEventEnvelope, SuiEvents, SubscriptionId, SubscriptionEvent, TransferObject, SuiTransferSui, SuiChangeEpoch, ExecuteTransactionRequestType, TransactionKindName, SuiTransactionKind, SuiTransactionData, EpochId, AuthorityQuorumSignInfo, CertifiedTransaction, GasCostSummary, ExecutionStatusType, ExecutionStatus, OwnedObjectRef, TransactionEffects, SuiTransactionResponse, SuiCertifiedTransactionEffects, SuiExecuteTransactionResponse, GatewayTxSeqNumber, GetTxnDigestsResponse, PaginatedTransactionDigests, MoveCall, SuiJsonValue, EmptySignInfo, AuthorityName, AuthoritySignature, TransactionBytes, SuiParsedMergeCoinResponse, SuiParsedSplitCoinResponse, SuiParsedPublishResponse, SuiPackage, SuiParsedTransactionResponse, DelegationData, DelegationSuiObject, TransferObjectTx, TransferSuiTx, PublishTx, ObjectArg, CallArg, StructTag, TypeTag, MoveCallTx, Transaction, TransactionKind, TransactionData } from "./index";
 
 export function isTransactionDigest(obj: any, _argumentName?: string): obj is TransactionDigest {
     return (
@@ -366,6 +366,18 @@ export function isGetOwnedObjectsResponse(obj: any, _argumentName?: string): obj
     )
 }
 
+export function isPaginatedObjectsResponse(obj: any, _argumentName?: string): obj is PaginatedObjectsResponse {
+    return (
+        (obj !== null &&
+            typeof obj === "object" ||
+            typeof obj === "function") &&
+        isGetOwnedObjectsResponse(obj.data) as boolean &&
+        (obj.nextCursor === null ||
+            isTransactionDigest(obj.nextCursor) as boolean) &&
+        typeof obj.hasNextPage === "boolean"
+    )
+}
+
 export function isGetObjectDataResponse(obj: any, _argumentName?: string): obj is GetObjectDataResponse {
     return (
         (obj !== null &&
@@ -697,7 +709,9 @@ export function isSuiTransactionData(obj: any, _argumentName?: string): obj is S
         ) &&
         isTransactionDigest(obj.sender) as boolean &&
         isSuiObjectRef(obj.gasPayment) as boolean &&
-        isSuiMoveTypeParameterIndex(obj.gasBudget) as boolean
+        isSuiMoveTypeParameterIndex(obj.gasBudget) as boolean &&
+        (typeof obj.gasSponsor === "undefined" ||
+            isTransactionDigest(obj.gasSponsor) as boolean)
     )
 }
 
@@ -728,6 +742,8 @@ export function isCertifiedTransaction(obj: any, _argumentName?: string): obj is
         isTransactionDigest(obj.transactionDigest) as boolean &&
         isSuiTransactionData(obj.data) as boolean &&
         isTransactionDigest(obj.txSignature) as boolean &&
+        (typeof obj.sponsorSignature === "undefined" ||
+            isTransactionDigest(obj.sponsorSignature) as boolean) &&
         isAuthorityQuorumSignInfo(obj.authSignInfo) as boolean
     )
 }
@@ -898,6 +914,18 @@ export function isGetTxnDigestsResponse(obj: any, _argumentName?: string): obj i
     )
 }
 
+export function isPaginatedTransactionDigests(obj: any, _argumentName?: string): obj is PaginatedTransactionDigests {
+    return (
+        (obj !== null &&
+            typeof obj === "object" ||
+            typeof obj === "function") &&
+        isGetTxnDigestsResponse(obj.data) as boolean &&
+        (obj.nextCursor === null ||
+            isSuiMoveTypeParameterIndex(obj.nextCursor) as boolean) &&
+        typeof obj.hasNextPage === "boolean"
+    )
+}
+
 export function isMoveCall(obj: any, _argumentName?: string): obj is MoveCall {
     return (
         (obj !== null &&
@@ -1280,6 +1308,9 @@ export function isTransactionData(obj: any, _argumentName?: string): obj is Tran
         isSuiMoveTypeParameterIndex(obj.gasBudget) as boolean &&
         isSuiMoveTypeParameterIndex(obj.gasPrice) as boolean &&
         isTransactionKind(obj.kind) as boolean &&
-        isSuiObjectRef(obj.gasPayment) as boolean
+        isSuiObjectRef(obj.gasPayment) as boolean &&
+        (obj.gasSponsor !== null &&
+            typeof obj.gasSponsor === "object" ||
+            typeof obj.gasSponsor === "function")
     )
 }
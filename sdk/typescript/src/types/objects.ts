@@ -137,6 +137,12 @@ export type ObjectType = 'moveObject' | 'package';
 
 export type GetOwnedObjectsResponse = SuiObjectInfo[];
 
+export type PaginatedObjectsResponse = {
+  data: SuiObjectInfo[];
+  nextCursor: ObjectId | null;
+  hasNextPage: boolean;
+};
+
 export type GetObjectDataResponse = {
   status: ObjectStatus;
   details: SuiObject | ObjectId | SuiObjectRef;
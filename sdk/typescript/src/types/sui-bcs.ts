@@ -255,14 +255,21 @@ export type TransactionData = {
   gasPrice: number;
   kind: TransactionKind;
   gasPayment: SuiObjectRef;
+  gasSponsor: { Some: string } | { None: null };
 };
 
+bcs.registerEnumType('Option<SuiAddress>', {
+  None: null,
+  Some: 'SuiAddress',
+});
+
 bcs.registerStructType('TransactionData', {
   kind: 'TransactionKind',
   sender: 'SuiAddress',
   gasPayment: 'SuiObjectRef',
   gasPrice: 'u64',
   gasBudget: 'u64',
+  gasSponsor: 'Option<SuiAddress>',
 });
 
 export { bcs };
@@ -44,6 +44,7 @@ export type SuiTransactionData = {
   sender: SuiAddress;
   gasPayment: SuiObjectRef;
   gasBudget: number;
+  gasSponsor?: SuiAddress;
 };
 
 // TODO: support u64
@@ -59,6 +60,7 @@ export type CertifiedTransaction = {
   transactionDigest: TransactionDigest;
   data: SuiTransactionData;
   txSignature: string;
+  sponsorSignature?: string;
   authSignInfo: AuthorityQuorumSignInfo;
 };
 
@@ -144,6 +146,12 @@ export type GatewayTxSeqNumber = number;
 
 export type GetTxnDigestsResponse = [GatewayTxSeqNumber, TransactionDigest][];
 
+export type PaginatedTransactionDigests = {
+  data: GetTxnDigestsResponse;
+  nextCursor: GatewayTxSeqNumber | null;
+  hasNextPage: boolean;
+};
+
 export type MoveCall = {
   package: SuiObjectRef;
   module: string;
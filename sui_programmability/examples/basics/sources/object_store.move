@@ -0,0 +1,76 @@
+// Copyright (c) 2022, Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+/// This example creates objects which take up storage, in bulk.
+/// Rules:
+/// - anyone can mint a batch of items holding a payload of a given size
+/// - anyone can create a collection, and grow it with items owned by the collection
+module basics::object_store {
+    use std::vector;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// An object holding a payload of bytes.
+    struct Item has key, store {
+        id: UID,
+        payload: vector<u8>,
+    }
+
+    /// An object owning the items added to it.
+    struct Collection has key {
+        id: UID,
+        size: u64,
+    }
+
+    public fun payload_size(item: &Item): u64 {
+        vector::length(&item.payload)
+    }
+
+    public fun size(collection: &Collection): u64 {
+        collection.size
+    }
+
+    fun new_item(payload_size: u64, ctx: &mut TxContext): Item {
+        let payload = vector::empty<u8>();
+        let i = 0;
+        while (i < payload_size) {
+            vector::push_back(&mut payload, ((i % 256) as u8));
+            i = i + 1;
+        };
+        Item { id: object::new(ctx), payload }
+    }
+
+    /// Mint `count` items of `payload_size` bytes, owned by the sender.
+    public entry fun mint(count: u64, payload_size: u64, ctx: &mut TxContext) {
+        let sender = tx_context::sender(ctx);
+        let i = 0;
+        while (i < count) {
+            transfer::transfer(new_item(payload_size, ctx), sender);
+            i = i + 1;
+        }
+    }
+
+    /// Create an empty collection, owned by the sender.
+    public entry fun create_collection(ctx: &mut TxContext) {
+        transfer::transfer(
+            Collection { id: object::new(ctx), size: 0 },
+            tx_context::sender(ctx)
+        )
+    }
+
+    /// Add `count` items of `payload_size` bytes to `collection`, owned by it.
+    public entry fun grow(
+        collection: &mut Collection,
+        count: u64,
+        payload_size: u64,
+        ctx: &mut TxContext
+    ) {
+        let i = 0;
+        while (i < count) {
+            transfer::transfer_to_object(new_item(payload_size, ctx), collection);
+            i = i + 1;
+        };
+        collection.size = collection.size + count;
+    }
+}